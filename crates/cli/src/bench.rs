@@ -0,0 +1,420 @@
+//! Built-in benchmark: `strata bench`.
+//!
+//! Runs a standard workload from N threads for a fixed duration and reports
+//! throughput and latency percentiles. Every run works in a scratch branch
+//! that is deleted afterwards, so benchmarking an existing database leaves
+//! its data alone.
+//!
+//! Without `--db` or `--cache` the run uses a fresh temporary database in the
+//! durability mode given by `--durability`, which makes comparing modes a
+//! matter of running the same command twice.
+//!
+//! The `vector-search` workload fails at load time in builds without the
+//! `vectors` feature; its helpers are then unused.
+#![cfg_attr(not(feature = "vectors"), allow(dead_code))]
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use hdrhistogram::Histogram;
+#[cfg(feature = "vectors")]
+use strata_executor::{BatchVectorEntry, DistanceMetric};
+use strata_executor::{Strata, Value};
+
+/// Vectors returned per search in the `vector-search` workload.
+const SEARCH_K: u64 = 10;
+
+/// Entries per batch when loading the data set.
+const LOAD_BATCH: usize = 1000;
+
+/// Name of the collection used by the `vector-search` workload.
+const COLLECTION: &str = "bench";
+
+/// Largest latency tracked exactly; slower operations are clamped to it.
+const MAX_TRACKED_NS: u64 = 3_600_000_000_000;
+
+/// A standard benchmark workload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    /// Overwrite random keys.
+    KvSet,
+    /// Read random keys from a preloaded data set.
+    KvGet,
+    /// Top-k search with random queries against a preloaded collection.
+    VectorSearch,
+}
+
+impl Workload {
+    /// Parse a `--workload` value.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "kv-set" => Ok(Workload::KvSet),
+            "kv-get" => Ok(Workload::KvGet),
+            "vector-search" => Ok(Workload::VectorSearch),
+            other => Err(format!(
+                "Unknown workload '{}' (expected kv-set, kv-get or vector-search)",
+                other
+            )),
+        }
+    }
+
+    /// Name as accepted by `--workload`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Workload::KvSet => "kv-set",
+            Workload::KvGet => "kv-get",
+            Workload::VectorSearch => "vector-search",
+        }
+    }
+}
+
+/// Where the benchmark runs.
+pub enum Target {
+    /// The database opened from the global flags (`--db` or `--cache`).
+    Existing,
+    /// A fresh temporary database with this durability mode.
+    Fresh { durability: String },
+}
+
+/// Settings for one benchmark run.
+pub struct BenchConfig {
+    pub workload: Workload,
+    pub target: Target,
+    pub threads: usize,
+    pub duration: Duration,
+    /// Keys (or vectors) in the data set.
+    pub keys: usize,
+    /// Size of each KV value in bytes.
+    pub value_size: usize,
+    /// Vector dimension for `vector-search`.
+    pub dimension: usize,
+}
+
+/// Throughput and latency of a benchmark run.
+pub struct BenchReport {
+    pub workload: Workload,
+    pub threads: usize,
+    pub elapsed: Duration,
+    pub ops: u64,
+    pub errors: u64,
+    pub p50_ns: u64,
+    pub p95_ns: u64,
+    pub p99_ns: u64,
+    pub max_ns: u64,
+}
+
+impl BenchReport {
+    /// Successful operations per second.
+    pub fn ops_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            0.0
+        } else {
+            self.ops as f64 / secs
+        }
+    }
+}
+
+/// Parse a duration like `30s`, `500ms`, `2m` or a bare number of seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let n: u64 = number
+        .parse()
+        .map_err(|_| format!("Invalid duration '{}' (e.g. 30s, 500ms, 2m)", s))?;
+    let duration = match unit {
+        "" | "s" => Duration::from_secs(n),
+        "ms" => Duration::from_millis(n),
+        "m" => Duration::from_secs(n * 60),
+        _ => return Err(format!("Invalid duration '{}' (e.g. 30s, 500ms, 2m)", s)),
+    };
+    if duration.is_zero() {
+        return Err("Duration must be greater than zero".to_string());
+    }
+    Ok(duration)
+}
+
+/// A temporary database directory, removed on drop.
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    /// Create a database directory whose `strata.toml` selects `durability`.
+    pub fn create(durability: &str) -> Result<Self, String> {
+        let path = std::env::temp_dir().join(format!("strata-bench-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path)
+            .map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+        std::fs::write(
+            path.join("strata.toml"),
+            format!("durability = \"{}\"\n", durability),
+        )
+        .map_err(|e| format!("Failed to write benchmark config: {}", e))?;
+        Ok(ScratchDir(path))
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run a benchmark against `db`.
+///
+/// Loads the data set into a scratch branch, runs the workload, and deletes
+/// the branch again whether or not the run succeeded.
+pub fn run(db: &Strata, config: &BenchConfig) -> Result<BenchReport, String> {
+    if config.threads == 0 {
+        return Err("--threads must be at least 1".to_string());
+    }
+    if config.keys == 0 {
+        return Err("--keys must be at least 1".to_string());
+    }
+
+    let branch = format!("bench-{}", std::process::id());
+    db.create_branch(&branch)
+        .map_err(|e| format!("Failed to create branch '{}': {}", branch, e))?;
+    let result = load(db, &branch, config).and_then(|()| measure(db, &branch, config));
+    if let Err(e) = db.delete_branch(&branch) {
+        eprintln!("Warning: failed to delete branch '{}': {}", branch, e);
+    }
+    result
+}
+
+/// A handle switched to the benchmark branch.
+fn handle(db: &Strata, branch: &str) -> Result<Strata, String> {
+    let mut handle = db.new_handle().map_err(|e| e.to_string())?;
+    handle.set_branch(branch).map_err(|e| e.to_string())?;
+    Ok(handle)
+}
+
+/// Load the data set the workload reads.
+fn load(db: &Strata, branch: &str, config: &BenchConfig) -> Result<(), String> {
+    let h = handle(db, branch)?;
+    let fail = |e: strata_executor::Error| format!("Failed to load data set: {}", e);
+    match config.workload {
+        Workload::KvSet => Ok(()),
+        Workload::KvGet => {
+            let value = bench_value(config.value_size);
+            for i in 0..config.keys {
+                h.kv_put(&bench_key(i), value.clone()).map_err(fail)?;
+            }
+            Ok(())
+        }
+        #[cfg(feature = "vectors")]
+        Workload::VectorSearch => {
+            h.vector_create_collection(COLLECTION, config.dimension as u64, DistanceMetric::Cosine)
+                .map_err(fail)?;
+            let mut rng = Rng::new(u64::MAX);
+            let mut start = 0;
+            while start < config.keys {
+                let end = (start + LOAD_BATCH).min(config.keys);
+                let entries = (start..end)
+                    .map(|i| BatchVectorEntry {
+                        key: bench_key(i),
+                        vector: rng.vector(config.dimension),
+                        metadata: None,
+                    })
+                    .collect();
+                h.vector_batch_upsert(COLLECTION, entries).map_err(fail)?;
+                start = end;
+            }
+            Ok(())
+        }
+        #[cfg(not(feature = "vectors"))]
+        Workload::VectorSearch => {
+            Err("vector-search requires the 'vectors' feature to be compiled in".into())
+        }
+    }
+}
+
+/// Run the workload on every thread until the duration is up.
+fn measure(db: &Strata, branch: &str, config: &BenchConfig) -> Result<BenchReport, String> {
+    let handles = (0..config.threads)
+        .map(|_| handle(db, branch))
+        .collect::<Result<Vec<_>, _>>()?;
+    let value = bench_value(config.value_size);
+
+    let start = Instant::now();
+    let deadline = start + config.duration;
+    let results: Vec<(Histogram<u64>, u64)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = handles
+            .into_iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let value = value.clone();
+                scope.spawn(move || worker(&h, config, &value, i as u64, deadline))
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|w| w.join().expect("benchmark thread panicked"))
+            .collect()
+    });
+    let elapsed = start.elapsed();
+
+    let mut latencies = new_histogram();
+    let mut errors = 0;
+    for (h, e) in results {
+        latencies.add(&h).expect("histograms share the same bounds");
+        errors += e;
+    }
+    Ok(BenchReport {
+        workload: config.workload,
+        threads: config.threads,
+        elapsed,
+        ops: latencies.len(),
+        errors,
+        p50_ns: latencies.value_at_quantile(0.50),
+        p95_ns: latencies.value_at_quantile(0.95),
+        p99_ns: latencies.value_at_quantile(0.99),
+        max_ns: latencies.max(),
+    })
+}
+
+/// One benchmark thread: returns the latencies of successful operations and
+/// the number of failed ones.
+fn worker(
+    h: &Strata,
+    config: &BenchConfig,
+    value: &Value,
+    seed: u64,
+    deadline: Instant,
+) -> (Histogram<u64>, u64) {
+    let mut rng = Rng::new(seed);
+    let mut latencies = new_histogram();
+    let mut errors = 0;
+    while Instant::now() < deadline {
+        let key = bench_key(rng.below(config.keys));
+        let op_start = Instant::now();
+        let ok = match config.workload {
+            Workload::KvSet => h.kv_put(&key, value.clone()).is_ok(),
+            Workload::KvGet => h.kv_get(&key).is_ok(),
+            #[cfg(feature = "vectors")]
+            Workload::VectorSearch => h
+                .vector_search(COLLECTION, rng.vector(config.dimension), SEARCH_K)
+                .is_ok(),
+            #[cfg(not(feature = "vectors"))]
+            Workload::VectorSearch => unreachable!("load rejects vector-search"),
+        };
+        let ns = u64::try_from(op_start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        if ok {
+            latencies.saturating_record(ns);
+        } else {
+            errors += 1;
+        }
+    }
+    (latencies, errors)
+}
+
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(1, MAX_TRACKED_NS, 2).expect("static histogram bounds are valid")
+}
+
+fn bench_key(i: usize) -> String {
+    format!("key:{:010}", i)
+}
+
+fn bench_value(size: usize) -> Value {
+    Value::String("x".repeat(size))
+}
+
+/// Small xorshift generator; benchmark keys and queries need speed, not
+/// quality.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Vector with components in [-1, 1).
+    fn vector(&mut self, dimension: usize) -> Vec<f32> {
+        (0..dimension)
+            .map(|_| (self.next() >> 40) as f32 / (1u64 << 23) as f32 - 1.0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("45").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("10h").is_err());
+    }
+
+    #[test]
+    fn test_workload_names_round_trip() {
+        for w in [Workload::KvSet, Workload::KvGet, Workload::VectorSearch] {
+            assert_eq!(Workload::parse(w.name()).unwrap(), w);
+        }
+        assert!(Workload::parse("scan").is_err());
+    }
+
+    #[test]
+    fn test_rng_vector_range() {
+        let mut rng = Rng::new(0);
+        let v = rng.vector(256);
+        assert_eq!(v.len(), 256);
+        assert!(v.iter().all(|x| (-1.0..1.0).contains(x)));
+        assert!(rng.below(7) < 7);
+    }
+
+    fn run_briefly(workload: Workload) -> BenchReport {
+        let db = Strata::cache().unwrap();
+        let config = BenchConfig {
+            workload,
+            target: Target::Existing,
+            threads: 2,
+            duration: Duration::from_millis(100),
+            keys: 50,
+            value_size: 16,
+            dimension: 8,
+        };
+        let report = run(&db, &config).unwrap();
+        // The scratch branch is gone afterwards
+        assert!(!db
+            .list_branches()
+            .unwrap()
+            .iter()
+            .any(|b| b.starts_with("bench-")));
+        report
+    }
+
+    #[test]
+    fn test_run_each_workload() {
+        for workload in [Workload::KvSet, Workload::KvGet, Workload::VectorSearch] {
+            let report = run_briefly(workload);
+            assert!(report.ops > 0, "{} ran no operations", workload.name());
+            assert_eq!(report.errors, 0);
+            assert!(report.p50_ns <= report.p99_ns);
+            assert!(report.p99_ns <= report.max_ns);
+            assert!(report.ops_per_sec() > 0.0);
+        }
+    }
+}
@@ -60,6 +60,14 @@ pub fn build_cli() -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .global(true),
         )
+        .arg(
+            Arg::new("force-unlock")
+                .long("force-unlock")
+                .help("Break a write lock left by a crashed process before opening")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("read-only")
+                .global(true),
+        )
         .arg(
             Arg::new("auto-embed")
                 .long("auto-embed")
@@ -67,10 +75,23 @@ pub fn build_cli() -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .global(true),
         )
+        .arg(
+            Arg::new("no-key-completion")
+                .long("no-key-completion")
+                .help("In the REPL, don't list keys, cells or collections for TAB completion")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("script")
+                .long("script")
+                .value_name("FILE")
+                .help("Run commands from a script file, stopping at the first error"),
+        )
         .subcommand(build_kv())
         .subcommand(build_json())
         .subcommand(build_event())
         .subcommand(build_state())
+        .subcommand(build_list())
         .subcommand(build_vector())
         .subcommand(build_branch())
         .subcommand(build_space())
@@ -82,7 +103,10 @@ pub fn build_cli() -> Command {
         .subcommand(build_info())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_stats())
+        .subcommand(build_latency())
         .subcommand(build_search())
+        .subcommand(build_bench())
         .subcommand(build_setup())
 }
 
@@ -95,6 +119,7 @@ pub fn build_repl_cmd() -> Command {
         .subcommand(build_json())
         .subcommand(build_event())
         .subcommand(build_state())
+        .subcommand(build_list())
         .subcommand(build_vector())
         .subcommand(build_branch())
         .subcommand(build_space())
@@ -106,6 +131,8 @@ pub fn build_repl_cmd() -> Command {
         .subcommand(build_info())
         .subcommand(build_flush())
         .subcommand(build_compact())
+        .subcommand(build_stats())
+        .subcommand(build_latency())
         .subcommand(build_search())
 }
 
@@ -186,8 +213,71 @@ fn build_kv() -> Command {
         .subcommand(
             Command::new("history")
                 .about("Get version history for a key")
-                .arg(Arg::new("key").required(true).help("Key name")),
+                .arg(Arg::new("key").required(true).help("Key name"))
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .short('n')
+                        .help("Maximum versions to return"),
+                ),
         )
+        .subcommand(
+            Command::new("history-policy")
+                .about("Set how many versions to keep for keys under a prefix")
+                .arg(Arg::new("prefix").required(true).help("Key prefix"))
+                .arg(
+                    Arg::new("max-len")
+                        .long("max-len")
+                        .value_name("N")
+                        .help("Keep the last N versions")
+                        .conflicts_with_all(["max-age", "keep-all"]),
+                )
+                .arg(
+                    Arg::new("max-age")
+                        .long("max-age")
+                        .value_name("SECS")
+                        .help("Keep versions written within SECS seconds")
+                        .conflicts_with("keep-all"),
+                )
+                .arg(
+                    Arg::new("keep-all")
+                        .long("keep-all")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Remove the history policy"),
+                )
+                .group(
+                    clap::ArgGroup::new("policy")
+                        .args(["max-len", "max-age", "keep-all"])
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("incr")
+                .about("Atomically add to a numeric value")
+                .arg(Arg::new("key").required(true).help("Key name"))
+                .arg(
+                    Arg::new("amount")
+                        .allow_negative_numbers(true)
+                        .default_value("1")
+                        .help("Integer or float amount (default 1)"),
+                ),
+        )
+        .subcommand(
+            Command::new("decr")
+                .about("Atomically subtract from a numeric value")
+                .arg(Arg::new("key").required(true).help("Key name"))
+                .arg(
+                    Arg::new("amount")
+                        .allow_negative_numbers(true)
+                        .default_value("1")
+                        .help("Integer or float amount (default 1)"),
+                ),
+        )
+        .subcommand(build_export(
+            "Export keys as JSON lines or CSV",
+            "Key prefix filter",
+        ))
+        .subcommand(build_import("Import keys from a JSON lines or CSV file"))
 }
 
 // =========================================================================
@@ -229,12 +319,111 @@ fn build_json() -> Command {
                         .help("Include version and timestamp in output"),
                 ),
         )
+        .subcommand(
+            Command::new("get-paths")
+                .about("Get the values at several paths of a JSON document")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(
+                    Arg::new("paths")
+                        .required(true)
+                        .num_args(1..)
+                        .help("JSON paths"),
+                ),
+        )
         .subcommand(
             Command::new("del")
                 .about("Delete a value at a path")
                 .arg(Arg::new("key").required(true).help("Document key"))
                 .arg(Arg::new("path").required(true).help("JSON path")),
         )
+        .subcommand(
+            Command::new("merge")
+                .about("Apply a JSON merge patch (RFC 7396) to a document")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(
+                    Arg::new("patch")
+                        .required_unless_present("file")
+                        .help("Merge patch (null fields are removed)"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read patch from JSON file ('-' for stdin)"),
+                ),
+        )
+        .subcommand(
+            Command::new("patch")
+                .about("Apply JSON Patch (RFC 6902) operations to a document")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(
+                    Arg::new("ops")
+                        .required_unless_present("file")
+                        .help("JSON array of patch operations"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read operations from JSON file ('-' for stdin)"),
+                ),
+        )
+        .subcommand(
+            Command::new("arr-append")
+                .about("Append a value to an array in a JSON document")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(Arg::new("path").required(true).help("JSON path"))
+                .arg(
+                    Arg::new("value")
+                        .required_unless_present("file")
+                        .help("JSON value"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read value from JSON file ('-' for stdin)"),
+                ),
+        )
+        .subcommand(
+            Command::new("arr-insert")
+                .about("Insert a value into an array before an index")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(Arg::new("path").required(true).help("JSON path"))
+                .arg(Arg::new("index").required(true).help("Insert position"))
+                .arg(
+                    Arg::new("value")
+                        .required_unless_present("file")
+                        .help("JSON value"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read value from JSON file ('-' for stdin)"),
+                ),
+        )
+        .subcommand(
+            Command::new("arr-remove")
+                .about("Remove and print the element at an index of an array")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(Arg::new("path").required(true).help("JSON path"))
+                .arg(Arg::new("index").required(true).help("Element index")),
+        )
+        .subcommand(
+            Command::new("arr-len")
+                .about("Get the length of an array in a JSON document")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(
+                    Arg::new("path")
+                        .default_value("$")
+                        .help("JSON path (default: $)"),
+                ),
+        )
         .subcommand(
             Command::new("list")
                 .about("List JSON documents")
@@ -255,6 +444,58 @@ fn build_json() -> Command {
                 .about("Get version history for a document")
                 .arg(Arg::new("key").required(true).help("Document key")),
         )
+        .subcommand(
+            Command::new("schema")
+                .about("Get or set the JSON schema of documents under a key prefix")
+                .arg(
+                    Arg::new("prefix")
+                        .required(true)
+                        .help("Document key prefix ('' for every document)"),
+                )
+                .arg(
+                    Arg::new("schema")
+                        .help("JSON schema documents must match (omit to show the current one)"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read schema from JSON file ('-' for stdin)")
+                        .conflicts_with("schema"),
+                )
+                .arg(
+                    Arg::new("clear")
+                        .long("clear")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Remove the schema")
+                        .conflicts_with_all(["schema", "file"]),
+                ),
+        )
+        .subcommand(
+            Command::new("validate")
+                .about("Check a document against its schema without writing it")
+                .arg(Arg::new("key").required(true).help("Document key"))
+                .arg(
+                    Arg::new("value")
+                        .required_unless_present("file")
+                        .help("JSON document"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read document from JSON file ('-' for stdin)"),
+                ),
+        )
+        .subcommand(build_export(
+            "Export documents as JSON lines or CSV",
+            "Document key prefix filter",
+        ))
+        .subcommand(build_import(
+            "Import documents from a JSON lines or CSV file",
+        ))
 }
 
 // =========================================================================
@@ -295,6 +536,132 @@ fn build_event() -> Command {
                 .arg(Arg::new("after").long("after").help("After sequence number")),
         )
         .subcommand(Command::new("len").about("Get total event count"))
+        .subcommand(
+            Command::new("retention")
+                .about("Set the retention policy of an event type")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(
+                    Arg::new("max-len")
+                        .long("max-len")
+                        .value_name("N")
+                        .help("Keep at most N events")
+                        .conflicts_with_all(["max-age", "keep-all"]),
+                )
+                .arg(
+                    Arg::new("max-age")
+                        .long("max-age")
+                        .value_name("SECS")
+                        .help("Keep events appended within SECS seconds")
+                        .conflicts_with("keep-all"),
+                )
+                .arg(
+                    Arg::new("keep-all")
+                        .long("keep-all")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Remove the retention policy"),
+                )
+                .group(
+                    clap::ArgGroup::new("policy")
+                        .args(["max-len", "max-age", "keep-all"])
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("schema")
+                .about("Get or set the JSON schema of an event type")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(
+                    Arg::new("schema")
+                        .help("JSON schema payloads must match (omit to show the current one)"),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .value_name("PATH")
+                        .help("Read schema from JSON file ('-' for stdin)")
+                        .conflicts_with("schema"),
+                )
+                .arg(
+                    Arg::new("clear")
+                        .long("clear")
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Remove the schema")
+                        .conflicts_with_all(["schema", "file"]),
+                ),
+        )
+        .subcommand(
+            Command::new("group-create")
+                .about("Create a consumer group on an event type")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(Arg::new("group").required(true).help("Consumer group")),
+        )
+        .subcommand(
+            Command::new("read-group")
+                .about("Deliver undelivered events to a group consumer")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(Arg::new("group").required(true).help("Consumer group"))
+                .arg(Arg::new("consumer").required(true).help("Consumer name"))
+                .arg(
+                    Arg::new("count")
+                        .long("count")
+                        .short('n')
+                        .default_value("1")
+                        .help("Maximum events to deliver"),
+                ),
+        )
+        .subcommand(
+            Command::new("ack")
+                .about("Acknowledge events delivered to a consumer group")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(Arg::new("group").required(true).help("Consumer group"))
+                .arg(
+                    Arg::new("sequences")
+                        .required(true)
+                        .num_args(1..)
+                        .value_name("SEQUENCE")
+                        .help("Sequence number(s) to acknowledge"),
+                ),
+        )
+        .subcommand(
+            Command::new("pending")
+                .about("List unacknowledged events of a consumer group")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(Arg::new("group").required(true).help("Consumer group")),
+        )
+        .subcommand(
+            Command::new("tail")
+                .about("Print the latest events of a type, optionally following new ones")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(
+                    Arg::new("lines")
+                        .long("lines")
+                        .short('n')
+                        .default_value("10")
+                        .help("Existing events to print first"),
+                )
+                .arg(
+                    Arg::new("follow")
+                        .long("follow")
+                        .short('f')
+                        .action(clap::ArgAction::SetTrue)
+                        .help("Keep printing new events as they are appended"),
+                )
+                .arg(
+                    Arg::new("interval")
+                        .long("interval")
+                        .value_name("MS")
+                        .default_value("500")
+                        .help("Poll interval in milliseconds when following"),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Export events of a type as JSON lines or CSV")
+                .arg(Arg::new("type").required(true).help("Event type"))
+                .arg(format_arg())
+                .arg(output_arg()),
+        )
 }
 
 // =========================================================================
@@ -332,6 +699,11 @@ fn build_state() -> Command {
                         .short('v')
                         .action(clap::ArgAction::SetTrue)
                         .help("Include version and timestamp in output"),
+                )
+                .arg(
+                    Arg::new("at-version")
+                        .long("at-version")
+                        .help("Read the value the cell had at this version"),
                 ),
         )
         .subcommand(
@@ -371,7 +743,113 @@ fn build_state() -> Command {
         .subcommand(
             Command::new("history")
                 .about("Get version history for a cell")
-                .arg(Arg::new("cell").required(true).help("Cell name")),
+                .arg(Arg::new("cell").required(true).help("Cell name"))
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .short('n')
+                        .help("Maximum versions to show, newest first"),
+                ),
+        )
+        .subcommand(
+            Command::new("incr")
+                .about("Atomically add to a numeric cell")
+                .arg(Arg::new("cell").required(true).help("Cell name"))
+                .arg(
+                    Arg::new("amount")
+                        .allow_negative_numbers(true)
+                        .default_value("1")
+                        .help("Integer or float amount (default 1)"),
+                ),
+        )
+        .subcommand(
+            Command::new("decr")
+                .about("Atomically subtract from a numeric cell")
+                .arg(Arg::new("cell").required(true).help("Cell name"))
+                .arg(
+                    Arg::new("amount")
+                        .allow_negative_numbers(true)
+                        .default_value("1")
+                        .help("Integer or float amount (default 1)"),
+                ),
+        )
+}
+
+// =========================================================================
+// List
+// =========================================================================
+
+fn build_list() -> Command {
+    Command::new("list")
+        .about("List operations")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("lpush")
+                .about("Push values onto the head of a list")
+                .arg(Arg::new("list").required(true).help("List name"))
+                .arg(
+                    Arg::new("values")
+                        .required(true)
+                        .num_args(1..)
+                        .help("Values to push"),
+                ),
+        )
+        .subcommand(
+            Command::new("rpush")
+                .about("Push values onto the tail of a list")
+                .arg(Arg::new("list").required(true).help("List name"))
+                .arg(
+                    Arg::new("values")
+                        .required(true)
+                        .num_args(1..)
+                        .help("Values to push"),
+                ),
+        )
+        .subcommand(
+            Command::new("lpop")
+                .about("Pop the head of a list")
+                .arg(Arg::new("list").required(true).help("List name"))
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .short('t')
+                        .value_name("MS")
+                        .help("Wait up to this many milliseconds for a value"),
+                ),
+        )
+        .subcommand(
+            Command::new("rpop")
+                .about("Pop the tail of a list")
+                .arg(Arg::new("list").required(true).help("List name"))
+                .arg(
+                    Arg::new("timeout")
+                        .long("timeout")
+                        .short('t')
+                        .value_name("MS")
+                        .help("Wait up to this many milliseconds for a value"),
+                ),
+        )
+        .subcommand(
+            Command::new("range")
+                .about("Read elements between two indices (inclusive)")
+                .arg(Arg::new("list").required(true).help("List name"))
+                .arg(
+                    Arg::new("start")
+                        .allow_negative_numbers(true)
+                        .default_value("0")
+                        .help("Start index (negative counts from the tail)"),
+                )
+                .arg(
+                    Arg::new("stop")
+                        .allow_negative_numbers(true)
+                        .default_value("-1")
+                        .help("Stop index (negative counts from the tail)"),
+                ),
+        )
+        .subcommand(
+            Command::new("len")
+                .about("Get the number of elements in a list")
+                .arg(Arg::new("list").required(true).help("List name")),
         )
 }
 
@@ -389,7 +867,8 @@ fn build_vector() -> Command {
                 .arg(Arg::new("collection").required(true).help("Collection name"))
                 .arg(Arg::new("key").required(true).help("Vector key"))
                 .arg(Arg::new("vector").required(true).help("Vector as JSON array, e.g. [1.0,2.0,3.0]"))
-                .arg(Arg::new("metadata").long("metadata").help("Metadata as JSON")),
+                .arg(Arg::new("metadata").long("metadata").help("Metadata as JSON"))
+                .arg(Arg::new("namespace").long("namespace").help("Namespace within the collection")),
         )
         .subcommand(
             Command::new("get")
@@ -410,6 +889,15 @@ fn build_vector() -> Command {
                 .arg(Arg::new("query").required(true).help("Query vector as JSON array"))
                 .arg(Arg::new("k").default_value("10").help("Number of results"))
                 .arg(Arg::new("metric").long("metric").help("Distance metric: cosine, euclidean, dotproduct"))
+                .arg(Arg::new("filter").long("filter").help("Metadata filter as JSON"))
+                .arg(Arg::new("namespace").long("namespace").help("Only search this namespace")),
+        )
+        .subcommand(
+            Command::new("search-batch")
+                .about("Search for similar vectors for several queries at once")
+                .arg(Arg::new("collection").required(true).help("Collection name"))
+                .arg(Arg::new("queries").required(true).help("JSON array of query vectors"))
+                .arg(Arg::new("k").default_value("10").help("Number of results per query"))
                 .arg(Arg::new("filter").long("filter").help("Metadata filter as JSON")),
         )
         .subcommand(
@@ -417,7 +905,15 @@ fn build_vector() -> Command {
                 .about("Create a vector collection")
                 .arg(Arg::new("name").required(true).help("Collection name"))
                 .arg(Arg::new("dim").required(true).help("Vector dimension"))
-                .arg(Arg::new("metric").long("metric").default_value("cosine").help("Distance metric")),
+                .arg(Arg::new("metric").long("metric").default_value("cosine").help("Distance metric"))
+                .arg(Arg::new("dtype").long("dtype").help("Index storage type: f32, f16, int8")),
+        )
+        .subcommand(
+            Command::new("reindex")
+                .about("Rebuild a collection's index, optionally changing metric or index type")
+                .arg(Arg::new("name").required(true).help("Collection name"))
+                .arg(Arg::new("metric").long("metric").help("New distance metric: cosine, euclidean, dotproduct"))
+                .arg(Arg::new("index").long("index").help("New index type: brute_force, hnsw")),
         )
         .subcommand(
             Command::new("drop")
@@ -435,7 +931,8 @@ fn build_vector() -> Command {
             Command::new("batch-upsert")
                 .about("Batch insert/update vectors")
                 .arg(Arg::new("collection").required(true).help("Collection name"))
-                .arg(Arg::new("json").required(true).help("JSON array of {key, vector, metadata?}")),
+                .arg(Arg::new("json").required(true).help("JSON array of {key, vector, metadata?}"))
+                .arg(Arg::new("namespace").long("namespace").help("Namespace for every entry")),
         )
 }
 
@@ -468,6 +965,11 @@ fn build_branch() -> Command {
                 .about("Check if a branch exists")
                 .arg(Arg::new("name").required(true).help("Branch name")),
         )
+        .subcommand(
+            Command::new("stats")
+                .about("Show entry counts and memory usage of a branch")
+                .arg(Arg::new("name").required(true).help("Branch name")),
+        )
         .subcommand(
             Command::new("del")
                 .about("Delete a branch")
@@ -495,6 +997,11 @@ fn build_branch() -> Command {
                         .help("Merge strategy: lww or strict"),
                 ),
         )
+        .subcommand(
+            Command::new("schema-log")
+                .about("Show collection creations, drops and reindexes on a branch")
+                .arg(Arg::new("name").help("Branch name (defaults to the current branch)")),
+        )
         .subcommand(
             Command::new("export")
                 .about("Export a branch to a bundle file")
@@ -504,13 +1011,34 @@ fn build_branch() -> Command {
         .subcommand(
             Command::new("import")
                 .about("Import a branch from a bundle file")
-                .arg(Arg::new("path").required(true).help("Bundle file path")),
+                .arg(Arg::new("path").required(true).help("Bundle file path"))
+                .arg(
+                    Arg::new("as")
+                        .long("as")
+                        .help("Import into a new branch with this name"),
+                ),
         )
         .subcommand(
             Command::new("validate")
                 .about("Validate a branch bundle file")
                 .arg(Arg::new("path").required(true).help("Bundle file path")),
         )
+        .subcommand(
+            Command::new("export-all")
+                .about("Export every branch to a database bundle file")
+                .arg(Arg::new("path").required(true).help("Output file path")),
+        )
+        .subcommand(
+            Command::new("import-all")
+                .about("Import every branch from a database bundle file")
+                .arg(Arg::new("path").required(true).help("Bundle file path"))
+                .arg(
+                    Arg::new("on-conflict")
+                        .long("on-conflict")
+                        .default_value("fail")
+                        .help("When a branch exists: fail, skip, rename, or overwrite"),
+                ),
+        )
 }
 
 // =========================================================================
@@ -558,6 +1086,12 @@ fn build_txn_begin() -> Command {
                 .action(clap::ArgAction::SetTrue)
                 .help("Start a read-only transaction"),
         )
+        .arg(
+            Arg::new("txn-pessimistic")
+                .long("pessimistic")
+                .action(clap::ArgAction::SetTrue)
+                .help("Lock each key on first access instead of checking for conflicts at commit"),
+        )
 }
 
 fn build_txn_commit() -> Command {
@@ -585,7 +1119,7 @@ fn build_ping() -> Command {
 }
 
 fn build_info() -> Command {
-    Command::new("info").about("Get database information")
+    Command::new("info").about("Get database information, entry counts, and memory and WAL usage")
 }
 
 fn build_flush() -> Command {
@@ -596,6 +1130,21 @@ fn build_compact() -> Command {
     Command::new("compact").about("Trigger compaction")
 }
 
+fn build_stats() -> Command {
+    Command::new("stats").about("Get runtime statistics (result cursors, transaction pool)")
+}
+
+fn build_latency() -> Command {
+    Command::new("latency")
+        .about("Show p50/p95/p99 latency per command")
+        .arg(
+            Arg::new("reset")
+                .long("reset")
+                .action(clap::ArgAction::SetTrue)
+                .help("Clear recorded latencies instead"),
+        )
+}
+
 // =========================================================================
 // Search
 // =========================================================================
@@ -610,6 +1159,143 @@ fn build_search() -> Command {
                 .long("primitives")
                 .help("Comma-separated list of primitives to search"),
         )
+        .arg(
+            Arg::new("text")
+                .long("text")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with("primitives")
+                .help("Full-text BM25 search over KV strings, JSON documents and event payloads"),
+        )
+        .arg(
+            Arg::new("semantic")
+                .long("semantic")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["primitives", "text"])
+                .help("Semantic search over auto-embedded values (requires auto-embed)"),
+        )
+        .arg(
+            Arg::new("rerank")
+                .long("rerank")
+                .value_name("N")
+                .requires("semantic")
+                .help("Rerank the top N semantic candidates with the configured reranker"),
+        )
+        .arg(
+            Arg::new("hybrid")
+                .long("hybrid")
+                .value_name("COLLECTION")
+                .conflicts_with_all(["primitives", "text", "semantic"])
+                .requires("embedding")
+                .help("Hybrid search: fuse full-text results with vector matches from COLLECTION"),
+        )
+        .arg(
+            Arg::new("embedding")
+                .long("embedding")
+                .requires("hybrid")
+                .help("Query vector as JSON array (with --hybrid)"),
+        )
+        .arg(
+            Arg::new("vector-weight")
+                .long("vector-weight")
+                .requires("hybrid")
+                .help("Fuse by weighted score with this vector weight (0-1) instead of RRF"),
+        )
+}
+
+// =========================================================================
+// Import / export
+// =========================================================================
+
+fn build_export(about: &'static str, prefix_help: &'static str) -> Command {
+    Command::new("export")
+        .about(about)
+        .arg(format_arg())
+        .arg(output_arg())
+        .arg(
+            Arg::new("prefix")
+                .long("prefix")
+                .short('p')
+                .help(prefix_help),
+        )
+}
+
+fn build_import(about: &'static str) -> Command {
+    Command::new("import")
+        .about(about)
+        .arg(
+            Arg::new("file")
+                .required(true)
+                .value_name("PATH")
+                .help("File to import ('-' for stdin)"),
+        )
+        .arg(format_arg().help("Input format (default: from the file extension, else jsonl)"))
+}
+
+fn format_arg() -> Arg {
+    Arg::new("format")
+        .long("format")
+        .value_parser(["jsonl", "csv"])
+        .help("Output format (default: jsonl)")
+}
+
+fn output_arg() -> Arg {
+    Arg::new("output")
+        .long("output")
+        .short('o')
+        .value_name("PATH")
+        .help("Write to a file instead of stdout")
+}
+
+// =========================================================================
+// Bench
+// =========================================================================
+
+fn build_bench() -> Command {
+    Command::new("bench")
+        .about("Measure throughput and latency of a standard workload")
+        .arg(
+            Arg::new("workload")
+                .long("workload")
+                .required(true)
+                .value_parser(["kv-set", "kv-get", "vector-search"])
+                .help("Workload to run"),
+        )
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .value_name("N")
+                .help("Concurrent client threads (default: 1)"),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("TIME")
+                .help("How long to run, e.g. 30s, 500ms, 2m (default: 10s)"),
+        )
+        .arg(
+            Arg::new("keys")
+                .long("keys")
+                .value_name("N")
+                .help("Keys or vectors in the data set (default: 10000)"),
+        )
+        .arg(
+            Arg::new("value-size")
+                .long("value-size")
+                .value_name("BYTES")
+                .help("Size of each KV value (default: 100)"),
+        )
+        .arg(
+            Arg::new("dim")
+                .long("dim")
+                .value_name("N")
+                .help("Vector dimension for vector-search (default: 128)"),
+        )
+        .arg(
+            Arg::new("durability")
+                .long("durability")
+                .value_parser(["standard", "always"])
+                .help("Durability of the temporary database (default: standard)"),
+        )
 }
 
 // =========================================================================
@@ -6,9 +6,13 @@
 //! - **Raw** (`--raw`): Bare values, no quotes, no type prefixes
 
 use strata_executor::{
-    BranchDiffResult, Error, ForkInfo, MergeInfo, Output, Value, VersionedValue,
+    value_to_json, BranchDiffResult, BranchRetention, BranchStats, DatabaseInfo, DatabaseStats,
+    EnvironmentInfo, Error, ForkInfo, MergeInfo, Output, PrimitiveCounts, RetentionPolicy,
+    SchemaChange, SchemaChangeKind, Value, VersionedValue,
 };
 
+use crate::bench::BenchReport;
+
 /// Output formatting mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum OutputMode {
@@ -76,6 +80,29 @@ fn format_versioned_value(vv: &VersionedValue, mode: OutputMode) -> String {
     }
 }
 
+/// Format one event as a single line, as printed by `event tail`.
+///
+/// JSON mode writes a compact object per line so a stream can be piped
+/// into line-oriented tools.
+pub fn format_event_line(event: &VersionedValue, event_type: &str, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Human => format!(
+            "{} (seq {}, ts={})",
+            format_value_human(&event.value),
+            event.version,
+            event.timestamp
+        ),
+        OutputMode::Json => serde_json::json!({
+            "sequence": event.version,
+            "event_type": event_type,
+            "timestamp": event.timestamp,
+            "payload": value_to_json(&event.value),
+        })
+        .to_string(),
+        OutputMode::Raw => format_value_raw(&event.value),
+    }
+}
+
 /// Format multiple outputs (for multi-key operations).
 pub fn format_multi_output(outputs: &[Output], mode: OutputMode) -> String {
     outputs
@@ -177,25 +204,199 @@ pub fn format_merge_info(info: &MergeInfo, mode: OutputMode) -> String {
             "source": info.source,
             "target": info.target,
             "keys_applied": info.keys_applied,
+            "keys_deleted": info.keys_deleted,
             "conflicts": info.conflicts.len(),
             "spaces_merged": info.spaces_merged,
+            "merge_base": info.merge_base,
         }))
         .unwrap(),
         OutputMode::Raw => format!("{}", info.keys_applied),
         OutputMode::Human => {
+            let delete_note = if info.keys_deleted == 0 {
+                String::new()
+            } else {
+                format!(", {} deleted", info.keys_deleted)
+            };
             let conflict_note = if info.conflicts.is_empty() {
                 String::new()
             } else {
                 format!(", {} conflicts resolved", info.conflicts.len())
             };
             format!(
-                "Merged \"{}\" -> \"{}\" ({} keys, {} spaces{})",
-                info.source, info.target, info.keys_applied, info.spaces_merged, conflict_note
+                "Merged \"{}\" -> \"{}\" ({} keys{}, {} spaces{})",
+                info.source,
+                info.target,
+                info.keys_applied,
+                delete_note,
+                info.spaces_merged,
+                conflict_note
             )
         }
     }
 }
 
+/// Format a branch schema log.
+pub fn format_schema_log(changes: &[SchemaChange], mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(changes).unwrap(),
+        OutputMode::Raw => changes
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    c.timestamp,
+                    c.space,
+                    c.collection,
+                    schema_change_detail(&c.kind)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputMode::Human => {
+            if changes.is_empty() {
+                return "(empty list)".to_string();
+            }
+            changes
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    format!(
+                        "{}) {} \"{}\" in space \"{}\": {}",
+                        i + 1,
+                        c.timestamp,
+                        c.collection,
+                        c.space,
+                        schema_change_detail(&c.kind)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+}
+
+/// What a schema log entry did, e.g. `created (dim: 384, metric: cosine, dtype: F32)`.
+fn schema_change_detail(kind: &SchemaChangeKind) -> String {
+    match kind {
+        SchemaChangeKind::CollectionCreated {
+            dimension,
+            metric,
+            dtype,
+        } => format!(
+            "created (dim: {}, metric: {}, dtype: {:?})",
+            dimension,
+            metric.name(),
+            dtype
+        ),
+        SchemaChangeKind::CollectionDropped { dimension } => {
+            format!("dropped (dim: {})", dimension)
+        }
+        SchemaChangeKind::IndexRebuilt { metric, index } => {
+            format!("reindexed (metric: {}, index: {})", metric.name(), index)
+        }
+    }
+}
+
+/// Format `info`: database info followed by storage statistics.
+pub fn format_info(info: &DatabaseInfo, stats: &DatabaseStats, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "info": info,
+            "stats": stats,
+        }))
+        .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e)),
+        OutputMode::Raw => {
+            let t = &stats.transactions;
+            let mut lines = vec![
+                format_raw(&Output::DatabaseInfo(info.clone())),
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    stats.memory_bytes,
+                    stats.wal_bytes,
+                    stats
+                        .snapshot_watermark
+                        .map(|w| w.to_string())
+                        .unwrap_or_default(),
+                    t.committed,
+                    t.aborted,
+                    t.conflicts
+                ),
+            ];
+            lines.extend(stats.branches.iter().map(format_branch_stats_raw));
+            lines.join("\n")
+        }
+        OutputMode::Human => {
+            let t = &stats.transactions;
+            let mut lines = vec![
+                format_human(&Output::DatabaseInfo(info.clone())),
+                format!("entries: {}", format_primitive_counts(&stats.primitives)),
+                format!("memory: {}", format_bytes(stats.memory_bytes)),
+                format!("wal: {}", format_bytes(stats.wal_bytes)),
+                format!(
+                    "snapshot_watermark: {}",
+                    stats
+                        .snapshot_watermark
+                        .map_or("(none)".to_string(), |w| w.to_string())
+                ),
+                format!(
+                    "transactions: {} committed, {} aborted ({} conflicts), {} active, {} retries ({} gave up)",
+                    t.committed, t.aborted, t.conflicts, t.active, t.retries, t.retries_exhausted
+                ),
+            ];
+            lines.extend(stats.branches.iter().map(|b| {
+                format!(
+                    "branch \"{}\": {} entries, {}",
+                    b.branch,
+                    b.primitives.total(),
+                    format_bytes(b.memory_bytes)
+                )
+            }));
+            lines.join("\n")
+        }
+    }
+}
+
+/// Format a benchmark report.
+pub fn format_bench_report(report: &BenchReport, mode: OutputMode) -> String {
+    match mode {
+        OutputMode::Json => serde_json::to_string_pretty(&serde_json::json!({
+            "workload": report.workload.name(),
+            "threads": report.threads,
+            "duration_ms": report.elapsed.as_millis() as u64,
+            "ops": report.ops,
+            "errors": report.errors,
+            "ops_per_sec": report.ops_per_sec(),
+            "p50_ns": report.p50_ns,
+            "p95_ns": report.p95_ns,
+            "p99_ns": report.p99_ns,
+            "max_ns": report.max_ns,
+        }))
+        .unwrap(),
+        OutputMode::Raw => format!(
+            "{:.0}\t{}\t{}\t{}\t{}",
+            report.ops_per_sec(),
+            report.p50_ns,
+            report.p95_ns,
+            report.p99_ns,
+            report.max_ns
+        ),
+        OutputMode::Human => format!(
+            "{} ({} threads, {:.1}s): {} ops, {:.0} ops/sec, {} errors\n\
+             latency: p50={} p95={} p99={} max={}",
+            report.workload.name(),
+            report.threads,
+            report.elapsed.as_secs_f64(),
+            report.ops,
+            report.ops_per_sec(),
+            report.errors,
+            format_ns(report.p50_ns),
+            format_ns(report.p95_ns),
+            format_ns(report.p99_ns),
+            format_ns(report.max_ns)
+        ),
+    }
+}
+
 // =========================================================================
 // JSON mode
 // =========================================================================
@@ -237,13 +438,51 @@ fn format_raw(output: &Output) -> String {
             .map(|vv| format_value_raw(&vv.value))
             .collect::<Vec<_>>()
             .join("\n"),
+        Output::KvHistory(h) => h
+            .versions
+            .iter()
+            .map(|vv| format_value_raw(&vv.value))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::Keys(keys) => keys.join("\n"),
+        Output::Values(vals) => vals
+            .iter()
+            .map(format_value_raw)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Output::MaybeValues(vals) => vals
+            .iter()
+            .map(|v| v.as_ref().map(format_value_raw).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Output::PendingEvents(pending) => pending
+            .iter()
+            .map(|e| format!("{}\t{}\t{}", e.sequence, e.consumer, e.delivered_at))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Output::SchemaViolations(violations) => violations
+            .iter()
+            .map(|v| format!("{}\t{}", v.path, v.message))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::JsonListResult { keys, .. } => keys.join("\n"),
+        Output::CursorPage { page, .. } => format_raw(page),
         Output::VectorMatches(matches) => matches
             .iter()
             .map(|m| format!("{}\t{}", m.key, m.score))
             .collect::<Vec<_>>()
             .join("\n"),
+        Output::VectorBatchMatches(batches) => batches
+            .iter()
+            .map(|matches| {
+                matches
+                    .iter()
+                    .map(|m| format!("{}\t{}", m.key, m.score))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
         Output::VectorData(None) => String::new(),
         Output::VectorData(Some(vd)) => format!("{:?}", vd.data.embedding),
         Output::VectorCollectionList(colls) => colls
@@ -278,14 +517,53 @@ fn format_raw(output: &Output) -> String {
             )
         }
         Output::Pong { version } => version.clone(),
+        Output::Compacted(r) => format!("{}\t{}", r.segments_removed, r.bytes_reclaimed),
+        Output::Stats(s) => format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            s.cursors.open,
+            s.cursors.opened,
+            s.cursors.closed,
+            s.cursors.expired,
+            s.cursors.buffered_rows,
+            s.transaction_pool.hits,
+            s.transaction_pool.misses,
+            s.transaction_pool.returned,
+            s.transaction_pool.discarded,
+            s.transaction_pool.shrunk
+        ),
+        Output::BranchStats(b) => format_branch_stats_raw(b),
+        Output::BranchRetention(r) => format_branch_retention(r, "\t"),
+        Output::LatencyReport(r) => r
+            .commands
+            .iter()
+            .map(|c| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}",
+                    c.command, c.count, c.p50_ns, c.p95_ns, c.p99_ns, c.max_ns
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::SearchResults(hits) => hits
             .iter()
             .map(|h| format!("{}\t{}\t{}", h.entity, h.primitive, h.score))
             .collect::<Vec<_>>()
             .join("\n"),
+        Output::TextSearchResults(hits) => hits
+            .iter()
+            .map(|h| format!("{}\t{}\t{}", h.entity, h.primitive, h.score))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::SpaceList(spaces) => spaces.join("\n"),
         Output::BranchExported(r) => format!("{}\t{}", r.path, r.entry_count),
         Output::BranchImported(r) => format!("{}\t{}", r.branch_id, r.keys_written),
+        Output::DatabaseExported(r) => format!("{}\t{}", r.path, r.branches.len()),
+        Output::DatabaseImported(r) => r
+            .imported
+            .iter()
+            .map(|b| format!("{}\t{}", b.branch_id, b.keys_written))
+            .collect::<Vec<_>>()
+            .join("\n"),
         Output::BundleValidated(r) => {
             if r.checksums_valid {
                 "1".to_string()
@@ -374,7 +652,81 @@ fn format_human(output: &Output) -> String {
                     .join("\n")
             }
         }
+        Output::KvHistory(h) => {
+            let mut lines: Vec<String> = h
+                .versions
+                .iter()
+                .enumerate()
+                .map(|(i, vv)| {
+                    format!(
+                        "{}) v{}: {}",
+                        i + 1,
+                        vv.version,
+                        format_value_human(&vv.value)
+                    )
+                })
+                .collect();
+            if lines.is_empty() {
+                lines.push("(empty list)".to_string());
+            }
+            if h.truncated {
+                lines.push("(older versions truncated by history policy)".to_string());
+            }
+            lines.join("\n")
+        }
         Output::Keys(keys) => format_string_list(keys),
+        Output::Values(vals) => {
+            if vals.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                vals.iter()
+                    .enumerate()
+                    .map(|(i, v)| format!("{}) {}", i + 1, format_value_human(v)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        Output::MaybeValues(vals) => vals
+            .iter()
+            .enumerate()
+            .map(|(i, v)| match v {
+                Some(v) => format!("{}) {}", i + 1, format_value_human(v)),
+                None => format!("{}) (nil)", i + 1),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Output::PendingEvents(pending) => {
+            if pending.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                pending
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| {
+                        format!(
+                            "{}) seq={} consumer=\"{}\" delivered_at={}",
+                            i + 1,
+                            e.sequence,
+                            e.consumer,
+                            e.delivered_at
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        Output::SchemaViolations(violations) => {
+            if violations.is_empty() {
+                "(valid)".to_string()
+            } else {
+                violations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| format!("{}) {}: {}", i + 1, v.path, v.message))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
         Output::JsonListResult { keys, cursor } => {
             let mut out = format_string_list(keys);
             if let Some(c) = cursor {
@@ -385,6 +737,13 @@ fn format_human(output: &Output) -> String {
             }
             out
         }
+        Output::CursorPage { page, cursor, .. } => {
+            let mut out = format_human(page);
+            if let Some(c) = cursor {
+                out.push_str(&format!("\n(cursor) {}", c));
+            }
+            out
+        }
         Output::VectorMatches(matches) => {
             if matches.is_empty() {
                 "(empty list)".to_string()
@@ -397,6 +756,23 @@ fn format_human(output: &Output) -> String {
                     .join("\n")
             }
         }
+        Output::VectorBatchMatches(batches) => {
+            batches
+                .iter()
+                .enumerate()
+                .map(|(q, matches)| {
+                    let mut lines = vec![format!("query {}:", q + 1)];
+                    if matches.is_empty() {
+                        lines.push("  (empty list)".to_string());
+                    }
+                    lines.extend(matches.iter().enumerate().map(|(i, m)| {
+                        format!("  {}) \"{}\" (score: {:.3})", i + 1, m.key, m.score)
+                    }));
+                    lines.join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
         Output::VectorData(None) => "(nil)".to_string(),
         Output::VectorData(Some(vd)) => {
             let mut lines = vec![
@@ -450,9 +826,27 @@ fn format_human(output: &Output) -> String {
                 format!("created_at: {}", bi.info.created_at),
                 format!("updated_at: {}", bi.info.updated_at),
             ];
+            if let Some(completed_at) = bi.info.completed_at {
+                lines.push(format!("completed_at: {}", completed_at));
+            }
+            if let Some(error) = &bi.info.error {
+                lines.push(format!("error: \"{}\"", error));
+            }
             if let Some(parent) = &bi.info.parent_id {
                 lines.push(format!("parent: \"{}\"", parent));
             }
+            if let Some(fork_version) = bi.info.fork_version {
+                lines.push(format!("fork_version: {}", fork_version));
+            }
+            if !bi.info.tags.is_empty() {
+                lines.push(format!("tags: {}", bi.info.tags.join(", ")));
+            }
+            for (key, value) in &bi.info.metadata {
+                lines.push(format!("metadata.{}: {}", key, format_value_human(value)));
+            }
+            if let Some(env) = &bi.info.environment {
+                lines.push(format!("environment: {}", format_environment(env)));
+            }
             lines.join("\n")
         }
         Output::BranchInfoList(branches) => {
@@ -487,6 +881,56 @@ fn format_human(output: &Output) -> String {
             )
         }
         Output::Pong { version } => format!("PONG {}", version),
+        Output::Compacted(r) => {
+            format!(
+                "Compacted: {} segments removed, {} bytes reclaimed, {} versions pruned ({} ms)",
+                r.segments_removed, r.bytes_reclaimed, r.versions_removed, r.duration_ms
+            )
+        }
+        Output::BranchStats(b) => format!(
+            "branch: \"{}\"\nentries: {}\nmemory: {}",
+            b.branch,
+            format_primitive_counts(&b.primitives),
+            format_bytes(b.memory_bytes)
+        ),
+        Output::BranchRetention(r) => format_branch_retention(r, ": "),
+        Output::Stats(s) => {
+            format!(
+                "cursors_open: {}\ncursors_opened: {}\ncursors_closed: {}\ncursors_expired: {}\ncursor_buffered_rows: {}\n\
+                 txn_pool_hits: {}\ntxn_pool_misses: {}\ntxn_pool_returned: {}\ntxn_pool_discarded: {}\ntxn_pool_shrunk: {}",
+                s.cursors.open,
+                s.cursors.opened,
+                s.cursors.closed,
+                s.cursors.expired,
+                s.cursors.buffered_rows,
+                s.transaction_pool.hits,
+                s.transaction_pool.misses,
+                s.transaction_pool.returned,
+                s.transaction_pool.discarded,
+                s.transaction_pool.shrunk
+            )
+        }
+        Output::LatencyReport(r) => {
+            if r.commands.is_empty() {
+                "(no commands recorded)".to_string()
+            } else {
+                r.commands
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "{}: count={} p50={} p95={} p99={} max={}",
+                            c.command,
+                            c.count,
+                            format_ns(c.p50_ns),
+                            format_ns(c.p95_ns),
+                            format_ns(c.p99_ns),
+                            format_ns(c.max_ns)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
         Output::SearchResults(hits) => {
             if hits.is_empty() {
                 "(empty list)".to_string()
@@ -512,6 +956,25 @@ fn format_human(output: &Output) -> String {
                     .join("\n")
             }
         }
+        Output::TextSearchResults(hits) => {
+            if hits.is_empty() {
+                "(empty list)".to_string()
+            } else {
+                hits.iter()
+                    .map(|h| {
+                        format!(
+                            "{}) \"{}\" [{}] (score: {:.3}) - {}",
+                            h.rank,
+                            h.entity,
+                            h.primitive,
+                            h.score,
+                            emphasize(&h.snippet, &h.highlights)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
         Output::SpaceList(spaces) => format_string_list(spaces),
         Output::BranchExported(r) => {
             format!(
@@ -519,20 +982,60 @@ fn format_human(output: &Output) -> String {
                 r.branch_id, r.path, r.entry_count, r.bundle_size
             )
         }
-        Output::BranchImported(r) => {
-            format!(
+        Output::BranchImported(r) => match &r.renamed_from {
+            Some(from) => format!(
+                "Imported branch \"{}\" as \"{}\" ({} transactions, {} keys)",
+                from, r.branch_id, r.transactions_applied, r.keys_written
+            ),
+            None => format!(
                 "Imported branch \"{}\" ({} transactions, {} keys)",
                 r.branch_id, r.transactions_applied, r.keys_written
+            ),
+        },
+        Output::DatabaseExported(r) => {
+            format!(
+                "Exported {} branches to {} ({} entries, {} bytes)",
+                r.branches.len(),
+                r.path,
+                r.entry_count,
+                r.bundle_size
             )
         }
+        Output::DatabaseImported(r) => {
+            let mut lines = Vec::new();
+            for b in &r.imported {
+                match &b.renamed_from {
+                    Some(from) => lines.push(format!(
+                        "Imported branch \"{}\" as \"{}\" ({} transactions, {} keys)",
+                        from, b.branch_id, b.transactions_applied, b.keys_written
+                    )),
+                    None => lines.push(format!(
+                        "Imported branch \"{}\" ({} transactions, {} keys)",
+                        b.branch_id, b.transactions_applied, b.keys_written
+                    )),
+                }
+            }
+            for name in &r.skipped {
+                lines.push(format!("Skipped branch \"{}\" (already exists)", name));
+            }
+            if lines.is_empty() {
+                "(no branches)".to_string()
+            } else {
+                lines.join("\n")
+            }
+        }
         Output::BundleValidated(r) => {
-            format!(
+            let summary = format!(
                 "Bundle valid: branch=\"{}\", format_version={}, entries={}, checksums={}",
                 r.branch_id,
                 r.format_version,
                 r.entry_count,
                 if r.checksums_valid { "OK" } else { "FAILED" }
-            )
+            );
+            match &r.environment {
+                Some(env) => format!("{}\nenvironment: {}", summary, format_environment(env)),
+                None => summary,
+            }
         }
         Output::TimeRange { oldest_ts, latest_ts } => {
             match (oldest_ts, latest_ts) {
@@ -580,6 +1083,117 @@ fn format_value_human(v: &Value) -> String {
     }
 }
 
+/// Wrap the highlighted byte ranges of a search snippet in `*`.
+fn emphasize(snippet: &str, highlights: &[(u64, u64)]) -> String {
+    let mut out = String::with_capacity(snippet.len() + highlights.len() * 2);
+    let mut pos = 0;
+    for &(start, end) in highlights {
+        let (start, end) = (start as usize, end as usize);
+        match (snippet.get(pos..start), snippet.get(start..end)) {
+            (Some(before), Some(term)) => {
+                out.push_str(before);
+                out.push('*');
+                out.push_str(term);
+                out.push('*');
+                pos = end;
+            }
+            _ => continue,
+        }
+    }
+    out.push_str(&snippet[pos..]);
+    out
+}
+
+/// One raw line per branch: name, entries per primitive, memory bytes.
+fn format_branch_stats_raw(b: &BranchStats) -> String {
+    let c = &b.primitives;
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        b.branch, c.kv, c.events, c.state, c.json, c.lists, c.vectors, c.custom, b.memory_bytes
+    )
+}
+
+/// Default retention, then one line per primitive override, sorted.
+fn format_branch_retention(r: &BranchRetention, sep: &str) -> String {
+    let policy = |p: &RetentionPolicy| match p {
+        RetentionPolicy::KeepAll => "keep_all".to_string(),
+        RetentionPolicy::MaxLen(n) => format!("max_len {}", n),
+        RetentionPolicy::MaxAgeSecs(s) => format!("max_age {}s", s),
+    };
+    let mut overrides: Vec<String> = r
+        .overrides
+        .iter()
+        .map(|(primitive, p)| format!("{}{}{}", primitive.id(), sep, policy(p)))
+        .collect();
+    overrides.sort();
+    std::iter::once(format!("default{}{}", sep, policy(&r.default)))
+        .chain(overrides)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Entry total followed by the per-primitive breakdown.
+fn format_primitive_counts(c: &PrimitiveCounts) -> String {
+    format!(
+        "{} (kv={} events={} state={} json={} lists={} vectors={} custom={})",
+        c.total(),
+        c.kv,
+        c.events,
+        c.state,
+        c.json,
+        c.lists,
+        c.vectors,
+        c.custom
+    )
+}
+
+/// Render a byte count with a readable binary unit.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Render a nanosecond latency with a readable unit.
+fn format_ns(ns: u64) -> String {
+    if ns >= 1_000_000_000 {
+        format!("{:.2}s", ns as f64 / 1e9)
+    } else if ns >= 1_000_000 {
+        format!("{:.2}ms", ns as f64 / 1e6)
+    } else if ns >= 1_000 {
+        format!("{:.2}us", ns as f64 / 1e3)
+    } else {
+        format!("{}ns", ns)
+    }
+}
+
+/// One-line summary of a captured environment.
+fn format_environment(env: &EnvironmentInfo) -> String {
+    let mut line = format!(
+        "strata {} (wal v{}, snapshot v{}, bundle v{}) on \"{}\" {}/{}",
+        env.strata_version,
+        env.wal_format,
+        env.snapshot_format,
+        env.bundle_format,
+        env.host,
+        env.os,
+        env.arch
+    );
+    for (role, model) in &env.models {
+        line.push_str(&format!(", {}={}", role, model));
+    }
+    line.push_str(&format!(", captured {}", env.captured_at));
+    line
+}
+
 fn format_string_list(items: &[String]) -> String {
     if items.is_empty() {
         "(empty list)".to_string()
@@ -623,6 +1237,77 @@ mod tests {
     use super::*;
     use strata_executor::VersionedValue;
 
+    #[test]
+    fn test_format_bundle_validated_environment() {
+        let mut result = strata_executor::BundleValidateResult {
+            branch_id: "exp".to_string(),
+            format_version: 2,
+            entry_count: 3,
+            checksums_valid: true,
+            environment: None,
+        };
+        let plain = format_output(&Output::BundleValidated(result.clone()), OutputMode::Human);
+        assert!(!plain.contains("environment"));
+
+        result.environment = Some(Box::new(EnvironmentInfo {
+            strata_version: "0.12.0".to_string(),
+            wal_format: 2,
+            snapshot_format: 2,
+            bundle_format: 2,
+            host: "lab-1".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            models: [("embed".to_string(), "minilm-l6-v2".to_string())].into(),
+            captured_at: "2025-01-24T10:00:00Z".to_string(),
+        }));
+        let out = format_output(&Output::BundleValidated(result), OutputMode::Human);
+        assert!(out.ends_with(
+            "environment: strata 0.12.0 (wal v2, snapshot v2, bundle v2) on \"lab-1\" \
+             linux/x86_64, embed=minilm-l6-v2, captured 2025-01-24T10:00:00Z"
+        ));
+    }
+
+    #[test]
+    fn test_format_schema_log() {
+        let changes: Vec<SchemaChange> = serde_json::from_str(
+            r#"[
+                {"timestamp": 10, "space": "default", "collection": "docs", "op": "collection_created",
+                 "dimension": 384, "metric": "Cosine", "dtype": "F32"},
+                {"timestamp": 20, "space": "default", "collection": "docs", "op": "index_rebuilt",
+                 "metric": "Euclidean", "index": "hnsw"},
+                {"timestamp": 30, "space": "default", "collection": "docs", "op": "collection_dropped",
+                 "dimension": 384}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format_schema_log(&changes, OutputMode::Human),
+            "1) 10 \"docs\" in space \"default\": created (dim: 384, metric: cosine, dtype: F32)\n\
+             2) 20 \"docs\" in space \"default\": reindexed (metric: euclidean, index: hnsw)\n\
+             3) 30 \"docs\" in space \"default\": dropped (dim: 384)"
+        );
+        assert_eq!(
+            format_schema_log(&changes[2..], OutputMode::Raw),
+            "30\tdefault\tdocs\tdropped (dim: 384)"
+        );
+        assert_eq!(format_schema_log(&[], OutputMode::Human), "(empty list)");
+    }
+
+    #[test]
+    fn test_format_event_line() {
+        let event = VersionedValue {
+            value: Value::Object([("step".to_string(), Value::Int(3))].into()),
+            version: 12,
+            timestamp: 1000,
+        };
+        assert_eq!(
+            format_event_line(&event, "agent.step", OutputMode::Json),
+            r#"{"event_type":"agent.step","payload":{"step":3},"sequence":12,"timestamp":1000}"#
+        );
+        assert!(format_event_line(&event, "agent.step", OutputMode::Human)
+            .ends_with("(seq 12, ts=1000)"));
+    }
+
     #[test]
     fn test_format_unit() {
         assert_eq!(format_output(&Output::Unit, OutputMode::Human), "OK");
@@ -692,4 +1377,39 @@ mod tests {
         };
         assert_eq!(format_output(&pong, OutputMode::Human), "PONG 0.5.2");
     }
+
+    #[test]
+    fn test_format_text_search_results() {
+        let hits = Output::TextSearchResults(vec![strata_executor::TextSearchHit {
+            entity: "note".to_string(),
+            primitive: "kv".to_string(),
+            score: 1.25,
+            rank: 1,
+            snippet: "the disk is failing".to_string(),
+            highlights: vec![(4, 8)],
+        }]);
+        assert_eq!(
+            format_output(&hits, OutputMode::Human),
+            "1) \"note\" [kv] (score: 1.250) - the *disk* is failing"
+        );
+        assert_eq!(format_output(&hits, OutputMode::Raw), "note\tkv\t1.25");
+    }
+
+    #[test]
+    fn test_format_vector_batch_matches() {
+        let hit = |key: &str, score| strata_executor::VectorMatch {
+            key: key.to_string(),
+            score,
+            metadata: None,
+        };
+        let batches = Output::VectorBatchMatches(vec![vec![hit("a", 0.9), hit("b", 0.5)], vec![]]);
+        assert_eq!(
+            format_output(&batches, OutputMode::Human),
+            "query 1:\n  1) \"a\" (score: 0.900)\n  2) \"b\" (score: 0.500)\nquery 2:\n  (empty list)"
+        );
+        assert_eq!(
+            format_output(&batches, OutputMode::Raw),
+            "a\t0.9\nb\t0.5\n\n"
+        );
+    }
 }
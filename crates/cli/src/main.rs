@@ -4,12 +4,18 @@
 //! - **Shell mode**: `strata [flags] COMMAND` — single command, exit
 //! - **REPL mode**: `strata [flags]` — interactive prompt (if stdin is TTY)
 //! - **Pipe mode**: `echo "kv put k v" | strata` — line-by-line from stdin
+//! - **Script mode**: `strata --script setup.strata` — a file with variables
+//!   and conditionals
 
+mod bench;
 mod commands;
 mod format;
 mod parse;
 mod repl;
+mod script;
 mod state;
+mod tail;
+mod transfer;
 mod value;
 
 use std::io::IsTerminal;
@@ -19,10 +25,11 @@ use strata_executor::{AccessMode, Command, OpenOptions, Output, Strata};
 
 use commands::build_cli;
 use format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_bench_report, format_diff, format_error, format_fork_info, format_info,
+    format_merge_info, format_multi_output, format_multi_versioned_output, format_output,
+    format_schema_log, format_versioned_output, OutputMode,
 };
-use parse::{matches_to_action, BranchOp, CliAction, Primitive};
+use parse::{matches_to_action, parse_bench, BranchOp, CliAction, Primitive};
 use state::SessionState;
 
 fn main() {
@@ -44,6 +51,11 @@ fn main() {
         OutputMode::Human
     };
 
+    // `bench` may open its own temporary database.
+    if let Some(bench_matches) = matches.subcommand_matches("bench") {
+        process::exit(run_bench(&matches, bench_matches, output_mode));
+    }
+
     // Auto-download model files when --auto-embed is set (best-effort).
     #[cfg(feature = "embed")]
     if matches.get_flag("auto-embed") {
@@ -79,13 +91,20 @@ fn main() {
     let mut state = SessionState::new(db, initial_branch, initial_space);
 
     // Dispatch mode
-    if matches.subcommand().is_some() {
+    if let Some(path) = matches.get_one::<String>("script") {
+        if matches.subcommand().is_some() {
+            eprintln!("(error) --script cannot be combined with a command");
+            process::exit(2);
+        }
+        process::exit(script::run_file(path, &mut state, output_mode));
+    } else if matches.subcommand().is_some() {
         // Shell mode: parse, execute, format, exit
         let exit_code = run_shell_mode(&matches, &mut state, output_mode);
         process::exit(exit_code);
     } else if std::io::stdin().is_terminal() {
         // REPL mode
-        repl::run_repl(&mut state, output_mode);
+        let complete_keys = !matches.get_flag("no-key-completion");
+        repl::run_repl(&mut state, output_mode, complete_keys);
     } else {
         // Pipe mode
         let exit_code = repl::run_pipe(&mut state, output_mode);
@@ -97,6 +116,7 @@ fn open_database(matches: &clap::ArgMatches) -> Result<Strata, String> {
     let read_only = matches.get_flag("read-only");
     let use_cache = matches.get_flag("cache");
     let auto_embed = matches.get_flag("auto-embed");
+    let force_unlock = matches.get_flag("force-unlock");
 
     if use_cache {
         Strata::cache().map_err(|e| format!("Failed to open cache database: {}", e))
@@ -114,6 +134,9 @@ fn open_database(matches: &clap::ArgMatches) -> Result<Strata, String> {
         if auto_embed {
             opts = opts.auto_embed(true);
         }
+        if force_unlock {
+            opts = opts.force_unlock(true);
+        }
 
         Strata::open_with(path, opts)
             .map_err(|e| format!("Failed to open database: {}", e))
@@ -173,6 +196,16 @@ fn run_shell_mode(
                     1
                 }
             },
+            BranchOp::SchemaLog { branch } => match state.schema_log(branch.as_deref()) {
+                Ok(changes) => {
+                    println!("{}", format_schema_log(&changes, mode));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    1
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             eprintln!("(error) Meta-commands are only available in REPL mode");
@@ -348,6 +381,96 @@ fn run_shell_mode(
                 1
             }
         },
+        Ok(CliAction::Transfer(op)) => match transfer::run(state, op) {
+            Ok(summary) => {
+                if let Some(summary) = summary {
+                    println!("{}", summary);
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("(error) {}", e);
+                1
+            }
+        },
+        Ok(CliAction::Info) => match state.info() {
+            Ok((info, stats)) => {
+                println!("{}", format_info(&info, &stats, mode));
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", format_error(&e, mode));
+                1
+            }
+        },
+        Ok(CliAction::Tail(op)) => match tail::run(state, op, mode) {
+            Ok(()) => 0,
+            Err(e) => {
+                eprintln!("(error) {}", e);
+                1
+            }
+        },
+        Err(e) => {
+            eprintln!("(error) {}", e);
+            1
+        }
+    }
+}
+
+fn run_bench(
+    matches: &clap::ArgMatches,
+    bench_matches: &clap::ArgMatches,
+    mode: OutputMode,
+) -> i32 {
+    let existing = matches.get_one::<String>("db").is_some() || matches.get_flag("cache");
+    let config = match parse_bench(bench_matches, existing) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("(error) {}", e);
+            return 1;
+        }
+    };
+
+    let scratch = match &config.target {
+        bench::Target::Existing => None,
+        bench::Target::Fresh { durability } => match bench::ScratchDir::create(durability) {
+            Ok(dir) => Some(dir),
+            Err(e) => {
+                eprintln!("(error) {}", e);
+                return 1;
+            }
+        },
+    };
+    let opened = match &scratch {
+        Some(dir) => {
+            Strata::open(dir.path()).map_err(|e| format!("Failed to open database: {}", e))
+        }
+        None => open_database(matches),
+    };
+    let db = match opened {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 1;
+        }
+    };
+
+    eprintln!(
+        "Running {} with {} thread(s) for {:?}...",
+        config.workload.name(),
+        config.threads,
+        config.duration
+    );
+    let result = bench::run(&db, &config);
+    // Close the database before its temporary directory is removed
+    drop(db);
+    drop(scratch);
+
+    match result {
+        Ok(report) => {
+            println!("{}", format_bench_report(&report, mode));
+            0
+        }
         Err(e) => {
             eprintln!("(error) {}", e);
             1
@@ -6,17 +6,23 @@
 //! - REPL meta-commands → `CliAction::Meta`
 //! - Multi-key operations → `CliAction::MultiPut/MultiGet/MultiDel`
 //! - Pagination → `CliAction::ListAll`
+//! - Bulk import/export → `CliAction::Transfer`
+//! - Event tail → `CliAction::Tail`
 
 use std::io::Read;
+use std::time::Duration;
 
 use clap::ArgMatches;
 use strata_executor::{
-    BranchId, BatchVectorEntry, Command, DistanceMetric, MergeStrategy, MetadataFilter, TxnOptions,
-    Value,
+    BranchId, BatchVectorEntry, Command, DistanceMetric, FusionMethod, ImportConflict, ListEnd,
+    LockMode, MergeStrategy, RetentionPolicy, StorageDtype, TxnOptions, Value, VectorIndexType,
 };
 
+use crate::bench::{parse_duration, BenchConfig, Target, Workload};
 use crate::state::SessionState;
-use crate::value::{parse_json_value, parse_value, parse_vector};
+use crate::tail::TailOp;
+use crate::transfer::{Dataset, Format, TransferOp};
+use crate::value::{parse_filters, parse_json_value, parse_value, parse_vector, parse_vectors};
 
 /// The result of parsing user input.
 #[allow(dead_code)]
@@ -58,6 +64,12 @@ pub enum CliAction {
         command: Command,
         with_version: bool,
     },
+    /// Bulk import or export of a primitive's data.
+    Transfer(TransferOp),
+    /// Print, and optionally follow, the latest events of a type.
+    Tail(TailOp),
+    /// Database info together with storage statistics.
+    Info,
 }
 
 /// Primitive type for ListAll pagination.
@@ -73,6 +85,7 @@ pub enum BranchOp {
     Fork { destination: String },
     Diff { branch_a: String, branch_b: String },
     Merge { source: String, strategy: MergeStrategy },
+    SchemaLog { branch: Option<String> },
 }
 
 /// REPL meta-commands.
@@ -118,6 +131,7 @@ pub fn matches_to_action(matches: &ArgMatches, state: &SessionState) -> Result<C
         "json" => parse_json(sub_matches, state),
         "event" => parse_event(sub_matches, state),
         "state" => parse_state(sub_matches, state),
+        "list" => parse_list(sub_matches, state),
         "vector" => parse_vector_cmd(sub_matches, state),
         "branch" => parse_branch(sub_matches, state),
         "space" => parse_space(sub_matches, state),
@@ -126,9 +140,17 @@ pub fn matches_to_action(matches: &ArgMatches, state: &SessionState) -> Result<C
         "rollback" => Ok(CliAction::Execute(Command::TxnRollback)),
         "txn" => parse_txn(sub_matches),
         "ping" => Ok(CliAction::Execute(Command::Ping)),
-        "info" => Ok(CliAction::Execute(Command::Info)),
+        "info" => Ok(CliAction::Info),
         "flush" => Ok(CliAction::Execute(Command::Flush)),
         "compact" => Ok(CliAction::Execute(Command::Compact)),
+        "stats" => Ok(CliAction::Execute(Command::Stats)),
+        "latency" => {
+            if sub_matches.get_flag("reset") {
+                Ok(CliAction::Execute(Command::LatencyReset))
+            } else {
+                Ok(CliAction::Execute(Command::LatencyReport))
+            }
+        }
         "search" => parse_search(sub_matches, state),
         other => Err(format!("Unknown command: {}", other)),
     }
@@ -150,6 +172,21 @@ fn space(state: &SessionState) -> Option<String> {
 // File reading helper
 // =========================================================================
 
+/// Parse the `amount` argument of `incr`/`decr`, negated for `decr`.
+fn parse_amount(m: &ArgMatches, negate: bool) -> Result<Value, String> {
+    let amount = m.get_one::<String>("amount").unwrap();
+    match (parse_value(amount), negate) {
+        (Value::Int(i), false) => Ok(Value::Int(i)),
+        (Value::Int(i), true) => i
+            .checked_neg()
+            .map(Value::Int)
+            .ok_or_else(|| format!("Cannot decrement by {}", i)),
+        (Value::Float(f), false) => Ok(Value::Float(f)),
+        (Value::Float(f), true) => Ok(Value::Float(-f)),
+        _ => Err(format!("Amount must be a number, got '{}'", amount)),
+    }
+}
+
 /// Read a value from a file or stdin.
 ///
 /// If `source` is "-", reads from stdin.
@@ -168,7 +205,9 @@ fn read_value_from_source(source: &str) -> Result<Value, String> {
 
     // Try JSON first
     if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-        return Ok(Value::from(json));
+        if let Ok(value) = Value::try_from(&json) {
+            return Ok(value);
+        }
     }
 
     // Fall back to string (trimmed)
@@ -193,7 +232,7 @@ fn read_json_from_source(source: &str) -> Result<Value, String> {
 
     let json: serde_json::Value =
         serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in file: {}", e))?;
-    Ok(Value::from(json))
+    Value::try_from(&json).map_err(|e| format!("Invalid JSON in file: {}", e))
 }
 
 // =========================================================================
@@ -345,17 +384,123 @@ fn parse_kv(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, Str
         }
         "history" => {
             let key = m.get_one::<String>("key").unwrap().clone();
-            Ok(CliAction::Execute(Command::KvGetv {
+            let limit = m
+                .get_one::<String>("limit")
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid limit: {}", e))?;
+            Ok(CliAction::Execute(Command::KvHistory {
                 branch: branch(state),
                 space: space(state),
                 key,
-                as_of: None,
+                limit,
             }))
         }
+        "history-policy" => {
+            let prefix = m.get_one::<String>("prefix").unwrap().clone();
+            let policy = if let Some(n) = m.get_one::<String>("max-len") {
+                RetentionPolicy::MaxLen(
+                    n.parse::<u64>()
+                        .map_err(|e| format!("Invalid max-len: {}", e))?,
+                )
+            } else if let Some(secs) = m.get_one::<String>("max-age") {
+                RetentionPolicy::MaxAgeSecs(
+                    secs.parse::<u64>()
+                        .map_err(|e| format!("Invalid max-age: {}", e))?,
+                )
+            } else {
+                RetentionPolicy::KeepAll
+            };
+            Ok(CliAction::Execute(Command::KvSetHistoryPolicy {
+                branch: branch(state),
+                space: space(state),
+                prefix,
+                policy,
+            }))
+        }
+        "incr" | "decr" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            Ok(CliAction::Execute(Command::KvIncr {
+                branch: branch(state),
+                space: space(state),
+                key,
+                delta: parse_amount(m, sub == "decr")?,
+            }))
+        }
+        "export" => parse_export(m, Dataset::Kv),
+        "import" => parse_import(m, Dataset::Kv),
         other => Err(format!("Unknown kv subcommand: {}", other)),
     }
 }
 
+fn parse_export(m: &ArgMatches, dataset: Dataset) -> Result<CliAction, String> {
+    let format = match m.get_one::<String>("format") {
+        Some(f) => Format::parse(f)?,
+        None => Format::Jsonl,
+    };
+    Ok(CliAction::Transfer(TransferOp::Export {
+        dataset,
+        format,
+        prefix: m.try_get_one::<String>("prefix").ok().flatten().cloned(),
+        output: m.get_one::<String>("output").cloned(),
+    }))
+}
+
+fn parse_import(m: &ArgMatches, dataset: Dataset) -> Result<CliAction, String> {
+    let input = m.get_one::<String>("file").unwrap().clone();
+    let format = match m.get_one::<String>("format") {
+        Some(f) => Format::parse(f)?,
+        None => Format::from_path(&input),
+    };
+    Ok(CliAction::Transfer(TransferOp::Import {
+        dataset,
+        format,
+        input,
+    }))
+}
+
+// =========================================================================
+// Bench
+// =========================================================================
+
+/// Convert `bench` arguments into a benchmark configuration.
+///
+/// `existing` is set when `--db` or `--cache` chose the database to run
+/// against; otherwise the run gets a temporary database.
+pub fn parse_bench(m: &ArgMatches, existing: bool) -> Result<BenchConfig, String> {
+    let count = |name: &str, default: usize| -> Result<usize, String> {
+        m.get_one::<String>(name)
+            .map(|s| s.parse::<usize>())
+            .transpose()
+            .map_err(|e| format!("Invalid {}: {}", name, e))
+            .map(|n| n.unwrap_or(default))
+    };
+    let durability = m.get_one::<String>("durability");
+    let target = match (existing, durability) {
+        (true, Some(_)) => {
+            return Err(
+                "--durability applies to the temporary database; drop --db/--cache".to_string(),
+            )
+        }
+        (true, None) => Target::Existing,
+        (false, durability) => Target::Fresh {
+            durability: durability.map_or("standard", |d| d.as_str()).to_string(),
+        },
+    };
+    Ok(BenchConfig {
+        workload: Workload::parse(m.get_one::<String>("workload").unwrap())?,
+        target,
+        threads: count("threads", 1)?,
+        duration: match m.get_one::<String>("duration") {
+            Some(d) => parse_duration(d)?,
+            None => std::time::Duration::from_secs(10),
+        },
+        keys: count("keys", 10_000)?,
+        value_size: count("value-size", 100)?,
+        dimension: count("dim", 128)?,
+    })
+}
+
 // =========================================================================
 // JSON
 // =========================================================================
@@ -452,6 +597,146 @@ fn parse_json(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, S
                 as_of: None,
             }))
         }
+        "schema" => {
+            let prefix = m.get_one::<String>("prefix").unwrap().clone();
+            let schema = if m.get_flag("clear") {
+                Value::Null
+            } else if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else if let Some(raw) = m.get_one::<String>("schema") {
+                parse_json_value(raw)?
+            } else {
+                return Ok(CliAction::Execute(Command::JsonGetSchema {
+                    branch: branch(state),
+                    space: space(state),
+                    prefix,
+                }));
+            };
+            Ok(CliAction::Execute(Command::JsonSetSchema {
+                branch: branch(state),
+                space: space(state),
+                prefix,
+                schema,
+            }))
+        }
+        "get-paths" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let paths = m.get_many::<String>("paths").unwrap().cloned().collect();
+            Ok(CliAction::Execute(Command::JsonGetPaths {
+                branch: branch(state),
+                space: space(state),
+                key,
+                paths,
+            }))
+        }
+        "merge" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let patch = if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else {
+                parse_json_value(m.get_one::<String>("patch").unwrap())?
+            };
+            Ok(CliAction::Execute(Command::JsonMerge {
+                branch: branch(state),
+                space: space(state),
+                key,
+                patch,
+            }))
+        }
+        "patch" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let ops = if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else {
+                parse_json_value(m.get_one::<String>("ops").unwrap())?
+            };
+            Ok(CliAction::Execute(Command::JsonPatch {
+                branch: branch(state),
+                space: space(state),
+                key,
+                ops,
+            }))
+        }
+        "arr-append" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let path = m.get_one::<String>("path").unwrap().clone();
+            let value = if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else {
+                parse_json_value(m.get_one::<String>("value").unwrap())?
+            };
+            Ok(CliAction::Execute(Command::JsonArrayAppend {
+                branch: branch(state),
+                space: space(state),
+                key,
+                path,
+                value,
+            }))
+        }
+        "arr-insert" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let path = m.get_one::<String>("path").unwrap().clone();
+            let index = m
+                .get_one::<String>("index")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid index: {}", e))?;
+            let value = if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else {
+                parse_json_value(m.get_one::<String>("value").unwrap())?
+            };
+            Ok(CliAction::Execute(Command::JsonArrayInsert {
+                branch: branch(state),
+                space: space(state),
+                key,
+                path,
+                index,
+                value,
+            }))
+        }
+        "arr-remove" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let path = m.get_one::<String>("path").unwrap().clone();
+            let index = m
+                .get_one::<String>("index")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid index: {}", e))?;
+            Ok(CliAction::Execute(Command::JsonArrayRemove {
+                branch: branch(state),
+                space: space(state),
+                key,
+                path,
+                index,
+            }))
+        }
+        "arr-len" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let path = m.get_one::<String>("path").unwrap().clone();
+            Ok(CliAction::Execute(Command::JsonArrayLen {
+                branch: branch(state),
+                space: space(state),
+                key,
+                path,
+            }))
+        }
+        "validate" => {
+            let key = m.get_one::<String>("key").unwrap().clone();
+            let value = if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else {
+                parse_json_value(m.get_one::<String>("value").unwrap())?
+            };
+            Ok(CliAction::Execute(Command::JsonValidate {
+                branch: branch(state),
+                space: space(state),
+                key,
+                value,
+            }))
+        }
+        "export" => parse_export(m, Dataset::Json),
+        "import" => parse_import(m, Dataset::Json),
         other => Err(format!("Unknown json subcommand: {}", other)),
     }
 }
@@ -518,6 +803,119 @@ fn parse_event(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
             branch: branch(state),
             space: space(state),
         })),
+        "retention" => {
+            let event_type = m.get_one::<String>("type").unwrap().clone();
+            let policy = if let Some(n) = m.get_one::<String>("max-len") {
+                RetentionPolicy::MaxLen(
+                    n.parse::<u64>()
+                        .map_err(|e| format!("Invalid max-len: {}", e))?,
+                )
+            } else if let Some(secs) = m.get_one::<String>("max-age") {
+                RetentionPolicy::MaxAgeSecs(
+                    secs.parse::<u64>()
+                        .map_err(|e| format!("Invalid max-age: {}", e))?,
+                )
+            } else {
+                RetentionPolicy::KeepAll
+            };
+            Ok(CliAction::Execute(Command::EventSetRetention {
+                branch: branch(state),
+                space: space(state),
+                event_type,
+                policy,
+            }))
+        }
+        "schema" => {
+            let event_type = m.get_one::<String>("type").unwrap().clone();
+            let schema = if m.get_flag("clear") {
+                Value::Null
+            } else if let Some(file_path) = m.get_one::<String>("file") {
+                read_json_from_source(file_path)?
+            } else if let Some(raw) = m.get_one::<String>("schema") {
+                parse_json_value(raw)?
+            } else {
+                return Ok(CliAction::Execute(Command::EventGetSchema {
+                    branch: branch(state),
+                    space: space(state),
+                    event_type,
+                }));
+            };
+            Ok(CliAction::Execute(Command::EventSetSchema {
+                branch: branch(state),
+                space: space(state),
+                event_type,
+                schema,
+            }))
+        }
+        "group-create" => Ok(CliAction::Execute(Command::EventCreateGroup {
+            branch: branch(state),
+            space: space(state),
+            event_type: m.get_one::<String>("type").unwrap().clone(),
+            group: m.get_one::<String>("group").unwrap().clone(),
+        })),
+        "read-group" => {
+            let count = m
+                .get_one::<String>("count")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid count: {}", e))?;
+            Ok(CliAction::Execute(Command::EventReadGroup {
+                branch: branch(state),
+                space: space(state),
+                event_type: m.get_one::<String>("type").unwrap().clone(),
+                group: m.get_one::<String>("group").unwrap().clone(),
+                consumer: m.get_one::<String>("consumer").unwrap().clone(),
+                count,
+            }))
+        }
+        "ack" => {
+            let sequences = m
+                .get_many::<String>("sequences")
+                .unwrap()
+                .map(|s| s.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid sequence: {}", e))?;
+            Ok(CliAction::Execute(Command::EventAck {
+                branch: branch(state),
+                space: space(state),
+                event_type: m.get_one::<String>("type").unwrap().clone(),
+                group: m.get_one::<String>("group").unwrap().clone(),
+                sequences,
+            }))
+        }
+        "pending" => Ok(CliAction::Execute(Command::EventPending {
+            branch: branch(state),
+            space: space(state),
+            event_type: m.get_one::<String>("type").unwrap().clone(),
+            group: m.get_one::<String>("group").unwrap().clone(),
+        })),
+        "tail" => {
+            let count = m
+                .get_one::<String>("lines")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid lines: {}", e))?;
+            let interval = m
+                .get_one::<String>("interval")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid interval: {}", e))?;
+            if interval == 0 {
+                return Err("Interval must be at least 1 ms".to_string());
+            }
+            Ok(CliAction::Tail(TailOp {
+                event_type: m.get_one::<String>("type").unwrap().clone(),
+                count,
+                follow: m.get_flag("follow"),
+                interval: Duration::from_millis(interval),
+            }))
+        }
+        "export" => parse_export(
+            m,
+            Dataset::Events {
+                event_type: m.get_one::<String>("type").unwrap().clone(),
+            },
+        ),
         other => Err(format!("Unknown event subcommand: {}", other)),
     }
 }
@@ -550,11 +948,21 @@ fn parse_state(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
             let cell = m.get_one::<String>("cell").unwrap().clone();
             let with_version = m.get_flag("with-version");
 
-            let cmd = Command::StateGet {
-                branch: branch(state),
-                space: space(state),
-                cell,
-                as_of: None,
+            let cmd = match m.get_one::<String>("at-version") {
+                Some(raw) => Command::StateGetVersion {
+                    branch: branch(state),
+                    space: space(state),
+                    cell,
+                    version: raw
+                        .parse::<u64>()
+                        .map_err(|e| format!("Invalid version: {}", e))?,
+                },
+                None => Command::StateGet {
+                    branch: branch(state),
+                    space: space(state),
+                    cell,
+                    as_of: None,
+                },
             };
 
             if with_version {
@@ -629,17 +1037,109 @@ fn parse_state(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
         }
         "history" => {
             let cell = m.get_one::<String>("cell").unwrap().clone();
+            let limit = m
+                .get_one::<String>("limit")
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid limit: {}", e))?;
             Ok(CliAction::Execute(Command::StateGetv {
                 branch: branch(state),
                 space: space(state),
                 cell,
                 as_of: None,
+                limit,
+            }))
+        }
+        "incr" | "decr" => {
+            let cell = m.get_one::<String>("cell").unwrap().clone();
+            Ok(CliAction::Execute(Command::StateIncr {
+                branch: branch(state),
+                space: space(state),
+                cell,
+                delta: parse_amount(m, sub == "decr")?,
             }))
         }
         other => Err(format!("Unknown state subcommand: {}", other)),
     }
 }
 
+// =========================================================================
+// List
+// =========================================================================
+
+fn parse_list(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
+    let (sub, m) = matches.subcommand().ok_or("No list subcommand")?;
+    match sub {
+        "lpush" | "rpush" => {
+            let list = m.get_one::<String>("list").unwrap().clone();
+            let values = m
+                .get_many::<String>("values")
+                .unwrap()
+                .map(|raw| parse_value(raw))
+                .collect();
+            Ok(CliAction::Execute(Command::ListPush {
+                branch: branch(state),
+                space: space(state),
+                list,
+                end: if sub == "lpush" {
+                    ListEnd::Left
+                } else {
+                    ListEnd::Right
+                },
+                values,
+            }))
+        }
+        "lpop" | "rpop" => {
+            let list = m.get_one::<String>("list").unwrap().clone();
+            let timeout_ms = m
+                .get_one::<String>("timeout")
+                .map(|s| s.parse::<u64>())
+                .transpose()
+                .map_err(|e| format!("Invalid timeout: {}", e))?;
+            Ok(CliAction::Execute(Command::ListPop {
+                branch: branch(state),
+                space: space(state),
+                list,
+                end: if sub == "lpop" {
+                    ListEnd::Left
+                } else {
+                    ListEnd::Right
+                },
+                timeout_ms,
+            }))
+        }
+        "range" => {
+            let list = m.get_one::<String>("list").unwrap().clone();
+            let start = m
+                .get_one::<String>("start")
+                .unwrap()
+                .parse::<i64>()
+                .map_err(|e| format!("Invalid start: {}", e))?;
+            let stop = m
+                .get_one::<String>("stop")
+                .unwrap()
+                .parse::<i64>()
+                .map_err(|e| format!("Invalid stop: {}", e))?;
+            Ok(CliAction::Execute(Command::ListRange {
+                branch: branch(state),
+                space: space(state),
+                list,
+                start,
+                stop,
+            }))
+        }
+        "len" => {
+            let list = m.get_one::<String>("list").unwrap().clone();
+            Ok(CliAction::Execute(Command::ListLen {
+                branch: branch(state),
+                space: space(state),
+                list,
+            }))
+        }
+        other => Err(format!("Unknown list subcommand: {}", other)),
+    }
+}
+
 // =========================================================================
 // Vector
 // =========================================================================
@@ -653,6 +1153,23 @@ fn parse_metric(s: &str) -> Result<DistanceMetric, String> {
     }
 }
 
+fn parse_storage_dtype(s: &str) -> Result<StorageDtype, String> {
+    match s.to_lowercase().as_str() {
+        "f32" => Ok(StorageDtype::F32),
+        "f16" => Ok(StorageDtype::F16),
+        "int8" | "i8" => Ok(StorageDtype::Int8),
+        other => Err(format!("Unknown dtype: {}. Use f32, f16, or int8", other)),
+    }
+}
+
+fn parse_index_type(s: &str) -> Result<VectorIndexType, String> {
+    match s.to_lowercase().as_str() {
+        "brute_force" | "bruteforce" | "flat" => Ok(VectorIndexType::BruteForce),
+        "hnsw" => Ok(VectorIndexType::Hnsw),
+        other => Err(format!("Unknown index: {}. Use brute_force or hnsw", other)),
+    }
+}
+
 fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
     let (sub, m) = matches.subcommand().ok_or("No vector subcommand")?;
     match sub {
@@ -664,6 +1181,7 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 .get_one::<String>("metadata")
                 .map(|s| parse_json_value(s))
                 .transpose()?;
+            let namespace = m.get_one::<String>("namespace").cloned();
             Ok(CliAction::Execute(Command::VectorUpsert {
                 branch: branch(state),
                 space: space(state),
@@ -671,6 +1189,7 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 key,
                 vector,
                 metadata,
+                namespace,
             }))
         }
         "get" => {
@@ -708,10 +1227,9 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 .transpose()?;
             let filter = m
                 .get_one::<String>("filter")
-                .map(|s| -> Result<Vec<MetadataFilter>, String> {
-                    serde_json::from_str(s).map_err(|e| format!("Invalid filter JSON: {}", e))
-                })
+                .map(|s| parse_filters(s))
                 .transpose()?;
+            let namespace = m.get_one::<String>("namespace").cloned();
             Ok(CliAction::Execute(Command::VectorSearch {
                 branch: branch(state),
                 space: space(state),
@@ -721,6 +1239,28 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 filter,
                 metric,
                 as_of: None,
+                namespace,
+            }))
+        }
+        "search-batch" => {
+            let collection = m.get_one::<String>("collection").unwrap().clone();
+            let queries = parse_vectors(m.get_one::<String>("queries").unwrap())?;
+            let k = m
+                .get_one::<String>("k")
+                .unwrap()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid k: {}", e))?;
+            let filter = m
+                .get_one::<String>("filter")
+                .map(|s| parse_filters(s))
+                .transpose()?;
+            Ok(CliAction::Execute(Command::VectorSearchBatch {
+                branch: branch(state),
+                space: space(state),
+                collection,
+                queries,
+                k,
+                filter,
             }))
         }
         "create" => {
@@ -731,12 +1271,35 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
                 .parse::<u64>()
                 .map_err(|e| format!("Invalid dimension: {}", e))?;
             let metric = parse_metric(m.get_one::<String>("metric").unwrap())?;
+            let storage_dtype = m
+                .get_one::<String>("dtype")
+                .map(|s| parse_storage_dtype(s))
+                .transpose()?;
             Ok(CliAction::Execute(Command::VectorCreateCollection {
                 branch: branch(state),
                 space: space(state),
                 collection,
                 dimension,
                 metric,
+                storage_dtype,
+            }))
+        }
+        "reindex" => {
+            let collection = m.get_one::<String>("name").unwrap().clone();
+            let metric = m
+                .get_one::<String>("metric")
+                .map(|s| parse_metric(s))
+                .transpose()?;
+            let index_type = m
+                .get_one::<String>("index")
+                .map(|s| parse_index_type(s))
+                .transpose()?;
+            Ok(CliAction::Execute(Command::VectorReindex {
+                branch: branch(state),
+                space: space(state),
+                collection,
+                metric,
+                index_type,
             }))
         }
         "drop" => {
@@ -764,11 +1327,13 @@ fn parse_vector_cmd(matches: &ArgMatches, state: &SessionState) -> Result<CliAct
             let raw = m.get_one::<String>("json").unwrap();
             let entries: Vec<BatchVectorEntry> =
                 serde_json::from_str(raw).map_err(|e| format!("Invalid batch JSON: {}", e))?;
+            let namespace = m.get_one::<String>("namespace").cloned();
             Ok(CliAction::Execute(Command::VectorBatchUpsert {
                 branch: branch(state),
                 space: space(state),
                 collection,
                 entries,
+                namespace,
             }))
         }
         other => Err(format!("Unknown vector subcommand: {}", other)),
@@ -813,6 +1378,12 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
                 branch: BranchId::from(name),
             }))
         }
+        "stats" => {
+            let name = m.get_one::<String>("name").unwrap().clone();
+            Ok(CliAction::Execute(Command::BranchStats {
+                branch: BranchId::from(name),
+            }))
+        }
         "del" => {
             let name = m.get_one::<String>("name").unwrap().clone();
             Ok(CliAction::Execute(Command::BranchDelete {
@@ -836,6 +1407,10 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
             };
             Ok(CliAction::BranchOp(BranchOp::Merge { source, strategy }))
         }
+        "schema-log" => {
+            let branch = m.get_one::<String>("name").cloned();
+            Ok(CliAction::BranchOp(BranchOp::SchemaLog { branch }))
+        }
         "export" => {
             let branch_id = m.get_one::<String>("branch").unwrap().clone();
             let path = m.get_one::<String>("path").unwrap().clone();
@@ -843,12 +1418,39 @@ fn parse_branch(matches: &ArgMatches, _state: &SessionState) -> Result<CliAction
         }
         "import" => {
             let path = m.get_one::<String>("path").unwrap().clone();
-            Ok(CliAction::Execute(Command::BranchImport { path }))
+            let as_branch = m.get_one::<String>("as").cloned();
+            Ok(CliAction::Execute(Command::BranchImport {
+                path,
+                as_branch,
+            }))
         }
         "validate" => {
             let path = m.get_one::<String>("path").unwrap().clone();
             Ok(CliAction::Execute(Command::BranchBundleValidate { path }))
         }
+        "export-all" => {
+            let path = m.get_one::<String>("path").unwrap().clone();
+            Ok(CliAction::Execute(Command::DatabaseExport { path }))
+        }
+        "import-all" => {
+            let path = m.get_one::<String>("path").unwrap().clone();
+            let on_conflict = match m.get_one::<String>("on-conflict").unwrap().as_str() {
+                "fail" => ImportConflict::Fail,
+                "skip" => ImportConflict::Skip,
+                "rename" => ImportConflict::Rename,
+                "overwrite" => ImportConflict::Overwrite,
+                other => {
+                    return Err(format!(
+                        "Invalid conflict policy: {} (expected fail, skip, rename, or overwrite)",
+                        other
+                    ))
+                }
+            };
+            Ok(CliAction::Execute(Command::DatabaseImport {
+                path,
+                on_conflict,
+            }))
+        }
         other => Err(format!("Unknown branch subcommand: {}", other)),
     }
 }
@@ -896,9 +1498,17 @@ fn parse_space(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
 
 fn parse_begin(matches: &ArgMatches, state: &SessionState) -> Result<CliAction, String> {
     let read_only = matches.get_flag("txn-read-only");
+    let lock_mode = if matches.get_flag("txn-pessimistic") {
+        LockMode::Pessimistic
+    } else {
+        LockMode::Optimistic
+    };
     Ok(CliAction::Execute(Command::TxnBegin {
         branch: branch(state),
-        options: Some(TxnOptions { read_only }),
+        options: Some(TxnOptions {
+            read_only,
+            lock_mode,
+        }),
     }))
 }
 
@@ -922,6 +1532,46 @@ fn parse_search(matches: &ArgMatches, state: &SessionState) -> Result<CliAction,
         .map(|s| s.parse::<u64>())
         .transpose()
         .map_err(|e| format!("Invalid k: {}", e))?;
+    if matches.get_flag("text") {
+        return Ok(CliAction::Execute(Command::TextSearch {
+            branch: branch(state),
+            space: space(state),
+            query,
+            k,
+        }));
+    }
+    if matches.get_flag("semantic") {
+        let rerank_top_n = matches
+            .get_one::<String>("rerank")
+            .map(|s| s.parse::<u64>())
+            .transpose()
+            .map_err(|e| format!("Invalid rerank count: {}", e))?;
+        return Ok(CliAction::Execute(Command::SemanticSearch {
+            branch: branch(state),
+            space: space(state),
+            query,
+            k,
+            rerank_top_n,
+        }));
+    }
+    if let Some(collection) = matches.get_one::<String>("hybrid") {
+        let embedding = parse_vector(matches.get_one::<String>("embedding").unwrap())?;
+        let fusion = matches
+            .get_one::<String>("vector-weight")
+            .map(|s| s.parse::<f32>())
+            .transpose()
+            .map_err(|e| format!("Invalid vector weight: {}", e))?
+            .map(|vector_weight| FusionMethod::Weighted { vector_weight });
+        return Ok(CliAction::Execute(Command::HybridSearch {
+            branch: branch(state),
+            space: space(state),
+            collection: collection.clone(),
+            query,
+            embedding,
+            k,
+            fusion,
+        }));
+    }
     let primitives = matches
         .get_one::<String>("primitives")
         .map(|s| s.split(',').map(|p| p.trim().to_string()).collect());
@@ -2,7 +2,16 @@
 //!
 //! Interactive mode: prompt, meta-commands, history, TAB completion.
 //! Pipe mode: read lines from stdin, execute each.
-
+//!
+//! History is appended to `~/.strata_history` after every command, so it
+//! survives a killed session and is shared between concurrent ones. TAB
+//! completes commands, branch and space names and, unless disabled, the
+//! keys, state cells and vector collections of the current branch and space
+//! (at most [`COMPLETION_PREFETCH_LIMIT`] of each, fetched before every
+//! prompt). Event types cannot be listed, so those used in past commands are
+//! offered instead.
+
+use std::collections::BTreeSet;
 use std::io::{self, BufRead};
 
 use rustyline::completion::{Completer, Pair};
@@ -12,36 +21,48 @@ use rustyline::hint::Hinter;
 use rustyline::validate::Validator;
 use rustyline::{CompletionType, Config, Context, Editor, Helper};
 
-use strata_executor::{Command, Output};
+use strata_executor::{BranchId, Command, Output, Result};
 
 use crate::commands::build_repl_cmd;
 use crate::format::{
-    format_diff, format_error, format_fork_info, format_merge_info, format_multi_output,
-    format_multi_versioned_output, format_output, format_versioned_output, OutputMode,
+    format_diff, format_error, format_fork_info, format_info, format_merge_info,
+    format_multi_output, format_multi_versioned_output, format_output, format_schema_log,
+    format_versioned_output, OutputMode,
 };
 use crate::parse::{
     check_meta_command, matches_to_action, BranchOp, CliAction, MetaCommand, Primitive,
 };
 use crate::state::SessionState;
+use crate::tail;
+use crate::transfer;
 
 /// Run the interactive REPL.
-pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
+///
+/// With `complete_keys` off, TAB completion never lists keys, cells or
+/// collections, so no reads are issued between commands.
+pub fn run_repl(state: &mut SessionState, mode: OutputMode, complete_keys: bool) {
     let config = Config::builder()
         .history_ignore_space(true)
         .completion_type(CompletionType::List)
         .build();
 
-    let helper = StrataHelper::new();
+    let mut helper = StrataHelper::new(complete_keys);
     let mut rl: Editor<StrataHelper, _> = Editor::with_config(config).unwrap();
-    rl.set_helper(Some(helper));
 
     // Load history
     let history_path = history_file();
     if let Some(ref path) = history_path {
         let _ = rl.load_history(path);
     }
+    for line in rl.history().iter() {
+        helper.names.note_event_type(line);
+    }
+    rl.set_helper(Some(helper));
 
     loop {
+        if let Some(helper) = rl.helper_mut() {
+            helper.refresh(state);
+        }
         let prompt = state.prompt();
         match rl.readline(&prompt) {
             Ok(line) => {
@@ -50,7 +71,14 @@ pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
                     continue;
                 }
 
-                let _ = rl.add_history_entry(trimmed);
+                if rl.add_history_entry(trimmed).unwrap_or(false) {
+                    if let Some(ref path) = history_path {
+                        let _ = rl.append_history(path);
+                    }
+                }
+                if let Some(helper) = rl.helper_mut() {
+                    helper.names.note_event_type(trimmed);
+                }
 
                 // Check meta-commands first
                 if let Some(meta) = check_meta_command(trimmed) {
@@ -122,9 +150,8 @@ pub fn run_repl(state: &mut SessionState, mode: OutputMode) {
         }
     }
 
-    // Save history
-    if let Some(ref path) = history_path {
-        let _ = rl.save_history(path);
+    if state.rollback_open_transaction() {
+        eprintln!("(txn) Uncommitted transaction rolled back");
     }
 }
 
@@ -171,11 +198,17 @@ pub fn run_pipe(state: &mut SessionState, mode: OutputMode) -> i32 {
         }
     }
 
+    // A script that ends without `commit` loses its writes; don't report success
+    if state.rollback_open_transaction() {
+        eprintln!("(txn) Uncommitted transaction rolled back");
+        exit_code = 1;
+    }
+
     exit_code
 }
 
 /// Execute a parsed action. Returns true on success, false on error.
-fn execute_action(
+pub fn execute_action(
     matches: &clap::ArgMatches,
     state: &mut SessionState,
     mode: OutputMode,
@@ -228,6 +261,16 @@ fn execute_action(
                     false
                 }
             },
+            BranchOp::SchemaLog { branch } => match state.schema_log(branch.as_deref()) {
+                Ok(changes) => {
+                    println!("{}", format_schema_log(&changes, mode));
+                    true
+                }
+                Err(e) => {
+                    eprintln!("{}", format_error(&e, mode));
+                    false
+                }
+            },
         },
         Ok(CliAction::Meta(_)) => {
             // Meta-commands should have been handled before reaching here
@@ -403,6 +446,40 @@ fn execute_action(
                 false
             }
         },
+        Ok(CliAction::Transfer(op)) => match transfer::run(state, op) {
+            Ok(summary) => {
+                if let Some(summary) = summary {
+                    println!("{}", summary);
+                }
+                true
+            }
+            Err(e) => {
+                eprintln!("(error) {}", e);
+                false
+            }
+        },
+        Ok(CliAction::Info) => match state.info() {
+            Ok((info, stats)) => {
+                println!("{}", format_info(&info, &stats, mode));
+                true
+            }
+            Err(e) => {
+                eprintln!("{}", format_error(&e, mode));
+                false
+            }
+        },
+        Ok(CliAction::Tail(op)) if op.follow => {
+            // Interrupting the poll loop would end the whole shell
+            eprintln!("(error) event tail --follow is only available from the command line");
+            false
+        }
+        Ok(CliAction::Tail(op)) => match tail::run(state, op, mode) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("(error) {}", e);
+                false
+            }
+        },
         Err(e) => {
             eprintln!("(error) {}", e);
             false
@@ -426,9 +503,9 @@ fn print_help(command: Option<&str>) {
         }
     } else {
         println!("Available commands:");
-        println!("  kv          Key-value operations (put, get, del, list, history)");
-        println!("  json        JSON document operations (set, get, del, list, history)");
-        println!("  event       Event log operations (append, get, list, len)");
+        println!("  kv          Key-value operations (put, get, del, list, history, export, ...)");
+        println!("  json        JSON document operations (set, get, del, list, history, ...)");
+        println!("  event       Event log operations (append, get, list, len, export)");
         println!("  state       State cell operations (set, get, del, init, cas, list, history)");
         println!("  vector      Vector store operations (upsert, get, del, search, create, ...)");
         println!("  branch      Branch operations (create, info, list, fork, diff, merge, ...)");
@@ -457,17 +534,44 @@ fn print_help(command: Option<&str>) {
 
 /// Known top-level commands for TAB completion.
 const TOP_LEVEL_COMMANDS: &[&str] = &[
-    "kv", "json", "event", "state", "vector", "branch", "space", "begin", "commit", "rollback",
-    "txn", "ping", "info", "flush", "compact", "search", "use", "help", "quit", "exit", "clear",
+    "kv", "json", "event", "state", "list", "vector", "branch", "space", "begin", "commit",
+    "rollback", "txn", "ping", "info", "flush", "compact", "stats", "latency", "search", "use",
+    "help", "quit", "exit", "clear",
 ];
 
 /// Known subcommands for each top-level command.
 fn subcommands_for(cmd: &str) -> &'static [&'static str] {
     match cmd {
-        "kv" => &["put", "get", "del", "list", "history"],
-        "json" => &["set", "get", "del", "list", "history"],
-        "event" => &["append", "get", "list", "len"],
-        "state" => &["set", "get", "del", "init", "cas", "list", "history"],
+        "kv" => &[
+            "put",
+            "get",
+            "del",
+            "list",
+            "history",
+            "history-policy",
+            "incr",
+            "decr",
+            "export",
+            "import",
+        ],
+        "json" => &["set", "get", "del", "list", "history", "export", "import"],
+        "event" => &[
+            "append",
+            "get",
+            "list",
+            "len",
+            "retention",
+            "group-create",
+            "read-group",
+            "ack",
+            "pending",
+            "tail",
+            "export",
+        ],
+        "state" => &[
+            "set", "get", "del", "init", "cas", "list", "history", "incr", "decr",
+        ],
+        "list" => &["lpush", "rpush", "lpop", "rpop", "range", "len"],
         "vector" => &[
             "upsert",
             "get",
@@ -481,8 +585,19 @@ fn subcommands_for(cmd: &str) -> &'static [&'static str] {
             "batch-upsert",
         ],
         "branch" => &[
-            "create", "info", "get", "list", "exists", "del", "fork", "diff", "merge", "export",
-            "import", "validate",
+            "create",
+            "info",
+            "get",
+            "list",
+            "exists",
+            "del",
+            "fork",
+            "diff",
+            "merge",
+            "schema-log",
+            "export",
+            "import",
+            "validate",
         ],
         "space" => &["list", "create", "del", "exists"],
         "txn" => &["info", "active"],
@@ -490,11 +605,214 @@ fn subcommands_for(cmd: &str) -> &'static [&'static str] {
     }
 }
 
-struct StrataHelper;
+/// Most names of each kind fetched for completion before a prompt.
+const COMPLETION_PREFETCH_LIMIT: usize = 1000;
+
+/// Subcommands of `event` whose first argument is an event type.
+const EVENT_TYPE_SUBCOMMANDS: &[&str] = &[
+    "append",
+    "list",
+    "retention",
+    "group-create",
+    "read-group",
+    "ack",
+    "pending",
+    "tail",
+    "export",
+];
+
+/// Kind of name an argument position takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NameKind {
+    Branch,
+    Space,
+    KvKey,
+    JsonKey,
+    StateCell,
+    Collection,
+    EventType,
+}
+
+/// Kind of name completed after `args`, the words already typed with
+/// flags left out.
+fn arg_kind(args: &[&str]) -> Option<NameKind> {
+    use NameKind::*;
+    match args {
+        ["use"] => Some(Branch),
+        ["use", _] => Some(Space),
+        ["branch", "info" | "get" | "exists" | "del" | "merge" | "schema-log" | "export"] => {
+            Some(Branch)
+        }
+        ["branch", "diff"] | ["branch", "diff", _] => Some(Branch),
+        ["space", "del" | "exists"] => Some(Space),
+        ["kv", "get" | "del", ..] => Some(KvKey),
+        ["kv", "history" | "incr" | "decr"] => Some(KvKey),
+        // `kv put` takes key/value pairs
+        ["kv", "put", rest @ ..] if rest.len() % 2 == 0 => Some(KvKey),
+        ["json", "set" | "get" | "del" | "history"] => Some(JsonKey),
+        ["state", "set" | "get" | "del" | "cas" | "history" | "incr" | "decr"] => Some(StateCell),
+        // Every `vector` subcommand but these starts with a collection
+        ["vector", sub] if !matches!(*sub, "create" | "collections") => Some(Collection),
+        ["event", sub] if EVENT_TYPE_SUBCOMMANDS.contains(sub) => Some(EventType),
+        _ => None,
+    }
+}
+
+/// Names offered by TAB completion.
+#[derive(Default)]
+struct CompletionNames {
+    branches: Vec<String>,
+    spaces: Vec<String>,
+    kv_keys: Vec<String>,
+    json_keys: Vec<String>,
+    state_cells: Vec<String>,
+    collections: Vec<String>,
+    event_types: BTreeSet<String>,
+}
+
+impl CompletionNames {
+    fn get(&self, kind: NameKind) -> Vec<&str> {
+        let names: Box<dyn Iterator<Item = &String>> = match kind {
+            NameKind::Branch => Box::new(self.branches.iter()),
+            NameKind::Space => Box::new(self.spaces.iter()),
+            NameKind::KvKey => Box::new(self.kv_keys.iter()),
+            NameKind::JsonKey => Box::new(self.json_keys.iter()),
+            NameKind::StateCell => Box::new(self.state_cells.iter()),
+            NameKind::Collection => Box::new(self.collections.iter()),
+            NameKind::EventType => Box::new(self.event_types.iter()),
+        };
+        names.map(String::as_str).collect()
+    }
+
+    /// Remember the event type named by an `event` command line.
+    fn note_event_type(&mut self, line: &str) {
+        if self.event_types.len() >= COMPLETION_PREFETCH_LIMIT {
+            return;
+        }
+        let Some(tokens) = shlex::split(line) else {
+            return;
+        };
+        if let [cmd, sub, event_type, ..] = tokens.as_slice() {
+            if cmd == "event"
+                && EVENT_TYPE_SUBCOMMANDS.contains(&sub.as_str())
+                && !event_type.starts_with('-')
+            {
+                self.event_types.insert(event_type.clone());
+            }
+        }
+    }
+}
+
+/// Names listed by a command's output, capped at the prefetch limit.
+///
+/// Completion is best-effort: a failed listing yields no names.
+fn listed_names(output: Result<Output>) -> Vec<String> {
+    let mut names = match output {
+        Ok(Output::Keys(names)) | Ok(Output::SpaceList(names)) => names,
+        Ok(Output::JsonListResult { keys, .. }) => keys,
+        Ok(Output::BranchInfoList(branches)) => branches.into_iter().map(|b| b.info.id.0).collect(),
+        Ok(Output::VectorCollectionList(collections)) => {
+            collections.into_iter().map(|c| c.name).collect()
+        }
+        _ => Vec::new(),
+    };
+    names.truncate(COMPLETION_PREFETCH_LIMIT);
+    names
+}
+
+struct StrataHelper {
+    names: CompletionNames,
+    complete_keys: bool,
+}
 
 impl StrataHelper {
-    fn new() -> Self {
-        Self
+    fn new(complete_keys: bool) -> Self {
+        Self {
+            names: CompletionNames::default(),
+            complete_keys,
+        }
+    }
+
+    /// Fetch the names to complete for the current branch and space.
+    ///
+    /// Skipped inside a transaction: listings run in it would become reads
+    /// the commit has to validate. The names from before it began are kept.
+    fn refresh(&mut self, state: &mut SessionState) {
+        if state.in_transaction() {
+            return;
+        }
+        let limit = COMPLETION_PREFETCH_LIMIT as u64;
+        let names = &mut self.names;
+        names.branches = listed_names(state.execute(Command::BranchList {
+            state: None,
+            limit: Some(limit),
+            offset: None,
+        }));
+        let branch = Some(BranchId::from(state.branch()));
+        names.spaces = listed_names(state.execute(Command::SpaceList {
+            branch: branch.clone(),
+        }));
+        if !self.complete_keys {
+            return;
+        }
+
+        let space = Some(state.space().to_string());
+        names.kv_keys = listed_names(state.execute(Command::KvList {
+            branch: branch.clone(),
+            space: space.clone(),
+            prefix: None,
+            cursor: None,
+            limit: Some(limit),
+            as_of: None,
+        }));
+        names.json_keys = listed_names(state.execute(Command::JsonList {
+            branch: branch.clone(),
+            space: space.clone(),
+            prefix: None,
+            cursor: None,
+            limit,
+            as_of: None,
+        }));
+        names.state_cells = listed_names(state.execute(Command::StateList {
+            branch: branch.clone(),
+            space: space.clone(),
+            prefix: None,
+            as_of: None,
+        }));
+        names.collections =
+            listed_names(state.execute(Command::VectorListCollections { branch, space }));
+    }
+
+    /// Completions for the word ending at the cursor.
+    ///
+    /// `words` are the words before it and `prefix` the part of it typed so
+    /// far.
+    fn candidates(&self, words: &[&str], prefix: &str) -> Vec<Pair> {
+        let names: Vec<&str> = match words {
+            [] => TOP_LEVEL_COMMANDS.to_vec(),
+            [cmd] if !subcommands_for(cmd).is_empty() => subcommands_for(cmd).to_vec(),
+            _ => {
+                let args: Vec<&str> = words
+                    .iter()
+                    .copied()
+                    .filter(|w| !w.starts_with('-'))
+                    .collect();
+                match arg_kind(&args) {
+                    Some(kind) => self.names.get(kind),
+                    None => Vec::new(),
+                }
+            }
+        };
+        names
+            .into_iter()
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| Pair {
+                display: name.to_string(),
+                replacement: shlex::try_quote(name)
+                    .map(|q| q.into_owned())
+                    .unwrap_or_else(|_| name.to_string()),
+            })
+            .collect()
     }
 }
 
@@ -519,51 +837,164 @@ impl Completer for StrataHelper {
         _ctx: &Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Pair>)> {
         let line_to_pos = &line[..pos];
-        let parts: Vec<&str> = line_to_pos.split_whitespace().collect();
+        let mut words: Vec<&str> = line_to_pos.split_whitespace().collect();
 
         // Determine if we're completing a partial word or starting a new word
-        let trailing_space = line_to_pos.ends_with(' ');
-
-        if parts.is_empty() || (parts.len() == 1 && !trailing_space) {
-            // Completing top-level command
-            let prefix = parts.first().copied().unwrap_or("");
-            let start = pos - prefix.len();
-            let candidates: Vec<Pair> = TOP_LEVEL_COMMANDS
-                .iter()
-                .filter(|cmd| cmd.starts_with(prefix))
-                .map(|cmd| Pair {
-                    display: cmd.to_string(),
-                    replacement: cmd.to_string(),
-                })
-                .collect();
-            Ok((start, candidates))
-        } else if parts.len() == 1 && trailing_space {
-            // Just typed the top-level command, completing subcommand
-            let subs = subcommands_for(parts[0]);
-            let candidates: Vec<Pair> = subs
-                .iter()
-                .map(|s| Pair {
-                    display: s.to_string(),
-                    replacement: s.to_string(),
-                })
-                .collect();
-            Ok((pos, candidates))
-        } else if parts.len() == 2 && !trailing_space {
-            // Completing partial subcommand
-            let subs = subcommands_for(parts[0]);
-            let prefix = parts[1];
-            let start = pos - prefix.len();
-            let candidates: Vec<Pair> = subs
-                .iter()
-                .filter(|s| s.starts_with(prefix))
-                .map(|s| Pair {
-                    display: s.to_string(),
-                    replacement: s.to_string(),
-                })
-                .collect();
-            Ok((start, candidates))
+        let trailing_space = line_to_pos.is_empty() || line_to_pos.ends_with(' ');
+        let prefix = if trailing_space {
+            ""
         } else {
-            Ok((pos, vec![]))
-        }
+            words.pop().unwrap_or("")
+        };
+        Ok((pos - prefix.len(), self.candidates(&words, prefix)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper() -> StrataHelper {
+        let mut helper = StrataHelper::new(true);
+        helper.names.branches = vec!["default".into(), "dev".into(), "exp".into()];
+        helper.names.kv_keys = vec!["user:1".into(), "user:2".into(), "my key".into()];
+        helper.names.collections = vec!["docs".into()];
+        helper
+    }
+
+    fn complete(helper: &StrataHelper, line: &str) -> (usize, Vec<String>) {
+        let mut words: Vec<&str> = line.split_whitespace().collect();
+        let prefix = if line.is_empty() || line.ends_with(' ') {
+            ""
+        } else {
+            words.pop().unwrap()
+        };
+        let pairs = helper.candidates(&words, prefix);
+        (
+            line.len() - prefix.len(),
+            pairs.into_iter().map(|p| p.replacement).collect(),
+        )
+    }
+
+    #[test]
+    fn test_completes_commands_and_subcommands() {
+        let helper = helper();
+        assert_eq!(complete(&helper, "comm").1, vec!["commit"]);
+        assert_eq!(complete(&helper, "branch ex").1, vec!["exists", "export"]);
+        assert_eq!(complete(&helper, "").1.len(), TOP_LEVEL_COMMANDS.len());
+    }
+
+    #[test]
+    fn test_completes_branches_and_keys() {
+        let helper = helper();
+        assert_eq!(complete(&helper, "use d").1, vec!["default", "dev"]);
+        assert_eq!(complete(&helper, "branch diff dev e").1, vec!["exp"]);
+        assert!(complete(&helper, "branch fork ").1.is_empty());
+
+        let (start, names) = complete(&helper, "kv get user:1 us");
+        assert_eq!(start, "kv get user:1 ".len());
+        assert_eq!(names, vec!["user:1", "user:2"]);
+        // Keys needing quotes are quoted
+        assert_eq!(complete(&helper, "kv del my").1, vec!["'my key'"]);
+        // Only keys of `kv put` pairs, not values
+        assert_eq!(complete(&helper, "kv put user:1 ").1.len(), 0);
+        assert_eq!(complete(&helper, "kv put user:1 v ").1.len(), 3);
+        // Flags are skipped when counting arguments
+        assert_eq!(
+            complete(&helper, "kv get --with-version user:1 us").1.len(),
+            2
+        );
+        assert_eq!(complete(&helper, "vector stats d").1, vec!["docs"]);
+    }
+
+    #[test]
+    fn test_event_types_from_history() {
+        let mut helper = helper();
+        helper
+            .names
+            .note_event_type("event append user.created '{}'");
+        helper.names.note_event_type("event len");
+        helper.names.note_event_type("kv put event x");
+        assert_eq!(complete(&helper, "event list u").1, vec!["user.created"]);
+        assert_eq!(helper.names.event_types.len(), 1);
+    }
+
+    #[test]
+    fn test_listed_names_capped() {
+        let keys: Vec<String> = (0..COMPLETION_PREFETCH_LIMIT + 5)
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            listed_names(Ok(Output::Keys(keys))).len(),
+            COMPLETION_PREFETCH_LIMIT
+        );
+        assert!(listed_names(Ok(Output::Unit)).is_empty());
+    }
+
+    #[test]
+    fn test_open_transaction_pins_branch_and_rolls_back() {
+        let db = strata_executor::Strata::cache().unwrap();
+        let mut state = SessionState::new(db, "default".into(), "default".into());
+        state
+            .execute(Command::BranchCreate {
+                branch_id: Some("dev".into()),
+                metadata: None,
+            })
+            .unwrap();
+        assert!(!state.rollback_open_transaction());
+
+        state
+            .execute(Command::TxnBegin {
+                branch: Some(BranchId::from("default")),
+                options: None,
+            })
+            .unwrap();
+        assert!(state.set_branch("dev").is_err());
+        assert!(state.set_branch("default").is_ok());
+
+        assert!(state.rollback_open_transaction());
+        assert!(!state.in_transaction());
+        assert!(state.set_branch("dev").is_ok());
+    }
+
+    #[test]
+    fn test_writes_in_transaction_get_the_commit_version() {
+        let db = strata_executor::Strata::cache().unwrap();
+        let mut state = SessionState::new(db, "default".into(), "default".into());
+        let put = |key: &str| Command::KvPut {
+            branch: None,
+            space: None,
+            key: key.into(),
+            value: strata_executor::Value::Int(1),
+        };
+        let Output::Version(before) = state.execute(put("a")).unwrap() else {
+            panic!("expected a version");
+        };
+
+        state
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        assert!(matches!(state.execute(put("b")).unwrap(), Output::Unit));
+        assert!(matches!(state.execute(put("c")).unwrap(), Output::Unit));
+        let Output::TxnCommitted { version } = state.execute(Command::TxnCommit).unwrap() else {
+            panic!("expected a commit");
+        };
+        assert!(version > before);
+
+        let Output::MaybeVersioned(Some(vv)) = state
+            .execute(Command::KvGet {
+                branch: None,
+                space: None,
+                key: "c".into(),
+                as_of: None,
+            })
+            .unwrap()
+        else {
+            panic!("expected c");
+        };
+        assert_eq!(vv.version, version);
     }
 }
@@ -0,0 +1,409 @@
+//! Script mode: `strata --script setup.strata`.
+//!
+//! Runs commands from a file like pipe mode, with a few additions for
+//! setup and teardown scripts:
+//!
+//! - `$name = <command>` stores the command's raw output in a variable
+//! - `$name` or `${name}` in a later line is replaced by its value (`$$` is
+//!   a literal `$`; JSON paths such as `$.a` are left alone)
+//! - `if <condition>` ... [`else` ...] `end` blocks, where the condition is
+//!   `exists <command>`, `not exists <command>`, `<a> == <b>` or `<a> != <b>`
+//! - `use <branch> [space]` switches context and `quit` ends the script
+//!
+//! Unlike pipe mode, a script stops at the first failing line.
+
+use std::collections::HashMap;
+
+use strata_executor::{Error, Output};
+
+use crate::commands::build_repl_cmd;
+use crate::format::{format_error, format_output, OutputMode};
+use crate::parse::{matches_to_action, CliAction};
+use crate::repl::execute_action;
+use crate::state::SessionState;
+
+/// Run the script at `path`. Returns the process exit code.
+pub fn run_file(path: &str, state: &mut SessionState, mode: OutputMode) -> i32 {
+    match std::fs::read_to_string(path) {
+        Ok(source) => run(&source, path, state, mode),
+        Err(e) => {
+            eprintln!("(error) Failed to read script {}: {}", path, e);
+            1
+        }
+    }
+}
+
+/// Run a script's source; `name` labels error locations.
+pub fn run(source: &str, name: &str, state: &mut SessionState, mode: OutputMode) -> i32 {
+    let mut script = Script {
+        state,
+        mode,
+        vars: HashMap::new(),
+        blocks: Vec::new(),
+    };
+    let mut last_line = 0;
+    for (index, line) in source.lines().enumerate() {
+        last_line = index + 1;
+        match script.line(line) {
+            Ok(Flow::Continue) => {}
+            Ok(Flow::Quit) => break,
+            Err(message) => {
+                if let Some(message) = message {
+                    eprintln!("(error) {}:{}: {}", name, last_line, message);
+                } else {
+                    eprintln!("(error) {}:{}: script stopped", name, last_line);
+                }
+                script.state.rollback_open_transaction();
+                return 1;
+            }
+        }
+    }
+    if !script.blocks.is_empty() {
+        eprintln!("(error) {}:{}: missing 'end'", name, last_line);
+        script.state.rollback_open_transaction();
+        return 1;
+    }
+    if script.state.rollback_open_transaction() {
+        eprintln!("(txn) Uncommitted transaction rolled back");
+        return 1;
+    }
+    0
+}
+
+/// What to do after a line.
+enum Flow {
+    Continue,
+    Quit,
+}
+
+/// A line's failure; `None` when the command already reported its error.
+type LineError = Option<String>;
+
+/// An open `if` block.
+struct Block {
+    /// Lines of the enclosing block run.
+    outer: bool,
+    /// The condition held.
+    taken: bool,
+    /// Inside the `else` part.
+    in_else: bool,
+}
+
+impl Block {
+    /// Lines inside this block run.
+    fn runs(&self) -> bool {
+        self.outer && self.taken != self.in_else
+    }
+}
+
+struct Script<'a> {
+    state: &'a mut SessionState,
+    mode: OutputMode,
+    vars: HashMap<String, String>,
+    blocks: Vec<Block>,
+}
+
+impl Script<'_> {
+    fn line(&mut self, line: &str) -> Result<Flow, LineError> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            return Ok(Flow::Continue);
+        }
+        let tokens = shlex::split(trimmed).ok_or_else(|| Some("Invalid quoting".to_string()))?;
+        let running = self.blocks.last().is_none_or(Block::runs);
+
+        // Block structure is followed even in skipped lines
+        match tokens[0].as_str() {
+            "if" => {
+                let taken = running && self.condition(&tokens[1..])?;
+                self.blocks.push(Block {
+                    outer: running,
+                    taken,
+                    in_else: false,
+                });
+                return Ok(Flow::Continue);
+            }
+            "else" | "end" if tokens.len() > 1 => {
+                return Err(Some(format!("unexpected text after '{}'", tokens[0])));
+            }
+            "else" => {
+                match self.blocks.last_mut() {
+                    Some(block) if !block.in_else => block.in_else = true,
+                    Some(_) => return Err(Some("'else' already seen for this 'if'".into())),
+                    None => return Err(Some("'else' without 'if'".into())),
+                }
+                return Ok(Flow::Continue);
+            }
+            "end" => {
+                self.blocks
+                    .pop()
+                    .ok_or_else(|| Some("'end' without 'if'".to_string()))?;
+                return Ok(Flow::Continue);
+            }
+            _ if !running => return Ok(Flow::Continue),
+            _ => {}
+        }
+
+        if tokens.len() >= 2 && tokens[1] == "=" {
+            if let Some(var) = tokens[0].strip_prefix('$') {
+                if !is_name(var) {
+                    return Err(Some(format!("invalid variable name '{}'", var)));
+                }
+                let command = self.interpolate_all(&tokens[2..])?;
+                let value = self.capture(&command)?;
+                self.vars.insert(var.to_string(), value);
+                return Ok(Flow::Continue);
+            }
+        }
+
+        let tokens = self.interpolate_all(&tokens)?;
+
+        match tokens[0].as_str() {
+            "quit" | "exit" => Ok(Flow::Quit),
+            "use" => {
+                let branch = tokens
+                    .get(1)
+                    .ok_or_else(|| Some("usage: use <branch> [space]".to_string()))?;
+                self.state
+                    .set_branch(branch)
+                    .map_err(|e| Some(format_error(&e, self.mode)))?;
+                self.state
+                    .set_space(tokens.get(2).map_or("default", String::as_str));
+                Ok(Flow::Continue)
+            }
+            _ => {
+                let matches = build_repl_cmd()
+                    .try_get_matches_from(&tokens)
+                    .map_err(|e| Some(e.to_string().trim_end().to_string()))?;
+                if execute_action(&matches, self.state, self.mode) {
+                    Ok(Flow::Continue)
+                } else {
+                    Err(None)
+                }
+            }
+        }
+    }
+
+    /// Evaluate the condition of an `if`.
+    fn condition(&mut self, tokens: &[String]) -> Result<bool, LineError> {
+        let tokens = self.interpolate_all(tokens)?;
+        match tokens.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["exists", ..] => self.exists(&tokens[1..]),
+            ["not", "exists", ..] => self.exists(&tokens[2..]).map(|found| !found),
+            [a, "==", b] => Ok(a == b),
+            [a, "!=", b] => Ok(a != b),
+            _ => Err(Some(
+                "expected 'exists <command>', 'not exists <command>', 'a == b' or 'a != b'".into(),
+            )),
+        }
+    }
+
+    /// Whether a lookup command finds something.
+    fn exists(&mut self, command: &[String]) -> Result<bool, LineError> {
+        match self.execute(command) {
+            Ok(output) => Ok(!is_absent(&output)),
+            Err(Some(e)) => Err(Some(e)),
+            Err(None) => Ok(false),
+        }
+    }
+
+    /// Run a command and return its raw output.
+    fn capture(&mut self, command: &[String]) -> Result<String, LineError> {
+        match self.execute(command) {
+            Ok(output) => Ok(format_output(&output, OutputMode::Raw)),
+            Err(Some(e)) => Err(Some(e)),
+            Err(None) => Err(Some("not found".into())),
+        }
+    }
+
+    /// Execute a single command; `Err(None)` when it reported "not found".
+    fn execute(&mut self, command: &[String]) -> Result<Output, LineError> {
+        if command.is_empty() {
+            return Err(Some("missing command".into()));
+        }
+        let matches = build_repl_cmd()
+            .try_get_matches_from(command)
+            .map_err(|e| Some(e.to_string().trim_end().to_string()))?;
+        let cmd = match matches_to_action(&matches, self.state).map_err(Some)? {
+            CliAction::Execute(cmd) | CliAction::GetWithVersion { command: cmd, .. } => cmd,
+            _ => {
+                return Err(Some(format!(
+                    "'{}' cannot be used in a condition or assignment",
+                    command.join(" ")
+                )))
+            }
+        };
+        match self.state.execute(cmd) {
+            Ok(output) => Ok(output),
+            Err(e) if is_not_found(&e) => Err(None),
+            Err(e) => Err(Some(format_error(&e, self.mode))),
+        }
+    }
+
+    fn interpolate_all(&self, tokens: &[String]) -> Result<Vec<String>, LineError> {
+        tokens
+            .iter()
+            .map(|t| interpolate(t, &self.vars).map_err(Some))
+            .collect()
+    }
+}
+
+/// Replace `$name` and `${name}` in a token with variable values.
+fn interpolate(token: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(token.len());
+    let mut rest = token;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, tail) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| format!("unclosed '${{' in '{}'", token))?;
+            (&braced[..end], &braced[end + 1..])
+        } else if let Some(tail) = after.strip_prefix('$') {
+            out.push('$');
+            rest = tail;
+            continue;
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if !is_name(name) {
+            // `$`, `$.path`, `$[0]`: not a variable reference
+            out.push('$');
+            rest = after;
+            continue;
+        }
+        let value = vars
+            .get(name)
+            .ok_or_else(|| format!("undefined variable '${}'", name))?;
+        out.push_str(value);
+        rest = tail;
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Output of a lookup that found nothing.
+fn is_absent(output: &Output) -> bool {
+    matches!(
+        output,
+        Output::Maybe(None)
+            | Output::MaybeVersioned(None)
+            | Output::MaybeVersion(None)
+            | Output::VersionHistory(None)
+            | Output::VectorData(None)
+            | Output::MaybeBranchInfo(None)
+            | Output::Bool(false)
+    )
+}
+
+fn is_not_found(e: &Error) -> bool {
+    matches!(
+        e,
+        Error::KeyNotFound { .. }
+            | Error::BranchNotFound { .. }
+            | Error::CollectionNotFound { .. }
+            | Error::StreamNotFound { .. }
+            | Error::CellNotFound { .. }
+            | Error::DocumentNotFound { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_executor::{BranchId, Command, Strata, Value};
+
+    fn state() -> SessionState {
+        SessionState::new(Strata::cache().unwrap(), "default".into(), "default".into())
+    }
+
+    fn kv_get(state: &mut SessionState, key: &str) -> Option<Value> {
+        match state
+            .execute(Command::KvGet {
+                branch: Some(BranchId::from("default")),
+                space: Some("default".into()),
+                key: key.into(),
+                as_of: None,
+            })
+            .unwrap()
+        {
+            Output::MaybeVersioned(v) => v.map(|vv| vv.value),
+            other => panic!("unexpected output {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let vars = HashMap::from([("name".to_string(), "ada".to_string())]);
+        assert_eq!(interpolate("user:$name", &vars).unwrap(), "user:ada");
+        assert_eq!(interpolate("${name}_1", &vars).unwrap(), "ada_1");
+        assert_eq!(interpolate("$$name", &vars).unwrap(), "$name");
+        assert_eq!(interpolate("$.a[0]", &vars).unwrap(), "$.a[0]");
+        assert_eq!(interpolate("$", &vars).unwrap(), "$");
+        assert!(interpolate("$missing", &vars).is_err());
+        assert!(interpolate("${name", &vars).is_err());
+    }
+
+    #[test]
+    fn test_variables_and_conditionals() {
+        let mut state = state();
+        let source = r#"
+            # seed once
+            kv put greeting hello
+            $g = kv get greeting
+            kv put copy "$g world"
+            if exists kv get greeting
+              kv put seen yes
+            else
+              kv put seen no
+            end
+            if not exists kv get absent
+              if $g == hello
+                kv put nested ok
+              end
+            end
+            if $g != hello
+              kv put skipped "$undefined"
+            end
+        "#;
+        assert_eq!(run(source, "test", &mut state, OutputMode::Raw), 0);
+        assert_eq!(
+            kv_get(&mut state, "copy"),
+            Some(Value::String("hello world".into()))
+        );
+        assert_eq!(
+            kv_get(&mut state, "seen"),
+            Some(Value::String("yes".into()))
+        );
+        assert_eq!(
+            kv_get(&mut state, "nested"),
+            Some(Value::String("ok".into()))
+        );
+        assert_eq!(kv_get(&mut state, "skipped"), None);
+    }
+
+    #[test]
+    fn test_stops_at_first_error() {
+        let mut state = state();
+        let source = "kv put a 1\nkv put b $nope\nkv put c 3\n";
+        assert_eq!(run(source, "test", &mut state, OutputMode::Raw), 1);
+        assert!(kv_get(&mut state, "a").is_some());
+        assert_eq!(kv_get(&mut state, "c"), None);
+
+        assert_eq!(
+            run("if exists kv get a\n", "test", &mut state, OutputMode::Raw),
+            1
+        );
+        assert_eq!(run("end\n", "test", &mut state, OutputMode::Raw), 1);
+    }
+}
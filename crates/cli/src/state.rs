@@ -5,8 +5,8 @@
 //! `Arc<Database>`.
 
 use strata_executor::{
-    BranchDiffResult, Branches, Command, Error, ForkInfo, MergeInfo, MergeStrategy, Output,
-    Result, Session, Strata,
+    BranchDiffResult, Branches, Command, DatabaseInfo, DatabaseStats, Error, ForkInfo, MergeInfo,
+    MergeStrategy, Output, Result, SchemaChange, Session, Strata,
 };
 
 /// Wraps the database handles and tracks current context.
@@ -32,8 +32,18 @@ impl SessionState {
     }
 
     /// Execute a command via the session.
+    ///
+    /// A KV or JSON write inside a transaction returns the transaction id,
+    /// not a version; the write only gets one at commit. Report it as `Unit`.
     pub fn execute(&mut self, cmd: Command) -> Result<Output> {
+        let buffered_write =
+            self.in_transaction && matches!(cmd, Command::KvPut { .. } | Command::JsonSet { .. });
         let output = self.session.execute(cmd)?;
+        if buffered_write {
+            if let Output::Version(_) = output {
+                return Ok(Output::Unit);
+            }
+        }
         // Track transaction state changes
         match &output {
             Output::TxnBegun => self.in_transaction = true,
@@ -64,6 +74,23 @@ impl SessionState {
         self.db.branches().merge(source, &self.branch, strategy)
     }
 
+    /// Schema log of a branch, defaulting to the current branch.
+    pub fn schema_log(&self, branch: Option<&str>) -> Result<Vec<SchemaChange>> {
+        self.db
+            .branches()
+            .schema_log(branch.unwrap_or(&self.branch))
+    }
+
+    /// Database info and storage statistics for the `info` command.
+    pub fn info(&self) -> Result<(DatabaseInfo, DatabaseStats)> {
+        Ok((self.db.info()?, self.db.stats()?))
+    }
+
+    /// Pick up writes another process made to a read-only database.
+    pub fn refresh(&self) -> Result<u64> {
+        self.db.refresh()
+    }
+
     /// Current branch name.
     pub fn branch(&self) -> &str {
         &self.branch
@@ -75,7 +102,18 @@ impl SessionState {
     }
 
     /// Switch branch context.
+    ///
+    /// Refused while a transaction is open: its commands keep writing to the
+    /// branch it was begun on, whatever the prompt says.
     pub fn set_branch(&mut self, name: &str) -> Result<()> {
+        if self.in_transaction && name != self.branch {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "cannot switch to branch '{}' inside a transaction on '{}'; commit or rollback first",
+                    name, self.branch
+                ),
+            });
+        }
         // Verify branch exists
         let exists = match self.session.execute(Command::BranchExists {
             branch: name.into(),
@@ -102,11 +140,15 @@ impl SessionState {
     }
 
     /// Whether a transaction is currently active.
-    #[allow(dead_code)]
     pub fn in_transaction(&self) -> bool {
         self.in_transaction
     }
 
+    /// Roll back the open transaction, if any. Returns whether there was one.
+    pub fn rollback_open_transaction(&mut self) -> bool {
+        self.in_transaction && self.execute(Command::TxnRollback).is_ok()
+    }
+
     /// Generate the REPL prompt string.
     pub fn prompt(&self) -> String {
         if self.in_transaction {
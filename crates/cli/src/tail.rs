@@ -0,0 +1,114 @@
+//! Print the latest events of a type, optionally following new ones.
+//!
+//! Following polls the event log. A database held open by another process
+//! can only be opened with `--read-only`; each poll then first picks up the
+//! WAL records that process has written since (see `Strata::refresh`).
+//!
+//! Events print one per line as they arrive, so `--json` output is a stream
+//! of JSON objects ready for `jq`.
+
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Write};
+use std::time::Duration;
+
+use strata_executor::{BranchId, Command, Output, VersionedValue};
+
+use crate::format::{format_event_line, OutputMode};
+use crate::state::SessionState;
+
+/// Events fetched per page.
+const PAGE_SIZE: u64 = 1000;
+
+/// An `event tail` invocation, scoped to the session's branch and space.
+pub struct TailOp {
+    pub event_type: String,
+    /// Existing events to print before following.
+    pub count: u64,
+    /// Keep polling for new events until interrupted.
+    pub follow: bool,
+    pub interval: Duration,
+}
+
+/// Run a tail.
+///
+/// With `follow` this only returns on error or when stdout is closed
+/// (e.g. the reading end of a pipe exits).
+pub fn run(state: &mut SessionState, op: TailOp, mode: OutputMode) -> Result<(), String> {
+    let mut out = std::io::stdout().lock();
+
+    let mut last = VecDeque::new();
+    let mut after_sequence = None;
+    loop {
+        let events = read_page(state, &op.event_type, after_sequence)?;
+        let full_page = events.len() as u64 == PAGE_SIZE;
+        for event in events {
+            after_sequence = Some(event.version);
+            if op.count > 0 {
+                if last.len() as u64 == op.count {
+                    last.pop_front();
+                }
+                last.push_back(event);
+            }
+        }
+        if !full_page {
+            break;
+        }
+    }
+    if !print(&mut out, last.iter(), &op.event_type, mode)? || !op.follow {
+        return Ok(());
+    }
+
+    loop {
+        std::thread::sleep(op.interval);
+        state.refresh().map_err(|e| e.to_string())?;
+        loop {
+            let events = read_page(state, &op.event_type, after_sequence)?;
+            let full_page = events.len() as u64 == PAGE_SIZE;
+            if let Some(event) = events.last() {
+                after_sequence = Some(event.version);
+            }
+            if !print(&mut out, events.iter(), &op.event_type, mode)? {
+                return Ok(());
+            }
+            if !full_page {
+                break;
+            }
+        }
+    }
+}
+
+fn read_page(
+    state: &mut SessionState,
+    event_type: &str,
+    after_sequence: Option<u64>,
+) -> Result<Vec<VersionedValue>, String> {
+    let cmd = Command::EventGetByType {
+        branch: Some(BranchId::from(state.branch())),
+        space: Some(state.space().to_string()),
+        event_type: event_type.to_string(),
+        limit: Some(PAGE_SIZE),
+        after_sequence,
+        as_of: None,
+    };
+    match state.execute(cmd).map_err(|e| e.to_string())? {
+        Output::VersionedValues(events) => Ok(events),
+        other => Err(format!("Unexpected output: {:?}", other)),
+    }
+}
+
+/// Write events and flush; `false` once stdout has been closed.
+fn print<'a>(
+    out: &mut impl Write,
+    mut events: impl Iterator<Item = &'a VersionedValue>,
+    event_type: &str,
+    mode: OutputMode,
+) -> Result<bool, String> {
+    let result = events
+        .try_for_each(|event| writeln!(out, "{}", format_event_line(event, event_type, mode)))
+        .and_then(|()| out.flush());
+    match result {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(false),
+        Err(e) => Err(format!("Failed to write events: {}", e)),
+    }
+}
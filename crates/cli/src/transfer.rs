@@ -0,0 +1,576 @@
+//! Bulk export and import of KV, JSON and event data.
+//!
+//! Records stream page by page through the current session, so dumps of
+//! any size run in constant memory. Values use the wire encoding: bytes
+//! become `{"$bytes": "<base64>"}` and non-finite floats `{"$f64": "NaN"}`,
+//! so a dump imports back to identical values.
+//!
+//! | Format | Layout |
+//! |--------|--------|
+//! | `jsonl` | One JSON object per line: `{"key": ..., "value": ...}` |
+//! | `csv` | Header row, then one row per record; value cells hold JSON text |
+//!
+//! Event exports carry `sequence`, `event_type`, `timestamp` and `payload`.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use serde_json::{Map, Value as JsonValue};
+use strata_executor::{json_to_value, value_to_json, BranchId, Command, Output, Value};
+
+use crate::state::SessionState;
+
+/// Records fetched per page while exporting.
+const PAGE_SIZE: u64 = 1000;
+
+/// File format of a dump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Jsonl,
+    Csv,
+}
+
+impl Format {
+    /// Parse a `--format` value.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "jsonl" => Ok(Format::Jsonl),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!(
+                "Unknown format '{}' (expected jsonl or csv)",
+                other
+            )),
+        }
+    }
+
+    /// Guess the format of an import file from its extension.
+    pub fn from_path(path: &str) -> Self {
+        if path.to_ascii_lowercase().ends_with(".csv") {
+            Format::Csv
+        } else {
+            Format::Jsonl
+        }
+    }
+}
+
+/// What a transfer reads or writes.
+pub enum Dataset {
+    Kv,
+    Json,
+    Events { event_type: String },
+}
+
+impl Dataset {
+    fn noun(&self) -> &'static str {
+        match self {
+            Dataset::Kv => "keys",
+            Dataset::Json => "documents",
+            Dataset::Events { .. } => "events",
+        }
+    }
+}
+
+/// A bulk import or export, scoped to the session's branch and space.
+pub enum TransferOp {
+    Export {
+        dataset: Dataset,
+        format: Format,
+        prefix: Option<String>,
+        /// Destination file; stdout if `None`.
+        output: Option<String>,
+    },
+    Import {
+        dataset: Dataset,
+        format: Format,
+        /// Source file; `-` for stdin.
+        input: String,
+    },
+}
+
+/// Run a transfer.
+///
+/// Returns a summary line for the user, or `None` when the export went to
+/// stdout and a summary would corrupt the stream.
+pub fn run(state: &mut SessionState, op: TransferOp) -> Result<Option<String>, String> {
+    match op {
+        TransferOp::Export {
+            dataset,
+            format,
+            prefix,
+            output,
+        } => {
+            let out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(
+                    File::create(path)
+                        .map_err(|e| format!("Failed to create '{}': {}", path, e))?,
+                ),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            let mut sink = RecordSink::new(BufWriter::new(out), format);
+            let count = export(state, &dataset, prefix, &mut sink)?;
+            sink.finish()?;
+            Ok(output.map(|path| format!("Exported {} {} to {}", count, dataset.noun(), path)))
+        }
+        TransferOp::Import {
+            dataset,
+            format,
+            input,
+        } => {
+            let reader: Box<dyn BufRead> = if input == "-" {
+                Box::new(BufReader::new(std::io::stdin().lock()))
+            } else {
+                Box::new(BufReader::new(
+                    File::open(&input).map_err(|e| format!("Failed to read '{}': {}", input, e))?,
+                ))
+            };
+            let count = import(state, &dataset, format, reader)?;
+            Ok(Some(format!("Imported {} {}", count, dataset.noun())))
+        }
+    }
+}
+
+// =========================================================================
+// Export
+// =========================================================================
+
+fn export(
+    state: &mut SessionState,
+    dataset: &Dataset,
+    prefix: Option<String>,
+    sink: &mut RecordSink,
+) -> Result<u64, String> {
+    let branch = Some(BranchId::from(state.branch()));
+    let space = Some(state.space().to_string());
+    let mut count = 0;
+
+    match dataset {
+        Dataset::Kv | Dataset::Json => {
+            let mut cursor: Option<String> = None;
+            loop {
+                let (keys, next) = match dataset {
+                    Dataset::Kv => {
+                        let keys = match execute(
+                            state,
+                            Command::KvList {
+                                branch: branch.clone(),
+                                space: space.clone(),
+                                prefix: prefix.clone(),
+                                cursor: cursor.clone(),
+                                limit: Some(PAGE_SIZE),
+                                as_of: None,
+                            },
+                        )? {
+                            Output::Keys(keys) => keys,
+                            other => return Err(unexpected(&other)),
+                        };
+                        let next = if keys.len() as u64 == PAGE_SIZE {
+                            keys.last().cloned()
+                        } else {
+                            None
+                        };
+                        (keys, next)
+                    }
+                    _ => match execute(
+                        state,
+                        Command::JsonList {
+                            branch: branch.clone(),
+                            space: space.clone(),
+                            prefix: prefix.clone(),
+                            cursor: cursor.clone(),
+                            limit: PAGE_SIZE,
+                            as_of: None,
+                        },
+                    )? {
+                        Output::JsonListResult { keys, cursor } => (keys, cursor),
+                        other => return Err(unexpected(&other)),
+                    },
+                };
+
+                for key in keys {
+                    let get = match dataset {
+                        Dataset::Kv => Command::KvGet {
+                            branch: branch.clone(),
+                            space: space.clone(),
+                            key: key.clone(),
+                            as_of: None,
+                        },
+                        _ => Command::JsonGet {
+                            branch: branch.clone(),
+                            space: space.clone(),
+                            key: key.clone(),
+                            path: "$".to_string(),
+                            as_of: None,
+                        },
+                    };
+                    // Skip keys deleted between the list and the read
+                    let value = match execute(state, get)? {
+                        Output::MaybeVersioned(Some(vv)) => vv.value,
+                        Output::MaybeVersioned(None) => continue,
+                        other => return Err(unexpected(&other)),
+                    };
+                    sink.write(&[
+                        ("key", JsonValue::String(key)),
+                        ("value", value_to_json(&value)),
+                    ])?;
+                    count += 1;
+                }
+
+                match next {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+        }
+        Dataset::Events { event_type } => {
+            let mut after_sequence: Option<u64> = None;
+            loop {
+                let events = match execute(
+                    state,
+                    Command::EventGetByType {
+                        branch: branch.clone(),
+                        space: space.clone(),
+                        event_type: event_type.clone(),
+                        limit: Some(PAGE_SIZE),
+                        after_sequence,
+                        as_of: None,
+                    },
+                )? {
+                    Output::VersionedValues(events) => events,
+                    other => return Err(unexpected(&other)),
+                };
+                let Some(last) = events.last() else {
+                    break;
+                };
+                after_sequence = Some(last.version);
+                let full_page = events.len() as u64 == PAGE_SIZE;
+
+                for event in events {
+                    sink.write(&[
+                        ("sequence", JsonValue::from(event.version)),
+                        ("event_type", JsonValue::String(event_type.clone())),
+                        ("timestamp", JsonValue::from(event.timestamp)),
+                        ("payload", value_to_json(&event.value)),
+                    ])?;
+                    count += 1;
+                }
+                if !full_page {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(count)
+}
+
+/// Writes records as JSON lines or CSV rows.
+struct RecordSink {
+    out: BufWriter<Box<dyn Write>>,
+    format: Format,
+    wrote_header: bool,
+}
+
+impl RecordSink {
+    fn new(out: BufWriter<Box<dyn Write>>, format: Format) -> Self {
+        Self {
+            out,
+            format,
+            wrote_header: false,
+        }
+    }
+
+    fn write(&mut self, fields: &[(&str, JsonValue)]) -> Result<(), String> {
+        let line = match self.format {
+            Format::Jsonl => {
+                let obj: Map<String, JsonValue> = fields
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect();
+                JsonValue::Object(obj).to_string()
+            }
+            Format::Csv => {
+                if !self.wrote_header {
+                    let header: Vec<String> =
+                        fields.iter().map(|(name, _)| csv_escape(name)).collect();
+                    writeln!(self.out, "{}", header.join(",")).map_err(write_error)?;
+                    self.wrote_header = true;
+                }
+                let row: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| csv_escape(&csv_cell(name, value)))
+                    .collect();
+                row.join(",")
+            }
+        };
+        writeln!(self.out, "{}", line).map_err(write_error)
+    }
+
+    fn finish(mut self) -> Result<(), String> {
+        self.out.flush().map_err(write_error)
+    }
+}
+
+/// Text of a CSV cell: names and numbers as-is, values as JSON.
+fn csv_cell(column: &str, value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) if !is_value_column(column) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn is_value_column(column: &str) -> bool {
+    column == "value" || column == "payload"
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn write_error(e: std::io::Error) -> String {
+    format!("Failed to write export: {}", e)
+}
+
+// =========================================================================
+// Import
+// =========================================================================
+
+fn import(
+    state: &mut SessionState,
+    dataset: &Dataset,
+    format: Format,
+    mut reader: Box<dyn BufRead>,
+) -> Result<u64, String> {
+    if matches!(dataset, Dataset::Events { .. }) {
+        return Err("Events cannot be imported".to_string());
+    }
+    let branch = Some(BranchId::from(state.branch()));
+    let space = Some(state.space().to_string());
+    let mut count = 0;
+    let mut line_no = 0;
+    let mut columns: Option<(usize, usize)> = None;
+
+    loop {
+        let record = match format {
+            Format::Jsonl => {
+                let mut line = String::new();
+                let read = reader.read_line(&mut line).map_err(read_error)?;
+                if read == 0 {
+                    break;
+                }
+                line_no += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                parse_jsonl_record(&line).map_err(|e| format!("line {}: {}", line_no, e))?
+            }
+            Format::Csv => {
+                let Some((row, lines)) = read_csv_row(&mut reader)? else {
+                    break;
+                };
+                line_no += lines;
+                if row.iter().all(|cell| cell.is_empty()) {
+                    continue;
+                }
+                let Some((key_col, value_col)) = columns else {
+                    columns = Some(csv_columns(&row)?);
+                    continue;
+                };
+                parse_csv_record(&row, key_col, value_col)
+                    .map_err(|e| format!("line {}: {}", line_no, e))?
+            }
+        };
+
+        let (key, value) = record;
+        let cmd = match dataset {
+            Dataset::Kv => Command::KvPut {
+                branch: branch.clone(),
+                space: space.clone(),
+                key,
+                value,
+            },
+            _ => Command::JsonSet {
+                branch: branch.clone(),
+                space: space.clone(),
+                key,
+                path: "$".to_string(),
+                value,
+            },
+        };
+        execute(state, cmd).map_err(|e| format!("line {}: {}", line_no, e))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn parse_jsonl_record(line: &str) -> Result<(String, Value), String> {
+    let json: JsonValue = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {}", e))?;
+    let key = json
+        .get("key")
+        .and_then(JsonValue::as_str)
+        .ok_or("missing string field \"key\"")?
+        .to_string();
+    let value = json.get("value").ok_or("missing field \"value\"")?;
+    Ok((key, json_to_value(value)?))
+}
+
+/// Positions of the `key` and `value` columns in a CSV header.
+fn csv_columns(header: &[String]) -> Result<(usize, usize), String> {
+    let find = |name: &str| {
+        header
+            .iter()
+            .position(|c| c.trim() == name)
+            .ok_or_else(|| format!("CSV header has no \"{}\" column", name))
+    };
+    Ok((find("key")?, find("value")?))
+}
+
+/// Build a record from a CSV row.
+///
+/// Value cells that aren't valid JSON are imported as strings, so
+/// hand-written files don't need to quote text twice.
+fn parse_csv_record(
+    row: &[String],
+    key_col: usize,
+    value_col: usize,
+) -> Result<(String, Value), String> {
+    let key = row.get(key_col).ok_or("missing key cell")?.clone();
+    let cell = row.get(value_col).ok_or("missing value cell")?;
+    let value = match serde_json::from_str::<JsonValue>(cell) {
+        Ok(json) => json_to_value(&json)?,
+        Err(_) => Value::String(cell.clone()),
+    };
+    Ok((key, value))
+}
+
+/// Read one CSV row, following quoted cells across line breaks.
+///
+/// Returns the cells and the number of lines consumed, or `None` at end
+/// of input.
+fn read_csv_row(reader: &mut Box<dyn BufRead>) -> Result<Option<(Vec<String>, usize)>, String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut lines = 0;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).map_err(read_error)? == 0 {
+            if lines == 0 {
+                return Ok(None);
+            }
+            if in_quotes {
+                return Err("unterminated quoted CSV cell".to_string());
+            }
+            break;
+        }
+        lines += 1;
+
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, in_quotes) {
+                ('"', true) if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                ('"', true) => in_quotes = false,
+                ('"', false) if cell.is_empty() => in_quotes = true,
+                (',', false) => cells.push(std::mem::take(&mut cell)),
+                ('\r' | '\n', false) => {}
+                (c, _) => cell.push(c),
+            }
+        }
+        if !in_quotes {
+            break;
+        }
+    }
+    cells.push(cell);
+    Ok(Some((cells, lines)))
+}
+
+fn read_error(e: std::io::Error) -> String {
+    format!("Failed to read import: {}", e)
+}
+
+// =========================================================================
+// Helpers
+// =========================================================================
+
+fn execute(state: &mut SessionState, cmd: Command) -> Result<Output, String> {
+    state.execute(cmd).map_err(|e| e.to_string())
+}
+
+fn unexpected(output: &Output) -> String {
+    format!("Unexpected output: {:?}", output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(input: &str) -> Vec<Vec<String>> {
+        let mut reader: Box<dyn BufRead> = Box::new(std::io::Cursor::new(input.to_string()));
+        let mut rows = Vec::new();
+        while let Some((row, _)) = read_csv_row(&mut reader).unwrap() {
+            rows.push(row);
+        }
+        rows
+    }
+
+    #[test]
+    fn test_csv_escape_round_trip() {
+        let cells = ["plain", "a,b", "say \"hi\"", "two\nlines", ""];
+        let line: Vec<String> = cells.iter().map(|c| csv_escape(c)).collect();
+        let parsed = rows(&format!("{}\n", line.join(",")));
+        assert_eq!(parsed, vec![cells.map(String::from).to_vec()]);
+    }
+
+    #[test]
+    fn test_csv_rows_and_crlf() {
+        let parsed = rows("key,value\r\nk1,1\r\nk2,\"x,y\"\r\n");
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2], vec!["k2".to_string(), "x,y".to_string()]);
+    }
+
+    #[test]
+    fn test_csv_cell_keeps_values_typed() {
+        let value = value_to_json(&Value::String("hi".into()));
+        assert_eq!(csv_cell("key", &value), "hi");
+        assert_eq!(csv_cell("value", &value), "\"hi\"");
+
+        let row = vec!["k".to_string(), "\"hi\"".to_string()];
+        assert_eq!(
+            parse_csv_record(&row, 0, 1).unwrap().1,
+            Value::String("hi".into())
+        );
+        let row = vec!["k".to_string(), "plain text".to_string()];
+        assert_eq!(
+            parse_csv_record(&row, 0, 1).unwrap().1,
+            Value::String("plain text".into())
+        );
+    }
+
+    #[test]
+    fn test_jsonl_record_uses_wire_encoding() {
+        let (key, value) =
+            parse_jsonl_record(r#"{"key":"blob","value":{"$bytes":"AQID"}}"#).unwrap();
+        assert_eq!(key, "blob");
+        assert_eq!(value, Value::Bytes(vec![1, 2, 3]));
+
+        let (_, value) = parse_jsonl_record(r#"{"key":"f","value":{"$f64":"-Inf"}}"#).unwrap();
+        assert_eq!(value, Value::Float(f64::NEG_INFINITY));
+
+        assert!(parse_jsonl_record(r#"{"value":1}"#).is_err());
+        assert!(parse_jsonl_record("not json").is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path("dump.CSV"), Format::Csv);
+        assert_eq!(Format::from_path("dump.jsonl"), Format::Jsonl);
+        assert_eq!(Format::from_path("-"), Format::Jsonl);
+        assert!(Format::parse("xml").is_err());
+    }
+}
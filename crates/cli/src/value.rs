@@ -8,12 +8,12 @@
 //! 5. Float pattern → Value::Float
 //! 6. Everything else → Value::String
 
-use strata_executor::Value;
+use strata_executor::{MetadataFilter, Value};
 
 /// Auto-detect value type from a user-supplied string.
 ///
 /// Rules applied in order:
-/// 1. If starts with `{`, `[`, or `"` → parse as JSON, convert via `From<serde_json::Value>`
+/// 1. If starts with `{`, `[`, or `"` → parse as JSON, convert via `TryFrom<serde_json::Value>`
 /// 2. `null` → `Value::Null`
 /// 3. `true` / `false` → `Value::Bool`
 /// 4. Matches `^-?[0-9]+$` and fits i64 → `Value::Int`
@@ -23,9 +23,11 @@ pub fn parse_value(s: &str) -> Value {
     // Rule 1: JSON structures
     if s.starts_with('{') || s.starts_with('[') || s.starts_with('"') {
         if let Ok(json) = serde_json::from_str::<serde_json::Value>(s) {
-            return Value::from(json);
+            if let Ok(value) = Value::try_from(&json) {
+                return value;
+            }
         }
-        // If JSON parse fails, fall through to string
+        // If JSON parse or conversion fails, fall through to string
     }
 
     // Rule 2: null
@@ -63,7 +65,43 @@ pub fn parse_value(s: &str) -> Value {
 pub fn parse_json_value(s: &str) -> Result<Value, String> {
     let json: serde_json::Value =
         serde_json::from_str(s).map_err(|e| format!("Invalid JSON: {}", e))?;
-    Ok(Value::from(json))
+    Value::try_from(&json).map_err(|e| format!("Invalid JSON: {}", e))
+}
+
+/// Parse a vector search filter list like
+/// `[{"field": "year", "op": "range", "value": [2000, 2010]}]`.
+///
+/// Filter values are plain JSON, converted the same way as metadata.
+pub fn parse_filters(s: &str) -> Result<Vec<MetadataFilter>, String> {
+    let mut json: serde_json::Value =
+        serde_json::from_str(s).map_err(|e| format!("Invalid filter JSON: {}", e))?;
+    tag_filter_values(&mut json)?;
+    serde_json::from_value(json).map_err(|e| format!("Invalid filter JSON: {}", e))
+}
+
+/// Rewrite each plain JSON `value` (including in `or`/`not` sub-filters)
+/// into the tagged form `Value` deserializes from.
+fn tag_filter_values(json: &mut serde_json::Value) -> Result<(), String> {
+    match json {
+        serde_json::Value::Array(filters) => {
+            for filter in filters {
+                tag_filter_values(filter)?;
+            }
+        }
+        serde_json::Value::Object(filter) => {
+            if let Some(value) = filter.get_mut("value") {
+                let tagged =
+                    Value::try_from(&*value).map_err(|e| format!("Invalid filter value: {}", e))?;
+                *value = serde_json::to_value(tagged)
+                    .map_err(|e| format!("Invalid filter value: {}", e))?;
+            }
+            if let Some(nested) = filter.get_mut("filters") {
+                tag_filter_values(nested)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
 }
 
 /// Parse a vector literal like `[1.0, 2.0, 3.0]`.
@@ -85,6 +123,20 @@ pub fn parse_vector(s: &str) -> Result<Vec<f32>, String> {
     }
 }
 
+/// Parse a JSON array of vectors, e.g. `[[1.0,0.0],[0.0,1.0]]`.
+pub fn parse_vectors(s: &str) -> Result<Vec<Vec<f32>>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(s).map_err(|e| format!("Invalid vector list: {}", e))?;
+    match json {
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(i, v)| parse_vector(&v.to_string()).map_err(|e| format!("Vector {}: {}", i, e)))
+            .collect(),
+        _ => Err("Expected a JSON array of vectors".to_string()),
+    }
+}
+
 fn is_integer(s: &str) -> bool {
     let s = if let Some(rest) = s.strip_prefix('-') {
         rest
@@ -174,6 +226,35 @@ mod tests {
         assert_eq!(v, vec![1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn test_parse_vectors() {
+        let v = parse_vectors("[[1.0, 2.0], [3, 4]]").unwrap();
+        assert_eq!(v, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert!(parse_vectors("[[1.0], [\"x\"]]")
+            .unwrap_err()
+            .starts_with("Vector 1:"));
+        assert!(parse_vectors("[1.0, 2.0]").is_err());
+    }
+
+    #[test]
+    fn test_parse_filters_plain_json_values() {
+        use strata_executor::FilterOp;
+
+        let filters = parse_filters(
+            r#"[{"field":"year","op":"range","value":[2000,2010]},
+                {"op":"or","filters":[{"field":"tag","op":"eq","value":"a"}]}]"#,
+        )
+        .unwrap();
+        assert_eq!(filters[0].op, FilterOp::Range);
+        assert_eq!(
+            filters[0].value,
+            Value::Array(vec![Value::Int(2000), Value::Int(2010)])
+        );
+        assert_eq!(filters[1].op, FilterOp::Or);
+        assert_eq!(filters[1].filters[0].value, Value::String("a".into()));
+        assert!(parse_filters(r#"[{"field":"a","op":"bogus","value":1}]"#).is_err());
+    }
+
     #[test]
     fn test_parse_json_value_strict() {
         assert!(parse_json_value("not json").is_err());
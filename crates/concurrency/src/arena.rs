@@ -0,0 +1,98 @@
+//! Per-transaction scratch arena
+//!
+//! Encoding a committed transaction for the WAL needs a list of its write-set
+//! entries and a byte buffer to serialize them into. Building those fresh on
+//! every commit cost one allocation per key and value (the entries were
+//! cloned) plus a buffer that grew from empty each time.
+//!
+//! `TransactionArena` lives in the `TransactionContext`, so it is pooled with
+//! it. Entry lists borrow from the write set and are bump-allocated, and the
+//! encode buffer is handed back after the WAL append. Once a pooled context
+//! has committed a transaction of a given size, later commits of that size
+//! allocate nothing for either.
+
+use bumpalo::Bump;
+
+/// Bump arena and reusable encode buffer scoped to one transaction
+///
+/// Reset whenever the owning context is reset for reuse; the largest bump
+/// chunk and the buffer's capacity are kept.
+#[derive(Default)]
+pub(crate) struct TransactionArena {
+    bump: Bump,
+    buffer: Vec<u8>,
+}
+
+impl TransactionArena {
+    /// Create an empty arena (allocates nothing until first used)
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bump allocator for commit-time views of the write set
+    pub(crate) fn bump(&self) -> &Bump {
+        &self.bump
+    }
+
+    /// Take the encode buffer, empty but with its retained capacity
+    ///
+    /// Give it back with `restore_buffer` once the bytes have been written.
+    pub(crate) fn take_buffer(&mut self) -> Vec<u8> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        buffer
+    }
+
+    /// Return a buffer obtained from `take_buffer` for the next commit
+    pub(crate) fn restore_buffer(&mut self, buffer: Vec<u8>) {
+        if buffer.capacity() > self.buffer.capacity() {
+            self.buffer = buffer;
+        }
+    }
+
+    /// Free everything allocated for the previous transaction
+    pub(crate) fn reset(&mut self) {
+        self.bump.reset();
+        self.buffer.clear();
+    }
+
+    /// Free everything, including the retained chunk and buffer capacity
+    pub(crate) fn release(&mut self) {
+        self.bump = Bump::new();
+        self.buffer = Vec::new();
+    }
+
+    /// Bytes currently held by the arena and the encode buffer
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.bump.allocated_bytes() + self.buffer.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_capacity_survives_reset() {
+        let mut arena = TransactionArena::new();
+        let mut buffer = arena.take_buffer();
+        buffer.extend_from_slice(&[7u8; 1000]);
+        arena.restore_buffer(buffer);
+
+        arena.reset();
+        let buffer = arena.take_buffer();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 1000);
+    }
+
+    #[test]
+    fn test_release_frees_memory() {
+        let mut arena = TransactionArena::new();
+        arena.bump().alloc_slice_fill_copy(64 * 1024, 0u8);
+        arena.restore_buffer(Vec::with_capacity(64 * 1024));
+        assert!(arena.allocated_bytes() >= 128 * 1024);
+
+        arena.release();
+        assert_eq!(arena.allocated_bytes(), 0);
+    }
+}
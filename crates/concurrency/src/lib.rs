@@ -9,21 +9,29 @@
 //! - Compare-and-swap (CAS) operations
 //! - WAL integration for durability
 //! - JSON region-based conflict detection
+//! - Pessimistic per-key locks for hot keys
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub(crate) mod arena;
 pub(crate) mod conflict;
+pub mod locks;
 pub mod manager;
 pub mod payload;
 pub mod recovery;
 pub mod snapshot;
+mod sync;
 pub mod transaction;
 pub mod validation;
 
+pub use locks::{KeyLockTable, LockMode, DEFAULT_LOCK_TIMEOUT};
 pub use manager::TransactionManager;
 pub use payload::TransactionPayload;
-pub use recovery::{RecoveryCoordinator, RecoveryResult, RecoveryStats};
+pub use recovery::{
+    CancellationToken, RecoveryCoordinator, RecoveryObserver, RecoveryProgress, RecoveryProgressFn,
+    RecoveryResult, RecoveryStats,
+};
 pub use snapshot::ClonedSnapshotView;
 pub use transaction::{CommitError, JsonStoreExt, TransactionContext, TransactionStatus};
 
@@ -0,0 +1,178 @@
+//! Pessimistic key locks
+//!
+//! Optimistic transactions that all update the same hot key keep failing
+//! validation and retrying. A transaction can lock the key instead: the
+//! [`KeyLockTable`] hands each key to one transaction at a time, so other
+//! lockers wait for the holder to commit or abort rather than conflicting
+//! with it.
+//!
+//! Locks live in memory and are held until the transaction ends. A
+//! transaction that waits longer than the table's timeout fails with a
+//! retryable conflict, which also breaks deadlocks between transactions
+//! that lock keys in different orders. Writers that do not lock are still
+//! caught by commit-time validation.
+
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use strata_core::types::Key;
+use strata_core::{StrataError, StrataResult};
+
+/// How long a transaction waits for a locked key by default
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a transaction guards the keys it touches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockMode {
+    /// Detect conflicting writers at commit (the default)
+    #[default]
+    Optimistic,
+    /// Lock every key on its first read or write
+    Pessimistic,
+}
+
+/// Keys currently locked and the transaction holding each
+///
+/// One table is shared by all transactions of a database.
+pub struct KeyLockTable {
+    owners: Mutex<HashMap<Key, u64>>,
+    released: Condvar,
+    timeout: Duration,
+}
+
+impl KeyLockTable {
+    /// Create a table whose lockers give up after `timeout`
+    pub fn new(timeout: Duration) -> Self {
+        KeyLockTable {
+            owners: Mutex::new(HashMap::new()),
+            released: Condvar::new(),
+            timeout,
+        }
+    }
+
+    /// How long `acquire` waits for a held key
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Lock `key` for transaction `txn_id`, waiting for its current holder
+    ///
+    /// Locking a key the transaction already holds succeeds at once.
+    ///
+    /// # Errors
+    /// `Conflict` if the key is still held by another transaction after the
+    /// timeout.
+    pub fn acquire(&self, key: &Key, txn_id: u64) -> StrataResult<()> {
+        let deadline = Instant::now() + self.timeout;
+        let mut owners = self.owners.lock();
+        loop {
+            match owners.get(key) {
+                None => {
+                    owners.insert(key.clone(), txn_id);
+                    return Ok(());
+                }
+                Some(&owner) if owner == txn_id => return Ok(()),
+                Some(_) => {}
+            }
+            if self.released.wait_until(&mut owners, deadline).timed_out()
+                && owners.contains_key(key)
+            {
+                return Err(StrataError::conflict(format!(
+                    "timed out after {}ms waiting for lock on {:?}",
+                    self.timeout.as_millis(),
+                    key
+                )));
+            }
+        }
+    }
+
+    /// Unlock the `keys` held by transaction `txn_id`
+    pub fn release<'a>(&self, keys: impl IntoIterator<Item = &'a Key>, txn_id: u64) {
+        let mut owners = self.owners.lock();
+        let mut released = false;
+        for key in keys {
+            if owners.get(key) == Some(&txn_id) {
+                owners.remove(key);
+                released = true;
+            }
+        }
+        if released {
+            self.released.notify_all();
+        }
+    }
+
+    /// Number of keys currently locked
+    pub fn len(&self) -> usize {
+        self.owners.lock().len()
+    }
+
+    /// Whether no key is locked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for KeyLockTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_TIMEOUT)
+    }
+}
+
+impl std::fmt::Debug for KeyLockTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyLockTable")
+            .field("locked", &self.len())
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use strata_core::types::{BranchId, Namespace};
+
+    fn key(name: &str) -> Key {
+        Key::new_kv(Namespace::for_branch(BranchId::new()), name)
+    }
+
+    #[test]
+    fn test_holder_blocks_other_lockers_until_release() {
+        let table = Arc::new(KeyLockTable::new(Duration::from_secs(5)));
+        let k = key("counter");
+        table.acquire(&k, 1).unwrap();
+        table.acquire(&k, 1).unwrap();
+        assert_eq!(table.len(), 1);
+
+        let waiter = {
+            let (table, k) = (Arc::clone(&table), k.clone());
+            std::thread::spawn(move || {
+                let start = Instant::now();
+                table.acquire(&k, 2).unwrap();
+                start.elapsed()
+            })
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        table.release([&k], 1);
+        assert!(waiter.join().unwrap() >= Duration::from_millis(40));
+
+        // Only the holder can release
+        table.release([&k], 1);
+        assert_eq!(table.len(), 1);
+        table.release([&k], 2);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_waiting_past_timeout_is_a_retryable_conflict() {
+        let table = KeyLockTable::new(Duration::from_millis(20));
+        let k = key("counter");
+        table.acquire(&k, 1).unwrap();
+        let err = table.acquire(&k, 2).unwrap_err();
+        assert!(err.is_retryable());
+        table.acquire(&key("other"), 2).unwrap();
+    }
+}
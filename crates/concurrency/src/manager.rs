@@ -26,12 +26,23 @@
 //!
 //! If crash occurs before step 7: Transaction is not durable, discarded on recovery.
 //! If crash occurs after step 7: Transaction is durable, replayed on recovery.
+//!
+//! With [`TransactionManager::commit_pipelined`] the WAL mutex covers only
+//! steps 5-7; step 8 runs after it is released, and the version becomes
+//! visible once every lower version has been applied.
+//!
+//! ## Tracing
+//!
+//! With [`TransactionManager::set_commit_tracing`] enabled, every commit runs
+//! in an `INFO` span `commit` (target `strata::txn`) carrying `branch_id`,
+//! `txn_id` and `keys`, with child spans `validate`, `wal_append` and
+//! `apply` for steps 2, 5-7 and 8. Disabled, no spans are created.
 
 use crate::payload::TransactionPayload;
+use crate::sync::{AtomicU64, BranchLocks, Ordering};
 use crate::{CommitError, TransactionContext, TransactionStatus};
-use dashmap::DashMap;
 use parking_lot::Mutex;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::AtomicBool;
 use strata_core::traits::Storage;
 use strata_core::types::BranchId;
 use strata_durability::format::WalRecord;
@@ -57,6 +68,9 @@ use strata_durability::wal::WalWriter;
 ///
 /// Transactions on different branches can commit in parallel, as ShardedStore
 /// maintains per-branch shards and there's no cross-branch conflict.
+/// A transaction whose keys span several branches takes each branch's lock,
+/// in branch ID order, and commits to all of them with one version and one
+/// WAL record.
 pub struct TransactionManager {
     /// Global version counter
     ///
@@ -80,7 +94,22 @@ pub struct TransactionManager {
     ///
     /// Using per-branch locks allows parallel commits for different branches while
     /// still preventing TOCTOU within each branch.
-    commit_locks: DashMap<BranchId, Mutex<()>>,
+    commit_locks: BranchLocks,
+
+    /// Whether commits run inside `tracing` spans
+    trace_commits: AtomicBool,
+}
+
+/// A span on the commit path, or [`Span::none`](tracing::Span::none) when
+/// commit tracing is off
+macro_rules! commit_span {
+    ($enabled:expr, $name:literal $(, $($field:tt)*)?) => {
+        if $enabled {
+            tracing::info_span!(target: "strata::txn", $name $(, $($field)*)?)
+        } else {
+            tracing::Span::none()
+        }
+    };
 }
 
 impl TransactionManager {
@@ -105,10 +134,23 @@ impl TransactionManager {
             version: AtomicU64::new(initial_version),
             // Start next_txn_id at max_txn_id + 1 to avoid conflicts
             next_txn_id: AtomicU64::new(max_txn_id + 1),
-            commit_locks: DashMap::new(),
+            commit_locks: BranchLocks::new(),
+            trace_commits: AtomicBool::new(false),
         }
     }
 
+    /// Enable or disable `tracing` spans on the commit path
+    ///
+    /// Off by default, so commits pay nothing for the spans unless asked.
+    pub fn set_commit_tracing(&self, enabled: bool) {
+        self.trace_commits.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether commits run inside `tracing` spans
+    pub fn commit_tracing(&self) -> bool {
+        self.trace_commits.load(Ordering::Relaxed)
+    }
+
     /// Get current global version
     pub fn current_version(&self) -> u64 {
         self.version.load(Ordering::SeqCst)
@@ -170,7 +212,8 @@ impl TransactionManager {
     ///
     /// # Commit Sequence
     ///
-    /// 1. Acquire per-branch commit lock (prevents TOCTOU race within same branch)
+    /// 1. Acquire per-branch commit lock (prevents TOCTOU race within same branch);
+    ///    multi-branch transactions lock every branch they touch
     /// 2. Validate and mark committed (in-memory state transition)
     /// 3. Allocate commit version
     /// 4. Write to WAL if provided (BeginTxn, operations, CommitTxn)
@@ -188,12 +231,92 @@ impl TransactionManager {
     /// TOCTOU race where validation passes but storage changes before apply.
     ///
     /// Transactions on different branches can commit in parallel.
+    ///
+    /// # Panic Safety
+    ///
+    /// A panic in any commit phase releases the branch commit lock and marks
+    /// the transaction aborted before the panic continues to the caller, so
+    /// later commits on the branch proceed normally. The WAL record is only
+    /// appended whole; if the panic struck after it was written, recovery
+    /// replays the transaction, otherwise nothing of it is durable.
     pub fn commit<S: Storage>(
         &self,
         txn: &mut TransactionContext,
         store: &S,
-        mut wal: Option<&mut WalWriter>,
+        wal: Option<&mut WalWriter>,
+    ) -> std::result::Result<u64, CommitError> {
+        let wal = match wal {
+            Some(wal) => WalAccess::Exclusive(wal),
+            None => WalAccess::None,
+        };
+        self.commit_with(txn, store, wal)
+    }
+
+    /// Commit a transaction, locking the WAL only for the append
+    ///
+    /// Same protocol as [`commit`](Self::commit), but the WAL mutex is held
+    /// only while the commit version is allocated and the record appended.
+    /// Storage apply runs after the mutex is released, under the branch
+    /// commit lock alone, so commits on different branches apply in
+    /// parallel while the next commit writes the WAL.
+    ///
+    /// WAL order is still commit order: versions are allocated under the WAL
+    /// mutex. Each version is reserved in the store until its writes are
+    /// applied (see [`Storage::reserve_version`]), so snapshots never see a
+    /// commit before every earlier one. This returns once the commit version
+    /// is visible.
+    ///
+    /// Callers that need storage to reflect every appended commit while
+    /// holding the WAL mutex must wait for in-flight applies themselves.
+    pub fn commit_pipelined<S: Storage>(
+        &self,
+        txn: &mut TransactionContext,
+        store: &S,
+        wal: Option<&Mutex<WalWriter>>,
+    ) -> std::result::Result<u64, CommitError> {
+        let wal = match wal {
+            Some(wal) => WalAccess::Shared(wal),
+            None => WalAccess::None,
+        };
+        self.commit_with(txn, store, wal)
+    }
+
+    fn commit_with<S: Storage>(
+        &self,
+        txn: &mut TransactionContext,
+        store: &S,
+        wal: WalAccess<'_>,
+    ) -> std::result::Result<u64, CommitError> {
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            self.commit_inner(txn, store, wal)
+        }));
+        match outcome {
+            Ok(result) => result,
+            Err(panic) => {
+                tracing::error!(target: "strata::txn", txn_id = txn.txn_id, "Commit panicked; transaction aborted");
+                txn.status = TransactionStatus::Aborted {
+                    reason: "panicked during commit".to_string(),
+                };
+                std::panic::resume_unwind(panic)
+            }
+        }
+    }
+
+    fn commit_inner<S: Storage>(
+        &self,
+        txn: &mut TransactionContext,
+        store: &S,
+        wal: WalAccess<'_>,
     ) -> std::result::Result<u64, CommitError> {
+        let span = commit_span!(
+            self.commit_tracing(),
+            "commit",
+            branch_id = %txn.branch_id,
+            txn_id = txn.txn_id,
+            keys = txn.write_count() + txn.delete_count() + txn.cas_count(),
+        );
+        let _entered = span.enter();
+
         // Fast path: read-only transactions skip lock, validation, version alloc, WAL, apply
         if txn.is_read_only() && txn.json_writes().is_empty() {
             if !txn.is_active() {
@@ -210,11 +333,33 @@ impl TransactionManager {
         // This ensures no other transaction on the same branch can modify storage between
         // our validation check and our apply_writes call.
         // Transactions on different branches can proceed in parallel.
-        let branch_lock = self
-            .commit_locks
-            .entry(txn.branch_id)
-            .or_insert_with(|| Mutex::new(()));
-        let _commit_guard = branch_lock.lock();
+        // A transaction that also touches other branches holds all their locks,
+        // so its writes land in every branch under the one commit version.
+        let other_branches = txn.other_branches();
+        let (commit_version, reservation) = if other_branches.is_empty() {
+            self.commit_locks
+                .with_lock(txn.branch_id, || self.commit_locked(txn, store, wal))?
+        } else {
+            let branches = txn.branches();
+            self.commit_locks
+                .with_locks(&branches, || self.commit_locked(txn, store, wal))?
+        };
+
+        // Publish outside the branch lock: waiting for earlier commits to
+        // finish applying must not hold up the next commit on this branch
+        drop(reservation);
+        Ok(commit_version)
+    }
+
+    /// Validate, allocate a version, write the WAL and apply, with the
+    /// branch commit lock held
+    fn commit_locked<'s, S: Storage>(
+        &self,
+        txn: &mut TransactionContext,
+        store: &'s S,
+        wal: WalAccess<'_>,
+    ) -> std::result::Result<(u64, Option<ReservedVersion<'s, S>>), CommitError> {
+        let tracing = self.commit_tracing();
 
         // Step 1: Validate and mark committed (in-memory)
         // This performs: Active → Validating → Committed
@@ -234,6 +379,7 @@ impl TransactionManager {
             }
             txn.status = TransactionStatus::Committed;
         } else {
+            let _validate = commit_span!(tracing, "validate").entered();
             txn.commit(store)?;
             tracing::debug!(target: "strata::txn", txn_id = txn.txn_id, "Validation passed");
         }
@@ -241,38 +387,37 @@ impl TransactionManager {
         // At this point, transaction is in Committed state
         // but NOT yet durable (not in WAL)
 
-        // Step 2: Allocate commit version
-        let commit_version = self.allocate_version();
-
-        // Step 3: Write to WAL (durability) - only for transactions with mutations
+        // Steps 2 and 3: Allocate commit version and write to WAL (durability)
         // Skip WAL for read-only transactions (no writes, deletes, CAS ops, or JSON patches)
         let has_mutations = !txn.is_read_only() || !txn.json_writes().is_empty();
-        if has_mutations {
-            if let Some(wal) = wal.as_mut() {
-                let payload = TransactionPayload::from_transaction(txn, commit_version);
-                let record = WalRecord::new(
-                    txn.txn_id,
-                    *txn.branch_id.as_bytes(),
-                    now_micros(),
-                    payload.to_bytes(),
-                );
-
-                if let Err(e) = wal.append(&record) {
-                    txn.status = TransactionStatus::Aborted {
-                        reason: format!("WAL write failed: {}", e),
-                    };
-                    return Err(CommitError::WALError(e.to_string()));
-                }
-
-                // DURABILITY POINT: Transaction is now durable
-                // Even if we crash after this, recovery will replay from WAL
-                tracing::debug!(target: "strata::txn", txn_id = txn.txn_id, commit_version, "WAL durable");
+        let mut reservation = None;
+        let writes_wal = has_mutations && !matches!(wal, WalAccess::None);
+        let wal_span = commit_span!(tracing && writes_wal, "wal_append");
+        let wal_entered = wal_span.enter();
+        let (commit_version, wal_written) = match wal {
+            WalAccess::Exclusive(wal) if has_mutations => {
+                let commit_version = self.allocate_version();
+                self.append_to_wal(txn, commit_version, wal)?;
+                (commit_version, true)
             }
-        }
+            WalAccess::Shared(wal) if has_mutations => {
+                // Allocate under the WAL lock so WAL order is version order,
+                // and keep the version invisible until it has been applied
+                let mut wal = wal.lock();
+                let commit_version = self.allocate_version();
+                let reserved = ReservedVersion::new(store, commit_version);
+                self.append_to_wal(txn, commit_version, &mut wal)?;
+                reservation = Some(reserved);
+                (commit_version, true)
+            }
+            _ => (self.allocate_version(), false),
+        };
+        drop(wal_entered);
 
         // Step 4: Apply to storage
+        let _apply = commit_span!(tracing, "apply").entered();
         if let Err(e) = txn.apply_writes(store, commit_version) {
-            if wal.is_some() {
+            if wal_written {
                 // WAL says committed but storage failed - serious error
                 // Log error but return success since WAL is authoritative
                 // Recovery will replay the transaction anyway
@@ -296,7 +441,40 @@ impl TransactionManager {
         }
 
         // Step 5: Return commit version
-        Ok(commit_version)
+        Ok((commit_version, reservation))
+    }
+
+    /// Encode the transaction and append it to the WAL as one record
+    fn append_to_wal(
+        &self,
+        txn: &mut TransactionContext,
+        commit_version: u64,
+        wal: &mut WalWriter,
+    ) -> std::result::Result<(), CommitError> {
+        // Encode into the context's pooled buffer and hand it back
+        // after the append so the next commit reuses its capacity
+        let mut writeset = txn.arena.take_buffer();
+        TransactionPayload::encode_transaction(txn, commit_version, &mut writeset);
+        let record = WalRecord::new(
+            txn.txn_id,
+            *txn.branch_id.as_bytes(),
+            now_micros(),
+            writeset,
+        );
+        let appended = wal.append(&record);
+        txn.arena.restore_buffer(record.writeset);
+
+        if let Err(e) = appended {
+            txn.status = TransactionStatus::Aborted {
+                reason: format!("WAL write failed: {}", e),
+            };
+            return Err(CommitError::WALError(e.to_string()));
+        }
+
+        // DURABILITY POINT: Transaction is now durable
+        // Even if we crash after this, recovery will replay from WAL
+        tracing::debug!(target: "strata::txn", txn_id = txn.txn_id, commit_version, "WAL durable");
+        Ok(())
     }
 
     /// Remove the per-branch commit lock for a deleted branch.
@@ -309,12 +487,44 @@ impl TransactionManager {
     /// This should only be called after the branch has been fully deleted
     /// and no further transactions will target it. If a concurrent transaction
     /// is in-flight for this branch, the lock will be lazily re-created on
-    /// next commit (via `or_insert_with` in `commit()`).
+    /// next commit.
     pub fn remove_branch_lock(&self, branch_id: &BranchId) {
         self.commit_locks.remove(branch_id);
     }
 }
 
+/// How a commit reaches the WAL
+enum WalAccess<'a> {
+    /// No WAL (ephemeral database or cache durability)
+    None,
+    /// The caller holds the WAL for the whole commit
+    Exclusive(&'a mut WalWriter),
+    /// The WAL is locked only around version allocation and the append
+    Shared(&'a Mutex<WalWriter>),
+}
+
+/// A commit version reserved in the store, published when dropped
+///
+/// Dropping also covers failed and panicking commits, so a reserved version
+/// can never hold back visibility of later commits.
+struct ReservedVersion<'s, S: Storage> {
+    store: &'s S,
+    version: u64,
+}
+
+impl<'s, S: Storage> ReservedVersion<'s, S> {
+    fn new(store: &'s S, version: u64) -> Self {
+        store.reserve_version(version);
+        ReservedVersion { store, version }
+    }
+}
+
+impl<S: Storage> Drop for ReservedVersion<'_, S> {
+    fn drop(&mut self) {
+        self.store.publish_version(self.version);
+    }
+}
+
 impl Default for TransactionManager {
     fn default() -> Self {
         Self::new(0)
@@ -326,11 +536,13 @@ mod tests {
     use super::*;
     use crate::TransactionContext;
     use parking_lot::Mutex as ParkingMutex;
+    use std::sync::atomic::AtomicU8;
     use std::sync::Arc;
+    use strata_core::traits::SnapshotView;
     use strata_core::types::{Key, Namespace};
     use strata_core::value::Value;
-    use strata_durability::codec::IdentityCodec;
-    use strata_durability::wal::{DurabilityMode, WalConfig};
+    use strata_durability::codec::{CodecError, IdentityCodec, StorageCodec};
+    use strata_durability::wal::{DurabilityMode, WalConfig, WalReader};
     use strata_storage::ShardedStore;
     use tempfile::TempDir;
 
@@ -449,6 +661,55 @@ mod tests {
         assert!(store.get(&key2).unwrap().is_some());
     }
 
+    #[test]
+    fn test_held_branch_lock_does_not_block_other_branches() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let manager = Arc::new(TransactionManager::new(0));
+        let store = Arc::new(ShardedStore::new());
+
+        // Hold one branch's commit lock until told to release it
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let holder = {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || {
+                manager.commit_locks.with_lock(BranchId::new(), || {
+                    locked_tx.send(()).unwrap();
+                    release_rx.recv().unwrap();
+                })
+            })
+        };
+        locked_rx.recv().unwrap();
+
+        // Enough branches that some share a lock-map shard with the held one
+        let (done_tx, done_rx) = mpsc::channel();
+        let committer = {
+            let manager = Arc::clone(&manager);
+            let store = Arc::clone(&store);
+            std::thread::spawn(move || {
+                for i in 0..256 {
+                    let branch_id = BranchId::new();
+                    let key = create_test_key(&create_test_namespace(branch_id), "k");
+                    let mut txn = TransactionContext::new(i + 1, branch_id, 0);
+                    txn.put(key, Value::Int(i as i64)).unwrap();
+                    manager.commit(&mut txn, store.as_ref(), None).unwrap();
+                }
+                done_tx.send(()).unwrap();
+            })
+        };
+
+        let finished = done_rx.recv_timeout(Duration::from_secs(10)).is_ok();
+        release_tx.send(()).unwrap();
+        holder.join().unwrap();
+        committer.join().unwrap();
+        assert!(
+            finished,
+            "commits on other branches waited for an unrelated branch lock"
+        );
+    }
+
     #[test]
     fn test_same_branch_commits_serialize() {
         // This test verifies that commits on the same branch are serialized
@@ -874,4 +1135,315 @@ mod tests {
         // Verify it went through the normal path (version incremented)
         assert!(manager.current_version() > 0);
     }
+
+    // ========================================================================
+    // Panic Safety Tests
+    // ========================================================================
+
+    const PANIC_NONE: u8 = 0;
+    const PANIC_VALIDATE: u8 = 1;
+    const PANIC_WAL: u8 = 2;
+    const PANIC_APPLY: u8 = 3;
+
+    /// Storage that panics on validation reads or on apply when armed
+    struct PanicStore {
+        inner: Arc<ShardedStore>,
+        armed: Arc<AtomicU8>,
+    }
+
+    impl PanicStore {
+        fn trip(&self, phase: u8) {
+            if self.armed.load(Ordering::SeqCst) == phase {
+                panic!("injected panic in commit phase {}", phase);
+            }
+        }
+    }
+
+    impl Storage for PanicStore {
+        fn get(&self, key: &Key) -> strata_core::StrataResult<Option<strata_core::VersionedValue>> {
+            self.trip(PANIC_VALIDATE);
+            self.inner.get(key)
+        }
+        fn get_versioned(
+            &self,
+            key: &Key,
+            max_version: u64,
+        ) -> strata_core::StrataResult<Option<strata_core::VersionedValue>> {
+            self.inner.get_versioned(key, max_version)
+        }
+        fn get_history(
+            &self,
+            key: &Key,
+            limit: Option<usize>,
+            before_version: Option<u64>,
+        ) -> strata_core::StrataResult<Vec<strata_core::VersionedValue>> {
+            self.inner.get_history(key, limit, before_version)
+        }
+        fn put(
+            &self,
+            key: Key,
+            value: Value,
+            ttl: Option<std::time::Duration>,
+        ) -> strata_core::StrataResult<u64> {
+            Storage::put(self.inner.as_ref(), key, value, ttl)
+        }
+        fn delete(
+            &self,
+            key: &Key,
+        ) -> strata_core::StrataResult<Option<strata_core::VersionedValue>> {
+            Storage::delete(self.inner.as_ref(), key)
+        }
+        fn scan_prefix(
+            &self,
+            prefix: &Key,
+            max_version: u64,
+        ) -> strata_core::StrataResult<Vec<(Key, strata_core::VersionedValue)>> {
+            Storage::scan_prefix(self.inner.as_ref(), prefix, max_version)
+        }
+        fn scan_by_branch(
+            &self,
+            branch_id: BranchId,
+            max_version: u64,
+        ) -> strata_core::StrataResult<Vec<(Key, strata_core::VersionedValue)>> {
+            self.inner.scan_by_branch(branch_id, max_version)
+        }
+        fn current_version(&self) -> u64 {
+            self.inner.current_version()
+        }
+        fn put_with_version(
+            &self,
+            key: Key,
+            value: Value,
+            version: u64,
+            ttl: Option<std::time::Duration>,
+        ) -> strata_core::StrataResult<()> {
+            self.trip(PANIC_APPLY);
+            self.inner.put_with_version(key, value, version, ttl)
+        }
+        fn delete_with_version(
+            &self,
+            key: &Key,
+            version: u64,
+        ) -> strata_core::StrataResult<Option<strata_core::VersionedValue>> {
+            Storage::delete_with_version(self.inner.as_ref(), key, version)
+        }
+    }
+
+    /// Identity codec that panics while encoding a WAL record when armed
+    struct PanicCodec(Arc<AtomicU8>);
+
+    impl StorageCodec for PanicCodec {
+        fn encode(&self, data: &[u8]) -> Vec<u8> {
+            if self.0.load(Ordering::SeqCst) == PANIC_WAL {
+                panic!("injected panic in commit phase {}", PANIC_WAL);
+            }
+            data.to_vec()
+        }
+        fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+            Ok(data.to_vec())
+        }
+        fn codec_id(&self) -> &str {
+            "identity"
+        }
+    }
+
+    #[test]
+    fn test_commit_survives_panic_in_each_phase() {
+        for phase in [PANIC_VALIDATE, PANIC_WAL, PANIC_APPLY] {
+            let temp_dir = TempDir::new().unwrap();
+            let wal_dir = temp_dir.path().join("wal");
+            let armed = Arc::new(AtomicU8::new(PANIC_NONE));
+            let mut wal = WalWriter::new(
+                wal_dir.clone(),
+                [0u8; 16],
+                DurabilityMode::Always,
+                WalConfig::for_testing(),
+                Box::new(PanicCodec(Arc::clone(&armed))),
+            )
+            .unwrap();
+            let store = PanicStore {
+                inner: Arc::new(ShardedStore::new()),
+                armed: Arc::clone(&armed),
+            };
+            let manager = TransactionManager::new(0);
+            let branch_id = BranchId::new();
+            let key = create_test_key(&create_test_namespace(branch_id), "k");
+
+            // Read before writing so the commit goes through validation
+            let mut txn =
+                TransactionContext::with_snapshot(1, branch_id, Box::new(store.inner.snapshot()));
+            let _ = txn.get(&key).unwrap();
+            txn.put(key.clone(), Value::Int(1)).unwrap();
+
+            armed.store(phase, Ordering::SeqCst);
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                manager.commit(&mut txn, &store, Some(&mut wal))
+            }));
+            armed.store(PANIC_NONE, Ordering::SeqCst);
+            assert!(outcome.is_err(), "phase {} should panic", phase);
+            assert!(
+                matches!(txn.status, TransactionStatus::Aborted { .. }),
+                "phase {} left {:?}",
+                phase,
+                txn.status
+            );
+
+            // The branch lock was released and the WAL still takes records
+            let mut next =
+                TransactionContext::with_snapshot(2, branch_id, Box::new(store.inner.snapshot()));
+            let _ = next.get(&key).unwrap();
+            next.put(key.clone(), Value::Int(2)).unwrap();
+            manager.commit(&mut next, &store, Some(&mut wal)).unwrap();
+            assert_eq!(store.inner.get(&key).unwrap().unwrap().value, Value::Int(2));
+
+            // Only a panic after the WAL append leaves the first record behind,
+            // and every record reads back intact
+            drop(wal);
+            let records = WalReader::new(Box::new(IdentityCodec))
+                .read_all(&wal_dir)
+                .unwrap()
+                .records;
+            let expected = if phase == PANIC_APPLY { 2 } else { 1 };
+            assert_eq!(records.len(), expected, "phase {}", phase);
+        }
+    }
+
+    #[test]
+    fn test_pipelined_commits_keep_wal_order_and_visibility() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+        let wal = Arc::new(ParkingMutex::new(create_test_wal(&wal_dir)));
+        let store = Arc::new(ShardedStore::new());
+        let manager = Arc::new(TransactionManager::new(0));
+
+        let num_threads = 8;
+        let commits_per_thread = 25;
+        let handles: Vec<_> = (0..num_threads)
+            .map(|t| {
+                let manager = Arc::clone(&manager);
+                let store = Arc::clone(&store);
+                let wal = Arc::clone(&wal);
+                std::thread::spawn(move || {
+                    let branch_id = BranchId::new();
+                    let ns = create_test_namespace(branch_id);
+                    for i in 0..commits_per_thread {
+                        let key = create_test_key(&ns, &format!("key_{}", i));
+                        let mut txn = TransactionContext::with_snapshot(
+                            (t * commits_per_thread + i) as u64 + 1,
+                            branch_id,
+                            Box::new(store.snapshot()),
+                        );
+                        txn.put(key.clone(), Value::Int(i as i64)).unwrap();
+                        let version = manager
+                            .commit_pipelined(&mut txn, store.as_ref(), Some(&*wal))
+                            .unwrap();
+
+                        // A returned commit is visible to the next snapshot
+                        let snapshot = store.snapshot();
+                        assert!(snapshot.version() >= version);
+                        assert_eq!(
+                            SnapshotView::get(&snapshot, &key).unwrap().unwrap().value,
+                            Value::Int(i as i64)
+                        );
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let total = (num_threads * commits_per_thread) as u64;
+        assert_eq!(store.version(), total);
+
+        // WAL order is commit-version order
+        drop(wal);
+        let versions: Vec<u64> = WalReader::new(Box::new(IdentityCodec))
+            .read_all(&wal_dir)
+            .unwrap()
+            .records
+            .iter()
+            .map(|r| TransactionPayload::from_bytes(&r.writeset).unwrap().version)
+            .collect();
+        assert_eq!(versions, (1..=total).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_pipelined_commit_without_wal_applies() {
+        let store = ShardedStore::new();
+        let manager = TransactionManager::new(0);
+        let branch_id = BranchId::new();
+        let key = create_test_key(&create_test_namespace(branch_id), "k");
+
+        let mut txn = TransactionContext::new(1, branch_id, 0);
+        txn.put(key.clone(), Value::Int(7)).unwrap();
+        let version = manager.commit_pipelined(&mut txn, &store, None).unwrap();
+
+        assert_eq!(version, 1);
+        assert_eq!(store.version(), 1);
+        assert_eq!(store.get(&key).unwrap().unwrap().value, Value::Int(7));
+    }
+
+    /// Records the name and fields of every span created under it
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<ParkingMutex<Vec<(&'static str, String)>>>);
+
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut fields = String::new();
+            attrs.record(
+                &mut |field: &tracing::field::Field, value: &dyn std::fmt::Debug| {
+                    fields.push_str(&format!("{}={:?} ", field.name(), value));
+                },
+            );
+            let mut spans = self.0.lock();
+            spans.push((attrs.metadata().name(), fields));
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {}
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test]
+    fn test_commit_spans_only_when_tracing_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal = ParkingMutex::new(create_test_wal(&temp_dir.path().join("wal")));
+        let store = Arc::new(ShardedStore::new());
+        let manager = TransactionManager::new(0);
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        let commit = |txn_id: u64| {
+            let mut txn =
+                TransactionContext::with_snapshot(txn_id, branch_id, Box::new(store.snapshot()));
+            let _ = txn.get(&create_test_key(&ns, "a")).unwrap();
+            txn.put(create_test_key(&ns, "a"), Value::Int(1)).unwrap();
+            txn.put(create_test_key(&ns, "b"), Value::Int(2)).unwrap();
+            manager
+                .commit_pipelined(&mut txn, store.as_ref(), Some(&wal))
+                .unwrap();
+        };
+
+        let recorder = SpanRecorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || commit(1));
+        assert!(recorder.0.lock().is_empty());
+
+        manager.set_commit_tracing(true);
+        tracing::subscriber::with_default(recorder.clone(), || commit(2));
+        let spans = recorder.0.lock();
+        let names: Vec<_> = spans.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["commit", "validate", "wal_append", "apply"]);
+        let fields = &spans[0].1;
+        assert!(fields.contains(&format!("branch_id={}", branch_id)));
+        assert!(fields.contains("txn_id=2"));
+        assert!(fields.contains("keys=2"));
+    }
 }
@@ -10,6 +10,7 @@
 //! The payload is serialized using MessagePack (`rmp-serde`) for compact
 //! binary encoding with schema evolution support.
 
+use bumpalo::collections::Vec as BumpVec;
 use serde::{Deserialize, Serialize};
 use strata_core::types::Key;
 use strata_core::value::Value;
@@ -29,6 +30,13 @@ pub struct TransactionPayload {
     pub puts: Vec<(Key, Value)>,
     /// Keys to delete (from delete_set)
     pub deletes: Vec<Key>,
+    /// Keys whose older versions were pruned before this record was written
+    ///
+    /// Only set by WAL compaction, so recovery can tell trimmed history from
+    /// a key that never had more versions. Omitted from the encoding when
+    /// empty, so commit records are unchanged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trimmed: Vec<Key>,
 }
 
 impl TransactionPayload {
@@ -64,8 +72,42 @@ impl TransactionPayload {
             version,
             puts,
             deletes,
+            trimmed: Vec::new(),
         }
     }
+
+    /// Encode a committed transaction's payload, appending to `buf`.
+    ///
+    /// Produces the same bytes as `from_transaction(txn, version).to_bytes()`
+    /// without cloning the write set: the entry lists borrow from it and are
+    /// allocated in the transaction's arena.
+    pub fn encode_transaction(txn: &TransactionContext, version: u64, buf: &mut Vec<u8>) {
+        let bump = txn.arena.bump();
+
+        let mut puts = BumpVec::with_capacity_in(txn.write_set.len() + txn.cas_set.len(), bump);
+        puts.extend(txn.write_set.iter());
+        // CAS operations become puts, as in `from_transaction`
+        puts.extend(txn.cas_set.iter().map(|op| (&op.key, &op.new_value)));
+
+        let mut deletes = BumpVec::with_capacity_in(txn.delete_set.len(), bump);
+        deletes.extend(txn.delete_set.iter());
+
+        let view = PayloadView {
+            version,
+            puts: &puts,
+            deletes: &deletes,
+        };
+        rmp_serde::encode::write(buf, &view)
+            .expect("TransactionPayload serialization should not fail");
+    }
+}
+
+/// Borrowed `TransactionPayload`; fields match it so the encodings agree.
+#[derive(Serialize)]
+struct PayloadView<'a> {
+    version: u64,
+    puts: &'a [(&'a Key, &'a Value)],
+    deletes: &'a [&'a Key],
 }
 
 /// Errors from payload serialization/deserialization.
@@ -99,6 +141,7 @@ mod tests {
             version: 42,
             puts: vec![],
             deletes: vec![],
+            trimmed: vec![],
         };
         let bytes = payload.to_bytes();
         let decoded = TransactionPayload::from_bytes(&bytes).unwrap();
@@ -121,6 +164,7 @@ mod tests {
                 (key2.clone(), Value::String("hello".to_string())),
             ],
             deletes: vec![key3.clone()],
+            trimmed: vec![],
         };
 
         let bytes = payload.to_bytes();
@@ -134,6 +178,57 @@ mod tests {
         assert_eq!(decoded.deletes[0], key3);
     }
 
+    #[test]
+    fn test_roundtrip_trimmed_keys() {
+        let key = Key::new_kv(test_ns(), "key");
+        let payload = TransactionPayload {
+            version: 5,
+            puts: vec![],
+            deletes: vec![],
+            trimmed: vec![key.clone()],
+        };
+        let decoded = TransactionPayload::from_bytes(&payload.to_bytes()).unwrap();
+        assert_eq!(decoded.trimmed, vec![key]);
+
+        // Records without trimmed keys keep the three-field encoding
+        let view = PayloadView {
+            version: 5,
+            puts: &[],
+            deletes: &[],
+        };
+        let plain = TransactionPayload {
+            trimmed: vec![],
+            ..payload
+        };
+        assert_eq!(plain.to_bytes(), rmp_serde::to_vec(&view).unwrap());
+    }
+
+    #[test]
+    fn test_encode_transaction_matches_owned_payload() {
+        let ns = test_ns();
+        let mut txn = TransactionContext::new(1, BranchId::new(), 0);
+        for i in 0..10 {
+            txn.put(Key::new_kv(ns.clone(), format!("put{}", i)), Value::Int(i))
+                .unwrap();
+        }
+        txn.delete(Key::new_kv(ns.clone(), "gone")).unwrap();
+        txn.cas(Key::new_kv(ns, "cas"), 3, Value::String("new".to_string()))
+            .unwrap();
+
+        let mut buf = vec![0xAA];
+        TransactionPayload::encode_transaction(&txn, 7, &mut buf);
+        assert_eq!(buf[0], 0xAA, "encoding appends to the buffer");
+        assert_eq!(
+            buf[1..],
+            TransactionPayload::from_transaction(&txn, 7).to_bytes()[..]
+        );
+
+        let decoded = TransactionPayload::from_bytes(&buf[1..]).unwrap();
+        assert_eq!(decoded.version, 7);
+        assert_eq!(decoded.puts.len(), 11);
+        assert_eq!(decoded.deletes.len(), 1);
+    }
+
     #[test]
     fn test_invalid_bytes() {
         let result = TransactionPayload::from_bytes(&[0xFF, 0x00, 0x01]);
@@ -13,16 +13,131 @@
 //! 3. Each WalRecord = one committed transaction (TransactionPayload)
 //! 4. Apply all records in order
 //! 5. Initialize TransactionManager with final version
+//!
+//! Replaying a large WAL can take a while. A [`RecoveryObserver`] receives
+//! [`RecoveryProgress`] reports during step 4 and can cancel recovery
+//! through a [`CancellationToken`].
 
 use crate::payload::TransactionPayload;
 use crate::TransactionManager;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use strata_core::traits::Storage;
-use strata_core::StrataResult;
-use strata_durability::codec::IdentityCodec;
+use strata_core::{StrataError, StrataResult};
+use strata_durability::codec::{IdentityCodec, StorageCodec};
+use strata_durability::format::{WalRecord, WalSegment};
 use strata_durability::wal::WalReader;
 use strata_storage::ShardedStore;
 
+/// Minimum time between two progress reports within a segment
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How far WAL replay has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryProgress {
+    /// Bytes of WAL replayed so far (approximate within a segment)
+    pub bytes_processed: u64,
+    /// Bytes of WAL to replay in total
+    pub bytes_total: u64,
+    /// Transactions replayed so far
+    pub records_replayed: u64,
+    /// Time since replay started
+    pub elapsed: Duration,
+}
+
+impl RecoveryProgress {
+    /// Fraction of the WAL replayed, from 0.0 to 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.bytes_total == 0 {
+            1.0
+        } else {
+            self.bytes_processed as f64 / self.bytes_total as f64
+        }
+    }
+
+    /// Estimated time left, extrapolated from the rate so far
+    ///
+    /// `None` until some bytes have been replayed.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.bytes_processed == 0 {
+            return None;
+        }
+        let remaining = self.bytes_total.saturating_sub(self.bytes_processed);
+        Some(
+            self.elapsed
+                .mul_f64(remaining as f64 / self.bytes_processed as f64),
+        )
+    }
+}
+
+/// Callback receiving [`RecoveryProgress`] reports
+pub type RecoveryProgressFn = Arc<dyn Fn(RecoveryProgress) + Send + Sync>;
+
+/// Shared flag that asks a long-running operation to stop
+///
+/// Clones share the flag, so a UI thread can keep one and cancel while
+/// another thread is blocked in the operation.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the operation to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) was called on any clone
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress reporting and cancellation for recovery
+///
+/// Reports arrive on the recovering thread, at most every 100ms within a WAL
+/// segment and after each segment, so the last report covers the whole WAL.
+/// Cancelling the
+/// token makes recovery fail with [`StrataError::Cancelled`] before the next
+/// record is applied.
+#[derive(Clone, Default)]
+pub struct RecoveryObserver {
+    /// Called with each progress report
+    pub on_progress: Option<RecoveryProgressFn>,
+    /// Checked before each record is applied
+    pub cancel: Option<CancellationToken>,
+}
+
+impl RecoveryObserver {
+    fn check_cancelled(&self) -> StrataResult<()> {
+        match &self.cancel {
+            Some(token) if token.is_cancelled() => Err(StrataError::cancelled("WAL recovery")),
+            _ => Ok(()),
+        }
+    }
+
+    fn report(&self, progress: RecoveryProgress) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(progress);
+        }
+    }
+}
+
+impl std::fmt::Debug for RecoveryObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecoveryObserver")
+            .field("on_progress", &self.on_progress.is_some())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
 /// Coordinates database recovery after crash or restart
 ///
 /// Per spec Section 5.4:
@@ -37,6 +152,10 @@ pub struct RecoveryCoordinator {
     /// Path to snapshot directory (optional, not used in M2)
     #[allow(dead_code)]
     snapshot_path: Option<PathBuf>,
+    /// Codec used to decode WAL records
+    codec: Arc<dyn StorageCodec>,
+    /// Progress reporting and cancellation for `recover()`
+    observer: RecoveryObserver,
 }
 
 impl RecoveryCoordinator {
@@ -48,9 +167,23 @@ impl RecoveryCoordinator {
         RecoveryCoordinator {
             wal_dir,
             snapshot_path: None,
+            codec: Arc::new(IdentityCodec),
+            observer: RecoveryObserver::default(),
         }
     }
 
+    /// Set the codec used to decode WAL records (defaults to identity)
+    pub fn with_codec(mut self, codec: Arc<dyn StorageCodec>) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Report progress and check for cancellation during `recover()`
+    pub fn with_observer(mut self, observer: RecoveryObserver) -> Self {
+        self.observer = observer;
+        self
+    }
+
     /// Set snapshot path for checkpoint-based recovery (M3+ feature)
     ///
     /// Note: Snapshot-based recovery is not implemented in M2.
@@ -73,10 +206,9 @@ impl RecoveryCoordinator {
     /// # Errors
     /// - If WAL directory cannot be read
     /// - If record deserialization fails
+    /// - [`StrataError::Cancelled`] if the observer's token was cancelled
     pub fn recover(&self) -> StrataResult<RecoveryResult> {
         let storage = ShardedStore::new();
-        let mut max_version = 0u64;
-        let mut max_txn_id = 0u64;
         let mut stats = RecoveryStats::default();
 
         // If WAL dir doesn't exist, return empty result
@@ -88,43 +220,51 @@ impl RecoveryCoordinator {
             });
         }
 
-        // Read all records from segmented WAL
-        let reader = WalReader::new(Box::new(IdentityCodec));
-        let read_result = reader
-            .read_all(&self.wal_dir)
-            .map_err(|e| strata_core::StrataError::storage(format!("WAL read failed: {}", e)))?;
-
-        for record in &read_result.records {
-            max_txn_id = max_txn_id.max(record.txn_id);
-
-            let payload = TransactionPayload::from_bytes(&record.writeset).map_err(|e| {
-                strata_core::StrataError::storage(format!(
-                    "Failed to decode transaction payload for txn {}: {}",
-                    record.txn_id, e
-                ))
-            })?;
-
-            max_version = max_version.max(payload.version);
-
-            // Apply puts
-            for (key, value) in &payload.puts {
-                storage.put_with_version(key.clone(), value.clone(), payload.version, None)?;
-                stats.writes_applied += 1;
-            }
-
-            // Apply deletes — use trait method explicitly so Storage::version is updated
-            for key in &payload.deletes {
-                Storage::delete_with_version(&storage, key, payload.version)?;
-                stats.deletes_applied += 1;
+        // Replay the segmented WAL one segment at a time, in order
+        let reader = WalReader::new(Box::new(Arc::clone(&self.codec)));
+        let wal_error = |e| StrataError::storage(format!("WAL read failed: {}", e));
+        let mut segments = reader.list_segments(&self.wal_dir).map_err(wal_error)?;
+        segments.sort();
+        let sizes: Vec<u64> = segments
+            .iter()
+            .map(|n| {
+                std::fs::metadata(WalSegment::segment_path(&self.wal_dir, *n))
+                    .map_or(0, |m| m.len())
+            })
+            .collect();
+
+        let started = Instant::now();
+        let mut last_report = started;
+        let mut progress = RecoveryProgress {
+            bytes_processed: 0,
+            bytes_total: sizes.iter().sum(),
+            records_replayed: 0,
+            elapsed: Duration::ZERO,
+        };
+        for (segment, size) in segments.into_iter().zip(sizes) {
+            self.observer.check_cancelled()?;
+            let (records, ..) = reader
+                .read_segment(&self.wal_dir, segment)
+                .map_err(wal_error)?;
+            let segment_start = progress.bytes_processed;
+            for (i, record) in records.iter().enumerate() {
+                self.observer.check_cancelled()?;
+                apply_record(&storage, record, &mut stats)?;
+                progress.records_replayed += 1;
+                if last_report.elapsed() >= PROGRESS_INTERVAL {
+                    last_report = Instant::now();
+                    progress.bytes_processed =
+                        segment_start + size * (i as u64 + 1) / records.len() as u64;
+                    progress.elapsed = started.elapsed();
+                    self.observer.report(progress);
+                }
             }
-
-            stats.txns_replayed += 1;
+            progress.bytes_processed = segment_start + size;
+            progress.elapsed = started.elapsed();
+            self.observer.report(progress);
         }
 
-        stats.final_version = max_version;
-        stats.max_txn_id = max_txn_id;
-
-        let txn_manager = TransactionManager::with_txn_id(max_version, max_txn_id);
+        let txn_manager = TransactionManager::with_txn_id(stats.final_version, stats.max_txn_id);
 
         Ok(RecoveryResult {
             storage,
@@ -132,6 +272,79 @@ impl RecoveryCoordinator {
             stats,
         })
     }
+
+    /// Apply the records written after `watermark` to an already recovered
+    /// storage
+    ///
+    /// Lets a read-only instance catch up with a writer that kept appending
+    /// after it recovered. Only complete records are applied; a partially
+    /// written tail is picked up by a later call once it is finished.
+    ///
+    /// # Returns
+    /// - `RecoveryStats` for the applied records; `max_txn_id` is the new
+    ///   watermark, or `watermark` itself when nothing was applied
+    ///
+    /// # Errors
+    /// - If the WAL cannot be read or a record cannot be decoded
+    pub fn replay_since(
+        &self,
+        storage: &ShardedStore,
+        watermark: u64,
+    ) -> StrataResult<RecoveryStats> {
+        let mut stats = RecoveryStats {
+            max_txn_id: watermark,
+            ..RecoveryStats::default()
+        };
+        if !self.wal_dir.exists() {
+            return Ok(stats);
+        }
+
+        let reader = WalReader::new(Box::new(Arc::clone(&self.codec)));
+        let records = reader
+            .read_since(&self.wal_dir, watermark)
+            .map_err(|e| strata_core::StrataError::storage(format!("WAL read failed: {}", e)))?;
+        for record in &records {
+            apply_record(storage, record, &mut stats)?;
+        }
+        Ok(stats)
+    }
+}
+
+/// Apply one committed transaction from the WAL to `storage`
+fn apply_record(
+    storage: &ShardedStore,
+    record: &WalRecord,
+    stats: &mut RecoveryStats,
+) -> StrataResult<()> {
+    stats.max_txn_id = stats.max_txn_id.max(record.txn_id);
+
+    let payload = TransactionPayload::from_bytes(&record.writeset).map_err(|e| {
+        strata_core::StrataError::storage(format!(
+            "Failed to decode transaction payload for txn {}: {}",
+            record.txn_id, e
+        ))
+    })?;
+
+    stats.final_version = stats.final_version.max(payload.version);
+
+    // Apply puts
+    for (key, value) in &payload.puts {
+        storage.put_with_version(key.clone(), value.clone(), payload.version, None)?;
+        stats.writes_applied += 1;
+    }
+
+    // Apply deletes — use trait method explicitly so Storage::version is updated
+    for key in &payload.deletes {
+        Storage::delete_with_version(storage, key, payload.version)?;
+        stats.deletes_applied += 1;
+    }
+
+    for key in &payload.trimmed {
+        storage.mark_history_trimmed(key);
+    }
+
+    stats.txns_replayed += 1;
+    Ok(())
 }
 
 /// Result of recovery operation
@@ -270,6 +483,7 @@ mod tests {
             version,
             puts,
             deletes,
+            trimmed: Vec::new(),
         };
         let record = WalRecord::new(
             txn_id,
@@ -482,6 +696,58 @@ mod tests {
         assert!(result.storage.get(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_replay_since_applies_new_records() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let first = Key::new_kv(ns.clone(), "first");
+        let second = Key::new_kv(ns, "second");
+
+        let mut wal = create_test_wal(&wal_dir);
+        write_txn(
+            &mut wal,
+            1,
+            branch_id,
+            vec![(first.clone(), Value::Int(1))],
+            vec![],
+            100,
+        );
+
+        let coordinator = RecoveryCoordinator::new(wal_dir);
+        let result = coordinator.recover().unwrap();
+        let watermark = result.stats.max_txn_id;
+
+        // Nothing new yet
+        let stats = coordinator
+            .replay_since(&result.storage, watermark)
+            .unwrap();
+        assert_eq!(stats.txns_replayed, 0);
+        assert_eq!(stats.max_txn_id, watermark);
+
+        write_txn(
+            &mut wal,
+            2,
+            branch_id,
+            vec![(second.clone(), Value::Int(2))],
+            vec![first.clone()],
+            101,
+        );
+        let stats = coordinator
+            .replay_since(&result.storage, watermark)
+            .unwrap();
+        assert_eq!(stats.txns_replayed, 1);
+        assert_eq!(stats.max_txn_id, 2);
+        assert_eq!(stats.final_version, 101);
+        assert!(result.storage.get(&first).unwrap().is_none());
+        assert_eq!(
+            result.storage.get(&second).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+    }
+
     #[test]
     fn test_recovery_stats_helpers() {
         let stats = RecoveryStats {
@@ -928,4 +1194,66 @@ mod tests {
             assert_eq!(stored.value, Value::Int(i as i64));
         }
     }
+
+    #[test]
+    fn test_recovery_reports_progress_and_honors_cancellation() {
+        let temp_dir = TempDir::new().unwrap();
+        let wal_dir = temp_dir.path().join("wal");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+
+        // 200KB of records spans several 64KB test segments
+        {
+            let mut wal = create_test_wal(&wal_dir);
+            for i in 1..=20u64 {
+                write_txn(
+                    &mut wal,
+                    i,
+                    branch_id,
+                    vec![(
+                        Key::new_kv(ns.clone(), format!("key_{}", i)),
+                        Value::Bytes(vec![0; 10_000]),
+                    )],
+                    vec![],
+                    i,
+                );
+            }
+        }
+
+        let reports = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let observer = RecoveryObserver {
+            on_progress: Some(Arc::new(move |p| sink.lock().push(p))),
+            cancel: None,
+        };
+        let result = RecoveryCoordinator::new(wal_dir.clone())
+            .with_observer(observer)
+            .recover()
+            .unwrap();
+        assert_eq!(result.stats.txns_replayed, 20);
+
+        let reports = reports.lock();
+        assert!(reports.len() > 1);
+        assert!(reports
+            .windows(2)
+            .all(|w| w[0].bytes_processed <= w[1].bytes_processed));
+        let last = reports.last().unwrap();
+        assert_eq!(last.records_replayed, 20);
+        assert!(last.bytes_total > 200_000);
+        assert_eq!(last.bytes_processed, last.bytes_total);
+        assert_eq!(last.fraction(), 1.0);
+        assert_eq!(last.eta(), Some(Duration::ZERO));
+
+        // Cancelling at the first report stops before the next segment
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        let observer = RecoveryObserver {
+            on_progress: Some(Arc::new(move |_| canceller.cancel())),
+            cancel: Some(token),
+        };
+        let result = RecoveryCoordinator::new(wal_dir)
+            .with_observer(observer)
+            .recover();
+        assert!(matches!(result, Err(StrataError::Cancelled { .. })));
+    }
 }
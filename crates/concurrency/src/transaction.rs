@@ -6,8 +6,11 @@
 //!
 //! See `docs/architecture/M2_TRANSACTION_SEMANTICS.md` for the full specification.
 
+use crate::arena::TransactionArena;
+use crate::locks::{KeyLockTable, LockMode};
 use crate::validation::{validate_transaction, ValidationResult};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use strata_core::primitives::json::{get_at_path, JsonPatch, JsonPath, JsonValue};
 use strata_core::traits::{SnapshotView, Storage};
@@ -327,6 +330,15 @@ pub trait JsonStoreExt {
     fn json_exists(&mut self, key: &Key) -> StrataResult<bool>;
 }
 
+/// Keys a transaction has locked, and the view their reads go to
+#[derive(Default)]
+struct LockedKeys {
+    /// Locked key -> whether it was locked before its first read
+    keys: HashMap<Key, bool>,
+    /// Committed state as of the most recent lock
+    latest: Option<Box<dyn SnapshotView>>,
+}
+
 /// Transaction context for OCC with snapshot isolation
 ///
 /// Tracks all reads, writes, deletes, and CAS operations for a transaction.
@@ -422,6 +434,19 @@ pub struct TransactionContext {
     /// Only allocated when JSON operations are performed.
     json_snapshot_versions: Option<HashMap<Key, u64>>,
 
+    /// Scratch memory for encoding the commit (see `arena` module)
+    pub(crate) arena: TransactionArena,
+
+    // Key locks
+    /// Lock table shared with the database's other transactions
+    key_locks: Option<Arc<KeyLockTable>>,
+
+    /// Whether reads and writes lock their keys automatically
+    lock_mode: LockMode,
+
+    /// Keys this transaction holds locks on (lazy - most transactions lock nothing)
+    locked: Option<LockedKeys>,
+
     // State
     /// Current transaction status
     pub status: TransactionStatus,
@@ -469,6 +494,10 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            arena: TransactionArena::new(),
+            key_locks: None,
+            lock_mode: LockMode::Optimistic,
+            locked: None,
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -517,6 +546,10 @@ impl TransactionContext {
             json_reads: None,
             json_writes: None,
             json_snapshot_versions: None,
+            arena: TransactionArena::new(),
+            key_locks: None,
+            lock_mode: LockMode::Optimistic,
+            locked: None,
             status: TransactionStatus::Active,
             start_time: Instant::now(),
         }
@@ -556,6 +589,7 @@ impl TransactionContext {
     /// ```
     pub fn get(&mut self, key: &Key) -> StrataResult<Option<Value>> {
         self.ensure_active()?;
+        self.auto_lock(key)?;
 
         // 1. Check write_set first (read-your-writes)
         // No read_set entry - we're reading our own uncommitted write
@@ -577,11 +611,7 @@ impl TransactionContext {
     ///
     /// This is the core read path that tracks reads for conflict detection.
     fn read_from_snapshot(&mut self, key: &Key) -> StrataResult<Option<Value>> {
-        let snapshot = self.snapshot.as_ref().ok_or_else(|| {
-            StrataError::invalid_input("Transaction has no snapshot for reads".to_string())
-        })?;
-
-        let versioned = snapshot.get(key)?;
+        let versioned = self.read_view(key)?.get(key)?;
 
         // Track in read_set for conflict detection
         if let Some(ref vv) = versioned {
@@ -609,6 +639,7 @@ impl TransactionContext {
     /// Returns `StrataError::invalid_input` if transaction is not active.
     pub fn get_versioned(&mut self, key: &Key) -> StrataResult<Option<VersionedValue>> {
         self.ensure_active()?;
+        self.auto_lock(key)?;
 
         // 1. Check write_set first (read-your-writes)
         if let Some(value) = self.write_set.get(key) {
@@ -630,11 +661,7 @@ impl TransactionContext {
 
     /// Read from snapshot preserving version metadata, and track in read_set
     fn get_versioned_from_snapshot(&mut self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        let snapshot = self.snapshot.as_ref().ok_or_else(|| {
-            StrataError::invalid_input("Transaction has no snapshot for reads".to_string())
-        })?;
-
-        let versioned = snapshot.get(key)?;
+        let versioned = self.read_view(key)?.get(key)?;
 
         // Track in read_set for conflict detection
         if let Some(ref vv) = versioned {
@@ -723,6 +750,46 @@ impl TransactionContext {
         Ok(results.into_iter().collect())
     }
 
+    /// Scan at most `limit` keys matching `prefix`, starting at `start`
+    ///
+    /// Like [`scan_prefix`](Self::scan_prefix), with read-your-writes and
+    /// read-set tracking, but only for the keys returned, so paging through
+    /// a long prefix costs the page rather than the whole prefix.
+    ///
+    /// # Errors
+    /// Returns `StrataError::invalid_input` if transaction is not active or has no snapshot.
+    pub fn scan_prefix_from(
+        &mut self,
+        prefix: &Key,
+        start: &Key,
+        limit: usize,
+    ) -> StrataResult<Vec<(Key, Value)>> {
+        self.ensure_active()?;
+
+        let snapshot = self.snapshot.as_ref().ok_or_else(|| {
+            StrataError::invalid_input("Transaction has no snapshot for reads".to_string())
+        })?;
+
+        // Our own deletes can hide at most `delete_set.len()` snapshot keys
+        let fetch = limit.saturating_add(self.delete_set.len());
+        let snapshot_results = snapshot.scan_prefix_from(prefix, start, fetch)?;
+
+        let mut results: BTreeMap<Key, Value> = BTreeMap::new();
+        for (key, vv) in snapshot_results {
+            self.read_set.insert(key.clone(), vv.version.as_u64());
+            if !self.delete_set.contains(&key) {
+                results.insert(key, vv.value);
+            }
+        }
+        for (key, value) in &self.write_set {
+            if key.starts_with(prefix) && key >= start {
+                results.insert(key.clone(), value.clone());
+            }
+        }
+
+        Ok(results.into_iter().take(limit).collect())
+    }
+
     /// Get the version that was read for a key (from read_set)
     ///
     /// Returns None if the key hasn't been read from snapshot.
@@ -762,6 +829,7 @@ impl TransactionContext {
     /// ```
     pub fn put(&mut self, key: Key, value: Value) -> StrataResult<()> {
         self.ensure_active()?;
+        self.auto_lock(&key)?;
 
         // Remove from delete_set if previously deleted in this txn
         self.delete_set.remove(&key);
@@ -800,6 +868,7 @@ impl TransactionContext {
     /// ```
     pub fn delete(&mut self, key: Key) -> StrataResult<()> {
         self.ensure_active()?;
+        self.auto_lock(&key)?;
 
         // Remove from write_set if previously written in this txn
         self.write_set.remove(&key);
@@ -844,6 +913,7 @@ impl TransactionContext {
     /// ```
     pub fn cas(&mut self, key: Key, expected_version: u64, new_value: Value) -> StrataResult<()> {
         self.ensure_active()?;
+        self.auto_lock(&key)?;
 
         self.cas_set.push(CASOperation {
             key,
@@ -853,6 +923,116 @@ impl TransactionContext {
         Ok(())
     }
 
+    // === Key Locks ===
+
+    /// Attach the lock table that `lock()` acquires keys from
+    ///
+    /// The database attaches its table to every transaction it begins.
+    pub fn set_key_locks(&mut self, key_locks: Option<Arc<KeyLockTable>>) {
+        self.key_locks = key_locks;
+    }
+
+    /// Choose whether reads and writes lock their keys automatically
+    pub fn set_lock_mode(&mut self, mode: LockMode) {
+        self.lock_mode = mode;
+    }
+
+    /// The transaction's lock mode
+    pub fn lock_mode(&self) -> LockMode {
+        self.lock_mode
+    }
+
+    /// Lock `key` until this transaction ends
+    ///
+    /// Waits while another transaction holds the key. Once locked, a key
+    /// that this transaction has not read yet is read from the latest
+    /// committed state instead of the start snapshot, so a read-modify-write
+    /// of a locked key does not conflict with writers that committed before
+    /// the lock was taken. Keys already read keep their snapshot value.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the transaction is not active or has no lock table
+    /// - `Conflict` (retryable) if the key stays locked past the table's timeout
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use strata_concurrency::TransactionContext;
+    /// # use strata_core::types::{BranchId, Key, Namespace};
+    /// # use strata_core::value::Value;
+    /// # fn example(txn: &mut TransactionContext) -> strata_core::StrataResult<()> {
+    /// # let key = Key::new_kv(Namespace::for_branch(BranchId::default()), "hits");
+    /// txn.lock(&key)?;
+    /// let hits = match txn.get(&key)? {
+    ///     Some(Value::Int(n)) => n,
+    ///     _ => 0,
+    /// };
+    /// txn.put(key, Value::Int(hits + 1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lock(&mut self, key: &Key) -> StrataResult<()> {
+        self.ensure_active()?;
+        if self
+            .locked
+            .as_ref()
+            .is_some_and(|l| l.keys.contains_key(key))
+        {
+            return Ok(());
+        }
+        let table = self.key_locks.as_ref().ok_or_else(|| {
+            StrataError::invalid_input("Transaction has no lock table".to_string())
+        })?;
+        table.acquire(key, self.txn_id)?;
+
+        let unread = !self.read_set.contains_key(key);
+        let latest = self.snapshot.as_ref().and_then(|s| s.latest());
+        let locked = self.locked.get_or_insert_with(LockedKeys::default);
+        locked.keys.insert(key.clone(), unread);
+        if latest.is_some() {
+            locked.latest = latest;
+        }
+        Ok(())
+    }
+
+    /// Whether this transaction holds the lock on `key`
+    pub fn holds_lock(&self, key: &Key) -> bool {
+        self.locked
+            .as_ref()
+            .is_some_and(|l| l.keys.contains_key(key))
+    }
+
+    /// Release every key lock this transaction holds
+    ///
+    /// Called when the transaction ends; the locks must outlive the commit
+    /// so the next holder reads the committed value.
+    pub fn release_locks(&mut self) {
+        if let (Some(locked), Some(table)) = (self.locked.take(), self.key_locks.as_ref()) {
+            table.release(locked.keys.keys(), self.txn_id);
+        }
+    }
+
+    /// Lock `key` first if the transaction runs in pessimistic mode
+    fn auto_lock(&mut self, key: &Key) -> StrataResult<()> {
+        if self.lock_mode == LockMode::Pessimistic && self.key_locks.is_some() {
+            self.lock(key)?;
+        }
+        Ok(())
+    }
+
+    /// The view a read of `key` goes to: the latest state for keys locked
+    /// before their first read, the start snapshot otherwise
+    fn read_view(&self, key: &Key) -> StrataResult<&dyn SnapshotView> {
+        if let Some(locked) = &self.locked {
+            if let (Some(true), Some(latest)) = (locked.keys.get(key), &locked.latest) {
+                return Ok(latest.as_ref());
+            }
+        }
+        self.snapshot.as_deref().ok_or_else(|| {
+            StrataError::invalid_input("Transaction has no snapshot for reads".to_string())
+        })
+    }
+
     // === Event State Tracking ===
 
     /// Get the current event sequence count tracked across Transaction instances.
@@ -1316,6 +1496,40 @@ impl TransactionContext {
         self.cas_set.len()
     }
 
+    /// Branches other than `branch_id` whose keys this transaction reads or
+    /// writes, sorted by ID
+    ///
+    /// Keys carry their own branch, so one transaction can update several
+    /// branches atomically. Empty for the usual single-branch transaction.
+    pub fn other_branches(&self) -> Vec<BranchId> {
+        let mut others: Vec<BranchId> = self
+            .read_set
+            .keys()
+            .chain(self.write_set.keys())
+            .chain(self.delete_set.iter())
+            .chain(self.cas_set.iter().map(|cas| &cas.key))
+            .chain(self.json_snapshot_versions.iter().flat_map(|v| v.keys()))
+            .chain(self.json_writes().iter().map(|entry| &entry.key))
+            .map(|key| key.namespace.branch_id)
+            .filter(|branch_id| *branch_id != self.branch_id)
+            .collect();
+        others.sort_unstable_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        others.dedup();
+        others
+    }
+
+    /// Every branch this transaction touches, `branch_id` included, sorted by ID
+    ///
+    /// Commit takes the branches' commit locks in this order.
+    pub fn branches(&self) -> Vec<BranchId> {
+        let mut branches = self.other_branches();
+        let at = branches
+            .binary_search_by(|b| b.as_bytes().cmp(self.branch_id.as_bytes()))
+            .unwrap_err();
+        branches.insert(at, self.branch_id);
+        branches
+    }
+
     /// Check if transaction has any pending operations
     ///
     /// Returns true if there are buffered writes, deletes, or CAS operations
@@ -1384,6 +1598,11 @@ impl TransactionContext {
         branch_id: BranchId,
         snapshot: Option<Box<dyn SnapshotView>>,
     ) {
+        // Locks belong to the previous transaction
+        self.release_locks();
+        self.key_locks = None;
+        self.lock_mode = LockMode::Optimistic;
+
         // Update identity
         self.txn_id = txn_id;
         self.branch_id = branch_id;
@@ -1409,6 +1628,9 @@ impl TransactionContext {
         self.json_writes = None;
         self.json_snapshot_versions = None;
 
+        // Free the previous commit's scratch memory, keeping the capacity
+        self.arena.reset();
+
         // Reset state
         self.status = TransactionStatus::Active;
         self.start_time = Instant::now();
@@ -1436,6 +1658,36 @@ impl TransactionContext {
             self.cas_set.capacity(),
         )
     }
+
+    /// Bytes retained by the commit scratch arena and encode buffer
+    ///
+    /// Grows with the largest transaction this context has committed.
+    pub fn arena_bytes(&self) -> usize {
+        self.arena.allocated_bytes()
+    }
+
+    /// Clear the read, write, delete and CAS sets and shrink them to `capacity`
+    ///
+    /// Releases the memory a pooled context kept after an unusually large
+    /// transaction, including the commit scratch arena. Collections already
+    /// at or below `capacity` keep their allocation.
+    pub fn shrink_to(&mut self, capacity: usize) {
+        self.read_set.clear();
+        self.write_set.clear();
+        self.delete_set.clear();
+        self.cas_set.clear();
+        self.read_set.shrink_to(capacity);
+        self.write_set.shrink_to(capacity);
+        self.delete_set.shrink_to(capacity);
+        self.cas_set.shrink_to(capacity);
+        self.arena.release();
+    }
+}
+
+impl Drop for TransactionContext {
+    fn drop(&mut self) {
+        self.release_locks();
+    }
 }
 
 // ============================================================================
@@ -1707,4 +1959,51 @@ mod tests {
         // Verify version tracked for conflict detection
         assert_eq!(txn.read_set.get(&key), Some(&15));
     }
+
+    #[test]
+    fn test_pessimistic_mode_locks_until_reset_or_drop() {
+        let ns = test_namespace();
+        let key = test_key(&ns, "hot");
+        let branch_id = BranchId::new();
+        let table = Arc::new(KeyLockTable::new(Duration::from_millis(10)));
+
+        let mut txn = TransactionContext::new(1, branch_id, 0);
+        assert!(matches!(
+            txn.lock(&key),
+            Err(StrataError::InvalidInput { .. })
+        ));
+        txn.set_key_locks(Some(Arc::clone(&table)));
+        txn.set_lock_mode(LockMode::Pessimistic);
+        txn.put(key.clone(), Value::Int(1)).unwrap();
+        assert!(txn.holds_lock(&key));
+        assert!(table.acquire(&key, 2).unwrap_err().is_retryable());
+
+        txn.reset(3, branch_id, None);
+        assert!(table.is_empty());
+        assert_eq!(txn.lock_mode(), LockMode::Optimistic);
+
+        txn.set_key_locks(Some(Arc::clone(&table)));
+        txn.lock(&key).unwrap();
+        drop(txn);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_branches_include_other_branches_written() {
+        let primary = test_namespace();
+        let other = test_namespace();
+        let mut txn = TransactionContext::new(1, primary.branch_id, 0);
+        txn.put(test_key(&primary, "counter"), Value::Int(1))
+            .unwrap();
+        assert!(txn.other_branches().is_empty());
+        assert_eq!(txn.branches(), vec![primary.branch_id]);
+
+        txn.put(test_key(&other, "a"), Value::Int(1)).unwrap();
+        txn.delete(test_key(&other, "b")).unwrap();
+        assert_eq!(txn.other_branches(), vec![other.branch_id]);
+
+        let mut expected = vec![primary.branch_id, other.branch_id];
+        expected.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        assert_eq!(txn.branches(), expected);
+    }
 }
@@ -64,17 +64,24 @@ pub enum PrimitiveType {
     /// Vector similarity search with HNSW index.
     /// Versioning: TxnId
     Vector,
+
+    /// List (queue)
+    ///
+    /// Double-ended lists with push, pop, range and blocking pop.
+    /// Versioning: TxnId
+    List,
 }
 
 impl PrimitiveType {
     /// All primitive types (for iteration)
-    pub const ALL: [PrimitiveType; 6] = [
+    pub const ALL: [PrimitiveType; 7] = [
         PrimitiveType::Kv,
         PrimitiveType::Event,
         PrimitiveType::State,
         PrimitiveType::Branch,
         PrimitiveType::Json,
         PrimitiveType::Vector,
+        PrimitiveType::List,
     ];
 
     /// Get all primitive types as a slice
@@ -91,6 +98,7 @@ impl PrimitiveType {
             PrimitiveType::Branch => "BranchIndex",
             PrimitiveType::Json => "JsonStore",
             PrimitiveType::Vector => "VectorStore",
+            PrimitiveType::List => "ListStore",
         }
     }
 
@@ -103,6 +111,7 @@ impl PrimitiveType {
             PrimitiveType::Branch => "branch",
             PrimitiveType::Json => "json",
             PrimitiveType::Vector => "vector",
+            PrimitiveType::List => "list",
         }
     }
 
@@ -115,13 +124,14 @@ impl PrimitiveType {
             "branch" => Some(PrimitiveType::Branch),
             "json" => Some(PrimitiveType::Json),
             "vector" => Some(PrimitiveType::Vector),
+            "list" => Some(PrimitiveType::List),
             _ => None,
         }
     }
 
     /// Check if this primitive supports CRUD lifecycle
     ///
-    /// Kv, State, Branch, Json, Vector support full CRUD.
+    /// Kv, State, Branch, Json, Vector, List support full CRUD.
     /// Event is append-only (CR only).
     pub const fn supports_crud(&self) -> bool {
         match self {
@@ -131,6 +141,7 @@ impl PrimitiveType {
             PrimitiveType::Branch => true,
             PrimitiveType::Json => true,
             PrimitiveType::Vector => true,
+            PrimitiveType::List => true,
         }
     }
 
@@ -148,6 +159,7 @@ impl PrimitiveType {
     /// - State: 0x40-0x4F
     /// - Branch: 0x60-0x6F
     /// - Vector: 0x70-0x7F
+    /// - List: 0x80-0x8F
     pub const fn entry_type_range(&self) -> (u8, u8) {
         match self {
             PrimitiveType::Kv => (0x10, 0x1F),
@@ -156,6 +168,7 @@ impl PrimitiveType {
             PrimitiveType::State => (0x40, 0x4F),
             PrimitiveType::Branch => (0x60, 0x6F),
             PrimitiveType::Vector => (0x70, 0x7F),
+            PrimitiveType::List => (0x80, 0x8F),
         }
     }
 
@@ -170,6 +183,7 @@ impl PrimitiveType {
             PrimitiveType::State => 4,
             PrimitiveType::Branch => 6,
             PrimitiveType::Vector => 7,
+            PrimitiveType::List => 8,
         }
     }
 }
@@ -191,7 +205,7 @@ mod tests {
     #[test]
     fn test_primitive_type_all() {
         let all = PrimitiveType::all();
-        assert_eq!(all.len(), 6);
+        assert_eq!(all.len(), 7);
 
         // Verify all variants are present
         assert!(all.contains(&PrimitiveType::Kv));
@@ -200,11 +214,12 @@ mod tests {
         assert!(all.contains(&PrimitiveType::Branch));
         assert!(all.contains(&PrimitiveType::Json));
         assert!(all.contains(&PrimitiveType::Vector));
+        assert!(all.contains(&PrimitiveType::List));
     }
 
     #[test]
     fn test_primitive_type_const_all() {
-        assert_eq!(PrimitiveType::ALL.len(), 6);
+        assert_eq!(PrimitiveType::ALL.len(), 7);
     }
 
     #[test]
@@ -299,7 +314,7 @@ mod tests {
         for pt in PrimitiveType::all() {
             set.insert(*pt);
         }
-        assert_eq!(set.len(), 6, "All PrimitiveTypes should be unique");
+        assert_eq!(set.len(), 7, "All PrimitiveTypes should be unique");
     }
 
     #[test]
@@ -326,6 +341,7 @@ mod tests {
         assert_eq!(PrimitiveType::State.entry_type_range(), (0x40, 0x4F));
         assert_eq!(PrimitiveType::Branch.entry_type_range(), (0x60, 0x6F));
         assert_eq!(PrimitiveType::Vector.entry_type_range(), (0x70, 0x7F));
+        assert_eq!(PrimitiveType::List.entry_type_range(), (0x80, 0x8F));
     }
 
     #[test]
@@ -336,6 +352,7 @@ mod tests {
         assert_eq!(PrimitiveType::State.primitive_id(), 4);
         assert_eq!(PrimitiveType::Branch.primitive_id(), 6);
         assert_eq!(PrimitiveType::Vector.primitive_id(), 7);
+        assert_eq!(PrimitiveType::List.primitive_id(), 8);
     }
 
     #[test]
@@ -368,7 +385,7 @@ mod tests {
             .iter()
             .map(|pt| pt.primitive_id())
             .collect();
-        assert_eq!(ids.len(), 6, "All primitive IDs must be unique");
+        assert_eq!(ids.len(), 7, "All primitive IDs must be unique");
     }
 
     #[test]
@@ -0,0 +1,482 @@
+//! Conversions between Rust types and [`Value`]
+//!
+//! [`ToValue`] and [`FromValue`] map Rust types onto the canonical value
+//! model. They are implemented here for the standard scalar, string,
+//! `Option`, `Vec`, `Box` and string-keyed map types, and are usually
+//! derived for user types with `#[derive(ToValue, FromValue)]`.
+//!
+//! ## Mapping
+//!
+//! | Rust | Value |
+//! |------|-------|
+//! | `bool` | `Bool` |
+//! | integers | `Int` (`u64`/`usize` beyond `i64::MAX` become `Float`) |
+//! | `f32`, `f64` | `Float` (reads also accept `Int`) |
+//! | `String` | `String` |
+//! | `Option<T>` | `Null` for `None`; a missing field reads as `None` |
+//! | `Vec<T>` | `Array` |
+//! | `HashMap<String, T>`, `BTreeMap<String, T>` | `Object` |
+//! | `Vec<u8>` marked `#[strata(bytes)]` | `Bytes` |
+//!
+//! Derived structs map to `Object`s keyed by field name, newtype structs to
+//! their inner value, and enums are externally tagged: a unit variant is its
+//! name as a `String`, any other variant an `Object` with the name as its
+//! single key.
+//!
+//! ## Errors
+//!
+//! [`FromValueError`] carries the path of the value that didn't fit, in the
+//! `$.field[0]` form used by JSON schema violations.
+
+use std::collections::{BTreeMap, HashMap};
+
+use thiserror::Error;
+
+use crate::value::Value;
+
+// =============================================================================
+// Traits
+// =============================================================================
+
+/// Convert a Rust value into a [`Value`]
+pub trait ToValue {
+    /// Build the [`Value`] representing `self`
+    fn to_value(&self) -> Value;
+}
+
+/// Build a Rust value from a [`Value`]
+pub trait FromValue: Sized {
+    /// Convert `value`, or report where it doesn't fit `Self`
+    fn from_value(value: Value) -> Result<Self, FromValueError>;
+
+    /// The value to use when an object field is absent
+    ///
+    /// `None` makes the field required; `Option<T>` returns `Some(None)`.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+// =============================================================================
+// Error
+// =============================================================================
+
+/// A [`Value`] that doesn't fit the Rust type it is converted into
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("${path}: {message}")]
+pub struct FromValueError {
+    path: String,
+    message: String,
+}
+
+impl FromValueError {
+    /// An error at the root of the value being converted
+    pub fn new(message: impl Into<String>) -> Self {
+        FromValueError {
+            path: String::new(),
+            message: message.into(),
+        }
+    }
+
+    /// `found` is not the `expected` kind of value
+    pub fn type_mismatch(expected: &str, found: &Value) -> Self {
+        Self::new(format!(
+            "expected {}, found {}",
+            expected,
+            found.type_name()
+        ))
+    }
+
+    /// A required object field is absent
+    pub fn missing_field(name: &str) -> Self {
+        Self::new("missing field").in_field(name)
+    }
+
+    /// An enum tag that names none of the variants
+    pub fn unknown_variant(found: &str, expected: &[&str]) -> Self {
+        Self::new(format!(
+            "unknown variant `{}`, expected one of {}",
+            found,
+            expected
+                .iter()
+                .map(|v| format!("`{}`", v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+
+    /// Place this error inside object field `name`
+    pub fn in_field(mut self, name: &str) -> Self {
+        self.path.insert_str(0, &format!(".{}", name));
+        self
+    }
+
+    /// Place this error inside array element `index`
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.insert_str(0, &format!("[{}]", index));
+        self
+    }
+
+    /// Path of the offending value, e.g. `$.turns[2].role`
+    pub fn path(&self) -> String {
+        format!("${}", self.path)
+    }
+
+    /// What was wrong with the value at [`path`](Self::path)
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+// =============================================================================
+// Value, bool, strings
+// =============================================================================
+
+impl ToValue for Value {
+    fn to_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        Ok(value)
+    }
+}
+
+impl ToValue for bool {
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Bool(b) => Ok(b),
+            other => Err(FromValueError::type_mismatch("Bool", &other)),
+        }
+    }
+}
+
+impl ToValue for String {
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+}
+
+impl ToValue for str {
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::String(s) => Ok(s),
+            other => Err(FromValueError::type_mismatch("String", &other)),
+        }
+    }
+}
+
+// =============================================================================
+// Numbers
+// =============================================================================
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {$(
+        impl ToValue for $ty {
+            fn to_value(&self) -> Value {
+                Value::Int(*self as i64)
+            }
+        }
+
+        impl FromValue for $ty {
+            fn from_value(value: Value) -> Result<Self, FromValueError> {
+                match value {
+                    Value::Int(i) => <$ty>::try_from(i).map_err(|_| {
+                        FromValueError::new(format!(
+                            "{} is out of range for {}",
+                            i,
+                            stringify!($ty)
+                        ))
+                    }),
+                    other => Err(FromValueError::type_mismatch("Int", &other)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8, i16, i32, i64, isize);
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {$(
+        impl ToValue for $ty {
+            fn to_value(&self) -> Value {
+                // Like numbers parsed from JSON, values past i64::MAX fall
+                // back to Float
+                match i64::try_from(*self) {
+                    Ok(i) => Value::Int(i),
+                    Err(_) => Value::Float(*self as f64),
+                }
+            }
+        }
+
+        impl FromValue for $ty {
+            fn from_value(value: Value) -> Result<Self, FromValueError> {
+                let out_of_range = |n: &dyn std::fmt::Display| {
+                    FromValueError::new(format!(
+                        "{} is out of range for {}",
+                        n,
+                        stringify!($ty)
+                    ))
+                };
+                match value {
+                    Value::Int(i) => <$ty>::try_from(i).map_err(|_| out_of_range(&i)),
+                    Value::Float(f) if f.fract() == 0.0 && f >= 0.0 && f <= <$ty>::MAX as f64 => {
+                        Ok(f as $ty)
+                    }
+                    Value::Float(f) => Err(out_of_range(&f)),
+                    other => Err(FromValueError::type_mismatch("Int", &other)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+
+macro_rules! impl_float {
+    ($($ty:ty),*) => {$(
+        impl ToValue for $ty {
+            fn to_value(&self) -> Value {
+                Value::Float(*self as f64)
+            }
+        }
+
+        impl FromValue for $ty {
+            fn from_value(value: Value) -> Result<Self, FromValueError> {
+                match value {
+                    Value::Float(f) => Ok(f as $ty),
+                    Value::Int(i) => Ok(i as $ty),
+                    other => Err(FromValueError::type_mismatch("Float", &other)),
+                }
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+// =============================================================================
+// Containers
+// =============================================================================
+
+impl<T: ToValue + ?Sized> ToValue for &T {
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+}
+
+impl<T: ToValue + ?Sized> ToValue for Box<T> {
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+}
+
+impl<T: FromValue> FromValue for Box<T> {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        T::from_value(value).map(Box::new)
+    }
+
+    fn from_missing() -> Option<Self> {
+        T::from_missing().map(Box::new)
+    }
+}
+
+impl<T: ToValue> ToValue for Option<T> {
+    fn to_value(&self) -> Value {
+        match self {
+            Some(v) => v.to_value(),
+            None => Value::Null,
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Null => Ok(None),
+            other => T::from_value(other).map(Some),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: ToValue> ToValue for [T] {
+    fn to_value(&self) -> Value {
+        Value::Array(self.iter().map(ToValue::to_value).collect())
+    }
+}
+
+impl<T: ToValue> ToValue for Vec<T> {
+    fn to_value(&self) -> Value {
+        self.as_slice().to_value()
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Array(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| T::from_value(item).map_err(|e| e.at_index(i)))
+                .collect(),
+            other => Err(FromValueError::type_mismatch("Array", &other)),
+        }
+    }
+}
+
+impl<T: ToValue, S> ToValue for HashMap<String, T, S> {
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (k.clone(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: FromValue, S: std::hash::BuildHasher + Default> FromValue for HashMap<String, T, S> {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(k, v)| match T::from_value(v) {
+                    Ok(v) => Ok((k, v)),
+                    Err(e) => Err(e.in_field(&k)),
+                })
+                .collect(),
+            other => Err(FromValueError::type_mismatch("Object", &other)),
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for BTreeMap<String, T> {
+    fn to_value(&self) -> Value {
+        Value::Object(
+            self.iter()
+                .map(|(k, v)| (k.clone(), v.to_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: FromValue> FromValue for BTreeMap<String, T> {
+    fn from_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(k, v)| match T::from_value(v) {
+                    Ok(v) => Ok((k, v)),
+                    Err(e) => Err(e.in_field(&k)),
+                })
+                .collect(),
+            other => Err(FromValueError::type_mismatch("Object", &other)),
+        }
+    }
+}
+
+// =============================================================================
+// Bytes
+// =============================================================================
+
+/// Fields marked `#[strata(bytes)]`, stored as `Value::Bytes`
+///
+/// Used by the derive macros; `Vec<u8>` on its own maps to an `Array` of
+/// `Int`s like any other `Vec`.
+#[doc(hidden)]
+pub trait BytesField: Sized {
+    fn to_bytes_value(&self) -> Value;
+    fn from_bytes_value(value: Value) -> Result<Self, FromValueError>;
+    fn from_missing_bytes() -> Option<Self> {
+        None
+    }
+}
+
+impl BytesField for Vec<u8> {
+    fn to_bytes_value(&self) -> Value {
+        Value::Bytes(self.clone())
+    }
+
+    fn from_bytes_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Bytes(b) => Ok(b),
+            other => Err(FromValueError::type_mismatch("Bytes", &other)),
+        }
+    }
+}
+
+impl BytesField for Option<Vec<u8>> {
+    fn to_bytes_value(&self) -> Value {
+        match self {
+            Some(b) => b.to_bytes_value(),
+            None => Value::Null,
+        }
+    }
+
+    fn from_bytes_value(value: Value) -> Result<Self, FromValueError> {
+        match value {
+            Value::Null => Ok(None),
+            other => Vec::<u8>::from_bytes_value(other).map(Some),
+        }
+    }
+
+    fn from_missing_bytes() -> Option<Self> {
+        Some(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalars_round_trip() {
+        assert_eq!(i32::from_value(7i32.to_value()), Ok(7));
+        assert_eq!(u64::from_value(u64::MAX.to_value()), Ok(u64::MAX));
+        assert_eq!(f64::from_value(Value::Int(2)), Ok(2.0));
+        assert_eq!(String::from_value("a".to_value()), Ok("a".to_string()));
+        assert_eq!(Option::<bool>::from_value(Value::Null), Ok(None));
+        assert_eq!(Option::<bool>::from_missing(), Some(None));
+        assert_eq!(bool::from_missing(), None);
+    }
+
+    #[test]
+    fn test_errors_carry_the_path() {
+        let err = u8::from_value(Value::Int(300)).unwrap_err();
+        assert_eq!(err.to_string(), "$: 300 is out of range for u8");
+
+        let value = Value::Object(
+            [(
+                "turns".to_string(),
+                Value::Array(vec![Value::Int(1), Value::String("x".into())]),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let err = HashMap::<String, Vec<i64>>::from_value(value).unwrap_err();
+        assert_eq!(err.path(), "$.turns[1]");
+        assert_eq!(err.message(), "expected Int, found String");
+        assert_eq!(
+            FromValueError::unknown_variant("c", &["a", "b"]).message(),
+            "unknown variant `c`, expected one of `a`, `b`"
+        );
+    }
+}
@@ -657,6 +657,24 @@ pub enum StrataError {
         message: String,
     },
 
+    /// Key uses a reserved prefix
+    ///
+    /// User-supplied keys must not collide with the keys internal
+    /// structures store alongside them. See `strata_core::key::RESERVED_PREFIXES`.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::reserved_key("_strata/config", "_strata/");
+    /// ```
+    #[error("key '{key}' uses reserved prefix '{prefix}'")]
+    ReservedKey {
+        /// The rejected key
+        key: String,
+        /// The reserved prefix it starts with
+        prefix: String,
+    },
+
     /// Dimension mismatch (Vector-specific)
     ///
     /// The vector dimension doesn't match the collection's configured dimension.
@@ -784,6 +802,25 @@ pub enum StrataError {
         message: String,
     },
 
+    /// Database already open
+    ///
+    /// Another process holds the single-writer lock on the database
+    /// directory. Opening the same directory read-write from two processes
+    /// would corrupt the WAL, so the second open is refused.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::already_open("/data/strata", "pid 4242 on host 'db-1'");
+    /// ```
+    #[error("database at '{path}' is already open by {holder}")]
+    AlreadyOpen {
+        /// Database directory
+        path: String,
+        /// Description of the process holding the lock
+        holder: String,
+    },
+
     // =========================================================================
     // Resource Errors
     // =========================================================================
@@ -827,6 +864,21 @@ pub enum StrataError {
         operation: String,
     },
 
+    /// Cancelled
+    ///
+    /// The caller cancelled a long-running operation before it finished.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::cancelled("WAL recovery");
+    /// ```
+    #[error("cancelled: {operation}")]
+    Cancelled {
+        /// What operation was cancelled
+        operation: String,
+    },
+
     // =========================================================================
     // Internal Errors
     // =========================================================================
@@ -977,6 +1029,20 @@ impl StrataError {
         }
     }
 
+    /// Create a ReservedKey error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::reserved_key("__idx_status", "__idx_");
+    /// ```
+    pub fn reserved_key(key: impl Into<String>, prefix: impl Into<String>) -> Self {
+        StrataError::ReservedKey {
+            key: key.into(),
+            prefix: prefix.into(),
+        }
+    }
+
     /// Create a DimensionMismatch error
     ///
     /// ## Example
@@ -1089,6 +1155,20 @@ impl StrataError {
         }
     }
 
+    /// Create an AlreadyOpen error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::already_open("/data/strata", "pid 4242 on host 'db-1'");
+    /// ```
+    pub fn already_open(path: impl Into<String>, holder: impl Into<String>) -> Self {
+        StrataError::AlreadyOpen {
+            path: path.into(),
+            holder: holder.into(),
+        }
+    }
+
     /// Create a CapacityExceeded error
     ///
     /// ## Example
@@ -1117,6 +1197,19 @@ impl StrataError {
         }
     }
 
+    /// Create a Cancelled error
+    ///
+    /// ## Example
+    /// ```no_run
+    /// # use strata_core::StrataError;
+    /// StrataError::cancelled("WAL recovery");
+    /// ```
+    pub fn cancelled(operation: impl Into<String>) -> Self {
+        StrataError::Cancelled {
+            operation: operation.into(),
+        }
+    }
+
     /// Create an Internal error
     ///
     /// ## Example
@@ -1203,6 +1296,10 @@ impl StrataError {
             StrataError::DimensionMismatch { .. } => ErrorCode::ConstraintViolation,
             StrataError::CapacityExceeded { .. } => ErrorCode::ConstraintViolation,
             StrataError::BudgetExceeded { .. } => ErrorCode::ConstraintViolation,
+            StrataError::Cancelled { .. } => ErrorCode::ConstraintViolation,
+
+            // Key errors
+            StrataError::ReservedKey { .. } => ErrorCode::InvalidKey,
 
             // Path errors
             StrataError::PathNotFound { .. } => ErrorCode::InvalidPath,
@@ -1214,6 +1311,7 @@ impl StrataError {
             StrataError::Storage { .. } => ErrorCode::StorageError,
             StrataError::Serialization { .. } => ErrorCode::SerializationError,
             StrataError::Corruption { .. } => ErrorCode::StorageError,
+            StrataError::AlreadyOpen { .. } => ErrorCode::StorageError,
 
             // Internal errors
             StrataError::Internal { .. } => ErrorCode::InternalError,
@@ -1275,6 +1373,9 @@ impl StrataError {
             StrataError::InvalidInput { message } => {
                 ErrorDetails::new().with_string("message", message)
             }
+            StrataError::ReservedKey { key, prefix } => ErrorDetails::new()
+                .with_string("key", key)
+                .with_string("prefix", prefix),
             StrataError::DimensionMismatch { expected, got } => ErrorDetails::new()
                 .with_int("expected", *expected as i64)
                 .with_int("got", *got as i64),
@@ -1298,6 +1399,9 @@ impl StrataError {
             StrataError::Corruption { message } => {
                 ErrorDetails::new().with_string("message", message)
             }
+            StrataError::AlreadyOpen { path, holder } => ErrorDetails::new()
+                .with_string("path", path)
+                .with_string("holder", holder),
             StrataError::CapacityExceeded {
                 resource,
                 limit,
@@ -1309,6 +1413,9 @@ impl StrataError {
             StrataError::BudgetExceeded { operation } => {
                 ErrorDetails::new().with_string("operation", operation)
             }
+            StrataError::Cancelled { operation } => {
+                ErrorDetails::new().with_string("operation", operation)
+            }
             StrataError::Internal { message } => {
                 ErrorDetails::new().with_string("message", message)
             }
@@ -1391,7 +1498,8 @@ impl StrataError {
 
     /// Check if this is a validation error
     ///
-    /// Returns true for: `InvalidOperation`, `InvalidInput`, `DimensionMismatch`
+    /// Returns true for: `InvalidOperation`, `InvalidInput`, `ReservedKey`,
+    /// `DimensionMismatch`
     ///
     /// Validation errors indicate bad input - don't retry, fix the input.
     ///
@@ -1408,13 +1516,14 @@ impl StrataError {
             self,
             StrataError::InvalidOperation { .. }
                 | StrataError::InvalidInput { .. }
+                | StrataError::ReservedKey { .. }
                 | StrataError::DimensionMismatch { .. }
         )
     }
 
     /// Check if this is a storage error
     ///
-    /// Returns true for: `Storage`, `Serialization`, `Corruption`
+    /// Returns true for: `Storage`, `Serialization`, `Corruption`, `AlreadyOpen`
     ///
     /// ## Example
     /// ```no_run
@@ -1430,6 +1539,7 @@ impl StrataError {
             StrataError::Storage { .. }
                 | StrataError::Serialization { .. }
                 | StrataError::Corruption { .. }
+                | StrataError::AlreadyOpen { .. }
         )
     }
 
@@ -1720,6 +1830,18 @@ mod strata_error_tests {
         assert!(e.entity_ref().is_none());
     }
 
+    #[test]
+    fn test_reserved_key_constructor() {
+        let e = StrataError::reserved_key("__idx_status", "__idx_");
+
+        assert!(e.is_validation_error());
+        assert_eq!(e.code(), ErrorCode::InvalidKey);
+        assert_eq!(
+            e.to_string(),
+            "key '__idx_status' uses reserved prefix '__idx_'"
+        );
+    }
+
     #[test]
     fn test_dimension_mismatch_constructor() {
         let e = StrataError::dimension_mismatch(384, 768);
@@ -2057,6 +2179,30 @@ mod strata_error_tests {
         assert_eq!(e.code(), ErrorCode::StorageError);
     }
 
+    #[test]
+    fn test_error_code_mapping_already_open() {
+        let e = StrataError::already_open("/data/db", "pid 42 on host 'h'");
+        assert_eq!(e.code(), ErrorCode::StorageError);
+        assert!(e.is_storage_error());
+        assert!(!e.is_retryable());
+        let msg = e.to_string();
+        assert!(msg.contains("/data/db"));
+        assert!(msg.contains("pid 42"));
+        let details = e.details();
+        assert!(details.fields().contains_key("path"));
+        assert!(details.fields().contains_key("holder"));
+    }
+
+    #[test]
+    fn test_error_code_mapping_cancelled() {
+        let e = StrataError::cancelled("WAL recovery");
+        assert_eq!(e.code(), ErrorCode::ConstraintViolation);
+        assert!(!e.is_retryable());
+        assert!(!e.is_serious());
+        assert_eq!(e.to_string(), "cancelled: WAL recovery");
+        assert!(e.details().fields().contains_key("operation"));
+    }
+
     #[test]
     fn test_error_code_mapping_internal() {
         let e = StrataError::internal("bug");
@@ -9,15 +9,85 @@
 //! - Keys must be valid UTF-8 (guaranteed by Rust's &str type)
 //! - Keys must not be empty
 //! - Keys must not contain NUL bytes (\0)
-//! - Keys must not start with reserved prefix `_strata/`
+//! - Keys must not start with a reserved prefix (see [`RESERVED_PREFIXES`])
 //! - Keys must not exceed `max_key_bytes` (default: 1024)
 
+use crate::error::{StrataError, StrataResult};
 use crate::limits::Limits;
 use thiserror::Error;
 
 /// Reserved system prefix for internal keys
 pub const RESERVED_PREFIX: &str = "_strata/";
 
+/// Reserved prefix of conversation session records
+pub const SESSION_PREFIX: &str = "_session/";
+
+/// Reserved prefix of the conversation session message index
+pub const SESSION_MESSAGE_PREFIX: &str = "_session_msg/";
+
+/// Key prefixes reserved for internal structures.
+///
+/// | Prefix | Used by |
+/// |--------|---------|
+/// | `_strata/` | System keys |
+/// | `_system_` | Shadow collections and system spaces |
+/// | `__idx_` | Branch index keys |
+/// | `__tidx__` | Event type index keys |
+/// | `__meta__` | Event log metadata |
+/// | `__retention__` | Event stream retention policies |
+/// | `__group__` | Event stream consumer groups |
+/// | `__evschema__` | Event stream payload schemas |
+/// | `__history__` | KV history policies |
+/// | `__jsonschema__` | JSON document schemas |
+/// | `__schema__` | Branch schema log entries |
+/// | `_session/` | Conversation session records |
+/// | `_session_msg/` | Conversation session message index |
+///
+/// User-supplied keys, cell names, document ids and branch names must not
+/// start with any of these. Internal layers bypass the check by writing
+/// through the `system_*` primitive methods or raw storage keys.
+pub const RESERVED_PREFIXES: &[&str] = &[
+    RESERVED_PREFIX,
+    "_system_",
+    "__idx_",
+    "__tidx__",
+    "__meta__",
+    "__retention__",
+    "__group__",
+    "__evschema__",
+    "__history__",
+    "__jsonschema__",
+    "__schema__",
+    SESSION_PREFIX,
+    SESSION_MESSAGE_PREFIX,
+];
+
+/// Return the reserved prefix `key` starts with, if any.
+///
+/// ```
+/// use strata_core::key::reserved_prefix;
+///
+/// assert_eq!(reserved_prefix("__idx_status"), Some("__idx_"));
+/// assert_eq!(reserved_prefix("_mykey"), None);
+/// ```
+pub fn reserved_prefix(key: &str) -> Option<&'static str> {
+    RESERVED_PREFIXES
+        .iter()
+        .copied()
+        .find(|prefix| key.starts_with(prefix))
+}
+
+/// Reject a user-supplied name that starts with a reserved prefix.
+///
+/// Primitives call this on every write path that takes a user key, so
+/// reserved keys are refused regardless of which API layer the write came from.
+pub fn ensure_not_reserved(key: &str) -> StrataResult<()> {
+    match reserved_prefix(key) {
+        Some(prefix) => Err(StrataError::reserved_key(key, prefix)),
+        None => Ok(()),
+    }
+}
+
 /// Validate a key using default limits
 ///
 /// This is the primary validation function for user-facing APIs.
@@ -57,8 +127,8 @@ pub fn validate_key_with_limits(key: &str, limits: &Limits) -> Result<(), KeyErr
     }
 
     // Rule 3: Key cannot use reserved prefix
-    if key.starts_with(RESERVED_PREFIX) {
-        return Err(KeyError::ReservedPrefix);
+    if let Some(prefix) = reserved_prefix(key) {
+        return Err(KeyError::ReservedPrefix { prefix });
     }
 
     // Rule 4: Key cannot exceed max length
@@ -88,9 +158,12 @@ pub enum KeyError {
     #[error("Key cannot contain NUL bytes")]
     ContainsNul,
 
-    /// Key uses reserved system prefix `_strata/`
-    #[error("Key cannot use reserved prefix '{}'", RESERVED_PREFIX)]
-    ReservedPrefix,
+    /// Key uses one of the [`RESERVED_PREFIXES`]
+    #[error("Key cannot use reserved prefix '{prefix}'")]
+    ReservedPrefix {
+        /// The reserved prefix the key starts with
+        prefix: &'static str,
+    },
 
     /// Key exceeds maximum length
     #[error("Key too long: {actual} bytes exceeds maximum {max}")]
@@ -108,7 +181,7 @@ impl KeyError {
         match self {
             KeyError::Empty => "empty_key",
             KeyError::ContainsNul => "contains_nul",
-            KeyError::ReservedPrefix => "reserved_prefix",
+            KeyError::ReservedPrefix { .. } => "reserved_prefix",
             KeyError::TooLong { .. } => "key_too_long",
         }
     }
@@ -228,19 +301,44 @@ mod tests {
     #[test]
     fn test_invalid_reserved_prefix() {
         let result = validate_key("_strata/foo");
-        assert!(matches!(result, Err(KeyError::ReservedPrefix)));
+        assert!(matches!(result, Err(KeyError::ReservedPrefix { .. })));
     }
 
     #[test]
     fn test_invalid_reserved_prefix_exact() {
         let result = validate_key("_strata/");
-        assert!(matches!(result, Err(KeyError::ReservedPrefix)));
+        assert!(matches!(result, Err(KeyError::ReservedPrefix { .. })));
     }
 
     #[test]
     fn test_invalid_reserved_prefix_nested() {
         let result = validate_key("_strata/system/config");
-        assert!(matches!(result, Err(KeyError::ReservedPrefix)));
+        assert!(matches!(result, Err(KeyError::ReservedPrefix { .. })));
+    }
+
+    #[test]
+    fn test_invalid_internal_prefixes() {
+        for (key, prefix) in [
+            ("_system_embed_kv", "_system_"),
+            ("__idx_status__Active__b1", "__idx_"),
+            ("__tidx__order", "__tidx__"),
+            ("__meta__", "__meta__"),
+            ("__retention__order", "__retention__"),
+            ("__group__orders", "__group__"),
+            ("__evschema__orders", "__evschema__"),
+            ("__history__user:", "__history__"),
+            ("__jsonschema__orders/", "__jsonschema__"),
+            ("__schema__00000001", "__schema__"),
+            ("_session/chat", "_session/"),
+            ("_session_msg/chat/00000000000000000000", "_session_msg/"),
+        ] {
+            assert_eq!(validate_key(key), Err(KeyError::ReservedPrefix { prefix }));
+        }
+        // Near misses stay valid
+        assert!(validate_key("_system").is_ok());
+        assert!(validate_key("__index").is_ok());
+        assert!(validate_key("__meta").is_ok());
+        assert!(validate_key("_session").is_ok());
     }
 
     #[test]
@@ -278,7 +376,10 @@ mod tests {
     fn test_reason_codes() {
         assert_eq!(KeyError::Empty.reason_code(), "empty_key");
         assert_eq!(KeyError::ContainsNul.reason_code(), "contains_nul");
-        assert_eq!(KeyError::ReservedPrefix.reason_code(), "reserved_prefix");
+        assert_eq!(
+            KeyError::ReservedPrefix { prefix: "_strata/" }.reason_code(),
+            "reserved_prefix"
+        );
         assert_eq!(
             KeyError::TooLong {
                 actual: 2000,
@@ -299,7 +400,7 @@ mod tests {
             "Key cannot contain NUL bytes"
         );
         assert_eq!(
-            KeyError::ReservedPrefix.to_string(),
+            KeyError::ReservedPrefix { prefix: "_strata/" }.to_string(),
             "Key cannot use reserved prefix '_strata/'"
         );
         assert_eq!(
@@ -317,6 +418,7 @@ mod tests {
     #[test]
     fn test_reserved_prefix_constant() {
         assert_eq!(RESERVED_PREFIX, "_strata/");
+        assert!(RESERVED_PREFIXES.contains(&RESERVED_PREFIX));
     }
 
     // === Multi-byte UTF-8 Keys ===
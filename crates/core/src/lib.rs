@@ -6,6 +6,7 @@
 //! - Key: Composite key with type tagging
 //! - TypeTag: Discriminates between primitive types
 //! - Value: Unified value enum for all data types
+//! - Convert: ToValue/FromValue conversions between Rust types and Value
 //! - Error: Error type hierarchy
 //! - Traits: Core trait definitions (Storage, SnapshotView)
 //! - Primitive types: Event, State, JSON, Vector types (in `primitives` module)
@@ -17,7 +18,9 @@
 // Module declarations
 pub mod branch_types; // Branch lifecycle types
 pub mod contract; // contract types
+pub mod convert; // ToValue/FromValue conversions
 pub mod error;
+pub mod key; // Key validation and reserved prefixes
 pub mod limits; // Size limits for keys, values, and vectors
 pub mod primitive_ext; // extension trait for primitives to integrate with storage/durability
 pub mod primitives; // primitive types (Event, State, Vector, JSON types)
@@ -28,13 +31,16 @@ pub mod value;
 
 // Re-export commonly used types and traits
 pub use branch_types::{BranchEventOffsets, BranchMetadata, BranchStatus};
+#[doc(hidden)]
+pub use convert::BytesField;
+pub use convert::{FromValue, FromValueError, ToValue};
 pub use error::{
     ConstraintReason, DetailValue, ErrorCode, ErrorDetails, StrataError, StrataResult,
 };
 pub use limits::{LimitError, Limits};
 pub use traits::{SnapshotView, Storage};
 pub use types::{validate_space_name, BranchId, Key, Namespace, TypeTag};
-pub use value::Value;
+pub use value::{JsonValueError, Value};
 
 // Re-export contract types at crate root for convenience
 pub use contract::{
@@ -51,6 +57,7 @@ pub use primitive_ext::{
 // Re-export primitive types at crate root for convenience
 pub use primitives::{
     // JSON types
+    apply_json_patch,
     apply_patches,
     delete_at_path,
     get_at_path,
@@ -71,6 +78,8 @@ pub use primitives::{
     JsonScalar,
     JsonValue,
     MetadataFilter,
+    PatchError,
+    PatchOperation,
     PathParseError,
     PathSegment,
     // State types
@@ -226,7 +226,7 @@ pub mod wal_ranges {
     /// Vector primitive end
     pub const VECTOR_END: u8 = 0x7F;
 
-    /// Future primitives (0x80-0xFF)
+    /// Future primitives (0x80-0xFF), also the ID range of custom primitives
     pub const FUTURE_START: u8 = 0x80;
     /// Future primitives end
     pub const FUTURE_END: u8 = 0xFF;
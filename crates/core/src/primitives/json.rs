@@ -5,6 +5,7 @@
 //! - JsonPath: Path into a JSON document (e.g., `user.name` or `items[0]`)
 //! - PathSegment: Individual path component (Key or Index)
 //! - JsonPatch: Patch operation (Set or Delete)
+//! - PatchOperation: RFC 6902 JSON Patch operation, applied with `apply_json_patch`
 //!
 //! # Document Size Limits
 //!
@@ -788,7 +789,8 @@ impl fmt::Display for JsonPath {
 /// - `copy`: Copy value from one path to another
 ///
 /// These operations are reserved for future enhancements if needed. The WAL entry
-/// type 0x24 (`JsonPatch`) is reserved for future RFC 6902 support.
+/// type 0x24 (`JsonPatch`) is reserved for future RFC 6902 support. To apply a
+/// full RFC 6902 patch to a document value, use [`apply_json_patch`].
 ///
 /// ## Design Rationale
 ///
@@ -1444,12 +1446,13 @@ fn merge_patch_inner(target: &mut serde_json::Value, patch: &serde_json::Value)
                 if value.is_null() {
                     // Null removes the key
                     target_obj.remove(key);
-                } else if let Some(target_value) = target_obj.get_mut(key) {
-                    // Key exists - recursively merge
-                    merge_patch_inner(target_value, value);
                 } else {
-                    // Key doesn't exist - add it
-                    target_obj.insert(key.clone(), value.clone());
+                    // Merge into the existing value, or into nothing so that
+                    // nulls nested in an added object are dropped too
+                    let target_value = target_obj
+                        .entry(key.clone())
+                        .or_insert(serde_json::Value::Null);
+                    merge_patch_inner(target_value, value);
                 }
             }
         }
@@ -1459,6 +1462,234 @@ fn merge_patch_inner(target: &mut serde_json::Value, patch: &serde_json::Value)
     }
 }
 
+// =============================================================================
+// RFC 6902 JSON Patch
+// =============================================================================
+
+/// One operation of an RFC 6902 JSON Patch
+///
+/// Paths are JSON Pointers (RFC 6901): `""` is the whole document and
+/// `/items/0` the first element of `items`. As the last token of an `add`
+/// path, `-` appends to an array. Serializes to the RFC's form, e.g.
+/// `{"op": "add", "path": "/tags/-", "value": "new"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Add a member to an object, or insert an element into an array
+    Add {
+        /// Where to add the value
+        path: String,
+        /// The value to add
+        value: JsonValue,
+    },
+    /// Remove an existing value
+    Remove {
+        /// The value to remove
+        path: String,
+    },
+    /// Replace an existing value
+    Replace {
+        /// The value to replace
+        path: String,
+        /// The new value
+        value: JsonValue,
+    },
+    /// Remove a value and add it elsewhere
+    Move {
+        /// The value to move
+        from: String,
+        /// Where to add it
+        path: String,
+    },
+    /// Add a copy of a value elsewhere
+    Copy {
+        /// The value to copy
+        from: String,
+        /// Where to add it
+        path: String,
+    },
+    /// Fail the patch unless a value equals the given one
+    Test {
+        /// The value to compare
+        path: String,
+        /// The expected value
+        value: JsonValue,
+    },
+}
+
+/// Error applying an RFC 6902 JSON Patch
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PatchError {
+    /// A path is not a JSON Pointer
+    #[error("invalid JSON pointer '{0}'")]
+    InvalidPointer(String),
+
+    /// A path doesn't lead to a value, or to a place a value can be added
+    #[error("path '{0}' not found")]
+    NotFound(String),
+
+    /// A `test` operation found a different value
+    #[error("test failed at '{0}'")]
+    TestFailed(String),
+
+    /// A `move` into a child of the moved value
+    #[error("cannot move '{from}' into its own child '{path}'")]
+    MoveIntoChild {
+        /// The value being moved
+        from: String,
+        /// Where it was to be added
+        path: String,
+    },
+}
+
+/// Apply an RFC 6902 JSON Patch
+///
+/// Operations are applied in order. If one fails, `target` is left
+/// unchanged and the error names the failing path.
+///
+/// # Examples
+///
+/// ```
+/// use strata_core::primitives::json::{apply_json_patch, JsonValue, PatchOperation};
+///
+/// let mut doc: JsonValue = serde_json::json!({"tags": ["a"], "draft": true}).into();
+/// let ops: Vec<PatchOperation> = serde_json::from_value(serde_json::json!([
+///     {"op": "test", "path": "/draft", "value": true},
+///     {"op": "add", "path": "/tags/-", "value": "b"},
+///     {"op": "remove", "path": "/draft"}
+/// ]))
+/// .unwrap();
+///
+/// apply_json_patch(&mut doc, &ops).unwrap();
+/// assert_eq!(doc, serde_json::json!({"tags": ["a", "b"]}).into());
+/// ```
+pub fn apply_json_patch(target: &mut JsonValue, ops: &[PatchOperation]) -> Result<(), PatchError> {
+    let mut doc = target.as_inner().clone();
+    for op in ops {
+        apply_patch_operation(&mut doc, op)?;
+    }
+    *target = JsonValue(doc);
+    Ok(())
+}
+
+fn apply_patch_operation(
+    doc: &mut serde_json::Value,
+    op: &PatchOperation,
+) -> Result<(), PatchError> {
+    match op {
+        PatchOperation::Add { path, value } => pointer_add(doc, path, value.as_inner().clone()),
+        PatchOperation::Remove { path } => pointer_remove(doc, path).map(drop),
+        PatchOperation::Replace { path, value } => {
+            check_pointer(path)?;
+            let slot = doc
+                .pointer_mut(path)
+                .ok_or_else(|| PatchError::NotFound(path.clone()))?;
+            *slot = value.as_inner().clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            check_pointer(path)?;
+            let inside = path.strip_prefix(from.as_str());
+            if inside.is_some_and(|rest| rest.starts_with('/')) {
+                return Err(PatchError::MoveIntoChild {
+                    from: from.clone(),
+                    path: path.clone(),
+                });
+            }
+            let value = pointer_remove(doc, from)?;
+            pointer_add(doc, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            check_pointer(from)?;
+            let value = doc
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| PatchError::NotFound(from.clone()))?;
+            pointer_add(doc, path, value)
+        }
+        PatchOperation::Test { path, value } => {
+            check_pointer(path)?;
+            match doc.pointer(path) {
+                Some(found) if found == value.as_inner() => Ok(()),
+                Some(_) => Err(PatchError::TestFailed(path.clone())),
+                None => Err(PatchError::NotFound(path.clone())),
+            }
+        }
+    }
+}
+
+fn check_pointer(path: &str) -> Result<(), PatchError> {
+    if path.is_empty() || path.starts_with('/') {
+        Ok(())
+    } else {
+        Err(PatchError::InvalidPointer(path.to_string()))
+    }
+}
+
+/// Split a non-empty pointer into its parent pointer and unescaped last token
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+    check_pointer(path)?;
+    let at = path.rfind('/').unwrap_or(0);
+    let token = path[at + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&path[..at], token))
+}
+
+/// Array index named by `token`: decimal digits without leading zeros
+fn pointer_index(token: &str) -> Option<usize> {
+    let digits = !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit());
+    if !digits || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+fn pointer_add(
+    doc: &mut serde_json::Value,
+    path: &str,
+    value: serde_json::Value,
+) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match doc.pointer_mut(parent) {
+        Some(serde_json::Value::Object(map)) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Some(serde_json::Value::Array(items)) => {
+            let index = if token == "-" {
+                Some(items.len())
+            } else {
+                pointer_index(&token).filter(|i| *i <= items.len())
+            };
+            let index = index.ok_or_else(|| PatchError::NotFound(path.to_string()))?;
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(PatchError::NotFound(path.to_string())),
+    }
+}
+
+fn pointer_remove(
+    doc: &mut serde_json::Value,
+    path: &str,
+) -> Result<serde_json::Value, PatchError> {
+    if path.is_empty() {
+        return Ok(std::mem::take(doc));
+    }
+    let (parent, token) = split_pointer(path)?;
+    let removed = match doc.pointer_mut(parent) {
+        Some(serde_json::Value::Object(map)) => map.remove(&token),
+        Some(serde_json::Value::Array(items)) => pointer_index(&token)
+            .filter(|i| *i < items.len())
+            .map(|i| items.remove(i)),
+        _ => None,
+    };
+    removed.ok_or_else(|| PatchError::NotFound(path.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3013,6 +3244,84 @@ mod tests {
         assert_eq!(arr.len(), 2);
     }
 
+    // =========================================================================
+    // RFC 7396 / RFC 6902 Tests
+    // =========================================================================
+
+    fn json_patch(ops: serde_json::Value) -> Vec<PatchOperation> {
+        serde_json::from_value(ops).unwrap()
+    }
+
+    #[test]
+    fn test_merge_patch_drops_nulls_in_added_objects() {
+        let mut doc: JsonValue = serde_json::json!({"a": 1}).into();
+        let patch: JsonValue = serde_json::json!({"b": {"c": 2, "d": null}}).into();
+        merge_patch(&mut doc, &patch);
+        assert_eq!(doc, serde_json::json!({"a": 1, "b": {"c": 2}}).into());
+    }
+
+    #[test]
+    fn test_apply_json_patch_operations() {
+        let mut doc: JsonValue =
+            serde_json::json!({"user": {"name": "Ann", "a/b": 1}, "tags": ["x", "z"]}).into();
+        let ops = json_patch(serde_json::json!([
+            {"op": "add", "path": "/tags/1", "value": "y"},
+            {"op": "add", "path": "/tags/-", "value": "end"},
+            {"op": "replace", "path": "/user/name", "value": "Bo"},
+            {"op": "copy", "from": "/user/name", "path": "/owner"},
+            {"op": "move", "from": "/user/a~1b", "path": "/count"},
+            {"op": "remove", "path": "/tags/0"},
+            {"op": "test", "path": "/count", "value": 1}
+        ]));
+        apply_json_patch(&mut doc, &ops).unwrap();
+        assert_eq!(
+            doc,
+            serde_json::json!({
+                "user": {"name": "Bo"},
+                "tags": ["y", "z", "end"],
+                "owner": "Bo",
+                "count": 1
+            })
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_apply_json_patch_failure_leaves_target_unchanged() {
+        let original: JsonValue = serde_json::json!({"a": {"b": 1}, "list": [1]}).into();
+        let cases = [
+            (
+                serde_json::json!([{"op": "add", "path": "/x", "value": 1},
+                                   {"op": "test", "path": "/a/b", "value": 2}]),
+                PatchError::TestFailed("/a/b".to_string()),
+            ),
+            (
+                serde_json::json!([{"op": "remove", "path": "/missing"}]),
+                PatchError::NotFound("/missing".to_string()),
+            ),
+            (
+                serde_json::json!([{"op": "replace", "path": "/list/1", "value": 2}]),
+                PatchError::NotFound("/list/1".to_string()),
+            ),
+            (
+                serde_json::json!([{"op": "add", "path": "a", "value": 1}]),
+                PatchError::InvalidPointer("a".to_string()),
+            ),
+            (
+                serde_json::json!([{"op": "move", "from": "/a", "path": "/a/c"}]),
+                PatchError::MoveIntoChild {
+                    from: "/a".to_string(),
+                    path: "/a/c".to_string(),
+                },
+            ),
+        ];
+        for (ops, expected) in cases {
+            let mut doc = original.clone();
+            assert_eq!(apply_json_patch(&mut doc, &json_patch(ops)), Err(expected));
+            assert_eq!(doc, original);
+        }
+    }
+
     // =========================================================================
     // Apply Patches Tests
     // =========================================================================
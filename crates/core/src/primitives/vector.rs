@@ -78,14 +78,20 @@ impl DistanceMetric {
 
 /// Storage data type for embeddings
 ///
-/// Only F32 supported initially. F16 and Int8 are reserved for future quantization.
+/// Controls how the in-memory index stores embeddings. `F16` halves and
+/// `Int8` quarters the memory of `F32`, at some cost in score precision.
+/// Queries are always `f32`, and the full-precision embedding is kept in
+/// the vector record, so reads return exactly what was written.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum StorageDtype {
     /// 32-bit floating point (default)
     #[default]
     F32,
-    // F16,     // Reserved for half precision (value = 1)
-    // Int8,    // Reserved for scalar quantization (value = 2)
+    /// IEEE 754 half precision (2 bytes per dimension)
+    F16,
+    /// Scalar quantization to 8 bits per dimension, with a per-vector
+    /// scale and offset
+    Int8,
 }
 
 impl StorageDtype {
@@ -93,6 +99,8 @@ impl StorageDtype {
     pub fn to_byte(&self) -> u8 {
         match self {
             StorageDtype::F32 => 0,
+            StorageDtype::F16 => 1,
+            StorageDtype::Int8 => 2,
         }
     }
 
@@ -100,9 +108,20 @@ impl StorageDtype {
     pub fn from_byte(b: u8) -> Option<Self> {
         match b {
             0 => Some(StorageDtype::F32),
+            1 => Some(StorageDtype::F16),
+            2 => Some(StorageDtype::Int8),
             _ => None,
         }
     }
+
+    /// Bytes used per embedding dimension
+    pub fn bytes_per_dimension(&self) -> usize {
+        match self {
+            StorageDtype::F32 => 4,
+            StorageDtype::F16 => 2,
+            StorageDtype::Int8 => 1,
+        }
+    }
 }
 
 /// Collection configuration - immutable after creation
@@ -119,8 +138,8 @@ pub struct VectorConfig {
     /// Immutable after collection creation.
     pub metric: DistanceMetric,
 
-    /// Storage data type
-    /// Only F32 supported initially. Reserved for F16/Int8 in future.
+    /// Storage data type for the in-memory index
+    /// Immutable after collection creation.
     pub storage_dtype: StorageDtype,
 }
 
@@ -141,6 +160,12 @@ impl VectorConfig {
         })
     }
 
+    /// Set the storage data type
+    pub fn with_storage_dtype(mut self, storage_dtype: StorageDtype) -> Self {
+        self.storage_dtype = storage_dtype;
+        self
+    }
+
     /// Config for OpenAI text-embedding-ada-002 (1536 dims)
     pub fn for_openai_ada() -> Self {
         VectorConfig {
@@ -459,6 +484,8 @@ pub enum FilterOp {
     In,
     /// String contains substring (string only)
     Contains,
+    /// String starts with prefix (string only)
+    Prefix,
 }
 
 /// A single filter condition on a metadata field
@@ -472,11 +499,21 @@ pub struct FilterCondition {
     pub value: JsonScalar,
 }
 
+/// A sub-filter combined into a [`MetadataFilter`] with OR or NOT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NestedFilter {
+    /// At least one of the filters must match (an empty list matches nothing)
+    Or(Vec<MetadataFilter>),
+    /// The filter must not match
+    Not(MetadataFilter),
+}
+
 /// Metadata filter for search
 ///
-/// Supports equality filtering via `equals` (backwards-compatible) and
-/// advanced filtering via `conditions` (Ne, Gt, Gte, Lt, Lte, In, Contains).
-/// All conditions use AND semantics.
+/// Supports equality filtering via `equals` (backwards-compatible),
+/// advanced filtering via `conditions` (Ne, Gt, Gte, Lt, Lte, In, Contains,
+/// Prefix) and OR/NOT groups via `nested`. Everything at the top level uses
+/// AND semantics.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MetadataFilter {
     /// Top-level field equality (scalar values only)
@@ -486,6 +523,9 @@ pub struct MetadataFilter {
     /// Advanced filter conditions (AND semantics)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<FilterCondition>,
+    /// OR / NOT groups (AND semantics with the rest of the filter)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nested: Vec<NestedFilter>,
 }
 
 impl MetadataFilter {
@@ -494,6 +534,7 @@ impl MetadataFilter {
         MetadataFilter {
             equals: HashMap::new(),
             conditions: Vec::new(),
+            nested: Vec::new(),
         }
     }
 
@@ -576,23 +617,58 @@ impl MetadataFilter {
         self
     }
 
+    /// Add a string-prefix condition (string only)
+    pub fn prefix(mut self, field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.conditions.push(FilterCondition {
+            field: field.into(),
+            op: FilterOp::Prefix,
+            value: JsonScalar::String(prefix.into()),
+        });
+        self
+    }
+
+    /// Add an inclusive numeric range condition (`min <= field <= max`)
+    pub fn range(
+        self,
+        field: impl Into<String>,
+        min: impl Into<JsonScalar>,
+        max: impl Into<JsonScalar>,
+    ) -> Self {
+        let field: String = field.into();
+        self.gte(field.clone(), min).lte(field, max)
+    }
+
+    /// Require at least one of `filters` to match
+    pub fn or(mut self, filters: Vec<MetadataFilter>) -> Self {
+        self.nested.push(NestedFilter::Or(filters));
+        self
+    }
+
+    /// Require `filter` not to match
+    pub fn not(mut self, filter: MetadataFilter) -> Self {
+        self.nested.push(NestedFilter::Not(filter));
+        self
+    }
+
     /// Check if metadata matches this filter
     ///
-    /// Returns true if all conditions match (AND semantics).
-    /// Returns false if metadata is None and filter is non-empty.
+    /// Returns true if all conditions match (AND semantics). Missing or
+    /// non-object metadata is treated as an object with no fields, so it
+    /// fails any field condition but can satisfy a NOT group.
     pub fn matches(&self, metadata: &Option<serde_json::Value>) -> bool {
-        if self.equals.is_empty() && self.conditions.is_empty() {
+        if self.is_empty() {
             return true;
         }
 
-        let Some(meta) = metadata else {
-            return false;
-        };
-
-        let Some(obj) = meta.as_object() else {
-            return false;
-        };
+        let empty = serde_json::Map::new();
+        let obj = metadata
+            .as_ref()
+            .and_then(|meta| meta.as_object())
+            .unwrap_or(&empty);
+        self.matches_object(obj)
+    }
 
+    fn matches_object(&self, obj: &serde_json::Map<String, serde_json::Value>) -> bool {
         // Check legacy equality conditions
         for (key, expected) in &self.equals {
             let Some(actual) = obj.get(key) else {
@@ -634,17 +710,23 @@ impl MetadataFilter {
             }
         }
 
-        true
+        // Evaluate OR / NOT groups
+        self.nested.iter().all(|nested| match nested {
+            NestedFilter::Or(filters) => filters.iter().any(|f| f.matches_object(obj)),
+            NestedFilter::Not(filter) => !filter.matches_object(obj),
+        })
     }
 
     /// Check if filter is empty (matches all)
     pub fn is_empty(&self) -> bool {
-        self.equals.is_empty() && self.conditions.is_empty()
+        self.equals.is_empty() && self.conditions.is_empty() && self.nested.is_empty()
     }
 
     /// Get the number of conditions in the filter
+    ///
+    /// Each OR / NOT group counts as one condition.
     pub fn len(&self) -> usize {
-        self.equals.len() + self.conditions.len()
+        self.equals.len() + self.conditions.len() + self.nested.len()
     }
 }
 
@@ -678,6 +760,12 @@ fn eval_condition(op: &FilterOp, expected: &JsonScalar, actual: &serde_json::Val
                 _ => false,
             }
         }
+        FilterOp::Prefix => match (expected, actual) {
+            (JsonScalar::String(prefix), serde_json::Value::String(s)) => {
+                s.starts_with(prefix.as_str())
+            }
+            _ => false,
+        },
     }
 }
 
@@ -808,13 +896,21 @@ mod tests {
 
     #[test]
     fn test_storage_dtype_byte_roundtrip() {
-        let dtype = StorageDtype::F32;
-        let byte = dtype.to_byte();
-        let restored = StorageDtype::from_byte(byte).unwrap();
-        assert_eq!(dtype, restored);
+        for dtype in [StorageDtype::F32, StorageDtype::F16, StorageDtype::Int8] {
+            let byte = dtype.to_byte();
+            let restored = StorageDtype::from_byte(byte).unwrap();
+            assert_eq!(dtype, restored);
+        }
         assert!(StorageDtype::from_byte(255).is_none());
     }
 
+    #[test]
+    fn test_storage_dtype_bytes_per_dimension() {
+        assert_eq!(StorageDtype::F32.bytes_per_dimension(), 4);
+        assert_eq!(StorageDtype::F16.bytes_per_dimension(), 2);
+        assert_eq!(StorageDtype::Int8.bytes_per_dimension(), 1);
+    }
+
     // ================================================================
     // VectorConfig
     // ================================================================
@@ -1028,6 +1124,70 @@ mod tests {
         assert!(!filter.matches(&Some(serde_json::json!([1, 2, 3]))));
     }
 
+    #[test]
+    fn test_metadata_filter_prefix() {
+        let filter = MetadataFilter::new().prefix("path", "docs/");
+        assert!(filter.matches(&Some(serde_json::json!({"path": "docs/intro.md"}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"path": "src/docs/x"}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"path": 42}))));
+    }
+
+    #[test]
+    fn test_metadata_filter_range_is_inclusive() {
+        let filter = MetadataFilter::new().range("year", 2000, 2010);
+        assert!(filter.matches(&Some(serde_json::json!({"year": 2000}))));
+        assert!(filter.matches(&Some(serde_json::json!({"year": 2010}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"year": 2011}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"year": "2005"}))));
+    }
+
+    #[test]
+    fn test_metadata_filter_or() {
+        let filter = MetadataFilter::new().eq("kind", "doc").or(vec![
+            MetadataFilter::new().eq("lang", "en"),
+            MetadataFilter::new().gt("score", 0.9),
+        ]);
+        assert!(filter.matches(&Some(serde_json::json!({"kind": "doc", "lang": "en"}))));
+        assert!(filter.matches(&Some(serde_json::json!({"kind": "doc", "score": 0.95}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"kind": "doc", "lang": "fr"}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"kind": "img", "lang": "en"}))));
+    }
+
+    #[test]
+    fn test_metadata_filter_empty_or_matches_nothing() {
+        let filter = MetadataFilter::new().or(vec![]);
+        assert!(!filter.is_empty());
+        assert!(!filter.matches(&Some(serde_json::json!({"a": 1}))));
+    }
+
+    #[test]
+    fn test_metadata_filter_not() {
+        let filter = MetadataFilter::new().not(MetadataFilter::new().eq("archived", true));
+        assert!(filter.matches(&Some(serde_json::json!({"archived": false}))));
+        assert!(filter.matches(&Some(serde_json::json!({"other": 1}))));
+        assert!(!filter.matches(&Some(serde_json::json!({"archived": true}))));
+        // No metadata has no `archived` field, so NOT matches
+        assert!(filter.matches(&None));
+        assert_eq!(filter.len(), 1);
+    }
+
+    #[test]
+    fn test_metadata_filter_nested_serialization_roundtrip() {
+        let filter = MetadataFilter::new()
+            .prefix("path", "a/")
+            .or(vec![MetadataFilter::new().eq("x", 1)])
+            .not(MetadataFilter::new().eq("y", 2));
+        let json = serde_json::to_string(&filter).unwrap();
+        let back: MetadataFilter = serde_json::from_str(&json).unwrap();
+        let meta = Some(serde_json::json!({"path": "a/b", "x": 1, "y": 3}));
+        assert!(back.matches(&meta));
+        assert_eq!(back.len(), filter.len());
+
+        // Filters serialized before `nested` existed still deserialize
+        let legacy: MetadataFilter = serde_json::from_str(r#"{"equals":{}}"#).unwrap();
+        assert!(legacy.is_empty());
+    }
+
     // ================================================================
     // CollectionInfo
     // ================================================================
@@ -1225,16 +1385,17 @@ mod tests {
 
     #[test]
     fn test_storage_dtype_serde_roundtrip() {
-        let dtype = StorageDtype::F32;
-        let json = serde_json::to_string(&dtype).unwrap();
-        let restored: StorageDtype = serde_json::from_str(&json).unwrap();
-        assert_eq!(dtype, restored);
+        for dtype in [StorageDtype::F32, StorageDtype::F16, StorageDtype::Int8] {
+            let json = serde_json::to_string(&dtype).unwrap();
+            let restored: StorageDtype = serde_json::from_str(&json).unwrap();
+            assert_eq!(dtype, restored);
+        }
     }
 
     #[test]
     fn test_storage_dtype_from_byte_reserved_values() {
-        // Bytes 1+ are reserved for future F16/Int8
-        for b in 1..=255u8 {
+        // Bytes 3+ are reserved for future dtypes
+        for b in 3..=255u8 {
             assert!(
                 StorageDtype::from_byte(b).is_none(),
                 "Byte {} should not map to a dtype",
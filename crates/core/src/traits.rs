@@ -163,6 +163,23 @@ pub trait Storage: Send + Sync {
     ///
     /// Returns an error if the storage operation fails.
     fn delete_with_version(&self, key: &Key, version: u64) -> StrataResult<Option<VersionedValue>>;
+
+    /// Reserve a commit version whose writes will be applied later
+    ///
+    /// Used by pipelined commits, which allocate versions in WAL order but
+    /// apply them to storage concurrently. While `version` is reserved,
+    /// snapshots must not observe it or any later version, so a reader never
+    /// sees a commit without every commit ordered before it.
+    ///
+    /// The default does nothing, for stores that apply commits in order.
+    fn reserve_version(&self, _version: u64) {}
+
+    /// Publish a version reserved with [`reserve_version`](Self::reserve_version)
+    ///
+    /// Called once the version's writes are applied, or its commit failed.
+    /// Returns when `version` is visible to new snapshots, i.e. after every
+    /// lower reserved version has been published too.
+    fn publish_version(&self, _version: u64) {}
 }
 
 /// Snapshot view abstraction for snapshot isolation
@@ -199,10 +216,42 @@ pub trait SnapshotView: Send + Sync {
     /// Returns an error if the storage operation fails.
     fn scan_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>>;
 
+    /// Scan at most `limit` keys with prefix from snapshot, starting at `start`
+    ///
+    /// Returns matching keys at or after `start` in key order. The default
+    /// filters [`scan_prefix`](Self::scan_prefix); stores that keep keys
+    /// ordered override it to skip the keys before `start`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the storage operation fails.
+    fn scan_prefix_from(
+        &self,
+        prefix: &Key,
+        start: &Key,
+        limit: usize,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        Ok(self
+            .scan_prefix(prefix)?
+            .into_iter()
+            .filter(|(key, _)| key >= start)
+            .take(limit)
+            .collect())
+    }
+
     /// Get snapshot version
     ///
     /// Returns the version this snapshot was created at.
     fn version(&self) -> u64;
+
+    /// A fresh view of the same storage at its current version
+    ///
+    /// Lets a transaction read a key it has locked as last committed rather
+    /// than as of its start. Returns None for views that are detached from
+    /// live storage.
+    fn latest(&self) -> Option<Box<dyn SnapshotView>> {
+        None
+    }
 }
 
 #[cfg(test)]
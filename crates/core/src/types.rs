@@ -168,13 +168,16 @@ impl PartialOrd for Namespace {
 /// - Event = 0x02
 /// - State = 0x03
 /// - Branch = 0x05
+/// - Space = 0x06
+/// - List = 0x07
 /// - Vector = 0x10 (vector metadata)
 /// - Json = 0x11 (JSON primitive)
 /// - VectorConfig = 0x12 (vector collection config)
+/// - Custom = 0x30 (custom primitives, keyed by primitive ID)
 ///
 /// Note: 0x04 was formerly Trace (TraceStore was removed in 0.12.0)
 ///
-/// Ordering: KV < Event < State < Branch < Vector < Json < VectorConfig
+/// Ordering: KV < Event < State < Branch < Space < List < Vector < Json < VectorConfig < Custom
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 #[repr(u8)]
 pub enum TypeTag {
@@ -191,12 +194,16 @@ pub enum TypeTag {
     Branch = 0x05,
     /// Space metadata entries
     Space = 0x06,
+    /// List (queue) metadata and elements
+    List = 0x07,
     /// Vector store entries
     Vector = 0x10,
     /// JSON document store entries
     Json = 0x11,
     /// Vector collection configuration
     VectorConfig = 0x12,
+    /// Custom primitive entries; the first user key byte is the primitive ID
+    Custom = 0x30,
 }
 
 impl TypeTag {
@@ -215,9 +222,11 @@ impl TypeTag {
             0x04 => Some(TypeTag::Trace), // Deprecated but needed for backwards compatibility
             0x05 => Some(TypeTag::Branch),
             0x06 => Some(TypeTag::Space),
+            0x07 => Some(TypeTag::List),
             0x10 => Some(TypeTag::Vector),
             0x11 => Some(TypeTag::Json),
             0x12 => Some(TypeTag::VectorConfig),
+            0x30 => Some(TypeTag::Custom),
             _ => None,
         }
     }
@@ -285,6 +294,17 @@ impl Key {
         Self::new(namespace, TypeTag::KV, key.as_ref().to_vec())
     }
 
+    /// Create a KV history policy key
+    ///
+    /// Stores the history policy for KV keys starting with `prefix`.
+    /// Key format: `__history__{prefix}`
+    pub fn new_kv_history_policy(namespace: Namespace, prefix: &str) -> Self {
+        let mut user_key = Vec::with_capacity(11 + prefix.len());
+        user_key.extend_from_slice(b"__history__");
+        user_key.extend_from_slice(prefix.as_bytes());
+        Self::new(namespace, TypeTag::KV, user_key)
+    }
+
     /// Create an event key with sequence number
     ///
     /// Helper that automatically sets type_tag to TypeTag::Event and
@@ -328,6 +348,41 @@ impl Key {
         Self::new(namespace, TypeTag::Event, user_key)
     }
 
+    /// Create an event stream retention policy key
+    ///
+    /// Stores the retention policy for one event type.
+    /// Key format: `__retention__{event_type}`
+    pub fn new_event_retention(namespace: Namespace, event_type: &str) -> Self {
+        let mut user_key = Vec::with_capacity(13 + event_type.len());
+        user_key.extend_from_slice(b"__retention__");
+        user_key.extend_from_slice(event_type.as_bytes());
+        Self::new(namespace, TypeTag::Event, user_key)
+    }
+
+    /// Create an event stream payload schema key
+    ///
+    /// Stores the JSON schema appends to one event type must match.
+    /// Key format: `__evschema__{event_type}`
+    pub fn new_event_schema(namespace: Namespace, event_type: &str) -> Self {
+        let mut user_key = Vec::with_capacity(12 + event_type.len());
+        user_key.extend_from_slice(b"__evschema__");
+        user_key.extend_from_slice(event_type.as_bytes());
+        Self::new(namespace, TypeTag::Event, user_key)
+    }
+
+    /// Create an event stream consumer group key
+    ///
+    /// Stores one consumer group's delivery cursor and pending entries.
+    /// Key format: `__group__{event_type}\0{group}`
+    pub fn new_event_group(namespace: Namespace, event_type: &str, group: &str) -> Self {
+        let mut user_key = Vec::with_capacity(9 + event_type.len() + 1 + group.len());
+        user_key.extend_from_slice(b"__group__");
+        user_key.extend_from_slice(event_type.as_bytes());
+        user_key.push(0); // null separator
+        user_key.extend_from_slice(group.as_bytes());
+        Self::new(namespace, TypeTag::Event, user_key)
+    }
+
     /// Create a state cell key
     ///
     /// Helper that automatically sets type_tag to TypeTag::State
@@ -335,6 +390,27 @@ impl Key {
         Self::new(namespace, TypeTag::State, key.as_ref().to_vec())
     }
 
+    /// Create a list metadata key
+    ///
+    /// Stores the list's head and tail positions. Key format: `{list}`
+    pub fn new_list(namespace: Namespace, list: &str) -> Self {
+        Self::new(namespace, TypeTag::List, list.as_bytes().to_vec())
+    }
+
+    /// Create a list element key
+    ///
+    /// Key format: `{list}\0{position_be_bytes}`
+    ///
+    /// The null byte separator keeps elements of one list apart from
+    /// other lists' metadata, and big-endian positions sort in list order.
+    pub fn new_list_item(namespace: Namespace, list: &str, position: u64) -> Self {
+        let mut user_key = Vec::with_capacity(list.len() + 1 + 8);
+        user_key.extend_from_slice(list.as_bytes());
+        user_key.push(0); // null separator
+        user_key.extend_from_slice(&position.to_be_bytes());
+        Self::new(namespace, TypeTag::List, user_key)
+    }
+
     /// Create a branch index key
     ///
     /// Helper that automatically sets type_tag to TypeTag::Branch and
@@ -388,6 +464,17 @@ impl Key {
         Self::new(namespace, TypeTag::Json, doc_id.as_bytes().to_vec())
     }
 
+    /// Create a JSON document schema key
+    ///
+    /// Stores the schema documents whose ids start with `prefix` must match.
+    /// Key format: `__jsonschema__{prefix}`
+    pub fn new_json_schema(namespace: Namespace, prefix: &str) -> Self {
+        let mut user_key = Vec::with_capacity(14 + prefix.len());
+        user_key.extend_from_slice(b"__jsonschema__");
+        user_key.extend_from_slice(prefix.as_bytes());
+        Self::new(namespace, TypeTag::Json, user_key)
+    }
+
     /// Create prefix for scanning all JSON docs in namespace
     ///
     /// This key can be used with starts_with() to match all JSON
@@ -442,6 +529,38 @@ impl Key {
         Self::new(namespace, TypeTag::Space, vec![])
     }
 
+    /// Create a schema log entry key.
+    ///
+    /// Uses the branch-level namespace so one log covers every space.
+    /// Key format: `__schema__{entry}`
+    pub fn new_schema_log(branch_id: BranchId, entry: &str) -> Self {
+        let namespace = Namespace::for_branch(branch_id);
+        let mut user_key = Vec::with_capacity(10 + entry.len());
+        user_key.extend_from_slice(b"__schema__");
+        user_key.extend_from_slice(entry.as_bytes());
+        Self::new(namespace, TypeTag::KV, user_key)
+    }
+
+    /// Prefix for scanning all schema log entries of a branch.
+    pub fn new_schema_log_prefix(branch_id: BranchId) -> Self {
+        Self::new_schema_log(branch_id, "")
+    }
+
+    /// Create key for a custom primitive entry
+    ///
+    /// Format: namespace + TypeTag::Custom + primitive_id + key
+    pub fn new_custom(namespace: Namespace, primitive_id: u8, key: &[u8]) -> Self {
+        let mut user_key = Vec::with_capacity(1 + key.len());
+        user_key.push(primitive_id);
+        user_key.extend_from_slice(key);
+        Self::new(namespace, TypeTag::Custom, user_key)
+    }
+
+    /// Create prefix for scanning all entries of a custom primitive
+    pub fn new_custom_prefix(namespace: Namespace, primitive_id: u8) -> Self {
+        Self::new_custom(namespace, primitive_id, &[])
+    }
+
     /// Extract user key as string (if valid UTF-8)
     ///
     /// Returns None if the user_key is not valid UTF-8
@@ -1027,6 +1146,7 @@ mod tests {
         assert_eq!(TypeTag::from_byte(0x04), Some(TypeTag::Trace));
         assert_eq!(TypeTag::from_byte(0x05), Some(TypeTag::Branch));
         assert_eq!(TypeTag::from_byte(0x06), Some(TypeTag::Space));
+        assert_eq!(TypeTag::from_byte(0x07), Some(TypeTag::List));
         assert_eq!(TypeTag::from_byte(0x10), Some(TypeTag::Vector));
         assert_eq!(TypeTag::from_byte(0x11), Some(TypeTag::Json));
         assert_eq!(TypeTag::from_byte(0x00), None);
@@ -1042,6 +1162,7 @@ mod tests {
             TypeTag::State,
             TypeTag::Branch,
             TypeTag::Space,
+            TypeTag::List,
             TypeTag::Vector,
             TypeTag::Json,
         ];
@@ -1059,6 +1180,7 @@ mod tests {
             TypeTag::State,
             TypeTag::Branch,
             TypeTag::Space,
+            TypeTag::List,
             TypeTag::Vector,
             TypeTag::Json,
         ];
@@ -1078,8 +1200,8 @@ mod tests {
     fn test_typetag_from_byte_gap_values_return_none() {
         // Bytes between defined variants must return None (on-disk format safety)
         for byte in [
-            0x00, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x13, 0x14, 0x20, 0x80,
-            0xFE, 0xFF,
+            0x00, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x13, 0x14, 0x20, 0x80, 0xFE,
+            0xFF,
         ] {
             assert_eq!(
                 TypeTag::from_byte(byte),
@@ -1108,9 +1230,11 @@ mod tests {
             TypeTag::Trace,
             TypeTag::Branch,
             TypeTag::Space,
+            TypeTag::List,
             TypeTag::Vector,
             TypeTag::Json,
             TypeTag::VectorConfig,
+            TypeTag::Custom,
         ];
         for tag in all_tags {
             let byte = tag.as_byte();
@@ -1136,9 +1260,11 @@ mod tests {
             TypeTag::Trace,
             TypeTag::Branch,
             TypeTag::Space,
+            TypeTag::List,
             TypeTag::Vector,
             TypeTag::Json,
             TypeTag::VectorConfig,
+            TypeTag::Custom,
         ];
         for window in tags_in_order.windows(2) {
             assert!(
@@ -1306,6 +1432,21 @@ mod tests {
         assert!(key2 < key3);
     }
 
+    #[test]
+    fn test_list_item_keys_sort_by_position_within_list() {
+        let ns = Namespace::for_branch(BranchId::new());
+
+        let first = Key::new_list_item(ns.clone(), "jobs", 1);
+        let second = Key::new_list_item(ns.clone(), "jobs", 256);
+        assert!(first < second);
+
+        // Items live under `{list}\0`, so they never match another list's prefix
+        let prefix = Key::new(ns.clone(), TypeTag::List, b"jobs\0".to_vec());
+        assert!(first.starts_with(&prefix));
+        assert!(!Key::new_list_item(ns.clone(), "jobs2", 1).starts_with(&prefix));
+        assert!(!Key::new_list(ns, "jobs").starts_with(&prefix));
+    }
+
     #[test]
     fn test_keys_with_same_inputs_are_equal() {
         let branch_id = BranchId::new();
@@ -1546,6 +1687,22 @@ mod tests {
         assert_eq!(key.user_key_string().unwrap(), "my_collection");
     }
 
+    #[test]
+    fn test_key_custom_prefix_matches_primitive() {
+        let ns = Namespace::for_branch(BranchId::new());
+        let prefix = Key::new_custom_prefix(ns.clone(), 0x80);
+        let key = Key::new_custom(ns.clone(), 0x80, b"item");
+        let other = Key::new_custom(ns.clone(), 0x81, b"item");
+
+        assert_eq!(key.type_tag, TypeTag::Custom);
+        assert_eq!(key.user_key, b"\x80item");
+        assert!(key.starts_with(&prefix));
+        assert!(
+            !other.starts_with(&prefix),
+            "Entry of another primitive should not match"
+        );
+    }
+
     #[test]
     fn test_key_vector_collection_prefix_matches_vectors() {
         let ns = Namespace::for_branch(BranchId::new());
@@ -54,12 +54,9 @@ pub enum Value {
     String(String),
     /// Raw bytes.
     ///
-    /// **JSON roundtrip note**: When a `Value::Bytes` is serialized to JSON
-    /// (e.g., via `serde_json`), the bytes are base64-encoded into a JSON string.
-    /// Deserializing that JSON string back produces `Value::String`, not
-    /// `Value::Bytes`. This means `Bytes` values do not survive a JSON roundtrip
-    /// with their original type intact. Use binary serialization (e.g., bincode,
-    /// MessagePack) for lossless `Bytes` roundtrips.
+    /// **JSON roundtrip note**: Converting to `serde_json::Value` with `From`
+    /// writes `{"$bytes": "<base64>"}`, which converts back to `Bytes`. The
+    /// derived `Serialize` impl is a different, externally tagged encoding.
     Bytes(Vec<u8>),
     /// Array of values
     Array(Vec<Value>),
@@ -277,31 +274,59 @@ impl From<()> for Value {
 }
 
 // ============================================================================
-// serde_json interop for ergonomic JSON construction
+// serde_json interop
 // ============================================================================
+//
+// JSON has no bytes and no non-finite floats, so those are written as
+// single-key wrapper objects, as in the executor's canonical JSON:
+//
+// | Value | JSON |
+// |-------|------|
+// | `Bytes` | `{"$bytes": "<base64>"}` |
+// | `Float(NaN)` | `{"$f64": "NaN"}` |
+// | `Float(±inf)` | `{"$f64": "+Inf"}` / `{"$f64": "-Inf"}` |
+// | `Float(-0.0)` | `{"$f64": "-0.0"}` |
+//
+// Every Value survives the round trip through JSON, short of an Object that
+// is itself shaped like a wrapper. The other way, a JSON integer beyond
+// `i64` becomes a `Float`; everything else maps exactly. An object with
+// extra keys or a non-string payload isn't a wrapper and stays an ordinary
+// object, but a wrapper whose payload doesn't decode (bad base64, an
+// unknown `$f64` name) is an error when reading through `TryFrom<&_>`. The
+// owned `From` is kept for `json!` literals and leaves such a wrapper as an
+// ordinary object.
+
+/// A JSON value that has no [`Value`] equivalent
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum JsonValueError {
+    /// A `$bytes` wrapper whose payload is not standard padded base64
+    #[error("Invalid base64: {0:?}")]
+    InvalidBase64(String),
+    /// A `$f64` wrapper whose payload is not a float
+    #[error("Invalid float: {0:?}")]
+    InvalidFloat(String),
+    /// A number that is neither an `i64` nor an `f64`
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+}
 
-impl From<serde_json::Value> for Value {
-    fn from(v: serde_json::Value) -> Self {
+impl From<&Value> for serde_json::Value {
+    fn from(v: &Value) -> Self {
         match v {
-            serde_json::Value::Null => Value::Null,
-            serde_json::Value::Bool(b) => Value::Bool(b),
-            serde_json::Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    Value::Int(i)
-                } else if let Some(f) = n.as_f64() {
-                    Value::Float(f)
-                } else {
-                    // Fallback for u64 that doesn't fit in i64
-                    Value::Float(n.as_f64().unwrap_or(0.0))
-                }
-            }
-            serde_json::Value::String(s) => Value::String(s),
-            serde_json::Value::Array(arr) => {
-                Value::Array(arr.into_iter().map(Value::from).collect())
-            }
-            serde_json::Value::Object(obj) => {
-                Value::Object(obj.into_iter().map(|(k, v)| (k, Value::from(v))).collect())
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Int(i) => serde_json::Value::Number((*i).into()),
+            Value::Float(f) => float_to_json(*f),
+            Value::String(s) => serde_json::Value::String(s.clone()),
+            Value::Bytes(b) => serde_json::json!({ "$bytes": base64_encode(b) }),
+            Value::Array(arr) => {
+                serde_json::Value::Array(arr.iter().map(serde_json::Value::from).collect())
             }
+            Value::Object(obj) => serde_json::Value::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), serde_json::Value::from(v)))
+                    .collect(),
+            ),
         }
     }
 }
@@ -312,14 +337,9 @@ impl From<Value> for serde_json::Value {
             Value::Null => serde_json::Value::Null,
             Value::Bool(b) => serde_json::Value::Bool(b),
             Value::Int(i) => serde_json::Value::Number(i.into()),
-            Value::Float(f) => serde_json::Number::from_f64(f)
-                .map(serde_json::Value::Number)
-                .unwrap_or(serde_json::Value::Null),
+            Value::Float(f) => float_to_json(f),
             Value::String(s) => serde_json::Value::String(s),
-            Value::Bytes(b) => {
-                // Encode bytes as base64 string for JSON compatibility
-                serde_json::Value::String(base64_encode(&b))
-            }
+            Value::Bytes(b) => serde_json::json!({ "$bytes": base64_encode(&b) }),
             Value::Array(arr) => {
                 serde_json::Value::Array(arr.into_iter().map(serde_json::Value::from).collect())
             }
@@ -332,6 +352,109 @@ impl From<Value> for serde_json::Value {
     }
 }
 
+impl TryFrom<&serde_json::Value> for Value {
+    type Error = JsonValueError;
+
+    fn try_from(v: &serde_json::Value) -> Result<Self, Self::Error> {
+        Ok(match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(*b),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                (Some(i), _) => Value::Int(i),
+                // Integers beyond i64 and all non-integers
+                (None, Some(f)) => Value::Float(f),
+                (None, None) => return Err(JsonValueError::InvalidNumber(n.to_string())),
+            },
+            serde_json::Value::String(s) => Value::String(s.clone()),
+            serde_json::Value::Array(arr) => {
+                Value::Array(arr.iter().map(Value::try_from).collect::<Result<_, _>>()?)
+            }
+            serde_json::Value::Object(obj) => match json_wrapper(obj)? {
+                Some(value) => value,
+                None => Value::Object(
+                    obj.iter()
+                        .map(|(k, v)| Ok((k.clone(), Value::try_from(v)?)))
+                        .collect::<Result<_, JsonValueError>>()?,
+                ),
+            },
+        })
+    }
+}
+
+/// Lossy conversion for building values with `json!(..).into()`.
+///
+/// Unlike `Value::try_from(&json)`, this never fails: a `$bytes` or `$f64`
+/// wrapper whose payload doesn't decode is kept as an ordinary object.
+/// Use the `TryFrom` impl when the JSON comes from outside the program.
+impl From<serde_json::Value> for Value {
+    fn from(v: serde_json::Value) -> Self {
+        match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Int(i),
+                None => Value::Float(n.as_f64().unwrap_or(0.0)),
+            },
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(arr) => {
+                Value::Array(arr.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(obj) => match json_wrapper(&obj) {
+                Ok(Some(value)) => value,
+                _ => Value::Object(obj.into_iter().map(|(k, v)| (k, Value::from(v))).collect()),
+            },
+        }
+    }
+}
+
+/// The Bytes or Float a `$bytes` / `$f64` wrapper object encodes, or `None`
+/// if `obj` isn't a wrapper
+fn json_wrapper(
+    obj: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Option<Value>, JsonValueError> {
+    if obj.len() != 1 {
+        return Ok(None);
+    }
+    match obj.iter().next() {
+        Some((k, serde_json::Value::String(s))) if k == "$bytes" => base64_decode(s)
+            .map(|b| Some(Value::Bytes(b)))
+            .ok_or_else(|| JsonValueError::InvalidBase64(s.clone())),
+        Some((k, serde_json::Value::String(s))) if k == "$f64" => special_float_from_str(s)
+            .map(|f| Some(Value::Float(f)))
+            .ok_or_else(|| JsonValueError::InvalidFloat(s.clone())),
+        _ => Ok(None),
+    }
+}
+
+/// JSON number for finite floats, `$f64` wrapper otherwise
+fn float_to_json(f: f64) -> serde_json::Value {
+    let special = if f.is_nan() {
+        "NaN"
+    } else if f == f64::INFINITY {
+        "+Inf"
+    } else if f == f64::NEG_INFINITY {
+        "-Inf"
+    } else if f == 0.0 && f.is_sign_negative() {
+        "-0.0"
+    } else {
+        return serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| serde_json::json!({ "$f64": f.to_string() }));
+    };
+    serde_json::json!({ "$f64": special })
+}
+
+/// Parse the payload of a `$f64` wrapper
+fn special_float_from_str(s: &str) -> Option<f64> {
+    match s {
+        "NaN" => Some(f64::NAN),
+        "+Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        "-0.0" => Some(-0.0),
+        other => other.parse().ok(),
+    }
+}
+
 /// Simple base64 encoding for bytes (no external dependency)
 fn base64_encode(data: &[u8]) -> String {
     use std::fmt::Write;
@@ -371,6 +494,40 @@ fn base64_encode(data: &[u8]) -> String {
     result
 }
 
+/// Decode standard padded base64, the inverse of [`base64_encode`]
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    fn sextet(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Some((c - b'0' + 52) as u32),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let chunks = text.as_bytes().chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+    let count = chunks.len();
+    let mut out = Vec::with_capacity(count * 3);
+    for (i, chunk) in chunks.enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != count) {
+            return None;
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            n = (n << 6) | sextet(c)?;
+        }
+        n <<= 6 * padding as u32;
+        out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Some(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -616,37 +773,39 @@ mod tests {
         // Value -> serde_json::Value -> Value
         let original = Value::Int(42);
         let json: serde_json::Value = original.clone().into();
-        let restored: Value = json.into();
+        let restored = Value::try_from(&json).unwrap();
         assert_eq!(original, restored);
 
         let original = Value::String("test".to_string());
         let json: serde_json::Value = original.clone().into();
-        let restored: Value = json.into();
+        let restored = Value::try_from(&json).unwrap();
         assert_eq!(original, restored);
 
         let original = Value::Bool(true);
         let json: serde_json::Value = original.clone().into();
-        let restored: Value = json.into();
+        let restored = Value::try_from(&json).unwrap();
         assert_eq!(original, restored);
 
         let original = Value::Null;
         let json: serde_json::Value = original.clone().into();
-        let restored: Value = json.into();
+        let restored = Value::try_from(&json).unwrap();
         assert_eq!(original, restored);
     }
 
     #[test]
-    fn test_serde_json_float_nan_becomes_null() {
-        // NaN cannot be represented in JSON; From<Value> for serde_json::Value maps it to Null
+    fn test_serde_json_nan_uses_f64_wrapper() {
+        // NaN has no JSON number, so it is written as a $f64 wrapper
         let v = Value::Float(f64::NAN);
         let json: serde_json::Value = v.into();
-        assert!(json.is_null());
+        assert_eq!(json, serde_json::json!({"$f64": "NaN"}));
+        let restored = Value::try_from(&json).unwrap();
+        assert!(restored.as_float().unwrap().is_nan());
     }
 
     #[test]
     fn test_serde_json_nested_conversion() {
         let json = serde_json::json!({"a": [1, 2, "three"], "b": null});
-        let v: Value = json.into();
+        let v = Value::try_from(&json).unwrap();
         assert!(v.is_object());
         let obj = v.as_object().unwrap();
         assert!(obj.get("a").unwrap().is_array());
@@ -832,44 +991,96 @@ mod tests {
     // ====================================================================
 
     #[test]
-    fn test_serde_json_infinity_becomes_null() {
-        let v = Value::Float(f64::INFINITY);
-        let json: serde_json::Value = v.into();
-        assert!(json.is_null(), "Infinity should become null in JSON");
+    fn test_serde_json_special_floats_round_trip() {
+        for (f, tag) in [
+            (f64::INFINITY, "+Inf"),
+            (f64::NEG_INFINITY, "-Inf"),
+            (-0.0, "-0.0"),
+        ] {
+            let json: serde_json::Value = Value::Float(f).into();
+            assert_eq!(json, serde_json::json!({ "$f64": tag }));
+            let restored = Value::try_from(&json).unwrap().as_float().unwrap();
+            assert_eq!(restored.to_bits(), f.to_bits());
+        }
     }
 
     #[test]
-    fn test_serde_json_neg_infinity_becomes_null() {
-        let v = Value::Float(f64::NEG_INFINITY);
-        let json: serde_json::Value = v.into();
-        assert!(
-            json.is_null(),
-            "Negative infinity should become null in JSON"
+    fn test_serde_json_bytes_round_trip() {
+        let original = Value::Bytes(vec![0, 1, 2, 255]);
+        let json: serde_json::Value = original.clone().into();
+        assert_eq!(json, serde_json::json!({"$bytes": "AAEC/w=="}));
+        assert_eq!(Value::try_from(&json).unwrap(), original);
+    }
+
+    #[test]
+    fn test_serde_json_wrapper_lookalikes_stay_objects() {
+        let json = serde_json::json!({"$bytes": "AAEC", "len": 3});
+        assert!(Value::try_from(&json).unwrap().is_object());
+        let json = serde_json::json!({"$f64": 1.5});
+        assert!(Value::try_from(&json).unwrap().is_object());
+    }
+
+    #[test]
+    fn test_serde_json_undecodable_wrappers_are_errors() {
+        assert_eq!(
+            Value::try_from(&serde_json::json!({"$bytes": "!!"})),
+            Err(JsonValueError::InvalidBase64("!!".to_string()))
+        );
+        assert_eq!(
+            Value::try_from(&serde_json::json!({"nested": [{"$f64": "one"}]})),
+            Err(JsonValueError::InvalidFloat("one".to_string()))
         );
     }
 
     #[test]
-    fn test_serde_json_bytes_is_lossy() {
-        // Bytes -> serde_json::Value produces base64 string
-        // serde_json::Value (String) -> Value produces Value::String, NOT Value::Bytes
-        let original = Value::Bytes(vec![1, 2, 3]);
-        let json: serde_json::Value = original.into();
-        assert!(
-            json.is_string(),
-            "Bytes should become base64 string in JSON"
+    fn test_serde_json_from_keeps_undecodable_wrappers_as_objects() {
+        let v = Value::from(serde_json::json!({"$bytes": "!!"}));
+        assert_eq!(
+            v,
+            Value::Object(HashMap::from([(
+                "$bytes".to_string(),
+                Value::String("!!".to_string())
+            )]))
         );
-        let restored: Value = json.into();
-        assert!(
-            restored.is_string(),
-            "Converting back produces String, not Bytes (lossy)"
+        let v: Value = serde_json::json!({"nested": [{"$f64": "+Inf"}], "n": 2}).into();
+        assert_eq!(v.as_object().unwrap()["n"], Value::Int(2));
+        assert_eq!(
+            v.as_object().unwrap()["nested"],
+            Value::Array(vec![Value::Float(f64::INFINITY)])
         );
     }
 
+    #[test]
+    fn test_serde_json_borrowed_conversions_match_owned() {
+        let original = Value::Object(HashMap::from([
+            ("bytes".to_string(), Value::Bytes(vec![1, 2])),
+            ("inf".to_string(), Value::Float(f64::INFINITY)),
+            ("list".to_string(), Value::Array(vec![Value::Int(1)])),
+        ]));
+        let json = serde_json::Value::from(&original);
+        assert_eq!(json, serde_json::Value::from(original.clone()));
+        assert_eq!(Value::try_from(&json).unwrap(), original);
+    }
+
+    #[test]
+    fn test_base64_decode_known_vectors() {
+        for text in [
+            "", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy",
+        ] {
+            let decoded = base64_decode(text).unwrap();
+            assert_eq!(base64_encode(&decoded), text);
+        }
+        assert_eq!(base64_decode("Zm9vYmFy").unwrap(), b"foobar");
+        assert_eq!(base64_decode("Zg"), None);
+        assert_eq!(base64_decode("Zg==Zm9v"), None);
+        assert_eq!(base64_decode("Z!=="), None);
+    }
+
     #[test]
     fn test_serde_json_u64_max_conversion() {
         // u64::MAX cannot fit in i64, so it goes through the f64 fallback
         let json = serde_json::json!(u64::MAX);
-        let v: Value = json.into();
+        let v = Value::try_from(&json).unwrap();
         // Should become Float since it doesn't fit in i64
         assert!(
             v.is_float(),
@@ -880,7 +1091,7 @@ mod tests {
     #[test]
     fn test_serde_json_large_negative_int() {
         let json = serde_json::json!(i64::MIN);
-        let v: Value = json.into();
+        let v = Value::try_from(&json).unwrap();
         assert_eq!(v, Value::Int(i64::MIN));
     }
 }
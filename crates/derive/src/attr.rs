@@ -0,0 +1,154 @@
+//! `#[strata(...)]` attribute parsing.
+
+use syn::{Attribute, LitStr, Path, Result};
+
+/// Container attributes: `crate = "..."` and `rename_all = "..."`.
+pub struct ContainerAttrs {
+    /// Path the Value types are reachable under (default `::stratadb`)
+    pub krate: Path,
+    pub rename_all: Option<RenameRule>,
+}
+
+/// Field and variant attributes.
+#[derive(Default)]
+pub struct MemberAttrs {
+    pub rename: Option<String>,
+    /// Store a `Vec<u8>` as `Value::Bytes`
+    pub bytes: bool,
+    /// Use `Default::default()` when the field is missing
+    pub default: bool,
+    /// Neither written nor read; filled with `Default::default()`
+    pub skip: bool,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut out = ContainerAttrs {
+            krate: syn::parse_quote!(::stratadb),
+            rename_all: None,
+        };
+        for attr in attrs.iter().filter(|a| a.path().is_ident("strata")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("crate") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    out.krate = lit.parse()?;
+                } else if meta.path.is_ident("rename_all") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    out.rename_all = Some(RenameRule::parse(&lit)?);
+                } else {
+                    return Err(meta.error("expected `crate` or `rename_all`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+impl MemberAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut out = MemberAttrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("strata")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    out.rename = Some(lit.value());
+                } else if meta.path.is_ident("bytes") {
+                    out.bytes = true;
+                } else if meta.path.is_ident("default") {
+                    out.default = true;
+                } else if meta.path.is_ident("skip") {
+                    out.skip = true;
+                } else {
+                    return Err(meta.error("expected `rename`, `bytes`, `default` or `skip`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// Case conversion applied by `rename_all` to field or variant names.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    Camel,
+    Pascal,
+}
+
+impl RenameRule {
+    fn parse(lit: &LitStr) -> Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "camelCase" => RenameRule::Camel,
+            "PascalCase" => RenameRule::Pascal,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected one of \"lowercase\", \"UPPERCASE\", \"snake_case\", \
+                     \"SCREAMING_SNAKE_CASE\", \"kebab-case\", \"camelCase\", \"PascalCase\"",
+                ))
+            }
+        })
+    }
+
+    /// Apply the rule to a `snake_case` field or `PascalCase` variant name.
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        let capitalize = |w: &str| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        };
+        match self {
+            RenameRule::Lower => words.concat(),
+            RenameRule::Upper => words.concat().to_uppercase(),
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+            RenameRule::Camel => {
+                let mut out = words.first().cloned().unwrap_or_default();
+                out.extend(words.iter().skip(1).map(|w| capitalize(w)));
+                out
+            }
+            RenameRule::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+        }
+    }
+}
+
+/// Lowercase words of an identifier, split at `_` and lower-to-upper case
+/// boundaries.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
@@ -0,0 +1,382 @@
+//! Code generation for `ToValue` and `FromValue`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Path, Result};
+
+use crate::attr::{ContainerAttrs, MemberAttrs, RenameRule};
+
+/// A field of a struct or enum variant.
+struct Member {
+    /// Variable the field is bound to when destructuring
+    binding: Ident,
+    /// Object key for named fields
+    key: String,
+    attrs: MemberAttrs,
+}
+
+/// The fields of a struct or enum variant.
+enum Shape {
+    Named(Vec<Member>),
+    /// Exactly one unnamed field, stored as the value itself
+    Newtype(Member),
+    Tuple(Vec<Member>),
+    Unit,
+}
+
+impl Shape {
+    fn from_fields(fields: &Fields, rule: Option<RenameRule>) -> Result<Self> {
+        match fields {
+            Fields::Named(named) => named
+                .named
+                .iter()
+                .map(|f| {
+                    let ident = f.ident.clone().unwrap();
+                    let attrs = MemberAttrs::parse(&f.attrs)?;
+                    let name = ident.unraw().to_string();
+                    let key = match (&attrs.rename, rule) {
+                        (Some(rename), _) => rename.clone(),
+                        (None, Some(rule)) => rule.apply(&name),
+                        (None, None) => name,
+                    };
+                    Ok(Member {
+                        binding: ident,
+                        key,
+                        attrs,
+                    })
+                })
+                .collect::<Result<_>>()
+                .map(Shape::Named),
+            Fields::Unnamed(unnamed) => {
+                let mut members = unnamed
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, f)| {
+                        let attrs = MemberAttrs::parse(&f.attrs)?;
+                        if attrs.rename.is_some() || attrs.default || attrs.skip {
+                            return Err(Error::new_spanned(
+                                f,
+                                "`rename`, `default` and `skip` need a named field",
+                            ));
+                        }
+                        Ok(Member {
+                            binding: format_ident!("__f{}", i),
+                            key: String::new(),
+                            attrs,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if members.len() == 1 {
+                    Ok(Shape::Newtype(members.remove(0)))
+                } else {
+                    Ok(Shape::Tuple(members))
+                }
+            }
+            Fields::Unit => Ok(Shape::Unit),
+        }
+    }
+
+    /// Pattern binding every (non-skipped) field by reference.
+    fn pattern(&self) -> TokenStream {
+        match self {
+            Shape::Named(members) => {
+                let bindings = members.iter().filter(|m| !m.attrs.skip).map(|m| &m.binding);
+                quote!({ #(#bindings,)* .. })
+            }
+            Shape::Newtype(member) => {
+                let binding = &member.binding;
+                quote!((#binding))
+            }
+            Shape::Tuple(members) => {
+                let bindings = members.iter().map(|m| &m.binding);
+                quote!((#(#bindings),*))
+            }
+            Shape::Unit => quote!(),
+        }
+    }
+
+    /// Expression building the `Value` from the bound fields.
+    fn encode(&self, krate: &Path) -> TokenStream {
+        let field = |m: &Member| {
+            let binding = &m.binding;
+            if m.attrs.bytes {
+                quote!(#krate::BytesField::to_bytes_value(#binding))
+            } else {
+                quote!(#krate::ToValue::to_value(#binding))
+            }
+        };
+        match self {
+            Shape::Named(members) => {
+                let inserts = members.iter().filter(|m| !m.attrs.skip).map(|m| {
+                    let key = &m.key;
+                    let value = field(m);
+                    quote!(__map.insert(::std::string::String::from(#key), #value);)
+                });
+                quote!({
+                    #[allow(unused_mut)]
+                    let mut __map = ::std::collections::HashMap::new();
+                    #(#inserts)*
+                    #krate::Value::Object(__map)
+                })
+            }
+            Shape::Newtype(member) => field(member),
+            Shape::Tuple(members) => {
+                let items = members.iter().map(field);
+                quote!(#krate::Value::Array(::std::vec![#(#items),*]))
+            }
+            Shape::Unit => quote!(#krate::Value::Null),
+        }
+    }
+
+    /// Expression of type `Self` built from `__value`, returning early on
+    /// errors; `wrap` places every error inside the enclosing variant.
+    fn decode(&self, krate: &Path, ctor: &TokenStream, wrap: &TokenStream) -> TokenStream {
+        let read = |m: &Member, value: TokenStream, locate: TokenStream| {
+            let convert = if m.attrs.bytes {
+                quote!(#krate::BytesField::from_bytes_value)
+            } else {
+                quote!(#krate::FromValue::from_value)
+            };
+            quote!(#convert(#value).map_err(|e| e #locate #wrap)?)
+        };
+        let mismatch = |expected: &str| {
+            quote!(return ::std::result::Result::Err(
+                #krate::FromValueError::type_mismatch(#expected, &other) #wrap
+            ))
+        };
+        match self {
+            Shape::Named(members) => {
+                let fields = members.iter().map(|m| {
+                    let binding = &m.binding;
+                    if m.attrs.skip {
+                        return quote!(#binding: ::std::default::Default::default());
+                    }
+                    let key = &m.key;
+                    let present = read(m, quote!(v), quote!(.in_field(#key)));
+                    let missing = if m.attrs.default {
+                        quote!(::std::default::Default::default())
+                    } else {
+                        let from_missing = if m.attrs.bytes {
+                            quote!(#krate::BytesField::from_missing_bytes())
+                        } else {
+                            quote!(#krate::FromValue::from_missing())
+                        };
+                        quote!(#from_missing.ok_or_else(|| {
+                            #krate::FromValueError::missing_field(#key) #wrap
+                        })?)
+                    };
+                    quote!(#binding: match __map.remove(#key) {
+                        ::std::option::Option::Some(v) => #present,
+                        ::std::option::Option::None => #missing,
+                    })
+                });
+                let mismatch = mismatch("Object");
+                quote!({
+                    #[allow(unused_mut, unused_variables)]
+                    let mut __map = match __value {
+                        #krate::Value::Object(map) => map,
+                        other => #mismatch,
+                    };
+                    #ctor { #(#fields),* }
+                })
+            }
+            Shape::Newtype(member) => {
+                let value = read(member, quote!(__value), quote!());
+                quote!(#ctor(#value))
+            }
+            Shape::Tuple(members) => {
+                let len = members.len();
+                let items = members
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| read(m, quote!(__items.next().unwrap()), quote!(.at_index(#i))));
+                let mismatch = mismatch("Array");
+                quote!({
+                    let mut __items = match __value {
+                        #krate::Value::Array(items) if items.len() == #len => items.into_iter(),
+                        #krate::Value::Array(items) => {
+                            return ::std::result::Result::Err(#krate::FromValueError::new(
+                                ::std::format!(
+                                    "expected Array of length {}, found length {}",
+                                    #len,
+                                    items.len()
+                                ),
+                            ) #wrap)
+                        }
+                        other => #mismatch,
+                    };
+                    #ctor(#(#items),*)
+                })
+            }
+            Shape::Unit => {
+                let mismatch = mismatch("Null");
+                quote!({
+                    match __value {
+                        #krate::Value::Null => {}
+                        other => #mismatch,
+                    }
+                    #ctor
+                })
+            }
+        }
+    }
+}
+
+/// Expand `#[derive(ToValue)]`.
+pub fn to_value(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let krate = &attrs.krate;
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let shape = Shape::from_fields(&data.fields, attrs.rename_all)?;
+            let pattern = shape.pattern();
+            let value = shape.encode(krate);
+            quote!({
+                let Self #pattern = self;
+                #value
+            })
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let ident = &v.ident;
+                    let tag = variant_tag(v, attrs.rename_all)?;
+                    let shape = Shape::from_fields(&v.fields, None)?;
+                    let pattern = shape.pattern();
+                    if let Shape::Unit = shape {
+                        return Ok(quote!(Self::#ident => {
+                            #krate::Value::String(::std::string::String::from(#tag))
+                        }));
+                    }
+                    let value = shape.encode(krate);
+                    Ok(quote!(Self::#ident #pattern => {
+                        let mut __tagged = ::std::collections::HashMap::with_capacity(1);
+                        __tagged.insert(::std::string::String::from(#tag), #value);
+                        #krate::Value::Object(__tagged)
+                    }))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if arms.is_empty() {
+                quote!({ match *self {} })
+            } else {
+                quote!({ match self { #(#arms)* } })
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "ToValue can't be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#krate::ToValue));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::ToValue for #name #ty_generics #where_clause {
+            fn to_value(&self) -> #krate::Value #body
+        }
+    })
+}
+
+/// Expand `#[derive(FromValue)]`.
+pub fn from_value(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let krate = &attrs.krate;
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let shape = Shape::from_fields(&data.fields, attrs.rename_all)?;
+            let value = shape.decode(krate, &quote!(Self), &quote!());
+            quote!(::std::result::Result::Ok(#value))
+        }
+        Data::Enum(data) => {
+            let mut tags = Vec::new();
+            let mut unit_arms = Vec::new();
+            let mut tagged_arms = Vec::new();
+            for v in &data.variants {
+                let ident = &v.ident;
+                let tag = variant_tag(v, attrs.rename_all)?;
+                let shape = Shape::from_fields(&v.fields, None)?;
+                if let Shape::Unit = shape {
+                    unit_arms.push(quote!(#tag => ::std::result::Result::Ok(Self::#ident),));
+                }
+                let value = shape.decode(krate, &quote!(Self::#ident), &quote!(.in_field(#tag)));
+                tagged_arms.push(quote!(#tag => ::std::result::Result::Ok(#value),));
+                tags.push(tag);
+            }
+            quote!({
+                const __TAGS: &[&str] = &[#(#tags),*];
+                match __value {
+                    #krate::Value::String(tag) => match tag.as_str() {
+                        #(#unit_arms)*
+                        other => ::std::result::Result::Err(
+                            #krate::FromValueError::unknown_variant(other, __TAGS),
+                        ),
+                    },
+                    #krate::Value::Object(map) if map.len() == 1 => {
+                        let (tag, __value) = map.into_iter().next().unwrap();
+                        match tag.as_str() {
+                            #(#tagged_arms)*
+                            other => ::std::result::Result::Err(
+                                #krate::FromValueError::unknown_variant(other, __TAGS),
+                            ),
+                        }
+                    }
+                    other => ::std::result::Result::Err(#krate::FromValueError::type_mismatch(
+                        "String or single-key Object",
+                        &other,
+                    )),
+                }
+            })
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "FromValue can't be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#krate::FromValue));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::FromValue for #name #ty_generics #where_clause {
+            fn from_value(
+                __value: #krate::Value,
+            ) -> ::std::result::Result<Self, #krate::FromValueError> {
+                #body
+            }
+        }
+    })
+}
+
+/// The tag an enum variant is stored under.
+fn variant_tag(variant: &syn::Variant, rule: Option<RenameRule>) -> Result<String> {
+    let attrs = MemberAttrs::parse(&variant.attrs)?;
+    if attrs.bytes || attrs.default || attrs.skip {
+        return Err(Error::new_spanned(
+            variant,
+            "only `rename` applies to enum variants",
+        ));
+    }
+    let name = variant.ident.unraw().to_string();
+    Ok(match (attrs.rename, rule) {
+        (Some(rename), _) => rename,
+        (None, Some(rule)) => rule.apply(&name),
+        (None, None) => name,
+    })
+}
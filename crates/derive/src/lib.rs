@@ -0,0 +1,72 @@
+//! Derive macros for converting Rust types to and from Strata values
+//!
+//! `#[derive(ToValue, FromValue)]` implements the `ToValue` and `FromValue`
+//! traits for structs and enums, mapping them onto `Value` as described in
+//! `strata_core::convert`:
+//!
+//! - structs with named fields become `Object`s keyed by field name
+//! - newtype structs become their inner value, tuple structs `Array`s and
+//!   unit structs `Null`
+//! - enums are externally tagged: unit variants become their name as a
+//!   `String`, other variants an `Object` with the name as its single key
+//!
+//! # Example
+//!
+//! ```text
+//! use stratadb::{FromValue, Strata, ToValue};
+//!
+//! #[derive(ToValue, FromValue)]
+//! #[strata(rename_all = "camelCase")]
+//! struct Turn {
+//!     role: Role,
+//!     content: String,
+//!     tool_calls: Vec<String>,   // "toolCalls"
+//!     #[strata(bytes)]
+//!     audio: Option<Vec<u8>>,    // Value::Bytes, missing reads as None
+//! }
+//!
+//! #[derive(ToValue, FromValue)]
+//! enum Role { User, Assistant }
+//!
+//! db.kv_put("turn:1", turn.to_value())?;
+//! let turn = Turn::from_value(db.kv_get("turn:1")?.unwrap())?;
+//! ```
+//!
+//! # Attributes
+//!
+//! | Attribute | On | Effect |
+//! |-----------|----|--------|
+//! | `#[strata(rename_all = "...")]` | struct, enum | Renames fields (structs) or variants (enums): `lowercase`, `UPPERCASE`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `camelCase`, `PascalCase` |
+//! | `#[strata(crate = "...")]` | struct, enum | Path the traits and `Value` are reached through; defaults to `::stratadb` |
+//! | `#[strata(rename = "...")]` | field, variant | Stores the field or variant under another name |
+//! | `#[strata(bytes)]` | field | Stores a `Vec<u8>` or `Option<Vec<u8>>` as `Value::Bytes` rather than an `Array` |
+//! | `#[strata(default)]` | field | A missing field reads as `Default::default()` |
+//! | `#[strata(skip)]` | field | Not stored; reads as `Default::default()` |
+//!
+//! Type parameters get a `ToValue` or `FromValue` bound. Fields of type
+//! `Option<T>` may be missing; any other missing field is an error, as is a
+//! value of the wrong kind, reported with the path to the offending field.
+
+mod attr;
+mod expand;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive `ToValue` for a struct or enum.
+#[proc_macro_derive(ToValue, attributes(strata))]
+pub fn derive_to_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::to_value(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `FromValue` for a struct or enum.
+#[proc_macro_derive(FromValue, attributes(strata))]
+pub fn derive_from_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::from_value(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,187 @@
+//! Round trips and errors for `#[derive(ToValue, FromValue)]`
+
+use std::collections::{BTreeMap, HashMap};
+
+use strata_core::{FromValue, FromValueError, ToValue, Value};
+use strata_derive::{FromValue, ToValue};
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+#[strata(crate = "strata_core")]
+enum Role {
+    User,
+    #[strata(rename = "bot")]
+    Assistant,
+    Tool(String),
+    Handoff {
+        to: String,
+        reason: Option<String>,
+    },
+}
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+#[strata(crate = "strata_core", rename_all = "camelCase")]
+struct Turn {
+    role: Role,
+    content: String,
+    tool_calls: Vec<String>,
+    scores: HashMap<String, f64>,
+    #[strata(bytes)]
+    audio: Option<Vec<u8>>,
+    #[strata(rename = "n")]
+    token_count: u32,
+    #[strata(default)]
+    retries: u8,
+    #[strata(skip)]
+    cached: bool,
+}
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+#[strata(crate = "strata_core")]
+struct TurnId(u64);
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+#[strata(crate = "strata_core")]
+struct Page<T> {
+    items: Vec<T>,
+    next: Option<TurnId>,
+    meta: BTreeMap<String, Value>,
+}
+
+fn object(entries: &[(&str, Value)]) -> Value {
+    Value::Object(
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect(),
+    )
+}
+
+fn turn() -> Turn {
+    Turn {
+        role: Role::Handoff {
+            to: "billing".into(),
+            reason: None,
+        },
+        content: "hi".into(),
+        tool_calls: vec!["search".into()],
+        scores: [("relevance".to_string(), 0.5)].into_iter().collect(),
+        audio: Some(vec![1, 2]),
+        token_count: 3,
+        retries: 1,
+        cached: false,
+    }
+}
+
+#[test]
+fn struct_maps_to_object() {
+    let value = turn().to_value();
+    assert_eq!(
+        value,
+        object(&[
+            (
+                "role",
+                object(&[(
+                    "Handoff",
+                    object(&[("to", "billing".into()), ("reason", Value::Null)])
+                )])
+            ),
+            ("content", "hi".into()),
+            ("toolCalls", Value::Array(vec!["search".into()])),
+            ("scores", object(&[("relevance", Value::Float(0.5))])),
+            ("audio", Value::Bytes(vec![1, 2])),
+            ("n", Value::Int(3)),
+            ("retries", Value::Int(1)),
+        ])
+    );
+    assert_eq!(Turn::from_value(value), Ok(turn()));
+}
+
+#[test]
+fn enums_are_externally_tagged() {
+    assert_eq!(Role::User.to_value(), "User".into());
+    assert_eq!(Role::Assistant.to_value(), "bot".into());
+    assert_eq!(
+        Role::Tool("search".into()).to_value(),
+        object(&[("Tool", "search".into())])
+    );
+    assert_eq!(Role::from_value("bot".into()), Ok(Role::Assistant));
+    assert_eq!(
+        Role::from_value("Assistant".into()).unwrap_err().message(),
+        "unknown variant `Assistant`, expected one of `User`, `bot`, `Tool`, `Handoff`"
+    );
+}
+
+#[test]
+fn missing_fields_use_option_and_default() {
+    let value = object(&[
+        ("role", "User".into()),
+        ("content", "hi".into()),
+        ("toolCalls", Value::Array(vec![])),
+        ("scores", object(&[])),
+        ("n", Value::Int(3)),
+        ("cached", Value::Bool(true)),
+    ]);
+    let turn = Turn::from_value(value).unwrap();
+    assert_eq!(turn.audio, None);
+    assert_eq!(turn.retries, 0);
+    assert!(!turn.cached);
+}
+
+#[test]
+fn generics_and_newtypes() {
+    let page = Page {
+        items: vec![TurnId(1), TurnId(2)],
+        next: Some(TurnId(3)),
+        meta: BTreeMap::new(),
+    };
+    let value = page.to_value();
+    assert_eq!(
+        value,
+        object(&[
+            ("items", Value::Array(vec![Value::Int(1), Value::Int(2)])),
+            ("next", Value::Int(3)),
+            ("meta", object(&[])),
+        ])
+    );
+    assert_eq!(Page::from_value(value), Ok(page));
+}
+
+#[test]
+fn errors_point_at_the_offending_field() {
+    let err = |value: Value| -> FromValueError { Turn::from_value(value).unwrap_err() };
+
+    let mut value = turn().to_value();
+    if let Value::Object(map) = &mut value {
+        map.remove("content");
+    }
+    assert_eq!(err(value).to_string(), "$.content: missing field");
+
+    let mut value = turn().to_value();
+    if let Value::Object(map) = &mut value {
+        map.insert(
+            "toolCalls".into(),
+            Value::Array(vec!["a".into(), Value::Int(1)]),
+        );
+    }
+    assert_eq!(
+        err(value).to_string(),
+        "$.toolCalls[1]: expected String, found Int"
+    );
+
+    let mut value = turn().to_value();
+    if let Value::Object(map) = &mut value {
+        map.insert(
+            "role".into(),
+            object(&[("Handoff", object(&[("to", Value::Int(1))]))]),
+        );
+    }
+    assert_eq!(
+        err(value).to_string(),
+        "$.role.Handoff.to: expected String, found Int"
+    );
+
+    assert_eq!(
+        err(Value::Int(1)).to_string(),
+        "$: expected Object, found Int"
+    );
+}
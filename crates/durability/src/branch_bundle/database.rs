@@ -0,0 +1,427 @@
+//! Database bundles — every branch of a database in one archive
+//!
+//! A database bundle carries the same per-branch files as a branch bundle,
+//! one directory per branch, under a single manifest:
+//!
+//! ```text
+//! archive.databundle.tar.zst
+//! └── databundle/
+//!     ├── MANIFEST.json        — format version, branch list, checksums
+//!     └── branches/
+//!         ├── 0/BRANCH.json    — branch metadata
+//!         ├── 0/WAL.branchlog  — branch payloads (msgpack v2)
+//!         └── 1/...
+//! ```
+//!
+//! Branch directories are numbered rather than named, so branch names never
+//! need escaping. Databases with encryption at rest seal the whole archive
+//! the same way as a branch bundle, manifest included.
+
+use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
+use crate::branch_bundle::reader::{extract_files_under, unseal_archive};
+use crate::branch_bundle::types::{
+    chrono_now_iso8601, xxh3_hex, BundleBranchInfo, EnvironmentInfo, ExportOptions,
+    ENCRYPTED_BUNDLE_MAGIC,
+};
+use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogReader, WalLogWriter};
+use crate::branch_bundle::writer::{add_file, seal_archive, write_file_atomic};
+use crate::codec::StorageCodec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tar::Builder;
+
+/// Current database bundle format version
+pub const DATABUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// File extension for database bundle archives
+pub const DATABUNDLE_EXTENSION: &str = ".databundle.tar.zst";
+
+/// Root directory in a database bundle archive
+const ROOT: &str = "databundle";
+
+/// Database bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatabaseBundleManifest {
+    /// Format version (currently 1)
+    pub format_version: u32,
+
+    /// Strata version that created this bundle
+    pub strata_version: String,
+
+    /// ISO 8601 timestamp when bundle was created
+    pub created_at: String,
+
+    /// Checksum algorithm used (currently "xxh3")
+    pub checksum_algorithm: String,
+
+    /// Bundled branches, in export order
+    pub branches: Vec<DatabaseBundleEntry>,
+
+    /// Environment of the exporting database, when it captures one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvironmentInfo>,
+}
+
+/// One branch in a database bundle manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatabaseBundleEntry {
+    /// Branch name
+    pub name: String,
+
+    /// Directory holding the branch's files, relative to the bundle root
+    pub dir: String,
+
+    /// Number of WAL entries for the branch
+    pub wal_entry_count: u64,
+
+    /// Checksums of `BRANCH.json` and `WAL.branchlog`
+    pub checksums: HashMap<String, String>,
+}
+
+/// A branch read from (or written to) a database bundle
+#[derive(Debug, Clone)]
+pub struct BundledBranch {
+    /// Branch metadata
+    pub branch_info: BundleBranchInfo,
+    /// Transaction payloads
+    pub payloads: Vec<BranchlogPayload>,
+}
+
+/// Information returned after writing a database bundle
+#[derive(Debug, Clone)]
+pub struct DatabaseExportInfo {
+    /// Path where the bundle was written
+    pub path: PathBuf,
+
+    /// Number of branches in the bundle
+    pub branch_count: usize,
+
+    /// Number of WAL entries across all branches
+    pub wal_entry_count: u64,
+
+    /// Size of the bundle file in bytes
+    pub bundle_size_bytes: u64,
+}
+
+/// Complete database bundle contents after reading
+#[derive(Debug)]
+pub struct DatabaseBundleContents {
+    /// Bundle manifest
+    pub manifest: DatabaseBundleManifest,
+    /// Branches in manifest order
+    pub branches: Vec<BundledBranch>,
+}
+
+/// Writer for database bundle archives
+pub struct DatabaseBundleWriter {
+    compression_level: i32,
+    environment: Option<EnvironmentInfo>,
+}
+
+impl DatabaseBundleWriter {
+    /// Create a new writer with the given options
+    pub fn new(options: &ExportOptions) -> Self {
+        Self {
+            compression_level: options.compression_level,
+            environment: options.environment.clone(),
+        }
+    }
+
+    /// Write a database bundle holding `branches`
+    ///
+    /// Either the complete archive is written or no file is left behind.
+    pub fn write(
+        &self,
+        branches: &[BundledBranch],
+        path: &Path,
+    ) -> BranchBundleResult<DatabaseExportInfo> {
+        let (archive, info) = self.write_to_vec(branches)?;
+        write_file_atomic(path, &archive)?;
+        Ok(DatabaseExportInfo {
+            path: path.to_path_buf(),
+            ..info
+        })
+    }
+
+    /// Write a database bundle sealed with `codec` (encryption at rest)
+    pub fn write_encrypted(
+        &self,
+        branches: &[BundledBranch],
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<DatabaseExportInfo> {
+        let (archive, info) = self.write_to_vec(branches)?;
+        let sealed = seal_archive(&archive, codec);
+        write_file_atomic(path, &sealed)?;
+        Ok(DatabaseExportInfo {
+            path: path.to_path_buf(),
+            bundle_size_bytes: sealed.len() as u64,
+            ..info
+        })
+    }
+
+    /// Write a database bundle to a Vec<u8>
+    pub fn write_to_vec(
+        &self,
+        branches: &[BundledBranch],
+    ) -> BranchBundleResult<(Vec<u8>, DatabaseExportInfo)> {
+        let mut manifest = DatabaseBundleManifest {
+            format_version: DATABUNDLE_FORMAT_VERSION,
+            strata_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono_now_iso8601(),
+            checksum_algorithm: "xxh3".to_string(),
+            branches: Vec::with_capacity(branches.len()),
+            environment: self.environment.clone(),
+        };
+        let mut files = Vec::with_capacity(branches.len() * 2);
+        let mut wal_entry_count = 0;
+
+        for (i, branch) in branches.iter().enumerate() {
+            let dir = format!("branches/{}", i);
+            let branch_json = serde_json::to_vec_pretty(&branch.branch_info)?;
+            let (wal_data, wal_info) = WalLogWriter::write_to_vec(&branch.payloads)?;
+
+            let mut checksums = HashMap::new();
+            checksums.insert("BRANCH.json".to_string(), xxh3_hex(&branch_json));
+            checksums.insert("WAL.branchlog".to_string(), wal_info.checksum);
+            manifest.branches.push(DatabaseBundleEntry {
+                name: branch.branch_info.name.clone(),
+                dir: dir.clone(),
+                wal_entry_count: wal_info.entry_count,
+                checksums,
+            });
+            wal_entry_count += wal_info.entry_count;
+
+            files.push((format!("{}/{}/BRANCH.json", ROOT, dir), branch_json));
+            files.push((format!("{}/{}/WAL.branchlog", ROOT, dir), wal_data));
+        }
+
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+
+        let mut buffer = Vec::new();
+        {
+            let zstd_writer = zstd::Encoder::new(&mut buffer, self.compression_level)
+                .map_err(|e| BranchBundleError::compression(format!("zstd encoder: {}", e)))?;
+            let mut tar_builder = Builder::new(zstd_writer.auto_finish());
+
+            add_file(
+                &mut tar_builder,
+                &format!("{}/MANIFEST.json", ROOT),
+                &manifest_json,
+            )?;
+            for (path, data) in &files {
+                add_file(&mut tar_builder, path, data)?;
+            }
+
+            let zstd_writer = tar_builder
+                .into_inner()
+                .map_err(|e| BranchBundleError::archive(format!("tar finish: {}", e)))?;
+            drop(zstd_writer);
+        }
+
+        let info = DatabaseExportInfo {
+            path: PathBuf::new(),
+            branch_count: branches.len(),
+            wal_entry_count,
+            bundle_size_bytes: buffer.len() as u64,
+        };
+        Ok((buffer, info))
+    }
+}
+
+/// Reader for database bundle archives
+pub struct DatabaseBundleReader;
+
+impl DatabaseBundleReader {
+    /// Whether the file is a database bundle (plain or encrypted)
+    ///
+    /// Only plain bundles can be told apart from branch bundles without a
+    /// key; encrypted files of either kind return `Ok(true)`.
+    pub fn is_database_bundle(path: &Path) -> BranchBundleResult<bool> {
+        let data = std::fs::read(path)?;
+        if data.starts_with(ENCRYPTED_BUNDLE_MAGIC) {
+            return Ok(true);
+        }
+        Ok(extract_files_under(data.as_slice(), ROOT)?.contains_key("MANIFEST.json"))
+    }
+
+    /// Read every branch from a plain database bundle, verifying checksums
+    pub fn read_all(path: &Path) -> BranchBundleResult<DatabaseBundleContents> {
+        Self::read_from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Read every branch from a database bundle sealed with `codec`
+    pub fn read_all_encrypted(
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<DatabaseBundleContents> {
+        let archive = unseal_archive(&std::fs::read(path)?, codec)?;
+        Self::read_from_bytes(&archive)
+    }
+
+    /// Read every branch from a plain database bundle in memory
+    pub fn read_from_bytes(data: &[u8]) -> BranchBundleResult<DatabaseBundleContents> {
+        let files = extract_files_under(data, ROOT)?;
+        let manifest_data = files
+            .get("MANIFEST.json")
+            .ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
+        let manifest: DatabaseBundleManifest = serde_json::from_slice(manifest_data)?;
+        if manifest.format_version != DATABUNDLE_FORMAT_VERSION {
+            return Err(BranchBundleError::invalid_bundle(format!(
+                "unsupported database bundle format version {} (supported: {})",
+                manifest.format_version, DATABUNDLE_FORMAT_VERSION
+            )));
+        }
+
+        let mut branches = Vec::with_capacity(manifest.branches.len());
+        for entry in &manifest.branches {
+            let file = |name: &str| -> BranchBundleResult<&Vec<u8>> {
+                let path = format!("{}/{}", entry.dir, name);
+                let data = files
+                    .get(&path)
+                    .ok_or_else(|| BranchBundleError::missing_file(path.clone()))?;
+                if let Some(expected) = entry.checksums.get(name) {
+                    let actual = xxh3_hex(data);
+                    if expected != &actual {
+                        return Err(BranchBundleError::ChecksumMismatch {
+                            file: path,
+                            expected: expected.clone(),
+                            actual,
+                        });
+                    }
+                }
+                Ok(data)
+            };
+            let branch_info: BundleBranchInfo = serde_json::from_slice(file("BRANCH.json")?)?;
+            let payloads = WalLogReader::read_from_slice(file("WAL.branchlog")?)?;
+            branches.push(BundledBranch {
+                branch_info,
+                payloads,
+            });
+        }
+
+        Ok(DatabaseBundleContents { manifest, branches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::branch_bundle::writer::BranchBundleWriter;
+    use strata_core::types::{BranchId, Key, Namespace, TypeTag};
+    use strata_core::value::Value;
+    use tempfile::tempdir;
+
+    fn make_branch(name: &str, values: &[i64]) -> BundledBranch {
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        BundledBranch {
+            branch_info: BundleBranchInfo {
+                branch_id: branch_id.to_string(),
+                name: name.to_string(),
+                state: "active".to_string(),
+                created_at: "2025-01-24T10:00:00Z".to_string(),
+                closed_at: "2025-01-24T11:00:00Z".to_string(),
+                parent_branch_id: None,
+                error: None,
+                environment: None,
+            },
+            payloads: values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| BranchlogPayload {
+                    branch_id: branch_id.to_string(),
+                    version: i as u64 + 1,
+                    puts: vec![(
+                        Key::new(ns.clone(), TypeTag::KV, b"k".to_vec()),
+                        Value::Int(*v),
+                    )],
+                    deletes: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("all.databundle.tar.zst");
+        let branches = vec![make_branch("default", &[1, 2]), make_branch("a/b c", &[3])];
+
+        let info = DatabaseBundleWriter::new(&ExportOptions::default())
+            .write(&branches, &path)
+            .unwrap();
+        assert_eq!(info.branch_count, 2);
+        assert_eq!(info.wal_entry_count, 3);
+        assert!(DatabaseBundleReader::is_database_bundle(&path).unwrap());
+
+        let contents = DatabaseBundleReader::read_all(&path).unwrap();
+        assert_eq!(contents.manifest.branches.len(), 2);
+        assert_eq!(contents.manifest.branches[1].name, "a/b c");
+        for (read, written) in contents.branches.iter().zip(&branches) {
+            assert_eq!(read.branch_info, written.branch_info);
+            assert_eq!(read.payloads, written.payloads);
+        }
+    }
+
+    #[test]
+    fn test_empty_database() {
+        let (data, info) = DatabaseBundleWriter::new(&ExportOptions::default())
+            .write_to_vec(&[])
+            .unwrap();
+        assert_eq!(info.branch_count, 0);
+        let contents = DatabaseBundleReader::read_from_bytes(&data).unwrap();
+        assert!(contents.branches.is_empty());
+    }
+
+    #[test]
+    fn test_branch_bundle_is_not_database_bundle() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("one.branchbundle.tar.zst");
+        let branch = make_branch("main", &[1]);
+        BranchBundleWriter::with_defaults()
+            .write(&branch.branch_info, &branch.payloads, &path)
+            .unwrap();
+
+        assert!(!DatabaseBundleReader::is_database_bundle(&path).unwrap());
+        assert!(matches!(
+            DatabaseBundleReader::read_all(&path),
+            Err(BranchBundleError::MissingFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_corrupted_branch_is_rejected() {
+        let branches = vec![make_branch("main", &[1])];
+        let (data, _) = DatabaseBundleWriter::new(&ExportOptions::default())
+            .write_to_vec(&branches)
+            .unwrap();
+
+        // Rebuild the archive with a tampered manifest checksum
+        let mut files = extract_files_under(data.as_slice(), ROOT).unwrap();
+        let mut manifest: DatabaseBundleManifest =
+            serde_json::from_slice(&files["MANIFEST.json"]).unwrap();
+        manifest.branches[0]
+            .checksums
+            .insert("WAL.branchlog".to_string(), "0000000000000000".to_string());
+        files.insert(
+            "MANIFEST.json".to_string(),
+            serde_json::to_vec(&manifest).unwrap(),
+        );
+        let mut tampered = Vec::new();
+        {
+            let encoder = zstd::Encoder::new(&mut tampered, 3).unwrap();
+            let mut builder = Builder::new(encoder.auto_finish());
+            for (name, data) in &files {
+                add_file(&mut builder, &format!("{}/{}", ROOT, name), data).unwrap();
+            }
+            drop(builder.into_inner().unwrap());
+        }
+
+        assert!(matches!(
+            DatabaseBundleReader::read_from_bytes(&tampered),
+            Err(BranchBundleError::ChecksumMismatch { .. })
+        ));
+    }
+}
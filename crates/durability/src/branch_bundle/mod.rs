@@ -43,7 +43,28 @@
 //! - **Portable**: Archives can be moved between machines, stored in VCS
 //! - **Inspectable**: Standard tools (tar, jq) can inspect contents
 //! - **Deterministic**: Same branch exported twice produces identical bundles
+//!
+//! ## Encryption
+//!
+//! Databases with encryption at rest export bundles sealed with their storage
+//! codec (see [`ENCRYPTED_BUNDLE_MAGIC`]). Such bundles can only be read with
+//! [`BranchBundleReader::read_all_encrypted`] and a codec holding the key.
+//!
+//! ## Streaming
+//!
+//! [`BranchBundleWriter::write_stream`] takes payloads from an iterator and
+//! spools WAL.branchlog to disk, so export never holds the whole log in
+//! memory; [`ExportOptions::with_progress`] reports each entry written.
+//! [`BranchBundleReader::stream`] hands payloads to a [`BundleVisitor`] as
+//! they are decompressed.
+//!
+//! ## Database Bundles
+//!
+//! [`DatabaseBundleWriter`] packs every branch of a database into one
+//! `.databundle.tar.zst` archive with the same per-branch files (see
+//! [`database`]), so a whole workspace can be moved between machines.
 
+pub mod database;
 pub mod error;
 pub mod reader;
 pub mod types;
@@ -51,12 +72,18 @@ pub mod wal_log;
 pub mod writer;
 
 // Re-export public types
+pub use database::{
+    BundledBranch, DatabaseBundleContents, DatabaseBundleEntry, DatabaseBundleManifest,
+    DatabaseBundleReader, DatabaseBundleWriter, DatabaseExportInfo, DATABUNDLE_EXTENSION,
+    DATABUNDLE_FORMAT_VERSION,
+};
 pub use error::{BranchBundleError, BranchBundleResult};
-pub use reader::{BranchBundleReader, BundleContents as ReadBundleContents};
+pub use reader::{BranchBundleReader, BundleContents as ReadBundleContents, BundleVisitor};
 pub use types::{
     paths, xxh3_hex, BranchExportInfo, BundleBranchInfo, BundleContents, BundleManifest,
-    BundleVerifyInfo, ExportOptions, ImportedBranchInfo, BRANCHBUNDLE_EXTENSION,
-    BRANCHBUNDLE_FORMAT_VERSION, WAL_BRANCHLOG_MAGIC, WAL_BRANCHLOG_VERSION,
+    BundleProgress, BundleVerifyInfo, EnvironmentInfo, ExportOptions, ImportedBranchInfo,
+    ProgressCallback, BRANCHBUNDLE_EXTENSION, BRANCHBUNDLE_FORMAT_VERSION, ENCRYPTED_BUNDLE_MAGIC,
+    WAL_BRANCHLOG_MAGIC, WAL_BRANCHLOG_VERSION,
 };
 pub use wal_log::{BranchlogPayload, WalLogInfo, WalLogIterator, WalLogReader, WalLogWriter};
 pub use writer::BranchBundleWriter;
@@ -4,16 +4,36 @@
 
 use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
 use crate::branch_bundle::types::{
-    paths, xxh3_hex, BundleBranchInfo, BundleManifest, BundleVerifyInfo,
-    BRANCHBUNDLE_FORMAT_VERSION,
+    paths, xxh3_hex, BundleBranchInfo, BundleManifest, BundleVerifyInfo, HashingReader,
+    BRANCHBUNDLE_FORMAT_VERSION, ENCRYPTED_BUNDLE_MAGIC,
 };
-use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogReader};
+use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogIterator, WalLogReader};
+use crate::codec::StorageCodec;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
 use tar::Archive;
 
+/// Receives a bundle's contents from [`BranchBundleReader::stream`] as they
+/// are decoded
+///
+/// Payloads are delivered before the WAL.branchlog checksum can be checked
+/// (each one has already passed its own CRC32). If `stream` fails after
+/// `begin`, the visitor should undo what it applied.
+pub trait BundleVisitor {
+    /// Called once, before the first payload, with the manifest and the
+    /// checksum-verified branch info
+    fn begin(
+        &mut self,
+        manifest: &BundleManifest,
+        branch_info: &BundleBranchInfo,
+    ) -> BranchBundleResult<()>;
+
+    /// Called for each payload, in order
+    fn payload(&mut self, payload: BranchlogPayload) -> BranchBundleResult<()>;
+}
+
 /// Reader for BranchBundle archives (v2)
 ///
 /// Reads and validates .branchbundle.tar.zst files.
@@ -26,23 +46,51 @@ impl BranchBundleReader {
     /// - Archive can be decompressed
     /// - Required files exist (MANIFEST.json, BRANCH.json, WAL.branchlog)
     /// - Checksums match manifest
-    /// - WAL.branchlog header is valid
+    /// - WAL.branchlog header and entry CRCs are valid
+    ///
+    /// WAL.branchlog is checked as it is decompressed, never held whole.
     pub fn validate(path: &Path) -> BranchBundleResult<BundleVerifyInfo> {
-        let files = Self::extract_all_files(path)?;
+        let decoder = zstd::Decoder::new(BufReader::new(File::open(path)?))
+            .map_err(|e| BranchBundleError::compression(format!("zstd decode: {}", e)))?;
+        let mut archive = Archive::new(decoder);
+
+        let mut manifest_data = None;
+        let mut branch_data = None;
+        let mut wal_checksum = None;
+        for entry in archive
+            .entries()
+            .map_err(|e| BranchBundleError::archive(e.to_string()))?
+        {
+            let mut entry = entry.map_err(|e| BranchBundleError::archive(e.to_string()))?;
+            let entry_path = entry
+                .path()
+                .map_err(|e| BranchBundleError::archive(e.to_string()))?
+                .to_string_lossy()
+                .to_string();
+
+            match entry_path.as_str() {
+                paths::MANIFEST => manifest_data = Some(read_entry(&mut entry, "MANIFEST.json")?),
+                paths::BRANCH => branch_data = Some(read_entry(&mut entry, "BRANCH.json")?),
+                paths::WAL => {
+                    // Validate WAL header and entry CRCs (without parsing entries)
+                    let mut wal = HashingReader::new(&mut entry);
+                    WalLogReader::validate(&mut wal)?;
+                    wal_checksum = Some(wal.finish()?);
+                }
+                _ => {}
+            }
+        }
 
         // Check required files
-        let manifest_data = files
-            .get("MANIFEST.json")
-            .ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
-        let branch_data = files
-            .get("BRANCH.json")
-            .ok_or_else(|| BranchBundleError::missing_file("BRANCH.json"))?;
-        let wal_data = files
-            .get("WAL.branchlog")
-            .ok_or_else(|| BranchBundleError::missing_file("WAL.branchlog"))?;
+        let manifest_data =
+            manifest_data.ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
+        let branch_data =
+            branch_data.ok_or_else(|| BranchBundleError::missing_file("BRANCH.json"))?;
+        let wal_checksum =
+            wal_checksum.ok_or_else(|| BranchBundleError::missing_file("WAL.branchlog"))?;
 
         // Parse manifest
-        let manifest: BundleManifest = serde_json::from_slice(manifest_data)?;
+        let manifest: BundleManifest = serde_json::from_slice(&manifest_data)?;
 
         // Validate format version
         if manifest.format_version != BRANCHBUNDLE_FORMAT_VERSION {
@@ -55,30 +103,27 @@ impl BranchBundleReader {
         let mut checksums_valid = true;
 
         if let Some(expected) = manifest.checksums.get("BRANCH.json") {
-            let actual = xxh3_hex(branch_data);
+            let actual = xxh3_hex(&branch_data);
             if expected != &actual {
                 checksums_valid = false;
             }
         }
 
         if let Some(expected) = manifest.checksums.get("WAL.branchlog") {
-            let actual = xxh3_hex(wal_data);
-            if expected != &actual {
+            if expected != &wal_checksum {
                 checksums_valid = false;
             }
         }
 
-        // Validate WAL header (without parsing entries)
-        WalLogReader::validate(std::io::Cursor::new(wal_data))?;
-
         // Parse branch info for branch_id
-        let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
+        let branch_info: BundleBranchInfo = serde_json::from_slice(&branch_data)?;
 
         Ok(BundleVerifyInfo {
             branch_id: branch_info.branch_id,
             format_version: manifest.format_version,
             wal_entry_count: manifest.contents.wal_entry_count,
             checksums_valid,
+            environment: manifest.environment,
         })
     }
 
@@ -139,8 +184,62 @@ impl BranchBundleReader {
 
     /// Read all components from the bundle
     pub fn read_all(path: &Path) -> BranchBundleResult<BundleContents> {
-        let files = Self::extract_all_files(path)?;
+        Self::contents_from_files(Self::extract_all_files(path)?)
+    }
+
+    /// Read a bundle, handing each payload to `visitor` as it is decoded
+    ///
+    /// Unlike [`read_all`](Self::read_all), memory stays bounded by the
+    /// largest single payload. MANIFEST.json and BRANCH.json must precede
+    /// WAL.branchlog in the archive, as [`BranchBundleWriter`] writes them.
+    /// Fails with [`BranchBundleError::ChecksumMismatch`] after the last
+    /// payload if WAL.branchlog does not match the manifest.
+    ///
+    /// [`BranchBundleWriter`]: crate::branch_bundle::BranchBundleWriter
+    pub fn stream<V: BundleVisitor + ?Sized>(
+        path: &Path,
+        visitor: &mut V,
+    ) -> BranchBundleResult<()> {
+        stream_archive(BufReader::new(File::open(path)?), visitor)
+    }
 
+    /// Read a bundle sealed with `codec` like [`stream`](Self::stream)
+    ///
+    /// The sealed archive is decrypted in memory as a whole; payloads are
+    /// still decoded one at a time.
+    pub fn stream_encrypted<V: BundleVisitor + ?Sized>(
+        path: &Path,
+        codec: &dyn StorageCodec,
+        visitor: &mut V,
+    ) -> BranchBundleResult<()> {
+        let archive = unseal_archive(&std::fs::read(path)?, codec)?;
+        stream_archive(archive.as_slice(), visitor)
+    }
+
+    /// Whether the file is an encrypted bundle
+    pub fn is_encrypted(path: &Path) -> BranchBundleResult<bool> {
+        let mut magic = [0u8; ENCRYPTED_BUNDLE_MAGIC.len()];
+        let mut file = File::open(path)?;
+        match file.read_exact(&mut magic) {
+            Ok(()) => Ok(&magic == ENCRYPTED_BUNDLE_MAGIC),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Read all components from a bundle sealed with `codec`
+    ///
+    /// Fails if the bundle is not encrypted, was sealed with a different
+    /// codec, or cannot be decoded (e.g. wrong key).
+    pub fn read_all_encrypted(
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<BundleContents> {
+        let archive = unseal_archive(&std::fs::read(path)?, codec)?;
+        Self::contents_from_files(extract_files_under(archive.as_slice(), paths::ROOT)?)
+    }
+
+    fn contents_from_files(files: HashMap<String, Vec<u8>>) -> BranchBundleResult<BundleContents> {
         let manifest_data = files
             .get("MANIFEST.json")
             .ok_or_else(|| BranchBundleError::missing_file("MANIFEST.json"))?;
@@ -198,38 +297,7 @@ impl BranchBundleReader {
     /// Extract all files from the archive into a HashMap
     fn extract_all_files(path: &Path) -> BranchBundleResult<HashMap<String, Vec<u8>>> {
         let file = File::open(path)?;
-        let buf_reader = BufReader::new(file);
-        let decoder = zstd::Decoder::new(buf_reader)
-            .map_err(|e| BranchBundleError::compression(format!("zstd decode: {}", e)))?;
-
-        let mut archive = Archive::new(decoder);
-        let mut files = HashMap::new();
-        let prefix = format!("{}/", paths::ROOT);
-
-        for entry in archive
-            .entries()
-            .map_err(|e| BranchBundleError::archive(e.to_string()))?
-        {
-            let mut entry = entry.map_err(|e| BranchBundleError::archive(e.to_string()))?;
-            let entry_path = entry
-                .path()
-                .map_err(|e| BranchBundleError::archive(e.to_string()))?
-                .to_string_lossy()
-                .to_string();
-
-            // Strip prefix to get relative file name
-            if let Some(name) = entry_path.strip_prefix(&prefix) {
-                if !name.is_empty() {
-                    let mut data = Vec::new();
-                    entry
-                        .read_to_end(&mut data)
-                        .map_err(|e| BranchBundleError::archive(format!("read {}: {}", name, e)))?;
-                    files.insert(name.to_string(), data);
-                }
-            }
-        }
-
-        Ok(files)
+        extract_files_under(BufReader::new(file), paths::ROOT)
     }
 
     /// Read from a byte slice (for testing)
@@ -322,6 +390,164 @@ impl BranchBundleReader {
     }
 }
 
+/// Read a whole (small) archive entry into memory
+fn read_entry<R: Read>(entry: &mut R, name: &str) -> BranchBundleResult<Vec<u8>> {
+    let mut data = Vec::new();
+    entry
+        .read_to_end(&mut data)
+        .map_err(|e| BranchBundleError::archive(format!("read {}: {}", name, e)))?;
+    Ok(data)
+}
+
+/// Check `data` against the manifest checksum recorded for `file`
+fn verify_checksum(
+    manifest: &BundleManifest,
+    file: &str,
+    actual: String,
+) -> BranchBundleResult<()> {
+    match manifest.checksums.get(file) {
+        Some(expected) if expected != &actual => Err(BranchBundleError::ChecksumMismatch {
+            file: file.to_string(),
+            expected: expected.clone(),
+            actual,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Decode a `.tar.zst` bundle from `reader`, streaming WAL.branchlog into
+/// `visitor`
+fn stream_archive<R: Read, V: BundleVisitor + ?Sized>(
+    reader: R,
+    visitor: &mut V,
+) -> BranchBundleResult<()> {
+    let decoder = zstd::Decoder::new(reader)
+        .map_err(|e| BranchBundleError::compression(format!("zstd decode: {}", e)))?;
+    let mut archive = Archive::new(decoder);
+
+    let mut manifest: Option<BundleManifest> = None;
+    let mut branch_data = None;
+    for entry in archive
+        .entries()
+        .map_err(|e| BranchBundleError::archive(e.to_string()))?
+    {
+        let mut entry = entry.map_err(|e| BranchBundleError::archive(e.to_string()))?;
+        let entry_path = entry
+            .path()
+            .map_err(|e| BranchBundleError::archive(e.to_string()))?
+            .to_string_lossy()
+            .to_string();
+
+        match entry_path.as_str() {
+            paths::MANIFEST => {
+                let parsed: BundleManifest =
+                    serde_json::from_slice(&read_entry(&mut entry, "MANIFEST.json")?)?;
+                if parsed.format_version != BRANCHBUNDLE_FORMAT_VERSION {
+                    return Err(BranchBundleError::UnsupportedVersion {
+                        version: parsed.format_version,
+                    });
+                }
+                manifest = Some(parsed);
+            }
+            paths::BRANCH => branch_data = Some(read_entry(&mut entry, "BRANCH.json")?),
+            paths::WAL => {
+                let manifest = manifest.as_ref().ok_or_else(|| {
+                    BranchBundleError::invalid_bundle("WAL.branchlog precedes MANIFEST.json")
+                })?;
+                let branch_data = branch_data.as_ref().ok_or_else(|| {
+                    BranchBundleError::invalid_bundle("WAL.branchlog precedes BRANCH.json")
+                })?;
+                verify_checksum(manifest, "BRANCH.json", xxh3_hex(branch_data))?;
+                let branch_info: BundleBranchInfo = serde_json::from_slice(branch_data)?;
+                visitor.begin(manifest, &branch_info)?;
+
+                let mut wal = HashingReader::new(&mut entry);
+                for payload in WalLogIterator::new(&mut wal)? {
+                    visitor.payload(payload?)?;
+                }
+                return verify_checksum(manifest, "WAL.branchlog", wal.finish()?);
+            }
+            _ => {}
+        }
+    }
+
+    Err(BranchBundleError::missing_file(
+        match (&manifest, &branch_data) {
+            (None, _) => "MANIFEST.json",
+            (_, None) => "BRANCH.json",
+            _ => "WAL.branchlog",
+        },
+    ))
+}
+
+/// Extract all files under `root/` from a `.tar.zst` stream, keyed by their
+/// path relative to `root`
+pub(crate) fn extract_files_under<R: Read>(
+    reader: R,
+    root: &str,
+) -> BranchBundleResult<HashMap<String, Vec<u8>>> {
+    let decoder = zstd::Decoder::new(reader)
+        .map_err(|e| BranchBundleError::compression(format!("zstd decode: {}", e)))?;
+
+    let mut archive = Archive::new(decoder);
+    let mut files = HashMap::new();
+    let prefix = format!("{}/", root);
+
+    for entry in archive
+        .entries()
+        .map_err(|e| BranchBundleError::archive(e.to_string()))?
+    {
+        let mut entry = entry.map_err(|e| BranchBundleError::archive(e.to_string()))?;
+        let entry_path = entry
+            .path()
+            .map_err(|e| BranchBundleError::archive(e.to_string()))?
+            .to_string_lossy()
+            .to_string();
+
+        // Strip prefix to get relative file name
+        if let Some(name) = entry_path.strip_prefix(&prefix) {
+            if !name.is_empty() {
+                let mut data = Vec::new();
+                entry
+                    .read_to_end(&mut data)
+                    .map_err(|e| BranchBundleError::archive(format!("read {}: {}", name, e)))?;
+                files.insert(name.to_string(), data);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Decode an archive sealed by `seal_archive`
+///
+/// Fails if the data is not encrypted, was sealed with a different codec,
+/// or cannot be decoded (e.g. wrong key).
+pub(crate) fn unseal_archive(data: &[u8], codec: &dyn StorageCodec) -> BranchBundleResult<Vec<u8>> {
+    let body = data
+        .strip_prefix(ENCRYPTED_BUNDLE_MAGIC.as_slice())
+        .ok_or_else(|| BranchBundleError::invalid_bundle("bundle is not encrypted"))?;
+    let (&id_len, body) = body
+        .split_first()
+        .ok_or_else(|| BranchBundleError::invalid_bundle("truncated encrypted bundle"))?;
+    if body.len() < id_len as usize {
+        return Err(BranchBundleError::invalid_bundle(
+            "truncated encrypted bundle",
+        ));
+    }
+    let (codec_id, sealed) = body.split_at(id_len as usize);
+    if codec_id != codec.codec_id().as_bytes() {
+        return Err(BranchBundleError::invalid_bundle(format!(
+            "bundle was sealed with codec '{}', not '{}'",
+            String::from_utf8_lossy(codec_id),
+            codec.codec_id()
+        )));
+    }
+    codec
+        .decode(sealed)
+        .map_err(|e| BranchBundleError::invalid_bundle(format!("cannot decrypt bundle: {}", e)))
+}
+
 /// Complete bundle contents after reading (v2)
 #[derive(Debug)]
 pub struct BundleContents {
@@ -352,6 +578,7 @@ mod tests {
             closed_at: "2025-01-24T11:00:00Z".to_string(),
             parent_branch_id: None,
             error: None,
+            environment: None,
         }
     }
 
@@ -582,4 +809,115 @@ mod tests {
             assert_eq!(original, read);
         }
     }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        use crate::codec::{AesGcmCodec, EncryptionKey};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sealed.branchbundle.tar.zst");
+        let codec = AesGcmCodec::new(&EncryptionKey::from_bytes([3u8; 32]));
+
+        let writer = BranchBundleWriter::new(&ExportOptions::default());
+        let branch_info = make_test_branch_info();
+        let payloads = make_test_payloads();
+        writer
+            .write_encrypted(&branch_info, &payloads, &path, &codec)
+            .unwrap();
+
+        assert!(BranchBundleReader::is_encrypted(&path).unwrap());
+        assert!(BranchBundleReader::read_all(&path).is_err());
+
+        let contents = BranchBundleReader::read_all_encrypted(&path, &codec).unwrap();
+        assert_eq!(contents.branch_info.name, branch_info.name);
+        assert_eq!(contents.payloads, payloads);
+
+        let other = AesGcmCodec::new(&EncryptionKey::from_bytes([4u8; 32]));
+        assert!(BranchBundleReader::read_all_encrypted(&path, &other).is_err());
+    }
+
+    /// Visitor that records everything it is handed
+    #[derive(Default)]
+    struct Collect {
+        branch_info: Option<BundleBranchInfo>,
+        payloads: Vec<BranchlogPayload>,
+    }
+
+    impl BundleVisitor for Collect {
+        fn begin(
+            &mut self,
+            _manifest: &BundleManifest,
+            branch_info: &BundleBranchInfo,
+        ) -> BranchBundleResult<()> {
+            self.branch_info = Some(branch_info.clone());
+            Ok(())
+        }
+
+        fn payload(&mut self, payload: BranchlogPayload) -> BranchBundleResult<()> {
+            self.payloads.push(payload);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        use crate::codec::{AesGcmCodec, EncryptionKey};
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stream.branchbundle.tar.zst");
+        let sealed_path = dir.path().join("sealed.branchbundle.tar.zst");
+        let codec = AesGcmCodec::new(&EncryptionKey::from_bytes([3u8; 32]));
+
+        let writer = BranchBundleWriter::new(&ExportOptions::default());
+        let branch_info = make_test_branch_info();
+        let payloads = make_test_payloads();
+        writer.write(&branch_info, &payloads, &path).unwrap();
+        writer
+            .write_encrypted(&branch_info, &payloads, &sealed_path, &codec)
+            .unwrap();
+
+        let mut visitor = Collect::default();
+        BranchBundleReader::stream(&path, &mut visitor).unwrap();
+        assert_eq!(visitor.branch_info.unwrap().name, branch_info.name);
+        assert_eq!(visitor.payloads, payloads);
+
+        let mut visitor = Collect::default();
+        BranchBundleReader::stream_encrypted(&sealed_path, &codec, &mut visitor).unwrap();
+        assert_eq!(visitor.payloads, payloads);
+    }
+
+    #[test]
+    fn test_stream_detects_wal_checksum_mismatch() {
+        use crate::branch_bundle::writer::add_file;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("tampered.branchbundle.tar.zst");
+        let writer = BranchBundleWriter::new(&ExportOptions::default());
+        writer
+            .write(&make_test_branch_info(), &make_test_payloads(), &path)
+            .unwrap();
+
+        // Rebuild the archive with a manifest that disagrees about the WAL
+        let files = BranchBundleReader::extract_all_files(&path).unwrap();
+        let mut manifest: BundleManifest = serde_json::from_slice(&files["MANIFEST.json"]).unwrap();
+        manifest.add_checksum("WAL.branchlog", "0000000000000000");
+        let mut builder = tar::Builder::new(zstd::Encoder::new(Vec::new(), 3).unwrap());
+        let manifest_json = serde_json::to_vec_pretty(&manifest).unwrap();
+        add_file(&mut builder, paths::MANIFEST, &manifest_json).unwrap();
+        add_file(&mut builder, paths::BRANCH, &files["BRANCH.json"]).unwrap();
+        add_file(&mut builder, paths::WAL, &files["WAL.branchlog"]).unwrap();
+        let archive = builder.into_inner().unwrap().finish().unwrap();
+        std::fs::write(&path, archive).unwrap();
+
+        // Payloads arrive before the mismatch can be detected
+        let mut visitor = Collect::default();
+        let result = BranchBundleReader::stream(&path, &mut visitor);
+        assert!(matches!(
+            result,
+            Err(BranchBundleError::ChecksumMismatch { ref file, .. }) if file == "WAL.branchlog"
+        ));
+        assert_eq!(visitor.payloads.len(), 2);
+
+        assert!(!BranchBundleReader::validate(&path).unwrap().checksums_valid);
+    }
 }
@@ -2,9 +2,14 @@
 //!
 //! Types for the BranchBundle archive format (.branchbundle.tar.zst)
 
+use crate::format::SNAPSHOT_FORMAT_VERSION;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{Read, Write};
 use std::path::PathBuf;
+use std::sync::Arc;
+use xxhash_rust::xxh3::Xxh3;
 
 /// Current BranchBundle format version
 pub const BRANCHBUNDLE_FORMAT_VERSION: u32 = 2;
@@ -12,6 +17,13 @@ pub const BRANCHBUNDLE_FORMAT_VERSION: u32 = 2;
 /// File extension for BranchBundle archives
 pub const BRANCHBUNDLE_EXTENSION: &str = ".branchbundle.tar.zst";
 
+/// Magic bytes at the start of an encrypted bundle.
+///
+/// An encrypted bundle is `magic | codec_id_len (u8) | codec_id | sealed archive`,
+/// where the sealed archive is the plain `.tar.zst` bundle encoded by the
+/// database's storage codec.
+pub const ENCRYPTED_BUNDLE_MAGIC: &[u8; 8] = b"STRATAEB";
+
 /// Archive paths within the bundle
 pub mod paths {
     /// Root directory in the archive
@@ -68,6 +80,10 @@ pub struct BundleManifest {
 
     /// Summary of bundle contents
     pub contents: BundleContents,
+
+    /// Environment of the exporting database, when it captures one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvironmentInfo>,
 }
 
 impl BundleManifest {
@@ -80,6 +96,7 @@ impl BundleManifest {
             checksum_algorithm: "xxh3".to_string(),
             checksums: HashMap::new(),
             contents,
+            environment: None,
         }
     }
 
@@ -118,7 +135,7 @@ pub struct BundleBranchInfo {
     /// Human-readable branch name
     pub name: String,
 
-    /// Branch state: "active"
+    /// Branch state: "active", "completed" or "failed"
     pub state: String,
 
     /// ISO 8601 timestamp when branch was created
@@ -134,6 +151,10 @@ pub struct BundleBranchInfo {
     /// Error message if branch failed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+
+    /// Environment the branch was created in, when captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvironmentInfo>,
 }
 
 impl BundleBranchInfo {
@@ -146,6 +167,97 @@ impl BundleBranchInfo {
     }
 }
 
+// =============================================================================
+// Environment
+// =============================================================================
+
+/// Software and host a branch was created or a bundle exported on
+///
+/// Captured only when the database opts in. Recorded so a bundle can be
+/// checked for compatibility before import and results stay attributable
+/// to the build and models that produced them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct EnvironmentInfo {
+    /// Strata version
+    pub strata_version: String,
+    /// WAL segment format version written
+    pub wal_format: u32,
+    /// Snapshot format version written
+    pub snapshot_format: u32,
+    /// Branch bundle format version written
+    pub bundle_format: u32,
+    /// Host name
+    pub host: String,
+    /// Operating system (`std::env::consts::OS`)
+    pub os: String,
+    /// CPU architecture (`std::env::consts::ARCH`)
+    pub arch: String,
+    /// Models in use by role, e.g. `"embed" => "minilm-l6-v2"`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, String>,
+    /// ISO 8601 timestamp of the capture
+    pub captured_at: String,
+}
+
+impl EnvironmentInfo {
+    /// Capture this process's environment
+    ///
+    /// `wal_format` is the segment format the database writes, which depends
+    /// on its codec; the other formats are fixed by this build.
+    pub fn new(wal_format: u32, host: impl Into<String>, models: BTreeMap<String, String>) -> Self {
+        Self {
+            strata_version: env!("CARGO_PKG_VERSION").to_string(),
+            wal_format,
+            snapshot_format: SNAPSHOT_FORMAT_VERSION,
+            bundle_format: BRANCHBUNDLE_FORMAT_VERSION,
+            host: host.into(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            models,
+            captured_at: chrono_now_iso8601(),
+        }
+    }
+
+    /// Reasons data from this environment may not behave the same in
+    /// `current`
+    ///
+    /// Reports formats newer than `current` writes, a different Strata
+    /// version, and models whose id differs for the same role. Host, OS and
+    /// architecture are informational and never reported.
+    pub fn compatibility_issues(&self, current: &EnvironmentInfo) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.strata_version != current.strata_version {
+            issues.push(format!(
+                "created by Strata {}, this is Strata {}",
+                self.strata_version, current.strata_version
+            ));
+        }
+        for (name, theirs, ours) in [
+            ("WAL", self.wal_format, current.wal_format),
+            ("snapshot", self.snapshot_format, current.snapshot_format),
+            ("bundle", self.bundle_format, current.bundle_format),
+        ] {
+            if theirs > ours {
+                issues.push(format!(
+                    "{} format v{} is newer than supported v{}",
+                    name, theirs, ours
+                ));
+            }
+        }
+        for (role, model) in &self.models {
+            match current.models.get(role) {
+                Some(ours) if ours != model => issues.push(format!(
+                    "{} model '{}' differs from '{}' in use",
+                    role, model, ours
+                )),
+                None => issues.push(format!("{} model '{}' is not in use", role, model)),
+                _ => {}
+            }
+        }
+        issues
+    }
+}
+
 // =============================================================================
 // Export Types
 // =============================================================================
@@ -169,21 +281,71 @@ pub struct BranchExportInfo {
     pub checksum: String,
 }
 
+/// Progress of an export, reported after each WAL entry is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleProgress {
+    /// WAL entries written so far
+    pub entries_written: u64,
+    /// WAL entries the bundle will hold
+    pub entries_total: u64,
+    /// Uncompressed WAL.branchlog bytes written so far
+    pub bytes_written: u64,
+}
+
+/// Callback receiving [`BundleProgress`] updates
+pub type ProgressCallback = Arc<dyn Fn(&BundleProgress) + Send + Sync>;
+
 /// Options for export operation
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ExportOptions {
     /// Zstd compression level (1-22, default: 3)
     pub compression_level: i32,
+    /// Called after each WAL entry is written (default: none)
+    pub on_progress: Option<ProgressCallback>,
+    /// Recorded in the bundle manifest (default: none)
+    pub environment: Option<EnvironmentInfo>,
+}
+
+impl ExportOptions {
+    /// Report progress to `callback` (builder pattern)
+    pub fn with_progress(
+        mut self,
+        callback: impl Fn(&BundleProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Record `environment` in the manifest (builder pattern)
+    pub fn with_environment(mut self, environment: EnvironmentInfo) -> Self {
+        self.environment = Some(environment);
+        self
+    }
 }
 
 impl Default for ExportOptions {
     fn default() -> Self {
         Self {
             compression_level: 3,
+            on_progress: None,
+            environment: None,
         }
     }
 }
 
+impl fmt::Debug for ExportOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExportOptions")
+            .field("compression_level", &self.compression_level)
+            .field(
+                "on_progress",
+                &self.on_progress.as_ref().map(|_| "<callback>"),
+            )
+            .field("environment", &self.environment)
+            .finish()
+    }
+}
+
 // =============================================================================
 // Verify Types
 // =============================================================================
@@ -202,6 +364,9 @@ pub struct BundleVerifyInfo {
 
     /// Whether all checksums are valid
     pub checksums_valid: bool,
+
+    /// Environment recorded in the manifest, if any
+    pub environment: Option<EnvironmentInfo>,
 }
 
 // =============================================================================
@@ -228,7 +393,7 @@ pub struct ImportedBranchInfo {
 /// The date may be off by a few days due to simplified leap year handling. This is
 /// acceptable because this timestamp is only used for bundle metadata/display, not for
 /// correctness. The actual WAL entries use proper microsecond timestamps from SystemTime.
-fn chrono_now_iso8601() -> String {
+pub(crate) fn chrono_now_iso8601() -> String {
     let now = std::time::SystemTime::now();
     let duration = now
         .duration_since(std::time::UNIX_EPOCH)
@@ -262,6 +427,80 @@ pub fn xxh3_hex(data: &[u8]) -> String {
     format!("{:016x}", xxh3_64(data))
 }
 
+/// Writer that hashes and counts everything passed through it
+///
+/// Gives the same checksum as [`xxh3_hex`] over the written bytes without
+/// holding them.
+pub(crate) struct HashingWriter<W> {
+    inner: W,
+    hasher: Xxh3,
+    bytes: u64,
+}
+
+impl<W: Write> HashingWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Xxh3::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Bytes written so far
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Return the inner writer, the byte count and the checksum
+    pub(crate) fn finish(self) -> (W, u64, String) {
+        let checksum = format!("{:016x}", self.hasher.digest());
+        (self.inner, self.bytes, checksum)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.bytes += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader that hashes everything read through it
+pub(crate) struct HashingReader<R> {
+    inner: R,
+    hasher: Xxh3,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Xxh3::new(),
+        }
+    }
+
+    /// Read the rest of the input and return its checksum, as
+    /// [`xxh3_hex`] would give for all of it
+    pub(crate) fn finish(mut self) -> std::io::Result<String> {
+        std::io::copy(&mut self, &mut std::io::sink())?;
+        Ok(format!("{:016x}", self.hasher.digest()))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,6 +570,7 @@ mod tests {
             closed_at: "2025-01-24T01:00:00Z".to_string(),
             parent_branch_id: None,
             error: None,
+            environment: None,
         };
 
         assert!(make_branch("completed").is_terminal_state());
@@ -352,6 +592,7 @@ mod tests {
             closed_at: "2025-01-24T11:30:00Z".to_string(),
             parent_branch_id: None,
             error: None,
+            environment: None,
         };
 
         let json = serde_json::to_string_pretty(&branch_info).unwrap();
@@ -370,6 +611,7 @@ mod tests {
             closed_at: "2025-01-24T10:05:00Z".to_string(),
             parent_branch_id: Some("parent-id".to_string()),
             error: Some("Connection timeout".to_string()),
+            environment: None,
         };
 
         let json = serde_json::to_string(&branch_info).unwrap();
@@ -394,6 +636,89 @@ mod tests {
     fn test_export_options_default() {
         let opts = ExportOptions::default();
         assert_eq!(opts.compression_level, 3);
+        assert!(opts.on_progress.is_none());
+    }
+
+    #[test]
+    fn test_hashing_writer_and_reader_match_xxh3_hex() {
+        let data: Vec<u8> = (0..10_000u32).flat_map(|i| i.to_le_bytes()).collect();
+
+        let mut writer = HashingWriter::new(Vec::new());
+        for chunk in data.chunks(777) {
+            writer.write_all(chunk).unwrap();
+        }
+        let (written, bytes, checksum) = writer.finish();
+        assert_eq!(written, data);
+        assert_eq!(bytes, data.len() as u64);
+        assert_eq!(checksum, xxh3_hex(&data));
+
+        let mut reader = HashingReader::new(data.as_slice());
+        let mut head = [0u8; 100];
+        reader.read_exact(&mut head).unwrap();
+        assert_eq!(reader.finish().unwrap(), xxh3_hex(&data));
+    }
+
+    fn make_environment() -> EnvironmentInfo {
+        EnvironmentInfo {
+            strata_version: "0.12.0".to_string(),
+            wal_format: 2,
+            snapshot_format: 1,
+            bundle_format: BRANCHBUNDLE_FORMAT_VERSION,
+            host: "host-a".to_string(),
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            models: [("embed".to_string(), "minilm-l6-v2".to_string())].into(),
+            captured_at: "2025-01-24T10:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_environment_is_optional_in_manifest() {
+        let contents = BundleContents {
+            wal_entry_count: 1,
+            wal_size_bytes: 10,
+        };
+        let manifest = BundleManifest::new("0.12.0", contents);
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(!json.contains("environment"));
+        let parsed: BundleManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.environment, None);
+
+        let mut manifest = parsed;
+        manifest.environment = Some(make_environment());
+        let json = serde_json::to_string(&manifest).unwrap();
+        let parsed: BundleManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, manifest);
+    }
+
+    #[test]
+    fn test_environment_compatibility_issues() {
+        let env = make_environment();
+        let mut current = env.clone();
+        current.host = "host-b".to_string();
+        current.arch = "aarch64".to_string();
+        assert!(env.compatibility_issues(&current).is_empty());
+
+        current.wal_format = 1;
+        current.strata_version = "0.13.0".to_string();
+        current
+            .models
+            .insert("embed".to_string(), "other".to_string());
+        let issues = env.compatibility_issues(&current);
+        assert_eq!(issues.len(), 3, "{:?}", issues);
+        assert!(issues.iter().any(|i| i.contains("WAL format v2")));
+        assert!(issues.iter().any(|i| i.contains("Strata 0.12.0")));
+        assert!(issues.iter().any(|i| i.contains("'other'")));
+
+        // An older format is readable
+        let issues = current.compatibility_issues(&env);
+        assert!(!issues.iter().any(|i| i.contains("format")));
+
+        current.models.clear();
+        assert!(env
+            .compatibility_issues(&current)
+            .iter()
+            .any(|i| i.contains("not in use")));
     }
 
     #[test]
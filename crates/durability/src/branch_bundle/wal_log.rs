@@ -23,8 +23,9 @@
 //! ```
 
 use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
-use crate::branch_bundle::types::{xxh3_hex, WAL_BRANCHLOG_MAGIC, WAL_BRANCHLOG_VERSION};
+use crate::branch_bundle::types::{HashingWriter, WAL_BRANCHLOG_MAGIC, WAL_BRANCHLOG_VERSION};
 use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
 use std::io::{Read, Write};
 use strata_core::types::Key;
 use strata_core::value::Value;
@@ -92,38 +93,57 @@ impl WalLogWriter {
     /// Returns information about the written data including checksum.
     pub fn write<W: Write>(
         payloads: &[BranchlogPayload],
-        mut writer: W,
+        writer: W,
     ) -> BranchBundleResult<WalLogInfo> {
-        let mut buffer = Vec::new();
+        Self::write_stream(payloads, writer, |_, _| {})
+    }
+
+    /// Write payloads to a writer one entry at a time
+    ///
+    /// Only one serialized entry is held in memory, so `payloads` can be
+    /// produced lazily. The entry count goes in the header, so the iterator
+    /// must know its length. `on_entry` is called after each entry with the
+    /// entries and bytes written so far. Many small writes are issued;
+    /// wrap unbuffered writers in a `BufWriter`.
+    pub fn write_stream<W, I>(
+        payloads: I,
+        writer: W,
+        mut on_entry: impl FnMut(u64, u64),
+    ) -> BranchBundleResult<WalLogInfo>
+    where
+        W: Write,
+        I: IntoIterator,
+        I::Item: Borrow<BranchlogPayload>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let payloads = payloads.into_iter();
+        let entry_count = payloads.len() as u64;
+        let mut writer = HashingWriter::new(writer);
 
         // Write header
-        buffer.extend_from_slice(WAL_BRANCHLOG_MAGIC);
-        buffer.extend_from_slice(&WAL_BRANCHLOG_VERSION.to_le_bytes());
-        buffer.extend_from_slice(&(payloads.len() as u32).to_le_bytes());
+        writer.write_all(WAL_BRANCHLOG_MAGIC)?;
+        writer.write_all(&WAL_BRANCHLOG_VERSION.to_le_bytes())?;
+        writer.write_all(&(entry_count as u32).to_le_bytes())?;
 
         // Write entries
-        for payload in payloads {
+        for (i, payload) in payloads.enumerate() {
             // Serialize entry with msgpack
-            let entry_data = payload.to_bytes();
+            let entry_data = payload.borrow().to_bytes();
 
             // Calculate CRC32 of entry data
             let crc = crc32fast::hash(&entry_data);
 
             // Write: length + data + crc
-            buffer.extend_from_slice(&(entry_data.len() as u32).to_le_bytes());
-            buffer.extend_from_slice(&entry_data);
-            buffer.extend_from_slice(&crc.to_le_bytes());
-        }
-
-        // Calculate checksum of entire buffer
-        let checksum = xxh3_hex(&buffer);
+            writer.write_all(&(entry_data.len() as u32).to_le_bytes())?;
+            writer.write_all(&entry_data)?;
+            writer.write_all(&crc.to_le_bytes())?;
 
-        // Write to output
-        let bytes_written = buffer.len() as u64;
-        writer.write_all(&buffer).map_err(BranchBundleError::from)?;
+            on_entry(i as u64 + 1, writer.bytes());
+        }
 
+        let (_, bytes_written, checksum) = writer.finish();
         Ok(WalLogInfo {
-            entry_count: payloads.len() as u64,
+            entry_count,
             bytes_written,
             checksum,
         })
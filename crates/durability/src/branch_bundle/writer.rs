@@ -8,11 +8,14 @@
 use crate::branch_bundle::error::{BranchBundleError, BranchBundleResult};
 use crate::branch_bundle::types::{
     paths, xxh3_hex, BranchExportInfo, BundleBranchInfo, BundleContents, BundleManifest,
-    ExportOptions,
+    BundleProgress, EnvironmentInfo, ExportOptions, HashingWriter, ProgressCallback,
+    ENCRYPTED_BUNDLE_MAGIC,
 };
-use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogWriter};
+use crate::branch_bundle::wal_log::{BranchlogPayload, WalLogInfo, WalLogWriter};
+use crate::codec::StorageCodec;
+use std::borrow::Borrow;
 use std::fs::{self, File};
-use std::io::{BufWriter, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 use tar::{Builder, Header};
 
@@ -21,6 +24,8 @@ use tar::{Builder, Header};
 /// Creates .branchbundle.tar.zst files with atomic write semantics.
 pub struct BranchBundleWriter {
     compression_level: i32,
+    on_progress: Option<ProgressCallback>,
+    environment: Option<EnvironmentInfo>,
 }
 
 impl BranchBundleWriter {
@@ -28,6 +33,8 @@ impl BranchBundleWriter {
     pub fn new(options: &ExportOptions) -> Self {
         Self {
             compression_level: options.compression_level,
+            on_progress: options.on_progress.clone(),
+            environment: options.environment.clone(),
         }
     }
 
@@ -46,9 +53,27 @@ impl BranchBundleWriter {
         payloads: &[BranchlogPayload],
         path: &Path,
     ) -> BranchBundleResult<BranchExportInfo> {
-        // Create temp file path
-        let temp_path = path.with_extension("tmp");
+        self.write_stream(branch_info, payloads, path)
+    }
 
+    /// Write a complete BranchBundle archive from payloads produced one at a time
+    ///
+    /// Memory stays bounded however large the branch is: each payload is
+    /// serialized and dropped before the next is taken, and the archive is
+    /// compressed straight to disk. Tar needs an entry's size before its
+    /// contents, so WAL.branchlog is first spooled to a temp file next to
+    /// `path`. Atomic like [`write`](Self::write).
+    pub fn write_stream<I>(
+        &self,
+        branch_info: &BundleBranchInfo,
+        payloads: I,
+        path: &Path,
+    ) -> BranchBundleResult<BranchExportInfo>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<BranchlogPayload>,
+        I::IntoIter: ExactSizeIterator,
+    {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             if !parent.as_os_str().is_empty() && !parent.exists() {
@@ -56,16 +81,21 @@ impl BranchBundleWriter {
             }
         }
 
+        let temp_path = path.with_extension("tmp");
+        let spool_path = path.with_extension("wal.tmp");
+
         // Try to write, clean up on failure
-        match self.write_inner(branch_info, payloads, &temp_path) {
-            Ok(info) => {
-                // Atomic rename
-                fs::rename(&temp_path, path)?;
-                Ok(BranchExportInfo {
-                    path: path.to_path_buf(),
-                    ..info
-                })
-            }
+        let result = self.write_inner(branch_info, payloads, &spool_path, &temp_path);
+        let _ = fs::remove_file(&spool_path);
+        match result.and_then(|info| {
+            // Atomic rename
+            fs::rename(&temp_path, path)?;
+            Ok(info)
+        }) {
+            Ok(info) => Ok(BranchExportInfo {
+                path: path.to_path_buf(),
+                ..info
+            }),
             Err(e) => {
                 // Clean up temp file
                 let _ = fs::remove_file(&temp_path);
@@ -75,15 +105,71 @@ impl BranchBundleWriter {
     }
 
     /// Internal write implementation
-    fn write_inner(
+    fn write_inner<I>(
         &self,
         branch_info: &BundleBranchInfo,
-        payloads: &[BranchlogPayload],
+        payloads: I,
+        spool_path: &Path,
         path: &Path,
-    ) -> BranchBundleResult<BranchExportInfo> {
-        // Prepare file contents
+    ) -> BranchBundleResult<BranchExportInfo>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<BranchlogPayload>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        // Spool WAL.branchlog to learn its size and checksum
+        let mut spool = BufWriter::new(File::create(spool_path)?);
+        let wal_info = self.write_wal(payloads, &mut spool)?;
+        spool.flush()?;
+        drop(spool);
+
+        // Compress the archive to disk, checksumming it on the way
+        let out = HashingWriter::new(BufWriter::new(File::create(path)?));
+        let wal = BufReader::new(File::open(spool_path)?);
+        let out = self.write_archive(branch_info, &wal_info, wal, out)?;
+        let (mut buf_writer, bundle_size, checksum) = out.finish();
+        buf_writer.flush()?;
+
+        Ok(BranchExportInfo {
+            branch_id: branch_info.branch_id.clone(),
+            path: path.to_path_buf(),
+            wal_entry_count: wal_info.entry_count,
+            bundle_size_bytes: bundle_size,
+            checksum,
+        })
+    }
+
+    /// Write WAL.branchlog, reporting progress after each entry
+    fn write_wal<I, W>(&self, payloads: I, writer: W) -> BranchBundleResult<WalLogInfo>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<BranchlogPayload>,
+        I::IntoIter: ExactSizeIterator,
+        W: Write,
+    {
+        let payloads = payloads.into_iter();
+        let entries_total = payloads.len() as u64;
+        WalLogWriter::write_stream(payloads, writer, |entries_written, bytes_written| {
+            if let Some(on_progress) = &self.on_progress {
+                on_progress(&BundleProgress {
+                    entries_written,
+                    entries_total,
+                    bytes_written,
+                });
+            }
+        })
+    }
+
+    /// Write the compressed tar of MANIFEST.json, BRANCH.json and the
+    /// WAL.branchlog read from `wal` to `out`
+    fn write_archive<R: Read, W: Write>(
+        &self,
+        branch_info: &BundleBranchInfo,
+        wal_info: &WalLogInfo,
+        wal: R,
+        out: W,
+    ) -> BranchBundleResult<W> {
         let branch_json = serde_json::to_vec_pretty(branch_info)?;
-        let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
 
         // Build manifest with checksums
         let mut manifest = BundleManifest::new(
@@ -93,6 +179,7 @@ impl BranchBundleWriter {
                 wal_size_bytes: wal_info.bytes_written,
             },
         );
+        manifest.environment = self.environment.clone();
         manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
         manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
 
@@ -103,18 +190,14 @@ impl BranchBundleWriter {
         let manifest_json = serde_json::to_vec_pretty(&manifest)?;
 
         // Create compressed tar archive
-        let file = File::create(path)?;
-        let buf_writer = BufWriter::new(file);
-        let zstd_writer = zstd::Encoder::new(buf_writer, self.compression_level)
+        let zstd_writer = zstd::Encoder::new(out, self.compression_level)
             .map_err(|e| BranchBundleError::compression(format!("zstd encoder: {}", e)))?;
-        let zstd_writer = zstd_writer.auto_finish();
-
         let mut tar_builder = Builder::new(zstd_writer);
 
         // Add files to archive
-        self.add_file(&mut tar_builder, paths::MANIFEST, &manifest_json)?;
-        self.add_file(&mut tar_builder, paths::BRANCH, &branch_json)?;
-        self.add_file(&mut tar_builder, paths::WAL, &wal_data)?;
+        add_file(&mut tar_builder, paths::MANIFEST, &manifest_json)?;
+        add_file(&mut tar_builder, paths::BRANCH, &branch_json)?;
+        add_reader(&mut tar_builder, paths::WAL, wal_info.bytes_written, wal)?;
 
         // Finish tar archive
         let zstd_writer = tar_builder
@@ -122,92 +205,47 @@ impl BranchBundleWriter {
             .map_err(|e| BranchBundleError::archive(format!("tar finish: {}", e)))?;
 
         // Finish zstd compression
-        drop(zstd_writer);
-
-        // Get file size and compute checksum
-        let metadata = fs::metadata(path)?;
-        let bundle_size = metadata.len();
+        zstd_writer
+            .finish()
+            .map_err(|e| BranchBundleError::compression(format!("zstd finish: {}", e)))
+    }
 
-        let bundle_data = fs::read(path)?;
-        let checksum = xxh3_hex(&bundle_data);
+    /// Write a bundle sealed with `codec` (encryption at rest)
+    ///
+    /// The plain archive is built in memory, encoded with `codec`, and
+    /// written atomically behind an [`ENCRYPTED_BUNDLE_MAGIC`] header.
+    /// Sealing covers the whole archive at once, so unlike
+    /// [`write_stream`](Self::write_stream) this holds the bundle in memory.
+    pub fn write_encrypted(
+        &self,
+        branch_info: &BundleBranchInfo,
+        payloads: &[BranchlogPayload],
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> BranchBundleResult<BranchExportInfo> {
+        let (archive, info) = self.write_to_vec(branch_info, payloads)?;
+        let sealed = seal_archive(&archive, codec);
+        write_file_atomic(path, &sealed)?;
 
         Ok(BranchExportInfo {
-            branch_id: branch_info.branch_id.clone(),
             path: path.to_path_buf(),
-            wal_entry_count: wal_info.entry_count,
-            bundle_size_bytes: bundle_size,
-            checksum,
+            bundle_size_bytes: sealed.len() as u64,
+            checksum: xxh3_hex(&sealed),
+            ..info
         })
     }
 
-    /// Add a file to the tar archive
-    fn add_file<W: Write>(
-        &self,
-        builder: &mut Builder<W>,
-        path: &str,
-        data: &[u8],
-    ) -> BranchBundleResult<()> {
-        let mut header = Header::new_gnu();
-        header
-            .set_path(path)
-            .map_err(|e| BranchBundleError::archive(format!("set path '{}': {}", path, e)))?;
-        header.set_size(data.len() as u64);
-        header.set_mode(0o644);
-        header.set_mtime(0); // Reproducible builds: zero mtime
-        header.set_cksum();
-
-        builder
-            .append(&header, data)
-            .map_err(|e| BranchBundleError::archive(format!("append '{}': {}", path, e)))?;
-
-        Ok(())
-    }
-
     /// Write a bundle to a Vec<u8> (for testing)
     pub fn write_to_vec(
         &self,
         branch_info: &BundleBranchInfo,
         payloads: &[BranchlogPayload],
     ) -> BranchBundleResult<(Vec<u8>, BranchExportInfo)> {
-        // Prepare file contents
-        let branch_json = serde_json::to_vec_pretty(branch_info)?;
-        let (wal_data, wal_info) = WalLogWriter::write_to_vec(payloads)?;
-
-        // Build manifest with checksums
-        let mut manifest = BundleManifest::new(
-            env!("CARGO_PKG_VERSION"),
-            BundleContents {
-                wal_entry_count: wal_info.entry_count,
-                wal_size_bytes: wal_info.bytes_written,
-            },
-        );
-        manifest.add_checksum("BRANCH.json", xxh3_hex(&branch_json));
-        manifest.add_checksum("WAL.branchlog", &wal_info.checksum);
-
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
-        manifest.add_checksum("MANIFEST.json", xxh3_hex(&manifest_json));
-
-        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let mut wal_data = Vec::new();
+        let wal_info = self.write_wal(payloads, &mut wal_data)?;
 
         // Create compressed tar in memory
-        let mut buffer = Vec::new();
-        {
-            let zstd_writer = zstd::Encoder::new(&mut buffer, self.compression_level)
-                .map_err(|e| BranchBundleError::compression(format!("zstd encoder: {}", e)))?;
-            let zstd_writer = zstd_writer.auto_finish();
-
-            let mut tar_builder = Builder::new(zstd_writer);
-
-            self.add_file(&mut tar_builder, paths::MANIFEST, &manifest_json)?;
-            self.add_file(&mut tar_builder, paths::BRANCH, &branch_json)?;
-            self.add_file(&mut tar_builder, paths::WAL, &wal_data)?;
-
-            let zstd_writer = tar_builder
-                .into_inner()
-                .map_err(|e| BranchBundleError::archive(format!("tar finish: {}", e)))?;
-
-            drop(zstd_writer);
-        }
+        let buffer = self.write_archive(branch_info, &wal_info, wal_data.as_slice(), Vec::new())?;
 
         let checksum = xxh3_hex(&buffer);
 
@@ -223,6 +261,81 @@ impl BranchBundleWriter {
     }
 }
 
+/// Add a file to the tar archive
+pub(crate) fn add_file<W: Write>(
+    builder: &mut Builder<W>,
+    path: &str,
+    data: &[u8],
+) -> BranchBundleResult<()> {
+    let mut header = Header::new_gnu();
+    header
+        .set_path(path)
+        .map_err(|e| BranchBundleError::archive(format!("set path '{}': {}", path, e)))?;
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0); // Reproducible builds: zero mtime
+    header.set_cksum();
+
+    builder
+        .append(&header, data)
+        .map_err(|e| BranchBundleError::archive(format!("append '{}': {}", path, e)))?;
+
+    Ok(())
+}
+
+/// Add a file of `size` bytes read from `data` to the tar archive
+///
+/// The data is copied through in chunks rather than loaded whole.
+pub(crate) fn add_reader<W: Write, R: Read>(
+    builder: &mut Builder<W>,
+    path: &str,
+    size: u64,
+    data: R,
+) -> BranchBundleResult<()> {
+    let mut header = Header::new_gnu();
+    header
+        .set_path(path)
+        .map_err(|e| BranchBundleError::archive(format!("set path '{}': {}", path, e)))?;
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_mtime(0); // Reproducible builds: zero mtime
+    header.set_cksum();
+
+    builder
+        .append(&header, data.take(size))
+        .map_err(|e| BranchBundleError::archive(format!("append '{}': {}", path, e)))?;
+
+    Ok(())
+}
+
+/// Seal a plain `.tar.zst` archive with `codec` behind an
+/// [`ENCRYPTED_BUNDLE_MAGIC`] header
+pub(crate) fn seal_archive(archive: &[u8], codec: &dyn StorageCodec) -> Vec<u8> {
+    let codec_id = codec.codec_id().as_bytes();
+    let mut sealed = Vec::with_capacity(ENCRYPTED_BUNDLE_MAGIC.len() + 1 + codec_id.len());
+    sealed.extend_from_slice(ENCRYPTED_BUNDLE_MAGIC);
+    sealed.push(codec_id.len() as u8);
+    sealed.extend_from_slice(codec_id);
+    sealed.extend_from_slice(&codec.encode(archive));
+    sealed
+}
+
+/// Write `data` to `path` via a temp file and rename, creating parent
+/// directories as needed
+pub(crate) fn write_file_atomic(path: &Path, data: &[u8]) -> BranchBundleResult<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    let temp_path = path.with_extension("tmp");
+    if let Err(e) = fs::write(&temp_path, data).and_then(|_| fs::rename(&temp_path, path)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,6 +355,7 @@ mod tests {
             closed_at: "2025-01-24T11:00:00Z".to_string(),
             parent_branch_id: None,
             error: None,
+            environment: None,
         }
     }
 
@@ -421,6 +535,65 @@ mod tests {
         assert_eq!(parsed_info.state, branch_info.state);
     }
 
+    #[test]
+    fn test_write_stream_matches_write_to_vec() {
+        use crate::branch_bundle::reader::BranchBundleReader;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("stream.branchbundle.tar.zst");
+        let writer = BranchBundleWriter::with_defaults();
+        let branch_info = make_test_branch_info();
+        let payloads = make_test_payloads();
+
+        // Owned payloads, produced one at a time
+        let info = writer
+            .write_stream(&branch_info, payloads.clone(), &path)
+            .unwrap();
+        let (expected, expected_info) = writer.write_to_vec(&branch_info, &payloads).unwrap();
+
+        // Manifests differ only in their creation time
+        let data = std::fs::read(&path).unwrap();
+        assert_eq!(info.checksum, xxh3_hex(&data));
+        assert_eq!(info.bundle_size_bytes, data.len() as u64);
+        assert_eq!(info.wal_entry_count, expected_info.wal_entry_count);
+        let manifest = BranchBundleReader::read_manifest_from_bytes(&data).unwrap();
+        let expected_manifest = BranchBundleReader::read_manifest_from_bytes(&expected).unwrap();
+        for file in ["BRANCH.json", "WAL.branchlog"] {
+            assert_eq!(manifest.checksums[file], expected_manifest.checksums[file]);
+        }
+        assert_eq!(
+            BranchBundleReader::read_wal_entries_from_bytes(&data).unwrap(),
+            payloads
+        );
+
+        // Only the bundle is left behind
+        let files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        use std::sync::{Arc, Mutex};
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let options = ExportOptions::default().with_progress(move |p| {
+            sink.lock().unwrap().push(*p);
+        });
+        let writer = BranchBundleWriter::new(&options);
+        let (_, info) = writer
+            .write_to_vec(&make_test_branch_info(), &make_test_payloads())
+            .unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].entries_written, 1);
+        assert_eq!(seen[1].entries_written, 2);
+        assert!(seen.iter().all(|p| p.entries_total == 2));
+        assert!(seen[0].bytes_written < seen[1].bytes_written);
+        assert_eq!(info.wal_entry_count, 2);
+    }
+
     #[test]
     fn test_empty_entries() {
         let writer = BranchBundleWriter::with_defaults();
@@ -0,0 +1,108 @@
+//! Zstd compression dictionaries.
+//!
+//! Small values (a few hundred bytes of JSON) compress poorly on their own:
+//! zstd has no history to match against. A dictionary trained over sampled
+//! values of one primitive supplies that history, so each value compresses
+//! well individually. Dictionaries are persisted in the MANIFEST and handed
+//! to [`ZstdCodec::with_dictionary`](super::ZstdCodec::with_dictionary).
+
+use super::traits::CodecError;
+
+/// Default maximum dictionary size (bytes).
+pub const DEFAULT_DICTIONARY_SIZE: usize = 16 * 1024;
+
+/// Minimum number of samples needed to train a dictionary.
+pub const MIN_DICTIONARY_SAMPLES: usize = 16;
+
+/// A trained zstd dictionary.
+///
+/// The id is derived from the dictionary bytes and is recorded in every
+/// payload compressed with it, so decoding with the wrong dictionary is
+/// reported instead of producing garbage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressionDictionary {
+    id: u32,
+    bytes: Vec<u8>,
+}
+
+impl CompressionDictionary {
+    /// Wrap raw dictionary bytes (e.g. loaded from the MANIFEST).
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        CompressionDictionary {
+            id: crc32fast::hash(&bytes),
+            bytes,
+        }
+    }
+
+    /// Dictionary id recorded in compressed payloads.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Raw dictionary bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Train a dictionary of at most `max_size` bytes over `samples`.
+///
+/// # Errors
+///
+/// Returns [`CodecError::DictionaryTraining`] if there are fewer than
+/// [`MIN_DICTIONARY_SAMPLES`] samples or zstd cannot build a dictionary
+/// from them (e.g. the samples are too small or too uniform).
+pub fn train_dictionary<S: AsRef<[u8]>>(
+    samples: &[S],
+    max_size: usize,
+) -> Result<CompressionDictionary, CodecError> {
+    if samples.len() < MIN_DICTIONARY_SAMPLES {
+        return Err(CodecError::DictionaryTraining(format!(
+            "need at least {} samples, got {}",
+            MIN_DICTIONARY_SAMPLES,
+            samples.len()
+        )));
+    }
+    let bytes = zstd::dict::from_samples(samples, max_size)
+        .map_err(|e| CodecError::DictionaryTraining(e.to_string()))?;
+    Ok(CompressionDictionary::from_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_samples(n: usize) -> Vec<Vec<u8>> {
+        (0..n)
+            .map(|i| {
+                format!(
+                    r#"{{"user_id":{},"status":"active","plan":"pro","region":"eu-west-{}","tags":["alpha","beta"]}}"#,
+                    i,
+                    i % 3
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_train_dictionary() {
+        let dict = train_dictionary(&json_samples(500), DEFAULT_DICTIONARY_SIZE).unwrap();
+        assert!(!dict.as_bytes().is_empty());
+        assert!(dict.as_bytes().len() <= DEFAULT_DICTIONARY_SIZE);
+        assert_eq!(dict.id(), crc32fast::hash(dict.as_bytes()));
+    }
+
+    #[test]
+    fn test_train_dictionary_too_few_samples() {
+        let result = train_dictionary(&json_samples(3), DEFAULT_DICTIONARY_SIZE);
+        assert!(matches!(result, Err(CodecError::DictionaryTraining(_))));
+    }
+
+    #[test]
+    fn test_dictionary_from_bytes_round_trip() {
+        let dict = train_dictionary(&json_samples(500), DEFAULT_DICTIONARY_SIZE).unwrap();
+        let reloaded = CompressionDictionary::from_bytes(dict.as_bytes().to_vec());
+        assert_eq!(dict, reloaded);
+    }
+}
@@ -32,6 +32,14 @@ impl StorageCodec for IdentityCodec {
         data.to_vec()
     }
 
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(data);
+    }
+
+    fn is_identity(&self) -> bool {
+        true
+    }
+
     fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
         Ok(data.to_vec())
     }
@@ -100,6 +108,16 @@ mod tests {
         assert_eq!(codec.codec_id(), "identity");
     }
 
+    #[test]
+    fn test_identity_encode_into() {
+        let codec = IdentityCodec;
+        assert!(codec.is_identity());
+
+        let mut out = vec![0u8];
+        codec.encode_into(&[1, 2, 3], &mut out);
+        assert_eq!(out, vec![0, 1, 2, 3]);
+    }
+
     #[test]
     fn test_identity_is_copy() {
         let codec = IdentityCodec;
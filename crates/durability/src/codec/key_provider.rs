@@ -0,0 +1,177 @@
+//! Encryption keys and where to load them from.
+//!
+//! Keys never live in `strata.toml`; the config names a provider instead:
+//!
+//! | Provider | Meaning |
+//! |----------|---------|
+//! | `env:NAME` | Hex-encoded key in environment variable `NAME` |
+//! | `file:PATH` | Key file: 64 hex characters or 32 raw bytes |
+
+use std::path::PathBuf;
+
+use super::traits::CodecError;
+
+/// Size of an AES-256 key in bytes.
+pub const ENCRYPTION_KEY_SIZE: usize = 32;
+
+/// A 256-bit encryption key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; ENCRYPTION_KEY_SIZE]);
+
+impl EncryptionKey {
+    /// Create a key from raw bytes.
+    pub fn from_bytes(bytes: [u8; ENCRYPTION_KEY_SIZE]) -> Self {
+        EncryptionKey(bytes)
+    }
+
+    /// Parse a key from 64 hex characters (surrounding whitespace ignored).
+    pub fn from_hex(hex: &str) -> Result<Self, CodecError> {
+        let hex = hex.trim();
+        if hex.len() != ENCRYPTION_KEY_SIZE * 2 {
+            return Err(CodecError::InvalidKey(format!(
+                "expected {} hex characters, got {}",
+                ENCRYPTION_KEY_SIZE * 2,
+                hex.len()
+            )));
+        }
+        // Decode byte pairs rather than slicing the str, which would panic
+        // on a multi-byte character
+        let digit = |c: u8| (c as char).to_digit(16);
+        let mut bytes = [0u8; ENCRYPTION_KEY_SIZE];
+        for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            match (digit(pair[0]), digit(pair[1])) {
+                (Some(high), Some(low)) => *byte = (high << 4 | low) as u8,
+                _ => return Err(CodecError::InvalidKey("key is not valid hex".into())),
+            }
+        }
+        Ok(EncryptionKey(bytes))
+    }
+
+    /// Raw key bytes.
+    pub fn as_bytes(&self) -> &[u8; ENCRYPTION_KEY_SIZE] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EncryptionKey(<redacted>)")
+    }
+}
+
+/// Source of an encryption key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyProvider {
+    /// Hex-encoded key in an environment variable.
+    Env(String),
+    /// Key file containing 64 hex characters or 32 raw bytes.
+    File(PathBuf),
+}
+
+impl KeyProvider {
+    /// Parse a provider spec such as `env:STRATA_KEY` or `file:/etc/strata.key`.
+    pub fn parse(spec: &str) -> Result<Self, CodecError> {
+        match spec.split_once(':') {
+            Some(("env", name)) if !name.is_empty() => Ok(KeyProvider::Env(name.to_string())),
+            Some(("file", path)) if !path.is_empty() => Ok(KeyProvider::File(path.into())),
+            _ => Err(CodecError::InvalidKey(format!(
+                "invalid key provider '{}': expected \"env:NAME\" or \"file:PATH\"",
+                spec
+            ))),
+        }
+    }
+
+    /// Load the key from this provider.
+    pub fn load(&self) -> Result<EncryptionKey, CodecError> {
+        match self {
+            KeyProvider::Env(name) => {
+                let value = std::env::var(name).map_err(|_| {
+                    CodecError::InvalidKey(format!("environment variable {} is not set", name))
+                })?;
+                EncryptionKey::from_hex(&value)
+            }
+            KeyProvider::File(path) => {
+                let data = std::fs::read(path).map_err(|e| {
+                    CodecError::InvalidKey(format!(
+                        "cannot read key file '{}': {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                let text = std::str::from_utf8(&data).map(str::trim).unwrap_or("");
+                let is_hex = text.len() == ENCRYPTION_KEY_SIZE * 2
+                    && text.bytes().all(|b| b.is_ascii_hexdigit());
+                match <[u8; ENCRYPTION_KEY_SIZE]>::try_from(data.as_slice()) {
+                    Ok(raw) if !is_hex => Ok(EncryptionKey::from_bytes(raw)),
+                    _ => EncryptionKey::from_hex(&String::from_utf8_lossy(&data)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_key_from_hex() {
+        let key = EncryptionKey::from_hex(HEX_KEY).unwrap();
+        assert_eq!(key.as_bytes()[31], 0x1f);
+        assert!(EncryptionKey::from_hex("abcd").is_err());
+        assert!(EncryptionKey::from_hex(&"zz".repeat(32)).is_err());
+        assert!(EncryptionKey::from_hex(&"+f".repeat(32)).is_err());
+        // Multi-byte characters are rejected, not split mid-character
+        let multibyte = format!("é{}", &HEX_KEY[2..]);
+        assert_eq!(multibyte.len(), 64);
+        assert!(EncryptionKey::from_hex(&multibyte).is_err());
+        assert!(EncryptionKey::from_hex(&format!("0é{}", &HEX_KEY[3..])).is_err());
+        assert_eq!(format!("{:?}", key), "EncryptionKey(<redacted>)");
+    }
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(
+            KeyProvider::parse("env:STRATA_KEY").unwrap(),
+            KeyProvider::Env("STRATA_KEY".into())
+        );
+        assert_eq!(
+            KeyProvider::parse("file:/tmp/k").unwrap(),
+            KeyProvider::File("/tmp/k".into())
+        );
+        assert!(KeyProvider::parse("vault:x").is_err());
+        assert!(KeyProvider::parse("env:").is_err());
+    }
+
+    #[test]
+    fn test_load_from_file_and_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let hex_path = dir.path().join("hex.key");
+        std::fs::write(&hex_path, format!("{}\n", HEX_KEY)).unwrap();
+        let raw_path = dir.path().join("raw.key");
+        std::fs::write(&raw_path, [0x11u8; 32]).unwrap();
+
+        let expected = EncryptionKey::from_hex(HEX_KEY).unwrap();
+        assert_eq!(KeyProvider::File(hex_path).load().unwrap(), expected);
+        assert_eq!(
+            KeyProvider::File(raw_path).load().unwrap(),
+            EncryptionKey::from_bytes([0x11; 32])
+        );
+        assert!(KeyProvider::File(dir.path().join("missing"))
+            .load()
+            .is_err());
+
+        std::env::set_var("STRATA_TEST_KEY_PROVIDER", HEX_KEY);
+        assert_eq!(
+            KeyProvider::Env("STRATA_TEST_KEY_PROVIDER".into())
+                .load()
+                .unwrap(),
+            expected
+        );
+        assert!(KeyProvider::Env("STRATA_TEST_KEY_UNSET".into())
+            .load()
+            .is_err());
+    }
+}
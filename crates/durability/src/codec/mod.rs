@@ -6,12 +6,20 @@
 //!
 //!
 //!
-//! Uses `IdentityCodec` which performs no transformation. This establishes
-//! the codec seam without adding complexity. Future milestones can add:
+//! `IdentityCodec` (the default) performs no transformation. `ZstdCodec`
+//! compresses payloads above a size threshold. `AesGcmCodec` encrypts
+//! payloads at rest (optionally compressing them first); it needs a key, so
+//! it is constructed directly rather than through [`get_codec`].
 //!
-//! - `AesGcmCodec`: AES-256-GCM encryption at rest
-//! - `Lz4Codec`: LZ4 compression
-//! - `ChainedCodec`: Compression + encryption pipeline
+//! A `ZstdCodec` can also carry a [`CompressionDictionary`] trained with
+//! [`train_dictionary`], which lets it compress small values that would
+//! otherwise be stored as-is.
+//!
+//! # Codec Bytes
+//!
+//! WAL segment headers record the codec as a single byte (see
+//! [`codec_byte`]) so segments written with different codecs can be
+//! replayed side by side.
 //!
 //! # Usage
 //!
@@ -27,12 +35,31 @@
 //! assert_eq!(data.as_slice(), decoded.as_slice());
 //! ```
 
+mod aes_gcm;
+mod dictionary;
 mod identity;
+mod key_provider;
 mod traits;
+mod zstd;
 
+pub use self::zstd::{ZstdCodec, DEFAULT_ZSTD_LEVEL, DEFAULT_ZSTD_THRESHOLD};
+pub use aes_gcm::{AesGcmCodec, AES_GCM_CODEC_ID};
+pub use dictionary::{
+    train_dictionary, CompressionDictionary, DEFAULT_DICTIONARY_SIZE, MIN_DICTIONARY_SAMPLES,
+};
 pub use identity::IdentityCodec;
+pub use key_provider::{EncryptionKey, KeyProvider, ENCRYPTION_KEY_SIZE};
 pub use traits::{CodecError, StorageCodec};
 
+/// Codec byte for [`IdentityCodec`].
+pub const CODEC_BYTE_IDENTITY: u8 = 0;
+
+/// Codec byte for [`ZstdCodec`].
+pub const CODEC_BYTE_ZSTD: u8 = 1;
+
+/// Codec byte for [`AesGcmCodec`].
+pub const CODEC_BYTE_AES_GCM: u8 = 2;
+
 /// Get a codec by its identifier.
 ///
 /// Returns the codec if recognized, or an error for unknown codec IDs.
@@ -40,18 +67,39 @@ pub use traits::{CodecError, StorageCodec};
 /// # Known Codecs
 ///
 /// - `"identity"`: No-op codec (pass-through)
+/// - `"zstd"`: Zstd compression with default level and threshold
 ///
-/// # Future Codecs
-///
-/// - `"aes-gcm-256"`: AES-256-GCM encryption
-/// - `"lz4"`: LZ4 compression
+/// `"aes-gcm-256"` is recognized but returns [`CodecError::KeyRequired`];
+/// build an [`AesGcmCodec`] with a key instead.
 pub fn get_codec(codec_id: &str) -> Result<Box<dyn StorageCodec>, CodecError> {
     match codec_id {
         "identity" => Ok(Box::new(IdentityCodec)),
+        "zstd" => Ok(Box::new(ZstdCodec::default())),
+        AES_GCM_CODEC_ID => Err(CodecError::KeyRequired(codec_id.to_string())),
         _ => Err(CodecError::UnknownCodec(codec_id.to_string())),
     }
 }
 
+/// Get the on-disk codec byte for a codec identifier.
+pub fn codec_byte(codec_id: &str) -> Result<u8, CodecError> {
+    match codec_id {
+        "identity" => Ok(CODEC_BYTE_IDENTITY),
+        "zstd" => Ok(CODEC_BYTE_ZSTD),
+        AES_GCM_CODEC_ID => Ok(CODEC_BYTE_AES_GCM),
+        _ => Err(CodecError::UnknownCodec(codec_id.to_string())),
+    }
+}
+
+/// Get the codec identifier for an on-disk codec byte.
+pub fn codec_id_for_byte(byte: u8) -> Result<&'static str, CodecError> {
+    match byte {
+        CODEC_BYTE_IDENTITY => Ok("identity"),
+        CODEC_BYTE_ZSTD => Ok("zstd"),
+        CODEC_BYTE_AES_GCM => Ok(AES_GCM_CODEC_ID),
+        other => Err(CodecError::UnknownCodec(format!("codec byte {}", other))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,6 +110,28 @@ mod tests {
         assert_eq!(codec.codec_id(), "identity");
     }
 
+    #[test]
+    fn test_get_zstd_codec() {
+        let codec = get_codec("zstd").unwrap();
+        assert_eq!(codec.codec_id(), "zstd");
+    }
+
+    #[test]
+    fn test_codec_byte_round_trip() {
+        for id in ["identity", "zstd", AES_GCM_CODEC_ID] {
+            let byte = codec_byte(id).unwrap();
+            assert_eq!(codec_id_for_byte(byte).unwrap(), id);
+        }
+        assert!(codec_byte("unknown").is_err());
+        assert!(codec_id_for_byte(200).is_err());
+    }
+
+    #[test]
+    fn test_get_aes_gcm_codec_requires_key() {
+        let result = get_codec(AES_GCM_CODEC_ID);
+        assert!(matches!(result, Err(CodecError::KeyRequired(_))));
+    }
+
     #[test]
     fn test_get_unknown_codec() {
         let result = get_codec("unknown");
@@ -22,6 +22,22 @@ pub trait StorageCodec: Send + Sync {
     /// For IdentityCodec, this is a no-op.
     fn encode(&self, data: &[u8]) -> Vec<u8>;
 
+    /// Encode bytes for storage, appending them to `out`.
+    ///
+    /// Lets callers reuse one buffer across many encodes. The default
+    /// appends the result of [`encode`](Self::encode).
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.encode(data));
+    }
+
+    /// Whether `encode` returns its input unchanged.
+    ///
+    /// When true, callers may store the input directly and skip encoding.
+    /// Defaults to false.
+    fn is_identity(&self) -> bool {
+        false
+    }
+
     /// Decode bytes from storage.
     ///
     /// Reverses the encode operation. Returns an error if the data
@@ -35,6 +51,28 @@ pub trait StorageCodec: Send + Sync {
     fn codec_id(&self) -> &str;
 }
 
+impl<C: StorageCodec + ?Sized> StorageCodec for std::sync::Arc<C> {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        (**self).encode(data)
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        (**self).encode_into(data, out)
+    }
+
+    fn is_identity(&self) -> bool {
+        (**self).is_identity()
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        (**self).decode(data)
+    }
+
+    fn codec_id(&self) -> &str {
+        (**self).codec_id()
+    }
+}
+
 /// Codec errors.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum CodecError {
@@ -56,6 +94,18 @@ pub enum CodecError {
     #[error("Unknown codec: {0}")]
     UnknownCodec(String),
 
+    /// The codec needs an encryption key that was not provided.
+    #[error("Data is encrypted with codec '{0}' but no encryption key was provided")]
+    KeyRequired(String),
+
+    /// A compression dictionary could not be trained.
+    #[error("Dictionary training failed: {0}")]
+    DictionaryTraining(String),
+
+    /// An encryption key or key provider is invalid.
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
     /// Codec mismatch (database was created with different codec).
     #[error("Codec mismatch: expected {expected}, got {actual}")]
     CodecMismatch {
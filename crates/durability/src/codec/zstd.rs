@@ -0,0 +1,307 @@
+//! Zstd compression codec.
+//!
+//! Payloads at or above a size threshold are zstd-compressed; smaller
+//! payloads (and payloads that do not shrink) are stored as-is. Every
+//! encoded buffer starts with a one-byte tag, so decoding never depends on
+//! the threshold that was in effect when the data was written.
+//!
+//! With a [`CompressionDictionary`], payloads of any size are compressed
+//! against the dictionary. Such payloads carry the dictionary id after the
+//! tag, and decoding them requires a codec holding the same dictionary.
+
+use std::io::Read;
+use std::sync::Arc;
+
+use super::dictionary::CompressionDictionary;
+use super::traits::{CodecError, StorageCodec};
+
+/// Tag byte: payload stored uncompressed.
+const TAG_RAW: u8 = 0;
+
+/// Tag byte: payload is a zstd frame.
+const TAG_ZSTD: u8 = 1;
+
+/// Tag byte: payload is a dictionary id (u32 LE) followed by a zstd frame
+/// compressed with that dictionary.
+const TAG_ZSTD_DICT: u8 = 2;
+
+/// Default zstd compression level.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Default minimum payload size (bytes) before compression is attempted.
+pub const DEFAULT_ZSTD_THRESHOLD: usize = 512;
+
+/// Zstd codec - compresses payloads above a threshold.
+///
+/// # Example
+///
+/// ```
+/// use strata_durability::codec::{StorageCodec, ZstdCodec};
+///
+/// let codec = ZstdCodec::default();
+/// let data = vec![b'a'; 4096];
+///
+/// let encoded = codec.encode(&data);
+/// assert!(encoded.len() < data.len());
+///
+/// let decoded = codec.decode(&encoded).unwrap();
+/// assert_eq!(data, decoded);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZstdCodec {
+    level: i32,
+    threshold: usize,
+    dictionary: Option<Arc<CompressionDictionary>>,
+}
+
+impl ZstdCodec {
+    /// Create a zstd codec with an explicit level and size threshold.
+    pub fn new(level: i32, threshold: usize) -> Self {
+        ZstdCodec {
+            level,
+            threshold,
+            dictionary: None,
+        }
+    }
+
+    /// Compress every payload against `dictionary`, ignoring the threshold.
+    ///
+    /// Payloads written without a dictionary stay readable.
+    pub fn with_dictionary(mut self, dictionary: Arc<CompressionDictionary>) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Dictionary used for compression, if any.
+    pub fn dictionary(&self) -> Option<&CompressionDictionary> {
+        self.dictionary.as_deref()
+    }
+
+    /// Compression level.
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    /// Minimum payload size before compression is attempted.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl Default for ZstdCodec {
+    fn default() -> Self {
+        Self::new(DEFAULT_ZSTD_LEVEL, DEFAULT_ZSTD_THRESHOLD)
+    }
+}
+
+impl StorageCodec for ZstdCodec {
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(data, &mut out);
+        out
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut Vec<u8>) {
+        if let Some(dict) = &self.dictionary {
+            let compressed = zstd::bulk::Compressor::with_dictionary(self.level, dict.as_bytes())
+                .and_then(|mut c| c.compress(data));
+            if let Ok(compressed) = compressed {
+                if 5 + compressed.len() < 1 + data.len() {
+                    out.reserve(5 + compressed.len());
+                    out.push(TAG_ZSTD_DICT);
+                    out.extend_from_slice(&dict.id().to_le_bytes());
+                    out.extend_from_slice(&compressed);
+                    return;
+                }
+            }
+        } else if data.len() >= self.threshold {
+            if let Ok(compressed) = zstd::bulk::compress(data, self.level) {
+                if compressed.len() < data.len() {
+                    out.reserve(1 + compressed.len());
+                    out.push(TAG_ZSTD);
+                    out.extend_from_slice(&compressed);
+                    return;
+                }
+            }
+        }
+
+        out.reserve(1 + data.len());
+        out.push(TAG_RAW);
+        out.extend_from_slice(data);
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+        match data.split_first() {
+            Some((&TAG_RAW, rest)) => Ok(rest.to_vec()),
+            Some((&TAG_ZSTD, rest)) => zstd::stream::decode_all(rest)
+                .map_err(|e| CodecError::decode(e.to_string(), self.codec_id(), data.len())),
+            Some((&TAG_ZSTD_DICT, rest)) if rest.len() >= 4 => {
+                let id = u32::from_le_bytes(rest[..4].try_into().unwrap());
+                let dict = match &self.dictionary {
+                    Some(dict) if dict.id() == id => dict,
+                    _ => {
+                        return Err(CodecError::decode(
+                            format!("payload needs compression dictionary {:08x}", id),
+                            self.codec_id(),
+                            data.len(),
+                        ))
+                    }
+                };
+                let mut out = Vec::new();
+                zstd::stream::read::Decoder::with_dictionary(&rest[4..], dict.as_bytes())
+                    .and_then(|mut d| d.read_to_end(&mut out))
+                    .map_err(|e| CodecError::decode(e.to_string(), self.codec_id(), data.len()))?;
+                Ok(out)
+            }
+            Some((tag, _)) => Err(CodecError::decode(
+                format!("unknown payload tag {}", tag),
+                self.codec_id(),
+                data.len(),
+            )),
+            None => Err(CodecError::decode(
+                "empty payload",
+                self.codec_id(),
+                data.len(),
+            )),
+        }
+    }
+
+    fn codec_id(&self) -> &str {
+        "zstd"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zstd_small_payload_stored_raw() {
+        let codec = ZstdCodec::default();
+        let data = vec![1, 2, 3];
+        let encoded = codec.encode(&data);
+        assert_eq!(encoded[0], TAG_RAW);
+        assert_eq!(&encoded[1..], data.as_slice());
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zstd_large_payload_compressed() {
+        let codec = ZstdCodec::default();
+        let data = br#"{"field":"value"}"#.repeat(200);
+        let encoded = codec.encode(&data);
+        assert_eq!(encoded[0], TAG_ZSTD);
+        assert!(encoded.len() < data.len() / 4);
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zstd_incompressible_payload_stored_raw() {
+        let codec = ZstdCodec::new(DEFAULT_ZSTD_LEVEL, 0);
+        // Pseudo-random bytes do not compress
+        let mut x = 0x2545_F491_4F6C_DD1Du64;
+        let data: Vec<u8> = (0..1024)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect();
+        let encoded = codec.encode(&data);
+        assert_eq!(encoded[0], TAG_RAW);
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zstd_encode_into_appends() {
+        let codec = ZstdCodec::default();
+        for data in [b"small".to_vec(), b"compress me ".repeat(100)] {
+            let mut out = b"prefix".to_vec();
+            codec.encode_into(&data, &mut out);
+            assert_eq!(&out[..6], b"prefix");
+            assert_eq!(&out[6..], codec.encode(&data).as_slice());
+            assert_eq!(codec.decode(&out[6..]).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_zstd_decode_independent_of_threshold() {
+        let data = vec![7u8; 4096];
+        let encoded = ZstdCodec::new(DEFAULT_ZSTD_LEVEL, 0).encode(&data);
+        let decoded = ZstdCodec::new(DEFAULT_ZSTD_LEVEL, usize::MAX)
+            .decode(&encoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_zstd_decode_rejects_garbage() {
+        let codec = ZstdCodec::default();
+        assert!(codec.decode(&[]).is_err());
+        assert!(codec.decode(&[9, 1, 2]).is_err());
+        assert!(codec.decode(&[TAG_ZSTD, 1, 2, 3]).is_err());
+    }
+
+    fn small_values(n: usize) -> Vec<Vec<u8>> {
+        (0..n)
+            .map(|i| {
+                format!(
+                    r#"{{"order_id":{},"state":"shipped","carrier":"ups","items":[{{"sku":"A-{}","qty":1}}]}}"#,
+                    i,
+                    i % 7
+                )
+                .into_bytes()
+            })
+            .collect()
+    }
+
+    fn trained_codec() -> ZstdCodec {
+        let dict = crate::codec::train_dictionary(
+            &small_values(500),
+            crate::codec::DEFAULT_DICTIONARY_SIZE,
+        )
+        .unwrap();
+        ZstdCodec::default().with_dictionary(Arc::new(dict))
+    }
+
+    #[test]
+    fn test_zstd_dictionary_compresses_small_values() {
+        let plain = ZstdCodec::default();
+        let codec = trained_codec();
+
+        let value = &small_values(1000)[750];
+        assert!(value.len() < DEFAULT_ZSTD_THRESHOLD);
+
+        let without = plain.encode(value);
+        let with = codec.encode(value);
+        assert_eq!(without[0], TAG_RAW);
+        assert_eq!(with[0], TAG_ZSTD_DICT);
+        assert!(with.len() * 2 < without.len());
+        assert_eq!(codec.decode(&with).unwrap(), *value);
+    }
+
+    #[test]
+    fn test_zstd_dictionary_reads_plain_payloads() {
+        let data = vec![7u8; 4096];
+        let encoded = ZstdCodec::default().encode(&data);
+        assert_eq!(trained_codec().decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_zstd_dictionary_payload_needs_dictionary() {
+        let encoded = trained_codec().encode(&small_values(1)[0]);
+        assert_eq!(encoded[0], TAG_ZSTD_DICT);
+
+        assert!(ZstdCodec::default().decode(&encoded).is_err());
+
+        let other = crate::codec::CompressionDictionary::from_bytes(b"unrelated".to_vec());
+        let codec = ZstdCodec::default().with_dictionary(Arc::new(other));
+        assert!(codec.decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_zstd_codec_id() {
+        assert_eq!(ZstdCodec::default().codec_id(), "zstd");
+    }
+}
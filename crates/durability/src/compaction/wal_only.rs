@@ -244,11 +244,7 @@ impl WalOnlyCompactor {
         }
 
         // Determine actual header size based on format version
-        let actual_header_size = if header.format_version >= 2 {
-            crate::format::SEGMENT_HEADER_SIZE_V2
-        } else {
-            SEGMENT_HEADER_SIZE
-        };
+        let actual_header_size = header.size();
 
         // Empty segment (just header) is considered covered
         if file_data.len() <= actual_header_size {
@@ -2,15 +2,36 @@
 //!
 //! Coordinates snapshot creation with watermark tracking.
 //! This is the main API for creating database checkpoints.
+//!
+//! # Incremental Snapshots
+//!
+//! Sections whose content is unchanged since the previous snapshot are
+//! written as references to the snapshot holding their data. Every
+//! `full_interval` checkpoints a full snapshot is written, so no snapshot
+//! references one more than `full_interval - 1` checkpoints older.
+//!
+//! # Compression Dictionaries
+//!
+//! Values inside sections can be compressed against per-primitive
+//! dictionaries (see [`CheckpointData::train_dictionaries`]). The
+//! dictionaries are not stored in the snapshot; callers persist them in the
+//! MANIFEST and must supply the same ones to deserialize the sections.
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use crate::codec::StorageCodec;
-use crate::disk_snapshot::{SnapshotSection, SnapshotWriter};
+use crate::codec::{train_dictionary, CompressionDictionary, StorageCodec};
+use crate::disk_snapshot::{SnapshotReader, SnapshotSection, SnapshotWriter};
 use crate::format::primitives::SnapshotSerializer;
-use crate::format::snapshot::primitive_tags;
+use crate::format::snapshot::{primitive_tags, snapshot_path};
 use crate::format::watermark::{CheckpointInfo, SnapshotWatermark};
 
+/// Default number of checkpoints between full snapshots
+pub const DEFAULT_FULL_SNAPSHOT_INTERVAL: u64 = 8;
+
+/// Maximum number of values sampled per primitive when training dictionaries
+pub const MAX_DICTIONARY_SAMPLES: usize = 4096;
+
 /// Checkpoint coordinator
 ///
 /// Manages the lifecycle of checkpoints:
@@ -22,6 +43,7 @@ pub struct CheckpointCoordinator {
     snapshot_writer: SnapshotWriter,
     serializer: SnapshotSerializer,
     watermark: SnapshotWatermark,
+    full_interval: u64,
 }
 
 impl CheckpointCoordinator {
@@ -39,6 +61,7 @@ impl CheckpointCoordinator {
             snapshot_writer,
             serializer,
             watermark: SnapshotWatermark::new(),
+            full_interval: DEFAULT_FULL_SNAPSHOT_INTERVAL,
         })
     }
 
@@ -57,9 +80,33 @@ impl CheckpointCoordinator {
             snapshot_writer,
             serializer,
             watermark,
+            full_interval: DEFAULT_FULL_SNAPSHOT_INTERVAL,
         })
     }
 
+    /// Set the number of checkpoints between full snapshots
+    ///
+    /// A value of 0 or 1 writes every snapshot in full.
+    pub fn with_full_interval(mut self, full_interval: u64) -> Self {
+        self.full_interval = full_interval;
+        self
+    }
+
+    /// Compress section values against per-primitive dictionaries
+    ///
+    /// `dictionaries` is keyed by primitive tag; primitives without one are
+    /// serialized as before.
+    pub fn with_dictionaries(mut self, dictionaries: &BTreeMap<u8, CompressionDictionary>) -> Self {
+        self.serializer = SnapshotSerializer::new(Box::new(crate::codec::IdentityCodec))
+            .with_dictionaries(dictionaries);
+        self
+    }
+
+    /// Get the number of checkpoints between full snapshots
+    pub fn full_interval(&self) -> u64 {
+        self.full_interval
+    }
+
     /// Get the current watermark state
     pub fn watermark(&self) -> &SnapshotWatermark {
         &self.watermark
@@ -75,8 +122,9 @@ impl CheckpointCoordinator {
     /// This is the main checkpoint API. It:
     /// 1. Determines the next snapshot ID
     /// 2. Serializes all provided primitive sections
-    /// 3. Creates a crash-safe snapshot
-    /// 4. Updates the watermark on success
+    /// 3. Replaces sections unchanged since the previous snapshot with references
+    /// 4. Creates a crash-safe snapshot
+    /// 5. Updates the watermark on success
     ///
     /// Returns `CheckpointInfo` on success.
     pub fn checkpoint(
@@ -131,6 +179,12 @@ impl CheckpointCoordinator {
             ));
         }
 
+        for (tag, section) in data.custom {
+            sections.push(SnapshotSection::new(tag, section));
+        }
+
+        self.reference_unchanged(snapshot_id, &mut sections);
+
         // Create the snapshot
         let snapshot_info = self
             .snapshot_writer
@@ -148,6 +202,49 @@ impl CheckpointCoordinator {
         ))
     }
 
+    /// Replace sections matching the previous snapshot with references
+    ///
+    /// Writes everything in full when the previous snapshot is unreadable or
+    /// when the chain it belongs to has reached `full_interval` checkpoints.
+    fn reference_unchanged(&self, snapshot_id: u64, sections: &mut [SnapshotSection]) {
+        if self.full_interval <= 1 {
+            return;
+        }
+        let Some(prev_id) = self.watermark.snapshot_id() else {
+            return;
+        };
+        let prev_path = snapshot_path(self.snapshot_writer.snapshots_dir(), prev_id);
+        let Ok(prev_table) = SnapshotReader::section_table(&prev_path) else {
+            return;
+        };
+
+        // Oldest snapshot the previous one depends on
+        let chain_start = prev_table
+            .iter()
+            .filter(|h| h.is_reference())
+            .map(|h| h.base_snapshot_id)
+            .fold(prev_id, u64::min);
+        if snapshot_id.saturating_sub(chain_start) >= self.full_interval {
+            return;
+        }
+
+        for section in sections.iter_mut() {
+            let unchanged = prev_table.iter().find(|h| {
+                h.primitive_type == section.primitive_type && h.content_hash == section.content_hash
+            });
+            if let Some(prev) = unchanged {
+                // Point at the snapshot physically holding the data
+                let base = if prev.is_reference() {
+                    prev.base_snapshot_id
+                } else {
+                    prev_id
+                };
+                *section =
+                    SnapshotSection::reference(section.primitive_type, section.content_hash, base);
+            }
+        }
+    }
+
     /// Clean up temporary files from failed checkpoints
     pub fn cleanup(&self) -> std::io::Result<usize> {
         self.snapshot_writer.cleanup_temp_files()
@@ -176,6 +273,8 @@ pub struct CheckpointData {
     pub json: Option<Vec<crate::format::primitives::JsonSnapshotEntry>>,
     /// Vector primitive entries
     pub vectors: Option<Vec<crate::format::primitives::VectorCollectionSnapshotEntry>>,
+    /// Custom primitive sections, already serialized, by section tag
+    pub custom: BTreeMap<u8, Vec<u8>>,
 }
 
 impl CheckpointData {
@@ -184,6 +283,40 @@ impl CheckpointData {
         CheckpointData::default()
     }
 
+    /// Train a compression dictionary per primitive from sampled values
+    ///
+    /// Up to [`MAX_DICTIONARY_SAMPLES`] values are sampled per primitive,
+    /// spread evenly over its entries. Primitives with too few values to
+    /// train on are left out of the result.
+    pub fn train_dictionaries(&self, max_size: usize) -> BTreeMap<u8, CompressionDictionary> {
+        fn sample<T>(entries: &Option<Vec<T>>, value: impl Fn(&T) -> &[u8]) -> Vec<&[u8]> {
+            let entries = entries.as_deref().unwrap_or_default();
+            let step =
+                ((entries.len() + MAX_DICTIONARY_SAMPLES - 1) / MAX_DICTIONARY_SAMPLES).max(1);
+            entries.iter().step_by(step).map(value).collect()
+        }
+
+        let samples = [
+            (primitive_tags::KV, sample(&self.kv, |e| &e.value)),
+            (primitive_tags::EVENT, sample(&self.events, |e| &e.payload)),
+            (primitive_tags::STATE, sample(&self.states, |e| &e.value)),
+            (
+                primitive_tags::BRANCH,
+                sample(&self.branches, |e| &e.metadata),
+            ),
+            (primitive_tags::JSON, sample(&self.json, |e| &e.content)),
+        ];
+
+        samples
+            .into_iter()
+            .filter_map(|(tag, values)| {
+                train_dictionary(&values, max_size)
+                    .ok()
+                    .map(|dict| (tag, dict))
+            })
+            .collect()
+    }
+
     /// Set KV entries
     pub fn with_kv(mut self, entries: Vec<crate::format::primitives::KvSnapshotEntry>) -> Self {
         self.kv = Some(entries);
@@ -231,6 +364,16 @@ impl CheckpointData {
         self.vectors = Some(entries);
         self
     }
+
+    /// Add a custom primitive section
+    ///
+    /// `tag` must be at or above [`primitive_tags::CUSTOM_START`] so it
+    /// cannot shadow a built-in section.
+    pub fn with_custom(mut self, tag: u8, section: Vec<u8>) -> Self {
+        debug_assert!(tag >= primitive_tags::CUSTOM_START);
+        self.custom.insert(tag, section);
+        self
+    }
 }
 
 /// Errors that can occur during checkpoint creation
@@ -363,6 +506,204 @@ mod tests {
         assert_eq!(info.snapshot_id, 1);
     }
 
+    #[test]
+    fn test_checkpoint_with_custom_section() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let data = kv_data(b"v").with_custom(0x80, b"custom state".to_vec());
+        coordinator.checkpoint(100, data).unwrap();
+
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let loaded = reader.load(&snapshot_path(temp_dir.path(), 1)).unwrap();
+        assert_eq!(loaded.find_section(0x80).unwrap().data, b"custom state");
+        assert!(loaded.find_section(primitive_tags::KV).is_some());
+    }
+
+    fn kv_data(value: &[u8]) -> CheckpointData {
+        CheckpointData::new()
+            .with_kv(vec![KvSnapshotEntry {
+                key: "k".to_string(),
+                value: value.to_vec(),
+                version: 1,
+                timestamp: 0,
+            }])
+            .with_events(vec![EventSnapshotEntry {
+                sequence: 1,
+                payload: vec![7; 256],
+                timestamp: 0,
+            }])
+    }
+
+    #[test]
+    fn test_incremental_checkpoint_references_unchanged_sections() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        coordinator.checkpoint(100, kv_data(b"v1")).unwrap();
+        coordinator.checkpoint(200, kv_data(b"v2")).unwrap();
+        coordinator.checkpoint(300, kv_data(b"v2")).unwrap();
+
+        // Events never changed, so both later snapshots point at snapshot 1
+        let table2 = SnapshotReader::section_table(&snapshot_path(temp_dir.path(), 2)).unwrap();
+        assert!(!table2[0].is_reference());
+        assert_eq!(table2[1].base_snapshot_id, 1);
+
+        let table3 = SnapshotReader::section_table(&snapshot_path(temp_dir.path(), 3)).unwrap();
+        assert_eq!(table3[0].base_snapshot_id, 2);
+        assert_eq!(table3[1].base_snapshot_id, 1);
+
+        let full_size = std::fs::metadata(snapshot_path(temp_dir.path(), 1))
+            .unwrap()
+            .len();
+        let incremental_size = std::fs::metadata(snapshot_path(temp_dir.path(), 3))
+            .unwrap()
+            .len();
+        assert!(incremental_size < full_size);
+
+        // Loading resolves references to the same data as a full snapshot
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let loaded2 = reader.load(&snapshot_path(temp_dir.path(), 2)).unwrap();
+        let loaded3 = reader.load(&snapshot_path(temp_dir.path(), 3)).unwrap();
+        for tag in [primitive_tags::KV, primitive_tags::EVENT] {
+            assert_eq!(
+                loaded3.find_section(tag).unwrap().data,
+                loaded2.find_section(tag).unwrap().data
+            );
+        }
+    }
+
+    fn json_kv_data(n: usize) -> CheckpointData {
+        CheckpointData::new().with_kv(
+            (0..n)
+                .map(|i| KvSnapshotEntry {
+                    key: format!("user:{}", i),
+                    value: format!(
+                        r#"{{"id":{},"name":"user-{}","role":"member","active":true}}"#,
+                        i, i
+                    )
+                    .into_bytes(),
+                    version: i as u64 + 1,
+                    timestamp: 0,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_train_dictionaries_skips_sparse_primitives() {
+        let data = json_kv_data(500).with_events(vec![EventSnapshotEntry {
+            sequence: 1,
+            payload: b"{}".to_vec(),
+            timestamp: 0,
+        }]);
+
+        let dictionaries = data.train_dictionaries(crate::codec::DEFAULT_DICTIONARY_SIZE);
+        assert_eq!(
+            dictionaries.keys().copied().collect::<Vec<_>>(),
+            vec![primitive_tags::KV]
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_with_dictionaries() {
+        let dictionaries =
+            json_kv_data(500).train_dictionaries(crate::codec::DEFAULT_DICTIONARY_SIZE);
+
+        let plain_dir = tempfile::tempdir().unwrap();
+        CheckpointCoordinator::new(
+            plain_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap()
+        .checkpoint(100, json_kv_data(500))
+        .unwrap();
+
+        let dict_dir = tempfile::tempdir().unwrap();
+        CheckpointCoordinator::new(
+            dict_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap()
+        .with_dictionaries(&dictionaries)
+        .checkpoint(100, json_kv_data(500))
+        .unwrap();
+
+        let plain_size = std::fs::metadata(snapshot_path(plain_dir.path(), 1))
+            .unwrap()
+            .len();
+        let dict_size = std::fs::metadata(snapshot_path(dict_dir.path(), 1))
+            .unwrap()
+            .len();
+        assert!(dict_size < plain_size);
+
+        // Values read back with the same dictionaries
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let loaded = reader.load(&snapshot_path(dict_dir.path(), 1)).unwrap();
+        let section = &loaded.find_section(primitive_tags::KV).unwrap().data;
+        let serializer =
+            SnapshotSerializer::new(Box::new(IdentityCodec)).with_dictionaries(&dictionaries);
+        assert_eq!(
+            Some(serializer.deserialize_kv(section).unwrap()),
+            json_kv_data(500).kv
+        );
+    }
+
+    #[test]
+    fn test_full_snapshot_every_interval() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap()
+        .with_full_interval(3);
+
+        for txn in 1..=7 {
+            coordinator.checkpoint(txn * 100, kv_data(b"same")).unwrap();
+        }
+
+        let is_full = |id: u64| {
+            SnapshotReader::section_table(&snapshot_path(temp_dir.path(), id))
+                .unwrap()
+                .iter()
+                .all(|h| !h.is_reference())
+        };
+        let fulls: Vec<u64> = (1..=7).filter(|&id| is_full(id)).collect();
+        assert_eq!(fulls, vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn test_full_interval_one_disables_references() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut coordinator = CheckpointCoordinator::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap()
+        .with_full_interval(1);
+
+        coordinator.checkpoint(100, kv_data(b"same")).unwrap();
+        coordinator.checkpoint(200, kv_data(b"same")).unwrap();
+
+        let table = SnapshotReader::section_table(&snapshot_path(temp_dir.path(), 2)).unwrap();
+        assert!(table.iter().all(|h| !h.is_reference()));
+    }
+
     #[test]
     fn test_watermark_coverage() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -31,6 +31,8 @@ pub mod checkpoint;
 pub mod reader;
 pub mod writer;
 
-pub use checkpoint::{CheckpointCoordinator, CheckpointData, CheckpointError};
+pub use checkpoint::{
+    CheckpointCoordinator, CheckpointData, CheckpointError, DEFAULT_FULL_SNAPSHOT_INTERVAL,
+};
 pub use reader::{LoadedSection, LoadedSnapshot, SnapshotReadError, SnapshotReader};
 pub use writer::{SnapshotInfo, SnapshotSection, SnapshotWriter};
@@ -2,13 +2,14 @@
 //!
 //! Loads and validates snapshot files for database recovery.
 
-use std::fs::File;
-use std::io::{BufReader, Read};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::path::Path;
 
-use crate::codec::{CodecError, StorageCodec};
+use crate::codec::{get_codec, CodecError, StorageCodec};
 use crate::format::snapshot::{
-    primitive_tags, SectionHeader, SnapshotHeader, SNAPSHOT_HEADER_SIZE, SNAPSHOT_MAGIC,
+    primitive_tags, snapshot_path, SectionHeader, SnapshotHeader, SNAPSHOT_HEADER_SIZE,
+    SNAPSHOT_MAGIC,
 };
 
 /// Snapshot reader for recovery
@@ -16,31 +17,29 @@ pub struct SnapshotReader {
     codec: Box<dyn StorageCodec>,
 }
 
-impl SnapshotReader {
-    /// Create a new snapshot reader with the given codec
-    pub fn new(codec: Box<dyn StorageCodec>) -> Self {
-        SnapshotReader { codec }
-    }
+/// Snapshot file contents after header parsing, before CRC validation
+struct RawSnapshot {
+    header: SnapshotHeader,
+    header_bytes: [u8; SNAPSHOT_HEADER_SIZE],
+    codec_id: String,
+    /// Section headers and data, without the footer CRC
+    body: Vec<u8>,
+    stored_crc: u32,
+}
 
-    /// Load a snapshot from file
-    ///
-    /// Validates magic bytes, format version, and codec ID.
-    /// Returns the loaded snapshot data with all sections.
-    pub fn load(&self, path: &Path) -> Result<LoadedSnapshot, SnapshotReadError> {
-        let file = File::open(path)?;
-        let metadata = file.metadata()?;
-        let file_size = metadata.len() as usize;
+impl RawSnapshot {
+    /// Parse the header and codec ID and split off the footer CRC
+    fn parse(mut bytes: Vec<u8>) -> Result<Self, SnapshotReadError> {
+        let file_size = bytes.len();
 
         // Minimum size: header + codec ID (at least 1 byte) + CRC32 (4 bytes)
         if file_size < SNAPSHOT_HEADER_SIZE + 1 + 4 {
             return Err(SnapshotReadError::FileTooSmall { size: file_size });
         }
 
-        let mut reader = BufReader::new(file);
-
         // Read and validate header
-        let mut header_bytes = [0u8; SNAPSHOT_HEADER_SIZE];
-        reader.read_exact(&mut header_bytes)?;
+        let header_bytes: [u8; SNAPSHOT_HEADER_SIZE] =
+            bytes[..SNAPSHOT_HEADER_SIZE].try_into().unwrap();
 
         let header =
             SnapshotHeader::from_bytes(&header_bytes).ok_or(SnapshotReadError::InvalidHeader)?;
@@ -58,90 +57,76 @@ impl SnapshotReader {
             .validate()
             .map_err(|e| SnapshotReadError::HeaderValidation(e.to_string()))?;
 
-        // Read codec ID
-        let codec_id_len = header.codec_id_len as usize;
-        let mut codec_id_bytes = vec![0u8; codec_id_len];
-        reader.read_exact(&mut codec_id_bytes)?;
-        let codec_id =
-            String::from_utf8(codec_id_bytes).map_err(|_| SnapshotReadError::InvalidCodecId)?;
-
-        // Validate codec ID matches
-        if codec_id != self.codec.codec_id() {
-            return Err(SnapshotReadError::CodecMismatch {
-                expected: codec_id.clone(),
-                actual: self.codec.codec_id().to_string(),
-            });
-        }
-
-        // Read all remaining data for CRC validation
-        let mut remaining_data = Vec::new();
-        reader.read_to_end(&mut remaining_data)?;
-
-        // Validate CRC (last 4 bytes)
-        if remaining_data.len() < 4 {
+        // Codec ID, then the body with the CRC (last 4 bytes) split off
+        let codec_end = SNAPSHOT_HEADER_SIZE + header.codec_id_len as usize;
+        if file_size < codec_end + 4 {
             return Err(SnapshotReadError::FileTooSmall { size: file_size });
         }
+        let crc_bytes = bytes.split_off(file_size - 4);
+        let stored_crc = u32::from_le_bytes(crc_bytes.try_into().unwrap());
+        let body = bytes.split_off(codec_end);
+        let codec_id = String::from_utf8(bytes.split_off(SNAPSHOT_HEADER_SIZE))
+            .map_err(|_| SnapshotReadError::InvalidCodecId)?;
 
-        let stored_crc = u32::from_le_bytes(
-            remaining_data[remaining_data.len() - 4..]
-                .try_into()
-                .unwrap(),
-        );
+        Ok(RawSnapshot {
+            header,
+            header_bytes,
+            codec_id,
+            body,
+            stored_crc,
+        })
+    }
 
+    fn validate_crc(&self) -> Result<(), SnapshotReadError> {
         // Compute CRC of header + codec_id + sections
         let mut hasher = crc32fast::Hasher::new();
-        hasher.update(&header_bytes);
-        hasher.update(codec_id.as_bytes());
-        hasher.update(&remaining_data[..remaining_data.len() - 4]);
+        hasher.update(&self.header_bytes);
+        hasher.update(self.codec_id.as_bytes());
+        hasher.update(&self.body);
         let computed_crc = hasher.finalize();
 
-        if stored_crc != computed_crc {
+        if self.stored_crc != computed_crc {
             return Err(SnapshotReadError::CrcMismatch {
-                stored: stored_crc,
+                stored: self.stored_crc,
                 computed: computed_crc,
             });
         }
-
-        // Parse sections
-        let sections = self.parse_sections(&remaining_data[..remaining_data.len() - 4])?;
-
-        Ok(LoadedSnapshot {
-            header,
-            codec_id,
-            sections,
-            crc: stored_crc,
-        })
+        Ok(())
     }
 
-    /// Parse sections from the data blob
-    fn parse_sections(&self, data: &[u8]) -> Result<Vec<LoadedSection>, SnapshotReadError> {
+    /// Split the body into section headers and the offsets of their data
+    fn section_table(&self) -> Result<Vec<(SectionHeader, usize)>, SnapshotReadError> {
+        let data = &self.body;
+        let header_size = SectionHeader::size_for_version(self.header.format_version);
         let mut sections = Vec::new();
         let mut cursor = 0;
 
         while cursor < data.len() {
             // Check if we have enough bytes for section header
-            if cursor + SectionHeader::SIZE > data.len() {
+            if cursor + header_size > data.len() {
                 // Might be at the end with no more sections
                 break;
             }
 
-            let section_header_bytes: [u8; SectionHeader::SIZE] = data
-                [cursor..cursor + SectionHeader::SIZE]
-                .try_into()
-                .unwrap();
-            let section_header = SectionHeader::from_bytes(&section_header_bytes);
-            cursor += SectionHeader::SIZE;
+            let header_bytes = &data[cursor..cursor + header_size];
+            let section_header = if self.header.format_version >= 2 {
+                SectionHeader::from_bytes_v2(header_bytes.try_into().unwrap())
+            } else {
+                SectionHeader::from_bytes(header_bytes.try_into().unwrap())
+            };
+            cursor += header_size;
 
             // Validate primitive type
-            if !primitive_tags::ALL_TAGS.contains(&section_header.primitive_type) {
+            if !primitive_tags::is_valid(section_header.primitive_type) {
                 return Err(SnapshotReadError::InvalidPrimitiveType {
                     tag: section_header.primitive_type,
                 });
             }
 
-            // Check if we have enough data for the section
-            let data_len = section_header.data_len as usize;
-            if cursor + data_len > data.len() {
+            // Check if we have enough data for the section. The length is
+            // untrusted, so compare against what is left rather than adding
+            let data_len = usize::try_from(section_header.data_len).unwrap_or(usize::MAX);
+            if data_len > data.len() - cursor {
                 return Err(SnapshotReadError::SectionDataTruncated {
                     primitive_type: section_header.primitive_type,
                     expected: data_len,
@@ -149,16 +134,147 @@ impl SnapshotReader {
                 });
             }
 
-            let section_data = data[cursor..cursor + data_len].to_vec();
+            sections.push((section_header, cursor));
             cursor += data_len;
+        }
+
+        Ok(sections)
+    }
+}
+
+impl SnapshotReader {
+    /// Create a new snapshot reader with the given codec
+    pub fn new(codec: Box<dyn StorageCodec>) -> Self {
+        SnapshotReader { codec }
+    }
+
+    /// Load a snapshot from file
+    ///
+    /// Validates magic bytes, format version, and codec ID.
+    /// Returns the loaded snapshot data with all sections, decoded.
+    ///
+    /// A snapshot written with a different known codec is decoded with that
+    /// codec, so changing compression settings does not strand old snapshots.
+    ///
+    /// Sections that reference an earlier snapshot are resolved by reading
+    /// that snapshot from the same directory, so callers always see full data.
+    pub fn load(&self, path: &Path) -> Result<LoadedSnapshot, SnapshotReadError> {
+        let bytes = std::fs::read(path)?;
+        let snapshots_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        self.decode(bytes, snapshots_dir)
+    }
 
+    /// Decode snapshot file contents already read into memory
+    ///
+    /// Same validation as [`load`](Self::load). Reference sections are
+    /// resolved against snapshots in `snapshots_dir`.
+    pub fn decode(
+        &self,
+        bytes: Vec<u8>,
+        snapshots_dir: &Path,
+    ) -> Result<LoadedSnapshot, SnapshotReadError> {
+        let raw = RawSnapshot::parse(bytes)?;
+        let file_codec = self.file_codec(&raw.codec_id)?;
+        let codec = file_codec.as_deref().unwrap_or(self.codec.as_ref());
+        raw.validate_crc()?;
+
+        let mut bases: HashMap<u64, RawSnapshot> = HashMap::new();
+        let mut sections = Vec::new();
+
+        for (section_header, offset) in raw.section_table()? {
+            let data = if section_header.is_reference() {
+                self.resolve_reference(snapshots_dir, &section_header, &mut bases)?
+            } else {
+                let end = offset + section_header.data_len as usize;
+                codec.decode(&raw.body[offset..end])?
+            };
             sections.push(LoadedSection {
                 primitive_type: section_header.primitive_type,
-                data: section_data,
+                data,
             });
         }
 
-        Ok(sections)
+        Ok(LoadedSnapshot {
+            header: raw.header,
+            codec_id: raw.codec_id,
+            sections,
+            crc: raw.stored_crc,
+        })
+    }
+
+    /// Read the section headers of a snapshot without decoding any data
+    ///
+    /// Validates the header and CRC. Used by checkpointing to find sections
+    /// that are unchanged since the previous snapshot.
+    pub fn section_table(path: &Path) -> Result<Vec<SectionHeader>, SnapshotReadError> {
+        let raw = Self::read_raw(path)?;
+        raw.validate_crc()?;
+        Ok(raw.section_table()?.into_iter().map(|(h, _)| h).collect())
+    }
+
+    /// Read and validate everything up to the sections, keeping the body raw
+    fn read_raw(path: &Path) -> Result<RawSnapshot, SnapshotReadError> {
+        RawSnapshot::parse(std::fs::read(path)?)
+    }
+
+    /// Resolve the codec a snapshot was written with
+    ///
+    /// Returns `None` when it matches this reader's codec.
+    fn file_codec(
+        &self,
+        codec_id: &str,
+    ) -> Result<Option<Box<dyn StorageCodec>>, SnapshotReadError> {
+        if codec_id == self.codec.codec_id() {
+            return Ok(None);
+        }
+        get_codec(codec_id)
+            .map(Some)
+            .map_err(|_| SnapshotReadError::CodecMismatch {
+                expected: codec_id.to_string(),
+                actual: self.codec.codec_id().to_string(),
+            })
+    }
+
+    /// Load the data of a reference section from the snapshot holding it
+    fn resolve_reference(
+        &self,
+        snapshots_dir: &Path,
+        reference: &SectionHeader,
+        bases: &mut HashMap<u64, RawSnapshot>,
+    ) -> Result<Vec<u8>, SnapshotReadError> {
+        let base_id = reference.base_snapshot_id;
+        let base = match bases.entry(base_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let base_path = snapshot_path(snapshots_dir, base_id);
+                if !base_path.exists() {
+                    return Err(SnapshotReadError::MissingBaseSnapshot {
+                        snapshot_id: base_id,
+                    });
+                }
+                let base = Self::read_raw(&base_path)?;
+                base.validate_crc()?;
+                entry.insert(base)
+            }
+        };
+
+        let (section_header, offset) = base
+            .section_table()?
+            .into_iter()
+            .find(|(h, _)| {
+                !h.is_reference()
+                    && h.primitive_type == reference.primitive_type
+                    && h.content_hash == reference.content_hash
+            })
+            .ok_or(SnapshotReadError::BaseSectionMissing {
+                snapshot_id: base_id,
+                primitive_type: reference.primitive_type,
+            })?;
+
+        let file_codec = self.file_codec(&base.codec_id)?;
+        let codec = file_codec.as_deref().unwrap_or(self.codec.as_ref());
+        let end = offset + section_header.data_len as usize;
+        Ok(codec.decode(&base.body[offset..end])?)
     }
 
     /// Get the codec used by this reader
@@ -288,6 +404,20 @@ pub enum SnapshotReadError {
         /// Available data length
         available: usize,
     },
+    /// A reference section names a snapshot that no longer exists
+    #[error("Base snapshot {snapshot_id} referenced by this snapshot is missing")]
+    MissingBaseSnapshot {
+        /// Referenced snapshot ID
+        snapshot_id: u64,
+    },
+    /// The referenced snapshot does not hold the expected section
+    #[error("Base snapshot {snapshot_id} has no matching section for primitive {primitive_type}")]
+    BaseSectionMissing {
+        /// Referenced snapshot ID
+        snapshot_id: u64,
+        /// Primitive type of the reference
+        primitive_type: u8,
+    },
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -335,6 +465,44 @@ mod tests {
         assert_eq!(loaded.database_uuid(), test_uuid());
     }
 
+    #[test]
+    fn test_load_zstd_snapshot() {
+        use crate::codec::ZstdCodec;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let kv_data = b"key:value;".repeat(500);
+
+        let writer = SnapshotWriter::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(ZstdCodec::default()),
+            test_uuid(),
+        )
+        .unwrap();
+        let sections = vec![
+            SnapshotSection::new(primitive_tags::KV, kv_data.clone()),
+            SnapshotSection::new(primitive_tags::EVENT, b"small".to_vec()),
+        ];
+        let info = writer.create_snapshot(1, 100, sections).unwrap();
+        assert!((std::fs::metadata(&info.path).unwrap().len() as usize) < kv_data.len());
+
+        // Readers decode with the snapshot's codec, whatever their own is
+        for reader in [
+            SnapshotReader::new(Box::new(ZstdCodec::default())),
+            SnapshotReader::new(Box::new(IdentityCodec)),
+        ] {
+            let loaded = reader.load(&info.path).unwrap();
+            assert_eq!(loaded.codec_id, "zstd");
+            assert_eq!(
+                loaded.find_section(primitive_tags::KV).unwrap().data,
+                kv_data
+            );
+            assert_eq!(
+                loaded.find_section(primitive_tags::EVENT).unwrap().data,
+                b"small"
+            );
+        }
+    }
+
     #[test]
     fn test_load_all_sections() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -394,11 +562,11 @@ mod tests {
         // Document the file structure:
         // - Header: bytes 0-63 (64 bytes)
         // - Codec ID "identity": bytes 64-71 (8 bytes)
-        // - Section header: bytes 72-80 (9 bytes)
-        // - Section data: bytes 81-84 (4 bytes)
-        // - CRC: bytes 85-88 (4 bytes)
-        // Total: 89 bytes
-        assert_eq!(data.len(), 89, "Expected file size");
+        // - Section header: bytes 72-104 (33 bytes)
+        // - Section data: bytes 105-108 (4 bytes)
+        // - CRC: bytes 109-112 (4 bytes)
+        // Total: 113 bytes
+        assert_eq!(data.len(), 113, "Expected file size");
 
         // Verify structure
         assert_eq!(&data[0..4], b"SNAP", "Magic bytes");
@@ -448,9 +616,9 @@ mod tests {
 
         let mut data = std::fs::read(&info.path).unwrap();
 
-        // Corrupt section data area (bytes 81-84), not the CRC (bytes 85-88)
+        // Corrupt section data area (bytes 105-108), not the CRC (bytes 109-112)
         // This should trigger CRC mismatch
-        data[82] ^= 0xFF;
+        data[106] ^= 0xFF;
         std::fs::write(&info.path, &data).unwrap();
 
         let reader = SnapshotReader::new(Box::new(IdentityCodec));
@@ -495,6 +663,121 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_resolves_reference_sections() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let writer = SnapshotWriter::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let kv = SnapshotSection::new(primitive_tags::KV, b"unchanged".to_vec());
+        let kv_hash = kv.content_hash;
+        writer
+            .create_snapshot(
+                1,
+                100,
+                vec![
+                    kv,
+                    SnapshotSection::new(primitive_tags::EVENT, b"old".to_vec()),
+                ],
+            )
+            .unwrap();
+
+        let info = writer
+            .create_snapshot(
+                2,
+                200,
+                vec![
+                    SnapshotSection::reference(primitive_tags::KV, kv_hash, 1),
+                    SnapshotSection::new(primitive_tags::EVENT, b"new".to_vec()),
+                ],
+            )
+            .unwrap();
+
+        let table = SnapshotReader::section_table(&info.path).unwrap();
+        assert!(table[0].is_reference());
+        assert_eq!(table[0].base_snapshot_id, 1);
+        assert_eq!(table[0].data_len, 0);
+        assert!(!table[1].is_reference());
+
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let loaded = reader.load(&info.path).unwrap();
+        assert_eq!(
+            loaded.find_section(primitive_tags::KV).unwrap().data,
+            b"unchanged"
+        );
+        assert_eq!(
+            loaded.find_section(primitive_tags::EVENT).unwrap().data,
+            b"new"
+        );
+    }
+
+    #[test]
+    fn test_load_reference_to_missing_base() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let writer = SnapshotWriter::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let info = writer
+            .create_snapshot(
+                2,
+                200,
+                vec![SnapshotSection::reference(primitive_tags::KV, 42, 1)],
+            )
+            .unwrap();
+
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        assert!(matches!(
+            reader.load(&info.path),
+            Err(SnapshotReadError::MissingBaseSnapshot { snapshot_id: 1 })
+        ));
+
+        // A base that exists but holds different content is also an error
+        writer
+            .create_snapshot(
+                1,
+                100,
+                vec![SnapshotSection::new(primitive_tags::KV, b"other".to_vec())],
+            )
+            .unwrap();
+        assert!(matches!(
+            reader.load(&info.path),
+            Err(SnapshotReadError::BaseSectionMissing { snapshot_id: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_format_v1_snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("snap-000001.chk");
+
+        // Hand-build a version 1 file: 9-byte section headers, no hashes
+        let mut header = SnapshotHeader::new(1, 100, 0, test_uuid(), 8);
+        header.format_version = 1;
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend_from_slice(b"identity");
+        bytes.extend_from_slice(&SectionHeader::new(primitive_tags::KV, 4).to_bytes());
+        bytes.extend_from_slice(b"data");
+        let crc = crc32fast::hash(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let loaded = reader.load(&path).unwrap();
+        assert_eq!(loaded.header.format_version, 1);
+        assert_eq!(
+            loaded.find_section(primitive_tags::KV).unwrap().data,
+            b"data"
+        );
+    }
+
     #[test]
     fn test_invalid_magic() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -531,6 +814,38 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_section_length_past_end_of_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let writer = SnapshotWriter::new(
+            temp_dir.path().to_path_buf(),
+            Box::new(IdentityCodec),
+            test_uuid(),
+        )
+        .unwrap();
+
+        let sections = vec![SnapshotSection::new(primitive_tags::KV, vec![0, 0, 0, 0])];
+        let info = writer.create_snapshot(1, 100, sections).unwrap();
+
+        // Section length u64::MAX (bytes 73-80, after the "identity" codec
+        // ID), with the CRC fixed up so parsing reaches the section table
+        let mut data = std::fs::read(&info.path).unwrap();
+        data[73..81].copy_from_slice(&u64::MAX.to_le_bytes());
+        let body = data.len() - 4;
+        let crc = crc32fast::hash(&data[..body]);
+        data[body..].copy_from_slice(&crc.to_le_bytes());
+
+        let reader = SnapshotReader::new(Box::new(IdentityCodec));
+        let result = reader.decode(data, temp_dir.path());
+
+        assert!(
+            matches!(result, Err(SnapshotReadError::SectionDataTruncated { .. })),
+            "Expected SectionDataTruncated, got: {:?}",
+            result
+        );
+    }
+
     #[test]
     fn test_section_types() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -18,7 +18,7 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 
 use crate::codec::StorageCodec;
-use crate::format::snapshot::{snapshot_path, SectionHeader, SnapshotHeader};
+use crate::format::snapshot::{section_content_hash, snapshot_path, SectionHeader, SnapshotHeader};
 
 #[cfg(test)]
 use crate::format::snapshot::SNAPSHOT_FORMAT_VERSION;
@@ -102,16 +102,30 @@ impl SnapshotWriter {
         let mut all_bytes = header.to_bytes().to_vec();
         all_bytes.extend_from_slice(codec_id.as_bytes());
 
-        // Write sections
+        // Write sections (section data is encoded through the codec).
+        // Reference sections carry only a header naming the base snapshot.
         for section in &sections {
-            let section_header =
-                SectionHeader::new(section.primitive_type, section.data.len() as u64);
-            let section_header_bytes = section_header.to_bytes();
+            let (section_header, data) = match section.base_snapshot_id {
+                Some(base) => (
+                    SectionHeader::reference(section.primitive_type, section.content_hash, base),
+                    Vec::new(),
+                ),
+                None => {
+                    let data = self.codec.encode(&section.data);
+                    let header = SectionHeader::full(
+                        section.primitive_type,
+                        data.len() as u64,
+                        section.content_hash,
+                    );
+                    (header, data)
+                }
+            };
+            let section_header_bytes = section_header.to_bytes_v2();
             file.write_all(&section_header_bytes)?;
-            file.write_all(&section.data)?;
+            file.write_all(&data)?;
 
             all_bytes.extend_from_slice(&section_header_bytes);
-            all_bytes.extend_from_slice(&section.data);
+            all_bytes.extend_from_slice(&data);
         }
 
         // Write footer CRC32
@@ -177,8 +191,12 @@ impl SnapshotWriter {
 pub struct SnapshotSection {
     /// Primitive type tag (from primitive_tags module)
     pub primitive_type: u8,
-    /// Serialized section data
+    /// Serialized section data (empty for a reference)
     pub data: Vec<u8>,
+    /// Hash of the serialized section data
+    pub content_hash: u128,
+    /// Snapshot holding the data, if this section is a reference
+    pub base_snapshot_id: Option<u64>,
 }
 
 impl SnapshotSection {
@@ -186,9 +204,26 @@ impl SnapshotSection {
     pub fn new(primitive_type: u8, data: Vec<u8>) -> Self {
         SnapshotSection {
             primitive_type,
+            content_hash: section_content_hash(&data),
             data,
+            base_snapshot_id: None,
         }
     }
+
+    /// Create a section referencing unchanged data held by `base_snapshot_id`
+    pub fn reference(primitive_type: u8, content_hash: u128, base_snapshot_id: u64) -> Self {
+        SnapshotSection {
+            primitive_type,
+            data: Vec::new(),
+            content_hash,
+            base_snapshot_id: Some(base_snapshot_id),
+        }
+    }
+
+    /// Whether this section is a reference to an earlier snapshot
+    pub fn is_reference(&self) -> bool {
+        self.base_snapshot_id.is_some()
+    }
 }
 
 /// Information about a created snapshot
@@ -15,19 +15,28 @@
 //! | Active WAL Seg   | 8 bytes (u64 LE)
 //! | Snapshot Watermark | 8 bytes (u64 LE, 0 = none)
 //! | Snapshot ID      | 8 bytes (u64 LE, 0 = none)
+//! | Dictionary Count | 4 bytes (u32 LE, version 2+)
+//! | Dictionaries     | variable (version 2+)
 //! | CRC32            | 4 bytes
 //! +------------------+
 //! ```
+//!
+//! Each dictionary entry is the primitive tag (1 byte), the dictionary
+//! length (4 bytes, u32 LE) and the dictionary bytes. Version 1 manifests
+//! have no dictionary section and are still readable.
 
+use std::collections::BTreeMap;
 use std::fs::{File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use crate::codec::CompressionDictionary;
+
 /// MANIFEST magic bytes: "STRM" (0x5354524D)
 pub const MANIFEST_MAGIC: [u8; 4] = *b"STRM";
 
 /// Current MANIFEST format version
-pub const MANIFEST_FORMAT_VERSION: u32 = 1;
+pub const MANIFEST_FORMAT_VERSION: u32 = 2;
 
 /// MANIFEST file structure
 ///
@@ -36,6 +45,7 @@ pub const MANIFEST_FORMAT_VERSION: u32 = 1;
 /// - Codec configuration
 /// - Active WAL segment
 /// - Snapshot watermark
+/// - Compression dictionaries
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Manifest {
     /// Format version for forward compatibility
@@ -50,6 +60,8 @@ pub struct Manifest {
     pub snapshot_watermark: Option<u64>,
     /// Latest snapshot identifier (if any)
     pub snapshot_id: Option<u64>,
+    /// Compression dictionaries keyed by primitive tag (see `primitive_tags`)
+    pub dictionaries: BTreeMap<u8, CompressionDictionary>,
 }
 
 impl Manifest {
@@ -62,6 +74,7 @@ impl Manifest {
             active_wal_segment: 1,
             snapshot_watermark: None,
             snapshot_id: None,
+            dictionaries: BTreeMap::new(),
         }
     }
 
@@ -93,6 +106,16 @@ impl Manifest {
         let snapshot_id = self.snapshot_id.unwrap_or(0);
         bytes.extend_from_slice(&snapshot_id.to_le_bytes());
 
+        // Compression dictionaries (version 2+)
+        if self.format_version >= 2 {
+            bytes.extend_from_slice(&(self.dictionaries.len() as u32).to_le_bytes());
+            for (tag, dict) in &self.dictionaries {
+                bytes.push(*tag);
+                bytes.extend_from_slice(&(dict.as_bytes().len() as u32).to_le_bytes());
+                bytes.extend_from_slice(dict.as_bytes());
+            }
+        }
+
         // CRC32 of all preceding bytes
         let crc = crc32fast::hash(&bytes);
         bytes.extend_from_slice(&crc.to_le_bytes());
@@ -157,12 +180,39 @@ impl Manifest {
 
         // Snapshot ID
         let snapshot_id_val = u64::from_le_bytes(bytes[cursor..cursor + 8].try_into().unwrap());
+        cursor += 8;
         let snapshot_id = if snapshot_id_val > 0 {
             Some(snapshot_id_val)
         } else {
             None
         };
 
+        // Compression dictionaries
+        let end = bytes.len() - 4;
+        let mut dictionaries = BTreeMap::new();
+        if format_version >= 2 {
+            if cursor + 4 > end {
+                return Err(ManifestError::TooShort);
+            }
+            let count = u32::from_le_bytes(bytes[cursor..cursor + 4].try_into().unwrap());
+            cursor += 4;
+            for _ in 0..count {
+                if cursor + 5 > end {
+                    return Err(ManifestError::TooShort);
+                }
+                let tag = bytes[cursor];
+                let len =
+                    u32::from_le_bytes(bytes[cursor + 1..cursor + 5].try_into().unwrap()) as usize;
+                cursor += 5;
+                if cursor + len > end {
+                    return Err(ManifestError::TooShort);
+                }
+                let dict = CompressionDictionary::from_bytes(bytes[cursor..cursor + len].to_vec());
+                dictionaries.insert(tag, dict);
+                cursor += len;
+            }
+        }
+
         Ok(Manifest {
             format_version,
             database_uuid,
@@ -170,6 +220,7 @@ impl Manifest {
             active_wal_segment,
             snapshot_watermark,
             snapshot_id,
+            dictionaries,
         })
     }
 }
@@ -268,6 +319,18 @@ impl ManifestManager {
         self.persist()
     }
 
+    /// Replace the compression dictionaries and persist
+    ///
+    /// Upgrades a version 1 manifest to the current format version.
+    pub fn set_dictionaries(
+        &mut self,
+        dictionaries: BTreeMap<u8, CompressionDictionary>,
+    ) -> Result<(), ManifestError> {
+        self.manifest.format_version = MANIFEST_FORMAT_VERSION;
+        self.manifest.dictionaries = dictionaries;
+        self.persist()
+    }
+
     /// Clear snapshot info (for testing/reset)
     pub fn clear_snapshot(&mut self) -> Result<(), ManifestError> {
         self.manifest.snapshot_id = None;
@@ -344,6 +407,7 @@ mod tests {
             active_wal_segment: 42,
             snapshot_watermark: Some(1000),
             snapshot_id: Some(5),
+            dictionaries: BTreeMap::new(),
         };
 
         let bytes = manifest.to_bytes();
@@ -483,6 +547,54 @@ mod tests {
         assert_eq!(loaded.manifest().snapshot_watermark, Some(1000));
     }
 
+    fn test_dictionary(fill: u8) -> CompressionDictionary {
+        CompressionDictionary::from_bytes(vec![fill; 64])
+    }
+
+    #[test]
+    fn test_manifest_dictionaries_roundtrip() {
+        let mut manifest = Manifest::new(test_uuid(), "zstd".to_string());
+        manifest.snapshot_id = Some(2);
+        manifest.dictionaries.insert(0x01, test_dictionary(1));
+        manifest.dictionaries.insert(0x06, test_dictionary(6));
+
+        let parsed = Manifest::from_bytes(&manifest.to_bytes()).unwrap();
+        assert_eq!(parsed, manifest);
+        assert_eq!(parsed.dictionaries[&0x06].id(), test_dictionary(6).id());
+    }
+
+    #[test]
+    fn test_manifest_v1_still_readable() {
+        let mut manifest = Manifest::new(test_uuid(), "identity".to_string());
+        manifest.format_version = 1;
+        manifest.snapshot_id = Some(4);
+        manifest.snapshot_watermark = Some(40);
+
+        let parsed = Manifest::from_bytes(&manifest.to_bytes()).unwrap();
+        assert_eq!(parsed.format_version, 1);
+        assert_eq!(parsed.snapshot_id, Some(4));
+        assert!(parsed.dictionaries.is_empty());
+    }
+
+    #[test]
+    fn test_manifest_manager_set_dictionaries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path = temp_dir.path().join("MANIFEST");
+
+        let mut manager =
+            ManifestManager::create(manifest_path.clone(), test_uuid(), "zstd".to_string())
+                .unwrap();
+        manager.manifest_mut().format_version = 1;
+
+        let mut dictionaries = BTreeMap::new();
+        dictionaries.insert(0x03, test_dictionary(3));
+        manager.set_dictionaries(dictionaries.clone()).unwrap();
+
+        let loaded = ManifestManager::load(manifest_path).unwrap();
+        assert_eq!(loaded.manifest().format_version, MANIFEST_FORMAT_VERSION);
+        assert_eq!(loaded.manifest().dictionaries, dictionaries);
+    }
+
     #[test]
     fn test_manifest_manager_exists() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -20,13 +20,15 @@ pub mod watermark;
 pub mod writeset;
 
 pub use snapshot::{
-    find_latest_snapshot, list_snapshots, parse_snapshot_id, primitive_tags, snapshot_path,
-    SectionHeader, SnapshotHeader, SnapshotHeaderError, SNAPSHOT_FORMAT_VERSION,
+    find_latest_snapshot, list_snapshots, parse_snapshot_id, primitive_tags, section_content_hash,
+    snapshot_path, SectionHeader, SnapshotHeader, SnapshotHeaderError, SNAPSHOT_FORMAT_VERSION,
     SNAPSHOT_HEADER_SIZE, SNAPSHOT_MAGIC,
 };
 pub use wal_record::{
-    SegmentHeader, WalRecord, WalRecordError, WalSegment, SEGMENT_FORMAT_VERSION,
-    SEGMENT_HEADER_SIZE, SEGMENT_HEADER_SIZE_V2, SEGMENT_MAGIC, WAL_RECORD_FORMAT_VERSION,
+    SegmentHeader, WalRecord, WalRecordError, WalRecordFrame, WalSegment, SEGMENT_FORMAT_VERSION,
+    SEGMENT_FORMAT_VERSION_CODEC, SEGMENT_HEADER_SIZE, SEGMENT_HEADER_SIZE_V2,
+    SEGMENT_HEADER_SIZE_V3, SEGMENT_MAGIC, WAL_RECORD_FORMAT_VERSION, WAL_RECORD_HEADER_SIZE,
+    WAL_RECORD_TRAILER_SIZE,
 };
 pub use writeset::{Mutation, Writeset, WritesetError};
 
@@ -9,7 +9,11 @@
 //! Strings are length-prefixed (4-byte length + bytes).
 //! All integers are little-endian.
 
-use crate::codec::StorageCodec;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::codec::{CompressionDictionary, StorageCodec, ZstdCodec};
+use crate::format::snapshot::primitive_tags;
 
 /// Snapshot entry for KV primitive
 ///
@@ -112,19 +116,61 @@ pub struct VectorSnapshotEntry {
     pub metadata: Vec<u8>,
 }
 
+/// Smallest encoded size of each entry kind, used to bound the capacity
+/// reserved for an untrusted entry count
+const KV_ENTRY_MIN_SIZE: usize = 4 + 4 + 8 + 8;
+const EVENT_ENTRY_MIN_SIZE: usize = 8 + 4 + 8;
+const STATE_ENTRY_MIN_SIZE: usize = 4 + 4 + 8 + 8;
+const BRANCH_ENTRY_MIN_SIZE: usize = 16 + 4 + 8 + 4;
+const JSON_ENTRY_MIN_SIZE: usize = 4 + 4 + 8 + 8;
+const VECTOR_COLLECTION_MIN_SIZE: usize = 4 + 4 + 4;
+const VECTOR_ENTRY_MIN_SIZE: usize = 4 + 8 + 4 + 4;
+
+/// Capacity for `count` entries of at least `min_size` bytes each, capped
+/// by the bytes that remain so a corrupt count cannot force a huge allocation
+fn bounded_capacity(count: usize, remaining: usize, min_size: usize) -> usize {
+    count.min(remaining / min_size)
+}
+
 /// Serializer for snapshot primitive data
 pub struct SnapshotSerializer {
     codec: Box<dyn StorageCodec>,
+    /// Per-primitive codecs that override `codec`, keyed by primitive tag
+    primitive_codecs: BTreeMap<u8, Box<dyn StorageCodec>>,
 }
 
 impl SnapshotSerializer {
     /// Create a new serializer with the given codec
     pub fn new(codec: Box<dyn StorageCodec>) -> Self {
-        SnapshotSerializer { codec }
+        SnapshotSerializer {
+            codec,
+            primitive_codecs: BTreeMap::new(),
+        }
+    }
+
+    /// Compress the values of each primitive with its dictionary
+    ///
+    /// `dictionaries` is keyed by primitive tag (see `primitive_tags`).
+    /// Values of primitives without a dictionary still use the base codec.
+    /// Deserializing requires a serializer built with the same dictionaries.
+    pub fn with_dictionaries(mut self, dictionaries: &BTreeMap<u8, CompressionDictionary>) -> Self {
+        for (tag, dict) in dictionaries {
+            let codec = ZstdCodec::default().with_dictionary(Arc::new(dict.clone()));
+            self.primitive_codecs.insert(*tag, Box::new(codec));
+        }
+        self
+    }
+
+    /// Codec for the values of the primitive with `tag`
+    fn codec_for(&self, tag: u8) -> &dyn StorageCodec {
+        self.primitive_codecs
+            .get(&tag)
+            .map_or(self.codec.as_ref(), |codec| codec.as_ref())
     }
 
     /// Serialize KV entries to bytes
     pub fn serialize_kv(&self, entries: &[KvSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::KV);
         let mut data = Vec::new();
 
         // Entry count
@@ -137,7 +183,7 @@ impl SnapshotSerializer {
             data.extend_from_slice(key_bytes);
 
             // Value (through codec)
-            let value_bytes = self.codec.encode(&entry.value);
+            let value_bytes = codec.encode(&entry.value);
             data.extend_from_slice(&(value_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&value_bytes);
 
@@ -154,6 +200,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<KvSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::KV);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -163,7 +210,11 @@ impl SnapshotSerializer {
         let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(bounded_capacity(
+            count,
+            data.len() - cursor,
+            KV_ENTRY_MIN_SIZE,
+        ));
 
         for _ in 0..count {
             // Key
@@ -192,7 +243,7 @@ impl SnapshotSerializer {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
             let encoded_value = &data[cursor..cursor + value_len];
-            let value = self.codec.decode(encoded_value)?;
+            let value = codec.decode(encoded_value)?;
             cursor += value_len;
 
             // Version and timestamp
@@ -217,6 +268,7 @@ impl SnapshotSerializer {
 
     /// Serialize Event entries to bytes
     pub fn serialize_events(&self, entries: &[EventSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::EVENT);
         let mut data = Vec::new();
 
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
@@ -224,7 +276,7 @@ impl SnapshotSerializer {
         for entry in entries {
             data.extend_from_slice(&entry.sequence.to_le_bytes());
 
-            let payload_bytes = self.codec.encode(&entry.payload);
+            let payload_bytes = codec.encode(&entry.payload);
             data.extend_from_slice(&(payload_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&payload_bytes);
 
@@ -239,6 +291,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<EventSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::EVENT);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -248,7 +301,11 @@ impl SnapshotSerializer {
         let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(bounded_capacity(
+            count,
+            data.len() - cursor,
+            EVENT_ENTRY_MIN_SIZE,
+        ));
 
         for _ in 0..count {
             if cursor + 8 > data.len() {
@@ -268,7 +325,7 @@ impl SnapshotSerializer {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
             let encoded_payload = &data[cursor..cursor + payload_len];
-            let payload = self.codec.decode(encoded_payload)?;
+            let payload = codec.decode(encoded_payload)?;
             cursor += payload_len;
 
             if cursor + 8 > data.len() {
@@ -289,6 +346,7 @@ impl SnapshotSerializer {
 
     /// Serialize State entries to bytes
     pub fn serialize_states(&self, entries: &[StateSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::STATE);
         let mut data = Vec::new();
 
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
@@ -298,7 +356,7 @@ impl SnapshotSerializer {
             data.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(name_bytes);
 
-            let value_bytes = self.codec.encode(&entry.value);
+            let value_bytes = codec.encode(&entry.value);
             data.extend_from_slice(&(value_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&value_bytes);
 
@@ -314,6 +372,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<StateSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::STATE);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -323,7 +382,11 @@ impl SnapshotSerializer {
         let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(bounded_capacity(
+            count,
+            data.len() - cursor,
+            STATE_ENTRY_MIN_SIZE,
+        ));
 
         for _ in 0..count {
             if cursor + 4 > data.len() {
@@ -350,7 +413,7 @@ impl SnapshotSerializer {
             if cursor + value_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let value = self.codec.decode(&data[cursor..cursor + value_len])?;
+            let value = codec.decode(&data[cursor..cursor + value_len])?;
             cursor += value_len;
 
             if cursor + 16 > data.len() {
@@ -374,6 +437,7 @@ impl SnapshotSerializer {
 
     /// Serialize Run entries to bytes
     pub fn serialize_branches(&self, entries: &[BranchSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::BRANCH);
         let mut data = Vec::new();
 
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
@@ -387,7 +451,7 @@ impl SnapshotSerializer {
 
             data.extend_from_slice(&entry.created_at.to_le_bytes());
 
-            let metadata_bytes = self.codec.encode(&entry.metadata);
+            let metadata_bytes = codec.encode(&entry.metadata);
             data.extend_from_slice(&(metadata_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&metadata_bytes);
         }
@@ -400,6 +464,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<BranchSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::BRANCH);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -409,7 +474,11 @@ impl SnapshotSerializer {
         let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(bounded_capacity(
+            count,
+            data.len() - cursor,
+            BRANCH_ENTRY_MIN_SIZE,
+        ));
 
         for _ in 0..count {
             // Run ID (16 bytes)
@@ -452,7 +521,7 @@ impl SnapshotSerializer {
             if cursor + metadata_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let metadata = self.codec.decode(&data[cursor..cursor + metadata_len])?;
+            let metadata = codec.decode(&data[cursor..cursor + metadata_len])?;
             cursor += metadata_len;
 
             entries.push(BranchSnapshotEntry {
@@ -468,6 +537,7 @@ impl SnapshotSerializer {
 
     /// Serialize Json entries to bytes
     pub fn serialize_json(&self, entries: &[JsonSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::JSON);
         let mut data = Vec::new();
 
         data.extend_from_slice(&(entries.len() as u32).to_le_bytes());
@@ -477,7 +547,7 @@ impl SnapshotSerializer {
             data.extend_from_slice(&(doc_id_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(doc_id_bytes);
 
-            let content_bytes = self.codec.encode(&entry.content);
+            let content_bytes = codec.encode(&entry.content);
             data.extend_from_slice(&(content_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&content_bytes);
 
@@ -493,6 +563,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<JsonSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::JSON);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -502,7 +573,11 @@ impl SnapshotSerializer {
         let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut entries = Vec::with_capacity(count);
+        let mut entries = Vec::with_capacity(bounded_capacity(
+            count,
+            data.len() - cursor,
+            JSON_ENTRY_MIN_SIZE,
+        ));
 
         for _ in 0..count {
             // Doc ID
@@ -531,7 +606,7 @@ impl SnapshotSerializer {
             if cursor + content_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let content = self.codec.decode(&data[cursor..cursor + content_len])?;
+            let content = codec.decode(&data[cursor..cursor + content_len])?;
             cursor += content_len;
 
             // Version and timestamp
@@ -556,6 +631,7 @@ impl SnapshotSerializer {
 
     /// Serialize Vector collections to bytes
     pub fn serialize_vectors(&self, collections: &[VectorCollectionSnapshotEntry]) -> Vec<u8> {
+        let codec = self.codec_for(primitive_tags::VECTOR);
         let mut data = Vec::new();
 
         data.extend_from_slice(&(collections.len() as u32).to_le_bytes());
@@ -567,7 +643,7 @@ impl SnapshotSerializer {
             data.extend_from_slice(name_bytes);
 
             // Config (through codec)
-            let config_bytes = self.codec.encode(&collection.config);
+            let config_bytes = codec.encode(&collection.config);
             data.extend_from_slice(&(config_bytes.len() as u32).to_le_bytes());
             data.extend_from_slice(&config_bytes);
 
@@ -589,7 +665,7 @@ impl SnapshotSerializer {
                 }
 
                 // Metadata (through codec)
-                let metadata_bytes = self.codec.encode(&vector.metadata);
+                let metadata_bytes = codec.encode(&vector.metadata);
                 data.extend_from_slice(&(metadata_bytes.len() as u32).to_le_bytes());
                 data.extend_from_slice(&metadata_bytes);
             }
@@ -603,6 +679,7 @@ impl SnapshotSerializer {
         &self,
         data: &[u8],
     ) -> Result<Vec<VectorCollectionSnapshotEntry>, PrimitiveSerializeError> {
+        let codec = self.codec_for(primitive_tags::VECTOR);
         let mut cursor = 0;
 
         if data.len() < 4 {
@@ -612,7 +689,11 @@ impl SnapshotSerializer {
         let collections_count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
         cursor += 4;
 
-        let mut collections = Vec::with_capacity(collections_count);
+        let mut collections = Vec::with_capacity(bounded_capacity(
+            collections_count,
+            data.len() - cursor,
+            VECTOR_COLLECTION_MIN_SIZE,
+        ));
 
         for _ in 0..collections_count {
             // Collection name
@@ -641,7 +722,7 @@ impl SnapshotSerializer {
             if cursor + config_len > data.len() {
                 return Err(PrimitiveSerializeError::UnexpectedEof);
             }
-            let config = self.codec.decode(&data[cursor..cursor + config_len])?;
+            let config = codec.decode(&data[cursor..cursor + config_len])?;
             cursor += config_len;
 
             // Vectors
@@ -652,7 +733,11 @@ impl SnapshotSerializer {
                 u32::from_le_bytes(data[cursor..cursor + 4].try_into().unwrap()) as usize;
             cursor += 4;
 
-            let mut vectors = Vec::with_capacity(vectors_count);
+            let mut vectors = Vec::with_capacity(bounded_capacity(
+                vectors_count,
+                data.len() - cursor,
+                VECTOR_ENTRY_MIN_SIZE,
+            ));
             for _ in 0..vectors_count {
                 // Key
                 if cursor + 4 > data.len() {
@@ -705,7 +790,7 @@ impl SnapshotSerializer {
                 if cursor + metadata_len > data.len() {
                     return Err(PrimitiveSerializeError::UnexpectedEof);
                 }
-                let metadata = self.codec.decode(&data[cursor..cursor + metadata_len])?;
+                let metadata = codec.decode(&data[cursor..cursor + metadata_len])?;
                 cursor += metadata_len;
 
                 vectors.push(VectorSnapshotEntry {
@@ -965,4 +1050,19 @@ mod tests {
             Err(PrimitiveSerializeError::UnexpectedEof)
         ));
     }
+
+    #[test]
+    fn test_deserialize_huge_count() {
+        // A corrupt count must fail on the missing data, not abort on an
+        // allocation sized by the count
+        let serializer = test_serializer();
+        let data = [0xFF; 8];
+
+        assert!(serializer.deserialize_kv(&data).is_err());
+        assert!(serializer.deserialize_events(&data).is_err());
+        assert!(serializer.deserialize_states(&data).is_err());
+        assert!(serializer.deserialize_branches(&data).is_err());
+        assert!(serializer.deserialize_json(&data).is_err());
+        assert!(serializer.deserialize_vectors(&data).is_err());
+    }
 }
@@ -20,6 +20,14 @@
 //! | Footer CRC32     | 4 bytes
 //! +------------------+
 //! ```
+//!
+//! # Incremental Snapshots
+//!
+//! Since format version 2, each section header carries a hash of the
+//! section's serialized data. A section that is unchanged since an earlier
+//! snapshot is written as a reference: a header naming the snapshot that
+//! holds the data, with no data of its own. References always point at the
+//! snapshot that stores the section in full, never at another reference.
 
 use std::path::{Path, PathBuf};
 
@@ -27,7 +35,9 @@ use std::path::{Path, PathBuf};
 pub const SNAPSHOT_MAGIC: [u8; 4] = *b"SNAP";
 
 /// Snapshot format version for forward compatibility
-pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+///
+/// Version 2 added content hashes and section references.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
 
 /// Snapshot header size in bytes
 pub const SNAPSHOT_HEADER_SIZE: usize = 64;
@@ -150,19 +160,60 @@ pub enum SnapshotHeaderError {
 pub struct SectionHeader {
     /// Primitive type tag (see `primitive_tags` module)
     pub primitive_type: u8,
-    /// Section data length in bytes
+    /// Section data length in bytes (0 for a reference)
     pub data_len: u64,
+    /// Hash of the serialized section data, before codec encoding (v2)
+    pub content_hash: u128,
+    /// Snapshot holding the section data, or 0 if it follows this header (v2)
+    pub base_snapshot_id: u64,
 }
 
 impl SectionHeader {
-    /// Section header size in bytes
+    /// Section header size in bytes (format version 1)
     pub const SIZE: usize = 9;
 
+    /// Section header size in bytes (format version 2)
+    pub const SIZE_V2: usize = 33;
+
     /// Create a new section header
     pub fn new(primitive_type: u8, data_len: u64) -> Self {
         SectionHeader {
             primitive_type,
             data_len,
+            content_hash: 0,
+            base_snapshot_id: 0,
+        }
+    }
+
+    /// Create a header for a section stored in full, with its content hash
+    pub fn full(primitive_type: u8, data_len: u64, content_hash: u128) -> Self {
+        SectionHeader {
+            content_hash,
+            ..Self::new(primitive_type, data_len)
+        }
+    }
+
+    /// Create a header referencing the section stored in `base_snapshot_id`
+    pub fn reference(primitive_type: u8, content_hash: u128, base_snapshot_id: u64) -> Self {
+        SectionHeader {
+            primitive_type,
+            data_len: 0,
+            content_hash,
+            base_snapshot_id,
+        }
+    }
+
+    /// Whether the section data lives in another snapshot
+    pub fn is_reference(&self) -> bool {
+        self.base_snapshot_id != 0
+    }
+
+    /// Section header size for the given snapshot format version
+    pub fn size_for_version(format_version: u32) -> usize {
+        if format_version >= 2 {
+            Self::SIZE_V2
+        } else {
+            Self::SIZE
         }
     }
 
@@ -176,13 +227,36 @@ impl SectionHeader {
 
     /// Parse section header from bytes
     pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        SectionHeader::new(
+            bytes[0],
+            u64::from_le_bytes(bytes[1..9].try_into().unwrap()),
+        )
+    }
+
+    /// Serialize section header to bytes (format version 2)
+    pub fn to_bytes_v2(&self) -> [u8; Self::SIZE_V2] {
+        let mut bytes = [0u8; Self::SIZE_V2];
+        bytes[0..Self::SIZE].copy_from_slice(&self.to_bytes());
+        bytes[9..25].copy_from_slice(&self.content_hash.to_le_bytes());
+        bytes[25..33].copy_from_slice(&self.base_snapshot_id.to_le_bytes());
+        bytes
+    }
+
+    /// Parse section header from bytes (format version 2)
+    pub fn from_bytes_v2(bytes: &[u8; Self::SIZE_V2]) -> Self {
         SectionHeader {
-            primitive_type: bytes[0],
-            data_len: u64::from_le_bytes(bytes[1..9].try_into().unwrap()),
+            content_hash: u128::from_le_bytes(bytes[9..25].try_into().unwrap()),
+            base_snapshot_id: u64::from_le_bytes(bytes[25..33].try_into().unwrap()),
+            ..Self::from_bytes(bytes[0..Self::SIZE].try_into().unwrap())
         }
     }
 }
 
+/// Hash of serialized section data, used to detect unchanged sections
+pub fn section_content_hash(data: &[u8]) -> u128 {
+    xxhash_rust::xxh3::xxh3_128(data)
+}
+
 /// Primitive type tags for snapshot sections
 pub mod primitive_tags {
     /// Key-Value store
@@ -197,6 +271,9 @@ pub mod primitive_tags {
     pub const JSON: u8 = 0x06;
     /// Vector embedding
     pub const VECTOR: u8 = 0x07;
+    /// First tag of the custom primitive range; a custom primitive's
+    /// section is tagged with its primitive ID
+    pub const CUSTOM_START: u8 = 0x80;
 
     /// Get the tag name for display
     pub fn tag_name(tag: u8) -> &'static str {
@@ -213,6 +290,11 @@ pub mod primitive_tags {
 
     /// All valid primitive tags in order
     pub const ALL_TAGS: [u8; 6] = [KV, EVENT, STATE, BRANCH, JSON, VECTOR];
+
+    /// Whether a section with this tag may appear in a snapshot
+    pub fn is_valid(tag: u8) -> bool {
+        ALL_TAGS.contains(&tag) || tag >= CUSTOM_START
+    }
 }
 
 /// Generate snapshot file path
@@ -318,6 +400,24 @@ mod tests {
         assert_eq!(header, parsed);
     }
 
+    #[test]
+    fn test_section_header_v2_roundtrip() {
+        let full = SectionHeader::full(primitive_tags::JSON, 512, 0xABCD);
+        let bytes = full.to_bytes_v2();
+        assert_eq!(bytes.len(), SectionHeader::SIZE_V2);
+        assert_eq!(SectionHeader::from_bytes_v2(&bytes), full);
+        assert!(!full.is_reference());
+
+        let reference = SectionHeader::reference(primitive_tags::JSON, 0xABCD, 7);
+        let parsed = SectionHeader::from_bytes_v2(&reference.to_bytes_v2());
+        assert_eq!(parsed, reference);
+        assert!(parsed.is_reference());
+        assert_eq!(parsed.data_len, 0);
+
+        assert_eq!(SectionHeader::size_for_version(1), SectionHeader::SIZE);
+        assert_eq!(SectionHeader::size_for_version(2), SectionHeader::SIZE_V2);
+    }
+
     #[test]
     fn test_snapshot_path() {
         use std::path::Path;
@@ -368,6 +468,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_custom_tags_are_valid() {
+        assert!(primitive_tags::is_valid(primitive_tags::KV));
+        assert!(primitive_tags::is_valid(primitive_tags::CUSTOM_START));
+        assert!(primitive_tags::is_valid(0xFF));
+        assert!(!primitive_tags::is_valid(0x04));
+        assert!(!primitive_tags::is_valid(0x7F));
+    }
+
     #[test]
     fn test_list_snapshots() {
         let temp_dir = tempfile::tempdir().unwrap();
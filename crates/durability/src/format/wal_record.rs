@@ -6,7 +6,7 @@
 //!
 //! ```text
 //! ┌────────────────────────────────────┐
-//! │ Segment Header (32 bytes)          │
+//! │ Segment Header (32/36/40 bytes)    │
 //! ├────────────────────────────────────┤
 //! │ Record 1                           │
 //! ├────────────────────────────────────┤
@@ -29,9 +29,10 @@
 //! └──────────────┴──────────────┴──────────────┴─────────────────────────────┘
 //! ```
 
+use crate::codec::CODEC_BYTE_IDENTITY;
 use crc32fast::Hasher;
 use std::fs::{File, OpenOptions};
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::{IoSlice, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 /// Magic bytes identifying a WAL segment file: "STRA"
@@ -46,16 +47,40 @@ pub const SEGMENT_HEADER_SIZE: usize = 32;
 /// Size of v2 segment header in bytes (with CRC32)
 pub const SEGMENT_HEADER_SIZE_V2: usize = 36;
 
+/// Segment format version that adds a codec byte (v3)
+///
+/// Only written for segments whose records use a non-identity codec, so
+/// identity segments stay readable by v2-only readers.
+pub const SEGMENT_FORMAT_VERSION_CODEC: u32 = 3;
+
+/// Size of v3 segment header in bytes (codec byte + reserved + CRC32)
+pub const SEGMENT_HEADER_SIZE_V3: usize = 40;
+
 /// Current WAL record format version
 pub const WAL_RECORD_FORMAT_VERSION: u8 = 1;
 
-/// WAL segment header (32 bytes for v1, 36 bytes for v2).
+/// Size of the bytes preceding a record's writeset:
+/// length (4) + format version (1) + txn_id (8) + branch_id (16) + timestamp (8)
+pub const WAL_RECORD_HEADER_SIZE: usize = 37;
+
+/// Size of the CRC32 trailer following a record's writeset
+pub const WAL_RECORD_TRAILER_SIZE: usize = 4;
+
+/// Maximum slices handed to a single `write_vectored` call.
+const MAX_IO_SLICES: usize = 8;
+
+/// WAL segment header (32 bytes for v1, 36 bytes for v2, 40 bytes for v3).
 ///
 /// The header is written at the beginning of each segment file and contains
 /// metadata for validation and compatibility checking.
 ///
 /// v2 (format_version=2) adds a CRC32 checksum of the first 32 bytes,
 /// appended as 4 bytes after the base header. v1 headers are still readable.
+///
+/// v3 (format_version=3) adds a codec byte and 3 reserved bytes after the
+/// base header, followed by a CRC32 of the first 36 bytes. The codec byte
+/// identifies how record writesets in this segment are encoded (see
+/// [`crate::codec::codec_byte`]); v1/v2 segments are always identity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C)]
 pub struct SegmentHeader {
@@ -71,52 +96,84 @@ pub struct SegmentHeader {
     /// Database UUID (for integrity checking across segments)
     pub database_uuid: [u8; 16],
 
-    /// CRC32 of the first 32 header bytes (v2+ only, 0 for v1)
+    /// Codec byte for record writesets (v3 only, identity for v1/v2)
+    pub codec: u8,
+
+    /// CRC32 of the header bytes before it (v2+ only, 0 for v1)
     pub header_crc: u32,
 }
 
 impl SegmentHeader {
     /// Create a new segment header (always creates v2 with CRC).
     pub fn new(segment_number: u64, database_uuid: [u8; 16]) -> Self {
+        Self::with_codec(segment_number, database_uuid, CODEC_BYTE_IDENTITY)
+    }
+
+    /// Create a new segment header for records encoded with `codec`.
+    ///
+    /// Creates a v2 header for the identity codec and a v3 header otherwise.
+    pub fn with_codec(segment_number: u64, database_uuid: [u8; 16], codec: u8) -> Self {
+        let format_version = if codec == CODEC_BYTE_IDENTITY {
+            SEGMENT_FORMAT_VERSION
+        } else {
+            SEGMENT_FORMAT_VERSION_CODEC
+        };
         let mut header = SegmentHeader {
             magic: SEGMENT_MAGIC,
-            format_version: SEGMENT_FORMAT_VERSION,
+            format_version,
             segment_number,
             database_uuid,
+            codec,
             header_crc: 0,
         };
-        // Compute CRC of the base 32 bytes
         header.header_crc = header.compute_crc();
         header
     }
 
-    /// Compute CRC32 of the first 32 header bytes.
+    /// Header size in bytes for this header's format version.
+    pub fn size(&self) -> usize {
+        Self::size_for_version(self.format_version)
+    }
+
+    fn size_for_version(format_version: u32) -> usize {
+        match format_version {
+            0 | 1 => SEGMENT_HEADER_SIZE,
+            2 => SEGMENT_HEADER_SIZE_V2,
+            _ => SEGMENT_HEADER_SIZE_V3,
+        }
+    }
+
+    /// Bytes covered by the CRC (everything before the CRC field).
+    fn crc_bytes(&self) -> Vec<u8> {
+        let mut base = Vec::with_capacity(SEGMENT_HEADER_SIZE_V3 - 4);
+        base.extend_from_slice(&self.magic);
+        base.extend_from_slice(&self.format_version.to_le_bytes());
+        base.extend_from_slice(&self.segment_number.to_le_bytes());
+        base.extend_from_slice(&self.database_uuid);
+        if self.format_version >= SEGMENT_FORMAT_VERSION_CODEC {
+            base.extend_from_slice(&[self.codec, 0, 0, 0]);
+        }
+        base
+    }
+
+    /// Compute CRC32 of the header bytes that precede the CRC field.
     fn compute_crc(&self) -> u32 {
-        let mut base = [0u8; SEGMENT_HEADER_SIZE];
-        base[0..4].copy_from_slice(&self.magic);
-        base[4..8].copy_from_slice(&self.format_version.to_le_bytes());
-        base[8..16].copy_from_slice(&self.segment_number.to_le_bytes());
-        base[16..32].copy_from_slice(&self.database_uuid);
         let mut hasher = Hasher::new();
-        hasher.update(&base);
+        hasher.update(&self.crc_bytes());
         hasher.finalize()
     }
 
-    /// Serialize header to bytes (v2: 36 bytes).
-    pub fn to_bytes(&self) -> [u8; SEGMENT_HEADER_SIZE_V2] {
-        let mut bytes = [0u8; SEGMENT_HEADER_SIZE_V2];
-        bytes[0..4].copy_from_slice(&self.magic);
-        bytes[4..8].copy_from_slice(&self.format_version.to_le_bytes());
-        bytes[8..16].copy_from_slice(&self.segment_number.to_le_bytes());
-        bytes[16..32].copy_from_slice(&self.database_uuid);
-        bytes[32..36].copy_from_slice(&self.header_crc.to_le_bytes());
+    /// Serialize header to bytes (v2: 36 bytes, v3: 40 bytes).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.crc_bytes();
+        bytes.extend_from_slice(&self.header_crc.to_le_bytes());
         bytes
     }
 
     /// Deserialize header from a byte slice.
     ///
-    /// Accepts both v1 (32-byte) and v2 (36-byte) headers.
-    /// For v2, validates the CRC; for v1, CRC is set to 0.
+    /// Accepts v1 (32-byte), v2 (36-byte), and v3 (40-byte) headers.
+    /// For v2+, validates the CRC; for v1, CRC is set to 0.
     pub fn from_bytes_slice(bytes: &[u8]) -> Option<Self> {
         if bytes.len() < SEGMENT_HEADER_SIZE {
             return None;
@@ -127,12 +184,21 @@ impl SegmentHeader {
         let segment_number = u64::from_le_bytes(bytes[8..16].try_into().ok()?);
         let database_uuid: [u8; 16] = bytes[16..32].try_into().ok()?;
 
-        let header_crc = if format_version >= 2 && bytes.len() >= SEGMENT_HEADER_SIZE_V2 {
-            let stored_crc = u32::from_le_bytes(bytes[32..36].try_into().ok()?);
+        let size = Self::size_for_version(format_version);
+        let codec = if format_version >= SEGMENT_FORMAT_VERSION_CODEC {
+            // The codec byte is required to decode records; never guess it
+            *bytes.get(..size)?.get(SEGMENT_HEADER_SIZE)?
+        } else {
+            CODEC_BYTE_IDENTITY
+        };
+
+        let header_crc = if format_version >= 2 && bytes.len() >= size {
+            let crc_offset = size - 4;
+            let stored_crc = u32::from_le_bytes(bytes[crc_offset..size].try_into().ok()?);
 
             // Verify CRC
             let mut hasher = Hasher::new();
-            hasher.update(&bytes[0..SEGMENT_HEADER_SIZE]);
+            hasher.update(&bytes[0..crc_offset]);
             let computed_crc = hasher.finalize();
             if stored_crc != computed_crc {
                 return None; // CRC mismatch — header corrupted
@@ -147,6 +213,7 @@ impl SegmentHeader {
             format_version,
             segment_number,
             database_uuid,
+            codec,
             header_crc,
         })
     }
@@ -185,8 +252,11 @@ pub struct WalSegment {
     /// Database UUID for this segment
     database_uuid: [u8; 16],
 
-    /// Actual header size in bytes (32 for v1, 36 for v2)
+    /// Actual header size in bytes (32 for v1, 36 for v2, 40 for v3)
     header_size: usize,
+
+    /// Codec byte for record writesets in this segment
+    codec: u8,
 }
 
 impl WalSegment {
@@ -197,6 +267,19 @@ impl WalSegment {
         dir: &Path,
         segment_number: u64,
         database_uuid: [u8; 16],
+    ) -> std::io::Result<Self> {
+        Self::create_with_codec(dir, segment_number, database_uuid, CODEC_BYTE_IDENTITY)
+    }
+
+    /// Create a new WAL segment whose records are encoded with `codec`.
+    ///
+    /// Writes a v2 header for the identity codec and a v3 header (40 bytes,
+    /// with codec byte) otherwise.
+    pub fn create_with_codec(
+        dir: &Path,
+        segment_number: u64,
+        database_uuid: [u8; 16],
+        codec: u8,
     ) -> std::io::Result<Self> {
         let path = Self::segment_path(dir, segment_number);
 
@@ -206,32 +289,32 @@ impl WalSegment {
             .read(true)
             .open(&path)?;
 
-        // Write v2 header (36 bytes)
-        let header = SegmentHeader::new(segment_number, database_uuid);
+        let header = SegmentHeader::with_codec(segment_number, database_uuid, codec);
         file.write_all(&header.to_bytes())?;
 
         Ok(WalSegment {
             file,
             segment_number,
-            write_position: SEGMENT_HEADER_SIZE_V2 as u64,
+            write_position: header.size() as u64,
             path,
             closed: false,
             database_uuid,
-            header_size: SEGMENT_HEADER_SIZE_V2,
+            header_size: header.size(),
+            codec,
         })
     }
 
     /// Open an existing WAL segment for reading.
     ///
     /// Validates the header and positions at the end for size calculation.
-    /// Handles both v1 (32-byte) and v2 (36-byte) headers.
+    /// Handles v1 (32-byte), v2 (36-byte), and v3 (40-byte) headers.
     pub fn open_read(dir: &Path, segment_number: u64) -> std::io::Result<Self> {
         let path = Self::segment_path(dir, segment_number);
 
         let mut file = OpenOptions::new().read(true).open(&path)?;
 
-        // Try reading v2 header (36 bytes); fall back to v1 (32 bytes) if file is too small
-        let mut header_buf = [0u8; SEGMENT_HEADER_SIZE_V2];
+        // Read up to a v3 header (40 bytes); shorter headers are parsed by version
+        let mut header_buf = [0u8; SEGMENT_HEADER_SIZE_V3];
         let bytes_read = {
             let mut total = 0;
             loop {
@@ -239,7 +322,7 @@ impl WalSegment {
                     Ok(0) => break,
                     Ok(n) => {
                         total += n;
-                        if total >= SEGMENT_HEADER_SIZE_V2 {
+                        if total >= SEGMENT_HEADER_SIZE_V3 {
                             break;
                         }
                     }
@@ -279,12 +362,6 @@ impl WalSegment {
             ));
         }
 
-        let actual_header_size = if header.format_version >= 2 {
-            SEGMENT_HEADER_SIZE_V2
-        } else {
-            SEGMENT_HEADER_SIZE
-        };
-
         let write_position = file.seek(SeekFrom::End(0))?;
 
         Ok(WalSegment {
@@ -294,21 +371,22 @@ impl WalSegment {
             path,
             closed: true, // Opened for reading = treat as closed
             database_uuid: header.database_uuid,
-            header_size: actual_header_size,
+            header_size: header.size(),
+            codec: header.codec,
         })
     }
 
     /// Open an existing WAL segment for appending.
     ///
     /// Used when resuming writes to an existing active segment.
-    /// Handles both v1 (32-byte) and v2 (36-byte) headers.
+    /// Handles v1 (32-byte), v2 (36-byte), and v3 (40-byte) headers.
     pub fn open_append(dir: &Path, segment_number: u64) -> std::io::Result<Self> {
         let path = Self::segment_path(dir, segment_number);
 
         let mut file = OpenOptions::new().read(true).write(true).open(&path)?;
 
-        // Try reading v2 header (36 bytes); fall back to v1 (32 bytes) if file is too small
-        let mut header_buf = [0u8; SEGMENT_HEADER_SIZE_V2];
+        // Read up to a v3 header (40 bytes); shorter headers are parsed by version
+        let mut header_buf = [0u8; SEGMENT_HEADER_SIZE_V3];
         let bytes_read = {
             let mut total = 0;
             loop {
@@ -316,7 +394,7 @@ impl WalSegment {
                     Ok(0) => break,
                     Ok(n) => {
                         total += n;
-                        if total >= SEGMENT_HEADER_SIZE_V2 {
+                        if total >= SEGMENT_HEADER_SIZE_V3 {
                             break;
                         }
                     }
@@ -346,12 +424,6 @@ impl WalSegment {
             ));
         }
 
-        let actual_header_size = if header.format_version >= 2 {
-            SEGMENT_HEADER_SIZE_V2
-        } else {
-            SEGMENT_HEADER_SIZE
-        };
-
         // Seek to end for appending
         let write_position = file.seek(SeekFrom::End(0))?;
 
@@ -362,7 +434,8 @@ impl WalSegment {
             path,
             closed: false,
             database_uuid: header.database_uuid,
-            header_size: actual_header_size,
+            header_size: header.size(),
+            codec: header.codec,
         })
     }
 
@@ -393,11 +466,16 @@ impl WalSegment {
         self.database_uuid
     }
 
-    /// Get the actual header size in bytes (32 for v1, 36 for v2).
+    /// Get the actual header size in bytes (32 for v1, 36 for v2, 40 for v3).
     pub fn header_size(&self) -> usize {
         self.header_size
     }
 
+    /// Get the codec byte for record writesets in this segment.
+    pub fn codec_byte(&self) -> u8 {
+        self.codec
+    }
+
     /// Write bytes to segment and update write position.
     ///
     /// Returns an error if the segment is closed.
@@ -414,6 +492,71 @@ impl WalSegment {
         Ok(())
     }
 
+    /// Write several buffers back to back and update write position.
+    ///
+    /// The buffers go to the file through vectored writes, so a record's
+    /// frame and writeset land in one syscall without first being copied
+    /// into a contiguous buffer. Short writes are resumed until every
+    /// buffer has been written.
+    ///
+    /// Returns an error if the segment is closed.
+    pub fn write_vectored(&mut self, bufs: &[&[u8]]) -> std::io::Result<()> {
+        if self.closed {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Cannot write to closed segment",
+            ));
+        }
+
+        let total: usize = bufs.iter().map(|buf| buf.len()).sum();
+        let mut bufs = bufs;
+        // Bytes of `bufs[0]` already written
+        let mut offset = 0;
+
+        while let Some((first, rest)) = bufs.split_first() {
+            if offset == first.len() {
+                bufs = rest;
+                offset = 0;
+                continue;
+            }
+
+            let mut slices = [IoSlice::new(&[]); MAX_IO_SLICES];
+            slices[0] = IoSlice::new(&first[offset..]);
+            let mut count = 1;
+            for buf in rest.iter().take(MAX_IO_SLICES - 1) {
+                slices[count] = IoSlice::new(buf);
+                count += 1;
+            }
+
+            let mut written = match self.file.write_vectored(&slices[..count]) {
+                Ok(0) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "failed to write whole buffer to segment",
+                    ))
+                }
+                Ok(n) => n,
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+
+            // Skip past everything the write consumed
+            while let Some((first, rest)) = bufs.split_first() {
+                let remaining = first.len() - offset;
+                if written < remaining {
+                    offset += written;
+                    break;
+                }
+                written -= remaining;
+                bufs = rest;
+                offset = 0;
+            }
+        }
+
+        self.write_position += total as u64;
+        Ok(())
+    }
+
     /// Sync segment data to disk.
     pub fn sync(&mut self) -> std::io::Result<()> {
         self.file.sync_all()
@@ -499,24 +642,12 @@ impl WalRecord {
     ///
     /// The length field contains the size of (format_version + payload + crc32).
     pub fn to_bytes(&self) -> Vec<u8> {
-        // Build payload: format_version + txn_id + branch_id + timestamp + writeset
-        let mut payload = Vec::with_capacity(33 + self.writeset.len());
-        payload.push(WAL_RECORD_FORMAT_VERSION);
-        payload.extend_from_slice(&self.txn_id.to_le_bytes());
-        payload.extend_from_slice(&self.branch_id);
-        payload.extend_from_slice(&self.timestamp.to_le_bytes());
-        payload.extend_from_slice(&self.writeset);
-
-        // Calculate CRC32 of payload
-        let crc = Self::compute_crc(&payload);
-
-        // Build final record: length + payload + crc
-        let total_len = payload.len() + 4; // payload + crc
-        let mut record = Vec::with_capacity(4 + total_len);
-        record.extend_from_slice(&(total_len as u32).to_le_bytes());
-        record.extend_from_slice(&payload);
-        record.extend_from_slice(&crc.to_le_bytes());
-
+        let frame =
+            WalRecordFrame::new(self.txn_id, &self.branch_id, self.timestamp, &self.writeset);
+        let mut record = Vec::with_capacity(frame.record_len());
+        record.extend_from_slice(&frame.header);
+        record.extend_from_slice(&self.writeset);
+        record.extend_from_slice(&frame.trailer);
         record
     }
 
@@ -619,6 +750,51 @@ impl WalRecord {
     }
 }
 
+/// Framing around a serialized record's writeset.
+///
+/// A serialized record is `header ++ writeset ++ trailer`. Building the frame
+/// on its own lets the writer pass the writeset to the segment by reference,
+/// as one slice of a vectored write, instead of copying it into a record
+/// buffer first. The CRC is computed incrementally over the header and the
+/// writeset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalRecordFrame {
+    /// Length prefix, format version, txn_id, branch_id and timestamp
+    pub header: [u8; WAL_RECORD_HEADER_SIZE],
+
+    /// CRC32 of everything after the length prefix
+    pub trailer: [u8; WAL_RECORD_TRAILER_SIZE],
+}
+
+impl WalRecordFrame {
+    /// Build the frame for a record carrying `writeset`.
+    pub fn new(txn_id: u64, branch_id: &[u8; 16], timestamp: u64, writeset: &[u8]) -> Self {
+        // Length covers format_version + payload + crc
+        let length = (WAL_RECORD_HEADER_SIZE - 4 + writeset.len() + WAL_RECORD_TRAILER_SIZE) as u32;
+
+        let mut header = [0u8; WAL_RECORD_HEADER_SIZE];
+        header[0..4].copy_from_slice(&length.to_le_bytes());
+        header[4] = WAL_RECORD_FORMAT_VERSION;
+        header[5..13].copy_from_slice(&txn_id.to_le_bytes());
+        header[13..29].copy_from_slice(branch_id);
+        header[29..37].copy_from_slice(&timestamp.to_le_bytes());
+
+        let mut hasher = Hasher::new();
+        hasher.update(&header[4..]);
+        hasher.update(writeset);
+
+        WalRecordFrame {
+            header,
+            trailer: hasher.finalize().to_le_bytes(),
+        }
+    }
+
+    /// Total size of the serialized record, writeset included.
+    pub fn record_len(&self) -> usize {
+        4 + u32::from_le_bytes(self.header[0..4].try_into().unwrap()) as usize
+    }
+}
+
 /// WAL record parsing errors.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum WalRecordError {
@@ -665,6 +841,30 @@ mod tests {
         assert_ne!(parsed.header_crc, 0);
     }
 
+    #[test]
+    fn test_segment_header_v3_codec_roundtrip() {
+        let header = SegmentHeader::with_codec(7, [0xCD; 16], crate::codec::CODEC_BYTE_ZSTD);
+
+        let bytes = header.to_bytes();
+        assert_eq!(bytes.len(), SEGMENT_HEADER_SIZE_V3);
+        let parsed = SegmentHeader::from_bytes_slice(&bytes).unwrap();
+
+        assert_eq!(parsed.format_version, SEGMENT_FORMAT_VERSION_CODEC);
+        assert_eq!(parsed.codec, crate::codec::CODEC_BYTE_ZSTD);
+        assert_eq!(parsed.size(), SEGMENT_HEADER_SIZE_V3);
+
+        // Identity codec keeps the v2 layout
+        let header = SegmentHeader::with_codec(7, [0xCD; 16], CODEC_BYTE_IDENTITY);
+        assert_eq!(header.format_version, SEGMENT_FORMAT_VERSION);
+        assert_eq!(header.to_bytes().len(), SEGMENT_HEADER_SIZE_V2);
+
+        // A truncated or corrupted v3 header is rejected
+        assert!(SegmentHeader::from_bytes_slice(&bytes[..SEGMENT_HEADER_SIZE_V2]).is_none());
+        let mut corrupted = bytes.clone();
+        corrupted[SEGMENT_HEADER_SIZE] ^= 0xFF;
+        assert!(SegmentHeader::from_bytes_slice(&corrupted).is_none());
+    }
+
     #[test]
     fn test_segment_header_invalid_magic() {
         let mut header = SegmentHeader::new(1, [0; 16]);
@@ -704,6 +904,24 @@ mod tests {
         assert!(segment.is_closed());
     }
 
+    #[test]
+    fn test_segment_create_with_codec_and_open() {
+        let dir = tempdir().unwrap();
+        let codec = crate::codec::CODEC_BYTE_ZSTD;
+
+        let segment = WalSegment::create_with_codec(dir.path(), 1, [1u8; 16], codec).unwrap();
+        assert_eq!(segment.size(), SEGMENT_HEADER_SIZE_V3 as u64);
+        assert_eq!(segment.codec_byte(), codec);
+        drop(segment);
+
+        let segment = WalSegment::open_read(dir.path(), 1).unwrap();
+        assert_eq!(segment.header_size(), SEGMENT_HEADER_SIZE_V3);
+        assert_eq!(segment.codec_byte(), codec);
+
+        let segment = WalSegment::open_append(dir.path(), 1).unwrap();
+        assert_eq!(segment.codec_byte(), codec);
+    }
+
     #[test]
     fn test_segment_write_and_close() {
         let dir = tempdir().unwrap();
@@ -723,6 +941,43 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_segment_write_vectored() {
+        let dir = tempdir().unwrap();
+        let mut segment = WalSegment::create(dir.path(), 1, [3u8; 16]).unwrap();
+        let initial_size = segment.size();
+
+        // More slices than one vectored call takes, with empty ones mixed in
+        let chunks: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; i as usize % 4]).collect();
+        let bufs: Vec<&[u8]> = chunks.iter().map(|c| c.as_slice()).collect();
+        segment.write_vectored(&bufs).unwrap();
+
+        let expected = chunks.concat();
+        assert_eq!(segment.size(), initial_size + expected.len() as u64);
+
+        let contents = std::fs::read(segment.path()).unwrap();
+        assert_eq!(&contents[initial_size as usize..], expected.as_slice());
+
+        segment.close().unwrap();
+        assert!(segment.write_vectored(&[b"more"]).is_err());
+    }
+
+    #[test]
+    fn test_wal_record_frame_matches_to_bytes() {
+        let record = WalRecord::new(7, [9u8; 16], 555, vec![10, 20, 30, 40]);
+        let frame = WalRecordFrame::new(
+            record.txn_id,
+            &record.branch_id,
+            record.timestamp,
+            &record.writeset,
+        );
+
+        let framed = [&frame.header[..], &record.writeset, &frame.trailer].concat();
+        assert_eq!(framed, record.to_bytes());
+        assert_eq!(frame.record_len(), framed.len());
+        assert!(WalRecord::verify_checksum(&framed).is_ok());
+    }
+
     #[test]
     fn test_wal_record_roundtrip() {
         let record = WalRecord::new(42, [1u8; 16], 1234567890, vec![1, 2, 3, 4, 5]);
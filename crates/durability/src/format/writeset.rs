@@ -38,6 +38,9 @@ const ENTITY_RUN: u8 = 0x05;
 const ENTITY_JSON: u8 = 0x06;
 const ENTITY_VECTOR: u8 = 0x07;
 
+/// Smallest encoded mutation: mutation tag + entity tag + branch ID
+const MIN_MUTATION_SIZE: usize = 1 + 1 + 16;
+
 /// A mutation within a transaction writeset.
 ///
 /// Each mutation represents a single operation that modifies the database state.
@@ -164,7 +167,8 @@ impl Writeset {
 
         let count = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
         let mut cursor = 4;
-        let mut mutations = Vec::with_capacity(count);
+        // The count is untrusted: never reserve more than the bytes can hold
+        let mut mutations = Vec::with_capacity(count.min((bytes.len() - 4) / MIN_MUTATION_SIZE));
 
         for _ in 0..count {
             let (mutation, consumed) = Self::read_mutation(&bytes[cursor..])?;
@@ -657,6 +661,16 @@ mod tests {
         assert!(Writeset::from_bytes(&[1, 0, 0, 0]).is_err());
     }
 
+    #[test]
+    fn test_writeset_huge_count_does_not_preallocate() {
+        // A corrupt count must fail on the missing data, not abort on an
+        // allocation sized by the count
+        assert!(matches!(
+            Writeset::from_bytes(&[0xFF, 0xFF, 0xFF, 0xFF]),
+            Err(WritesetError::InsufficientData)
+        ));
+    }
+
     #[test]
     fn test_writeset_invalid_mutation_tag() {
         // Invalid mutation tag (0xFF)
@@ -25,7 +25,7 @@ pub mod snapshot_types; // Snapshot envelope and header types
 pub mod wal; // WAL segment types, durability modes
 
 // === Modules moved from storage crate (Phase 1 consolidation) ===
-pub mod codec; // Storage codec abstraction (identity, future encryption/compression)
+pub mod codec; // Storage codec abstraction (identity, zstd, AES-GCM)
 pub mod compaction; // WAL segment cleanup and tombstone tracking
 pub mod disk_snapshot; // Crash-safe snapshot I/O and checkpoint coordination
 pub mod format; // Binary on-disk formats (WAL segments, snapshots, manifest, writesets)
@@ -44,7 +44,7 @@ pub use snapshot_types::{
     now_micros, primitive_ids, PrimitiveSection, SnapshotEnvelope, SnapshotError, SnapshotHeader,
     SnapshotInfo, SNAPSHOT_HEADER_SIZE, SNAPSHOT_MAGIC, SNAPSHOT_VERSION_1,
 };
-pub use wal::DurabilityMode;
+pub use wal::{DurabilityMode, LossWindow};
 
 // BranchBundle types
 pub use branch_bundle::{
@@ -58,13 +58,16 @@ pub use branch_bundle::{
 // === Re-exports from moved modules ===
 
 // Codec
-pub use codec::{get_codec, CodecError, IdentityCodec, StorageCodec};
+pub use codec::{
+    get_codec, train_dictionary, AesGcmCodec, CodecError, CompressionDictionary, EncryptionKey,
+    IdentityCodec, KeyProvider, StorageCodec, ZstdCodec,
+};
 
 // Disk snapshot
 pub use disk_snapshot::{
     CheckpointCoordinator, CheckpointData, CheckpointError, LoadedSection, LoadedSnapshot,
     SnapshotInfo as DiskSnapshotInfo, SnapshotReadError, SnapshotReader as DiskSnapshotReader,
-    SnapshotSection, SnapshotWriter as DiskSnapshotWriter,
+    SnapshotSection, SnapshotWriter as DiskSnapshotWriter, DEFAULT_FULL_SNAPSHOT_INTERVAL,
 };
 
 // Format types
@@ -74,6 +77,7 @@ pub use format::{
     list_snapshots,
     parse_snapshot_id,
     primitive_tags,
+    section_content_hash,
     snapshot_path,
     BranchSnapshotEntry,
     // Watermark tracking
@@ -110,8 +114,10 @@ pub use format::{
     SegmentMeta,
     SegmentMetaError,
     SEGMENT_FORMAT_VERSION,
+    SEGMENT_FORMAT_VERSION_CODEC,
     SEGMENT_HEADER_SIZE,
     SEGMENT_HEADER_SIZE_V2,
+    SEGMENT_HEADER_SIZE_V3,
     SEGMENT_MAGIC,
     SNAPSHOT_FORMAT_VERSION,
     SNAPSHOT_HEADER_SIZE as FORMAT_SNAPSHOT_HEADER_SIZE,
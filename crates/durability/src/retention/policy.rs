@@ -29,13 +29,14 @@
 use std::collections::HashMap;
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
 use strata_core::PrimitiveType;
 
 /// Retention policy for a branch
 ///
 /// Controls how much version history is retained.
 /// Policies are stored as database entries and are themselves versioned.
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum RetentionPolicy {
     /// Keep all versions forever (default)
     ///
@@ -364,6 +365,7 @@ fn primitive_type_to_byte(ptype: PrimitiveType) -> u8 {
         PrimitiveType::Branch => 0x05,
         PrimitiveType::Json => 0x06,
         PrimitiveType::Vector => 0x07,
+        PrimitiveType::List => 0x08,
     }
 }
 
@@ -377,6 +379,7 @@ fn primitive_type_from_byte(byte: u8) -> Option<PrimitiveType> {
         0x05 => Some(PrimitiveType::Branch),
         0x06 => Some(PrimitiveType::Json),
         0x07 => Some(PrimitiveType::Vector),
+        0x08 => Some(PrimitiveType::List),
         _ => None,
     }
 }
@@ -550,6 +553,22 @@ mod tests {
         assert!(composite.summary().contains("Composite"));
     }
 
+    #[test]
+    fn test_json_roundtrip_composite() {
+        let policy = RetentionPolicy::composite(RetentionPolicy::keep_last(1))
+            .with_override(PrimitiveType::Vector, RetentionPolicy::keep_all())
+            .with_override(
+                PrimitiveType::Event,
+                RetentionPolicy::keep_for(Duration::from_secs(60)),
+            )
+            .build();
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RetentionPolicy>(&json).unwrap(),
+            policy
+        );
+    }
+
     #[test]
     fn test_primitive_type_byte_roundtrip() {
         for ptype in PrimitiveType::all() {
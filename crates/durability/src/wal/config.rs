@@ -13,7 +13,8 @@ pub struct WalConfig {
     /// Bytes between fsyncs in Standard mode (default: 4MB).
     ///
     /// For Standard durability mode, fsync is triggered when this many
-    /// bytes have been written since the last fsync.
+    /// bytes have been written since the last fsync, without waiting for
+    /// the mode's interval. 0 leaves only the interval.
     pub buffered_sync_bytes: u64,
 }
 
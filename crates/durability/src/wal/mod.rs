@@ -11,7 +11,7 @@ pub mod reader;
 pub mod writer;
 
 // Canonical DurabilityMode
-pub use mode::DurabilityMode;
+pub use mode::{DurabilityMode, LossWindow};
 
 // Segmented WAL types (primary API)
 pub use config::{WalConfig, WalConfigError};
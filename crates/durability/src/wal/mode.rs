@@ -2,6 +2,8 @@
 //!
 //! Controls WAL sync behavior (Cache, Standard, Always).
 
+use std::time::Duration;
+
 /// Durability mode for WAL operations
 ///
 /// Controls when the WAL is fsynced to disk. This is orthogonal to
@@ -13,7 +15,10 @@
 /// |------|-------|-----------------|
 /// | Cache | Never | All uncommitted |
 /// | Always | Every commit | Zero |
-/// | Standard | Periodic | Up to interval/batch |
+/// | Standard | Periodic | Up to interval_ms or buffered_sync_bytes |
+///
+/// [`WalWriter::max_loss_window`](super::WalWriter::max_loss_window) gives
+/// the exact bound for a writer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DurabilityMode {
     /// In-memory cache — all data lost on crash (fastest mode)
@@ -33,15 +38,17 @@ pub enum DurabilityMode {
     /// Expect 10ms+ latency per write.
     Always,
 
-    /// fsync every N commits OR every T milliseconds (the default)
+    /// fsync every T milliseconds OR every N bytes (the default)
     ///
-    /// Good balance of speed and safety. May lose up to batch_size
-    /// writes or interval_ms of data on crash.
+    /// Good balance of speed and safety. May lose up to interval_ms of
+    /// commits, and less than `WalConfig::buffered_sync_bytes` of WAL, on
+    /// crash. Whichever limit is reached first triggers the fsync.
     /// Target latency: <30µs.
     Standard {
         /// Maximum time between fsyncs in milliseconds
         interval_ms: u64,
-        /// Maximum writes between fsyncs
+        /// Maximum writes between fsyncs (not enforced; byte volume is
+        /// bounded by `WalConfig::buffered_sync_bytes` instead)
         batch_size: usize,
     },
 }
@@ -88,8 +95,15 @@ impl DurabilityMode {
     ///
     /// This is the recommended mode for production workloads.
     pub fn standard_default() -> Self {
+        Self::standard(100)
+    }
+
+    /// Create a standard mode syncing at least every `interval_ms`
+    ///
+    /// Keeps the default `batch_size` of 1000.
+    pub fn standard(interval_ms: u64) -> Self {
         DurabilityMode::Standard {
-            interval_ms: 100,
+            interval_ms,
             batch_size: 1000,
         }
     }
@@ -104,3 +118,38 @@ impl Default for DurabilityMode {
         }
     }
 }
+
+/// Most committed data a crash can lose
+///
+/// Covers machine crashes and power loss. A process crash alone loses
+/// nothing in Standard mode, since records reach the WAL file on commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossWindow {
+    /// Nothing is persisted; a crash loses every commit
+    Unbounded,
+    /// A crash loses only commits younger than `max_age` that together
+    /// hold at most `max_bytes` of WAL
+    ///
+    /// `Always` mode is `Bounded` with a zero age and zero bytes. The age
+    /// bound can be exceeded by however late the OS schedules the flush
+    /// worker.
+    Bounded {
+        /// Longest a committed record stays unsynced
+        max_age: Duration,
+        /// Most WAL bytes left unsynced, or `None` when only age is limited
+        max_bytes: Option<u64>,
+    },
+}
+
+impl LossWindow {
+    /// Whether every commit is durable once it returns
+    pub fn is_zero(&self) -> bool {
+        matches!(
+            self,
+            LossWindow::Bounded {
+                max_age,
+                max_bytes: Some(0),
+            } if max_age.is_zero()
+        )
+    }
+}
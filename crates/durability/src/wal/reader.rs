@@ -2,7 +2,7 @@
 //!
 //! The reader handles reading WAL records from segments for recovery.
 
-use crate::codec::StorageCodec;
+use crate::codec::{codec_id_for_byte, get_codec, StorageCodec};
 use crate::format::segment_meta::SegmentMeta;
 use crate::format::{WalRecord, WalRecordError, WalSegment};
 use std::io::Read;
@@ -17,11 +17,10 @@ const MAX_RECOVERY_SCAN_WINDOW: usize = 1_024 * 1_024; // 1 MB
 ///
 /// The reader can read individual segments or scan all segments in order.
 pub struct WalReader {
-    /// Storage codec for decoding.
+    /// Storage codec for decoding record writesets.
     ///
-    /// Currently the codec is stored for future use when codec-aware
-    /// decoding is implemented. The identity codec passes through unchanged.
-    #[allow(dead_code)]
+    /// Segments record the codec they were written with; a segment written
+    /// with a different codec is decoded with that codec instead.
     codec: Box<dyn StorageCodec>,
 }
 
@@ -67,18 +66,30 @@ impl WalReader {
             .read_to_end(&mut buffer)
             .map_err(|e: std::io::Error| WalReaderError::IoError(e.to_string()))?;
 
+        let segment_codec = self.codec_for_segment(segment)?;
+        let codec = segment_codec.as_deref().unwrap_or(self.codec.as_ref());
+
         let mut offset = 0;
         let mut stop_reason = ReadStopReason::EndOfData;
         let mut skipped_corrupted = 0usize;
 
         while offset < buffer.len() {
-            // Try to decode through codec first
-            // For identity codec, this is just the raw bytes
             let remaining = &buffer[offset..];
 
             // Try to parse a record
             match WalRecord::from_bytes(remaining) {
-                Ok((record, consumed)) => {
+                Ok((mut record, consumed)) => {
+                    // Record framing is always plain; only the writeset is encoded
+                    match codec.decode(&record.writeset) {
+                        Ok(writeset) => record.writeset = writeset,
+                        Err(e) => {
+                            stop_reason = ReadStopReason::ParseError {
+                                offset,
+                                detail: e.to_string(),
+                            };
+                            break;
+                        }
+                    }
                     records.push(record);
                     offset += consumed;
                     valid_end = hdr_size + offset as u64;
@@ -137,6 +148,47 @@ impl WalReader {
         Ok((records, valid_end, stop_reason, skipped_corrupted))
     }
 
+    /// Check that every segment in the WAL directory can be decoded.
+    ///
+    /// Resolves each segment's codec and decodes its first record, so a
+    /// missing or wrong encryption key is reported up front instead of
+    /// surfacing as a truncated WAL during recovery.
+    pub fn check_codecs(&self, wal_dir: &Path) -> Result<(), WalReaderError> {
+        for segment_number in self.list_segments(wal_dir)? {
+            let mut segment = WalSegment::open_read(wal_dir, segment_number)
+                .map_err(|e: std::io::Error| WalReaderError::IoError(e.to_string()))?;
+            let segment_codec = self.codec_for_segment(&segment)?;
+            let codec = segment_codec.as_deref().unwrap_or(self.codec.as_ref());
+
+            let mut buffer = Vec::new();
+            segment
+                .seek_to(segment.header_size() as u64)
+                .and_then(|_| segment.file_mut().read_to_end(&mut buffer))
+                .map_err(|e: std::io::Error| WalReaderError::IoError(e.to_string()))?;
+            if let Ok((record, _)) = WalRecord::from_bytes(&buffer) {
+                codec.decode(&record.writeset).map_err(|e| {
+                    WalReaderError::Codec(format!("segment {}: {}", segment_number, e))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Codec for a segment's records, if it differs from this reader's codec.
+    fn codec_for_segment(
+        &self,
+        segment: &WalSegment,
+    ) -> Result<Option<Box<dyn StorageCodec>>, WalReaderError> {
+        let codec_id = codec_id_for_byte(segment.codec_byte())
+            .map_err(|e| WalReaderError::Codec(e.to_string()))?;
+        if codec_id == self.codec.codec_id() {
+            return Ok(None);
+        }
+        get_codec(codec_id)
+            .map(Some)
+            .map_err(|e| WalReaderError::Codec(e.to_string()))
+    }
+
     /// Read all records from all segments in a WAL directory.
     ///
     /// Segments are read in order. Returns all valid records and information
@@ -212,6 +264,32 @@ impl WalReader {
             .collect())
     }
 
+    /// Read all records after a watermark, skipping sealed segments that
+    /// hold nothing newer.
+    ///
+    /// A segment is skipped when it is not the last one and its `.meta`
+    /// sidecar puts its highest transaction ID at or below `watermark`, so
+    /// a reader following a live writer does not re-read the whole WAL on
+    /// every poll. The last segment may still be growing and is always read.
+    pub fn read_since(
+        &self,
+        wal_dir: &Path,
+        watermark: u64,
+    ) -> Result<Vec<WalRecord>, WalReaderError> {
+        let segments = self.list_segments_with_metadata(wal_dir)?;
+        let last = segments.last().map(|(number, _)| *number);
+
+        let mut records = Vec::new();
+        for (number, meta) in segments {
+            let sealed = Some(number) != last;
+            if sealed && meta.is_some_and(|m| m.max_txn_id <= watermark) {
+                continue;
+            }
+            records.extend(self.read_segment_after_watermark(wal_dir, number, watermark)?);
+        }
+        Ok(records)
+    }
+
     /// List all segment numbers in the WAL directory.
     pub fn list_segments(&self, wal_dir: &Path) -> Result<Vec<u64>, WalReaderError> {
         let mut segments = Vec::new();
@@ -434,6 +512,11 @@ pub enum WalReaderError {
     /// Record parsing error
     #[error("Record parsing error: {0}")]
     ParseError(String),
+
+    /// A segment's codec is unknown or cannot decode its records
+    /// (e.g. missing or wrong encryption key)
+    #[error("Codec error: {0}")]
+    Codec(String),
 }
 
 #[cfg(test)]
@@ -535,6 +618,60 @@ mod tests {
         assert!(filtered.iter().all(|r| r.txn_id > 5));
     }
 
+    #[test]
+    fn test_read_since_skips_sealed_segments() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        let config = crate::wal::config::WalConfig::new()
+            .with_segment_size(100)
+            .with_buffered_sync_bytes(50);
+        let mut writer = WalWriter::new(
+            wal_dir.to_path_buf(),
+            [1u8; 16],
+            DurabilityMode::Always,
+            config,
+            make_codec(),
+        )
+        .unwrap();
+        for i in 1..=4 {
+            writer
+                .append(&WalRecord::new(i, [1u8; 16], i * 1000, vec![0; 50]))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+
+        let reader = WalReader::new(make_codec());
+        assert!(reader.list_segments(&wal_dir).unwrap().len() > 1);
+        let all: Vec<_> = reader
+            .read_since(&wal_dir, 0)
+            .unwrap()
+            .iter()
+            .map(|r| r.txn_id)
+            .collect();
+        assert_eq!(all, vec![1, 2, 3, 4]);
+        let newer: Vec<_> = reader
+            .read_since(&wal_dir, 2)
+            .unwrap()
+            .iter()
+            .map(|r| r.txn_id)
+            .collect();
+        assert_eq!(newer, vec![3, 4]);
+        assert!(reader.read_since(&wal_dir, 4).unwrap().is_empty());
+
+        // Records appended after a read show up on the next one
+        writer
+            .append(&WalRecord::new(5, [1u8; 16], 5000, vec![0; 50]))
+            .unwrap();
+        writer.flush().unwrap();
+        let newest: Vec<_> = reader
+            .read_since(&wal_dir, 4)
+            .unwrap()
+            .iter()
+            .map(|r| r.txn_id)
+            .collect();
+        assert_eq!(newest, vec![5]);
+    }
+
     #[test]
     fn test_list_segments() {
         let dir = tempdir().unwrap();
@@ -916,4 +1053,55 @@ mod tests {
         assert_eq!(meta.max_txn_id, 2);
         assert_eq!(meta.record_count, 2);
     }
+
+    #[test]
+    fn test_read_mixed_codec_segments() {
+        use crate::codec::ZstdCodec;
+
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        let large = br#"{"embedding":[0.0,0.0,0.0,0.0]}"#.repeat(100);
+
+        // Identity segment first
+        write_records(
+            &wal_dir,
+            &[WalRecord::new(1, [1u8; 16], 1000, large.clone())],
+        );
+
+        // Reopening with zstd starts a new segment instead of appending
+        {
+            let mut writer = WalWriter::new(
+                wal_dir.clone(),
+                [1u8; 16],
+                DurabilityMode::Always,
+                WalConfig::for_testing(),
+                Box::new(ZstdCodec::default()),
+            )
+            .unwrap();
+            assert_eq!(writer.current_segment(), 2);
+            writer
+                .append(&WalRecord::new(2, [1u8; 16], 2000, large.clone()))
+                .unwrap();
+            writer
+                .append(&WalRecord::new(3, [1u8; 16], 3000, vec![1, 2, 3]))
+                .unwrap();
+            writer.flush().unwrap();
+        }
+
+        let identity_size = WalSegment::open_read(&wal_dir, 1).unwrap().size();
+        let zstd_size = WalSegment::open_read(&wal_dir, 2).unwrap().size();
+        assert!(zstd_size < identity_size);
+
+        // Any reader decodes each segment with the codec it was written with
+        for reader in [
+            WalReader::new(make_codec()),
+            WalReader::new(Box::new(ZstdCodec::default())),
+        ] {
+            let result = reader.read_all(&wal_dir).unwrap();
+            assert_eq!(result.stop_reason, ReadStopReason::EndOfData);
+            assert!(result.truncate_info.is_none());
+            let writesets: Vec<_> = result.records.iter().map(|r| &r.writeset).collect();
+            assert_eq!(writesets, vec![&large, &large, &vec![1, 2, 3]]);
+        }
+    }
 }
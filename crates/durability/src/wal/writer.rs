@@ -3,16 +3,26 @@
 //! The writer handles appending WAL records to segments with proper
 //! durability guarantees based on the configured mode.
 
-use super::DurabilityMode;
-use crate::codec::StorageCodec;
+use super::{DurabilityMode, LossWindow};
+use crate::codec::{codec_byte, StorageCodec};
 use crate::format::segment_meta::SegmentMeta;
-use crate::format::{WalRecord, WalSegment, SEGMENT_HEADER_SIZE_V2};
+use crate::format::{WalRecord, WalRecordFrame, WalSegment, SEGMENT_HEADER_SIZE_V2};
 use crate::wal::config::WalConfig;
 use crate::wal::reader::WalReader;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+/// Largest codec buffer kept between appends; bigger ones are freed after use
+/// so one oversized transaction does not pin its memory on the thread.
+const MAX_RETAINED_ENCODE_BUFFER: usize = 1024 * 1024;
+
+thread_local! {
+    /// Per-thread buffer that non-identity codecs encode writesets into.
+    static ENCODE_BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
 /// Cumulative WAL operation counters.
 ///
 /// These counters accumulate over the lifetime of the WalWriter
@@ -39,7 +49,8 @@ pub struct WalCounters {
 ///
 /// - `Cache`: No persistence - records are not written to disk
 /// - `Always`: fsync after every record - maximum durability
-/// - `Standard`: fsync periodically based on time/count
+/// - `Standard`: fsync once the oldest unsynced record is `interval_ms`
+///   old or `buffered_sync_bytes` are unsynced, whichever comes first
 ///
 /// # Segment Rotation
 ///
@@ -61,17 +72,20 @@ pub struct WalWriter {
     /// Configuration
     config: WalConfig,
 
-    /// Storage codec for encoding
+    /// Storage codec for encoding record writesets
     codec: Box<dyn StorageCodec>,
 
+    /// Segment header codec byte for `codec`
+    codec_byte: u8,
+
     /// Bytes written since last fsync (for Standard mode)
     bytes_since_sync: u64,
 
     /// Writes since last fsync (for Standard mode)
     writes_since_sync: usize,
 
-    /// Last fsync time (for Standard mode)
-    last_sync_time: Instant,
+    /// When the oldest unsynced record was appended (for Standard mode)
+    oldest_unsynced_time: Instant,
 
     /// Current segment number
     current_segment_number: u64,
@@ -98,6 +112,8 @@ impl WalWriter {
     ///
     /// If the WAL directory contains existing segments, the writer will
     /// either open the last segment for appending or create a new one.
+    /// A segment written with a different codec is never appended to; the
+    /// writer starts a new segment instead, so each segment has one codec.
     pub fn new(
         wal_dir: PathBuf,
         database_uuid: [u8; 16],
@@ -105,6 +121,9 @@ impl WalWriter {
         config: WalConfig,
         codec: Box<dyn StorageCodec>,
     ) -> std::io::Result<Self> {
+        let codec_byte = codec_byte(codec.codec_id())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+
         // For Cache mode, don't create any files
         if !durability.requires_wal() {
             return Ok(WalWriter {
@@ -114,9 +133,10 @@ impl WalWriter {
                 database_uuid,
                 config,
                 codec,
+                codec_byte,
                 bytes_since_sync: 0,
                 writes_since_sync: 0,
-                last_sync_time: Instant::now(),
+                oldest_unsynced_time: Instant::now(),
                 current_segment_number: 0,
                 current_segment_meta: None,
                 has_unsynced_data: false,
@@ -137,18 +157,24 @@ impl WalWriter {
             Some(num) => {
                 // Try to open existing segment for appending
                 match WalSegment::open_append(&wal_dir, num) {
-                    Ok(seg) => (seg, num, true),
-                    Err(_) => {
-                        // Segment might be corrupted or closed, create new one
+                    Ok(seg) if seg.codec_byte() == codec_byte => (seg, num, true),
+                    _ => {
+                        // Segment might be corrupted, closed, or written with
+                        // another codec; create new one
                         let new_num = num + 1;
-                        let seg = WalSegment::create(&wal_dir, new_num, database_uuid)?;
+                        let seg = WalSegment::create_with_codec(
+                            &wal_dir,
+                            new_num,
+                            database_uuid,
+                            codec_byte,
+                        )?;
                         (seg, new_num, false)
                     }
                 }
             }
             None => {
                 // No existing segments, create first one
-                let seg = WalSegment::create(&wal_dir, 1, database_uuid)?;
+                let seg = WalSegment::create_with_codec(&wal_dir, 1, database_uuid, codec_byte)?;
                 (seg, 1, false)
             }
         };
@@ -168,9 +194,10 @@ impl WalWriter {
             database_uuid,
             config,
             codec,
+            codec_byte,
             bytes_since_sync: 0,
             writes_since_sync: 0,
-            last_sync_time: Instant::now(),
+            oldest_unsynced_time: Instant::now(),
             current_segment_number: segment_number,
             current_segment_meta,
             has_unsynced_data: false,
@@ -193,25 +220,47 @@ impl WalWriter {
             return Ok(());
         }
 
-        let segment = self
-            .segment
-            .as_mut()
-            .expect("Segment should exist for non-Cache mode");
+        // Encode the writeset through the codec; record framing stays plain
+        // so recovery can always locate record boundaries and verify CRCs.
+        // The identity codec's output is the writeset itself, so it is
+        // written by reference without any intermediate buffer.
+        if self.codec.is_identity() {
+            return self.append_encoded(record, &record.writeset);
+        }
 
-        // Serialize record
-        let record_bytes = record.to_bytes();
+        ENCODE_BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            buffer.clear();
+            self.codec.encode_into(&record.writeset, &mut buffer);
+            let result = self.append_encoded(record, &buffer);
+            if buffer.capacity() > MAX_RETAINED_ENCODE_BUFFER {
+                *buffer = Vec::new();
+            }
+            result
+        })
+    }
 
-        // Encode through codec
-        let encoded = self.codec.encode(&record_bytes);
+    /// Frame `writeset` (already codec-encoded) as `record` and write it.
+    ///
+    /// The frame header, writeset and CRC trailer go to the segment as one
+    /// vectored write.
+    fn append_encoded(&mut self, record: &WalRecord, writeset: &[u8]) -> std::io::Result<()> {
+        let frame =
+            WalRecordFrame::new(record.txn_id, &record.branch_id, record.timestamp, writeset);
+        let record_len = frame.record_len() as u64;
 
         // Check if we need to rotate before writing
-        if segment.size() + encoded.len() as u64 > self.config.segment_size {
+        let segment = self
+            .segment
+            .as_mut()
+            .expect("Segment should exist for non-Cache mode");
+        if segment.size() + record_len > self.config.segment_size {
             self.rotate_segment()?;
         }
 
         // Write to segment
         let segment = self.segment.as_mut().unwrap();
-        segment.write(&encoded)?;
+        segment.write_vectored(&[&frame.header, writeset, &frame.trailer])?;
 
         // Track metadata for the current segment
         if let Some(ref mut meta) = self.current_segment_meta {
@@ -219,11 +268,14 @@ impl WalWriter {
         }
 
         self.total_wal_appends += 1;
-        self.total_bytes_written += encoded.len() as u64;
+        self.total_bytes_written += record_len;
 
-        debug!(target: "strata::wal", txn_id = record.txn_id, record_bytes = encoded.len(), segment = self.current_segment_number, "WAL record appended");
+        debug!(target: "strata::wal", txn_id = record.txn_id, record_bytes = record_len, segment = self.current_segment_number, "WAL record appended");
 
-        self.bytes_since_sync += encoded.len() as u64;
+        if !self.has_unsynced_data {
+            self.oldest_unsynced_time = Instant::now();
+        }
+        self.bytes_since_sync += record_len;
         self.writes_since_sync += 1;
         self.has_unsynced_data = true;
 
@@ -251,6 +303,13 @@ impl WalWriter {
                 // Standard mode: fsync is deferred to the background flush thread (#969).
                 // Data is already written to the BufWriter by append().
                 // The background thread periodically calls sync_if_overdue().
+                // Only a burst that fills buffered_sync_bytes first pays for the
+                // fsync inline, which coalesces it into one sync.
+                let limit = self.config.buffered_sync_bytes;
+                if limit > 0 && self.bytes_since_sync >= limit {
+                    self.sync_segment()?;
+                    debug!(target: "strata::wal", segment = self.current_segment_number, "WAL size-triggered sync");
+                }
             }
             DurabilityMode::Cache => {
                 // No sync needed
@@ -260,11 +319,23 @@ impl WalWriter {
         Ok(())
     }
 
+    /// Fsync the active segment and reset sync tracking counters.
+    fn sync_segment(&mut self) -> std::io::Result<()> {
+        if let Some(ref mut segment) = self.segment {
+            let start = Instant::now();
+            segment.sync()?;
+            let elapsed = start.elapsed();
+            self.total_sync_calls += 1;
+            self.total_sync_nanos += elapsed.as_nanos() as u64;
+        }
+        self.reset_sync_counters();
+        Ok(())
+    }
+
     /// Reset sync tracking counters.
     fn reset_sync_counters(&mut self) {
         self.bytes_since_sync = 0;
         self.writes_since_sync = 0;
-        self.last_sync_time = Instant::now();
         self.has_unsynced_data = false;
     }
 
@@ -290,10 +361,11 @@ impl WalWriter {
 
         // Create new segment
         self.current_segment_number += 1;
-        let new_segment = WalSegment::create(
+        let new_segment = WalSegment::create_with_codec(
             &self.wal_dir,
             self.current_segment_number,
             self.database_uuid,
+            self.codec_byte,
         )?;
 
         self.segment = Some(new_segment);
@@ -305,6 +377,51 @@ impl WalWriter {
         Ok(())
     }
 
+    /// Close the active segment and start a new one.
+    ///
+    /// Returns the new active segment number. Compaction uses this to fence
+    /// off the segments it is about to replace. No-op in Cache mode.
+    pub fn rotate(&mut self) -> std::io::Result<u64> {
+        if self.segment.is_some() {
+            self.rotate_segment()?;
+        }
+        Ok(self.current_segment_number)
+    }
+
+    /// Remove all closed segments numbered below `segment_number`.
+    ///
+    /// The active segment is never removed. Also removes the `.meta`
+    /// sidecars of deleted segments. Returns `(segments_removed, bytes_removed)`.
+    pub fn remove_segments_before(&self, segment_number: u64) -> std::io::Result<(usize, u64)> {
+        if self.segment.is_none() {
+            return Ok((0, 0));
+        }
+
+        let mut removed = 0;
+        let mut bytes = 0;
+        for number in self.list_segments()? {
+            if number >= segment_number || number >= self.current_segment_number {
+                continue;
+            }
+            let path = WalSegment::segment_path(&self.wal_dir, number);
+            let size = std::fs::metadata(&path)?.len();
+            std::fs::remove_file(&path)?;
+
+            let meta_path = SegmentMeta::meta_path(&self.wal_dir, number);
+            if meta_path.exists() {
+                if let Err(e) = std::fs::remove_file(&meta_path) {
+                    warn!(target: "strata::wal", segment = number, error = %e, "Failed to remove .meta sidecar");
+                }
+            }
+
+            removed += 1;
+            bytes += size;
+        }
+
+        info!(target: "strata::wal", removed, bytes, before = segment_number, "WAL segments removed");
+        Ok((removed, bytes))
+    }
+
     /// Force flush any buffered data to disk.
     ///
     /// This ensures all written records are persisted, regardless of
@@ -322,26 +439,22 @@ impl WalWriter {
         Ok(())
     }
 
-    /// Sync if the batched interval has elapsed and there is unsynced data.
+    /// Sync if the oldest unsynced record is at least half the Standard
+    /// mode interval old.
     ///
-    /// Call this periodically (e.g., from a maintenance timer) to ensure
-    /// Standard mode honors its `interval_ms` even when no new writes arrive.
+    /// Call this every [`sync_check_interval`](Self::sync_check_interval)
+    /// (e.g., from a maintenance timer) so Standard mode honors its
+    /// `interval_ms` even when no new writes arrive: a record missed by one
+    /// call is synced by the next, within `interval_ms` of being appended.
     /// Returns `true` if a sync was performed.
     pub fn sync_if_overdue(&mut self) -> std::io::Result<bool> {
         if !self.has_unsynced_data {
             return Ok(false);
         }
 
-        if let DurabilityMode::Standard { interval_ms, .. } = self.durability {
-            if self.last_sync_time.elapsed().as_millis() as u64 >= interval_ms {
-                if let Some(ref mut segment) = self.segment {
-                    let start = Instant::now();
-                    segment.sync()?;
-                    let elapsed = start.elapsed();
-                    self.total_sync_calls += 1;
-                    self.total_sync_nanos += elapsed.as_nanos() as u64;
-                }
-                self.reset_sync_counters();
+        if let Some(half_interval) = self.sync_check_interval() {
+            if self.oldest_unsynced_time.elapsed() >= half_interval {
+                self.sync_segment()?;
                 debug!(target: "strata::wal", segment = self.current_segment_number, "WAL periodic sync");
                 return Ok(true);
             }
@@ -350,6 +463,48 @@ impl WalWriter {
         Ok(false)
     }
 
+    /// How often [`sync_if_overdue`](Self::sync_if_overdue) must be called,
+    /// or `None` outside Standard mode.
+    pub fn sync_check_interval(&self) -> Option<Duration> {
+        match self.durability {
+            DurabilityMode::Standard { interval_ms, .. } => {
+                Some(Duration::from_millis(interval_ms) / 2)
+            }
+            _ => None,
+        }
+    }
+
+    /// Most committed data a crash can lose with this writer.
+    ///
+    /// The age bound in Standard mode assumes `sync_if_overdue` is called
+    /// every `sync_check_interval`.
+    pub fn max_loss_window(&self) -> LossWindow {
+        match self.durability {
+            DurabilityMode::Cache => LossWindow::Unbounded,
+            DurabilityMode::Always => LossWindow::Bounded {
+                max_age: Duration::ZERO,
+                max_bytes: Some(0),
+            },
+            DurabilityMode::Standard { interval_ms, .. } => LossWindow::Bounded {
+                max_age: Duration::from_millis(interval_ms),
+                max_bytes: match self.config.buffered_sync_bytes {
+                    0 => None,
+                    limit => Some(limit),
+                },
+            },
+        }
+    }
+
+    /// Number of records appended since the last fsync.
+    pub fn unsynced_writes(&self) -> usize {
+        self.writes_since_sync
+    }
+
+    /// Bytes appended since the last fsync.
+    pub fn unsynced_bytes(&self) -> u64 {
+        self.bytes_since_sync
+    }
+
     /// Get the current segment number.
     pub fn current_segment(&self) -> u64 {
         self.current_segment_number
@@ -551,6 +706,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rotate_and_remove_segments_before() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+
+        let mut writer = make_writer(&wal_dir, DurabilityMode::Always);
+        writer.append(&make_record(1)).unwrap();
+        assert_eq!(writer.rotate().unwrap(), 2);
+        writer.append(&make_record(2)).unwrap();
+        assert_eq!(writer.rotate().unwrap(), 3);
+        assert_eq!(writer.list_segments().unwrap(), vec![1, 2, 3]);
+
+        let (removed, bytes) = writer.remove_segments_before(3).unwrap();
+        assert_eq!(removed, 2);
+        assert!(bytes > 0);
+        assert_eq!(writer.list_segments().unwrap(), vec![3]);
+        assert!(!SegmentMeta::meta_path(&wal_dir, 1).exists());
+
+        // The active segment is never removed
+        let (removed, _) = writer.remove_segments_before(u64::MAX).unwrap();
+        assert_eq!(removed, 0);
+    }
+
     #[test]
     fn test_flush() {
         let dir = tempdir().unwrap();
@@ -610,6 +788,64 @@ mod tests {
         assert!(writer.current_segment() >= 1);
     }
 
+    #[test]
+    fn test_append_writes_serialized_record() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+
+        let record = WalRecord::new(9, [4u8; 16], 777, b"writeset bytes".to_vec());
+        let mut writer = make_writer(&wal_dir, DurabilityMode::Always);
+        writer.append(&record).unwrap();
+
+        let contents = std::fs::read(WalSegment::segment_path(&wal_dir, 1)).unwrap();
+        assert_eq!(
+            &contents[SEGMENT_HEADER_SIZE_V2..],
+            record.to_bytes().as_slice()
+        );
+        assert_eq!(
+            writer.counters().bytes_written,
+            record.to_bytes().len() as u64
+        );
+    }
+
+    #[test]
+    fn test_codec_encode_buffer_is_reused_and_bounded() {
+        use crate::codec::ZstdCodec;
+
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+        let mut writer = WalWriter::new(
+            wal_dir.clone(),
+            [1u8; 16],
+            DurabilityMode::Always,
+            WalConfig::new().with_segment_size(64 * 1024 * 1024),
+            Box::new(ZstdCodec::default()),
+        )
+        .unwrap();
+
+        writer.append(&make_record(1)).unwrap();
+        let retained = ENCODE_BUFFER.with(|b| b.borrow().capacity());
+        assert!(retained > 0);
+        writer.append(&make_record(2)).unwrap();
+        assert_eq!(ENCODE_BUFFER.with(|b| b.borrow().capacity()), retained);
+
+        // An oversized (incompressible) writeset is not kept on the thread
+        let large: Vec<u8> = (0..2 * MAX_RETAINED_ENCODE_BUFFER as u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+            .collect();
+        writer
+            .append(&WalRecord::new(3, [1u8; 16], 12345, large.clone()))
+            .unwrap();
+        assert!(ENCODE_BUFFER.with(|b| b.borrow().capacity()) <= MAX_RETAINED_ENCODE_BUFFER);
+        writer.flush().unwrap();
+
+        let result = WalReader::new(Box::new(ZstdCodec::default()))
+            .read_all(&wal_dir)
+            .unwrap();
+        let writesets: Vec<_> = result.records.iter().map(|r| &r.writeset).collect();
+        assert_eq!(writesets, vec![&vec![1, 2, 3], &vec![1, 2, 3], &large]);
+    }
+
     #[test]
     fn test_batched_mode_sync_threshold() {
         let dir = tempdir().unwrap();
@@ -639,4 +875,115 @@ mod tests {
         // Segment should have data
         assert!(writer.current_segment_size() > SEGMENT_HEADER_SIZE_V2 as u64);
     }
+
+    #[test]
+    fn test_standard_mode_syncs_at_byte_threshold() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+
+        let record_len = {
+            let record = make_record(0);
+            WalRecordFrame::new(
+                record.txn_id,
+                &record.branch_id,
+                record.timestamp,
+                &[1, 2, 3],
+            )
+            .record_len() as u64
+        };
+        let config = WalConfig::new().with_buffered_sync_bytes(record_len * 3);
+        let mut writer = WalWriter::new(
+            wal_dir,
+            [1u8; 16],
+            DurabilityMode::Standard {
+                interval_ms: 60_000,
+                batch_size: 1000,
+            },
+            config,
+            Box::new(IdentityCodec),
+        )
+        .unwrap();
+
+        writer.append(&make_record(1)).unwrap();
+        writer.append(&make_record(2)).unwrap();
+        assert_eq!(writer.counters().sync_calls, 0);
+        assert_eq!(writer.unsynced_bytes(), record_len * 2);
+
+        // The third record reaches the threshold and is synced inline
+        writer.append(&make_record(3)).unwrap();
+        assert_eq!(writer.counters().sync_calls, 1);
+        assert_eq!(writer.unsynced_bytes(), 0);
+        assert_eq!(writer.unsynced_writes(), 0);
+    }
+
+    #[test]
+    fn test_sync_if_overdue_uses_oldest_unsynced_record() {
+        let dir = tempdir().unwrap();
+        let wal_dir = dir.path().join("wal");
+
+        let mut writer = make_writer(
+            &wal_dir,
+            DurabilityMode::Standard {
+                interval_ms: 200,
+                batch_size: 1000,
+            },
+        );
+        assert_eq!(
+            writer.sync_check_interval(),
+            Some(Duration::from_millis(100))
+        );
+
+        // Nothing to sync, however long the writer has been idle
+        std::thread::sleep(Duration::from_millis(120));
+        assert!(!writer.sync_if_overdue().unwrap());
+
+        // A fresh record is not overdue yet; once half the interval has
+        // passed since it was appended, it is
+        writer.append(&make_record(1)).unwrap();
+        assert!(!writer.sync_if_overdue().unwrap());
+        std::thread::sleep(Duration::from_millis(120));
+        assert!(writer.sync_if_overdue().unwrap());
+        assert!(!writer.sync_if_overdue().unwrap());
+    }
+
+    #[test]
+    fn test_max_loss_window() {
+        let dir = tempdir().unwrap();
+
+        let cache = make_writer(&dir.path().join("cache"), DurabilityMode::Cache);
+        assert_eq!(cache.max_loss_window(), LossWindow::Unbounded);
+        assert_eq!(cache.sync_check_interval(), None);
+
+        let always = make_writer(&dir.path().join("always"), DurabilityMode::Always);
+        assert!(always.max_loss_window().is_zero());
+
+        let standard = make_writer(
+            &dir.path().join("standard"),
+            DurabilityMode::standard_default(),
+        );
+        assert_eq!(
+            standard.max_loss_window(),
+            LossWindow::Bounded {
+                max_age: Duration::from_millis(100),
+                max_bytes: Some(WalConfig::for_testing().buffered_sync_bytes),
+            }
+        );
+        assert!(!standard.max_loss_window().is_zero());
+
+        let interval_only = WalWriter::new(
+            dir.path().join("interval_only"),
+            [1u8; 16],
+            DurabilityMode::standard_default(),
+            WalConfig::new().with_buffered_sync_bytes(0),
+            Box::new(IdentityCodec),
+        )
+        .unwrap();
+        assert_eq!(
+            interval_only.max_loss_window(),
+            LossWindow::Bounded {
+                max_age: Duration::from_millis(100),
+                max_bytes: None,
+            }
+        );
+    }
 }
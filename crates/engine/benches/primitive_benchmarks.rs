@@ -8,6 +8,7 @@
 //! - Cross-primitive txn: >1K ops/sec
 
 use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use strata_core::types::BranchId;
@@ -23,6 +24,11 @@ fn setup_db() -> (Arc<Database>, TempDir, BranchId) {
     (db, temp_dir, branch_id)
 }
 
+/// Event payloads must be JSON objects
+fn payload(v: i64) -> Value {
+    Value::Object(HashMap::from([("value".to_string(), Value::Int(v))]))
+}
+
 /// Benchmark KV put operations
 /// Target: >10K ops/sec
 fn bench_kv_put(c: &mut Criterion) {
@@ -36,8 +42,13 @@ fn bench_kv_put(c: &mut Criterion) {
     group.bench_function("put", |b| {
         b.iter(|| {
             let i = counter.fetch_add(1, Ordering::SeqCst);
-            kv.put(&branch_id, &format!("key{}", i), Value::Int(i as i64))
-                .unwrap()
+            kv.put(
+                &branch_id,
+                "default",
+                &format!("key{}", i),
+                Value::Int(i as i64),
+            )
+            .unwrap()
         })
     });
     group.finish();
@@ -51,8 +62,13 @@ fn bench_kv_get(c: &mut Criterion) {
 
     // Pre-populate 1000 keys
     for i in 0..1000 {
-        kv.put(&branch_id, &format!("key{}", i), Value::Int(i as i64))
-            .unwrap();
+        kv.put(
+            &branch_id,
+            "default",
+            &format!("key{}", i),
+            Value::Int(i as i64),
+        )
+        .unwrap();
     }
 
     let mut group = c.benchmark_group("kv");
@@ -62,7 +78,7 @@ fn bench_kv_get(c: &mut Criterion) {
     group.bench_function("get", |b| {
         b.iter(|| {
             let i = counter.fetch_add(1, Ordering::SeqCst) % 1000;
-            kv.get(&branch_id, &format!("key{}", i)).unwrap()
+            kv.get(&branch_id, "default", &format!("key{}", i)).unwrap()
         })
     });
     group.finish();
@@ -80,7 +96,7 @@ fn bench_event_append(c: &mut Criterion) {
     group.bench_function("append", |b| {
         b.iter(|| {
             event_log
-                .append(&branch_id, "benchmark_event", Value::Int(42))
+                .append(&branch_id, "default", "benchmark_event", payload(42))
                 .unwrap()
         })
     });
@@ -95,7 +111,7 @@ fn bench_state_cas(c: &mut Criterion) {
 
     // Initialize the cell
     state_cell
-        .init(&branch_id, "bench_cell", Value::Int(0))
+        .init(&branch_id, "default", "bench_cell", Value::Int(0))
         .unwrap();
 
     let mut group = c.benchmark_group("state_cell");
@@ -105,7 +121,7 @@ fn bench_state_cas(c: &mut Criterion) {
     group.bench_function("cas", |b| {
         b.iter(|| {
             let current = state_cell
-                .get_versioned(&branch_id, "bench_cell")
+                .get_versioned(&branch_id, "default", "bench_cell")
                 .unwrap()
                 .unwrap();
             let val = match current.value {
@@ -115,6 +131,7 @@ fn bench_state_cas(c: &mut Criterion) {
             state_cell
                 .cas(
                     &branch_id,
+                    "default",
                     "bench_cell",
                     current.version,
                     Value::Int(val + 1),
@@ -133,7 +150,7 @@ fn bench_cross_primitive_transaction(c: &mut Criterion) {
     // Initialize state cell for the transaction
     let state_cell = StateCell::new(db.clone());
     state_cell
-        .init(&branch_id, "txn_cell", Value::Int(0))
+        .init(&branch_id, "default", "txn_cell", Value::Int(0))
         .unwrap();
 
     let mut group = c.benchmark_group("cross_primitive");
@@ -145,7 +162,7 @@ fn bench_cross_primitive_transaction(c: &mut Criterion) {
             let n = counter.fetch_add(1, Ordering::SeqCst);
             db.transaction(branch_id, |txn| {
                 txn.kv_put(&format!("txn_key{}", n), Value::Int(n as i64))?;
-                txn.event_append("txn_event", Value::Int(n as i64))?;
+                txn.event_append("txn_event", payload(n as i64))?;
                 txn.state_set("txn_cell", Value::Int(n as i64))?;
                 Ok(())
             })
@@ -163,7 +180,7 @@ fn bench_event_get(c: &mut Criterion) {
     // Pre-populate 1000 events
     for i in 0..1000 {
         event_log
-            .append(&branch_id, "numbered", Value::Int(i as i64))
+            .append(&branch_id, "default", "numbered", payload(i as i64))
             .unwrap();
     }
 
@@ -174,7 +191,7 @@ fn bench_event_get(c: &mut Criterion) {
     group.bench_function("read", |b| {
         b.iter(|| {
             let i = counter.fetch_add(1, Ordering::SeqCst) % 1000;
-            event_log.get(&branch_id, i).unwrap()
+            event_log.get(&branch_id, "default", i).unwrap()
         })
     });
     group.finish();
@@ -208,21 +225,27 @@ fn bench_kv_list(c: &mut Criterion) {
     for i in 0..100 {
         kv.put(
             &branch_id,
+            "default",
             &format!("prefix/key{}", i),
             Value::Int(i as i64),
         )
         .unwrap();
     }
     for i in 0..100 {
-        kv.put(&branch_id, &format!("other/key{}", i), Value::Int(i as i64))
-            .unwrap();
+        kv.put(
+            &branch_id,
+            "default",
+            &format!("other/key{}", i),
+            Value::Int(i as i64),
+        )
+        .unwrap();
     }
 
     let mut group = c.benchmark_group("kv");
     group.throughput(Throughput::Elements(1));
 
     group.bench_function("list", |b| {
-        b.iter(|| kv.list(&branch_id, Some("prefix/")).unwrap())
+        b.iter(|| kv.list(&branch_id, "default", Some("prefix/")).unwrap())
     });
     group.finish();
 }
@@ -0,0 +1,474 @@
+//! Recovery Regression Benchmarks
+//!
+//! Times reopening a database after realistic write histories, so changes to
+//! the WAL format or replay logic are judged against numbers rather than
+//! impressions. Each workload commits 50K transactions in standard mode:
+//! - `kv_insert`: one new key per transaction
+//! - `kv_overwrite`: 1K hot keys rewritten over and over
+//! - `large_values`: one new key per transaction with a 1 KiB value
+//! - `mixed`: KV put, overwrite and delete, event append and JSON set in turn
+//!
+//! Every iteration is instrumented in two phases:
+//! - `replay`: `RecoveryCoordinator::recover` over the WAL directory alone
+//! - `open`: a full `Database::open` on a fresh copy of the directory,
+//!   including primitive recovery
+//!
+//! The summary records the median and fastest time of each phase alongside
+//! the WAL size and replay counts.
+//!
+//! Run with `cargo bench -p strata-engine --bench recovery_benchmarks`.
+//! Extra arguments:
+//! - `--save-baseline <name>`: also store the summary as a named baseline
+//! - `--compare <name|path>`: compare against a stored baseline (or a
+//!   summary file) and exit non-zero if any median slowed down by more than
+//!   the tolerance
+//! - any other bare argument filters workloads by substring
+//!
+//! Environment variables:
+//! - `STRATA_BENCH_JSON`: output path for the JSON summary
+//!   (default: `<criterion dir>/recovery_summary.json`)
+//! - `STRATA_BENCH_RECOVERY_TXNS`: transactions per workload (default: 50000)
+//! - `STRATA_BENCH_RECOVERY_ITERS`: timed reopens per workload (default: 5)
+//! - `STRATA_BENCH_TOLERANCE`: allowed slowdown in percent for `--compare`
+//!   (default: 10)
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use strata_concurrency::{RecoveryCoordinator, RecoveryStats};
+use strata_core::primitives::json::{JsonPath, JsonValue};
+use strata_core::types::BranchId;
+use strata_core::value::Value;
+use strata_engine::{Database, EventLog, JsonStore, KVStore};
+use tempfile::TempDir;
+
+/// Prefix of every workload id in the summary.
+const ID_PREFIX: &str = "recovery/";
+
+const DEFAULT_TXNS: usize = 50_000;
+
+const DEFAULT_ITERS: usize = 5;
+
+const DEFAULT_TOLERANCE_PCT: f64 = 10.0;
+
+/// Distinct keys rewritten by `kv_overwrite`.
+const HOT_KEYS: usize = 1_000;
+
+/// Value size for `large_values`.
+const LARGE_VALUE_BYTES: usize = 1024;
+
+// ============================================================================
+// Workloads
+// ============================================================================
+
+#[derive(Clone, Copy)]
+enum Workload {
+    KvInsert,
+    KvOverwrite,
+    LargeValues,
+    Mixed,
+}
+
+impl Workload {
+    const ALL: [Workload; 4] = [
+        Workload::KvInsert,
+        Workload::KvOverwrite,
+        Workload::LargeValues,
+        Workload::Mixed,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Workload::KvInsert => "kv_insert",
+            Workload::KvOverwrite => "kv_overwrite",
+            Workload::LargeValues => "large_values",
+            Workload::Mixed => "mixed",
+        }
+    }
+
+    /// Commit `txns` transactions into a database at `path`, then close it.
+    fn populate(self, path: &Path, txns: usize) {
+        let db = Database::open(path).unwrap();
+        let branch_id = BranchId::new();
+        let kv = KVStore::new(db.clone());
+
+        match self {
+            Workload::KvInsert => {
+                for i in 0..txns {
+                    kv.put(
+                        &branch_id,
+                        "default",
+                        &format!("k{}", i),
+                        Value::Int(i as i64),
+                    )
+                    .unwrap();
+                }
+            }
+            Workload::KvOverwrite => {
+                for i in 0..txns {
+                    let key = format!("hot{}", i % HOT_KEYS);
+                    kv.put(&branch_id, "default", &key, Value::Int(i as i64))
+                        .unwrap();
+                }
+            }
+            Workload::LargeValues => {
+                for i in 0..txns {
+                    let value = Value::Bytes(pseudo_random_bytes(i as u64, LARGE_VALUE_BYTES));
+                    kv.put(&branch_id, "default", &format!("k{}", i), value)
+                        .unwrap();
+                }
+            }
+            Workload::Mixed => {
+                let events = EventLog::new(db.clone());
+                let json = JsonStore::new(db.clone());
+                json.create(
+                    &branch_id,
+                    "default",
+                    "doc",
+                    JsonValue::from(serde_json::json!({ "counter": 0 })),
+                )
+                .unwrap();
+                let path: JsonPath = "counter".parse().unwrap();
+
+                for i in 0..txns {
+                    match i % 5 {
+                        0 => kv
+                            .put(&branch_id, "default", &format!("k{}", i), Value::Int(1))
+                            .map(|_| ()),
+                        1 => {
+                            let key = format!("hot{}", i % HOT_KEYS);
+                            kv.put(&branch_id, "default", &key, Value::Int(i as i64))
+                                .map(|_| ())
+                        }
+                        // Delete the key put two transactions earlier
+                        2 => kv
+                            .delete(&branch_id, "default", &format!("k{}", i - 2))
+                            .map(|_| ()),
+                        3 => events
+                            .append(
+                                &branch_id,
+                                "default",
+                                "bench",
+                                Value::Object(HashMap::from([(
+                                    "seq".to_string(),
+                                    Value::Int(i as i64),
+                                )])),
+                            )
+                            .map(|_| ()),
+                        _ => json
+                            .set(
+                                &branch_id,
+                                "default",
+                                "doc",
+                                &path,
+                                JsonValue::from(serde_json::json!(i)),
+                            )
+                            .map(|_| ()),
+                    }
+                    .unwrap();
+                }
+            }
+        }
+        db.flush().unwrap();
+    }
+}
+
+/// Deterministic, poorly compressible bytes (xorshift), so runs are comparable.
+fn pseudo_random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+// ============================================================================
+// Measurement
+// ============================================================================
+
+struct Measurement {
+    id: String,
+    wal_bytes: u64,
+    wal_segments: u64,
+    stats: RecoveryStats,
+    replay: Vec<Duration>,
+    open: Vec<Duration>,
+}
+
+impl Measurement {
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "wal_bytes": self.wal_bytes,
+            "wal_segments": self.wal_segments,
+            "txns_replayed": self.stats.txns_replayed,
+            "writes_applied": self.stats.writes_applied,
+            "deletes_applied": self.stats.deletes_applied,
+            "replay_ns": phase_json(&self.replay),
+            "open_ns": phase_json(&self.open),
+        })
+    }
+}
+
+fn phase_json(samples: &[Duration]) -> serde_json::Value {
+    let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+    nanos.sort_unstable();
+    serde_json::json!({
+        "median": nanos[nanos.len() / 2] as u64,
+        "min": nanos[0] as u64,
+    })
+}
+
+fn measure(workload: Workload, txns: usize, iters: usize) -> Measurement {
+    let template = TempDir::new().unwrap();
+    let db_path = template.path().join("db");
+    workload.populate(&db_path, txns);
+    let wal_dir = db_path.join("wal");
+
+    let (wal_bytes, wal_segments) = std::fs::read_dir(&wal_dir)
+        .unwrap()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "seg"))
+        .fold((0, 0), |(bytes, segments), entry| {
+            (bytes + entry.metadata().unwrap().len(), segments + 1)
+        });
+
+    // One untimed pass of each phase warms the page cache
+    let stats = RecoveryCoordinator::new(wal_dir.clone())
+        .recover()
+        .unwrap()
+        .stats;
+    open_copy(&db_path);
+
+    let mut replay = Vec::with_capacity(iters);
+    let mut open = Vec::with_capacity(iters);
+    for _ in 0..iters {
+        let start = Instant::now();
+        let result = RecoveryCoordinator::new(wal_dir.clone()).recover().unwrap();
+        replay.push(start.elapsed());
+        drop(result);
+
+        open.push(open_copy(&db_path));
+    }
+
+    Measurement {
+        id: format!("{}{}", ID_PREFIX, workload.name()),
+        wal_bytes,
+        wal_segments,
+        stats,
+        replay,
+        open,
+    }
+}
+
+/// Time a full open of a fresh copy of `db_path`, so no run sees another's
+/// WAL segments or snapshots.
+fn open_copy(db_path: &Path) -> Duration {
+    let scratch = TempDir::new().unwrap();
+    let copy = scratch.path().join("db");
+    copy_dir(db_path, &copy);
+
+    let start = Instant::now();
+    let db: Arc<Database> = Database::open(&copy).unwrap();
+    let elapsed = start.elapsed();
+    drop(db);
+    elapsed
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    std::fs::create_dir_all(to).unwrap();
+    for entry in std::fs::read_dir(from).unwrap().flatten() {
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            std::fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+// ============================================================================
+// Baselines
+// ============================================================================
+
+/// Resolve the directory criterion writes to, following criterion's own
+/// lookup order, so every suite's output lives together.
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(target).join("criterion");
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/criterion")
+}
+
+fn baseline_path(name: &str) -> PathBuf {
+    criterion_dir()
+        .join("recovery_baselines")
+        .join(format!("{}.json", name))
+}
+
+/// A baseline is looked up by name first, then as a path to a summary.
+fn load_baseline(name_or_path: &str) -> Result<serde_json::Value, String> {
+    let named = baseline_path(name_or_path);
+    let path = if named.exists() {
+        named
+    } else {
+        PathBuf::from(name_or_path)
+    };
+    let bytes = std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write_json(path: &Path, value: &serde_json::Value) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    match std::fs::write(path, serde_json::to_vec_pretty(value).unwrap()) {
+        Ok(()) => println!("Wrote recovery summary to {}", path.display()),
+        Err(e) => eprintln!("Failed to write {}: {}", path.display(), e),
+    }
+}
+
+/// Print each phase's median against the baseline; returns the number of
+/// phases that slowed down by more than `tolerance_pct`.
+fn compare(summary: &serde_json::Value, baseline: &serde_json::Value, tolerance_pct: f64) -> usize {
+    let baseline_by_id: HashMap<&str, &serde_json::Value> = baseline["workloads"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|w| Some((w["id"].as_str()?, w)))
+        .collect();
+
+    println!(
+        "\n{:<28} {:>7} {:>12} {:>12} {:>9}",
+        "workload", "phase", "baseline", "current", "change"
+    );
+    let mut regressions = 0;
+    for current in summary["workloads"].as_array().into_iter().flatten() {
+        let id = current["id"].as_str().unwrap_or_default();
+        let Some(old) = baseline_by_id.get(id) else {
+            println!("{:<28} not in baseline", id);
+            continue;
+        };
+        if old["txns_replayed"] != current["txns_replayed"] {
+            println!(
+                "{:<28} replayed {} transactions, baseline {}; not comparable",
+                id, current["txns_replayed"], old["txns_replayed"]
+            );
+            continue;
+        }
+        for phase in ["replay_ns", "open_ns"] {
+            let (Some(before), Some(after)) = (
+                old[phase]["median"].as_f64(),
+                current[phase]["median"].as_f64(),
+            ) else {
+                continue;
+            };
+            let change = (after - before) / before * 100.0;
+            let regressed = change > tolerance_pct;
+            regressions += usize::from(regressed);
+            println!(
+                "{:<28} {:>7} {:>10.1}ms {:>10.1}ms {:>+8.1}%{}",
+                id,
+                phase.trim_end_matches("_ns"),
+                before / 1e6,
+                after / 1e6,
+                change,
+                if regressed { "  REGRESSED" } else { "" }
+            );
+        }
+    }
+    regressions
+}
+
+// ============================================================================
+// Driver
+// ============================================================================
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+fn main() -> ExitCode {
+    let mut save_baseline = None;
+    let mut compare_to = None;
+    let mut filters = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--save-baseline" => save_baseline = args.next(),
+            "--compare" => compare_to = args.next(),
+            // Flags cargo passes to every bench target
+            _ if arg.starts_with("--") => {}
+            _ => filters.push(arg),
+        }
+    }
+
+    let txns = env_or("STRATA_BENCH_RECOVERY_TXNS", DEFAULT_TXNS);
+    let iters = env_or("STRATA_BENCH_RECOVERY_ITERS", DEFAULT_ITERS).max(1);
+    let tolerance_pct = env_or("STRATA_BENCH_TOLERANCE", DEFAULT_TOLERANCE_PCT);
+
+    let mut workloads = Vec::new();
+    for workload in Workload::ALL {
+        if !filters.is_empty() && !filters.iter().any(|f| workload.name().contains(f.as_str())) {
+            continue;
+        }
+        let m = measure(workload, txns, iters);
+        let json = m.to_json();
+        println!(
+            "{:<28} replay {:>8.1}ms  open {:>8.1}ms  ({} txns, {} KiB WAL in {} segments)",
+            m.id,
+            json["replay_ns"]["median"].as_f64().unwrap_or_default() / 1e6,
+            json["open_ns"]["median"].as_f64().unwrap_or_default() / 1e6,
+            m.stats.txns_replayed,
+            m.wal_bytes / 1024,
+            m.wal_segments,
+        );
+        workloads.push(json);
+    }
+
+    let summary = serde_json::json!({
+        "suite": "recovery",
+        "version": env!("CARGO_PKG_VERSION"),
+        "transactions": txns,
+        "iterations": iters,
+        "workloads": workloads,
+    });
+    let out = std::env::var_os("STRATA_BENCH_JSON")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| criterion_dir().join("recovery_summary.json"));
+    write_json(&out, &summary);
+    if let Some(name) = save_baseline {
+        write_json(&baseline_path(&name), &summary);
+    }
+
+    if let Some(baseline) = compare_to {
+        let baseline = match load_baseline(&baseline) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("Failed to load baseline {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        let regressions = compare(&summary, &baseline, tolerance_pct);
+        if regressions > 0 {
+            eprintln!(
+                "{} phase(s) slower than baseline by more than {}%",
+                regressions, tolerance_pct
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,480 @@
+//! Release Regression Benchmarks
+//!
+//! A fixed suite run between releases to catch performance regressions:
+//! - KV put/get under each durability mode (cache, standard, always) and key size
+//! - JSON set at a nested path
+//! - EventLog append
+//! - Vector search at 10K and 100K vectors (1M opt-in)
+//!
+//! Run with `cargo bench -p strata-engine --bench regression_benchmarks`.
+//! After criterion finishes, a summary of every `regression/*` benchmark is
+//! written as JSON so two releases can be compared mechanically. The summary
+//! also records heap allocations per KV put, counted by a wrapping global
+//! allocator over a fixed run outside criterion's timing.
+//!
+//! Environment variables:
+//! - `STRATA_BENCH_JSON`: output path for the JSON summary
+//!   (default: `<criterion dir>/regression_summary.json`)
+//! - `STRATA_BENCH_VECTOR_SIZES`: comma-separated collection sizes for the
+//!   vector search group (default: `10000,100000`; add `1000000` for the
+//!   full run)
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use strata_core::primitives::json::{JsonPath, JsonValue};
+use strata_core::types::BranchId;
+use strata_core::value::Value;
+use strata_engine::{
+    Database, DistanceMetric, EventLog, JsonStore, KVStore, VectorConfig, VectorStore,
+};
+use tempfile::TempDir;
+
+/// Prefix shared by every group in this suite; the JSON emitter keys on it.
+const GROUP_PREFIX: &str = "regression/";
+
+/// Key lengths in bytes exercised by the KV groups.
+const KEY_SIZES: [usize; 3] = [16, 256, 1024];
+
+/// Keys pre-populated for the KV get group.
+const KV_GET_KEYS: usize = 1_000;
+
+/// Embedding dimension for the vector search group.
+const VECTOR_DIM: usize = 128;
+
+/// Vectors inserted per `batch_insert` call while populating a collection.
+const VECTOR_INSERT_CHUNK: usize = 10_000;
+
+const DEFAULT_VECTOR_SIZES: [usize; 2] = [10_000, 100_000];
+
+/// Puts run before counting allocations, so pools and buffers are warm.
+const ALLOC_WARMUP_OPS: u64 = 100;
+
+/// Puts whose allocations are counted and averaged.
+const ALLOC_MEASURED_OPS: u64 = 500;
+
+// ============================================================================
+// Allocation counting
+// ============================================================================
+
+/// Counts allocations (including reallocations) made by any thread.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+// ============================================================================
+// Setup
+// ============================================================================
+
+/// Durability modes compared by the KV groups.
+#[derive(Clone, Copy)]
+enum Mode {
+    Cache,
+    Standard,
+    Always,
+}
+
+impl Mode {
+    const ALL: [Mode; 3] = [Mode::Cache, Mode::Standard, Mode::Always];
+
+    fn name(self) -> &'static str {
+        match self {
+            Mode::Cache => "cache",
+            Mode::Standard => "standard",
+            Mode::Always => "always",
+        }
+    }
+
+    /// Open a database in this mode. The temp dir must outlive the database.
+    fn open(self) -> (Arc<Database>, Option<TempDir>) {
+        match self {
+            Mode::Cache => (Database::cache().unwrap(), None),
+            Mode::Standard => {
+                let temp_dir = TempDir::new().unwrap();
+                let db = Database::open(temp_dir.path()).unwrap();
+                (db, Some(temp_dir))
+            }
+            Mode::Always => {
+                let temp_dir = TempDir::new().unwrap();
+                std::fs::write(
+                    temp_dir.path().join("strata.toml"),
+                    "durability = \"always\"\n",
+                )
+                .unwrap();
+                let db = Database::open(temp_dir.path()).unwrap();
+                (db, Some(temp_dir))
+            }
+        }
+    }
+}
+
+/// Build a key of exactly `size` bytes that is unique per `i`.
+fn sized_key(i: u64, size: usize) -> String {
+    let mut key = format!("{:016x}", i);
+    while key.len() < size {
+        key.push('k');
+    }
+    key
+}
+
+/// Deterministic pseudo-random vector (xorshift), so runs are comparable.
+fn bench_vector(seed: u64) -> Vec<f32> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..VECTOR_DIM)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 2_000) as f32 / 1_000.0 - 1.0
+        })
+        .collect()
+}
+
+fn vector_sizes() -> Vec<usize> {
+    match std::env::var("STRATA_BENCH_VECTOR_SIZES") {
+        Ok(sizes) => sizes
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect(),
+        Err(_) => DEFAULT_VECTOR_SIZES.to_vec(),
+    }
+}
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+fn bench_kv_put(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/kv_put");
+    group.throughput(Throughput::Elements(1));
+
+    for mode in Mode::ALL {
+        let (db, _temp) = mode.open();
+        let kv = KVStore::new(db);
+        let branch_id = BranchId::new();
+
+        for size in KEY_SIZES {
+            let counter = AtomicU64::new(0);
+            group.bench_with_input(
+                BenchmarkId::new(mode.name(), format!("key_{}b", size)),
+                &size,
+                |b, &size| {
+                    b.iter(|| {
+                        let i = counter.fetch_add(1, Ordering::Relaxed);
+                        kv.put(
+                            &branch_id,
+                            "default",
+                            &sized_key(i, size),
+                            Value::Int(i as i64),
+                        )
+                        .unwrap()
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_kv_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/kv_get");
+    group.throughput(Throughput::Elements(1));
+
+    for mode in Mode::ALL {
+        let (db, _temp) = mode.open();
+        let kv = KVStore::new(db);
+        let branch_id = BranchId::new();
+
+        for size in KEY_SIZES {
+            let keys: Vec<String> = (0..KV_GET_KEYS as u64)
+                .map(|i| sized_key(i, size))
+                .collect();
+            for (i, key) in keys.iter().enumerate() {
+                kv.put(&branch_id, "default", key, Value::Int(i as i64))
+                    .unwrap();
+            }
+
+            let counter = AtomicU64::new(0);
+            group.bench_with_input(
+                BenchmarkId::new(mode.name(), format!("key_{}b", size)),
+                &keys,
+                |b, keys| {
+                    b.iter(|| {
+                        let i = counter.fetch_add(1, Ordering::Relaxed) as usize % keys.len();
+                        kv.get(&branch_id, "default", &keys[i]).unwrap()
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+fn bench_json_set_path(c: &mut Criterion) {
+    let (db, _temp) = Mode::Standard.open();
+    let json = JsonStore::new(db);
+    let branch_id = BranchId::new();
+
+    json.create(
+        &branch_id,
+        "default",
+        "doc",
+        JsonValue::from(serde_json::json!({
+            "user": { "profile": { "name": "bench", "visits": 0 } }
+        })),
+    )
+    .unwrap();
+    let path: JsonPath = "user.profile.visits".parse().unwrap();
+
+    let mut group = c.benchmark_group("regression/json");
+    group.throughput(Throughput::Elements(1));
+
+    let counter = AtomicU64::new(0);
+    group.bench_function("set_path", |b| {
+        b.iter(|| {
+            let i = counter.fetch_add(1, Ordering::Relaxed);
+            json.set(
+                &branch_id,
+                "default",
+                "doc",
+                &path,
+                JsonValue::from(serde_json::json!(i)),
+            )
+            .unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn bench_event_append(c: &mut Criterion) {
+    let (db, _temp) = Mode::Standard.open();
+    let event_log = EventLog::new(db);
+    let branch_id = BranchId::new();
+
+    let mut group = c.benchmark_group("regression/event");
+    group.throughput(Throughput::Elements(1));
+
+    let counter = AtomicU64::new(0);
+    group.bench_function("append", |b| {
+        b.iter(|| {
+            let i = counter.fetch_add(1, Ordering::Relaxed);
+            event_log
+                .append(
+                    &branch_id,
+                    "default",
+                    "bench",
+                    Value::Object(HashMap::from([("seq".to_string(), Value::Int(i as i64))])),
+                )
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn bench_vector_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("regression/vector_search");
+    group.throughput(Throughput::Elements(1));
+    group.sample_size(20);
+    group.measurement_time(Duration::from_secs(10));
+
+    for size in vector_sizes() {
+        // Populate in memory: the group measures search, not persistence.
+        let (db, _temp) = Mode::Cache.open();
+        let vectors = VectorStore::new(db);
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(VECTOR_DIM, DistanceMetric::Cosine).unwrap();
+        vectors
+            .create_collection(branch_id, "default", "bench", config)
+            .unwrap();
+
+        let mut start = 0;
+        while start < size {
+            let end = (start + VECTOR_INSERT_CHUNK).min(size);
+            let entries = (start..end)
+                .map(|i| (format!("v{}", i), bench_vector(i as u64), None))
+                .collect();
+            vectors
+                .batch_insert(branch_id, "default", "bench", entries)
+                .unwrap();
+            start = end;
+        }
+
+        let counter = AtomicU64::new(0);
+        group.bench_with_input(BenchmarkId::new("k10", size), &size, |b, _| {
+            b.iter(|| {
+                let seed = u64::MAX - counter.fetch_add(1, Ordering::Relaxed);
+                vectors
+                    .search(branch_id, "default", "bench", &bench_vector(seed), 10, None)
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    regression,
+    bench_kv_put,
+    bench_kv_get,
+    bench_json_set_path,
+    bench_event_append,
+    bench_vector_search,
+);
+
+/// Average allocations per KV put for each `regression/kv_put` case.
+///
+/// Background threads (e.g. WAL flushing) are counted too, so treat the
+/// numbers as comparable between runs rather than exact.
+fn measure_kv_put_allocations() -> Vec<serde_json::Value> {
+    let mut results = Vec::new();
+    for mode in Mode::ALL {
+        let (db, _temp) = mode.open();
+        let kv = KVStore::new(db);
+        let branch_id = BranchId::new();
+
+        for size in KEY_SIZES {
+            let keys: Vec<String> = (0..ALLOC_WARMUP_OPS + ALLOC_MEASURED_OPS)
+                .map(|i| sized_key(i, size))
+                .collect();
+            let put = |i: u64| {
+                kv.put(
+                    &branch_id,
+                    "default",
+                    &keys[i as usize],
+                    Value::Int(i as i64),
+                )
+                .unwrap();
+            };
+
+            (0..ALLOC_WARMUP_OPS).for_each(put);
+            let before = ALLOCATIONS.load(Ordering::Relaxed);
+            (ALLOC_WARMUP_OPS..ALLOC_WARMUP_OPS + ALLOC_MEASURED_OPS).for_each(put);
+            let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+            results.push(serde_json::json!({
+                "id": format!("regression/kv_put/{}/key_{}b", mode.name(), size),
+                "allocs_per_op": allocations as f64 / ALLOC_MEASURED_OPS as f64,
+            }));
+        }
+    }
+    results
+}
+
+// ============================================================================
+// JSON results
+// ============================================================================
+
+/// Resolve the directory criterion writes to, following criterion's own
+/// lookup order.
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        return PathBuf::from(target).join("criterion");
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../target/criterion")
+}
+
+/// Collect `new/benchmark.json` + `new/estimates.json` pairs for this suite.
+fn collect_results(dir: &Path, results: &mut Vec<serde_json::Value>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().is_some_and(|name| name == "new") {
+            if let Some(result) = read_result(&path) {
+                results.push(result);
+            }
+        } else {
+            collect_results(&path, results);
+        }
+    }
+}
+
+fn read_result(dir: &Path) -> Option<serde_json::Value> {
+    let read = |name: &str| -> Option<serde_json::Value> {
+        let bytes = std::fs::read(dir.join(name)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    };
+    let benchmark = read("benchmark.json")?;
+    if !benchmark["group_id"]
+        .as_str()
+        .is_some_and(|group| group.starts_with(GROUP_PREFIX))
+    {
+        return None;
+    }
+    let estimates = read("estimates.json")?;
+
+    Some(serde_json::json!({
+        "id": benchmark["full_id"],
+        "mean_ns": estimates["mean"]["point_estimate"],
+        "median_ns": estimates["median"]["point_estimate"],
+        "std_dev_ns": estimates["std_dev"]["point_estimate"],
+        "throughput": benchmark["throughput"],
+    }))
+}
+
+/// Write the suite summary. Each entry reflects the latest run of that
+/// benchmark, so filtered runs keep earlier results for the others.
+fn emit_json_results() {
+    let dir = criterion_dir();
+    let mut results = Vec::new();
+    collect_results(&dir, &mut results);
+    if results.is_empty() {
+        return;
+    }
+    results.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+
+    let summary = serde_json::json!({
+        "suite": "regression",
+        "version": env!("CARGO_PKG_VERSION"),
+        "benchmarks": results,
+        "allocations": measure_kv_put_allocations(),
+    });
+    let out = std::env::var_os("STRATA_BENCH_JSON")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dir.join("regression_summary.json"));
+    match std::fs::write(&out, serde_json::to_vec_pretty(&summary).unwrap()) {
+        Ok(()) => println!("Wrote benchmark summary to {}", out.display()),
+        Err(e) => eprintln!("Failed to write {}: {}", out.display(), e),
+    }
+}
+
+fn main() {
+    regression();
+    Criterion::default().configure_from_args().final_summary();
+    emit_json_results();
+}
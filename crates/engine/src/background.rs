@@ -0,0 +1,529 @@
+//! Background worker scheduling
+//!
+//! A database runs periodic background jobs: the WAL flush in standard
+//! durability mode, scheduled compaction and automatic checkpoints. By default each job gets its
+//! own named thread. Embedders that open many databases, or that need to
+//! name or pin threads, build one [`WorkerPool`] and open databases with
+//! [`Database::open_in_pool`](crate::Database::open_in_pool); every job then
+//! shares the pool's threads.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_engine::{Database, WorkerPool};
+//!
+//! let pool = WorkerPool::builder()
+//!     .threads(2)
+//!     .name_prefix("myapp-strata")
+//!     .on_thread_start(|index| pin_to_core(index))
+//!     .build()?;
+//!
+//! let a = Database::open_in_pool("/data/a", &pool)?;
+//! let b = Database::open_in_pool("/data/b", &pool)?;
+//! ```
+
+use parking_lot::{Condvar, Mutex};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{JoinHandle, ThreadId};
+use std::time::{Duration, Instant};
+use strata_core::{StrataError, StrataResult};
+
+/// Default number of threads in a [`WorkerPool`]
+pub const DEFAULT_WORKER_THREADS: usize = 2;
+
+/// Default thread name prefix for a [`WorkerPool`]
+pub const DEFAULT_WORKER_NAME_PREFIX: &str = "strata-worker";
+
+/// Longest a dedicated worker thread sleeps before checking for shutdown
+const MAX_TICK: Duration = Duration::from_millis(100);
+
+/// A periodic job; returns `false` to stop running
+type Task = Box<dyn FnMut() -> bool + Send>;
+
+/// Hook run on each pool thread before it takes jobs
+type ThreadStartHook = Arc<dyn Fn(usize) + Send + Sync>;
+
+/// Shared thread pool for database background jobs
+///
+/// Cheap to clone; clones share the same threads. Each database opened in
+/// the pool holds a clone, so the threads stay up until the last database
+/// and the last handle are dropped.
+#[derive(Clone)]
+pub struct WorkerPool {
+    inner: Arc<PoolThreads>,
+}
+
+impl std::fmt::Debug for WorkerPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerPool")
+            .field("threads", &self.threads())
+            .field("jobs", &self.jobs())
+            .finish()
+    }
+}
+
+impl WorkerPool {
+    /// Start configuring a pool
+    pub fn builder() -> WorkerPoolBuilder {
+        WorkerPoolBuilder::default()
+    }
+
+    /// Number of threads in the pool
+    pub fn threads(&self) -> usize {
+        self.inner.handles.lock().len()
+    }
+
+    /// Number of jobs scheduled on the pool
+    pub fn jobs(&self) -> usize {
+        self.inner.shared.state.lock().jobs.len()
+    }
+
+    /// Run `task` every `interval` on the pool until it returns `false` or
+    /// the returned job is stopped or dropped
+    ///
+    /// Lets embedders run their own periodic work alongside the databases'
+    /// jobs. `name` identifies the job in logs.
+    pub fn schedule(
+        &self,
+        name: &str,
+        interval: Duration,
+        task: impl FnMut() -> bool + Send + 'static,
+    ) -> PoolJob {
+        let shared = &self.inner.shared;
+        let mut state = shared.state.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.jobs.insert(
+            id,
+            Job {
+                name: name.to_string(),
+                interval,
+                next_run: Instant::now() + interval,
+                task: Some(Box::new(task)),
+                running_on: None,
+                cancelled: false,
+            },
+        );
+        shared.wake.notify_all();
+        PoolJob {
+            shared: Arc::clone(shared),
+            id,
+            _pool: self.clone(),
+        }
+    }
+}
+
+/// Builder for [`WorkerPool`]
+pub struct WorkerPoolBuilder {
+    threads: usize,
+    name_prefix: String,
+    on_thread_start: Option<ThreadStartHook>,
+}
+
+impl Default for WorkerPoolBuilder {
+    fn default() -> Self {
+        Self {
+            threads: DEFAULT_WORKER_THREADS,
+            name_prefix: DEFAULT_WORKER_NAME_PREFIX.to_string(),
+            on_thread_start: None,
+        }
+    }
+}
+
+impl WorkerPoolBuilder {
+    /// Set the number of threads (default 2)
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Set the thread name prefix; threads are named `<prefix>-<index>`
+    pub fn name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.name_prefix = prefix.into();
+        self
+    }
+
+    /// Run `hook` on each pool thread, with its index, before it takes jobs
+    ///
+    /// Use it to pin threads to cores or set their priority.
+    pub fn on_thread_start(mut self, hook: impl Fn(usize) + Send + Sync + 'static) -> Self {
+        self.on_thread_start = Some(Arc::new(hook));
+        self
+    }
+
+    /// Spawn the pool's threads
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `threads` is zero or a thread cannot be spawned.
+    pub fn build(self) -> StrataResult<WorkerPool> {
+        if self.threads == 0 {
+            return Err(StrataError::invalid_input(
+                "worker pool needs at least one thread".to_string(),
+            ));
+        }
+
+        let shared = Arc::new(PoolShared {
+            state: Mutex::new(PoolState {
+                jobs: HashMap::new(),
+                next_id: 0,
+                shutdown: false,
+            }),
+            wake: Condvar::new(),
+        });
+        let pool = PoolThreads {
+            shared: Arc::clone(&shared),
+            handles: Mutex::new(Vec::with_capacity(self.threads)),
+        };
+
+        for index in 0..self.threads {
+            let shared = Arc::clone(&shared);
+            let hook = self.on_thread_start.clone();
+            let handle = std::thread::Builder::new()
+                .name(format!("{}-{}", self.name_prefix, index))
+                .spawn(move || {
+                    if let Some(hook) = hook {
+                        hook(index);
+                    }
+                    shared.run();
+                })
+                .map_err(|e| {
+                    StrataError::internal(format!("failed to spawn worker thread: {}", e))
+                })?;
+            pool.handles.lock().push(handle);
+        }
+
+        Ok(WorkerPool {
+            inner: Arc::new(pool),
+        })
+    }
+}
+
+/// The pool's threads; stops and joins them when the last handle drops
+struct PoolThreads {
+    shared: Arc<PoolShared>,
+    handles: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Drop for PoolThreads {
+    fn drop(&mut self) {
+        self.shared.state.lock().shutdown = true;
+        self.shared.wake.notify_all();
+        let current = std::thread::current().id();
+        for handle in self.handles.lock().drain(..) {
+            // A job may drop the last database, and with it the pool, on a
+            // pool thread, which must not join itself.
+            if handle.thread().id() != current {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+struct PoolShared {
+    state: Mutex<PoolState>,
+    /// Signals new jobs, finished runs and shutdown
+    wake: Condvar,
+}
+
+struct PoolState {
+    jobs: HashMap<u64, Job>,
+    next_id: u64,
+    shutdown: bool,
+}
+
+struct Job {
+    name: String,
+    interval: Duration,
+    next_run: Instant,
+    /// Taken out while the job runs
+    task: Option<Task>,
+    running_on: Option<ThreadId>,
+    cancelled: bool,
+}
+
+impl PoolShared {
+    /// Worker thread loop: run the earliest due job, or wait for one
+    fn run(&self) {
+        let mut state = self.state.lock();
+        while !state.shutdown {
+            let next = state
+                .jobs
+                .iter()
+                .filter(|(_, job)| job.task.is_some())
+                .min_by_key(|(_, job)| job.next_run)
+                .map(|(id, job)| (*id, job.next_run));
+
+            let id = match next {
+                None => {
+                    self.wake.wait(&mut state);
+                    continue;
+                }
+                Some((_, at)) if at > Instant::now() => {
+                    self.wake.wait_until(&mut state, at);
+                    continue;
+                }
+                Some((id, _)) => id,
+            };
+
+            let job = state.jobs.get_mut(&id).expect("job is scheduled");
+            let mut task = job.task.take().expect("job is idle");
+            job.running_on = Some(std::thread::current().id());
+            let name = job.name.clone();
+
+            let keep = parking_lot::MutexGuard::unlocked(&mut state, || {
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(&mut task))
+                    .unwrap_or_else(|_| {
+                        tracing::error!(target: "strata::db", job = %name, "Background job panicked");
+                        false
+                    })
+            });
+
+            let finished = match state.jobs.get_mut(&id) {
+                Some(job) if keep && !job.cancelled => {
+                    job.running_on = None;
+                    job.next_run = Instant::now() + job.interval;
+                    job.task = Some(task);
+                    None
+                }
+                Some(_) => {
+                    state.jobs.remove(&id);
+                    Some(task)
+                }
+                None => Some(task),
+            };
+            self.wake.notify_all();
+            // Tasks may own databases, whose drop stops jobs on this pool
+            if let Some(task) = finished {
+                parking_lot::MutexGuard::unlocked(&mut state, || drop(task));
+            }
+        }
+    }
+}
+
+/// A job scheduled with [`WorkerPool::schedule`]
+///
+/// Dropping it stops the job.
+pub struct PoolJob {
+    shared: Arc<PoolShared>,
+    id: u64,
+    /// Keeps the threads alive while the job is scheduled
+    _pool: WorkerPool,
+}
+
+impl std::fmt::Debug for PoolJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolJob").field("id", &self.id).finish()
+    }
+}
+
+impl PoolJob {
+    /// Stop the job, waiting for a run in progress on another thread
+    ///
+    /// A job stopping itself from inside its task returns without waiting.
+    pub fn stop(self) {}
+}
+
+impl Drop for PoolJob {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock();
+        let Some(job) = state.jobs.get_mut(&self.id) else {
+            return;
+        };
+        match job.running_on {
+            None => {
+                let job = state.jobs.remove(&self.id);
+                drop(state);
+                drop(job);
+            }
+            Some(thread) => {
+                job.cancelled = true;
+                // A job that stops itself finishes when it returns
+                if thread != std::thread::current().id() {
+                    while state.jobs.contains_key(&self.id) {
+                        self.shared.wake.wait(&mut state);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A periodic background job, on its own thread or on a [`WorkerPool`]
+pub(crate) enum Worker {
+    Thread {
+        handle: JoinHandle<()>,
+        shutdown: Arc<AtomicBool>,
+    },
+    Pooled(PoolJob),
+}
+
+impl Worker {
+    /// Run `task` every `interval` until it returns `false` or the worker is
+    /// stopped
+    ///
+    /// Without a pool, spawns a thread named `name`.
+    pub(crate) fn spawn(
+        pool: Option<&WorkerPool>,
+        name: &str,
+        interval: Duration,
+        mut task: impl FnMut() -> bool + Send + 'static,
+    ) -> StrataResult<Self> {
+        if let Some(pool) = pool {
+            return Ok(Worker::Pooled(pool.schedule(name, interval, task)));
+        }
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let tick = interval.min(MAX_TICK);
+        let handle = std::thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                let mut next_run = Instant::now() + interval;
+                while !stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(tick);
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if Instant::now() < next_run {
+                        continue;
+                    }
+                    if !task() {
+                        break;
+                    }
+                    next_run = Instant::now() + interval;
+                }
+            })
+            .map_err(|e| {
+                StrataError::internal(format!("failed to spawn {} thread: {}", name, e))
+            })?;
+        Ok(Worker::Thread { handle, shutdown })
+    }
+
+    /// Whether the worker owns a dedicated thread
+    pub(crate) fn is_thread(&self) -> bool {
+        matches!(self, Worker::Thread { .. })
+    }
+
+    /// Stop the worker, waiting for a run in progress to finish
+    ///
+    /// A worker stopping itself (its task dropped the last reference to the
+    /// owner) returns without waiting.
+    pub(crate) fn stop(self) {
+        match self {
+            Worker::Thread { handle, shutdown } => {
+                shutdown.store(true, Ordering::SeqCst);
+                if handle.thread().id() != std::thread::current().id() {
+                    let _ = handle.join();
+                }
+            }
+            Worker::Pooled(job) => job.stop(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn wait_for(mut condition: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "timed out");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_pool_names_threads_and_runs_start_hook() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let hook_started = Arc::clone(&started);
+        let pool = WorkerPool::builder()
+            .threads(3)
+            .name_prefix("test-pool")
+            .on_thread_start(move |index| {
+                let name = std::thread::current().name().unwrap().to_string();
+                hook_started.lock().push((index, name));
+            })
+            .build()
+            .unwrap();
+        assert_eq!(pool.threads(), 3);
+
+        wait_for(|| started.lock().len() == 3);
+        let mut started = started.lock().clone();
+        started.sort();
+        assert_eq!(
+            started,
+            vec![
+                (0, "test-pool-0".to_string()),
+                (1, "test-pool-1".to_string()),
+                (2, "test-pool-2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pool_rejects_zero_threads() {
+        assert!(WorkerPool::builder().threads(0).build().is_err());
+    }
+
+    #[test]
+    fn test_pooled_jobs_share_threads_and_stop() {
+        let pool = WorkerPool::builder().threads(1).build().unwrap();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let workers: Vec<Worker> = (0..4)
+            .map(|_| {
+                let runs = Arc::clone(&runs);
+                Worker::spawn(Some(&pool), "job", Duration::from_millis(1), move || {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    true
+                })
+                .unwrap()
+            })
+            .collect();
+        assert!(workers.iter().all(|w| !w.is_thread()));
+        assert_eq!(pool.jobs(), 4);
+
+        wait_for(|| runs.load(Ordering::SeqCst) >= 20);
+        for worker in workers {
+            worker.stop();
+        }
+        assert_eq!(pool.jobs(), 0);
+
+        let after_stop = runs.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(runs.load(Ordering::SeqCst), after_stop);
+    }
+
+    #[test]
+    fn test_job_returning_false_is_unscheduled() {
+        let pool = WorkerPool::builder().threads(1).build().unwrap();
+        let worker =
+            Worker::spawn(Some(&pool), "once", Duration::from_millis(1), || false).unwrap();
+        wait_for(|| pool.jobs() == 0);
+        worker.stop();
+    }
+
+    #[test]
+    fn test_dedicated_thread_without_pool() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        let worker = Worker::spawn(
+            None,
+            "test-dedicated",
+            Duration::from_millis(1),
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                true
+            },
+        )
+        .unwrap();
+        assert!(worker.is_thread());
+        wait_for(|| runs.load(Ordering::SeqCst) >= 3);
+        worker.stop();
+    }
+}
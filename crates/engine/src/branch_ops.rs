@@ -5,37 +5,56 @@
 //!
 //! ## Operations
 //!
-//! - `fork_branch` — Create a copy of a branch with all its data
+//! - `fork_branch` — Create a copy-on-write fork of a branch
 //! - `diff_branches` — Compare two branches and return structured differences
 //! - `merge_branches` — Merge data from one branch into another
+//! - `schema_log` — List the schema-affecting operations made on a branch
 
 use crate::database::Database;
 use crate::primitives::branch::resolve_branch_name;
 use crate::BranchIndex;
 use crate::SpaceIndex;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use strata_core::primitives::vector::{DistanceMetric, StorageDtype};
+use strata_core::traits::Storage;
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
 use strata_core::PrimitiveType;
 use strata_core::StrataError;
 use strata_core::StrataResult;
+use strata_storage::ShardedStore;
 use tracing::info;
 
 // =============================================================================
 // Data TypeTags to scan (all user data types)
 // =============================================================================
 
-const DATA_TYPE_TAGS: [TypeTag; 6] = [
+const DATA_TYPE_TAGS: [TypeTag; 7] = [
     TypeTag::KV,
     TypeTag::Event,
     TypeTag::State,
+    TypeTag::List,
     TypeTag::Json,
     TypeTag::Vector,
     TypeTag::VectorConfig,
 ];
 
+/// TypeTags counted by fork: user data plus custom primitive entries, which
+/// have no `PrimitiveType` and so stay out of diff and merge
+const FORK_TYPE_TAGS: [TypeTag; 8] = [
+    TypeTag::KV,
+    TypeTag::Event,
+    TypeTag::State,
+    TypeTag::List,
+    TypeTag::Json,
+    TypeTag::Vector,
+    TypeTag::VectorConfig,
+    TypeTag::Custom,
+];
+
 // =============================================================================
 // Public result types
 // =============================================================================
@@ -47,9 +66,10 @@ pub struct ForkInfo {
     pub source: String,
     /// Destination branch name
     pub destination: String,
-    /// Number of keys copied
+    /// Number of keys the destination starts with, shared with the source
+    /// until either branch writes them
     pub keys_copied: u64,
-    /// Number of spaces copied
+    /// Number of spaces the destination starts with
     pub spaces_copied: u64,
 }
 
@@ -136,6 +156,35 @@ pub struct ConflictEntry {
     pub source_value: String,
     /// Value in the target branch
     pub target_value: String,
+    /// How source changed the entry since the merge base (`None` when the
+    /// branches have no common ancestor)
+    pub source_change: Option<EntryChange>,
+    /// How target changed the entry since the merge base (`None` when the
+    /// branches have no common ancestor)
+    pub target_change: Option<EntryChange>,
+}
+
+/// How a branch changed an entry since the merge base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryChange {
+    /// Not in the merge base, present in the branch
+    Added,
+    /// In both, with a different value in the branch
+    Modified,
+    /// In the merge base, deleted in the branch
+    Deleted,
+}
+
+impl EntryChange {
+    /// Classify the change from `base` to `value`, if there is one
+    fn between(base: &Option<Value>, value: &Option<Value>) -> Option<Self> {
+        match (base, value) {
+            (None, Some(_)) => Some(EntryChange::Added),
+            (Some(_), None) => Some(EntryChange::Deleted),
+            (Some(b), Some(v)) if b != v => Some(EntryChange::Modified),
+            _ => None,
+        }
+    }
 }
 
 /// Information returned after merging branches.
@@ -147,10 +196,14 @@ pub struct MergeInfo {
     pub target: String,
     /// Number of keys written to target
     pub keys_applied: u64,
+    /// Number of keys deleted from target because source deleted them
+    pub keys_deleted: u64,
     /// Conflicts encountered (empty for LWW, populated for Strict failures)
     pub conflicts: Vec<ConflictEntry>,
     /// Number of spaces merged
     pub spaces_merged: u64,
+    /// Common ancestor the merge compared both branches against, if any
+    pub merge_base: Option<String>,
 }
 
 // =============================================================================
@@ -163,6 +216,7 @@ fn type_tag_to_primitive(tag: TypeTag) -> PrimitiveType {
         TypeTag::KV => PrimitiveType::Kv,
         TypeTag::Event => PrimitiveType::Event,
         TypeTag::State => PrimitiveType::State,
+        TypeTag::List => PrimitiveType::List,
         TypeTag::Json => PrimitiveType::Json,
         TypeTag::Vector | TypeTag::VectorConfig => PrimitiveType::Vector,
         _ => PrimitiveType::Kv, // fallback for Branch/Space/Trace metadata tags
@@ -201,11 +255,13 @@ fn resolve_and_verify(db: &Arc<Database>, name: &str) -> StrataResult<BranchId>
 // Fork
 // =============================================================================
 
-/// Fork a branch, creating a complete copy of all its data.
+/// Fork a branch as a copy-on-write view of its data.
 ///
-/// Creates a new branch with `destination` name and copies all data
-/// (KV, Event, State, JSON, Vector, VectorConfig) from `source` to it,
-/// preserving space organization.
+/// Creates a new branch with `destination` name that reads the data of
+/// `source` (KV, Event, State, JSON, Vector, VectorConfig, spaces) as of
+/// the fork, without copying it. Storage keeps the source versions the
+/// fork reads; a key gets its own version in the fork the first time the
+/// fork writes it, and later writes to `source` are not seen by the fork.
 ///
 /// # Errors
 ///
@@ -228,62 +284,32 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
         )));
     }
 
-    // 3. Create destination branch
-    branch_index.create_branch(destination)?;
+    // 3. Create destination branch, recording the version it forks at
+    let storage = db.storage();
+    let fork_version = storage.version();
+    branch_index.create_fork(destination, source, fork_version)?;
 
     // 4. Resolve BranchIds
     let source_id = resolve_branch_name(source);
     let dest_id = resolve_branch_name(destination);
 
-    // 5. List source spaces and register them in destination
-    let source_spaces = space_index.list(source_id)?;
-    let mut spaces_copied = 0u64;
-
-    for space in &source_spaces {
-        if space != "default" {
-            space_index.register(dest_id, space)?;
-        }
-        spaces_copied += 1;
-    }
-
-    // 6. Scan all source data and copy to destination
-    let storage = db.storage();
-    let mut keys_copied = 0u64;
+    // 5. Link the destination to the source in storage. Reads cached under
+    // the destination's name before the fork are stale now.
+    storage.fork_branch(source_id, dest_id, fork_version)?;
+    db.clear_read_cache();
 
-    for type_tag in DATA_TYPE_TAGS {
-        let entries = storage.list_by_type(&source_id, type_tag);
-
-        if entries.is_empty() {
-            continue;
-        }
-
-        // Batch write all entries for this type tag
-        let batch: Vec<(Key, Value)> = entries
-            .into_iter()
-            .map(|(key, vv)| {
-                // Rewrite key with destination namespace (preserving space and user_key)
-                let new_ns = Namespace::for_branch_space(dest_id, &key.namespace.space);
-                let new_key = Key::new(new_ns, key.type_tag, key.user_key.clone());
-                (new_key, vv.value)
-            })
-            .collect();
-
-        let batch_len = batch.len() as u64;
-
-        db.transaction(dest_id, |txn| {
-            for (key, value) in &batch {
-                txn.put(key.clone(), value.clone())?;
-            }
-            Ok(())
-        })?;
-
-        keys_copied += batch_len;
-    }
+    // 6. Count what the destination starts with
+    let spaces_copied = space_index.list(dest_id)?.len() as u64;
+    let keys_copied = FORK_TYPE_TAGS
+        .iter()
+        .map(|type_tag| storage.count_by_type(&dest_id, *type_tag) as u64)
+        .sum();
 
     info!(
         target: "strata::branch_ops",
         source,
         destination,
+        fork_version,
         keys_copied,
         spaces_copied,
         "Branch forked"
@@ -297,6 +323,19 @@ pub fn fork_branch(db: &Arc<Database>, source: &str, destination: &str) -> Strat
     })
 }
 
+/// Relink the forks recorded in branch metadata after recovery.
+///
+/// Storage only holds what a fork has written itself, so this runs before
+/// anything reads branch data.
+pub(crate) fn restore_forks(db: &Database) -> StrataResult<()> {
+    let storage = db.storage();
+    for (child, parent, fork_version) in crate::primitives::branch::branch_fork_points(db) {
+        storage.fork_branch(parent, child, fork_version)?;
+    }
+    db.clear_read_cache();
+    Ok(())
+}
+
 // =============================================================================
 // Diff
 // =============================================================================
@@ -444,11 +483,23 @@ pub fn diff_branches(
 /// Uses `diff_branches(target, source)` to identify changes, then applies
 /// them to the target. Target is branch A (base), source is branch B (incoming).
 ///
-/// - **Added entries** (in source but not target): written to target
-/// - **Modified entries** (in both, different values):
-///   - `LastWriterWins`: source value overwrites target (appends new version)
-///   - `Strict`: merge fails with conflict list (no writes)
-/// - **Removed entries** (in target but not source): left unchanged
+/// When the branches share data with a common ancestor (one was forked
+/// from the other, or both from the same branch), the merge is three-way.
+/// Each differing entry is classified as added, modified or deleted on
+/// each side, relative to the ancestor at the version both branches
+/// started from:
+///
+/// - **Changed only in source**: the value (or delete) is applied to target
+/// - **Changed only in target**: target is left unchanged
+/// - **Changed in both**: a conflict, resolved by the strategy
+///
+/// Without a common ancestor the merge is two-way: entries only in source
+/// are written to target, entries only in target are left unchanged, and
+/// entries in both with different values conflict.
+///
+/// Conflicts depend on strategy:
+/// - `LastWriterWins`: source value overwrites target (appends new version)
+/// - `Strict`: merge fails with conflict list (no writes)
 ///
 /// Version history in the target is preserved — merged values are appended
 /// as new versions via `db.transaction()`.
@@ -468,22 +519,76 @@ pub fn merge_branches(
     // 1. Diff: target is A (base), source is B (incoming)
     let diff = diff_branches(db, target, source)?;
 
-    // 2. Check for conflicts in Strict mode
-    if strategy == MergeStrategy::Strict && diff.summary.total_modified > 0 {
-        let conflicts: Vec<ConflictEntry> = diff
-            .spaces
+    // 2. Resolve IDs and the common ancestor
+    let source_id = resolve_branch_name(source);
+    let target_id = resolve_branch_name(target);
+    let merge_base = find_merge_base(db, source, target)?;
+    let base = merge_base
+        .as_ref()
+        .map(|(name, version)| (resolve_branch_name(name), *version));
+    let storage = db.storage();
+
+    // 3. Decide what to write and delete in each space
+    let lww = strategy == MergeStrategy::LastWriterWins;
+    let mut conflicts: Vec<ConflictEntry> = Vec::new();
+    let mut plans: Vec<(&str, Vec<MergeWrite>)> = Vec::new();
+    for space_diff in &diff.spaces {
+        let space = space_diff.space.as_str();
+        let read = |branch: BranchId, entry: &BranchDiffEntry, version: u64| {
+            read_entry(storage, branch, space, entry, version)
+        };
+        let mut writes = Vec::new();
+        let entries = space_diff
+            .added
             .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect();
+            .chain(&space_diff.modified)
+            .chain(&space_diff.removed);
+        for entry in entries {
+            let source_value = read(source_id, entry, u64::MAX);
+            let target_value = read(target_id, entry, u64::MAX);
+            let Some(tag) = source_value
+                .as_ref()
+                .or(target_value.as_ref())
+                .map(|(t, _)| *t)
+            else {
+                continue;
+            };
+            let source_value = source_value.map(|(_, v)| v);
+            let target_value = target_value.map(|(_, v)| v);
+            let write = (tag, entry.raw_key.clone(), source_value.clone());
+            match base {
+                Some((base_id, version)) => {
+                    let base_value = read(base_id, entry, version).map(|(_, v)| v);
+                    let source_change = EntryChange::between(&base_value, &source_value);
+                    let target_change = EntryChange::between(&base_value, &target_value);
+                    match (source_change, target_change) {
+                        // Only target changed it
+                        (None, _) => {}
+                        (Some(_), None) => writes.push(write),
+                        // Both changed it, to different results
+                        (Some(_), Some(_)) => {
+                            conflicts.push(conflict_entry(entry, source_change, target_change));
+                            writes.push(write);
+                        }
+                    }
+                }
+                // Without an ancestor, new source entries are added and
+                // entries source lacks are kept
+                None => match (&source_value, &target_value) {
+                    (Some(_), None) => writes.push(write),
+                    (Some(_), Some(_)) => {
+                        conflicts.push(conflict_entry(entry, None, None));
+                        writes.push(write);
+                    }
+                    (None, _) => {}
+                },
+            }
+        }
+        plans.push((space, writes));
+    }
 
+    // 4. Check for conflicts in Strict mode
+    if !lww && !conflicts.is_empty() {
         return Err(StrataError::invalid_input(format!(
             "Merge conflict: {} keys differ between '{}' and '{}'. Use LastWriterWins strategy or resolve conflicts manually.",
             conflicts.len(),
@@ -492,104 +597,45 @@ pub fn merge_branches(
         )));
     }
 
-    // Collect conflicts for reporting (LWW resolves them, Strict already returned error)
-    let conflicts: Vec<ConflictEntry> = if strategy == MergeStrategy::LastWriterWins {
-        diff.spaces
-            .iter()
-            .flat_map(|sd| {
-                sd.modified.iter().map(|entry| ConflictEntry {
-                    key: entry.key.clone(),
-                    primitive: entry.primitive,
-                    space: entry.space.clone(),
-                    source_value: entry.value_b.clone().unwrap_or_default(),
-                    target_value: entry.value_a.clone().unwrap_or_default(),
-                })
-            })
-            .collect()
-    } else {
-        vec![]
-    };
-
-    // 3. Resolve IDs
-    let source_id = resolve_branch_name(source);
-    let target_id = resolve_branch_name(target);
-    let storage = db.storage();
-
+    // 5. Apply changes
     let mut keys_applied = 0u64;
+    let mut keys_deleted = 0u64;
     let mut spaces_merged = 0u64;
-
-    // 4. Apply changes
-    for space_diff in &diff.spaces {
-        let space = &space_diff.space;
-
+    for (space, writes) in plans {
         // Ensure target has this space
         if space != "default" {
             space_index.register(target_id, space)?;
         }
         spaces_merged += 1;
 
-        // Collect entries to write from source: added + modified (for LWW)
-        let entries_to_apply: Vec<&BranchDiffEntry> = space_diff
-            .added
-            .iter()
-            .chain(if strategy == MergeStrategy::LastWriterWins {
-                space_diff.modified.iter()
-            } else {
-                [].iter()
-            })
-            .collect();
-
-        if entries_to_apply.is_empty() {
+        if writes.is_empty() {
             continue;
         }
-
-        // Re-scan source data for this space to get actual values
-        // (diff only stores string representations)
-        let mut source_values: HashMap<(Vec<u8>, TypeTag), Value> = HashMap::new();
-        for type_tag in DATA_TYPE_TAGS {
-            let entries = storage.list_by_type(&source_id, type_tag);
-            for (key, vv) in entries {
-                if key.namespace.space == *space {
-                    source_values.insert((key.user_key.clone(), type_tag), vv.value);
-                }
-            }
-        }
-
-        // Write to target
-        let mut batch: Vec<(Key, Value)> = Vec::new();
-        for diff_entry in &entries_to_apply {
-            // Find the matching source value
-            for type_tag in DATA_TYPE_TAGS {
-                if type_tag_to_primitive(type_tag) == diff_entry.primitive {
-                    let user_key_bytes = diff_entry.raw_key.clone();
-                    if let Some(value) = source_values.get(&(user_key_bytes.clone(), type_tag)) {
-                        let target_ns = Namespace::for_branch_space(target_id, space);
-                        let target_key = Key::new(target_ns, type_tag, user_key_bytes);
-                        batch.push((target_key, value.clone()));
-                        break;
-                    }
+        let target_ns = Namespace::for_branch_space(target_id, space);
+        db.transaction(target_id, |txn| {
+            for (type_tag, user_key, value) in &writes {
+                let key = Key::new(target_ns.clone(), *type_tag, user_key.clone());
+                match value {
+                    Some(value) => txn.put(key, value.clone())?,
+                    None => txn.delete(key)?,
                 }
             }
-        }
-
-        let batch_len = batch.len() as u64;
-        if batch_len > 0 {
-            db.transaction(target_id, |txn| {
-                for (key, value) in &batch {
-                    txn.put(key.clone(), value.clone())?;
-                }
-                Ok(())
-            })?;
-            keys_applied += batch_len;
-        }
+            Ok(())
+        })?;
+        let deleted = writes.iter().filter(|(_, _, v)| v.is_none()).count() as u64;
+        keys_deleted += deleted;
+        keys_applied += writes.len() as u64 - deleted;
     }
 
+    let merge_base = merge_base.map(|(name, _)| name);
     info!(
         target: "strata::branch_ops",
         source,
         target,
         keys_applied,
+        keys_deleted,
         spaces_merged,
+        merge_base = merge_base.as_deref(),
         strategy = ?strategy,
         "Branches merged"
     );
@@ -598,11 +644,162 @@ pub fn merge_branches(
         source: source.to_string(),
         target: target.to_string(),
         keys_applied,
+        keys_deleted,
         conflicts,
         spaces_merged,
+        merge_base,
     })
 }
 
+/// A merge write to the target: a user key and the value to put, or
+/// `None` to delete it
+type MergeWrite = (TypeTag, Vec<u8>, Option<Value>);
+
+/// Find the closest common ancestor two branches still share data with,
+/// and the newest version of it both started from
+///
+/// A branch counts as its own ancestor, so merging a fork into its parent
+/// compares against the parent at the fork version.
+fn find_merge_base(
+    db: &Arc<Database>,
+    source: &str,
+    target: &str,
+) -> StrataResult<Option<(String, u64)>> {
+    let branch_index = BranchIndex::new(db.clone());
+    let source_lineage = branch_index.shared_lineage(source)?;
+    let target_lineage = branch_index.shared_lineage(target)?;
+    Ok(source_lineage.into_iter().find_map(|(name, version)| {
+        target_lineage
+            .iter()
+            .find(|(other, _)| *other == name)
+            .map(|(_, other_version)| (name, version.min(*other_version)))
+    }))
+}
+
+/// Read the value of a diff entry in `branch` as of `version`
+fn read_entry(
+    storage: &ShardedStore,
+    branch: BranchId,
+    space: &str,
+    entry: &BranchDiffEntry,
+    version: u64,
+) -> Option<(TypeTag, Value)> {
+    let ns = Namespace::for_branch_space(branch, space);
+    DATA_TYPE_TAGS
+        .into_iter()
+        .filter(|tag| type_tag_to_primitive(*tag) == entry.primitive)
+        .find_map(|tag| {
+            let key = Key::new(ns.clone(), tag, entry.raw_key.clone());
+            let found = storage.get_versioned(&key, version).ok().flatten()?;
+            Some((tag, found.value))
+        })
+}
+
+/// Report a diff entry as a merge conflict
+fn conflict_entry(
+    entry: &BranchDiffEntry,
+    source_change: Option<EntryChange>,
+    target_change: Option<EntryChange>,
+) -> ConflictEntry {
+    ConflictEntry {
+        key: entry.key.clone(),
+        primitive: entry.primitive,
+        space: entry.space.clone(),
+        source_value: entry.value_b.clone().unwrap_or_default(),
+        target_value: entry.value_a.clone().unwrap_or_default(),
+        source_change,
+        target_change,
+    }
+}
+
+// =============================================================================
+// Schema Log
+// =============================================================================
+
+/// User-key prefix of schema log entries
+pub(crate) const SCHEMA_LOG_PREFIX: &[u8] = b"__schema__";
+
+/// What a schema log entry changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum SchemaChangeKind {
+    /// A vector collection was created
+    CollectionCreated {
+        /// Embedding dimension
+        dimension: usize,
+        /// Distance metric
+        metric: DistanceMetric,
+        /// Storage data type of the index
+        dtype: StorageDtype,
+    },
+    /// A vector collection and all its vectors were dropped
+    CollectionDropped {
+        /// Dimension the collection had
+        dimension: usize,
+    },
+    /// A vector collection's index was rebuilt, possibly with a new metric
+    /// or backend
+    IndexRebuilt {
+        /// Distance metric after the rebuild
+        metric: DistanceMetric,
+        /// Index backend after the rebuild (`brute_force` or `hnsw`)
+        index: String,
+    },
+}
+
+/// One schema-affecting operation recorded in a branch's schema log.
+///
+/// Entries are written in the same transaction as the change they
+/// describe, so the log never disagrees with the stored configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    /// When the change was made (microseconds since epoch)
+    pub timestamp: u64,
+    /// Space the collection lives in
+    pub space: String,
+    /// Collection name
+    pub collection: String,
+    /// What changed
+    #[serde(flatten)]
+    pub kind: SchemaChangeKind,
+}
+
+impl SchemaChange {
+    /// Storage key and value for this entry on `branch_id`.
+    #[cfg(feature = "vectors")]
+    pub(crate) fn to_entry(&self, branch_id: BranchId) -> StrataResult<(Key, Value)> {
+        // Zero-padded timestamp first so entries scan in chronological order
+        let entry = format!("{:020}:{}:{}", self.timestamp, self.space, self.collection);
+        let value = serde_json::to_string(self)
+            .map(Value::String)
+            .map_err(|e| StrataError::serialization(e.to_string()))?;
+        Ok((Key::new_schema_log(branch_id, &entry), value))
+    }
+}
+
+/// List the schema-affecting operations made on a branch, oldest first.
+///
+/// Covers vector collection creation, drops and index rebuilds across all
+/// spaces. A forked branch starts with its parent's log.
+///
+/// # Errors
+///
+/// - Branch does not exist
+pub fn schema_log(db: &Arc<Database>, branch: &str) -> StrataResult<Vec<SchemaChange>> {
+    let branch_id = resolve_and_verify(db, branch)?;
+    let mut changes = db
+        .storage()
+        .list_by_prefix(&Key::new_schema_log_prefix(branch_id))
+        .into_iter()
+        .filter_map(|(_, vv)| match vv.value {
+            Value::String(s) => serde_json::from_str::<SchemaChange>(&s).ok(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    changes.sort_by_key(|change| change.timestamp);
+    Ok(changes)
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -826,6 +1023,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fork_shares_data_copy_on_write() {
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        write_kv(&db, "source", "default", "k2", Value::Int(2));
+
+        let info = fork_branch(&db, "source", "dest").unwrap();
+        assert_eq!(info.keys_copied, 2);
+        let dest_id = resolve_branch_name("dest");
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 0);
+
+        // Source writes after the fork stay out of it
+        write_kv(&db, "source", "default", "k1", Value::Int(10));
+        write_kv(&db, "source", "default", "k3", Value::Int(3));
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "k3"), None);
+
+        // Only the key the fork writes gets its own copy
+        write_kv(&db, "dest", "default", "k2", Value::Int(20));
+        assert_eq!(db.storage().branch_entry_count(&dest_id), 1);
+        assert_eq!(read_kv(&db, "source", "default", "k2"), Some(Value::Int(2)));
+
+        let diff = diff_branches(&db, "source", "dest").unwrap();
+        assert_eq!(diff.summary.total_added, 0);
+        assert_eq!(diff.summary.total_removed, 1);
+        assert_eq!(diff.summary.total_modified, 2);
+    }
+
+    #[test]
+    fn test_fork_invalidates_read_cache() {
+        use crate::primitives::KVStore;
+
+        let (_temp, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        db.set_read_cache_capacity(16);
+        let kv = KVStore::new(db.clone());
+        let dest_id = resolve_branch_name("dest");
+
+        // A miss cached before the fork is not served after it
+        assert!(kv
+            .get_versioned(&dest_id, "default", "k1")
+            .unwrap()
+            .is_none());
+        fork_branch(&db, "source", "dest").unwrap();
+        let value = kv.get_versioned(&dest_id, "default", "k1").unwrap();
+        assert_eq!(value.unwrap().value, Value::Int(1));
+
+        // Nor is data the fork inherited once it is deleted
+        BranchIndex::new(db.clone()).delete_branch("dest").unwrap();
+        assert!(kv
+            .get_versioned(&dest_id, "default", "k1")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_fork_survives_compaction_and_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            BranchIndex::new(db.clone())
+                .create_branch("source")
+                .unwrap();
+            write_kv(&db, "source", "default", "k1", Value::Int(1));
+            write_kv(&db, "source", "default", "k2", Value::Int(2));
+            fork_branch(&db, "source", "dest").unwrap();
+
+            write_kv(&db, "source", "default", "k1", Value::Int(10));
+            let dest_id = resolve_branch_name("dest");
+            db.transaction(dest_id, |txn| {
+                txn.delete(Key::new(
+                    Namespace::for_branch(dest_id),
+                    TypeTag::KV,
+                    b"k2".to_vec(),
+                ))
+            })
+            .unwrap();
+            db.compact().unwrap();
+            db.shutdown().unwrap();
+        }
+
+        let db = Database::open(temp_dir.path()).unwrap();
+        let dest_id = resolve_branch_name("dest");
+        assert!(db.storage().fork_point(&dest_id).is_some());
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        assert_eq!(read_kv(&db, "dest", "default", "k2"), None);
+        assert_eq!(
+            read_kv(&db, "source", "default", "k1"),
+            Some(Value::Int(10))
+        );
+        assert_eq!(read_kv(&db, "source", "default", "k2"), Some(Value::Int(2)));
+    }
+
+    #[test]
+    fn test_source_delete_after_fork_survives_compaction_and_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            BranchIndex::new(db.clone())
+                .create_branch("source")
+                .unwrap();
+            write_kv(&db, "source", "default", "gone", Value::Int(1));
+            fork_branch(&db, "source", "dest").unwrap();
+
+            let source_id = resolve_branch_name("source");
+            db.transaction(source_id, |txn| {
+                txn.delete(Key::new(
+                    Namespace::for_branch(source_id),
+                    TypeTag::KV,
+                    b"gone".to_vec(),
+                ))
+            })
+            .unwrap();
+            db.compact().unwrap();
+            db.shutdown().unwrap();
+        }
+
+        let db = Database::open(temp_dir.path()).unwrap();
+        assert_eq!(read_kv(&db, "source", "default", "gone"), None);
+        assert_eq!(read_kv(&db, "dest", "default", "gone"), Some(Value::Int(1)));
+    }
+
+    #[test]
+    fn test_delete_source_detaches_fork() {
+        let (temp_dir, db) = setup_with_branch("source");
+        write_kv(&db, "source", "default", "k1", Value::Int(1));
+        fork_branch(&db, "source", "dest").unwrap();
+
+        let branch_index = BranchIndex::new(db.clone());
+        branch_index.delete_branch("source").unwrap();
+        let dest_id = resolve_branch_name("dest");
+        assert_eq!(db.storage().fork_point(&dest_id), None);
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+        let meta = branch_index.get_branch("dest").unwrap().unwrap().value;
+        assert_eq!(meta.parent_branch.as_deref(), Some("source"));
+        assert!(meta.fork_version.is_some());
+        assert!(meta.detached);
+        assert!(branch_index.ancestry("dest").unwrap().is_empty());
+
+        // The copy is durable
+        db.shutdown().unwrap();
+        drop(branch_index);
+        drop(db);
+        let db = Database::open(temp_dir.path()).unwrap();
+        assert_eq!(read_kv(&db, "dest", "default", "k1"), Some(Value::Int(1)));
+    }
+
     // =========================================================================
     // Diff Tests
     // =========================================================================
@@ -1133,4 +1477,233 @@ mod tests {
         assert_eq!(info.conflicts[0].key, "shared");
         assert_eq!(info.conflicts[0].primitive, PrimitiveType::Kv);
     }
+
+    #[test]
+    fn test_merge_fork_is_three_way() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "source_edit", Value::Int(1));
+        write_kv(&db, "main", "default", "target_edit", Value::Int(1));
+        write_kv(&db, "main", "default", "both_edit", Value::Int(1));
+        write_kv(&db, "main", "default", "source_delete", Value::Int(1));
+        write_kv(&db, "main", "default", "target_delete", Value::Int(1));
+        fork_branch(&db, "main", "feature").unwrap();
+
+        write_kv(&db, "feature", "default", "source_edit", Value::Int(2));
+        write_kv(&db, "main", "default", "target_edit", Value::Int(3));
+        write_kv(&db, "feature", "default", "both_edit", Value::Int(2));
+        write_kv(&db, "main", "default", "both_edit", Value::Int(3));
+        let delete = |branch: &str, key: &str| {
+            let branch_id = resolve_branch_name(branch);
+            db.transaction(branch_id, |txn| {
+                txn.delete(Key::new(
+                    Namespace::for_branch(branch_id),
+                    TypeTag::KV,
+                    key.as_bytes().to_vec(),
+                ))
+            })
+            .unwrap();
+        };
+        delete("feature", "source_delete");
+        delete("main", "target_delete");
+
+        // Only the key both branches changed conflicts
+        let err = merge_branches(&db, "feature", "main", MergeStrategy::Strict).unwrap_err();
+        assert!(err.to_string().contains("1 keys differ"), "{}", err);
+
+        let info = merge_branches(&db, "feature", "main", MergeStrategy::LastWriterWins).unwrap();
+        assert_eq!(info.merge_base.as_deref(), Some("main"));
+        assert_eq!(info.conflicts.len(), 1);
+        assert_eq!(info.conflicts[0].key, "both_edit");
+        assert_eq!(info.keys_applied, 2);
+        assert_eq!(info.keys_deleted, 1);
+        assert_eq!(
+            read_kv(&db, "main", "default", "source_edit"),
+            Some(Value::Int(2))
+        );
+        assert_eq!(
+            read_kv(&db, "main", "default", "target_edit"),
+            Some(Value::Int(3))
+        );
+        assert_eq!(
+            read_kv(&db, "main", "default", "both_edit"),
+            Some(Value::Int(2))
+        );
+        assert_eq!(read_kv(&db, "main", "default", "source_delete"), None);
+        assert_eq!(read_kv(&db, "main", "default", "target_delete"), None);
+    }
+
+    #[test]
+    fn test_merge_classifies_changes_on_each_side() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "same_edit", Value::Int(1));
+        write_kv(&db, "main", "default", "edit_vs_delete", Value::Int(1));
+        fork_branch(&db, "main", "a").unwrap();
+        fork_branch(&db, "main", "b").unwrap();
+
+        // The same change on both sides is not a conflict
+        write_kv(&db, "a", "default", "same_edit", Value::Int(2));
+        write_kv(&db, "b", "default", "same_edit", Value::Int(2));
+        write_kv(&db, "a", "default", "edit_vs_delete", Value::Int(2));
+        let b_id = resolve_branch_name("b");
+        db.transaction(b_id, |txn| {
+            txn.delete(Key::new(
+                Namespace::for_branch(b_id),
+                TypeTag::KV,
+                b"edit_vs_delete".to_vec(),
+            ))
+        })
+        .unwrap();
+        write_kv(&db, "a", "default", "both_added", Value::Int(1));
+        write_kv(&db, "b", "default", "both_added", Value::Int(2));
+
+        let info = merge_branches(&db, "a", "b", MergeStrategy::LastWriterWins).unwrap();
+        let mut kinds: Vec<(&str, Option<EntryChange>, Option<EntryChange>)> = info
+            .conflicts
+            .iter()
+            .map(|c| (c.key.as_str(), c.source_change, c.target_change))
+            .collect();
+        kinds.sort_by_key(|(key, _, _)| *key);
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    "both_added",
+                    Some(EntryChange::Added),
+                    Some(EntryChange::Added)
+                ),
+                (
+                    "edit_vs_delete",
+                    Some(EntryChange::Modified),
+                    Some(EntryChange::Deleted)
+                ),
+            ]
+        );
+        assert_eq!(
+            read_kv(&db, "b", "default", "edit_vs_delete"),
+            Some(Value::Int(2))
+        );
+    }
+
+    #[test]
+    fn test_merge_siblings_uses_common_ancestor() {
+        let (_temp, db) = setup_with_branch("main");
+        write_kv(&db, "main", "default", "k", Value::Int(1));
+        fork_branch(&db, "main", "a").unwrap();
+        fork_branch(&db, "main", "b").unwrap();
+        write_kv(&db, "a", "default", "k", Value::Int(2));
+
+        // Unrelated branches have no merge base
+        BranchIndex::new(db.clone()).create_branch("other").unwrap();
+        let info = merge_branches(&db, "other", "b", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.merge_base, None);
+
+        let info = merge_branches(&db, "a", "b", MergeStrategy::Strict).unwrap();
+        assert_eq!(info.merge_base.as_deref(), Some("main"));
+        assert!(info.conflicts.is_empty());
+        assert_eq!(read_kv(&db, "b", "default", "k"), Some(Value::Int(2)));
+    }
+
+    // =========================================================================
+    // Schema Log Tests
+    // =========================================================================
+
+    #[test]
+    fn test_schema_log_records_collection_changes() {
+        use crate::primitives::vector::{IndexBackendFactory, VectorConfig, VectorStore};
+
+        let (_temp, db) = setup_with_branch("main");
+        let branch_id = resolve_branch_name("main");
+        let store = VectorStore::new(db.clone());
+        store
+            .create_collection(branch_id, "default", "docs", VectorConfig::for_minilm())
+            .unwrap();
+        store
+            .reindex(
+                branch_id,
+                "default",
+                "docs",
+                Some(DistanceMetric::Euclidean),
+                Some(IndexBackendFactory::Hnsw(Default::default())),
+            )
+            .unwrap();
+        store
+            .delete_collection(branch_id, "default", "docs")
+            .unwrap();
+
+        let log = schema_log(&db, "main").unwrap();
+        let kinds: Vec<_> = log.iter().map(|c| c.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SchemaChangeKind::CollectionCreated {
+                    dimension: 384,
+                    metric: DistanceMetric::Cosine,
+                    dtype: StorageDtype::F32,
+                },
+                SchemaChangeKind::IndexRebuilt {
+                    metric: DistanceMetric::Euclidean,
+                    index: "hnsw".to_string(),
+                },
+                SchemaChangeKind::CollectionDropped { dimension: 384 },
+            ]
+        );
+        assert!(log
+            .iter()
+            .all(|c| c.space == "default" && c.collection == "docs"));
+        assert!(log.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+
+        // Failed operations leave no entry
+        assert!(store
+            .delete_collection(branch_id, "default", "docs")
+            .is_err());
+        assert_eq!(schema_log(&db, "main").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_schema_log_is_per_branch_and_inherited_by_fork() {
+        use crate::primitives::vector::{VectorConfig, VectorStore};
+
+        let (_temp, db) = setup_with_branch("main");
+        let store = VectorStore::new(db.clone());
+        store
+            .create_collection(
+                resolve_branch_name("main"),
+                "default",
+                "docs",
+                VectorConfig::for_minilm(),
+            )
+            .unwrap();
+        fork_branch(&db, "main", "child").unwrap();
+        store
+            .create_collection(
+                resolve_branch_name("child"),
+                "default",
+                "images",
+                VectorConfig::for_mpnet(),
+            )
+            .unwrap();
+
+        let main_log = schema_log(&db, "main").unwrap();
+        assert_eq!(main_log.len(), 1);
+        let child_log = schema_log(&db, "child").unwrap();
+        let names: Vec<_> = child_log.iter().map(|c| c.collection.as_str()).collect();
+        assert_eq!(names, vec!["docs", "images"]);
+
+        assert!(schema_log(&db, "missing").is_err());
+    }
+
+    #[test]
+    fn test_schema_log_hidden_from_kv_list() {
+        use crate::primitives::vector::{VectorConfig, VectorStore};
+        use crate::KVStore;
+
+        let (_temp, db) = setup_with_branch("main");
+        let branch_id = resolve_branch_name("main");
+        VectorStore::new(db.clone())
+            .create_collection(branch_id, "default", "docs", VectorConfig::for_minilm())
+            .unwrap();
+
+        let kv = KVStore::new(db.clone());
+        assert!(kv.list(&branch_id, "default", None).unwrap().is_empty());
+    }
 }
@@ -7,12 +7,34 @@
 //! ## Export
 //!
 //! Exports scan the KV store for all keys in a branch's namespace and
-//! reconstruct `BranchlogPayload` records grouped by version.
+//! reconstruct `BranchlogPayload` records grouped by version. Each payload
+//! is serialized and compressed to disk as it is built; progress can be
+//! observed with [`ExportOptions::with_progress`].
 //!
 //! ## Import
 //!
 //! Imports replay each `BranchlogPayload` as a transaction, writing puts
-//! and deletes into the target database.
+//! and deletes into the target database. Single-branch imports replay
+//! payloads as they are decompressed, and delete the new branch again if
+//! the bundle turns out to be corrupt partway through.
+//!
+//! ## Whole Databases
+//!
+//! [`export_all`] writes every branch into one `.databundle.tar.zst`
+//! archive; [`import_all`] restores them, resolving name clashes with an
+//! [`ImportConflict`] policy.
+//!
+//! ## Encryption
+//!
+//! Databases with encryption at rest export sealed bundles, which can only
+//! be imported into a database holding the same key.
+//!
+//! ## Environment
+//!
+//! Databases that capture their environment record it in every manifest they
+//! export, and each branch carries the environment it was created in. Imports
+//! log a warning for each incompatibility between the bundle's environment
+//! and the importing database's, and keep the branch's own environment.
 
 use crate::database::Database;
 use crate::BranchIndex;
@@ -25,8 +47,12 @@ use strata_core::types::{BranchId, Key, TypeTag};
 use strata_core::StrataError;
 use strata_core::StrataResult;
 use strata_durability::branch_bundle::{
-    BranchBundleReader, BranchBundleWriter, BranchlogPayload, BundleBranchInfo, ExportOptions,
+    BranchBundleError, BranchBundleReader, BranchBundleResult, BranchBundleWriter,
+    BranchlogPayload, BundleBranchInfo, BundleManifest, BundleVisitor, BundledBranch,
+    DatabaseBundleReader, DatabaseBundleWriter, EnvironmentInfo, ExportOptions,
 };
+use strata_durability::codec::AES_GCM_CODEC_ID;
+use tracing::warn;
 
 // =============================================================================
 // Public result types
@@ -54,6 +80,43 @@ pub struct ImportInfo {
     pub transactions_applied: u64,
     /// Total number of keys written
     pub keys_written: u64,
+    /// Name the branch had in the bundle, when imported under a new name
+    pub renamed_from: Option<String>,
+}
+
+/// Information returned after exporting a whole database
+#[derive(Debug, Clone)]
+pub struct DatabaseExportInfo {
+    /// Path where the bundle was written
+    pub path: PathBuf,
+    /// Names of the exported branches
+    pub branches: Vec<String>,
+    /// Number of transaction payloads across all branches
+    pub entry_count: u64,
+    /// Size of the bundle file in bytes
+    pub bundle_size: u64,
+}
+
+/// Information returned after importing a whole database
+#[derive(Debug, Clone)]
+pub struct DatabaseImportInfo {
+    /// Imported branches, under the names they were imported as
+    pub imported: Vec<ImportInfo>,
+    /// Bundled branches left out because they already existed
+    pub skipped: Vec<String>,
+}
+
+/// What [`import_all`] does with a bundled branch whose name already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportConflict {
+    /// Import nothing and fail if any bundled branch already exists
+    Fail,
+    /// Keep the existing branch and leave the bundled one out
+    Skip,
+    /// Import under `<name>-imported` (or `<name>-imported-N`)
+    Rename,
+    /// Delete the existing branch and all its data, then import
+    Overwrite,
 }
 
 /// Information about a bundle (from validation)
@@ -67,6 +130,8 @@ pub struct BundleInfo {
     pub entry_count: u64,
     /// Whether all checksums are valid
     pub checksums_valid: bool,
+    /// Environment of the exporting database, if it captured one
+    pub environment: Option<EnvironmentInfo>,
 }
 
 // =============================================================================
@@ -86,13 +151,80 @@ pub fn export_branch(db: &Arc<Database>, branch_id: &str, path: &Path) -> Strata
     export_branch_with_options(db, branch_id, path, &ExportOptions::default())
 }
 
-/// Export a branch with custom options (e.g., compression level)
+/// Export a branch with custom options (e.g., compression level, progress)
 pub fn export_branch_with_options(
     db: &Arc<Database>,
     branch_id: &str,
     path: &Path,
     options: &ExportOptions,
 ) -> StrataResult<ExportInfo> {
+    let (branch_info, core_branch_id) = bundle_branch_info(db, branch_id)?;
+    let version_groups = scan_version_groups(db, core_branch_id)?;
+
+    // Write bundle (sealed with the database codec when encrypted)
+    let writer = BranchBundleWriter::new(&with_environment(db, options));
+    let codec = db.codec();
+    let result = if codec.codec_id() == AES_GCM_CODEC_ID {
+        let payloads: Vec<_> = into_payloads(version_groups, branch_id).collect();
+        writer.write_encrypted(&branch_info, &payloads, path, codec.as_ref())
+    } else {
+        writer.write_stream(&branch_info, into_payloads(version_groups, branch_id), path)
+    };
+    let export_info =
+        result.map_err(|e| StrataError::storage(format!("Failed to write bundle: {}", e)))?;
+
+    Ok(ExportInfo {
+        branch_id: branch_id.to_string(),
+        path: export_info.path,
+        entry_count: export_info.wal_entry_count,
+        bundle_size: export_info.bundle_size_bytes,
+    })
+}
+
+/// Export every branch to one `.databundle.tar.zst` archive
+///
+/// Branches are exported whatever their state, in name order.
+pub fn export_all(db: &Arc<Database>, path: &Path) -> StrataResult<DatabaseExportInfo> {
+    let names = BranchIndex::new(db.clone()).list_branches()?;
+    let branches = names
+        .iter()
+        .map(|name| bundle_branch(db, name))
+        .collect::<StrataResult<Vec<_>>>()?;
+
+    let writer = DatabaseBundleWriter::new(&with_environment(db, &ExportOptions::default()));
+    let codec = db.codec();
+    let result = if codec.codec_id() == AES_GCM_CODEC_ID {
+        writer.write_encrypted(&branches, path, codec.as_ref())
+    } else {
+        writer.write(&branches, path)
+    };
+    let export_info =
+        result.map_err(|e| StrataError::storage(format!("Failed to write bundle: {}", e)))?;
+
+    Ok(DatabaseExportInfo {
+        path: export_info.path,
+        branches: names,
+        entry_count: export_info.wal_entry_count,
+        bundle_size: export_info.bundle_size_bytes,
+    })
+}
+
+/// Collect a branch's metadata and payloads for bundling
+fn bundle_branch(db: &Arc<Database>, branch_id: &str) -> StrataResult<BundledBranch> {
+    let (branch_info, core_branch_id) = bundle_branch_info(db, branch_id)?;
+    let payloads = into_payloads(scan_version_groups(db, core_branch_id)?, branch_id).collect();
+
+    Ok(BundledBranch {
+        branch_info,
+        payloads,
+    })
+}
+
+/// Build a branch's bundle metadata and resolve its storage namespace
+fn bundle_branch_info(
+    db: &Arc<Database>,
+    branch_id: &str,
+) -> StrataResult<(BundleBranchInfo, BranchId)> {
     let branch_index = BranchIndex::new(db.clone());
 
     // 1. Verify branch exists and get metadata
@@ -102,7 +234,7 @@ pub fn export_branch_with_options(
         .value;
 
     // 2. Build BundleBranchInfo from metadata
-    let bundle_branch_info = BundleBranchInfo {
+    let branch_info = BundleBranchInfo {
         branch_id: branch_meta.branch_id.clone(),
         name: branch_meta.name.clone(),
         state: branch_meta.status.as_str().to_lowercase(),
@@ -110,36 +242,27 @@ pub fn export_branch_with_options(
         closed_at: format_micros(branch_meta.completed_at.unwrap_or(branch_meta.updated_at)),
         parent_branch_id: branch_meta.parent_branch.clone(),
         error: branch_meta.error.clone(),
+        environment: branch_meta.environment.clone(),
     };
 
-    // 3. Scan storage for all branch data -> Vec<BranchlogPayload>
+    // 3. Resolve the namespace holding the branch data
     let core_branch_id = crate::primitives::branch::resolve_branch_name(&branch_meta.name);
 
-    let payloads = scan_branch_data(db, core_branch_id, branch_id)?;
-
-    // 4. Write bundle
-    let writer = BranchBundleWriter::new(options);
-    let export_info = writer
-        .write(&bundle_branch_info, &payloads, path)
-        .map_err(|e| StrataError::storage(format!("Failed to write bundle: {}", e)))?;
-
-    Ok(ExportInfo {
-        branch_id: branch_id.to_string(),
-        path: export_info.path,
-        entry_count: export_info.wal_entry_count,
-        bundle_size: export_info.bundle_size_bytes,
-    })
+    Ok((branch_info, core_branch_id))
 }
 
-/// Scan all data in a branch's namespace and group into BranchlogPayload records.
+/// Entries of one commit version: (key, value) pairs written at it
+type VersionGroups = BTreeMap<u64, Vec<(Key, strata_core::value::Value)>>;
+
+/// Scan all data in a branch's namespace and group it by commit version.
 ///
-/// Uses the storage layer's version history to produce one payload per commit
-/// version, preserving the full version chain for import.
-fn scan_branch_data(
+/// Uses the storage layer's version history so that [`into_payloads`]
+/// produces one payload per commit version, preserving the full version
+/// chain for import.
+fn scan_version_groups(
     db: &Arc<Database>,
     core_branch_id: BranchId,
-    branch_id_str: &str,
-) -> StrataResult<Vec<BranchlogPayload>> {
+) -> StrataResult<VersionGroups> {
     let storage = db.storage();
 
     // Discover all current keys across all type tags
@@ -147,8 +270,10 @@ fn scan_branch_data(
         TypeTag::KV,
         TypeTag::Event,
         TypeTag::State,
+        TypeTag::List,
         TypeTag::Json,
         TypeTag::Vector,
+        TypeTag::Custom,
     ];
 
     let mut all_keys: Vec<Key> = Vec::new();
@@ -157,13 +282,9 @@ fn scan_branch_data(
         all_keys.extend(entries.into_iter().map(|(k, _)| k));
     }
 
-    if all_keys.is_empty() {
-        return Ok(vec![]);
-    }
-
     // For each key, get the full version history and group entries by version.
     // BTreeMap keeps versions sorted ascending for deterministic replay order.
-    let mut version_groups: BTreeMap<u64, Vec<(Key, strata_core::value::Value)>> = BTreeMap::new();
+    let mut version_groups = VersionGroups::new();
 
     for key in &all_keys {
         let history = db.get_history(key, None, None)?;
@@ -180,18 +301,22 @@ fn scan_branch_data(
         }
     }
 
-    // Convert grouped entries into payloads sorted by version
-    let payloads: Vec<BranchlogPayload> = version_groups
+    Ok(version_groups)
+}
+
+/// Turn grouped entries into payloads sorted by version, one at a time
+fn into_payloads(
+    version_groups: VersionGroups,
+    branch_id_str: &str,
+) -> impl ExactSizeIterator<Item = BranchlogPayload> + '_ {
+    version_groups
         .into_iter()
-        .map(|(version, puts)| BranchlogPayload {
+        .map(move |(version, puts)| BranchlogPayload {
             branch_id: branch_id_str.to_string(),
             version,
             puts,
             deletes: vec![],
         })
-        .collect();
-
-    Ok(payloads)
 }
 
 // =============================================================================
@@ -200,7 +325,9 @@ fn scan_branch_data(
 
 /// Import a branch from a `.branchbundle.tar.zst` archive
 ///
-/// Creates the branch in the database and replays all transaction payloads.
+/// Creates the branch in the database and replays transaction payloads as
+/// they are read. If the bundle fails to read or verify partway through,
+/// the partially imported branch is deleted again.
 ///
 /// # Errors
 ///
@@ -208,67 +335,311 @@ fn scan_branch_data(
 /// - Branch with same ID already exists
 /// - I/O errors reading the archive
 pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo> {
-    // 1. Read and validate bundle
-    let contents = BranchBundleReader::read_all(path)
+    import_bundle(db, path, None)
+}
+
+/// Import a branch bundle into a new branch called `branch_name`
+///
+/// Like [`import_branch`], but the bundled branch's name is ignored, so a
+/// bundle can be loaded next to existing data — including the branch it was
+/// exported from — and inspected in isolation. The result's `renamed_from`
+/// holds the bundled name when it differs.
+///
+/// # Errors
+///
+/// - Bundle is invalid or corrupt
+/// - `branch_name` already exists
+/// - I/O errors reading the archive
+pub fn import_branch_as(
+    db: &Arc<Database>,
+    path: &Path,
+    branch_name: &str,
+) -> StrataResult<ImportInfo> {
+    import_bundle(db, path, Some(branch_name))
+}
+
+/// Stream a branch bundle into a new branch, named `target` or as bundled
+fn import_bundle(
+    db: &Arc<Database>,
+    path: &Path,
+    target: Option<&str>,
+) -> StrataResult<ImportInfo> {
+    let is_encrypted = BranchBundleReader::is_encrypted(path)
         .map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
 
-    let branch_id_str = &contents.branch_info.name;
+    let mut visitor = ImportVisitor {
+        db,
+        target,
+        replay: None,
+        renamed_from: None,
+        failure: None,
+    };
+    let result = if is_encrypted {
+        BranchBundleReader::stream_encrypted(path, db.codec().as_ref(), &mut visitor)
+    } else {
+        BranchBundleReader::stream(path, &mut visitor)
+    };
+
+    match (result, visitor.replay) {
+        (Ok(()), Some(replay)) => Ok(ImportInfo {
+            renamed_from: visitor.renamed_from,
+            ..replay.finish()
+        }),
+        (Ok(()), None) => Err(StrataError::internal(
+            "Bundle stream ended without a branch",
+        )),
+        (Err(e), replay) => {
+            // Undo the partial import before reporting
+            if let Some(replay) = replay {
+                BranchIndex::new(db.clone()).delete_branch(&replay.branch_id_str)?;
+            }
+            Err(visitor
+                .failure
+                .unwrap_or_else(|| StrataError::storage(format!("Failed to read bundle: {}", e))))
+        }
+    }
+}
+
+/// Replays a streamed branch bundle into the database
+struct ImportVisitor<'a> {
+    db: &'a Arc<Database>,
+    /// Name to import under instead of the bundled one
+    target: Option<&'a str>,
+    /// Set once the branch has been created
+    replay: Option<BranchReplay>,
+    /// Bundled name, when imported under a different one
+    renamed_from: Option<String>,
+    /// Engine error behind a failed callback, reported instead of the
+    /// bundle error it was wrapped in
+    failure: Option<StrataError>,
+}
+
+impl ImportVisitor<'_> {
+    fn fail(&mut self, error: StrataError) -> BranchBundleError {
+        let wrapped = BranchBundleError::wal_replay(error.to_string());
+        self.failure = Some(error);
+        wrapped
+    }
+}
+
+impl BundleVisitor for ImportVisitor<'_> {
+    fn begin(
+        &mut self,
+        manifest: &BundleManifest,
+        branch_info: &BundleBranchInfo,
+    ) -> BranchBundleResult<()> {
+        warn_incompatible(self.db, manifest.environment.as_ref());
+        let name = self.target.unwrap_or(&branch_info.name);
+        if name != branch_info.name {
+            self.renamed_from = Some(branch_info.name.clone());
+        }
+
+        // Check branch doesn't already exist
+        match BranchIndex::new(self.db.clone()).exists(name) {
+            Ok(false) => {}
+            Ok(true) => {
+                return Err(self.fail(StrataError::invalid_input(format!(
+                    "Branch '{}' already exists. Delete it first or use a different name.",
+                    name
+                ))))
+            }
+            Err(e) => return Err(self.fail(e)),
+        }
+
+        match BranchReplay::start(self.db, name, branch_info.environment.clone()) {
+            Ok(replay) => {
+                self.replay = Some(replay);
+                Ok(())
+            }
+            Err(e) => Err(self.fail(e)),
+        }
+    }
+
+    fn payload(&mut self, payload: BranchlogPayload) -> BranchBundleResult<()> {
+        let Some(replay) = self.replay.as_mut() else {
+            return Err(BranchBundleError::wal_replay("payload before branch info"));
+        };
+        match replay.apply(&payload) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(self.fail(e)),
+        }
+    }
+}
+
+/// Import every branch from a `.databundle.tar.zst` archive
+///
+/// Bundled branches whose name is already taken are handled according to
+/// `on_conflict`. With [`ImportConflict::Fail`], nothing is imported if any
+/// name is taken. Every database has a `default` branch, so importing a
+/// whole database into another one needs one of the other policies.
+///
+/// # Errors
+///
+/// - Bundle is invalid, corrupt or sealed with another key
+/// - A branch already exists and `on_conflict` is `Fail`
+/// - I/O errors reading the archive
+pub fn import_all(
+    db: &Arc<Database>,
+    path: &Path,
+    on_conflict: ImportConflict,
+) -> StrataResult<DatabaseImportInfo> {
+    let is_encrypted = BranchBundleReader::is_encrypted(path)
+        .map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
+    let contents = if is_encrypted {
+        DatabaseBundleReader::read_all_encrypted(path, db.codec().as_ref())
+    } else {
+        DatabaseBundleReader::read_all(path)
+    };
+    let contents =
+        contents.map_err(|e| StrataError::storage(format!("Failed to read bundle: {}", e)))?;
+    warn_incompatible(db, contents.manifest.environment.as_ref());
+
     let branch_index = BranchIndex::new(db.clone());
+    if on_conflict == ImportConflict::Fail {
+        let mut taken = Vec::new();
+        for branch in &contents.branches {
+            if branch_index.exists(&branch.branch_info.name)? {
+                taken.push(branch.branch_info.name.as_str());
+            }
+        }
+        if !taken.is_empty() {
+            return Err(StrataError::invalid_input(format!(
+                "Branches already exist: {}. Choose skip, rename or overwrite.",
+                taken.join(", ")
+            )));
+        }
+    }
 
-    // 2. Check branch doesn't already exist
-    if branch_index.exists(branch_id_str)? {
-        return Err(StrataError::invalid_input(format!(
-            "Branch '{}' already exists. Delete it first or use a different name.",
-            branch_id_str
-        )));
+    let mut info = DatabaseImportInfo {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for branch in &contents.branches {
+        let name = &branch.branch_info.name;
+        let mut target = name.clone();
+        let mut renamed_from = None;
+        if branch_index.exists(name)? {
+            match on_conflict {
+                ImportConflict::Fail => unreachable!("conflicts were rejected above"),
+                ImportConflict::Skip => {
+                    info.skipped.push(name.clone());
+                    continue;
+                }
+                ImportConflict::Rename => {
+                    target = format!("{}-imported", name);
+                    let mut n = 2;
+                    while branch_index.exists(&target)? {
+                        target = format!("{}-imported-{}", name, n);
+                        n += 1;
+                    }
+                    renamed_from = Some(name.clone());
+                }
+                ImportConflict::Overwrite => branch_index.delete_branch(name)?,
+            }
+        }
+        let mut imported = replay_branch(db, &target, branch)?;
+        imported.renamed_from = renamed_from;
+        info.imported.push(imported);
     }
 
-    // 3. Create branch via BranchIndex
-    branch_index.create_branch(branch_id_str)?;
+    Ok(info)
+}
 
-    // 4. Resolve BranchId for namespace
-    let branch_meta = branch_index
-        .get_branch(branch_id_str)?
-        .ok_or_else(|| {
-            StrataError::internal(format!(
-                "Branch '{}' was just created but cannot be found",
-                branch_id_str
-            ))
-        })?
-        .value;
+/// Create branch `name` and replay a bundled branch into it
+fn replay_branch(
+    db: &Arc<Database>,
+    branch_id_str: &str,
+    branch: &BundledBranch,
+) -> StrataResult<ImportInfo> {
+    let environment = branch.branch_info.environment.clone();
+    let mut replay = BranchReplay::start(db, branch_id_str, environment)?;
+    for payload in &branch.payloads {
+        replay.apply(payload)?;
+    }
+    Ok(replay.finish())
+}
 
-    let core_branch_id = crate::primitives::branch::resolve_branch_name(&branch_meta.name);
+/// Replay of payloads into a newly created branch
+///
+/// Keys are moved into the new branch's namespace, so a branch can be
+/// imported under a different name than it was exported with.
+struct BranchReplay {
+    db: Arc<Database>,
+    branch_id_str: String,
+    core_branch_id: BranchId,
+    transactions_applied: u64,
+    keys_written: u64,
+}
 
-    // 5. Replay each payload as a transaction
-    let mut transactions_applied = 0u64;
-    let mut keys_written = 0u64;
+impl BranchReplay {
+    /// Create branch `branch_id_str` to replay into, keeping the
+    /// environment the bundled branch was created in
+    fn start(
+        db: &Arc<Database>,
+        branch_id_str: &str,
+        environment: Option<EnvironmentInfo>,
+    ) -> StrataResult<Self> {
+        let branch_index = BranchIndex::new(db.clone());
 
-    for payload in &contents.payloads {
-        let put_count = payload.puts.len() as u64;
+        // 1. Create branch via BranchIndex
+        branch_index.create_branch_with_environment(branch_id_str, environment)?;
+
+        // 2. Resolve BranchId for namespace
+        let branch_meta = branch_index
+            .get_branch(branch_id_str)?
+            .ok_or_else(|| {
+                StrataError::internal(format!(
+                    "Branch '{}' was just created but cannot be found",
+                    branch_id_str
+                ))
+            })?
+            .value;
 
-        db.transaction(core_branch_id, |txn| {
+        Ok(BranchReplay {
+            db: db.clone(),
+            branch_id_str: branch_id_str.to_string(),
+            core_branch_id: crate::primitives::branch::resolve_branch_name(&branch_meta.name),
+            transactions_applied: 0,
+            keys_written: 0,
+        })
+    }
+
+    /// Replay one payload as a transaction
+    fn apply(&mut self, payload: &BranchlogPayload) -> StrataResult<()> {
+        let core_branch_id = self.core_branch_id;
+        let rebase = |key: &Key| {
+            let mut key = key.clone();
+            key.namespace.branch_id = core_branch_id;
+            key
+        };
+
+        self.db.transaction(core_branch_id, |txn| {
             // Apply puts
             for (key, value) in &payload.puts {
-                txn.put(key.clone(), value.clone())?;
+                txn.put(rebase(key), value.clone())?;
             }
 
             // Apply deletes
             for key in &payload.deletes {
-                txn.delete(key.clone())?;
+                txn.delete(rebase(key))?;
             }
 
             Ok(())
         })?;
 
-        transactions_applied += 1;
-        keys_written += put_count;
+        self.transactions_applied += 1;
+        self.keys_written += payload.puts.len() as u64;
+        Ok(())
     }
 
-    Ok(ImportInfo {
-        branch_id: branch_id_str.to_string(),
-        transactions_applied,
-        keys_written,
-    })
+    fn finish(self) -> ImportInfo {
+        ImportInfo {
+            branch_id: self.branch_id_str,
+            transactions_applied: self.transactions_applied,
+            keys_written: self.keys_written,
+            renamed_from: None,
+        }
+    }
 }
 
 // =============================================================================
@@ -278,7 +649,13 @@ pub fn import_branch(db: &Arc<Database>, path: &Path) -> StrataResult<ImportInfo
 /// Validate a bundle without importing it
 ///
 /// Checks the archive structure, checksums, and format version.
+/// Encrypted bundles cannot be validated without a key.
 pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
+    if BranchBundleReader::is_encrypted(path).unwrap_or(false) {
+        return Err(StrataError::invalid_input(
+            "Bundle is encrypted; import it into a database configured with the same key",
+        ));
+    }
     let verify = BranchBundleReader::validate(path)
         .map_err(|e| StrataError::storage(format!("Bundle validation failed: {}", e)))?;
 
@@ -287,6 +664,7 @@ pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
         format_version: verify.format_version,
         entry_count: verify.wal_entry_count,
         checksums_valid: verify.checksums_valid,
+        environment: verify.environment,
     })
 }
 
@@ -294,6 +672,33 @@ pub fn validate_bundle(path: &Path) -> StrataResult<BundleInfo> {
 // Helpers
 // =============================================================================
 
+/// `options` plus the database's environment, when it captures one
+fn with_environment(db: &Database, options: &ExportOptions) -> ExportOptions {
+    if db.capture_environment_enabled() && options.environment.is_none() {
+        options.clone().with_environment(db.environment())
+    } else {
+        options.clone()
+    }
+}
+
+/// Log each way a bundle's environment differs from this database's
+///
+/// Bundles without an environment, from databases that do not capture it,
+/// are imported silently.
+fn warn_incompatible(db: &Database, environment: Option<&EnvironmentInfo>) {
+    let Some(environment) = environment else {
+        return;
+    };
+    for issue in environment.compatibility_issues(&db.environment()) {
+        warn!(
+            target: "strata::bundle",
+            host = %environment.host,
+            "Bundle environment differs: {}",
+            issue
+        );
+    }
+}
+
 /// Format microsecond timestamp as ISO 8601 string
 fn format_micros(micros: u64) -> String {
     let secs = micros / 1_000_000;
@@ -459,6 +864,117 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_import_branch_as_next_to_source() {
+        let (temp_dir, db) = setup_with_branch("failed-run");
+        put_kv(&db, "failed-run", "step", 3);
+        let path = temp_dir.path().join("failed.branchbundle.tar.zst");
+        export_branch(&db, "failed-run", &path).unwrap();
+
+        // The source branch moves on after the export
+        put_kv(&db, "failed-run", "step", 4);
+
+        let info = import_branch_as(&db, &path, "failed-run-inspect").unwrap();
+        assert_eq!(info.branch_id, "failed-run-inspect");
+        assert_eq!(info.renamed_from.as_deref(), Some("failed-run"));
+        assert_eq!(get_kv(&db, "failed-run-inspect", "step"), Some(3));
+        assert_eq!(get_kv(&db, "failed-run", "step"), Some(4));
+
+        // Writes to the sandbox stay there
+        put_kv(&db, "failed-run-inspect", "step", 9);
+        assert_eq!(get_kv(&db, "failed-run", "step"), Some(4));
+
+        // The name must be free; the existing branch is left alone
+        assert!(import_branch_as(&db, &path, "failed-run-inspect").is_err());
+        assert_eq!(get_kv(&db, "failed-run-inspect", "step"), Some(9));
+
+        // Importing under the bundled name is not a rename
+        let (_dir, other) = setup();
+        let info = import_branch_as(&other, &path, "failed-run").unwrap();
+        assert_eq!(info.renamed_from, None);
+    }
+
+    #[test]
+    fn test_bundle_keeps_event_stream_schema() {
+        use crate::primitives::EventLog;
+        use strata_core::value::Value;
+
+        let (temp_dir, db) = setup_with_branch("typed-run");
+        let schema =
+            Value::try_from(&serde_json::json!({"type": "object", "required": ["tool"]})).unwrap();
+        let branch_id = crate::primitives::branch::resolve_branch_name("typed-run");
+        EventLog::new(db.clone())
+            .set_schema(&branch_id, "default", "tool_call", schema.clone())
+            .unwrap();
+        let path = temp_dir.path().join("typed.branchbundle.tar.zst");
+        export_branch(&db, "typed-run", &path).unwrap();
+
+        let (_dir, other) = setup();
+        import_branch(&other, &path).unwrap();
+        let log = EventLog::new(other.clone());
+        assert_eq!(
+            log.schema(&branch_id, "default", "tool_call").unwrap(),
+            Some(schema)
+        );
+        let payload = Value::try_from(&serde_json::json!({"args": []})).unwrap();
+        assert!(log
+            .append(&branch_id, "default", "tool_call", payload)
+            .is_err());
+    }
+
+    #[test]
+    fn test_export_reports_progress() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let (temp_dir, db) = setup_with_branch("progress-branch");
+        for i in 0..3 {
+            put_kv(&db, "progress-branch", &format!("k{}", i), i);
+        }
+
+        let written = Arc::new(AtomicU64::new(0));
+        let sink = written.clone();
+        let options = ExportOptions::default().with_progress(move |p| {
+            assert_eq!(p.entries_total, 3);
+            sink.store(p.entries_written, Ordering::SeqCst);
+        });
+        let path = temp_dir.path().join("progress.branchbundle.tar.zst");
+        let info = export_branch_with_options(&db, "progress-branch", &path, &options).unwrap();
+
+        assert_eq!(info.entry_count, 3);
+        assert_eq!(written.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_import_truncated_bundle_leaves_no_branch() {
+        let (temp_dir, db) = setup_with_branch("big-branch");
+        // Enough poorly compressible data to span several zstd blocks
+        let mut seed = 0x2545_f491_u64;
+        for i in 0..64 {
+            let bytes: Vec<u8> = (0..4096)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (seed >> 56) as u8
+                })
+                .collect();
+            let key = kv_key("big-branch", &format!("k{}", i));
+            db.transaction(key.namespace.branch_id, |txn| {
+                txn.put(key.clone(), strata_core::value::Value::Bytes(bytes.clone()))
+            })
+            .unwrap();
+        }
+
+        let path = temp_dir.path().join("big.branchbundle.tar.zst");
+        export_branch(&db, "big-branch", &path).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() * 2 / 3]).unwrap();
+
+        let (_dir, target) = setup();
+        assert!(import_branch(&target, &path).is_err());
+        assert!(!BranchIndex::new(target.clone())
+            .exists("big-branch")
+            .unwrap());
+    }
+
     #[test]
     fn test_export_empty_branch() {
         let (temp_dir, db) = setup_with_branch("empty-branch");
@@ -468,6 +984,222 @@ mod tests {
         assert_eq!(info.entry_count, 0);
     }
 
+    #[test]
+    fn test_encrypted_export_import() {
+        let open_encrypted = |dir: &Path, key_byte: u8| {
+            let key_path = dir.join("db.key");
+            std::fs::write(&key_path, [key_byte; 32]).unwrap();
+            std::fs::write(
+                dir.join("strata.toml"),
+                format!(
+                    "[encryption]\nkey_provider = \"file:{}\"\n",
+                    key_path.display()
+                ),
+            )
+            .unwrap();
+            Database::open(dir).unwrap()
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let db = open_encrypted(temp_dir.path(), 0xAA);
+        BranchIndex::new(db.clone())
+            .create_branch("sealed-branch")
+            .unwrap();
+        let path = temp_dir.path().join("sealed.branchbundle.tar.zst");
+        export_branch(&db, "sealed-branch", &path).unwrap();
+
+        assert!(BranchBundleReader::is_encrypted(&path).unwrap());
+        assert!(validate_bundle(&path).is_err());
+
+        // Same key imports; a plain or differently keyed database cannot
+        let same_dir = TempDir::new().unwrap();
+        let same_db = open_encrypted(same_dir.path(), 0xAA);
+        assert_eq!(
+            import_branch(&same_db, &path).unwrap().branch_id,
+            "sealed-branch"
+        );
+
+        let (_plain_dir, plain_db) = setup();
+        assert!(import_branch(&plain_db, &path).is_err());
+
+        let other_dir = TempDir::new().unwrap();
+        let other_db = open_encrypted(other_dir.path(), 0xBB);
+        assert!(import_branch(&other_db, &path).is_err());
+    }
+
+    fn kv_key(branch_name: &str, key: &str) -> Key {
+        let core_branch_id = crate::primitives::branch::resolve_branch_name(branch_name);
+        Key::new(
+            Namespace::for_branch(core_branch_id),
+            TypeTag::KV,
+            key.as_bytes().to_vec(),
+        )
+    }
+
+    fn put_kv(db: &Arc<Database>, branch_name: &str, key: &str, value: i64) {
+        let key = kv_key(branch_name, key);
+        db.transaction(key.namespace.branch_id, |txn| {
+            txn.put(key.clone(), strata_core::value::Value::Int(value))
+        })
+        .unwrap();
+    }
+
+    fn get_kv(db: &Arc<Database>, branch_name: &str, key: &str) -> Option<i64> {
+        let key = kv_key(branch_name, key);
+        let value = db
+            .transaction(key.namespace.branch_id, |txn| txn.get(&key))
+            .unwrap();
+        value.map(|v| match v {
+            strata_core::value::Value::Int(i) => i,
+            other => panic!("unexpected value {:?}", other),
+        })
+    }
+
+    /// Database with branches `alpha` (a=1) and `beta` (b=2), exported to a
+    /// database bundle
+    fn exported_database() -> (TempDir, PathBuf) {
+        let (temp_dir, db) = setup_with_branch("alpha");
+        BranchIndex::new(db.clone()).create_branch("beta").unwrap();
+        put_kv(&db, "alpha", "a", 1);
+        put_kv(&db, "beta", "b", 2);
+
+        let path = temp_dir.path().join("all.databundle.tar.zst");
+        let info = export_all(&db, &path).unwrap();
+        assert_eq!(info.branches, vec!["alpha", "beta"]);
+        assert_eq!(info.entry_count, 2);
+        (temp_dir, path)
+    }
+
+    #[test]
+    fn test_export_all_import_all() {
+        let (_source_dir, path) = exported_database();
+
+        let (_dir, db) = setup();
+        let info = import_all(&db, &path, ImportConflict::Fail).unwrap();
+        assert_eq!(info.imported.len(), 2);
+        assert!(info.skipped.is_empty());
+        assert!(info.imported.iter().all(|i| i.renamed_from.is_none()));
+        assert_eq!(get_kv(&db, "alpha", "a"), Some(1));
+        assert_eq!(get_kv(&db, "beta", "b"), Some(2));
+    }
+
+    #[test]
+    fn test_import_all_conflict_policies() {
+        let (_source_dir, path) = exported_database();
+        // Each target already has an `alpha` holding a different key
+        let target = || {
+            let (dir, db) = setup_with_branch("alpha");
+            put_kv(&db, "alpha", "old", 9);
+            (dir, db)
+        };
+
+        let (_dir, db) = target();
+        assert!(import_all(&db, &path, ImportConflict::Fail).is_err());
+        assert!(!BranchIndex::new(db.clone()).exists("beta").unwrap());
+
+        let (_dir, db) = target();
+        let info = import_all(&db, &path, ImportConflict::Skip).unwrap();
+        assert_eq!(info.skipped, vec!["alpha"]);
+        assert_eq!(get_kv(&db, "alpha", "old"), Some(9));
+        assert_eq!(get_kv(&db, "alpha", "a"), None);
+        assert_eq!(get_kv(&db, "beta", "b"), Some(2));
+
+        let (_dir, db) = target();
+        let info = import_all(&db, &path, ImportConflict::Rename).unwrap();
+        assert_eq!(info.imported[0].branch_id, "alpha-imported");
+        assert_eq!(info.imported[0].renamed_from.as_deref(), Some("alpha"));
+        assert_eq!(info.imported[1].renamed_from, None);
+        assert_eq!(get_kv(&db, "alpha", "old"), Some(9));
+        assert_eq!(get_kv(&db, "alpha-imported", "a"), Some(1));
+        // A second import picks the next free name
+        let info = import_all(&db, &path, ImportConflict::Rename).unwrap();
+        assert_eq!(info.imported[0].branch_id, "alpha-imported-2");
+        assert_eq!(info.imported[1].branch_id, "beta-imported");
+
+        let (_dir, db) = target();
+        let info = import_all(&db, &path, ImportConflict::Overwrite).unwrap();
+        assert_eq!(info.imported.len(), 2);
+        assert_eq!(get_kv(&db, "alpha", "old"), None);
+        assert_eq!(get_kv(&db, "alpha", "a"), Some(1));
+    }
+
+    #[test]
+    fn test_import_all_rejects_branch_bundle() {
+        let (temp_dir, db) = setup_with_branch("single");
+        let path = temp_dir.path().join("single.branchbundle.tar.zst");
+        export_branch(&db, "single", &path).unwrap();
+
+        let (_dir, target) = setup();
+        assert!(import_all(&target, &path, ImportConflict::Fail).is_err());
+    }
+
+    #[test]
+    fn test_environment_recorded_only_when_captured() {
+        let (temp_dir, db) = setup_with_branch("plain");
+        let branch_index = BranchIndex::new(db.clone());
+        let plain = temp_dir.path().join("plain.branchbundle.tar.zst");
+        export_branch(&db, "plain", &plain).unwrap();
+        assert!(validate_bundle(&plain).unwrap().environment.is_none());
+
+        db.set_capture_environment(true);
+        branch_index.create_branch("captured").unwrap();
+        let meta = branch_index.get_branch("captured").unwrap().unwrap().value;
+        let environment = meta.environment.expect("branch environment");
+        assert_eq!(
+            environment,
+            EnvironmentInfo {
+                captured_at: environment.captured_at.clone(),
+                ..db.environment()
+            }
+        );
+        assert_eq!(environment.models["embed"], "minilm-l6-v2");
+        assert!(branch_index
+            .get_branch("plain")
+            .unwrap()
+            .unwrap()
+            .value
+            .environment
+            .is_none());
+
+        // The manifest records the exporting database even for a branch
+        // created before capture was enabled
+        export_branch(&db, "plain", &plain).unwrap();
+        let info = validate_bundle(&plain).unwrap();
+        assert_eq!(
+            info.environment.unwrap().strata_version,
+            environment.strata_version
+        );
+    }
+
+    #[test]
+    fn test_import_keeps_branch_environment() {
+        let (temp_dir, db) = setup();
+        db.set_capture_environment(true);
+        BranchIndex::new(db.clone()).create_branch("exp").unwrap();
+        let source = BranchIndex::new(db.clone())
+            .get_branch("exp")
+            .unwrap()
+            .unwrap()
+            .value
+            .environment;
+        assert!(source.is_some());
+        let path = temp_dir.path().join("exp.branchbundle.tar.zst");
+        export_branch(&db, "exp", &path).unwrap();
+        let all = temp_dir.path().join("all.databundle.tar.zst");
+        export_all(&db, &all).unwrap();
+
+        // Imports keep the environment the branch was created in, whether or
+        // not the importing database captures its own
+        let (_dir, target) = setup();
+        import_branch(&target, &path).unwrap();
+        import_all(&target, &all, ImportConflict::Rename).unwrap();
+        let target_index = BranchIndex::new(target.clone());
+        for name in ["exp", "exp-imported"] {
+            let meta = target_index.get_branch(name).unwrap().unwrap().value;
+            assert_eq!(meta.environment, source, "{}", name);
+        }
+    }
+
     #[test]
     fn test_format_micros() {
         // Epoch should be 1970
@@ -9,9 +9,10 @@
 //! - Transaction metrics (started, committed, aborted)
 //! - Commit rate calculation
 
+use parking_lot::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use strata_concurrency::{RecoveryResult, TransactionContext, TransactionManager};
+use strata_concurrency::{CommitError, RecoveryResult, TransactionContext, TransactionManager};
 use strata_core::traits::Storage;
 use strata_core::types::BranchId;
 use strata_core::StrataError;
@@ -27,8 +28,8 @@ use tracing::{debug, info, warn};
 ///
 /// # Memory Ordering
 ///
-/// The metric counters (active_count, total_started, total_committed, total_aborted)
-/// use Relaxed ordering intentionally because:
+/// The metric counters (active_count, total_started, total_committed, total_aborted,
+/// total_conflicts, total_retries, total_retries_exhausted) use Relaxed ordering intentionally because:
 /// 1. They are purely observational metrics for monitoring/debugging
 /// 2. They do not synchronize any other memory operations
 /// 3. Approximate counts are acceptable for metrics purposes
@@ -44,6 +45,12 @@ pub struct TransactionCoordinator {
     total_committed: AtomicU64,
     /// Total transactions aborted - uses Relaxed ordering
     total_aborted: AtomicU64,
+    /// Aborts caused by validation conflicts - uses Relaxed ordering
+    total_conflicts: AtomicU64,
+    /// Conflicted transactions re-run by a retry loop - uses Relaxed ordering
+    total_retries: AtomicU64,
+    /// Retry loops that ran out of attempts - uses Relaxed ordering
+    total_retries_exhausted: AtomicU64,
 }
 
 impl TransactionCoordinator {
@@ -58,6 +65,9 @@ impl TransactionCoordinator {
             total_started: AtomicU64::new(0),
             total_committed: AtomicU64::new(0),
             total_aborted: AtomicU64::new(0),
+            total_conflicts: AtomicU64::new(0),
+            total_retries: AtomicU64::new(0),
+            total_retries_exhausted: AtomicU64::new(0),
         }
     }
 
@@ -82,6 +92,9 @@ impl TransactionCoordinator {
             total_started: AtomicU64::new(0),
             total_committed: AtomicU64::new(0),
             total_aborted: AtomicU64::new(0),
+            total_conflicts: AtomicU64::new(0),
+            total_retries: AtomicU64::new(0),
+            total_retries_exhausted: AtomicU64::new(0),
         }
     }
 
@@ -102,6 +115,9 @@ impl TransactionCoordinator {
         storage: &Arc<ShardedStore>,
     ) -> TransactionContext {
         let txn_id = self.manager.next_txn_id();
+        let _span = self
+            .commit_tracing()
+            .then(|| begin_span(branch_id, txn_id).entered());
         let snapshot = storage.create_snapshot();
 
         self.active_count.fetch_add(1, Ordering::Relaxed);
@@ -133,6 +149,10 @@ impl TransactionCoordinator {
     /// - Recording commit/abort metrics
     /// - Converting CommitError to StrataError
     ///
+    /// A panic during the commit is recorded as an abort before it continues
+    /// to the caller, so the active transaction count stays accurate and
+    /// shutdown does not wait on a transaction that will never finish.
+    ///
     /// # Arguments
     /// * `txn` - Transaction to commit (must be in Active state)
     /// * `store` - Storage to validate against and apply writes to
@@ -148,7 +168,37 @@ impl TransactionCoordinator {
         store: &S,
         wal: Option<&mut WalWriter>,
     ) -> StrataResult<u64> {
-        match self.manager.commit(txn, store, wal) {
+        self.record_outcome(|| self.manager.commit(txn, store, wal))
+    }
+
+    /// Commit a transaction, locking the WAL only for the append
+    ///
+    /// Like [`commit`](Self::commit), but storage apply runs after the WAL
+    /// mutex is released (see [`TransactionManager::commit_pipelined`]).
+    /// Returns once the commit version is visible to new snapshots.
+    pub fn commit_pipelined<S: Storage>(
+        &self,
+        txn: &mut TransactionContext,
+        store: &S,
+        wal: Option<&Mutex<WalWriter>>,
+    ) -> StrataResult<u64> {
+        self.record_outcome(|| self.manager.commit_pipelined(txn, store, wal))
+    }
+
+    /// Run a commit and record its outcome in the metrics
+    fn record_outcome(
+        &self,
+        commit: impl FnOnce() -> Result<u64, CommitError>,
+    ) -> StrataResult<u64> {
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(commit));
+        let result = match outcome {
+            Ok(result) => result,
+            Err(panic) => {
+                self.record_abort();
+                std::panic::resume_unwind(panic)
+            }
+        };
+        match result {
             Ok(version) => {
                 self.record_commit();
                 info!(target: "strata::txn", "Transaction committed");
@@ -156,6 +206,9 @@ impl TransactionCoordinator {
             }
             Err(e) => {
                 self.record_abort();
+                if matches!(e, CommitError::ValidationFailed(_)) {
+                    self.total_conflicts.fetch_add(1, Ordering::Relaxed);
+                }
                 warn!(target: "strata::txn", error = %e, "Transaction aborted");
                 Err(StrataError::from(e))
             }
@@ -197,6 +250,16 @@ impl TransactionCoordinator {
         self.total_aborted.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record that a conflicted transaction is being run again
+    pub fn record_retry(&self) {
+        self.total_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record that a retry loop gave up on a conflict
+    pub fn record_retries_exhausted(&self) {
+        self.total_retries_exhausted.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Get current global version
     pub fn current_version(&self) -> u64 {
         self.manager.current_version()
@@ -215,6 +278,16 @@ impl TransactionCoordinator {
         self.manager.remove_branch_lock(branch_id);
     }
 
+    /// Enable or disable `tracing` spans on transaction begin and commit
+    pub fn set_commit_tracing(&self, enabled: bool) {
+        self.manager.set_commit_tracing(enabled);
+    }
+
+    /// Whether transactions run inside `tracing` spans
+    pub fn commit_tracing(&self) -> bool {
+        self.manager.commit_tracing()
+    }
+
     /// Get transaction metrics
     ///
     /// Returns current snapshot of transaction statistics.
@@ -227,6 +300,9 @@ impl TransactionCoordinator {
             total_started: started,
             total_committed: committed,
             total_aborted: self.total_aborted.load(Ordering::Relaxed),
+            total_conflicts: self.total_conflicts.load(Ordering::Relaxed),
+            total_retries: self.total_retries.load(Ordering::Relaxed),
+            total_retries_exhausted: self.total_retries_exhausted.load(Ordering::Relaxed),
             commit_rate: if started > 0 {
                 committed as f64 / started as f64
             } else {
@@ -266,6 +342,11 @@ impl TransactionCoordinator {
     }
 }
 
+/// Span covering the start of a transaction, entered when commit tracing is on
+pub(crate) fn begin_span(branch_id: BranchId, txn_id: u64) -> tracing::Span {
+    tracing::info_span!(target: "strata::txn", "begin", branch_id = %branch_id, txn_id)
+}
+
 /// Transaction metrics
 ///
 /// Provides statistics about transaction lifecycle.
@@ -279,6 +360,13 @@ pub struct TransactionMetrics {
     pub total_committed: u64,
     /// Total number of transactions aborted
     pub total_aborted: u64,
+    /// Aborts caused by a read-set or CAS conflict at commit (included in
+    /// `total_aborted`)
+    pub total_conflicts: u64,
+    /// Times a retry loop re-ran a transaction after a conflict
+    pub total_retries: u64,
+    /// Retry loops that still conflicted on their last attempt
+    pub total_retries_exhausted: u64,
     /// Commit success rate (committed / started)
     pub commit_rate: f64,
 }
@@ -390,6 +478,95 @@ mod tests {
         assert_eq!(metrics.commit_rate, 0.0);
     }
 
+    #[test]
+    fn test_validation_conflict_counts_as_conflict() {
+        let coordinator = TransactionCoordinator::new(0);
+        let storage = create_test_storage();
+        let branch_id = BranchId::new();
+        let key = strata_core::types::Key::new_kv(
+            strata_core::types::Namespace::for_branch(branch_id),
+            "k",
+        );
+
+        let mut txn1 = coordinator.start_transaction(branch_id, &storage);
+        let mut txn2 = coordinator.start_transaction(branch_id, &storage);
+        for txn in [&mut txn1, &mut txn2] {
+            txn.get(&key).unwrap();
+            txn.put(key.clone(), strata_core::value::Value::Int(1))
+                .unwrap();
+        }
+        coordinator
+            .commit(&mut txn1, storage.as_ref(), None)
+            .unwrap();
+        assert!(coordinator
+            .commit(&mut txn2, storage.as_ref(), None)
+            .is_err());
+
+        let metrics = coordinator.metrics();
+        assert_eq!(metrics.total_aborted, 1);
+        assert_eq!(metrics.total_conflicts, 1);
+    }
+
+    #[test]
+    fn test_commit_panic_records_abort() {
+        use strata_durability::codec::{CodecError, StorageCodec};
+        use strata_durability::wal::{DurabilityMode, WalConfig};
+
+        /// Codec that panics while the WAL record is being encoded
+        struct PanickingCodec;
+
+        impl StorageCodec for PanickingCodec {
+            fn encode(&self, _data: &[u8]) -> Vec<u8> {
+                panic!("injected panic while writing the WAL record");
+            }
+            fn decode(&self, data: &[u8]) -> Result<Vec<u8>, CodecError> {
+                Ok(data.to_vec())
+            }
+            fn codec_id(&self) -> &str {
+                "identity"
+            }
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut wal = WalWriter::new(
+            temp_dir.path().to_path_buf(),
+            [0u8; 16],
+            DurabilityMode::Always,
+            WalConfig::for_testing(),
+            Box::new(PanickingCodec),
+        )
+        .unwrap();
+        let coordinator = TransactionCoordinator::new(0);
+        let storage = create_test_storage();
+        let branch_id = BranchId::new();
+        let key = strata_core::types::Key::new_kv(
+            strata_core::types::Namespace::for_branch(branch_id),
+            "k",
+        );
+
+        let mut txn = coordinator.start_transaction(branch_id, &storage);
+        txn.put(key.clone(), strata_core::value::Value::Int(1))
+            .unwrap();
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            coordinator.commit(&mut txn, storage.as_ref(), Some(&mut wal))
+        }));
+        assert!(outcome.is_err());
+
+        let metrics = coordinator.metrics();
+        assert_eq!(metrics.active_count, 0);
+        assert_eq!(metrics.total_aborted, 1);
+
+        // The branch still commits without a WAL
+        let mut txn = coordinator.start_transaction(branch_id, &storage);
+        txn.put(key.clone(), strata_core::value::Value::Int(2))
+            .unwrap();
+        coordinator
+            .commit(&mut txn, storage.as_ref(), None)
+            .unwrap();
+        assert_eq!(coordinator.metrics().total_committed, 1);
+        assert_eq!(coordinator.active_count(), 0);
+    }
+
     #[test]
     fn test_version_monotonic() {
         let coordinator = TransactionCoordinator::new(100);
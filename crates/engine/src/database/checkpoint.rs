@@ -0,0 +1,132 @@
+//! Automatic checkpoint scheduling
+//!
+//! A [`CheckpointPolicy`] decides when a database's background checkpoint
+//! job runs: after a number of commits, on an interval, or once the WAL has
+//! grown by a number of bytes. Each run writes a snapshot and then compacts
+//! the WAL, so recovery time stays bounded without the application calling
+//! `checkpoint()` or `compact()` itself.
+//!
+//! Recovery replays the WAL rather than loading snapshots, so segments are
+//! only ever removed by compaction, which first rewrites the live data into
+//! a fresh segment.
+
+use std::time::Duration;
+use strata_core::{StrataError, StrataResult};
+
+/// How often commit- and size-based policies check whether a run is due
+pub(crate) const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// When the background checkpoint job runs
+///
+/// A `Duration` converts into [`CheckpointPolicy::Interval`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointPolicy {
+    /// After this many write transactions have committed
+    Transactions(u64),
+    /// On a fixed interval
+    Interval(Duration),
+    /// Once this many bytes have been appended to the WAL
+    WalBytes(u64),
+}
+
+impl From<Duration> for CheckpointPolicy {
+    fn from(interval: Duration) -> Self {
+        CheckpointPolicy::Interval(interval)
+    }
+}
+
+impl CheckpointPolicy {
+    pub(crate) fn validate(&self) -> StrataResult<()> {
+        let zero = match self {
+            CheckpointPolicy::Transactions(n) | CheckpointPolicy::WalBytes(n) => *n == 0,
+            CheckpointPolicy::Interval(interval) => interval.is_zero(),
+        };
+        if zero {
+            return Err(StrataError::invalid_input(format!(
+                "checkpoint policy must be greater than zero: {:?}",
+                self
+            )));
+        }
+        Ok(())
+    }
+
+    /// How often the job wakes up to evaluate the policy
+    pub(crate) fn poll_interval(&self) -> Duration {
+        match self {
+            CheckpointPolicy::Interval(interval) => *interval,
+            _ => CHECKPOINT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Commits and WAL growth since the last scheduled checkpoint
+pub(crate) struct CheckpointTrigger {
+    policy: CheckpointPolicy,
+    /// Database version at the last run
+    version: u64,
+    /// Total WAL bytes written at the last run
+    wal_bytes: u64,
+}
+
+impl CheckpointTrigger {
+    pub(crate) fn new(policy: CheckpointPolicy, version: u64, wal_bytes: u64) -> Self {
+        CheckpointTrigger {
+            policy,
+            version,
+            wal_bytes,
+        }
+    }
+
+    /// Whether a run is due at `version` with `wal_bytes` written in total
+    ///
+    /// Never due when nothing was committed since the last run, so an idle
+    /// database is not rewritten on every interval.
+    pub(crate) fn is_due(&self, version: u64, wal_bytes: u64) -> bool {
+        if version <= self.version {
+            return false;
+        }
+        match self.policy {
+            CheckpointPolicy::Transactions(n) => version - self.version >= n,
+            CheckpointPolicy::Interval(_) => true,
+            CheckpointPolicy::WalBytes(n) => wal_bytes.saturating_sub(self.wal_bytes) >= n,
+        }
+    }
+
+    /// Start counting again after a successful run
+    pub(crate) fn reset(&mut self, version: u64, wal_bytes: u64) {
+        self.version = version;
+        self.wal_bytes = wal_bytes;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_counts_from_last_run() {
+        let mut commits = CheckpointTrigger::new(CheckpointPolicy::Transactions(3), 10, 0);
+        assert!(!commits.is_due(12, 0));
+        assert!(commits.is_due(13, 0));
+        commits.reset(13, 0);
+        assert!(!commits.is_due(15, 0));
+
+        let bytes = CheckpointTrigger::new(CheckpointPolicy::WalBytes(100), 0, 50);
+        assert!(!bytes.is_due(1, 149));
+        assert!(bytes.is_due(1, 150));
+
+        let interval = CheckpointTrigger::new(Duration::from_secs(1).into(), 5, 0);
+        assert!(!interval.is_due(5, 1000));
+        assert!(interval.is_due(6, 0));
+    }
+
+    #[test]
+    fn test_zero_policies_are_rejected() {
+        assert!(CheckpointPolicy::Transactions(0).validate().is_err());
+        assert!(CheckpointPolicy::WalBytes(0).validate().is_err());
+        assert!(CheckpointPolicy::Interval(Duration::ZERO)
+            .validate()
+            .is_err());
+        assert!(CheckpointPolicy::WalBytes(1).validate().is_ok());
+    }
+}
@@ -5,13 +5,120 @@
 //! edit the file and restart — same model as Redis.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 use strata_core::{StrataError, StrataResult};
-use strata_durability::wal::DurabilityMode;
+use strata_durability::codec::{get_codec, AesGcmCodec, KeyProvider, StorageCodec, ZstdCodec};
+use strata_durability::wal::{DurabilityMode, WalConfig};
+use strata_durability::DEFAULT_FULL_SNAPSHOT_INTERVAL;
 
 /// Config file name placed in the database data directory.
 pub const CONFIG_FILE_NAME: &str = "strata.toml";
 
+/// Default time dropping a database waits for its final WAL flush.
+pub const DEFAULT_DROP_TIMEOUT_MS: u64 = 5_000;
+
+pub(crate) const DEFAULT_DROP_TIMEOUT: Duration = Duration::from_millis(DEFAULT_DROP_TIMEOUT_MS);
+
+/// Default longest a commit stays unsynced in standard durability.
+pub const DEFAULT_SYNC_INTERVAL_MS: u64 = 100;
+
+/// Default unsynced WAL bytes that trigger an fsync in standard durability.
+pub const DEFAULT_SYNC_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What dropping a database does with WAL records not yet fsynced.
+///
+/// Records are written to the WAL file on commit either way; this only
+/// decides whether drop waits for them to reach the disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropBehavior {
+    /// Fsync them, waiting at most the drop timeout.
+    #[default]
+    Flush,
+    /// Leave them unsynced and log how many there were.
+    Warn,
+    /// Leave them unsynced without logging.
+    Discard,
+}
+
+/// Source of the embeddings used by auto-embedding and semantic search.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub enum EmbedProvider {
+    /// Built-in MiniLM-L6-v2 model, loaded from local model files.
+    #[default]
+    Local,
+    /// OpenAI-compatible embeddings endpoint.
+    ///
+    /// Each text is sent as `{"model": model, "input": text}` and the first
+    /// `data[].embedding` of the response is used.
+    Http {
+        /// Endpoint URL, e.g. `https://api.openai.com/v1/embeddings`.
+        url: String,
+        /// Model name sent with each request.
+        model: String,
+        /// Sent as a bearer token when set.
+        api_key: Option<String>,
+    },
+}
+
+impl fmt::Debug for EmbedProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmbedProvider::Local => f.write_str("Local"),
+            EmbedProvider::Http {
+                url,
+                model,
+                api_key,
+            } => f
+                .debug_struct("Http")
+                .field("url", url)
+                .field("model", model)
+                .field("api_key", &api_key.as_ref().map(|_| "<redacted>"))
+                .finish(),
+        }
+    }
+}
+
+/// Source of the relevance scores used to rerank semantic search results.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub enum RerankProvider {
+    /// Built-in MiniLM-L6 cross-encoder, loaded from local model files.
+    #[default]
+    Local,
+    /// Cohere-compatible rerank endpoint.
+    ///
+    /// Candidates are sent as `{"model", "query", "documents"}` and
+    /// `results[].relevance_score` of the response is used.
+    Http {
+        /// Endpoint URL, e.g. `https://api.cohere.com/v2/rerank`.
+        url: String,
+        /// Model name sent with each request.
+        model: String,
+        /// Sent as a bearer token when set.
+        api_key: Option<String>,
+    },
+}
+
+impl fmt::Debug for RerankProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RerankProvider::Local => f.write_str("Local"),
+            RerankProvider::Http {
+                url,
+                model,
+                api_key,
+            } => f
+                .debug_struct("Http")
+                .field("url", url)
+                .field("model", model)
+                .field("api_key", &api_key.as_ref().map(|_| "<redacted>"))
+                .finish(),
+        }
+    }
+}
+
 /// Database configuration loaded from `strata.toml`.
 ///
 /// # Example
@@ -27,20 +134,178 @@ pub struct StrataConfig {
     /// Durability mode: `"standard"` or `"always"`.
     #[serde(default = "default_durability_str")]
     pub durability: String,
+    /// Longest a commit stays unsynced in standard durability, in milliseconds.
+    #[serde(default = "default_sync_interval_ms")]
+    pub sync_interval_ms: u64,
+    /// Unsynced WAL bytes that trigger an fsync in standard durability (0 = none).
+    #[serde(default = "default_sync_bytes")]
+    pub sync_bytes: u64,
     /// Enable automatic text embedding for semantic search.
     #[serde(default)]
     pub auto_embed: bool,
+    /// Interval in seconds between background WAL compactions (0 = disabled).
+    #[serde(default)]
+    pub compaction_interval_secs: u64,
+    /// Compression for WAL records and snapshots: `"none"` or `"zstd"`.
+    #[serde(default = "default_compression_str")]
+    pub compression: String,
+    /// Encryption at rest (disabled when absent).
+    #[serde(default)]
+    pub encryption: Option<EncryptionConfig>,
+    /// Checkpoints between full snapshots (0 or 1 = every snapshot is full).
+    #[serde(default = "default_snapshot_full_interval")]
+    pub snapshot_full_interval: u64,
+    /// Unsynced WAL records on drop: `"flush"`, `"warn"` or `"discard"`.
+    #[serde(default = "default_on_drop_str")]
+    pub on_drop: String,
+    /// Milliseconds drop waits for the final WAL flush (0 = no limit).
+    #[serde(default = "default_drop_timeout_ms")]
+    pub drop_timeout_ms: u64,
+    /// Record the environment (version, formats, host, models) in new
+    /// branches and exported bundles.
+    #[serde(default)]
+    pub capture_environment: bool,
+    /// Embedding provider (built-in model when absent).
+    #[serde(default)]
+    pub embed: Option<EmbedConfig>,
+    /// Reranking provider (built-in cross-encoder when absent).
+    ///
+    /// The `[rerank]` section has the same fields as `[embed]`.
+    #[serde(default)]
+    pub rerank: Option<EmbedConfig>,
+}
+
+/// `[embed]` section of `strata.toml`.
+///
+/// The API key is read from the environment variable named by
+/// `api_key_env`; the key itself never goes in the config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbedConfig {
+    /// `"local"` (built-in model) or `"http"` (OpenAI-compatible endpoint).
+    pub provider: String,
+    /// Endpoint URL (required for `"http"`).
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Model name sent with each request (required for `"http"`).
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Environment variable holding the API key.
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+}
+
+impl EmbedConfig {
+    /// Check the provider name and that `"http"` has `url` and `model`.
+    ///
+    /// `section` names the table in error messages (`"embed"` or `"rerank"`).
+    fn validate(&self, section: &str) -> StrataResult<()> {
+        match self.provider.as_str() {
+            "local" => Ok(()),
+            "http" => {
+                for (field, name) in [(&self.url, "url"), (&self.model, "model")] {
+                    if field.is_none() {
+                        return Err(StrataError::invalid_input(format!(
+                            "[{}] provider \"http\" requires '{}' in strata.toml",
+                            section, name
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            other => Err(StrataError::invalid_input(format!(
+                "Invalid {} provider '{}' in strata.toml. Expected \"local\" or \"http\".",
+                section, other
+            ))),
+        }
+    }
+
+    /// Endpoint URL, model and API key of an `"http"` provider, or `None`
+    /// for `"local"`.
+    fn http_endpoint(
+        &self,
+        section: &str,
+    ) -> StrataResult<Option<(String, String, Option<String>)>> {
+        self.validate(section)?;
+        if self.provider == "local" {
+            return Ok(None);
+        }
+        let api_key = self
+            .api_key_env
+            .as_ref()
+            .map(|var| {
+                std::env::var(var).map_err(|_| {
+                    StrataError::invalid_input(format!(
+                        "Environment variable '{}' for [{}] api_key_env is not set",
+                        var, section
+                    ))
+                })
+            })
+            .transpose()?;
+        Ok(Some((
+            self.url.clone().unwrap_or_default(),
+            self.model.clone().unwrap_or_default(),
+            api_key,
+        )))
+    }
+}
+
+/// `[encryption]` section of `strata.toml`.
+///
+/// Key providers are `"env:NAME"` (hex key in an environment variable) or
+/// `"file:PATH"` (key file). The key itself never goes in the config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionConfig {
+    /// Provider of the key used to encrypt new data.
+    pub key_provider: String,
+    /// Providers of retired keys, still accepted when reading.
+    #[serde(default)]
+    pub previous_key_providers: Vec<String>,
 }
 
 fn default_durability_str() -> String {
     "standard".to_string()
 }
 
+fn default_sync_interval_ms() -> u64 {
+    DEFAULT_SYNC_INTERVAL_MS
+}
+
+fn default_sync_bytes() -> u64 {
+    DEFAULT_SYNC_BYTES
+}
+
+fn default_compression_str() -> String {
+    "none".to_string()
+}
+
+fn default_snapshot_full_interval() -> u64 {
+    DEFAULT_FULL_SNAPSHOT_INTERVAL
+}
+
+fn default_on_drop_str() -> String {
+    "flush".to_string()
+}
+
+fn default_drop_timeout_ms() -> u64 {
+    DEFAULT_DROP_TIMEOUT_MS
+}
+
 impl Default for StrataConfig {
     fn default() -> Self {
         Self {
             durability: default_durability_str(),
+            sync_interval_ms: default_sync_interval_ms(),
+            sync_bytes: default_sync_bytes(),
             auto_embed: false,
+            compaction_interval_secs: 0,
+            compression: default_compression_str(),
+            encryption: None,
+            snapshot_full_interval: default_snapshot_full_interval(),
+            on_drop: default_on_drop_str(),
+            drop_timeout_ms: default_drop_timeout_ms(),
+            capture_environment: false,
+            embed: None,
+            rerank: None,
         }
     }
 }
@@ -50,10 +315,19 @@ impl StrataConfig {
     ///
     /// # Errors
     ///
-    /// Returns an error if the string is not `"standard"` or `"always"`.
+    /// Returns an error if the string is not `"standard"` or `"always"`, or
+    /// `sync_interval_ms` is 0.
     pub fn durability_mode(&self) -> StrataResult<DurabilityMode> {
         match self.durability.as_str() {
-            "standard" => Ok(DurabilityMode::standard_default()),
+            "standard" => {
+                if self.sync_interval_ms == 0 {
+                    return Err(StrataError::invalid_input(
+                        "Invalid sync_interval_ms 0 in strata.toml. Use durability = \"always\" \
+                         to fsync every commit.",
+                    ));
+                }
+                Ok(DurabilityMode::standard(self.sync_interval_ms))
+            }
             "always" => Ok(DurabilityMode::Always),
             other => Err(StrataError::invalid_input(format!(
                 "Invalid durability mode '{}' in strata.toml. Expected \"standard\" or \"always\".",
@@ -62,6 +336,132 @@ impl StrataConfig {
         }
     }
 
+    /// Build the WAL config carrying the `sync_bytes` threshold.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sync_bytes` exceeds the WAL segment size.
+    pub fn wal_config(&self) -> StrataResult<WalConfig> {
+        let config = WalConfig::default().with_buffered_sync_bytes(self.sync_bytes);
+        config.validate().map_err(|e| {
+            StrataError::invalid_input(format!(
+                "Invalid sync_bytes {} in strata.toml: {} ({} bytes)",
+                self.sync_bytes, e, config.segment_size
+            ))
+        })?;
+        Ok(config)
+    }
+
+    /// Parse the on_drop string into a `DropBehavior`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not `"flush"`, `"warn"` or `"discard"`.
+    pub fn drop_behavior(&self) -> StrataResult<DropBehavior> {
+        match self.on_drop.as_str() {
+            "flush" => Ok(DropBehavior::Flush),
+            "warn" => Ok(DropBehavior::Warn),
+            "discard" => Ok(DropBehavior::Discard),
+            other => Err(StrataError::invalid_input(format!(
+                "Invalid on_drop '{}' in strata.toml. Expected \"flush\", \"warn\" or \"discard\".",
+                other
+            ))),
+        }
+    }
+
+    /// How long drop waits for the final WAL flush, or `None` for no limit.
+    pub fn drop_timeout(&self) -> Option<Duration> {
+        (self.drop_timeout_ms > 0).then(|| Duration::from_millis(self.drop_timeout_ms))
+    }
+
+    /// Build the embedding provider from the `[embed]` section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section is invalid or `api_key_env` names an
+    /// unset variable.
+    pub fn embed_provider(&self) -> StrataResult<EmbedProvider> {
+        let Some(embed) = &self.embed else {
+            return Ok(EmbedProvider::Local);
+        };
+        Ok(match embed.http_endpoint("embed")? {
+            Some((url, model, api_key)) => EmbedProvider::Http {
+                url,
+                model,
+                api_key,
+            },
+            None => EmbedProvider::Local,
+        })
+    }
+
+    /// Build the reranking provider from the `[rerank]` section.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the section is invalid or `api_key_env` names an
+    /// unset variable.
+    pub fn rerank_provider(&self) -> StrataResult<RerankProvider> {
+        let Some(rerank) = &self.rerank else {
+            return Ok(RerankProvider::Local);
+        };
+        Ok(match rerank.http_endpoint("rerank")? {
+            Some((url, model, api_key)) => RerankProvider::Http {
+                url,
+                model,
+                api_key,
+            },
+            None => RerankProvider::Local,
+        })
+    }
+
+    /// Storage codec id for the configured compression.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not `"none"` or `"zstd"`.
+    pub fn codec_id(&self) -> StrataResult<&'static str> {
+        match self.compression.as_str() {
+            "none" => Ok("identity"),
+            "zstd" => Ok("zstd"),
+            other => Err(StrataError::invalid_input(format!(
+                "Invalid compression '{}' in strata.toml. Expected \"none\" or \"zstd\".",
+                other
+            ))),
+        }
+    }
+
+    /// Build the storage codec for the configured compression and encryption.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the compression is invalid or a key cannot be loaded.
+    pub fn build_codec(&self) -> StrataResult<Arc<dyn StorageCodec>> {
+        let codec_id = self.codec_id()?;
+        let Some(encryption) = &self.encryption else {
+            let codec =
+                get_codec(codec_id).map_err(|e| StrataError::invalid_input(e.to_string()))?;
+            return Ok(Arc::from(codec));
+        };
+
+        let load = |spec: &str| {
+            KeyProvider::parse(spec)
+                .and_then(|provider| provider.load())
+                .map_err(|e| StrataError::invalid_input(format!("{} (in strata.toml)", e)))
+        };
+        let key = load(&encryption.key_provider)?;
+        let previous = encryption
+            .previous_key_providers
+            .iter()
+            .map(|spec| load(spec))
+            .collect::<StrataResult<Vec<_>>>()?;
+
+        let mut codec = AesGcmCodec::new(&key).with_previous_keys(&previous);
+        if codec_id == "zstd" {
+            codec = codec.with_compression(ZstdCodec::default());
+        }
+        Ok(Arc::new(codec))
+    }
+
     /// Returns the default config file content with comments.
     pub fn default_toml() -> &'static str {
         r#"# Strata database configuration
@@ -71,9 +471,75 @@ impl StrataConfig {
 #   "always"   = fsync every commit, zero data loss
 durability = "standard"
 
+# Standard durability fsyncs when the oldest unsynced commit is
+# sync_interval_ms old or sync_bytes of WAL are unsynced, whichever comes
+# first, so a crash loses at most that much. (0 bytes = interval only)
+sync_interval_ms = 100
+sync_bytes = 4194304
+
 # Auto-embed: automatically generate embeddings for text data (default: false)
 # Requires the "embed" feature to be compiled in.
 auto_embed = false
+
+# Compaction interval in seconds (default: 0 = disabled)
+# When set, the WAL is periodically rewritten to contain only live data.
+compaction_interval_secs = 0
+
+# Compression for WAL records and snapshots: "none" (default) or "zstd"
+# Large payloads are zstd-compressed; existing files stay readable either way.
+compression = "none"
+
+# Checkpoints between full snapshots (default: 8; 0 or 1 = always full)
+# Checkpoints in between only store sections that changed since the last one.
+snapshot_full_interval = 8
+
+# What closing the database does with WAL records not yet fsynced
+#   "flush"   = fsync them (default), waiting at most drop_timeout_ms
+#   "warn"    = leave them to the OS and log how many there were
+#   "discard" = leave them to the OS silently
+# Unsynced records survive a process exit but may be lost on power failure.
+on_drop = "flush"
+
+# Milliseconds closing waits for the final fsync (default: 5000; 0 = no limit)
+# Keeps a host from hanging on exit when the disk stops responding.
+drop_timeout_ms = 5000
+
+# Record the environment in new branches and exported bundles (default: false)
+# Stores the Strata version, storage formats, host and models in use, so
+# imports can warn about incompatibilities and results stay attributable.
+capture_environment = false
+
+# Encryption at rest (default: disabled)
+# WAL records, snapshots and exported bundles are sealed with AES-256-GCM.
+# Keys are loaded from "env:NAME" (64 hex chars) or "file:PATH".
+# To rotate: set a new key_provider, list the old one in
+# previous_key_providers, and compact; the old key can then be dropped.
+#
+# [encryption]
+# key_provider = "env:STRATA_ENCRYPTION_KEY"
+# previous_key_providers = []
+
+# Embedding provider for auto_embed and semantic search (default: "local")
+#   "local" = built-in MiniLM-L6-v2 model files (see `strata setup`)
+#   "http"  = OpenAI-compatible embeddings endpoint; no model files needed
+# The API key is read from the environment variable named by api_key_env.
+#
+# [embed]
+# provider = "http"
+# url = "https://api.openai.com/v1/embeddings"
+# model = "text-embedding-3-small"
+# api_key_env = "OPENAI_API_KEY"
+
+# Reranker for reranked semantic search (default: "local")
+#   "local" = built-in MiniLM-L6 cross-encoder model files
+#   "http"  = Cohere-compatible rerank endpoint; no model files needed
+# Same fields as [embed].
+#
+# [rerank]
+# provider = "http"
+# url = "https://api.cohere.com/v2/rerank"
+# model = "rerank-v3.5"
+# api_key_env = "COHERE_API_KEY"
 "#
     }
 
@@ -97,8 +563,25 @@ auto_embed = false
                 e
             ))
         })?;
-        // Validate the durability value eagerly
+        // Validate the durability, sync, compression, drop, embed, rerank and key provider values eagerly
         config.durability_mode()?;
+        config.wal_config()?;
+        config.codec_id()?;
+        config.drop_behavior()?;
+        if let Some(embed) = &config.embed {
+            embed.validate("embed")?;
+        }
+        if let Some(rerank) = &config.rerank {
+            rerank.validate("rerank")?;
+        }
+        if let Some(encryption) = &config.encryption {
+            for spec in
+                std::iter::once(&encryption.key_provider).chain(&encryption.previous_key_providers)
+            {
+                KeyProvider::parse(spec)
+                    .map_err(|e| StrataError::invalid_input(format!("{} (in strata.toml)", e)))?;
+            }
+        }
         Ok(config)
     }
 
@@ -147,6 +630,35 @@ mod tests {
         assert_eq!(config.durability_mode().unwrap(), DurabilityMode::Always);
     }
 
+    #[test]
+    fn parse_sync_window() {
+        let config = StrataConfig::default();
+        assert_eq!(
+            config.durability_mode().unwrap(),
+            DurabilityMode::standard_default()
+        );
+        assert_eq!(
+            config.wal_config().unwrap().buffered_sync_bytes,
+            DEFAULT_SYNC_BYTES
+        );
+
+        let config: StrataConfig =
+            toml::from_str("sync_interval_ms = 20\nsync_bytes = 65536").unwrap();
+        assert!(matches!(
+            config.durability_mode().unwrap(),
+            DurabilityMode::Standard {
+                interval_ms: 20,
+                ..
+            }
+        ));
+        assert_eq!(config.wal_config().unwrap().buffered_sync_bytes, 65536);
+
+        let config: StrataConfig = toml::from_str("sync_interval_ms = 0").unwrap();
+        assert!(config.durability_mode().is_err());
+        let config: StrataConfig = toml::from_str("sync_bytes = 1099511627776").unwrap();
+        assert!(config.wal_config().is_err());
+    }
+
     #[test]
     fn parse_invalid_mode_returns_error() {
         let config: StrataConfig = toml::from_str("durability = \"turbo\"").unwrap();
@@ -157,6 +669,167 @@ mod tests {
     fn default_toml_parses_correctly() {
         let config: StrataConfig = toml::from_str(StrataConfig::default_toml()).unwrap();
         assert_eq!(config.durability, "standard");
+        assert_eq!(config.sync_interval_ms, DEFAULT_SYNC_INTERVAL_MS);
+        assert_eq!(config.sync_bytes, DEFAULT_SYNC_BYTES);
+        assert_eq!(config.compaction_interval_secs, 0);
+    }
+
+    #[test]
+    fn parse_compaction_interval() {
+        let config: StrataConfig = toml::from_str("compaction_interval_secs = 3600").unwrap();
+        assert_eq!(config.compaction_interval_secs, 3600);
+    }
+
+    #[test]
+    fn parse_snapshot_full_interval() {
+        let config = StrataConfig::default();
+        assert_eq!(
+            config.snapshot_full_interval,
+            DEFAULT_FULL_SNAPSHOT_INTERVAL
+        );
+
+        let config: StrataConfig = toml::from_str("snapshot_full_interval = 1").unwrap();
+        assert_eq!(config.snapshot_full_interval, 1);
+    }
+
+    #[test]
+    fn parse_capture_environment() {
+        assert!(!StrataConfig::default().capture_environment);
+        let config: StrataConfig = toml::from_str(StrataConfig::default_toml()).unwrap();
+        assert!(!config.capture_environment);
+
+        let config: StrataConfig = toml::from_str("capture_environment = true").unwrap();
+        assert!(config.capture_environment);
+    }
+
+    #[test]
+    fn parse_on_drop() {
+        let config = StrataConfig::default();
+        assert_eq!(config.drop_behavior().unwrap(), DropBehavior::Flush);
+        assert_eq!(
+            config.drop_timeout(),
+            Some(Duration::from_millis(DEFAULT_DROP_TIMEOUT_MS))
+        );
+
+        let config: StrataConfig =
+            toml::from_str("on_drop = \"warn\"\ndrop_timeout_ms = 0").unwrap();
+        assert_eq!(config.drop_behavior().unwrap(), DropBehavior::Warn);
+        assert_eq!(config.drop_timeout(), None);
+
+        let config: StrataConfig = toml::from_str("on_drop = \"block\"").unwrap();
+        assert!(config.drop_behavior().is_err());
+    }
+
+    #[test]
+    fn parse_compression() {
+        let config = StrataConfig::default();
+        assert_eq!(config.codec_id().unwrap(), "identity");
+
+        let config: StrataConfig = toml::from_str("compression = \"zstd\"").unwrap();
+        assert_eq!(config.codec_id().unwrap(), "zstd");
+
+        let config: StrataConfig = toml::from_str("compression = \"lz4\"").unwrap();
+        assert!(config.codec_id().is_err());
+    }
+
+    #[test]
+    fn parse_encryption() {
+        let config = StrataConfig::default();
+        assert!(config.encryption.is_none());
+        assert_eq!(config.build_codec().unwrap().codec_id(), "identity");
+
+        let dir = TempDir::new().unwrap();
+        let key_path = dir.path().join("db.key");
+        std::fs::write(&key_path, "ab".repeat(32)).unwrap();
+        let config: StrataConfig = toml::from_str(&format!(
+            "compression = \"zstd\"\n[encryption]\nkey_provider = \"file:{}\"\n",
+            key_path.display()
+        ))
+        .unwrap();
+        assert_eq!(config.build_codec().unwrap().codec_id(), "aes-gcm-256");
+
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[encryption]\nkey_provider = \"vault:x\"\n").unwrap();
+        assert!(StrataConfig::from_file(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "[encryption]\nkey_provider = \"file:/nonexistent/key\"\n",
+        )
+        .unwrap();
+        let config = StrataConfig::from_file(&path).unwrap();
+        assert!(config.build_codec().is_err());
+    }
+
+    #[test]
+    fn parse_embed() {
+        let config = StrataConfig::default();
+        assert_eq!(config.embed_provider().unwrap(), EmbedProvider::Local);
+
+        let config: StrataConfig = toml::from_str(
+            "[embed]\nprovider = \"http\"\nurl = \"http://localhost:8080/v1/embeddings\"\nmodel = \"bge-small\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.embed_provider().unwrap(),
+            EmbedProvider::Http {
+                url: "http://localhost:8080/v1/embeddings".into(),
+                model: "bge-small".into(),
+                api_key: None,
+            }
+        );
+
+        std::env::set_var("STRATA_TEST_EMBED_KEY", "sk-test");
+        let config: StrataConfig = toml::from_str(
+            "[embed]\nprovider = \"http\"\nurl = \"u\"\nmodel = \"m\"\napi_key_env = \"STRATA_TEST_EMBED_KEY\"\n",
+        )
+        .unwrap();
+        match config.embed_provider().unwrap() {
+            EmbedProvider::Http { api_key, .. } => assert_eq!(api_key.as_deref(), Some("sk-test")),
+            other => panic!("expected Http, got {:?}", other),
+        }
+        assert!(!format!("{:?}", config.embed_provider().unwrap()).contains("sk-test"));
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[embed]\nprovider = \"http\"\nurl = \"u\"\n").unwrap();
+        assert!(StrataConfig::from_file(&path).is_err());
+        std::fs::write(&path, "[embed]\nprovider = \"onnx\"\n").unwrap();
+        assert!(StrataConfig::from_file(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "[embed]\nprovider = \"http\"\nurl = \"u\"\nmodel = \"m\"\napi_key_env = \"STRATA_TEST_EMBED_KEY_UNSET\"\n",
+        )
+        .unwrap();
+        let config = StrataConfig::from_file(&path).unwrap();
+        assert!(config.embed_provider().is_err());
+    }
+
+    #[test]
+    fn parse_rerank() {
+        let config = StrataConfig::default();
+        assert_eq!(config.rerank_provider().unwrap(), RerankProvider::Local);
+
+        let config: StrataConfig = toml::from_str(
+            "[rerank]\nprovider = \"http\"\nurl = \"http://localhost:8080/v1/rerank\"\nmodel = \"bge-reranker\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.rerank_provider().unwrap(),
+            RerankProvider::Http {
+                url: "http://localhost:8080/v1/rerank".into(),
+                model: "bge-reranker".into(),
+                api_key: None,
+            }
+        );
+        assert_eq!(config.embed_provider().unwrap(), EmbedProvider::Local);
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&path, "[rerank]\nprovider = \"http\"\nmodel = \"m\"\n").unwrap();
+        let err = StrataConfig::from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("[rerank]"), "{}", err);
     }
 
     #[test]
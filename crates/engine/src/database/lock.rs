@@ -0,0 +1,270 @@
+//! Single-writer lock on the database directory
+//!
+//! A read-write open takes an exclusive advisory lock on `<dir>/.lock` and
+//! writes its owner (pid, host, open time) into the file. The kernel drops
+//! the lock when the process exits, so a crashed writer never blocks the next
+//! open; the owner record it leaves behind is reported as a stale lock.
+//!
+//! When the lock is contended the second open fails with
+//! [`StrataError::AlreadyOpen`] naming the holder. [`force_unlock`] lets an
+//! operator break a lock whose owner is gone (e.g. a lock kept alive by a
+//! network filesystem after the writer's host crashed).
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use strata_core::{StrataError, StrataResult};
+use tracing::warn;
+
+/// Name of the lock file inside the database directory
+pub(crate) const LOCK_FILE: &str = ".lock";
+
+/// Process recorded as the owner of a database lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockOwner {
+    /// Process ID of the writer
+    pub pid: u32,
+    /// Host the writer runs on
+    pub host: String,
+    /// When the writer opened the database (seconds since the Unix epoch)
+    pub opened_at: u64,
+}
+
+impl LockOwner {
+    /// Owner record for the current process
+    fn current() -> Self {
+        LockOwner {
+            pid: std::process::id(),
+            host: hostname(),
+            opened_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Parse the `key=value` lines written by [`LockOwner::render`]
+    ///
+    /// Returns `None` for an empty or unrecognisable file.
+    fn parse(text: &str) -> Option<Self> {
+        let mut pid = None;
+        let mut host = None;
+        let mut opened_at = 0;
+        for line in text.lines() {
+            match line.split_once('=') {
+                Some(("pid", v)) => pid = v.trim().parse().ok(),
+                Some(("host", v)) => host = Some(v.trim().to_string()),
+                Some(("opened_at", v)) => opened_at = v.trim().parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        Some(LockOwner {
+            pid: pid?,
+            host: host.unwrap_or_else(|| "unknown".to_string()),
+            opened_at,
+        })
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "pid={}\nhost={}\nopened_at={}\n",
+            self.pid, self.host, self.opened_at
+        )
+    }
+
+    /// Whether the owning process is still running
+    ///
+    /// Only answerable for owners on this host on systems with `/proc`;
+    /// returns `None` otherwise.
+    pub fn is_alive(&self) -> Option<bool> {
+        if self.host != hostname() || !Path::new("/proc/self").exists() {
+            return None;
+        }
+        Some(Path::new(&format!("/proc/{}", self.pid)).exists())
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pid {} on host '{}'", self.pid, self.host)
+    }
+}
+
+/// Exclusive lock held by a read-write [`Database`](super::Database)
+///
+/// Released on drop, or by the kernel if the process dies without dropping
+/// it.
+pub(crate) struct DatabaseLock {
+    file: File,
+}
+
+impl DatabaseLock {
+    /// Take the lock on `dir`, recording this process as the owner
+    pub(crate) fn acquire(dir: &Path) -> StrataResult<Self> {
+        let lock_path = dir.join(LOCK_FILE);
+        let mut file = open_lock_file(&lock_path)?;
+        if let Err(e) = fs2::FileExt::try_lock_exclusive(&file) {
+            if !is_contended(&e) {
+                return Err(StrataError::storage(format!(
+                    "failed to lock '{}': {}",
+                    lock_path.display(),
+                    e
+                )));
+            }
+            return Err(already_open(dir, read_owner(&mut file)));
+        }
+
+        // The lock is free but an owner record survived: the previous writer
+        // exited without closing the database.
+        if let Some(stale) = read_owner(&mut file) {
+            warn!(
+                target: "strata::db",
+                path = ?dir,
+                owner = %stale,
+                "Previous writer did not shut down cleanly; taking over its stale lock"
+            );
+        }
+
+        let owner = LockOwner::current();
+        let write = |file: &mut File| -> std::io::Result<()> {
+            file.set_len(0)?;
+            file.seek(SeekFrom::Start(0))?;
+            file.write_all(owner.render().as_bytes())?;
+            file.sync_data()
+        };
+        write(&mut file).map_err(|e| {
+            StrataError::storage(format!(
+                "failed to write lock file '{}': {}",
+                lock_path.display(),
+                e
+            ))
+        })?;
+        Ok(DatabaseLock { file })
+    }
+}
+
+impl Drop for DatabaseLock {
+    /// Clear the owner record and release the lock
+    ///
+    /// An empty lock file tells the next writer the previous one shut down
+    /// cleanly.
+    fn drop(&mut self) {
+        let _ = self.file.set_len(0);
+        let _ = fs2::FileExt::unlock(&self.file);
+    }
+}
+
+/// Break the lock on `dir` left behind by a writer that is no longer running
+///
+/// Returns `Ok(None)` when the directory was not locked and
+/// `Ok(Some(owner))` when a held lock was removed. Refuses with
+/// [`StrataError::AlreadyOpen`] when the owner is known to still be running
+/// on this host.
+pub(crate) fn force_unlock(dir: &Path) -> StrataResult<Option<LockOwner>> {
+    let lock_path = dir.join(LOCK_FILE);
+    if !lock_path.exists() {
+        return Ok(None);
+    }
+    let mut file = open_lock_file(&lock_path)?;
+    if fs2::FileExt::try_lock_exclusive(&file).is_ok() {
+        // Nobody holds it; a stale record is cleared on the next open.
+        let _ = fs2::FileExt::unlock(&file);
+        return Ok(None);
+    }
+
+    let owner = read_owner(&mut file);
+    if owner.as_ref().and_then(LockOwner::is_alive) == Some(true) {
+        return Err(already_open(dir, owner));
+    }
+
+    // Unlinking the file detaches the old lock: the next open creates a
+    // fresh file and locks that instead.
+    std::fs::remove_file(&lock_path).map_err(|e| {
+        StrataError::storage(format!(
+            "failed to remove lock file '{}': {}",
+            lock_path.display(),
+            e
+        ))
+    })?;
+    let owner = owner.unwrap_or_else(|| LockOwner {
+        pid: 0,
+        host: "unknown".to_string(),
+        opened_at: 0,
+    });
+    warn!(target: "strata::db", path = ?dir, owner = %owner, "Forcibly removed database lock");
+    Ok(Some(owner))
+}
+
+fn open_lock_file(lock_path: &Path) -> StrataResult<File> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(lock_path)
+        .map_err(|e| StrataError::storage(format!("failed to open lock file: {}", e)))
+}
+
+fn read_owner(file: &mut File) -> Option<LockOwner> {
+    let mut text = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut text).ok()?;
+    LockOwner::parse(&text)
+}
+
+fn is_contended(e: &std::io::Error) -> bool {
+    e.raw_os_error() == fs2::lock_contended_error().raw_os_error()
+        || e.kind() == std::io::ErrorKind::WouldBlock
+}
+
+fn already_open(dir: &Path, owner: Option<LockOwner>) -> StrataError {
+    let holder = match owner {
+        Some(owner) if owner.is_alive() == Some(false) => {
+            format!("{} (no longer running; use force unlock to recover)", owner)
+        }
+        Some(owner) => owner.to_string(),
+        None => "another process".to_string(),
+    };
+    StrataError::already_open(dir.display().to_string(), holder)
+}
+
+pub(crate) fn hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_round_trip() {
+        let owner = LockOwner::current();
+        assert_eq!(LockOwner::parse(&owner.render()), Some(owner));
+        assert_eq!(LockOwner::parse(""), None);
+        assert_eq!(LockOwner::parse("garbage"), None);
+    }
+
+    #[test]
+    fn test_is_alive() {
+        if !Path::new("/proc/self").exists() {
+            return;
+        }
+        let mut owner = LockOwner::current();
+        assert_eq!(owner.is_alive(), Some(true));
+        owner.pid = u32::MAX;
+        assert_eq!(owner.is_alive(), Some(false));
+        assert!(already_open(Path::new("/db"), Some(owner.clone()))
+            .to_string()
+            .contains("no longer running"));
+        owner.host = "some-other-host".to_string();
+        assert_eq!(owner.is_alive(), None);
+    }
+}
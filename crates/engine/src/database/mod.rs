@@ -19,39 +19,67 @@
 //!
 //! Per spec Section 4: Implicit transactions wrap legacy-style operations.
 
+mod backup;
+mod checkpoint;
 pub mod config;
+mod lock;
+mod read_cache;
 mod registry;
 mod transactions;
 
-pub use config::StrataConfig;
+pub use backup::{BackupFile, BackupManifest};
+pub use checkpoint::CheckpointPolicy;
+pub use config::{DropBehavior, EmbedProvider, RerankProvider, StrataConfig};
+pub use lock::LockOwner;
+pub use read_cache::ReadCacheStats;
 pub use registry::OPEN_DATABASES;
 pub use transactions::RetryConfig;
 
-use crate::coordinator::TransactionCoordinator;
+use self::backup::BackupRegistry;
+use self::checkpoint::CheckpointTrigger;
+use self::lock::DatabaseLock;
+use self::read_cache::ReadCache;
+use crate::background::{Worker, WorkerPool};
+use crate::coordinator::{begin_span, TransactionCoordinator, TransactionMetrics};
+use crate::search::TextIndex;
 use crate::transaction::TransactionPool;
 use dashmap::DashMap;
 use parking_lot::Mutex as ParkingMutex;
 use std::any::{Any, TypeId};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use strata_concurrency::{RecoveryCoordinator, TransactionContext};
-use strata_core::types::{BranchId, Key};
-use strata_core::StrataError;
+use std::collections::{BTreeMap, HashMap};
+use strata_concurrency::{
+    KeyLockTable, RecoveryCoordinator, RecoveryObserver, TransactionContext, TransactionPayload,
+};
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::{PrimitiveType, StrataError};
+use strata_core::value::Value;
 use strata_core::{StrataResult, VersionedValue};
 use strata_core::types::TypeTag;
-use strata_durability::codec::IdentityCodec;
-use strata_durability::wal::{DurabilityMode, WalConfig, WalWriter};
+use strata_durability::codec::{
+    CompressionDictionary, IdentityCodec, StorageCodec, AES_GCM_CODEC_ID, DEFAULT_DICTIONARY_SIZE,
+};
+use strata_durability::wal::{DurabilityMode, LossWindow, WalConfig, WalReader, WalWriter};
+use strata_durability::branch_bundle::EnvironmentInfo;
+use strata_durability::format::{WalRecord, SEGMENT_FORMAT_VERSION, SEGMENT_FORMAT_VERSION_CODEC};
+use strata_durability::retention::RetentionPolicy;
 use strata_durability::{
-    CheckpointCoordinator, CheckpointData, CheckpointError, CompactionError, ManifestError,
-    ManifestManager, WalOnlyCompactor,
+    CheckpointCoordinator, CheckpointData, CheckpointError, CompactInfo, ManifestError,
+    ManifestManager, DEFAULT_FULL_SNAPSHOT_INTERVAL,
 };
 use strata_durability::{
     BranchSnapshotEntry, EventSnapshotEntry, JsonSnapshotEntry, KvSnapshotEntry,
     StateSnapshotEntry,
 };
-use strata_storage::ShardedStore;
-use tracing::{info, warn};
+use strata_storage::{
+    MaterializedSnapshot, MemoryBudget, MemoryBudgetStats, ShardUsage, ShardedStore,
+};
+use tracing::{debug, info, warn};
+
+/// File in the data directory holding the saved full-text index
+const TEXT_INDEX_FILE: &str = "text.idx";
 
 // ============================================================================
 // Auto-Embed State
@@ -59,9 +87,12 @@ use tracing::{info, warn};
 
 /// In-memory state for auto-embedding configuration.
 ///
-/// Stored as a Database extension to share the enabled flag across all handles.
+/// Stored as a Database extension to share the enabled flag and the
+/// embedding and reranking providers across all handles.
 pub struct AutoEmbedState {
     enabled: AtomicBool,
+    provider: parking_lot::RwLock<EmbedProvider>,
+    rerank_provider: parking_lot::RwLock<RerankProvider>,
     /// Tracks which shadow collections have been created (keyed by "branch_id/collection_name").
     /// Prevents repeated `create_system_collection` calls on every write.
     pub shadow_collections_created: DashMap<String, ()>,
@@ -71,6 +102,8 @@ impl Default for AutoEmbedState {
     fn default() -> Self {
         Self {
             enabled: AtomicBool::new(false),
+            provider: parking_lot::RwLock::new(EmbedProvider::Local),
+            rerank_provider: parking_lot::RwLock::new(RerankProvider::Local),
             shadow_collections_created: DashMap::new(),
         }
     }
@@ -119,6 +152,14 @@ pub(crate) enum PersistenceMode {
     /// depending on the `DurabilityMode`.
     #[default]
     Disk,
+
+    /// Attached to another process's disk database without the write lock
+    ///
+    /// - No files created or modified
+    /// - WAL replayed once at open; later writes by the owner are not seen
+    /// - Commits with writes are rejected
+    /// - Not registered in the global registry
+    ReadOnly,
 }
 
 // ============================================================================
@@ -177,6 +218,9 @@ pub struct Database {
     /// Current durability mode
     durability_mode: DurabilityMode,
 
+    /// Storage codec for WAL records and snapshots (from `compression` and `encryption`)
+    codec: Arc<dyn StorageCodec>,
+
     /// Flag to track if database is accepting new transactions
     ///
     /// Set to false during shutdown to reject new transactions.
@@ -190,27 +234,73 @@ pub struct Database {
     /// Extensions are lazily initialized on first access via `extension<T>()`.
     extensions: DashMap<TypeId, Arc<dyn Any + Send + Sync>>,
 
-    /// Shutdown signal for the background WAL flush thread (Standard mode only)
-    flush_shutdown: Arc<AtomicBool>,
+    /// Shared pool that runs background jobs, if opened with `open_in_pool()`
+    ///
+    /// Without a pool, each background job gets a dedicated thread.
+    worker_pool: Option<WorkerPool>,
 
-    /// Handle for the background WAL flush thread
+    /// Background WAL flush job
     ///
-    /// In Standard mode, a background thread periodically calls sync_if_overdue()
+    /// In Standard mode, a background worker periodically calls sync_if_overdue()
     /// to flush WAL data to disk without blocking the write path (#969).
-    flush_handle: ParkingMutex<Option<std::thread::JoinHandle<()>>>,
+    flush_worker: ParkingMutex<Option<Worker>>,
+
+    /// Background compaction job and its interval
+    ///
+    /// Set by `schedule_compaction()`; runs `compact()` on a fixed interval.
+    compaction_worker: ParkingMutex<Option<(Worker, std::time::Duration)>>,
+
+    /// Background checkpoint job and its policy
+    ///
+    /// Set by `schedule_checkpoints()`; checkpoints and compacts whenever
+    /// the policy is met.
+    checkpoint_worker: ParkingMutex<Option<(Worker, CheckpointPolicy)>>,
+
+    /// Checkpoints between full snapshots
+    ///
+    /// Snapshots in between reference sections unchanged since the last one.
+    snapshot_full_interval: AtomicU64,
+
+    /// Compress snapshot values against per-primitive dictionaries
+    ///
+    /// Dictionaries are trained at the first checkpoint with enough data and
+    /// kept in the MANIFEST.
+    snapshot_dictionaries: AtomicBool,
+
+    /// Record the environment in new branches and exported bundles
+    capture_environment: AtomicBool,
+
+    /// LRU cache of hot KV and state point reads (capacity 0 = off)
+    read_cache: ReadCache,
+
+    /// Per-key locks taken by `TransactionContext::lock()`
+    key_locks: Arc<KeyLockTable>,
+
+    /// Highest WAL transaction ID a read-only instance has applied
+    ///
+    /// `refresh()` replays the records after it.
+    replayed_txn_id: ParkingMutex<u64>,
+
+    /// What drop does with unsynced WAL records, and how long it waits
+    ///
+    /// A timeout of `None` waits for the final flush however long it takes.
+    drop_behavior: ParkingMutex<(DropBehavior, Option<std::time::Duration>)>,
+
+    /// Hot backups in progress; compaction leaves the WAL alone while any are
+    backups: BackupRegistry,
 
     /// Exclusive lock file preventing concurrent process access to the same database.
     ///
-    /// Held for the lifetime of the Database. Dropped automatically when the
-    /// Database is dropped, releasing the lock. None for ephemeral databases.
-    _lock_file: Option<std::fs::File>,
+    /// Held for the lifetime of the Database and released on drop once the
+    /// WAL is closed. None for read-only and ephemeral databases.
+    _lock_file: Option<DatabaseLock>,
 }
 
 impl Database {
     /// Open database at given path with automatic recovery
     ///
-    /// Reads `strata.toml` from the data directory to determine durability mode.
-    /// If no config file exists, creates one with defaults (standard durability).
+    /// Reads `strata.toml` from the data directory to determine durability mode
+    /// and the background compaction schedule. If no config file exists, creates one with defaults (standard durability).
     ///
     /// # Thread Safety
     ///
@@ -250,6 +340,66 @@ impl Database {
     /// let db = Database::open("/path/to/data")?;
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> StrataResult<Arc<Self>> {
+        Self::open_with_pool(path, None, RecoveryObserver::default())
+    }
+
+    /// Open database at given path, running its background jobs on `pool`
+    ///
+    /// Like [`Database::open`], but the WAL flush and scheduled compaction
+    /// share `pool`'s threads instead of spawning their own, so an embedder
+    /// opening many databases controls the total thread count. The database
+    /// keeps the pool alive while it is open.
+    ///
+    /// If this process already has the path open, the existing instance is
+    /// returned and keeps running its jobs where they already are.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// use strata_engine::{Database, WorkerPool};
+    ///
+    /// let pool = WorkerPool::builder().threads(2).build()?;
+    /// let db = Database::open_in_pool("/path/to/data", &pool)?;
+    /// ```
+    pub fn open_in_pool<P: AsRef<Path>>(path: P, pool: &WorkerPool) -> StrataResult<Arc<Self>> {
+        Self::open_with_pool(path, Some(pool.clone()), RecoveryObserver::default())
+    }
+
+    /// Open database at given path, watching or cancelling WAL replay
+    ///
+    /// Like [`Database::open`] (or [`Database::open_in_pool`] when `pool` is
+    /// given), with `observer` receiving progress reports while the WAL is
+    /// replayed. Cancelling its token makes the open fail with
+    /// [`StrataError::Cancelled`]; the write lock is released and nothing on
+    /// disk is changed, so the database can be opened again later.
+    ///
+    /// The observer is unused if this process already has the path open.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// use strata_engine::{CancellationToken, Database, RecoveryObserver};
+    ///
+    /// let token = CancellationToken::new();
+    /// let observer = RecoveryObserver {
+    ///     on_progress: Some(Arc::new(|p| println!("{:.0}%", p.fraction() * 100.0))),
+    ///     cancel: Some(token.clone()),
+    /// };
+    /// let db = Database::open_with_recovery("/path/to/data", None, observer)?;
+    /// ```
+    pub fn open_with_recovery<P: AsRef<Path>>(
+        path: P,
+        pool: Option<&WorkerPool>,
+        observer: RecoveryObserver,
+    ) -> StrataResult<Arc<Self>> {
+        Self::open_with_pool(path, pool.cloned(), observer)
+    }
+
+    fn open_with_pool<P: AsRef<Path>>(
+        path: P,
+        worker_pool: Option<WorkerPool>,
+        observer: RecoveryObserver,
+    ) -> StrataResult<Arc<Self>> {
         let data_dir = path.as_ref().to_path_buf();
         std::fs::create_dir_all(&data_dir).map_err(StrataError::from)?;
 
@@ -257,7 +407,17 @@ impl Database {
         config::StrataConfig::write_default_if_missing(&config_path)?;
         let cfg = config::StrataConfig::from_file(&config_path)?;
         let mode = cfg.durability_mode()?;
+        let wal_config = cfg.wal_config()?;
         let auto_embed = cfg.auto_embed;
+        let embed_provider = cfg.embed_provider()?;
+        let rerank_provider = cfg.rerank_provider()?;
+        let compaction_interval_secs = cfg.compaction_interval_secs;
+        let snapshot_full_interval = cfg.snapshot_full_interval;
+        let snapshot_dictionaries = cfg.codec_id()? == "zstd";
+        let capture_environment = cfg.capture_environment;
+        let drop_behavior = cfg.drop_behavior()?;
+        let drop_timeout = cfg.drop_timeout();
+        let codec = cfg.build_codec()?;
 
         #[cfg(not(feature = "embed"))]
         let auto_embed = if auto_embed {
@@ -270,19 +430,174 @@ impl Database {
             auto_embed
         };
 
-        let db = Self::open_with_mode(path, mode)?;
+        let db = Self::open_with_codec(path, mode, wal_config, codec, worker_pool, observer)?;
         // Only apply config-based auto_embed on fresh creation (strong_count == 1
         // means we just created it; the registry only holds a Weak reference).
         // This avoids overriding a runtime toggle set via OpenOptions.
         if Arc::strong_count(&db) == 1 {
             db.set_auto_embed(auto_embed);
+            db.set_embed_provider(embed_provider);
+            db.set_rerank_provider(rerank_provider);
+            db.set_snapshot_full_interval(snapshot_full_interval);
+            db.set_snapshot_dictionaries(snapshot_dictionaries);
+            db.set_capture_environment(capture_environment);
+            db.set_drop_behavior(drop_behavior, drop_timeout);
+            if compaction_interval_secs > 0 {
+                db.schedule_compaction(std::time::Duration::from_secs(compaction_interval_secs))?;
+            }
+        }
+        Ok(db)
+    }
+
+    /// Break the write lock left on `path` by a writer that crashed
+    ///
+    /// Recovery path for operators: a normal open already takes over the
+    /// lock of a process that exited, so this is only needed when the lock
+    /// is still held but its owner is gone, e.g. a network filesystem that
+    /// kept the lock of a crashed host. Call it before [`Database::open`].
+    ///
+    /// Returns the owner whose lock was removed, or `None` if the directory
+    /// was not locked.
+    ///
+    /// # Errors
+    ///
+    /// * [`StrataError::AlreadyOpen`] if the owner is still running on this host
+    /// * The lock file cannot be read or removed
+    pub fn force_unlock<P: AsRef<Path>>(path: P) -> StrataResult<Option<LockOwner>> {
+        lock::force_unlock(path.as_ref())
+    }
+
+    /// Open an existing database read-only, without taking the write lock
+    ///
+    /// Lets inspection tooling attach to a database that another process
+    /// holds open for writing. The reader replays every complete WAL record
+    /// present at open time and ignores a partially written tail; it does
+    /// not follow writes made afterwards. Nothing in the data directory is
+    /// created or modified, and `strata.toml` defaults apply if it is missing.
+    ///
+    /// If this process already has the path open, the existing instance is
+    /// returned instead.
+    ///
+    /// # Errors
+    ///
+    /// * The directory does not exist
+    /// * The config is invalid or the WAL is encrypted with an unknown key
+    /// * The WAL cannot be read
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> StrataResult<Arc<Self>> {
+        Self::open_read_only_with_recovery(path, RecoveryObserver::default())
+    }
+
+    /// Open an existing database read-only, watching or cancelling WAL replay
+    ///
+    /// See [`Database::open_read_only`] and [`Database::open_with_recovery`].
+    pub fn open_read_only_with_recovery<P: AsRef<Path>>(
+        path: P,
+        observer: RecoveryObserver,
+    ) -> StrataResult<Arc<Self>> {
+        let data_dir = path.as_ref();
+        if !data_dir.is_dir() {
+            return Err(StrataError::invalid_input(format!(
+                "database at '{}' does not exist",
+                data_dir.display()
+            )));
         }
+        let canonical_path = data_dir.canonicalize().map_err(StrataError::from)?;
+
+        if let Some(db) = OPEN_DATABASES
+            .lock()
+            .get(&canonical_path)
+            .and_then(|weak| weak.upgrade())
+        {
+            info!(target: "strata::db", path = ?canonical_path, "Returning existing database instance");
+            return Ok(db);
+        }
+
+        let config_path = canonical_path.join(config::CONFIG_FILE_NAME);
+        let cfg = if config_path.exists() {
+            config::StrataConfig::from_file(&config_path)?
+        } else {
+            config::StrataConfig::default()
+        };
+        let durability_mode = cfg.durability_mode()?;
+        let codec = cfg.build_codec()?;
+
+        let wal_dir = canonical_path.join("wal");
+        let result = if wal_dir.is_dir() {
+            check_wal_codecs(&canonical_path, &wal_dir, &codec)?;
+            RecoveryCoordinator::new(wal_dir)
+                .with_codec(Arc::clone(&codec))
+                .with_observer(observer)
+                .recover()?
+        } else {
+            strata_concurrency::RecoveryResult::empty()
+        };
+
+        info!(
+            target: "strata::db",
+            path = ?canonical_path,
+            txns_replayed = result.stats.txns_replayed,
+            final_version = result.stats.final_version,
+            "Opened read-only"
+        );
+
+        let coordinator = TransactionCoordinator::from_recovery(&result);
+        let db = Arc::new(Self {
+            data_dir: canonical_path,
+            storage: Arc::new(result.storage),
+            wal_writer: None,
+            persistence_mode: PersistenceMode::ReadOnly,
+            coordinator,
+            durability_mode,
+            codec,
+            accepting_transactions: AtomicBool::new(true),
+            extensions: DashMap::new(),
+            worker_pool: None,
+            flush_worker: ParkingMutex::new(None),
+            compaction_worker: ParkingMutex::new(None),
+            checkpoint_worker: ParkingMutex::new(None),
+            snapshot_full_interval: AtomicU64::new(DEFAULT_FULL_SNAPSHOT_INTERVAL),
+            snapshot_dictionaries: AtomicBool::new(false),
+            capture_environment: AtomicBool::new(false),
+            read_cache: ReadCache::new(0),
+            key_locks: Arc::new(KeyLockTable::default()),
+            replayed_txn_id: ParkingMutex::new(result.stats.max_txn_id),
+            drop_behavior: ParkingMutex::new((
+                DropBehavior::Flush,
+                Some(config::DEFAULT_DROP_TIMEOUT),
+            )),
+            backups: BackupRegistry::default(),
+            _lock_file: None, // Readers never block the writer
+        });
+
+        crate::branch_ops::restore_forks(&db)?;
+        crate::recovery::recover_all_participants(&db)?;
+        crate::primitives::custom::replay_custom_primitives(&db)?;
+
         Ok(db)
     }
 
-    /// Open database with specific durability mode
+    /// Open database with specific durability mode and no compression
+    #[cfg(test)]
+    pub(crate) fn open_with_mode<P: AsRef<Path>>(
+        path: P,
+        durability_mode: DurabilityMode,
+    ) -> StrataResult<Arc<Self>> {
+        Self::open_with_codec(
+            path,
+            durability_mode,
+            WalConfig::default(),
+            Arc::new(IdentityCodec),
+            None,
+            RecoveryObserver::default(),
+        )
+    }
+
+    /// Open database with specific durability mode, WAL config and storage codec
     ///
     /// Allows selecting between Cache, Always, or Standard durability modes.
+    /// `codec` selects how new WAL records and snapshots are encoded;
+    /// existing segments record their own codec and stay readable as long
+    /// as any encryption key they need is available.
     ///
     /// # Thread Safety
     ///
@@ -293,19 +608,29 @@ impl Database {
     ///
     /// * `path` - Directory path for the database
     /// * `durability_mode` - Durability mode for WAL operations
+    /// * `wal_config` - Segment size and Standard mode byte threshold
+    /// * `codec` - Storage codec (identity, zstd, or AES-GCM)
+    /// * `worker_pool` - Pool for background jobs (dedicated threads if `None`)
+    /// * `observer` - Progress callback and cancellation token for replay
     ///
     /// # Returns
     ///
     /// * `Ok(Arc<Database>)` - Ready-to-use database instance
-    /// * `Err` - If directory creation, WAL opening, or recovery fails
+    /// * `Err` - If directory creation, WAL opening, or recovery fails, the
+    ///   WAL is encrypted and `codec` does not hold the key, or replay was
+    ///   cancelled
     ///
     /// # Recovery
     ///
     /// Per spec Section 5: Uses RecoveryCoordinator to replay WAL and
     /// initialize TransactionManager with the recovered version.
-    pub(crate) fn open_with_mode<P: AsRef<Path>>(
+    fn open_with_codec<P: AsRef<Path>>(
         path: P,
         durability_mode: DurabilityMode,
+        wal_config: WalConfig,
+        codec: Arc<dyn StorageCodec>,
+        worker_pool: Option<WorkerPool>,
+        observer: RecoveryObserver,
     ) -> StrataResult<Arc<Self>> {
         // Create directory first so we can canonicalize the path
         let data_dir = path.as_ref().to_path_buf();
@@ -331,29 +656,25 @@ impl Database {
         // Acquire an exclusive filesystem lock to prevent concurrent process access.
         // This protects against multiple processes opening the same database directory,
         // which would corrupt WAL files via interleaved writes.
-        let lock_path = canonical_path.join(".lock");
-        let lock_file = std::fs::OpenOptions::new()
-            .create(true)
-            .truncate(false)
-            .read(true)
-            .write(true)
-            .open(&lock_path)
-            .map_err(|e| StrataError::storage(format!("failed to open lock file: {}", e)))?;
-        fs2::FileExt::try_lock_exclusive(&lock_file).map_err(|_| {
-            StrataError::storage(format!(
-                "database at '{}' is already in use by another process",
-                canonical_path.display()
-            ))
-        })?;
+        let lock_file = DatabaseLock::acquire(&canonical_path)?;
         // Create WAL directory
         let wal_dir = data_dir.join("wal");
         std::fs::create_dir_all(&wal_dir).map_err(StrataError::from)?;
 
+        check_wal_codecs(&canonical_path, &wal_dir, &codec)?;
+
         // Use RecoveryCoordinator for proper transaction-aware recovery
         // This reads all WalRecords from the segmented WAL directory
-        let recovery = RecoveryCoordinator::new(wal_dir.clone());
+        let recovery = RecoveryCoordinator::new(wal_dir.clone())
+            .with_codec(Arc::clone(&codec))
+            .with_observer(observer);
         let result = match recovery.recover() {
             Ok(result) => result,
+            // Abandoning replay must not be mistaken for an unreadable WAL
+            Err(e @ StrataError::Cancelled { .. }) => {
+                info!(target: "strata::db", path = ?canonical_path, "Open cancelled during recovery");
+                return Err(e);
+            }
             Err(e) => {
                 warn!(
                     target: "strata::db",
@@ -378,38 +699,28 @@ impl Database {
             wal_dir,
             [0u8; 16], // database UUID placeholder
             durability_mode,
-            WalConfig::default(),
-            Box::new(IdentityCodec),
+            wal_config,
+            Box::new(Arc::clone(&codec)),
         )?;
 
         // Create coordinator from recovery result (preserves version continuity)
         let coordinator = TransactionCoordinator::from_recovery(&result);
 
+        // Start background WAL flush for Standard mode (#969)
+        let sync_check_interval = wal_writer.sync_check_interval();
         let wal_arc = Arc::new(ParkingMutex::new(wal_writer));
-        let flush_shutdown = Arc::new(AtomicBool::new(false));
 
-        // Spawn background WAL flush thread for Standard mode (#969)
-        let flush_handle = if let DurabilityMode::Standard { interval_ms, .. } = durability_mode {
+        let flush_worker = if let Some(interval) = sync_check_interval {
             let wal = Arc::clone(&wal_arc);
-            let shutdown = Arc::clone(&flush_shutdown);
-            let interval = std::time::Duration::from_millis(interval_ms);
-
-            let handle = std::thread::Builder::new()
-                .name("strata-wal-flush".to_string())
-                .spawn(move || {
-                    while !shutdown.load(Ordering::Relaxed) {
-                        std::thread::sleep(interval);
-                        if shutdown.load(Ordering::Relaxed) {
-                            break;
-                        }
-                        let mut wal = wal.lock();
-                        let _ = wal.sync_if_overdue();
-                    }
-                })
-                .map_err(|e| {
-                    StrataError::internal(format!("failed to spawn WAL flush thread: {}", e))
-                })?;
-            Some(handle)
+            Some(Worker::spawn(
+                worker_pool.as_ref(),
+                "strata-wal-flush",
+                interval,
+                move || {
+                    let _ = wal.lock().sync_if_overdue();
+                    true
+                },
+            )?)
         } else {
             None
         };
@@ -421,10 +732,24 @@ impl Database {
             persistence_mode: PersistenceMode::Disk,
             coordinator,
             durability_mode,
+            codec,
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
-            flush_shutdown,
-            flush_handle: ParkingMutex::new(flush_handle),
+            worker_pool,
+            flush_worker: ParkingMutex::new(flush_worker),
+            compaction_worker: ParkingMutex::new(None),
+            checkpoint_worker: ParkingMutex::new(None),
+            snapshot_full_interval: AtomicU64::new(DEFAULT_FULL_SNAPSHOT_INTERVAL),
+            snapshot_dictionaries: AtomicBool::new(false),
+            capture_environment: AtomicBool::new(false),
+            read_cache: ReadCache::new(0),
+            key_locks: Arc::new(KeyLockTable::default()),
+            replayed_txn_id: ParkingMutex::new(0),
+            drop_behavior: ParkingMutex::new((
+                DropBehavior::Flush,
+                Some(config::DEFAULT_DROP_TIMEOUT),
+            )),
+            backups: BackupRegistry::default(),
             _lock_file: Some(lock_file),
         });
 
@@ -436,8 +761,11 @@ impl Database {
 
         // Run primitive recovery (e.g., VectorStore)
         // This must happen AFTER KV recovery completes, as primitives may
-        // depend on config data stored in KV.
+        // depend on config data stored in KV. Forks are relinked first so
+        // they see the data they share with their parents.
+        crate::branch_ops::restore_forks(&db)?;
         crate::recovery::recover_all_participants(&db)?;
+        crate::primitives::custom::replay_custom_primitives(&db)?;
 
         Ok(db)
     }
@@ -495,10 +823,24 @@ impl Database {
             persistence_mode: PersistenceMode::Ephemeral,
             coordinator,
             durability_mode: DurabilityMode::Cache, // Irrelevant but set for consistency
+            codec: Arc::new(IdentityCodec),
             accepting_transactions: AtomicBool::new(true),
             extensions: DashMap::new(),
-            flush_shutdown: Arc::new(AtomicBool::new(false)),
-            flush_handle: ParkingMutex::new(None),
+            worker_pool: None,
+            flush_worker: ParkingMutex::new(None),
+            compaction_worker: ParkingMutex::new(None),
+            checkpoint_worker: ParkingMutex::new(None),
+            snapshot_full_interval: AtomicU64::new(DEFAULT_FULL_SNAPSHOT_INTERVAL),
+            snapshot_dictionaries: AtomicBool::new(false),
+            capture_environment: AtomicBool::new(false),
+            read_cache: ReadCache::new(0),
+            key_locks: Arc::new(KeyLockTable::default()),
+            replayed_txn_id: ParkingMutex::new(0),
+            drop_behavior: ParkingMutex::new((
+                DropBehavior::Flush,
+                Some(config::DEFAULT_DROP_TIMEOUT),
+            )),
+            backups: BackupRegistry::default(),
             _lock_file: None, // No lock for ephemeral databases
         });
 
@@ -564,6 +906,55 @@ impl Database {
         self.persistence_mode == PersistenceMode::Ephemeral
     }
 
+    /// Check if this database was opened with [`Database::open_read_only`]
+    pub fn is_read_only(&self) -> bool {
+        self.persistence_mode == PersistenceMode::ReadOnly
+    }
+
+    /// Catch up with a writer that kept writing after this read-only
+    /// instance opened
+    ///
+    /// Applies the complete WAL records appended since the open (or the
+    /// last refresh), so later reads see those writes. Vector and search
+    /// indexes are not updated. Returns the number of transactions applied;
+    /// always 0 for writable and cache databases, which see their own writes.
+    ///
+    /// # Errors
+    ///
+    /// * The WAL cannot be read or a record cannot be decoded
+    pub fn refresh(&self) -> StrataResult<u64> {
+        if !self.is_read_only() {
+            return Ok(0);
+        }
+        let mut replayed = self.replayed_txn_id.lock();
+        let stats = RecoveryCoordinator::new(self.data_dir.join("wal"))
+            .with_codec(Arc::clone(&self.codec))
+            .replay_since(&self.storage, *replayed)?;
+        *replayed = stats.max_txn_id;
+        if stats.txns_replayed > 0 {
+            self.read_cache.clear();
+            debug!(
+                target: "strata::db",
+                txns = stats.txns_replayed,
+                max_txn_id = stats.max_txn_id,
+                "Read-only database refreshed"
+            );
+        }
+        Ok(stats.txns_replayed as u64)
+    }
+
+    /// Most committed data a crash can lose
+    ///
+    /// Follows from the durability mode and, in Standard mode, the
+    /// `sync_interval_ms` and `sync_bytes` settings. Databases without a
+    /// WAL (cache and read-only) persist nothing and return
+    /// [`LossWindow::Unbounded`].
+    pub fn max_loss_window(&self) -> LossWindow {
+        self.wal_writer
+            .as_ref()
+            .map_or(LossWindow::Unbounded, |w| w.lock().max_loss_window())
+    }
+
     /// Get current WAL counters snapshot.
     ///
     /// Returns `None` for ephemeral databases (no WAL).
@@ -572,6 +963,96 @@ impl Database {
         self.wal_writer.as_ref().map(|w| w.lock().counters())
     }
 
+    /// Approximate bytes of data held in memory, including old versions
+    ///
+    /// Walks all of storage, so avoid calling it on a hot path.
+    pub fn memory_usage(&self) -> usize {
+        self.storage.memory_usage()
+    }
+
+    /// Live entries per primitive and approximate bytes held by a branch
+    ///
+    /// Each branch is its own storage shard, so this is also the shard's
+    /// memory. Walks the branch's data, so avoid calling it on a hot path.
+    pub fn branch_usage(&self, branch_id: BranchId) -> ShardUsage {
+        self.storage.shard_usage(&branch_id)
+    }
+
+    /// Bytes of WAL segments on disk
+    ///
+    /// Always 0 for cache databases, which have no WAL directory.
+    pub fn wal_size(&self) -> StrataResult<u64> {
+        if self.persistence_mode == PersistenceMode::Ephemeral {
+            return Ok(0);
+        }
+        let entries = match std::fs::read_dir(self.data_dir.join("wal")) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(StrataError::from(e)),
+        };
+        let mut total = 0;
+        for entry in entries {
+            let entry = entry.map_err(StrataError::from)?;
+            if entry.file_name().to_string_lossy().ends_with(".seg") {
+                total += entry.metadata().map_err(StrataError::from)?.len();
+            }
+        }
+        Ok(total)
+    }
+
+    /// Version covered by the latest snapshot checkpoint, or `None` if the
+    /// database has never been checkpointed
+    pub fn snapshot_watermark(&self) -> StrataResult<Option<u64>> {
+        let manifest_path = self.data_dir.join("MANIFEST");
+        if self.persistence_mode == PersistenceMode::Ephemeral
+            || !ManifestManager::exists(&manifest_path)
+        {
+            return Ok(None);
+        }
+        let manifest = ManifestManager::load(manifest_path).map_err(|e: ManifestError| {
+            StrataError::internal(format!("failed to load MANIFEST: {}", e))
+        })?;
+        Ok(manifest.manifest().snapshot_watermark)
+    }
+
+    /// Transaction counters since the database was opened
+    pub fn transaction_metrics(&self) -> TransactionMetrics {
+        self.coordinator.metrics()
+    }
+
+    /// Number of background threads this database is running
+    ///
+    /// Counts the WAL flush thread (standard durability) and the scheduled
+    /// compaction thread. Jobs on a shared [`WorkerPool`] own no thread and
+    /// are not counted.
+    pub fn background_threads(&self) -> usize {
+        let is_thread = |worker: &Worker| usize::from(worker.is_thread());
+        self.flush_worker.lock().as_ref().map_or(0, is_thread)
+            + self
+                .compaction_worker
+                .lock()
+                .as_ref()
+                .map_or(0, |(worker, _)| is_thread(worker))
+            + self
+                .checkpoint_worker
+                .lock()
+                .as_ref()
+                .map_or(0, |(worker, _)| is_thread(worker))
+    }
+
+    /// The shared pool running this database's background jobs, if any
+    pub fn worker_pool(&self) -> Option<&WorkerPool> {
+        self.worker_pool.as_ref()
+    }
+
+    /// Number of file descriptors this database holds open
+    ///
+    /// Counts the directory lock and the active WAL segment; segments read
+    /// during recovery or compaction are closed again.
+    pub fn open_files(&self) -> usize {
+        usize::from(self._lock_file.is_some()) + usize::from(self.wal_writer.is_some())
+    }
+
     /// Check if the database is currently open and accepting transactions
     pub fn is_open(&self) -> bool {
         self.accepting_transactions.load(Ordering::SeqCst)
@@ -644,6 +1125,37 @@ impl Database {
         }
     }
 
+    /// Embedding provider used by auto-embedding and semantic search.
+    pub fn embed_provider(&self) -> EmbedProvider {
+        self.extension::<AutoEmbedState>()
+            .map(|s| s.provider.read().clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the embedding provider.
+    ///
+    /// Vectors from different providers are not comparable, so switch
+    /// providers before any text is embedded.
+    pub fn set_embed_provider(&self, provider: EmbedProvider) {
+        if let Ok(state) = self.extension::<AutoEmbedState>() {
+            *state.provider.write() = provider;
+        }
+    }
+
+    /// Reranking provider used by reranked semantic search.
+    pub fn rerank_provider(&self) -> RerankProvider {
+        self.extension::<AutoEmbedState>()
+            .map(|s| s.rerank_provider.read().clone())
+            .unwrap_or_default()
+    }
+
+    /// Set the reranking provider.
+    pub fn set_rerank_provider(&self, provider: RerankProvider) {
+        if let Ok(state) = self.extension::<AutoEmbedState>() {
+            *state.rerank_provider.write() = provider;
+        }
+    }
+
     /// Path to the model directory for MiniLM-L6-v2.
     ///
     /// Checks in order:
@@ -651,7 +1163,19 @@ impl Database {
     /// 2. System-wide `~/.stratadb/models/minilm-l6-v2/`
     /// 3. Falls back to the local path (for error messages)
     pub fn model_dir(&self) -> PathBuf {
-        let local = self.data_dir.join("models/minilm-l6-v2");
+        self.find_model_dir("minilm-l6-v2")
+    }
+
+    /// Path to the model directory for the MiniLM-L6 cross-encoder.
+    ///
+    /// Looked up like [`model_dir`](Self::model_dir), under
+    /// `models/ms-marco-minilm-l6-v2/`.
+    pub fn rerank_model_dir(&self) -> PathBuf {
+        self.find_model_dir("ms-marco-minilm-l6-v2")
+    }
+
+    fn find_model_dir(&self, name: &str) -> PathBuf {
+        let local = self.data_dir.join("models").join(name);
         if local.join("model.safetensors").exists() && local.join("vocab.txt").exists() {
             return local;
         }
@@ -659,7 +1183,7 @@ impl Database {
             .or_else(|_| std::env::var("USERPROFILE"))
             .ok();
         if let Some(home) = home {
-            let system = PathBuf::from(home).join(".stratadb/models/minilm-l6-v2");
+            let system = PathBuf::from(home).join(".stratadb/models").join(name);
             if system.join("model.safetensors").exists() && system.join("vocab.txt").exists() {
                 return system;
             }
@@ -687,6 +1211,136 @@ impl Database {
         self.coordinator.current_version()
     }
 
+    // ========================================================================
+    // KV History Policies
+    // ========================================================================
+
+    /// History policies set in `namespace`, with the KV key prefix each
+    /// one governs.
+    ///
+    /// Reads storage directly, like `get_history()`.
+    pub(crate) fn history_policies(
+        &self,
+        namespace: &Namespace,
+    ) -> StrataResult<Vec<(String, RetentionPolicy)>> {
+        let scan = Key::new_kv_history_policy(namespace.clone(), "");
+        let mut policies = Vec::new();
+        for (key, vv) in self.storage.list_by_prefix(&scan) {
+            let Value::Bytes(bytes) = vv.value else {
+                continue;
+            };
+            let policy = RetentionPolicy::from_bytes(&bytes)
+                .map_err(|e| StrataError::serialization(e.to_string()))?;
+            let prefix = String::from_utf8_lossy(&key.user_key[scan.user_key.len()..]);
+            policies.push((prefix.into_owned(), policy));
+        }
+        Ok(policies)
+    }
+
+    /// Policy governing the history of a KV key: the one set on the
+    /// longest prefix of its name, if any.
+    pub(crate) fn history_policy_for(&self, key: &Key) -> StrataResult<Option<RetentionPolicy>> {
+        Ok(governing_policy(&self.history_policies(&key.namespace)?, &key.user_key).cloned())
+    }
+
+    /// Whether older versions of `key` were already pruned from storage.
+    pub(crate) fn is_history_trimmed(&self, key: &Key) -> bool {
+        self.storage.is_history_trimmed(key)
+    }
+
+    /// Prune versions that history policies no longer retain.
+    ///
+    /// Walks every space of every branch that has KV history policies set,
+    /// then every key of branches with a retention policy (see
+    /// `BranchIndex::set_retention()`), skipping KV keys a history policy
+    /// already governs. The latest version of a key is always kept. Runs as
+    /// part of `compact()`.
+    ///
+    /// Returns the number of pruned versions.
+    pub fn enforce_history_policies(&self) -> StrataResult<usize> {
+        let now = strata_core::Timestamp::now().as_micros();
+        let retention = crate::primitives::branch::branch_retention_policies(self)?;
+        let mut pruned = 0;
+        for branch_id in self.storage.branch_ids() {
+            let mut kv_policies = HashMap::new();
+            let mut spaces: Vec<String> = self
+                .storage
+                .list_by_prefix(&Key::new_space_prefix(branch_id))
+                .into_iter()
+                .filter_map(|(k, _)| k.user_key_string())
+                .collect();
+            if !spaces.iter().any(|s| s == "default") {
+                spaces.push("default".to_string());
+            }
+            for space in spaces {
+                let namespace = Namespace::for_branch_space(branch_id, &space);
+                let policies = self.history_policies(&namespace)?;
+                if policies.is_empty() {
+                    continue;
+                }
+                let scan = Key::new_kv(namespace, "");
+                pruned += self.storage.trim_prefix(&scan, |key, sv, position| {
+                    if key.user_key.starts_with(HISTORY_POLICY_PREFIX) {
+                        return true;
+                    }
+                    match governing_policy(&policies, &key.user_key) {
+                        Some(policy) => policy.should_retain(
+                            sv.version().as_u64(),
+                            u64::from(sv.timestamp()),
+                            position + 1,
+                            now,
+                            PrimitiveType::Kv,
+                        ),
+                        None => true,
+                    }
+                });
+                kv_policies.insert(space, policies);
+            }
+
+            let Some((_, policy)) = retention.iter().find(|(id, _)| *id == branch_id) else {
+                continue;
+            };
+            pruned += self.storage.trim_branch(branch_id, |key, sv, position| {
+                let Some(primitive) = retained_primitive(key.type_tag) else {
+                    return true;
+                };
+                if primitive == PrimitiveType::Kv {
+                    let governed = kv_policies
+                        .get(&key.namespace.space)
+                        .is_some_and(|p| governing_policy(p, &key.user_key).is_some());
+                    if governed || key.user_key.starts_with(HISTORY_POLICY_PREFIX) {
+                        return true;
+                    }
+                }
+                policy.should_retain(
+                    sv.version().as_u64(),
+                    u64::from(sv.timestamp()),
+                    position + 1,
+                    now,
+                    primitive,
+                )
+            });
+        }
+        Ok(pruned)
+    }
+
+    // ========================================================================
+    // Analytics
+    // ========================================================================
+
+    /// Take a frozen, fully materialized read view for analytical scans.
+    ///
+    /// The view sees the same committed state a transaction started now
+    /// would see, copied out of storage once. Scanning it takes no shard
+    /// locks and never blocks writers, and `gc_versions_before` can prune
+    /// the versions it was built from. Clones share the copied data.
+    ///
+    /// Creation is O(n) in live keys and the view holds a full copy of
+    /// them, so take one per analytical job rather than per request.
+    pub fn analytics_view(&self) -> MaterializedSnapshot {
+        self.storage.create_snapshot().materialize()
+    }
+
     /// Remove the per-branch commit lock after a branch is deleted.
     ///
     /// This prevents unbounded growth of the commit_locks map in the
@@ -718,41 +1372,202 @@ impl Database {
         }
     }
 
+    /// Set what dropping the database does with unsynced WAL records.
+    ///
+    /// With [`DropBehavior::Flush`], drop waits at most `timeout` for the
+    /// fsync (`None` = no limit) and then returns, logging a warning, while
+    /// the flush finishes in the background. The lock file stays held until
+    /// it does. Set from `on_drop` and `drop_timeout_ms` in `strata.toml`.
+    pub fn set_drop_behavior(&self, behavior: DropBehavior, timeout: Option<std::time::Duration>) {
+        *self.drop_behavior.lock() = (behavior, timeout);
+    }
+
     // ========================================================================
     // Checkpoint & Compaction
     // ========================================================================
 
-    /// Create a snapshot checkpoint of the current database state.
-    ///
-    /// Checkpoints serialize all primitive state to a crash-safe snapshot file
-    /// and update the MANIFEST watermark. After a checkpoint, WAL compaction
-    /// can safely remove segments covered by the snapshot.
+    /// Set the number of checkpoints between full snapshots.
     ///
-    /// For ephemeral (cache) databases, this is a no-op.
+    /// A value of 0 or 1 writes every snapshot in full.
+    pub fn set_snapshot_full_interval(&self, interval: u64) {
+        self.snapshot_full_interval
+            .store(interval, Ordering::Relaxed);
+    }
+
+    /// Enable or disable dictionary compression of snapshot values.
     ///
-    /// See: `docs/architecture/STORAGE_DURABILITY_ARCHITECTURE.md` Section 6.3
-    pub fn checkpoint(&self) -> StrataResult<()> {
-        if self.persistence_mode == PersistenceMode::Ephemeral {
-            return Ok(());
-        }
+    /// When enabled, the first checkpoint with enough data trains a zstd
+    /// dictionary per primitive and stores it in the MANIFEST; later
+    /// checkpoints reuse those dictionaries. Enabled by `compression = "zstd"`.
+    pub fn set_snapshot_dictionaries(&self, enabled: bool) {
+        self.snapshot_dictionaries.store(enabled, Ordering::Relaxed);
+    }
 
-        // Flush WAL first to ensure all buffered writes are on disk
-        self.flush()?;
+    /// Enable or disable environment capture.
+    ///
+    /// When enabled, new branches record [`Database::environment`] in their
+    /// metadata and exported bundles record it in their manifest. Enabled by
+    /// `capture_environment = true`.
+    pub fn set_capture_environment(&self, enabled: bool) {
+        self.capture_environment.store(enabled, Ordering::Relaxed);
+    }
 
-        let watermark_txn = self.coordinator.current_version();
+    /// Whether environment capture is enabled.
+    pub fn capture_environment_enabled(&self) -> bool {
+        self.capture_environment.load(Ordering::Relaxed)
+    }
 
-        // Collect data from storage
-        let data = self.collect_checkpoint_data();
+    /// Enable or disable `tracing` spans on the transaction path.
+    ///
+    /// When enabled, `begin` and `commit` spans (target `strata::txn`) carry
+    /// the branch id, transaction id and, for commits, the number of keys
+    /// written; `validate`, `wal_append` and `apply` spans nest under
+    /// `commit`. Off by default: a disabled database creates no spans.
+    pub fn set_commit_tracing(&self, enabled: bool) {
+        self.coordinator.set_commit_tracing(enabled);
+    }
 
-        // Create snapshots directory
-        let snapshots_dir = self.data_dir.join("snapshots");
-        std::fs::create_dir_all(&snapshots_dir).map_err(StrataError::from)?;
+    /// Set how many KV and state point reads to cache; 0 turns it off.
+    ///
+    /// Cached reads skip snapshot creation and value decoding. Commits
+    /// evict the keys they write, so reads still see the latest committed
+    /// version. Shrinking evicts the least recently used keys. Off by default.
+    pub fn set_read_cache_capacity(&self, capacity: usize) {
+        self.read_cache.set_capacity(capacity);
+    }
 
-        // Load or create MANIFEST
-        let mut manifest = self.load_or_create_manifest()?;
+    /// Hit and size counters of the read cache.
+    pub fn read_cache_stats(&self) -> ReadCacheStats {
+        self.read_cache.stats()
+    }
 
-        // Build watermark state from existing MANIFEST if present
-        let existing_watermark = {
+    /// Cap the memory a cache database holds.
+    ///
+    /// Once storage passes `budget.max_bytes`, LRU and LFU budgets evict
+    /// whole keys (every version) until it is back under the limit, while
+    /// `RejectWrites` fails commits that add data with `CapacityExceeded`.
+    /// Exempt key types (branch metadata and events by default) and keys
+    /// under reserved prefixes are never evicted. A budget of 0 bytes
+    /// removes the limit.
+    ///
+    /// Only cache databases accept a budget: evicting data that is also in
+    /// the WAL would make it reappear on the next open.
+    pub fn set_memory_budget(&self, budget: MemoryBudget) -> StrataResult<()> {
+        if budget.is_enabled() && self.persistence_mode != PersistenceMode::Ephemeral {
+            return Err(StrataError::invalid_input(
+                "a memory budget requires a cache database",
+            ));
+        }
+        self.storage.set_memory_budget(budget);
+        self.read_cache.clear();
+        Ok(())
+    }
+
+    /// Usage and eviction counters of the memory budget.
+    pub fn memory_budget_stats(&self) -> MemoryBudgetStats {
+        self.storage.memory_budget_stats()
+    }
+
+    /// Drop every read cache entry.
+    ///
+    /// For changes to what a branch reads that bypass commits, such as
+    /// linking or unlinking a fork.
+    pub(crate) fn clear_read_cache(&self) {
+        self.read_cache.clear();
+    }
+
+    /// Read `key` through the read cache, calling `load` on a miss.
+    ///
+    /// `load` must return the latest committed value of `key`.
+    pub(crate) fn cached_read(
+        &self,
+        key: &Key,
+        load: impl FnOnce() -> StrataResult<Option<VersionedValue>>,
+    ) -> StrataResult<Option<VersionedValue>> {
+        if !self.read_cache.is_enabled() {
+            return load();
+        }
+        if let Some(hit) = self.read_cache.get(key) {
+            return Ok(hit);
+        }
+        let generation = self.read_cache.generation();
+        let value = load()?;
+        self.read_cache
+            .insert(key.clone(), value.clone(), generation);
+        Ok(value)
+    }
+
+    /// The environment this database runs in.
+    ///
+    /// Models are listed by role (`"embed"`, `"rerank"`) under the id of the
+    /// configured provider: the built-in model name, or the HTTP model.
+    pub fn environment(&self) -> EnvironmentInfo {
+        let wal_format = if self.codec.codec_id() == IdentityCodec.codec_id() {
+            SEGMENT_FORMAT_VERSION
+        } else {
+            SEGMENT_FORMAT_VERSION_CODEC
+        };
+        let mut models = BTreeMap::new();
+        let embed = match self.embed_provider() {
+            EmbedProvider::Local => "minilm-l6-v2".to_string(),
+            EmbedProvider::Http { model, .. } => model,
+        };
+        models.insert("embed".to_string(), embed);
+        let rerank = match self.rerank_provider() {
+            RerankProvider::Local => "ms-marco-minilm-l6-v2".to_string(),
+            RerankProvider::Http { model, .. } => model,
+        };
+        models.insert("rerank".to_string(), rerank);
+        EnvironmentInfo::new(wal_format, lock::hostname(), models)
+    }
+
+    /// Create a snapshot checkpoint of the current database state.
+    ///
+    /// Checkpoints serialize all primitive state to a crash-safe snapshot file
+    /// and update the MANIFEST watermark. After a checkpoint, WAL compaction
+    /// can safely remove segments covered by the snapshot.
+    ///
+    /// Sections unchanged since the previous checkpoint are written as
+    /// references to it, with a full snapshot every `snapshot_full_interval`
+    /// checkpoints.
+    ///
+    /// For ephemeral (cache) databases, this is a no-op. Read-only databases
+    /// return an error.
+    ///
+    /// See: `docs/architecture/STORAGE_DURABILITY_ARCHITECTURE.md` Section 6.3
+    pub fn checkpoint(&self) -> StrataResult<()> {
+        match self.persistence_mode {
+            PersistenceMode::Ephemeral => return Ok(()),
+            PersistenceMode::ReadOnly => {
+                return Err(StrataError::invalid_input(format!(
+                    "cannot checkpoint database at '{}': open read-only",
+                    self.data_dir.display()
+                )))
+            }
+            PersistenceMode::Disk => {}
+        }
+
+        // Flush WAL first to ensure all buffered writes are on disk
+        self.flush()?;
+        self.save_text_index()?;
+
+        let watermark_txn = self.coordinator.current_version();
+
+        // Collect data from storage
+        let mut data = self.collect_checkpoint_data();
+        for (id, section) in crate::primitives::custom::custom_snapshot_sections(self)? {
+            data = data.with_custom(id, section);
+        }
+
+        // Create snapshots directory
+        let snapshots_dir = self.data_dir.join("snapshots");
+        std::fs::create_dir_all(&snapshots_dir).map_err(StrataError::from)?;
+
+        // Load or create MANIFEST
+        let mut manifest = self.load_or_create_manifest()?;
+
+        // Build watermark state from existing MANIFEST if present
+        let existing_watermark = {
             let m = manifest.manifest();
             match (m.snapshot_id, m.snapshot_watermark) {
                 (Some(sid), Some(wtxn)) => {
@@ -763,18 +1578,19 @@ impl Database {
         };
 
         // Create CheckpointCoordinator
-        let mut coordinator = if let Some(wm) = existing_watermark {
-            CheckpointCoordinator::with_watermark(
-                snapshots_dir,
-                Box::new(IdentityCodec),
-                [0u8; 16],
-                wm,
-            )
-            .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
+        let coordinator = if let Some(wm) = existing_watermark {
+            CheckpointCoordinator::with_watermark(snapshots_dir, self.codec(), [0u8; 16], wm)
+                .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
         } else {
-            CheckpointCoordinator::new(snapshots_dir, Box::new(IdentityCodec), [0u8; 16])
+            CheckpointCoordinator::new(snapshots_dir, self.codec(), [0u8; 16])
                 .map_err(|e| StrataError::internal(format!("checkpoint coordinator: {}", e)))?
         };
+        let mut coordinator =
+            coordinator.with_full_interval(self.snapshot_full_interval.load(Ordering::Relaxed));
+        if self.snapshot_dictionaries.load(Ordering::Relaxed) {
+            let dictionaries = Self::snapshot_dictionaries(&mut manifest, &data)?;
+            coordinator = coordinator.with_dictionaries(&dictionaries);
+        }
 
         // Create the checkpoint
         let info = coordinator
@@ -800,45 +1616,361 @@ impl Database {
         Ok(())
     }
 
-    /// Compact WAL segments that are no longer needed for recovery.
+    /// Compact the WAL down to the versions storage still holds.
     ///
-    /// Removes closed WAL segments whose max transaction ID is at or below the
-    /// latest snapshot watermark. The active segment is never removed.
+    /// Takes a consistent snapshot of storage (commits are fenced by holding
+    /// the WAL lock), rotates to a fresh segment, rewrites every version
+    /// still held in each key's version chain into it, and then truncates
+    /// all segments older than the snapshot watermark. History survives as
+    /// far as retention policies keep it; what the WAL sheds is per-commit
+    /// overhead, versions already pruned, and keys whose only remaining
+    /// version is a delete.
     ///
-    /// A checkpoint must exist before compaction can run. For ephemeral (cache)
-    /// databases, this is a no-op.
+    /// Recovery replays the rewritten segment like any other, so no
+    /// checkpoint is required. With encryption at rest the live data is
+    /// re-encrypted under the current key, which completes a key rotation.
+    /// KV history and branch retention policies are enforced first, in
+    /// memory, for every database (see `enforce_history_policies()`). Beyond that, for
+    /// ephemeral (cache) and read-only databases this is a no-op, and while a
+    /// hot backup is in progress the WAL is left untouched.
     ///
     /// See: `docs/architecture/STORAGE_DURABILITY_ARCHITECTURE.md` Section 5.6
-    pub fn compact(&self) -> StrataResult<()> {
-        if self.persistence_mode == PersistenceMode::Ephemeral {
-            return Ok(());
+    pub fn compact(&self) -> StrataResult<CompactInfo> {
+        let start = std::time::Instant::now();
+        let versions_removed = self.enforce_history_policies()?;
+        let memory_only = CompactInfo {
+            versions_removed,
+            duration_ms: start.elapsed().as_millis() as u64,
+            ..CompactInfo::default()
+        };
+
+        let wal = match (&self.persistence_mode, &self.wal_writer) {
+            (PersistenceMode::Disk, Some(wal)) => wal,
+            _ => return Ok(memory_only),
+        };
+
+        // Holding the WAL lock blocks every committer that needs the WAL,
+        // so storage cannot change underneath the snapshot once commits
+        // already appended have finished applying.
+        let mut wal = wal.lock();
+        self.storage.wait_for_pending_versions();
+        if wal.current_segment() == 0 {
+            // Cache durability: nothing on disk to compact
+            return Ok(memory_only);
+        }
+        if self.backups.in_progress() {
+            // Truncating would delete segments a backup is still copying
+            info!(target: "strata::db", "WAL compaction deferred: backup in progress");
+            return Ok(memory_only);
         }
 
-        let wal_dir = self.data_dir.join("wal");
+        wal.flush().map_err(StrataError::from)?;
+        let watermark = self.coordinator.current_version();
+        let bytes_before = wal.counters().bytes_written;
+        let live_segment = wal.rotate().map_err(StrataError::from)?;
 
-        // Load or create MANIFEST
-        let manifest = self.load_or_create_manifest()?;
-        let manifest_arc = Arc::new(parking_lot::Mutex::new(manifest));
-
-        // Create compactor and run
-        let compactor = WalOnlyCompactor::new(wal_dir, manifest_arc);
-        let compact_info = compactor.compact().map_err(|e: CompactionError| match e {
-            CompactionError::NoSnapshot => StrataError::invalid_input(
-                "No checkpoint exists yet. Run checkpoint() before compact().".to_string(),
-            ),
-            other => StrataError::internal(format!("compaction failed: {}", other)),
-        })?;
+        // Rewrite every held version, one record per (branch, version) so
+        // recovery restores the exact versions readers observed. Forks keep
+        // only what they wrote, and their parents the versions they share.
+        for branch_id in self.storage.branch_ids() {
+            let mut by_version: BTreeMap<u64, TransactionPayload> = BTreeMap::new();
+            for (key, version, value) in self.storage.durable_entries(&branch_id) {
+                let payload = by_version
+                    .entry(version)
+                    .or_insert_with(|| TransactionPayload {
+                        version,
+                        puts: Vec::new(),
+                        deletes: Vec::new(),
+                        trimmed: Vec::new(),
+                    });
+                match value {
+                    Some(value) => payload.puts.push((key, value)),
+                    None => payload.deletes.push(key),
+                }
+            }
+            for payload in by_version.into_values() {
+                let record = WalRecord::new(
+                    self.coordinator.next_txn_id(),
+                    *branch_id.as_bytes(),
+                    strata_durability::now_micros(),
+                    payload.to_bytes(),
+                );
+                wal.append(&record).map_err(StrataError::from)?;
+            }
+        }
+
+        // Marker record so recovery resumes the version counter at the
+        // watermark even if the newest versions were all deletes. It also
+        // lists the keys whose history was pruned, now that the segments
+        // that held the older versions are going away.
+        let marker = TransactionPayload {
+            version: watermark,
+            puts: Vec::new(),
+            deletes: Vec::new(),
+            trimmed: self
+                .storage
+                .branch_ids()
+                .iter()
+                .flat_map(|branch_id| self.storage.trimmed_keys(branch_id))
+                .collect(),
+        };
+        let record = WalRecord::new(
+            self.coordinator.next_txn_id(),
+            [0u8; 16],
+            strata_durability::now_micros(),
+            marker.to_bytes(),
+        );
+        wal.append(&record).map_err(StrataError::from)?;
+        wal.flush().map_err(StrataError::from)?;
+
+        let (segments_removed, bytes_removed) = wal
+            .remove_segments_before(live_segment)
+            .map_err(StrataError::from)?;
+        let bytes_written = wal.counters().bytes_written - bytes_before;
+        drop(wal);
+
+        let compact_info = CompactInfo {
+            reclaimed_bytes: bytes_removed.saturating_sub(bytes_written),
+            wal_segments_removed: segments_removed,
+            versions_removed,
+            snapshot_watermark: Some(watermark),
+            duration_ms: start.elapsed().as_millis() as u64,
+            timestamp: strata_durability::now_micros(),
+            ..CompactInfo::default()
+        };
 
         info!(
             target: "strata::db",
+            watermark,
             segments_removed = compact_info.wal_segments_removed,
+            versions_removed,
             bytes_reclaimed = compact_info.reclaimed_bytes,
+            duration_ms = compact_info.duration_ms,
             "WAL compaction completed"
         );
 
+        Ok(compact_info)
+    }
+
+    /// Start a hot backup for an external snapshot tool.
+    ///
+    /// Flushes the WAL and returns the files, with the byte range of each,
+    /// that hold every commit up to the returned version. Writes continue
+    /// during the backup; compaction stops truncating the WAL until
+    /// `end_backup()` so the listed ranges stay intact. Copying each range
+    /// into an empty directory yields a database that opens at the backup's
+    /// version.
+    ///
+    /// Backups may overlap. A backup that is never ended holds off WAL
+    /// truncation until the database is closed.
+    pub fn begin_backup(&self) -> StrataResult<BackupManifest> {
+        let wal = match (&self.persistence_mode, &self.wal_writer) {
+            (PersistenceMode::Disk, Some(wal)) => wal,
+            _ => {
+                return Err(StrataError::invalid_input(
+                    "hot backup requires a read-write disk database",
+                ))
+            }
+        };
+
+        // Same fence as compact(): with the WAL locked and pending versions
+        // applied, the flushed WAL matches the current version exactly.
+        let mut wal = wal.lock();
+        self.storage.wait_for_pending_versions();
+        if wal.current_segment() == 0 {
+            return Err(StrataError::invalid_input(
+                "hot backup requires a durability mode that writes the WAL",
+            ));
+        }
+        wal.flush().map_err(StrataError::from)?;
+        let version = self.coordinator.current_version();
+        let files = backup::collect_files(&self.data_dir, &wal)
+            .map_err(|e| StrataError::storage(format!("failed to list backup files: {}", e)))?;
+        let id = self.backups.begin();
+        drop(wal);
+
+        let manifest = BackupManifest {
+            id,
+            data_dir: self.data_dir.clone(),
+            version,
+            files,
+            started_at: strata_durability::now_micros(),
+        };
+        info!(
+            target: "strata::db",
+            id,
+            version,
+            files = manifest.files.len(),
+            bytes = manifest.total_bytes(),
+            "Hot backup started"
+        );
+        Ok(manifest)
+    }
+
+    /// Finish the hot backup started by `begin_backup()`.
+    ///
+    /// Once no backup is in progress, compaction truncates the WAL again.
+    pub fn end_backup(&self, id: u64) -> StrataResult<()> {
+        if !self.backups.end(id) {
+            return Err(StrataError::invalid_input(format!(
+                "no backup with id {} is in progress",
+                id
+            )));
+        }
+        info!(target: "strata::db", id, "Hot backup ended");
+        Ok(())
+    }
+
+    /// Check whether a hot backup is in progress
+    pub fn backup_in_progress(&self) -> bool {
+        self.backups.in_progress()
+    }
+
+    /// Run `compact()` periodically in the background.
+    ///
+    /// Replaces any previously scheduled compaction. The job runs on the
+    /// database's worker pool, or else its own thread. It holds only a weak
+    /// reference, so it never keeps the database alive, and it stops on
+    /// `shutdown()` or drop. Errors are logged and retried on the next tick.
+    /// For ephemeral and read-only databases, this is a no-op.
+    pub fn schedule_compaction(
+        self: &Arc<Self>,
+        interval: std::time::Duration,
+    ) -> StrataResult<()> {
+        if interval.is_zero() {
+            return Err(StrataError::invalid_input(
+                "compaction interval must be greater than zero".to_string(),
+            ));
+        }
+        if self.persistence_mode != PersistenceMode::Disk {
+            return Ok(());
+        }
+
+        self.stop_scheduled_compaction();
+
+        let db = Arc::downgrade(self);
+        let worker = Worker::spawn(
+            self.worker_pool.as_ref(),
+            "strata-compaction",
+            interval,
+            move || {
+                let Some(db) = db.upgrade() else {
+                    return false;
+                };
+                if !db.is_open() {
+                    return false;
+                }
+                if let Err(e) = db.compact() {
+                    warn!(target: "strata::db", error = %e, "Scheduled compaction failed");
+                }
+                true
+            },
+        )?;
+
+        *self.compaction_worker.lock() = Some((worker, interval));
+        info!(target: "strata::db", interval_secs = interval.as_secs_f64(), "Scheduled WAL compaction");
+        Ok(())
+    }
+
+    /// Interval of the background compaction job, if one is scheduled.
+    pub fn scheduled_compaction(&self) -> Option<std::time::Duration> {
+        self.compaction_worker
+            .lock()
+            .as_ref()
+            .map(|(_, interval)| *interval)
+    }
+
+    /// Stop the background compaction job, if one is running.
+    ///
+    /// Hosts that schedule compaction across many databases themselves use
+    /// this to take over from the per-database job.
+    pub fn stop_scheduled_compaction(&self) {
+        // The compaction job may hold the last strong reference, in which
+        // case drop runs on its thread; `stop()` then doesn't wait on itself.
+        let worker = self.compaction_worker.lock().take();
+        if let Some((worker, _)) = worker {
+            worker.stop();
+        }
+    }
+
+    /// Checkpoint and compact automatically in the background.
+    ///
+    /// Whenever `policy` is met, the job writes a snapshot with
+    /// `checkpoint()` and then truncates the WAL with `compact()`, so
+    /// recovery time stays bounded without manual `flush()` or
+    /// `checkpoint()` calls. Nothing runs while no commits arrive.
+    ///
+    /// Replaces any previous checkpoint schedule. Like scheduled compaction,
+    /// the job runs on the worker pool or else its own thread, holds only a
+    /// weak reference, and stops on `shutdown()` or drop. A failed run is
+    /// logged and retried at the next check. For ephemeral and read-only
+    /// databases, this is a no-op.
+    pub fn schedule_checkpoints(self: &Arc<Self>, policy: CheckpointPolicy) -> StrataResult<()> {
+        policy.validate()?;
+        if self.persistence_mode != PersistenceMode::Disk {
+            return Ok(());
+        }
+
+        self.stop_scheduled_checkpoints();
+
+        let wal_bytes = |db: &Database| db.durability_counters().map_or(0, |c| c.bytes_written);
+        let mut trigger = CheckpointTrigger::new(policy, self.current_version(), wal_bytes(self));
+        let db = Arc::downgrade(self);
+        let worker = Worker::spawn(
+            self.worker_pool.as_ref(),
+            "strata-checkpoint",
+            policy.poll_interval(),
+            move || {
+                let Some(db) = db.upgrade() else {
+                    return false;
+                };
+                if !db.is_open() {
+                    return false;
+                }
+                if !trigger.is_due(db.current_version(), wal_bytes(&db)) {
+                    return true;
+                }
+                match db.checkpoint().and_then(|()| db.compact()) {
+                    // Compaction rewrites the WAL, so count from after it
+                    Ok(_) => trigger.reset(db.current_version(), wal_bytes(&db)),
+                    Err(e) => {
+                        warn!(target: "strata::db", error = %e, "Scheduled checkpoint failed")
+                    }
+                }
+                true
+            },
+        )?;
+
+        *self.checkpoint_worker.lock() = Some((worker, policy));
+        info!(target: "strata::db", ?policy, "Scheduled automatic checkpoints");
         Ok(())
     }
 
+    /// Policy of the background checkpoint job, if one is scheduled.
+    pub fn scheduled_checkpoints(&self) -> Option<CheckpointPolicy> {
+        self.checkpoint_worker
+            .lock()
+            .as_ref()
+            .map(|(_, policy)| *policy)
+    }
+
+    /// Stop the background checkpoint job, if one is running.
+    pub fn stop_scheduled_checkpoints(&self) {
+        // As with compaction, drop may be running on the job's own thread
+        let worker = self.checkpoint_worker.lock().take();
+        if let Some((worker, _)) = worker {
+            worker.stop();
+        }
+    }
+
+    /// Stop the background WAL flush job, if one is running.
+    fn stop_flush(&self) {
+        let worker = self.flush_worker.lock().take();
+        if let Some(worker) = worker {
+            worker.stop();
+        }
+    }
+
     /// Collect all primitive data from storage for checkpointing.
     fn collect_checkpoint_data(&self) -> CheckpointData {
         let mut kv_entries = Vec::new();
@@ -847,9 +1979,13 @@ impl Database {
         let mut branch_entries = Vec::new();
         let mut json_entries = Vec::new();
 
-        let now = strata_durability::now_micros();
+        // Branches are visited in a fixed order and entries carry their
+        // stored timestamps, so unchanged data serializes to identical
+        // sections that incremental snapshots can reference.
+        let mut branch_ids = self.storage.branch_ids();
+        branch_ids.sort_by_key(|id| *id.as_bytes());
 
-        for branch_id in self.storage.branch_ids() {
+        for branch_id in branch_ids {
             // KV entries
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::KV) {
                 let value_bytes =
@@ -858,14 +1994,20 @@ impl Database {
                     key: key.user_key_string().unwrap_or_default(),
                     value: value_bytes,
                     version: vv.version.as_u64(),
-                    timestamp: now,
+                    timestamp: vv.timestamp.as_micros(),
                 });
             }
 
             // Event entries
             for (key, vv) in self.storage.list_by_type(&branch_id, TypeTag::Event) {
-                // Skip metadata keys
-                if key.user_key == b"__meta__" || key.user_key.starts_with(b"__tidx__") {
+                // Skip metadata, index, retention policy, consumer group and
+                // schema keys
+                if key.user_key == b"__meta__"
+                    || key.user_key.starts_with(b"__tidx__")
+                    || key.user_key.starts_with(b"__retention__")
+                    || key.user_key.starts_with(b"__group__")
+                    || key.user_key.starts_with(b"__evschema__")
+                {
                     continue;
                 }
                 let sequence = if key.user_key.len() == 8 {
@@ -877,7 +2019,7 @@ impl Database {
                 event_entries.push(EventSnapshotEntry {
                     sequence,
                     payload,
-                    timestamp: now,
+                    timestamp: vv.timestamp.as_micros(),
                 });
             }
 
@@ -889,7 +2031,7 @@ impl Database {
                     name: key.user_key_string().unwrap_or_default(),
                     value: value_bytes,
                     counter: vv.version.as_u64(),
-                    timestamp: now,
+                    timestamp: vv.timestamp.as_micros(),
                 });
             }
 
@@ -909,7 +2051,7 @@ impl Database {
                 branch_entries.push(BranchSnapshotEntry {
                     branch_id: branch_id_bytes,
                     name: String::new(),
-                    created_at: now,
+                    created_at: vv.timestamp.as_micros(),
                     metadata,
                 });
             }
@@ -922,7 +2064,7 @@ impl Database {
                     doc_id: key.user_key_string().unwrap_or_default(),
                     content,
                     version: vv.version.as_u64(),
-                    timestamp: now,
+                    timestamp: vv.timestamp.as_micros(),
                 });
             }
         }
@@ -946,6 +2088,58 @@ impl Database {
         data
     }
 
+    /// Storage codec for new WAL records, snapshots and bundles.
+    pub(crate) fn codec(&self) -> Box<dyn StorageCodec> {
+        Box::new(Arc::clone(&self.codec))
+    }
+
+    /// Where the full-text index is saved, or None if it lives in memory only
+    pub(crate) fn text_index_path(&self) -> Option<PathBuf> {
+        match self.persistence_mode {
+            PersistenceMode::Disk if !self.data_dir.as_os_str().is_empty() => {
+                Some(self.data_dir.join(TEXT_INDEX_FILE))
+            }
+            _ => None,
+        }
+    }
+
+    /// Save the full-text index, if one has been built
+    fn save_text_index(&self) -> StrataResult<()> {
+        let Some(path) = self.text_index_path() else {
+            return Ok(());
+        };
+        let index = self
+            .extensions
+            .get(&TypeId::of::<TextIndex>())
+            .map(|entry| entry.value().clone());
+        match index.and_then(|index| index.downcast::<TextIndex>().ok()) {
+            Some(index) => index.save(&path, &*self.codec),
+            None => Ok(()),
+        }
+    }
+
+    /// Compression dictionaries for snapshot values.
+    ///
+    /// Returns the dictionaries stored in the MANIFEST, training and storing
+    /// them from `data` if there are none yet. Dictionaries are never
+    /// retrained, so every snapshot stays readable with the stored set.
+    fn snapshot_dictionaries(
+        manifest: &mut ManifestManager,
+        data: &CheckpointData,
+    ) -> StrataResult<BTreeMap<u8, CompressionDictionary>> {
+        if manifest.manifest().dictionaries.is_empty() {
+            let trained = data.train_dictionaries(DEFAULT_DICTIONARY_SIZE);
+            if !trained.is_empty() {
+                manifest
+                    .set_dictionaries(trained)
+                    .map_err(|e: ManifestError| {
+                        StrataError::internal(format!("manifest update failed: {}", e))
+                    })?;
+            }
+        }
+        Ok(manifest.manifest().dictionaries.clone())
+    }
+
     /// Load an existing MANIFEST or create a new one.
     ///
     /// Also updates the active WAL segment from the current WAL writer.
@@ -957,11 +2151,10 @@ impl Database {
                 StrataError::internal(format!("failed to load MANIFEST: {}", e))
             })?
         } else {
-            ManifestManager::create(manifest_path, [0u8; 16], "identity".to_string()).map_err(
-                |e: ManifestError| {
+            ManifestManager::create(manifest_path, [0u8; 16], self.codec.codec_id().to_string())
+                .map_err(|e: ManifestError| {
                     StrataError::internal(format!("failed to create MANIFEST: {}", e))
-                },
-            )?
+                })?
         };
 
         // Update active WAL segment from the writer
@@ -991,6 +2184,24 @@ impl Database {
         Ok(())
     }
 
+    /// Run a transaction closure, aborting the transaction if it panics.
+    ///
+    /// The panic continues to the caller once the abort is recorded, so a
+    /// panicking closure never leaves a transaction counted as active.
+    fn run_closure<F, T>(&self, txn: &mut TransactionContext, f: F) -> StrataResult<T>
+    where
+        F: FnOnce(&mut TransactionContext) -> StrataResult<T>,
+    {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(&mut *txn))) {
+            Ok(result) => result,
+            Err(panic) => {
+                let _ = txn.mark_aborted("transaction closure panicked".to_string());
+                self.coordinator.record_abort();
+                std::panic::resume_unwind(panic)
+            }
+        }
+    }
+
     /// Execute one transaction attempt: commit on success, abort on error.
     ///
     /// Handles the commit-or-abort decision and coordinator bookkeeping.
@@ -1047,7 +2258,7 @@ impl Database {
     {
         self.check_accepting()?;
         let mut txn = self.begin_transaction(branch_id);
-        let result = f(&mut txn);
+        let result = self.run_closure(&mut txn, f);
         let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
         self.end_transaction(txn);
         outcome.map(|(value, _)| value)
@@ -1080,12 +2291,53 @@ impl Database {
     {
         self.check_accepting()?;
         let mut txn = self.begin_transaction(branch_id);
-        let result = f(&mut txn);
+        let result = self.run_closure(&mut txn, f);
         let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
         self.end_transaction(txn);
         outcome
     }
 
+    /// Run a transaction, re-running it with backoff while it conflicts
+    ///
+    /// Begins a fresh transaction for every attempt and calls `f` with it.
+    /// When the commit (or `f` itself) fails with a retryable error - a
+    /// commit-time conflict, a CAS mismatch or a lock timeout - the attempt
+    /// is discarded and the closure runs again after an exponential backoff
+    /// delay, with jitter unless `policy.jitter` is off. Any other error is
+    /// returned at once. `f` may run several times, so it should only have
+    /// effects through `txn`.
+    ///
+    /// Re-runs and exhausted loops are counted in `transaction_metrics()`
+    /// as `total_retries` and `total_retries_exhausted`.
+    ///
+    /// # Errors
+    /// The closure's non-retryable error, or the last conflict once
+    /// `policy.max_retries` retries have failed.
+    ///
+    /// # Example
+    /// ```text
+    /// let policy = RetryConfig::default().with_max_retries(10);
+    /// db.with_retry(branch_id, policy, |txn| {
+    ///     let n = match txn.get(&key)? {
+    ///         Some(Value::Int(n)) => n,
+    ///         _ => 0,
+    ///     };
+    ///     txn.put(key.clone(), Value::Int(n + 1))
+    /// })?;
+    /// ```
+    pub fn with_retry<F, T>(
+        &self,
+        branch_id: BranchId,
+        policy: RetryConfig,
+        f: F,
+    ) -> StrataResult<T>
+    where
+        F: FnMut(&mut TransactionContext) -> StrataResult<T>,
+    {
+        self.transaction_with_retry_version(branch_id, policy, f)
+            .map(|(value, _)| value)
+    }
+
     /// Execute a transaction with automatic retry on conflict
     ///
     /// Per spec Section 4.3: Implicit transactions include automatic retry on conflict.
@@ -1093,7 +2345,8 @@ impl Database {
     ///
     /// The closure is called repeatedly until either:
     /// - The transaction commits successfully
-    /// - A non-conflict error occurs (not retried)
+    /// - A non-retryable error occurs (conflicts and failed commit
+    ///   validation are retried)
     /// - Maximum retries are exceeded
     ///
     /// # Arguments
@@ -1103,7 +2356,7 @@ impl Database {
     ///
     /// # Returns
     /// * `Ok(T)` - Closure return value on successful commit
-    /// * `Err` - On non-conflict error or max retries exceeded
+    /// * `Err` - On non-retryable error or max retries exceeded
     ///
     /// # Example
     /// ```text
@@ -1121,7 +2374,24 @@ impl Database {
         f: F,
     ) -> StrataResult<T>
     where
-        F: Fn(&mut TransactionContext) -> StrataResult<T>,
+        F: FnMut(&mut TransactionContext) -> StrataResult<T>,
+    {
+        self.transaction_with_retry_version(branch_id, config, f)
+            .map(|(value, _)| value)
+    }
+
+    /// Execute a transaction with automatic retry on conflict, returning the
+    /// commit version of the successful attempt
+    ///
+    /// Same retry behavior as `transaction_with_retry()`.
+    pub(crate) fn transaction_with_retry_version<F, T>(
+        &self,
+        branch_id: BranchId,
+        config: RetryConfig,
+        mut f: F,
+    ) -> StrataResult<(T, u64)>
+    where
+        F: FnMut(&mut TransactionContext) -> StrataResult<T>,
     {
         self.check_accepting()?;
 
@@ -1129,18 +2399,24 @@ impl Database {
 
         for attempt in 0..=config.max_retries {
             let mut txn = self.begin_transaction(branch_id);
-            let result = f(&mut txn);
+            let result = self.run_closure(&mut txn, &mut f);
             let outcome = self.run_single_attempt(&mut txn, result, self.durability_mode);
             self.end_transaction(txn);
 
             match outcome {
-                Ok((value, _)) => return Ok(value),
-                Err(e) if e.is_conflict() && attempt < config.max_retries => {
+                Ok(committed) => return Ok(committed),
+                Err(e) if e.is_retryable() && attempt < config.max_retries => {
+                    self.coordinator.record_retry();
                     last_error = Some(e);
                     std::thread::sleep(config.calculate_delay(attempt));
                     continue;
                 }
-                Err(e) => return Err(e),
+                Err(e) => {
+                    if e.is_retryable() {
+                        self.coordinator.record_retries_exhausted();
+                    }
+                    return Err(e);
+                }
             }
         }
 
@@ -1174,10 +2450,16 @@ impl Database {
     /// ```
     pub fn begin_transaction(&self, branch_id: BranchId) -> TransactionContext {
         let txn_id = self.coordinator.next_txn_id();
+        let _span = self
+            .coordinator
+            .commit_tracing()
+            .then(|| begin_span(branch_id, txn_id).entered());
         let snapshot = self.storage.create_snapshot();
         self.coordinator.record_start();
 
-        TransactionPool::acquire(txn_id, branch_id, Some(Box::new(snapshot)))
+        let mut txn = TransactionPool::acquire(txn_id, branch_id, Some(Box::new(snapshot)));
+        txn.set_key_locks(Some(Arc::clone(&self.key_locks)));
+        txn
     }
 
     /// End a transaction (return to pool)
@@ -1198,10 +2480,32 @@ impl Database {
     /// db.commit_transaction(&mut txn)?;
     /// db.end_transaction(txn); // Return to pool for reuse
     /// ```
-    pub fn end_transaction(&self, ctx: TransactionContext) {
+    pub fn end_transaction(&self, mut ctx: TransactionContext) {
+        ctx.release_locks();
         TransactionPool::release(ctx);
     }
 
+    /// Abort a transaction that will not be committed (return to pool)
+    ///
+    /// Marks the transaction aborted, records the abort so it no longer
+    /// counts as active, and returns the context to the pool. Use this
+    /// instead of `end_transaction()` when discarding a transaction begun
+    /// with `begin_transaction()`.
+    ///
+    /// # Example
+    /// ```text
+    /// let mut txn = db.begin_transaction(branch_id);
+    /// txn.put(key, value)?;
+    /// db.abort_transaction(txn); // Discard the writes
+    /// ```
+    pub fn abort_transaction(&self, mut ctx: TransactionContext) {
+        if ctx.is_active() {
+            let _ = ctx.mark_aborted("aborted by caller".to_string());
+            self.coordinator.record_abort();
+        }
+        self.end_transaction(ctx);
+    }
+
     /// Commit a transaction
     ///
     /// Per spec commit sequence:
@@ -1238,24 +2542,67 @@ impl Database {
     /// - Validation (first-committer-wins)
     /// - Version allocation
     /// - WAL writing (when WAL reference is provided)
-    /// - Storage application
+    /// - Storage application, after the WAL lock is released
     /// - Fsync (WAL::append handles fsync based on its DurabilityMode)
     fn commit_internal(
         &self,
         txn: &mut TransactionContext,
         durability: DurabilityMode,
     ) -> StrataResult<u64> {
-        let needs_wal =
-            durability.requires_wal() && (!txn.is_read_only() || !txn.json_writes().is_empty());
+        let has_writes = !txn.is_read_only() || !txn.json_writes().is_empty();
+        if has_writes && self.is_read_only() {
+            let _ = txn.mark_aborted("database is open read-only".to_string());
+            self.coordinator.record_abort();
+            return Err(StrataError::invalid_input(format!(
+                "database at '{}' is open read-only",
+                self.data_dir.display()
+            )));
+        }
+        let adds_data =
+            !txn.write_set.is_empty() || !txn.cas_set.is_empty() || !txn.json_writes().is_empty();
+        if adds_data {
+            if let Err(e) = self.storage.check_memory_budget() {
+                let _ = txn.mark_aborted(e.to_string());
+                self.coordinator.record_abort();
+                return Err(e);
+            }
+        }
+        let needs_wal = durability.requires_wal() && has_writes;
 
-        let mut wal_guard = if needs_wal {
-            self.wal_writer.as_ref().map(|w| w.lock())
+        // The WAL is locked only for version allocation and the append;
+        // storage apply proceeds in parallel with the next commit's append
+        let wal = if needs_wal {
+            self.wal_writer.as_deref()
         } else {
             None
         };
-        let wal_ref = wal_guard.as_deref_mut();
 
-        self.coordinator.commit(txn, self.storage.as_ref(), wal_ref)
+        let evicted = self.storage.memory_budget_stats().evicted_keys;
+        let result = self
+            .coordinator
+            .commit_pipelined(txn, self.storage.as_ref(), wal);
+        if self.read_cache.is_enabled()
+            && self.storage.memory_budget_stats().evicted_keys != evicted
+        {
+            // Evicted keys are not named; drop everything cached
+            self.read_cache.clear();
+        } else if has_writes && self.read_cache.is_enabled() {
+            self.read_cache.invalidate(
+                txn.write_set
+                    .keys()
+                    .chain(txn.delete_set.iter())
+                    .chain(txn.cas_set.iter().map(|cas| &cas.key)),
+            );
+        }
+        let version = result?;
+
+        // Keep the full-text index current once it has been built
+        if let Some(index) = self.extensions.get(&TypeId::of::<TextIndex>()) {
+            if let Some(index) = index.value().downcast_ref::<TextIndex>() {
+                index.apply_commit(txn, &self.storage);
+            }
+        }
+        Ok(version)
     }
 
     // ========================================================================
@@ -1279,13 +2626,11 @@ impl Database {
         // Stop accepting new transactions
         self.accepting_transactions.store(false, Ordering::SeqCst);
 
-        // Signal the background flush thread to stop
-        self.flush_shutdown.store(true, Ordering::SeqCst);
+        // Stop the background flush job so it releases the WAL lock
+        self.stop_flush();
 
-        // Join the flush thread so it releases the WAL lock
-        if let Some(handle) = self.flush_handle.lock().take() {
-            let _ = handle.join();
-        }
+        self.stop_scheduled_compaction();
+        self.stop_scheduled_checkpoints();
 
         // Wait for in-flight transactions to complete
         // This ensures all transactions that started before shutdown
@@ -1304,37 +2649,157 @@ impl Database {
     }
 }
 
-impl Drop for Database {
-    fn drop(&mut self) {
-        // Stop the background flush thread
-        self.flush_shutdown.store(true, Ordering::SeqCst);
-        if let Some(handle) = self.flush_handle.lock().take() {
-            let _ = handle.join();
-        }
+/// Fail loudly on a missing or wrong key rather than letting recovery
+/// treat undecodable records as a torn tail.
+fn check_wal_codecs(
+    data_dir: &Path,
+    wal_dir: &Path,
+    codec: &Arc<dyn StorageCodec>,
+) -> StrataResult<()> {
+    WalReader::new(Box::new(Arc::clone(codec)))
+        .check_codecs(wal_dir)
+        .map_err(|e| {
+            let hint = if codec.codec_id() == AES_GCM_CODEC_ID {
+                "check [encryption] key_provider and previous_key_providers in strata.toml"
+            } else {
+                "if the database is encrypted, set [encryption] key_provider in strata.toml"
+            };
+            StrataError::invalid_input(format!(
+                "cannot open database at '{}': {} ({})",
+                data_dir.display(),
+                e,
+                hint
+            ))
+        })
+}
 
-        // Final flush to persist any remaining data
-        let _ = self.flush();
+/// User-key prefix of KV history policy entries
+pub(crate) const HISTORY_POLICY_PREFIX: &[u8] = b"__history__";
+
+/// The policy set on the longest prefix of `user_key`, if any.
+fn governing_policy<'a>(
+    policies: &'a [(String, RetentionPolicy)],
+    user_key: &[u8],
+) -> Option<&'a RetentionPolicy> {
+    policies
+        .iter()
+        .filter(|(prefix, _)| user_key.starts_with(prefix.as_bytes()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, policy)| policy)
+}
 
-        // Remove from registry if we're disk-backed
-        if self.persistence_mode == PersistenceMode::Disk && !self.data_dir.as_os_str().is_empty() {
-            let mut registry = OPEN_DATABASES.lock();
-            registry.remove(&self.data_dir);
-        }
+/// Primitive whose history a branch retention policy governs for keys
+/// tagged `tag`, or `None` for metadata that is never pruned.
+fn retained_primitive(tag: TypeTag) -> Option<PrimitiveType> {
+    match tag {
+        TypeTag::KV => Some(PrimitiveType::Kv),
+        TypeTag::Event => Some(PrimitiveType::Event),
+        TypeTag::State => Some(PrimitiveType::State),
+        TypeTag::List => Some(PrimitiveType::List),
+        TypeTag::Json => Some(PrimitiveType::Json),
+        TypeTag::Vector => Some(PrimitiveType::Vector),
+        _ => None,
     }
 }
 
-// ============================================================================
-// Tests
-// ============================================================================
+/// Stop the WAL flush job and settle unsynced records per `behavior`.
+fn close_wal(
+    wal: Arc<ParkingMutex<WalWriter>>,
+    flush_worker: Option<Worker>,
+    behavior: DropBehavior,
+    data_dir: &Path,
+) -> std::io::Result<()> {
+    if let Some(worker) = flush_worker {
+        worker.stop();
+    }
+    let mut wal = wal.lock();
+    match behavior {
+        DropBehavior::Flush => wal.flush(),
+        DropBehavior::Warn => {
+            if wal.unsynced_writes() > 0 {
+                warn!(
+                    target: "strata::db",
+                    path = ?data_dir,
+                    records = wal.unsynced_writes(),
+                    bytes = wal.unsynced_bytes(),
+                    "Closing database without syncing WAL records"
+                );
+            }
+            Ok(())
+        }
+        DropBehavior::Discard => Ok(()),
+    }
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        // Stop the background compaction and checkpoint jobs
+        self.stop_scheduled_compaction();
+        self.stop_scheduled_checkpoints();
+
+        if let Err(e) = self.save_text_index() {
+            warn!(target: "strata::db", path = ?self.data_dir, error = %e, "Failed to save text index");
+        }
+
+        // Settle the WAL tail on a helper thread so a dying disk can't hold
+        // drop past the timeout. The helper keeps the lock file until the
+        // WAL is closed, so no other process opens it mid-flush.
+        if let Some(wal) = self.wal_writer.take() {
+            let (behavior, timeout) = *self.drop_behavior.lock();
+            let flush_worker = self.flush_worker.lock().take();
+            let lock_file = self._lock_file.take();
+            let data_dir = self.data_dir.clone();
+            let (done_tx, done_rx) = std::sync::mpsc::channel();
+            let spawned = std::thread::Builder::new()
+                .name("strata-wal-close".to_string())
+                .spawn(move || {
+                    let result = close_wal(wal, flush_worker, behavior, &data_dir);
+                    drop(lock_file);
+                    let _ = done_tx.send(result);
+                });
+            let outcome = match (spawned, timeout) {
+                (Err(e), _) => Err(e.to_string()),
+                (Ok(_), Some(timeout)) => done_rx.recv_timeout(timeout).map_err(|e| match e {
+                    std::sync::mpsc::RecvTimeoutError::Timeout => {
+                        format!("timed out after {} ms", timeout.as_millis())
+                    }
+                    std::sync::mpsc::RecvTimeoutError::Disconnected => "flush panicked".into(),
+                }),
+                (Ok(_), None) => done_rx.recv().map_err(|_| "flush panicked".to_string()),
+            };
+            match outcome {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    warn!(target: "strata::db", path = ?self.data_dir, error = %e, "Final WAL flush failed")
+                }
+                Err(reason) => {
+                    warn!(target: "strata::db", path = ?self.data_dir, reason = %reason, "Dropped database before its final WAL flush finished")
+                }
+            }
+        }
+
+        // Remove from registry if we're disk-backed
+        if self.persistence_mode == PersistenceMode::Disk && !self.data_dir.as_os_str().is_empty() {
+            let mut registry = OPEN_DATABASES.lock();
+            registry.remove(&self.data_dir);
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use strata_concurrency::TransactionPayload;
+    use strata_core::traits::SnapshotView;
     use strata_core::types::{Key, Namespace};
     use strata_core::value::Value;
     use strata_core::Storage;
-    use strata_durability::format::WalRecord;
+    use strata_durability::codec::{IdentityCodec, CODEC_BYTE_ZSTD};
+    use strata_durability::format::{WalRecord, WalSegment};
     use strata_durability::now_micros;
     use tempfile::TempDir;
 
@@ -1360,6 +2825,7 @@ mod tests {
             version,
             puts,
             deletes,
+            trimmed: Vec::new(),
         };
         let record = WalRecord::new(
             txn_id,
@@ -1480,6 +2946,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_drop_gives_up_on_stuck_wal_flush() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+
+        let db = Database::open(&db_path).unwrap();
+        db.set_drop_behavior(
+            DropBehavior::Flush,
+            Some(std::time::Duration::from_millis(50)),
+        );
+        let wal = Arc::clone(db.wal_writer.as_ref().unwrap());
+        let stuck = wal.lock();
+
+        let start = std::time::Instant::now();
+        drop(db);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+        // The lock file is released once the abandoned flush completes
+        drop(stuck);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while Database::open(&db_path).is_err() {
+            assert!(std::time::Instant::now() < deadline);
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_on_drop_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        let config_path = db_path.join(config::CONFIG_FILE_NAME);
+
+        std::fs::write(&config_path, "on_drop = \"block\"\n").unwrap();
+        assert!(Database::open(&db_path).is_err());
+
+        std::fs::write(&config_path, "on_drop = \"discard\"\n").unwrap();
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        {
+            let db = Database::open(&db_path).unwrap();
+            assert_eq!(db.drop_behavior.lock().0, DropBehavior::Discard);
+            db.transaction(branch_id, |txn| {
+                txn.put(Key::new_kv(ns.clone(), "k"), Value::Int(1))?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        // Discarding skips the fsync, but the record still reached the OS
+        let db = Database::open(&db_path).unwrap();
+        let val = db.storage().get(&Key::new_kv(ns, "k")).unwrap().unwrap();
+        assert_eq!(val.value, Value::Int(1));
+    }
+
     #[test]
     fn test_partial_record_discarded() {
         // With the segmented WAL, partial records (crash mid-write) are
@@ -1700,6 +3221,66 @@ mod tests {
         assert!(db.storage().get(&key).unwrap().is_none());
     }
 
+    #[test]
+    fn test_transaction_survives_closure_panic() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "panic_key");
+        {
+            let db = Database::open(&db_path).unwrap();
+
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                db.transaction(branch_id, |txn| -> StrataResult<()> {
+                    txn.put(key.clone(), Value::Int(1))?;
+                    panic!("injected panic inside transaction");
+                })
+            }));
+            assert!(outcome.is_err());
+            assert_eq!(db.coordinator.active_count(), 0);
+            assert!(db.storage().get(&key).unwrap().is_none());
+
+            // The retrying variant recovers the same way
+            let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                db.transaction_with_retry(
+                    branch_id,
+                    RetryConfig::default(),
+                    |_| -> StrataResult<()> {
+                        panic!("injected panic inside retried transaction");
+                    },
+                )
+            }));
+            assert!(outcome.is_err());
+            assert_eq!(db.coordinator.active_count(), 0);
+
+            db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(2)))
+                .unwrap();
+            let start = std::time::Instant::now();
+            db.shutdown().unwrap();
+            assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let stored = db.storage().get(&key).unwrap().unwrap();
+        assert_eq!(stored.value, Value::Int(2));
+    }
+
+    #[test]
+    fn test_abort_transaction_records_abort() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "discarded");
+
+        let mut txn = db.begin_transaction(branch_id);
+        txn.put(key.clone(), Value::Int(1)).unwrap();
+        assert_eq!(db.coordinator.active_count(), 1);
+        db.abort_transaction(txn);
+
+        assert_eq!(db.coordinator.active_count(), 0);
+        assert!(db.storage().get(&key).unwrap().is_none());
+    }
+
     #[test]
     fn test_begin_and_commit_manual() {
         let temp_dir = TempDir::new().unwrap();
@@ -1757,6 +3338,7 @@ mod tests {
             max_retries: 5,
             base_delay_ms: 10,
             max_delay_ms: 100,
+            jitter: false,
         };
 
         // Exponential backoff: 10, 20, 40, 80, 100 (capped)
@@ -1768,6 +3350,72 @@ mod tests {
         assert_eq!(config.calculate_delay(5).as_millis(), 100); // Still capped
     }
 
+    #[test]
+    fn test_retry_config_jitter_stays_within_half_to_full_delay() {
+        let config = RetryConfig::new()
+            .with_base_delay_ms(40)
+            .with_max_delay_ms(40);
+        assert!(config.jitter);
+        for _ in 0..100 {
+            let delay = config.calculate_delay(0).as_millis();
+            assert!((20..=40).contains(&delay), "delay {}ms", delay);
+        }
+    }
+
+    #[test]
+    fn test_with_retry_reruns_conflicts_and_counts_them() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(Namespace::for_branch(branch_id), "k");
+        let policy = RetryConfig::new().with_max_retries(5).with_base_delay_ms(1);
+
+        let mut attempts = 0;
+        let value = db
+            .with_retry(branch_id, policy.clone(), |txn| {
+                attempts += 1;
+                if attempts < 3 {
+                    return Err(StrataError::conflict("simulated".to_string()));
+                }
+                txn.put(key.clone(), Value::Int(attempts))?;
+                Ok(attempts)
+            })
+            .unwrap();
+        assert_eq!(value, 3);
+        let metrics = db.transaction_metrics();
+        assert_eq!(metrics.total_retries, 2);
+        assert_eq!(metrics.total_retries_exhausted, 0);
+
+        // Gives up after max_retries, returning the last conflict
+        let mut attempts = 0;
+        let err = db
+            .with_retry(
+                branch_id,
+                policy.clone().with_max_retries(2),
+                |_| -> StrataResult<()> {
+                    attempts += 1;
+                    Err(StrataError::conflict("simulated".to_string()))
+                },
+            )
+            .unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(attempts, 3);
+        let metrics = db.transaction_metrics();
+        assert_eq!(metrics.total_retries, 4);
+        assert_eq!(metrics.total_retries_exhausted, 1);
+
+        // Other errors are not retried
+        let mut attempts = 0;
+        let err = db
+            .with_retry(branch_id, policy, |_| -> StrataResult<()> {
+                attempts += 1;
+                Err(StrataError::invalid_input("bad".to_string()))
+            })
+            .unwrap_err();
+        assert!(!err.is_retryable());
+        assert_eq!(attempts, 1);
+        assert_eq!(db.transaction_metrics().total_retries, 4);
+    }
+
     // ========================================================================
     // Graceful Shutdown Tests
     // ========================================================================
@@ -1911,6 +3559,36 @@ mod tests {
         assert!(!db.is_cache());
     }
 
+    #[test]
+    fn test_max_loss_window_follows_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let db_path = temp_dir.path().join("sync_window");
+        std::fs::create_dir_all(&db_path).unwrap();
+        std::fs::write(
+            db_path.join("strata.toml"),
+            "sync_interval_ms = 250\nsync_bytes = 65536\n",
+        )
+        .unwrap();
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(
+            db.max_loss_window(),
+            LossWindow::Bounded {
+                max_age: std::time::Duration::from_millis(250),
+                max_bytes: Some(65536),
+            }
+        );
+
+        let db_path = temp_dir.path().join("always_window");
+        std::fs::create_dir_all(&db_path).unwrap();
+        std::fs::write(db_path.join("strata.toml"), "durability = \"always\"\n").unwrap();
+        let db = Database::open(&db_path).unwrap();
+        assert!(db.max_loss_window().is_zero());
+
+        let db = Database::cache().unwrap();
+        assert_eq!(db.max_loss_window(), LossWindow::Unbounded);
+    }
+
     #[test]
     fn test_open_rejects_invalid_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -1923,6 +3601,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_analytics_view_is_frozen() {
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        let key = Key::new_kv(ns.clone(), "k");
+
+        db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+            .unwrap();
+        let view = db.analytics_view();
+
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(2))?;
+            txn.put(Key::new_kv(ns.clone(), "other"), Value::Int(3))
+        })
+        .unwrap();
+        db.gc_versions_before(branch_id, db.current_version());
+
+        assert_eq!(view.get(&key).unwrap().unwrap().value, Value::Int(1));
+        assert_eq!(view.branch_entry_count(&branch_id), 1);
+        assert_eq!(
+            db.analytics_view().branch_entry_count(&branch_id),
+            2,
+            "a new view sees the later commit"
+        );
+    }
+
     // ========================================================================
     // Checkpoint & Compaction Tests
     // ========================================================================
@@ -1942,65 +3647,1052 @@ mod tests {
     }
 
     #[test]
-    fn test_compact_without_checkpoint_fails() {
+    fn test_compact_without_checkpoint_succeeds() {
         let temp_dir = TempDir::new().unwrap();
         let db = Database::open(temp_dir.path().join("db")).unwrap();
 
-        // Compact before any checkpoint should fail (no snapshot watermark)
-        let result = db.compact();
-        assert!(result.is_err());
+        // Compaction rewrites live data itself, so no checkpoint is required
+        let info = db.compact().unwrap();
+        assert_eq!(info.snapshot_watermark, Some(0));
     }
 
     #[test]
-    fn test_checkpoint_creates_snapshot() {
+    fn test_compact_keeps_only_live_data_across_reopen() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let live = Key::new_kv(ns.clone(), "live");
+        let deleted = Key::new_kv(ns.clone(), "deleted");
+
+        let live_version = {
+            let db = Database::open(&db_path).unwrap();
+
+            for i in 0..50 {
+                db.transaction(branch_id, |txn| {
+                    txn.put(live.clone(), Value::Int(i))?;
+                    txn.put(deleted.clone(), Value::Int(i))?;
+                    Ok(())
+                })
+                .unwrap();
+            }
+            db.transaction(branch_id, |txn| {
+                txn.delete(deleted.clone())?;
+                Ok(())
+            })
+            .unwrap();
+
+            // Compaction keeps every version storage holds; drop the history
+            assert_eq!(
+                db.gc_versions_before(branch_id, db.current_version()),
+                2 * 49 + 1
+            );
+            let info = db.compact().unwrap();
+            assert!(info.wal_segments_removed >= 1);
+            assert!(info.reclaimed_bytes > 0);
+            assert_eq!(
+                info.snapshot_watermark,
+                Some(db.coordinator.current_version())
+            );
+            let segments = db
+                .wal_writer
+                .as_ref()
+                .unwrap()
+                .lock()
+                .list_segments()
+                .unwrap();
+            assert_eq!(segments.len(), 1);
+
+            db.storage().get(&live).unwrap().unwrap().version
+        };
+
         let db = Database::open(&db_path).unwrap();
+        let val = db.storage().get(&live).unwrap().unwrap();
+        assert_eq!(val.value, Value::Int(49));
+        assert_eq!(val.version, live_version);
+        assert!(db.storage().get(&deleted).unwrap().is_none());
+
+        // Version counter continues past the pre-compaction watermark
+        let version = db
+            .transaction_with_version(branch_id, |txn| {
+                txn.put(live.clone(), Value::Int(50))?;
+                Ok(())
+            })
+            .unwrap()
+            .1;
+        assert!(version > live_version.as_u64());
+    }
 
+    #[test]
+    fn test_hot_backup_restores_state_at_begin() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
         let branch_id = BranchId::new();
-        let ns = create_test_namespace(branch_id);
-        let key = Key::new_kv(ns, "checkpoint_test");
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        let put = |v: i64| {
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(v))?;
+                Ok(())
+            })
+            .unwrap();
+        };
+
+        put(1);
+        let backup = db.begin_backup().unwrap();
+        assert!(db.backup_in_progress());
+        assert_eq!(backup.version, db.coordinator.current_version());
+        assert!(backup
+            .files
+            .iter()
+            .any(|f| f.path == Path::new(config::CONFIG_FILE_NAME)));
+        assert!(backup.files.iter().any(|f| f.path.starts_with("wal")));
+
+        // Writes continue, and compaction leaves the listed segments alone
+        put(2);
+        let info = db.compact().unwrap();
+        assert_eq!(info.wal_segments_removed, 0);
+        assert_eq!(info.snapshot_watermark, None);
+        put(3);
+
+        let restore_path = temp_dir.path().join("restore");
+        backup.copy_to(&restore_path).unwrap();
+        db.end_backup(backup.id).unwrap();
+        assert!(!db.backup_in_progress());
+        assert!(db.end_backup(backup.id).is_err());
+        assert!(db.compact().unwrap().snapshot_watermark.is_some());
+
+        let restored = Database::open(&restore_path).unwrap();
+        let val = restored.storage().get(&key).unwrap().unwrap();
+        assert_eq!(val.value, Value::Int(1));
+        assert_eq!(restored.coordinator.current_version(), backup.version);
+    }
+
+    #[test]
+    fn test_hot_backup_requires_disk_database() {
+        let db = Database::cache().unwrap();
+        assert!(db.begin_backup().is_err());
+        assert!(db.end_backup(1).is_err());
+    }
+
+    #[test]
+    fn test_scheduled_compaction_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
 
-        // Write some data
         db.transaction(branch_id, |txn| {
-            txn.put(key.clone(), Value::String("hello".to_string()))?;
+            txn.put(key.clone(), Value::Int(1))?;
             Ok(())
         })
         .unwrap();
 
-        // Checkpoint should succeed
-        assert!(db.checkpoint().is_ok());
+        assert!(db.schedule_compaction(std::time::Duration::ZERO).is_err());
+        db.schedule_compaction(std::time::Duration::from_millis(10))
+            .unwrap();
 
-        // Snapshots directory should exist with files
-        let snapshots_dir = db_path.canonicalize().unwrap().join("snapshots");
-        assert!(snapshots_dir.exists());
+        let first_segment = || {
+            db.wal_writer
+                .as_ref()
+                .unwrap()
+                .lock()
+                .list_segments()
+                .unwrap()[0]
+        };
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while first_segment() < 2 {
+            assert!(std::time::Instant::now() < deadline, "compaction never ran");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
 
-        // MANIFEST should exist
-        let manifest_path = db_path.canonicalize().unwrap().join("MANIFEST");
-        assert!(manifest_path.exists());
+        db.shutdown().unwrap();
+        assert!(db.compaction_worker.lock().is_none());
     }
 
     #[test]
-    fn test_checkpoint_then_compact() {
+    fn test_scheduled_checkpoints_truncate_wal() {
         let temp_dir = TempDir::new().unwrap();
         let db_path = temp_dir.path().join("db");
         let db = Database::open(&db_path).unwrap();
-
         let branch_id = BranchId::new();
         let ns = create_test_namespace(branch_id);
-        let key = Key::new_kv(ns, "compact_test");
 
-        // Write data
+        assert!(db
+            .schedule_checkpoints(CheckpointPolicy::Transactions(0))
+            .is_err());
+        db.schedule_checkpoints(CheckpointPolicy::Transactions(5))
+            .unwrap();
+        assert_eq!(
+            db.scheduled_checkpoints(),
+            Some(CheckpointPolicy::Transactions(5))
+        );
+
+        for i in 0..4 {
+            db.transaction(branch_id, |txn| {
+                txn.put(Key::new_kv(ns.clone(), format!("k{}", i)), Value::Int(i))
+            })
+            .unwrap();
+        }
+        std::thread::sleep(checkpoint::CHECKPOINT_POLL_INTERVAL * 3);
+        assert_eq!(db.snapshot_watermark().unwrap(), None);
+
         db.transaction(branch_id, |txn| {
-            txn.put(key.clone(), Value::Int(42))?;
+            txn.put(Key::new_kv(ns.clone(), "k4"), Value::Int(4))?;
+            txn.put(Key::new_kv(ns.clone(), "k0"), Value::Int(10))
+        })
+        .unwrap();
+        let first_segment = || {
+            db.wal_writer
+                .as_ref()
+                .unwrap()
+                .lock()
+                .list_segments()
+                .unwrap()[0]
+        };
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while db.snapshot_watermark().unwrap().is_none() || first_segment() < 2 {
+            assert!(std::time::Instant::now() < deadline, "checkpoint never ran");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        db.shutdown().unwrap();
+        assert!(db.checkpoint_worker.lock().is_none());
+        drop(db);
+
+        let db = Database::open(&db_path).unwrap();
+        for i in 1..5 {
+            let key = Key::new_kv(ns.clone(), format!("k{}", i));
+            assert_eq!(
+                db.storage().get(&key).unwrap().unwrap().value,
+                Value::Int(i)
+            );
+        }
+
+        // Truncation keeps history, not just the latest versions
+        let history = db
+            .get_history(&Key::new_kv(ns.clone(), "k0"), None, None)
+            .unwrap();
+        let values: Vec<Value> = history.into_iter().map(|vv| vv.value).collect();
+        assert_eq!(values, vec![Value::Int(10), Value::Int(0)]);
+    }
+
+    #[test]
+    fn test_resource_accounting() {
+        let cache = Database::cache().unwrap();
+        assert_eq!((cache.open_files(), cache.background_threads()), (0, 0));
+
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        assert_eq!((db.open_files(), db.background_threads()), (2, 1));
+
+        let interval = std::time::Duration::from_secs(3600);
+        db.schedule_compaction(interval).unwrap();
+        assert_eq!(db.scheduled_compaction(), Some(interval));
+        assert_eq!(db.background_threads(), 2);
+        db.stop_scheduled_compaction();
+        assert_eq!(db.scheduled_compaction(), None);
+        assert_eq!(db.background_threads(), 1);
+
+        let before = db.memory_usage();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "blob");
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Bytes(vec![0; 8192]))?;
+            Ok(())
+        })
+        .unwrap();
+        assert!(db.memory_usage() >= before + 8192);
+    }
+
+    #[test]
+    fn test_open_in_pool_runs_jobs_on_shared_threads() {
+        let pool = WorkerPool::builder().threads(1).build().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let a = Database::open_in_pool(temp_dir.path().join("a"), &pool).unwrap();
+        let b = Database::open_in_pool(temp_dir.path().join("b"), &pool).unwrap();
+
+        // WAL flush jobs run on the pool instead of dedicated threads
+        assert_eq!(pool.jobs(), 2);
+        assert_eq!(a.background_threads() + b.background_threads(), 0);
+
+        a.schedule_compaction(std::time::Duration::from_millis(10))
+            .unwrap();
+        assert_eq!(pool.jobs(), 3);
+        assert_eq!(a.background_threads(), 0);
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        a.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(1))?;
             Ok(())
         })
         .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(50));
 
-        // Checkpoint first
-        assert!(db.checkpoint().is_ok());
+        a.shutdown().unwrap();
+        drop(a);
+        assert_eq!(pool.jobs(), 1);
+        drop(b);
+        assert_eq!(pool.jobs(), 0);
+    }
 
-        // Now compact should succeed
-        assert!(db.compact().is_ok());
+    #[test]
+    fn test_zstd_compression_recovers_mixed_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let before = Key::new_kv(ns.clone(), "before");
+        let after = Key::new_kv(ns.clone(), "after");
+        let payload = Value::String("compressible ".repeat(200));
+
+        // Write with the default (no compression)
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(before.clone(), payload.clone())?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        // Switch to zstd and keep writing
+        std::fs::write(
+            db_path.join(config::CONFIG_FILE_NAME),
+            "durability = \"always\"\ncompression = \"zstd\"\n",
+        )
+        .unwrap();
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(after.clone(), payload.clone())?;
+                Ok(())
+            })
+            .unwrap();
+
+            let wal = db.wal_writer.as_ref().unwrap().lock();
+            let segment = WalSegment::open_read(wal.wal_dir(), wal.current_segment()).unwrap();
+            assert_eq!(segment.codec_byte(), CODEC_BYTE_ZSTD);
+        }
+
+        // Both identity and zstd segments recover
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(db.storage().get(&before).unwrap().unwrap().value, payload);
+        assert_eq!(db.storage().get(&after).unwrap().unwrap().value, payload);
+    }
+
+    fn write_encryption_config(db_path: &Path, key_provider: &str, previous: &[&str]) {
+        std::fs::create_dir_all(db_path).unwrap();
+        std::fs::write(
+            db_path.join(config::CONFIG_FILE_NAME),
+            format!(
+                "durability = \"always\"\n[encryption]\nkey_provider = {:?}\nprevious_key_providers = {:?}\n",
+                key_provider, previous
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_database_reopens_and_hides_plaintext() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let key_path = temp_dir.path().join("a.key");
+        std::fs::write(&key_path, [0x11u8; 32]).unwrap();
+        let provider = format!("file:{}", key_path.display());
+        write_encryption_config(&db_path, &provider, &[]);
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "secret");
+        let value = Value::String("top-secret-plaintext".to_string());
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), value.clone())?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        for entry in std::fs::read_dir(db_path.join("wal")).unwrap() {
+            let bytes = std::fs::read(entry.unwrap().path()).unwrap();
+            assert!(!bytes
+                .windows(b"top-secret-plaintext".len())
+                .any(|w| w == b"top-secret-plaintext"));
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(db.storage().get(&key).unwrap().unwrap().value, value);
+    }
+
+    #[test]
+    fn test_encrypted_database_without_key_fails_clearly() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let key_path = temp_dir.path().join("a.key");
+        std::fs::write(&key_path, [0x22u8; 32]).unwrap();
+        write_encryption_config(&db_path, &format!("file:{}", key_path.display()), &[]);
+
+        let branch_id = BranchId::new();
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(
+                    Key::new_kv(create_test_namespace(branch_id), "k"),
+                    Value::Int(1),
+                )?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        // No [encryption] section
+        std::fs::write(db_path.join(config::CONFIG_FILE_NAME), "").unwrap();
+        let err = Database::open(&db_path).err().unwrap().to_string();
+        assert!(err.contains("no encryption key was provided"), "{}", err);
+        assert!(err.contains("[encryption] key_provider"), "{}", err);
+
+        // Wrong key
+        let wrong_path = temp_dir.path().join("b.key");
+        std::fs::write(&wrong_path, [0x33u8; 32]).unwrap();
+        write_encryption_config(&db_path, &format!("file:{}", wrong_path.display()), &[]);
+        let err = Database::open(&db_path).err().unwrap().to_string();
+        assert!(err.contains("not configured"), "{}", err);
+    }
+
+    #[test]
+    fn test_key_rotation_via_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let old_key = temp_dir.path().join("old.key");
+        let new_key = temp_dir.path().join("new.key");
+        std::fs::write(&old_key, [0x44u8; 32]).unwrap();
+        std::fs::write(&new_key, [0x55u8; 32]).unwrap();
+        let old_provider = format!("file:{}", old_key.display());
+        let new_provider = format!("file:{}", new_key.display());
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let first = Key::new_kv(ns.clone(), "first");
+        let second = Key::new_kv(ns, "second");
+
+        write_encryption_config(&db_path, &old_provider, &[]);
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(first.clone(), Value::Int(1))?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        // Rotate: new key encrypts, old key still decrypts, compaction rewrites
+        write_encryption_config(&db_path, &new_provider, &[&old_provider]);
+        {
+            let db = Database::open(&db_path).unwrap();
+            assert_eq!(
+                db.storage().get(&first).unwrap().unwrap().value,
+                Value::Int(1)
+            );
+            db.transaction(branch_id, |txn| {
+                txn.put(second.clone(), Value::Int(2))?;
+                Ok(())
+            })
+            .unwrap();
+            db.compact().unwrap();
+        }
+
+        // The old key is no longer needed
+        write_encryption_config(&db_path, &new_provider, &[]);
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(
+            db.storage().get(&first).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+        assert_eq!(
+            db.storage().get(&second).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_creates_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "checkpoint_test");
+
+        // Write some data
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::String("hello".to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+
+        // Checkpoint should succeed
+        assert!(db.checkpoint().is_ok());
+
+        // Snapshots directory should exist with files
+        let snapshots_dir = db_path.canonicalize().unwrap().join("snapshots");
+        assert!(snapshots_dir.exists());
+
+        // MANIFEST should exist
+        let manifest_path = db_path.canonicalize().unwrap().join("MANIFEST");
+        assert!(manifest_path.exists());
+    }
+
+    #[test]
+    fn test_introspection_reports_wal_watermark_and_branch_usage() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("db")).unwrap();
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        assert_eq!(db.snapshot_watermark().unwrap(), None);
+        assert_eq!(db.branch_usage(branch_id), ShardUsage::default());
+
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::String("hello".to_string()))?;
+            Ok(())
+        })
+        .unwrap();
+        db.flush().unwrap();
+        assert!(db.wal_size().unwrap() > 0);
+        let usage = db.branch_usage(branch_id);
+        assert_eq!(usage.entries.get(&TypeTag::KV), Some(&1));
+        assert!(usage.memory_bytes > 0);
+        assert_eq!(db.transaction_metrics().total_committed, 1);
+
+        db.checkpoint().unwrap();
+        assert_eq!(db.snapshot_watermark().unwrap(), Some(db.current_version()));
+
+        let cache = Database::cache().unwrap();
+        assert_eq!(cache.wal_size().unwrap(), 0);
+        assert_eq!(cache.snapshot_watermark().unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_cache_serves_hot_keys_until_written() {
+        use crate::primitives::{KVStore, StateCell};
+
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let state = StateCell::new(db.clone());
+        let branch_id = BranchId::new();
+        kv.put(&branch_id, "default", "a", Value::Int(1)).unwrap();
+
+        // Disabled by default: every read goes to storage
+        kv.get_versioned(&branch_id, "default", "a").unwrap();
+        assert_eq!(db.read_cache_stats(), ReadCacheStats::default());
+
+        db.set_read_cache_capacity(2);
+        for _ in 0..3 {
+            let value = kv.get_versioned(&branch_id, "default", "a").unwrap();
+            assert_eq!(value.unwrap().value, Value::Int(1));
+        }
+        let stats = db.read_cache_stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 1, 1));
+
+        // A commit evicts the keys it wrote
+        kv.put(&branch_id, "default", "a", Value::Int(2)).unwrap();
+        let value = kv.get_versioned(&branch_id, "default", "a").unwrap();
+        assert_eq!(value.unwrap().value, Value::Int(2));
+        assert_eq!(db.read_cache_stats().misses, 2);
+
+        // Absent keys are cached too, and state cells are cached decoded
+        assert!(state
+            .get_versioned(&branch_id, "default", "cell")
+            .unwrap()
+            .is_none());
+        state
+            .set(&branch_id, "default", "cell", Value::Int(7))
+            .unwrap();
+        let cell = state.get_versioned(&branch_id, "default", "cell").unwrap();
+        assert_eq!(cell.unwrap().value, Value::Int(7));
+        assert!(state
+            .get_versioned(&branch_id, "default", "cell")
+            .unwrap()
+            .is_some());
+
+        // Capacity bounds the cache; "a" is the least recently used
+        kv.get_versioned(&branch_id, "default", "b").unwrap();
+        let stats = db.read_cache_stats();
+        assert_eq!((stats.entries, stats.capacity), (2, 2));
+        state.get_versioned(&branch_id, "default", "cell").unwrap();
+        assert_eq!(db.read_cache_stats().misses, stats.misses);
+        kv.get_versioned(&branch_id, "default", "a").unwrap();
+        assert_eq!(db.read_cache_stats().misses, stats.misses + 1);
+
+        // Branches never share entries
+        let other = BranchId::new();
+        assert!(kv.get_versioned(&other, "default", "a").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_references_unchanged_sections() {
+        use strata_durability::format::primitive_tags;
+        use strata_durability::{snapshot_path, DiskSnapshotReader};
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+
+        // Several branches, so a stable section needs a stable branch order
+        let branches: Vec<BranchId> = (0..4).map(|_| BranchId::new()).collect();
+        for (i, branch_id) in branches.iter().enumerate() {
+            let key = Key::new_kv(create_test_namespace(*branch_id), "k");
+            db.transaction(*branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(i as i64))?;
+                Ok(())
+            })
+            .unwrap();
+        }
+        db.checkpoint().unwrap();
+
+        let state = Key::new_state(create_test_namespace(branches[0]), "cell");
+        db.transaction(branches[0], |txn| {
+            txn.put(state.clone(), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+
+        let snapshots_dir = db_path.canonicalize().unwrap().join("snapshots");
+        let table = DiskSnapshotReader::section_table(&snapshot_path(&snapshots_dir, 2)).unwrap();
+        let kv = table
+            .iter()
+            .find(|h| h.primitive_type == primitive_tags::KV)
+            .unwrap();
+        assert_eq!(kv.base_snapshot_id, 1);
+        let state_section = table
+            .iter()
+            .find(|h| h.primitive_type == primitive_tags::STATE)
+            .unwrap();
+        assert!(!state_section.is_reference());
+
+        // The reference resolves to the KV data written by the first checkpoint
+        let reader = DiskSnapshotReader::new(Box::new(IdentityCodec));
+        let first = reader.load(&snapshot_path(&snapshots_dir, 1)).unwrap();
+        let second = reader.load(&snapshot_path(&snapshots_dir, 2)).unwrap();
+        assert_eq!(
+            second.find_section(primitive_tags::KV).unwrap().data,
+            first.find_section(primitive_tags::KV).unwrap().data
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_full_interval_one_writes_full_snapshots() {
+        use strata_durability::{snapshot_path, DiskSnapshotReader};
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+        db.set_snapshot_full_interval(1);
+
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(1))?;
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+        db.checkpoint().unwrap();
+
+        let snapshots_dir = db_path.canonicalize().unwrap().join("snapshots");
+        let table = DiskSnapshotReader::section_table(&snapshot_path(&snapshots_dir, 2)).unwrap();
+        assert!(table.iter().all(|h| !h.is_reference()));
+    }
+
+    #[test]
+    fn test_environment_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        std::fs::write(
+            db_path.join(config::CONFIG_FILE_NAME),
+            "compression = \"zstd\"\ncapture_environment = true\n",
+        )
+        .unwrap();
+        let db = Database::open(&db_path).unwrap();
+        assert!(db.capture_environment_enabled());
+
+        let env = db.environment();
+        assert_eq!(env.strata_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(env.wal_format, SEGMENT_FORMAT_VERSION_CODEC);
+        assert_eq!(env.os, std::env::consts::OS);
+        assert_eq!(env.models["rerank"], "ms-marco-minilm-l6-v2");
+
+        let cache = Database::cache().unwrap();
+        assert!(!cache.capture_environment_enabled());
+        assert_eq!(cache.environment().wal_format, SEGMENT_FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_checkpoint_trains_snapshot_dictionaries() {
+        use strata_durability::primitive_tags;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+        db.set_snapshot_dictionaries(true);
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        db.transaction(branch_id, |txn| {
+            for i in 0..200 {
+                let value = Value::String(format!(
+                    r#"{{"id":{},"name":"user-{}","role":"member"}}"#,
+                    i, i
+                ));
+                txn.put(Key::new_kv(ns.clone(), format!("user:{}", i)), value)?;
+            }
+            Ok(())
+        })
+        .unwrap();
+        db.checkpoint().unwrap();
+
+        let manifest_path = db_path.canonicalize().unwrap().join("MANIFEST");
+        let first = ManifestManager::load(manifest_path.clone()).unwrap();
+        let kv_dict = first.manifest().dictionaries[&primitive_tags::KV].clone();
+
+        // Later checkpoints reuse the stored dictionaries
+        db.checkpoint().unwrap();
+        let second = ManifestManager::load(manifest_path).unwrap();
+        assert_eq!(second.manifest().dictionaries[&primitive_tags::KV], kv_dict);
+    }
+
+    #[test]
+    fn test_checkpoint_then_compact() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let db = Database::open(&db_path).unwrap();
+
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let key = Key::new_kv(ns, "compact_test");
+
+        // Write data
+        db.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(42))?;
+            Ok(())
+        })
+        .unwrap();
+
+        // Checkpoint first
+        assert!(db.checkpoint().is_ok());
+
+        // Now compact should succeed
+        assert!(db.compact().is_ok());
+    }
+
+    #[test]
+    fn test_open_read_only_while_locked_by_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(create_test_namespace(branch_id), "k");
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| {
+                txn.put(key.clone(), Value::Int(7))?;
+                Ok(())
+            })
+            .unwrap();
+        }
+
+        // Stand in for a writer in another process
+        let lock_file = std::fs::File::open(db_path.join(".lock")).unwrap();
+        fs2::FileExt::lock_exclusive(&lock_file).unwrap();
+        assert!(Database::open(&db_path).is_err());
+
+        let reader = Database::open_read_only(&db_path).unwrap();
+        assert!(reader.is_read_only());
+        assert_eq!(
+            reader.storage().get(&key).unwrap().unwrap().value,
+            Value::Int(7)
+        );
+
+        let write = reader.transaction(branch_id, |txn| {
+            txn.put(key.clone(), Value::Int(8))?;
+            Ok(())
+        });
+        assert!(write.is_err());
+        assert!(reader.checkpoint().is_err());
+
+        // Readers don't share the writer's registry slot
+        let second = Database::open_read_only(&db_path).unwrap();
+        assert!(!Arc::ptr_eq(&reader, &second));
+    }
+
+    #[test]
+    fn test_read_only_refresh_follows_writer() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let ns = create_test_namespace(branch_id);
+        let first = Key::new_kv(ns.clone(), "first");
+        let second = Key::new_kv(ns, "second");
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| txn.put(first.clone(), Value::Int(1)))
+                .unwrap();
+        }
+
+        let reader = Database::open_read_only(&db_path).unwrap();
+        assert_eq!(reader.refresh().unwrap(), 0);
+
+        let writer = Database::open(&db_path).unwrap();
+        assert_eq!(writer.refresh().unwrap(), 0);
+        writer
+            .transaction(branch_id, |txn| {
+                txn.put(second.clone(), Value::Int(2))?;
+                txn.delete(first.clone())
+            })
+            .unwrap();
+        writer.flush().unwrap();
+
+        assert!(reader.storage().get(&second).unwrap().is_none());
+        assert_eq!(reader.refresh().unwrap(), 1);
+        assert_eq!(
+            reader.storage().get(&second).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+        assert!(reader.storage().get(&first).unwrap().is_none());
+        assert_eq!(reader.refresh().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_lock_file_records_owner() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let lock_path = db_path.join(".lock");
+        {
+            let _db = Database::open(&db_path).unwrap();
+            let record = std::fs::read_to_string(&lock_path).unwrap();
+            assert!(record.contains(&format!("pid={}", std::process::id())));
+            assert!(record.contains("host="));
+        }
+        // A clean close leaves an empty lock file behind
+        assert_eq!(std::fs::read_to_string(&lock_path).unwrap(), "");
+    }
+
+    #[test]
+    fn test_open_while_locked_reports_already_open() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        let lock_path = db_path.join(".lock");
+        std::fs::write(&lock_path, "pid=4242\nhost=elsewhere\nopened_at=1\n").unwrap();
+
+        // Stand in for a writer in another process
+        let lock_file = std::fs::File::open(&lock_path).unwrap();
+        fs2::FileExt::lock_exclusive(&lock_file).unwrap();
+
+        match Database::open(&db_path) {
+            Err(StrataError::AlreadyOpen { holder, .. }) => {
+                assert_eq!(holder, "pid 4242 on host 'elsewhere'");
+            }
+            other => panic!("expected AlreadyOpen, got {:?}", other.map(|_| ())),
+        }
+        // The holder's record is left untouched
+        assert!(std::fs::read_to_string(&lock_path)
+            .unwrap()
+            .contains("pid=4242"));
+    }
+
+    #[test]
+    fn test_open_takes_over_stale_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        let lock_path = db_path.join(".lock");
+        // Record left by a writer that crashed: the kernel released its lock
+        std::fs::write(&lock_path, "pid=4242\nhost=elsewhere\nopened_at=1\n").unwrap();
+
+        let _db = Database::open(&db_path).unwrap();
+        let record = std::fs::read_to_string(&lock_path).unwrap();
+        assert!(record.contains(&format!("pid={}", std::process::id())));
+        assert!(!record.contains("elsewhere"));
+    }
+
+    #[test]
+    fn test_force_unlock_breaks_lock_of_dead_owner() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        std::fs::create_dir_all(&db_path).unwrap();
+        let lock_path = db_path.join(".lock");
+        std::fs::write(&lock_path, "pid=4242\nhost=crashed-host\nopened_at=1\n").unwrap();
+
+        // A lock still held although its owner is gone (e.g. kept by NFS)
+        let lock_file = std::fs::File::open(&lock_path).unwrap();
+        fs2::FileExt::lock_exclusive(&lock_file).unwrap();
+        assert!(Database::open(&db_path).is_err());
+
+        let owner = Database::force_unlock(&db_path).unwrap().unwrap();
+        assert_eq!(owner.pid, 4242);
+        assert_eq!(owner.host, "crashed-host");
+
+        let db = Database::open(&db_path).unwrap();
+        assert!(db.is_open());
+    }
+
+    #[test]
+    fn test_force_unlock_refuses_live_owner() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        assert_eq!(Database::force_unlock(&db_path).unwrap(), None);
+
+        let db = Database::open(&db_path).unwrap();
+        // This process is the owner and still running
+        if Path::new("/proc/self").exists() {
+            assert!(matches!(
+                Database::force_unlock(&db_path),
+                Err(StrataError::AlreadyOpen { .. })
+            ));
+        }
+        drop(db);
+
+        // Once released there is nothing to break
+        assert_eq!(Database::force_unlock(&db_path).unwrap(), None);
+        Database::open(&db_path).unwrap();
+    }
+
+    #[test]
+    fn test_open_read_only_creates_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing");
+        assert!(Database::open_read_only(&missing).is_err());
+        assert!(!missing.exists());
+
+        let empty = temp_dir.path().join("empty");
+        std::fs::create_dir(&empty).unwrap();
+        let db = Database::open_read_only(&empty).unwrap();
+        drop(db);
+        assert_eq!(std::fs::read_dir(&empty).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_memory_budget_bounds_cache_database() {
+        use crate::primitives::KVStore;
+        use strata_storage::EvictionPolicy;
+
+        let temp_dir = TempDir::new().unwrap();
+        let disk = Database::open(temp_dir.path().join("db")).unwrap();
+        assert!(disk.set_memory_budget(MemoryBudget::new(1 << 20)).is_err());
+        disk.set_memory_budget(MemoryBudget::default()).unwrap();
+
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let branch_id = BranchId::new();
+        let blob = || Value::Bytes(vec![0; 4096]);
+        db.set_memory_budget(MemoryBudget::new(64 * 1024)).unwrap();
+        for i in 0..64 {
+            kv.put(&branch_id, "default", &format!("k{}", i), blob())
+                .unwrap();
+        }
+        let stats = db.memory_budget_stats();
+        assert!(stats.evicted_keys > 0);
+        assert!(db.memory_usage() <= 64 * 1024);
+        assert!(kv.get(&branch_id, "default", "k0").unwrap().is_none());
+        assert!(kv.get(&branch_id, "default", "k63").unwrap().is_some());
+
+        db.set_memory_budget(MemoryBudget::new(64 * 1024).policy(EvictionPolicy::RejectWrites))
+            .unwrap();
+        let mut rejected = None;
+        for i in 0..64 {
+            if let Err(e) = kv.put(&branch_id, "default", &format!("r{}", i), blob()) {
+                rejected = Some(e);
+                break;
+            }
+        }
+        assert!(matches!(
+            rejected,
+            Some(StrataError::CapacityExceeded { .. })
+        ));
+        assert_eq!(db.memory_budget_stats().rejected_writes, 1);
+        // Deleting is still allowed
+        kv.delete(&branch_id, "default", "k63").unwrap();
+    }
+
+    #[test]
+    fn test_memory_budget_keeps_event_log_intact() {
+        use crate::primitives::{EventLog, KVStore};
+
+        let db = Database::cache().unwrap();
+        let kv = KVStore::new(db.clone());
+        let events = EventLog::new(db.clone());
+        let branch_id = BranchId::new();
+        let payload = |i: i64| Value::Object([("n".to_string(), Value::Int(i))].into());
+        for i in 0..3 {
+            events
+                .append(&branch_id, "default", "audit", payload(i))
+                .unwrap();
+        }
+
+        db.set_memory_budget(MemoryBudget::new(64 * 1024)).unwrap();
+        for i in 0..2000 {
+            kv.put(&branch_id, "default", &format!("k{}", i), Value::Int(i))
+                .unwrap();
+        }
+        assert!(db.memory_budget_stats().evicted_keys > 0);
+
+        // The log's metadata and events survive, so appends continue the chain
+        assert_eq!(events.len(&branch_id, "default").unwrap(), 3);
+        let next = events
+            .append(&branch_id, "default", "audit", payload(3))
+            .unwrap();
+        assert_eq!(next, strata_core::Version::Sequence(3));
+        let event = |seq| events.get(&branch_id, "default", seq).unwrap().unwrap();
+        assert_eq!(event(3).value.prev_hash, event(2).value.hash);
+    }
+
+    #[test]
+    fn test_cancelled_recovery_fails_open_and_keeps_data() {
+        use strata_concurrency::CancellationToken;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(Namespace::for_branch(branch_id), "k");
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.transaction(branch_id, |txn| txn.put(key.clone(), Value::Int(1)))
+                .unwrap();
+            db.flush().unwrap();
+        }
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let observer = RecoveryObserver {
+            on_progress: None,
+            cancel: Some(token),
+        };
+        assert!(matches!(
+            Database::open_with_recovery(&db_path, None, observer.clone()),
+            Err(StrataError::Cancelled { .. })
+        ));
+        assert!(matches!(
+            Database::open_read_only_with_recovery(&db_path, observer),
+            Err(StrataError::Cancelled { .. })
+        ));
+
+        // The lock was released and the WAL left intact
+        let reports = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&reports);
+        let observer = RecoveryObserver {
+            on_progress: Some(Arc::new(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            })),
+            cancel: None,
+        };
+        let db = Database::open_with_recovery(&db_path, None, observer).unwrap();
+        assert_eq!(
+            db.storage().get(&key).unwrap().unwrap().value,
+            Value::Int(1)
+        );
+        assert!(reports.load(Ordering::Relaxed) > 0);
     }
 }
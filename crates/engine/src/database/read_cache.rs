@@ -0,0 +1,247 @@
+//! LRU cache for point reads of hot keys
+//!
+//! Repeated `KVStore::get_versioned` and `StateCell::get_versioned` calls
+//! for the same key are served from here instead of creating a snapshot and
+//! decoding the stored value. Entries are keyed by storage key, which
+//! carries the branch, and hold the value together with its version; a miss
+//! is cached as `None`.
+//!
+//! ## Invalidation
+//!
+//! A commit evicts every key it wrote once its version is visible, so a read
+//! that starts after the commit returns loads from storage and sees the write.
+//!
+//! A read racing a commit may still load the old value and try to cache it
+//! after the commit's eviction. Every eviction bumps a generation counter;
+//! readers note the generation before loading and the insert is dropped if
+//! it changed. The cache therefore only ever holds values that were the
+//! latest committed version when inserted and have not been written since.
+//!
+//! Ordering is a logical clock, as in the executor's database cache, with a
+//! tick index so the least recently used entry is found without a scan.
+
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use strata_core::types::Key;
+use strata_core::VersionedValue;
+
+/// Hit and size counters of a database's read cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadCacheStats {
+    /// Maximum number of cached keys (0 when the cache is disabled)
+    pub capacity: usize,
+    /// Keys currently cached
+    pub entries: usize,
+    /// Reads served from the cache
+    pub hits: u64,
+    /// Reads that went to storage
+    pub misses: u64,
+}
+
+/// Point-read cache shared by all readers of a database
+pub(crate) struct ReadCache {
+    capacity: AtomicUsize,
+    /// Bumped, under the entries lock, by every eviction
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    /// Cached value and the tick of its last use
+    entries: HashMap<Key, (u64, Option<VersionedValue>)>,
+    /// Keys by last-use tick, oldest first
+    order: BTreeMap<u64, Key>,
+    /// Logical clock for LRU ordering
+    clock: u64,
+}
+
+impl CacheInner {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if let Some((tick, _)) = self.entries.remove(key) {
+            self.order.remove(&tick);
+        }
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            match self.order.pop_first() {
+                Some((_, key)) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl ReadCache {
+    /// A cache holding at most `capacity` keys; 0 disables it
+    pub(crate) fn new(capacity: usize) -> Self {
+        ReadCache {
+            capacity: AtomicUsize::new(capacity),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity.load(Ordering::Relaxed) > 0
+    }
+
+    /// Resize the cache, evicting the least recently used keys that no
+    /// longer fit
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.inner.lock();
+        // A read that started before a disabled period may not insert after it
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.capacity.store(capacity, Ordering::Relaxed);
+        inner.evict_to(capacity);
+    }
+
+    /// The cached result of reading `key`, marking it recently used
+    ///
+    /// `None` is a miss; `Some(None)` is a cached absent key.
+    pub(crate) fn get(&self, key: &Key) -> Option<Option<VersionedValue>> {
+        let mut inner = self.inner.lock();
+        let tick = inner.tick();
+        let Some((last, value)) = inner.entries.get_mut(key) else {
+            drop(inner);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let previous = std::mem::replace(last, tick);
+        let value = value.clone();
+        inner.order.remove(&previous);
+        inner.order.insert(tick, key.clone());
+        drop(inner);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(value)
+    }
+
+    /// Generation to pass to [`insert`](Self::insert), read before loading
+    /// the value from storage
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Cache the value of `key` read at `generation`
+    ///
+    /// Dropped if any key was evicted since, as the value may be stale.
+    pub(crate) fn insert(&self, key: Key, value: Option<VersionedValue>, generation: u64) {
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let mut inner = self.inner.lock();
+        if capacity == 0 || self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let tick = inner.tick();
+        if let Some((previous, _)) = inner.entries.insert(key.clone(), (tick, value)) {
+            inner.order.remove(&previous);
+        }
+        inner.order.insert(tick, key);
+        inner.evict_to(capacity);
+    }
+
+    /// Evict keys written by a commit
+    pub(crate) fn invalidate<'k>(&self, keys: impl IntoIterator<Item = &'k Key>) {
+        let mut inner = self.inner.lock();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if inner.entries.is_empty() {
+            return;
+        }
+        for key in keys {
+            inner.remove(key);
+        }
+    }
+
+    /// Evict everything, for storage changes made outside a commit
+    pub(crate) fn clear(&self) {
+        let mut inner = self.inner.lock();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        inner.entries.clear();
+        inner.order.clear();
+    }
+
+    pub(crate) fn stats(&self) -> ReadCacheStats {
+        let entries = self.inner.lock().entries.len();
+        ReadCacheStats {
+            capacity: self.capacity.load(Ordering::Relaxed),
+            entries,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strata_core::types::{BranchId, Namespace};
+    use strata_core::value::Value;
+    use strata_core::{Timestamp, Version, Versioned};
+
+    fn key(name: &str) -> Key {
+        Key::new_kv(Namespace::for_branch(BranchId::new()), name)
+    }
+
+    fn value(n: i64) -> Option<VersionedValue> {
+        Some(Versioned::with_timestamp(
+            Value::Int(n),
+            Version::Txn(n as u64),
+            Timestamp::from_micros(0),
+        ))
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ReadCache::new(2);
+        let (a, b, c) = (key("a"), key("b"), key("c"));
+        cache.insert(a.clone(), value(1), cache.generation());
+        cache.insert(b.clone(), value(2), cache.generation());
+        assert_eq!(cache.get(&a), Some(value(1)));
+
+        cache.insert(c.clone(), None, cache.generation());
+        assert_eq!(cache.get(&b), None);
+        assert_eq!(cache.get(&a), Some(value(1)));
+        assert_eq!(cache.get(&c), Some(None));
+
+        cache.set_capacity(1);
+        assert_eq!(cache.get(&a), None);
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_insert_after_invalidation_is_dropped() {
+        let cache = ReadCache::new(8);
+        let (a, b) = (key("a"), key("b"));
+        cache.insert(a.clone(), value(1), cache.generation());
+
+        // A read of "b" begins, then a commit writes "a"
+        let generation = cache.generation();
+        cache.invalidate([&a]);
+        cache.insert(b.clone(), value(2), generation);
+
+        assert_eq!(cache.get(&a), None);
+        assert_eq!(cache.get(&b), None);
+        cache.insert(b.clone(), value(2), cache.generation());
+        assert_eq!(cache.get(&b), Some(value(2)));
+    }
+
+    #[test]
+    fn test_disabled_cache_holds_nothing() {
+        let cache = ReadCache::new(0);
+        assert!(!cache.is_enabled());
+        cache.insert(key("a"), value(1), cache.generation());
+        assert_eq!(cache.stats().entries, 0);
+    }
+}
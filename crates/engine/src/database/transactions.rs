@@ -2,6 +2,7 @@
 //!
 //! Contains RetryConfig for transaction retry behavior and related utilities.
 
+use rand::Rng;
 use std::time::Duration;
 
 // ============================================================================
@@ -19,8 +20,9 @@ use std::time::Duration;
 ///     max_retries: 5,
 ///     base_delay_ms: 10,
 ///     max_delay_ms: 200,
+///     jitter: true,
 /// };
-/// db.transaction_with_retry(branch_id, config, |txn| { ... })?;
+/// db.with_retry(branch_id, config, |txn| { ... })?;
 /// ```
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -30,6 +32,9 @@ pub struct RetryConfig {
     pub base_delay_ms: u64,
     /// Maximum delay between retries in milliseconds
     pub max_delay_ms: u64,
+    /// Sleep a random time between half and all of each delay, so writers
+    /// that conflicted together do not retry in lockstep
+    pub jitter: bool,
 }
 
 impl Default for RetryConfig {
@@ -38,6 +43,7 @@ impl Default for RetryConfig {
             max_retries: 3,
             base_delay_ms: 10,
             max_delay_ms: 100,
+            jitter: true,
         }
     }
 }
@@ -74,12 +80,24 @@ impl RetryConfig {
         self
     }
 
+    /// Enable or disable random jitter on each delay
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
     /// Calculate delay for a given attempt (exponential backoff)
     pub(crate) fn calculate_delay(&self, attempt: usize) -> Duration {
         // Cap the shift to prevent overflow (1 << 63 is the max for u64)
         let shift = attempt.min(63);
         let multiplier = 1u64 << shift;
-        let delay_ms = self.base_delay_ms.saturating_mul(multiplier);
-        Duration::from_millis(delay_ms.min(self.max_delay_ms))
+        let delay_ms = self
+            .base_delay_ms
+            .saturating_mul(multiplier)
+            .min(self.max_delay_ms);
+        if self.jitter && delay_ms > 1 {
+            return Duration::from_millis(rand::thread_rng().gen_range(delay_ms / 2..=delay_ms));
+        }
+        Duration::from_millis(delay_ms)
     }
 }
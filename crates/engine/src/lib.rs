@@ -5,7 +5,7 @@
 //! - Branch lifecycle: begin_branch, end_branch, fork_branch (Epic 5)
 //! - Transaction coordination
 //! - Recovery integration
-//! - Background tasks (snapshots, TTL cleanup)
+//! - Background tasks (WAL flush, compaction), optionally on a shared `WorkerPool`
 //!
 //! The engine is the only component that knows about:
 //! - Branch management
@@ -23,6 +23,7 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod background;
 pub mod coordinator;
 pub mod database;
 pub mod instrumentation;
@@ -30,18 +31,32 @@ pub mod recovery;
 pub mod transaction;
 pub mod transaction_ops; // TransactionOps Trait Definition
 
+pub use background::{PoolJob, WorkerPool, WorkerPoolBuilder};
 pub use coordinator::{TransactionCoordinator, TransactionMetrics};
-pub use database::{Database, RetryConfig, StrataConfig};
+pub use database::{
+    BackupFile, BackupManifest, CheckpointPolicy, Database, DropBehavior, EmbedProvider, LockOwner,
+    ReadCacheStats, RerankProvider, RetryConfig, StrataConfig,
+};
 pub use instrumentation::PerfTrace;
 pub use recovery::{
     diff_views, recover_all_participants, register_recovery_participant, BranchDiff, BranchError,
     DiffEntry, ReadOnlyView, RecoveryFn, RecoveryParticipant, ReplayBranchIndex, ReplayError,
 };
-pub use strata_durability::wal::DurabilityMode;
-pub use strata_durability::WalCounters;
+pub use strata_durability::retention::RetentionPolicy;
+pub use strata_durability::wal::{DurabilityMode, LossWindow};
+pub use strata_durability::{CompactInfo, WalCounters};
 // Note: Use strata_core::PrimitiveType for DiffEntry.primitive field
-pub use strata_concurrency::TransactionContext;
-pub use transaction::{Transaction, TransactionPool, MAX_POOL_SIZE};
+pub use strata_concurrency::{
+    CancellationToken, KeyLockTable, LockMode, RecoveryObserver, RecoveryProgress,
+    RecoveryProgressFn, TransactionContext, DEFAULT_LOCK_TIMEOUT,
+};
+pub use strata_storage::{
+    EvictionPolicy, MaterializedSnapshot, MemoryBudget, MemoryBudgetStats, ShardUsage,
+};
+pub use transaction::{
+    Transaction, TransactionPool, TransactionPoolConfig, TransactionPoolStats,
+    DEFAULT_SHRINK_THRESHOLD, MAX_POOL_SIZE,
+};
 pub use transaction_ops::TransactionOps;
 
 pub mod branch_ops;
@@ -50,45 +65,42 @@ pub mod primitives;
 pub mod search;
 
 // Re-export search types at crate root for convenience
-pub use search::{SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats};
+pub use search::{
+    SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats, TextHit,
+    TextSearch,
+};
 
 // Re-export submodules for `strata_engine::vector::*` and `strata_engine::extensions::*` access
 pub use primitives::extensions;
+#[cfg(feature = "vectors")]
 pub use primitives::vector;
 
 // Re-export primitive types at crate root for convenience
 pub use primitives::{
     build_search_response,
     build_search_response_with_index,
-    // Recovery
-    register_vector_recovery,
-    validate_collection_name,
-    validate_vector_key,
+    // Custom primitives
+    custom_primitive,
+    register_custom_primitive,
     BM25LiteScorer,
     // Handles
+    BranchAncestor,
+    BranchFilter,
     BranchHandle,
     BranchIndex,
     BranchMetadata,
     BranchStatus,
-    BruteForceBackend,
-    CollectionId,
-    CollectionInfo,
-    CollectionRecord,
-    DistanceMetric,
+    CustomPrimitive,
+    CustomStore,
     Event,
     EventHandle,
     EventLog,
     EventLogExt,
-    FilterCondition,
-    FilterOp,
-    HnswBackend,
-    HnswConfig,
-    IndexBackendFactory,
+    IndexVerification,
     // Index
     InvertedIndex,
     JsonDoc,
     JsonHandle,
-    JsonScalar,
     JsonStore,
     JsonStoreExt,
     // Primitives
@@ -96,9 +108,12 @@ pub use primitives::{
     // Extension traits
     KVStoreExt,
     KvHandle,
-    MetadataFilter,
+    KvHistory,
+    ListStore,
+    PendingEvent,
     PostingEntry,
     PostingList,
+    SchemaViolation,
     Scorer,
     ScorerContext,
     SearchCandidate,
@@ -111,31 +126,31 @@ pub use primitives::{
     StateCell,
     StateCellExt,
     StateHandle,
-    StorageDtype,
-    VectorBackendState,
-    // Vector types
-    VectorConfig,
-    VectorConfigSerde,
-    VectorEntry,
-    VectorError,
-    VectorHeap,
-    VectorId,
-    VectorIndexBackend,
-    VectorMatch,
-    VectorMatchWithSource,
-    VectorRecord,
-    VectorResult,
-    VectorStore,
     VectorStoreExt,
+    CUSTOM_PRIMITIVE_IDS,
+};
+
+// Vector primitive, compiled with the `vectors` feature
+#[cfg(feature = "vectors")]
+pub use primitives::{
+    register_vector_recovery, validate_collection_name, validate_vector_key, BruteForceBackend,
+    CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, FilterCondition, FilterOp,
+    HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, NestedFilter,
+    StorageDtype, VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError,
+    VectorHeap, VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord,
+    VectorResult, VectorStore,
 };
 
 // Re-export bundle types at crate root
-pub use bundle::{BundleInfo, ExportInfo, ImportInfo};
+pub use bundle::{
+    BundleInfo, DatabaseExportInfo, DatabaseImportInfo, ExportInfo, ImportConflict, ImportInfo,
+};
+pub use strata_durability::branch_bundle::EnvironmentInfo;
 
 // Re-export branch_ops types at crate root
 pub use branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, EntryChange, ForkInfo,
+    MergeInfo, MergeStrategy, SchemaChange, SchemaChangeKind, SpaceDiff,
 };
 
 #[cfg(feature = "perf-trace")]
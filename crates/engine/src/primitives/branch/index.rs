@@ -10,6 +10,12 @@
 //! - `exists(name)` - Check if branch exists
 //! - `list_branches()` - List all branch names
 //! - `delete_branch(name)` - Delete branch and ALL its data (cascading)
+//! - `set_retention(name, policy)` - Limit the version history compaction keeps
+//! - `add_tag(name, tag)` / `set_metadata(name, key, value)` - Label a branch
+//! - `query(filter)` - List the branches matching a `BranchFilter`
+//! - `close_branch(name)` / `fail_branch(name, error)` - End a branch, running
+//!   the hooks registered with `on_close()` / `on_fail()`
+//! - `verify_indexes()` - Remove search and vector index entries of deleted branches
 //!
 //! ## Key Design
 //!
@@ -18,14 +24,22 @@
 //! - BranchIndex uses a global namespace (not branch-scoped) since it manages branches themselves.
 
 use crate::database::Database;
+#[cfg(feature = "vectors")]
+use crate::primitives::vector::VectorBackendState;
+use crate::search::{InvertedIndex, TextIndex};
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use strata_core::contract::{Timestamp, Version, Versioned};
+use strata_core::key::ensure_not_reserved;
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
 use strata_core::StrataError;
 use strata_core::StrataResult;
-use tracing::info;
+use strata_durability::branch_bundle::EnvironmentInfo;
+use strata_durability::retention::RetentionPolicy;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Namespace UUID for generating deterministic branch IDs from names.
@@ -69,13 +83,17 @@ fn global_namespace() -> Namespace {
 
 /// Branch lifecycle status.
 ///
-/// All branches are Active. Additional statuses will be added when
-/// lifecycle transitions are implemented.
+/// Branches are created Active and end once, by `close_branch()` or
+/// `fail_branch()`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum BranchStatus {
     /// Branch is currently active
     #[default]
     Active,
+    /// Branch was closed normally
+    Completed,
+    /// Branch ended with an error
+    Failed,
 }
 
 impl BranchStatus {
@@ -83,8 +101,29 @@ impl BranchStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
             BranchStatus::Active => "Active",
+            BranchStatus::Completed => "Completed",
+            BranchStatus::Failed => "Failed",
         }
     }
+
+    /// Whether the branch has ended
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, BranchStatus::Active)
+    }
+}
+
+// ========== BranchAncestor Struct ==========
+
+/// A branch in the ancestry of a fork
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchAncestor {
+    /// Ancestor branch name
+    pub name: String,
+    /// Version of the ancestor that the branch below it was forked at
+    pub fork_version: u64,
+    /// Whether the branch below still reads the ancestor's data, rather
+    /// than holding its own copy
+    pub shared: bool,
 }
 
 // ========== BranchMetadata Struct ==========
@@ -96,7 +135,7 @@ pub struct BranchMetadata {
     pub name: String,
     /// Unique branch identifier (UUID) for internal use and namespacing
     pub branch_id: String,
-    /// Parent branch name if forked (post-MVP)
+    /// Parent branch name if forked
     pub parent_branch: Option<String>,
 
     /// Current status
@@ -105,13 +144,32 @@ pub struct BranchMetadata {
     pub created_at: u64,
     /// Last update timestamp (microseconds since epoch)
     pub updated_at: u64,
-    /// Completion timestamp (post-MVP)
+    /// Timestamp the branch was closed or failed at
     pub completed_at: Option<u64>,
-    /// Error message if failed (post-MVP)
+    /// Error message if failed
     pub error: Option<String>,
     /// Internal version counter
     #[serde(default = "default_version")]
     pub version: u64,
+    /// Environment the branch was created in, if the database captures it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvironmentInfo>,
+    /// History retention for the branch's data, enforced by compaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<RetentionPolicy>,
+    /// Storage version of the parent the branch was forked at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_version: Option<u64>,
+    /// Whether a fork holds its own copy of its parent's data, rather than
+    /// reading through to it (set when the parent is deleted)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub detached: bool,
+    /// Tags, sorted and without duplicates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// User metadata
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, Value>,
 }
 
 fn default_version() -> u64 {
@@ -133,6 +191,12 @@ impl BranchMetadata {
             completed_at: None,
             error: None,
             version: 1,
+            environment: None,
+            retention: None,
+            fork_version: None,
+            detached: false,
+            tags: Vec::new(),
+            metadata: BTreeMap::new(),
         }
     }
 
@@ -156,6 +220,71 @@ impl BranchMetadata {
     }
 }
 
+// ========== BranchFilter Struct ==========
+
+/// Conditions a branch must meet to be returned by `BranchIndex::query()`
+///
+/// Unset conditions match every branch; set ones must all hold.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchFilter {
+    /// Status the branch must have
+    pub status: Option<BranchStatus>,
+    /// The branch must have been created strictly after this timestamp
+    /// (microseconds since epoch)
+    pub created_after: Option<u64>,
+    /// Tag the branch must carry
+    pub tag: Option<String>,
+    /// Metadata entries the branch must have, with equal values
+    pub metadata: Vec<(String, Value)>,
+}
+
+impl BranchFilter {
+    /// Whether `meta` meets every condition of the filter
+    pub fn matches(&self, meta: &BranchMetadata) -> bool {
+        self.status.map_or(true, |status| meta.status == status)
+            && self
+                .created_after
+                .map_or(true, |after| meta.created_at > after)
+            && self
+                .tag
+                .as_ref()
+                .map_or(true, |tag| meta.tags.binary_search(tag).is_ok())
+            && self
+                .metadata
+                .iter()
+                .all(|(key, value)| meta.metadata.get(key) == Some(value))
+    }
+}
+
+// ========== IndexVerification Struct ==========
+
+/// Index entries `BranchIndex::verify_indexes()` found for branches that
+/// no longer exist, all of which it removed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexVerification {
+    /// Deleted branches that still had index entries
+    pub stale_branches: usize,
+    /// Search index documents removed
+    pub documents_removed: usize,
+    /// Vector collection backends removed
+    pub vector_backends_removed: usize,
+}
+
+// ========== Lifecycle Hooks ==========
+
+/// Hook registered by `BranchIndex::on_close` or `BranchIndex::on_fail`
+type BranchHook = Arc<dyn Fn(&BranchMetadata) -> StrataResult<()> + Send + Sync>;
+
+/// Hooks run when a branch ends
+///
+/// Stored as a database extension so every `BranchIndex` over the same
+/// database sees the same hooks.
+#[derive(Default)]
+struct BranchHooks {
+    on_close: RwLock<Vec<BranchHook>>,
+    on_fail: RwLock<Vec<BranchHook>>,
+}
+
 // ========== Serialization Helpers ==========
 
 /// Serialize a struct to Value::String for storage
@@ -227,11 +356,30 @@ impl BranchIndex {
 
     /// Create a new branch
     ///
-    /// Creates a branch with Active status.
+    /// Creates a branch with Active status. The branch records the
+    /// database's environment when environment capture is enabled.
     ///
     /// ## Errors
     /// - `InvalidInput` if branch already exists
     pub fn create_branch(&self, branch_id: &str) -> StrataResult<Versioned<BranchMetadata>> {
+        let environment = if self.db.capture_environment_enabled() {
+            Some(self.db.environment())
+        } else {
+            None
+        };
+        self.create_branch_with_environment(branch_id, environment)
+    }
+
+    /// Create a new branch recording `environment` as the one it was
+    /// created in
+    ///
+    /// Used by imports to keep the environment of the exporting database.
+    pub(crate) fn create_branch_with_environment(
+        &self,
+        branch_id: &str,
+        environment: Option<EnvironmentInfo>,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        ensure_not_reserved(branch_id)?;
         self.db.transaction(global_branch_id(), |txn| {
             let key = self.key_for(branch_id);
 
@@ -243,7 +391,8 @@ impl BranchIndex {
                 )));
             }
 
-            let branch_meta = BranchMetadata::new(branch_id);
+            let mut branch_meta = BranchMetadata::new(branch_id);
+            branch_meta.environment = environment.clone();
             txn.put(key, to_stored_value(&branch_meta)?)?;
 
             info!(target: "strata::branch", %branch_id, "Branch created");
@@ -251,6 +400,63 @@ impl BranchIndex {
         })
     }
 
+    /// Create a branch that reads `parent`'s data as of `fork_version`
+    ///
+    /// Only records the fork in the branch metadata; `fork_branch()` links
+    /// the branches in storage.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if branch already exists
+    pub(crate) fn create_fork(
+        &self,
+        branch_id: &str,
+        parent: &str,
+        fork_version: u64,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        ensure_not_reserved(branch_id)?;
+        let environment = if self.db.capture_environment_enabled() {
+            Some(self.db.environment())
+        } else {
+            None
+        };
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+            if txn.get(&key)?.is_some() {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' already exists",
+                    branch_id
+                )));
+            }
+
+            let mut branch_meta = BranchMetadata::new(branch_id);
+            branch_meta.environment = environment.clone();
+            branch_meta.parent_branch = Some(parent.to_string());
+            branch_meta.fork_version = Some(fork_version);
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+
+            info!(target: "strata::branch", %branch_id, parent, fork_version, "Branch created as fork");
+            Ok(branch_meta.into_versioned())
+        })
+    }
+
+    /// Mark a fork as holding its own copy of its parent's data
+    ///
+    /// Keeps `parent_branch` and `fork_version` as the branch's lineage.
+    fn mark_detached(&self, branch_id: &str) -> StrataResult<()> {
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+            let Some(value) = txn.get(&key)? else {
+                return Ok(());
+            };
+            let mut branch_meta: BranchMetadata =
+                from_stored_value(&value).map_err(|e| StrataError::serialization(e.to_string()))?;
+            branch_meta.detached = true;
+            branch_meta.updated_at = BranchMetadata::now();
+            branch_meta.version += 1;
+            txn.put(key, to_stored_value(&branch_meta)?)
+        })
+    }
+
     /// Get branch metadata
     ///
     /// ## Returns
@@ -278,7 +484,7 @@ impl BranchIndex {
         })
     }
 
-    /// List all branch IDs
+    /// List all branch IDs, in lexicographic order
     pub fn list_branches(&self) -> StrataResult<Vec<String>> {
         self.db.transaction(global_branch_id(), |txn| {
             let prefix = Key::new_branch_with_id(global_namespace(), "");
@@ -299,11 +505,352 @@ impl BranchIndex {
         })
     }
 
+    /// Set the history retention of a branch
+    ///
+    /// A `Composite` policy sets a policy per primitive. `Database::compact()`
+    /// prunes the versions the policy no longer retains; the latest version
+    /// of a key is always kept, and a KV history policy set on a key prefix
+    /// takes precedence for the keys it covers. `KeepAll` removes the policy.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist, or the policy (or one of
+    ///   its overrides) is `KeepLast(0)` or `KeepFor` a zero duration
+    pub fn set_retention(
+        &self,
+        branch_id: &str,
+        policy: RetentionPolicy,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        validate_retention(&policy)?;
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+            let Some(value) = txn.get(&key)? else {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' not found",
+                    branch_id
+                )));
+            };
+            let mut branch_meta: BranchMetadata = from_stored_value(&value)
+                .map_err(|e| StrataError::serialization(e.to_string()))?;
+            branch_meta.retention = (policy != RetentionPolicy::KeepAll).then(|| policy.clone());
+            branch_meta.updated_at = BranchMetadata::now();
+            branch_meta.version += 1;
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+
+            info!(target: "strata::branch", %branch_id, policy = %policy.summary(), "Branch retention set");
+            Ok(branch_meta.into_versioned())
+        })
+    }
+
+    /// Add a tag to a branch
+    ///
+    /// ## Returns
+    /// - `true` if the tag was added
+    /// - `false` if the branch already had it
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist or the tag is empty
+    pub fn add_tag(&self, branch_id: &str, tag: &str) -> StrataResult<bool> {
+        if tag.trim().is_empty() {
+            return Err(StrataError::invalid_input("Branch tag must not be empty"));
+        }
+        self.update(branch_id, |meta| {
+            match meta.tags.binary_search_by(|t| t.as_str().cmp(tag)) {
+                Ok(_) => false,
+                Err(at) => {
+                    meta.tags.insert(at, tag.to_string());
+                    true
+                }
+            }
+        })
+    }
+
+    /// Remove a tag from a branch
+    ///
+    /// ## Returns
+    /// - `true` if the tag was removed
+    /// - `false` if the branch didn't have it
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist
+    pub fn remove_tag(&self, branch_id: &str, tag: &str) -> StrataResult<bool> {
+        self.update(branch_id, |meta| {
+            match meta.tags.binary_search_by(|t| t.as_str().cmp(tag)) {
+                Ok(at) => {
+                    meta.tags.remove(at);
+                    true
+                }
+                Err(_) => false,
+            }
+        })
+    }
+
+    /// Set a metadata entry of a branch; `Value::Null` removes it
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist or the key is empty
+    pub fn set_metadata(&self, branch_id: &str, key: &str, value: Value) -> StrataResult<()> {
+        if key.is_empty() {
+            return Err(StrataError::invalid_input(
+                "Branch metadata key must not be empty",
+            ));
+        }
+        self.update(branch_id, |meta| {
+            if value == Value::Null {
+                meta.metadata.remove(key).is_some()
+            } else {
+                meta.metadata
+                    .insert(key.to_string(), value.clone())
+                    .as_ref()
+                    != Some(&value)
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Apply `change` to a branch's metadata, storing the result if it
+    /// reports a change
+    fn update(
+        &self,
+        branch_id: &str,
+        mut change: impl FnMut(&mut BranchMetadata) -> bool,
+    ) -> StrataResult<bool> {
+        self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+            let Some(value) = txn.get(&key)? else {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' not found",
+                    branch_id
+                )));
+            };
+            let mut branch_meta: BranchMetadata =
+                from_stored_value(&value).map_err(|e| StrataError::serialization(e.to_string()))?;
+            if !change(&mut branch_meta) {
+                return Ok(false);
+            }
+            branch_meta.updated_at = BranchMetadata::now();
+            branch_meta.version += 1;
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+            Ok(true)
+        })
+    }
+
+    /// Close a branch, marking it Completed
+    ///
+    /// Runs the `on_close()` hooks once the status change has committed.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist or has already ended
+    pub fn close_branch(&self, branch_id: &str) -> StrataResult<Versioned<BranchMetadata>> {
+        self.end_branch(branch_id, BranchStatus::Completed, None)
+    }
+
+    /// Fail a branch, marking it Failed with `error`
+    ///
+    /// Runs the `on_fail()` hooks once the status change has committed.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist or has already ended
+    pub fn fail_branch(
+        &self,
+        branch_id: &str,
+        error: &str,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        self.end_branch(branch_id, BranchStatus::Failed, Some(error))
+    }
+
+    /// Register a hook run after a branch is closed
+    ///
+    /// Hooks receive the branch's metadata with its final status. They run
+    /// on the closing thread after the status change has committed, in the
+    /// order they were registered; a failing hook is logged and does not
+    /// stop the others or fail the close.
+    pub fn on_close<F>(&self, hook: F) -> StrataResult<()>
+    where
+        F: Fn(&BranchMetadata) -> StrataResult<()> + Send + Sync + 'static,
+    {
+        let hooks = self.db.extension::<BranchHooks>()?;
+        hooks.on_close.write().push(Arc::new(hook));
+        Ok(())
+    }
+
+    /// Register a hook run after a branch fails
+    ///
+    /// Runs like the `on_close()` hooks; the metadata carries the error.
+    pub fn on_fail<F>(&self, hook: F) -> StrataResult<()>
+    where
+        F: Fn(&BranchMetadata) -> StrataResult<()> + Send + Sync + 'static,
+    {
+        let hooks = self.db.extension::<BranchHooks>()?;
+        hooks.on_fail.write().push(Arc::new(hook));
+        Ok(())
+    }
+
+    /// Move an Active branch to the terminal `status`, then run its hooks
+    fn end_branch(
+        &self,
+        branch_id: &str,
+        status: BranchStatus,
+        error: Option<&str>,
+    ) -> StrataResult<Versioned<BranchMetadata>> {
+        let ended = self.db.transaction(global_branch_id(), |txn| {
+            let key = self.key_for(branch_id);
+            let Some(value) = txn.get(&key)? else {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' not found",
+                    branch_id
+                )));
+            };
+            let mut branch_meta: BranchMetadata =
+                from_stored_value(&value).map_err(|e| StrataError::serialization(e.to_string()))?;
+            if branch_meta.status.is_terminal() {
+                return Err(StrataError::invalid_input(format!(
+                    "Branch '{}' has already ended ({})",
+                    branch_id,
+                    branch_meta.status.as_str()
+                )));
+            }
+            let now = BranchMetadata::now();
+            branch_meta.status = status;
+            branch_meta.error = error.map(str::to_string);
+            branch_meta.completed_at = Some(now);
+            branch_meta.updated_at = now;
+            branch_meta.version += 1;
+            txn.put(key, to_stored_value(&branch_meta)?)?;
+            Ok(branch_meta)
+        })?;
+        info!(target: "strata::branch", %branch_id, status = status.as_str(), "Branch ended");
+
+        // Clone the hooks out so they can register hooks or end branches
+        let hooks = self.db.extension::<BranchHooks>()?;
+        let hooks = match status {
+            BranchStatus::Failed => hooks.on_fail.read().clone(),
+            _ => hooks.on_close.read().clone(),
+        };
+        for hook in hooks {
+            if let Err(e) = hook(&ended) {
+                warn!(target: "strata::branch", %branch_id, error = %e, "Branch lifecycle hook failed");
+            }
+        }
+        Ok(ended.into_versioned())
+    }
+
+    /// List the branches matching `filter`, in lexicographic order
+    pub fn query(&self, filter: &BranchFilter) -> StrataResult<Vec<Versioned<BranchMetadata>>> {
+        self.db.transaction(global_branch_id(), |txn| {
+            let prefix = Key::new_branch_with_id(global_namespace(), "");
+            Ok(txn
+                .scan_prefix(&prefix)?
+                .into_iter()
+                .filter_map(|(_, value)| from_stored_value::<BranchMetadata>(&value).ok())
+                .filter(|meta| filter.matches(meta))
+                .map(BranchMetadata::into_versioned)
+                .collect())
+        })
+    }
+
+    /// Count the branches
+    pub fn count(&self) -> StrataResult<u64> {
+        Ok(self.list_branches()?.len() as u64)
+    }
+
+    /// Get the history retention of a branch
+    ///
+    /// ## Returns
+    /// - `Some(policy)` if a policy is set
+    /// - `None` if none is set or the branch doesn't exist
+    pub fn retention(&self, branch_id: &str) -> StrataResult<Option<RetentionPolicy>> {
+        Ok(self
+            .get_branch(branch_id)?
+            .and_then(|meta| meta.value.retention))
+    }
+
+    /// Get the ancestry of a branch, from its parent up to the root
+    ///
+    /// Each entry names an ancestor and the version the branch below it
+    /// was forked at. The walk stops at an ancestor that was deleted; a
+    /// branch created later under a deleted ancestor's name is not one.
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist
+    pub fn ancestry(&self, branch_id: &str) -> StrataResult<Vec<BranchAncestor>> {
+        let mut meta = self
+            .get_branch(branch_id)?
+            .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", branch_id)))?
+            .value;
+        let mut ancestors: Vec<BranchAncestor> = Vec::new();
+        while let (Some(parent), Some(fork_version)) =
+            (meta.parent_branch.clone(), meta.fork_version)
+        {
+            let Some(parent_meta) = self.get_branch(&parent)? else {
+                break;
+            };
+            let parent_meta = parent_meta.value;
+            if parent_meta.created_at > meta.created_at
+                || parent == branch_id
+                || ancestors.iter().any(|a| a.name == parent)
+            {
+                break;
+            }
+            ancestors.push(BranchAncestor {
+                name: parent,
+                fork_version,
+                shared: !meta.detached,
+            });
+            meta = parent_meta;
+        }
+        Ok(ancestors)
+    }
+
+    /// List the branches forked from a branch, in lexicographic order
+    ///
+    /// ## Errors
+    /// - `InvalidInput` if the branch doesn't exist
+    pub fn children(&self, branch_id: &str) -> StrataResult<Vec<String>> {
+        let parent = self
+            .get_branch(branch_id)?
+            .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", branch_id)))?
+            .value;
+        self.db.transaction(global_branch_id(), |txn| {
+            let prefix = Key::new_branch_with_id(global_namespace(), "");
+            Ok(txn
+                .scan_prefix(&prefix)?
+                .into_iter()
+                .filter_map(|(_, value)| from_stored_value::<BranchMetadata>(&value).ok())
+                .filter(|meta| {
+                    meta.parent_branch.as_deref() == Some(branch_id)
+                        && meta.fork_version.is_some()
+                        && meta.created_at >= parent.created_at
+                })
+                .map(|meta| meta.name)
+                .collect())
+        })
+    }
+
+    /// The branches whose data `branch_id` reads, with the newest version
+    /// of each it sees: itself at `u64::MAX`, then every ancestor it still
+    /// shares data with
+    pub(crate) fn shared_lineage(&self, branch_id: &str) -> StrataResult<Vec<(String, u64)>> {
+        let mut lineage = vec![(branch_id.to_string(), u64::MAX)];
+        let mut cap = u64::MAX;
+        for ancestor in self.ancestry(branch_id)? {
+            if !ancestor.shared {
+                break;
+            }
+            cap = cap.min(ancestor.fork_version);
+            lineage.push((ancestor.name, cap));
+        }
+        Ok(lineage)
+    }
+
     /// Delete a branch and ALL its data (cascading delete)
     ///
     /// This deletes:
     /// - The branch metadata
     /// - All branch-scoped data (KV, Events, States, JSON, Vectors)
+    /// - The branch's search index documents and vector index backends
+    ///
+    /// Forks of the branch first get their own copy of the data they
+    /// still share with it.
     ///
     /// USE WITH CAUTION - this is irreversible!
     pub fn delete_branch(&self, branch_id: &str) -> StrataResult<()> {
@@ -313,6 +860,8 @@ impl BranchIndex {
             .ok_or_else(|| StrataError::invalid_input(format!("Branch '{}' not found", branch_id)))?
             .value;
 
+        self.detach_forks(branch_id)?;
+
         // Resolve the executor's deterministic BranchId for this name.
         let executor_branch_id = resolve_branch_name(branch_id);
 
@@ -339,9 +888,119 @@ impl BranchIndex {
 
             info!(target: "strata::branch", %branch_id, "Branch deleted");
             Ok(())
+        })?;
+
+        // The data a deleted fork inherited is tombstoned, so nothing is copied
+        if branch_meta.fork_version.is_some() && !branch_meta.detached {
+            self.db.storage().detach_fork(&executor_branch_id);
+            self.db.clear_read_cache();
+        }
+
+        let mut deleted_ids = vec![executor_branch_id];
+        deleted_ids.extend(metadata_branch_id.filter(|id| *id != executor_branch_id));
+        self.clear_indexes(&deleted_ids)?;
+        Ok(())
+    }
+
+    /// Remove index entries left behind by deleted branches
+    ///
+    /// Databases written before branch deletion cleared the indexes can
+    /// still hold search postings and vector backends of branches that
+    /// are gone. Run it while no branch is being deleted and recreated
+    /// under the same name.
+    pub fn verify_indexes(&self) -> StrataResult<IndexVerification> {
+        // Collect indexed branches first: a branch created after this
+        // point is live below, so its entries are kept
+        let mut indexed = self.db.extension::<InvertedIndex>()?.branch_ids();
+        indexed.extend(self.db.extension::<TextIndex>()?.branch_ids());
+        #[cfg(feature = "vectors")]
+        indexed.extend(self.db.extension::<VectorBackendState>()?.branch_ids());
+
+        let mut live: HashSet<BranchId> = HashSet::from([global_branch_id()]);
+        for meta in self.query(&BranchFilter::default())? {
+            live.insert(resolve_branch_name(&meta.value.name));
+            live.extend(BranchId::from_string(&meta.value.branch_id));
+        }
+
+        let stale: Vec<BranchId> = indexed.difference(&live).copied().collect();
+        let (documents_removed, vector_backends_removed) = self.clear_indexes(&stale)?;
+        if !stale.is_empty() {
+            warn!(
+                target: "strata::branch",
+                stale_branches = stale.len(),
+                documents_removed,
+                vector_backends_removed,
+                "Removed index entries of deleted branches"
+            );
+        }
+        Ok(IndexVerification {
+            stale_branches: stale.len(),
+            documents_removed,
+            vector_backends_removed,
         })
     }
 
+    /// Drop the search documents and vector backends of `branch_ids`,
+    /// returning how many of each were dropped
+    fn clear_indexes(&self, branch_ids: &[BranchId]) -> StrataResult<(usize, usize)> {
+        let inverted = self.db.extension::<InvertedIndex>()?;
+        let text = self.db.extension::<TextIndex>()?;
+        let documents = branch_ids
+            .iter()
+            .map(|id| inverted.clear_branch(id) + text.clear_branch(id))
+            .sum();
+
+        #[cfg(feature = "vectors")]
+        let backends = {
+            let vectors = self.db.extension::<VectorBackendState>()?;
+            branch_ids.iter().map(|id| vectors.remove_branch(id)).sum()
+        };
+        #[cfg(not(feature = "vectors"))]
+        let backends = 0;
+        Ok((documents, backends))
+    }
+
+    /// Give every fork of `branch_id` its own copy of the data it still
+    /// reads from it, then unlink the forks
+    ///
+    /// The copies are committed like ordinary writes, so they are durable
+    /// before the forks stop reading through to the branch.
+    fn detach_forks(&self, branch_id: &str) -> StrataResult<()> {
+        let storage = self.db.storage();
+        let parent_id = resolve_branch_name(branch_id);
+        for child_id in storage.forks_of(&parent_id) {
+            let Some(child) = self.fork_name(&child_id)? else {
+                storage.detach_fork(&child_id);
+                self.db.clear_read_cache();
+                continue;
+            };
+            let inherited = storage.inherited_keys(&child_id);
+            if !inherited.is_empty() {
+                self.db.transaction(child_id, |txn| {
+                    for key in &inherited {
+                        if let Some(value) = txn.get(key)? {
+                            txn.put(key.clone(), value)?;
+                        }
+                    }
+                    Ok(())
+                })?;
+            }
+            self.mark_detached(&child)?;
+            storage.detach_fork(&child_id);
+            self.db.clear_read_cache();
+            info!(target: "strata::branch", parent = %branch_id, branch = %child, "Fork detached");
+        }
+        Ok(())
+    }
+
+    /// Name of the branch whose data is stored under `branch_id`
+    fn fork_name(&self, branch_id: &BranchId) -> StrataResult<Option<String>> {
+        Ok(self
+            .list_branches()?
+            .into_iter()
+            .find(|name| resolve_branch_name(name) == *branch_id))
+    }
+
     /// Delete all branch-scoped data within an existing transaction context.
     fn delete_namespace_data(
         txn: &mut strata_concurrency::TransactionContext,
@@ -355,8 +1014,10 @@ impl BranchIndex {
             TypeTag::Event,
             TypeTag::State,
             TypeTag::Trace, // Deprecated but kept for backwards compatibility
+            TypeTag::List,
             TypeTag::Json,
             TypeTag::Vector,
+            TypeTag::Custom,
         ] {
             let prefix = Key::new(ns.clone(), type_tag, vec![]);
             let entries = txn.scan_prefix(&prefix)?;
@@ -370,6 +1031,68 @@ impl BranchIndex {
     }
 }
 
+/// Reject policies that would prune every version they apply to
+fn validate_retention(policy: &RetentionPolicy) -> StrataResult<()> {
+    match policy {
+        RetentionPolicy::KeepAll => Ok(()),
+        RetentionPolicy::KeepLast(0) => Err(StrataError::invalid_input(
+            "retention KeepLast(n) requires n > 0".to_string(),
+        )),
+        RetentionPolicy::KeepFor(d) if d.is_zero() => Err(StrataError::invalid_input(
+            "retention KeepFor requires a non-zero duration".to_string(),
+        )),
+        RetentionPolicy::KeepLast(_) | RetentionPolicy::KeepFor(_) => Ok(()),
+        RetentionPolicy::Composite { default, overrides } => {
+            validate_retention(default)?;
+            overrides.values().try_for_each(|p| validate_retention(p))
+        }
+    }
+}
+
+/// Retention policies of all branches, keyed by the ID their data is
+/// stored under
+///
+/// Reads storage directly, like the KV history policies.
+pub(crate) fn branch_retention_policies(
+    db: &Database,
+) -> StrataResult<Vec<(BranchId, RetentionPolicy)>> {
+    let prefix = Key::new_branch_with_id(global_namespace(), "");
+    let mut policies = Vec::new();
+    for (_, vv) in db.storage().list_by_prefix(&prefix) {
+        let Ok(meta) = from_stored_value::<BranchMetadata>(&vv.value) else {
+            continue;
+        };
+        if let Some(policy) = meta.retention {
+            policies.push((resolve_branch_name(&meta.name), policy));
+        }
+    }
+    Ok(policies)
+}
+
+/// Forks recorded in branch metadata, as `(child, parent, fork_version)`
+/// keyed by the IDs their data is stored under
+///
+/// Reads storage directly, like `branch_retention_policies()`.
+pub(crate) fn branch_fork_points(db: &Database) -> Vec<(BranchId, BranchId, u64)> {
+    let prefix = Key::new_branch_with_id(global_namespace(), "");
+    db.storage()
+        .list_by_prefix(&prefix)
+        .into_iter()
+        .filter_map(|(_, vv)| {
+            let meta = from_stored_value::<BranchMetadata>(&vv.value).ok()?;
+            if meta.detached {
+                return None;
+            }
+            let parent = meta.parent_branch.as_deref()?;
+            Some((
+                resolve_branch_name(&meta.name),
+                resolve_branch_name(parent),
+                meta.fork_version?,
+            ))
+        })
+        .collect()
+}
+
 // ========== Searchable Trait Implementation ==========
 //
 // Search is handled by the intelligence layer.
@@ -432,6 +1155,100 @@ mod tests {
         assert_eq!(result.unwrap().value.name, "test-run");
     }
 
+    #[test]
+    fn test_set_retention() {
+        let (_temp, _db, ri) = setup();
+        ri.create_branch("test-run").unwrap();
+
+        let policy = RetentionPolicy::composite(RetentionPolicy::KeepLast(1))
+            .with_override(strata_core::PrimitiveType::Vector, RetentionPolicy::KeepAll)
+            .build();
+        let meta = ri.set_retention("test-run", policy.clone()).unwrap();
+        assert_eq!(meta.value.version, 2);
+        assert_eq!(ri.retention("test-run").unwrap(), Some(policy));
+
+        ri.set_retention("test-run", RetentionPolicy::KeepAll)
+            .unwrap();
+        assert_eq!(ri.retention("test-run").unwrap(), None);
+
+        assert!(ri
+            .set_retention("test-run", RetentionPolicy::KeepLast(0))
+            .is_err());
+        assert!(ri
+            .set_retention("missing", RetentionPolicy::KeepLast(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_compaction_enforces_branch_retention() {
+        use crate::primitives::{KVStore, StateCell};
+        use strata_core::PrimitiveType;
+
+        let (_temp, db, ri) = setup();
+        let (kv, state) = (KVStore::new(db.clone()), StateCell::new(db.clone()));
+        ri.create_branch("archive").unwrap();
+        let branch_id = resolve_branch_name("archive");
+        for i in 0..5 {
+            kv.put(&branch_id, "default", "a", Value::Int(i)).unwrap();
+            kv.put(&branch_id, "default", "metrics:x", Value::Int(i))
+                .unwrap();
+            state
+                .set(&branch_id, "default", "s", Value::Int(i))
+                .unwrap();
+        }
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "metrics:",
+            RetentionPolicy::KeepLast(3),
+        )
+        .unwrap();
+        let policy = RetentionPolicy::composite(RetentionPolicy::KeepLast(1))
+            .with_override(PrimitiveType::State, RetentionPolicy::KeepAll)
+            .build();
+        ri.set_retention("archive", policy).unwrap();
+
+        db.compact().unwrap();
+        let ns = Namespace::for_branch_space(branch_id, "default");
+        let versions = |key: Key| db.get_history(&key, None, None).unwrap().len();
+        assert_eq!(versions(Key::new_kv(ns.clone(), "a")), 1);
+        assert_eq!(versions(Key::new_kv(ns.clone(), "metrics:x")), 3);
+        assert_eq!(versions(Key::new_state(ns, "s")), 5);
+    }
+
+    #[test]
+    fn test_branch_retention_survives_compaction_and_reopen() {
+        use crate::primitives::{KVStore, StateCell};
+        use strata_core::PrimitiveType;
+
+        let (temp, db, ri) = setup();
+        ri.create_branch("archive").unwrap();
+        let branch_id = resolve_branch_name("archive");
+        {
+            let (kv, state) = (KVStore::new(db.clone()), StateCell::new(db.clone()));
+            for i in 0..5 {
+                kv.put(&branch_id, "default", "a", Value::Int(i)).unwrap();
+                state
+                    .set(&branch_id, "default", "s", Value::Int(i))
+                    .unwrap();
+            }
+        }
+        let policy = RetentionPolicy::composite(RetentionPolicy::KeepLast(2))
+            .with_override(PrimitiveType::State, RetentionPolicy::KeepAll)
+            .build();
+        ri.set_retention("archive", policy).unwrap();
+        db.compact().unwrap();
+        db.shutdown().unwrap();
+        drop(ri);
+        drop(db);
+
+        let db = Database::open(temp.path()).unwrap();
+        let ns = Namespace::for_branch_space(branch_id, "default");
+        let versions = |key: Key| db.get_history(&key, None, None).unwrap().len();
+        assert_eq!(versions(Key::new_kv(ns.clone(), "a")), 2);
+        assert_eq!(versions(Key::new_state(ns, "s")), 5);
+    }
+
     #[test]
     fn test_get_branch_not_found() {
         let (_temp, _db, ri) = setup();
@@ -476,6 +1293,63 @@ mod tests {
         assert!(!ri.exists("test-run").unwrap());
     }
 
+    #[test]
+    fn test_delete_branch_clears_indexes() {
+        let (_temp, db, ri) = setup();
+        ri.create_branch("run").unwrap();
+        let branch_id = resolve_branch_name("run");
+
+        let inverted = db.extension::<InvertedIndex>().unwrap();
+        inverted.enable();
+        let doc_ref = crate::search::EntityRef::Kv {
+            branch_id,
+            key: "notes".to_string(),
+        };
+        inverted.index_document(&doc_ref, "hello world", None);
+        #[cfg(feature = "vectors")]
+        {
+            use crate::primitives::vector::{VectorConfig, VectorStore};
+            VectorStore::new(db.clone())
+                .create_collection(branch_id, "default", "emb", VectorConfig::for_minilm())
+                .unwrap();
+        }
+
+        ri.delete_branch("run").unwrap();
+        assert!(inverted.branch_ids().is_empty());
+        assert_eq!(inverted.total_docs(), 0);
+        #[cfg(feature = "vectors")]
+        assert!(db
+            .extension::<VectorBackendState>()
+            .unwrap()
+            .branch_ids()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_verify_indexes_removes_stale_entries() {
+        let (_temp, db, ri) = setup();
+        ri.create_branch("live").unwrap();
+
+        let inverted = db.extension::<InvertedIndex>().unwrap();
+        inverted.enable();
+        let doc = |branch_id| crate::search::EntityRef::Kv {
+            branch_id,
+            key: "notes".to_string(),
+        };
+        // Left behind by a branch deleted before deletes cleared the index
+        inverted.index_document(&doc(BranchId::new()), "stale posting", None);
+        inverted.index_document(&doc(resolve_branch_name("live")), "live posting", None);
+
+        let report = ri.verify_indexes().unwrap();
+        assert_eq!(report.stale_branches, 1);
+        assert_eq!(report.documents_removed, 1);
+        assert_eq!(report.vector_backends_removed, 0);
+        assert_eq!(inverted.total_docs(), 1);
+        assert_eq!(inverted.doc_freq("stale"), 0);
+
+        assert_eq!(ri.verify_indexes().unwrap(), IndexVerification::default());
+    }
+
     #[test]
     fn test_delete_branch_not_found() {
         let (_temp, _db, ri) = setup();
@@ -484,6 +1358,200 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_ancestry_and_children() {
+        let (_temp, _db, ri) = setup();
+        ri.create_branch("root").unwrap();
+        ri.create_fork("mid", "root", 3).unwrap();
+        ri.create_fork("leaf", "mid", 7).unwrap();
+        ri.create_fork("sibling", "root", 5).unwrap();
+
+        let ancestry = ri.ancestry("leaf").unwrap();
+        let names: Vec<(&str, u64)> = ancestry
+            .iter()
+            .map(|a| (a.name.as_str(), a.fork_version))
+            .collect();
+        assert_eq!(names, vec![("mid", 7), ("root", 3)]);
+        assert!(ancestry.iter().all(|a| a.shared));
+        assert!(ri.ancestry("root").unwrap().is_empty());
+        assert_eq!(
+            ri.shared_lineage("leaf").unwrap(),
+            vec![
+                ("leaf".to_string(), u64::MAX),
+                ("mid".to_string(), 7),
+                ("root".to_string(), 3),
+            ]
+        );
+
+        assert_eq!(ri.children("root").unwrap(), vec!["mid", "sibling"]);
+        assert_eq!(ri.children("mid").unwrap(), vec!["leaf"]);
+        assert!(ri.children("leaf").unwrap().is_empty());
+        assert!(ri.ancestry("missing").is_err());
+    }
+
+    #[test]
+    fn test_ancestry_ignores_recreated_parent() {
+        let (_temp, db, ri) = setup();
+        ri.create_branch("parent").unwrap();
+        crate::branch_ops::fork_branch(&db, "parent", "child").unwrap();
+        ri.delete_branch("parent").unwrap();
+        ri.create_branch("parent").unwrap();
+
+        assert!(ri.ancestry("child").unwrap().is_empty());
+        assert!(ri.children("parent").unwrap().is_empty());
+        let meta = ri.get_branch("child").unwrap().unwrap().value;
+        assert_eq!(meta.parent_branch.as_deref(), Some("parent"));
+        assert!(meta.detached);
+    }
+
+    #[test]
+    fn test_tags_and_metadata() {
+        let (_temp, _db, ri) = setup();
+        ri.create_branch("run").unwrap();
+
+        assert!(ri.add_tag("run", "eval").unwrap());
+        assert!(ri.add_tag("run", "baseline").unwrap());
+        assert!(!ri.add_tag("run", "eval").unwrap());
+        assert!(ri.add_tag("run", " ").is_err());
+        assert!(ri.add_tag("missing", "eval").is_err());
+        ri.set_metadata("run", "model", Value::String("small".into()))
+            .unwrap();
+        ri.set_metadata("run", "seed", Value::Int(7)).unwrap();
+        ri.set_metadata("run", "seed", Value::Null).unwrap();
+
+        let meta = ri.get_branch("run").unwrap().unwrap().value;
+        assert_eq!(meta.tags, vec!["baseline", "eval"]);
+        assert_eq!(meta.metadata.len(), 1);
+        assert_eq!(meta.metadata["model"], Value::String("small".into()));
+
+        assert!(ri.remove_tag("run", "baseline").unwrap());
+        assert!(!ri.remove_tag("run", "baseline").unwrap());
+        let meta = ri.get_branch("run").unwrap().unwrap().value;
+        assert_eq!(meta.tags, vec!["eval"]);
+    }
+
+    #[test]
+    fn test_query_and_count() {
+        let (_temp, _db, ri) = setup();
+        let first = ri.create_branch("run-a").unwrap().value;
+        ri.create_branch("run-b").unwrap();
+        ri.create_branch("run-c").unwrap();
+        ri.add_tag("run-a", "eval").unwrap();
+        ri.add_tag("run-b", "eval").unwrap();
+        ri.set_metadata("run-b", "model", Value::String("large".into()))
+            .unwrap();
+        ri.set_metadata("run-c", "model", Value::String("large".into()))
+            .unwrap();
+
+        let names = |filter: BranchFilter| -> Vec<String> {
+            ri.query(&filter)
+                .unwrap()
+                .into_iter()
+                .map(|v| v.value.name)
+                .collect()
+        };
+        assert_eq!(ri.count().unwrap(), 3);
+        assert_eq!(names(BranchFilter::default()).len(), 3);
+        assert_eq!(
+            names(BranchFilter {
+                tag: Some("eval".into()),
+                ..Default::default()
+            }),
+            vec!["run-a", "run-b"]
+        );
+        assert_eq!(
+            names(BranchFilter {
+                tag: Some("eval".into()),
+                metadata: vec![("model".into(), Value::String("large".into()))],
+                ..Default::default()
+            }),
+            vec!["run-b"]
+        );
+        assert!(!names(BranchFilter {
+            created_after: Some(first.created_at),
+            ..Default::default()
+        })
+        .contains(&"run-a".to_string()));
+        assert_eq!(
+            names(BranchFilter {
+                status: Some(BranchStatus::Active),
+                ..Default::default()
+            })
+            .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_close_and_fail_branch() {
+        let (_temp, _db, ri) = setup();
+        ri.create_branch("ok").unwrap();
+        ri.create_branch("bad").unwrap();
+
+        let closed = ri.close_branch("ok").unwrap().value;
+        assert_eq!(closed.status, BranchStatus::Completed);
+        assert!(closed.completed_at.is_some());
+        assert!(ri.close_branch("ok").is_err());
+        assert!(ri.fail_branch("ok", "late").is_err());
+
+        let failed = ri.fail_branch("bad", "out of budget").unwrap().value;
+        assert_eq!(failed.status, BranchStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("out of budget"));
+        assert!(ri.close_branch("missing").is_err());
+
+        let ended = ri
+            .query(&BranchFilter {
+                status: Some(BranchStatus::Active),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(ended.is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_hooks() {
+        use std::sync::Mutex;
+
+        let (_temp, db, ri) = setup();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let on_close = seen.clone();
+        ri.on_close(move |meta| {
+            on_close
+                .lock()
+                .unwrap()
+                .push(format!("closed {}", meta.name));
+            Err(StrataError::internal("export failed"))
+        })
+        .unwrap();
+        let on_fail = seen.clone();
+        ri.on_fail(move |meta| {
+            let error = meta.error.clone().unwrap_or_default();
+            on_fail
+                .lock()
+                .unwrap()
+                .push(format!("failed {}: {}", meta.name, error));
+            Ok(())
+        })
+        .unwrap();
+
+        // Hooks are shared by every index over the database
+        let other = BranchIndex::new(db);
+        other.create_branch("a").unwrap();
+        other.create_branch("b").unwrap();
+        other.close_branch("a").unwrap();
+        other.fail_branch("b", "timeout").unwrap();
+
+        // A failing hook does not fail the close
+        assert_eq!(
+            ri.get_branch("a").unwrap().unwrap().value.status,
+            BranchStatus::Completed
+        );
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["closed a".to_string(), "failed b: timeout".to_string()]
+        );
+    }
+
     #[test]
     fn test_branch_status_default() {
         assert_eq!(BranchStatus::default(), BranchStatus::Active);
@@ -492,5 +1560,8 @@ mod tests {
     #[test]
     fn test_branch_status_as_str() {
         assert_eq!(BranchStatus::Active.as_str(), "Active");
+        assert_eq!(BranchStatus::Failed.as_str(), "Failed");
+        assert!(BranchStatus::Completed.is_terminal());
+        assert!(!BranchStatus::Active.is_terminal());
     }
 }
@@ -8,4 +8,8 @@ mod handle;
 mod index;
 
 pub use handle::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
-pub use index::{resolve_branch_name, BranchIndex, BranchMetadata, BranchStatus};
+pub(crate) use index::{branch_fork_points, branch_retention_policies};
+pub use index::{
+    resolve_branch_name, BranchAncestor, BranchFilter, BranchIndex, BranchMetadata, BranchStatus,
+    IndexVerification,
+};
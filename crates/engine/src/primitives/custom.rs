@@ -0,0 +1,450 @@
+//! Custom primitives: registering new primitives without forking the engine
+//!
+//! A custom primitive owns a one-byte primitive ID from
+//! [`CUSTOM_PRIMITIVE_IDS`]. The ID selects:
+//!
+//! - its key range: entries are stored under `TypeTag::Custom`, with the
+//!   primitive ID as the first user key byte (see [`Key::new_custom`])
+//! - its snapshot section: checkpoints write the section returned by
+//!   [`CustomPrimitive::snapshot`] under the primitive ID as section tag
+//!
+//! Entries are written through ordinary transactions, so they share the WAL,
+//! commit atomically with other primitives and follow branch forks and
+//! deletes. Runtime state kept outside storage (indexes, caches) is rebuilt
+//! at open by [`CustomPrimitive::replay`]; keep it in a `Database` extension
+//! (`db.extension::<T>()`) so each database gets its own.
+//!
+//! ## Registration
+//!
+//! Like recovery participants, primitives register once at startup, before
+//! any Database is opened:
+//!
+//! ```text
+//! use strata_engine::{register_custom_primitive, CustomPrimitive, CustomStore};
+//!
+//! struct Counters;
+//!
+//! impl CustomPrimitive for Counters {
+//!     fn id(&self) -> u8 { 0x80 }
+//!     fn name(&self) -> &'static str { "counters" }
+//! }
+//!
+//! register_custom_primitive(Arc::new(Counters))?;
+//!
+//! let db = Database::open("/path/to/data")?;
+//! let counters = CustomStore::new(db, 0x80);
+//! counters.put(&branch_id, "default", b"hits", Value::Int(1))?;
+//! ```
+
+use crate::database::Database;
+use parking_lot::RwLock;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use strata_core::primitive_ext::wal_ranges;
+use strata_core::types::{BranchId, Key, Namespace, TypeTag};
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult, Version};
+use tracing::info;
+
+/// Primitive IDs available to custom primitives
+pub const CUSTOM_PRIMITIVE_IDS: RangeInclusive<u8> =
+    wal_ranges::FUTURE_START..=wal_ranges::FUTURE_END;
+
+/// A primitive defined outside the engine
+///
+/// Only `id` and `name` are required; the hooks default to no-ops for
+/// primitives whose state lives entirely in storage.
+pub trait CustomPrimitive: Send + Sync + 'static {
+    /// Primitive ID, from [`CUSTOM_PRIMITIVE_IDS`]
+    ///
+    /// Part of the on-disk format: keys and snapshot sections are tagged
+    /// with it, so it must never change once data has been written.
+    fn id(&self) -> u8;
+
+    /// Name for logging and errors
+    fn name(&self) -> &'static str;
+
+    /// Rebuild runtime state from one entry replayed from the WAL
+    ///
+    /// Called when a Database opens, after WAL recovery, once for the
+    /// latest version of each live entry in the primitive's key range.
+    /// `key` is the entry key without the primitive ID byte. An error
+    /// aborts the open.
+    fn replay(
+        &self,
+        _db: &Database,
+        _branch_id: BranchId,
+        _space: &str,
+        _key: &[u8],
+        _value: &Value,
+    ) -> StrataResult<()> {
+        Ok(())
+    }
+
+    /// Serialize the primitive's snapshot section
+    ///
+    /// Called on every checkpoint. `None` writes no section. An error
+    /// fails the checkpoint.
+    fn snapshot(&self, _db: &Database) -> StrataResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+}
+
+/// Global registry of custom primitives
+static CUSTOM_PRIMITIVES: once_cell::sync::Lazy<RwLock<Vec<Arc<dyn CustomPrimitive>>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Register a custom primitive
+///
+/// Call once during application initialization, before any Database is
+/// opened, so its entries are replayed. Registering the same name under
+/// the same ID again is a no-op.
+///
+/// # Errors
+///
+/// - `InvalidInput` if the ID is outside [`CUSTOM_PRIMITIVE_IDS`] or
+///   already registered by another primitive
+pub fn register_custom_primitive(primitive: Arc<dyn CustomPrimitive>) -> StrataResult<()> {
+    let id = primitive.id();
+    if !CUSTOM_PRIMITIVE_IDS.contains(&id) {
+        return Err(StrataError::invalid_input(format!(
+            "custom primitive '{}' has ID {:#04x}, outside {:#04x}..={:#04x}",
+            primitive.name(),
+            id,
+            CUSTOM_PRIMITIVE_IDS.start(),
+            CUSTOM_PRIMITIVE_IDS.end()
+        )));
+    }
+
+    let mut registry = CUSTOM_PRIMITIVES.write();
+    if let Some(existing) = registry.iter().find(|p| p.id() == id) {
+        if existing.name() == primitive.name() {
+            return Ok(());
+        }
+        return Err(StrataError::invalid_input(format!(
+            "custom primitive ID {:#04x} is already registered by '{}'",
+            id,
+            existing.name()
+        )));
+    }
+
+    info!(target: "strata::custom", id, name = primitive.name(), "Registered custom primitive");
+    registry.push(primitive);
+    Ok(())
+}
+
+/// Look up a registered custom primitive by ID
+pub fn custom_primitive(id: u8) -> Option<Arc<dyn CustomPrimitive>> {
+    CUSTOM_PRIMITIVES
+        .read()
+        .iter()
+        .find(|p| p.id() == id)
+        .cloned()
+}
+
+/// Snapshot of the registry, so hooks run without holding its lock
+fn registered() -> Vec<Arc<dyn CustomPrimitive>> {
+    CUSTOM_PRIMITIVES.read().clone()
+}
+
+/// Replay every live custom primitive entry into its primitive
+///
+/// Called by Database open after recovery participants have run.
+pub(crate) fn replay_custom_primitives(db: &Database) -> StrataResult<()> {
+    let primitives = registered();
+    if primitives.is_empty() {
+        return Ok(());
+    }
+
+    for branch_id in db.storage().branch_ids() {
+        for (key, vv) in db.storage().list_by_type(&branch_id, TypeTag::Custom) {
+            let Some((&id, user_key)) = key.user_key.split_first() else {
+                continue;
+            };
+            // Entries of unregistered primitives are kept but not replayed
+            if let Some(primitive) = primitives.iter().find(|p| p.id() == id) {
+                primitive.replay(db, branch_id, &key.namespace.space, user_key, &vv.value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collect the snapshot sections of all registered custom primitives
+pub(crate) fn custom_snapshot_sections(db: &Database) -> StrataResult<Vec<(u8, Vec<u8>)>> {
+    let mut sections = Vec::new();
+    for primitive in registered() {
+        if let Some(section) = primitive.snapshot(db)? {
+            sections.push((primitive.id(), section));
+        }
+    }
+    Ok(sections)
+}
+
+/// Facade over a custom primitive's key range
+///
+/// Stateless like the built-in primitives: holds only an `Arc<Database>`
+/// and the primitive ID. To combine custom entries with other primitives
+/// in one transaction, build keys with [`CustomStore::key`] and use
+/// `Database::transaction` directly.
+#[derive(Clone)]
+pub struct CustomStore {
+    db: Arc<Database>,
+    id: u8,
+}
+
+impl CustomStore {
+    /// Create a facade for the custom primitive with this ID
+    pub fn new(db: Arc<Database>, id: u8) -> Self {
+        Self { db, id }
+    }
+
+    /// Primitive ID this facade reads and writes
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Storage key of an entry
+    pub fn key(&self, branch_id: &BranchId, space: &str, key: &[u8]) -> Key {
+        Key::new_custom(Namespace::for_branch_space(*branch_id, space), self.id, key)
+    }
+
+    /// Writes go only to registered primitives, so every stored entry has
+    /// a replay handler
+    fn ensure_registered(&self) -> StrataResult<()> {
+        if custom_primitive(self.id).is_none() {
+            return Err(StrataError::invalid_input(format!(
+                "custom primitive {:#04x} is not registered",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the latest value of an entry
+    pub fn get(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &[u8],
+    ) -> StrataResult<Option<Value>> {
+        self.db
+            .transaction(*branch_id, |txn| txn.get(&self.key(branch_id, space, key)))
+    }
+
+    /// Store a value
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the primitive is not registered
+    pub fn put(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &[u8],
+        value: Value,
+    ) -> StrataResult<Version> {
+        self.ensure_registered()?;
+        let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
+            txn.put(self.key(branch_id, space, key), value)
+        })?;
+        Ok(Version::Txn(commit_version))
+    }
+
+    /// Delete an entry
+    ///
+    /// Returns `true` if the entry existed.
+    ///
+    /// # Errors
+    /// - `InvalidInput` if the primitive is not registered
+    pub fn delete(&self, branch_id: &BranchId, space: &str, key: &[u8]) -> StrataResult<bool> {
+        self.ensure_registered()?;
+        self.db.transaction(*branch_id, |txn| {
+            let storage_key = self.key(branch_id, space, key);
+            let exists = txn.get(&storage_key)?.is_some();
+            if exists {
+                txn.delete(storage_key)?;
+            }
+            Ok(exists)
+        })
+    }
+
+    /// List entry keys starting with `prefix`, without the primitive ID byte
+    pub fn list(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: &[u8],
+    ) -> StrataResult<Vec<Vec<u8>>> {
+        self.db.transaction(*branch_id, |txn| {
+            let results = txn.scan_prefix(&self.key(branch_id, space, prefix))?;
+            Ok(results
+                .into_iter()
+                .map(|(key, _)| key.user_key[1..].to_vec())
+                .collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::BTreeMap;
+    use strata_durability::{snapshot_path, DiskSnapshotReader};
+
+    /// Entries replayed into one database
+    #[derive(Default)]
+    struct Replayed(Mutex<BTreeMap<Vec<u8>, Value>>);
+
+    struct TestPrimitive;
+
+    impl CustomPrimitive for TestPrimitive {
+        fn id(&self) -> u8 {
+            0xF0
+        }
+
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn replay(
+            &self,
+            db: &Database,
+            _branch_id: BranchId,
+            _space: &str,
+            key: &[u8],
+            value: &Value,
+        ) -> StrataResult<()> {
+            let replayed = db.extension::<Replayed>()?;
+            replayed.0.lock().insert(key.to_vec(), value.clone());
+            Ok(())
+        }
+
+        fn snapshot(&self, _db: &Database) -> StrataResult<Option<Vec<u8>>> {
+            Ok(Some(b"test section".to_vec()))
+        }
+    }
+
+    fn register() {
+        register_custom_primitive(Arc::new(TestPrimitive)).unwrap();
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_ids() {
+        struct Builtin;
+        impl CustomPrimitive for Builtin {
+            fn id(&self) -> u8 {
+                0x10
+            }
+            fn name(&self) -> &'static str {
+                "builtin"
+            }
+        }
+        struct Clash;
+        impl CustomPrimitive for Clash {
+            fn id(&self) -> u8 {
+                0xF0
+            }
+            fn name(&self) -> &'static str {
+                "clash"
+            }
+        }
+
+        register();
+        assert!(register_custom_primitive(Arc::new(Builtin)).is_err());
+        assert!(register_custom_primitive(Arc::new(Clash)).is_err());
+        // Re-registering the same primitive is fine
+        register();
+        assert_eq!(custom_primitive(0xF0).unwrap().name(), "test");
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        register();
+        let db = Database::cache().unwrap();
+        let branch_id = BranchId::new();
+        let store = CustomStore::new(db.clone(), 0xF0);
+
+        store
+            .put(&branch_id, "default", b"a:1", Value::Int(1))
+            .unwrap();
+        store
+            .put(&branch_id, "default", b"b:1", Value::Int(2))
+            .unwrap();
+        assert_eq!(
+            store.get(&branch_id, "default", b"a:1").unwrap(),
+            Some(Value::Int(1))
+        );
+        assert_eq!(
+            store.list(&branch_id, "default", b"a:").unwrap(),
+            vec![b"a:1".to_vec()]
+        );
+        assert!(store.delete(&branch_id, "default", b"a:1").unwrap());
+        assert!(!store.delete(&branch_id, "default", b"a:1").unwrap());
+        assert_eq!(store.get(&branch_id, "default", b"a:1").unwrap(), None);
+
+        // Unregistered primitives cannot be written
+        let unregistered = CustomStore::new(db, 0xEF);
+        assert!(unregistered
+            .put(&branch_id, "default", b"k", Value::Int(1))
+            .is_err());
+    }
+
+    #[test]
+    fn test_fork_copies_entries() {
+        use crate::branch_ops::fork_branch;
+        use crate::primitives::branch::{resolve_branch_name, BranchIndex};
+
+        register();
+        let db = Database::cache().unwrap();
+        BranchIndex::new(db.clone())
+            .create_branch("source")
+            .unwrap();
+        let store = CustomStore::new(db.clone(), 0xF0);
+        store
+            .put(
+                &resolve_branch_name("source"),
+                "default",
+                b"k",
+                Value::Int(7),
+            )
+            .unwrap();
+
+        fork_branch(&db, "source", "dest").unwrap();
+        assert_eq!(
+            store
+                .get(&resolve_branch_name("dest"), "default", b"k")
+                .unwrap(),
+            Some(Value::Int(7))
+        );
+    }
+
+    #[test]
+    fn test_replay_and_snapshot_section() {
+        register();
+        let dir = tempfile::tempdir().unwrap();
+        let branch_id = BranchId::new();
+        {
+            let db = Database::open(dir.path()).unwrap();
+            let store = CustomStore::new(db.clone(), 0xF0);
+            store
+                .put(&branch_id, "default", b"kept", Value::Int(1))
+                .unwrap();
+            store
+                .put(&branch_id, "default", b"gone", Value::Int(2))
+                .unwrap();
+            store.delete(&branch_id, "default", b"gone").unwrap();
+            db.checkpoint().unwrap();
+        }
+
+        let snapshots_dir = dir.path().join("snapshots");
+        let table = DiskSnapshotReader::section_table(&snapshot_path(&snapshots_dir, 1)).unwrap();
+        assert!(table.iter().any(|h| h.primitive_type == 0xF0));
+
+        let db = Database::open(dir.path()).unwrap();
+        let replayed = db.extension::<Replayed>().unwrap();
+        let replayed = replayed.0.lock();
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed.get(&b"kept"[..]), Some(&Value::Int(1)));
+    }
+}
@@ -33,7 +33,37 @@
 //! - TypeTag: Event (0x02)
 //! - Event key: `<namespace>:<TypeTag::Event>:<sequence_be_bytes>`
 //! - Metadata key: `<namespace>:<TypeTag::Event>:__meta__`
+//! - Retention key: `<namespace>:<TypeTag::Event>:__retention__<event_type>`
+//! - Consumer group key: `<namespace>:<TypeTag::Event>:__group__<event_type>\0<group>`
+//! - Schema key: `<namespace>:<TypeTag::Event>:__evschema__<event_type>`
+//!
+//! ## Retention
+//!
+//! Each stream (event type) may carry a [`RetentionPolicy`]. `KeepLast(n)`
+//! caps the stream length and `KeepFor(d)` caps event age. Appending to a
+//! stream deletes its oldest events the policy no longer retains, in the same
+//! transaction, so the deletes reach the WAL as tombstones and survive
+//! recovery. Streams that stop receiving appends are trimmed by
+//! [`EventLog::apply_retention`]. A trimmed stream's first remaining event
+//! still links to the removed event's hash.
+//!
+//! ## Consumer Groups
+//!
+//! A consumer group shares one stream among several workers, like Redis
+//! Streams. [`EventLog::read_group`] hands each event to exactly one consumer
+//! in the group and records it as pending until [`EventLog::ack`]. The
+//! group's cursor and pending entries are stored under the group key, so
+//! they survive restarts like any other write.
+//!
+//! ## Payload Schemas
+//!
+//! A stream may carry a JSON schema (see [`EventLog::set_schema`]). Appends
+//! whose payload doesn't match it are rejected with `InvalidInput` naming the
+//! offending field. The schema is an ordinary write in the branch, so it is
+//! recovered from the WAL and travels with the branch in bundles. Events
+//! appended before a schema was set are not revalidated.
 
+use super::json_schema;
 use crate::database::{Database, RetryConfig};
 use crate::primitives::extensions::EventLogExt;
 use serde::{Deserialize, Serialize};
@@ -44,8 +74,10 @@ use strata_concurrency::TransactionContext;
 use strata_core::contract::{Timestamp, Version, Versioned};
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
+use strata_core::PrimitiveType;
 use strata_core::StrataError;
 use strata_core::StrataResult;
+use strata_durability::retention::RetentionPolicy;
 
 // Re-export Event from core
 pub use strata_core::primitives::Event;
@@ -233,6 +265,329 @@ fn from_stored_value<T: for<'de> Deserialize<'de>>(
     }
 }
 
+/// Load the metadata of the log in `ns`, if it has any.
+pub(crate) fn load_meta(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+) -> StrataResult<Option<EventLogMeta>> {
+    Ok(txn
+        .get(&Key::new_event_meta(ns.clone()))?
+        .map(|v| from_stored_value(&v).unwrap_or_default()))
+}
+
+/// Append an event to the `event_type` stream of the log described by `meta`.
+///
+/// Checks the stream's schema, writes the event and its type-index key,
+/// trims the stream by its retention policy and writes `meta` back. Shared
+/// by [`EventLog::append`] and appends inside session transactions so both
+/// keep the same chain and stream metadata. Returns the event and the
+/// sequences trimmed.
+pub(crate) fn append_in(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    meta: &mut EventLogMeta,
+    event_type: &str,
+    payload: Value,
+) -> StrataResult<(Event, Vec<u64>)> {
+    check_schema(txn, ns, event_type, &payload)?;
+
+    // Compute event hash using current hash version
+    let sequence = meta.next_sequence;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_micros() as u64;
+
+    let hash = compute_event_hash(sequence, event_type, &payload, timestamp, &meta.head_hash);
+
+    // Build event
+    let event = Event {
+        sequence,
+        event_type: event_type.to_string(),
+        payload,
+        timestamp,
+        prev_hash: meta.head_hash,
+        hash,
+    };
+
+    // Write event
+    let event_key = Key::new_event(ns.clone(), sequence);
+    txn.put(event_key, to_stored_value(&event)?)?;
+
+    // Write per-type index key for efficient get_by_type lookups (#972)
+    let idx_key = Key::new_event_type_idx(ns.clone(), event_type, sequence);
+    txn.put(idx_key, Value::Null)?;
+
+    // Update stream metadata
+    match meta.streams.get_mut(event_type) {
+        Some(stream_meta) => stream_meta.update(sequence, timestamp),
+        None => {
+            meta.streams
+                .insert(event_type.to_string(), StreamMeta::new(sequence, timestamp));
+        }
+    }
+
+    let trimmed = match load_retention(txn, ns, event_type)? {
+        Some(policy) => trim_stream(txn, ns, meta, event_type, &policy, timestamp)?,
+        None => Vec::new(),
+    };
+
+    // Update metadata (CAS semantics through transaction)
+    meta.next_sequence = sequence + 1;
+    meta.head_hash = hash;
+    txn.put(Key::new_event_meta(ns.clone()), to_stored_value(meta)?)?;
+
+    Ok((event, trimmed))
+}
+
+/// Load the retention policy of `event_type`, if one is set.
+fn load_retention(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    event_type: &str,
+) -> StrataResult<Option<RetentionPolicy>> {
+    match txn.get(&Key::new_event_retention(ns.clone(), event_type))? {
+        Some(Value::Bytes(bytes)) => RetentionPolicy::from_bytes(&bytes)
+            .map(Some)
+            .map_err(|e| StrataError::serialization(e.to_string())),
+        _ => Ok(None),
+    }
+}
+
+/// Reject `payload` if `event_type` has a schema it doesn't match.
+pub(crate) fn check_schema(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    event_type: &str,
+    payload: &Value,
+) -> StrataResult<()> {
+    match txn.get(&Key::new_event_schema(ns.clone(), event_type))? {
+        Some(schema) => json_schema::check(&schema, payload).map_err(|e| {
+            StrataError::invalid_input(format!(
+                "payload does not match the schema of stream '{}': {}",
+                event_type, e
+            ))
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Delete the oldest events of `event_type` that `policy` no longer retains.
+///
+/// Updates the stream's metadata and returns the removed sequences. The
+/// caller writes `meta` back.
+fn trim_stream(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    meta: &mut EventLogMeta,
+    event_type: &str,
+    policy: &RetentionPolicy,
+    now: u64,
+) -> StrataResult<Vec<u64>> {
+    let mut trimmed = Vec::new();
+    let Some(stream) = meta.streams.get_mut(event_type) else {
+        return Ok(trimmed);
+    };
+
+    while stream.count > 0
+        && !policy.should_retain(
+            stream.first_sequence,
+            stream.first_timestamp,
+            stream.count as usize,
+            now,
+            PrimitiveType::Event,
+        )
+    {
+        let sequence = stream.first_sequence;
+        txn.delete(Key::new_event(ns.clone(), sequence))?;
+        txn.delete(Key::new_event_type_idx(ns.clone(), event_type, sequence))?;
+        trimmed.push(sequence);
+        stream.count -= 1;
+
+        // Streams interleave in the global sequence; seek this one's next
+        // event in the type index rather than walking the gap
+        let mut next = None;
+        let idx_prefix = Key::new_event_type_idx_prefix(ns.clone(), event_type);
+        let start = Key::new_event_type_idx(ns.clone(), event_type, sequence + 1);
+        let found = txn.scan_prefix_from(&idx_prefix, &start, 1)?;
+        if let Some(seq) = found.first().and_then(|(k, _)| indexed_sequence(k)) {
+            if let Some(v) = txn.get(&Key::new_event(ns.clone(), seq))? {
+                let event: Event =
+                    from_stored_value(&v).map_err(|e| StrataError::serialization(e.to_string()))?;
+                next = Some(event);
+            }
+        }
+        match next {
+            Some(event) => {
+                stream.first_sequence = event.sequence;
+                stream.first_timestamp = event.timestamp;
+            }
+            None => stream.count = 0,
+        }
+    }
+
+    if stream.count == 0 {
+        meta.streams.remove(event_type);
+    }
+    Ok(trimmed)
+}
+
+/// An event delivered to a consumer group and not yet acknowledged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingEvent {
+    /// Sequence number of the event
+    pub sequence: u64,
+    /// Consumer the event was delivered to
+    pub consumer: String,
+    /// When the event was delivered (microseconds since epoch)
+    pub delivered_at: u64,
+}
+
+/// Delivery state of one consumer group on a stream
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConsumerGroup {
+    /// Highest sequence delivered to the group, if any
+    last_delivered: Option<u64>,
+    /// Delivered but unacknowledged events, in sequence order
+    pending: Vec<PendingEvent>,
+}
+
+/// Validate a consumer group or consumer name
+fn validate_group_name(kind: &str, name: &str) -> StrataResult<()> {
+    if name.is_empty() {
+        return Err(StrataError::invalid_input(format!(
+            "{} name cannot be empty",
+            kind
+        )));
+    }
+    if name.len() > 256 {
+        return Err(StrataError::invalid_input(format!(
+            "{} name exceeds maximum length of 256 characters",
+            kind
+        )));
+    }
+    if name.contains('\0') {
+        return Err(StrataError::invalid_input(format!(
+            "{} name cannot contain NUL bytes",
+            kind
+        )));
+    }
+    Ok(())
+}
+
+/// Load a consumer group, failing if it doesn't exist
+fn load_group(
+    txn: &mut TransactionContext,
+    key: &Key,
+    event_type: &str,
+    group: &str,
+) -> StrataResult<ConsumerGroup> {
+    match txn.get(key)? {
+        Some(v) => from_stored_value(&v).map_err(|e| StrataError::serialization(e.to_string())),
+        None => Err(StrataError::invalid_input(format!(
+            "consumer group '{}' does not exist on stream '{}'",
+            group, event_type
+        ))),
+    }
+}
+
+/// Sequence number an event type index key points to
+fn indexed_sequence(idx_key: &Key) -> Option<u64> {
+    let user_key = &idx_key.user_key;
+    let seq_bytes = user_key.get(user_key.len().checked_sub(8)?..)?;
+    Some(u64::from_be_bytes(seq_bytes.try_into().ok()?))
+}
+
+/// Read up to `count` events of `event_type` after sequence `after`, oldest
+/// first
+///
+/// Seeks in the type index, so only the events returned are loaded.
+fn read_stream_after(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    event_type: &str,
+    after: Option<u64>,
+    count: usize,
+) -> StrataResult<Vec<Versioned<Event>>> {
+    let idx_prefix = Key::new_event_type_idx_prefix(ns.clone(), event_type);
+    let start = match after {
+        Some(last) => Key::new_event_type_idx(ns.clone(), event_type, last.saturating_add(1)),
+        None => idx_prefix.clone(),
+    };
+
+    let mut results = Vec::with_capacity(count);
+    for (idx_key, _) in txn.scan_prefix_from(&idx_prefix, &start, count)? {
+        let Some(seq) = indexed_sequence(&idx_key) else {
+            continue;
+        };
+        if let Some(v) = txn.get(&Key::new_event(ns.clone(), seq))? {
+            let event: Event =
+                from_stored_value(&v).map_err(|e| StrataError::serialization(e.to_string()))?;
+            results.push(Versioned::with_timestamp(
+                event.clone(),
+                Version::Sequence(seq),
+                Timestamp::from_micros(event.timestamp),
+            ));
+        }
+    }
+    Ok(results)
+}
+
+/// Read every event of `event_type`, oldest first
+fn read_stream(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    event_type: &str,
+) -> StrataResult<Vec<Versioned<Event>>> {
+    // Use per-type index keys for efficient lookup (#972)
+    let idx_prefix = Key::new_event_type_idx_prefix(ns.clone(), event_type);
+    let idx_entries = txn.scan_prefix(&idx_prefix)?;
+
+    if !idx_entries.is_empty() {
+        let mut results = Vec::with_capacity(idx_entries.len());
+        for (idx_key, _) in &idx_entries {
+            if let Some(seq) = indexed_sequence(idx_key) {
+                let event_key = Key::new_event(ns.clone(), seq);
+                if let Some(v) = txn.get(&event_key)? {
+                    let event: Event = from_stored_value(&v)
+                        .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?;
+                    results.push(Versioned::with_timestamp(
+                        event.clone(),
+                        Version::Sequence(seq),
+                        Timestamp::from_micros(event.timestamp),
+                    ));
+                }
+            }
+        }
+        return Ok(results);
+    }
+
+    // Fallback: O(N) scan for old data without type index keys
+    let meta_key = Key::new_event_meta(ns.clone());
+    let meta: EventLogMeta = match txn.get(&meta_key)? {
+        Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut filtered = Vec::new();
+    for seq in 0..meta.next_sequence {
+        let event_key = Key::new_event(ns.clone(), seq);
+        if let Some(v) = txn.get(&event_key)? {
+            let event: Event = from_stored_value(&v)
+                .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?;
+            if event.event_type == event_type {
+                filtered.push(Versioned::with_timestamp(
+                    event.clone(),
+                    Version::Sequence(seq),
+                    Timestamp::from_micros(event.timestamp),
+                ));
+            }
+        }
+    }
+
+    Ok(filtered)
+}
+
 /// Immutable append-only event stream
 ///
 /// DESIGN: Single-writer-ordered per branch.
@@ -302,6 +657,7 @@ impl EventLog {
     /// - `event_type` is empty or exceeds 256 characters
     /// - `payload` is not a JSON object
     /// - `payload` contains NaN or Infinity float values
+    /// - `payload` doesn't match the stream's schema
     pub fn append(
         &self,
         branch_id: &BranchId,
@@ -325,67 +681,17 @@ impl EventLog {
         let ns = self.namespace_for(branch_id, space);
         let event_type_owned = event_type.to_string();
 
-        let result = self
+        let appended = self
             .db
             .transaction_with_retry(*branch_id, retry_config, |txn| {
                 // Read current metadata (or default)
-                let meta_key = Key::new_event_meta(ns.clone());
-                let mut meta: EventLogMeta = match txn.get(&meta_key)? {
-                    Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
-                    None => EventLogMeta::default(),
-                };
-
-                // Compute event hash using current hash version
-                let sequence = meta.next_sequence;
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_micros() as u64;
-
-                let hash = compute_event_hash(
-                    sequence,
-                    &event_type_owned,
-                    &payload,
-                    timestamp,
-                    &meta.head_hash,
-                );
-
-                // Build event
-                let event = Event {
-                    sequence,
-                    event_type: event_type_owned.clone(),
-                    payload: payload.clone(),
-                    timestamp,
-                    prev_hash: meta.head_hash,
-                    hash,
-                };
-
-                // Write event
-                let event_key = Key::new_event(ns.clone(), sequence);
-                txn.put(event_key, to_stored_value(&event)?)?;
-
-                // Write per-type index key for efficient get_by_type lookups (#972)
-                let idx_key = Key::new_event_type_idx(ns.clone(), &event_type_owned, sequence);
-                txn.put(idx_key, Value::Null)?;
-
-                // Update stream metadata
-                match meta.streams.get_mut(&event_type_owned) {
-                    Some(stream_meta) => stream_meta.update(sequence, timestamp),
-                    None => {
-                        meta.streams.insert(
-                            event_type_owned.clone(),
-                            StreamMeta::new(sequence, timestamp),
-                        );
-                    }
-                }
-
-                // Update metadata (CAS semantics through transaction)
-                meta.next_sequence = sequence + 1;
-                meta.head_hash = hash;
-                txn.put(meta_key, to_stored_value(&meta)?)?;
-
-                Ok(Version::Sequence(sequence))
+                let mut meta = load_meta(txn, &ns)?.unwrap_or_default();
+                let (event, trimmed) =
+                    append_in(txn, &ns, &mut meta, &event_type_owned, payload.clone())?;
+                Ok((Version::Sequence(event.sequence), trimmed))
             })?;
+        let (result, trimmed) = appended;
+        self.unindex(branch_id, &trimmed)?;
 
         // Update inverted index (zero overhead when disabled)
         let idx = self.db.extension::<crate::search::InvertedIndex>()?;
@@ -407,6 +713,301 @@ impl EventLog {
         Ok(result)
     }
 
+    // ========== Retention ==========
+
+    /// Set the retention policy of the `event_type` stream
+    ///
+    /// Events the policy no longer retains are deleted right away, and on
+    /// every later append to the stream. `KeepAll` removes the policy.
+    ///
+    /// # Returns
+    /// The number of events deleted now
+    ///
+    /// # Errors
+    /// Returns error if `event_type` is invalid, or the policy is
+    /// `KeepLast(0)` or `KeepFor` a zero duration.
+    pub fn set_retention(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        policy: RetentionPolicy,
+    ) -> StrataResult<u64> {
+        validate_event_type(event_type).map_err(|e| StrataError::invalid_input(e.to_string()))?;
+        match &policy {
+            RetentionPolicy::KeepLast(0) => {
+                return Err(StrataError::invalid_input(
+                    "retention KeepLast(n) requires n > 0".to_string(),
+                ))
+            }
+            RetentionPolicy::KeepFor(d) if d.is_zero() => {
+                return Err(StrataError::invalid_input(
+                    "retention KeepFor requires a non-zero duration".to_string(),
+                ))
+            }
+            _ => {}
+        }
+
+        let ns = self.namespace_for(branch_id, space);
+        let trimmed = self.db.transaction(*branch_id, |txn| {
+            let policy_key = Key::new_event_retention(ns.clone(), event_type);
+            if policy == RetentionPolicy::KeepAll {
+                txn.delete(policy_key)?;
+                return Ok(Vec::new());
+            }
+            txn.put(policy_key, Value::Bytes(policy.to_bytes()))?;
+            self.trim_in(txn, &ns, event_type, &policy)
+        })?;
+        self.unindex(branch_id, &trimmed)?;
+        Ok(trimmed.len() as u64)
+    }
+
+    /// Get the retention policy of the `event_type` stream
+    ///
+    /// Returns `KeepAll` if none is set.
+    pub fn retention(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+    ) -> StrataResult<RetentionPolicy> {
+        let ns = self.namespace_for(branch_id, space);
+        self.db.transaction(*branch_id, |txn| {
+            Ok(load_retention(txn, &ns, event_type)?.unwrap_or_default())
+        })
+    }
+
+    /// Enforce every stream's retention policy
+    ///
+    /// Appends already trim the stream they write to; this catches streams
+    /// whose events aged out under `KeepFor` while nothing was appended.
+    ///
+    /// # Returns
+    /// The number of events deleted
+    pub fn apply_retention(&self, branch_id: &BranchId, space: &str) -> StrataResult<u64> {
+        let ns = self.namespace_for(branch_id, space);
+        let trimmed = self.db.transaction(*branch_id, |txn| {
+            let meta_key = Key::new_event_meta(ns.clone());
+            let streams: Vec<String> = match txn.get(&meta_key)? {
+                Some(v) => from_stored_value::<EventLogMeta>(&v)
+                    .map(|meta| meta.streams.into_keys().collect())
+                    .unwrap_or_default(),
+                None => return Ok(Vec::new()),
+            };
+            let mut trimmed = Vec::new();
+            for event_type in streams {
+                if let Some(policy) = load_retention(txn, &ns, &event_type)? {
+                    trimmed.extend(self.trim_in(txn, &ns, &event_type, &policy)?);
+                }
+            }
+            Ok(trimmed)
+        })?;
+        self.unindex(branch_id, &trimmed)?;
+        Ok(trimmed.len() as u64)
+    }
+
+    /// Trim one stream inside `txn`, writing back its metadata if it changed
+    fn trim_in(
+        &self,
+        txn: &mut TransactionContext,
+        ns: &Namespace,
+        event_type: &str,
+        policy: &RetentionPolicy,
+    ) -> StrataResult<Vec<u64>> {
+        let meta_key = Key::new_event_meta(ns.clone());
+        let mut meta: EventLogMeta = match txn.get(&meta_key)? {
+            Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
+            None => return Ok(Vec::new()),
+        };
+        let now = Timestamp::now().as_micros();
+        let trimmed = trim_stream(txn, ns, &mut meta, event_type, policy, now)?;
+        if !trimmed.is_empty() {
+            txn.put(meta_key, to_stored_value(&meta)?)?;
+        }
+        Ok(trimmed)
+    }
+
+    /// Drop trimmed events from the search index
+    fn unindex(&self, branch_id: &BranchId, sequences: &[u64]) -> StrataResult<()> {
+        if sequences.is_empty() {
+            return Ok(());
+        }
+        let idx = self.db.extension::<crate::search::InvertedIndex>()?;
+        if idx.is_enabled() {
+            for &sequence in sequences {
+                idx.remove_document(&crate::search::EntityRef::Event {
+                    branch_id: *branch_id,
+                    sequence,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // ========== Payload Schemas ==========
+
+    /// Set the JSON schema payloads appended to `event_type` must match
+    ///
+    /// Supports the `type`, `properties`, `required`, `additionalProperties`,
+    /// `items`, `enum`, `const`, `minimum`/`maximum`, `minLength`/`maxLength`
+    /// and `minItems`/`maxItems` keywords; others are ignored. Only later
+    /// appends are checked. `Value::Null` removes the schema.
+    ///
+    /// # Errors
+    /// Returns error if `event_type` is invalid or a supported keyword has a
+    /// malformed argument.
+    pub fn set_schema(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        schema: Value,
+    ) -> StrataResult<()> {
+        validate_event_type(event_type).map_err(|e| StrataError::invalid_input(e.to_string()))?;
+        if !schema.is_null() {
+            json_schema::validate_schema(&schema)?;
+        }
+        let key = Key::new_event_schema(self.namespace_for(branch_id, space), event_type);
+        self.db.transaction(*branch_id, |txn| {
+            if schema.is_null() {
+                txn.delete(key.clone())
+            } else {
+                txn.put(key.clone(), schema.clone())
+            }
+        })
+    }
+
+    /// Get the JSON schema of the `event_type` stream, if one is set
+    pub fn schema(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+    ) -> StrataResult<Option<Value>> {
+        let key = Key::new_event_schema(self.namespace_for(branch_id, space), event_type);
+        self.db.transaction(*branch_id, |txn| txn.get(&key))
+    }
+
+    // ========== Consumer Groups ==========
+
+    /// Create consumer group `group` on the `event_type` stream
+    ///
+    /// The group starts before the first event, so its consumers see the
+    /// whole stream.
+    ///
+    /// # Returns
+    /// `false` if the group already exists
+    pub fn create_group(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        group: &str,
+    ) -> StrataResult<bool> {
+        validate_event_type(event_type).map_err(|e| StrataError::invalid_input(e.to_string()))?;
+        validate_group_name("group", group)?;
+        let key = Key::new_event_group(self.namespace_for(branch_id, space), event_type, group);
+        self.db.transaction(*branch_id, |txn| {
+            if txn.get(&key)?.is_some() {
+                return Ok(false);
+            }
+            txn.put(key.clone(), to_stored_value(&ConsumerGroup::default())?)?;
+            Ok(true)
+        })
+    }
+
+    /// Deliver up to `count` new events of `event_type` to `consumer`
+    ///
+    /// Each event is delivered to one consumer of the group and stays
+    /// pending until acknowledged with [`EventLog::ack`]. Concurrent readers
+    /// in the same group never receive the same event.
+    ///
+    /// # Errors
+    /// Returns error if the group doesn't exist or `consumer` is invalid
+    pub fn read_group(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        group: &str,
+        consumer: &str,
+        count: usize,
+    ) -> StrataResult<Vec<Versioned<Event>>> {
+        validate_group_name("consumer", consumer)?;
+        let ns = self.namespace_for(branch_id, space);
+        let key = Key::new_event_group(ns.clone(), event_type, group);
+        // Consumers of one group contend on the group key
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+
+        self.db
+            .transaction_with_retry(*branch_id, retry_config, |txn| {
+                let mut state = load_group(txn, &key, event_type, group)?;
+                let after = state.last_delivered;
+                let delivered = read_stream_after(txn, &ns, event_type, after, count)?;
+                let Some(last) = delivered.last() else {
+                    return Ok(delivered);
+                };
+
+                state.last_delivered = Some(last.value.sequence);
+                let now = Timestamp::now().as_micros();
+                state.pending.extend(delivered.iter().map(|e| PendingEvent {
+                    sequence: e.value.sequence,
+                    consumer: consumer.to_string(),
+                    delivered_at: now,
+                }));
+                txn.put(key.clone(), to_stored_value(&state)?)?;
+                Ok(delivered)
+            })
+    }
+
+    /// Acknowledge events delivered to `group`, removing them from pending
+    ///
+    /// # Returns
+    /// The number of events that were pending
+    pub fn ack(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        group: &str,
+        sequences: &[u64],
+    ) -> StrataResult<u64> {
+        let key = Key::new_event_group(self.namespace_for(branch_id, space), event_type, group);
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+
+        self.db
+            .transaction_with_retry(*branch_id, retry_config, |txn| {
+                let mut state = load_group(txn, &key, event_type, group)?;
+                let before = state.pending.len();
+                state.pending.retain(|p| !sequences.contains(&p.sequence));
+                let acked = (before - state.pending.len()) as u64;
+                if acked > 0 {
+                    txn.put(key.clone(), to_stored_value(&state)?)?;
+                }
+                Ok(acked)
+            })
+    }
+
+    /// Events delivered to `group` and not yet acknowledged, oldest first
+    pub fn pending(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        event_type: &str,
+        group: &str,
+    ) -> StrataResult<Vec<PendingEvent>> {
+        let key = Key::new_event_group(self.namespace_for(branch_id, space), event_type, group);
+        self.db.transaction(*branch_id, |txn| {
+            Ok(load_group(txn, &key, event_type, group)?.pending)
+        })
+    }
+
     // ========== Read Operations ==========
 
     /// Read a single event by sequence number.
@@ -438,6 +1039,9 @@ impl EventLog {
     }
 
     /// Get the current length of the log.
+    ///
+    /// This is the next sequence number, so events removed by retention
+    /// still count.
     pub fn len(&self, branch_id: &BranchId, space: &str) -> StrataResult<u64> {
         self.db.transaction(*branch_id, |txn| {
             let ns = self.namespace_for(branch_id, space);
@@ -464,62 +1068,9 @@ impl EventLog {
         space: &str,
         event_type: &str,
     ) -> StrataResult<Vec<Versioned<Event>>> {
-        self.db.transaction(*branch_id, |txn| {
-            let ns = self.namespace_for(branch_id, space);
-
-            // Use per-type index keys for efficient lookup (#972)
-            let idx_prefix = Key::new_event_type_idx_prefix(ns.clone(), event_type);
-            let idx_entries = txn.scan_prefix(&idx_prefix)?;
-
-            if !idx_entries.is_empty() {
-                let mut results = Vec::with_capacity(idx_entries.len());
-                for (idx_key, _) in &idx_entries {
-                    // Extract sequence from the last 8 bytes of the user_key
-                    let user_key = &idx_key.user_key;
-                    if user_key.len() >= 8 {
-                        let seq_bytes: [u8; 8] = user_key[user_key.len() - 8..].try_into().unwrap();
-                        let seq = u64::from_be_bytes(seq_bytes);
-                        let event_key = Key::new_event(ns.clone(), seq);
-                        if let Some(v) = txn.get(&event_key)? {
-                            let event: Event = from_stored_value(&v).map_err(|e| {
-                                strata_core::StrataError::serialization(e.to_string())
-                            })?;
-                            results.push(Versioned::with_timestamp(
-                                event.clone(),
-                                Version::Sequence(seq),
-                                Timestamp::from_micros(event.timestamp),
-                            ));
-                        }
-                    }
-                }
-                return Ok(results);
-            }
-
-            // Fallback: O(N) scan for old data without type index keys
-            let meta_key = Key::new_event_meta(ns.clone());
-            let meta: EventLogMeta = match txn.get(&meta_key)? {
-                Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
-                None => return Ok(Vec::new()),
-            };
-
-            let mut filtered = Vec::new();
-            for seq in 0..meta.next_sequence {
-                let event_key = Key::new_event(ns.clone(), seq);
-                if let Some(v) = txn.get(&event_key)? {
-                    let event: Event = from_stored_value(&v)
-                        .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?;
-                    if event.event_type == event_type {
-                        filtered.push(Versioned::with_timestamp(
-                            event.clone(),
-                            Version::Sequence(seq),
-                            Timestamp::from_micros(event.timestamp),
-                        ));
-                    }
-                }
-            }
-
-            Ok(filtered)
-        })
+        let ns = self.namespace_for(branch_id, space);
+        self.db
+            .transaction(*branch_id, |txn| read_stream(txn, &ns, event_type))
     }
     // ========== Time-Travel API ==========
 
@@ -591,57 +1142,9 @@ impl EventLogExt for TransactionContext {
         validate_payload(&payload).map_err(|e| StrataError::invalid_input(e.to_string()))?;
 
         let ns = Namespace::for_branch(self.branch_id);
-
-        // Read current metadata (or default)
-        let meta_key = Key::new_event_meta(ns.clone());
-        let mut meta: EventLogMeta = match self.get(&meta_key)? {
-            Some(v) => from_stored_value(&v).unwrap_or_else(|_| EventLogMeta::default()),
-            None => EventLogMeta::default(),
-        };
-
-        // Compute event hash using current hash version
-        let sequence = meta.next_sequence;
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_micros() as u64;
-
-        let hash = compute_event_hash(sequence, event_type, &payload, timestamp, &meta.head_hash);
-
-        // Build event
-        let event = Event {
-            sequence,
-            event_type: event_type.to_string(),
-            payload: payload.clone(),
-            timestamp,
-            prev_hash: meta.head_hash,
-            hash,
-        };
-
-        // Write event
-        let event_key = Key::new_event(ns.clone(), sequence);
-        self.put(event_key, to_stored_value(&event)?)?;
-
-        // Write per-type index key for efficient get_by_type lookups (#972)
-        let idx_key = Key::new_event_type_idx(ns.clone(), event_type, sequence);
-        self.put(idx_key, Value::Null)?;
-
-        // Update stream metadata
-        let event_type_owned = event_type.to_string();
-        match meta.streams.get_mut(&event_type_owned) {
-            Some(stream_meta) => stream_meta.update(sequence, timestamp),
-            None => {
-                meta.streams
-                    .insert(event_type_owned, StreamMeta::new(sequence, timestamp));
-            }
-        }
-
-        // Update metadata
-        meta.next_sequence = sequence + 1;
-        meta.head_hash = hash;
-        self.put(meta_key, to_stored_value(&meta)?)?;
-
-        Ok(sequence)
+        let mut meta = load_meta(self, &ns)?.unwrap_or_default();
+        let (event, _) = append_in(self, &ns, &mut meta, event_type, payload)?;
+        Ok(event.sequence)
     }
 
     fn event_get(&mut self, sequence: u64) -> StrataResult<Option<Value>> {
@@ -1045,6 +1548,399 @@ mod tests {
         assert!(nonexistent.is_empty());
     }
 
+    // ========== Retention Tests ==========
+
+    #[test]
+    fn test_retention_max_len_trims_on_append() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+
+        for i in 0..5 {
+            log.append(&branch_id, "default", "trimmed", int_payload(i))
+                .unwrap();
+            log.append(&branch_id, "default", "other", int_payload(i))
+                .unwrap();
+        }
+
+        // Setting the policy trims the stream right away
+        let trimmed = log
+            .set_retention(
+                &branch_id,
+                "default",
+                "trimmed",
+                RetentionPolicy::KeepLast(3),
+            )
+            .unwrap();
+        assert_eq!(trimmed, 2);
+
+        log.append(&branch_id, "default", "trimmed", int_payload(5))
+            .unwrap();
+
+        let events = log.get_by_type(&branch_id, "default", "trimmed").unwrap();
+        let values: Vec<_> = events.iter().map(|e| e.value.payload.clone()).collect();
+        assert_eq!(values, vec![int_payload(3), int_payload(4), int_payload(5)]);
+
+        // Other streams and the global sequence are untouched
+        assert_eq!(
+            log.get_by_type(&branch_id, "default", "other")
+                .unwrap()
+                .len(),
+            5
+        );
+        assert_eq!(log.len(&branch_id, "default").unwrap(), 11);
+        assert!(log.get(&branch_id, "default", 0).unwrap().is_none());
+        assert!(log.get(&branch_id, "default", 1).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_retention_max_age_apply() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+
+        log.append(&branch_id, "default", "stale", empty_payload())
+            .unwrap();
+        let trimmed = log
+            .set_retention(
+                &branch_id,
+                "default",
+                "stale",
+                RetentionPolicy::KeepFor(std::time::Duration::from_millis(200)),
+            )
+            .unwrap();
+        assert_eq!(trimmed, 0);
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        assert_eq!(log.apply_retention(&branch_id, "default").unwrap(), 1);
+        assert!(log
+            .get_by_type(&branch_id, "default", "stale")
+            .unwrap()
+            .is_empty());
+        assert_eq!(log.apply_retention(&branch_id, "default").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_retention_policy_roundtrip_and_validation() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+
+        assert_eq!(
+            log.retention(&branch_id, "default", "test").unwrap(),
+            RetentionPolicy::KeepAll
+        );
+        log.set_retention(&branch_id, "default", "test", RetentionPolicy::KeepLast(10))
+            .unwrap();
+        assert_eq!(
+            log.retention(&branch_id, "default", "test").unwrap(),
+            RetentionPolicy::KeepLast(10)
+        );
+        log.set_retention(&branch_id, "default", "test", RetentionPolicy::KeepAll)
+            .unwrap();
+        assert_eq!(
+            log.retention(&branch_id, "default", "test").unwrap(),
+            RetentionPolicy::KeepAll
+        );
+
+        assert!(log
+            .set_retention(&branch_id, "default", "test", RetentionPolicy::KeepLast(0))
+            .is_err());
+        assert!(log
+            .set_retention(
+                &branch_id,
+                "default",
+                "test",
+                RetentionPolicy::KeepFor(std::time::Duration::ZERO)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_retention_truncation_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            let log = EventLog::new(db.clone());
+            for i in 0..4 {
+                log.append(&branch_id, "default", "test", int_payload(i))
+                    .unwrap();
+            }
+            log.set_retention(&branch_id, "default", "test", RetentionPolicy::KeepLast(2))
+                .unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let log = EventLog::new(db.clone());
+        assert!(log.get(&branch_id, "default", 0).unwrap().is_none());
+        assert_eq!(
+            log.get_by_type(&branch_id, "default", "test")
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            log.retention(&branch_id, "default", "test").unwrap(),
+            RetentionPolicy::KeepLast(2)
+        );
+
+        // The policy keeps applying after recovery
+        log.append(&branch_id, "default", "test", int_payload(4))
+            .unwrap();
+        assert_eq!(
+            log.get_by_type(&branch_id, "default", "test")
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    // ========== Payload Schema Tests ==========
+
+    fn order_schema() -> Value {
+        Value::try_from(&serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"id": {"type": "integer"}}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_schema_validates_appends() {
+        let (_temp, db, log) = setup();
+        let branch_id = BranchId::new();
+        log.append(&branch_id, "default", "order", empty_payload())
+            .unwrap();
+
+        log.set_schema(&branch_id, "default", "order", order_schema())
+            .unwrap();
+        assert_eq!(
+            log.schema(&branch_id, "default", "order").unwrap(),
+            Some(order_schema())
+        );
+
+        let err = log
+            .append(
+                &branch_id,
+                "default",
+                "order",
+                payload_with("id", Value::from("7")),
+            )
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("$.id: expected \"integer\", found string"));
+        log.append(
+            &branch_id,
+            "default",
+            "order",
+            payload_with("id", Value::Int(7)),
+        )
+        .unwrap();
+        // Other streams and earlier events are unaffected
+        log.append(&branch_id, "default", "refund", empty_payload())
+            .unwrap();
+        assert_eq!(log.len(&branch_id, "default").unwrap(), 3);
+
+        // Transactional appends are checked too
+        let result = db.transaction(branch_id, |txn| {
+            txn.event_append("order", empty_payload()).map(|_| ())
+        });
+        assert!(result.is_err());
+
+        log.set_schema(&branch_id, "default", "order", Value::Null)
+            .unwrap();
+        assert_eq!(log.schema(&branch_id, "default", "order").unwrap(), None);
+        log.append(&branch_id, "default", "order", empty_payload())
+            .unwrap();
+
+        assert!(log
+            .set_schema(
+                &branch_id,
+                "default",
+                "order",
+                Value::try_from(&serde_json::json!({"type": "decimal"})).unwrap()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_schema_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            EventLog::new(db.clone())
+                .set_schema(&branch_id, "default", "order", order_schema())
+                .unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let log = EventLog::new(db.clone());
+        assert_eq!(
+            log.schema(&branch_id, "default", "order").unwrap(),
+            Some(order_schema())
+        );
+        assert!(log
+            .append(&branch_id, "default", "order", empty_payload())
+            .is_err());
+    }
+
+    // ========== Consumer Group Tests ==========
+
+    #[test]
+    fn test_consumer_group_delivers_each_event_once() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+        for i in 0..5 {
+            log.append(&branch_id, "default", "jobs", int_payload(i))
+                .unwrap();
+            log.append(&branch_id, "default", "other", int_payload(i))
+                .unwrap();
+        }
+
+        assert!(log
+            .create_group(&branch_id, "default", "jobs", "workers")
+            .unwrap());
+        assert!(!log
+            .create_group(&branch_id, "default", "jobs", "workers")
+            .unwrap());
+
+        let first = log
+            .read_group(&branch_id, "default", "jobs", "workers", "w1", 3)
+            .unwrap();
+        let second = log
+            .read_group(&branch_id, "default", "jobs", "workers", "w2", 3)
+            .unwrap();
+        let seqs = |events: &[Versioned<Event>]| -> Vec<u64> {
+            events.iter().map(|e| e.value.sequence).collect()
+        };
+        assert_eq!(seqs(&first), vec![0, 2, 4]);
+        assert_eq!(seqs(&second), vec![6, 8]);
+        assert!(log
+            .read_group(&branch_id, "default", "jobs", "workers", "w1", 3)
+            .unwrap()
+            .is_empty());
+
+        // Another group reads the stream independently
+        log.create_group(&branch_id, "default", "jobs", "audit")
+            .unwrap();
+        assert_eq!(
+            log.read_group(&branch_id, "default", "jobs", "audit", "a1", 10)
+                .unwrap()
+                .len(),
+            5
+        );
+
+        let pending = log
+            .pending(&branch_id, "default", "jobs", "workers")
+            .unwrap();
+        assert_eq!(pending.len(), 5);
+        assert_eq!(pending[3].consumer, "w2");
+    }
+
+    #[test]
+    fn test_consumer_group_ack_clears_pending() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+        for i in 0..3 {
+            log.append(&branch_id, "default", "jobs", int_payload(i))
+                .unwrap();
+        }
+        log.create_group(&branch_id, "default", "jobs", "workers")
+            .unwrap();
+        log.read_group(&branch_id, "default", "jobs", "workers", "w1", 10)
+            .unwrap();
+
+        // Unknown and repeated sequences are not counted
+        assert_eq!(
+            log.ack(&branch_id, "default", "jobs", "workers", &[0, 2, 99])
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            log.ack(&branch_id, "default", "jobs", "workers", &[0])
+                .unwrap(),
+            0
+        );
+        let pending = log
+            .pending(&branch_id, "default", "jobs", "workers")
+            .unwrap();
+        assert_eq!(
+            pending.iter().map(|p| p.sequence).collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_consumer_group_errors() {
+        let (_temp, _db, log) = setup();
+        let branch_id = BranchId::new();
+
+        assert!(log
+            .read_group(&branch_id, "default", "jobs", "missing", "w1", 1)
+            .is_err());
+        assert!(log
+            .ack(&branch_id, "default", "jobs", "missing", &[0])
+            .is_err());
+        assert!(log
+            .pending(&branch_id, "default", "jobs", "missing")
+            .is_err());
+        assert!(log.create_group(&branch_id, "default", "jobs", "").is_err());
+
+        log.create_group(&branch_id, "default", "jobs", "workers")
+            .unwrap();
+        assert!(log
+            .read_group(&branch_id, "default", "jobs", "workers", "", 1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_consumer_group_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(&db_path).unwrap();
+            let log = EventLog::new(db.clone());
+            for i in 0..3 {
+                log.append(&branch_id, "default", "jobs", int_payload(i))
+                    .unwrap();
+            }
+            log.create_group(&branch_id, "default", "jobs", "workers")
+                .unwrap();
+            log.read_group(&branch_id, "default", "jobs", "workers", "w1", 2)
+                .unwrap();
+            log.ack(&branch_id, "default", "jobs", "workers", &[0])
+                .unwrap();
+            db.flush().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let log = EventLog::new(db.clone());
+        let pending = log
+            .pending(&branch_id, "default", "jobs", "workers")
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(
+            (pending[0].sequence, pending[0].consumer.as_str()),
+            (1, "w1")
+        );
+
+        // Delivery resumes after the last delivered event
+        let next = log
+            .read_group(&branch_id, "default", "jobs", "workers", "w2", 10)
+            .unwrap();
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].value.sequence, 2);
+    }
+
     // ========== EventLogExt Tests ==========
 
     #[test]
@@ -19,6 +19,18 @@
 //!
 //! All operations go through `db.transaction()` for consistency:
 //! - `create`, `get`, `set`, `delete_at_path`, `destroy`, `list`, `exists`
+//! - `get_paths` to read several subtrees of a document at once
+//! - `merge` (RFC 7396 merge patch) and `patch` (RFC 6902 JSON Patch)
+//! - `array_append`, `array_insert`, `array_remove`, `array_len` on arrays
+//!   inside a document
+//!
+//! ## Schemas
+//!
+//! A JSON schema can be set on a document id prefix with `set_schema`
+//! (key format: `__jsonschema__{prefix}`). Writes that would leave a
+//! document not matching the schema of the longest prefix of its id are
+//! rejected inside the write transaction. `validate` runs the same check
+//! without writing and reports every violation.
 //!
 //! ## Architectural Rules
 //!
@@ -30,6 +42,7 @@
 //! 5. WAL remains unified (entry types 0x20-0x23)
 //! 6. JSON API feels like other primitives
 
+use super::json_schema::{self, SchemaViolation};
 use crate::database::Database;
 use crate::primitives::extensions::JsonStoreExt;
 use serde::{Deserialize, Serialize};
@@ -37,8 +50,10 @@ use std::sync::Arc;
 use std::time::SystemTime;
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Timestamp, Version, Versioned};
+use strata_core::key::ensure_not_reserved;
 use strata_core::primitives::json::{
-    delete_at_path, get_at_path, set_at_path, JsonLimitError, JsonPath, JsonValue,
+    apply_json_patch, delete_at_path, get_at_path, get_at_path_mut, merge_patch, set_at_path,
+    JsonLimitError, JsonPath, JsonPathError, JsonValue, PatchOperation,
 };
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
@@ -54,6 +69,81 @@ fn limit_error_to_error(e: JsonLimitError) -> StrataError {
     StrataError::invalid_input(e.to_string())
 }
 
+// =============================================================================
+// Array Helpers
+// =============================================================================
+
+/// Error for an array operation on a path that doesn't hold an array
+fn not_an_array(found: &serde_json::Value) -> StrataError {
+    let found = match found {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    };
+    path_error(JsonPathError::TypeMismatch {
+        expected: "array",
+        found,
+    })
+}
+
+/// Convert a JsonPathError to a StrataError
+fn path_error(e: JsonPathError) -> StrataError {
+    StrataError::invalid_input(format!("Path error: {}", e))
+}
+
+// =============================================================================
+// Schema Helpers
+// =============================================================================
+
+/// The schema set on the longest prefix of `doc_id`, with that prefix.
+pub(crate) fn governing_schema(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    doc_id: &str,
+) -> StrataResult<Option<(String, Value)>> {
+    let scan = Key::new_json_schema(ns.clone(), "");
+    Ok(txn
+        .scan_prefix(&scan)?
+        .into_iter()
+        .filter_map(|(key, schema)| {
+            let prefix = String::from_utf8(key.user_key[scan.user_key.len()..].to_vec()).ok()?;
+            doc_id.starts_with(&prefix).then_some((prefix, schema))
+        })
+        .max_by_key(|(prefix, _)| prefix.len()))
+}
+
+/// Reject `doc` if a schema governs `doc_id` and it doesn't match.
+pub(crate) fn check_schema(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    doc_id: &str,
+    doc: &JsonValue,
+) -> StrataResult<()> {
+    match governing_schema(txn, ns, doc_id)? {
+        Some((prefix, schema)) => check_against(&prefix, &schema, doc),
+        None => Ok(()),
+    }
+}
+
+/// `doc` as a [`Value`], for checking against a schema
+fn doc_value(doc: &JsonValue) -> StrataResult<Value> {
+    Value::try_from(doc.as_inner())
+        .map_err(|e| StrataError::invalid_input(format!("document is not a valid value: {}", e)))
+}
+
+/// Reject `doc` if it doesn't match `schema`, set on `prefix`.
+pub(crate) fn check_against(prefix: &str, schema: &Value, doc: &JsonValue) -> StrataResult<()> {
+    json_schema::check(schema, &doc_value(doc)?).map_err(|e| {
+        StrataError::invalid_input(format!(
+            "document does not match the schema for prefix '{}': {}",
+            prefix, e
+        ))
+    })
+}
+
 // =============================================================================
 // JsonDoc - Internal Document Representation
 // =============================================================================
@@ -249,6 +339,7 @@ impl JsonStore {
         doc_id: &str,
         value: JsonValue,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         // Validate document limits (Issue #440)
         value.validate().map_err(limit_error_to_error)?;
 
@@ -263,6 +354,7 @@ impl JsonStore {
                     doc_id
                 )));
             }
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
 
             let serialized = Self::serialize_doc(&doc)?;
             txn.put(key.clone(), serialized)?;
@@ -311,6 +403,42 @@ impl JsonStore {
         })
     }
 
+    /// Get the values at several paths in a document
+    ///
+    /// Reads the document once and copies out only the requested subtrees,
+    /// so callers needing a few fields of a large document don't pay for
+    /// the rest of it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(values)` - One entry per path, in order; `None` where the path
+    ///   doesn't exist, and for every path if the document doesn't exist
+    /// * `Err` - On path limit or deserialization error
+    pub fn get_paths(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        paths: &[JsonPath],
+    ) -> StrataResult<Vec<Option<JsonValue>>> {
+        for path in paths {
+            path.validate().map_err(limit_error_to_error)?;
+        }
+
+        let key = self.key_for(branch_id, space, doc_id);
+
+        self.db.transaction(*branch_id, |txn| match txn.get(&key)? {
+            Some(value) => {
+                let doc = Self::deserialize_doc(&value)?;
+                Ok(paths
+                    .iter()
+                    .map(|path| get_at_path(&doc.value, path).cloned())
+                    .collect())
+            }
+            None => Ok(vec![None; paths.len()]),
+        })
+    }
+
     /// Get value at path in a document, with version metadata.
     ///
     /// Reads directly from the committed store (non-transactional) to
@@ -388,6 +516,7 @@ impl JsonStore {
         path: &JsonPath,
         value: JsonValue,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         path.validate().map_err(limit_error_to_error)?;
         value.validate().map_err(limit_error_to_error)?;
 
@@ -400,6 +529,7 @@ impl JsonStore {
                     let mut doc = Self::deserialize_doc(&stored)?;
                     set_at_path(&mut doc.value, path, value)
                         .map_err(|e| StrataError::invalid_input(format!("Path error: {}", e)))?;
+                    check_schema(txn, &key.namespace, doc_id, &doc.value)?;
                     doc.touch();
                     let serialized = Self::serialize_doc(&doc)?;
                     txn.put(key.clone(), serialized)?;
@@ -416,6 +546,7 @@ impl JsonStore {
                         })?;
                         obj
                     };
+                    check_schema(txn, &key.namespace, doc_id, &initial)?;
                     let doc = JsonDoc::new(doc_id, initial);
                     let serialized = Self::serialize_doc(&doc)?;
                     txn.put(key.clone(), serialized)?;
@@ -462,6 +593,7 @@ impl JsonStore {
         path: &JsonPath,
         value: JsonValue,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         // Validate path and value limits (Issue #440)
         path.validate().map_err(limit_error_to_error)?;
         value.validate().map_err(limit_error_to_error)?;
@@ -478,6 +610,7 @@ impl JsonStore {
             // Apply mutation
             set_at_path(&mut doc.value, path, value)
                 .map_err(|e| StrataError::invalid_input(format!("Path error: {}", e)))?;
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
             doc.touch();
 
             // Store updated document
@@ -518,6 +651,7 @@ impl JsonStore {
         doc_id: &str,
         path: &JsonPath,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         // Validate path limits (Issue #440)
         path.validate().map_err(limit_error_to_error)?;
 
@@ -533,6 +667,7 @@ impl JsonStore {
             // Apply deletion
             delete_at_path(&mut doc.value, path)
                 .map_err(|e| StrataError::invalid_input(format!("Path error: {}", e)))?;
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
             doc.touch();
 
             // Store updated document
@@ -543,6 +678,245 @@ impl JsonStore {
         })
     }
 
+    /// Apply an RFC 7396 JSON Merge Patch to a document
+    ///
+    /// Members of `patch` replace those of the document, objects merge
+    /// recursively, and `null` members remove the field. A missing document
+    /// is created from the patch, with its nulls dropped. Runs as a single
+    /// transaction.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Version)` - Document version after the merge
+    /// * `Err(InvalidInput)` - The result exceeds document limits or doesn't
+    ///   match the document's schema
+    pub fn merge(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        patch: JsonValue,
+    ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
+        patch.validate().map_err(limit_error_to_error)?;
+
+        let key = self.key_for(branch_id, space, doc_id);
+
+        self.db.transaction(*branch_id, |txn| {
+            let mut doc = match txn.get(&key)? {
+                Some(stored) => {
+                    let mut doc = Self::deserialize_doc(&stored)?;
+                    doc.touch();
+                    doc
+                }
+                None => JsonDoc::new(doc_id, JsonValue::null()),
+            };
+
+            merge_patch(&mut doc.value, &patch);
+            doc.value.validate().map_err(limit_error_to_error)?;
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
+
+            let serialized = Self::serialize_doc(&doc)?;
+            txn.put(key.clone(), serialized)?;
+
+            Ok(Version::counter(doc.version))
+        })
+    }
+
+    /// Apply an RFC 6902 JSON Patch to a document
+    ///
+    /// The operations are applied in order, in a single transaction: if any
+    /// fails, including a `test`, the document is left unchanged.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Version)` - Document version after the patch
+    /// * `Err(InvalidInput)` - Document doesn't exist, an operation failed,
+    ///   or the result exceeds document limits or doesn't match the
+    ///   document's schema
+    pub fn patch(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        ops: &[PatchOperation],
+    ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
+
+        let key = self.key_for(branch_id, space, doc_id);
+
+        self.db.transaction(*branch_id, |txn| {
+            let stored = txn.get(&key)?.ok_or_else(|| {
+                StrataError::invalid_input(format!("JSON document {} not found", doc_id))
+            })?;
+            let mut doc = Self::deserialize_doc(&stored)?;
+
+            apply_json_patch(&mut doc.value, ops)
+                .map_err(|e| StrataError::invalid_input(format!("Patch error: {}", e)))?;
+            doc.value.validate().map_err(limit_error_to_error)?;
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
+            doc.touch();
+
+            let serialized = Self::serialize_doc(&doc)?;
+            txn.put(key.clone(), serialized)?;
+
+            Ok(Version::counter(doc.version))
+        })
+    }
+
+    /// Append a value to the array at `path` in a document
+    ///
+    /// If nothing exists at `path` yet, an array holding just `value` is
+    /// created there.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(len)` - Length of the array after the append
+    /// * `Err(InvalidInput)` - Document doesn't exist, or `path` holds
+    ///   something other than an array
+    pub fn array_append(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        path: &JsonPath,
+        value: JsonValue,
+    ) -> StrataResult<usize> {
+        value.validate().map_err(limit_error_to_error)?;
+        self.update_array(branch_id, space, doc_id, path, true, |arr| {
+            arr.push(value.into_inner());
+            Ok(arr.len())
+        })
+    }
+
+    /// Insert a value into the array at `path` before `index`
+    ///
+    /// An `index` equal to the array length appends.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(len)` - Length of the array after the insert
+    /// * `Err(InvalidInput)` - Document or array doesn't exist, or `index`
+    ///   is past the end of the array
+    pub fn array_insert(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        path: &JsonPath,
+        index: usize,
+        value: JsonValue,
+    ) -> StrataResult<usize> {
+        value.validate().map_err(limit_error_to_error)?;
+        self.update_array(branch_id, space, doc_id, path, false, |arr| {
+            if index > arr.len() {
+                return Err(path_error(JsonPathError::IndexOutOfBounds {
+                    index,
+                    len: arr.len(),
+                }));
+            }
+            arr.insert(index, value.into_inner());
+            Ok(arr.len())
+        })
+    }
+
+    /// Remove the element at `index` from the array at `path`
+    ///
+    /// Later elements shift down by one.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(value)` - The removed element
+    /// * `Err(InvalidInput)` - Document or array doesn't exist, or `index`
+    ///   is out of bounds
+    pub fn array_remove(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        path: &JsonPath,
+        index: usize,
+    ) -> StrataResult<JsonValue> {
+        self.update_array(branch_id, space, doc_id, path, false, |arr| {
+            if index >= arr.len() {
+                return Err(path_error(JsonPathError::IndexOutOfBounds {
+                    index,
+                    len: arr.len(),
+                }));
+            }
+            Ok(JsonValue::from_value(arr.remove(index)))
+        })
+    }
+
+    /// Get the length of the array at `path` in a document
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(len))` - Length of the array
+    /// * `Ok(None)` - Document doesn't exist or path not found
+    /// * `Err(InvalidInput)` - `path` holds something other than an array
+    pub fn array_len(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        path: &JsonPath,
+    ) -> StrataResult<Option<usize>> {
+        match self.get(branch_id, space, doc_id, path)? {
+            Some(value) => match value.as_inner().as_array() {
+                Some(arr) => Ok(Some(arr.len())),
+                None => Err(not_an_array(value.as_inner())),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Read-modify-write the array at `path` in a single transaction
+    ///
+    /// With `create_missing`, an empty array is first set at `path` if
+    /// nothing is there. The updated document is checked against document
+    /// limits and its schema before it is stored.
+    fn update_array<T>(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        path: &JsonPath,
+        create_missing: bool,
+        f: impl FnOnce(&mut Vec<serde_json::Value>) -> StrataResult<T>,
+    ) -> StrataResult<T> {
+        ensure_not_reserved(doc_id)?;
+        path.validate().map_err(limit_error_to_error)?;
+
+        let key = self.key_for(branch_id, space, doc_id);
+
+        self.db.transaction(*branch_id, |txn| {
+            let stored = txn.get(&key)?.ok_or_else(|| {
+                StrataError::invalid_input(format!("JSON document {} not found", doc_id))
+            })?;
+            let mut doc = Self::deserialize_doc(&stored)?;
+
+            if create_missing && get_at_path(&doc.value, path).is_none() {
+                set_at_path(&mut doc.value, path, JsonValue::array()).map_err(path_error)?;
+            }
+            let target = get_at_path_mut(&mut doc.value, path)
+                .ok_or_else(|| path_error(JsonPathError::NotFound))?;
+            let result = match target.as_inner_mut() {
+                serde_json::Value::Array(arr) => f(arr)?,
+                other => return Err(not_an_array(other)),
+            };
+
+            doc.value.validate().map_err(limit_error_to_error)?;
+            check_schema(txn, &key.namespace, doc_id, &doc.value)?;
+            doc.touch();
+
+            let serialized = Self::serialize_doc(&doc)?;
+            txn.put(key.clone(), serialized)?;
+
+            Ok(result)
+        })
+    }
+
     /// Destroy (delete) an entire document
     ///
     /// Removes the document from storage. This operation is final.
@@ -565,6 +939,7 @@ impl JsonStore {
     /// assert!(existed);
     /// ```
     pub fn destroy(&self, branch_id: &BranchId, space: &str, doc_id: &str) -> StrataResult<bool> {
+        ensure_not_reserved(doc_id)?;
         let key = self.key_for(branch_id, space, doc_id);
 
         self.db.transaction(*branch_id, |txn| {
@@ -579,6 +954,89 @@ impl JsonStore {
         })
     }
 
+    // ========================================================================
+    // Schemas
+    // ========================================================================
+
+    /// Set the JSON schema documents whose ids start with `prefix` must match
+    ///
+    /// An empty prefix covers every document in the space. When schemas are
+    /// set on several prefixes of an id, the longest one applies. Writes
+    /// that would leave a document not matching it fail; documents already
+    /// stored are not rechecked. `Value::Null` removes the schema.
+    ///
+    /// # Errors
+    /// - `ReservedKey` if `prefix` starts with a reserved prefix
+    /// - `InvalidInput` if `schema` uses a keyword outside the supported
+    ///   subset incorrectly
+    pub fn set_schema(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: &str,
+        schema: Value,
+    ) -> StrataResult<()> {
+        ensure_not_reserved(prefix)?;
+        if !schema.is_null() {
+            json_schema::validate_schema(&schema)?;
+        }
+
+        let schema_key = Key::new_json_schema(self.namespace_for(branch_id, space), prefix);
+        self.db.transaction(*branch_id, |txn| {
+            if schema.is_null() {
+                txn.delete(schema_key.clone())
+            } else {
+                txn.put(schema_key.clone(), schema.clone())
+            }
+        })
+    }
+
+    /// Get the JSON schema set on exactly `prefix`, if any
+    pub fn schema(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: &str,
+    ) -> StrataResult<Option<Value>> {
+        let schema_key = Key::new_json_schema(self.namespace_for(branch_id, space), prefix);
+        self.db.transaction(*branch_id, |txn| txn.get(&schema_key))
+    }
+
+    /// List the JSON schemas of a space with the prefix each governs
+    pub fn schemas(&self, branch_id: &BranchId, space: &str) -> StrataResult<Vec<(String, Value)>> {
+        let scan = Key::new_json_schema(self.namespace_for(branch_id, space), "");
+        self.db.transaction(*branch_id, |txn| {
+            Ok(txn
+                .scan_prefix(&scan)?
+                .into_iter()
+                .map(|(key, schema)| {
+                    let prefix = String::from_utf8_lossy(&key.user_key[scan.user_key.len()..]);
+                    (prefix.into_owned(), schema)
+                })
+                .collect())
+        })
+    }
+
+    /// Check `value` against the schema governing `doc_id` without writing
+    ///
+    /// Returns every violation with its path, or an empty list if `value`
+    /// matches or no schema governs `doc_id`.
+    pub fn validate(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        doc_id: &str,
+        value: &JsonValue,
+    ) -> StrataResult<Vec<SchemaViolation>> {
+        let ns = self.namespace_for(branch_id, space);
+        self.db.transaction(*branch_id, |txn| {
+            Ok(match governing_schema(txn, &ns, doc_id)? {
+                Some((_, schema)) => json_schema::violations(&schema, &doc_value(value)?),
+                None => Vec::new(),
+            })
+        })
+    }
+
     // ========================================================================
     // Introspection
     // ========================================================================
@@ -588,6 +1046,11 @@ impl JsonStore {
     /// Supports Primitive Contract Invariant 6: Introspectable.
     /// Returns document IDs for a branch, optionally filtered by prefix.
     ///
+    /// IDs are returned in lexicographic (byte-wise) order. A page resumes at
+    /// the first ID greater than the cursor, so pagination stays consistent
+    /// when documents are created or destroyed between calls, including the
+    /// cursor document itself.
+    ///
     /// # Arguments
     ///
     /// * `branch_id` - BranchId for namespace isolation
@@ -624,16 +1087,13 @@ impl JsonStore {
         limit: usize,
     ) -> StrataResult<JsonListResult> {
         let ns = self.namespace_for(branch_id, space);
-        let scan_prefix = Key::new_json_prefix(ns);
+        // Document keys are the doc IDs, so scanning from the prefix key both
+        // filters by prefix and yields IDs in order.
+        let scan_prefix = Key::new_json(ns, prefix.unwrap_or(""));
 
         self.db.transaction(*branch_id, |txn| {
             let mut doc_ids = Vec::with_capacity(limit + 1);
 
-            // Cursor is now a simple string key (no UUID parsing needed)
-            let cursor_doc_id: Option<&str> = cursor;
-
-            let mut past_cursor = cursor_doc_id.is_none();
-
             for (_key, value) in txn.scan_prefix(&scan_prefix)? {
                 // Deserialize to get doc_id
                 let doc = match Self::deserialize_doc(&value) {
@@ -641,21 +1101,11 @@ impl JsonStore {
                     Err(_) => continue, // Skip invalid documents
                 };
 
-                // Handle cursor: skip until we're past the cursor
-                if !past_cursor {
-                    if cursor_doc_id == Some(doc.id.as_str()) {
-                        past_cursor = true;
-                    }
+                // Handle cursor: skip everything up to and including it
+                if cursor.is_some_and(|c| doc.id.as_str() <= c) {
                     continue;
                 }
 
-                // Apply prefix filter if specified
-                if let Some(p) = prefix {
-                    if !doc.id.starts_with(p) {
-                        continue;
-                    }
-                }
-
                 doc_ids.push(doc.id);
 
                 // Collect limit + 1 to detect if there are more
@@ -705,7 +1155,7 @@ impl JsonStore {
         }
     }
 
-    /// List document IDs as of a past timestamp.
+    /// List document IDs as of a past timestamp, in lexicographic order.
     pub fn list_at(
         &self,
         branch_id: &BranchId,
@@ -778,6 +1228,7 @@ impl JsonStoreExt for TransactionContext {
         path: &JsonPath,
         value: JsonValue,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         // Validate path and value limits (Issue #440)
         path.validate().map_err(limit_error_to_error)?;
         value.validate().map_err(limit_error_to_error)?;
@@ -793,6 +1244,7 @@ impl JsonStoreExt for TransactionContext {
         // Apply mutation
         set_at_path(&mut doc.value, path, value)
             .map_err(|e| StrataError::invalid_input(format!("Path error: {}", e)))?;
+        check_schema(self, &key.namespace, doc_id, &doc.value)?;
         doc.touch();
 
         // Store updated document in transaction write set
@@ -803,6 +1255,7 @@ impl JsonStoreExt for TransactionContext {
     }
 
     fn json_create(&mut self, doc_id: &str, value: JsonValue) -> StrataResult<Version> {
+        ensure_not_reserved(doc_id)?;
         // Validate document limits (Issue #440)
         value.validate().map_err(limit_error_to_error)?;
 
@@ -816,6 +1269,7 @@ impl JsonStoreExt for TransactionContext {
                 doc_id
             )));
         }
+        check_schema(self, &key.namespace, doc_id, &doc.value)?;
 
         // Store new document
         let serialized = JsonStore::serialize_doc(&doc)?;
@@ -1844,4 +2298,384 @@ mod tests {
         assert!(!store.destroy(&branch_id, "default", &doc_id).unwrap());
         assert!(!store.destroy(&branch_id, "default", &doc_id).unwrap());
     }
+
+    // ========================================
+    // Schema Tests
+    // ========================================
+
+    fn order_schema() -> Value {
+        Value::try_from(&serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"id": {"type": "integer"}, "status": {"type": "string"}}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_schema_validates_writes_under_prefix() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        store
+            .set_schema(&branch_id, "default", "order:", order_schema())
+            .unwrap();
+
+        let bad: JsonValue = serde_json::json!({"status": "new"}).into();
+        let err = store
+            .create(&branch_id, "default", "order:1", bad.clone())
+            .unwrap_err();
+        assert!(err.to_string().contains("$.id: missing required field"));
+        // Ids outside the prefix are not checked
+        store
+            .create(&branch_id, "default", "note:1", bad.clone())
+            .unwrap();
+
+        let good: JsonValue = serde_json::json!({"id": 1}).into();
+        store
+            .create(&branch_id, "default", "order:1", good)
+            .unwrap();
+        let status = "status".parse().unwrap();
+        store
+            .set(&branch_id, "default", "order:1", &status, "new".into())
+            .unwrap();
+        assert!(store
+            .set(&branch_id, "default", "order:1", &status, 7i64.into())
+            .is_err());
+        assert!(store
+            .delete_at_path(&branch_id, "default", "order:1", &"id".parse().unwrap())
+            .is_err());
+        assert!(store
+            .set_or_create(&branch_id, "default", "order:2", &status, "new".into())
+            .is_err());
+
+        // Listing skips the schema entry
+        let ids = store
+            .list(&branch_id, "default", None, None, 10)
+            .unwrap()
+            .doc_ids;
+        assert_eq!(ids, ["note:1", "order:1"]);
+
+        // Removing the schema lifts the check
+        store
+            .set_schema(&branch_id, "default", "order:", Value::Null)
+            .unwrap();
+        store.create(&branch_id, "default", "order:3", bad).unwrap();
+        assert!(store.schemas(&branch_id, "default").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_longest_prefix_schema_applies() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let any_object = Value::try_from(&serde_json::json!({"type": "object"})).unwrap();
+        store
+            .set_schema(&branch_id, "default", "", any_object.clone())
+            .unwrap();
+        store
+            .set_schema(&branch_id, "default", "order:", order_schema())
+            .unwrap();
+
+        let doc: JsonValue = serde_json::json!({"status": 1}).into();
+        store
+            .create(&branch_id, "default", "user:1", doc.clone())
+            .unwrap();
+        let found = store
+            .validate(&branch_id, "default", "order:1", &doc)
+            .unwrap();
+        let found: Vec<String> = found.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            [
+                "$.id: missing required field",
+                "$.status: expected \"string\", found integer"
+            ]
+        );
+        assert!(!store.exists(&branch_id, "default", "order:1").unwrap());
+
+        assert_eq!(
+            store.schema(&branch_id, "default", "").unwrap(),
+            Some(any_object)
+        );
+        let prefixes: Vec<String> = store
+            .schemas(&branch_id, "default")
+            .unwrap()
+            .into_iter()
+            .map(|(prefix, _)| prefix)
+            .collect();
+        assert_eq!(prefixes, ["", "order:"]);
+    }
+
+    #[test]
+    fn test_set_schema_rejects_malformed_schema() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let bad = Value::try_from(&serde_json::json!({"type": "float"})).unwrap();
+        assert!(store.set_schema(&branch_id, "default", "", bad).is_err());
+        assert!(store
+            .set_schema(&branch_id, "default", "__idx_", order_schema())
+            .is_err());
+    }
+
+    // ========================================
+    // Merge / Patch Tests
+    // ========================================
+
+    #[test]
+    fn test_merge_updates_and_creates() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+
+        let patch: JsonValue = serde_json::json!({"name": "Ann", "temp": null}).into();
+        let v1 = store.merge(&branch_id, "default", "doc", patch).unwrap();
+        assert_eq!(v1, Version::counter(1));
+
+        let patch: JsonValue = serde_json::json!({"name": null, "cfg": {"a": 1}}).into();
+        let v2 = store.merge(&branch_id, "default", "doc", patch).unwrap();
+        assert_eq!(v2, Version::counter(2));
+        let doc = store
+            .get(&branch_id, "default", "doc", &JsonPath::root())
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc, serde_json::json!({"cfg": {"a": 1}}).into());
+    }
+
+    #[test]
+    fn test_patch_is_all_or_nothing() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let ops = |ops: serde_json::Value| -> Vec<PatchOperation> {
+            serde_json::from_value(ops).unwrap()
+        };
+
+        assert!(store
+            .patch(&branch_id, "default", "doc", &ops(serde_json::json!([])))
+            .is_err());
+        store
+            .create(
+                &branch_id,
+                "default",
+                "doc",
+                serde_json::json!({"n": 1}).into(),
+            )
+            .unwrap();
+
+        let failing = ops(serde_json::json!([
+            {"op": "add", "path": "/m", "value": 2},
+            {"op": "test", "path": "/n", "value": 5}
+        ]));
+        let err = store
+            .patch(&branch_id, "default", "doc", &failing)
+            .unwrap_err();
+        assert!(err.to_string().contains("test failed at '/n'"));
+
+        let version = store
+            .patch(
+                &branch_id,
+                "default",
+                "doc",
+                &ops(serde_json::json!([
+                    {"op": "test", "path": "/n", "value": 1},
+                    {"op": "move", "from": "/n", "path": "/m"}
+                ])),
+            )
+            .unwrap();
+        assert_eq!(version, Version::counter(2));
+        let doc = store
+            .get(&branch_id, "default", "doc", &JsonPath::root())
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc, serde_json::json!({"m": 1}).into());
+    }
+
+    #[test]
+    fn test_merge_and_patch_check_schema() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        store
+            .set_schema(&branch_id, "default", "order:", order_schema())
+            .unwrap();
+        store
+            .create(
+                &branch_id,
+                "default",
+                "order:1",
+                serde_json::json!({"id": 1}).into(),
+            )
+            .unwrap();
+
+        let patch: JsonValue = serde_json::json!({"id": null}).into();
+        assert!(store
+            .merge(&branch_id, "default", "order:1", patch)
+            .is_err());
+        let ops: Vec<PatchOperation> = serde_json::from_value(serde_json::json!([
+            {"op": "replace", "path": "/id", "value": "1"}
+        ]))
+        .unwrap();
+        assert!(store.patch(&branch_id, "default", "order:1", &ops).is_err());
+    }
+
+    // ========================================
+    // Array Operation Tests
+    // ========================================
+
+    #[test]
+    fn test_array_append_insert_remove_len() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let turns: JsonPath = "turns".parse().unwrap();
+        store
+            .create(
+                &branch_id,
+                "default",
+                "chat",
+                serde_json::json!({"title": "t"}).into(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            store
+                .array_len(&branch_id, "default", "chat", &turns)
+                .unwrap(),
+            None
+        );
+        // Appending to a missing path creates the array
+        let len = store
+            .array_append(&branch_id, "default", "chat", &turns, JsonValue::from("b"))
+            .unwrap();
+        assert_eq!(len, 1);
+        let len = store
+            .array_insert(
+                &branch_id,
+                "default",
+                "chat",
+                &turns,
+                0,
+                JsonValue::from("a"),
+            )
+            .unwrap();
+        assert_eq!(len, 2);
+        let len = store
+            .array_append(&branch_id, "default", "chat", &turns, JsonValue::from("c"))
+            .unwrap();
+        assert_eq!(len, 3);
+
+        let removed = store
+            .array_remove(&branch_id, "default", "chat", &turns, 1)
+            .unwrap();
+        assert_eq!(removed, JsonValue::from("b"));
+        assert_eq!(
+            store
+                .array_len(&branch_id, "default", "chat", &turns)
+                .unwrap(),
+            Some(2)
+        );
+        let doc = store
+            .get(&branch_id, "default", "chat", &JsonPath::root())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            doc,
+            serde_json::json!({"title": "t", "turns": ["a", "c"]}).into()
+        );
+    }
+
+    #[test]
+    fn test_array_operations_reject_bad_targets() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let title: JsonPath = "title".parse().unwrap();
+        let turns: JsonPath = "turns".parse().unwrap();
+
+        assert!(store
+            .array_append(&branch_id, "default", "chat", &turns, JsonValue::from(1i64))
+            .is_err());
+        store
+            .create(
+                &branch_id,
+                "default",
+                "chat",
+                serde_json::json!({"title": "t", "turns": [1]}).into(),
+            )
+            .unwrap();
+
+        let err = store
+            .array_append(&branch_id, "default", "chat", &title, JsonValue::from(1i64))
+            .unwrap_err();
+        assert!(err.to_string().contains("expected array, found string"));
+        assert!(store
+            .array_len(&branch_id, "default", "chat", &title)
+            .is_err());
+        assert!(store
+            .array_insert(
+                &branch_id,
+                "default",
+                "chat",
+                &turns,
+                2,
+                JsonValue::from(2i64)
+            )
+            .is_err());
+        assert!(store
+            .array_remove(&branch_id, "default", "chat", &turns, 1)
+            .is_err());
+        assert!(store
+            .array_remove(
+                &branch_id,
+                "default",
+                "chat",
+                &"missing".parse().unwrap(),
+                0
+            )
+            .is_err());
+
+        // Failed operations don't bump the version
+        let doc = store
+            .get_versioned(&branch_id, "default", "chat", &JsonPath::root())
+            .unwrap()
+            .unwrap();
+        assert_eq!(doc.version, Version::counter(1));
+    }
+
+    #[test]
+    fn test_get_paths_returns_requested_subtrees() {
+        let db = Database::cache().unwrap();
+        let store = JsonStore::new(db);
+        let branch_id = BranchId::new();
+        let paths: Vec<JsonPath> = ["user.name", "missing", "tags[1]", ""]
+            .iter()
+            .map(|p| p.parse().unwrap())
+            .collect();
+
+        assert_eq!(
+            store
+                .get_paths(&branch_id, "default", "doc", &paths)
+                .unwrap(),
+            vec![None; 4]
+        );
+
+        let doc = serde_json::json!({"user": {"name": "Ann", "bio": "..."}, "tags": ["a", "b"]});
+        store
+            .create(&branch_id, "default", "doc", doc.clone().into())
+            .unwrap();
+        let values = store
+            .get_paths(&branch_id, "default", "doc", &paths)
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![
+                Some(JsonValue::from("Ann")),
+                None,
+                Some(JsonValue::from("b")),
+                Some(doc.into()),
+            ]
+        );
+    }
 }
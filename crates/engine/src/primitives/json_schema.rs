@@ -0,0 +1,392 @@
+//! JSON Schema subset for validating event payloads and JSON documents.
+//!
+//! Supported keywords: `type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `const`, `minimum`, `maximum`,
+//! `minLength`, `maxLength`, `minItems` and `maxItems`. Other keywords
+//! (`$schema`, `title`, `description`, ...) are accepted and ignored.
+//!
+//! Types follow Strata's value model without coercion: `integer` matches
+//! only `Int`, `number` matches `Int` or `Float`.
+
+use serde::{Deserialize, Serialize};
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult};
+
+/// Type names accepted by the `type` keyword
+const TYPE_NAMES: &[&str] = &[
+    "null", "boolean", "integer", "number", "string", "array", "object",
+];
+
+/// Check that `schema` is a well-formed schema in the supported subset.
+///
+/// # Errors
+/// `InvalidInput` naming the first malformed keyword and where it is.
+pub(crate) fn validate_schema(schema: &Value) -> StrataResult<()> {
+    check_schema(schema, "$")
+        .map_err(|e| StrataError::invalid_input(format!("invalid schema: {}", e)))
+}
+
+fn check_schema(schema: &Value, at: &str) -> Result<(), String> {
+    let Value::Object(map) = schema else {
+        return Err(format!("{}: schema must be an object", at));
+    };
+    for (keyword, arg) in map {
+        match keyword.as_str() {
+            "type" => {
+                let names: Vec<&Value> = match arg {
+                    Value::Array(names) if !names.is_empty() => names.iter().collect(),
+                    other => vec![other],
+                };
+                for name in names {
+                    match name {
+                        Value::String(s) if TYPE_NAMES.contains(&s.as_str()) => {}
+                        _ => return Err(format!("{}: unknown type {}", at, describe(name))),
+                    }
+                }
+            }
+            "properties" => {
+                let Value::Object(props) = arg else {
+                    return Err(format!("{}: properties must be an object", at));
+                };
+                for (name, sub) in props {
+                    check_schema(sub, &format!("{}.properties.{}", at, name))?;
+                }
+            }
+            "required" => match arg {
+                Value::Array(names) if names.iter().all(|n| matches!(n, Value::String(_))) => {}
+                _ => return Err(format!("{}: required must be an array of strings", at)),
+            },
+            "additionalProperties" => match arg {
+                Value::Bool(_) => {}
+                sub => check_schema(sub, &format!("{}.additionalProperties", at))?,
+            },
+            "items" => check_schema(arg, &format!("{}.items", at))?,
+            "enum" if !matches!(arg, Value::Array(_)) => {
+                return Err(format!("{}: enum must be an array", at));
+            }
+            "minimum" | "maximum" if as_f64(arg).is_none() => {
+                return Err(format!("{}: {} must be a number", at, keyword));
+            }
+            "minLength" | "maxLength" | "minItems" | "maxItems" if !is_count(arg) => {
+                return Err(format!(
+                    "{}: {} must be a non-negative integer",
+                    at, keyword
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// A place where a value doesn't match its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON path of the offending value, `$` being the value itself
+    pub path: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Check `value` against a schema accepted by [`validate_schema`].
+///
+/// Returns every violation, in document order with object fields sorted by
+/// name. A value of the wrong type is reported once, without descending
+/// into it.
+pub(crate) fn violations(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut found = Vec::new();
+    collect(schema, value, "$", &mut found);
+    found
+}
+
+/// Like [`violations`], but stops at the first one.
+pub(crate) fn check(schema: &Value, value: &Value) -> Result<(), SchemaViolation> {
+    match violations(schema, value).into_iter().next() {
+        Some(violation) => Err(violation),
+        None => Ok(()),
+    }
+}
+
+fn collect(schema: &Value, value: &Value, at: &str, found: &mut Vec<SchemaViolation>) {
+    let Value::Object(map) = schema else {
+        return;
+    };
+    if let Some(ty) = map.get("type") {
+        let names: Vec<&Value> = match ty {
+            Value::Array(names) => names.iter().collect(),
+            other => vec![other],
+        };
+        if !names
+            .iter()
+            .any(|n| matches!(n, Value::String(s) if is_type(value, s)))
+        {
+            let expected: Vec<String> = names.iter().map(|n| describe(n)).collect();
+            let message = format!(
+                "expected {}, found {}",
+                expected.join(" or "),
+                json_type(value)
+            );
+            found.push(violation(at, message));
+            return;
+        }
+    }
+    if let Some(allowed) = map.get("const") {
+        if value != allowed {
+            found.push(violation(at, format!("expected {}", describe(allowed))));
+        }
+    }
+    if let Some(Value::Array(allowed)) = map.get("enum") {
+        if !allowed.contains(value) {
+            let options: Vec<String> = allowed.iter().map(describe).collect();
+            found.push(violation(
+                at,
+                format!("expected one of {}", options.join(", ")),
+            ));
+        }
+    }
+
+    match value {
+        Value::Int(_) | Value::Float(_) => {
+            let n = as_f64(value).unwrap_or_default();
+            if let Some(min) = map.get("minimum").and_then(as_f64) {
+                if n < min {
+                    found.push(violation(at, format!("{} is less than minimum {}", n, min)));
+                }
+            }
+            if let Some(max) = map.get("maximum").and_then(as_f64) {
+                if n > max {
+                    found.push(violation(
+                        at,
+                        format!("{} is greater than maximum {}", n, max),
+                    ));
+                }
+            }
+        }
+        Value::String(s) => {
+            let (min, max) = (map.get("minLength"), map.get("maxLength"));
+            if let Some(message) = check_len(min, max, s.chars().count(), "characters") {
+                found.push(violation(at, message));
+            }
+        }
+        Value::Array(items) => {
+            let (min, max) = (map.get("minItems"), map.get("maxItems"));
+            if let Some(message) = check_len(min, max, items.len(), "items") {
+                found.push(violation(at, message));
+            }
+            if let Some(item_schema) = map.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    collect(item_schema, item, &format!("{}[{}]", at, i), found);
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(Value::Array(required)) = map.get("required") {
+                for name in required {
+                    match name {
+                        Value::String(name) if !fields.contains_key(name) => {
+                            let path = format!("{}.{}", at, name);
+                            found.push(violation(&path, "missing required field".into()));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            let props = match map.get("properties") {
+                Some(Value::Object(props)) => Some(props),
+                _ => None,
+            };
+            // Sorted so violations come out in the same order every time
+            let mut names: Vec<&String> = fields.keys().collect();
+            names.sort();
+            for name in names {
+                let field = &fields[name];
+                let path = format!("{}.{}", at, name);
+                match (
+                    props.and_then(|p| p.get(name)),
+                    map.get("additionalProperties"),
+                ) {
+                    (Some(sub), _) => collect(sub, field, &path, found),
+                    (None, Some(Value::Bool(false))) => {
+                        found.push(violation(&path, "unexpected field".into()))
+                    }
+                    (None, Some(sub @ Value::Object(_))) => collect(sub, field, &path, found),
+                    (None, _) => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn violation(path: &str, message: String) -> SchemaViolation {
+    SchemaViolation {
+        path: path.to_string(),
+        message,
+    }
+}
+
+fn check_len(min: Option<&Value>, max: Option<&Value>, len: usize, unit: &str) -> Option<String> {
+    match (min, max) {
+        (Some(Value::Int(min)), _) if (len as i64) < *min => {
+            Some(format!("{} {} is fewer than minimum {}", len, unit, min))
+        }
+        (_, Some(Value::Int(max))) if (len as i64) > *max => {
+            Some(format!("{} {} is more than maximum {}", len, unit, max))
+        }
+        _ => None,
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "number" => matches!(value, Value::Int(_) | Value::Float(_)),
+        other => json_type(value) == other,
+    }
+}
+
+/// JSON Schema name of a value's type
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Int(_) => "integer",
+        Value::Float(_) => "number",
+        Value::String(_) => "string",
+        Value::Bytes(_) => "bytes",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_count(value: &Value) -> bool {
+    matches!(value, Value::Int(n) if *n >= 0)
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(n) => Some(*n as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Render a schema argument for an error message
+fn describe(value: &Value) -> String {
+    serde_json::Value::from(value.clone()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        Value::try_from(&json!({
+            "type": "object",
+            "required": ["tool", "attempt"],
+            "properties": {
+                "tool": {"type": "string", "enum": ["search", "fetch"]},
+                "attempt": {"type": "integer", "minimum": 1},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 2},
+                "score": {"type": ["number", "null"]}
+            },
+            "additionalProperties": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_accepts_matching_payload() {
+        validate_schema(&schema()).unwrap();
+        let payload =
+            Value::try_from(&json!({"tool": "search", "attempt": 2, "tags": ["a"], "score": 0.5}))
+                .unwrap();
+        assert_eq!(check(&schema(), &payload), Ok(()));
+        let payload =
+            Value::try_from(&json!({"tool": "fetch", "attempt": 1, "score": null})).unwrap();
+        assert_eq!(check(&schema(), &payload), Ok(()));
+    }
+
+    #[test]
+    fn test_check_reports_first_mismatch_with_path() {
+        let cases = [
+            (
+                json!({"tool": "search"}),
+                "$.attempt: missing required field",
+            ),
+            (
+                json!({"tool": "search", "attempt": "2"}),
+                "$.attempt: expected \"integer\", found string",
+            ),
+            (
+                json!({"tool": "search", "attempt": 1.0}),
+                "$.attempt: expected \"integer\", found number",
+            ),
+            (
+                json!({"tool": "search", "attempt": 0}),
+                "$.attempt: 0 is less than minimum 1",
+            ),
+            (
+                json!({"tool": "rm", "attempt": 1}),
+                "$.tool: expected one of \"search\", \"fetch\"",
+            ),
+            (
+                json!({"tool": "search", "attempt": 1, "tags": ["a", 1]}),
+                "$.tags[1]: expected \"string\", found integer",
+            ),
+            (
+                json!({"tool": "search", "attempt": 1, "tags": ["a", "b", "c"]}),
+                "$.tags: 3 items is more than maximum 2",
+            ),
+            (
+                json!({"tool": "search", "attempt": 1, "extra": true}),
+                "$.extra: unexpected field",
+            ),
+        ];
+        for (payload, message) in cases {
+            let err = check(&schema(), &Value::try_from(&payload).unwrap()).unwrap_err();
+            assert_eq!(err.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_violations_reports_every_mismatch() {
+        let doc =
+            Value::try_from(&json!({"tool": "rm", "tags": [1, "b", 2], "extra": true})).unwrap();
+        let found: Vec<String> = violations(&schema(), &doc)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            found,
+            [
+                "$.attempt: missing required field",
+                "$.extra: unexpected field",
+                "$.tags: 3 items is more than maximum 2",
+                "$.tags[0]: expected \"string\", found integer",
+                "$.tags[2]: expected \"string\", found integer",
+                "$.tool: expected one of \"search\", \"fetch\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_schema_rejects_malformed_keywords() {
+        for bad in [
+            json!("object"),
+            json!({"type": "float"}),
+            json!({"required": "tool"}),
+            json!({"properties": {"n": {"minimum": "1"}}}),
+            json!({"items": {"maxLength": -1}}),
+        ] {
+            assert!(validate_schema(&Value::try_from(&bad).unwrap()).is_err());
+        }
+        validate_schema(&Value::try_from(&json!({"$schema": "x", "title": "t"})).unwrap()).unwrap();
+    }
+}
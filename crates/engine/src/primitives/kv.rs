@@ -21,15 +21,39 @@
 //! - `put(branch_id, key, value)` - Store a value
 //! - `delete(branch_id, key)` - Delete a key
 //! - `list(branch_id, prefix)` - List keys with prefix
-
-use crate::database::Database;
+//! - `incr(branch_id, key, delta)` - Atomically add to a numeric value
+//!
+//! ## History Policies
+//!
+//! Every write keeps the previous version around. A history policy set on a
+//! key prefix (`KeepLast(n)` or `KeepFor(duration)`) bounds that history:
+//! `history()` leaves out versions the policy no longer retains and
+//! `Database::compact()` prunes them. A key follows the policy set on the
+//! longest prefix of its name.
+
+use crate::branch_ops::SCHEMA_LOG_PREFIX;
+use crate::database::{Database, RetryConfig, HISTORY_POLICY_PREFIX};
 use crate::primitives::extensions::KVStoreExt;
+use crate::primitives::numeric::add_numeric;
 use std::sync::Arc;
 use strata_concurrency::TransactionContext;
+use strata_core::key::{ensure_not_reserved, SESSION_MESSAGE_PREFIX, SESSION_PREFIX};
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
-use strata_core::StrataResult;
-use strata_core::{Version, VersionedHistory};
+use strata_core::{PrimitiveType, StrataError, StrataResult, Timestamp};
+use strata_core::{Version, Versioned, VersionedHistory, VersionedValue};
+use strata_durability::retention::RetentionPolicy;
+
+/// Version history of a key, as its history policy sees it
+#[derive(Debug, Clone, PartialEq)]
+pub struct KvHistory {
+    /// Retained versions, newest first
+    pub versions: Vec<VersionedValue>,
+    /// Policy governing the key, if one is set
+    pub policy: Option<RetentionPolicy>,
+    /// Whether older versions were pruned or are hidden by the policy
+    pub truncated: bool,
+}
 
 /// General-purpose key-value store primitive
 ///
@@ -93,16 +117,18 @@ impl KVStore {
     ///
     /// Uses a transaction to retrieve the latest value together with its
     /// version and timestamp, providing snapshot isolation.
-    /// Returns `None` if the key doesn't exist.
+    /// Returns `None` if the key doesn't exist. Served from the database's
+    /// read cache when enabled.
     pub fn get_versioned(
         &self,
         branch_id: &BranchId,
         space: &str,
         key: &str,
     ) -> StrataResult<Option<strata_core::VersionedValue>> {
-        self.db.transaction(*branch_id, |txn| {
-            let storage_key = self.key_for(branch_id, space, key);
-            txn.get_versioned(&storage_key)
+        let storage_key = self.key_for(branch_id, space, key);
+        self.db.cached_read(&storage_key, || {
+            self.db
+                .transaction(*branch_id, |txn| txn.get_versioned(&storage_key))
         })
     }
 
@@ -110,15 +136,61 @@ impl KVStore {
     ///
     /// Returns `None` if the key doesn't exist. Index with `[0]` = latest,
     /// `[1]` = previous, etc. Reads directly from storage (non-transactional).
+    /// Versions the key's history policy no longer retains are left out.
     pub fn getv(
         &self,
         branch_id: &BranchId,
         space: &str,
         key: &str,
     ) -> StrataResult<Option<VersionedHistory<Value>>> {
+        let history = self.history(branch_id, space, key, None)?;
+        Ok(VersionedHistory::new(history.versions))
+    }
+
+    /// Get the newest `limit` versions of a key, newest first.
+    ///
+    /// Like `getv()`, but bounded (`None` returns every retained version)
+    /// and reporting the key's history policy. `truncated` is set when older
+    /// versions were already pruned or the policy hides them; `limit` does
+    /// not count as truncation. A missing key has no versions.
+    pub fn history(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        limit: Option<usize>,
+    ) -> StrataResult<KvHistory> {
         let storage_key = self.key_for(branch_id, space, key);
-        let history = self.db.get_history(&storage_key, None, None)?;
-        Ok(VersionedHistory::new(history))
+        let mut versions = self.db.get_history(&storage_key, None, None)?;
+        let mut truncated = self.db.is_history_trimmed(&storage_key);
+        let policy = self.db.history_policy_for(&storage_key)?;
+        if let Some(policy) = &policy {
+            let now = Timestamp::now().as_micros();
+            let retained = versions
+                .iter()
+                .enumerate()
+                .take_while(|(position, vv)| {
+                    *position == 0
+                        || policy.should_retain(
+                            vv.version.as_u64(),
+                            vv.timestamp.as_micros(),
+                            position + 1,
+                            now,
+                            PrimitiveType::Kv,
+                        )
+                })
+                .count();
+            truncated |= retained < versions.len();
+            versions.truncate(retained);
+        }
+        if let Some(limit) = limit {
+            versions.truncate(limit);
+        }
+        Ok(KvHistory {
+            versions,
+            policy,
+            truncated,
+        })
     }
 
     /// Put a value
@@ -126,6 +198,9 @@ impl KVStore {
     /// Creates the key if it doesn't exist, overwrites if it does.
     /// Returns the version created by this write operation.
     ///
+    /// # Errors
+    /// - `ReservedKey` if `key` starts with a reserved prefix
+    ///
     /// # Example
     ///
     /// ```text
@@ -137,6 +212,18 @@ impl KVStore {
         space: &str,
         key: &str,
         value: Value,
+    ) -> StrataResult<Version> {
+        ensure_not_reserved(key)?;
+        self.system_put(branch_id, space, key, value)
+    }
+
+    /// Put a value without the reserved-prefix check (internal use only)
+    pub fn system_put(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        value: Value,
     ) -> StrataResult<Version> {
         let ((), commit_version) = self.db.transaction_with_version(*branch_id, |txn| {
             let storage_key = self.key_for(branch_id, space, key);
@@ -150,12 +237,26 @@ impl KVStore {
     ///
     /// Returns `true` if the key existed and was deleted, `false` if it didn't exist.
     ///
+    /// # Errors
+    /// - `ReservedKey` if `key` starts with a reserved prefix
+    ///
     /// # Example
     ///
     /// ```text
     /// let was_deleted = kv.delete(&branch_id, "default", "user:123")?;
     /// ```
     pub fn delete(&self, branch_id: &BranchId, space: &str, key: &str) -> StrataResult<bool> {
+        ensure_not_reserved(key)?;
+        self.system_delete(branch_id, space, key)
+    }
+
+    /// Delete a key without the reserved-prefix check (internal use only)
+    pub fn system_delete(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+    ) -> StrataResult<bool> {
         self.db.transaction(*branch_id, |txn| {
             let storage_key = self.key_for(branch_id, space, key);
             let exists = txn.get(&storage_key)?.is_some();
@@ -166,9 +267,113 @@ impl KVStore {
         })
     }
 
+    /// Atomically add `delta` to a numeric value
+    ///
+    /// A missing key counts as zero. The read and write happen in one
+    /// transaction that locks the key, so concurrent increments queue up
+    /// instead of conflicting and none are lost. `delta` may be negative;
+    /// pass a `Value::Float` to add a float. Returns the new value and the
+    /// version of the write.
+    ///
+    /// # Errors
+    /// - `ReservedKey` if `key` starts with a reserved prefix
+    /// - `WrongType` if the stored value or `delta` is not an `Int` or `Float`
+    /// - `InvalidInput` if integer addition overflows
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let hits = kv.incr(&branch_id, "default", "page:hits", Value::Int(1))?;
+    /// ```
+    pub fn incr(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        key: &str,
+        delta: Value,
+    ) -> StrataResult<Versioned<Value>> {
+        ensure_not_reserved(key)?;
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+        let (value, commit_version) =
+            self.db
+                .transaction_with_retry_version(*branch_id, retry_config, |txn| {
+                    let storage_key = self.key_for(branch_id, space, key);
+                    txn.lock(&storage_key)?;
+                    let value = add_numeric(txn.get(&storage_key)?.as_ref(), &delta)?;
+                    txn.put(storage_key, value.clone())?;
+                    Ok(value)
+                })?;
+
+        Ok(Versioned::new(value, Version::Txn(commit_version)))
+    }
+
+    // ========== History Policies ==========
+
+    /// Set the history policy for keys starting with `prefix`
+    ///
+    /// An empty prefix covers every key in the space. History reads apply
+    /// the policy right away; `Database::compact()` prunes the versions it
+    /// no longer retains. The latest version of a key is always kept.
+    /// `KeepAll` removes the policy.
+    ///
+    /// # Errors
+    /// - `ReservedKey` if `prefix` starts with a reserved prefix
+    /// - `InvalidInput` if the policy is `KeepLast(0)`, `KeepFor` a zero
+    ///   duration, or `Composite`
+    pub fn set_history_policy(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        prefix: &str,
+        policy: RetentionPolicy,
+    ) -> StrataResult<()> {
+        ensure_not_reserved(prefix)?;
+        match &policy {
+            RetentionPolicy::KeepLast(0) => {
+                return Err(StrataError::invalid_input(
+                    "history policy KeepLast(n) requires n > 0".to_string(),
+                ))
+            }
+            RetentionPolicy::KeepFor(d) if d.is_zero() => {
+                return Err(StrataError::invalid_input(
+                    "history policy KeepFor requires a non-zero duration".to_string(),
+                ))
+            }
+            RetentionPolicy::Composite { .. } => {
+                return Err(StrataError::invalid_input(
+                    "history policies cannot be Composite".to_string(),
+                ))
+            }
+            _ => {}
+        }
+
+        let policy_key = Key::new_kv_history_policy(self.namespace_for(branch_id, space), prefix);
+        self.db.transaction(*branch_id, |txn| {
+            if policy == RetentionPolicy::KeepAll {
+                txn.delete(policy_key.clone())
+            } else {
+                txn.put(policy_key.clone(), Value::Bytes(policy.to_bytes()))
+            }
+        })
+    }
+
+    /// List the history policies of a space with the prefix each governs
+    pub fn history_policies(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+    ) -> StrataResult<Vec<(String, RetentionPolicy)>> {
+        self.db
+            .history_policies(&self.namespace_for(branch_id, space))
+    }
+
     /// List keys with optional prefix filter
     ///
-    /// Returns all keys matching the prefix (or all keys if prefix is None).
+    /// Returns all keys matching the prefix (or all keys if prefix is None),
+    /// in lexicographic (byte-wise) order.
     ///
     /// # Example
     ///
@@ -193,6 +398,7 @@ impl KVStore {
 
             Ok(results
                 .into_iter()
+                .filter(|(key, _)| !is_internal_kv_key(&key.user_key))
                 .filter_map(|(key, _)| key.user_key_string())
                 .collect())
         })
@@ -218,7 +424,8 @@ impl KVStore {
 
     /// List keys as of a past timestamp.
     ///
-    /// Returns keys whose values existed at the given timestamp.
+    /// Returns keys whose values existed at the given timestamp, in
+    /// lexicographic order.
     pub fn list_at(
         &self,
         branch_id: &BranchId,
@@ -231,11 +438,21 @@ impl KVStore {
         let results = self.db.scan_prefix_at_timestamp(&scan_prefix, as_of_ts)?;
         Ok(results
             .into_iter()
+            .filter(|(key, _)| !is_internal_kv_key(&key.user_key))
             .filter_map(|(key, _)| key.user_key_string())
             .collect())
     }
 }
 
+/// Whether a KV user key is an internal entry hidden from key listings
+/// (history policies, the branch schema log and conversation sessions)
+fn is_internal_kv_key(user_key: &[u8]) -> bool {
+    user_key.starts_with(HISTORY_POLICY_PREFIX)
+        || user_key.starts_with(SCHEMA_LOG_PREFIX)
+        || user_key.starts_with(SESSION_PREFIX.as_bytes())
+        || user_key.starts_with(SESSION_MESSAGE_PREFIX.as_bytes())
+}
+
 // ========== Searchable Trait Implementation ==========
 //
 // Search is handled by the intelligence layer (strata-intelligence).
@@ -264,11 +481,13 @@ impl KVStoreExt for TransactionContext {
     }
 
     fn kv_put(&mut self, key: &str, value: Value) -> StrataResult<()> {
+        ensure_not_reserved(key)?;
         let storage_key = Key::new_kv(Namespace::for_branch(self.branch_id), key);
         self.put(storage_key, value)
     }
 
     fn kv_delete(&mut self, key: &str) -> StrataResult<()> {
+        ensure_not_reserved(key)?;
         let storage_key = Key::new_kv(Namespace::for_branch(self.branch_id), key);
         self.delete(storage_key)?;
         Ok(())
@@ -279,6 +498,7 @@ impl KVStoreExt for TransactionContext {
 mod tests {
     use super::*;
     use strata_core::types::TypeTag;
+    use strata_core::StrataError;
     use tempfile::TempDir;
 
     fn setup() -> (TempDir, Arc<Database>, KVStore) {
@@ -288,6 +508,64 @@ mod tests {
         (temp_dir, db, kv)
     }
 
+    #[test]
+    fn test_incr() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        let first = kv
+            .incr(&branch_id, "default", "hits", Value::Int(5))
+            .unwrap();
+        assert_eq!(first.value, Value::Int(5));
+        let second = kv
+            .incr(&branch_id, "default", "hits", Value::Int(-2))
+            .unwrap();
+        assert_eq!(second.value, Value::Int(3));
+        assert!(second.version.as_u64() > first.version.as_u64());
+        assert_eq!(
+            kv.get(&branch_id, "default", "hits").unwrap(),
+            Some(Value::Int(3))
+        );
+
+        let float = kv
+            .incr(&branch_id, "default", "hits", Value::Float(0.5))
+            .unwrap();
+        assert_eq!(float.value, Value::Float(3.5));
+
+        kv.put(&branch_id, "default", "name", Value::String("x".into()))
+            .unwrap();
+        assert!(kv
+            .incr(&branch_id, "default", "name", Value::Int(1))
+            .unwrap_err()
+            .is_wrong_type());
+    }
+
+    #[test]
+    fn test_incr_concurrent_no_lost_updates() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let kv = kv.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        kv.incr(&branch_id, "default", "counter", Value::Int(1))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(
+            kv.get(&branch_id, "default", "counter").unwrap(),
+            Some(Value::Int(100))
+        );
+    }
+
     #[test]
     fn test_kvstore_creation() {
         let (_temp, _db, _kv) = setup();
@@ -607,6 +885,43 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_reserved_keys_rejected() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        for key in [
+            "_strata/config",
+            "_system_embed",
+            "__idx_status",
+            "__meta__",
+        ] {
+            let err = kv
+                .put(&branch_id, "default", key, Value::Int(1))
+                .unwrap_err();
+            assert!(matches!(err, StrataError::ReservedKey { .. }), "{}", key);
+            assert!(matches!(
+                kv.delete(&branch_id, "default", key),
+                Err(StrataError::ReservedKey { .. })
+            ));
+        }
+
+        // Internal layers write through the system_* escape hatch
+        kv.system_put(&branch_id, "default", "_strata/config", Value::Int(1))
+            .unwrap();
+        assert_eq!(
+            kv.get(&branch_id, "default", "_strata/config").unwrap(),
+            Some(Value::Int(1))
+        );
+        assert!(kv
+            .system_delete(&branch_id, "default", "_strata/config")
+            .unwrap());
+
+        // Leading underscores outside the reserved set stay available
+        kv.put(&branch_id, "default", "_private", Value::Int(1))
+            .unwrap();
+    }
+
     #[test]
     fn test_get_versioned_returns_version_info() {
         let (_temp, _db, kv) = setup();
@@ -649,4 +964,205 @@ mod tests {
 
         db.end_transaction(txn);
     }
+
+    fn put_n(kv: &KVStore, branch_id: &BranchId, key: &str, n: i64) {
+        for i in 1..=n {
+            kv.put(branch_id, "default", key, Value::Int(i)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_history_policy_filters_reads() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+        put_n(&kv, &branch_id, "session:1", 5);
+        put_n(&kv, &branch_id, "user:1", 5);
+
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "session:",
+            RetentionPolicy::KeepLast(2),
+        )
+        .unwrap();
+
+        let history = kv
+            .history(&branch_id, "default", "session:1", None)
+            .unwrap();
+        let values: Vec<_> = history.versions.iter().map(|v| v.value.clone()).collect();
+        assert_eq!(values, vec![Value::Int(5), Value::Int(4)]);
+        assert_eq!(history.policy, Some(RetentionPolicy::KeepLast(2)));
+        assert!(history.truncated);
+        assert_eq!(
+            kv.getv(&branch_id, "default", "session:1")
+                .unwrap()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Keys outside the prefix keep their full history
+        let history = kv
+            .history(&branch_id, "default", "user:1", Some(3))
+            .unwrap();
+        assert_eq!(history.versions.len(), 3);
+        assert_eq!(history.policy, None);
+        assert!(!history.truncated);
+
+        // Policy entries are not user keys
+        assert_eq!(
+            kv.list(&branch_id, "default", None).unwrap(),
+            vec!["session:1".to_string(), "user:1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_history_policy_longest_prefix_wins() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+        put_n(&kv, &branch_id, "user:1", 5);
+        put_n(&kv, &branch_id, "user:admin:1", 5);
+
+        kv.set_history_policy(&branch_id, "default", "user:", RetentionPolicy::KeepLast(1))
+            .unwrap();
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "user:admin:",
+            RetentionPolicy::KeepLast(3),
+        )
+        .unwrap();
+
+        let history = |key| kv.history(&branch_id, "default", key, None).unwrap();
+        assert_eq!(history("user:1").versions.len(), 1);
+        assert_eq!(history("user:admin:1").versions.len(), 3);
+
+        // KeepAll removes the policy
+        kv.set_history_policy(&branch_id, "default", "user:", RetentionPolicy::KeepAll)
+            .unwrap();
+        assert_eq!(history("user:1").versions.len(), 5);
+        assert_eq!(kv.history_policies(&branch_id, "default").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_compaction_enforces_history_policy() {
+        let (_temp, db, kv) = setup();
+        let branch_id = BranchId::new();
+        put_n(&kv, &branch_id, "metrics:cpu", 10);
+        put_n(&kv, &branch_id, "config", 10);
+
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "metrics:",
+            RetentionPolicy::KeepLast(3),
+        )
+        .unwrap();
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "config",
+            RetentionPolicy::KeepFor(std::time::Duration::from_secs(3600)),
+        )
+        .unwrap();
+
+        let info = db.compact().unwrap();
+        assert_eq!(info.versions_removed, 7);
+
+        let storage_key = kv.key_for(&branch_id, "default", "metrics:cpu");
+        assert_eq!(db.get_history(&storage_key, None, None).unwrap().len(), 3);
+        let history = kv
+            .history(&branch_id, "default", "metrics:cpu", None)
+            .unwrap();
+        assert_eq!(history.versions.len(), 3);
+        assert!(history.truncated);
+
+        // Nothing written in the last hour has aged out
+        let history = kv.history(&branch_id, "default", "config", None).unwrap();
+        assert_eq!(history.versions.len(), 10);
+        assert!(!history.truncated);
+    }
+
+    #[test]
+    fn test_history_survives_compaction_and_reopen() {
+        let (temp, db, kv) = setup();
+        let branch_id = BranchId::new();
+        put_n(&kv, &branch_id, "k", 5);
+        db.compact().unwrap();
+        db.shutdown().unwrap();
+        drop(kv);
+        drop(db);
+
+        let db = Database::open(temp.path()).unwrap();
+        let kv = KVStore::new(db.clone());
+        let history = kv.history(&branch_id, "default", "k", None).unwrap();
+        let values: Vec<Value> = history.versions.into_iter().map(|vv| vv.value).collect();
+        assert_eq!(values, (1..=5).rev().map(Value::Int).collect::<Vec<_>>());
+        assert!(!history.truncated);
+    }
+
+    #[test]
+    fn test_history_policy_survives_compaction_and_reopen() {
+        let (temp, db, kv) = setup();
+        let branch_id = BranchId::new();
+        put_n(&kv, &branch_id, "metrics:cpu", 5);
+        kv.set_history_policy(
+            &branch_id,
+            "default",
+            "metrics:",
+            RetentionPolicy::KeepLast(3),
+        )
+        .unwrap();
+        assert_eq!(db.compact().unwrap().versions_removed, 2);
+        db.shutdown().unwrap();
+        drop(kv);
+        drop(db);
+
+        let db = Database::open(temp.path()).unwrap();
+        let kv = KVStore::new(db.clone());
+        let history = kv
+            .history(&branch_id, "default", "metrics:cpu", None)
+            .unwrap();
+        assert_eq!(history.versions.len(), 3);
+        assert!(history.truncated);
+
+        // The flag survives a second compaction too
+        db.compact().unwrap();
+        db.shutdown().unwrap();
+        drop(kv);
+        drop(db);
+        let db = Database::open(temp.path()).unwrap();
+        let kv = KVStore::new(db.clone());
+        let history = kv
+            .history(&branch_id, "default", "metrics:cpu", None)
+            .unwrap();
+        assert_eq!(history.versions.len(), 3);
+        assert!(history.truncated);
+    }
+
+    #[test]
+    fn test_invalid_history_policies_rejected() {
+        let (_temp, _db, kv) = setup();
+        let branch_id = BranchId::new();
+
+        for policy in [
+            RetentionPolicy::KeepLast(0),
+            RetentionPolicy::KeepFor(std::time::Duration::ZERO),
+            RetentionPolicy::composite(RetentionPolicy::KeepAll).build(),
+        ] {
+            assert!(matches!(
+                kv.set_history_policy(&branch_id, "default", "k", policy),
+                Err(StrataError::InvalidInput { .. })
+            ));
+        }
+        assert!(matches!(
+            kv.set_history_policy(
+                &branch_id,
+                "default",
+                "__history__",
+                RetentionPolicy::KeepLast(1)
+            ),
+            Err(StrataError::ReservedKey { .. })
+        ));
+    }
 }
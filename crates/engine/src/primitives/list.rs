@@ -0,0 +1,639 @@
+//! ListStore: Double-ended lists for task queues
+//!
+//! ## Design: STATELESS FACADE
+//!
+//! ListStore holds ONLY `Arc<Database>`. Every push and pop is a regular
+//! transaction, so list contents are WAL-logged, branch-scoped and
+//! recovered like any other primitive.
+//!
+//! ## API
+//!
+//! - `lpush`, `rpush` - Add values at the head or tail
+//! - `lpop`, `rpop` - Remove and return the head or tail value
+//! - `lrange`, `llen` - Read without removing
+//! - `blpop`, `brpop` - Pop, waiting up to a timeout for a value to arrive
+//!
+//! Indexes follow Redis: position 0 is the head, negative positions count
+//! back from the tail, and `lrange` bounds are inclusive.
+//!
+//! ## Key Design
+//!
+//! - TypeTag: List (0x07)
+//! - Metadata key: `<namespace>:<TypeTag::List>:<list>` holding head/tail positions
+//! - Element key: `<namespace>:<TypeTag::List>:<list>\0<position_be_bytes>`
+//!
+//! Elements occupy positions `head..tail`. New lists start in the middle
+//! of the `u64` range so they can grow in both directions. A list whose
+//! last element is popped is removed entirely.
+//!
+//! ## Blocking Pops
+//!
+//! Pushes made through this facade wake blocked poppers on the same
+//! database. A woken popper retries the pop; if another worker took the
+//! value first it waits again until its timeout expires.
+
+use crate::database::{Database, RetryConfig};
+use parking_lot::{Condvar, Mutex};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use strata_concurrency::TransactionContext;
+use strata_core::key::ensure_not_reserved;
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult};
+
+/// Position of the first element pushed onto an empty list
+const INITIAL_POSITION: u64 = 1 << 63;
+
+/// Which end of a list an operation works on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum End {
+    Head,
+    Tail,
+}
+
+/// Per-list metadata: elements occupy positions `head..tail`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ListMeta {
+    head: u64,
+    tail: u64,
+}
+
+impl ListMeta {
+    fn empty() -> Self {
+        Self {
+            head: INITIAL_POSITION,
+            tail: INITIAL_POSITION,
+        }
+    }
+
+    fn len(&self) -> u64 {
+        self.tail - self.head
+    }
+}
+
+/// Wakes blocked poppers after pushes
+///
+/// Stored as a database extension so every `ListStore` over the same
+/// database shares it. `pushes` counts completed pushes; a popper that
+/// saw no value waits until the count moves past the one it read before
+/// trying, so a push landing in between is never missed.
+#[derive(Default)]
+struct ListWaiters {
+    pushes: Mutex<u64>,
+    pushed: Condvar,
+}
+
+/// Double-ended list primitive
+///
+/// # Example
+///
+/// ```text
+/// let lists = ListStore::new(db.clone());
+/// let branch_id = BranchId::new();
+///
+/// lists.rpush(&branch_id, "default", "jobs", vec![Value::from("a"), Value::from("b")])?;
+/// let next = lists.blpop(&branch_id, "default", "jobs", Duration::from_secs(5))?;
+/// assert_eq!(next, Some(Value::from("a")));
+/// ```
+#[derive(Clone)]
+pub struct ListStore {
+    db: Arc<Database>,
+}
+
+impl ListStore {
+    /// Create new ListStore instance
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Build namespace for branch+space-scoped operations
+    fn namespace_for(&self, branch_id: &BranchId, space: &str) -> Namespace {
+        Namespace::for_branch_space(*branch_id, space)
+    }
+
+    /// Retry policy for writes: concurrent pushes and pops all touch the
+    /// list's metadata key, so conflicts are expected under contention.
+    fn retry_config() -> RetryConfig {
+        RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50)
+    }
+
+    // ========== Write Operations ==========
+
+    /// Push values onto the head of a list, creating it if needed.
+    ///
+    /// Values are pushed one at a time, so `lpush [a, b]` leaves `b` at
+    /// the head. Returns the list length after the push.
+    pub fn lpush(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        values: Vec<Value>,
+    ) -> StrataResult<u64> {
+        self.push(branch_id, space, list, End::Head, values)
+    }
+
+    /// Append values to the tail of a list, creating it if needed.
+    ///
+    /// Returns the list length after the push.
+    pub fn rpush(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        values: Vec<Value>,
+    ) -> StrataResult<u64> {
+        self.push(branch_id, space, list, End::Tail, values)
+    }
+
+    /// Remove and return the head of a list, or `None` if it is empty.
+    pub fn lpop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+    ) -> StrataResult<Option<Value>> {
+        self.pop(branch_id, space, list, End::Head)
+    }
+
+    /// Remove and return the tail of a list, or `None` if it is empty.
+    pub fn rpop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+    ) -> StrataResult<Option<Value>> {
+        self.pop(branch_id, space, list, End::Tail)
+    }
+
+    /// Like [`lpop`](Self::lpop), but waits up to `timeout` for a value
+    /// when the list is empty. Returns `None` on timeout.
+    pub fn blpop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        timeout: Duration,
+    ) -> StrataResult<Option<Value>> {
+        self.blocking_pop(branch_id, space, list, End::Head, timeout)
+    }
+
+    /// Like [`rpop`](Self::rpop), but waits up to `timeout` for a value
+    /// when the list is empty. Returns `None` on timeout.
+    pub fn brpop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        timeout: Duration,
+    ) -> StrataResult<Option<Value>> {
+        self.blocking_pop(branch_id, space, list, End::Tail, timeout)
+    }
+
+    // ========== Read Operations ==========
+
+    /// Number of elements in a list (0 if it does not exist).
+    pub fn llen(&self, branch_id: &BranchId, space: &str, list: &str) -> StrataResult<u64> {
+        let ns = self.namespace_for(branch_id, space);
+        self.db.transaction(*branch_id, |txn| {
+            Ok(read_meta(txn, &ns, list)?.map_or(0, |meta| meta.len()))
+        })
+    }
+
+    /// Elements from `start` to `stop` inclusive, head first.
+    ///
+    /// Negative positions count back from the tail (`-1` is the last
+    /// element). Out-of-range bounds are clamped, so `lrange(0, -1)`
+    /// returns the whole list and an empty range returns no elements.
+    pub fn lrange(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        start: i64,
+        stop: i64,
+    ) -> StrataResult<Vec<Value>> {
+        let ns = self.namespace_for(branch_id, space);
+        self.db.transaction(*branch_id, |txn| {
+            let Some(meta) = read_meta(txn, &ns, list)? else {
+                return Ok(Vec::new());
+            };
+            let len = meta.len() as i64;
+            let start = if start < 0 { len + start } else { start }.max(0);
+            let stop = if stop < 0 { len + stop } else { stop }.min(len - 1);
+            if start > stop {
+                return Ok(Vec::new());
+            }
+
+            let mut values = Vec::with_capacity((stop - start + 1) as usize);
+            for offset in start..=stop {
+                let position = meta.head + offset as u64;
+                let value = txn
+                    .get(&Key::new_list_item(ns.clone(), list, position))?
+                    .ok_or_else(|| missing_element(list, position))?;
+                values.push(value);
+            }
+            Ok(values)
+        })
+    }
+
+    // ========== Internals ==========
+
+    fn push(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        end: End,
+        values: Vec<Value>,
+    ) -> StrataResult<u64> {
+        validate_list_name(list)?;
+        if values.is_empty() {
+            return Err(StrataError::invalid_input(
+                "push requires at least one value",
+            ));
+        }
+        let ns = self.namespace_for(branch_id, space);
+        let len = self
+            .db
+            .transaction_with_retry(*branch_id, Self::retry_config(), |txn| {
+                let mut meta = read_meta(txn, &ns, list)?.unwrap_or_else(ListMeta::empty);
+                for value in &values {
+                    let position = match end {
+                        End::Head => {
+                            meta.head = meta.head.checked_sub(1).ok_or_else(|| full(list))?;
+                            meta.head
+                        }
+                        End::Tail => {
+                            let position = meta.tail;
+                            meta.tail = meta.tail.checked_add(1).ok_or_else(|| full(list))?;
+                            position
+                        }
+                    };
+                    txn.put(
+                        Key::new_list_item(ns.clone(), list, position),
+                        value.clone(),
+                    )?;
+                }
+                write_meta(txn, &ns, list, &meta)?;
+                Ok(meta.len())
+            })?;
+
+        let waiters = self.db.extension::<ListWaiters>()?;
+        *waiters.pushes.lock() += 1;
+        waiters.pushed.notify_all();
+        Ok(len)
+    }
+
+    fn pop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        end: End,
+    ) -> StrataResult<Option<Value>> {
+        validate_list_name(list)?;
+        let ns = self.namespace_for(branch_id, space);
+        self.db
+            .transaction_with_retry(*branch_id, Self::retry_config(), |txn| {
+                let Some(mut meta) = read_meta(txn, &ns, list)? else {
+                    return Ok(None);
+                };
+                let position = match end {
+                    End::Head => {
+                        meta.head += 1;
+                        meta.head - 1
+                    }
+                    End::Tail => {
+                        meta.tail -= 1;
+                        meta.tail
+                    }
+                };
+                let item_key = Key::new_list_item(ns.clone(), list, position);
+                let value = txn
+                    .get(&item_key)?
+                    .ok_or_else(|| missing_element(list, position))?;
+                txn.delete(item_key)?;
+                if meta.len() == 0 {
+                    txn.delete(Key::new_list(ns.clone(), list))?;
+                } else {
+                    write_meta(txn, &ns, list, &meta)?;
+                }
+                Ok(Some(value))
+            })
+    }
+
+    fn blocking_pop(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        list: &str,
+        end: End,
+        timeout: Duration,
+    ) -> StrataResult<Option<Value>> {
+        let waiters = self.db.extension::<ListWaiters>()?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let seen = *waiters.pushes.lock();
+            if let Some(value) = self.pop(branch_id, space, list, end)? {
+                return Ok(Some(value));
+            }
+
+            let mut pushes = waiters.pushes.lock();
+            while *pushes == seen {
+                if waiters.pushed.wait_until(&mut pushes, deadline).timed_out() {
+                    if *pushes == seen {
+                        return Ok(None);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// List names are stored in front of a NUL separator in element keys
+fn validate_list_name(list: &str) -> StrataResult<()> {
+    ensure_not_reserved(list)?;
+    if list.contains('\0') {
+        return Err(StrataError::invalid_input(format!(
+            "List name '{}' must not contain NUL bytes",
+            list.escape_debug()
+        )));
+    }
+    Ok(())
+}
+
+fn read_meta(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    list: &str,
+) -> StrataResult<Option<ListMeta>> {
+    match txn.get(&Key::new_list(ns.clone(), list))? {
+        Some(Value::String(s)) => serde_json::from_str(&s)
+            .map(Some)
+            .map_err(|e| StrataError::serialization(e.to_string())),
+        Some(other) => Err(StrataError::serialization(format!(
+            "List '{}' metadata is {}, expected String",
+            list,
+            other.type_name()
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn write_meta(
+    txn: &mut TransactionContext,
+    ns: &Namespace,
+    list: &str,
+    meta: &ListMeta,
+) -> StrataResult<()> {
+    let encoded =
+        serde_json::to_string(meta).map_err(|e| StrataError::serialization(e.to_string()))?;
+    txn.put(Key::new_list(ns.clone(), list), Value::String(encoded))
+}
+
+fn missing_element(list: &str, position: u64) -> StrataError {
+    StrataError::internal(format!(
+        "List '{}' is missing the element at position {}",
+        list, position
+    ))
+}
+
+fn full(list: &str) -> StrataError {
+    StrataError::invalid_input(format!("List '{}' cannot grow any further", list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, Arc<Database>, ListStore) {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path()).unwrap();
+        let lists = ListStore::new(db.clone());
+        (temp_dir, db, lists)
+    }
+
+    fn ints(values: &[i64]) -> Vec<Value> {
+        values.iter().map(|v| Value::Int(*v)).collect()
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+
+        assert_eq!(
+            lists
+                .rpush(&branch_id, "default", "q", ints(&[1, 2]))
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            lists
+                .lpush(&branch_id, "default", "q", ints(&[0, -1]))
+                .unwrap(),
+            4
+        );
+        assert_eq!(
+            lists.lrange(&branch_id, "default", "q", 0, -1).unwrap(),
+            ints(&[-1, 0, 1, 2])
+        );
+
+        assert_eq!(
+            lists.lpop(&branch_id, "default", "q").unwrap(),
+            Some(Value::Int(-1))
+        );
+        assert_eq!(
+            lists.rpop(&branch_id, "default", "q").unwrap(),
+            Some(Value::Int(2))
+        );
+        assert_eq!(lists.llen(&branch_id, "default", "q").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_pop_empty_and_drained_list() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+
+        assert_eq!(lists.lpop(&branch_id, "default", "q").unwrap(), None);
+        lists.rpush(&branch_id, "default", "q", ints(&[1])).unwrap();
+        assert_eq!(
+            lists.rpop(&branch_id, "default", "q").unwrap(),
+            Some(Value::Int(1))
+        );
+        assert_eq!(lists.rpop(&branch_id, "default", "q").unwrap(), None);
+        assert_eq!(lists.llen(&branch_id, "default", "q").unwrap(), 0);
+
+        // A drained list starts over cleanly
+        lists.lpush(&branch_id, "default", "q", ints(&[7])).unwrap();
+        assert_eq!(
+            lists.lrange(&branch_id, "default", "q", 0, -1).unwrap(),
+            ints(&[7])
+        );
+    }
+
+    #[test]
+    fn test_lrange_bounds() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+        lists
+            .rpush(&branch_id, "default", "q", ints(&[0, 1, 2, 3, 4]))
+            .unwrap();
+
+        let range = |start, stop| {
+            lists
+                .lrange(&branch_id, "default", "q", start, stop)
+                .unwrap()
+        };
+        assert_eq!(range(1, 3), ints(&[1, 2, 3]));
+        assert_eq!(range(-2, -1), ints(&[3, 4]));
+        assert_eq!(range(-100, 100), ints(&[0, 1, 2, 3, 4]));
+        assert_eq!(range(3, 1), ints(&[]));
+        assert_eq!(range(5, 10), ints(&[]));
+        assert!(lists
+            .lrange(&branch_id, "default", "missing", 0, -1)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lists_are_isolated() {
+        let (_temp, _db, lists) = setup();
+        let branch_a = BranchId::new();
+        let branch_b = BranchId::new();
+
+        lists.rpush(&branch_a, "default", "q", ints(&[1])).unwrap();
+        lists.rpush(&branch_a, "default", "q2", ints(&[2])).unwrap();
+        lists.rpush(&branch_a, "other", "q", ints(&[3])).unwrap();
+
+        assert_eq!(lists.llen(&branch_a, "default", "q").unwrap(), 1);
+        assert_eq!(lists.llen(&branch_b, "default", "q").unwrap(), 0);
+        assert_eq!(
+            lists.lpop(&branch_a, "other", "q").unwrap(),
+            Some(Value::Int(3))
+        );
+        assert_eq!(
+            lists.lpop(&branch_a, "default", "q").unwrap(),
+            Some(Value::Int(1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_push() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+
+        assert!(lists
+            .rpush(&branch_id, "default", "q", Vec::new())
+            .unwrap_err()
+            .is_validation_error());
+        assert!(lists
+            .rpush(&branch_id, "default", "bad\0name", ints(&[1]))
+            .unwrap_err()
+            .is_validation_error());
+    }
+
+    #[test]
+    fn test_lists_survive_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("db");
+        let branch_id = BranchId::new();
+        {
+            let db = Database::open(&path).unwrap();
+            let lists = ListStore::new(db.clone());
+            lists
+                .rpush(&branch_id, "default", "q", ints(&[1, 2, 3]))
+                .unwrap();
+            lists.lpop(&branch_id, "default", "q").unwrap();
+            db.shutdown().unwrap();
+        }
+
+        let db = Database::open(&path).unwrap();
+        let lists = ListStore::new(db);
+        assert_eq!(
+            lists.lrange(&branch_id, "default", "q", 0, -1).unwrap(),
+            ints(&[2, 3])
+        );
+    }
+
+    #[test]
+    fn test_blocking_pop_times_out() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+
+        let start = Instant::now();
+        let popped = lists
+            .blpop(&branch_id, "default", "q", Duration::from_millis(50))
+            .unwrap();
+        assert_eq!(popped, None);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_blocking_pop_wakes_on_push() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+
+        let worker = {
+            let lists = lists.clone();
+            thread::spawn(move || {
+                lists
+                    .brpop(&branch_id, "default", "q", Duration::from_secs(10))
+                    .unwrap()
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+        lists
+            .rpush(
+                &branch_id,
+                "default",
+                "q",
+                vec![Value::String("job".into())],
+            )
+            .unwrap();
+
+        assert_eq!(worker.join().unwrap(), Some(Value::String("job".into())));
+    }
+
+    #[test]
+    fn test_concurrent_workers_pop_each_value_once() {
+        let (_temp, _db, lists) = setup();
+        let branch_id = BranchId::new();
+        let total = 40;
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let lists = lists.clone();
+                thread::spawn(move || {
+                    let mut seen = Vec::new();
+                    while let Some(Value::Int(v)) = lists
+                        .blpop(&branch_id, "default", "q", Duration::from_millis(500))
+                        .unwrap()
+                    {
+                        seen.push(v);
+                    }
+                    seen
+                })
+            })
+            .collect();
+        for i in 0..total {
+            lists.rpush(&branch_id, "default", "q", ints(&[i])).unwrap();
+        }
+
+        let mut popped: Vec<i64> = workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect();
+        popped.sort_unstable();
+        assert_eq!(popped, (0..total).collect::<Vec<_>>());
+    }
+}
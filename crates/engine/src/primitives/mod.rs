@@ -6,7 +6,9 @@
 //! - **StateCell**: CAS-based versioned cells for coordination
 //! - **BranchIndex**: Branch lifecycle management
 //! - **JsonStore**: JSON document storage with path-based operations
+//! - **ListStore**: Double-ended lists with blocking pops for task queues
 //! - **VectorStore**: Vector storage with similarity search and collection management
+//! - **CustomStore**: Facade over a primitive registered outside the engine
 //!
 //! ## Design Principle: Stateless Facades
 //!
@@ -40,29 +42,42 @@
 //! ```
 
 pub mod branch;
+pub mod custom;
 pub mod event;
 pub mod extensions;
 pub mod json;
+mod json_schema;
 pub mod kv;
+pub mod list;
+mod numeric;
 pub mod space;
 pub mod state;
+#[cfg(feature = "vectors")]
 pub mod vector;
 
 // Re-exports - primitives are exported as they're implemented
+pub use branch::{
+    BranchAncestor, BranchFilter, BranchIndex, BranchMetadata, BranchStatus, IndexVerification,
+};
 pub use branch::{BranchHandle, EventHandle, JsonHandle, KvHandle, StateHandle};
-pub use branch::{BranchIndex, BranchMetadata, BranchStatus};
-pub use event::{Event, EventLog};
+pub use custom::{
+    custom_primitive, register_custom_primitive, CustomPrimitive, CustomStore, CUSTOM_PRIMITIVE_IDS,
+};
+pub use event::{Event, EventLog, PendingEvent};
 pub use json::{JsonDoc, JsonStore};
-pub use kv::KVStore;
+pub use json_schema::SchemaViolation;
+pub use kv::{KVStore, KvHistory};
+pub use list::ListStore;
 pub use space::SpaceIndex;
 pub use state::{State, StateCell};
+#[cfg(feature = "vectors")]
 pub use vector::{
     register_vector_recovery, validate_collection_name, validate_vector_key, BruteForceBackend,
     CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, FilterCondition, FilterOp,
-    HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, StorageDtype,
-    VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError, VectorHeap,
-    VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord, VectorResult,
-    VectorStore,
+    HnswBackend, HnswConfig, IndexBackendFactory, JsonScalar, MetadataFilter, NestedFilter,
+    StorageDtype, VectorBackendState, VectorConfig, VectorConfigSerde, VectorEntry, VectorError,
+    VectorHeap, VectorId, VectorIndexBackend, VectorMatch, VectorMatchWithSource, VectorRecord,
+    VectorResult, VectorStore,
 };
 
 // Re-export search types for convenience (from search module)
@@ -0,0 +1,64 @@
+//! Numeric arithmetic shared by the atomic increment operations.
+
+use strata_core::value::Value;
+use strata_core::{StrataError, StrataResult};
+
+/// Add `delta` to `current`, treating a missing value as zero.
+///
+/// Integers stay integers (overflow is an error); adding a float to an
+/// integer, or anything to a float, produces a float.
+///
+/// # Errors
+/// - `WrongType` if `current` or `delta` is not an `Int` or `Float`
+/// - `InvalidInput` if integer addition overflows
+pub(crate) fn add_numeric(current: Option<&Value>, delta: &Value) -> StrataResult<Value> {
+    let zero = Value::Int(0);
+    match (current.unwrap_or(&zero), delta) {
+        (Value::Int(a), Value::Int(b)) => a.checked_add(*b).map(Value::Int).ok_or_else(|| {
+            StrataError::invalid_input(format!("integer overflow adding {} to {}", b, a))
+        }),
+        (Value::Int(a), Value::Float(b)) => Ok(Value::Float(*a as f64 + b)),
+        (Value::Float(a), Value::Int(b)) => Ok(Value::Float(a + *b as f64)),
+        (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a + b)),
+        (Value::Int(_) | Value::Float(_), other) | (other, _) => {
+            Err(StrataError::wrong_type("Int or Float", other.type_name()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_numeric() {
+        assert_eq!(add_numeric(None, &Value::Int(5)).unwrap(), Value::Int(5));
+        assert_eq!(
+            add_numeric(Some(&Value::Int(5)), &Value::Int(-7)).unwrap(),
+            Value::Int(-2)
+        );
+        assert_eq!(
+            add_numeric(Some(&Value::Int(1)), &Value::Float(0.5)).unwrap(),
+            Value::Float(1.5)
+        );
+        assert_eq!(
+            add_numeric(Some(&Value::Float(1.5)), &Value::Int(1)).unwrap(),
+            Value::Float(2.5)
+        );
+    }
+
+    #[test]
+    fn test_add_numeric_errors() {
+        assert!(add_numeric(Some(&Value::Int(i64::MAX)), &Value::Int(1))
+            .unwrap_err()
+            .is_validation_error());
+        assert!(
+            add_numeric(Some(&Value::String("x".into())), &Value::Int(1))
+                .unwrap_err()
+                .is_wrong_type()
+        );
+        assert!(add_numeric(None, &Value::Bool(true))
+            .unwrap_err()
+            .is_wrong_type());
+    }
+}
@@ -68,7 +68,7 @@ impl SpaceIndex {
         })
     }
 
-    /// List all spaces in a branch.
+    /// List all spaces in a branch, in lexicographic order.
     ///
     /// Always includes "default" in the result, even if not explicitly registered.
     pub fn list(&self, branch_id: BranchId) -> StrataResult<Vec<String>> {
@@ -81,9 +81,9 @@ impl SpaceIndex {
                 .filter_map(|(k, _)| String::from_utf8(k.user_key.clone()).ok())
                 .collect();
 
-            // Always include "default"
-            if !spaces.contains(&"default".to_string()) {
-                spaces.insert(0, "default".to_string());
+            // Always include "default", keeping the scan's key order
+            if let Err(pos) = spaces.binary_search_by(|s| s.as_str().cmp("default")) {
+                spaces.insert(pos, "default".to_string());
             }
 
             Ok(spaces)
@@ -105,8 +105,8 @@ impl SpaceIndex {
 
     /// Check if a space has any data.
     ///
-    /// Scans all data TypeTags (KV, Event, State, Json, Vector) in the
-    /// space's namespace to determine if any keys exist.
+    /// Scans all data TypeTags (KV, Event, State, List, Json, Vector, Custom)
+    /// in the space's namespace to determine if any keys exist.
     pub fn is_empty(&self, branch_id: BranchId, space: &str) -> StrataResult<bool> {
         self.db.transaction(branch_id, |txn| {
             let ns = Namespace::for_branch_space(branch_id, space);
@@ -115,9 +115,11 @@ impl SpaceIndex {
                 TypeTag::KV,
                 TypeTag::Event,
                 TypeTag::State,
+                TypeTag::List,
                 TypeTag::Json,
                 TypeTag::Vector,
                 TypeTag::VectorConfig,
+                TypeTag::Custom,
             ] {
                 let prefix = Key::new(ns.clone(), type_tag, vec![]);
                 let entries = txn.scan_prefix(&prefix)?;
@@ -241,4 +243,15 @@ mod tests {
         assert!(spaces.contains(&"beta".to_string()));
         assert!(spaces.contains(&"gamma".to_string()));
     }
+
+    #[test]
+    fn test_list_is_lexicographic() {
+        let (_temp, _db, si) = setup();
+        let bid = default_branch();
+
+        si.register(bid, "zeta").unwrap();
+        si.register(bid, "alpha").unwrap();
+
+        assert_eq!(si.list(bid).unwrap(), vec!["alpha", "default", "zeta"]);
+    }
 }
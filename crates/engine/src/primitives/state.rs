@@ -10,6 +10,9 @@
 //! All operations go through `db.transaction()` for consistency:
 //! - `init`, `read`, `set`, `cas`
 //!
+//! `watch` registers a callback that receives every value committed to a
+//! cell by `set` or `cas`, so coordinators need not poll.
+//!
 //! ## Key Design
 //!
 //! - TypeTag: State (0x03)
@@ -17,10 +20,14 @@
 
 use crate::database::{Database, RetryConfig};
 use crate::primitives::extensions::StateCellExt;
+use crate::primitives::numeric::add_numeric;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Version, Versioned};
+use strata_core::key::ensure_not_reserved;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
 use strata_core::Timestamp;
@@ -46,6 +53,18 @@ fn from_stored_value<T: for<'de> Deserialize<'de>>(
     }
 }
 
+/// Callback registered by `StateCell::watch`; returns `false` to unsubscribe
+type StateWatcher = Arc<dyn Fn(&Versioned<Value>) -> bool + Send + Sync>;
+
+/// Watchers of individual cells, keyed by cell key
+///
+/// Stored as a database extension so every `StateCell` over the same
+/// database sees the same watchers.
+#[derive(Default)]
+struct StateWatchers {
+    cells: Mutex<HashMap<Key, Vec<StateWatcher>>>,
+}
+
 /// CAS-based versioned cells for coordination
 ///
 /// ## Design
@@ -109,6 +128,7 @@ impl StateCell {
         name: &str,
         value: Value,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(name)?;
         self.db.transaction(*branch_id, |txn| {
             let key = self.key_for(branch_id, space, name);
 
@@ -151,7 +171,8 @@ impl StateCell {
     ///
     /// Reads directly from the committed store (non-transactional) to
     /// retrieve the user value together with its counter version and timestamp.
-    /// Returns `None` if the cell doesn't exist.
+    /// Returns `None` if the cell doesn't exist. Served decoded from the
+    /// database's read cache when enabled.
     pub fn get_versioned(
         &self,
         branch_id: &BranchId,
@@ -160,18 +181,19 @@ impl StateCell {
     ) -> StrataResult<Option<Versioned<Value>>> {
         let key = self.key_for(branch_id, space, name);
         use strata_core::Storage;
-        match self.db.storage().get(&key)? {
-            Some(vv) => {
-                let state: State = from_stored_value(&vv.value)
-                    .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?;
-                Ok(Some(Versioned::with_timestamp(
-                    state.value,
-                    state.version,
-                    Timestamp::from_micros(state.updated_at),
-                )))
-            }
-            None => Ok(None),
-        }
+        self.db
+            .cached_read(&key, || match self.db.storage().get(&key)? {
+                Some(vv) => {
+                    let state: State = from_stored_value(&vv.value)
+                        .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?;
+                    Ok(Some(Versioned::with_timestamp(
+                        state.value,
+                        state.version,
+                        Timestamp::from_micros(state.updated_at),
+                    )))
+                }
+                None => Ok(None),
+            })
     }
 
     /// Get full version history for a state cell.
@@ -186,6 +208,20 @@ impl StateCell {
         branch_id: &BranchId,
         space: &str,
         name: &str,
+    ) -> StrataResult<Option<VersionedHistory<Value>>> {
+        self.history(branch_id, space, name, None)
+    }
+
+    /// Get the newest `limit` versions of a state cell, newest first.
+    ///
+    /// Like `getv()`, but bounded; `None` returns every version. Deletions
+    /// do not count towards `limit`.
+    pub fn history(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        limit: Option<usize>,
     ) -> StrataResult<Option<VersionedHistory<Value>>> {
         let key = self.key_for(branch_id, space, name);
         let history = self.db.get_history(&key, None, None)?;
@@ -199,10 +235,36 @@ impl StateCell {
                     Timestamp::from_micros(state.updated_at),
                 ))
             })
+            .take(limit.unwrap_or(usize::MAX))
             .collect();
         Ok(VersionedHistory::new(versions))
     }
 
+    /// Read a state cell as it was at a given version counter.
+    ///
+    /// Returns `None` if the cell never had that version. A cell that was
+    /// deleted and recreated restarts its counter; the newest match wins.
+    pub fn get_version(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        version: Version,
+    ) -> StrataResult<Option<Versioned<Value>>> {
+        let key = self.key_for(branch_id, space, name);
+        let history = self.db.get_history(&key, None, None)?;
+        Ok(history.iter().find_map(|vv| {
+            let state: State = from_stored_value(&vv.value).ok()?;
+            (state.version == version).then(|| {
+                Versioned::with_timestamp(
+                    state.value,
+                    state.version,
+                    Timestamp::from_micros(state.updated_at),
+                )
+            })
+        }))
+    }
+
     // ========== CAS & Set Operations ==========
 
     /// Compare-and-swap: Update only if version matches
@@ -217,11 +279,13 @@ impl StateCell {
         expected_version: Version,
         new_value: Value,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(name)?;
         let retry_config = RetryConfig::default()
             .with_max_retries(50)
             .with_base_delay_ms(1)
             .with_max_delay_ms(50);
-        self.db
+        let new_state = self
+            .db
             .transaction_with_retry(*branch_id, retry_config, |txn| {
                 let key = self.key_for(branch_id, space, name);
 
@@ -251,8 +315,12 @@ impl StateCell {
                 };
 
                 txn.put(key, to_stored_value(&new_state)?)?;
-                Ok(new_state.version)
-            })
+                Ok(new_state)
+            })?;
+
+        let version = new_state.version;
+        self.notify_watchers(branch_id, space, name, new_state)?;
+        Ok(version)
     }
 
     /// Unconditional set (force write)
@@ -268,12 +336,13 @@ impl StateCell {
         name: &str,
         value: Value,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(name)?;
         let retry_config = RetryConfig::default()
             .with_max_retries(50)
             .with_base_delay_ms(1)
             .with_max_delay_ms(50);
         let value_for_index = value.clone();
-        let result = self
+        let new_state = self
             .db
             .transaction_with_retry(*branch_id, retry_config, |txn| {
                 let key = self.key_for(branch_id, space, name);
@@ -294,8 +363,10 @@ impl StateCell {
                 };
 
                 txn.put(key, to_stored_value(&new_state)?)?;
-                Ok(new_state.version)
+                Ok(new_state)
             })?;
+        let result = new_state.version;
+        self.notify_watchers(branch_id, space, name, new_state)?;
 
         // Update inverted index (zero overhead when disabled)
         let index = self.db.extension::<crate::search::InvertedIndex>()?;
@@ -315,12 +386,147 @@ impl StateCell {
         Ok(result)
     }
 
+    /// Atomically add `delta` to a numeric cell
+    ///
+    /// A missing cell counts as zero and is created. The read and write
+    /// happen in one transaction that locks the cell, so concurrent
+    /// increments queue up instead of conflicting and none are lost. `delta` may be negative; pass a
+    /// `Value::Float` to add a float. Returns the new value with its version.
+    ///
+    /// # Errors
+    /// - `WrongType` if the cell or `delta` is not an `Int` or `Float`
+    /// - `InvalidInput` if integer addition overflows
+    pub fn incr(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        delta: Value,
+    ) -> StrataResult<Versioned<Value>> {
+        ensure_not_reserved(name)?;
+        let retry_config = RetryConfig::default()
+            .with_max_retries(50)
+            .with_base_delay_ms(1)
+            .with_max_delay_ms(50);
+        let new_state = self
+            .db
+            .transaction_with_retry(*branch_id, retry_config, |txn| {
+                let key = self.key_for(branch_id, space, name);
+                txn.lock(&key)?;
+
+                let current: Option<State> = match txn.get(&key)? {
+                    Some(v) => Some(
+                        from_stored_value(&v)
+                            .map_err(|e| strata_core::StrataError::serialization(e.to_string()))?,
+                    ),
+                    None => None,
+                };
+
+                let new_state = State {
+                    value: add_numeric(current.as_ref().map(|s| &s.value), &delta)?,
+                    version: current.map_or(Version::counter(1), |s| s.version.increment()),
+                    updated_at: State::now(),
+                };
+
+                txn.put(key, to_stored_value(&new_state)?)?;
+                Ok(new_state)
+            })?;
+
+        let result = Versioned::with_timestamp(
+            new_state.value.clone(),
+            new_state.version,
+            Timestamp::from_micros(new_state.updated_at),
+        );
+        self.notify_watchers(branch_id, space, name, new_state)?;
+        Ok(result)
+    }
+
+    // ========== Watch Operations ==========
+
+    /// Watch a cell for changes.
+    ///
+    /// `on_change` is called with the new value, version and timestamp after
+    /// every successful `set` or `cas` of the cell, including ones that
+    /// create it. Return `false` from `on_change` to stop watching. The
+    /// callback runs on the writing thread, so it should hand the value off
+    /// (for example to a channel) rather than block.
+    ///
+    /// Writes made through `StateCellExt` inside a caller-managed transaction
+    /// are not reported. Concurrent writers may report out of version order;
+    /// compare versions to discard stale values.
+    pub fn watch<F>(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        on_change: F,
+    ) -> StrataResult<()>
+    where
+        F: Fn(&Versioned<Value>) -> bool + Send + Sync + 'static,
+    {
+        ensure_not_reserved(name)?;
+        let watchers = self.db.extension::<StateWatchers>()?;
+        watchers
+            .cells
+            .lock()
+            .entry(self.key_for(branch_id, space, name))
+            .or_default()
+            .push(Arc::new(on_change));
+        Ok(())
+    }
+
+    /// Report a committed value to the cell's watchers, dropping any that
+    /// unsubscribed.
+    ///
+    /// The callbacks run without the watcher lock held, so they may write
+    /// state cells themselves and writes to other cells are not held up.
+    fn notify_watchers(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        name: &str,
+        state: State,
+    ) -> StrataResult<()> {
+        let watchers = self.db.extension::<StateWatchers>()?;
+        let key = self.key_for(branch_id, space, name);
+        let cell_watchers = {
+            let cells = watchers.cells.lock();
+            match cells.get(&key) {
+                Some(cell_watchers) => cell_watchers.clone(),
+                None => return Ok(()),
+            }
+        };
+
+        let versioned = Versioned::with_timestamp(
+            state.value,
+            state.version,
+            Timestamp::from_micros(state.updated_at),
+        );
+        let unsubscribed: Vec<StateWatcher> = cell_watchers
+            .into_iter()
+            .filter(|on_change| !on_change(&versioned))
+            .collect();
+        if unsubscribed.is_empty() {
+            return Ok(());
+        }
+
+        let mut cells = watchers.cells.lock();
+        if let Some(cell_watchers) = cells.get_mut(&key) {
+            cell_watchers.retain(|w| !unsubscribed.iter().any(|u| Arc::ptr_eq(w, u)));
+            if cell_watchers.is_empty() {
+                cells.remove(&key);
+            }
+        }
+        Ok(())
+    }
+
     // ========== Delete & List Operations ==========
 
     /// Delete a state cell.
     ///
     /// Returns `true` if the cell existed and was deleted, `false` if it didn't exist.
     pub fn delete(&self, branch_id: &BranchId, space: &str, name: &str) -> StrataResult<bool> {
+        ensure_not_reserved(name)?;
         self.db.transaction(*branch_id, |txn| {
             let key = self.key_for(branch_id, space, name);
             let exists = txn.get(&key)?.is_some();
@@ -333,7 +539,8 @@ impl StateCell {
 
     /// List state cell names with optional prefix filter.
     ///
-    /// Returns all cell names matching the prefix (or all cells if prefix is None).
+    /// Returns all cell names matching the prefix (or all cells if prefix is None),
+    /// in lexicographic (byte-wise) order.
     pub fn list(
         &self,
         branch_id: &BranchId,
@@ -377,7 +584,7 @@ impl StateCell {
         }
     }
 
-    /// List state cell names as of a past timestamp.
+    /// List state cell names as of a past timestamp, in lexicographic order.
     pub fn list_at(
         &self,
         branch_id: &BranchId,
@@ -434,6 +641,7 @@ impl StateCellExt for TransactionContext {
         expected_version: Version,
         new_value: Value,
     ) -> StrataResult<Version> {
+        ensure_not_reserved(name)?;
         let ns = Namespace::for_branch(self.branch_id);
         let key = Key::new_state(ns, name);
 
@@ -467,6 +675,7 @@ impl StateCellExt for TransactionContext {
     }
 
     fn state_set(&mut self, name: &str, value: Value) -> StrataResult<Version> {
+        ensure_not_reserved(name)?;
         let ns = Namespace::for_branch(self.branch_id);
         let key = Key::new_state(ns, name);
 
@@ -888,4 +1097,287 @@ mod tests {
         assert!(version.is_counter());
         assert_eq!(version, Version::counter(2));
     }
+
+    // ========== History Tests ==========
+
+    #[test]
+    fn test_incr_creates_and_adds() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+
+        let first = sc
+            .incr(&branch_id, "default", "count", Value::Int(5))
+            .unwrap();
+        assert_eq!(first.value, Value::Int(5));
+        assert_eq!(first.version, Version::counter(1));
+
+        let second = sc
+            .incr(&branch_id, "default", "count", Value::Int(-8))
+            .unwrap();
+        assert_eq!(second.value, Value::Int(-3));
+        assert_eq!(second.version, Version::counter(2));
+
+        let third = sc
+            .incr(&branch_id, "default", "count", Value::Float(0.25))
+            .unwrap();
+        assert_eq!(third.value, Value::Float(-2.75));
+
+        sc.set(&branch_id, "default", "label", Value::String("x".into()))
+            .unwrap();
+        assert!(sc
+            .incr(&branch_id, "default", "label", Value::Int(1))
+            .unwrap_err()
+            .is_wrong_type());
+    }
+
+    #[test]
+    fn test_incr_concurrent_no_lost_updates() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let sc = sc.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        sc.incr(&branch_id, "default", "count", Value::Int(1))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let state = sc.get(&branch_id, "default", "count").unwrap().unwrap();
+        assert_eq!(state, Value::Int(100));
+    }
+
+    #[test]
+    fn test_incr_hot_cell_waits_instead_of_conflicting() {
+        let (_temp, db, sc) = setup();
+        let branch_id = BranchId::new();
+        let conflicts_before = db.transaction_metrics().total_conflicts;
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let sc = sc.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        sc.incr(&branch_id, "default", "hot", Value::Int(1))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let state = sc.get(&branch_id, "default", "hot").unwrap().unwrap();
+        assert_eq!(state, Value::Int(400));
+        assert_eq!(db.transaction_metrics().total_conflicts, conflicts_before);
+    }
+
+    #[test]
+    fn test_history_limit_newest_first() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        for i in 1..=5 {
+            sc.set(&branch_id, "default", "status", Value::Int(i))
+                .unwrap();
+        }
+
+        let history = sc
+            .history(&branch_id, "default", "status", Some(2))
+            .unwrap()
+            .unwrap();
+        let values: Vec<Value> = history
+            .into_versions()
+            .into_iter()
+            .map(|v| v.value)
+            .collect();
+        assert_eq!(values, vec![Value::Int(5), Value::Int(4)]);
+
+        let all = sc
+            .history(&branch_id, "default", "status", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(all.into_versions().len(), 5);
+        assert!(sc
+            .history(&branch_id, "default", "missing", Some(2))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_version_reads_past_value() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        sc.init(&branch_id, "default", "status", Value::from("idle"))
+            .unwrap();
+        sc.set(&branch_id, "default", "status", Value::from("busy"))
+            .unwrap();
+        sc.set(&branch_id, "default", "status", Value::from("done"))
+            .unwrap();
+
+        let v2 = sc
+            .get_version(&branch_id, "default", "status", Version::counter(2))
+            .unwrap()
+            .unwrap();
+        assert_eq!(v2.value, Value::from("busy"));
+        assert_eq!(v2.version, Version::counter(2));
+
+        let v1 = sc
+            .get_version(&branch_id, "default", "status", Version::counter(1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(v1.value, Value::from("idle"));
+
+        assert!(sc
+            .get_version(&branch_id, "default", "status", Version::counter(9))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_history_survives_compaction_and_reopen() {
+        let (temp, db, sc) = setup();
+        let branch_id = BranchId::new();
+        sc.init(&branch_id, "default", "status", Value::Int(1))
+            .unwrap();
+        for i in 2..=4 {
+            sc.set(&branch_id, "default", "status", Value::Int(i))
+                .unwrap();
+        }
+        db.compact().unwrap();
+        db.shutdown().unwrap();
+        drop(sc);
+        drop(db);
+
+        let db = Database::open(temp.path()).unwrap();
+        let sc = StateCell::new(db.clone());
+        let history = sc
+            .history(&branch_id, "default", "status", None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(history.into_versions().len(), 4);
+        let v1 = sc
+            .get_version(&branch_id, "default", "status", Version::counter(1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(v1.value, Value::Int(1));
+    }
+
+    // ========== Watch Tests ==========
+
+    #[test]
+    fn test_watch_reports_set_and_cas() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        sc.watch(&branch_id, "default", "status", move |v| {
+            tx.send(v.clone()).is_ok()
+        })
+        .unwrap();
+
+        sc.set(&branch_id, "default", "status", Value::from("starting"))
+            .unwrap();
+        sc.cas(
+            &branch_id,
+            "default",
+            "status",
+            Version::counter(1),
+            Value::from("ready"),
+        )
+        .unwrap();
+        // A failed CAS commits nothing and is not reported
+        assert!(sc
+            .cas(
+                &branch_id,
+                "default",
+                "status",
+                Version::counter(1),
+                Value::from("stale"),
+            )
+            .is_err());
+
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.value, Value::from("starting"));
+        assert_eq!(first.version, Version::counter(1));
+        let second = rx.try_recv().unwrap();
+        assert_eq!(second.value, Value::from("ready"));
+        assert_eq!(second.version, Version::counter(2));
+        assert!(second.timestamp.as_micros() > 0);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_watch_is_scoped_to_cell() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        sc.watch(&branch_id, "default", "status", move |v| {
+            tx.send(v.clone()).is_ok()
+        })
+        .unwrap();
+
+        sc.set(&branch_id, "default", "other", Value::Int(1))
+            .unwrap();
+        sc.set(&branch_id, "tenant", "status", Value::Int(2))
+            .unwrap();
+        sc.set(&BranchId::new(), "default", "status", Value::Int(3))
+            .unwrap();
+
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_watch_stops_when_callback_returns_false() {
+        let (_temp, _db, sc) = setup();
+        let branch_id = BranchId::new();
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        sc.watch(&branch_id, "default", "cell", move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            false
+        })
+        .unwrap();
+
+        sc.set(&branch_id, "default", "cell", Value::Int(1))
+            .unwrap();
+        sc.set(&branch_id, "default", "cell", Value::Int(2))
+            .unwrap();
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_watch_callback_can_write_cells() {
+        let (_temp, db, sc) = setup();
+        let branch_id = BranchId::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        // Mirror "leader" into "seen" from the watcher, and watch the mirror
+        let writer = StateCell::new(db.clone());
+        sc.watch(&branch_id, "default", "leader", move |v| {
+            writer
+                .set(&branch_id, "default", "seen", v.value.clone())
+                .is_ok()
+        })
+        .unwrap();
+        sc.watch(&branch_id, "default", "seen", move |v| {
+            tx.send(v.value.clone()).is_ok()
+        })
+        .unwrap();
+
+        sc.set(&branch_id, "default", "leader", Value::from("node-1"))
+            .unwrap();
+        assert_eq!(rx.try_recv().unwrap(), Value::from("node-1"));
+        assert_eq!(
+            sc.get(&branch_id, "default", "seen").unwrap(),
+            Some(Value::from("node-1"))
+        );
+    }
 }
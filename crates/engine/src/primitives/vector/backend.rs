@@ -4,6 +4,11 @@
 //! BruteForceBackend (O(n) search)
 //! HnswBackend (O(log n) search) - reserved
 
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use rayon::prelude::*;
+
 use crate::primitives::vector::{DistanceMetric, VectorConfig, VectorError, VectorId};
 
 /// Trait for swappable vector index implementations
@@ -89,6 +94,29 @@ pub trait VectorIndexBackend: Send + Sync {
     /// Results are sorted by (score desc, VectorId asc) for determinism (Invariant R4).
     fn search(&self, query: &[f32], k: usize) -> Vec<(VectorId, f32)>;
 
+    /// Search for the k nearest neighbors of each query
+    ///
+    /// Result `i` is what `search(&queries[i], k)` returns. The default runs
+    /// the queries in parallel on the rayon pool.
+    fn search_batch(&self, queries: &[Vec<f32>], k: usize) -> Vec<Vec<(VectorId, f32)>> {
+        queries
+            .par_iter()
+            .map(|query| self.search(query, k))
+            .collect()
+    }
+
+    /// Search for the k nearest neighbors among `ids` only
+    ///
+    /// Exact scan of just the given vectors, so the cost is O(ids) rather
+    /// than O(collection); used for namespace-restricted search. Ids not in
+    /// the index are skipped. Same score and ordering rules as `search`.
+    fn search_among(
+        &self,
+        query: &[f32],
+        k: usize,
+        ids: &BTreeSet<VectorId>,
+    ) -> Vec<(VectorId, f32)>;
+
     /// Search for k nearest neighbors as of a given timestamp.
     ///
     /// Backends that support temporal tracking override this. Default: delegates to
@@ -116,7 +144,9 @@ pub trait VectorIndexBackend: Send + Sync {
     fn config(&self) -> VectorConfig;
 
     /// Get a vector by ID (for metadata lookups after search)
-    fn get(&self, id: VectorId) -> Option<&[f32]>;
+    ///
+    /// Dequantized when the collection stores f16 or int8.
+    fn get(&self, id: VectorId) -> Option<Cow<'_, [f32]>>;
 
     /// Check if a vector exists
     fn contains(&self, id: VectorId) -> bool;
@@ -191,4 +221,23 @@ impl IndexBackendFactory {
             IndexBackendFactory::Hnsw(_) => "hnsw",
         }
     }
+
+    /// Index type byte persisted in collection records and snapshots
+    pub fn to_byte(&self) -> u8 {
+        match self {
+            IndexBackendFactory::BruteForce => 0,
+            IndexBackendFactory::Hnsw(_) => 1,
+        }
+    }
+
+    /// Factory for a persisted index type byte
+    ///
+    /// HNSW parameters are not persisted, so HNSW gets the default config.
+    /// Unknown bytes fall back to BruteForce.
+    pub fn from_byte(byte: u8) -> Self {
+        match byte {
+            1 => IndexBackendFactory::Hnsw(super::hnsw::HnswConfig::default()),
+            _ => IndexBackendFactory::BruteForce,
+        }
+    }
 }
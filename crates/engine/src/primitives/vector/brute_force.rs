@@ -6,12 +6,38 @@
 //!
 //! Switch threshold: P95 > 100ms at 50K vectors triggers HNSW priority.
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use rayon::prelude::*;
 
 use crate::primitives::vector::backend::VectorIndexBackend;
-use crate::primitives::vector::distance::compute_similarity;
 use crate::primitives::vector::{DistanceMetric, VectorConfig, VectorError, VectorHeap, VectorId};
 
+/// Queries scored together in one heap pass by `search_batch`
+///
+/// Large enough to reuse each stored vector several times while it is in
+/// cache, small enough to leave work for every rayon thread.
+const QUERIES_PER_PASS: usize = 8;
+
+/// Sort by (score desc, VectorId asc) and keep the first k
+pub(crate) fn top_k(mut results: Vec<(VectorId, f32)>, k: usize) -> Vec<(VectorId, f32)> {
+    // CRITICAL: VectorId tie-break ensures identical results across runs
+    // This satisfies Invariant R4 (Backend tie-break)
+    results.sort_by(|(id_a, score_a), (id_b, score_b)| {
+        // Primary: score descending (higher = better)
+        score_b
+            .partial_cmp(score_a)
+            .unwrap_or(Ordering::Equal)
+            // Secondary: VectorId ascending (deterministic tie-break)
+            .then_with(|| id_a.cmp(id_b))
+    });
+
+    results.truncate(k);
+    results
+}
+
 /// Brute-force vector search backend
 ///
 /// Simple O(n) brute-force implementation.
@@ -78,34 +104,48 @@ impl VectorIndexBackend for BruteForceBackend {
             return Vec::new();
         }
 
-        let metric = self.heap.metric();
-
         // Compute similarities for all vectors
-        // IMPORTANT: heap.iter() returns vectors in VectorId order (BTreeMap)
+        // IMPORTANT: heap.scores() visits vectors in VectorId order (BTreeMap)
         // This ensures deterministic iteration before scoring
-        let mut results: Vec<(VectorId, f32)> = self
-            .heap
-            .iter()
-            .map(|(id, embedding)| {
-                let score = compute_similarity(query, embedding, metric);
-                (id, score)
-            })
-            .collect();
+        top_k(self.heap.scores(query).collect(), k)
+    }
 
-        // Sort by (score desc, VectorId asc) for determinism
-        // CRITICAL: VectorId tie-break ensures identical results across runs
-        // This satisfies Invariant R4 (Backend tie-break)
-        results.sort_by(|(id_a, score_a), (id_b, score_b)| {
-            // Primary: score descending (higher = better)
-            score_b
-                .partial_cmp(score_a)
-                .unwrap_or(Ordering::Equal)
-                // Secondary: VectorId ascending (deterministic tie-break)
-                .then_with(|| id_a.cmp(id_b))
-        });
+    fn search_batch(&self, queries: &[Vec<f32>], k: usize) -> Vec<Vec<(VectorId, f32)>> {
+        if k == 0 || self.heap.is_empty() {
+            return vec![Vec::new(); queries.len()];
+        }
+        if queries.iter().any(|q| q.len() != self.heap.dimension()) {
+            // Mismatched queries come back empty, as in search()
+            return queries.iter().map(|q| self.search(q, k)).collect();
+        }
+
+        // Each chunk of queries shares one pass over the heap; chunks run
+        // in parallel
+        queries
+            .par_chunks(QUERIES_PER_PASS)
+            .flat_map_iter(|chunk| {
+                self.heap
+                    .scores_batch(chunk)
+                    .into_iter()
+                    .map(|scores| top_k(scores, k))
+            })
+            .collect()
+    }
 
-        results.truncate(k);
-        results
+    fn search_among(
+        &self,
+        query: &[f32],
+        k: usize,
+        ids: &BTreeSet<VectorId>,
+    ) -> Vec<(VectorId, f32)> {
+        if k == 0 || query.len() != self.heap.dimension() {
+            return Vec::new();
+        }
+        let scores = ids
+            .iter()
+            .filter_map(|&id| self.heap.score(query, id).map(|score| (id, score)))
+            .collect();
+        top_k(scores, k)
     }
 
     fn len(&self) -> usize {
@@ -124,7 +164,7 @@ impl VectorIndexBackend for BruteForceBackend {
         self.heap.config().clone()
     }
 
-    fn get(&self, id: VectorId) -> Option<&[f32]> {
+    fn get(&self, id: VectorId) -> Option<Cow<'_, [f32]>> {
         self.heap.get(id)
     }
 
@@ -137,9 +177,9 @@ impl VectorIndexBackend for BruteForceBackend {
     }
 
     fn memory_usage(&self) -> usize {
-        // Each active vector: dimension * 4 bytes (f32) for embedding data
+        // Each active vector: dimension * dtype size for embedding data
         // Plus overhead for BTreeMap entries and free_slots
-        let embedding_bytes = std::mem::size_of_val(self.heap.raw_data());
+        let embedding_bytes = self.heap.embedding_bytes();
         let map_overhead =
             self.heap.len() * (std::mem::size_of::<VectorId>() + std::mem::size_of::<usize>() + 64); // BTreeMap node overhead estimate
         let free_slots_bytes = std::mem::size_of_val(self.heap.free_slots());
@@ -162,6 +202,7 @@ impl VectorIndexBackend for BruteForceBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::primitives::vector::distance::compute_similarity;
 
     // Distance metric tests are in distance.rs
 
@@ -277,6 +318,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg_attr(miri, ignore)] // wall-clock bound
     fn test_large_scale_search() {
         let config = VectorConfig::new(128, DistanceMetric::Cosine).unwrap();
         let mut backend = BruteForceBackend::new(&config);
@@ -345,6 +387,37 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn test_search_batch_matches_search() {
+        let config = VectorConfig::new(8, DistanceMetric::Cosine).unwrap();
+        let mut backend = BruteForceBackend::new(&config);
+
+        // Every fifth embedding repeats, so ties exercise the VectorId tie-break
+        for i in 0..50u64 {
+            let embedding: Vec<f32> = (0..8).map(|j| ((i % 10) as f32 + j as f32).sin()).collect();
+            backend.insert(VectorId::new(i), &embedding).unwrap();
+        }
+
+        // Not a multiple of QUERIES_PER_PASS, so the last pass is partial
+        let queries: Vec<Vec<f32>> = (0..(QUERIES_PER_PASS as u64 * 2 + 3))
+            .map(|i| (0..8).map(|j| ((i * 3 + j) as f32).cos()).collect())
+            .collect();
+        let expected: Vec<_> = queries.iter().map(|q| backend.search(q, 7)).collect();
+        assert_eq!(backend.search_batch(&queries, 7), expected);
+
+        assert_eq!(
+            backend.search_batch(&queries, 0),
+            vec![Vec::new(); queries.len()]
+        );
+        assert!(backend.search_batch(&[], 7).is_empty());
+
+        // A mismatched query comes back empty without affecting the others
+        let mixed = vec![queries[0].clone(), vec![0.1; 3]];
+        let results = backend.search_batch(&mixed, 7);
+        assert_eq!(results[0], expected[0]);
+        assert!(results[1].is_empty());
+    }
+
     #[test]
     fn test_from_heap() {
         let config = VectorConfig::for_minilm();
@@ -368,4 +441,76 @@ mod tests {
         assert_eq!(backend.metric(), DistanceMetric::Cosine);
         assert!(backend.is_empty());
     }
+
+    /// Deterministic xorshift so a failing seed reproduces exactly
+    fn next_rand(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn test_fuzz_search_interleaved_with_writes() {
+        const DIM: usize = 3;
+        // Kept small under Miri, which runs orders of magnitude slower
+        let (seeds, steps) = if cfg!(miri) { (2, 60) } else { (16, 1_000) };
+
+        for seed in 1..=seeds {
+            let mut rng = (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            // Dot products of small integers are exact, so scores compare
+            // equal even under Miri's float nondeterminism
+            let config = VectorConfig::new(DIM, DistanceMetric::DotProduct).unwrap();
+            let mut backend = BruteForceBackend::new(&config);
+            let mut model: Vec<(VectorId, Vec<f32>)> = Vec::new();
+
+            for step in 0..steps {
+                let embedding = |rng: &mut u64| -> Vec<f32> {
+                    (0..DIM).map(|_| (next_rand(rng) % 8) as f32).collect()
+                };
+                match next_rand(&mut rng) % 8 {
+                    0..=2 => {
+                        let id = backend.allocate_id();
+                        let e = embedding(&mut rng);
+                        backend.insert(id, &e).unwrap();
+                        model.push((id, e));
+                    }
+                    3 | 4 if !model.is_empty() => {
+                        let (id, _) = model.remove(next_rand(&mut rng) as usize % model.len());
+                        assert!(backend.delete(id).unwrap());
+                    }
+                    5 => {
+                        // Recover through the snapshot path
+                        let (next_id, free_slots) = backend.snapshot_state();
+                        let mut rebuilt = BruteForceBackend::new(&config);
+                        for (id, e) in backend.heap().iter() {
+                            rebuilt.insert_with_id(id, &e).unwrap();
+                        }
+                        rebuilt.restore_snapshot_state(next_id, free_slots);
+                        backend = rebuilt;
+                    }
+                    _ => {
+                        let query = embedding(&mut rng);
+                        let k = next_rand(&mut rng) as usize % 6;
+                        let mut expected: Vec<(VectorId, f32)> = model
+                            .iter()
+                            .map(|(id, e)| (*id, compute_similarity(&query, e, config.metric)))
+                            .collect();
+                        expected.sort_by(|(a, sa), (b, sb)| {
+                            sb.partial_cmp(sa).unwrap().then_with(|| a.cmp(b))
+                        });
+                        expected.truncate(k);
+                        assert_eq!(
+                            backend.search(&query, k),
+                            expected,
+                            "seed {} step {}",
+                            seed,
+                            step
+                        );
+                    }
+                }
+                backend.heap().assert_invariants();
+            }
+        }
+    }
 }
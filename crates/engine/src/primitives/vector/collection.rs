@@ -126,6 +126,50 @@ pub fn validate_vector_key(key: &str) -> Result<(), VectorError> {
     Ok(())
 }
 
+/// Validate a user-supplied vector key
+///
+/// Applies [`validate_vector_key`] and also rejects reserved prefixes.
+/// System inserts skip the reserved-prefix check.
+pub fn validate_user_vector_key(key: &str) -> Result<(), VectorError> {
+    validate_vector_key(key)?;
+    if let Some(prefix) = strata_core::key::reserved_prefix(key) {
+        return Err(VectorError::ReservedKey {
+            key: key.to_string(),
+            prefix: prefix.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Validate a vector namespace
+///
+/// Namespaces partition a collection for search; they follow the key rules
+/// but must not be empty.
+pub fn validate_namespace(namespace: &str) -> Result<(), VectorError> {
+    if namespace.is_empty() {
+        return Err(VectorError::InvalidNamespace {
+            namespace: namespace.to_string(),
+            reason: "Namespace cannot be empty".to_string(),
+        });
+    }
+
+    if namespace.len() > 256 {
+        return Err(VectorError::InvalidNamespace {
+            namespace: namespace.to_string(),
+            reason: "Namespace cannot exceed 256 characters".to_string(),
+        });
+    }
+
+    if namespace.contains('\0') {
+        return Err(VectorError::InvalidNamespace {
+            namespace: namespace.to_string(),
+            reason: "Namespace cannot contain null bytes".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +297,15 @@ mod tests {
         let max_key = "a".repeat(1024);
         assert!(validate_vector_key(&max_key).is_ok());
     }
+
+    #[test]
+    fn test_namespace_validation() {
+        assert!(validate_namespace("tenant-42").is_ok());
+        assert!(matches!(
+            validate_namespace(""),
+            Err(VectorError::InvalidNamespace { .. })
+        ));
+        assert!(validate_namespace(&"n".repeat(257)).is_err());
+        assert!(validate_namespace("a\0b").is_err());
+    }
 }
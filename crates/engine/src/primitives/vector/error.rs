@@ -72,6 +72,24 @@ pub enum VectorError {
         reason: String,
     },
 
+    /// Vector namespace is invalid
+    #[error("Invalid namespace: {namespace} ({reason})")]
+    InvalidNamespace {
+        /// The invalid namespace
+        namespace: String,
+        /// Reason why it's invalid
+        reason: String,
+    },
+
+    /// Vector key uses a reserved prefix
+    #[error("Key '{key}' uses reserved prefix '{prefix}'")]
+    ReservedKey {
+        /// The rejected key
+        key: String,
+        /// The reserved prefix it starts with
+        prefix: String,
+    },
+
     /// Collection configuration cannot be changed
     #[error("Collection '{collection}' config mismatch: {field} cannot be changed")]
     ConfigMismatch {
@@ -134,6 +152,8 @@ impl VectorError {
                 | VectorError::InvalidEmbedding { .. }
                 | VectorError::InvalidCollectionName { .. }
                 | VectorError::InvalidKey { .. }
+                | VectorError::InvalidNamespace { .. }
+                | VectorError::ReservedKey { .. }
                 | VectorError::ConfigMismatch { .. }
         )
     }
@@ -208,6 +228,10 @@ impl From<VectorError> for StrataError {
             VectorError::InvalidKey { key, reason } => StrataError::InvalidInput {
                 message: format!("Invalid key '{}': {}", key, reason),
             },
+            VectorError::InvalidNamespace { namespace, reason } => StrataError::InvalidInput {
+                message: format!("Invalid namespace '{}': {}", namespace, reason),
+            },
+            VectorError::ReservedKey { key, prefix } => StrataError::ReservedKey { key, prefix },
             VectorError::ConfigMismatch { collection, field } => StrataError::InvalidOperation {
                 entity_ref: EntityRef::vector(placeholder_branch_id, collection, ""),
                 reason: format!("Config field '{}' cannot be changed", field),
@@ -1,11 +1,13 @@
 //! Metadata filtering for vector search
 //!
 //! Re-exports canonical types from strata-core.
-//! Supports only equality filtering on top-level scalar fields.
-//! Complex filters (ranges, nested paths, arrays) are deferred to future versions.
+//! Supports comparison, range, prefix and set conditions on top-level scalar
+//! fields, combined with AND, OR and NOT. Nested paths are not supported.
 
 // Re-export canonical filter types from core
-pub use strata_core::primitives::{FilterCondition, FilterOp, JsonScalar, MetadataFilter};
+pub use strata_core::primitives::{
+    FilterCondition, FilterOp, JsonScalar, MetadataFilter, NestedFilter,
+};
 
 #[cfg(test)]
 mod tests {
@@ -1,7 +1,12 @@
 //! Vector Heap - Contiguous embedding storage
 //!
-//! VectorHeap stores embeddings in a contiguous Vec<f32> for cache-friendly
-//! similarity computation. Uses BTreeMap for deterministic iteration order.
+//! VectorHeap stores embeddings contiguously for cache-friendly similarity
+//! computation. Uses BTreeMap for deterministic iteration order.
+//!
+//! Embeddings are held in the collection's `StorageDtype`: f32, f16, or
+//! int8 with a per-vector scale. Quantized heaps score queries directly
+//! against the stored values (see `quantize`), and `get` returns a
+//! dequantized copy.
 //!
 //! # Critical Invariants
 //!
@@ -10,33 +15,185 @@
 //! - **T4**: next_id is monotonically increasing and MUST be persisted in snapshots
 //! - **R3**: BTreeMap guarantees deterministic iteration order
 
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::primitives::vector::distance::compute_similarity;
 use crate::primitives::vector::error::{VectorError, VectorResult};
-use crate::primitives::vector::types::{DistanceMetric, VectorConfig, VectorId};
+use crate::primitives::vector::quantize::{
+    f16_to_f32, f32_to_f16, quantize_int8, similarity_with, Int8Params,
+};
+use crate::primitives::vector::types::{DistanceMetric, StorageDtype, VectorConfig, VectorId};
+
+/// Embedding values in the collection's storage dtype
+///
+/// Offsets are in values, not bytes, so slot bookkeeping is the same for
+/// every dtype.
+enum HeapData {
+    F32(Vec<f32>),
+    /// IEEE 754 half precision bits
+    F16(Vec<u16>),
+    /// Codes plus one scale per slot (indexed by offset / dimension)
+    Int8 {
+        codes: Vec<u8>,
+        params: Vec<Int8Params>,
+    },
+}
+
+impl HeapData {
+    fn new(dtype: StorageDtype) -> Self {
+        match dtype {
+            StorageDtype::F32 => HeapData::F32(Vec::new()),
+            StorageDtype::F16 => HeapData::F16(Vec::new()),
+            StorageDtype::Int8 => HeapData::Int8 {
+                codes: Vec::new(),
+                params: Vec::new(),
+            },
+        }
+    }
+
+    /// Number of stored values
+    fn len(&self) -> usize {
+        match self {
+            HeapData::F32(data) => data.len(),
+            HeapData::F16(data) => data.len(),
+            HeapData::Int8 { codes, .. } => codes.len(),
+        }
+    }
+
+    /// Bytes held by the stored values
+    fn byte_size(&self) -> usize {
+        match self {
+            HeapData::F32(data) => std::mem::size_of_val(data.as_slice()),
+            HeapData::F16(data) => std::mem::size_of_val(data.as_slice()),
+            HeapData::Int8 { codes, params } => {
+                codes.len() + std::mem::size_of_val(params.as_slice())
+            }
+        }
+    }
+
+    /// Append one embedding
+    fn push(&mut self, embedding: &[f32]) {
+        match self {
+            HeapData::F32(data) => data.extend_from_slice(embedding),
+            HeapData::F16(data) => data.extend(embedding.iter().map(|&x| f32_to_f16(x))),
+            HeapData::Int8 { codes, params } => {
+                let start = codes.len();
+                codes.resize(start + embedding.len(), 0);
+                params.push(quantize_int8(embedding, &mut codes[start..]));
+            }
+        }
+    }
+
+    /// Overwrite the embedding at `offset`
+    fn write(&mut self, offset: usize, embedding: &[f32]) {
+        let end = offset + embedding.len();
+        match self {
+            HeapData::F32(data) => data[offset..end].copy_from_slice(embedding),
+            HeapData::F16(data) => {
+                for (half, &x) in data[offset..end].iter_mut().zip(embedding) {
+                    *half = f32_to_f16(x);
+                }
+            }
+            HeapData::Int8 { codes, params } => {
+                params[offset / embedding.len()] =
+                    quantize_int8(embedding, &mut codes[offset..end]);
+            }
+        }
+    }
+
+    /// Zero the slot at `offset`
+    fn zero(&mut self, offset: usize, dimension: usize) {
+        let end = offset + dimension;
+        match self {
+            HeapData::F32(data) => data[offset..end].fill(0.0),
+            HeapData::F16(data) => data[offset..end].fill(0),
+            HeapData::Int8 { codes, params } => {
+                codes[offset..end].fill(0);
+                params[offset / dimension] = Int8Params::default();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        match self {
+            HeapData::F32(data) => data.clear(),
+            HeapData::F16(data) => data.clear(),
+            HeapData::Int8 { codes, params } => {
+                codes.clear();
+                params.clear();
+            }
+        }
+    }
+
+    /// Embedding at `offset`, borrowed for f32 and dequantized otherwise
+    fn get(&self, offset: usize, dimension: usize) -> Cow<'_, [f32]> {
+        let end = offset + dimension;
+        match self {
+            HeapData::F32(data) => Cow::Borrowed(&data[offset..end]),
+            HeapData::F16(data) => {
+                Cow::Owned(data[offset..end].iter().map(|&h| f16_to_f32(h)).collect())
+            }
+            HeapData::Int8 { codes, params } => {
+                let p = params[offset / dimension];
+                Cow::Owned(codes[offset..end].iter().map(|&c| p.decode(c)).collect())
+            }
+        }
+    }
+
+    /// Similarity of `query` to the embedding at `offset`
+    fn similarity(
+        &self,
+        query: &[f32],
+        offset: usize,
+        dimension: usize,
+        metric: DistanceMetric,
+    ) -> f32 {
+        let end = offset + dimension;
+        match self {
+            HeapData::F32(data) => compute_similarity(query, &data[offset..end], metric),
+            HeapData::F16(data) => similarity_with(
+                query,
+                data[offset..end].iter().map(|&h| f16_to_f32(h)),
+                metric,
+            ),
+            HeapData::Int8 { codes, params } => {
+                let p = params[offset / dimension];
+                similarity_with(
+                    query,
+                    codes[offset..end].iter().map(|&c| p.decode(c)),
+                    metric,
+                )
+            }
+        }
+    }
+}
 
 /// Per-collection vector heap
 ///
-/// Stores embeddings in a contiguous Vec<f32> for cache-friendly
-/// similarity computation. Uses BTreeMap for deterministic iteration.
+/// Stores embeddings contiguously in the collection's storage dtype for
+/// cache-friendly similarity computation. Uses BTreeMap for deterministic
+/// iteration.
 ///
 /// # Critical Invariants
 ///
 /// - id_to_offset is the SOLE source of truth for active vectors (S7)
 /// - VectorIds are NEVER reused, only storage slots are reused (S4)
 /// - next_id is monotonically increasing and MUST be persisted in snapshots (T4)
-/// - free_slots MUST be persisted in snapshots for correct recovery
+/// - free_slots holds distinct, dimension-aligned, in-bounds offsets that no
+///   live vector occupies; anything else lets an insert overwrite a live
+///   embedding or index past the end of `data`
 pub struct VectorHeap {
     /// Collection configuration
     config: VectorConfig,
 
     /// Contiguous embedding storage
     /// Layout: [v0_dim0, v0_dim1, ..., v0_dimN, v1_dim0, v1_dim1, ...]
-    /// Each vector occupies `config.dimension` consecutive f32 values.
-    data: Vec<f32>,
+    /// Each vector occupies `config.dimension` consecutive values.
+    data: HeapData,
 
-    /// VectorId -> offset in data (in floats, not bytes)
+    /// VectorId -> offset in data (in values, not bytes)
     ///
     /// IMPORTANT: Use BTreeMap for deterministic iteration order.
     /// HashMap would cause nondeterministic search results.
@@ -77,8 +234,8 @@ impl VectorHeap {
     /// where IDs are positive integers.
     pub fn new(config: VectorConfig) -> Self {
         VectorHeap {
+            data: HeapData::new(config.storage_dtype),
             config,
-            data: Vec::new(),
             id_to_offset: BTreeMap::new(),
             free_slots: Vec::new(),
             next_id: AtomicU64::new(1),
@@ -88,8 +245,12 @@ impl VectorHeap {
 
     /// Create from snapshot data (for recovery)
     ///
+    /// `data` holds f32 values in slot order and is stored in the config's
+    /// dtype.
+    ///
     /// CRITICAL: next_id and free_slots MUST be restored from snapshot
     /// to maintain invariants T4 (VectorId monotonicity across crashes).
+    /// Free slots that are not actually free in `data` are dropped.
     pub fn from_snapshot(
         config: VectorConfig,
         data: Vec<f32>,
@@ -97,14 +258,26 @@ impl VectorHeap {
         free_slots: Vec<usize>,
         next_id: u64,
     ) -> Self {
-        VectorHeap {
+        let heap_data = match config.storage_dtype {
+            StorageDtype::F32 => HeapData::F32(data),
+            dtype => {
+                let mut heap_data = HeapData::new(dtype);
+                for embedding in data.chunks(config.dimension.max(1)) {
+                    heap_data.push(embedding);
+                }
+                heap_data
+            }
+        };
+        let mut heap = VectorHeap {
             config,
-            data,
+            data: heap_data,
             id_to_offset,
-            free_slots,
+            free_slots: Vec::new(),
             next_id: AtomicU64::new(next_id),
             version: AtomicU64::new(0),
-        }
+        };
+        heap.free_slots = heap.valid_free_slots(free_slots);
+        heap
     }
 
     /// Get the dimension of vectors in this heap
@@ -117,6 +290,11 @@ impl VectorHeap {
         self.config.metric
     }
 
+    /// Get the storage data type
+    pub fn storage_dtype(&self) -> StorageDtype {
+        self.config.storage_dtype
+    }
+
     /// Get the config
     pub fn config(&self) -> &VectorConfig {
         &self.config
@@ -153,9 +331,32 @@ impl VectorHeap {
     /// to restore the exact next_id and free_slots from the snapshot.
     ///
     /// CRITICAL: This ensures VectorId uniqueness across restarts (T4).
+    ///
+    /// Re-inserting packs vectors from offset 0, so the snapshot's free
+    /// slots may now hold live vectors or lie past the end of the data.
+    /// Only slots that are still free are kept.
     pub fn restore_snapshot_state(&mut self, next_id: u64, free_slots: Vec<usize>) {
         self.next_id.store(next_id, Ordering::Relaxed);
-        self.free_slots = free_slots;
+        self.free_slots = self.valid_free_slots(free_slots);
+    }
+
+    /// Filter `free_slots` down to distinct, aligned, in-bounds offsets that
+    /// no live vector occupies
+    fn valid_free_slots(&self, free_slots: Vec<usize>) -> Vec<usize> {
+        let dimension = self.config.dimension;
+        let live: BTreeSet<usize> = self.id_to_offset.values().copied().collect();
+        let mut seen = BTreeSet::new();
+        free_slots
+            .into_iter()
+            .filter(|&slot| {
+                slot.checked_rem(dimension) == Some(0)
+                    && slot
+                        .checked_add(dimension)
+                        .is_some_and(|end| end <= self.data.len())
+                    && !live.contains(&slot)
+                    && seen.insert(slot)
+            })
+            .collect()
     }
 
     /// Allocate a new VectorId (monotonically increasing)
@@ -188,22 +389,18 @@ impl VectorHeap {
 
         if let Some(&offset) = self.id_to_offset.get(&id) {
             // Update existing vector in place
-            let start = offset;
-            let end = offset + self.config.dimension;
-            self.data[start..end].copy_from_slice(embedding);
+            self.data.write(offset, embedding);
         } else {
             // Insert new vector
             let offset = if let Some(slot) = self.free_slots.pop() {
                 // Reuse deleted slot
                 // CRITICAL: Must copy embedding into the reused slot
-                let start = slot;
-                let end = slot + self.config.dimension;
-                self.data[start..end].copy_from_slice(embedding);
+                self.data.write(slot, embedding);
                 slot
             } else {
                 // Append to end
                 let offset = self.data.len();
-                self.data.extend_from_slice(embedding);
+                self.data.push(embedding);
                 offset
             };
             self.id_to_offset.insert(id, offset);
@@ -262,9 +459,7 @@ impl VectorHeap {
             self.free_slots.push(offset);
 
             // Zero out data (security: prevent information leakage)
-            let start = offset;
-            let end = offset + self.config.dimension;
-            self.data[start..end].fill(0.0);
+            self.data.zero(offset, self.config.dimension);
 
             self.version.fetch_add(1, Ordering::Release);
             true
@@ -295,12 +490,56 @@ impl VectorHeap {
 
     /// Get embedding by VectorId
     ///
+    /// Borrowed for f32 storage; a dequantized copy for f16 and int8.
     /// Returns None if the vector doesn't exist.
-    pub fn get(&self, id: VectorId) -> Option<&[f32]> {
+    pub fn get(&self, id: VectorId) -> Option<Cow<'_, [f32]>> {
         let offset = *self.id_to_offset.get(&id)?;
-        let start = offset;
-        let end = offset + self.config.dimension;
-        Some(&self.data[start..end])
+        Some(self.data.get(offset, self.config.dimension))
+    }
+
+    /// Similarity of `query` to a stored vector under the heap's metric
+    ///
+    /// Quantized embeddings are scored in place, without a dequantized
+    /// copy. Returns None if the vector doesn't exist.
+    pub fn score(&self, query: &[f32], id: VectorId) -> Option<f32> {
+        let offset = *self.id_to_offset.get(&id)?;
+        Some(
+            self.data
+                .similarity(query, offset, self.config.dimension, self.config.metric),
+        )
+    }
+
+    /// Score `query` against every vector, in VectorId order
+    ///
+    /// Same ordering guarantee as [`iter`](Self::iter) (Invariant R3).
+    pub fn scores<'a>(&'a self, query: &'a [f32]) -> impl Iterator<Item = (VectorId, f32)> + 'a {
+        self.id_to_offset.iter().map(move |(&id, &offset)| {
+            let score =
+                self.data
+                    .similarity(query, offset, self.config.dimension, self.config.metric);
+            (id, score)
+        })
+    }
+
+    /// Score every query against every vector in one pass over the heap
+    ///
+    /// Each stored vector is read once and scored against all of `queries`
+    /// while it is in cache. Result `i` holds the scores for `queries[i]`,
+    /// in VectorId order like [`scores`](Self::scores).
+    pub fn scores_batch(&self, queries: &[Vec<f32>]) -> Vec<Vec<(VectorId, f32)>> {
+        let mut results: Vec<Vec<(VectorId, f32)>> = queries
+            .iter()
+            .map(|_| Vec::with_capacity(self.len()))
+            .collect();
+        for (&id, &offset) in &self.id_to_offset {
+            for (query, scores) in queries.iter().zip(&mut results) {
+                let score =
+                    self.data
+                        .similarity(query, offset, self.config.dimension, self.config.metric);
+                scores.push((id, score));
+            }
+        }
+        results
     }
 
     /// Check if a vector exists
@@ -313,13 +552,11 @@ impl VectorHeap {
     /// IMPORTANT: This uses BTreeMap iteration which guarantees sorted order.
     /// This is critical for deterministic brute-force search (Invariant R3).
     /// HashMap iteration would be nondeterministic.
-    pub fn iter(&self) -> impl Iterator<Item = (VectorId, &[f32])> {
+    pub fn iter(&self) -> impl Iterator<Item = (VectorId, Cow<'_, [f32]>)> {
         // BTreeMap iterates in key order (VectorId ascending)
-        self.id_to_offset.iter().map(|(&id, &offset)| {
-            let start = offset;
-            let end = offset + self.config.dimension;
-            (id, &self.data[start..end])
-        })
+        self.id_to_offset
+            .iter()
+            .map(|(&id, &offset)| (id, self.data.get(offset, self.config.dimension)))
     }
 
     /// Get all VectorIds in deterministic order
@@ -327,15 +564,71 @@ impl VectorHeap {
         self.id_to_offset.keys().copied()
     }
 
-    /// Get raw data slice (for snapshot serialization)
-    pub fn raw_data(&self) -> &[f32] {
-        &self.data
+    /// Get all stored values as f32, in slot order
+    ///
+    /// Dequantizes the whole heap for f16 and int8 storage.
+    pub fn raw_data(&self) -> Cow<'_, [f32]> {
+        match &self.data {
+            HeapData::F32(data) => Cow::Borrowed(data),
+            _ => {
+                let dimension = self.config.dimension;
+                let slots = self.data.len() / dimension.max(1);
+                Cow::Owned(
+                    (0..slots)
+                        .flat_map(|slot| self.data.get(slot * dimension, dimension).into_owned())
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Bytes used by embedding storage
+    pub fn embedding_bytes(&self) -> usize {
+        self.data.byte_size()
     }
 
     /// Get id_to_offset map (for snapshot serialization)
     pub fn id_to_offset_map(&self) -> &BTreeMap<VectorId, usize> {
         &self.id_to_offset
     }
+
+    /// Panic if the slot bookkeeping is inconsistent
+    ///
+    /// Every live and free slot must be aligned, in bounds and distinct,
+    /// free slots must be zeroed, and no live id may reach next_id.
+    #[cfg(test)]
+    pub(crate) fn assert_invariants(&self) {
+        let dimension = self.config.dimension;
+        let mut slots = BTreeSet::new();
+        for (id, &offset) in &self.id_to_offset {
+            assert_eq!(offset % dimension, 0, "{:?} at misaligned offset", id);
+            assert!(
+                offset + dimension <= self.data.len(),
+                "{:?} out of bounds",
+                id
+            );
+            assert!(slots.insert(offset), "{:?} shares offset {}", id, offset);
+            assert!(id.as_u64() < self.next_id_value(), "{:?} >= next_id", id);
+        }
+        for &slot in &self.free_slots {
+            assert_eq!(slot % dimension, 0, "misaligned free slot {}", slot);
+            assert!(
+                slot + dimension <= self.data.len(),
+                "free slot {} out of bounds",
+                slot
+            );
+            assert!(
+                slots.insert(slot),
+                "free slot {} is live or listed twice",
+                slot
+            );
+            assert!(
+                self.data.get(slot, dimension).iter().all(|&x| x == 0.0),
+                "free slot {} not zeroed",
+                slot
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -585,4 +878,231 @@ mod tests {
             assert_eq!(data[i], 0.0, "Data should be zeroed after deletion");
         }
     }
+
+    #[test]
+    fn test_restore_snapshot_state_drops_stale_free_slots() {
+        let config = VectorConfig::new(4, DistanceMetric::Cosine).unwrap();
+
+        // Recovery re-inserts the one surviving vector at offset 0, while
+        // the snapshot recorded offset 0 as free
+        let mut heap = VectorHeap::new(config);
+        let survivor = VectorId::new(2);
+        heap.insert_with_id(survivor, &[1.0, 2.0, 3.0, 4.0])
+            .unwrap();
+        heap.restore_snapshot_state(3, vec![0, 4, 6, 400]);
+        assert!(heap.free_slots().is_empty());
+        heap.assert_invariants();
+
+        let id = heap.insert(&[5.0, 6.0, 7.0, 8.0]).unwrap();
+        assert_eq!(&*heap.get(survivor).unwrap(), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(&*heap.get(id).unwrap(), &[5.0, 6.0, 7.0, 8.0]);
+        heap.assert_invariants();
+    }
+
+    #[test]
+    fn test_from_snapshot_drops_invalid_free_slots() {
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        let data = vec![0.0, 0.0, 1.0, 1.0];
+        let id_to_offset = BTreeMap::from([(VectorId::new(2), 2)]);
+
+        // 0 is genuinely free; 2 is live, 1 misaligned, 4 out of bounds
+        let heap = VectorHeap::from_snapshot(config, data, id_to_offset, vec![0, 2, 1, 4, 0], 3);
+        assert_eq!(heap.free_slots(), &[0]);
+        heap.assert_invariants();
+    }
+
+    #[test]
+    fn test_quantized_storage_shrinks_memory() {
+        let embedding: Vec<f32> = (0..384).map(|i| (i as f32).sin()).collect();
+        let bytes = |dtype| {
+            let config = VectorConfig::for_minilm().with_storage_dtype(dtype);
+            let mut heap = VectorHeap::new(config);
+            for _ in 0..10 {
+                heap.insert(&embedding).unwrap();
+            }
+            heap.embedding_bytes()
+        };
+
+        assert_eq!(bytes(StorageDtype::F32), 10 * 384 * 4);
+        assert_eq!(bytes(StorageDtype::F16), 10 * 384 * 2);
+        // One byte per value plus a min and scale per vector
+        assert_eq!(bytes(StorageDtype::Int8), 10 * 384 + 10 * 8);
+    }
+
+    #[test]
+    fn test_quantized_get_and_score_approximate() {
+        let embeddings = [
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.7, 0.7, 0.1, 0.0],
+            vec![-0.5, 0.25, 0.8, -0.1],
+        ];
+        let query = [0.9, 0.2, 0.0, 0.1];
+
+        for dtype in [StorageDtype::F16, StorageDtype::Int8] {
+            let config = VectorConfig::new(4, DistanceMetric::Cosine)
+                .unwrap()
+                .with_storage_dtype(dtype);
+            let mut heap = VectorHeap::new(config);
+            assert_eq!(heap.storage_dtype(), dtype);
+            let ids: Vec<VectorId> = embeddings.iter().map(|e| heap.insert(e).unwrap()).collect();
+
+            for (id, embedding) in ids.iter().zip(&embeddings) {
+                let stored = heap.get(*id).unwrap();
+                for (a, b) in stored.iter().zip(embedding) {
+                    assert!((a - b).abs() < 0.01, "{:?}: {} vs {}", dtype, a, b);
+                }
+                let exact = compute_similarity(&query, embedding, DistanceMetric::Cosine);
+                let score = heap.score(&query, *id).unwrap();
+                assert!((score - exact).abs() < 0.01, "{:?}", dtype);
+            }
+
+            let ranked: Vec<VectorId> = {
+                let mut scores: Vec<(VectorId, f32)> = heap.scores(&query).collect();
+                scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+                scores.into_iter().map(|(id, _)| id).collect()
+            };
+            assert_eq!(ranked, ids, "{:?}", dtype);
+        }
+    }
+
+    #[test]
+    fn test_quantized_slot_reuse_and_snapshot() {
+        for dtype in [StorageDtype::F16, StorageDtype::Int8] {
+            let config = VectorConfig::new(3, DistanceMetric::Euclidean)
+                .unwrap()
+                .with_storage_dtype(dtype);
+            let mut heap = VectorHeap::new(config.clone());
+            let id1 = heap.insert(&[1.0, 2.0, 3.0]).unwrap();
+            let id2 = heap.insert(&[4.0, 5.0, 6.0]).unwrap();
+            let bytes = heap.embedding_bytes();
+            heap.delete(id1);
+            heap.assert_invariants();
+
+            // The freed slot is reused, so storage does not grow
+            let id3 = heap.insert(&[-1.0, 0.0, 1.0]).unwrap();
+            assert_eq!(heap.embedding_bytes(), bytes);
+            heap.assert_invariants();
+
+            // Snapshots carry dequantized values, which re-encode to the same vector
+            let restored = VectorHeap::from_snapshot(
+                config,
+                heap.raw_data().to_vec(),
+                heap.id_to_offset_map().clone(),
+                heap.free_slots().to_vec(),
+                heap.next_id_value(),
+            );
+            assert_eq!(restored.storage_dtype(), dtype);
+            for id in [id2, id3] {
+                let (a, b) = (restored.get(id).unwrap(), heap.get(id).unwrap());
+                for (x, y) in a.iter().zip(b.iter()) {
+                    assert!((x - y).abs() < 1e-5, "{:?}: {} vs {}", dtype, x, y);
+                }
+            }
+            restored.assert_invariants();
+        }
+    }
+
+    /// Deterministic xorshift so a failing seed reproduces exactly
+    fn next_rand(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // Kept small under Miri, which runs orders of magnitude slower
+    const FUZZ_SEEDS: u64 = if cfg!(miri) { 2 } else { 32 };
+    const FUZZ_STEPS: usize = if cfg!(miri) { 100 } else { 2_000 };
+
+    /// Rebuild `heap` the way snapshot recovery does: re-insert live vectors
+    /// in id order, then restore next_id and free_slots
+    fn rebuild_like_recovery(heap: &VectorHeap) -> VectorHeap {
+        let mut rebuilt = VectorHeap::new(heap.config().clone());
+        for (id, embedding) in heap.iter() {
+            rebuilt.insert_with_id(id, &embedding).unwrap();
+        }
+        rebuilt.restore_snapshot_state(heap.next_id_value(), heap.free_slots().to_vec());
+        rebuilt
+    }
+
+    #[test]
+    fn test_fuzz_interleaved_operations_match_model() {
+        const DIM: usize = 4;
+
+        for seed in 1..=FUZZ_SEEDS {
+            let mut rng = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            let config = VectorConfig::new(DIM, DistanceMetric::Cosine).unwrap();
+            let mut heap = VectorHeap::new(config);
+            let mut model: BTreeMap<VectorId, Vec<f32>> = BTreeMap::new();
+
+            for step in 0..FUZZ_STEPS {
+                let embedding: Vec<f32> = (0..DIM)
+                    .map(|_| (next_rand(&mut rng) % 1000) as f32 + 1.0)
+                    .collect();
+                let existing = if model.is_empty() {
+                    None
+                } else {
+                    let nth = next_rand(&mut rng) as usize % model.len();
+                    model.keys().nth(nth).copied()
+                };
+
+                match next_rand(&mut rng) % 12 {
+                    0..=3 => {
+                        let id = heap.insert(&embedding).unwrap();
+                        assert!(model.insert(id, embedding).is_none(), "id reused");
+                    }
+                    4 => {
+                        if let Some(id) = existing {
+                            heap.upsert(id, &embedding).unwrap();
+                            model.insert(id, embedding);
+                        }
+                    }
+                    5 => {
+                        // WAL replay may skip ahead of next_id
+                        let id = VectorId::new(heap.next_id_value() + next_rand(&mut rng) % 3);
+                        heap.insert_with_id(id, &embedding).unwrap();
+                        model.insert(id, embedding);
+                    }
+                    6..=8 => {
+                        if let Some(id) = existing {
+                            assert!(heap.delete(id));
+                            model.remove(&id);
+                        }
+                    }
+                    9 => {
+                        let missing = VectorId::new(heap.next_id_value() + 10);
+                        assert!(!heap.delete(missing));
+                        assert!(heap.upsert(missing, &[0.0; DIM + 1]).is_err());
+                    }
+                    10 => {
+                        heap = if next_rand(&mut rng) % 2 == 0 {
+                            rebuild_like_recovery(&heap)
+                        } else {
+                            VectorHeap::from_snapshot(
+                                heap.config().clone(),
+                                heap.raw_data().to_vec(),
+                                heap.id_to_offset_map().clone(),
+                                heap.free_slots().to_vec(),
+                                heap.next_id_value(),
+                            )
+                        };
+                    }
+                    _ => {
+                        if next_rand(&mut rng) % 20 == 0 {
+                            heap.clear();
+                            model.clear();
+                        }
+                    }
+                }
+
+                heap.assert_invariants();
+                assert_eq!(heap.len(), model.len(), "seed {} step {}", seed, step);
+                let actual: Vec<(VectorId, Vec<f32>)> =
+                    heap.iter().map(|(id, e)| (id, e.to_vec())).collect();
+                let expected: Vec<(VectorId, Vec<f32>)> =
+                    model.iter().map(|(id, e)| (*id, e.clone())).collect();
+                assert_eq!(actual, expected, "seed {} step {}", seed, step);
+            }
+        }
+    }
 }
@@ -23,11 +23,12 @@
 //! - BTreeSet for neighbor lists (sorted)
 //! - Tie-breaking: (score desc, VectorId asc)
 
+use std::borrow::Cow;
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 
 use crate::primitives::vector::backend::VectorIndexBackend;
-use crate::primitives::vector::distance::compute_similarity;
+use crate::primitives::vector::brute_force::top_k;
 use crate::primitives::vector::heap::VectorHeap;
 use crate::primitives::vector::{DistanceMetric, VectorConfig, VectorError, VectorId};
 
@@ -137,8 +138,7 @@ impl Ord for ScoredId {
 /// HNSW index backend
 pub struct HnswBackend {
     config: HnswConfig,
-    vector_config: VectorConfig,
-    /// Embedding storage (reuses VectorHeap for contiguous f32 storage)
+    /// Embedding storage (reuses VectorHeap, including its storage dtype)
     heap: VectorHeap,
     /// Graph structure: VectorId -> HnswNode
     /// BTreeMap for deterministic iteration (Invariant R3)
@@ -160,7 +160,6 @@ impl HnswBackend {
     pub fn new(vector_config: &VectorConfig, hnsw_config: HnswConfig) -> Self {
         Self {
             config: hnsw_config,
-            vector_config: vector_config.clone(),
             heap: VectorHeap::new(vector_config.clone()),
             nodes: BTreeMap::new(),
             entry_point: None,
@@ -175,7 +174,6 @@ impl HnswBackend {
     pub fn from_heap(heap: VectorHeap, hnsw_config: HnswConfig) -> Self {
         Self {
             config: hnsw_config,
-            vector_config: heap.config().clone(),
             heap,
             nodes: BTreeMap::new(),
             entry_point: None,
@@ -242,13 +240,10 @@ impl HnswBackend {
         ef: usize,
         layer: usize,
     ) -> Vec<ScoredId> {
-        let metric = self.vector_config.metric;
-
-        let entry_embedding = match self.heap.get(entry_id) {
-            Some(e) => e,
+        let entry_score = match self.heap.score(query, entry_id) {
+            Some(score) => score,
             None => return Vec::new(),
         };
-        let entry_score = compute_similarity(query, entry_embedding, metric);
 
         let mut visited = BTreeSet::new();
         visited.insert(entry_id);
@@ -293,9 +288,7 @@ impl HnswBackend {
                         }
                         visited.insert(neighbor_id);
 
-                        if let Some(neighbor_embedding) = self.heap.get(neighbor_id) {
-                            let score = compute_similarity(query, neighbor_embedding, metric);
-
+                        if let Some(score) = self.heap.score(query, neighbor_id) {
                             let worst_result_score = results
                                 .peek()
                                 .map(|r| r.0.score)
@@ -355,18 +348,16 @@ impl HnswBackend {
         from_layer: usize,
         to_layer: usize,
     ) -> VectorId {
-        let metric = self.vector_config.metric;
         let mut current = entry_id;
 
         for layer in (to_layer..=from_layer).rev() {
             let mut improved = true;
             while improved {
                 improved = false;
-                let current_embedding = match self.heap.get(current) {
-                    Some(e) => e,
+                let current_score = match self.heap.score(query, current) {
+                    Some(score) => score,
                     None => break,
                 };
-                let current_score = compute_similarity(query, current_embedding, metric);
 
                 // Find the globally best neighbor (not just the first improvement)
                 let mut best_score = current_score;
@@ -375,8 +366,7 @@ impl HnswBackend {
                 if let Some(node) = self.nodes.get(&current) {
                     if layer < node.neighbors.len() {
                         for &neighbor_id in &node.neighbors[layer] {
-                            if let Some(neighbor_embedding) = self.heap.get(neighbor_id) {
-                                let score = compute_similarity(query, neighbor_embedding, metric);
+                            if let Some(score) = self.heap.score(query, neighbor_id) {
                                 if score > best_score
                                     || (score == best_score && neighbor_id < best_id)
                                 {
@@ -410,8 +400,6 @@ impl HnswBackend {
 
     /// Prune a node's neighbors at a given layer to max_connections
     fn prune_neighbors_for(&mut self, id: VectorId, layer: usize, max_connections: usize) {
-        let metric = self.vector_config.metric;
-
         let embedding = match self.heap.get(id) {
             Some(e) => e.to_vec(),
             None => return,
@@ -431,10 +419,9 @@ impl HnswBackend {
         let mut scored: Vec<ScoredId> = neighbors
             .iter()
             .filter_map(|&nid| {
-                self.heap.get(nid).map(|n_emb| ScoredId {
-                    score: compute_similarity(&embedding, n_emb, metric),
-                    id: nid,
-                })
+                self.heap
+                    .score(&embedding, nid)
+                    .map(|score| ScoredId { score, id: nid })
             })
             .collect();
 
@@ -854,6 +841,25 @@ impl VectorIndexBackend for HnswBackend {
         results
     }
 
+    fn search_among(
+        &self,
+        query: &[f32],
+        k: usize,
+        ids: &BTreeSet<VectorId>,
+    ) -> Vec<(VectorId, f32)> {
+        if k == 0 || query.len() != self.heap.dimension() {
+            return Vec::new();
+        }
+        // Scan the ids exactly: walking the graph would mostly visit
+        // vectors outside the requested set
+        let scores = ids
+            .iter()
+            .filter(|id| self.nodes.get(id).is_some_and(|n| !n.is_deleted()))
+            .filter_map(|&id| self.heap.score(query, id).map(|score| (id, score)))
+            .collect();
+        top_k(scores, k)
+    }
+
     fn search_at(&self, query: &[f32], k: usize, as_of_ts: u64) -> Vec<(VectorId, f32)> {
         if self.nodes.is_empty() || k == 0 {
             return Vec::new();
@@ -901,7 +907,7 @@ impl VectorIndexBackend for HnswBackend {
         self.heap.config().clone()
     }
 
-    fn get(&self, id: VectorId) -> Option<&[f32]> {
+    fn get(&self, id: VectorId) -> Option<Cow<'_, [f32]>> {
         self.heap.get(id)
     }
 
@@ -919,7 +925,7 @@ impl VectorIndexBackend for HnswBackend {
 
     fn memory_usage(&self) -> usize {
         // Embedding storage
-        let embedding_bytes = std::mem::size_of_val(self.heap.raw_data());
+        let embedding_bytes = self.heap.embedding_bytes();
         // Graph structure: each node has neighbor lists
         let graph_bytes: usize = self
             .nodes
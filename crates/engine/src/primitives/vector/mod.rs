@@ -27,6 +27,7 @@ pub mod error;
 pub mod filter;
 pub mod heap;
 pub mod hnsw;
+mod quantize;
 pub mod recovery;
 pub mod snapshot;
 pub mod store;
@@ -36,10 +37,11 @@ pub mod wal;
 pub use backend::{IndexBackendFactory, VectorIndexBackend};
 pub use brute_force::BruteForceBackend;
 pub use collection::{
-    validate_collection_name, validate_system_collection_name, validate_vector_key,
+    validate_collection_name, validate_system_collection_name, validate_user_vector_key,
+    validate_vector_key,
 };
 pub use error::{VectorError, VectorResult};
-pub use filter::{FilterCondition, FilterOp, JsonScalar, MetadataFilter};
+pub use filter::{FilterCondition, FilterOp, JsonScalar, MetadataFilter, NestedFilter};
 pub use heap::VectorHeap;
 pub use hnsw::{HnswBackend, HnswConfig};
 pub use recovery::register_vector_recovery;
@@ -0,0 +1,227 @@
+//! Quantized embedding encodings
+//!
+//! Conversions for `StorageDtype::F16` and `StorageDtype::Int8`, and
+//! similarity scoring against stored values without building an f32 copy.
+//!
+//! Scoring is asymmetric: the query stays f32 and each stored value is
+//! widened as it is read. F32 storage never comes through here, so its
+//! scores are bit-identical to `compute_similarity`.
+
+use crate::primitives::vector::DistanceMetric;
+
+/// Largest finite f16 value; larger magnitudes saturate to it
+const F16_MAX: f32 = 65504.0;
+
+/// Convert to IEEE 754 half precision bits, rounding to nearest even
+///
+/// Magnitudes beyond the f16 range saturate to the largest finite value
+/// instead of becoming infinite, so scores stay finite.
+pub(crate) fn f32_to_f16(value: f32) -> u16 {
+    let value = value.clamp(-F16_MAX, F16_MAX);
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        // NaN (infinities were clamped above)
+        return sign | 0x7e00;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7bff;
+    }
+    if half_exponent <= 0 {
+        // Subnormal or zero in f16
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let half_mantissa = mantissa >> shift;
+        let remainder = mantissa & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round_up = remainder > halfway || (remainder == halfway && half_mantissa & 1 == 1);
+        return sign | (half_mantissa + round_up as u32) as u16;
+    }
+
+    let half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    let remainder = mantissa & 0x1fff;
+    let round_up = remainder > 0x1000 || (remainder == 0x1000 && half & 1 == 1);
+    // A carry out of the mantissa correctly bumps the exponent
+    sign | (half + round_up as u32).min(0x7bff) as u16
+}
+
+/// Widen IEEE 754 half precision bits to f32 (exact)
+pub(crate) fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x03ff) as u32;
+
+    let bits = match exponent {
+        0 if mantissa == 0 => sign,
+        0 => {
+            // Subnormal: normalize into an f32 exponent
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x03ff;
+            sign | ((127 - 15 + 1 - shift) << 23) | (mantissa << 13)
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
+/// Per-vector scale for Int8 storage: `value = min + scale * code`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Int8Params {
+    pub(crate) min: f32,
+    pub(crate) scale: f32,
+}
+
+impl Int8Params {
+    /// Decode one stored code
+    #[inline]
+    pub(crate) fn decode(&self, code: u8) -> f32 {
+        self.min + self.scale * code as f32
+    }
+}
+
+/// Quantize `embedding` into `codes` over its own min..max range
+///
+/// Each value lands within `scale / 2` of the original.
+pub(crate) fn quantize_int8(embedding: &[f32], codes: &mut [u8]) -> Int8Params {
+    debug_assert_eq!(embedding.len(), codes.len());
+    let min = embedding.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = embedding.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if embedding.is_empty() || max <= min {
+        // Constant vector: every code decodes to `min`
+        codes.fill(0);
+        return Int8Params {
+            min: if embedding.is_empty() { 0.0 } else { min },
+            scale: 0.0,
+        };
+    }
+
+    let scale = (max - min) / 255.0;
+    for (code, &value) in codes.iter_mut().zip(embedding) {
+        *code = ((value - min) / scale).round().clamp(0.0, 255.0) as u8;
+    }
+    Int8Params { min, scale }
+}
+
+/// Similarity between an f32 query and stored values widened on the fly
+///
+/// Same definitions as `compute_similarity`: higher = more similar.
+pub(crate) fn similarity_with(
+    query: &[f32],
+    stored: impl Iterator<Item = f32>,
+    metric: DistanceMetric,
+) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => {
+            let (mut dot, mut norm_q, mut norm_s) = (0.0f32, 0.0f32, 0.0f32);
+            for (&q, s) in query.iter().zip(stored) {
+                dot += q * s;
+                norm_q += q * q;
+                norm_s += s * s;
+            }
+            if norm_q == 0.0 || norm_s == 0.0 {
+                0.0
+            } else {
+                dot / (norm_q.sqrt() * norm_s.sqrt())
+            }
+        }
+        DistanceMetric::Euclidean => {
+            let squared: f32 = query
+                .iter()
+                .zip(stored)
+                .map(|(&q, s)| (q - s).powi(2))
+                .sum();
+            1.0 / (1.0 + squared.sqrt())
+        }
+        DistanceMetric::DotProduct => query.iter().zip(stored).map(|(&q, s)| q * s).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::vector::distance::compute_similarity;
+
+    #[test]
+    fn test_f16_exact_values_roundtrip() {
+        for value in [0.0f32, -0.0, 1.0, -2.5, 0.5, 1024.0, 65504.0, -65504.0] {
+            let half = f32_to_f16(value);
+            assert_eq!(f16_to_f32(half).to_bits(), value.to_bits(), "{}", value);
+        }
+        // Smallest subnormal and largest subnormal
+        assert_eq!(f16_to_f32(0x0001), 2.0f32.powi(-24));
+        assert_eq!(f16_to_f32(0x03ff), 1023.0 * 2.0f32.powi(-24));
+        assert_eq!(f32_to_f16(2.0f32.powi(-24)), 0x0001);
+    }
+
+    #[test]
+    fn test_f16_rounding_and_saturation() {
+        // 1 + 2^-11 is halfway between 1 and the next f16: ties to even
+        assert_eq!(f32_to_f16(1.0 + 2.0f32.powi(-11)), 0x3c00);
+        assert_eq!(f32_to_f16(1.0 + 3.0 * 2.0f32.powi(-11)), 0x3c02);
+        assert_eq!(f32_to_f16(1.0e9), 0x7bff);
+        assert_eq!(f32_to_f16(f32::NEG_INFINITY), 0xfbff);
+        assert_eq!(f32_to_f16(1.0e-10), 0x0000);
+        assert!(f16_to_f32(f32_to_f16(f32::NAN)).is_nan());
+    }
+
+    #[test]
+    fn test_f16_relative_error_bound() {
+        let mut value = 1.0e-4f32;
+        while value < 6.0e4 {
+            let widened = f16_to_f32(f32_to_f16(value));
+            assert!(
+                ((widened - value) / value).abs() <= 2.0f32.powi(-11),
+                "{} -> {}",
+                value,
+                widened
+            );
+            value *= 1.37;
+        }
+    }
+
+    #[test]
+    fn test_int8_error_within_half_step() {
+        let embedding = [-1.0f32, -0.3, 0.0, 0.25, 0.7, 2.0];
+        let mut codes = [0u8; 6];
+        let params = quantize_int8(&embedding, &mut codes);
+        assert_eq!(codes[0], 0);
+        assert_eq!(codes[5], 255);
+        for (&code, &value) in codes.iter().zip(&embedding) {
+            assert!((params.decode(code) - value).abs() <= params.scale / 2.0 + 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_int8_constant_vector() {
+        let mut codes = [9u8; 3];
+        let params = quantize_int8(&[0.5, 0.5, 0.5], &mut codes);
+        assert_eq!(codes, [0, 0, 0]);
+        assert_eq!(params.decode(0), 0.5);
+    }
+
+    #[test]
+    fn test_similarity_with_matches_compute_similarity() {
+        let query = [0.3f32, -1.0, 2.0, 0.5];
+        let stored = [1.0f32, 0.5, -0.25, 2.0];
+        for metric in [
+            DistanceMetric::Cosine,
+            DistanceMetric::Euclidean,
+            DistanceMetric::DotProduct,
+        ] {
+            let expected = compute_similarity(&query, &stored, metric);
+            let actual = similarity_with(&query, stored.iter().copied(), metric);
+            assert!((expected - actual).abs() < 1e-6, "{:?}", metric);
+        }
+        let zero = similarity_with(&query, [0.0f32; 4].into_iter(), DistanceMetric::Cosine);
+        assert_eq!(zero, 0.0);
+    }
+}
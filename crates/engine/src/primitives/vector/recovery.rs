@@ -31,7 +31,7 @@ fn recover_vector_state(db: &Database) -> StrataResult<()> {
 
 /// Internal recovery implementation that works with &Database
 fn recover_from_db(db: &Database) -> StrataResult<()> {
-    use super::{CollectionId, IndexBackendFactory, VectorBackendState, VectorConfig, VectorId};
+    use super::{CollectionId, VectorBackendState, VectorConfig, VectorId};
     use strata_core::traits::SnapshotView;
     use strata_core::types::{Key, Namespace};
     use strata_core::value::Value;
@@ -43,7 +43,6 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
 
     // Get access to the shared backend state
     let state = db.extension::<VectorBackendState>()?;
-    let factory = IndexBackendFactory::default();
 
     let snapshot = db.storage().create_snapshot();
     let mut stats = super::RecoveryStats::default();
@@ -94,6 +93,7 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                 None => continue,
             };
 
+            let factory = record.index_factory();
             let config: VectorConfig = match record.config.try_into() {
                 Ok(c) => c,
                 Err(e) => {
@@ -161,6 +161,19 @@ fn recover_from_db(db: &Database) -> StrataResult<()> {
                     );
                     stats.vectors_upserted += 1;
                 }
+                if let Some(namespace) = vec_record.namespace.as_deref() {
+                    state
+                        .partitions
+                        .write()
+                        .entry(collection_id.clone())
+                        .or_default()
+                        .assign(vid, None, Some(namespace));
+                }
+            }
+
+            // HNSW builds its graph once all vectors are in the heap
+            if let Some(backend) = state.backends.write().get_mut(&collection_id) {
+                backend.rebuild_index();
             }
         }
     }
@@ -27,7 +27,9 @@
 //!    to ensure byte-identical snapshots for the same logical state.
 //!
 //! 2. **Critical State**: next_id and free_slots MUST be persisted and restored
-//!    to maintain VectorId uniqueness across restarts (Invariant T4).
+//!    to maintain VectorId uniqueness across restarts (Invariant T4). Restore
+//!    keeps only the free slots that are still free once vectors are
+//!    re-inserted.
 //!
 //! 3. **Embedding Format**: Raw f32 LE for efficiency. No compression currently.
 
@@ -37,6 +39,7 @@ use crate::primitives::vector::{
 };
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::io::{Read, Write};
 use strata_core::value::Value;
 use strata_core::BranchId;
@@ -144,7 +147,7 @@ impl VectorStore {
                 name: collection_id.name.clone(),
                 dimension: config.dimension,
                 metric: config.metric.to_byte(),
-                storage_dtype: 0, // F32
+                storage_dtype: config.storage_dtype.to_byte(),
                 next_id,
                 free_slots,
                 count: backend.len() as u32,
@@ -170,8 +173,8 @@ impl VectorStore {
                     .write_u64::<LittleEndian>(vector_id.as_u64())
                     .map_err(|e| VectorError::Io(e.to_string()))?;
 
-                // Get key and metadata from KV
-                let (key, metadata) = self.get_key_and_metadata(
+                // Get key, metadata and full-precision embedding from KV
+                let (key, record) = self.get_key_and_record(
                     collection_id.branch_id,
                     "default",
                     &collection_id.name,
@@ -187,18 +190,23 @@ impl VectorStore {
                     .write_all(key_bytes)
                     .map_err(|e| VectorError::Io(e.to_string()))?;
 
-                // Embedding (raw f32 LE)
-                let embedding = backend
-                    .get(vector_id)
-                    .ok_or_else(|| VectorError::VectorNotFound { key: key.clone() })?;
-                for &value in embedding {
+                // Embedding (raw f32 LE). The record holds the exact values;
+                // the backend's copy is lossy for f16 and int8 storage
+                let embedding = if record.embedding.len() == config.dimension {
+                    Cow::Owned(record.embedding)
+                } else {
+                    backend
+                        .get(vector_id)
+                        .ok_or_else(|| VectorError::VectorNotFound { key: key.clone() })?
+                };
+                for &value in embedding.iter() {
                     writer
                         .write_f32::<LittleEndian>(value)
                         .map_err(|e| VectorError::Io(e.to_string()))?;
                 }
 
                 // Metadata
-                if let Some(ref meta) = metadata {
+                if let Some(ref meta) = record.metadata {
                     writer
                         .write_u8(1)
                         .map_err(|e| VectorError::Io(e.to_string()))?;
@@ -277,14 +285,18 @@ impl VectorStore {
                 metric: DistanceMetric::from_byte(header.metric).ok_or_else(|| {
                     VectorError::Serialization(format!("Invalid metric: {}", header.metric))
                 })?,
-                storage_dtype: StorageDtype::F32,
+                // Snapshots from before quantization support carry 0 (F32)
+                storage_dtype: StorageDtype::from_byte(header.storage_dtype)
+                    .unwrap_or(StorageDtype::F32),
             };
 
             let collection_id = CollectionId::new(header.branch_id, &header.name);
 
             // Restore collection configuration in KV
             // Use "default" space for snapshot deserialization (backwards compat)
-            let collection_record = crate::primitives::vector::CollectionRecord::new(&config);
+            let factory = IndexBackendFactory::from_byte(header.index_type);
+            let collection_record =
+                crate::primitives::vector::CollectionRecord::new(&config).with_index(&factory);
             let config_key = strata_core::types::Key::new_vector_config(
                 strata_core::types::Namespace::for_branch_space(header.branch_id, "default"),
                 &header.name,
@@ -297,11 +309,6 @@ impl VectorStore {
                 .map_err(|e| VectorError::Database(e.to_string()))?;
 
             // Create backend using factory based on snapshot index_type
-            let factory = if header.index_type == 1 {
-                IndexBackendFactory::Hnsw(crate::primitives::vector::hnsw::HnswConfig::default())
-            } else {
-                IndexBackendFactory::default()
-            };
             let mut backend = factory.create(&config);
 
             // Read and insert vectors
@@ -496,6 +503,43 @@ mod tests {
         assert_eq!(v2.metadata, Some(serde_json::json!({"type": "doc"})));
     }
 
+    #[test]
+    fn test_snapshot_preserves_storage_dtype() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Euclidean)
+            .unwrap()
+            .with_storage_dtype(StorageDtype::Int8);
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "v1", &[0.1, 0.2, 0.3], None)
+            .unwrap();
+
+        let mut buffer = Vec::new();
+        store.snapshot_serialize(&mut buffer).unwrap();
+
+        let (_temp2, _db2, store2) = setup();
+        store2
+            .snapshot_deserialize(&mut Cursor::new(&buffer))
+            .unwrap();
+
+        let info = store2
+            .get_collection(branch_id, "default", "test")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(info.config.storage_dtype, StorageDtype::Int8);
+        let v1 = store2
+            .get(branch_id, "default", "test", "v1")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(v1.embedding, vec![0.1, 0.2, 0.3]);
+    }
+
     #[test]
     fn test_snapshot_preserves_next_id() {
         let (_temp, _db, store) = setup();
@@ -544,6 +588,53 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn test_snapshot_free_slots_do_not_clobber_live_vectors() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        // v1 takes slot 0, v2 slot 1; deleting v1 frees slot 0
+        store
+            .insert(branch_id, "default", "test", "v1", &[1.0, 0.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "v2", &[0.0, 1.0, 0.0], None)
+            .unwrap();
+        store.delete(branch_id, "default", "test", "v1").unwrap();
+
+        let mut buffer = Vec::new();
+        store.snapshot_serialize(&mut buffer).unwrap();
+
+        // Recovery packs v2 into slot 0, so the snapshot's free slot 0 is
+        // no longer free
+        let (_temp2, _db2, store2) = setup();
+        let mut cursor = Cursor::new(&buffer);
+        store2.snapshot_deserialize(&mut cursor).unwrap();
+        store2
+            .insert(branch_id, "default", "test", "v3", &[0.0, 0.0, 1.0], None)
+            .unwrap();
+        store2
+            .insert(branch_id, "default", "test", "v4", &[1.0, 1.0, 0.0], None)
+            .unwrap();
+
+        for (key, query) in [
+            ("v2", [0.0, 1.0, 0.0]),
+            ("v3", [0.0, 0.0, 1.0]),
+            ("v4", [1.0, 1.0, 0.0]),
+        ] {
+            let results = store2
+                .search(branch_id, "default", "test", &query, 1, None)
+                .unwrap();
+            assert_eq!(results[0].key, key);
+            assert!((results[0].score - 1.0).abs() < 1e-6);
+        }
+    }
+
     #[test]
     fn test_snapshot_multiple_collections() {
         let (_temp, _db, store) = setup();
@@ -25,19 +25,23 @@
 //! through `Database::extension::<VectorBackendState>()`.
 
 use crate::primitives::extensions::VectorStoreExt;
-use crate::primitives::vector::collection::{validate_collection_name, validate_vector_key};
+use crate::primitives::vector::collection::{
+    validate_collection_name, validate_namespace, validate_user_vector_key, validate_vector_key,
+};
 use crate::primitives::vector::{
-    CollectionId, CollectionInfo, CollectionRecord, IndexBackendFactory, MetadataFilter,
-    VectorConfig, VectorEntry, VectorError, VectorId, VectorIndexBackend, VectorMatch,
+    CollectionId, CollectionInfo, CollectionRecord, DistanceMetric, IndexBackendFactory,
+    MetadataFilter, VectorConfig, VectorConfigSerde, VectorEntry, VectorError, VectorId, VectorIndexBackend, VectorMatch,
     VectorMatchWithSource, VectorRecord, VectorResult,
 };
 use strata_concurrency::TransactionContext;
 use strata_core::contract::{Timestamp, Version, Versioned};
 use strata_core::EntityRef;
+use crate::branch_ops::{SchemaChange, SchemaChangeKind};
 use crate::database::Database;
 use parking_lot::RwLock;
+use rayon::prelude::*;
 use serde_json::Value as JsonValue;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
@@ -70,14 +74,83 @@ pub struct VectorBackendState {
     /// In-memory index backends per collection
     /// CRITICAL: BTreeMap for deterministic iteration (Invariant R3)
     pub backends: RwLock<BTreeMap<CollectionId, Box<dyn VectorIndexBackend>>>,
+    /// Namespace membership per collection, for namespace-restricted search
+    ///
+    /// Lock order: always acquire `backends` before `partitions`.
+    pub partitions: RwLock<BTreeMap<CollectionId, NamespacePartitions>>,
+}
+
+impl VectorBackendState {
+    /// Drop the backends of every collection of a branch; returns how
+    /// many were dropped
+    pub fn remove_branch(&self, branch_id: &BranchId) -> usize {
+        let mut backends = self.backends.write();
+        let before = backends.len();
+        backends.retain(|id, _| id.branch_id != *branch_id);
+        self.partitions
+            .write()
+            .retain(|id, _| id.branch_id != *branch_id);
+        before - backends.len()
+    }
+
+    /// Branches with at least one collection backend
+    pub fn branch_ids(&self) -> Vec<BranchId> {
+        let mut ids: Vec<BranchId> = Vec::new();
+        for id in self.backends.read().keys() {
+            if !ids.contains(&id.branch_id) {
+                ids.push(id.branch_id);
+            }
+        }
+        ids
+    }
 }
 
 impl Default for VectorBackendState {
     fn default() -> Self {
         Self {
             backends: RwLock::new(BTreeMap::new()),
+            partitions: RwLock::new(BTreeMap::new()),
+        }
+    }
+}
+
+/// Vectors of one collection grouped by namespace
+///
+/// Rebuilt from the stored VectorRecords on recovery and reindex; vectors
+/// without a namespace are not tracked.
+#[derive(Debug, Default)]
+pub struct NamespacePartitions {
+    members: BTreeMap<String, BTreeSet<VectorId>>,
+}
+
+impl NamespacePartitions {
+    /// Move `id` from namespace `from` to namespace `to`
+    pub fn assign(&mut self, id: VectorId, from: Option<&str>, to: Option<&str>) {
+        if from == to {
+            return;
+        }
+        if let Some(from) = from {
+            self.remove(id, from);
+        }
+        if let Some(to) = to {
+            self.members.entry(to.to_string()).or_default().insert(id);
         }
     }
+
+    /// Drop `id` from namespace `namespace`
+    pub fn remove(&mut self, id: VectorId, namespace: &str) {
+        if let Some(ids) = self.members.get_mut(namespace) {
+            ids.remove(&id);
+            if ids.is_empty() {
+                self.members.remove(namespace);
+            }
+        }
+    }
+
+    /// Vectors in `namespace`, if it has any
+    pub fn members(&self, namespace: &str) -> Option<&BTreeSet<VectorId>> {
+        self.members.get(namespace)
+    }
 }
 
 /// Vector storage and search primitive
@@ -144,9 +217,29 @@ impl VectorStore {
         Namespace::for_branch_space(branch_id, space)
     }
 
-    /// Get the backend factory (hardcoded currently, configurable in future versions)
+    /// Schema log key and value recording `kind` for a collection
+    fn schema_log_entry(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+        timestamp: u64,
+        kind: SchemaChangeKind,
+    ) -> VectorResult<(Key, Value)> {
+        SchemaChange {
+            timestamp,
+            space: space.to_string(),
+            collection: name.to_string(),
+            kind,
+        }
+        .to_entry(branch_id)
+        .map_err(|e| VectorError::Storage(e.to_string()))
+    }
+
+    /// Get the backend factory for newly created collections
+    ///
+    /// Always BruteForce; `reindex()` moves an existing collection to another backend.
     fn backend_factory(&self) -> IndexBackendFactory {
-        // Hardcoded to BruteForce. future versions may make this configurable.
         IndexBackendFactory::default()
     }
 
@@ -203,16 +296,28 @@ impl VectorStore {
         // Store config in KV
         let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), name);
         let config_bytes = record.to_bytes()?;
+        let (log_key, log_value) = self.schema_log_entry(
+            branch_id,
+            space,
+            name,
+            now,
+            SchemaChangeKind::CollectionCreated {
+                dimension: config.dimension,
+                metric: config.metric,
+                dtype: config.storage_dtype,
+            },
+        )?;
 
         // Use transaction for atomic storage
         self.db
             .transaction(branch_id, |txn| {
-                txn.put(config_key.clone(), Value::Bytes(config_bytes.clone()))
+                txn.put(config_key.clone(), Value::Bytes(config_bytes.clone()))?;
+                txn.put(log_key.clone(), log_value.clone())
             })
             .map_err(|e| VectorError::Storage(e.to_string()))?;
 
         // Initialize in-memory backend
-        self.init_backend(&collection_id, &config)?;
+        self.init_backend(&collection_id, &config, &self.backend_factory())?;
 
         let info = CollectionInfo {
             name: name.to_string(),
@@ -248,25 +353,34 @@ impl VectorStore {
         let collection_id = CollectionId::new(branch_id, name);
 
         // Check if collection exists
-        if !self.collection_exists(branch_id, space, name)? {
-            return Err(VectorError::CollectionNotFound {
-                name: name.to_string(),
-            });
-        }
+        let config = self.get_collection_config_required(branch_id, space, name)?;
 
         // Delete all vectors in the collection
         self.delete_all_vectors(branch_id, space, name)?;
 
         // Delete config from KV
         let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), name);
+        let (log_key, log_value) = self.schema_log_entry(
+            branch_id,
+            space,
+            name,
+            now_micros(),
+            SchemaChangeKind::CollectionDropped {
+                dimension: config.dimension,
+            },
+        )?;
         self.db
-            .transaction(branch_id, |txn| txn.delete(config_key.clone()))
+            .transaction(branch_id, |txn| {
+                txn.delete(config_key.clone())?;
+                txn.put(log_key.clone(), log_value.clone())
+            })
             .map_err(|e| VectorError::Storage(e.to_string()))?;
 
         // Remove in-memory backend
         {
             let state = self.state()?;
             state.backends.write().remove(&collection_id);
+            state.partitions.write().remove(&collection_id);
         }
 
         info!(target: "strata::vector", collection = name, branch_id = %branch_id, "Collection deleted");
@@ -274,6 +388,139 @@ impl VectorStore {
         Ok(())
     }
 
+    /// Rebuild a collection's index, optionally changing its metric or backend
+    ///
+    /// The new backend is built from the stored VectorRecords (exact f32
+    /// embeddings), so vectors never need to be exported and reinserted.
+    /// `None` keeps the current metric / backend; passing neither simply
+    /// rebuilds the index in place. Dimension and storage dtype are unchanged.
+    ///
+    /// Holds the backend write lock for the whole rebuild, so concurrent
+    /// writes to the collection wait and then land in the new backend.
+    ///
+    /// # Errors
+    /// - `CollectionNotFound` if collection doesn't exist
+    pub fn reindex(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+        metric: Option<DistanceMetric>,
+        index: Option<IndexBackendFactory>,
+    ) -> VectorResult<Versioned<CollectionInfo>> {
+        use strata_core::traits::SnapshotView;
+
+        let record = self
+            .load_collection_record(branch_id, space, name)?
+            .ok_or_else(|| VectorError::CollectionNotFound {
+                name: name.to_string(),
+            })?;
+        let factory = index.unwrap_or_else(|| record.index_factory());
+        let mut config = VectorConfig::try_from(record.config.clone())?;
+        if let Some(metric) = metric {
+            config.metric = metric;
+        }
+        let record = CollectionRecord {
+            config: VectorConfigSerde::from(&config),
+            ..record
+        }
+        .with_index(&factory);
+
+        let collection_id = CollectionId::new(branch_id, name);
+        let namespace = self.namespace_for(branch_id, space);
+        let config_key = Key::new_vector_config(namespace.clone(), name);
+        let config_bytes = record.to_bytes()?;
+        let (log_key, log_value) = self.schema_log_entry(
+            branch_id,
+            space,
+            name,
+            now_micros(),
+            SchemaChangeKind::IndexRebuilt {
+                metric: config.metric,
+                index: factory.index_type_name().to_string(),
+            },
+        )?;
+
+        let state = self.state()?;
+        let mut backends = state.backends.write();
+
+        self.db
+            .transaction(branch_id, |txn| {
+                txn.put(config_key.clone(), Value::Bytes(config_bytes.clone()))?;
+                txn.put(log_key.clone(), log_value.clone())
+            })
+            .map_err(|e| VectorError::Storage(e.to_string()))?;
+
+        let mut backend = factory.create(&config);
+        let mut partitions = NamespacePartitions::default();
+        let snapshot = self.db.storage().create_snapshot();
+        let entries = snapshot
+            .scan_prefix(&Key::vector_collection_prefix(namespace, name))
+            .map_err(|e| VectorError::Storage(e.to_string()))?;
+        for (key, versioned) in &entries {
+            let Value::Bytes(bytes) = &versioned.value else {
+                continue;
+            };
+            let vector = VectorRecord::from_bytes(bytes)?;
+            // Records from before embeddings were stored in KV can't be reindexed
+            if vector.embedding.len() != config.dimension {
+                tracing::warn!(
+                    target: "strata::vector",
+                    key = ?key,
+                    "Skipping vector without a stored embedding during reindex"
+                );
+                continue;
+            }
+            let vector_id = VectorId::new(vector.vector_id);
+            backend.insert_with_id_and_timestamp(
+                vector_id,
+                &vector.embedding,
+                vector.created_at,
+            )?;
+            partitions.assign(vector_id, None, vector.namespace.as_deref());
+        }
+
+        // Never reissue an ID the old backend already handed out (Invariant T4)
+        if let Some(old) = backends.get(&collection_id) {
+            let (old_next_id, _) = old.snapshot_state();
+            let (next_id, free_slots) = backend.snapshot_state();
+            if old_next_id > next_id {
+                backend.restore_snapshot_state(old_next_id, free_slots);
+            }
+        }
+        backend.rebuild_index();
+
+        let count = backend.len();
+        state
+            .partitions
+            .write()
+            .insert(collection_id.clone(), partitions);
+        backends.insert(collection_id, backend);
+        drop(backends);
+
+        info!(
+            target: "strata::vector",
+            collection = name,
+            metric = config.metric.name(),
+            index_type = factory.index_type_name(),
+            count,
+            branch_id = %branch_id,
+            "Collection reindexed"
+        );
+
+        let info = CollectionInfo {
+            name: name.to_string(),
+            config,
+            count,
+            created_at: record.created_at,
+        };
+        Ok(Versioned::with_timestamp(
+            info,
+            Version::counter(1),
+            Timestamp::from_micros(now_micros()),
+        ))
+    }
+
     /// List all collections for a branch
     ///
     /// Returns CollectionInfo for each collection, including current vector count.
@@ -408,6 +655,7 @@ impl VectorStore {
     /// # Errors
     /// - `CollectionNotFound` if collection doesn't exist
     /// - `InvalidKey` if key is invalid
+    /// - `ReservedKey` if key starts with a reserved prefix
     /// - `DimensionMismatch` if embedding dimension doesn't match config
     pub fn insert(
         &self,
@@ -418,7 +666,36 @@ impl VectorStore {
         embedding: &[f32],
         metadata: Option<JsonValue>,
     ) -> VectorResult<Version> {
-        self.insert_inner(branch_id, space, collection, key, embedding, metadata, None)
+        validate_user_vector_key(key)?;
+        self.insert_inner(
+            branch_id, space, collection, None, key, embedding, metadata, None,
+        )
+    }
+
+    /// Insert a vector into a namespace of the collection (upsert semantics)
+    ///
+    /// Like [`insert`](Self::insert), but the vector joins `namespace` (or
+    /// no namespace for `None`), replacing any namespace it had before.
+    /// [`search_in_namespace`](Self::search_in_namespace) only scores the
+    /// members of one namespace.
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert_in_namespace(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        namespace: Option<&str>,
+        key: &str,
+        embedding: &[f32],
+        metadata: Option<JsonValue>,
+    ) -> VectorResult<Version> {
+        validate_user_vector_key(key)?;
+        if let Some(namespace) = namespace {
+            validate_namespace(namespace)?;
+        }
+        self.insert_inner(
+            branch_id, space, collection, namespace, key, embedding, metadata, None,
+        )
     }
 
     /// Common insert implementation used by both `insert()` and `system_insert_with_source()`.
@@ -428,6 +705,7 @@ impl VectorStore {
         branch_id: BranchId,
         space: &str,
         collection: &str,
+        namespace: Option<&str>,
         key: &str,
         embedding: &[f32],
         metadata: Option<JsonValue>,
@@ -482,14 +760,15 @@ impl VectorStore {
         // Check existence under write lock
         let existing = self.get_vector_record_by_key(&kv_key)?;
 
-        let (vector_id, record) = if let Some(existing_record) = existing {
+        let (vector_id, mut record, previous_namespace) = if let Some(existing_record) = existing {
             // Update existing: keep the same VectorId
             let mut updated = existing_record;
+            let previous_namespace = updated.namespace.take();
             match source_ref {
                 Some(sr) => updated.update_with_source(embedding.to_vec(), metadata, Some(sr)),
                 None => updated.update(embedding.to_vec(), metadata),
             }
-            (VectorId(updated.vector_id), updated)
+            (VectorId(updated.vector_id), updated, previous_namespace)
         } else {
             // New vector: allocate VectorId from backend's per-collection counter
             let vector_id = backend.allocate_id();
@@ -497,8 +776,9 @@ impl VectorStore {
                 Some(sr) => VectorRecord::new_with_source(vector_id, embedding.to_vec(), metadata, sr),
                 None => VectorRecord::new(vector_id, embedding.to_vec(), metadata),
             };
-            (vector_id, record)
+            (vector_id, record, None)
         };
+        record.namespace = namespace.map(str::to_string);
 
         // Commit to KV FIRST (durability before in-memory update)
         let record_version = record.version;
@@ -511,6 +791,14 @@ impl VectorStore {
 
         // Only update backend AFTER KV commit succeeds
         backend.insert_with_timestamp(vector_id, embedding, record.created_at)?;
+        if previous_namespace.is_some() || namespace.is_some() {
+            state
+                .partitions
+                .write()
+                .entry(collection_id)
+                .or_default()
+                .assign(vector_id, previous_namespace.as_deref(), namespace);
+        }
 
         drop(backends);
 
@@ -572,9 +860,17 @@ impl VectorStore {
             .get(vector_id)
             .ok_or_else(|| VectorError::Internal("Embedding missing from backend".to_string()))?;
 
+        // The record keeps the exact embedding; the backend's copy is lossy
+        // for f16 and int8 storage. Legacy records without one fall back.
+        let embedding = if record.embedding.len() == embedding.len() {
+            record.embedding
+        } else {
+            embedding.into_owned()
+        };
+
         let entry = VectorEntry {
             key: key.to_string(),
-            embedding: embedding.to_vec(),
+            embedding,
             metadata: record.metadata,
             vector_id,
             version: Version::counter(record.version),
@@ -681,6 +977,11 @@ impl VectorStore {
             if let Some(backend) = backends.get_mut(&collection_id) {
                 backend.delete_with_timestamp(vector_id, now_micros())?;
             }
+            if let Some(namespace) = &record.namespace {
+                if let Some(partitions) = state.partitions.write().get_mut(&collection_id) {
+                    partitions.remove(vector_id, namespace);
+                }
+            }
         }
 
         // Delete from KV
@@ -707,15 +1008,33 @@ impl VectorStore {
         space: &str,
         collection: &str,
         entries: Vec<(String, Vec<f32>, Option<JsonValue>)>,
+    ) -> VectorResult<Vec<Version>> {
+        self.batch_insert_in_namespace(branch_id, space, collection, None, entries)
+    }
+
+    /// Batch insert multiple vectors into one namespace (upsert semantics)
+    ///
+    /// Like [`batch_insert`](Self::batch_insert), but every vector joins
+    /// `namespace` (or no namespace for `None`).
+    pub fn batch_insert_in_namespace(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        namespace: Option<&str>,
+        entries: Vec<(String, Vec<f32>, Option<JsonValue>)>,
     ) -> VectorResult<Vec<Version>> {
         if entries.is_empty() {
             return Ok(Vec::new());
         }
+        if let Some(namespace) = namespace {
+            validate_namespace(namespace)?;
+        }
 
         // Validate all entries before acquiring locks
         let config = self.get_collection_config_required(branch_id, space, collection)?;
         for (key, embedding, _) in &entries {
-            validate_vector_key(key)?;
+            validate_user_vector_key(key)?;
             if embedding.iter().any(|v| v.is_nan() || v.is_infinite()) {
                 return Err(VectorError::InvalidEmbedding {
                     reason: format!(
@@ -755,15 +1074,18 @@ impl VectorStore {
             // Check existence
             let existing = self.get_vector_record_by_key(&kv_key)?;
 
-            let (vector_id, record) = if let Some(existing_record) = existing {
-                let mut updated = existing_record;
-                updated.update(embedding.clone(), metadata);
-                (VectorId(updated.vector_id), updated)
-            } else {
-                let vector_id = backend.allocate_id();
-                let record = VectorRecord::new(vector_id, embedding.clone(), metadata);
-                (vector_id, record)
-            };
+            let (vector_id, mut record, previous_namespace) =
+                if let Some(existing_record) = existing {
+                    let mut updated = existing_record;
+                    let previous_namespace = updated.namespace.take();
+                    updated.update(embedding.clone(), metadata);
+                    (VectorId(updated.vector_id), updated, previous_namespace)
+                } else {
+                    let vector_id = backend.allocate_id();
+                    let record = VectorRecord::new(vector_id, embedding.clone(), metadata);
+                    (vector_id, record, None)
+                };
+            record.namespace = namespace.map(str::to_string);
 
             // Commit to KV
             let record_version = record.version;
@@ -776,6 +1098,14 @@ impl VectorStore {
 
             // Update backend with timestamp
             backend.insert_with_timestamp(vector_id, &embedding, record.created_at)?;
+            if previous_namespace.is_some() || namespace.is_some() {
+                state
+                    .partitions
+                    .write()
+                    .entry(collection_id.clone())
+                    .or_default()
+                    .assign(vector_id, previous_namespace.as_deref(), namespace);
+            }
 
             versions.push(Version::counter(record_version));
         }
@@ -835,7 +1165,7 @@ impl VectorStore {
         // we retry with a higher multiplier up to a max limit.
         //
         // Multiplier strategy: 3x -> 6x -> 12x -> all (capped at collection size)
-        let mut matches = Vec::with_capacity(k);
+        let mut matches = Vec::new();
 
         if filter.is_none() {
             // No filter - simple case, fetch exactly k
@@ -850,15 +1180,7 @@ impl VectorStore {
                 backend.search(query, k)
             };
 
-            for (vector_id, score) in candidates {
-                let (key, metadata) =
-                    self.get_key_and_metadata(branch_id, space, collection, vector_id)?;
-                matches.push(VectorMatch {
-                    key,
-                    score,
-                    metadata,
-                });
-            }
+            matches = self.resolve_matches(branch_id, space, collection, candidates, k, None)?;
         } else {
             // Filter active - use adaptive over-fetch
             let multipliers = [3, 6, 12];
@@ -885,27 +1207,14 @@ impl VectorStore {
                     backend.search(query, fetch_k)
                 };
 
-                matches.clear();
-                for (vector_id, score) in candidates {
-                    let (key, metadata) =
-                        self.get_key_and_metadata(branch_id, space, collection, vector_id)?;
-
-                    // Apply filter
-                    if let Some(ref f) = filter {
-                        if !f.matches(&metadata) {
-                            continue;
-                        }
-                    }
-
-                    matches.push(VectorMatch {
-                        key,
-                        score,
-                        metadata,
-                    });
-                    if matches.len() >= k {
-                        break;
-                    }
-                }
+                matches = self.resolve_matches(
+                    branch_id,
+                    space,
+                    collection,
+                    candidates,
+                    k,
+                    filter.as_ref(),
+                )?;
 
                 // If we have enough results or searched all vectors, stop
                 if matches.len() >= k || fetch_k >= collection_size {
@@ -914,23 +1223,194 @@ impl VectorStore {
             }
         }
 
-        // Apply facade-level tie-breaking (score desc, key asc)
-        // This satisfies Invariant R5
-        matches.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| a.key.cmp(&b.key))
-        });
-
-        // Ensure we don't exceed k after sorting
-        matches.truncate(k);
+        sort_matches(&mut matches, k);
 
         debug!(target: "strata::vector", collection, k, results = matches.len(), duration_us = start.elapsed().as_micros() as u64, branch_id = %branch_id, "Vector search completed");
 
         Ok(matches)
     }
 
+    /// Search for similar vectors within one namespace
+    ///
+    /// Only the namespace's members are scored, exactly and on either index
+    /// type, so the cost is O(namespace) rather than O(collection). An
+    /// unknown namespace has no matches. `filter` applies on top, over the
+    /// whole namespace rather than an over-fetched prefix.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search_in_namespace(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        namespace: &str,
+        query: &[f32],
+        k: usize,
+        filter: Option<MetadataFilter>,
+    ) -> VectorResult<Vec<VectorMatch>> {
+        let start = std::time::Instant::now();
+
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        validate_namespace(namespace)?;
+
+        self.ensure_collection_loaded(branch_id, space, collection)?;
+
+        let collection_id = CollectionId::new(branch_id, collection);
+
+        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        if query.len() != config.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: config.dimension,
+                got: query.len(),
+            });
+        }
+
+        let candidates = {
+            let state = self.state()?;
+            let backends = state.backends.read();
+            let backend =
+                backends
+                    .get(&collection_id)
+                    .ok_or_else(|| VectorError::CollectionNotFound {
+                        name: collection.to_string(),
+                    })?;
+            let partitions = state.partitions.read();
+            match partitions
+                .get(&collection_id)
+                .and_then(|p| p.members(namespace))
+            {
+                Some(ids) => {
+                    let fetch_k = if filter.is_some() { ids.len() } else { k };
+                    backend.search_among(query, fetch_k, ids)
+                }
+                None => Vec::new(),
+            }
+        };
+
+        let mut matches =
+            self.resolve_matches(branch_id, space, collection, candidates, k, filter.as_ref())?;
+        sort_matches(&mut matches, k);
+
+        debug!(target: "strata::vector", collection, namespace, k, results = matches.len(), duration_us = start.elapsed().as_micros() as u64, branch_id = %branch_id, "Vector namespace search completed");
+
+        Ok(matches)
+    }
+
+    /// Search for the vectors most similar to each of several queries
+    ///
+    /// Result `i` is what `search` returns for `queries[i]`. Validation,
+    /// collection loading and the backend lock are shared across the batch,
+    /// and the queries are scored and resolved in parallel.
+    ///
+    /// With a filter, each query over-fetches 3x in the batched pass; a
+    /// query that still comes up short falls back to `search` for the
+    /// wider retries.
+    pub fn search_batch(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        queries: &[Vec<f32>],
+        k: usize,
+        filter: Option<MetadataFilter>,
+    ) -> VectorResult<Vec<Vec<VectorMatch>>> {
+        let start = std::time::Instant::now();
+
+        if k == 0 || queries.is_empty() {
+            return Ok(vec![Vec::new(); queries.len()]);
+        }
+
+        self.ensure_collection_loaded(branch_id, space, collection)?;
+
+        let collection_id = CollectionId::new(branch_id, collection);
+
+        // Validate every query before doing any work
+        let config = self.get_collection_config_required(branch_id, space, collection)?;
+        if let Some(query) = queries.iter().find(|q| q.len() != config.dimension) {
+            return Err(VectorError::DimensionMismatch {
+                expected: config.dimension,
+                got: query.len(),
+            });
+        }
+
+        let (candidates, collection_size) = {
+            let state = self.state()?;
+            let backends = state.backends.read();
+            let backend =
+                backends
+                    .get(&collection_id)
+                    .ok_or_else(|| VectorError::CollectionNotFound {
+                        name: collection.to_string(),
+                    })?;
+            let fetch_k = if filter.is_some() {
+                (k * 3).min(backend.len())
+            } else {
+                k
+            };
+            (backend.search_batch(queries, fetch_k), backend.len())
+        };
+
+        let results = queries
+            .par_iter()
+            .zip(candidates)
+            .map(|(query, candidates)| {
+                let fetched = candidates.len();
+                let mut matches = self.resolve_matches(
+                    branch_id,
+                    space,
+                    collection,
+                    candidates,
+                    k,
+                    filter.as_ref(),
+                )?;
+                if filter.is_some() && matches.len() < k && fetched < collection_size {
+                    return self.search(branch_id, space, collection, query, k, filter.clone());
+                }
+                sort_matches(&mut matches, k);
+                Ok(matches)
+            })
+            .collect::<VectorResult<Vec<_>>>()?;
+
+        debug!(target: "strata::vector", collection, k, queries = queries.len(), duration_us = start.elapsed().as_micros() as u64, branch_id = %branch_id, "Vector batch search completed");
+
+        Ok(results)
+    }
+
+    /// Turn backend candidates into matches, keeping at most k that pass `filter`
+    fn resolve_matches(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        candidates: Vec<(VectorId, f32)>,
+        k: usize,
+        filter: Option<&MetadataFilter>,
+    ) -> VectorResult<Vec<VectorMatch>> {
+        let mut matches = Vec::with_capacity(k.min(candidates.len()));
+        for (vector_id, score) in candidates {
+            let (key, metadata) =
+                self.get_key_and_metadata(branch_id, space, collection, vector_id)?;
+
+            // Apply filter
+            if let Some(f) = filter {
+                if !f.matches(&metadata) {
+                    continue;
+                }
+            }
+
+            matches.push(VectorMatch {
+                key,
+                score,
+                metadata,
+            });
+            if matches.len() >= k {
+                break;
+            }
+        }
+        Ok(matches)
+    }
+
     /// Search for k nearest neighbors as of a given timestamp.
     ///
     /// Uses temporal filtering in the backend (HNSW nodes alive at as_of_ts)
@@ -1041,8 +1521,13 @@ impl VectorStore {
     // ========================================================================
 
     /// Initialize the index backend for a collection
-    fn init_backend(&self, id: &CollectionId, config: &VectorConfig) -> Result<(), VectorError> {
-        let backend = self.backend_factory().create(config);
+    fn init_backend(
+        &self,
+        id: &CollectionId,
+        config: &VectorConfig,
+        factory: &IndexBackendFactory,
+    ) -> Result<(), VectorError> {
+        let backend = factory.create(config);
         let state = self.state()?;
         state.backends.write().insert(id.clone(), backend);
         Ok(())
@@ -1094,12 +1579,24 @@ impl VectorStore {
         collection: &str,
         target_id: VectorId,
     ) -> VectorResult<(String, Option<JsonValue>)> {
-        use strata_core::traits::SnapshotView;
-
-        let namespace = self.namespace_for(branch_id, space);
-        let prefix = Key::vector_collection_prefix(namespace, collection);
+        self.get_key_and_record(branch_id, space, collection, target_id)
+            .map(|(key, record)| (key, record.metadata))
+    }
 
-        let snapshot = self.db.storage().create_snapshot();
+    /// Get key and full record for a VectorId by scanning KV (internal)
+    pub(crate) fn get_key_and_record(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        collection: &str,
+        target_id: VectorId,
+    ) -> VectorResult<(String, VectorRecord)> {
+        use strata_core::traits::SnapshotView;
+
+        let namespace = self.namespace_for(branch_id, space);
+        let prefix = Key::vector_collection_prefix(namespace, collection);
+
+        let snapshot = self.db.storage().create_snapshot();
         let entries = snapshot
             .scan_prefix(&prefix)
             .map_err(|e| VectorError::Storage(e.to_string()))?;
@@ -1127,7 +1624,7 @@ impl VectorStore {
                     .unwrap_or(&user_key)
                     .to_string();
 
-                return Ok((vector_key, record.metadata));
+                return Ok((vector_key, record));
             }
         }
 
@@ -1255,6 +1752,18 @@ impl VectorStore {
         space: &str,
         name: &str,
     ) -> VectorResult<Option<VectorConfig>> {
+        self.load_collection_record(branch_id, space, name)?
+            .map(|record| VectorConfig::try_from(record.config))
+            .transpose()
+    }
+
+    /// Load the full collection record (config and index type) from KV
+    fn load_collection_record(
+        &self,
+        branch_id: BranchId,
+        space: &str,
+        name: &str,
+    ) -> VectorResult<Option<CollectionRecord>> {
         use strata_core::traits::SnapshotView;
 
         let config_key = Key::new_vector_config(self.namespace_for(branch_id, space), name);
@@ -1276,9 +1785,7 @@ impl VectorStore {
             }
         };
 
-        let record = CollectionRecord::from_bytes(&bytes)?;
-        let config = VectorConfig::try_from(record.config)?;
-        Ok(Some(config))
+        Ok(Some(CollectionRecord::from_bytes(&bytes)?))
     }
 
     /// Ensure collection is loaded into memory
@@ -1302,14 +1809,16 @@ impl VectorStore {
         }
 
         // Load from KV
-        let config = self
-            .load_collection_config(branch_id, space, name)?
+        let record = self
+            .load_collection_record(branch_id, space, name)?
             .ok_or_else(|| VectorError::CollectionNotFound {
                 name: name.to_string(),
             })?;
+        let factory = record.index_factory();
+        let config = VectorConfig::try_from(record.config)?;
 
         // Initialize backend
-        self.init_backend(&collection_id, &config)?;
+        self.init_backend(&collection_id, &config, &factory)?;
 
         // Note: Loading vectors into backend happens during recovery
 
@@ -1510,7 +2019,7 @@ impl VectorStore {
             })
             .map_err(|e| VectorError::Storage(e.to_string()))?;
 
-        self.init_backend(&collection_id, &config)?;
+        self.init_backend(&collection_id, &config, &self.backend_factory())?;
 
         let info = CollectionInfo {
             name: name.to_string(),
@@ -1537,8 +2046,11 @@ impl VectorStore {
     ) -> VectorResult<Version> {
         use crate::primitives::vector::collection::validate_system_collection_name;
         validate_system_collection_name(collection)?;
-        // Delegate to the normal insert path (which doesn't re-check collection name)
-        self.insert(branch_id, "default", collection, key, embedding, metadata)
+        // Skip the user insert path so internal keys are not checked against
+        // reserved prefixes
+        self.insert_inner(
+            branch_id, "default", collection, None, key, embedding, metadata, None,
+        )
     }
 
     /// Insert into a system collection with a source reference (internal use only)
@@ -1556,7 +2068,7 @@ impl VectorStore {
     ) -> VectorResult<Version> {
         use crate::primitives::vector::collection::validate_system_collection_name;
         validate_system_collection_name(collection)?;
-        self.insert_inner(branch_id, "default", collection, key, embedding, metadata, Some(source_ref))
+        self.insert_inner(branch_id, "default", collection, None, key, embedding, metadata, Some(source_ref))
     }
 
     /// Search a system collection (internal use only)
@@ -1837,6 +2349,21 @@ impl strata_storage::PrimitiveStorageExt for VectorStore {
     }
 }
 
+/// Apply facade-level tie-breaking (score desc, key asc) and keep k
+///
+/// This satisfies Invariant R5.
+fn sort_matches(matches: &mut Vec<VectorMatch>, k: usize) {
+    matches.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    // Ensure we don't exceed k after sorting
+    matches.truncate(k);
+}
+
 /// Get current time in microseconds since Unix epoch
 ///
 /// Returns 0 if system clock is before Unix epoch (clock went backwards).
@@ -1912,7 +2439,7 @@ impl VectorStoreExt for TransactionContext {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::vector::{DistanceMetric, VectorConfig};
+    use crate::primitives::vector::{DistanceMetric, StorageDtype, VectorConfig};
     use tempfile::TempDir;
 
     fn setup() -> (TempDir, Arc<Database>, VectorStore) {
@@ -2364,6 +2891,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_reserved_vector_key() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config.clone())
+            .unwrap();
+        let result = store.insert(branch_id, "default", "test", "__idx_x", &[1.0, 0.0], None);
+        assert!(matches!(result, Err(VectorError::ReservedKey { .. })));
+
+        // System inserts are not subject to the check
+        store
+            .create_system_collection(branch_id, "_system_test", config)
+            .unwrap();
+        store
+            .system_insert(branch_id, "_system_test", "_strata/x", &[1.0, 0.0], None)
+            .unwrap();
+    }
+
     // ========================================
     // Vector Search Tests
     // ========================================
@@ -2400,6 +2948,354 @@ mod tests {
         assert_eq!(results[1].key, "c"); // Second most similar
     }
 
+    #[test]
+    fn test_search_quantized_collection() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        for (name, dtype) in [("half", StorageDtype::F16), ("int8", StorageDtype::Int8)] {
+            let config = VectorConfig::new(3, DistanceMetric::Cosine)
+                .unwrap()
+                .with_storage_dtype(dtype);
+            store
+                .create_collection(branch_id, "default", name, config)
+                .unwrap();
+            let info = store
+                .get_collection(branch_id, "default", name)
+                .unwrap()
+                .unwrap()
+                .value;
+            assert_eq!(info.config.storage_dtype, dtype);
+
+            store
+                .insert(branch_id, "default", name, "a", &[1.0, 0.0, 0.0], None)
+                .unwrap();
+            store
+                .insert(branch_id, "default", name, "b", &[0.0, 1.0, 0.0], None)
+                .unwrap();
+            store
+                .insert(branch_id, "default", name, "c", &[0.9, 0.1, 0.0], None)
+                .unwrap();
+
+            let results = store
+                .search(branch_id, "default", name, &[1.0, 0.0, 0.0], 2, None)
+                .unwrap();
+            assert_eq!(results.len(), 2);
+            assert_eq!(results[0].key, "a");
+            assert_eq!(results[1].key, "c");
+
+            // get returns the stored embedding, not the quantized copy
+            let c = store
+                .get(branch_id, "default", name, "c")
+                .unwrap()
+                .unwrap()
+                .value;
+            assert_eq!(c.embedding, vec![0.9, 0.1, 0.0]);
+        }
+    }
+
+    #[test]
+    fn test_reindex_changes_metric() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "a", &[1.0, 0.0], None)
+            .unwrap();
+        store
+            .insert(branch_id, "default", "test", "b", &[10.0, 1.0], None)
+            .unwrap();
+
+        // Cosine ignores magnitude: "a" points exactly along the query
+        let results = store
+            .search(branch_id, "default", "test", &[10.0, 0.0], 1, None)
+            .unwrap();
+        assert_eq!(results[0].key, "a");
+
+        let info = store
+            .reindex(
+                branch_id,
+                "default",
+                "test",
+                Some(DistanceMetric::Euclidean),
+                None,
+            )
+            .unwrap()
+            .value;
+        assert_eq!(info.config.metric, DistanceMetric::Euclidean);
+        assert_eq!(info.count, 2);
+
+        // Euclidean: "b" is closer to the query
+        let results = store
+            .search(branch_id, "default", "test", &[10.0, 0.0], 1, None)
+            .unwrap();
+        assert_eq!(results[0].key, "b");
+
+        let stored = store
+            .get_collection(branch_id, "default", "test")
+            .unwrap()
+            .unwrap()
+            .value;
+        assert_eq!(stored.config.metric, DistanceMetric::Euclidean);
+        assert_eq!(
+            store
+                .collection_backend_stats(branch_id, "default", "test")
+                .unwrap()
+                .0,
+            "brute_force"
+        );
+    }
+
+    #[test]
+    fn test_reindex_to_hnsw_survives_reload() {
+        crate::primitives::vector::register_vector_recovery();
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+            store
+                .create_collection(branch_id, "default", "test", config)
+                .unwrap();
+            store
+                .insert(branch_id, "default", "test", "a", &[1.0, 0.0, 0.0], None)
+                .unwrap();
+            store
+                .insert(branch_id, "default", "test", "b", &[0.0, 1.0, 0.0], None)
+                .unwrap();
+
+            store
+                .reindex(
+                    branch_id,
+                    "default",
+                    "test",
+                    None,
+                    Some(IndexBackendFactory::Hnsw(Default::default())),
+                )
+                .unwrap();
+            assert_eq!(
+                store
+                    .collection_backend_stats(branch_id, "default", "test")
+                    .unwrap()
+                    .0,
+                "hnsw"
+            );
+
+            // Writes after the reindex go to the new backend
+            store
+                .insert(branch_id, "default", "test", "c", &[0.0, 0.0, 1.0], None)
+                .unwrap();
+            let results = store
+                .search(branch_id, "default", "test", &[0.0, 0.0, 1.0], 1, None)
+                .unwrap();
+            assert_eq!(results[0].key, "c");
+        }
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            assert_eq!(
+                store
+                    .collection_backend_stats(branch_id, "default", "test")
+                    .unwrap()
+                    .0,
+                "hnsw"
+            );
+            let results = store
+                .search(branch_id, "default", "test", &[1.0, 0.0, 0.0], 3, None)
+                .unwrap();
+            assert_eq!(results.len(), 3);
+            assert_eq!(results[0].key, "a");
+        }
+    }
+
+    #[test]
+    fn test_reindex_collection_not_found() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let result = store.reindex(
+            branch_id,
+            "default",
+            "missing",
+            Some(DistanceMetric::Euclidean),
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(VectorError::CollectionNotFound { .. })
+        ));
+    }
+
+    fn insert_ns(store: &VectorStore, branch_id: BranchId, ns: Option<&str>, key: &str, v: &[f32]) {
+        store
+            .insert_in_namespace(branch_id, "default", "test", ns, key, v, None)
+            .unwrap();
+    }
+
+    fn keys_in(store: &VectorStore, branch_id: BranchId, ns: &str, k: usize) -> Vec<String> {
+        store
+            .search_in_namespace(branch_id, "default", "test", ns, &[1.0, 0.0], k, None)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_search_in_namespace_only_sees_members() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        insert_ns(&store, branch_id, Some("a"), "a1", &[1.0, 0.0]);
+        insert_ns(&store, branch_id, Some("a"), "a2", &[0.0, 1.0]);
+        insert_ns(&store, branch_id, Some("b"), "b1", &[1.0, 0.1]);
+        insert_ns(&store, branch_id, None, "x", &[1.0, 0.0]);
+
+        assert_eq!(keys_in(&store, branch_id, "a", 10), vec!["a1", "a2"]);
+        assert_eq!(keys_in(&store, branch_id, "b", 10), vec!["b1"]);
+        assert!(keys_in(&store, branch_id, "missing", 10).is_empty());
+        assert_eq!(keys_in(&store, branch_id, "a", 1), vec!["a1"]);
+
+        // Unrestricted search still covers every namespace
+        let all = store
+            .search(branch_id, "default", "test", &[1.0, 0.0], 10, None)
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[test]
+    fn test_namespace_follows_upsert_and_delete() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        insert_ns(&store, branch_id, Some("a"), "k", &[1.0, 0.0]);
+        insert_ns(&store, branch_id, Some("b"), "k", &[1.0, 0.0]);
+        assert!(keys_in(&store, branch_id, "a", 10).is_empty());
+        assert_eq!(keys_in(&store, branch_id, "b", 10), vec!["k"]);
+
+        // Plain upsert drops the namespace
+        store
+            .insert(branch_id, "default", "test", "k", &[1.0, 0.0], None)
+            .unwrap();
+        assert!(keys_in(&store, branch_id, "b", 10).is_empty());
+
+        insert_ns(&store, branch_id, Some("b"), "k", &[1.0, 0.0]);
+        store.delete(branch_id, "default", "test", "k").unwrap();
+        assert!(keys_in(&store, branch_id, "b", 10).is_empty());
+    }
+
+    #[test]
+    fn test_search_in_namespace_with_filter() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        // The only match for the filter ranks last in the namespace
+        for i in 0..20 {
+            let meta = serde_json::json!({ "tag": if i == 19 { "rare" } else { "common" } });
+            store
+                .insert_in_namespace(
+                    branch_id,
+                    "default",
+                    "test",
+                    Some("t"),
+                    &format!("k{:02}", i),
+                    &[1.0, i as f32],
+                    Some(meta),
+                )
+                .unwrap();
+        }
+
+        let results = store
+            .search_in_namespace(
+                branch_id,
+                "default",
+                "test",
+                "t",
+                &[1.0, 0.0],
+                1,
+                Some(MetadataFilter::new().eq("tag", "rare")),
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "k19");
+    }
+
+    #[test]
+    fn test_namespaces_survive_reindex_and_reload() {
+        crate::primitives::vector::register_vector_recovery();
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+            store
+                .create_collection(branch_id, "default", "test", config)
+                .unwrap();
+            insert_ns(&store, branch_id, Some("a"), "a1", &[1.0, 0.0]);
+            insert_ns(&store, branch_id, Some("b"), "b1", &[1.0, 0.0]);
+
+            store
+                .reindex(
+                    branch_id,
+                    "default",
+                    "test",
+                    None,
+                    Some(IndexBackendFactory::Hnsw(Default::default())),
+                )
+                .unwrap();
+            assert_eq!(keys_in(&store, branch_id, "a", 10), vec!["a1"]);
+        }
+
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let store = VectorStore::new(db);
+            assert_eq!(keys_in(&store, branch_id, "a", 10), vec!["a1"]);
+            assert_eq!(keys_in(&store, branch_id, "b", 10), vec!["b1"]);
+        }
+    }
+
+    #[test]
+    fn test_insert_rejects_empty_namespace() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+        let config = VectorConfig::new(2, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        let result = store.insert_in_namespace(
+            branch_id,
+            "default",
+            "test",
+            Some(""),
+            "k",
+            &[1.0, 0.0],
+            None,
+        );
+        assert!(matches!(result, Err(VectorError::InvalidNamespace { .. })));
+    }
+
     #[test]
     fn test_search_k_zero() {
         let (_temp, _db, store) = setup();
@@ -2481,6 +3377,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_with_or_not_prefix_filter() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+
+        let docs = [
+            (
+                "a",
+                [1.0, 0.0, 0.0],
+                serde_json::json!({"path": "docs/a", "year": 2001}),
+            ),
+            (
+                "b",
+                [0.9, 0.1, 0.0],
+                serde_json::json!({"path": "docs/b", "year": 2015}),
+            ),
+            (
+                "c",
+                [0.8, 0.2, 0.0],
+                serde_json::json!({"path": "src/c", "year": 2005}),
+            ),
+            (
+                "d",
+                [0.7, 0.3, 0.0],
+                serde_json::json!({"path": "docs/d", "draft": true}),
+            ),
+        ];
+        for (key, embedding, metadata) in docs {
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    key,
+                    &embedding,
+                    Some(metadata),
+                )
+                .unwrap();
+        }
+        store
+            .insert(branch_id, "default", "test", "e", &[0.6, 0.4, 0.0], None)
+            .unwrap();
+
+        // docs/* that are in range or not drafts: a (in range), b (not a draft),
+        // d is a draft and out of range
+        let filter = MetadataFilter::new().prefix("path", "docs/").or(vec![
+            MetadataFilter::new().range("year", 2000, 2010),
+            MetadataFilter::new().not(MetadataFilter::new().eq("draft", true)),
+        ]);
+        let results = store
+            .search(
+                branch_id,
+                "default",
+                "test",
+                &[1.0, 0.0, 0.0],
+                10,
+                Some(filter),
+            )
+            .unwrap();
+        let keys: Vec<&str> = results.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b"]);
+
+        // NOT on its own also matches vectors without metadata
+        let filter = MetadataFilter::new().not(MetadataFilter::new().prefix("path", "docs/"));
+        let results = store
+            .search(
+                branch_id,
+                "default",
+                "test",
+                &[1.0, 0.0, 0.0],
+                10,
+                Some(filter),
+            )
+            .unwrap();
+        let keys: Vec<&str> = results.iter().map(|m| m.key.as_str()).collect();
+        assert_eq!(keys, vec!["c", "e"]);
+    }
+
     #[test]
     fn test_search_deterministic_order() {
         let (_temp, _db, store) = setup();
@@ -2515,6 +3494,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_batch() {
+        let (_temp, _db, store) = setup();
+        let branch_id = BranchId::new();
+
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        store
+            .create_collection(branch_id, "default", "test", config)
+            .unwrap();
+        for i in 0..20 {
+            let embedding = [(i as f32).sin(), (i as f32).cos(), 0.5];
+            let metadata = serde_json::json!({"even": i % 2 == 0});
+            store
+                .insert(
+                    branch_id,
+                    "default",
+                    "test",
+                    &format!("v{}", i),
+                    &embedding,
+                    Some(metadata),
+                )
+                .unwrap();
+        }
+
+        let queries: Vec<Vec<f32>> = (0..12)
+            .map(|i| vec![(i as f32 * 0.7).cos(), (i as f32 * 0.7).sin(), 0.1])
+            .collect();
+        let filter = MetadataFilter::new().eq("even", true);
+
+        for filter in [None, Some(filter)] {
+            let batch = store
+                .search_batch(branch_id, "default", "test", &queries, 4, filter.clone())
+                .unwrap();
+            assert_eq!(batch.len(), queries.len());
+            for (query, matches) in queries.iter().zip(&batch) {
+                let single = store
+                    .search(branch_id, "default", "test", query, 4, filter.clone())
+                    .unwrap();
+                let keys = |m: &[VectorMatch]| -> Vec<(String, f32)> {
+                    m.iter().map(|m| (m.key.clone(), m.score)).collect()
+                };
+                assert_eq!(keys(matches), keys(&single));
+                assert_eq!(matches.len(), 4);
+            }
+        }
+
+        // Any mismatched query fails the whole batch
+        let err = store
+            .search_batch(
+                branch_id,
+                "default",
+                "test",
+                &[vec![1.0, 0.0, 0.0], vec![1.0, 0.0]],
+                4,
+                None,
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VectorError::DimensionMismatch {
+                expected: 3,
+                got: 2
+            }
+        ));
+
+        assert!(store
+            .search_batch(branch_id, "default", "test", &[], 4, None)
+            .unwrap()
+            .is_empty());
+    }
+
     // ========================================
     // WAL Replay Tests
     // ========================================
@@ -21,6 +21,7 @@ pub use strata_core::types::BranchId;
 // VectorRecord and CollectionRecord (Implementation types)
 // ============================================================================
 
+use crate::primitives::vector::IndexBackendFactory;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -74,8 +75,18 @@ pub struct VectorRecord {
     /// Used by internal search infrastructure to link embeddings back to
     /// their source documents for hydration during search result assembly.
     /// Backwards compatible: old WAL entries without this field will deserialize as None.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ///
+    /// Always serialized: records are MessagePack arrays, so skipping it
+    /// would shift `namespace` into its position.
+    #[serde(default)]
     pub source_ref: Option<EntityRef>,
+
+    /// Namespace (partition) within the collection, if any
+    ///
+    /// Searches restricted to a namespace only score its members.
+    /// Backwards compatible: old records without this field deserialize as None.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 impl VectorRecord {
@@ -90,6 +101,7 @@ impl VectorRecord {
             created_at: now,
             updated_at: now,
             source_ref: None,
+            namespace: None,
         }
     }
 
@@ -112,6 +124,7 @@ impl VectorRecord {
             created_at: now,
             updated_at: now,
             source_ref: Some(source_ref),
+            namespace: None,
         }
     }
 
@@ -211,6 +224,12 @@ pub struct CollectionRecord {
 
     /// Creation timestamp
     pub created_at: u64,
+
+    /// Index backend type (0 = BruteForce, 1 = HNSW)
+    ///
+    /// Records written before reindex support omit this and decode as BruteForce.
+    #[serde(default)]
+    pub index_type: u8,
 }
 
 impl CollectionRecord {
@@ -219,9 +238,21 @@ impl CollectionRecord {
         CollectionRecord {
             config: VectorConfigSerde::from(config),
             created_at: now_micros(),
+            index_type: 0,
         }
     }
 
+    /// Set the index backend type
+    pub fn with_index(mut self, factory: &IndexBackendFactory) -> Self {
+        self.index_type = factory.to_byte();
+        self
+    }
+
+    /// Factory for this collection's index backend
+    pub fn index_factory(&self) -> IndexBackendFactory {
+        IndexBackendFactory::from_byte(self.index_type)
+    }
+
     /// Serialize to bytes (MessagePack)
     pub fn to_bytes(&self) -> Result<Vec<u8>, crate::primitives::vector::VectorError> {
         rmp_serde::to_vec(self)
@@ -509,6 +540,59 @@ mod tests {
         assert_eq!(info.created_at, 1234567890);
     }
 
+    #[test]
+    fn test_collection_record_index_type_roundtrip() {
+        let config = VectorConfig::new(8, DistanceMetric::Cosine).unwrap();
+        let factory = IndexBackendFactory::Hnsw(Default::default());
+        let record = CollectionRecord::new(&config).with_index(&factory);
+
+        let decoded = CollectionRecord::from_bytes(&record.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.index_factory().index_type_name(), "hnsw");
+    }
+
+    #[test]
+    fn test_collection_record_without_index_type_is_brute_force() {
+        // Records written before index_type existed carry only config and created_at
+        let config =
+            VectorConfigSerde::from(&VectorConfig::new(8, DistanceMetric::Cosine).unwrap());
+        let bytes = rmp_serde::to_vec(&(config, 42u64)).unwrap();
+
+        let decoded = CollectionRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.created_at, 42);
+        assert_eq!(decoded.index_factory().index_type_name(), "brute_force");
+    }
+
+    #[test]
+    fn test_vector_record_namespace_roundtrip() {
+        // No source_ref: namespace must not slide into its array slot
+        let mut record = VectorRecord::new(VectorId::new(7), vec![1.0, 2.0], None);
+        record.namespace = Some("tenant-a".to_string());
+
+        let decoded = VectorRecord::from_bytes(&record.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.namespace.as_deref(), Some("tenant-a"));
+        assert!(decoded.source_ref.is_none());
+    }
+
+    #[test]
+    fn test_vector_record_without_namespace_field() {
+        // Records written before namespaces ended at (skipped) source_ref
+        let record = VectorRecord::new(VectorId::new(3), vec![0.5], None);
+        let legacy = (
+            record.vector_id,
+            record.embedding.clone(),
+            record.metadata.clone(),
+            record.version,
+            record.created_at,
+            record.updated_at,
+        );
+        let bytes = rmp_serde::to_vec(&legacy).unwrap();
+
+        let decoded = VectorRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.vector_id, 3);
+        assert!(decoded.source_ref.is_none());
+        assert!(decoded.namespace.is_none());
+    }
+
     // ========================================
     // CollectionId Tests (#396)
     // ========================================
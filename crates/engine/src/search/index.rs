@@ -21,9 +21,10 @@
 use super::tokenizer::tokenize;
 use super::types::EntityRef;
 use dashmap::DashMap;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
+use strata_core::types::BranchId;
 
 // ============================================================================
 // PostingEntry
@@ -341,6 +342,74 @@ impl InvertedIndex {
         }
     }
 
+    /// Remove every document of a branch
+    ///
+    /// Unlike `remove_document()` this also runs while the index is
+    /// disabled, so a deleted branch's postings cannot come back when the
+    /// index is re-enabled. Returns the number of documents removed.
+    pub fn clear_branch(&self, branch_id: &BranchId) -> usize {
+        let mut removed: HashSet<EntityRef> = HashSet::new();
+        let mut removed_len = 0usize;
+        self.doc_lengths.retain(|doc_ref, len| {
+            if doc_ref.branch_id() != *branch_id {
+                return true;
+            }
+            removed.insert(doc_ref.clone());
+            removed_len += *len as usize;
+            false
+        });
+
+        // Postings can outlive their length entry (indexes built before #608)
+        for mut entry in self.postings.iter_mut() {
+            let before = entry.entries.len();
+            entry.entries.retain(|e| {
+                if e.doc_ref.branch_id() != *branch_id {
+                    return true;
+                }
+                removed.insert(e.doc_ref.clone());
+                false
+            });
+            let count = before - entry.entries.len();
+            if count > 0 {
+                let term = entry.key().clone();
+                self.doc_freqs
+                    .entry(term)
+                    .and_modify(|c| *c = c.saturating_sub(count));
+            }
+        }
+        if removed.is_empty() {
+            return 0;
+        }
+        self.postings.retain(|_, list| !list.is_empty());
+        self.doc_freqs.retain(|_, count| *count > 0);
+
+        let _ = self
+            .total_docs
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                Some(n.saturating_sub(removed.len()))
+            });
+        let _ = self
+            .total_doc_len
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+                Some(n.saturating_sub(removed_len))
+            });
+        self.version.fetch_add(1, Ordering::Release);
+        removed.len()
+    }
+
+    /// Branches with at least one document in the index
+    pub fn branch_ids(&self) -> HashSet<BranchId> {
+        let mut ids: HashSet<BranchId> = self
+            .doc_lengths
+            .iter()
+            .map(|entry| entry.key().branch_id())
+            .collect();
+        for entry in self.postings.iter() {
+            ids.extend(entry.entries.iter().map(|e| e.doc_ref.branch_id()));
+        }
+        ids
+    }
+
     // ========================================================================
     // Query
     // ========================================================================
@@ -481,6 +550,34 @@ mod tests {
         assert_eq!(index.doc_freq("world"), 0);
     }
 
+    #[test]
+    fn test_clear_branch() {
+        let index = InvertedIndex::new();
+        index.enable();
+
+        let doc1 = test_doc_ref("doc1");
+        let doc2 = test_doc_ref("doc2");
+        index.index_document(&doc1, "hello world", None);
+        index.index_document(&doc2, "hello there", None);
+        assert_eq!(index.branch_ids().len(), 2);
+
+        // Also clears while disabled
+        index.disable();
+        assert_eq!(index.clear_branch(&doc1.branch_id()), 1);
+        assert_eq!(index.clear_branch(&doc1.branch_id()), 0);
+        index.enable();
+
+        assert_eq!(index.total_docs(), 1);
+        assert_eq!(index.doc_freq("hello"), 1);
+        assert_eq!(index.doc_freq("world"), 0);
+        assert!(index.lookup("world").is_none());
+        assert_eq!(index.avg_doc_len(), 2.0);
+        assert_eq!(
+            index.branch_ids().into_iter().collect::<Vec<_>>(),
+            vec![doc2.branch_id()]
+        );
+    }
+
     #[test]
     fn test_clear() {
         let index = InvertedIndex::new();
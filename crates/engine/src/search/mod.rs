@@ -5,9 +5,11 @@
 //! - `searchable`: Searchable trait and scoring infrastructure
 //! - `index`: Optional inverted index for fast keyword search
 //! - `tokenizer`: Basic text tokenization
+//! - `text`: BM25 full-text search over KV, JSON and event content
 
 mod index;
 mod searchable;
+mod text;
 pub mod tokenizer;
 mod types;
 
@@ -16,7 +18,8 @@ pub use searchable::{
     build_search_response, build_search_response_with_index, BM25LiteScorer, Scorer, ScorerContext,
     SearchCandidate, SearchDoc, Searchable, SimpleScorer,
 };
-pub use tokenizer::{tokenize, tokenize_unique};
+pub use text::{TextHit, TextIndex, TextSearch};
+pub use tokenizer::{tokenize, tokenize_unique, tokenize_with_offsets};
 pub use types::{
     EntityRef, PrimitiveType, SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse,
     SearchStats,
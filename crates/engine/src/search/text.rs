@@ -0,0 +1,752 @@
+//! Full-text search over KV strings, JSON documents and event payloads
+//!
+//! This module provides:
+//! - TextIndex: per-space inverted index kept current by the commit path
+//! - TextSearch: BM25 ranking with highlighted snippets
+//! - Persistence of the index next to the WAL
+//!
+//! # Consistency
+//!
+//! The index is built lazily on the first search and updated after every
+//! commit from then on. Updates re-read the touched keys from storage, so
+//! commits that finish out of order still leave the index matching storage.
+//! Each branch is reconciled against storage once per process, which picks
+//! up anything written before the index was loaded (recovery, forks). Only
+//! documents whose version changed since the saved index are re-tokenized.
+//!
+//! The index stores term statistics only. Snippets are cut from the current
+//! stored value at query time.
+
+use super::tokenizer::{tokenize, tokenize_unique, tokenize_with_offsets};
+use super::types::EntityRef;
+use crate::database::Database;
+use crate::primitives::json::JsonStore;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use strata_concurrency::TransactionContext;
+use strata_core::primitives::Event;
+use strata_core::types::{BranchId, Key, Namespace, TypeTag};
+use strata_core::value::Value;
+use strata_core::{Storage, StrataError, StrataResult};
+use strata_durability::codec::StorageCodec;
+use strata_storage::ShardedStore;
+
+/// BM25 term frequency saturation
+const BM25_K1: f32 = 1.2;
+
+/// BM25 document length normalization
+const BM25_B: f32 = 0.75;
+
+/// Target snippet length in bytes
+const SNIPPET_LEN: usize = 160;
+
+/// Context kept before the first match in a snippet, in bytes
+const SNIPPET_LEAD: usize = 60;
+
+/// Version of the persisted index layout
+const FORMAT_VERSION: u32 = 1;
+
+// ============================================================================
+// Documents
+// ============================================================================
+
+/// A searchable document within one space
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+enum DocId {
+    Kv(String),
+    Json(String),
+    Event(u64),
+}
+
+impl DocId {
+    /// Identify the document stored under `key`, if it is one
+    ///
+    /// Internal keys (reserved prefixes, event metadata and type indexes)
+    /// are not documents.
+    fn from_key(key: &Key) -> Option<DocId> {
+        match key.type_tag {
+            TypeTag::KV | TypeTag::Json => {
+                let name = key.user_key_string()?;
+                if strata_core::key::reserved_prefix(&name).is_some() {
+                    return None;
+                }
+                Some(if key.type_tag == TypeTag::KV {
+                    DocId::Kv(name)
+                } else {
+                    DocId::Json(name)
+                })
+            }
+            TypeTag::Event if key.user_key.len() == 8 && key.user_key != b"__meta__" => {
+                let mut seq = [0u8; 8];
+                seq.copy_from_slice(&key.user_key);
+                Some(DocId::Event(u64::from_be_bytes(seq)))
+            }
+            _ => None,
+        }
+    }
+
+    fn key(&self, branch_id: BranchId, space: &str) -> Key {
+        let ns = Namespace::for_branch_space(branch_id, space);
+        match self {
+            DocId::Kv(key) => Key::new_kv(ns, key),
+            DocId::Json(id) => Key::new_json(ns, id),
+            DocId::Event(seq) => Key::new_event(ns, *seq),
+        }
+    }
+
+    fn entity_ref(&self, branch_id: BranchId) -> EntityRef {
+        match self {
+            DocId::Kv(key) => EntityRef::kv(branch_id, key.clone()),
+            DocId::Json(id) => EntityRef::json(branch_id, id.clone()),
+            DocId::Event(seq) => EntityRef::event(branch_id, *seq),
+        }
+    }
+
+    /// Extract the searchable text from a stored value
+    ///
+    /// KV values are searchable when they are strings. JSON documents and
+    /// event payloads contribute every string they contain.
+    fn text(&self, value: &Value) -> Option<String> {
+        let mut parts = Vec::new();
+        match self {
+            DocId::Kv(_) => match value {
+                Value::String(s) => parts.push(s.clone()),
+                _ => return None,
+            },
+            DocId::Json(_) => {
+                let doc = JsonStore::deserialize_doc(value).ok()?;
+                json_strings(doc.value.as_inner(), &mut parts);
+            }
+            DocId::Event(_) => {
+                let Value::String(stored) = value else {
+                    return None;
+                };
+                let event: Event = serde_json::from_str(stored).ok()?;
+                value_strings(&event.payload, &mut parts);
+            }
+        }
+        Some(parts.join(" "))
+    }
+}
+
+fn json_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(s) => out.push(s.clone()),
+        serde_json::Value::Array(items) => items.iter().for_each(|v| json_strings(v, out)),
+        serde_json::Value::Object(fields) => fields.values().for_each(|v| json_strings(v, out)),
+        _ => {}
+    }
+}
+
+fn value_strings(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(s) => out.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|v| value_strings(v, out)),
+        Value::Object(fields) => fields.values().for_each(|v| value_strings(v, out)),
+        _ => {}
+    }
+}
+
+/// Term statistics for one indexed document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DocEntry {
+    /// Storage version the statistics were computed from
+    version: u64,
+    /// Document length in tokens
+    len: u32,
+    /// Term frequencies
+    terms: Vec<(String, u32)>,
+}
+
+impl DocEntry {
+    fn new(version: u64, text: &str) -> Self {
+        let tokens = tokenize(text);
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for token in &tokens {
+            *counts.entry(token.clone()).or_insert(0) += 1;
+        }
+        DocEntry {
+            version,
+            len: tokens.len() as u32,
+            terms: counts.into_iter().collect(),
+        }
+    }
+}
+
+// ============================================================================
+// SpaceIndex
+// ============================================================================
+
+/// Inverted index over the documents of one space in one branch
+#[derive(Debug, Default)]
+struct SpaceIndex {
+    docs: HashMap<DocId, DocEntry>,
+    postings: HashMap<String, HashMap<DocId, u32>>,
+    total_len: u64,
+}
+
+impl SpaceIndex {
+    fn insert(&mut self, id: DocId, entry: DocEntry) {
+        self.remove(&id);
+        for (term, tf) in &entry.terms {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(id.clone(), *tf);
+        }
+        self.total_len += entry.len as u64;
+        self.docs.insert(id, entry);
+    }
+
+    fn remove(&mut self, id: &DocId) {
+        let Some(entry) = self.docs.remove(id) else {
+            return;
+        };
+        for (term, _) in &entry.terms {
+            if let Some(posting) = self.postings.get_mut(term) {
+                posting.remove(id);
+                if posting.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        self.total_len -= entry.len as u64;
+    }
+
+    /// Score every document matching any query term with BM25
+    fn score(&self, terms: &[String]) -> Vec<(DocId, f32)> {
+        let n = self.docs.len() as f32;
+        if n == 0.0 {
+            return Vec::new();
+        }
+        let avg_len = (self.total_len as f32 / n).max(1.0);
+        let mut scores: HashMap<&DocId, f32> = HashMap::new();
+        for term in terms {
+            let Some(posting) = self.postings.get(term) else {
+                continue;
+            };
+            let df = posting.len() as f32;
+            let idf = ((n - df + 0.5) / (df + 0.5) + 1.0).ln();
+            for (id, tf) in posting {
+                let tf = *tf as f32;
+                let len = self.docs[id].len as f32;
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * len / avg_len);
+                *scores.entry(id).or_insert(0.0) += idf * tf * (BM25_K1 + 1.0) / (tf + norm);
+            }
+        }
+        let mut ranked: Vec<(DocId, f32)> = scores
+            .into_iter()
+            .map(|(id, score)| (id.clone(), score))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+}
+
+// ============================================================================
+// TextIndex
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct IndexState {
+    spaces: HashMap<(BranchId, String), SpaceIndex>,
+    /// Branches checked against storage since the index was loaded
+    reconciled: HashSet<BranchId>,
+}
+
+impl IndexState {
+    /// Bring the document stored under `key` up to date with `current`
+    fn refresh(&mut self, key: &Key, current: Option<(u64, &Value)>) {
+        let Some(id) = DocId::from_key(key) else {
+            return;
+        };
+        let space_key = (key.namespace.branch_id, key.namespace.space.clone());
+        let indexed = self
+            .spaces
+            .get(&space_key)
+            .and_then(|space| space.docs.get(&id))
+            .map(|entry| entry.version);
+        match current.and_then(|(version, value)| Some((version, id.text(value)?))) {
+            Some((version, _)) if indexed == Some(version) => {}
+            Some((version, text)) => {
+                let entry = DocEntry::new(version, &text);
+                self.spaces.entry(space_key).or_default().insert(id, entry);
+            }
+            None => {
+                if let Some(space) = self.spaces.get_mut(&space_key) {
+                    space.remove(&id);
+                    if space.docs.is_empty() {
+                        self.spaces.remove(&space_key);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Full-text index over KV strings, JSON documents and event payloads
+///
+/// Stored as a [`Database`] extension. Disabled until the first search, so
+/// databases that never search pay nothing on commit.
+#[derive(Debug, Default)]
+pub struct TextIndex {
+    enabled: AtomicBool,
+    state: RwLock<IndexState>,
+}
+
+/// On-disk form of the index: term statistics per document
+///
+/// Postings are rebuilt from these on load.
+#[derive(Serialize, Deserialize)]
+struct PersistedIndex {
+    format: u32,
+    spaces: Vec<PersistedSpace>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedSpace {
+    branch: [u8; 16],
+    space: String,
+    docs: Vec<(DocId, DocEntry)>,
+}
+
+impl TextIndex {
+    /// Check if the index is being maintained
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// Update the index for the keys written by a committed transaction
+    ///
+    /// Called by the commit path after the writes are applied to storage.
+    /// NOOP while the index is disabled.
+    pub(crate) fn apply_commit(&self, txn: &TransactionContext, storage: &ShardedStore) {
+        if !self.is_enabled() {
+            return;
+        }
+        let keys = txn
+            .write_set
+            .keys()
+            .chain(txn.delete_set.iter())
+            .chain(txn.cas_set.iter().map(|op| &op.key));
+        let mut state = self.state.write();
+        for key in keys {
+            if DocId::from_key(key).is_none() {
+                continue;
+            }
+            let current = storage.get(key).ok().flatten();
+            state.refresh(
+                key,
+                current.as_ref().map(|vv| (vv.version.as_u64(), &vv.value)),
+            );
+        }
+    }
+
+    /// Remove every document of a branch; returns how many were removed
+    ///
+    /// The branch is checked against storage again on its next search.
+    pub(crate) fn clear_branch(&self, branch_id: &BranchId) -> usize {
+        let mut state = self.state.write();
+        state.reconciled.remove(branch_id);
+        let mut removed = 0;
+        state.spaces.retain(|(branch, _), index| {
+            if branch != branch_id {
+                return true;
+            }
+            removed += index.docs.len();
+            false
+        });
+        removed
+    }
+
+    /// Branches with at least one document in the index
+    pub(crate) fn branch_ids(&self) -> HashSet<BranchId> {
+        self.state
+            .read()
+            .spaces
+            .keys()
+            .map(|(branch, _)| *branch)
+            .collect()
+    }
+
+    /// Load the saved index if there is one and start maintaining it
+    ///
+    /// A missing or unreadable file leaves the index empty; reconciliation
+    /// then rebuilds it from storage.
+    fn enable(&self, path: Option<&Path>, codec: &dyn StorageCodec) {
+        let mut state = self.state.write();
+        if self.is_enabled() {
+            return;
+        }
+        if let Some(path) = path.filter(|p| p.exists()) {
+            match Self::load(path, codec) {
+                Ok(spaces) => state.spaces = spaces,
+                Err(e) => {
+                    tracing::warn!(target: "strata::search", path = ?path, error = %e, "Discarding unreadable text index");
+                }
+            }
+        }
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Check every document of `branch_id` against storage, once
+    fn reconcile(&self, storage: &ShardedStore, branch_id: BranchId) {
+        if self.state.read().reconciled.contains(&branch_id) {
+            return;
+        }
+        let mut state = self.state.write();
+        if !state.reconciled.insert(branch_id) {
+            return;
+        }
+        let mut seen: HashSet<(String, DocId)> = HashSet::new();
+        for type_tag in [TypeTag::KV, TypeTag::Json, TypeTag::Event] {
+            for (key, vv) in storage.list_by_type(&branch_id, type_tag) {
+                if let Some(id) = DocId::from_key(&key) {
+                    state.refresh(&key, Some((vv.version.as_u64(), &vv.value)));
+                    seen.insert((key.namespace.space.clone(), id));
+                }
+            }
+        }
+        state.spaces.retain(|(branch, space), index| {
+            if *branch != branch_id {
+                return true;
+            }
+            let mut stale = Vec::new();
+            for id in index.docs.keys() {
+                if !seen.contains(&(space.clone(), id.clone())) {
+                    stale.push(id.clone());
+                }
+            }
+            for id in &stale {
+                index.remove(id);
+            }
+            !index.docs.is_empty()
+        });
+    }
+
+    fn load(
+        path: &Path,
+        codec: &dyn StorageCodec,
+    ) -> StrataResult<HashMap<(BranchId, String), SpaceIndex>> {
+        let raw = std::fs::read(path).map_err(StrataError::from)?;
+        let bytes = codec
+            .decode(&raw)
+            .map_err(|e| StrataError::corruption(format!("text index: {}", e)))?;
+        let persisted: PersistedIndex =
+            rmp_serde::from_slice(&bytes).map_err(|e| StrataError::serialization(e.to_string()))?;
+        if persisted.format != FORMAT_VERSION {
+            return Err(StrataError::corruption(format!(
+                "text index format {} is not supported",
+                persisted.format
+            )));
+        }
+        let mut spaces = HashMap::new();
+        for saved in persisted.spaces {
+            let mut index = SpaceIndex::default();
+            for (id, entry) in saved.docs {
+                index.insert(id, entry);
+            }
+            spaces.insert((BranchId::from_bytes(saved.branch), saved.space), index);
+        }
+        Ok(spaces)
+    }
+
+    /// Write the index to `path`, replacing any previous file atomically
+    ///
+    /// NOOP while the index is disabled.
+    pub(crate) fn save(&self, path: &Path, codec: &dyn StorageCodec) -> StrataResult<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let persisted = {
+            let state = self.state.read();
+            PersistedIndex {
+                format: FORMAT_VERSION,
+                spaces: state
+                    .spaces
+                    .iter()
+                    .map(|((branch, space), index)| PersistedSpace {
+                        branch: *branch.as_bytes(),
+                        space: space.clone(),
+                        docs: index
+                            .docs
+                            .iter()
+                            .map(|(id, entry)| (id.clone(), entry.clone()))
+                            .collect(),
+                    })
+                    .collect(),
+            }
+        };
+        let bytes =
+            rmp_serde::to_vec(&persisted).map_err(|e| StrataError::serialization(e.to_string()))?;
+        let tmp = path.with_extension("idx.tmp");
+        std::fs::write(&tmp, codec.encode(&bytes)).map_err(StrataError::from)?;
+        std::fs::rename(&tmp, path).map_err(StrataError::from)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// TextSearch
+// ============================================================================
+
+/// A ranked full-text match
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextHit {
+    /// The matching document
+    pub doc_ref: EntityRef,
+    /// BM25 score
+    pub score: f32,
+    /// Position in the results (1-indexed)
+    pub rank: u32,
+    /// Excerpt of the document around the first match
+    pub snippet: String,
+    /// Byte ranges of matched terms within `snippet`
+    pub highlights: Vec<(usize, usize)>,
+}
+
+/// BM25 full-text search over a branch space
+///
+/// Searches string KV values, the strings inside JSON documents and the
+/// strings inside event payloads.
+///
+/// # Example
+///
+/// ```text
+/// let search = TextSearch::new(db.clone());
+/// let hits = search.text(&branch_id, "default", "disk failure", 10)?;
+/// for hit in hits {
+///     println!("{:?} {:.2} {}", hit.doc_ref, hit.score, hit.snippet);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TextSearch {
+    db: Arc<Database>,
+}
+
+impl TextSearch {
+    /// Create new TextSearch instance
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Return the `k` best matches for `query` in a space
+    ///
+    /// The query is tokenized like the documents; a document matches if it
+    /// contains any query term. Results are ordered by descending score.
+    pub fn text(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        query: &str,
+        k: usize,
+    ) -> StrataResult<Vec<TextHit>> {
+        let index = self.db.extension::<TextIndex>()?;
+        index.enable(self.db.text_index_path().as_deref(), &*self.db.codec());
+        index.reconcile(self.db.storage(), *branch_id);
+
+        let terms = tokenize_unique(query);
+        let ranked = {
+            let state = index.state.read();
+            match state.spaces.get(&(*branch_id, space.to_string())) {
+                Some(space_index) => space_index.score(&terms),
+                None => Vec::new(),
+            }
+        };
+
+        let terms: HashSet<&str> = terms.iter().map(String::as_str).collect();
+        let mut hits = Vec::new();
+        for (id, score) in ranked {
+            if hits.len() >= k {
+                break;
+            }
+            let stored = self.db.storage().get(&id.key(*branch_id, space))?;
+            let Some(text) = stored.and_then(|vv| id.text(&vv.value)) else {
+                continue;
+            };
+            let (snippet, highlights) = snippet(&text, &terms);
+            hits.push(TextHit {
+                doc_ref: id.entity_ref(*branch_id),
+                score,
+                rank: hits.len() as u32 + 1,
+                snippet,
+                highlights,
+            });
+        }
+        Ok(hits)
+    }
+}
+
+/// Cut an excerpt around the first query term in `text`
+///
+/// Returns the excerpt, with `...` marking trimmed ends, and the byte
+/// ranges of every query term inside it.
+fn snippet(text: &str, terms: &HashSet<&str>) -> (String, Vec<(usize, usize)>) {
+    let tokens = tokenize_with_offsets(text);
+    let first = tokens
+        .iter()
+        .find(|(term, _)| terms.contains(term.as_str()))
+        .map_or(0, |(_, range)| range.start);
+
+    // Never cut a term in half at either end
+    let mut start = first.saturating_sub(SNIPPET_LEAD);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    if let Some((_, range)) = tokens
+        .iter()
+        .find(|(_, r)| r.start < start && r.end > start)
+    {
+        start = range.end;
+    }
+    let mut end = (start + SNIPPET_LEN).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    if let Some((_, range)) = tokens.iter().find(|(_, r)| r.start < end && r.end > end) {
+        end = range.start;
+    }
+
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < text.len() { "..." } else { "" };
+    let snippet = format!("{}{}{}", prefix, &text[start..end], suffix);
+    let highlights = tokens
+        .iter()
+        .filter(|(term, range)| {
+            range.start >= start && range.end <= end && terms.contains(term.as_str())
+        })
+        .map(|(_, range)| {
+            let offset = prefix.len() + range.start - start;
+            (offset, offset + range.len())
+        })
+        .collect();
+    (snippet, highlights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{EventLog, JsonStore, KVStore};
+    use strata_core::primitives::json::JsonValue;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_text_search_indexes_all_primitives_and_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let branch_id = BranchId::new();
+        {
+            let db = Database::open(temp_dir.path()).unwrap();
+            let kv = KVStore::new(db.clone());
+            kv.put(
+                &branch_id,
+                "default",
+                "note",
+                Value::String("the disk is failing".into()),
+            )
+            .unwrap();
+            kv.put(&branch_id, "default", "count", Value::Int(7))
+                .unwrap();
+            JsonStore::new(db.clone())
+                .create(
+                    &branch_id,
+                    "default",
+                    "incident",
+                    JsonValue::from(serde_json::json!({"title": "Disk replaced", "sev": 2})),
+                )
+                .unwrap();
+            let payload = Value::Object(
+                [("msg".to_string(), Value::String("fan noise".into()))]
+                    .into_iter()
+                    .collect(),
+            );
+            EventLog::new(db.clone())
+                .append(&branch_id, "default", "alert", payload)
+                .unwrap();
+
+            let search = TextSearch::new(db.clone());
+            let hits = search.text(&branch_id, "default", "disk", 10).unwrap();
+            let refs: Vec<_> = hits.iter().map(|h| h.doc_ref.clone()).collect();
+            assert_eq!(refs.len(), 2);
+            assert!(refs.contains(&EntityRef::kv(branch_id, "note")));
+            assert!(refs.contains(&EntityRef::json(branch_id, "incident")));
+            assert_eq!(hits[0].rank, 1);
+            let (start, end) = hits[0].highlights[0];
+            assert_eq!(hits[0].snippet[start..end].to_lowercase(), "disk");
+
+            let hits = search.text(&branch_id, "default", "noise", 10).unwrap();
+            assert_eq!(hits[0].doc_ref, EntityRef::event(branch_id, 0));
+            assert!(search
+                .text(&branch_id, "other", "disk", 10)
+                .unwrap()
+                .is_empty());
+
+            // Index follows commits once built
+            kv.delete(&branch_id, "default", "note").unwrap();
+            let hits = search.text(&branch_id, "default", "disk", 10).unwrap();
+            assert_eq!(hits.len(), 1);
+        }
+        assert!(temp_dir.path().join("text.idx").exists());
+
+        let db = Database::open(temp_dir.path()).unwrap();
+        // Written while the index is not loaded; reconciliation picks it up
+        KVStore::new(db.clone())
+            .put(
+                &branch_id,
+                "default",
+                "later",
+                Value::String("new disk".into()),
+            )
+            .unwrap();
+        let hits = TextSearch::new(db)
+            .text(&branch_id, "default", "disk", 10)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+    }
+
+    #[test]
+    fn test_bm25_prefers_rarer_terms_and_shorter_docs() {
+        let mut index = SpaceIndex::default();
+        index.insert(
+            DocId::Kv("a".into()),
+            DocEntry::new(1, "disk failure on node"),
+        );
+        index.insert(
+            DocId::Kv("b".into()),
+            DocEntry::new(1, "node restarted after a long maintenance window on node"),
+        );
+        index.insert(DocId::Kv("c".into()), DocEntry::new(1, "node healthy"));
+
+        let ranked = index.score(&tokenize_unique("disk node"));
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0].0, DocId::Kv("a".into()));
+
+        index.remove(&DocId::Kv("a".into()));
+        assert!(index.score(&tokenize_unique("disk")).is_empty());
+        assert!(!index.postings.contains_key("disk"));
+    }
+
+    #[test]
+    fn test_snippet_highlights_matches() {
+        let text = format!("{} the disk failed {}", "x ".repeat(100), "y ".repeat(100));
+        let terms: HashSet<&str> = ["disk"].into_iter().collect();
+        let (snippet, highlights) = snippet(&text, &terms);
+
+        assert!(snippet.starts_with("...") && snippet.ends_with("..."));
+        assert_eq!(highlights.len(), 1);
+        let (start, end) = highlights[0];
+        assert_eq!(&snippet[start..end], "disk");
+    }
+
+    #[test]
+    fn test_snippet_respects_char_boundaries() {
+        let text = "é".repeat(200) + " needle";
+        let terms: HashSet<&str> = ["needle"].into_iter().collect();
+        let (snippet, highlights) = snippet(&text, &terms);
+        let (start, end) = highlights[0];
+        assert_eq!(&snippet[start..end], "needle");
+    }
+}
@@ -3,6 +3,8 @@
 //! This module provides simple text tokenization for search operations.
 //! Future milestones can add stemming, stopwords, etc.
 
+use std::ops::Range;
+
 /// Tokenize text into searchable terms
 ///
 /// This is a simple tokenizer for Initial version:
@@ -44,6 +46,41 @@ pub fn tokenize_unique(text: &str) -> Vec<String> {
         .collect()
 }
 
+/// Tokenize text, keeping the byte range of each term in the input
+///
+/// Applies the same rules as [`tokenize`], but each term is paired with
+/// its position in `text` so callers can highlight matches.
+///
+/// # Example
+///
+/// ```
+/// use strata_engine::search::tokenizer::tokenize_with_offsets;
+///
+/// let tokens = tokenize_with_offsets("Hello, World!");
+/// assert_eq!(tokens[1], ("world".to_string(), 7..12));
+/// ```
+pub fn tokenize_with_offsets(text: &str) -> Vec<(String, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                let term = text[s..i].to_lowercase();
+                if term.len() >= 2 {
+                    tokens.push((term, s..i));
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,4 +127,15 @@ mod tests {
         let tokens = tokenize_unique("apple banana apple cherry");
         assert_eq!(tokens, vec!["apple", "banana", "cherry"]);
     }
+
+    #[test]
+    fn test_tokenize_with_offsets_matches_tokenize() {
+        let text = "Ünïcode résumé, I am a test123!";
+        let tokens = tokenize_with_offsets(text);
+        let terms: Vec<String> = tokens.iter().map(|(t, _)| t.clone()).collect();
+        assert_eq!(terms, tokenize(text));
+        for (term, range) in tokens {
+            assert_eq!(text[range].to_lowercase(), term);
+        }
+    }
 }
@@ -17,13 +17,16 @@
 //! - State cell CAS (compare-and-swap) support
 //! - JSON document operations via TransactionContext
 
-use crate::primitives::event::{EventLogMeta, HASH_VERSION_SHA256};
+use crate::primitives::event::{append_in, load_meta, EventLogMeta, HASH_VERSION_SHA256};
+use crate::primitives::json::{check_against, governing_schema, JsonStore};
 use crate::transaction_ops::TransactionOps;
 use strata_concurrency::{JsonStoreExt, TransactionContext};
+use strata_core::key::ensure_not_reserved;
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::{
-    BranchMetadata, BranchStatus, EntityRef, Event, JsonPatch, JsonPath, JsonValue, MetadataFilter,
-    State, StrataError, Timestamp, Value, VectorEntry, VectorMatch, Version, Versioned,
+    delete_at_path, set_at_path, BranchMetadata, BranchStatus, EntityRef, Event, JsonPatch,
+    JsonPath, JsonValue, MetadataFilter, State, StrataError, Value, VectorEntry, VectorMatch,
+    Version, Versioned,
 };
 
 /// Transaction wrapper that implements TransactionOps
@@ -98,9 +101,13 @@ impl<'a> Transaction<'a> {
         }
     }
 
-    /// Get the run ID for this transaction
+    /// Get the branch this wrapper reads and writes
+    ///
+    /// Usually the transaction's own branch. A wrapper built with another
+    /// branch's namespace adds that branch to the transaction, and the
+    /// commit applies to both atomically.
     pub fn branch_id(&self) -> BranchId {
-        self.ctx.branch_id
+        self.namespace.branch_id
     }
 
     /// Create a KV key for the given user key
@@ -118,17 +125,6 @@ impl<'a> Transaction<'a> {
         key.user_key_string().unwrap_or_default()
     }
 
-    /// Compute hash for an event using the canonical hash function.
-    fn compute_event_hash(event: &Event) -> [u8; 32] {
-        crate::primitives::event::compute_event_hash(
-            event.sequence,
-            &event.event_type,
-            &event.payload,
-            event.timestamp,
-            &event.prev_hash,
-        )
-    }
-
     /// Get pending events (for commit)
     pub fn pending_events(&self) -> &[Event] {
         &self.pending_events
@@ -148,6 +144,42 @@ impl<'a> Transaction<'a> {
     fn json_key(&self, doc_id: &str) -> Key {
         Key::new_json(self.namespace.clone(), doc_id)
     }
+
+    /// Reject `patch` if a schema governs `doc_id` and the document would
+    /// no longer match it
+    ///
+    /// The document is rebuilt from its stored value and the patches this
+    /// transaction has already buffered for it.
+    fn check_json_schema(&mut self, doc_id: &str, patch: JsonPatch) -> Result<(), StrataError> {
+        let Some((prefix, schema)) = governing_schema(self.ctx, &self.namespace, doc_id)? else {
+            return Ok(());
+        };
+        let full_key = self.json_key(doc_id);
+        let mut doc = match self.ctx.get(&full_key)? {
+            Some(stored) => Some(JsonStore::deserialize_doc(&stored)?.value),
+            None => None,
+        };
+        let buffered = self.ctx.json_writes().iter().filter(|e| e.key == full_key);
+        for patch in buffered.map(|e| &e.patch).chain(std::iter::once(&patch)) {
+            let path_error = |e| StrataError::invalid_input(format!("Path error: {}", e));
+            match patch {
+                JsonPatch::Set { path, value } => {
+                    let root = doc.get_or_insert_with(JsonValue::object);
+                    set_at_path(root, path, value.clone()).map_err(path_error)?;
+                }
+                JsonPatch::Delete { path } if path.is_root() => doc = None,
+                JsonPatch::Delete { path } => {
+                    if let Some(root) = doc.as_mut() {
+                        delete_at_path(root, path).map_err(path_error)?;
+                    }
+                }
+            }
+        }
+        match doc {
+            Some(doc) => check_against(&prefix, &schema, &doc),
+            None => Ok(()),
+        }
+    }
 }
 
 impl<'a> TransactionOps for Transaction<'a> {
@@ -178,6 +210,7 @@ impl<'a> TransactionOps for Transaction<'a> {
     }
 
     fn kv_put(&mut self, key: &str, value: Value) -> Result<Version, StrataError> {
+        ensure_not_reserved(key)?;
         let full_key = self.kv_key(key);
 
         // Use the ctx.put() method which handles all the bookkeeping
@@ -187,6 +220,7 @@ impl<'a> TransactionOps for Transaction<'a> {
     }
 
     fn kv_delete(&mut self, key: &str) -> Result<bool, StrataError> {
+        ensure_not_reserved(key)?;
         let full_key = self.kv_key(key);
 
         // Check if key exists (for return value)
@@ -249,46 +283,27 @@ impl<'a> TransactionOps for Transaction<'a> {
     // =========================================================================
 
     fn event_append(&mut self, event_type: &str, payload: Value) -> Result<Version, StrataError> {
-        let sequence = self.next_sequence();
-        let timestamp = Timestamp::now().as_micros();
-        let prev_hash = self.last_hash;
-
-        // Create the event
-        let mut event = Event {
-            sequence,
-            event_type: event_type.to_string(),
-            payload,
-            timestamp,
-            prev_hash,
-            hash: [0u8; 32], // Will be computed
+        // Continue the stored log if there is one; its metadata carries the
+        // chain head and per-stream counts the snapshot already has
+        let mut meta = match load_meta(self.ctx, &self.namespace)? {
+            Some(meta) => meta,
+            None => EventLogMeta {
+                next_sequence: self.next_sequence(),
+                head_hash: self.last_hash,
+                hash_version: HASH_VERSION_SHA256,
+                streams: Default::default(),
+            },
         };
+        if self.pending_events.is_empty() {
+            self.base_sequence = meta.next_sequence;
+        }
 
-        // Compute and set the hash
-        event.hash = Self::compute_event_hash(&event);
+        let (event, _) = append_in(self.ctx, &self.namespace, &mut meta, event_type, payload)?;
+        let sequence = event.sequence;
 
         // Update last_hash for next event in chain
         self.last_hash = event.hash;
 
-        // Write event to context as Value::String (matching EventLog primitive format)
-        let event_key = self.event_key(sequence);
-        let event_json = serde_json::to_string(&event).map_err(|e| StrataError::Serialization {
-            message: e.to_string(),
-        })?;
-        self.ctx.put(event_key, Value::String(event_json))?;
-
-        // Write EventLogMeta so EventLog::len() and other readers see the update after commit
-        let meta_key = Key::new_event_meta(self.namespace.clone());
-        let meta = EventLogMeta {
-            next_sequence: sequence + 1,
-            head_hash: event.hash,
-            hash_version: HASH_VERSION_SHA256,
-            streams: Default::default(),
-        };
-        let meta_json = serde_json::to_string(&meta).map_err(|e| StrataError::Serialization {
-            message: e.to_string(),
-        })?;
-        self.ctx.put(meta_key, Value::String(meta_json))?;
-
         // Update TransactionContext event state for cross-Transaction continuity
         self.ctx.set_event_state(
             self.base_sequence + self.pending_events.len() as u64 + 1,
@@ -369,6 +384,7 @@ impl<'a> TransactionOps for Transaction<'a> {
     }
 
     fn state_init(&mut self, name: &str, value: Value) -> Result<Version, StrataError> {
+        ensure_not_reserved(name)?;
         let full_key = self.state_key(name);
 
         // Check if state already exists (init should only work for new state)
@@ -399,6 +415,7 @@ impl<'a> TransactionOps for Transaction<'a> {
         expected_version: Version,
         value: Value,
     ) -> Result<Version, StrataError> {
+        ensure_not_reserved(name)?;
         let full_key = self.state_key(name);
 
         // Read current state to get version (Value::String matching StateCell format)
@@ -444,6 +461,7 @@ impl<'a> TransactionOps for Transaction<'a> {
     // =========================================================================
 
     fn json_create(&mut self, doc_id: &str, value: JsonValue) -> Result<Version, StrataError> {
+        ensure_not_reserved(doc_id)?;
         let full_key = self.json_key(doc_id);
 
         // Check if document already exists in this transaction's writes
@@ -462,6 +480,7 @@ impl<'a> TransactionOps for Transaction<'a> {
         }
 
         // Create the document by setting at root path
+        self.check_json_schema(doc_id, JsonPatch::set_at(JsonPath::root(), value.clone()))?;
         self.ctx.json_set(&full_key, &JsonPath::root(), value)?;
 
         Ok(Version::txn(self.ctx.txn_id))
@@ -545,15 +564,18 @@ impl<'a> TransactionOps for Transaction<'a> {
         path: &JsonPath,
         value: JsonValue,
     ) -> Result<Version, StrataError> {
+        ensure_not_reserved(doc_id)?;
         let full_key = self.json_key(doc_id);
 
         // Call ctx.json_set (same pattern as kv_put calling ctx.put)
+        self.check_json_schema(doc_id, JsonPatch::set_at(path.clone(), value.clone()))?;
         self.ctx.json_set(&full_key, path, value)?;
 
         Ok(Version::txn(self.ctx.txn_id))
     }
 
     fn json_delete(&mut self, doc_id: &str) -> Result<bool, StrataError> {
+        ensure_not_reserved(doc_id)?;
         let full_key = self.json_key(doc_id);
 
         // Check if document exists (for return value, same pattern as kv_delete)
@@ -590,6 +612,7 @@ impl<'a> TransactionOps for Transaction<'a> {
     }
 
     fn json_destroy(&mut self, doc_id: &str) -> Result<bool, StrataError> {
+        ensure_not_reserved(doc_id)?;
         // json_destroy is the same as json_delete
         // (destroy entire document)
         self.json_delete(doc_id)
@@ -9,8 +9,10 @@
 //!
 //! The pool uses thread-local storage to avoid synchronization overhead:
 //! - Each thread has its own pool of up to 8 TransactionContext objects
+//!   by default (configurable per thread)
 //! - Contexts are reset (not reallocated) when reused
-//! - HashMap/HashSet capacity is preserved across reuse
+//! - HashMap/HashSet capacity is preserved across reuse, up to a
+//!   shrink threshold
 //!
 //! # TransactionOps
 //!
@@ -21,4 +23,7 @@ pub mod context;
 pub mod pool;
 
 pub use context::Transaction;
-pub use pool::{TransactionPool, MAX_POOL_SIZE};
+pub use pool::{
+    TransactionPool, TransactionPoolConfig, TransactionPoolStats, DEFAULT_SHRINK_THRESHOLD,
+    MAX_POOL_SIZE,
+};
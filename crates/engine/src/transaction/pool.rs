@@ -1,7 +1,8 @@
 //! Thread-local transaction pool for performance
 //!
 //! Eliminates allocation overhead by reusing TransactionContext objects.
-//! Each thread maintains its own pool of up to MAX_POOL_SIZE contexts.
+//! Each thread maintains its own pool of up to `max_size` contexts
+//! (MAX_POOL_SIZE by default, see [`TransactionPoolConfig`]).
 //!
 //! # Key Optimization
 //!
@@ -12,21 +13,93 @@
 //! - Vec buffer allocation
 //!
 //! After warmup, the hot path has zero allocations.
+//!
+//! # Memory Bound
+//!
+//! Kept capacity is also kept memory: one transaction touching a million
+//! keys would leave a million-bucket read set parked in the pool forever.
+//! Contexts whose collections grew past `shrink_threshold` are cleared and
+//! shrunk to half of it when released. Hash tables round their capacity up,
+//! so the halving keeps a shrunk context under the threshold instead of
+//! shrinking it again on every release.
+//!
+//! Each context also keeps the scratch arena and WAL encode buffer of its
+//! largest commit. A context whose arena holds more than
+//! `arena_shrink_bytes` is shrunk the same way, so one transaction with a
+//! huge value doesn't pin a huge buffer.
+//!
+//! # Statistics
+//!
+//! Hit, miss, discard and shrink counts are summed over all threads and
+//! reported by [`TransactionPool::stats`].
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
 use strata_concurrency::TransactionContext;
 use strata_core::traits::SnapshotView;
 use strata_core::types::BranchId;
 
-/// Maximum contexts per thread
+/// Default maximum contexts per thread
 ///
 /// 8 is sufficient for typical concurrent workloads where each thread
 /// only needs 1-2 active transactions at a time.
 pub const MAX_POOL_SIZE: usize = 8;
 
+/// Default collection capacity above which a released context is shrunk
+pub const DEFAULT_SHRINK_THRESHOLD: usize = 4096;
+
+/// Default commit arena size above which a released context is shrunk
+pub const DEFAULT_ARENA_SHRINK_BYTES: usize = 1 << 20;
+
+/// Limits for the calling thread's transaction pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionPoolConfig {
+    /// Maximum contexts kept for reuse (0 disables pooling).
+    pub max_size: usize,
+    /// Read/write/delete/CAS set capacity above which a released context
+    /// is cleared and shrunk to half this size.
+    pub shrink_threshold: usize,
+    /// Commit arena bytes above which a released context is shrunk.
+    pub arena_shrink_bytes: usize,
+}
+
+impl Default for TransactionPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: MAX_POOL_SIZE,
+            shrink_threshold: DEFAULT_SHRINK_THRESHOLD,
+            arena_shrink_bytes: DEFAULT_ARENA_SHRINK_BYTES,
+        }
+    }
+}
+
+/// Pool activity summed over all threads since process start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionPoolStats {
+    /// Acquires served by a pooled context.
+    pub hits: u64,
+    /// Acquires that allocated a new context.
+    pub misses: u64,
+    /// Releases that kept the context for reuse.
+    pub returned: u64,
+    /// Releases that dropped the context because the pool was full.
+    pub discarded: u64,
+    /// Released contexts shrunk for exceeding `shrink_threshold`.
+    pub shrunk: u64,
+}
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static RETURNED: AtomicU64 = AtomicU64::new(0);
+static DISCARDED: AtomicU64 = AtomicU64::new(0);
+static SHRUNK: AtomicU64 = AtomicU64::new(0);
+
 thread_local! {
     /// Thread-local pool of reusable contexts
     static TXN_POOL: RefCell<Vec<TransactionContext>> = RefCell::new(Vec::with_capacity(MAX_POOL_SIZE));
+
+    /// Limits for this thread's pool
+    static POOL_CONFIG: Cell<TransactionPoolConfig> = Cell::new(TransactionPoolConfig::default());
 }
 
 /// Transaction pool operations
@@ -70,11 +143,13 @@ impl TransactionPool {
             match pool.borrow_mut().pop() {
                 Some(mut ctx) => {
                     // Reuse existing allocation - key optimization!
+                    HITS.fetch_add(1, Ordering::Relaxed);
                     ctx.reset(txn_id, branch_id, snapshot);
                     ctx
                 }
                 None => {
                     // Pool empty - allocate new
+                    MISSES.fetch_add(1, Ordering::Relaxed);
                     match snapshot {
                         Some(snap) => TransactionContext::with_snapshot(txn_id, branch_id, snap),
                         None => TransactionContext::new(txn_id, branch_id, 0),
@@ -88,20 +163,58 @@ impl TransactionPool {
     ///
     /// Context is returned if pool has room, dropped otherwise.
     /// Contexts that have grown large internal collections are
-    /// valuable to keep as they avoid future allocations.
+    /// valuable to keep as they avoid future allocations, up to
+    /// `shrink_threshold`; beyond that they are shrunk first.
     ///
     /// # Arguments
     /// * `ctx` - Transaction context to return to pool
-    pub fn release(ctx: TransactionContext) {
+    pub fn release(mut ctx: TransactionContext) {
+        let config = Self::config();
         TXN_POOL.with(|pool| {
             let mut pool = pool.borrow_mut();
-            if pool.len() < MAX_POOL_SIZE {
-                pool.push(ctx);
+            if pool.len() >= config.max_size {
+                // Pool full - drop
+                DISCARDED.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            let (r, w, d, c) = ctx.capacity();
+            if r.max(w).max(d).max(c) > config.shrink_threshold
+                || ctx.arena_bytes() > config.arena_shrink_bytes
+            {
+                ctx.shrink_to(config.shrink_threshold / 2);
+                SHRUNK.fetch_add(1, Ordering::Relaxed);
             }
-            // else: drop (pool full)
+            RETURNED.fetch_add(1, Ordering::Relaxed);
+            pool.push(ctx);
         });
     }
 
+    /// Set the calling thread's pool limits
+    ///
+    /// Pools are per thread, so each thread that runs transactions applies
+    /// its own limits. Contexts beyond a lowered `max_size` are dropped
+    /// at once.
+    pub fn configure(config: TransactionPoolConfig) {
+        POOL_CONFIG.with(|c| c.set(config));
+        TXN_POOL.with(|pool| pool.borrow_mut().truncate(config.max_size));
+    }
+
+    /// Get the calling thread's pool limits
+    pub fn config() -> TransactionPoolConfig {
+        POOL_CONFIG.with(|c| c.get())
+    }
+
+    /// Get pool activity summed over all threads
+    pub fn stats() -> TransactionPoolStats {
+        TransactionPoolStats {
+            hits: HITS.load(Ordering::Relaxed),
+            misses: MISSES.load(Ordering::Relaxed),
+            returned: RETURNED.load(Ordering::Relaxed),
+            discarded: DISCARDED.load(Ordering::Relaxed),
+            shrunk: SHRUNK.load(Ordering::Relaxed),
+        }
+    }
+
     /// Get current pool size (for debugging/testing)
     ///
     /// Returns the number of contexts currently in the pool.
@@ -123,9 +236,9 @@ impl TransactionPool {
     /// This eliminates allocation overhead during the hot path.
     ///
     /// # Arguments
-    /// * `count` - Number of contexts to pre-allocate (capped at MAX_POOL_SIZE)
+    /// * `count` - Number of contexts to pre-allocate (capped at `max_size`)
     pub fn warmup(count: usize) {
-        let count = count.min(MAX_POOL_SIZE);
+        let count = count.min(Self::config().max_size);
         TXN_POOL.with(|pool| {
             let mut pool = pool.borrow_mut();
             let current = pool.len();
@@ -351,6 +464,86 @@ mod tests {
         assert_eq!(TransactionPool::pool_size(), 1);
     }
 
+    #[test]
+    fn test_configure_max_size() {
+        TransactionPool::clear();
+        let branch_id = BranchId::new();
+
+        TransactionPool::configure(TransactionPoolConfig {
+            max_size: 2,
+            ..TransactionPoolConfig::default()
+        });
+        let contexts: Vec<_> = (0..4)
+            .map(|i| TransactionPool::acquire(i, branch_id, None))
+            .collect();
+        for ctx in contexts {
+            TransactionPool::release(ctx);
+        }
+        assert_eq!(TransactionPool::pool_size(), 2);
+
+        // Lowering the limit drops surplus contexts at once
+        TransactionPool::configure(TransactionPoolConfig {
+            max_size: 1,
+            ..TransactionPoolConfig::default()
+        });
+        assert_eq!(TransactionPool::pool_size(), 1);
+
+        TransactionPool::configure(TransactionPoolConfig::default());
+    }
+
+    #[test]
+    fn test_release_shrinks_oversized_context() {
+        TransactionPool::clear();
+        let ns = create_test_namespace();
+        TransactionPool::configure(TransactionPoolConfig {
+            shrink_threshold: 16,
+            ..TransactionPoolConfig::default()
+        });
+        let shrunk_before = TransactionPool::stats().shrunk;
+
+        let mut ctx = TransactionPool::acquire(1, ns.branch_id, None);
+        for i in 0..1000 {
+            let key = create_test_key(&ns, format!("key{}", i).as_bytes());
+            ctx.read_set.insert(key.clone(), i as u64);
+            ctx.write_set.insert(key, Value::Int(i));
+        }
+        TransactionPool::release(ctx);
+
+        let (r, w, _d, _c) = TransactionPool::total_capacity();
+        assert!(r < 1000 && w < 1000, "oversized sets should be shrunk");
+        assert!(TransactionPool::stats().shrunk > shrunk_before);
+
+        // Small transactions keep their capacity
+        let mut ctx = TransactionPool::acquire(2, ns.branch_id, None);
+        for i in 0..8 {
+            let key = create_test_key(&ns, format!("key{}", i).as_bytes());
+            ctx.read_set.insert(key, i as u64);
+        }
+        let cap = ctx.capacity();
+        TransactionPool::release(ctx);
+        assert_eq!(TransactionPool::total_capacity(), cap);
+
+        TransactionPool::configure(TransactionPoolConfig::default());
+    }
+
+    #[test]
+    fn test_stats_count_hits_and_misses() {
+        TransactionPool::clear();
+        let branch_id = BranchId::new();
+        let before = TransactionPool::stats();
+
+        let ctx = TransactionPool::acquire(1, branch_id, None); // miss
+        TransactionPool::release(ctx);
+        let ctx = TransactionPool::acquire(2, branch_id, None); // hit
+        TransactionPool::release(ctx);
+
+        // Counters are process-wide, so other tests may add to them
+        let after = TransactionPool::stats();
+        assert!(after.misses > before.misses);
+        assert!(after.hits > before.hits);
+        assert!(after.returned >= before.returned + 2);
+    }
+
     #[test]
     fn test_total_capacity() {
         TransactionPool::clear();
@@ -8,7 +8,7 @@ use std::sync::{Arc, Barrier};
 use std::thread;
 use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
-use strata_engine::Database;
+use strata_engine::{Database, LockMode};
 use tempfile::TempDir;
 
 fn create_ns(branch_id: BranchId) -> Namespace {
@@ -286,3 +286,72 @@ fn test_concurrent_disjoint_transactions() {
         "All disjoint transactions should succeed"
     );
 }
+
+// ============================================================================
+// Pessimistic Lock Tests
+// ============================================================================
+
+/// Test: Locking a key reads its latest value, so a write committed after
+/// the transaction began does not make the read-modify-write conflict
+#[test]
+fn test_locked_key_reads_latest_committed_value() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+    let branch_id = BranchId::new();
+    let key = Key::new_kv(create_ns(branch_id), "counter");
+
+    let mut txn = db.begin_transaction(branch_id);
+    db.transaction(branch_id, |other| other.put(key.clone(), Value::Int(10)))
+        .unwrap();
+
+    txn.lock(&key).unwrap();
+    assert!(txn.holds_lock(&key));
+    assert_eq!(txn.get(&key).unwrap(), Some(Value::Int(10)));
+    txn.put(key.clone(), Value::Int(11)).unwrap();
+    db.commit_transaction(&mut txn).unwrap();
+    db.end_transaction(txn);
+
+    let val = db.transaction(branch_id, |txn| txn.get(&key)).unwrap();
+    assert_eq!(val, Some(Value::Int(11)));
+}
+
+/// Test: Pessimistic transactions on one hot key wait for each other
+/// instead of conflicting
+#[test]
+fn test_pessimistic_mode_hot_key_no_conflicts() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+
+    let branch_id = BranchId::new();
+    let key = Key::new_kv(create_ns(branch_id), "hot");
+    let num_threads = 8;
+    let ops_per_thread = 25;
+
+    let handles: Vec<_> = (0..num_threads)
+        .map(|_| {
+            let db = Arc::clone(&db);
+            let key = key.clone();
+            thread::spawn(move || {
+                for _ in 0..ops_per_thread {
+                    db.transaction(branch_id, |txn| {
+                        txn.set_lock_mode(LockMode::Pessimistic);
+                        let n = match txn.get(&key)? {
+                            Some(Value::Int(n)) => n,
+                            _ => 0,
+                        };
+                        txn.put(key.clone(), Value::Int(n + 1))
+                    })
+                    .unwrap();
+                }
+            })
+        })
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+
+    let val = db.transaction(branch_id, |txn| txn.get(&key)).unwrap();
+    assert_eq!(val, Some(Value::Int((num_threads * ops_per_thread) as i64)));
+    assert_eq!(db.transaction_metrics().total_conflicts, 0);
+}
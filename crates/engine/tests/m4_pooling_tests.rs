@@ -10,7 +10,9 @@
 
 use strata_core::types::{BranchId, Key, Namespace, TypeTag};
 use strata_core::value::Value;
-use strata_engine::{Database, TransactionPool, MAX_POOL_SIZE};
+use strata_engine::{
+    Database, TransactionPool, TransactionPoolConfig, DEFAULT_SHRINK_THRESHOLD, MAX_POOL_SIZE,
+};
 use tempfile::TempDir;
 
 fn create_ns(branch_id: BranchId) -> Namespace {
@@ -275,3 +277,147 @@ fn test_pool_caps_at_max_size() {
         "Pool should cap at MAX_POOL_SIZE"
     );
 }
+
+#[test]
+fn test_configured_pool_size_is_respected() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+    let branch_id = BranchId::new();
+    let ns = create_ns(branch_id);
+
+    TransactionPool::configure(TransactionPoolConfig {
+        max_size: 2,
+        ..TransactionPoolConfig::default()
+    });
+
+    // Hold several transactions open at once, then end them all
+    let contexts: Vec<_> = (0..5).map(|_| db.begin_transaction(branch_id)).collect();
+    for mut txn in contexts {
+        txn.put(create_key(&ns, "k"), Value::Int(1)).unwrap();
+        db.end_transaction(txn);
+    }
+    assert_eq!(TransactionPool::pool_size(), 2);
+
+    TransactionPool::configure(TransactionPoolConfig::default());
+}
+
+// ============================================================================
+// Pool Memory and Statistics Tests
+// ============================================================================
+
+#[test]
+fn test_huge_transaction_does_not_bloat_pool() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+    let branch_id = BranchId::new();
+    let ns = create_ns(branch_id);
+    TransactionPool::warmup(0);
+
+    let shrunk_before = TransactionPool::stats().shrunk;
+    db.transaction(branch_id, |txn| {
+        for i in 0..(DEFAULT_SHRINK_THRESHOLD * 2) {
+            txn.put(
+                create_key(&ns, &format!("bulk_{}", i)),
+                Value::Int(i as i64),
+            )?;
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    assert!(TransactionPool::stats().shrunk > shrunk_before);
+    let mut txn = db.begin_transaction(branch_id);
+    let (_, write_cap, _, _) = txn.capacity();
+    assert!(
+        write_cap <= DEFAULT_SHRINK_THRESHOLD,
+        "pooled write set should be shrunk, capacity {}",
+        write_cap
+    );
+    txn.put(create_key(&ns, "small"), Value::Int(0)).unwrap();
+    db.end_transaction(txn);
+}
+
+#[test]
+fn test_pool_stats_track_hits_and_misses() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+    let branch_id = BranchId::new();
+    let ns = create_ns(branch_id);
+    let before = TransactionPool::stats();
+
+    for i in 0..10 {
+        db.transaction(branch_id, |txn| {
+            txn.put(create_key(&ns, &format!("key_{}", i)), Value::Int(i))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    // Counters are process-wide; other tests may run concurrently
+    let after = TransactionPool::stats();
+    assert!(after.hits + after.misses >= before.hits + before.misses + 10);
+    assert!(
+        after.hits >= before.hits + 9,
+        "steady state should reuse contexts"
+    );
+}
+
+#[test]
+fn test_commit_arena_is_reused() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+    let branch_id = BranchId::new();
+    let ns = create_ns(branch_id);
+
+    db.transaction(branch_id, |txn| {
+        txn.put(create_key(&ns, "key_w"), Value::Int(0))?;
+        Ok(())
+    })
+    .unwrap();
+
+    // The pooled context keeps the WAL encode buffer of its last commit
+    let txn = db.begin_transaction(branch_id);
+    let retained = txn.arena_bytes();
+    assert!(retained > 0, "commit buffer should be retained");
+    db.end_transaction(txn);
+
+    for i in 0..10 {
+        db.transaction(branch_id, |txn| {
+            txn.put(create_key(&ns, &format!("key_{}", i)), Value::Int(i))?;
+            Ok(())
+        })
+        .unwrap();
+    }
+    let txn = db.begin_transaction(branch_id);
+    assert_eq!(txn.arena_bytes(), retained, "same-sized commits reuse it");
+    db.end_transaction(txn);
+}
+
+#[test]
+fn test_oversized_commit_arena_is_released() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open(temp_dir.path().join("db")).unwrap();
+    let branch_id = BranchId::new();
+    let ns = create_ns(branch_id);
+
+    TransactionPool::configure(TransactionPoolConfig {
+        arena_shrink_bytes: 1024,
+        ..TransactionPoolConfig::default()
+    });
+
+    // One large value leaves a large encode buffer behind
+    db.transaction(branch_id, |txn| {
+        txn.put(create_key(&ns, "big"), Value::Bytes(vec![7; 64 * 1024]))?;
+        Ok(())
+    })
+    .unwrap();
+    let txn = db.begin_transaction(branch_id);
+    assert!(
+        txn.arena_bytes() <= 1024,
+        "oversized arena should be released, holds {}",
+        txn.arena_bytes()
+    );
+    db.end_transaction(txn);
+
+    TransactionPool::configure(TransactionPoolConfig::default());
+}
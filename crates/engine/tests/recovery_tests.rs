@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use strata_core::contract::Version;
-use strata_core::types::BranchId;
+use strata_core::types::{BranchId, Key, Namespace};
 use strata_core::value::Value;
 use strata_engine::Database;
 use strata_engine::{BranchIndex, EventLog, KVStore, StateCell};
@@ -547,6 +547,42 @@ fn test_cross_primitive_transaction_survives_recovery() {
     assert_eq!(state, Value::Int(42));
 }
 
+/// Test a transaction writing to two branches survives recovery in both
+#[test]
+fn test_multi_branch_transaction_survives_recovery() {
+    let (db, temp_dir, branch_id) = setup();
+    let path = get_path(&temp_dir);
+    let other_branch = BranchId::new();
+
+    let counter = Key::new_kv(Namespace::for_branch(branch_id), "counter");
+    let sample = Key::new_kv(Namespace::for_branch(other_branch), "sample");
+    db.transaction(branch_id, |txn| {
+        txn.put(counter.clone(), Value::Int(1))?;
+        txn.put(sample.clone(), Value::Int(7))
+    })
+    .unwrap();
+
+    // Simulate crash
+    drop(db);
+
+    // Recovery
+    let db = Database::open(&path).unwrap();
+    let kv = KVStore::new(db.clone());
+
+    // Both writes survived under the same commit version
+    let counter = kv
+        .get_versioned(&branch_id, "default", "counter")
+        .unwrap()
+        .unwrap();
+    let sample = kv
+        .get_versioned(&other_branch, "default", "sample")
+        .unwrap()
+        .unwrap();
+    assert_eq!(counter.value, Value::Int(1));
+    assert_eq!(sample.value, Value::Int(7));
+    assert_eq!(counter.version, sample.version);
+}
+
 /// Test multiple sequential recoveries
 #[test]
 fn test_multiple_recovery_cycles() {
@@ -18,20 +18,44 @@
 //! // Create a new branch
 //! db.branches().create("experiment-1")?;
 //!
-//! // Fork a branch (copies all data)
+//! // Fork a branch (copy-on-write)
 //! db.branches().fork("main", "experiment-2")?;
 //!
+//! // Walk the experiment tree
+//! let parents = db.branches().ancestry("experiment-2")?;
+//! let forks = db.branches().children("main")?;
+//!
 //! // Diff two branches
 //! let diff = db.branches().diff("main", "experiment-2")?;
 //!
 //! // Merge branches
 //! use strata_engine::MergeStrategy;
 //! db.branches().merge("experiment-2", "main", MergeStrategy::LastWriterWins)?;
+//!
+//! // Label runs and find them again
+//! db.branches().add_tag("experiment-2", "eval")?;
+//! db.branches().set_metadata("experiment-2", "model", "small")?;
+//! let evals = db.branches().query(BranchFilter::new().with_tag("eval").with_metadata("model", "small"))?;
+//!
+//! // End a run, exporting it first
+//! db.branches().on_close(|info| export_run(&info.id))?;
+//! db.branches().close("experiment-2")?;
+//!
+//! // See how a branch's collections came to be
+//! let changes = db.branches().schema_log("main")?;
+//!
+//! // Keep only the latest KV and state versions, but all vector history
+//! let retention = BranchRetention::new(RetentionPolicy::MaxLen(1))
+//!     .with(PrimitiveType::Vector, RetentionPolicy::KeepAll);
+//! db.branches().set_retention("main", retention)?;
 //! ```
 
-use crate::types::BranchId;
-use crate::{Command, Error, Executor, Output, Result};
-use strata_engine::branch_ops::{BranchDiffResult, ForkInfo, MergeInfo, MergeStrategy};
+use crate::types::{BranchFilter, BranchId, BranchInfo, BranchRetention, VersionedBranchInfo};
+use crate::{Command, Error, Executor, Output, Result, Value};
+use strata_engine::branch_ops::{
+    BranchDiffResult, ForkInfo, MergeInfo, MergeStrategy, SchemaChange,
+};
+use strata_engine::{BranchAncestor, BranchIndex, BranchMetadata};
 
 /// Handle for branch management operations.
 ///
@@ -156,6 +180,43 @@ impl<'a> Branches<'a> {
         })
     }
 
+    /// List the ancestors of a branch, from its parent up to the root.
+    ///
+    /// Each entry names an ancestor and the version of it the branch below
+    /// was forked at. The walk stops at an ancestor that was deleted.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for ancestor in db.branches().ancestry("experiment")? {
+    ///     println!("{} @ {}", ancestor.name, ancestor.fork_version);
+    /// }
+    /// ```
+    pub fn ancestry(&self, name: &str) -> Result<Vec<BranchAncestor>> {
+        let db = &self.executor.primitives().db;
+        BranchIndex::new(db.clone())
+            .ancestry(name)
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })
+    }
+
+    /// List the branches forked from a branch, in lexicographic order.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let forks = db.branches().children("main")?;
+    /// ```
+    pub fn children(&self, name: &str) -> Result<Vec<String>> {
+        let db = &self.executor.primitives().db;
+        BranchIndex::new(db.clone())
+            .children(name)
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })
+    }
+
     /// Compare two branches and return their differences.
     ///
     /// Returns a structured diff showing per-space added, removed, and
@@ -187,6 +248,10 @@ impl<'a> Branches<'a> {
     ///   - `Strict`: merge fails if any conflicts exist
     /// - Removed entries (in target but not source) are left unchanged
     ///
+    /// Branches with a common ancestor (see [`ancestry`](Self::ancestry))
+    /// merge three-way against it: an entry only one side changed is not
+    /// a conflict, and entries source deleted are deleted from target.
+    ///
     /// # Example
     ///
     /// ```text
@@ -204,4 +269,242 @@ impl<'a> Branches<'a> {
             }
         })
     }
+
+    /// List the schema-affecting operations made on a branch, oldest first.
+    ///
+    /// Records vector collection creations, drops and index rebuilds
+    /// across every space, with the configuration each one set. A fork
+    /// starts with its parent's log.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for change in db.branches().schema_log("main")? {
+    ///     println!("{} {}/{}: {:?}", change.timestamp, change.space, change.collection, change.kind);
+    /// }
+    /// ```
+    pub fn schema_log(&self, branch: &str) -> Result<Vec<SchemaChange>> {
+        let db = &self.executor.primitives().db;
+        strata_engine::branch_ops::schema_log(db, branch).map_err(|e| Error::Internal {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Set how much version history compaction keeps on a branch.
+    ///
+    /// Accepts a single `RetentionPolicy` for every primitive, or a
+    /// [`BranchRetention`] with per-primitive overrides. The policy is stored
+    /// in the branch's metadata, so it survives reopening, and old versions
+    /// are pruned the next time the database is compacted.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.branches().set_retention("main", RetentionPolicy::MaxLen(10))?;
+    /// db.compact()?;
+    /// ```
+    pub fn set_retention(&self, name: &str, retention: impl Into<BranchRetention>) -> Result<()> {
+        match self.executor.execute(Command::BranchSetRetention {
+            branch: BranchId::from(name),
+            retention: retention.into(),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchSetRetention".into(),
+            }),
+        }
+    }
+
+    /// Tag a branch.
+    ///
+    /// Returns `false` if the branch already had the tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the branch doesn't exist or the tag is empty.
+    pub fn add_tag(&self, name: &str, tag: &str) -> Result<bool> {
+        match self.executor.execute(Command::BranchAddTag {
+            branch: BranchId::from(name),
+            tag: tag.to_string(),
+        })? {
+            Output::Bool(added) => Ok(added),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchAddTag".into(),
+            }),
+        }
+    }
+
+    /// Remove a tag from a branch.
+    ///
+    /// Returns `false` if the branch didn't have the tag.
+    pub fn remove_tag(&self, name: &str, tag: &str) -> Result<bool> {
+        match self.executor.execute(Command::BranchRemoveTag {
+            branch: BranchId::from(name),
+            tag: tag.to_string(),
+        })? {
+            Output::Bool(removed) => Ok(removed),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchRemoveTag".into(),
+            }),
+        }
+    }
+
+    /// Set a metadata entry of a branch. Setting `Value::Null` removes it.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.branches().set_metadata("run-42", "model", "small")?;
+    /// db.branches().set_metadata("run-42", "seed", 7i64)?;
+    /// ```
+    pub fn set_metadata(&self, name: &str, key: &str, value: impl Into<Value>) -> Result<()> {
+        match self.executor.execute(Command::BranchSetMetadata {
+            branch: BranchId::from(name),
+            key: key.to_string(),
+            value: value.into(),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchSetMetadata".into(),
+            }),
+        }
+    }
+
+    /// List the branches carrying `tag`, in name order.
+    pub fn find_by_tag(&self, tag: &str) -> Result<Vec<VersionedBranchInfo>> {
+        self.query(BranchFilter::new().with_tag(tag))
+    }
+
+    /// List the branches matching `filter`, in name order.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let recent = db.branches().query(
+    ///     BranchFilter::new()
+    ///         .created_after(cutoff)
+    ///         .with_metadata("model", "small"),
+    /// )?;
+    /// ```
+    pub fn query(&self, filter: BranchFilter) -> Result<Vec<VersionedBranchInfo>> {
+        match self.executor.execute(Command::BranchQuery {
+            filter,
+            limit: None,
+            offset: None,
+        })? {
+            Output::BranchInfoList(branches) => Ok(branches),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchQuery".into(),
+            }),
+        }
+    }
+
+    /// Count the branches.
+    pub fn count(&self) -> Result<u64> {
+        match self.executor.execute(Command::BranchCount)? {
+            Output::Uint(count) => Ok(count),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchCount".into(),
+            }),
+        }
+    }
+
+    /// Close a branch, marking it `Completed`.
+    ///
+    /// The branch keeps its data. Hooks registered with [`on_close`](Self::on_close)
+    /// run after the status change has committed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for the default branch, a missing branch, or one
+    /// that has already been closed or failed.
+    pub fn close(&self, name: &str) -> Result<()> {
+        match self.executor.execute(Command::BranchClose {
+            branch: BranchId::from(name),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchClose".into(),
+            }),
+        }
+    }
+
+    /// Fail a branch, marking it `Failed` with `error`.
+    ///
+    /// Like [`close`](Self::close), but runs the [`on_fail`](Self::on_fail)
+    /// hooks.
+    pub fn fail(&self, name: &str, error: &str) -> Result<()> {
+        match self.executor.execute(Command::BranchFail {
+            branch: BranchId::from(name),
+            error: error.to_string(),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchFail".into(),
+            }),
+        }
+    }
+
+    /// Run `hook` after every branch is closed.
+    ///
+    /// Use it to export a finished run, prune its vectors, or announce it.
+    /// Hooks run on the closing thread, after the close has committed, and
+    /// are best-effort: an error is logged and does not fail the close.
+    /// They stay registered for as long as the database is open.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.branches().on_close(|info| {
+    ///     println!("{} finished", info.id);
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn on_close<F>(&self, hook: F) -> Result<()>
+    where
+        F: Fn(&BranchInfo) -> Result<()> + Send + Sync + 'static,
+    {
+        let index = BranchIndex::new(self.executor.primitives().db.clone());
+        index
+            .on_close(move |meta| run_hook(&hook, meta))
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Run `hook` after every branch fails; `BranchInfo::error` says why.
+    ///
+    /// Runs like the [`on_close`](Self::on_close) hooks.
+    pub fn on_fail<F>(&self, hook: F) -> Result<()>
+    where
+        F: Fn(&BranchInfo) -> Result<()> + Send + Sync + 'static,
+    {
+        let index = BranchIndex::new(self.executor.primitives().db.clone());
+        index
+            .on_fail(move |meta| run_hook(&hook, meta))
+            .map_err(|e| Error::Internal {
+                reason: e.to_string(),
+            })
+    }
+
+    /// Get the history retention of a branch (`KeepAll` when none is set).
+    pub fn retention(&self, name: &str) -> Result<BranchRetention> {
+        match self.executor.execute(Command::BranchGetRetention {
+            branch: BranchId::from(name),
+        })? {
+            Output::BranchRetention(retention) => Ok(retention),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchGetRetention".into(),
+            }),
+        }
+    }
+}
+
+/// Call an executor-level lifecycle hook with the branch's info.
+fn run_hook<F>(hook: &F, meta: &BranchMetadata) -> strata_core::StrataResult<()>
+where
+    F: Fn(&BranchInfo) -> Result<()>,
+{
+    hook(&crate::handlers::branch::metadata_to_branch_info(meta))
+        .map_err(|e| strata_core::StrataError::internal(e.to_string()))
 }
@@ -0,0 +1,91 @@
+//! Custom primitive API.
+//!
+//! Access via `db.custom(id)` to read and write the entries of a primitive
+//! registered with [`register_custom_primitive`](crate::register_custom_primitive).
+//! Entries live in the primitive's own key range, commit through the same
+//! WAL as every other primitive and follow branch forks and deletes.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::{register_custom_primitive, CustomPrimitive, Strata, Value};
+//!
+//! struct Counters;
+//!
+//! impl CustomPrimitive for Counters {
+//!     fn id(&self) -> u8 { 0x80 }
+//!     fn name(&self) -> &'static str { "counters" }
+//! }
+//!
+//! register_custom_primitive(Arc::new(Counters))?;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//! db.custom(0x80).put(b"hits", Value::Int(1))?;
+//! assert_eq!(db.custom(0x80).get(b"hits")?, Some(Value::Int(1)));
+//! ```
+
+use strata_engine::CustomStore;
+use strata_security::AccessMode;
+
+use super::Strata;
+use crate::convert::convert_result;
+use crate::{bridge, Error, Result, Value};
+
+/// Handle for a custom primitive's entries.
+///
+/// Obtained via [`Strata::custom()`]. Operates on the branch and space that
+/// were current when the handle was created.
+pub struct Custom<'a> {
+    strata: &'a Strata,
+    store: CustomStore,
+}
+
+impl<'a> Custom<'a> {
+    pub(crate) fn new(strata: &'a Strata, id: u8) -> Self {
+        let db = strata.executor.primitives().db.clone();
+        Self {
+            strata,
+            store: CustomStore::new(db, id),
+        }
+    }
+
+    fn ensure_writable(&self, command: &str) -> Result<()> {
+        if self.strata.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: command.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the latest value of an entry.
+    pub fn get(&self, key: &[u8]) -> Result<Option<Value>> {
+        let branch_id = bridge::to_core_branch_id(&self.strata.current_branch)?;
+        let space = &self.strata.current_space;
+        convert_result(self.store.get(&branch_id, space, key))
+    }
+
+    /// Store a value, returning the commit version.
+    pub fn put(&self, key: &[u8], value: Value) -> Result<u64> {
+        self.ensure_writable("CustomPut")?;
+        let branch_id = bridge::to_core_branch_id(&self.strata.current_branch)?;
+        let space = &self.strata.current_space;
+        let version = convert_result(self.store.put(&branch_id, space, key, value))?;
+        Ok(bridge::extract_version(&version))
+    }
+
+    /// Delete an entry. Returns `true` if it existed.
+    pub fn delete(&self, key: &[u8]) -> Result<bool> {
+        self.ensure_writable("CustomDelete")?;
+        let branch_id = bridge::to_core_branch_id(&self.strata.current_branch)?;
+        let space = &self.strata.current_space;
+        convert_result(self.store.delete(&branch_id, space, key))
+    }
+
+    /// List entry keys starting with `prefix`.
+    pub fn list(&self, prefix: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let branch_id = bridge::to_core_branch_id(&self.strata.current_branch)?;
+        let space = &self.strata.current_space;
+        convert_result(self.store.list(&branch_id, space, prefix))
+    }
+}
@@ -1,4 +1,4 @@
-//! Database operations: ping, info, flush, compact.
+//! Database operations: ping, info, flush, compact, stats.
 
 use super::Strata;
 use crate::types::*;
@@ -6,7 +6,7 @@ use crate::{Command, Error, Output, Result};
 
 impl Strata {
     // =========================================================================
-    // Database Operations (4)
+    // Database Operations (5)
     // =========================================================================
 
     /// Ping the database.
@@ -39,18 +39,67 @@ impl Strata {
         }
     }
 
-    /// Compact the database.
-    pub fn compact(&self) -> Result<()> {
+    /// Compact the WAL down to live data and report what was reclaimed.
+    pub fn compact(&self) -> Result<CompactResult> {
         match self.executor.execute(Command::Compact)? {
-            Output::Unit => Ok(()),
+            Output::Compacted(result) => Ok(result),
             _ => Err(Error::Internal {
                 reason: "Unexpected output for Compact".into(),
             }),
         }
     }
 
+    /// Get runtime statistics: entry counts per primitive and per branch,
+    /// memory and WAL size, the last snapshot watermark, transaction
+    /// conflicts, and open or leaked result cursors.
+    ///
+    /// Walks all stored data, so poll it rather than calling it per request.
+    pub fn stats(&self) -> Result<DatabaseStats> {
+        match self.executor.execute(Command::Stats)? {
+            Output::Stats(stats) => Ok(*stats),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for Stats".into(),
+            }),
+        }
+    }
+
+    /// Get entry counts and memory usage for one branch.
+    ///
+    /// Cheaper than [`stats`](Self::stats) when only one branch matters.
+    /// Fails with [`Error::BranchNotFound`] if the branch does not exist.
+    pub fn branch_stats(&self, branch: &str) -> Result<BranchStats> {
+        match self.executor.execute(Command::BranchStats {
+            branch: BranchId::from(branch),
+        })? {
+            Output::BranchStats(stats) => Ok(stats),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchStats".into(),
+            }),
+        }
+    }
+
+    /// Get p50/p95/p99 latency per command since open or the last reset.
+    pub fn latency_report(&self) -> Result<LatencyReport> {
+        match self.executor.execute(Command::LatencyReport)? {
+            Output::LatencyReport(report) => Ok(report),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for LatencyReport".into(),
+            }),
+        }
+    }
+
+    /// Clear recorded command latencies.
+    pub fn reset_latency(&self) -> Result<()> {
+        match self.executor.execute(Command::LatencyReset)? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for LatencyReset".into(),
+            }),
+        }
+    }
+
     // =========================================================================
-    // Bundle Operations (3)
+    // Bundle Operations (5)
     // =========================================================================
 
     /// Export a branch to a .branchbundle.tar.zst archive.
@@ -70,6 +119,25 @@ impl Strata {
     pub fn branch_import(&self, path: &str) -> Result<BranchImportResult> {
         match self.executor.execute(Command::BranchImport {
             path: path.to_string(),
+            as_branch: None,
+        })? {
+            Output::BranchImported(result) => Ok(result),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for BranchImport".into(),
+            }),
+        }
+    }
+
+    /// Import a .branchbundle.tar.zst archive into a new branch named
+    /// `branch_name`, whatever the bundled branch was called.
+    ///
+    /// The database does not need to be empty: the bundle lands in its own
+    /// branch next to existing data, ready to be inspected or diffed.
+    /// Fails if `branch_name` already exists.
+    pub fn branch_import_as(&self, path: &str, branch_name: &str) -> Result<BranchImportResult> {
+        match self.executor.execute(Command::BranchImport {
+            path: path.to_string(),
+            as_branch: Some(branch_name.to_string()),
         })? {
             Output::BranchImported(result) => Ok(result),
             _ => Err(Error::Internal {
@@ -89,4 +157,34 @@ impl Strata {
             }),
         }
     }
+
+    /// Export every branch to one .databundle.tar.zst archive.
+    pub fn export_all(&self, path: &str) -> Result<DatabaseExportResult> {
+        match self.executor.execute(Command::DatabaseExport {
+            path: path.to_string(),
+        })? {
+            Output::DatabaseExported(result) => Ok(result),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for DatabaseExport".into(),
+            }),
+        }
+    }
+
+    /// Import every branch from a .databundle.tar.zst archive, resolving
+    /// taken branch names with `on_conflict`.
+    pub fn import_all(
+        &self,
+        path: &str,
+        on_conflict: ImportConflict,
+    ) -> Result<DatabaseImportResult> {
+        match self.executor.execute(Command::DatabaseImport {
+            path: path.to_string(),
+            on_conflict,
+        })? {
+            Output::DatabaseImported(result) => Ok(result),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for DatabaseImport".into(),
+            }),
+        }
+    }
 }
@@ -0,0 +1,254 @@
+//! JSON document power API.
+//!
+//! Access via `db.json()` to read just the parts of a document you need,
+//! to update parts of a document in place with merge
+//! patches, JSON Patch operations and array operations, to constrain the shape of JSON
+//! documents with schemas set on key prefixes, and to check a candidate
+//! document against them without writing it, e.g. before storing output
+//! produced by an LLM.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//!
+//! // Documents under "order:" need an integer id
+//! db.json().set_schema("order:", serde_json::json!({
+//!     "type": "object",
+//!     "required": ["id"],
+//!     "properties": {"id": {"type": "integer"}}
+//! }))?;
+//!
+//! // Every violation, with its path, before anything is written
+//! for v in db.json().validate("order:42", serde_json::json!({"id": "42"}))? {
+//!     println!("{}: {}", v.path, v.message);
+//! }
+//!
+//! // Only the requested fields, one entry per path
+//! let fields = db.json().get_paths("order:42", &["$.status", "$.total"])?;
+//!
+//! // Partial updates, each in one transaction
+//! db.json().merge("order:42", serde_json::json!({"status": "paid", "note": null}))?;
+//! db.json().patch("order:42", serde_json::json!([
+//!     {"op": "test", "path": "/status", "value": "paid"},
+//!     {"op": "add", "path": "/items/-", "value": "gift wrap"}
+//! ]))?;
+//!
+//! // Lists inside documents, without rewriting the whole array
+//! db.json().array_append("chat:1", "$.turns", serde_json::json!({"role": "user"}))?;
+//! let first = db.json().array_remove("chat:1", "$.turns", 0)?;
+//! ```
+
+use super::Strata;
+use crate::types::SchemaViolation;
+use crate::{Command, Error, Output, Result, Value};
+
+/// Handle for JSON document partial read, patch, array and schema
+/// operations.
+///
+/// Obtained via [`Strata::json()`]. Operates on the branch and space that
+/// were current when the handle was created.
+pub struct Documents<'a> {
+    strata: &'a Strata,
+}
+
+impl<'a> Documents<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Get the values at `paths` in the document at `key`.
+    ///
+    /// Returns one entry per path, in order, with `None` where the path
+    /// doesn't exist or for every path if the document doesn't. Only the
+    /// requested subtrees are copied out of the document.
+    pub fn get_paths(&self, key: &str, paths: &[&str]) -> Result<Vec<Option<Value>>> {
+        match self.strata.executor.execute(Command::JsonGetPaths {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        })? {
+            Output::MaybeValues(values) => Ok(values),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonGetPaths".into(),
+            }),
+        }
+    }
+
+    /// Apply an RFC 7396 merge patch to the document at `key`.
+    ///
+    /// Fields in `patch` replace the document's, nested objects merge, and
+    /// `null` fields are removed. A missing document is created from the
+    /// patch. Returns the new document version.
+    pub fn merge(&self, key: &str, patch: impl Into<Value>) -> Result<u64> {
+        match self.strata.executor.execute(Command::JsonMerge {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            patch: patch.into(),
+        })? {
+            Output::Version(version) => Ok(version),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonMerge".into(),
+            }),
+        }
+    }
+
+    /// Apply RFC 6902 JSON Patch operations to the document at `key`.
+    ///
+    /// `ops` is an array of `add`, `remove`, `replace`, `move`, `copy` and
+    /// `test` operations. They apply all or nothing: if any fails, the
+    /// document is unchanged and `Error::InvalidInput` is returned. Returns
+    /// the new document version.
+    pub fn patch(&self, key: &str, ops: impl Into<Value>) -> Result<u64> {
+        match self.strata.executor.execute(Command::JsonPatch {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            ops: ops.into(),
+        })? {
+            Output::Version(version) => Ok(version),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonPatch".into(),
+            }),
+        }
+    }
+
+    /// Append `value` to the array at `path` in the document at `key`.
+    ///
+    /// If nothing exists at `path` yet, the array is created. Returns the
+    /// array length after the append.
+    pub fn array_append(&self, key: &str, path: &str, value: impl Into<Value>) -> Result<u64> {
+        match self.strata.executor.execute(Command::JsonArrayAppend {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            path: path.to_string(),
+            value: value.into(),
+        })? {
+            Output::Uint(len) => Ok(len),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonArrayAppend".into(),
+            }),
+        }
+    }
+
+    /// Insert `value` before `index` in the array at `path`.
+    ///
+    /// An `index` equal to the array length appends; a larger one fails with
+    /// `Error::InvalidInput`. Returns the array length after the insert.
+    pub fn array_insert(
+        &self,
+        key: &str,
+        path: &str,
+        index: u64,
+        value: impl Into<Value>,
+    ) -> Result<u64> {
+        match self.strata.executor.execute(Command::JsonArrayInsert {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            path: path.to_string(),
+            index,
+            value: value.into(),
+        })? {
+            Output::Uint(len) => Ok(len),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonArrayInsert".into(),
+            }),
+        }
+    }
+
+    /// Remove and return the element at `index` of the array at `path`.
+    ///
+    /// Later elements shift down by one. An out-of-bounds `index` fails with
+    /// `Error::InvalidInput`.
+    pub fn array_remove(&self, key: &str, path: &str, index: u64) -> Result<Value> {
+        match self.strata.executor.execute(Command::JsonArrayRemove {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            path: path.to_string(),
+            index,
+        })? {
+            Output::Maybe(Some(value)) => Ok(value),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonArrayRemove".into(),
+            }),
+        }
+    }
+
+    /// Get the length of the array at `path`, or `None` if the document or
+    /// path doesn't exist.
+    pub fn array_len(&self, key: &str, path: &str) -> Result<Option<u64>> {
+        match self.strata.executor.execute(Command::JsonArrayLen {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            path: path.to_string(),
+        })? {
+            Output::Maybe(None) => Ok(None),
+            Output::Maybe(Some(Value::Int(len))) => Ok(Some(len as u64)),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonArrayLen".into(),
+            }),
+        }
+    }
+
+    /// Set the JSON schema that documents with keys starting with `prefix`
+    /// must match.
+    ///
+    /// An empty prefix covers every document in the space; where schemas
+    /// are set on several prefixes of a key, the longest one applies. Writes
+    /// that would leave a document not matching it fail with
+    /// `Error::InvalidInput` naming the first offending field. Documents
+    /// already stored are not rechecked. `Value::Null` removes the schema.
+    pub fn set_schema(&self, prefix: &str, schema: impl Into<Value>) -> Result<()> {
+        match self.strata.executor.execute(Command::JsonSetSchema {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            prefix: prefix.to_string(),
+            schema: schema.into(),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonSetSchema".into(),
+            }),
+        }
+    }
+
+    /// Get the JSON schema set on exactly `prefix`, if any.
+    pub fn schema(&self, prefix: &str) -> Result<Option<Value>> {
+        match self.strata.executor.execute(Command::JsonGetSchema {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            prefix: prefix.to_string(),
+        })? {
+            Output::Maybe(schema) => Ok(schema),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonGetSchema".into(),
+            }),
+        }
+    }
+
+    /// Check `value` against the schema governing `key` without writing it.
+    ///
+    /// Returns every violation with the path of the offending value, or an
+    /// empty list if `value` matches or no schema governs `key`.
+    pub fn validate(&self, key: &str, value: impl Into<Value>) -> Result<Vec<SchemaViolation>> {
+        match self.strata.executor.execute(Command::JsonValidate {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            key: key.to_string(),
+            value: value.into(),
+        })? {
+            Output::SchemaViolations(violations) => Ok(violations),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for JsonValidate".into(),
+            }),
+        }
+    }
+}
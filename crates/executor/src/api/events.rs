@@ -0,0 +1,267 @@
+//! Event stream power API.
+//!
+//! Access via `db.events()` to page through an event stream, to bound its
+//! size, and to share it between consumers with consumer groups. A stream is
+//! the set of events appended with the same event type.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//!
+//! // First page
+//! let page = db.events().read("order.placed", 100)?;
+//!
+//! // Resume after the last event seen
+//! if let Some(last) = page.last() {
+//!     let next = db.events().read_after("order.placed", last.sequence, 100)?;
+//! }
+//!
+//! // Keep only the newest 10,000 events of the stream
+//! db.events().set_retention("order.placed", RetentionPolicy::MaxLen(10_000))?;
+//!
+//! // Reject appends whose payload has no integer order_id
+//! db.events().set_schema("order.placed", serde_json::json!({
+//!     "type": "object",
+//!     "required": ["order_id"],
+//!     "properties": {"order_id": {"type": "integer"}}
+//! }))?;
+//!
+//! // Split the stream between workers and acknowledge processed events
+//! db.events().create_group("order.placed", "billing")?;
+//! let batch = db.events().read_group("order.placed", "billing", "worker-1", 10)?;
+//! let done: Vec<u64> = batch.iter().map(|e| e.sequence).collect();
+//! db.events().ack("order.placed", "billing", &done)?;
+//! ```
+
+use super::Strata;
+use crate::types::{Event, PendingEvent, RetentionPolicy, VersionedValue};
+use crate::{Command, Error, Output, Result, Value};
+
+/// Handle for event stream operations.
+///
+/// Obtained via [`Strata::events()`]. Reads from the branch and space that
+/// were current when the handle was created.
+pub struct Events<'a> {
+    strata: &'a Strata,
+}
+
+impl<'a> Events<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Read the first `limit` events of `stream`, oldest first.
+    pub fn read(&self, stream: &str, limit: u64) -> Result<Vec<Event>> {
+        self.read_page(stream, None, limit)
+    }
+
+    /// Read up to `limit` events of `stream` with a sequence number greater
+    /// than `sequence`, oldest first.
+    ///
+    /// Pass the `sequence` of the last event from the previous page to
+    /// resume; an empty result means the reader has caught up.
+    pub fn read_after(&self, stream: &str, sequence: u64, limit: u64) -> Result<Vec<Event>> {
+        self.read_page(stream, Some(sequence), limit)
+    }
+
+    /// Set the retention policy of `stream`.
+    ///
+    /// Events the policy no longer retains are deleted now, and again each
+    /// time `stream` is appended to. Streams that stop receiving appends are
+    /// trimmed by `Command::RetentionApply`. `RetentionPolicy::KeepAll`
+    /// removes the policy. Returns the number of events deleted now.
+    pub fn set_retention(&self, stream: &str, policy: RetentionPolicy) -> Result<u64> {
+        match self.strata.executor.execute(Command::EventSetRetention {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            policy,
+        })? {
+            Output::Uint(trimmed) => Ok(trimmed),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventSetRetention".into(),
+            }),
+        }
+    }
+
+    /// Set the JSON schema that payloads appended to `stream` must match.
+    ///
+    /// Appends that don't match fail with `Error::InvalidInput` naming the
+    /// offending field; events already in the stream are kept. The schema
+    /// is stored in the branch, so it is recovered after restarts and
+    /// exported with the branch in bundles. `Value::Null` removes it.
+    pub fn set_schema(&self, stream: &str, schema: impl Into<Value>) -> Result<()> {
+        match self.strata.executor.execute(Command::EventSetSchema {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            schema: schema.into(),
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventSetSchema".into(),
+            }),
+        }
+    }
+
+    /// Get the JSON schema of `stream`, if one is set.
+    pub fn schema(&self, stream: &str) -> Result<Option<Value>> {
+        match self.strata.executor.execute(Command::EventGetSchema {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+        })? {
+            Output::Maybe(schema) => Ok(schema),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventGetSchema".into(),
+            }),
+        }
+    }
+
+    /// Create consumer group `group` on `stream`.
+    ///
+    /// A new group starts at the beginning of the stream. Returns `false` if
+    /// the group already exists, leaving it unchanged.
+    pub fn create_group(&self, stream: &str, group: &str) -> Result<bool> {
+        match self.strata.executor.execute(Command::EventCreateGroup {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            group: group.to_string(),
+        })? {
+            Output::Bool(created) => Ok(created),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventCreateGroup".into(),
+            }),
+        }
+    }
+
+    /// Deliver up to `count` events of `stream` that no consumer of `group`
+    /// has received yet to `consumer`, oldest first.
+    ///
+    /// Delivered events stay pending until they are passed to [`ack`](Self::ack).
+    pub fn read_group(
+        &self,
+        stream: &str,
+        group: &str,
+        consumer: &str,
+        count: u64,
+    ) -> Result<Vec<Event>> {
+        match self.strata.executor.execute(Command::EventReadGroup {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            group: group.to_string(),
+            consumer: consumer.to_string(),
+            count,
+        })? {
+            Output::VersionedValues(events) => Ok(to_events(stream, events)),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventReadGroup".into(),
+            }),
+        }
+    }
+
+    /// Acknowledge the events with the given sequence numbers, removing them
+    /// from the pending entries of `group`.
+    ///
+    /// Returns the number of events acknowledged; sequence numbers that are
+    /// not pending are ignored.
+    pub fn ack(&self, stream: &str, group: &str, sequences: &[u64]) -> Result<u64> {
+        match self.strata.executor.execute(Command::EventAck {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            group: group.to_string(),
+            sequences: sequences.to_vec(),
+        })? {
+            Output::Uint(acked) => Ok(acked),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventAck".into(),
+            }),
+        }
+    }
+
+    /// Events delivered to `group` that have not been acknowledged, in
+    /// delivery order.
+    pub fn pending(&self, stream: &str, group: &str) -> Result<Vec<PendingEvent>> {
+        match self.strata.executor.execute(Command::EventPending {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            group: group.to_string(),
+        })? {
+            Output::PendingEvents(pending) => Ok(pending),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventPending".into(),
+            }),
+        }
+    }
+
+    /// Number of events ever appended to the log, across all streams.
+    ///
+    /// This is the sequence number the next append gets. Events removed by
+    /// a retention policy still count, so it can exceed the number of
+    /// events that can be read.
+    pub fn len(&self) -> Result<u64> {
+        match self.strata.executor.execute(Command::EventLen {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+        })? {
+            Output::Uint(len) => Ok(len),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventLen".into(),
+            }),
+        }
+    }
+
+    /// Whether no event has ever been appended to the log.
+    ///
+    /// A log whose events were all removed by retention is not empty.
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Sequence number of the most recently appended event, in any stream.
+    ///
+    /// Returns `None` if the log is empty.
+    pub fn last_sequence(&self) -> Result<Option<u64>> {
+        Ok(self.len()?.checked_sub(1))
+    }
+
+    fn read_page(
+        &self,
+        stream: &str,
+        after_sequence: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<Event>> {
+        match self.strata.executor.execute(Command::EventGetByType {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            event_type: stream.to_string(),
+            limit: Some(limit),
+            after_sequence,
+            as_of: None,
+        })? {
+            Output::VersionedValues(events) => Ok(to_events(stream, events)),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for EventGetByType".into(),
+            }),
+        }
+    }
+}
+
+fn to_events(stream: &str, events: Vec<VersionedValue>) -> Vec<Event> {
+    events
+        .into_iter()
+        .map(|e| Event {
+            sequence: e.version,
+            event_type: stream.to_string(),
+            payload: e.value,
+            timestamp: e.timestamp,
+        })
+        .collect()
+}
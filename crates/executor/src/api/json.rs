@@ -15,6 +15,10 @@
 //! // Get a value at a path
 //! let name = db.json_get("user:123", "$.name")?;
 //!
+//! // Read and write your own types with serde
+//! db.json_set_t("user:123", "$.address", &address)?;
+//! let user: Option<User> = db.json_get_t("user:123", "$")?;
+//!
 //! // Delete a document
 //! db.json_delete("user:123", "$")?;
 //!
@@ -22,7 +26,10 @@
 //! let (keys, cursor) = db.json_list(Some("user:".into()), None, 100)?;
 //! ```
 
-use super::Strata;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{typed, Strata};
 use crate::{Command, Error, Output, Result, Value};
 
 impl Strata {
@@ -105,6 +112,30 @@ impl Strata {
         }
     }
 
+    /// Set any serializable value at a path in a JSON document.
+    ///
+    /// Same as [`json_set`](Self::json_set), with the value converted
+    /// through its JSON form.
+    pub fn json_set_t<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        path: &str,
+        value: &T,
+    ) -> Result<u64> {
+        self.json_set(key, path, typed::to_value(key, value)?)
+    }
+
+    /// Get the value at a path in a JSON document as a `T`.
+    ///
+    /// Returns `None` if the document or path doesn't exist, or
+    /// `Error::Serialization` naming the offending field if the value
+    /// doesn't fit `T`.
+    pub fn json_get_t<T: DeserializeOwned>(&self, key: &str, path: &str) -> Result<Option<T>> {
+        self.json_get(key, path)?
+            .map(|value| typed::from_value(key, value))
+            .transpose()
+    }
+
     /// Get the full version history for a JSON document.
     ///
     /// Returns all versions of the document, newest first, or None if the
@@ -1,6 +1,9 @@
 //! Key-value store operations.
 
-use super::Strata;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{typed, Strata};
 use crate::{Command, Error, Output, Result, Value};
 
 impl Strata {
@@ -62,6 +65,34 @@ impl Strata {
         }
     }
 
+    /// Put any serializable value in the KV store.
+    ///
+    /// The value is converted through its JSON form, so structs are stored
+    /// as `Value::Object`s. Returns the version created by this write.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Config { model: String, max_tokens: u32 }
+    ///
+    /// db.kv_put_t("config", &Config { model: "gpt-4".into(), max_tokens: 1000 })?;
+    /// let config: Option<Config> = db.kv_get_t("config")?;
+    /// ```
+    pub fn kv_put_t<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<u64> {
+        self.kv_put(key, typed::to_value(key, value)?)
+    }
+
+    /// Get a value from the KV store as a `T`.
+    ///
+    /// Returns `None` if the key doesn't exist, or `Error::Serialization`
+    /// naming the offending field if the stored value doesn't fit `T`.
+    pub fn kv_get_t<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.kv_get(key)?
+            .map(|value| typed::from_value(key, value))
+            .transpose()
+    }
+
     /// Delete a key from the KV store.
     ///
     /// Returns `true` if the key existed and was deleted, `false` if it didn't exist.
@@ -109,6 +140,61 @@ impl Strata {
         }
     }
 
+    /// Get the version history for a key as governed by its history policy.
+    ///
+    /// Versions are newest first. `limit` caps how many are returned. The
+    /// result carries the governing policy and whether older versions were
+    /// dropped by it.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_set_history_policy("session:", RetentionPolicy::MaxLen(2))?;
+    /// for i in 0..5 {
+    ///     db.kv_put("session:1", i as i64)?;
+    /// }
+    ///
+    /// let history = db.kv_history("session:1", None)?;
+    /// assert_eq!(history.versions.len(), 2);
+    /// assert!(history.truncated);
+    /// ```
+    pub fn kv_history(&self, key: &str, limit: Option<u64>) -> Result<crate::types::KvHistory> {
+        match self.executor.execute(Command::KvHistory {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_string(),
+            limit,
+        })? {
+            Output::KvHistory(h) => Ok(h),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvHistory".into(),
+            }),
+        }
+    }
+
+    /// Set the history policy for keys starting with `prefix`.
+    ///
+    /// When several policies match a key the longest prefix wins. Older
+    /// versions are hidden from reads immediately and pruned by compaction.
+    /// `RetentionPolicy::KeepAll` removes the policy.
+    pub fn kv_set_history_policy(
+        &self,
+        prefix: &str,
+        policy: crate::types::RetentionPolicy,
+    ) -> Result<()> {
+        match self.executor.execute(Command::KvSetHistoryPolicy {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            prefix: prefix.to_string(),
+            policy,
+        })? {
+            Output::Unit => Ok(()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvSetHistoryPolicy".into(),
+            }),
+        }
+    }
+
     /// List keys with optional prefix filter.
     ///
     /// Returns all keys matching the prefix (or all keys if prefix is None).
@@ -129,4 +215,52 @@ impl Strata {
             }),
         }
     }
+
+    /// Atomically add `delta` to an integer value.
+    ///
+    /// A missing key counts as zero. Concurrent increments are never lost.
+    /// Returns the new value with its version. Fails if the stored value is
+    /// not a number or the addition overflows.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.kv_incr("hits", 5)?;
+    /// let hits = db.kv_incr("hits", 1)?;
+    /// assert_eq!(hits.value, Value::Int(6));
+    /// ```
+    pub fn kv_incr(&self, key: &str, delta: i64) -> Result<crate::types::VersionedValue> {
+        self.kv_add(key, Value::Int(delta))
+    }
+
+    /// Atomically subtract `delta` from an integer value.
+    ///
+    /// Same as [`kv_incr`](Self::kv_incr) with the delta negated.
+    pub fn kv_decr(&self, key: &str, delta: i64) -> Result<crate::types::VersionedValue> {
+        let delta = delta.checked_neg().ok_or_else(|| Error::InvalidInput {
+            reason: format!("cannot decrement by {}", delta),
+        })?;
+        self.kv_add(key, Value::Int(delta))
+    }
+
+    /// Atomically add a float `delta` to a numeric value.
+    ///
+    /// The stored value becomes a float.
+    pub fn kv_add_float(&self, key: &str, delta: f64) -> Result<crate::types::VersionedValue> {
+        self.kv_add(key, Value::Float(delta))
+    }
+
+    fn kv_add(&self, key: &str, delta: Value) -> Result<crate::types::VersionedValue> {
+        match self.executor.execute(Command::KvIncr {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            key: key.to_string(),
+            delta,
+        })? {
+            Output::MaybeVersioned(Some(vv)) => Ok(vv),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for KvIncr".into(),
+            }),
+        }
+    }
 }
@@ -0,0 +1,122 @@
+//! List operations.
+//!
+//! Lists are ordered sequences with O(1) push and pop at both ends, suitable
+//! for work queues. Blocking pops wait for another writer to push.
+
+use std::time::Duration;
+
+use super::Strata;
+use crate::types::ListEnd;
+use crate::{Command, Error, Output, Result, Value};
+
+impl Strata {
+    // =========================================================================
+    // List Operations
+    // =========================================================================
+
+    /// Push values onto the head of a list, creating it if needed.
+    ///
+    /// Values are pushed one at a time, so the last one ends up first.
+    /// Returns the list length after the push.
+    pub fn list_lpush(&self, list: &str, values: Vec<Value>) -> Result<u64> {
+        self.list_push(list, ListEnd::Left, values)
+    }
+
+    /// Push values onto the tail of a list, creating it if needed.
+    ///
+    /// Returns the list length after the push.
+    pub fn list_rpush(&self, list: &str, values: Vec<Value>) -> Result<u64> {
+        self.list_push(list, ListEnd::Right, values)
+    }
+
+    /// Pop the head of a list, or None if the list is empty.
+    pub fn list_lpop(&self, list: &str) -> Result<Option<Value>> {
+        self.list_pop(list, ListEnd::Left, None)
+    }
+
+    /// Pop the tail of a list, or None if the list is empty.
+    pub fn list_rpop(&self, list: &str) -> Result<Option<Value>> {
+        self.list_pop(list, ListEnd::Right, None)
+    }
+
+    /// Pop the head of a list, waiting up to `timeout` for a value.
+    ///
+    /// Returns None if the list is still empty when the timeout expires.
+    pub fn list_blpop(&self, list: &str, timeout: Duration) -> Result<Option<Value>> {
+        self.list_pop(list, ListEnd::Left, Some(timeout))
+    }
+
+    /// Pop the tail of a list, waiting up to `timeout` for a value.
+    ///
+    /// Returns None if the list is still empty when the timeout expires.
+    pub fn list_brpop(&self, list: &str, timeout: Duration) -> Result<Option<Value>> {
+        self.list_pop(list, ListEnd::Right, Some(timeout))
+    }
+
+    /// Read the elements between `start` and `stop`, inclusive.
+    ///
+    /// Negative indices count from the tail (`-1` is the last element).
+    pub fn list_range(&self, list: &str, start: i64, stop: i64) -> Result<Vec<Value>> {
+        match self.executor.execute(Command::ListRange {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            list: list.to_string(),
+            start,
+            stop,
+        })? {
+            Output::Values(values) => Ok(values),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for ListRange".into(),
+            }),
+        }
+    }
+
+    /// Get the number of elements in a list (0 if it doesn't exist).
+    pub fn list_len(&self, list: &str) -> Result<u64> {
+        match self.executor.execute(Command::ListLen {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            list: list.to_string(),
+        })? {
+            Output::Uint(len) => Ok(len),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for ListLen".into(),
+            }),
+        }
+    }
+
+    fn list_push(&self, list: &str, end: ListEnd, values: Vec<Value>) -> Result<u64> {
+        match self.executor.execute(Command::ListPush {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            list: list.to_string(),
+            end,
+            values,
+        })? {
+            Output::Uint(len) => Ok(len),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for ListPush".into(),
+            }),
+        }
+    }
+
+    fn list_pop(
+        &self,
+        list: &str,
+        end: ListEnd,
+        timeout: Option<Duration>,
+    ) -> Result<Option<Value>> {
+        match self.executor.execute(Command::ListPop {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            list: list.to_string(),
+            end,
+            timeout_ms: timeout.map(|t| t.as_millis() as u64),
+        })? {
+            Output::Maybe(value) => Ok(value),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for ListPop".into(),
+            }),
+        }
+    }
+}
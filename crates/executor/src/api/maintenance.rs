@@ -0,0 +1,48 @@
+//! Database maintenance API.
+//!
+//! Access via `db.maintenance()` for checks that repair derived state, such
+//! as indexes, without touching the stored data.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//!
+//! // Drop index entries left behind by branches deleted with older versions
+//! let report = db.maintenance().verify_indexes()?;
+//! if report.stale_branches > 0 {
+//!     println!("removed {} stale documents", report.documents_removed);
+//! }
+//! ```
+
+use strata_engine::{BranchIndex, IndexVerification};
+
+use super::Strata;
+use crate::convert::convert_result;
+use crate::Result;
+
+/// Handle for maintenance operations.
+///
+/// Obtained via [`Strata::maintenance()`].
+pub struct Maintenance<'a> {
+    strata: &'a Strata,
+}
+
+impl<'a> Maintenance<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Find and remove index entries of branches that no longer exist.
+    ///
+    /// Deleting a branch clears its search postings and vector index
+    /// backends, but databases written by versions that did not can still
+    /// hold them. Stale postings inflate search statistics and stale
+    /// backends hold memory. Returns what was found; all of it is removed.
+    pub fn verify_indexes(&self) -> Result<IndexVerification> {
+        let db = self.strata.executor().primitives().db.clone();
+        convert_result(BranchIndex::new(db).verify_indexes())
+    }
+}
@@ -38,18 +38,38 @@
 
 mod branch;
 mod branches;
+mod custom;
 mod db;
+mod documents;
 mod event;
+mod events;
 mod json;
 mod kv;
+mod list;
+mod maintenance;
+#[cfg(feature = "search")]
+mod search;
+mod sessions;
 mod state;
+mod states;
+mod typed;
+#[cfg(feature = "vectors")]
 mod vector;
 
 pub use branches::Branches;
+pub use custom::Custom;
+pub use documents::Documents;
+pub use events::Events;
+pub use maintenance::Maintenance;
+#[cfg(feature = "search")]
+pub use search::Search;
+pub use sessions::{Sessions, SESSION_STREAM};
+pub use states::States;
 pub use strata_engine::branch_ops::{
-    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff,
+    BranchDiffEntry, BranchDiffResult, ConflictEntry, DiffSummary, EntryChange, ForkInfo,
+    MergeInfo, MergeStrategy, SchemaChange, SchemaChangeKind, SpaceDiff,
 };
+pub use strata_engine::{BranchAncestor, IndexVerification};
 
 use std::path::Path;
 use std::sync::Arc;
@@ -67,10 +87,25 @@ static VECTOR_RECOVERY_INIT: Once = Once::new();
 
 fn ensure_vector_recovery() {
     VECTOR_RECOVERY_INIT.call_once(|| {
+        #[cfg(feature = "vectors")]
         strata_engine::register_vector_recovery();
     });
 }
 
+/// Map a failed open to an executor error.
+///
+/// A held write lock keeps its own variant so callers can tell "another
+/// process has it open" apart from other failures.
+fn open_error(e: strata_core::StrataError) -> Error {
+    match e {
+        strata_core::StrataError::AlreadyOpen { .. }
+        | strata_core::StrataError::Cancelled { .. } => Error::from(e),
+        e => Error::Internal {
+            reason: format!("Failed to open database: {}", e),
+        },
+    }
+}
+
 /// High-level typed wrapper for database operations.
 ///
 /// `Strata` provides a convenient Rust API that wraps the executor's
@@ -114,6 +149,14 @@ impl Strata {
     /// Use this to open a database in read-only mode or with other
     /// configuration options.
     ///
+    /// Read-only opens don't take the database's write lock, so inspection
+    /// tools can attach while another process has it open for writing. They
+    /// see the data committed at open time and never create files.
+    ///
+    /// A read-write open of a directory another process holds fails with
+    /// [`Error::AlreadyOpen`]. After a crash that left the lock held,
+    /// `OpenOptions::force_unlock(true)` breaks it first.
+    ///
     /// # Example
     ///
     /// ```text
@@ -122,11 +165,38 @@ impl Strata {
     /// let db = Strata::open_with("/var/data/myapp", OpenOptions::new().access_mode(AccessMode::ReadOnly))?;
     /// ```
     pub fn open_with<P: AsRef<Path>>(path: P, opts: OpenOptions) -> Result<Self> {
+        let db = Self::open_database(path, &opts)?;
+        Self::from_database_with_mode(db, opts.access_mode)
+    }
+
+    /// Open the engine database at `path` and apply `opts` to it.
+    ///
+    /// Shared by [`Strata::open_with`] and [`StrataManager`](crate::StrataManager).
+    pub(crate) fn open_database<P: AsRef<Path>>(
+        path: P,
+        opts: &OpenOptions,
+    ) -> Result<Arc<Database>> {
         ensure_vector_recovery();
-        let db = Database::open(path).map_err(|e| Error::Internal {
-            reason: format!("Failed to open database: {}", e),
-        })?;
+        // Read-only opens skip the write lock so they can attach to a
+        // database another process is writing to.
+        if opts.force_unlock && opts.access_mode == AccessMode::ReadWrite {
+            Database::force_unlock(path.as_ref()).map_err(open_error)?;
+        }
+        let db = match opts.access_mode {
+            AccessMode::ReadOnly => {
+                Database::open_read_only_with_recovery(path, opts.recovery.clone())
+            }
+            AccessMode::ReadWrite => {
+                Database::open_with_recovery(path, opts.worker_pool.as_ref(), opts.recovery.clone())
+            }
+        }
+        .map_err(open_error)?;
+        Self::apply_options(&db, opts)?;
+        Ok(db)
+    }
 
+    /// Apply the runtime settings in `opts` to an open database.
+    fn apply_options(db: &Arc<Database>, opts: &OpenOptions) -> Result<()> {
         // Override auto_embed if explicitly set in OpenOptions
         if let Some(enabled) = opts.auto_embed {
             if enabled {
@@ -137,21 +207,27 @@ impl Strata {
             }
             db.set_auto_embed(enabled);
         }
-
-        let access_mode = opts.access_mode;
-        let executor = Executor::new_with_mode(db, access_mode);
-
-        match access_mode {
-            AccessMode::ReadWrite => Self::ensure_default_branch(&executor)?,
-            AccessMode::ReadOnly => Self::verify_default_branch(&executor)?,
+        if let Some(provider) = &opts.embed_provider {
+            db.set_embed_provider(provider.clone());
+        }
+        if let Some(provider) = &opts.rerank_provider {
+            db.set_rerank_provider(provider.clone());
+        }
+        if opts.trace_commits {
+            db.set_commit_tracing(true);
+        }
+        if opts.read_cache > 0 {
+            db.set_read_cache_capacity(opts.read_cache);
+        }
+        if let Some(policy) = opts.checkpoint {
+            db.schedule_checkpoints(policy).map_err(Error::from)?;
+        }
+        if opts.memory_budget.is_enabled() {
+            db.set_memory_budget(opts.memory_budget.clone())
+                .map_err(Error::from)?;
         }
 
-        Ok(Self {
-            executor,
-            current_branch: BranchId::default(),
-            current_space: "default".to_string(),
-            access_mode,
-        })
+        Ok(())
     }
 
     /// Create an ephemeral in-memory database.
@@ -165,10 +241,27 @@ impl Strata {
     /// db.kv_put("key", Value::Int(42))?;
     /// ```
     pub fn cache() -> Result<Self> {
+        Self::cache_with(OpenOptions::default())
+    }
+
+    /// Create an ephemeral in-memory database with explicit options.
+    ///
+    /// Runtime options such as `max_memory`, `read_cache` and
+    /// `trace_commits` apply as in [`Strata::open_with`]; options about the
+    /// data directory (access mode, locking, recovery, checkpoints, worker
+    /// pool) have no effect.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let db = Strata::cache_with(OpenOptions::new().max_memory(256 << 20))?;
+    /// ```
+    pub fn cache_with(opts: OpenOptions) -> Result<Self> {
         ensure_vector_recovery();
         let db = Database::cache().map_err(|e| Error::Internal {
             reason: format!("Failed to open cache database: {}", e),
         })?;
+        Self::apply_options(&db, &opts)?;
         let executor = Executor::new(db);
 
         // Ensure the default branch exists
@@ -206,13 +299,19 @@ impl Strata {
     ///
     /// Use this when you need more control over database configuration.
     /// For most cases, prefer [`Strata::open()`].
+    ///
+    /// Takes an engine type, so it is outside the stable API.
+    #[doc(hidden)]
     pub fn from_database(db: Arc<Database>) -> Result<Self> {
         Self::from_database_with_mode(db, AccessMode::ReadWrite)
     }
 
     /// Create a new Strata instance from an existing database with a
     /// specific access mode.
-    fn from_database_with_mode(db: Arc<Database>, access_mode: AccessMode) -> Result<Self> {
+    pub(crate) fn from_database_with_mode(
+        db: Arc<Database>,
+        access_mode: AccessMode,
+    ) -> Result<Self> {
         ensure_vector_recovery();
         let executor = Executor::new_with_mode(db, access_mode);
 
@@ -283,10 +382,41 @@ impl Strata {
     /// Get WAL durability counters for diagnostics.
     ///
     /// Returns `None` for cache (in-memory) databases.
-    pub fn durability_counters(&self) -> Option<strata_engine::WalCounters> {
+    pub fn durability_counters(&self) -> Option<crate::WalCounters> {
         self.executor.primitives().db.durability_counters()
     }
 
+    /// Most committed data a crash can lose under the configured durability.
+    ///
+    /// Returns `LossWindow::Unbounded` for cache (in-memory) databases.
+    pub fn max_loss_window(&self) -> crate::LossWindow {
+        self.executor.primitives().db.max_loss_window()
+    }
+
+    /// Pick up writes another process made since this read-only handle
+    /// opened (or last refreshed).
+    ///
+    /// Returns the number of transactions applied; 0 for writable and cache
+    /// databases, which always see their own writes. Vector and search
+    /// indexes are not updated.
+    pub fn refresh(&self) -> Result<u64> {
+        Ok(self.executor.primitives().db.refresh()?)
+    }
+
+    /// Start a hot backup for an external snapshot tool.
+    ///
+    /// Returns the files and byte ranges to copy; writes continue meanwhile.
+    /// Call [`end_backup`](Self::end_backup) with the manifest's `id` once
+    /// the copy is done so WAL compaction can resume.
+    pub fn begin_backup(&self) -> Result<crate::BackupManifest> {
+        Ok(self.executor.primitives().db.begin_backup()?)
+    }
+
+    /// Finish a hot backup started with [`begin_backup`](Self::begin_backup).
+    pub fn end_backup(&self, id: u64) -> Result<()> {
+        Ok(self.executor.primitives().db.end_backup(id)?)
+    }
+
     /// Get a handle for branch management operations.
     ///
     /// The returned [`Branches`] handle provides the "power API" for branch
@@ -311,6 +441,101 @@ impl Strata {
         Branches::new(&self.executor)
     }
 
+    /// Get a handle for paginated event stream reads.
+    ///
+    /// The returned [`Events`] handle reads from the current branch and
+    /// space. Each [`Event`](crate::Event) carries its sequence number, so a
+    /// reader can resume where it left off.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let mut page = db.events().read("user.created", 10)?;
+    /// while let Some(last) = page.last() {
+    ///     // process page...
+    ///     page = db.events().read_after("user.created", last.sequence, 10)?;
+    /// }
+    /// ```
+    pub fn events(&self) -> Events<'_> {
+        Events::new(self)
+    }
+
+    /// Get a handle for JSON document schemas.
+    ///
+    /// The returned [`Documents`] handle operates on the current branch and
+    /// space.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.json().set_schema("user:", json!({"type": "object", "required": ["name"]}))?;
+    /// let violations = db.json().validate("user:1", json!({"age": 30}))?;
+    /// assert_eq!(violations[0].path, "$.name");
+    /// ```
+    pub fn json(&self) -> Documents<'_> {
+        Documents::new(self)
+    }
+
+    /// Get a handle for maintenance operations.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let report = db.maintenance().verify_indexes()?;
+    /// println!("{} stale branches cleaned up", report.stale_branches);
+    /// ```
+    pub fn maintenance(&self) -> Maintenance<'_> {
+        Maintenance::new(self)
+    }
+
+    /// Get a handle for state cell watches.
+    ///
+    /// The returned [`States`] handle operates on the current branch and
+    /// space.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// let changes = db.state().watch("leader")?;
+    /// for change in changes {
+    ///     println!("new leader: {:?}", change.value);
+    /// }
+    /// ```
+    pub fn state(&self) -> States<'_> {
+        States::new(self)
+    }
+
+    /// Get a handle for full-text search.
+    ///
+    /// The returned [`Search`] handle searches the current branch and space.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// for hit in db.search().text("disk failure", 10)? {
+    ///     println!("{} ({}): {}", hit.entity, hit.primitive, hit.snippet);
+    /// }
+    /// ```
+    #[cfg(feature = "search")]
+    pub fn search(&self) -> Search<'_> {
+        Search::new(self)
+    }
+
+    /// Get a handle for the entries of a custom primitive.
+    ///
+    /// `id` is the primitive ID it was registered with. The returned
+    /// [`Custom`] handle operates on the current branch and space.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.custom(0x80).put(b"hits", Value::Int(1))?;
+    /// let hits = db.custom(0x80).get(b"hits")?;
+    /// ```
+    pub fn custom(&self, id: u8) -> Custom<'_> {
+        Custom::new(self, id)
+    }
+
     /// Create a new [`Session`] for interactive transaction support.
     ///
     /// The returned session wraps a fresh executor and can manage an
@@ -320,6 +545,22 @@ impl Strata {
         Session::new_with_mode(self.executor.primitives().db.clone(), self.access_mode)
     }
 
+    /// Get a handle for conversation sessions.
+    ///
+    /// The returned [`Sessions`] handle keeps chat transcripts in the
+    /// current branch and space. Not to be confused with [`session()`](Self::session),
+    /// which opens a transaction session.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// db.sessions().append_message("chat-42", "user", "hello")?;
+    /// let recent = db.sessions().window("chat-42", 20)?;
+    /// ```
+    pub fn sessions(&self) -> Sessions<'_> {
+        Sessions::new(self)
+    }
+
     // =========================================================================
     // Branch Context
     // =========================================================================
@@ -551,6 +792,10 @@ mod tests {
         Strata::cache().unwrap()
     }
 
+    fn from_json(json: serde_json::Value) -> Value {
+        Value::try_from(&json).unwrap()
+    }
+
     #[test]
     fn test_ping() {
         let db = create_strata();
@@ -617,6 +862,167 @@ mod tests {
         assert_eq!(value.unwrap(), Value::String("state".into()));
     }
 
+    #[test]
+    fn test_state_history_and_get_version() {
+        let db = create_strata();
+        db.state_set("status", "idle").unwrap();
+        db.state_set("status", "busy").unwrap();
+        let last = db.state_set("status", "done").unwrap();
+
+        let recent = db.state_history("status", 2).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].value, Value::String("done".into()));
+        assert_eq!(recent[0].version, last);
+        assert_eq!(recent[1].value, Value::String("busy".into()));
+        assert!(db.state_history("missing", 2).unwrap().is_empty());
+
+        let first = db.state_get_version("status", 1).unwrap().unwrap();
+        assert_eq!(first.value, Value::String("idle".into()));
+        assert_eq!(first.version, 1);
+        assert!(db.state_get_version("status", last + 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_kv_history_policy() {
+        let db = create_strata();
+        db.kv_set_history_policy("session:", RetentionPolicy::MaxLen(2))
+            .unwrap();
+        for i in 0..5i64 {
+            db.kv_put("session:1", i).unwrap();
+            db.kv_put("other", i).unwrap();
+        }
+
+        let history = db.kv_history("session:1", None).unwrap();
+        assert_eq!(history.policy, Some(RetentionPolicy::MaxLen(2)));
+        assert!(history.truncated);
+        let values: Vec<Value> = history.versions.into_iter().map(|v| v.value).collect();
+        assert_eq!(values, vec![Value::Int(4), Value::Int(3)]);
+
+        let other = db.kv_history("other", Some(3)).unwrap();
+        assert_eq!(other.policy, None);
+        assert!(!other.truncated);
+        assert_eq!(other.versions.len(), 3);
+
+        assert_eq!(db.compact().unwrap().versions_removed, 3);
+        assert!(db.kv_history("session:1", None).unwrap().truncated);
+        assert!(db
+            .kv_set_history_policy("session:", RetentionPolicy::MaxLen(0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_branch_retention_per_primitive() {
+        use crate::{BranchRetention, PrimitiveType};
+
+        let db = create_strata();
+        for i in 0..5i64 {
+            db.kv_put("k", i).unwrap();
+            db.state_set("s", i).unwrap();
+        }
+        let retention = BranchRetention::new(RetentionPolicy::MaxLen(1))
+            .with(PrimitiveType::State, RetentionPolicy::MaxLen(3));
+        db.branches()
+            .set_retention("default", retention.clone())
+            .unwrap();
+        assert_eq!(db.branches().retention("default").unwrap(), retention);
+
+        assert_eq!(db.compact().unwrap().versions_removed, 4 + 2);
+        assert_eq!(db.kv_history("k", None).unwrap().versions.len(), 1);
+        assert_eq!(db.state_history("s", 10).unwrap().len(), 3);
+
+        db.branches()
+            .set_retention("default", RetentionPolicy::KeepAll)
+            .unwrap();
+        assert_eq!(
+            db.branches().retention("default").unwrap(),
+            BranchRetention::default()
+        );
+        assert!(matches!(
+            db.branches()
+                .set_retention("default", RetentionPolicy::MaxLen(0)),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            db.branches().retention("missing"),
+            Err(Error::BranchNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_kv_and_state_incr() {
+        let db = create_strata();
+
+        assert_eq!(db.kv_incr("hits", 5).unwrap().value, Value::Int(5));
+        assert_eq!(db.kv_decr("hits", 2).unwrap().value, Value::Int(3));
+        let float = db.kv_add_float("hits", 0.5).unwrap();
+        assert_eq!(float.value, Value::Float(3.5));
+        assert_eq!(db.kv_get("hits").unwrap(), Some(Value::Float(3.5)));
+        assert!(db.kv_decr("hits", i64::MIN).is_err());
+
+        let first = db.state_incr("count", 1).unwrap();
+        assert_eq!(first.version, 1);
+        let second = db.state_decr("count", 3).unwrap();
+        assert_eq!(second.value, Value::Int(-2));
+        assert_eq!(second.version, 2);
+        assert_eq!(
+            db.state_add_float("count", 2.5).unwrap().value,
+            Value::Float(0.5)
+        );
+
+        db.kv_put("name", "alice").unwrap();
+        assert!(matches!(
+            db.kv_incr("name", 1),
+            Err(Error::WrongType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_list_push_pop_range() {
+        let db = create_strata();
+
+        assert_eq!(db.list_rpush("jobs", vec![1.into(), 2.into()]).unwrap(), 2);
+        assert_eq!(db.list_lpush("jobs", vec![0.into()]).unwrap(), 3);
+        assert_eq!(
+            db.list_range("jobs", 0, -1).unwrap(),
+            vec![Value::Int(0), Value::Int(1), Value::Int(2)]
+        );
+        assert_eq!(db.list_lpop("jobs").unwrap(), Some(Value::Int(0)));
+        assert_eq!(db.list_rpop("jobs").unwrap(), Some(Value::Int(2)));
+        assert_eq!(db.list_len("jobs").unwrap(), 1);
+        assert_eq!(
+            db.list_blpop("jobs", std::time::Duration::from_millis(10))
+                .unwrap(),
+            Some(Value::Int(1))
+        );
+        assert_eq!(
+            db.list_brpop("jobs", std::time::Duration::from_millis(10))
+                .unwrap(),
+            None
+        );
+        assert_eq!(db.list_len("jobs").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_state_watch_receives_set_and_cas() {
+        let db = create_strata();
+        let changes = db.state().watch("status").unwrap();
+
+        let version = db.state_set("status", "starting").unwrap();
+        db.state_cas("status", Some(version), "ready").unwrap();
+
+        let timeout = std::time::Duration::from_secs(5);
+        let first = changes.recv_timeout(timeout).unwrap();
+        assert_eq!(first.value, Value::String("starting".into()));
+        assert_eq!(first.version, version);
+        let second = changes.recv_timeout(timeout).unwrap();
+        assert_eq!(second.value, Value::String("ready".into()));
+        assert_eq!(second.version, version + 1);
+
+        // Dropping the receiver unsubscribes; later writes still succeed
+        drop(changes);
+        db.state_set("status", "stopped").unwrap();
+    }
+
     #[test]
     fn test_event_append_range() {
         let db = create_strata();
@@ -637,6 +1043,406 @@ mod tests {
         assert_eq!(events.len(), 2);
     }
 
+    #[test]
+    fn test_events_read_after_pages_through_stream() {
+        let db = create_strata();
+        assert_eq!(db.events().last_sequence().unwrap(), None);
+
+        for i in 0..5 {
+            let payload = Value::Object([("i".to_string(), Value::Int(i))].into_iter().collect());
+            db.event_append("orders", payload.clone()).unwrap();
+            db.event_append("audit", payload).unwrap();
+        }
+
+        let events = db.events();
+        assert_eq!(events.len().unwrap(), 10);
+        assert_eq!(events.last_sequence().unwrap(), Some(9));
+
+        let mut seen = Vec::new();
+        let mut page = events.read("orders", 2).unwrap();
+        while let Some(last) = page.last().map(|e| e.sequence) {
+            assert!(page.len() <= 2);
+            seen.extend(page.into_iter().map(|e| {
+                assert_eq!(e.event_type, "orders");
+                (e.sequence, e.payload)
+            }));
+            page = events.read_after("orders", last, 2).unwrap();
+        }
+
+        let sequences: Vec<u64> = seen.iter().map(|(s, _)| *s).collect();
+        assert_eq!(sequences, vec![0, 2, 4, 6, 8]);
+        assert_eq!(
+            seen[4].1,
+            Value::Object([("i".to_string(), Value::Int(4))].into_iter().collect())
+        );
+    }
+
+    #[test]
+    fn test_events_set_retention_caps_stream_length() {
+        let payload = |i| Value::Object([("i".to_string(), Value::Int(i))].into_iter().collect());
+        let db = create_strata();
+        for i in 0..5 {
+            db.event_append("orders", payload(i)).unwrap();
+        }
+
+        let events = db.events();
+        assert_eq!(
+            events
+                .set_retention("orders", RetentionPolicy::MaxLen(2))
+                .unwrap(),
+            3
+        );
+        db.event_append("orders", payload(5)).unwrap();
+
+        let kept: Vec<Value> = events
+            .read("orders", 10)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.payload)
+            .collect();
+        assert_eq!(kept, vec![payload(4), payload(5)]);
+
+        assert!(events
+            .set_retention("orders", RetentionPolicy::MaxLen(0))
+            .is_err());
+    }
+
+    #[test]
+    fn test_events_schema_rejects_mismatched_payloads() {
+        let db = create_strata();
+        let events = db.events();
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["order_id"],
+            "properties": {"order_id": {"type": "integer"}}
+        });
+        events
+            .set_schema("orders", from_json(schema.clone()))
+            .unwrap();
+        assert_eq!(events.schema("orders").unwrap(), Some(from_json(schema)));
+
+        let order = |id: Value| Value::Object([("order_id".to_string(), id)].into_iter().collect());
+        db.event_append("orders", order(Value::Int(1))).unwrap();
+        match db.event_append("orders", order(Value::from("1"))) {
+            Err(Error::InvalidInput { reason }) => assert!(reason.contains("$.order_id")),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+        assert_eq!(db.event_len().unwrap(), 1);
+
+        events.set_schema("orders", Value::Null).unwrap();
+        assert_eq!(events.schema("orders").unwrap(), None);
+        db.event_append("orders", order(Value::from("1"))).unwrap();
+        assert!(events
+            .set_schema("orders", from_json(serde_json::json!({"type": "decimal"})))
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_schema_rejects_mismatched_documents() {
+        let db = create_strata();
+        let json = db.json();
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"id": {"type": "integer"}, "tags": {"type": "array"}}
+        });
+        json.set_schema("order:", from_json(schema.clone()))
+            .unwrap();
+        assert_eq!(json.schema("order:").unwrap(), Some(from_json(schema)));
+
+        let violations = json
+            .validate(
+                "order:1",
+                from_json(serde_json::json!({"id": "1", "tags": "a"})),
+            )
+            .unwrap();
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(paths, ["$.id", "$.tags"]);
+        assert_eq!(db.json_get("order:1", "$").unwrap(), None);
+        assert!(json
+            .validate("note:1", from_json(serde_json::json!("anything")))
+            .unwrap()
+            .is_empty());
+
+        db.json_set("order:1", "$", from_json(serde_json::json!({"id": 1})))
+            .unwrap();
+        match db.json_set("order:1", "$.id", "1") {
+            Err(Error::InvalidInput { reason }) => assert!(reason.contains("$.id")),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+        assert!(json
+            .set_schema("order:", from_json(serde_json::json!({"type": "decimal"})))
+            .is_err());
+    }
+
+    #[test]
+    fn test_json_merge_and_patch() {
+        let db = create_strata();
+        let json = db.json();
+        let v1 = json
+            .merge(
+                "user:1",
+                from_json(serde_json::json!({"name": "Ann", "tags": ["a"]})),
+            )
+            .unwrap();
+        let v2 = json
+            .merge(
+                "user:1",
+                from_json(serde_json::json!({"name": null, "age": 30})),
+            )
+            .unwrap();
+        assert!(v2 > v1);
+
+        let v3 = json
+            .patch(
+                "user:1",
+                from_json(serde_json::json!([
+                    {"op": "test", "path": "/age", "value": 30},
+                    {"op": "add", "path": "/tags/-", "value": "b"}
+                ])),
+            )
+            .unwrap();
+        assert!(v3 > v2);
+        assert_eq!(
+            db.json_get("user:1", "$").unwrap(),
+            Some(from_json(
+                serde_json::json!({"age": 30, "tags": ["a", "b"]})
+            ))
+        );
+
+        let failing = serde_json::json!([
+            {"op": "remove", "path": "/age"},
+            {"op": "test", "path": "/tags/0", "value": "z"}
+        ]);
+        assert!(matches!(
+            json.patch("user:1", from_json(failing)),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            json.patch(
+                "user:1",
+                from_json(serde_json::json!([{"op": "frobnicate"}]))
+            ),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert_eq!(
+            db.json_get("user:1", "$.age").unwrap(),
+            Some(Value::Int(30))
+        );
+    }
+
+    #[test]
+    fn test_typed_kv_and_json_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            model: String,
+            max_tokens: u32,
+            tags: Vec<String>,
+        }
+
+        let db = create_strata();
+        let config = Config {
+            model: "gpt-4".into(),
+            max_tokens: 1000,
+            tags: vec!["a".into()],
+        };
+
+        db.kv_put_t("config", &config).unwrap();
+        assert_eq!(db.kv_get_t::<Config>("config").unwrap(), Some(config));
+        assert_eq!(db.kv_get_t::<Config>("missing").unwrap(), None);
+
+        db.json_set("doc", "$", from_json(serde_json::json!({"name": "agent"})))
+            .unwrap();
+        db.json_set_t("doc", "$.limits", &[1u32, 2]).unwrap();
+        assert_eq!(
+            db.json_get_t::<Vec<u32>>("doc", "$.limits").unwrap(),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            db.json_get_t::<String>("doc", "$.name").unwrap(),
+            Some("agent".into())
+        );
+
+        db.kv_put("config", from_json(serde_json::json!({"model": "gpt-4"})))
+            .unwrap();
+        match db.kv_get_t::<Config>("config") {
+            Err(Error::Serialization { reason }) => {
+                assert!(reason.contains("'config'"), "{}", reason);
+                assert!(reason.contains("max_tokens"), "{}", reason);
+            }
+            other => panic!("expected Serialization error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_get_paths() {
+        let db = create_strata();
+        let json = db.json();
+        let paths = ["$.user.name", "$.missing", "$.tags[1]"];
+        assert_eq!(json.get_paths("doc", &paths).unwrap(), vec![None; 3]);
+
+        db.json_set(
+            "doc",
+            "$",
+            from_json(
+                serde_json::json!({"user": {"name": "Ann", "bio": "long"}, "tags": ["a", "b"]}),
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            json.get_paths("doc", &paths).unwrap(),
+            vec![
+                Some(Value::String("Ann".into())),
+                None,
+                Some(Value::String("b".into())),
+            ]
+        );
+        assert!(matches!(
+            json.get_paths("doc", &["$.user..name"]),
+            Err(Error::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_json_array_operations() {
+        let db = create_strata();
+        let json = db.json();
+        db.json_set("chat:1", "$", from_json(serde_json::json!({"title": "t"})))
+            .unwrap();
+        assert_eq!(json.array_len("chat:1", "$.turns").unwrap(), None);
+
+        assert_eq!(json.array_append("chat:1", "$.turns", "b").unwrap(), 1);
+        assert_eq!(json.array_insert("chat:1", "$.turns", 0, "a").unwrap(), 2);
+        assert_eq!(json.array_append("chat:1", "$.turns", "c").unwrap(), 3);
+        assert_eq!(
+            json.array_remove("chat:1", "$.turns", 1).unwrap(),
+            Value::String("b".into())
+        );
+        assert_eq!(json.array_len("chat:1", "$.turns").unwrap(), Some(2));
+        assert_eq!(
+            db.json_get("chat:1", "$.turns").unwrap(),
+            Some(from_json(serde_json::json!(["a", "c"])))
+        );
+
+        assert!(matches!(
+            json.array_remove("chat:1", "$.turns", 5),
+            Err(Error::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            json.array_append("chat:1", "$.title", "x"),
+            Err(Error::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_events_consumer_group_read_and_ack() {
+        let payload = |i| Value::Object([("i".to_string(), Value::Int(i))].into_iter().collect());
+        let db = create_strata();
+        for i in 0..3 {
+            db.event_append("orders", payload(i)).unwrap();
+        }
+
+        let events = db.events();
+        assert!(events.create_group("orders", "billing").unwrap());
+        assert!(!events.create_group("orders", "billing").unwrap());
+
+        let first = events.read_group("orders", "billing", "w1", 2).unwrap();
+        let second = events.read_group("orders", "billing", "w2", 2).unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].payload, payload(2));
+        assert_eq!(second[0].event_type, "orders");
+
+        let done: Vec<u64> = first.iter().map(|e| e.sequence).collect();
+        assert_eq!(events.ack("orders", "billing", &done).unwrap(), 2);
+
+        let pending = events.pending("orders", "billing").unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].sequence, second[0].sequence);
+        assert_eq!(pending[0].consumer, "w2");
+
+        assert!(events.read_group("orders", "missing", "w1", 1).is_err());
+    }
+
+    #[test]
+    fn test_search_text_ranks_and_highlights() {
+        let mut db = create_strata();
+
+        db.kv_put("a", "disk failure on node seven").unwrap();
+        db.kv_put("b", "node seven rebooted").unwrap();
+        db.kv_put("c", 42i64).unwrap();
+        db.json_set(
+            "doc",
+            "$",
+            from_json(serde_json::json!({"note": "replace the disk"})),
+        )
+        .unwrap();
+        db.event_append("alert", from_json(serde_json::json!({"msg": "disk hot"})))
+            .unwrap();
+
+        let hits = db.search().text("disk", 10).unwrap();
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].rank, 1);
+        let primitives: Vec<&str> = hits.iter().map(|h| h.primitive.as_str()).collect();
+        assert!(primitives.contains(&"kv"));
+        assert!(primitives.contains(&"json"));
+        assert!(primitives.contains(&"event"));
+        for hit in &hits {
+            let (start, end) = hit.highlights[0];
+            assert_eq!(&hit.snippet[start as usize..end as usize], "disk");
+        }
+
+        assert_eq!(db.search().text("seven", 1).unwrap().len(), 1);
+        assert!(db.search().text("missing", 10).unwrap().is_empty());
+
+        db.kv_delete("a").unwrap();
+        assert_eq!(db.search().text("failure", 10).unwrap().len(), 0);
+
+        db.set_space("other").unwrap();
+        assert!(db.search().text("disk", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_search_hybrid_fuses_text_and_vectors() {
+        let db = create_strata();
+
+        db.kv_put("doc:a", "disk failure on node seven").unwrap();
+        db.kv_put("doc:b", "scheduled maintenance window").unwrap();
+        db.vector_create_collection("chunks", 2u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("chunks", "doc:a", vec![1.0, 0.0], None)
+            .unwrap();
+        db.vector_upsert("chunks", "doc:b", vec![0.0, 1.0], None)
+            .unwrap();
+
+        // Text favours doc:a, the embedding favours doc:b; both are kv hits
+        let hits = db
+            .search()
+            .hybrid("chunks", "disk", vec![0.1, 1.0], 10, FusionMethod::Rrf)
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].entity, "doc:a");
+        assert!(hits.iter().all(|h| h.primitive == "kv"));
+
+        let hits = db
+            .search()
+            .hybrid(
+                "chunks",
+                "disk",
+                vec![0.1, 1.0],
+                1,
+                FusionMethod::Weighted { vector_weight: 0.9 },
+            )
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entity, "doc:b");
+
+        assert!(db
+            .search()
+            .hybrid("missing", "disk", vec![1.0, 0.0], 10, FusionMethod::Rrf)
+            .is_err());
+    }
+
     #[test]
     fn test_vector_operations() {
         let db = create_strata();
@@ -892,6 +1698,132 @@ mod tests {
         assert!(info.keys_copied >= 2);
     }
 
+    #[test]
+    fn test_branches_lineage() {
+        let db = create_strata();
+        db.fork_branch("forked").unwrap();
+        db.branches().fork("forked", "nested").unwrap();
+
+        let ancestry = db.branches().ancestry("nested").unwrap();
+        let names: Vec<&str> = ancestry.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["forked", "default"]);
+        assert_eq!(db.branches().children("default").unwrap(), vec!["forked"]);
+
+        let info = db.branch_get("nested").unwrap().unwrap();
+        assert_eq!(info.info.parent_id, Some(BranchId::from("forked")));
+        assert_eq!(info.info.fork_version, Some(ancestry[0].fork_version));
+    }
+
+    #[test]
+    fn test_branches_tags_and_query() {
+        let db = create_strata();
+        db.create_branch("run-a").unwrap();
+        db.create_branch("run-b").unwrap();
+        let branches = db.branches();
+
+        assert!(branches.add_tag("run-a", "eval").unwrap());
+        assert!(branches.add_tag("run-b", "eval").unwrap());
+        assert!(!branches.add_tag("run-b", "eval").unwrap());
+        branches.set_metadata("run-b", "model", "small").unwrap();
+        assert!(matches!(
+            branches.add_tag("missing", "eval"),
+            Err(Error::BranchNotFound { .. })
+        ));
+
+        let names = |found: Vec<VersionedBranchInfo>| -> Vec<String> {
+            found.into_iter().map(|b| b.info.id.0).collect()
+        };
+        assert_eq!(
+            names(branches.find_by_tag("eval").unwrap()),
+            vec!["run-a", "run-b"]
+        );
+        let filter = BranchFilter::new()
+            .with_tag("eval")
+            .with_metadata("model", "small");
+        assert_eq!(names(branches.query(filter).unwrap()), vec!["run-b"]);
+        assert_eq!(branches.count().unwrap(), 3);
+
+        assert!(branches.remove_tag("run-a", "eval").unwrap());
+        assert_eq!(names(branches.find_by_tag("eval").unwrap()), vec!["run-b"]);
+        let info = db.branch_get("run-b").unwrap().unwrap().info;
+        assert_eq!(info.tags, vec!["eval"]);
+        assert_eq!(info.metadata["model"], Value::String("small".into()));
+    }
+
+    #[test]
+    fn test_branches_close_and_fail_hooks() {
+        use std::sync::{Arc, Mutex};
+
+        let db = create_strata();
+        db.create_branch("run-ok").unwrap();
+        db.create_branch("run-bad").unwrap();
+        let ended = Arc::new(Mutex::new(Vec::new()));
+        let closed = ended.clone();
+        db.branches()
+            .on_close(move |info| {
+                closed
+                    .lock()
+                    .unwrap()
+                    .push((info.id.0.clone(), info.status));
+                Ok(())
+            })
+            .unwrap();
+        let failed = ended.clone();
+        db.branches()
+            .on_fail(move |info| {
+                failed
+                    .lock()
+                    .unwrap()
+                    .push((info.id.0.clone(), info.status));
+                Err(Error::Internal {
+                    reason: "export failed".into(),
+                })
+            })
+            .unwrap();
+
+        db.branches().close("run-ok").unwrap();
+        db.branches().fail("run-bad", "timeout").unwrap();
+        assert!(db.branches().close("run-ok").is_err());
+        assert!(matches!(
+            db.branches().close("default"),
+            Err(Error::ConstraintViolation { .. })
+        ));
+        assert_eq!(
+            *ended.lock().unwrap(),
+            vec![
+                ("run-ok".to_string(), BranchStatus::Completed),
+                ("run-bad".to_string(), BranchStatus::Failed),
+            ]
+        );
+
+        let info = db.branch_get("run-bad").unwrap().unwrap().info;
+        assert_eq!(info.status, BranchStatus::Failed);
+        assert_eq!(info.error.as_deref(), Some("timeout"));
+        assert!(info.completed_at.is_some());
+        let done = db
+            .branches()
+            .query(BranchFilter::new().with_status(BranchStatus::Completed))
+            .unwrap();
+        assert_eq!(done.len(), 1);
+    }
+
+    #[cfg(feature = "vectors")]
+    #[test]
+    fn test_delete_branch_leaves_no_stale_indexes() {
+        let mut db = create_strata();
+        db.create_branch("run").unwrap();
+        db.set_branch("run").unwrap();
+        db.vector_create_collection("vecs", 4u64, DistanceMetric::Cosine)
+            .unwrap();
+        db.vector_upsert("vecs", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
+            .unwrap();
+        db.set_branch("default").unwrap();
+
+        db.delete_branch("run").unwrap();
+        let report = db.maintenance().verify_indexes().unwrap();
+        assert_eq!(report, IndexVerification::default());
+    }
+
     #[test]
     fn test_branches_diff() {
         let mut db = create_strata();
@@ -945,4 +1877,137 @@ mod tests {
             Some(Value::String("base-value".into()))
         );
     }
+
+    #[test]
+    fn test_custom_primitive_follows_branch() {
+        struct Counters;
+        impl crate::CustomPrimitive for Counters {
+            fn id(&self) -> u8 {
+                0xF1
+            }
+            fn name(&self) -> &'static str {
+                "counters"
+            }
+        }
+        crate::register_custom_primitive(Arc::new(Counters)).unwrap();
+
+        let mut db = create_strata();
+        db.custom(0xF1).put(b"hits", Value::Int(1)).unwrap();
+        assert_eq!(db.custom(0xF1).list(b"").unwrap(), vec![b"hits".to_vec()]);
+
+        db.create_branch("other").unwrap();
+        db.set_branch("other").unwrap();
+        assert_eq!(db.custom(0xF1).get(b"hits").unwrap(), None);
+
+        db.set_branch("default").unwrap();
+        assert!(db.custom(0xF1).delete(b"hits").unwrap());
+        assert_eq!(db.custom(0xF1).get(b"hits").unwrap(), None);
+    }
+
+    #[test]
+    fn test_sessions_append_and_window() {
+        let db = create_strata();
+        let sessions = db.sessions();
+        assert_eq!(sessions.len("chat").unwrap(), 0);
+        assert!(sessions.window("chat", 5).unwrap().is_empty());
+
+        for (i, role) in ["user", "assistant", "user"].iter().enumerate() {
+            let index = sessions
+                .append_message("chat", role, &format!("message {}", i))
+                .unwrap();
+            assert_eq!(index, i as u64);
+        }
+        // A session whose id extends another's stays separate
+        sessions.append_message("chat/2", "user", "other").unwrap();
+
+        let window = sessions.window("chat", 2).unwrap();
+        let indices: Vec<u64> = window.iter().map(|m| m.index).collect();
+        assert_eq!(indices, vec![1, 2]);
+        assert_eq!(window[0].role, "assistant");
+        assert_eq!(window[1].content, "message 2");
+        assert_eq!(sessions.window("chat", 10).unwrap().len(), 3);
+        assert_eq!(sessions.len("chat/2").unwrap(), 1);
+        assert_eq!(sessions.list().unwrap(), vec!["chat", "chat/2"]);
+
+        // The transcript is an ordinary event stream
+        let events = db.events().read(SESSION_STREAM, 10).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[1].sequence, window[0].sequence);
+
+        assert!(matches!(
+            sessions.append_message("", "user", "hi"),
+            Err(Error::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sessions_summarize_older_than() {
+        let db = create_strata();
+        let sessions = db.sessions();
+        for i in 0..5 {
+            sessions
+                .append_message("chat", "user", &format!("m{}", i))
+                .unwrap();
+        }
+
+        let summary = sessions
+            .summarize_older_than("chat", 2, |previous, messages| {
+                assert_eq!(previous, None);
+                let contents: Vec<&str> = messages.iter().map(|m| m.content.as_str()).collect();
+                contents.join(",")
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.text, "m0,m1,m2");
+        assert_eq!(summary.through, 3);
+        assert_eq!(sessions.summary("chat").unwrap(), Some(summary));
+
+        // Nothing new beyond the kept window: the hook isn't called
+        let none = sessions
+            .summarize_older_than("chat", 2, |_, _| panic!("nothing to summarize"))
+            .unwrap();
+        assert_eq!(none, None);
+
+        sessions.append_message("chat", "assistant", "m5").unwrap();
+        let summary = sessions
+            .summarize_older_than("chat", 2, |previous, messages| {
+                format!("{}+{}", previous.unwrap(), messages[0].content)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.text, "m0,m1,m2+m3");
+        assert_eq!(summary.through, 4);
+
+        // Messages stay available after summarizing
+        assert_eq!(sessions.len("chat").unwrap(), 6);
+        assert_eq!(sessions.window("chat", 6).unwrap()[0].content, "m0");
+    }
+
+    #[test]
+    fn test_sessions_keys_are_reserved() {
+        let db = create_strata();
+        let mut payload = std::collections::HashMap::new();
+        payload.insert("id".to_string(), Value::Int(1));
+        let sequence = db.event_append("orders", Value::Object(payload)).unwrap();
+        assert_eq!(sequence, 0);
+
+        let sessions = db.sessions();
+        sessions.append_message("chat", "user", "hello").unwrap();
+
+        // User KV can neither reset the count nor see the index
+        assert!(matches!(
+            db.kv_put("_session/chat", Value::Int(0)),
+            Err(Error::InvalidKey { .. })
+        ));
+        assert!(db.kv_put("_session_msg/chat/0", Value::Int(0)).is_err());
+        assert!(db.kv_list(None).unwrap().is_empty());
+
+        // The message continues the existing log
+        let window = sessions.window("chat", 5).unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].sequence, 1);
+        assert_eq!(sessions.append_message("chat", "user", "again").unwrap(), 1);
+        assert_eq!(db.events().read(SESSION_STREAM, 10).unwrap().len(), 2);
+        assert_eq!(db.events().read("orders", 10).unwrap().len(), 1);
+    }
 }
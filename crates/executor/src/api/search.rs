@@ -0,0 +1,152 @@
+//! Search API.
+//!
+//! Access via `db.search()` to find documents by their text. Full-text
+//! search covers KV string values, JSON documents and event payloads in the
+//! current branch and space, ranked with BM25. Hybrid search adds vector
+//! similarity from a collection and fuses both rankings. Semantic search
+//! embeds the query and matches it against values embedded automatically on
+//! write (requires `auto_embed` and the `embed` feature).
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//! db.kv_put("incident:42", "disk failure on node 7")?;
+//!
+//! for hit in db.search().text("disk failure", 10)? {
+//!     println!("{} {} {:.2}: {}", hit.primitive, hit.entity, hit.score, hit.snippet);
+//! }
+//!
+//! // With auto_embed enabled, values are embedded in the background on write
+//! for hit in db.search().semantic("storage hardware problems", 5)? {
+//!     println!("{} {} {:.2}", hit.primitive, hit.entity, hit.score);
+//! }
+//!
+//! // Rerank the 50 closest values and keep the best 5
+//! let hits = db.search().semantic_reranked("storage hardware problems", 5, 50)?;
+//!
+//! let embedding = embed("disk failure");
+//! let hits = db.search().hybrid("incidents", "disk failure", embedding, 10, FusionMethod::Rrf)?;
+//! ```
+
+use super::Strata;
+use crate::types::{FusionMethod, SearchResultHit, TextSearchHit};
+use crate::{Command, Error, Output, Result};
+
+/// Handle for search operations.
+///
+/// Obtained via [`Strata::search()`]. Searches the branch and space that
+/// were current when the handle was created.
+pub struct Search<'a> {
+    strata: &'a Strata,
+}
+
+impl<'a> Search<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Return the `k` best full-text matches for `query`.
+    ///
+    /// A document matches if it contains any query term. Each hit carries a
+    /// snippet around the first match and the byte ranges of the matched
+    /// terms within it. The index is built on the first search and saved
+    /// with the database, so later opens only re-index changed documents.
+    pub fn text(&self, query: &str, k: u64) -> Result<Vec<TextSearchHit>> {
+        match self.strata.executor.execute(Command::TextSearch {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            query: query.to_string(),
+            k: Some(k),
+        })? {
+            Output::TextSearchResults(hits) => Ok(hits),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for TextSearch".into(),
+            }),
+        }
+    }
+
+    /// Return the `k` values closest in meaning to `query`.
+    ///
+    /// The query is embedded with the built-in MiniLM model and compared
+    /// against the KV values, JSON documents, state cells and events that
+    /// auto-embedding has indexed in this space. Embedding runs in the
+    /// background after each write; this call waits for queued writes to be
+    /// embedded before searching. Requires the `embed` feature.
+    pub fn semantic(&self, query: &str, k: u64) -> Result<Vec<SearchResultHit>> {
+        match self.strata.executor.execute(Command::SemanticSearch {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            query: query.to_string(),
+            k: Some(k),
+            rerank_top_n: None,
+        })? {
+            Output::SearchResults(hits) => Ok(hits),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for SemanticSearch".into(),
+            }),
+        }
+    }
+
+    /// Like [`semantic`](Self::semantic), but rerank the `rerank_top_n`
+    /// closest values with a cross-encoder and return the best `k` of them.
+    ///
+    /// The reranker reads the query and each candidate's passage together,
+    /// which ranks better than comparing embeddings. Hit scores are the
+    /// reranker's relevance probabilities in `[0, 1]`, comparable across
+    /// queries. The reranker is the built-in MiniLM-L6 cross-encoder or the
+    /// endpoint set by the `[rerank]` config section or
+    /// [`OpenOptions::rerank_provider`](crate::OpenOptions::rerank_provider).
+    pub fn semantic_reranked(
+        &self,
+        query: &str,
+        k: u64,
+        rerank_top_n: u64,
+    ) -> Result<Vec<SearchResultHit>> {
+        match self.strata.executor.execute(Command::SemanticSearch {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            query: query.to_string(),
+            k: Some(k),
+            rerank_top_n: Some(rerank_top_n),
+        })? {
+            Output::SearchResults(hits) => Ok(hits),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for SemanticSearch".into(),
+            }),
+        }
+    }
+
+    /// Return the `k` best documents by text relevance and vector similarity.
+    ///
+    /// Runs `query` against the full-text index and `embedding` against
+    /// `collection`, then fuses both rankings with `fusion`. A vector whose
+    /// key matches a KV key or JSON document id in the space counts as that
+    /// document, so a chunk stored under the same key as its embedding is
+    /// ranked once using both signals.
+    pub fn hybrid(
+        &self,
+        collection: &str,
+        query: &str,
+        embedding: Vec<f32>,
+        k: u64,
+        fusion: FusionMethod,
+    ) -> Result<Vec<SearchResultHit>> {
+        match self.strata.executor.execute(Command::HybridSearch {
+            branch: self.strata.branch_id(),
+            space: self.strata.space_id(),
+            collection: collection.to_string(),
+            query: query.to_string(),
+            embedding,
+            k: Some(k),
+            fusion: Some(fusion),
+        })? {
+            Output::SearchResults(hits) => Ok(hits),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for HybridSearch".into(),
+            }),
+        }
+    }
+}
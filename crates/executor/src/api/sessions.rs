@@ -0,0 +1,308 @@
+//! Conversation session API.
+//!
+//! Access via `db.sessions()` to keep chat transcripts for agents: append
+//! messages as they happen, read back the last few for the prompt, and fold
+//! older ones into a rolling summary produced by your own model.
+//!
+//! Messages are events on the `session.message` stream, so the transcript
+//! is append-only and can be consumed with consumer groups like any other
+//! stream. Each session's message count, summary and message index are
+//! kept in KV under the reserved `_session/` and `_session_msg/` prefixes,
+//! which user keys can't touch.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//!
+//! db.sessions().append_message("chat-42", "user", "What's the weather in Oslo?")?;
+//! db.sessions().append_message("chat-42", "assistant", "Cloudy, 8°C.")?;
+//!
+//! // Keep the newest 20 messages verbatim; summarize the rest
+//! db.sessions().summarize_older_than("chat-42", 20, |previous, messages| {
+//!     llm_summarize(previous, messages)
+//! })?;
+//!
+//! // Prompt context: the summary, then the last 20 messages
+//! let summary = db.sessions().summary("chat-42")?;
+//! let recent = db.sessions().window("chat-42", 20)?;
+//! ```
+
+use std::collections::HashMap;
+
+use strata_core::key::{SESSION_MESSAGE_PREFIX, SESSION_PREFIX};
+use strata_core::types::{Key, Namespace};
+use strata_core::StrataResult;
+use strata_engine::{Transaction, TransactionContext, TransactionOps};
+use strata_security::AccessMode;
+
+use super::Strata;
+use crate::bridge::{extract_version, to_core_branch_id};
+use crate::convert::convert_result;
+use crate::types::{SessionMessage, SessionSummary};
+use crate::{Error, Result, Value};
+
+/// Stream the messages of every session are appended to.
+pub const SESSION_STREAM: &str = "session.message";
+
+/// Handle for conversation sessions.
+///
+/// Obtained via [`Strata::sessions()`]. Operates on the branch and space
+/// that were current when the handle was created.
+pub struct Sessions<'a> {
+    strata: &'a Strata,
+}
+
+/// Per-session record kept in KV.
+#[derive(Default)]
+struct Meta {
+    count: u64,
+    summary: Option<String>,
+    summarized_through: u64,
+}
+
+impl Meta {
+    fn from_value(value: Option<Value>) -> Self {
+        let Some(Value::Object(fields)) = value else {
+            return Meta::default();
+        };
+        let uint = |name: &str| match fields.get(name) {
+            Some(Value::Int(n)) => *n as u64,
+            _ => 0,
+        };
+        Meta {
+            count: uint("count"),
+            summary: match fields.get("summary") {
+                Some(Value::String(s)) => Some(s.clone()),
+                _ => None,
+            },
+            summarized_through: uint("summarized_through"),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        let mut fields = HashMap::new();
+        fields.insert("count".to_string(), Value::Int(self.count as i64));
+        fields.insert(
+            "summary".to_string(),
+            self.summary.map_or(Value::Null, Value::String),
+        );
+        fields.insert(
+            "summarized_through".to_string(),
+            Value::Int(self.summarized_through as i64),
+        );
+        Value::Object(fields)
+    }
+}
+
+fn meta_key(session_id: &str) -> String {
+    format!("{}{}", SESSION_PREFIX, session_id)
+}
+
+/// Index entry of a message; the fixed-width suffix keeps sessions whose
+/// ids share a prefix apart.
+fn message_key(session_id: &str, index: u64) -> String {
+    format!("{}{}/{:020}", SESSION_MESSAGE_PREFIX, session_id, index)
+}
+
+impl<'a> Sessions<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Append a message to `session_id`, creating the session on its first
+    /// message. Returns the message's index in the session.
+    ///
+    /// The event, its index entry and the new message count are written in
+    /// one transaction, so concurrent appends to the same session conflict
+    /// rather than reuse an index, and a failed append leaves no trace.
+    pub fn append_message(&self, session_id: &str, role: &str, content: &str) -> Result<u64> {
+        if session_id.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "session id must not be empty".into(),
+            });
+        }
+        self.check_writable("SessionAppend")?;
+        self.transaction(|txn, ns| {
+            let key = Key::new_kv(ns.clone(), meta_key(session_id));
+            let mut meta = Meta::from_value(txn.get(&key)?);
+            let index = meta.count;
+
+            let mut payload = HashMap::new();
+            payload.insert("session".to_string(), Value::from(session_id));
+            payload.insert("index".to_string(), Value::Int(index as i64));
+            payload.insert("role".to_string(), Value::from(role));
+            payload.insert("content".to_string(), Value::from(content));
+            let version = Transaction::new(txn, ns.clone())
+                .event_append(SESSION_STREAM, Value::Object(payload))?;
+
+            let sequence = extract_version(&version) as i64;
+            txn.put(
+                Key::new_kv(ns.clone(), message_key(session_id, index)),
+                Value::Int(sequence),
+            )?;
+            meta.count += 1;
+            txn.put(key, meta.into_value())?;
+            Ok(index)
+        })
+    }
+
+    /// The last `n` messages of `session_id`, oldest first.
+    ///
+    /// Messages whose events were removed by a retention policy are
+    /// skipped. An unknown session has no messages.
+    pub fn window(&self, session_id: &str, n: u64) -> Result<Vec<SessionMessage>> {
+        let count = self.meta(session_id)?.count;
+        self.read(session_id, count.saturating_sub(n), count)
+    }
+
+    /// Number of messages in `session_id`.
+    pub fn len(&self, session_id: &str) -> Result<u64> {
+        Ok(self.meta(session_id)?.count)
+    }
+
+    /// Ids of every session, in key order.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let entries =
+            self.transaction(|txn, ns| txn.scan_prefix(&Key::new_kv(ns.clone(), SESSION_PREFIX)))?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(key, _)| key.user_key_string())
+            .filter_map(|key| key.strip_prefix(SESSION_PREFIX).map(str::to_string))
+            .collect())
+    }
+
+    /// The current summary of `session_id`, if one has been made.
+    pub fn summary(&self, session_id: &str) -> Result<Option<SessionSummary>> {
+        let meta = self.meta(session_id)?;
+        Ok(meta.summary.map(|text| SessionSummary {
+            text,
+            through: meta.summarized_through,
+        }))
+    }
+
+    /// Fold every message older than the newest `keep_last` into the
+    /// session's summary.
+    ///
+    /// `summarize` is called with the previous summary, if any, and the
+    /// messages not yet covered by it, and returns the new summary. It is
+    /// not called when there is nothing new to summarize, and `None` is
+    /// returned. The messages themselves are kept; only the summary and
+    /// the count it covers are updated.
+    ///
+    /// Returns [`Error::Conflict`] if another summary of the session was
+    /// stored while `summarize` ran.
+    pub fn summarize_older_than<F>(
+        &self,
+        session_id: &str,
+        keep_last: u64,
+        summarize: F,
+    ) -> Result<Option<SessionSummary>>
+    where
+        F: FnOnce(Option<&str>, &[SessionMessage]) -> String,
+    {
+        let meta = self.meta(session_id)?;
+        let through = meta.count.saturating_sub(keep_last);
+        if through <= meta.summarized_through {
+            return Ok(None);
+        }
+        let messages = self.read(session_id, meta.summarized_through, through)?;
+        let text = summarize(meta.summary.as_deref(), &messages);
+
+        self.check_writable("SessionSummarize")?;
+        self.transaction(|txn, ns| {
+            let key = Key::new_kv(ns.clone(), meta_key(session_id));
+            let mut current = Meta::from_value(txn.get(&key)?);
+            if current.summarized_through != meta.summarized_through {
+                return Err(strata_core::StrataError::conflict(format!(
+                    "session {} was summarized concurrently",
+                    session_id
+                )));
+            }
+            current.summary = Some(text.clone());
+            current.summarized_through = through;
+            txn.put(key, current.into_value())
+        })?;
+        Ok(Some(SessionSummary { text, through }))
+    }
+
+    fn meta(&self, session_id: &str) -> Result<Meta> {
+        let value =
+            self.transaction(|txn, ns| txn.get(&Key::new_kv(ns.clone(), meta_key(session_id))))?;
+        Ok(Meta::from_value(value))
+    }
+
+    /// Messages `from..to` of `session_id`.
+    fn read(&self, session_id: &str, from: u64, to: u64) -> Result<Vec<SessionMessage>> {
+        let sequences = self.transaction(|txn, ns| {
+            let mut sequences = Vec::with_capacity(to.saturating_sub(from) as usize);
+            for index in from..to {
+                if let Some(Value::Int(sequence)) =
+                    txn.get(&Key::new_kv(ns.clone(), message_key(session_id, index)))?
+                {
+                    sequences.push((index, sequence as u64));
+                }
+            }
+            Ok(sequences)
+        })?;
+
+        let mut messages = Vec::with_capacity(sequences.len());
+        for (index, sequence) in sequences {
+            let Some(event) = self.strata.event_get(sequence)? else {
+                continue;
+            };
+            let text = |name: &str| match &event.value {
+                Value::Object(fields) => match fields.get(name) {
+                    Some(Value::String(s)) => s.clone(),
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            };
+            messages.push(SessionMessage {
+                index,
+                role: text("role"),
+                content: text("content"),
+                sequence,
+                timestamp: event.timestamp,
+            });
+        }
+        Ok(messages)
+    }
+
+    /// Reject a write through a read-only handle, as the executor does
+    /// for write commands.
+    fn check_writable(&self, command: &str) -> Result<()> {
+        if self.strata.access_mode == AccessMode::ReadOnly {
+            return Err(Error::AccessDenied {
+                command: command.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Run `f` in a transaction on the handle's branch, with the namespace
+    /// of its space.
+    ///
+    /// Session keys are reserved, so they are read and written here as raw
+    /// storage keys rather than through KV commands.
+    fn transaction<T>(
+        &self,
+        f: impl FnOnce(&mut TransactionContext, &Namespace) -> StrataResult<T>,
+    ) -> Result<T> {
+        let branch = to_core_branch_id(&self.strata.branch_id().unwrap_or_default())?;
+        let space = self
+            .strata
+            .space_id()
+            .unwrap_or_else(|| "default".to_string());
+        let ns = Namespace::for_branch_space(branch, &space);
+        convert_result(
+            self.strata
+                .executor()
+                .primitives()
+                .db
+                .transaction(branch, |txn| f(txn, &ns)),
+        )
+    }
+}
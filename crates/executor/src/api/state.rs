@@ -51,6 +51,7 @@ impl Strata {
             space: self.space_id(),
             as_of: None,
             cell: cell.to_string(),
+            limit: None,
         })? {
             Output::VersionHistory(h) => Ok(h),
             _ => Err(Error::Internal {
@@ -59,6 +60,49 @@ impl Strata {
         }
     }
 
+    /// Get the newest `limit` versions of a state cell, newest first.
+    ///
+    /// Returns an empty list if the cell doesn't exist.
+    pub fn state_history(
+        &self,
+        cell: &str,
+        limit: u64,
+    ) -> Result<Vec<crate::types::VersionedValue>> {
+        match self.executor.execute(Command::StateGetv {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            as_of: None,
+            cell: cell.to_string(),
+            limit: Some(limit),
+        })? {
+            Output::VersionHistory(h) => Ok(h.unwrap_or_default()),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateGetv".into(),
+            }),
+        }
+    }
+
+    /// Read a state cell as it was at a given version.
+    ///
+    /// Returns None if the cell never had that version.
+    pub fn state_get_version(
+        &self,
+        cell: &str,
+        version: u64,
+    ) -> Result<Option<crate::types::VersionedValue>> {
+        match self.executor.execute(Command::StateGetVersion {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            cell: cell.to_string(),
+            version,
+        })? {
+            Output::MaybeVersioned(v) => Ok(v),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateGetVersion".into(),
+            }),
+        }
+    }
+
     /// Compare-and-swap on a state cell.
     pub fn state_cas(
         &self,
@@ -94,4 +138,43 @@ impl Strata {
             }),
         }
     }
+
+    /// Atomically add `delta` to an integer state cell.
+    ///
+    /// A missing cell counts as zero and is created. Concurrent increments
+    /// are never lost. Returns the new value with its version counter.
+    pub fn state_incr(&self, cell: &str, delta: i64) -> Result<crate::types::VersionedValue> {
+        self.state_add(cell, Value::Int(delta))
+    }
+
+    /// Atomically subtract `delta` from an integer state cell.
+    ///
+    /// Same as [`state_incr`](Self::state_incr) with the delta negated.
+    pub fn state_decr(&self, cell: &str, delta: i64) -> Result<crate::types::VersionedValue> {
+        let delta = delta.checked_neg().ok_or_else(|| Error::InvalidInput {
+            reason: format!("cannot decrement by {}", delta),
+        })?;
+        self.state_add(cell, Value::Int(delta))
+    }
+
+    /// Atomically add a float `delta` to a numeric state cell.
+    ///
+    /// The cell's value becomes a float.
+    pub fn state_add_float(&self, cell: &str, delta: f64) -> Result<crate::types::VersionedValue> {
+        self.state_add(cell, Value::Float(delta))
+    }
+
+    fn state_add(&self, cell: &str, delta: Value) -> Result<crate::types::VersionedValue> {
+        match self.executor.execute(Command::StateIncr {
+            branch: self.branch_id(),
+            space: self.space_id(),
+            cell: cell.to_string(),
+            delta,
+        })? {
+            Output::MaybeVersioned(Some(vv)) => Ok(vv),
+            _ => Err(Error::Internal {
+                reason: "Unexpected output for StateIncr".into(),
+            }),
+        }
+    }
 }
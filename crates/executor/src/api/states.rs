@@ -0,0 +1,63 @@
+//! State cell power API.
+//!
+//! Access via `db.state()` to watch a cell for changes instead of polling it,
+//! for coordination patterns such as leader election or config fan-out.
+//!
+//! # Example
+//!
+//! ```text
+//! use strata_executor::Strata;
+//!
+//! let db = Strata::open("/path/to/data")?;
+//!
+//! let changes = db.state().watch("status")?;
+//! std::thread::spawn(move || {
+//!     for change in changes {
+//!         println!("status is now {:?} (v{})", change.value, change.version);
+//!     }
+//! });
+//!
+//! db.state_set("status", "ready")?;
+//! ```
+
+use std::sync::mpsc::{channel, Receiver};
+
+use super::Strata;
+use crate::convert::convert_result;
+use crate::types::VersionedValue;
+use crate::{bridge, Result};
+
+/// Handle for state cell operations.
+///
+/// Obtained via [`Strata::state()`]. Operates on the branch and space that
+/// were current when the handle was created.
+pub struct States<'a> {
+    strata: &'a Strata,
+}
+
+impl<'a> States<'a> {
+    pub(crate) fn new(strata: &'a Strata) -> Self {
+        Self { strata }
+    }
+
+    /// Watch `cell` for changes.
+    ///
+    /// The returned channel receives the new value, version and timestamp
+    /// after every successful set or CAS of the cell, including the set that
+    /// creates it. Drop the receiver to stop watching. Writes made inside an
+    /// explicit [`Session`](crate::Session) transaction are not reported,
+    /// and concurrent writers may report out of version order.
+    pub fn watch(&self, cell: &str) -> Result<Receiver<VersionedValue>> {
+        let branch_id = bridge::to_core_branch_id(&self.strata.current_branch)?;
+        convert_result(bridge::validate_key(cell))?;
+
+        let (tx, rx) = channel();
+        convert_result(self.strata.executor.primitives().state.watch(
+            &branch_id,
+            &self.strata.current_space,
+            cell,
+            move |change| tx.send(bridge::to_versioned_value(change.clone())).is_ok(),
+        ))?;
+        Ok(rx)
+    }
+}
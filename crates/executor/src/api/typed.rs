@@ -0,0 +1,35 @@
+//! Conversions between Rust types and [`Value`] for the typed accessors.
+//!
+//! Types go through `serde_json::Value` on their way to and from the
+//! Value model, so anything that serializes to JSON can be stored with
+//! `kv_put_t`/`json_set_t` and read back with `kv_get_t`/`json_get_t`.
+
+use std::any::type_name;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{Error, Result, Value};
+
+/// Convert `value` into a [`Value`] for storing under `key`.
+pub(super) fn to_value<T: Serialize + ?Sized>(key: &str, value: &T) -> Result<Value> {
+    let reason = |e: &dyn std::fmt::Display| Error::Serialization {
+        reason: format!("cannot store {} at '{}': {}", type_name::<T>(), key, e),
+    };
+    let json = serde_json::to_value(value).map_err(|e| reason(&e))?;
+    Value::try_from(&json).map_err(|e| reason(&e))
+}
+
+/// Convert the [`Value`] stored under `key` into a `T`.
+///
+/// A shape mismatch names the key, the target type and the offending field.
+pub(super) fn from_value<T: DeserializeOwned>(key: &str, value: Value) -> Result<T> {
+    serde_json::from_value(serde_json::Value::from(value)).map_err(|e| Error::Serialization {
+        reason: format!(
+            "value at '{}' is not a valid {}: {}",
+            key,
+            type_name::<T>(),
+            e
+        ),
+    })
+}
@@ -10,14 +10,14 @@
 
 use std::sync::Arc;
 
+use strata_core::key::ensure_not_reserved;
 use strata_core::limits::Limits;
 use strata_core::primitives::json::{JsonPath, JsonValue};
 use strata_core::{StrataError, StrataResult, Value};
 use strata_engine::{
     BranchIndex as PrimitiveBranchIndex, Database, EventLog as PrimitiveEventLog,
-    JsonStore as PrimitiveJsonStore, KVStore as PrimitiveKVStore,
+    JsonStore as PrimitiveJsonStore, KVStore as PrimitiveKVStore, ListStore as PrimitiveListStore,
     SpaceIndex as PrimitiveSpaceIndex, StateCell as PrimitiveStateCell,
-    VectorStore as PrimitiveVectorStore,
 };
 
 use crate::types::BranchId;
@@ -42,10 +42,13 @@ pub struct Primitives {
     pub event: PrimitiveEventLog,
     /// State primitive
     pub state: PrimitiveStateCell,
+    /// List primitive
+    pub list: PrimitiveListStore,
     /// Branch primitive
     pub branch: PrimitiveBranchIndex,
     /// Vector primitive
-    pub vector: PrimitiveVectorStore,
+    #[cfg(feature = "vectors")]
+    pub vector: strata_engine::VectorStore,
     /// Space primitive
     pub space: PrimitiveSpaceIndex,
     /// Size limits for keys, values, and vectors
@@ -60,8 +63,10 @@ impl Primitives {
             json: PrimitiveJsonStore::new(db.clone()),
             event: PrimitiveEventLog::new(db.clone()),
             state: PrimitiveStateCell::new(db.clone()),
+            list: PrimitiveListStore::new(db.clone()),
             branch: PrimitiveBranchIndex::new(db.clone()),
-            vector: PrimitiveVectorStore::new(db.clone()),
+            #[cfg(feature = "vectors")]
+            vector: strata_engine::VectorStore::new(db.clone()),
             space: PrimitiveSpaceIndex::new(db.clone()),
             db,
             limits: Limits::default(),
@@ -105,13 +110,11 @@ pub fn to_core_branch_id(branch: &BranchId) -> crate::Result<strata_core::types:
 // Validation Helpers
 // =============================================================================
 
-/// Reserved key prefix that users cannot use.
-const RESERVED_KEY_PREFIX: &str = "_strata/";
-
 /// Validate a KV/JSON key.
 ///
-/// Keys must be non-empty, contain no NUL bytes, not start with `_strata/`,
-/// and not exceed the configured maximum key length.
+/// Keys must be non-empty, contain no NUL bytes, not start with a reserved
+/// prefix (see `strata_core::key::RESERVED_PREFIXES`), and not exceed the
+/// configured maximum key length.
 pub fn validate_key(key: &str) -> StrataResult<()> {
     validate_key_with_limits(key, &Limits::default())
 }
@@ -127,13 +130,7 @@ pub fn validate_key_with_limits(key: &str, limits: &Limits) -> StrataResult<()>
     if key.contains('\0') {
         return Err(StrataError::invalid_input("Key must not contain NUL bytes"));
     }
-    if key.starts_with(RESERVED_KEY_PREFIX) {
-        return Err(StrataError::invalid_input(format!(
-            "Key must not start with reserved prefix '{}'",
-            RESERVED_KEY_PREFIX
-        )));
-    }
-    Ok(())
+    ensure_not_reserved(key)
 }
 
 /// Validate a value against size limits.
@@ -142,6 +139,7 @@ pub fn validate_value(value: &Value, limits: &Limits) -> StrataResult<()> {
 }
 
 /// Validate a vector against dimension limits.
+#[cfg(feature = "vectors")]
 pub fn validate_vector(vec: &[f32], limits: &Limits) -> StrataResult<()> {
     limits.validate_vector(vec).map_err(limit_error_to_strata)
 }
@@ -151,11 +149,13 @@ fn limit_error_to_strata(e: strata_core::limits::LimitError) -> StrataError {
     StrataError::capacity_exceeded(e.reason_code(), e.max(), e.actual())
 }
 /// Check if a collection name is internal (starts with `_`).
+#[cfg(feature = "vectors")]
 pub fn is_internal_collection(name: &str) -> bool {
     name.starts_with('_')
 }
 
 /// Validate that a collection name is not internal.
+#[cfg(feature = "vectors")]
 pub fn validate_not_internal_collection(name: &str) -> StrataResult<()> {
     if is_internal_collection(name) {
         return Err(StrataError::invalid_input(format!(
@@ -315,6 +315,7 @@ pub fn to_versioned_value(v: strata_core::Versioned<Value>) -> crate::types::Ver
 // =============================================================================
 
 /// Convert executor DistanceMetric to engine DistanceMetric.
+#[cfg(feature = "vectors")]
 pub fn to_engine_metric(metric: crate::types::DistanceMetric) -> strata_engine::DistanceMetric {
     match metric {
         crate::types::DistanceMetric::Cosine => strata_engine::DistanceMetric::Cosine,
@@ -324,6 +325,7 @@ pub fn to_engine_metric(metric: crate::types::DistanceMetric) -> strata_engine::
 }
 
 /// Convert engine DistanceMetric to executor DistanceMetric.
+#[cfg(feature = "vectors")]
 pub fn from_engine_metric(metric: strata_engine::DistanceMetric) -> crate::types::DistanceMetric {
     match metric {
         strata_engine::DistanceMetric::Cosine => crate::types::DistanceMetric::Cosine,
@@ -332,56 +334,265 @@ pub fn from_engine_metric(metric: strata_engine::DistanceMetric) -> crate::types
     }
 }
 
+// =============================================================================
+// StorageDtype Conversion
+// =============================================================================
+
+/// Convert executor StorageDtype to engine StorageDtype.
+#[cfg(feature = "vectors")]
+pub fn to_engine_dtype(dtype: crate::types::StorageDtype) -> strata_engine::StorageDtype {
+    match dtype {
+        crate::types::StorageDtype::F32 => strata_engine::StorageDtype::F32,
+        crate::types::StorageDtype::F16 => strata_engine::StorageDtype::F16,
+        crate::types::StorageDtype::Int8 => strata_engine::StorageDtype::Int8,
+    }
+}
+
+/// Convert engine StorageDtype to executor StorageDtype.
+#[cfg(feature = "vectors")]
+pub fn from_engine_dtype(dtype: strata_engine::StorageDtype) -> crate::types::StorageDtype {
+    match dtype {
+        strata_engine::StorageDtype::F32 => crate::types::StorageDtype::F32,
+        strata_engine::StorageDtype::F16 => crate::types::StorageDtype::F16,
+        strata_engine::StorageDtype::Int8 => crate::types::StorageDtype::Int8,
+    }
+}
+
+/// Convert executor VectorIndexType to an engine backend factory.
+#[cfg(feature = "vectors")]
+pub fn to_engine_index(
+    index_type: crate::types::VectorIndexType,
+) -> strata_engine::IndexBackendFactory {
+    match index_type {
+        crate::types::VectorIndexType::BruteForce => strata_engine::IndexBackendFactory::BruteForce,
+        crate::types::VectorIndexType::Hnsw => {
+            strata_engine::IndexBackendFactory::Hnsw(strata_engine::HnswConfig::default())
+        }
+    }
+}
+
+// =============================================================================
+// ImportConflict Conversion
+// =============================================================================
+
+/// Convert executor ImportConflict to engine ImportConflict.
+pub fn to_engine_import_conflict(
+    policy: crate::types::ImportConflict,
+) -> strata_engine::ImportConflict {
+    match policy {
+        crate::types::ImportConflict::Fail => strata_engine::ImportConflict::Fail,
+        crate::types::ImportConflict::Skip => strata_engine::ImportConflict::Skip,
+        crate::types::ImportConflict::Rename => strata_engine::ImportConflict::Rename,
+        crate::types::ImportConflict::Overwrite => strata_engine::ImportConflict::Overwrite,
+    }
+}
+
+// =============================================================================
+// RetentionPolicy Conversion
+// =============================================================================
+
+/// Convert executor RetentionPolicy to engine RetentionPolicy.
+pub fn to_engine_retention(
+    policy: crate::types::RetentionPolicy,
+) -> strata_engine::RetentionPolicy {
+    match policy {
+        crate::types::RetentionPolicy::KeepAll => strata_engine::RetentionPolicy::KeepAll,
+        crate::types::RetentionPolicy::MaxLen(n) => {
+            strata_engine::RetentionPolicy::KeepLast(n as usize)
+        }
+        crate::types::RetentionPolicy::MaxAgeSecs(secs) => {
+            strata_engine::RetentionPolicy::KeepFor(std::time::Duration::from_secs(secs))
+        }
+    }
+}
+
+/// Convert engine RetentionPolicy to executor RetentionPolicy.
+///
+/// Composite policies are never stored per stream or prefix; they map to
+/// `KeepAll`.
+pub fn from_engine_retention(
+    policy: &strata_engine::RetentionPolicy,
+) -> crate::types::RetentionPolicy {
+    match policy {
+        strata_engine::RetentionPolicy::KeepLast(n) => {
+            crate::types::RetentionPolicy::MaxLen(*n as u64)
+        }
+        strata_engine::RetentionPolicy::KeepFor(d) => {
+            crate::types::RetentionPolicy::MaxAgeSecs(d.as_secs())
+        }
+        strata_engine::RetentionPolicy::KeepAll
+        | strata_engine::RetentionPolicy::Composite { .. } => {
+            crate::types::RetentionPolicy::KeepAll
+        }
+    }
+}
+
+/// Convert executor BranchRetention to an engine RetentionPolicy.
+///
+/// Overrides become a `Composite` policy.
+pub fn to_engine_branch_retention(
+    retention: &crate::types::BranchRetention,
+) -> strata_engine::RetentionPolicy {
+    let default = to_engine_retention(retention.default);
+    if retention.overrides.is_empty() {
+        return default;
+    }
+    retention
+        .overrides
+        .iter()
+        .fold(
+            strata_engine::RetentionPolicy::composite(default),
+            |builder, (primitive, policy)| {
+                builder.with_override(*primitive, to_engine_retention(*policy))
+            },
+        )
+        .build()
+}
+
+/// Convert an engine branch RetentionPolicy to executor BranchRetention.
+pub fn from_engine_branch_retention(
+    policy: &strata_engine::RetentionPolicy,
+) -> crate::types::BranchRetention {
+    match policy {
+        strata_engine::RetentionPolicy::Composite { default, overrides } => {
+            crate::types::BranchRetention {
+                default: from_engine_retention(default),
+                overrides: overrides
+                    .iter()
+                    .map(|(primitive, policy)| (*primitive, from_engine_retention(policy)))
+                    .collect(),
+            }
+        }
+        other => crate::types::BranchRetention::new(from_engine_retention(other)),
+    }
+}
+
 // =============================================================================
 // SearchFilter Conversion
 // =============================================================================
 
 /// Convert executor MetadataFilter list to engine MetadataFilter.
+///
+/// Returns `InvalidInput` for a malformed `Range` or an `Or`/`Not` without
+/// sub-filters.
+#[cfg(feature = "vectors")]
 pub fn to_engine_filter(
     filters: &[crate::types::MetadataFilter],
-) -> Option<strata_engine::MetadataFilter> {
+) -> crate::Result<Option<strata_engine::MetadataFilter>> {
     if filters.is_empty() {
-        return None;
+        return Ok(None);
     }
 
+    let engine_filter = build_engine_filter(filters)?;
+    if engine_filter.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(engine_filter))
+    }
+}
+
+/// Build an engine filter matching when all of `filters` match.
+#[cfg(feature = "vectors")]
+fn build_engine_filter(
+    filters: &[crate::types::MetadataFilter],
+) -> crate::Result<strata_engine::MetadataFilter> {
+    use crate::types::FilterOp;
+
     let mut engine_filter = strata_engine::MetadataFilter::new();
 
     for f in filters {
-        let scalar = value_to_json_scalar(&f.value);
         match f.op {
-            crate::types::FilterOp::Eq => {
-                engine_filter.equals.insert(f.field.clone(), scalar);
+            FilterOp::Eq => {
+                engine_filter
+                    .equals
+                    .insert(f.field.clone(), value_to_json_scalar(&f.value));
             }
-            _ => {
+            FilterOp::In => {
+                // One condition per element; the engine ORs In conditions on a field
+                let values = match &f.value {
+                    Value::Array(values) => values.iter().map(value_to_json_scalar).collect(),
+                    other => vec![value_to_json_scalar(other)],
+                };
+                engine_filter = engine_filter.in_values(f.field.clone(), values);
+            }
+            FilterOp::Range => {
+                let (min, max) = match &f.value {
+                    Value::Array(bounds) if bounds.len() == 2 => (&bounds[0], &bounds[1]),
+                    _ => {
+                        return Err(crate::Error::InvalidInput {
+                            reason: format!(
+                                "range filter on '{}' needs a [min, max] array",
+                                f.field
+                            ),
+                        })
+                    }
+                };
+                engine_filter = engine_filter.range(
+                    f.field.clone(),
+                    value_to_json_scalar(min),
+                    value_to_json_scalar(max),
+                );
+            }
+            FilterOp::Or => {
+                require_sub_filters(f, "or")?;
+                let alternatives = f
+                    .filters
+                    .iter()
+                    .map(|sub| build_engine_filter(std::slice::from_ref(sub)))
+                    .collect::<crate::Result<Vec<_>>>()?;
+                engine_filter = engine_filter.or(alternatives);
+            }
+            FilterOp::Not => {
+                require_sub_filters(f, "not")?;
+                engine_filter = engine_filter.not(build_engine_filter(&f.filters)?);
+            }
+            FilterOp::Ne
+            | FilterOp::Gt
+            | FilterOp::Gte
+            | FilterOp::Lt
+            | FilterOp::Lte
+            | FilterOp::Contains
+            | FilterOp::Prefix => {
                 let engine_op = match f.op {
-                    crate::types::FilterOp::Eq => strata_engine::FilterOp::Eq,
-                    crate::types::FilterOp::Ne => strata_engine::FilterOp::Ne,
-                    crate::types::FilterOp::Gt => strata_engine::FilterOp::Gt,
-                    crate::types::FilterOp::Gte => strata_engine::FilterOp::Gte,
-                    crate::types::FilterOp::Lt => strata_engine::FilterOp::Lt,
-                    crate::types::FilterOp::Lte => strata_engine::FilterOp::Lte,
-                    crate::types::FilterOp::In => strata_engine::FilterOp::In,
-                    crate::types::FilterOp::Contains => strata_engine::FilterOp::Contains,
+                    FilterOp::Ne => strata_engine::FilterOp::Ne,
+                    FilterOp::Gt => strata_engine::FilterOp::Gt,
+                    FilterOp::Gte => strata_engine::FilterOp::Gte,
+                    FilterOp::Lt => strata_engine::FilterOp::Lt,
+                    FilterOp::Lte => strata_engine::FilterOp::Lte,
+                    FilterOp::Contains => strata_engine::FilterOp::Contains,
+                    FilterOp::Prefix => strata_engine::FilterOp::Prefix,
+                    FilterOp::Eq
+                    | FilterOp::In
+                    | FilterOp::Range
+                    | FilterOp::Or
+                    | FilterOp::Not => unreachable!("handled above"),
                 };
                 engine_filter
                     .conditions
                     .push(strata_engine::FilterCondition {
                         field: f.field.clone(),
                         op: engine_op,
-                        value: scalar,
+                        value: value_to_json_scalar(&f.value),
                     });
             }
         }
     }
 
-    if engine_filter.is_empty() {
-        None
-    } else {
-        Some(engine_filter)
+    Ok(engine_filter)
+}
+
+#[cfg(feature = "vectors")]
+fn require_sub_filters(filter: &crate::types::MetadataFilter, op: &str) -> crate::Result<()> {
+    if filter.filters.is_empty() {
+        return Err(crate::Error::InvalidInput {
+            reason: format!("{} filter needs at least one sub-filter", op),
+        });
     }
+    Ok(())
 }
 
 /// Convert a Value to a JsonScalar for vector metadata filtering.
+#[cfg(feature = "vectors")]
 fn value_to_json_scalar(value: &Value) -> strata_engine::JsonScalar {
     match value {
         Value::Null => strata_engine::JsonScalar::Null,
@@ -403,6 +614,27 @@ pub fn from_engine_branch_status(
 ) -> crate::types::BranchStatus {
     match status {
         strata_engine::BranchStatus::Active => crate::types::BranchStatus::Active,
+        strata_engine::BranchStatus::Completed => crate::types::BranchStatus::Completed,
+        strata_engine::BranchStatus::Failed => crate::types::BranchStatus::Failed,
+    }
+}
+
+/// Convert executor BranchStatus to engine BranchStatus.
+pub fn to_engine_branch_status(status: crate::types::BranchStatus) -> strata_engine::BranchStatus {
+    match status {
+        crate::types::BranchStatus::Active => strata_engine::BranchStatus::Active,
+        crate::types::BranchStatus::Completed => strata_engine::BranchStatus::Completed,
+        crate::types::BranchStatus::Failed => strata_engine::BranchStatus::Failed,
+    }
+}
+
+/// Convert executor BranchFilter to engine BranchFilter.
+pub fn to_engine_branch_filter(filter: crate::types::BranchFilter) -> strata_engine::BranchFilter {
+    strata_engine::BranchFilter {
+        status: filter.status.map(to_engine_branch_status),
+        created_after: filter.created_after,
+        tag: filter.tag,
+        metadata: filter.metadata,
     }
 }
 
@@ -411,11 +643,13 @@ pub fn from_engine_branch_status(
 // =============================================================================
 
 /// Convert `Value` to `serde_json::Value` for vector metadata storage.
+#[cfg(feature = "vectors")]
 pub fn value_to_serde_json_public(value: Value) -> StrataResult<serde_json::Value> {
     value_to_serde_json(value)
 }
 
 /// Convert `serde_json::Value` to `Value` for vector metadata retrieval.
+#[cfg(feature = "vectors")]
 pub fn serde_json_to_value_public(json: serde_json::Value) -> StrataResult<Value> {
     serde_json_to_value(json)
 }
@@ -469,6 +703,11 @@ mod tests {
     #[test]
     fn test_validate_key_reserved() {
         assert!(validate_key("_strata/internal").is_err());
+        assert!(matches!(
+            validate_key("__idx_status__Active"),
+            Err(StrataError::ReservedKey { .. })
+        ));
+        assert!(validate_key("_private").is_ok());
     }
 
     #[test]
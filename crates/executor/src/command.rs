@@ -25,13 +25,14 @@ use crate::types::*;
 /// |----------|-------|-------------|
 /// | KV | 4 | Key-value operations |
 /// | JSON | 17 | JSON document operations |
-/// | Event | 4 | Event log operations (MVP) |
+/// | Event | 5 | Event log operations |
 /// | State | 4 | State cell operations (MVP) |
 /// | Vector | 7 | Vector store operations (MVP) |
 /// | Branch | 5 | Branch lifecycle operations (MVP) |
 /// | Transaction | 5 | Transaction control |
 /// | Retention | 3 | Retention policy |
 /// | Database | 4 | Database-level operations |
+/// | Cursor | 3 | Server-side result cursors |
 ///
 /// # Branch field
 ///
@@ -148,6 +149,52 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
+    /// Atomically add to a numeric value (a missing key counts as zero).
+    /// Returns: `Output::MaybeVersioned` (always `Some`, the new value)
+    KvIncr {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key to update.
+        key: String,
+        /// Amount to add (`Int` or `Float`, may be negative).
+        delta: Value,
+    },
+
+    /// Get the newest versions of a key under its history policy.
+    /// Returns: `Output::KvHistory`
+    KvHistory {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key to retrieve history for.
+        key: String,
+        /// Maximum versions to return, newest first (all if omitted).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u64>,
+    },
+
+    /// Set the history policy for keys starting with a prefix.
+    /// `KeepAll` removes the policy. Returns: `Output::Unit`
+    KvSetHistoryPolicy {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Key prefix the policy governs (empty for every key).
+        prefix: String,
+        /// Versions to keep per key.
+        policy: RetentionPolicy,
+    },
+
     // ==================== JSON (4 MVP) ====================
     /// Set a value at a path in a JSON document.
     /// Returns: `Output::Version`
@@ -184,6 +231,21 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
+    /// Get the values at several paths of a JSON document in one read.
+    /// Returns: `Output::MaybeValues` (one entry per path, in order)
+    JsonGetPaths {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// JSON paths to read.
+        paths: Vec<String>,
+    },
+
     /// Delete a value at a path from a JSON document.
     /// Returns: `Output::Uint` (count of elements removed)
     JsonDelete {
@@ -235,6 +297,148 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
+    /// Apply an RFC 7396 merge patch to a JSON document, creating it if missing.
+    /// Returns: `Output::Version`
+    JsonMerge {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// Merge patch; `null` members remove fields.
+        patch: Value,
+    },
+
+    /// Apply an RFC 6902 JSON Patch to a JSON document.
+    /// Returns: `Output::Version`
+    JsonPatch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// Array of patch operations, applied all or nothing.
+        ops: Value,
+    },
+
+    /// Append a value to the array at a path, creating the array if missing.
+    /// Returns: `Output::Uint` (new array length)
+    JsonArrayAppend {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// JSON path of the array.
+        path: String,
+        /// Value to append.
+        value: Value,
+    },
+
+    /// Insert a value into the array at a path before an index.
+    /// Returns: `Output::Uint` (new array length)
+    JsonArrayInsert {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// JSON path of the array.
+        path: String,
+        /// Position to insert at; the array length appends.
+        index: u64,
+        /// Value to insert.
+        value: Value,
+    },
+
+    /// Remove the element at an index from the array at a path.
+    /// Returns: `Output::Maybe` (the removed element)
+    JsonArrayRemove {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// JSON path of the array.
+        path: String,
+        /// Position of the element to remove.
+        index: u64,
+    },
+
+    /// Get the length of the array at a path.
+    /// Returns: `Output::Maybe` (`None` if the document or path is missing)
+    JsonArrayLen {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key.
+        key: String,
+        /// JSON path of the array.
+        path: String,
+    },
+
+    /// Set the JSON schema documents with ids starting with a prefix must match.
+    /// Returns: `Output::Unit`
+    JsonSetSchema {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key prefix the schema applies to; empty for every document.
+        prefix: String,
+        /// JSON schema; `null` removes any existing schema.
+        schema: Value,
+    },
+
+    /// Get the JSON schema set on a document key prefix.
+    /// Returns: `Output::Maybe`
+    JsonGetSchema {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key prefix to look up.
+        prefix: String,
+    },
+
+    /// Check a value against the schema governing a document key, without
+    /// writing it.
+    /// Returns: `Output::SchemaViolations`
+    JsonValidate {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Document key whose schema applies.
+        key: String,
+        /// Candidate document.
+        value: Value,
+    },
+
     // ==================== Event (4 MVP) ====================
     // MVP: append, read, get_by_type, len
     /// Append an event to the log.
@@ -290,7 +494,8 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
-    /// Get the total count of events in the log.
+    /// Get the total count of events appended to the log, including any
+    /// since removed by retention.
     /// Returns: `Output::Uint`
     EventLen {
         /// Target branch (defaults to "default").
@@ -301,6 +506,115 @@ pub enum Command {
         space: Option<String>,
     },
 
+    /// Set the retention policy of an event stream.
+    /// Returns: `Output::Uint` (events deleted now)
+    EventSetRetention {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the policy applies to.
+        event_type: String,
+        /// Retention policy; `keep_all` removes any existing policy.
+        policy: RetentionPolicy,
+    },
+
+    /// Set the JSON schema event payloads of a stream must match.
+    /// Returns: `Output::Unit`
+    EventSetSchema {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the schema applies to.
+        event_type: String,
+        /// JSON schema; `null` removes any existing schema.
+        schema: Value,
+    },
+
+    /// Get the JSON schema of an event stream.
+    /// Returns: `Output::Maybe`
+    EventGetSchema {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) to look up.
+        event_type: String,
+    },
+
+    /// Create a consumer group on an event stream.
+    /// Returns: `Output::Bool` (false if the group already exists)
+    EventCreateGroup {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the group reads.
+        event_type: String,
+        /// Consumer group name.
+        group: String,
+    },
+
+    /// Deliver the next undelivered events of a stream to a group consumer.
+    /// Returns: `Output::VersionedValues`
+    EventReadGroup {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the group reads.
+        event_type: String,
+        /// Consumer group name.
+        group: String,
+        /// Consumer the events are delivered to.
+        consumer: String,
+        /// Maximum number of events to deliver.
+        count: u64,
+    },
+
+    /// Acknowledge events delivered to a consumer group.
+    /// Returns: `Output::Uint` (events acknowledged)
+    EventAck {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the group reads.
+        event_type: String,
+        /// Consumer group name.
+        group: String,
+        /// Sequence numbers of the events to acknowledge.
+        sequences: Vec<u64>,
+    },
+
+    /// List the delivered but unacknowledged events of a consumer group.
+    /// Returns: `Output::PendingEvents`
+    EventPending {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Stream (event type) the group reads.
+        event_type: String,
+        /// Consumer group name.
+        group: String,
+    },
+
     // ==================== State (4 MVP) ====================
     // MVP: set, read, cas, init
     /// Set a state cell value (unconditional write).
@@ -351,6 +665,21 @@ pub enum Command {
         value: Value,
     },
 
+    /// Atomically add to a numeric state cell (a missing cell counts as zero).
+    /// Returns: `Output::MaybeVersioned` (always `Some`, the new value)
+    StateIncr {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Cell name.
+        cell: String,
+        /// Amount to add (`Int` or `Float`, may be negative).
+        delta: Value,
+    },
+
     /// Get full version history for a state cell.
     /// Returns: `Output::VersionHistory`
     StateGetv {
@@ -365,6 +694,24 @@ pub enum Command {
         /// Optional timestamp for time-travel reads (microseconds since epoch).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         as_of: Option<u64>,
+        /// Maximum number of versions to return, newest first.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u64>,
+    },
+
+    /// Read a state cell as it was at a given version counter.
+    /// Returns: `Output::MaybeVersioned`
+    StateGetVersion {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Cell name.
+        cell: String,
+        /// Version counter to read.
+        version: u64,
     },
 
     /// Initialize a state cell (only if it doesn't exist).
@@ -411,6 +758,74 @@ pub enum Command {
         as_of: Option<u64>,
     },
 
+    // ==================== List (4) ====================
+    /// Push values onto one end of a list, creating it if needed.
+    /// Returns: `Output::Uint` (list length after the push)
+    ListPush {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// List name.
+        list: String,
+        /// End to push onto.
+        end: ListEnd,
+        /// Values to push, in order.
+        values: Vec<Value>,
+    },
+
+    /// Remove and return the value at one end of a list.
+    /// Returns: `Output::Maybe` (`None` if the list is empty)
+    ListPop {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// List name.
+        list: String,
+        /// End to pop from.
+        end: ListEnd,
+        /// If set, wait up to this many milliseconds for a value to arrive
+        /// when the list is empty.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_ms: Option<u64>,
+    },
+
+    /// Read list elements from `start` to `stop` inclusive; negative
+    /// indexes count back from the tail.
+    /// Returns: `Output::Values`
+    ListRange {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// List name.
+        list: String,
+        /// First index to return.
+        start: i64,
+        /// Last index to return.
+        stop: i64,
+    },
+
+    /// Get the number of elements in a list.
+    /// Returns: `Output::Uint`
+    ListLen {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// List name.
+        list: String,
+    },
+
     // ==================== Vector (7 MVP) ====================
     // MVP: upsert, get, delete, search, create_collection, delete_collection, list_collections
     /// Insert or update a vector.
@@ -430,6 +845,9 @@ pub enum Command {
         vector: Vec<f32>,
         /// Optional metadata to associate with the vector.
         metadata: Option<Value>,
+        /// Optional namespace within the collection; replaces any previous one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        namespace: Option<String>,
     },
 
     /// Get a vector by key.
@@ -487,6 +905,29 @@ pub enum Command {
         /// Optional timestamp for time-travel reads (microseconds since epoch).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         as_of: Option<u64>,
+        /// Restrict the search to one namespace (cannot be combined with `as_of`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        namespace: Option<String>,
+    },
+
+    /// Search for similar vectors for several queries at once.
+    /// Returns: `Output::VectorBatchMatches`
+    VectorSearchBatch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Collection to search.
+        collection: String,
+        /// Query embedding vectors.
+        queries: Vec<Vec<f32>>,
+        /// Number of nearest neighbors to return per query.
+        k: u64,
+        /// Optional metadata filters, applied to every query.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter: Option<Vec<MetadataFilter>>,
     },
 
     /// Create a collection with explicit configuration.
@@ -504,6 +945,29 @@ pub enum Command {
         dimension: u64,
         /// Distance metric for similarity search.
         metric: DistanceMetric,
+        /// Element type for the in-memory index (defaults to f32).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        storage_dtype: Option<StorageDtype>,
+    },
+
+    /// Rebuild a collection's index from its stored vectors, optionally
+    /// switching the distance metric or index backend.
+    /// Returns: `Output::VectorCollectionList` (with single entry)
+    VectorReindex {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Collection name.
+        collection: String,
+        /// New distance metric (keeps the current one if omitted).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        metric: Option<DistanceMetric>,
+        /// New index backend (keeps the current one if omitted).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        index_type: Option<VectorIndexType>,
     },
 
     /// Delete a collection.
@@ -556,6 +1020,9 @@ pub enum Command {
         collection: String,
         /// Vector entries to upsert.
         entries: Vec<BatchVectorEntry>,
+        /// Optional namespace for every entry in the batch.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        namespace: Option<String>,
     },
 
     // ==================== Branch (5 MVP) ====================
@@ -593,6 +1060,92 @@ pub enum Command {
         branch: BranchId,
     },
 
+    /// Get entry counts and memory usage for a branch.
+    /// Returns: `Output::BranchStats`
+    BranchStats {
+        /// Branch to inspect.
+        branch: BranchId,
+    },
+
+    /// Set the history retention of a branch, enforced by compaction.
+    /// Returns: `Output::Unit`
+    BranchSetRetention {
+        /// Branch to configure.
+        branch: BranchId,
+        /// Retention; a `keep_all` default without overrides removes it.
+        retention: BranchRetention,
+    },
+
+    /// Get the history retention of a branch.
+    /// Returns: `Output::BranchRetention`
+    BranchGetRetention {
+        /// Branch to inspect.
+        branch: BranchId,
+    },
+
+    /// Tag a branch.
+    /// Returns: `Output::Bool` (false if the branch already had the tag)
+    BranchAddTag {
+        /// Branch to tag.
+        branch: BranchId,
+        /// Tag to add.
+        tag: String,
+    },
+
+    /// Remove a tag from a branch.
+    /// Returns: `Output::Bool` (false if the branch didn't have the tag)
+    BranchRemoveTag {
+        /// Branch to untag.
+        branch: BranchId,
+        /// Tag to remove.
+        tag: String,
+    },
+
+    /// Set a metadata entry of a branch.
+    /// Returns: `Output::Unit`
+    BranchSetMetadata {
+        /// Branch to annotate.
+        branch: BranchId,
+        /// Metadata key.
+        key: String,
+        /// Value to store; `null` removes the entry.
+        value: Value,
+    },
+
+    /// List the branches matching a filter, in name order.
+    /// Returns: `Output::BranchInfoList`
+    BranchQuery {
+        /// Conditions the branches must meet.
+        #[serde(default)]
+        filter: BranchFilter,
+        /// Maximum number of branches to return.
+        limit: Option<u64>,
+        /// Number of matching branches to skip.
+        offset: Option<u64>,
+    },
+
+    /// Count the branches.
+    /// Returns: `Output::Uint`
+    BranchCount,
+
+    /// Close an active branch, marking it completed and running the
+    /// close hooks.
+    /// Returns: `Output::Unit`
+    BranchClose {
+        /// Branch to close.
+        branch: BranchId,
+    },
+
+    /// Fail an active branch, recording the error and running the fail
+    /// hooks.
+    /// Returns: `Output::Unit`
+    BranchFail {
+        /// Branch to fail.
+        branch: BranchId,
+        /// Why the branch failed.
+        error: String,
+    },
+
     /// Delete a branch and all its data (cascading delete).
     /// Returns: `Output::Unit`
     BranchDelete {
@@ -628,7 +1181,7 @@ pub enum Command {
     TxnIsActive,
 
     // ==================== Retention (3) ====================
-    // Note: Branch-level retention is handled via BranchSetRetention/BranchGetRetention
+    // Note: Branch-level retention is set via BranchSetRetention/BranchGetRetention
     // These are database-wide retention operations
     /// Apply retention policy (trigger garbage collection).
     /// Returns: `Output::RetentionResult`
@@ -664,9 +1217,22 @@ pub enum Command {
     /// Flush pending writes to disk
     Flush,
 
-    /// Trigger compaction
+    /// Compact the WAL down to live data.
+    /// Returns: `Output::Compacted`
     Compact,
 
+    /// Get database runtime statistics (cursor accounting).
+    /// Returns: `Output::Stats`
+    Stats,
+
+    /// Get per-command latency percentiles.
+    /// Returns: `Output::LatencyReport`
+    LatencyReport,
+
+    /// Clear recorded command latencies.
+    /// Returns: `Output::Unit`
+    LatencyReset,
+
     /// Get the available time range for a branch.
     /// Returns: `Output::TimeRange`
     TimeRange {
@@ -675,7 +1241,7 @@ pub enum Command {
         branch: Option<BranchId>,
     },
 
-    // ==================== Bundle (3) ====================
+    // ==================== Bundle (5) ====================
     /// Export a branch to a .branchbundle.tar.zst archive.
     /// Returns: `Output::BranchExported`
     BranchExport {
@@ -690,6 +1256,9 @@ pub enum Command {
     BranchImport {
         /// Path to the bundle archive.
         path: String,
+        /// Import into a new branch with this name instead of the bundled one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        as_branch: Option<String>,
     },
 
     /// Validate a .branchbundle.tar.zst archive without importing.
@@ -699,7 +1268,23 @@ pub enum Command {
         path: String,
     },
 
-    // ==================== Intelligence (1) ====================
+    /// Export every branch to one .databundle.tar.zst archive.
+    /// Returns: `Output::DatabaseExported`
+    DatabaseExport {
+        /// Output file path.
+        path: String,
+    },
+
+    /// Import every branch from a .databundle.tar.zst archive.
+    /// Returns: `Output::DatabaseImported`
+    DatabaseImport {
+        /// Path to the bundle archive.
+        path: String,
+        /// What to do with bundled branches whose name is already taken.
+        on_conflict: ImportConflict,
+    },
+
+    // ==================== Intelligence (3) ====================
     /// Search across multiple primitives.
     /// Returns: `Output::SearchResults`
     Search {
@@ -719,6 +1304,70 @@ pub enum Command {
         primitives: Option<Vec<String>>,
     },
 
+    /// Full-text search over KV string values, JSON documents and event
+    /// payloads, ranked with BM25.
+    /// Returns: `Output::TextSearchResults`
+    TextSearch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Keyword query; documents matching any term are returned.
+        query: String,
+        /// Number of results to return (defaults to 10).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        k: Option<u64>,
+    },
+
+    /// Semantic search: embed the query text and return the closest
+    /// auto-embedded KV values, JSON documents, state cells and events.
+    /// With `rerank_top_n`, that many candidates are rescored by the
+    /// configured reranker before the best `k` are returned.
+    /// Requires the `embed` feature.
+    /// Returns: `Output::SearchResults`
+    SemanticSearch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Natural-language query to embed.
+        query: String,
+        /// Number of results to return (defaults to 10).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        k: Option<u64>,
+        /// Candidates to rerank (no reranking when absent).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rerank_top_n: Option<u64>,
+    },
+
+    /// Hybrid search: full-text and vector similarity in one call, fused
+    /// into a single ranking.
+    /// Returns: `Output::SearchResults`
+    HybridSearch {
+        /// Target branch (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<BranchId>,
+        /// Target space (defaults to "default").
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        space: Option<String>,
+        /// Vector collection to search.
+        collection: String,
+        /// Keyword query for the full-text side.
+        query: String,
+        /// Query embedding for the vector side.
+        embedding: Vec<f32>,
+        /// Number of results to return (defaults to 10).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        k: Option<u64>,
+        /// How to combine the two rankings (defaults to RRF).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fusion: Option<FusionMethod>,
+    },
+
     // ==================== Space (4) ====================
     /// List spaces in a branch.
     /// Returns: `Output::SpaceList`
@@ -760,6 +1409,34 @@ pub enum Command {
         /// Space name.
         space: String,
     },
+
+    // ==================== Cursor (3) ====================
+    /// Run a list-returning read command and page through its result.
+    /// Returns: `Output::CursorPage`
+    CursorOpen {
+        /// The read command to run (e.g. `KvList`, `VectorSearch`).
+        query: Box<Command>,
+        /// Rows per page (defaults to 100).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        page_size: Option<u64>,
+        /// Idle seconds before the cursor expires (defaults to 300).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_secs: Option<u64>,
+    },
+
+    /// Fetch the next page of an open cursor.
+    /// Returns: `Output::CursorPage`
+    CursorFetch {
+        /// Cursor id from a previous `CursorPage`.
+        cursor: String,
+    },
+
+    /// Close a cursor before its last page is fetched.
+    /// Returns: `Output::Bool` (false if the cursor was unknown or expired)
+    CursorClose {
+        /// Cursor id from a previous `CursorPage`.
+        cursor: String,
+    },
 }
 
 impl Command {
@@ -772,20 +1449,43 @@ impl Command {
             self,
             Command::KvPut { .. }
                 | Command::KvDelete { .. }
+                | Command::KvIncr { .. }
+                | Command::KvSetHistoryPolicy { .. }
                 | Command::JsonSet { .. }
                 | Command::JsonDelete { .. }
+                | Command::JsonMerge { .. }
+                | Command::JsonPatch { .. }
+                | Command::JsonArrayAppend { .. }
+                | Command::JsonArrayInsert { .. }
+                | Command::JsonArrayRemove { .. }
+                | Command::JsonSetSchema { .. }
                 | Command::EventAppend { .. }
+                | Command::EventSetRetention { .. }
+                | Command::EventSetSchema { .. }
+                | Command::EventCreateGroup { .. }
+                | Command::EventReadGroup { .. }
+                | Command::EventAck { .. }
                 | Command::StateSet { .. }
                 | Command::StateCas { .. }
+                | Command::StateIncr { .. }
                 | Command::StateInit { .. }
                 | Command::StateDelete { .. }
+                | Command::ListPush { .. }
+                | Command::ListPop { .. }
                 | Command::VectorUpsert { .. }
                 | Command::VectorDelete { .. }
                 | Command::VectorCreateCollection { .. }
+                | Command::VectorReindex { .. }
                 | Command::VectorDeleteCollection { .. }
                 | Command::VectorBatchUpsert { .. }
                 | Command::BranchCreate { .. }
                 | Command::BranchDelete { .. }
+                | Command::BranchSetRetention { .. }
+                | Command::BranchAddTag { .. }
+                | Command::BranchRemoveTag { .. }
+                | Command::BranchSetMetadata { .. }
+                | Command::BranchClose { .. }
+                | Command::BranchFail { .. }
                 | Command::SpaceCreate { .. }
                 | Command::SpaceDelete { .. }
                 | Command::TxnBegin { .. }
@@ -796,6 +1496,8 @@ impl Command {
                 | Command::Compact
                 | Command::BranchExport { .. }
                 | Command::BranchImport { .. }
+                | Command::DatabaseExport { .. }
+                | Command::DatabaseImport { .. }
         )
     }
 
@@ -810,27 +1512,55 @@ impl Command {
             Command::KvDelete { .. } => "KvDelete",
             Command::KvList { .. } => "KvList",
             Command::KvGetv { .. } => "KvGetv",
+            Command::KvIncr { .. } => "KvIncr",
+            Command::KvHistory { .. } => "KvHistory",
+            Command::KvSetHistoryPolicy { .. } => "KvSetHistoryPolicy",
             Command::JsonSet { .. } => "JsonSet",
             Command::JsonGet { .. } => "JsonGet",
             Command::JsonDelete { .. } => "JsonDelete",
             Command::JsonGetv { .. } => "JsonGetv",
             Command::JsonList { .. } => "JsonList",
+            Command::JsonGetPaths { .. } => "JsonGetPaths",
+            Command::JsonMerge { .. } => "JsonMerge",
+            Command::JsonPatch { .. } => "JsonPatch",
+            Command::JsonArrayAppend { .. } => "JsonArrayAppend",
+            Command::JsonArrayInsert { .. } => "JsonArrayInsert",
+            Command::JsonArrayRemove { .. } => "JsonArrayRemove",
+            Command::JsonArrayLen { .. } => "JsonArrayLen",
+            Command::JsonSetSchema { .. } => "JsonSetSchema",
+            Command::JsonGetSchema { .. } => "JsonGetSchema",
+            Command::JsonValidate { .. } => "JsonValidate",
             Command::EventAppend { .. } => "EventAppend",
             Command::EventGet { .. } => "EventGet",
             Command::EventGetByType { .. } => "EventGetByType",
             Command::EventLen { .. } => "EventLen",
+            Command::EventSetRetention { .. } => "EventSetRetention",
+            Command::EventSetSchema { .. } => "EventSetSchema",
+            Command::EventGetSchema { .. } => "EventGetSchema",
+            Command::EventCreateGroup { .. } => "EventCreateGroup",
+            Command::EventReadGroup { .. } => "EventReadGroup",
+            Command::EventAck { .. } => "EventAck",
+            Command::EventPending { .. } => "EventPending",
             Command::StateSet { .. } => "StateSet",
             Command::StateGet { .. } => "StateGet",
             Command::StateCas { .. } => "StateCas",
+            Command::StateIncr { .. } => "StateIncr",
             Command::StateGetv { .. } => "StateGetv",
+            Command::StateGetVersion { .. } => "StateGetVersion",
             Command::StateInit { .. } => "StateInit",
             Command::StateDelete { .. } => "StateDelete",
             Command::StateList { .. } => "StateList",
+            Command::ListPush { .. } => "ListPush",
+            Command::ListPop { .. } => "ListPop",
+            Command::ListRange { .. } => "ListRange",
+            Command::ListLen { .. } => "ListLen",
             Command::VectorUpsert { .. } => "VectorUpsert",
             Command::VectorGet { .. } => "VectorGet",
             Command::VectorDelete { .. } => "VectorDelete",
             Command::VectorSearch { .. } => "VectorSearch",
+            Command::VectorSearchBatch { .. } => "VectorSearchBatch",
             Command::VectorCreateCollection { .. } => "VectorCreateCollection",
+            Command::VectorReindex { .. } => "VectorReindex",
             Command::VectorDeleteCollection { .. } => "VectorDeleteCollection",
             Command::VectorListCollections { .. } => "VectorListCollections",
             Command::VectorCollectionStats { .. } => "VectorCollectionStats",
@@ -839,6 +1569,16 @@ impl Command {
             Command::BranchGet { .. } => "BranchGet",
             Command::BranchList { .. } => "BranchList",
             Command::BranchExists { .. } => "BranchExists",
+            Command::BranchStats { .. } => "BranchStats",
+            Command::BranchSetRetention { .. } => "BranchSetRetention",
+            Command::BranchGetRetention { .. } => "BranchGetRetention",
+            Command::BranchAddTag { .. } => "BranchAddTag",
+            Command::BranchRemoveTag { .. } => "BranchRemoveTag",
+            Command::BranchSetMetadata { .. } => "BranchSetMetadata",
+            Command::BranchQuery { .. } => "BranchQuery",
+            Command::BranchCount => "BranchCount",
+            Command::BranchClose { .. } => "BranchClose",
+            Command::BranchFail { .. } => "BranchFail",
             Command::BranchDelete { .. } => "BranchDelete",
             Command::TxnBegin { .. } => "TxnBegin",
             Command::TxnCommit => "TxnCommit",
@@ -852,15 +1592,26 @@ impl Command {
             Command::Info => "Info",
             Command::Flush => "Flush",
             Command::Compact => "Compact",
+            Command::Stats => "Stats",
+            Command::LatencyReport => "LatencyReport",
+            Command::LatencyReset => "LatencyReset",
             Command::TimeRange { .. } => "TimeRange",
             Command::BranchExport { .. } => "BranchExport",
             Command::BranchImport { .. } => "BranchImport",
             Command::BranchBundleValidate { .. } => "BranchBundleValidate",
+            Command::DatabaseExport { .. } => "DatabaseExport",
+            Command::DatabaseImport { .. } => "DatabaseImport",
             Command::Search { .. } => "Search",
+            Command::TextSearch { .. } => "TextSearch",
+            Command::SemanticSearch { .. } => "SemanticSearch",
+            Command::HybridSearch { .. } => "HybridSearch",
             Command::SpaceList { .. } => "SpaceList",
             Command::SpaceCreate { .. } => "SpaceCreate",
             Command::SpaceDelete { .. } => "SpaceDelete",
             Command::SpaceExists { .. } => "SpaceExists",
+            Command::CursorOpen { .. } => "CursorOpen",
+            Command::CursorFetch { .. } => "CursorFetch",
+            Command::CursorClose { .. } => "CursorClose",
         }
     }
 
@@ -891,37 +1642,69 @@ impl Command {
             | Command::KvDelete { branch, space, .. }
             | Command::KvList { branch, space, .. }
             | Command::KvGetv { branch, space, .. }
+            | Command::KvIncr { branch, space, .. }
+            | Command::KvHistory { branch, space, .. }
+            | Command::KvSetHistoryPolicy { branch, space, .. }
             // JSON
             | Command::JsonSet { branch, space, .. }
             | Command::JsonGet { branch, space, .. }
             | Command::JsonGetv { branch, space, .. }
             | Command::JsonDelete { branch, space, .. }
             | Command::JsonList { branch, space, .. }
+            | Command::JsonGetPaths { branch, space, .. }
+            | Command::JsonMerge { branch, space, .. }
+            | Command::JsonPatch { branch, space, .. }
+            | Command::JsonArrayAppend { branch, space, .. }
+            | Command::JsonArrayInsert { branch, space, .. }
+            | Command::JsonArrayRemove { branch, space, .. }
+            | Command::JsonArrayLen { branch, space, .. }
+            | Command::JsonSetSchema { branch, space, .. }
+            | Command::JsonGetSchema { branch, space, .. }
+            | Command::JsonValidate { branch, space, .. }
             // Event (4 MVP)
             | Command::EventAppend { branch, space, .. }
             | Command::EventGet { branch, space, .. }
             | Command::EventGetByType { branch, space, .. }
             | Command::EventLen { branch, space, .. }
+            | Command::EventSetRetention { branch, space, .. }
+            | Command::EventSetSchema { branch, space, .. }
+            | Command::EventGetSchema { branch, space, .. }
+            | Command::EventCreateGroup { branch, space, .. }
+            | Command::EventReadGroup { branch, space, .. }
+            | Command::EventAck { branch, space, .. }
+            | Command::EventPending { branch, space, .. }
             // State
             | Command::StateSet { branch, space, .. }
             | Command::StateGet { branch, space, .. }
             | Command::StateGetv { branch, space, .. }
+            | Command::StateGetVersion { branch, space, .. }
             | Command::StateCas { branch, space, .. }
+            | Command::StateIncr { branch, space, .. }
             | Command::StateInit { branch, space, .. }
             | Command::StateDelete { branch, space, .. }
             | Command::StateList { branch, space, .. }
+            // List
+            | Command::ListPush { branch, space, .. }
+            | Command::ListPop { branch, space, .. }
+            | Command::ListRange { branch, space, .. }
+            | Command::ListLen { branch, space, .. }
             // Vector (7 MVP)
             | Command::VectorUpsert { branch, space, .. }
             | Command::VectorGet { branch, space, .. }
             | Command::VectorDelete { branch, space, .. }
             | Command::VectorSearch { branch, space, .. }
+            | Command::VectorSearchBatch { branch, space, .. }
             | Command::VectorCreateCollection { branch, space, .. }
+            | Command::VectorReindex { branch, space, .. }
             | Command::VectorDeleteCollection { branch, space, .. }
             | Command::VectorListCollections { branch, space, .. }
             | Command::VectorCollectionStats { branch, space, .. }
             | Command::VectorBatchUpsert { branch, space, .. }
             // Intelligence
-            | Command::Search { branch, space, .. } => {
+            | Command::Search { branch, space, .. }
+            | Command::TextSearch { branch, space, .. }
+            | Command::SemanticSearch { branch, space, .. }
+            | Command::HybridSearch { branch, space, .. } => {
                 resolve_branch!(branch);
                 resolve_space!(space);
             }
@@ -943,12 +1726,25 @@ impl Command {
                 resolve_branch!(branch);
             }
 
-            // Branch lifecycle, Transaction, and Database commands have no
-            // optional branch to resolve.
+            // Cursors resolve the defaults of the command they page
+            Command::CursorOpen { query, .. } => query.resolve_defaults(),
+
+            // Branch lifecycle, Transaction, Database, and remaining Cursor
+            // commands have no optional branch to resolve.
             Command::BranchCreate { .. }
             | Command::BranchGet { .. }
             | Command::BranchList { .. }
             | Command::BranchExists { .. }
+            | Command::BranchStats { .. }
+            | Command::BranchSetRetention { .. }
+            | Command::BranchGetRetention { .. }
+            | Command::BranchAddTag { .. }
+            | Command::BranchRemoveTag { .. }
+            | Command::BranchSetMetadata { .. }
+            | Command::BranchQuery { .. }
+            | Command::BranchCount
+            | Command::BranchClose { .. }
+            | Command::BranchFail { .. }
             | Command::BranchDelete { .. }
             | Command::TxnCommit
             | Command::TxnRollback
@@ -958,9 +1754,16 @@ impl Command {
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::Stats
+            | Command::LatencyReport
+            | Command::LatencyReset
             | Command::BranchExport { .. }
             | Command::BranchImport { .. }
-            | Command::BranchBundleValidate { .. } => {}
+            | Command::BranchBundleValidate { .. }
+            | Command::DatabaseExport { .. }
+            | Command::DatabaseImport { .. }
+            | Command::CursorFetch { .. }
+            | Command::CursorClose { .. } => {}
         }
     }
 
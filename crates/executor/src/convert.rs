@@ -4,7 +4,7 @@
 //! the executor's [`Error`] type.
 
 use crate::Error;
-use strata_core::{EntityRef, StrataError};
+use strata_core::{EntityRef, JsonValueError, StrataError};
 
 /// Convert a StrataError to an executor Error.
 ///
@@ -73,6 +73,10 @@ impl From<StrataError> for Error {
 
             StrataError::InvalidInput { message } => Error::InvalidInput { reason: message },
 
+            StrataError::ReservedKey { key, prefix } => Error::InvalidKey {
+                reason: format!("'{}' uses reserved prefix '{}'", key, prefix),
+            },
+
             // Constraint errors
             StrataError::DimensionMismatch { expected, got } => Error::DimensionMismatch {
                 expected,
@@ -124,11 +128,25 @@ impl From<StrataError> for Error {
                 reason: format!("Data corruption: {}", message),
             },
 
+            StrataError::AlreadyOpen { path, holder } => Error::AlreadyOpen { path, holder },
+
+            StrataError::Cancelled { operation } => Error::Cancelled { operation },
+
             StrataError::Internal { message } => Error::Internal { reason: message },
         }
     }
 }
 
+/// A JSON value with no `Value` equivalent is invalid input, so `?` works
+/// on `Value::try_from(&json)`.
+impl From<JsonValueError> for Error {
+    fn from(err: JsonValueError) -> Self {
+        Error::InvalidInput {
+            reason: err.to_string(),
+        }
+    }
+}
+
 /// Convert a strata_core::StrataResult to an executor Result.
 pub fn convert_result<T>(result: strata_core::StrataResult<T>) -> crate::Result<T> {
     result.map_err(Error::from)
@@ -217,6 +235,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_json_value_error() {
+        let err = JsonValueError::InvalidBase64("!!".to_string());
+        match Error::from(err) {
+            Error::InvalidInput { reason } => assert!(reason.contains("base64")),
+            _ => panic!("Expected InvalidInput"),
+        }
+    }
+
+    #[test]
+    fn test_already_open() {
+        let err = StrataError::already_open("/data/db", "pid 42 on host 'h'");
+        let converted: Error = err.into();
+        match converted {
+            Error::AlreadyOpen { path, holder } => {
+                assert_eq!(path, "/data/db");
+                assert_eq!(holder, "pid 42 on host 'h'");
+            }
+            _ => panic!("Expected AlreadyOpen"),
+        }
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let err = StrataError::cancelled("recovery");
+        let converted: Error = err.into();
+        match converted {
+            Error::Cancelled { operation } => assert_eq!(operation, "recovery"),
+            _ => panic!("Expected Cancelled"),
+        }
+    }
+
     #[test]
     fn test_dimension_mismatch() {
         let err = StrataError::dimension_mismatch(384, 768);
@@ -0,0 +1,257 @@
+//! Server-side result cursors.
+//!
+//! A cursor runs a list-returning read command once, keeps the result on the
+//! server, and hands it out page by page. Remote clients can walk a large
+//! result without re-executing the query for every page.
+//!
+//! Cursors are shared by every [`Executor`](crate::Executor) on the same
+//! database (the registry lives in a database extension), so a cursor opened
+//! on one connection can be fetched from another.
+//!
+//! ## Lifecycle
+//!
+//! | Step | Command | Notes |
+//! |------|---------|-------|
+//! | Open | `CursorOpen` | Returns the first page; no cursor if it holds everything |
+//! | Fetch | `CursorFetch` | Returns the next page and refreshes the TTL |
+//! | Close | `CursorClose` | Frees the cursor early |
+//!
+//! A cursor closes itself after its last page is fetched. Cursors that sit
+//! idle longer than their TTL expire and are dropped. Open, closed, and
+//! expired counts are reported by `Command::Stats` so leaked cursors show up.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::*;
+use crate::{Error, Output, Result};
+
+/// Default number of rows per page.
+pub const DEFAULT_CURSOR_PAGE_SIZE: u64 = 100;
+
+/// Default idle time before a cursor expires.
+pub const DEFAULT_CURSOR_TTL_SECS: u64 = 300;
+
+/// Materialized rows of a list-shaped [`Output`].
+#[derive(Debug)]
+enum Rows {
+    Keys(Vec<String>),
+    JsonKeys(Vec<String>),
+    VersionedValues(Vec<VersionedValue>),
+    VectorMatches(Vec<VectorMatch>),
+    VectorCollections(Vec<CollectionInfo>),
+    Branches(Vec<VersionedBranchInfo>),
+    SearchHits(Vec<SearchResultHit>),
+    TextHits(Vec<TextSearchHit>),
+    Spaces(Vec<String>),
+}
+
+impl Rows {
+    /// Split a command output into rows, or `None` if it is not a list.
+    fn from_output(output: Output) -> Option<Rows> {
+        match output {
+            Output::Keys(v) => Some(Rows::Keys(v)),
+            Output::JsonListResult { keys, .. } => Some(Rows::JsonKeys(keys)),
+            Output::VersionedValues(v) => Some(Rows::VersionedValues(v)),
+            Output::VectorMatches(v) => Some(Rows::VectorMatches(v)),
+            Output::VectorCollectionList(v) => Some(Rows::VectorCollections(v)),
+            Output::BranchInfoList(v) => Some(Rows::Branches(v)),
+            Output::SearchResults(v) => Some(Rows::SearchHits(v)),
+            Output::TextSearchResults(v) => Some(Rows::TextHits(v)),
+            Output::SpaceList(v) => Some(Rows::Spaces(v)),
+            _ => None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Rows::Keys(v) | Rows::JsonKeys(v) | Rows::Spaces(v) => v.len(),
+            Rows::VersionedValues(v) => v.len(),
+            Rows::VectorMatches(v) => v.len(),
+            Rows::VectorCollections(v) => v.len(),
+            Rows::Branches(v) => v.len(),
+            Rows::SearchHits(v) => v.len(),
+            Rows::TextHits(v) => v.len(),
+        }
+    }
+
+    /// Rows `start..end` as the same output variant the query returned.
+    fn page(&self, start: usize, end: usize) -> Output {
+        match self {
+            Rows::Keys(v) => Output::Keys(v[start..end].to_vec()),
+            Rows::JsonKeys(v) => Output::JsonListResult {
+                keys: v[start..end].to_vec(),
+                cursor: None,
+            },
+            Rows::VersionedValues(v) => Output::VersionedValues(v[start..end].to_vec()),
+            Rows::VectorMatches(v) => Output::VectorMatches(v[start..end].to_vec()),
+            Rows::VectorCollections(v) => Output::VectorCollectionList(v[start..end].to_vec()),
+            Rows::Branches(v) => Output::BranchInfoList(v[start..end].to_vec()),
+            Rows::SearchHits(v) => Output::SearchResults(v[start..end].to_vec()),
+            Rows::TextHits(v) => Output::TextSearchResults(v[start..end].to_vec()),
+            Rows::Spaces(v) => Output::SpaceList(v[start..end].to_vec()),
+        }
+    }
+}
+
+struct CursorState {
+    rows: Rows,
+    position: usize,
+    page_size: usize,
+    ttl: Duration,
+    expires_at: Instant,
+}
+
+impl CursorState {
+    fn remaining(&self) -> usize {
+        self.rows.len() - self.position
+    }
+
+    /// Take the next page and advance.
+    fn next_page(&mut self) -> Output {
+        let end = (self.position + self.page_size).min(self.rows.len());
+        let page = self.rows.page(self.position, end);
+        self.position = end;
+        page
+    }
+}
+
+#[derive(Default)]
+struct RegistryInner {
+    cursors: HashMap<String, CursorState>,
+    opened: u64,
+    closed: u64,
+    expired: u64,
+}
+
+impl RegistryInner {
+    fn sweep(&mut self, now: Instant) {
+        let before = self.cursors.len();
+        self.cursors.retain(|_, c| c.expires_at > now);
+        self.expired += (before - self.cursors.len()) as u64;
+    }
+}
+
+/// Registry of open cursors for one database.
+#[derive(Default)]
+pub(crate) struct CursorRegistry {
+    inner: Mutex<RegistryInner>,
+}
+
+impl CursorRegistry {
+    /// Start a cursor over `output` and return its first page.
+    ///
+    /// No cursor is kept when the first page already holds every row.
+    pub(crate) fn open(
+        &self,
+        command: &str,
+        output: Output,
+        page_size: Option<u64>,
+        ttl_secs: Option<u64>,
+    ) -> Result<Output> {
+        let page_size = positive("page_size", page_size, DEFAULT_CURSOR_PAGE_SIZE)?;
+        let ttl_secs = positive("ttl_secs", ttl_secs, DEFAULT_CURSOR_TTL_SECS)?;
+        let rows = Rows::from_output(output).ok_or_else(|| Error::InvalidInput {
+            reason: format!("{} does not return a list and cannot be paged", command),
+        })?;
+
+        let now = Instant::now();
+        let ttl = Duration::from_secs(ttl_secs);
+        let mut state = CursorState {
+            rows,
+            position: 0,
+            page_size: usize::try_from(page_size).unwrap_or(usize::MAX),
+            ttl,
+            expires_at: now + ttl,
+        };
+        let page = state.next_page();
+        let remaining = state.remaining() as u64;
+
+        let cursor = if remaining > 0 {
+            let id = uuid::Uuid::new_v4().simple().to_string();
+            let mut inner = self.lock();
+            inner.sweep(now);
+            inner.opened += 1;
+            inner.cursors.insert(id.clone(), state);
+            Some(id)
+        } else {
+            None
+        };
+
+        Ok(Output::CursorPage {
+            page: Box::new(page),
+            cursor,
+            remaining,
+        })
+    }
+
+    /// Fetch the next page of `cursor`, closing it after the last page.
+    pub(crate) fn fetch(&self, cursor: &str) -> Result<Output> {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        inner.sweep(now);
+
+        let state = inner
+            .cursors
+            .get_mut(cursor)
+            .ok_or_else(|| Error::CursorNotFound {
+                cursor: cursor.to_string(),
+            })?;
+        let page = state.next_page();
+        let remaining = state.remaining() as u64;
+        state.expires_at = now + state.ttl;
+
+        let cursor = if remaining > 0 {
+            Some(cursor.to_string())
+        } else {
+            inner.cursors.remove(cursor);
+            inner.closed += 1;
+            None
+        };
+
+        Ok(Output::CursorPage {
+            page: Box::new(page),
+            cursor,
+            remaining,
+        })
+    }
+
+    /// Close `cursor`. Returns `false` if it was unknown or already expired.
+    pub(crate) fn close(&self, cursor: &str) -> bool {
+        let mut inner = self.lock();
+        inner.sweep(Instant::now());
+        let existed = inner.cursors.remove(cursor).is_some();
+        if existed {
+            inner.closed += 1;
+        }
+        existed
+    }
+
+    /// Current cursor accounting.
+    pub(crate) fn stats(&self) -> CursorStats {
+        let mut inner = self.lock();
+        inner.sweep(Instant::now());
+        CursorStats {
+            open: inner.cursors.len() as u64,
+            opened: inner.opened,
+            closed: inner.closed,
+            expired: inner.expired,
+            buffered_rows: inner.cursors.values().map(|c| c.remaining() as u64).sum(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RegistryInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn positive(field: &str, value: Option<u64>, default: u64) -> Result<u64> {
+    match value {
+        None => Ok(default),
+        Some(0) => Err(Error::InvalidInput {
+            reason: format!("{} must be greater than 0", field),
+        }),
+        Some(v) => Ok(v),
+    }
+}
@@ -0,0 +1,175 @@
+//! Background queue for auto-embedding.
+//!
+//! Write handlers must not wait on the embedding model, so the auto-embed hook
+//! hands each embed or remove off to this queue and returns. Jobs run in FIFO
+//! order on a single `strata-embed` worker thread, which is started on demand
+//! and exits once the queue is empty. The queue lives in a database extension,
+//! so every executor and session on the same database shares one worker.
+//!
+//! Consecutive text jobs are embedded together, up to [`MAX_TEXT_BATCH`] at a
+//! time, so a bulk load reaches the embedding backend in batches instead of
+//! one sentence per call. Other jobs (removals) split batches, which keeps
+//! every job in submission order.
+//!
+//! `Command::SemanticSearch` calls [`EmbedQueue::wait_idle`] before searching,
+//! so a search sees every write that was acknowledged before it started.
+
+use std::collections::VecDeque;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// A unit of deferred embedding work.
+pub(crate) type EmbedJob = Box<dyn FnOnce() + Send + 'static>;
+
+/// Most text jobs embedded in one batch.
+pub(crate) const MAX_TEXT_BATCH: usize = 32;
+
+/// Embeds a batch of texts, returning one vector per text in order.
+pub(crate) type EmbedBatchFn = Box<dyn Fn(&[&str]) -> Result<Vec<Vec<f32>>, String> + Send>;
+
+/// Text waiting to be embedded.
+pub(crate) struct TextJob {
+    /// The text to embed
+    pub(crate) text: String,
+    /// Embeds the batch this job lands in. Every job on a queue embeds with
+    /// its database's provider, so the first job's function serves the batch.
+    pub(crate) embed: EmbedBatchFn,
+    /// Receives this text's embedding, or the reason embedding failed
+    pub(crate) finish: Box<dyn FnOnce(Result<Vec<f32>, String>) + Send>,
+}
+
+enum Queued {
+    Task(EmbedJob),
+    Text(TextJob),
+}
+
+/// Work taken off the queue in one step.
+enum Step {
+    Task(EmbedJob),
+    Texts(Vec<TextJob>),
+}
+
+/// Pending embedding jobs for one database.
+#[derive(Default)]
+pub(crate) struct EmbedQueue {
+    state: Mutex<QueueState>,
+    idle: Condvar,
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: VecDeque<Queued>,
+    worker_running: bool,
+}
+
+#[cfg_attr(not(feature = "embed"), allow(dead_code))]
+impl EmbedQueue {
+    /// Queue `job`, starting the worker thread if none is running.
+    ///
+    /// If the thread cannot be spawned the job runs inline instead, so no
+    /// embedding is lost.
+    pub(crate) fn enqueue(self: &Arc<Self>, job: EmbedJob) {
+        self.push(Queued::Task(job));
+    }
+
+    /// Queue text for embedding; see [`enqueue`](Self::enqueue).
+    pub(crate) fn enqueue_text(self: &Arc<Self>, job: TextJob) {
+        self.push(Queued::Text(job));
+    }
+
+    fn push(self: &Arc<Self>, job: Queued) {
+        let mut state = self.state.lock().unwrap();
+        state.jobs.push_back(job);
+        if state.worker_running {
+            return;
+        }
+        state.worker_running = true;
+        drop(state);
+
+        let queue = self.clone();
+        let spawned = std::thread::Builder::new()
+            .name("strata-embed".into())
+            .spawn(move || queue.run_worker());
+        if let Err(e) = spawned {
+            tracing::warn!(target: "strata::embed", error = %e, "Failed to spawn embed worker, embedding inline");
+            self.run_worker();
+        }
+    }
+
+    /// Block until every queued job has finished.
+    pub(crate) fn wait_idle(&self) {
+        let mut state = self.state.lock().unwrap();
+        while state.worker_running {
+            state = self.idle.wait(state).unwrap();
+        }
+    }
+
+    /// Drain the queue, then mark the worker stopped and wake waiters.
+    fn run_worker(&self) {
+        loop {
+            let step = {
+                let mut state = self.state.lock().unwrap();
+                match state.jobs.pop_front() {
+                    Some(Queued::Task(job)) => Step::Task(job),
+                    Some(Queued::Text(first)) => {
+                        let mut batch = vec![first];
+                        while batch.len() < MAX_TEXT_BATCH {
+                            match state.jobs.pop_front() {
+                                Some(Queued::Text(job)) => batch.push(job),
+                                Some(other) => {
+                                    state.jobs.push_front(other);
+                                    break;
+                                }
+                                None => break,
+                            }
+                        }
+                        Step::Texts(batch)
+                    }
+                    None => {
+                        state.worker_running = false;
+                        self.idle.notify_all();
+                        return;
+                    }
+                }
+            };
+            // A panicking job must not strand the queue with `worker_running` set.
+            let outcome = match step {
+                Step::Task(job) => catch_unwind(AssertUnwindSafe(job)),
+                Step::Texts(batch) => catch_unwind(AssertUnwindSafe(|| run_text_batch(batch))),
+            };
+            if outcome.is_err() {
+                tracing::warn!(target: "strata::embed", "Embed job panicked");
+            }
+        }
+    }
+}
+
+/// Embed a batch of texts with one call and hand each job its vector.
+fn run_text_batch(batch: Vec<TextJob>) {
+    let result = {
+        let texts: Vec<&str> = batch.iter().map(|job| job.text.as_str()).collect();
+        (batch[0].embed)(&texts)
+    };
+    let mut embeddings = match result {
+        Ok(embeddings) if embeddings.len() == batch.len() => {
+            embeddings.into_iter().map(Ok).collect()
+        }
+        Ok(embeddings) => vec![
+            Err(format!(
+                "Embedder returned {} vectors for {} texts",
+                embeddings.len(),
+                batch.len()
+            ));
+            batch.len()
+        ],
+        Err(e) => vec![Err(e); batch.len()],
+    }
+    .into_iter();
+
+    for job in batch {
+        let embedding = embeddings.next().expect("one result per job");
+        if catch_unwind(AssertUnwindSafe(|| (job.finish)(embedding))).is_err() {
+            tracing::warn!(target: "strata::embed", "Embed job panicked");
+        }
+    }
+}
@@ -24,7 +24,7 @@ use serde::{Deserialize, Serialize};
 /// | State | `BranchClosed`, `BranchExists`, `CollectionExists` | Invalid state transition |
 /// | Constraint | `DimensionMismatch`, `ConstraintViolation`, etc. | Limits exceeded |
 /// | Transaction | `TransactionNotActive`, `TransactionAlreadyActive` | Transaction state |
-/// | System | `Io`, `Serialization`, `Internal` | Infrastructure errors |
+/// | System | `Io`, `AlreadyOpen`, `Cancelled`, `Serialization`, `Internal` | Infrastructure errors |
 ///
 /// # Example
 ///
@@ -86,6 +86,20 @@ pub enum Error {
         key: String,
     },
 
+    /// Result cursor not found (never opened, closed, exhausted, or expired)
+    #[error("cursor not found: {cursor}")]
+    CursorNotFound {
+        /// The missing cursor id.
+        cursor: String,
+    },
+
+    /// Managed database not found (read-only managers never create databases)
+    #[error("database not found: {name}")]
+    DatabaseNotFound {
+        /// The missing database name.
+        name: String,
+    },
+
     // ==================== Type Errors ====================
     /// Wrong type for operation
     #[error("wrong type: expected {expected}, got {actual}")]
@@ -235,6 +249,22 @@ pub enum Error {
         reason: String,
     },
 
+    /// Database directory is locked by another process
+    #[error("database at '{path}' is already open by {holder}")]
+    AlreadyOpen {
+        /// Database directory.
+        path: String,
+        /// Description of the process holding the lock.
+        holder: String,
+    },
+
+    /// Operation stopped by its cancellation token
+    #[error("cancelled: {operation}")]
+    Cancelled {
+        /// The operation that was cancelled.
+        operation: String,
+    },
+
     /// Serialization error
     #[error("serialization error: {reason}")]
     Serialization {
@@ -6,14 +6,99 @@
 use std::sync::Arc;
 use std::time::Instant;
 
-use strata_engine::Database;
+use strata_core::types::TypeTag;
+use strata_engine::{BranchIndex, Database, TransactionPool};
 use strata_security::AccessMode;
 use tracing::{debug, warn};
 
 use crate::bridge::{to_core_branch_id, Primitives};
 use crate::convert::convert_result;
-use crate::types::BranchId;
-use crate::{Command, Error, Output, Result};
+use crate::cursor::CursorRegistry;
+use crate::latency::LatencyTracker;
+use crate::types::{
+    BranchId, BranchStats, DatabaseStats, PrimitiveCounts, TransactionPoolStats, TransactionStats,
+};
+use crate::{Command, Error, Output, Params, PreparedCommand, Result};
+
+/// Runtime statistics for `db`.
+pub(crate) fn database_stats(db: &Arc<Database>) -> Result<DatabaseStats> {
+    let pool = TransactionPool::stats();
+    // Read before listing branches, which runs a transaction of its own
+    let txns = db.transaction_metrics();
+    let mut branches = convert_result(BranchIndex::new(db.clone()).list_branches())?
+        .iter()
+        .map(|name| branch_stats(db, name))
+        .collect::<Result<Vec<_>>>()?;
+    branches.sort_by_key(|b| std::cmp::Reverse(b.memory_bytes));
+    let mut primitives = PrimitiveCounts::default();
+    for branch in &branches {
+        primitives.add(&branch.primitives);
+    }
+    Ok(DatabaseStats {
+        cursors: convert_result(db.extension::<CursorRegistry>())?.stats(),
+        transaction_pool: TransactionPoolStats {
+            hits: pool.hits,
+            misses: pool.misses,
+            returned: pool.returned,
+            discarded: pool.discarded,
+            shrunk: pool.shrunk,
+        },
+        primitives,
+        branches,
+        memory_bytes: db.memory_usage() as u64,
+        wal_bytes: convert_result(db.wal_size())?,
+        snapshot_watermark: convert_result(db.snapshot_watermark())?,
+        transactions: TransactionStats {
+            active: txns.active_count,
+            started: txns.total_started,
+            committed: txns.total_committed,
+            aborted: txns.total_aborted,
+            conflicts: txns.total_conflicts,
+            retries: txns.total_retries,
+            retries_exhausted: txns.total_retries_exhausted,
+        },
+    })
+}
+
+/// Entry counts and memory for the branch named `name`.
+pub(crate) fn branch_stats(db: &Database, name: &str) -> Result<BranchStats> {
+    let usage = db.branch_usage(to_core_branch_id(&BranchId::from(name))?);
+    let count = |tag| usage.entries.get(&tag).copied().unwrap_or(0) as u64;
+    Ok(BranchStats {
+        branch: name.to_string(),
+        primitives: PrimitiveCounts {
+            kv: count(TypeTag::KV),
+            events: count(TypeTag::Event),
+            state: count(TypeTag::State),
+            json: count(TypeTag::Json),
+            lists: count(TypeTag::List),
+            vectors: count(TypeTag::Vector),
+            custom: count(TypeTag::Custom),
+        },
+        memory_bytes: usage.memory_bytes as u64,
+    })
+}
+
+impl PrimitiveCounts {
+    pub(crate) fn add(&mut self, other: &PrimitiveCounts) {
+        self.kv += other.kv;
+        self.events += other.events;
+        self.state += other.state;
+        self.json += other.json;
+        self.lists += other.lists;
+        self.vectors += other.vectors;
+        self.custom += other.custom;
+    }
+}
+
+/// Error for a command whose primitive was left out of this build.
+#[cfg(not(all(feature = "vectors", feature = "search")))]
+fn not_compiled_in(cmd: &Command, feature: &str) -> Error {
+    Error::NotImplemented {
+        feature: cmd.name().into(),
+        reason: format!("requires the '{}' feature to be compiled in", feature),
+    }
+}
 
 /// The command executor - single entry point to Strata's engine.
 ///
@@ -53,6 +138,10 @@ pub struct Executor {
 
 impl Executor {
     /// Create a new executor from a database instance.
+    ///
+    /// Takes an engine type, so it is outside the stable API; use
+    /// [`Strata::executor`](crate::Strata::executor) instead.
+    #[doc(hidden)]
     pub fn new(db: Arc<Database>) -> Self {
         Self {
             primitives: Arc::new(Primitives::new(db)),
@@ -61,6 +150,9 @@ impl Executor {
     }
 
     /// Create a new executor with an explicit access mode.
+    ///
+    /// Outside the stable API, like [`new`](Self::new).
+    #[doc(hidden)]
     pub fn new_with_mode(db: Arc<Database>, access_mode: AccessMode) -> Self {
         Self {
             primitives: Arc::new(Primitives::new(db)),
@@ -87,40 +179,81 @@ impl Executor {
         Ok(())
     }
 
+    /// Cursor registry shared by every executor on this database.
+    fn cursors(&self) -> Result<Arc<CursorRegistry>> {
+        convert_result(self.primitives.db.extension::<CursorRegistry>())
+    }
+
     /// Execute a single command.
     ///
     /// Resolves any `None` branch fields to the default branch before dispatch.
     /// Returns the command result or an error.
-    pub fn execute(&self, mut cmd: Command) -> Result<Output> {
-        if self.access_mode == AccessMode::ReadOnly && cmd.is_write() {
-            warn!(target: "strata::command", command = %cmd.name(), "Write rejected in read-only mode");
+    pub fn execute(&self, cmd: Command) -> Result<Output> {
+        self.check_access(cmd.name(), cmd.is_write())?;
+        self.run(cmd)
+    }
+
+    /// Reject writes on a read-only executor.
+    fn check_access(&self, name: &'static str, is_write: bool) -> Result<()> {
+        if self.access_mode == AccessMode::ReadOnly && is_write {
+            warn!(target: "strata::command", command = %name, "Write rejected in read-only mode");
             return Err(Error::AccessDenied {
-                command: cmd.name().to_string(),
+                command: name.to_string(),
             });
         }
+        Ok(())
+    }
 
+    /// Resolve defaults, dispatch and record the latency of a command whose
+    /// access has been checked.
+    fn run(&self, mut cmd: Command) -> Result<Output> {
         cmd.resolve_defaults();
 
         let cmd_name = cmd.name();
+        // Reading or clearing the report should not show up in it.
+        let tracked = !matches!(cmd, Command::LatencyReport | Command::LatencyReset);
         let start = Instant::now();
+        let result = self.dispatch(cmd);
+        let elapsed = start.elapsed();
+
+        if tracked {
+            if let Ok(tracker) = self.primitives.db.extension::<LatencyTracker>() {
+                tracker.record(cmd_name, elapsed);
+            }
+        }
+
+        match &result {
+            Ok(_) => {
+                debug!(target: "strata::command", command = %cmd_name, duration_us = elapsed.as_micros() as u64, "Command executed");
+            }
+            Err(e) => {
+                warn!(target: "strata::command", command = %cmd_name, duration_us = elapsed.as_micros() as u64, error = %e, "Command failed");
+            }
+        }
 
-        let result = match cmd {
+        result
+    }
+
+    /// Run a resolved command against its handler.
+    fn dispatch(&self, cmd: Command) -> Result<Output> {
+        match cmd {
             // Database commands
             Command::Ping => Ok(Output::Pong {
                 version: env!("CARGO_PKG_VERSION").to_string(),
             }),
             Command::Info => {
-                let branch_count = self
-                    .primitives
-                    .branch
-                    .list_branches()
-                    .map(|ids| ids.len() as u64)
-                    .unwrap_or(0);
+                let names = self.primitives.branch.list_branches().unwrap_or_default();
+                let total_keys = names
+                    .iter()
+                    .map(|name| {
+                        branch_stats(&self.primitives.db, name).map(|b| b.primitives.total())
+                    })
+                    .sum::<Result<u64>>()?;
                 Ok(Output::DatabaseInfo(crate::types::DatabaseInfo {
                     version: env!("CARGO_PKG_VERSION").to_string(),
                     uptime_secs: 0,
-                    branch_count,
-                    total_keys: 0,
+                    branch_count: names.len() as u64,
+                    total_keys,
                 }))
             }
             Command::Flush => {
@@ -128,14 +261,23 @@ impl Executor {
                 Ok(Output::Unit)
             }
             Command::Compact => {
-                convert_result(self.primitives.db.compact())?;
-                Ok(Output::Unit)
+                let info = convert_result(self.primitives.db.compact())?;
+                Ok(Output::Compacted(crate::types::CompactResult {
+                    segments_removed: info.wal_segments_removed as u64,
+                    bytes_reclaimed: info.reclaimed_bytes,
+                    versions_removed: info.versions_removed as u64,
+                    watermark: info.snapshot_watermark,
+                    duration_ms: info.duration_ms,
+                }))
             }
+            Command::Stats => Ok(Output::Stats(Box::new(database_stats(
+                &self.primitives.db,
+            )?))),
             Command::TimeRange { branch } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
-                crate::handlers::vector::time_range(&self.primitives, branch)
+                crate::handlers::branch::time_range(&self.primitives, branch)
             }
 
             // KV commands (MVP: 4 commands)
@@ -221,6 +363,50 @@ impl Executor {
                 let space = space.unwrap_or_else(|| "default".to_string());
                 crate::handlers::kv::kv_getv(&self.primitives, branch, space, key)
             }
+            Command::KvIncr {
+                branch,
+                space,
+                key,
+                delta,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_incr(&self.primitives, branch, space, key, delta)
+            }
+            Command::KvHistory {
+                branch,
+                space,
+                key,
+                limit,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::kv::kv_history(&self.primitives, branch, space, key, limit)
+            }
+            Command::KvSetHistoryPolicy {
+                branch,
+                space,
+                prefix,
+                policy,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::kv::kv_set_history_policy(
+                    &self.primitives,
+                    branch,
+                    space,
+                    prefix,
+                    policy,
+                )
+            }
 
             // JSON commands
             Command::JsonSet {
@@ -288,6 +474,163 @@ impl Executor {
                 self.ensure_space_registered(&branch, &space)?;
                 crate::handlers::json::json_delete(&self.primitives, branch, space, key, path)
             }
+            Command::JsonGetPaths {
+                branch,
+                space,
+                key,
+                paths,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::json::json_get_paths(&self.primitives, branch, space, key, paths)
+            }
+            Command::JsonMerge {
+                branch,
+                space,
+                key,
+                patch,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_merge(&self.primitives, branch, space, key, patch)
+            }
+            Command::JsonPatch {
+                branch,
+                space,
+                key,
+                ops,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_patch(&self.primitives, branch, space, key, ops)
+            }
+            Command::JsonArrayAppend {
+                branch,
+                space,
+                key,
+                path,
+                value,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_array_append(
+                    &self.primitives,
+                    branch,
+                    space,
+                    key,
+                    path,
+                    value,
+                )
+            }
+            Command::JsonArrayInsert {
+                branch,
+                space,
+                key,
+                path,
+                index,
+                value,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_array_insert(
+                    &self.primitives,
+                    branch,
+                    space,
+                    key,
+                    path,
+                    index,
+                    value,
+                )
+            }
+            Command::JsonArrayRemove {
+                branch,
+                space,
+                key,
+                path,
+                index,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_array_remove(
+                    &self.primitives,
+                    branch,
+                    space,
+                    key,
+                    path,
+                    index,
+                )
+            }
+            Command::JsonArrayLen {
+                branch,
+                space,
+                key,
+                path,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::json::json_array_len(&self.primitives, branch, space, key, path)
+            }
+            Command::JsonSetSchema {
+                branch,
+                space,
+                prefix,
+                schema,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::json::json_set_schema(
+                    &self.primitives,
+                    branch,
+                    space,
+                    prefix,
+                    schema,
+                )
+            }
+            Command::JsonGetSchema {
+                branch,
+                space,
+                prefix,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::json::json_get_schema(&self.primitives, branch, space, prefix)
+            }
+            Command::JsonValidate {
+                branch,
+                space,
+                key,
+                value,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::json::json_validate(&self.primitives, branch, space, key, value)
+            }
             Command::JsonList {
                 branch,
                 space,
@@ -400,6 +743,139 @@ impl Executor {
                 let space = space.unwrap_or_else(|| "default".to_string());
                 crate::handlers::event::event_len(&self.primitives, branch, space)
             }
+            Command::EventSetRetention {
+                branch,
+                space,
+                event_type,
+                policy,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::event::event_set_retention(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    policy,
+                )
+            }
+            Command::EventSetSchema {
+                branch,
+                space,
+                event_type,
+                schema,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::event::event_set_schema(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    schema,
+                )
+            }
+            Command::EventGetSchema {
+                branch,
+                space,
+                event_type,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::event::event_get_schema(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                )
+            }
+            Command::EventCreateGroup {
+                branch,
+                space,
+                event_type,
+                group,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::event::event_create_group(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    group,
+                )
+            }
+            Command::EventReadGroup {
+                branch,
+                space,
+                event_type,
+                group,
+                consumer,
+                count,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::event::event_read_group(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    group,
+                    consumer,
+                    count,
+                )
+            }
+            Command::EventAck {
+                branch,
+                space,
+                event_type,
+                group,
+                sequences,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::event::event_ack(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    group,
+                    sequences,
+                )
+            }
+            Command::EventPending {
+                branch,
+                space,
+                event_type,
+                group,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::event::event_pending(
+                    &self.primitives,
+                    branch,
+                    space,
+                    event_type,
+                    group,
+                )
+            }
 
             // State commands (4 MVP)
             Command::StateSet {
@@ -438,12 +914,31 @@ impl Executor {
                 space,
                 cell,
                 as_of: _,
+                limit,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
-                crate::handlers::state::state_getv(&self.primitives, branch, space, cell)
+                crate::handlers::state::state_getv(&self.primitives, branch, space, cell, limit)
+            }
+            Command::StateGetVersion {
+                branch,
+                space,
+                cell,
+                version,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::state::state_get_version(
+                    &self.primitives,
+                    branch,
+                    space,
+                    cell,
+                    version,
+                )
             }
             Command::StateCas {
                 branch,
@@ -466,6 +961,19 @@ impl Executor {
                     value,
                 )
             }
+            Command::StateIncr {
+                branch,
+                space,
+                cell,
+                delta,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::state::state_incr(&self.primitives, branch, space, cell, delta)
+            }
             Command::StateInit {
                 branch,
                 space,
@@ -514,7 +1022,75 @@ impl Executor {
                 }
             }
 
+            // List commands
+            Command::ListPush {
+                branch,
+                space,
+                list,
+                end,
+                values,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                self.ensure_space_registered(&branch, &space)?;
+                crate::handlers::list::list_push(&self.primitives, branch, space, list, end, values)
+            }
+            Command::ListPop {
+                branch,
+                space,
+                list,
+                end,
+                timeout_ms,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::list::list_pop(
+                    &self.primitives,
+                    branch,
+                    space,
+                    list,
+                    end,
+                    timeout_ms,
+                )
+            }
+            Command::ListRange {
+                branch,
+                space,
+                list,
+                start,
+                stop,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::list::list_range(
+                    &self.primitives,
+                    branch,
+                    space,
+                    list,
+                    start,
+                    stop,
+                )
+            }
+            Command::ListLen {
+                branch,
+                space,
+                list,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::list::list_len(&self.primitives, branch, space, list)
+            }
+
             // Vector commands
+            #[cfg(feature = "vectors")]
             Command::VectorUpsert {
                 branch,
                 space,
@@ -522,6 +1098,7 @@ impl Executor {
                 key,
                 vector,
                 metadata,
+                namespace,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -536,8 +1113,10 @@ impl Executor {
                     key,
                     vector,
                     metadata,
+                    namespace,
                 )
             }
+            #[cfg(feature = "vectors")]
             Command::VectorGet {
                 branch,
                 space,
@@ -568,6 +1147,7 @@ impl Executor {
                     )
                 }
             }
+            #[cfg(feature = "vectors")]
             Command::VectorDelete {
                 branch,
                 space,
@@ -587,6 +1167,7 @@ impl Executor {
                     key,
                 )
             }
+            #[cfg(feature = "vectors")]
             Command::VectorSearch {
                 branch,
                 space,
@@ -596,11 +1177,17 @@ impl Executor {
                 filter,
                 metric,
                 as_of,
+                namespace,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
                 })?;
                 let space = space.unwrap_or_else(|| "default".to_string());
+                if as_of.is_some() && namespace.is_some() {
+                    return Err(Error::InvalidInput {
+                        reason: "VectorSearch namespace cannot be combined with as_of".into(),
+                    });
+                }
                 if let Some(ts) = as_of {
                     crate::handlers::vector::vector_search_at(
                         &self.primitives,
@@ -623,15 +1210,41 @@ impl Executor {
                         k,
                         filter,
                         metric,
+                        namespace,
                     )
                 }
             }
+            #[cfg(feature = "vectors")]
+            Command::VectorSearchBatch {
+                branch,
+                space,
+                collection,
+                queries,
+                k,
+                filter,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::vector::vector_search_batch(
+                    &self.primitives,
+                    branch,
+                    space,
+                    collection,
+                    queries,
+                    k,
+                    filter,
+                )
+            }
+            #[cfg(feature = "vectors")]
             Command::VectorCreateCollection {
                 branch,
                 space,
                 collection,
                 dimension,
                 metric,
+                storage_dtype,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -645,8 +1258,31 @@ impl Executor {
                     collection,
                     dimension,
                     metric,
+                    storage_dtype,
                 )
             }
+            #[cfg(feature = "vectors")]
+            Command::VectorReindex {
+                branch,
+                space,
+                collection,
+                metric,
+                index_type,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::vector::vector_reindex(
+                    &self.primitives,
+                    branch,
+                    space,
+                    collection,
+                    metric,
+                    index_type,
+                )
+            }
+            #[cfg(feature = "vectors")]
             Command::VectorDeleteCollection {
                 branch,
                 space,
@@ -664,6 +1300,7 @@ impl Executor {
                     collection,
                 )
             }
+            #[cfg(feature = "vectors")]
             Command::VectorListCollections { branch, space } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -671,6 +1308,7 @@ impl Executor {
                 let space = space.unwrap_or_else(|| "default".to_string());
                 crate::handlers::vector::vector_list_collections(&self.primitives, branch, space)
             }
+            #[cfg(feature = "vectors")]
             Command::VectorCollectionStats {
                 branch,
                 space,
@@ -687,11 +1325,13 @@ impl Executor {
                     collection,
                 )
             }
+            #[cfg(feature = "vectors")]
             Command::VectorBatchUpsert {
                 branch,
                 space,
                 collection,
                 entries,
+                namespace,
             } => {
                 let branch = branch.ok_or(Error::InvalidInput {
                     reason: "Branch must be specified or resolved to default".into(),
@@ -704,8 +1344,21 @@ impl Executor {
                     space,
                     collection,
                     entries,
+                    namespace,
                 )
             }
+            #[cfg(not(feature = "vectors"))]
+            cmd @ (Command::VectorUpsert { .. }
+            | Command::VectorGet { .. }
+            | Command::VectorDelete { .. }
+            | Command::VectorSearch { .. }
+            | Command::VectorSearchBatch { .. }
+            | Command::VectorCreateCollection { .. }
+            | Command::VectorReindex { .. }
+            | Command::VectorDeleteCollection { .. }
+            | Command::VectorListCollections { .. }
+            | Command::VectorCollectionStats { .. }
+            | Command::VectorBatchUpsert { .. }) => Err(not_compiled_in(&cmd, "vectors")),
 
             // Branch commands (5 MVP)
             Command::BranchCreate {
@@ -723,6 +1376,36 @@ impl Executor {
             Command::BranchExists { branch } => {
                 crate::handlers::branch::branch_exists(&self.primitives, branch)
             }
+            Command::BranchStats { branch } => {
+                crate::handlers::branch::branch_stats(&self.primitives, branch)
+            }
+            Command::BranchSetRetention { branch, retention } => {
+                crate::handlers::branch::branch_set_retention(&self.primitives, branch, retention)
+            }
+            Command::BranchGetRetention { branch } => {
+                crate::handlers::branch::branch_get_retention(&self.primitives, branch)
+            }
+            Command::BranchAddTag { branch, tag } => {
+                crate::handlers::branch::branch_add_tag(&self.primitives, branch, tag)
+            }
+            Command::BranchRemoveTag { branch, tag } => {
+                crate::handlers::branch::branch_remove_tag(&self.primitives, branch, tag)
+            }
+            Command::BranchSetMetadata { branch, key, value } => {
+                crate::handlers::branch::branch_set_metadata(&self.primitives, branch, key, value)
+            }
+            Command::BranchQuery {
+                filter,
+                limit,
+                offset,
+            } => crate::handlers::branch::branch_query(&self.primitives, filter, limit, offset),
+            Command::BranchCount => crate::handlers::branch::branch_count(&self.primitives),
+            Command::BranchClose { branch } => {
+                crate::handlers::branch::branch_close(&self.primitives, branch)
+            }
+            Command::BranchFail { branch, error } => {
+                crate::handlers::branch::branch_fail(&self.primitives, branch, error)
+            }
             Command::BranchDelete { branch } => {
                 crate::handlers::branch::branch_delete(&self.primitives, branch)
             }
@@ -747,6 +1430,10 @@ impl Executor {
                 // since they have been superseded by newer commits.
                 let current = self.primitives.db.current_version();
                 let _pruned = self.primitives.db.gc_versions_before(branch_id, current);
+                // Trim event streams whose policies are only enforced on append
+                for space in convert_result(self.primitives.space.list(branch_id))? {
+                    convert_result(self.primitives.event.apply_retention(&branch_id, &space))?;
+                }
                 Ok(Output::Unit)
             }
             Command::RetentionStats { .. } | Command::RetentionPreview { .. } => {
@@ -759,14 +1446,21 @@ impl Executor {
             Command::BranchExport { branch_id, path } => {
                 crate::handlers::branch::branch_export(&self.primitives, branch_id, path)
             }
-            Command::BranchImport { path } => {
-                crate::handlers::branch::branch_import(&self.primitives, path)
+            Command::BranchImport { path, as_branch } => {
+                crate::handlers::branch::branch_import(&self.primitives, path, as_branch)
             }
             Command::BranchBundleValidate { path } => {
                 crate::handlers::branch::branch_bundle_validate(path)
             }
+            Command::DatabaseExport { path } => {
+                crate::handlers::branch::database_export(&self.primitives, path)
+            }
+            Command::DatabaseImport { path, on_conflict } => {
+                crate::handlers::branch::database_import(&self.primitives, path, on_conflict)
+            }
 
             // Intelligence commands
+            #[cfg(feature = "search")]
             Command::Search {
                 branch,
                 space,
@@ -787,6 +1481,70 @@ impl Executor {
                     primitives,
                 )
             }
+            #[cfg(feature = "search")]
+            Command::TextSearch {
+                branch,
+                space,
+                query,
+                k,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::search::text_search(&self.primitives, branch, space, query, k)
+            }
+            #[cfg(feature = "search")]
+            Command::SemanticSearch {
+                branch,
+                space,
+                query,
+                k,
+                rerank_top_n,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::search::semantic_search(
+                    &self.primitives,
+                    branch,
+                    space,
+                    query,
+                    k,
+                    rerank_top_n,
+                )
+            }
+            #[cfg(feature = "search")]
+            Command::HybridSearch {
+                branch,
+                space,
+                collection,
+                query,
+                embedding,
+                k,
+                fusion,
+            } => {
+                let branch = branch.ok_or(Error::InvalidInput {
+                    reason: "Branch must be specified or resolved to default".into(),
+                })?;
+                let space = space.unwrap_or_else(|| "default".to_string());
+                crate::handlers::search::hybrid_search(
+                    &self.primitives,
+                    branch,
+                    space,
+                    collection,
+                    query,
+                    embedding,
+                    k,
+                    fusion,
+                )
+            }
+            #[cfg(not(feature = "search"))]
+            cmd @ (Command::Search { .. }
+            | Command::TextSearch { .. }
+            | Command::SemanticSearch { .. }
+            | Command::HybridSearch { .. }) => Err(not_compiled_in(&cmd, "search")),
 
             // Space commands
             Command::SpaceList { branch } => {
@@ -817,18 +1575,40 @@ impl Executor {
                 })?;
                 crate::handlers::space::space_exists(&self.primitives, branch, space)
             }
-        };
 
-        match &result {
-            Ok(_) => {
-                debug!(target: "strata::command", command = %cmd_name, duration_us = start.elapsed().as_micros() as u64, "Command executed");
+            // Cursor commands
+            Command::CursorOpen {
+                query,
+                page_size,
+                ttl_secs,
+            } => {
+                if query.is_write()
+                    || matches!(
+                        *query,
+                        Command::CursorOpen { .. }
+                            | Command::CursorFetch { .. }
+                            | Command::CursorClose { .. }
+                    )
+                {
+                    return Err(Error::InvalidInput {
+                        reason: format!("CursorOpen requires a read query, got {}", query.name()),
+                    });
+                }
+                let query_name = query.name();
+                let output = self.execute(*query)?;
+                self.cursors()?
+                    .open(query_name, output, page_size, ttl_secs)
             }
-            Err(e) => {
-                warn!(target: "strata::command", command = %cmd_name, duration_us = start.elapsed().as_micros() as u64, error = %e, "Command failed");
+            Command::CursorFetch { cursor } => self.cursors()?.fetch(&cursor),
+            Command::CursorClose { cursor } => Ok(Output::Bool(self.cursors()?.close(&cursor))),
+            Command::LatencyReport => Ok(Output::LatencyReport(
+                convert_result(self.primitives.db.extension::<LatencyTracker>())?.report(),
+            )),
+            Command::LatencyReset => {
+                convert_result(self.primitives.db.extension::<LatencyTracker>())?.reset();
+                Ok(Output::Unit)
             }
         }
-
-        result
     }
 
     /// Execute multiple commands sequentially.
@@ -839,7 +1619,31 @@ impl Executor {
         cmds.into_iter().map(|cmd| self.execute(cmd)).collect()
     }
 
+    /// Validate a command template for repeated execution.
+    ///
+    /// See [`PreparedCommand`] for the placeholder syntax. Write templates
+    /// are rejected up front on read-only executors.
+    pub fn prepare(&self, template: Command) -> Result<PreparedCommand> {
+        let stmt = PreparedCommand::new(template)?;
+        if self.access_mode == AccessMode::ReadOnly && stmt.is_write() {
+            return Err(Error::AccessDenied {
+                command: stmt.name().to_string(),
+            });
+        }
+        Ok(stmt)
+    }
+
+    /// Bind parameters to a prepared command and execute it.
+    ///
+    /// The access check uses what [`prepare`](Self::prepare) recorded about
+    /// the template, so only the bound values are inspected.
+    pub fn execute_prepared(&self, stmt: &PreparedCommand, params: Params) -> Result<Output> {
+        self.check_access(stmt.name(), stmt.is_write())?;
+        self.run(stmt.bind(params)?)
+    }
+
     /// Get a reference to the underlying primitives.
+    #[doc(hidden)]
     pub fn primitives(&self) -> &Arc<Primitives> {
         &self.primitives
     }
@@ -7,9 +7,12 @@ use std::sync::Arc;
 
 use strata_engine::BranchMetadata;
 
-use crate::bridge::{extract_version, from_engine_branch_status, Primitives};
+use crate::bridge::{
+    extract_version, from_engine_branch_retention, from_engine_branch_status,
+    to_engine_branch_filter, to_engine_branch_retention, Primitives,
+};
 use crate::convert::convert_result;
-use crate::types::{BranchId, BranchInfo, VersionedBranchInfo};
+use crate::types::{BranchFilter, BranchId, BranchInfo, BranchRetention, VersionedBranchInfo};
 use crate::{Error, Output, Result};
 
 // =============================================================================
@@ -17,13 +20,19 @@ use crate::{Error, Output, Result};
 // =============================================================================
 
 /// Convert engine BranchMetadata to executor BranchInfo.
-fn metadata_to_branch_info(m: &BranchMetadata) -> BranchInfo {
+pub(crate) fn metadata_to_branch_info(m: &BranchMetadata) -> BranchInfo {
     BranchInfo {
         id: BranchId::from(m.name.clone()),
         status: from_engine_branch_status(m.status),
         created_at: m.created_at,
         updated_at: m.updated_at,
-        parent_id: None,
+        completed_at: m.completed_at,
+        error: m.error.clone(),
+        parent_id: m.parent_branch.clone().map(BranchId::from),
+        fork_version: m.fork_version,
+        environment: m.environment.clone().map(Box::new),
+        tags: m.tags.clone(),
+        metadata: m.metadata.clone(),
     }
 }
 
@@ -123,9 +132,10 @@ pub fn branch_list(
     p: &Arc<Primitives>,
     _state: Option<crate::types::BranchStatus>,
     limit: Option<u64>,
-    _offset: Option<u64>,
+    offset: Option<u64>,
 ) -> Result<Output> {
-    // MVP: ignore status filter, list all branches
+    // MVP: ignore status filter, list all branches. Branch IDs come back in
+    // lexicographic order, so offset/limit pages are stable between calls.
     let ids = convert_result(p.branch.list_branches())?;
 
     let mut all = Vec::new();
@@ -135,10 +145,11 @@ pub fn branch_list(
         }
     }
 
-    // Apply limit if specified
+    // Apply offset and limit if specified
+    let skipped = all.into_iter().skip(offset.unwrap_or(0) as usize);
     let limited: Vec<VersionedBranchInfo> = match limit {
-        Some(l) => all.into_iter().take(l as usize).collect(),
-        None => all,
+        Some(l) => skipped.take(l as usize).collect(),
+        None => skipped.collect(),
     };
 
     Ok(Output::BranchInfoList(limited))
@@ -150,11 +161,130 @@ pub fn branch_exists(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
     Ok(Output::Bool(exists))
 }
 
+/// Handle BranchStats command.
+pub fn branch_stats(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
+    if !convert_result(p.branch.exists(branch.as_str()))? {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    Ok(Output::BranchStats(crate::executor::branch_stats(
+        &p.db,
+        branch.as_str(),
+    )?))
+}
+
+/// Handle BranchSetRetention command.
+pub fn branch_set_retention(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    retention: BranchRetention,
+) -> Result<Output> {
+    if !convert_result(p.branch.exists(branch.as_str()))? {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    convert_result(
+        p.branch
+            .set_retention(branch.as_str(), to_engine_branch_retention(&retention)),
+    )?;
+    Ok(Output::Unit)
+}
+
+/// Handle BranchGetRetention command.
+pub fn branch_get_retention(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
+    if !convert_result(p.branch.exists(branch.as_str()))? {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    let retention = convert_result(p.branch.retention(branch.as_str()))?
+        .map(|policy| from_engine_branch_retention(&policy))
+        .unwrap_or_default();
+    Ok(Output::BranchRetention(retention))
+}
+
+/// Fail with `BranchNotFound` unless `branch` exists.
+fn require_branch(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
+    if !convert_result(p.branch.exists(branch.as_str()))? {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Handle BranchAddTag command.
+pub fn branch_add_tag(p: &Arc<Primitives>, branch: BranchId, tag: String) -> Result<Output> {
+    require_branch(p, &branch)?;
+    let added = convert_result(p.branch.add_tag(branch.as_str(), &tag))?;
+    Ok(Output::Bool(added))
+}
+
+/// Handle BranchRemoveTag command.
+pub fn branch_remove_tag(p: &Arc<Primitives>, branch: BranchId, tag: String) -> Result<Output> {
+    require_branch(p, &branch)?;
+    let removed = convert_result(p.branch.remove_tag(branch.as_str(), &tag))?;
+    Ok(Output::Bool(removed))
+}
+
+/// Handle BranchSetMetadata command.
+pub fn branch_set_metadata(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    key: String,
+    value: strata_core::Value,
+) -> Result<Output> {
+    require_branch(p, &branch)?;
+    convert_result(p.branch.set_metadata(branch.as_str(), &key, value))?;
+    Ok(Output::Unit)
+}
+
+/// Handle BranchQuery command.
+pub fn branch_query(
+    p: &Arc<Primitives>,
+    filter: BranchFilter,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> Result<Output> {
+    let matched = convert_result(p.branch.query(&to_engine_branch_filter(filter)))?;
+    let skipped = matched
+        .into_iter()
+        .skip(offset.unwrap_or(0) as usize)
+        .map(versioned_to_branch_info);
+    let limited: Vec<VersionedBranchInfo> = match limit {
+        Some(l) => skipped.take(l as usize).collect(),
+        None => skipped.collect(),
+    };
+    Ok(Output::BranchInfoList(limited))
+}
+
+/// Handle BranchCount command.
+pub fn branch_count(p: &Arc<Primitives>) -> Result<Output> {
+    Ok(Output::Uint(convert_result(p.branch.count())?))
+}
+
+/// Handle BranchClose command.
+pub fn branch_close(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
+    reject_default_branch(&branch, "close")?;
+    require_branch(p, &branch)?;
+    convert_result(p.branch.close_branch(branch.as_str()))?;
+    Ok(Output::Unit)
+}
+
+/// Handle BranchFail command.
+pub fn branch_fail(p: &Arc<Primitives>, branch: BranchId, error: String) -> Result<Output> {
+    reject_default_branch(&branch, "fail")?;
+    require_branch(p, &branch)?;
+    convert_result(p.branch.fail_branch(branch.as_str(), &error))?;
+    Ok(Output::Unit)
+}
+
 /// Handle BranchDelete command.
 ///
-/// After deleting the branch metadata, performs cleanup:
-/// - Removes the per-branch commit lock to prevent unbounded growth (#944)
-/// - Deletes all vector collections for the branch to free memory (#946)
+/// The engine drops the branch's index entries along with its data. After
+/// that, removes the per-branch commit lock to prevent unbounded growth (#944).
 pub fn branch_delete(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
     reject_default_branch(&branch, "delete")?;
     convert_result(p.branch.delete_branch(branch.as_str()))?;
@@ -163,22 +293,27 @@ pub fn branch_delete(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
     // Convert the executor BranchId to core BranchId for the lock cleanup
     if let Ok(core_branch_id) = crate::bridge::to_core_branch_id(&branch) {
         p.db.remove_branch_lock(&core_branch_id);
-
-        // Cleanup: delete all vector collections for this branch (#946)
-        // Best-effort: silently continue if vector cleanup fails, since the
-        // branch metadata is already deleted and data will be orphaned but harmless.
-        if let Ok(collections) = p.vector.list_collections(core_branch_id, "default") {
-            for collection in collections {
-                let _ = p
-                    .vector
-                    .delete_collection(core_branch_id, "default", &collection.name);
-            }
-        }
     }
 
     Ok(Output::Unit)
 }
 
+/// Handle TimeRange command — get the available time range for a branch.
+pub fn time_range(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
+    let branch_id = crate::bridge::to_core_branch_id(&branch)?;
+    let range = convert_result(p.db.time_range(branch_id))?;
+    match range {
+        Some((oldest, latest)) => Ok(Output::TimeRange {
+            oldest_ts: Some(oldest),
+            latest_ts: Some(latest),
+        }),
+        None => Ok(Output::TimeRange {
+            oldest_ts: None,
+            latest_ts: None,
+        }),
+    }
+}
+
 // =============================================================================
 // Bundle Handlers
 // =============================================================================
@@ -202,17 +337,36 @@ pub fn branch_export(p: &Arc<Primitives>, branch_id: String, path: String) -> Re
 }
 
 /// Handle BranchImport command.
-pub fn branch_import(p: &Arc<Primitives>, path: String) -> Result<Output> {
+///
+/// With `as_branch`, the bundle is imported into a new branch of that name,
+/// so it can sit next to the branch it was exported from.
+pub fn branch_import(
+    p: &Arc<Primitives>,
+    path: String,
+    as_branch: Option<String>,
+) -> Result<Output> {
     let import_path = std::path::Path::new(&path);
-    let info = strata_engine::bundle::import_branch(&p.db, import_path).map_err(|e| Error::Io {
+    let result = match &as_branch {
+        Some(name) => {
+            validate_branch_name(name)?;
+            strata_engine::bundle::import_branch_as(&p.db, import_path, name)
+        }
+        None => strata_engine::bundle::import_branch(&p.db, import_path),
+    };
+    let info = result.map_err(|e| Error::Io {
         reason: format!("Import failed: {}", e),
     })?;
 
-    Ok(Output::BranchImported(crate::types::BranchImportResult {
+    Ok(Output::BranchImported(to_import_result(info)))
+}
+
+fn to_import_result(info: strata_engine::ImportInfo) -> crate::types::BranchImportResult {
+    crate::types::BranchImportResult {
         branch_id: info.branch_id,
         transactions_applied: info.transactions_applied,
         keys_written: info.keys_written,
-    }))
+        renamed_from: info.renamed_from,
+    }
 }
 
 /// Handle BranchBundleValidate command.
@@ -228,6 +382,46 @@ pub fn branch_bundle_validate(path: String) -> Result<Output> {
             format_version: info.format_version,
             entry_count: info.entry_count,
             checksums_valid: info.checksums_valid,
+            environment: info.environment.map(Box::new),
+        },
+    ))
+}
+
+/// Handle DatabaseExport command.
+pub fn database_export(p: &Arc<Primitives>, path: String) -> Result<Output> {
+    let export_path = std::path::Path::new(&path);
+    let info = strata_engine::bundle::export_all(&p.db, export_path).map_err(|e| Error::Io {
+        reason: format!("Export failed: {}", e),
+    })?;
+
+    Ok(Output::DatabaseExported(
+        crate::types::DatabaseExportResult {
+            path: info.path.to_string_lossy().to_string(),
+            branches: info.branches,
+            entry_count: info.entry_count,
+            bundle_size: info.bundle_size,
+        },
+    ))
+}
+
+/// Handle DatabaseImport command.
+pub fn database_import(
+    p: &Arc<Primitives>,
+    path: String,
+    on_conflict: crate::types::ImportConflict,
+) -> Result<Output> {
+    let import_path = std::path::Path::new(&path);
+    let on_conflict = crate::bridge::to_engine_import_conflict(on_conflict);
+    let info = strata_engine::bundle::import_all(&p.db, import_path, on_conflict).map_err(|e| {
+        Error::Io {
+            reason: format!("Import failed: {}", e),
+        }
+    })?;
+
+    Ok(Output::DatabaseImported(
+        crate::types::DatabaseImportResult {
+            imported: info.imported.into_iter().map(to_import_result).collect(),
+            skipped: info.skipped,
         },
     ))
 }
@@ -257,9 +451,16 @@ mod tests {
             completed_at: None,
             error: None,
             version: 1,
+            environment: None,
+            retention: None,
+            fork_version: None,
+            detached: false,
+            tags: vec!["eval".to_string()],
+            metadata: Default::default(),
         };
         let info = metadata_to_branch_info(&m);
         assert_eq!(info.id.as_str(), "test-branch");
         assert_eq!(info.status, crate::types::BranchStatus::Active);
+        assert_eq!(info.tags, vec!["eval"]);
     }
 }
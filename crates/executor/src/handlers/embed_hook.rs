@@ -2,7 +2,9 @@
 //!
 //! When auto-embedding is enabled and the `embed` feature is compiled in,
 //! this module generates embeddings for text values and stores them in
-//! shadow vector collections.
+//! shadow vector collections. The work is queued on the database's
+//! [`EmbedQueue`](crate::embed_queue::EmbedQueue) so writes never wait on
+//! the model.
 
 use std::sync::Arc;
 
@@ -19,7 +21,12 @@ pub const SHADOW_STATE: &str = "_system_embed_state";
 #[cfg(feature = "embed")]
 const SHADOW_KEY_SEP: char = '\x1f';
 
-/// Attempt to embed text and store in a shadow vector collection.
+/// Queue embedding of `text` into a shadow vector collection.
+///
+/// The text is split into overlapping chunks and each chunk gets its own
+/// vector, with the chunk's byte offsets in the metadata, so search can
+/// return the passage that matched. Chunks left over from a longer earlier
+/// version of the record are removed.
 ///
 /// Best-effort: failures are logged, never propagated to the caller.
 /// The `source_ref` traces the shadow embedding back to the originating record.
@@ -28,60 +35,141 @@ pub fn maybe_embed_text(
     p: &Arc<Primitives>,
     branch_id: strata_core::types::BranchId,
     space: &str,
-    shadow_collection: &str,
+    shadow_collection: &'static str,
     key: &str,
     text: &str,
     source_ref: strata_core::EntityRef,
 ) {
-    use strata_intelligence::embed::EmbedModelState;
+    use strata_intelligence::chunking::{chunk_text, ChunkConfig};
 
     if !p.db.auto_embed_enabled() {
         return;
     }
 
-    let model_dir = p.db.model_dir();
-    let embed_state = match p.db.extension::<EmbedModelState>() {
-        Ok(s) => s,
-        Err(e) => {
-            tracing::warn!(target: "strata::embed", error = %e, "Failed to get embed model state");
-            return;
-        }
+    let target = ShadowTarget {
+        branch_id,
+        space: space.to_string(),
+        collection: shadow_collection,
+        key: key.to_string(),
     };
+    let chunks = chunk_text(text, &ChunkConfig::default());
+    let chunk_count = chunks.len();
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let embed_p = p.clone();
+        let store_p = p.clone();
+        let target = target.clone();
+        let span = ChunkSpan {
+            index,
+            start: chunk.start,
+            end: chunk.end,
+        };
+        let source_ref = source_ref.clone();
+        enqueue_text(
+            p,
+            crate::embed_queue::TextJob {
+                text: chunk.text.to_string(),
+                embed: Box::new(move |texts| embed_batch(&embed_p, texts)),
+                finish: Box::new(move |embedding| match embedding {
+                    Ok(embedding) => {
+                        store_embedding(&store_p, &target, span, &embedding, source_ref)
+                    }
+                    Err(e) => {
+                        tracing::warn!(target: "strata::embed", error = %e, "Failed to embed text");
+                    }
+                }),
+            },
+        );
+    }
 
-    let model = match embed_state.get_or_load(&model_dir) {
-        Ok(m) => m,
-        Err(e) => {
-            tracing::warn!(target: "strata::embed", error = %e, "Failed to load embedding model");
-            return;
+    // Queued after the chunks, so it runs once they are stored
+    let job_p = p.clone();
+    enqueue(p, move || remove_chunks(&job_p, &target, chunk_count));
+}
+
+/// The shadow entries of one source record.
+#[cfg(feature = "embed")]
+#[derive(Clone)]
+struct ShadowTarget {
+    branch_id: strata_core::types::BranchId,
+    space: String,
+    collection: &'static str,
+    key: String,
+}
+
+#[cfg(feature = "embed")]
+impl ShadowTarget {
+    /// Shadow key of a chunk: "{space}\x1f{key}" for the first chunk, with
+    /// "\x1f{index}" appended for the rest.
+    fn chunk_key(&self, index: usize) -> String {
+        if index == 0 {
+            format!("{}{}{}", self.space, SHADOW_KEY_SEP, self.key)
+        } else {
+            format!(
+                "{}{}{}{}{}",
+                self.space, SHADOW_KEY_SEP, self.key, SHADOW_KEY_SEP, index
+            )
         }
-    };
+    }
+}
 
-    let embedding = model.embed(text);
+/// Position of a chunk within the text extracted from its record.
+#[cfg(feature = "embed")]
+#[derive(Clone, Copy)]
+struct ChunkSpan {
+    index: usize,
+    start: usize,
+    end: usize,
+}
 
-    // Ensure shadow collection exists (384-dim cosine)
-    ensure_shadow_collection(p, branch_id, shadow_collection);
+/// Embed a batch of texts with the database's provider (runs on the worker).
+#[cfg(feature = "embed")]
+fn embed_batch(p: &Arc<Primitives>, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+    use strata_intelligence::embed::EmbedModelState;
+
+    let embed_state =
+        p.db.extension::<EmbedModelState>()
+            .map_err(|e| format!("Failed to get embed model state: {}", e))?;
+    let embedder = embed_state
+        .embedder(&p.db)
+        .map_err(|e| format!("Failed to load embedding model: {}", e))?;
+    embedder.embed_batch(texts)
+}
 
-    // Build composite key: "{space}\x1f{key}"
-    let composite_key = format!("{}{}{}", space, SHADOW_KEY_SEP, key);
+/// Store a chunk's embedding in a shadow vector collection (runs on the worker).
+#[cfg(feature = "embed")]
+fn store_embedding(
+    p: &Arc<Primitives>,
+    target: &ShadowTarget,
+    chunk: ChunkSpan,
+    embedding: &[f32],
+    source_ref: strata_core::EntityRef,
+) {
+    // Ensure shadow collection exists (cosine, sized to the provider's vectors)
+    ensure_shadow_collection(p, target.branch_id, target.collection, embedding.len());
 
-    // Build source metadata
+    let shadow_key = target.chunk_key(chunk.index);
+
+    // Build source metadata; offsets are bytes into the extracted text
     let metadata = serde_json::json!({
-        "source_space": space,
-        "source_key": key,
+        "source_space": target.space,
+        "source_key": target.key,
+        "chunk_index": chunk.index,
+        "chunk_start": chunk.start,
+        "chunk_end": chunk.end,
     });
 
     if let Err(e) = p.vector.system_insert_with_source(
-        branch_id,
-        shadow_collection,
-        &composite_key,
-        &embedding,
+        target.branch_id,
+        target.collection,
+        &shadow_key,
+        embedding,
         Some(metadata),
         source_ref,
     ) {
         tracing::warn!(
             target: "strata::embed",
-            collection = shadow_collection,
-            key = composite_key,
+            collection = target.collection,
+            key = shadow_key,
             error = %e,
             "Failed to insert embedding"
         );
@@ -94,14 +182,17 @@ pub fn maybe_embed_text(
     _p: &Arc<Primitives>,
     _branch_id: strata_core::types::BranchId,
     _space: &str,
-    _shadow_collection: &str,
+    _shadow_collection: &'static str,
     _key: &str,
     _text: &str,
     _source_ref: strata_core::EntityRef,
 ) {
 }
 
-/// Remove a shadow embedding entry on delete.
+/// Queue removal of a record's shadow embeddings on delete.
+///
+/// Removals share the queue with embeds, so a delete that follows a write
+/// always removes the embeddings that write produced.
 ///
 /// Best-effort: failures are logged, never propagated to the caller.
 #[cfg(feature = "embed")]
@@ -109,28 +200,49 @@ pub fn maybe_remove_embedding(
     p: &Arc<Primitives>,
     branch_id: strata_core::types::BranchId,
     space: &str,
-    shadow_collection: &str,
+    shadow_collection: &'static str,
     key: &str,
 ) {
     if !p.db.auto_embed_enabled() {
         return;
     }
 
-    let composite_key = format!("{}{}{}", space, SHADOW_KEY_SEP, key);
+    let job_p = p.clone();
+    let target = ShadowTarget {
+        branch_id,
+        space: space.to_string(),
+        collection: shadow_collection,
+        key: key.to_string(),
+    };
+    enqueue(p, move || remove_chunks(&job_p, &target, 0));
+}
 
-    if let Err(e) = p
-        .vector
-        .system_delete(branch_id, shadow_collection, &composite_key)
-    {
-        // Collection or vector may not exist yet (no embeds were ever created), that's fine.
-        if !e.is_not_found() {
-            tracing::warn!(
-                target: "strata::embed",
-                collection = shadow_collection,
-                key = composite_key,
-                error = %e,
-                "Failed to remove shadow embedding"
-            );
+/// Delete shadow chunks from index `from` onwards (runs on the worker).
+///
+/// A record's chunks are numbered contiguously, so deletion stops at the
+/// first missing chunk.
+#[cfg(feature = "embed")]
+fn remove_chunks(p: &Arc<Primitives>, target: &ShadowTarget, from: usize) {
+    for index in from.. {
+        let shadow_key = target.chunk_key(index);
+        match p
+            .vector
+            .system_delete(target.branch_id, target.collection, &shadow_key)
+        {
+            Ok(true) => {}
+            Ok(false) => break,
+            // Collection may not exist yet (no embeds were ever created), that's fine.
+            Err(e) if e.is_not_found() => break,
+            Err(e) => {
+                tracing::warn!(
+                    target: "strata::embed",
+                    collection = target.collection,
+                    key = shadow_key,
+                    error = %e,
+                    "Failed to remove shadow embedding"
+                );
+                break;
+            }
         }
     }
 }
@@ -141,11 +253,37 @@ pub fn maybe_remove_embedding(
     _p: &Arc<Primitives>,
     _branch_id: strata_core::types::BranchId,
     _space: &str,
-    _shadow_collection: &str,
+    _shadow_collection: &'static str,
     _key: &str,
 ) {
 }
 
+/// Hand a job to the database's embed queue.
+#[cfg(feature = "embed")]
+fn enqueue(p: &Arc<Primitives>, job: impl FnOnce() + Send + 'static) {
+    use crate::embed_queue::EmbedQueue;
+
+    match p.db.extension::<EmbedQueue>() {
+        Ok(queue) => queue.enqueue(Box::new(job)),
+        Err(e) => {
+            tracing::warn!(target: "strata::embed", error = %e, "Failed to get embed queue");
+        }
+    }
+}
+
+/// Hand text to the database's embed queue for batched embedding.
+#[cfg(feature = "embed")]
+fn enqueue_text(p: &Arc<Primitives>, job: crate::embed_queue::TextJob) {
+    use crate::embed_queue::EmbedQueue;
+
+    match p.db.extension::<EmbedQueue>() {
+        Ok(queue) => queue.enqueue_text(job),
+        Err(e) => {
+            tracing::warn!(target: "strata::embed", error = %e, "Failed to get embed queue");
+        }
+    }
+}
+
 /// Extract embeddable text from a Value.
 #[cfg(feature = "embed")]
 pub fn extract_text(value: &strata_core::Value) -> Option<String> {
@@ -176,8 +314,9 @@ fn ensure_shadow_collection(
     p: &Arc<Primitives>,
     branch_id: strata_core::types::BranchId,
     name: &str,
+    dimension: usize,
 ) {
-    use strata_core::primitives::vector::VectorConfig;
+    use strata_core::primitives::vector::{DistanceMetric, VectorConfig};
     use strata_engine::database::AutoEmbedState;
 
     let cache_key = format!("{:?}{}{}", branch_id.as_bytes(), SHADOW_KEY_SEP, name);
@@ -194,7 +333,13 @@ fn ensure_shadow_collection(
         return;
     }
 
-    let config = VectorConfig::for_minilm();
+    let config = match VectorConfig::new(dimension, DistanceMetric::Cosine) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!(target: "strata::embed", collection = name, error = %e, "Invalid embedding dimension");
+            return;
+        }
+    };
 
     match p.vector.create_system_collection(branch_id, name, config) {
         Ok(_) => {
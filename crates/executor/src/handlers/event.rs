@@ -1,12 +1,15 @@
-//! Event command handlers (4 MVP).
+//! Event command handlers.
 //!
-//! MVP: append, read, get_by_type, len
+//! MVP: append, read, get_by_type, len; plus per-stream retention, payload
+//! schemas and consumer groups
 
 use std::sync::Arc;
 
+use strata_core::Value;
+
 use crate::bridge::{self, validate_value, Primitives};
 use crate::convert::convert_result;
-use crate::types::{BranchId, VersionedValue};
+use crate::types::{BranchId, PendingEvent, RetentionPolicy, VersionedValue};
 use crate::{Error, Output, Result};
 
 /// Validate that a branch exists before performing a write operation (#951).
@@ -199,6 +202,150 @@ pub fn event_len(p: &Arc<Primitives>, branch: BranchId, space: String) -> Result
     Ok(Output::Uint(count))
 }
 
+/// Handle EventSetRetention command.
+pub fn event_set_retention(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    policy: RetentionPolicy,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let policy = bridge::to_engine_retention(policy);
+    let trimmed =
+        convert_result(
+            p.event
+                .set_retention(&core_branch_id, &space, &event_type, policy),
+        )?;
+    Ok(Output::Uint(trimmed))
+}
+
+/// Handle EventSetSchema command.
+pub fn event_set_schema(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    schema: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    convert_result(validate_value(&schema, &p.limits))?;
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    convert_result(
+        p.event
+            .set_schema(&core_branch_id, &space, &event_type, schema),
+    )?;
+    Ok(Output::Unit)
+}
+
+/// Handle EventGetSchema command.
+pub fn event_get_schema(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+) -> Result<Output> {
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let schema = convert_result(p.event.schema(&core_branch_id, &space, &event_type))?;
+    Ok(Output::Maybe(schema))
+}
+
+/// Handle EventCreateGroup command.
+pub fn event_create_group(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    group: String,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let created =
+        convert_result(
+            p.event
+                .create_group(&core_branch_id, &space, &event_type, &group),
+        )?;
+    Ok(Output::Bool(created))
+}
+
+/// Handle EventReadGroup command.
+pub fn event_read_group(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    group: String,
+    consumer: String,
+    count: u64,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let events = convert_result(p.event.read_group(
+        &core_branch_id,
+        &space,
+        &event_type,
+        &group,
+        &consumer,
+        count as usize,
+    ))?;
+
+    let versioned: Vec<VersionedValue> = events
+        .into_iter()
+        .map(|e| VersionedValue {
+            value: e.value.payload.clone(),
+            version: bridge::extract_version(&e.version),
+            timestamp: strata_core::Timestamp::from_micros(e.value.timestamp).into(),
+        })
+        .collect();
+
+    Ok(Output::VersionedValues(versioned))
+}
+
+/// Handle EventAck command.
+pub fn event_ack(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    group: String,
+    sequences: Vec<u64>,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let acked =
+        convert_result(
+            p.event
+                .ack(&core_branch_id, &space, &event_type, &group, &sequences),
+        )?;
+    Ok(Output::Uint(acked))
+}
+
+/// Handle EventPending command.
+pub fn event_pending(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    event_type: String,
+    group: String,
+) -> Result<Output> {
+    let core_branch_id = bridge::to_core_branch_id(&branch)?;
+    let pending = convert_result(
+        p.event
+            .pending(&core_branch_id, &space, &event_type, &group),
+    )?;
+    Ok(Output::PendingEvents(
+        pending
+            .into_iter()
+            .map(|e| PendingEvent {
+                sequence: e.sequence,
+                consumer: e.consumer,
+                delivered_at: e.delivered_at,
+            })
+            .collect(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,11 @@
 //! JSON command handlers (MVP).
 //!
-//! This module implements handlers for the 4 MVP JSON commands.
+//! This module implements handlers for the 4 MVP JSON commands, multi-path
+//! reads, merge and patch updates, array operations, and document schemas.
 
 use std::sync::Arc;
 
+use strata_core::primitives::json::PatchOperation;
 use strata_core::Value;
 
 use crate::bridge::{
@@ -11,7 +13,7 @@ use crate::bridge::{
     value_to_json, Primitives,
 };
 use crate::convert::convert_result;
-use crate::types::{BranchId, VersionedValue};
+use crate::types::{BranchId, SchemaViolation, VersionedValue};
 use crate::{Error, Output, Result};
 
 /// Validate that a branch exists before performing a write operation (#951).
@@ -229,6 +231,237 @@ pub fn json_list(
     })
 }
 
+/// Handle JsonGetPaths command.
+///
+/// Only the requested subtrees are converted and returned; missing paths,
+/// or every path of a missing document, come back as `None`.
+pub fn json_get_paths(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    paths: Vec<String>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let json_paths = paths
+        .iter()
+        .map(|path| convert_result(parse_path(path)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let values = convert_result(p.json.get_paths(&branch_id, &space, &key, &json_paths))?;
+    let values = values
+        .into_iter()
+        .map(|v| v.map(|v| convert_result(json_to_value(v))).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Output::MaybeValues(values))
+}
+
+/// Handle JsonMerge command.
+///
+/// Applies an RFC 7396 merge patch in a single transaction, creating the
+/// document if it doesn't exist.
+pub fn json_merge(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    patch: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    convert_result(validate_value(&patch, &p.limits))?;
+    let json_patch = convert_result(value_to_json(patch))?;
+
+    let version = convert_result(p.json.merge(&branch_id, &space, &key, json_patch))?;
+    embed_full_doc(p, branch_id, &space, &key);
+
+    Ok(Output::Version(extract_version(&version)))
+}
+
+/// Handle JsonPatch command.
+///
+/// Applies RFC 6902 operations in a single transaction; if any operation
+/// fails the document is left unchanged.
+pub fn json_patch(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    ops: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    convert_result(validate_value(&ops, &p.limits))?;
+    let json_ops = convert_result(value_to_json(ops))?;
+    let ops: Vec<PatchOperation> =
+        serde_json::from_value(json_ops.into_inner()).map_err(|e| Error::InvalidInput {
+            reason: format!("invalid JSON Patch: {}", e),
+        })?;
+
+    let version = convert_result(p.json.patch(&branch_id, &space, &key, &ops))?;
+    embed_full_doc(p, branch_id, &space, &key);
+
+    Ok(Output::Version(extract_version(&version)))
+}
+
+/// Handle JsonArrayAppend command.
+///
+/// Returns the array length after the append.
+pub fn json_array_append(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    path: String,
+    value: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    convert_result(validate_value(&value, &p.limits))?;
+    let json_path = convert_result(parse_path(&path))?;
+    let json_value = convert_result(value_to_json(value))?;
+
+    let len = convert_result(
+        p.json
+            .array_append(&branch_id, &space, &key, &json_path, json_value),
+    )?;
+    embed_full_doc(p, branch_id, &space, &key);
+
+    Ok(Output::Uint(len as u64))
+}
+
+/// Handle JsonArrayInsert command.
+///
+/// Returns the array length after the insert.
+pub fn json_array_insert(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    path: String,
+    index: u64,
+    value: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    convert_result(validate_value(&value, &p.limits))?;
+    let json_path = convert_result(parse_path(&path))?;
+    let json_value = convert_result(value_to_json(value))?;
+
+    let len = convert_result(p.json.array_insert(
+        &branch_id,
+        &space,
+        &key,
+        &json_path,
+        index as usize,
+        json_value,
+    ))?;
+    embed_full_doc(p, branch_id, &space, &key);
+
+    Ok(Output::Uint(len as u64))
+}
+
+/// Handle JsonArrayRemove command.
+///
+/// Returns the removed element.
+pub fn json_array_remove(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    path: String,
+    index: u64,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let json_path = convert_result(parse_path(&path))?;
+
+    let index = index as usize;
+    let removed = convert_result(
+        p.json
+            .array_remove(&branch_id, &space, &key, &json_path, index),
+    )?;
+    embed_full_doc(p, branch_id, &space, &key);
+
+    Ok(Output::Maybe(Some(convert_result(json_to_value(removed))?)))
+}
+
+/// Handle JsonArrayLen command.
+///
+/// Returns `None` if the document or path doesn't exist.
+pub fn json_array_len(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    path: String,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let json_path = convert_result(parse_path(&path))?;
+    let len = convert_result(p.json.array_len(&branch_id, &space, &key, &json_path))?;
+    Ok(Output::Maybe(len.map(|n| Value::Int(n as i64))))
+}
+
+/// Handle JsonSetSchema command.
+pub fn json_set_schema(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    prefix: String,
+    schema: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    convert_result(validate_value(&schema, &p.limits))?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(p.json.set_schema(&branch_id, &space, &prefix, schema))?;
+    Ok(Output::Unit)
+}
+
+/// Handle JsonGetSchema command.
+pub fn json_get_schema(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    prefix: String,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    let schema = convert_result(p.json.schema(&branch_id, &space, &prefix))?;
+    Ok(Output::Maybe(schema))
+}
+
+/// Handle JsonValidate command.
+///
+/// Checks `value` against the schema governing `key` and reports every
+/// violation; nothing is written.
+pub fn json_validate(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    value: Value,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let json_value = convert_result(value_to_json(value))?;
+    let violations = convert_result(p.json.validate(&branch_id, &space, &key, &json_value))?;
+    Ok(Output::SchemaViolations(
+        violations
+            .into_iter()
+            .map(|v| SchemaViolation {
+                path: v.path,
+                message: v.message,
+            })
+            .collect(),
+    ))
+}
+
 /// Best-effort: read back the full JSON document and embed its complete text.
 ///
 /// This ensures that partial-path writes (e.g. `$.name`) produce an embedding
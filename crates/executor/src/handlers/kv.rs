@@ -8,11 +8,11 @@ use std::sync::Arc;
 use strata_core::Value;
 
 use crate::bridge::{
-    extract_version, to_core_branch_id, to_versioned_value, validate_key, validate_value,
-    Primitives,
+    extract_version, from_engine_retention, to_core_branch_id, to_engine_retention,
+    to_versioned_value, validate_key, validate_value, Primitives,
 };
 use crate::convert::convert_result;
-use crate::types::BranchId;
+use crate::types::{BranchId, KvHistory, RetentionPolicy};
 use crate::{Error, Output, Result};
 
 /// Validate that a branch exists before performing a write operation (#951).
@@ -53,6 +53,63 @@ pub fn kv_getv(
     Ok(Output::VersionHistory(mapped))
 }
 
+/// Handle KvIncr command — atomically add to a numeric value.
+pub fn kv_incr(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    delta: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let result = convert_result(p.kv.incr(&branch_id, &space, &key, delta))?;
+    Ok(Output::MaybeVersioned(Some(to_versioned_value(result))))
+}
+
+/// Handle KvHistory command — version history under the key's policy.
+pub fn kv_history(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    key: String,
+    limit: Option<u64>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&key))?;
+    let limit = limit.map(|n| n as usize);
+    let history = convert_result(p.kv.history(&branch_id, &space, &key, limit))?;
+    Ok(Output::KvHistory(KvHistory {
+        versions: history
+            .versions
+            .into_iter()
+            .map(to_versioned_value)
+            .collect(),
+        policy: history.policy.as_ref().map(from_engine_retention),
+        truncated: history.truncated,
+    }))
+}
+
+/// Handle KvSetHistoryPolicy command.
+pub fn kv_set_history_policy(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    prefix: String,
+    policy: RetentionPolicy,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(p.kv.set_history_policy(
+        &branch_id,
+        &space,
+        &prefix,
+        to_engine_retention(policy),
+    ))?;
+    Ok(Output::Unit)
+}
+
 // =============================================================================
 // MVP Handlers (4 commands)
 // =============================================================================
@@ -0,0 +1,101 @@
+//! List command handlers.
+//!
+//! This module implements handlers for the 4 List commands by dispatching
+//! directly to engine primitives via `bridge::Primitives`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use strata_core::Value;
+
+use crate::bridge::{to_core_branch_id, validate_key, validate_value, Primitives};
+use crate::convert::convert_result;
+use crate::types::{BranchId, ListEnd};
+use crate::{Error, Output, Result};
+
+/// Validate that a branch exists before performing a write operation (#951).
+///
+/// The default branch is always allowed (it is implicit and not stored in BranchIndex).
+fn require_branch_exists(p: &Arc<Primitives>, branch: &BranchId) -> Result<()> {
+    if branch.is_default() {
+        return Ok(());
+    }
+    let exists = convert_result(p.branch.exists(branch.as_str()))?;
+    if !exists {
+        return Err(Error::BranchNotFound {
+            branch: branch.as_str().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Handle ListPush command.
+pub fn list_push(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    list: String,
+    end: ListEnd,
+    values: Vec<Value>,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&list))?;
+    for value in &values {
+        convert_result(validate_value(value, &p.limits))?;
+    }
+    let len = match end {
+        ListEnd::Left => convert_result(p.list.lpush(&branch_id, &space, &list, values))?,
+        ListEnd::Right => convert_result(p.list.rpush(&branch_id, &space, &list, values))?,
+    };
+    Ok(Output::Uint(len))
+}
+
+/// Handle ListPop command, blocking up to `timeout_ms` when given.
+pub fn list_pop(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    list: String,
+    end: ListEnd,
+    timeout_ms: Option<u64>,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&list))?;
+    let value = match (end, timeout_ms.map(Duration::from_millis)) {
+        (ListEnd::Left, None) => p.list.lpop(&branch_id, &space, &list),
+        (ListEnd::Right, None) => p.list.rpop(&branch_id, &space, &list),
+        (ListEnd::Left, Some(timeout)) => p.list.blpop(&branch_id, &space, &list, timeout),
+        (ListEnd::Right, Some(timeout)) => p.list.brpop(&branch_id, &space, &list, timeout),
+    };
+    Ok(Output::Maybe(convert_result(value)?))
+}
+
+/// Handle ListRange command.
+pub fn list_range(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    list: String,
+    start: i64,
+    stop: i64,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&list))?;
+    let values = convert_result(p.list.lrange(&branch_id, &space, &list, start, stop))?;
+    Ok(Output::Values(values))
+}
+
+/// Handle ListLen command.
+pub fn list_len(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    list: String,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_key(&list))?;
+    let len = convert_result(p.list.llen(&branch_id, &space, &list))?;
+    Ok(Output::Uint(len))
+}
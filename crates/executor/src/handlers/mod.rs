@@ -19,9 +19,12 @@ pub mod embed_hook;
 pub mod event;
 pub mod json;
 pub mod kv;
+pub mod list;
+#[cfg(feature = "search")]
 pub mod search;
 pub mod space;
 pub mod state;
+#[cfg(feature = "vectors")]
 pub mod vector;
 
 // Transaction commands are deferred because the Executor is stateless by design.
@@ -1,16 +1,19 @@
 //! Search command handler.
 //!
-//! Handles cross-primitive search via the intelligence layer's HybridSearch.
+//! Handles cross-primitive search via the intelligence layer's HybridSearch,
+//! full-text search via the engine's BM25 text index, hybrid text + vector
+//! search fused by the intelligence layer, and semantic search over the
+//! auto-embed shadow collections.
 
 use std::sync::Arc;
 
 use strata_engine::search::PrimitiveType;
-use strata_engine::{SearchBudget, SearchRequest};
-use strata_intelligence::HybridSearch;
+use strata_engine::{SearchBudget, SearchRequest, TextSearch};
+use strata_intelligence::{Fuser, HybridSearch, RRFFuser, WeightedFuser};
 
 use crate::bridge::{to_core_branch_id, Primitives};
-use crate::types::{BranchId, SearchResultHit};
-use crate::{Output, Result};
+use crate::types::{BranchId, FusionMethod, SearchResultHit, TextSearchHit};
+use crate::{Error, Output, Result};
 
 /// Handle Search command: cross-primitive search
 pub fn search(
@@ -72,6 +75,326 @@ pub fn search(
     Ok(Output::SearchResults(results))
 }
 
+/// Default number of full-text and hybrid results
+const DEFAULT_TEXT_SEARCH_K: u64 = 10;
+
+/// Handle TextSearch command: BM25 full-text search within a space
+pub fn text_search(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    query: String,
+    k: Option<u64>,
+) -> Result<Output> {
+    let core_branch_id = to_core_branch_id(&branch)?;
+    let k = k.unwrap_or(DEFAULT_TEXT_SEARCH_K) as usize;
+
+    let hits = TextSearch::new(p.db.clone())
+        .text(&core_branch_id, &space, &query, k)
+        .map_err(crate::Error::from)?;
+
+    let results = hits
+        .into_iter()
+        .map(|hit| {
+            let (entity, primitive) = format_entity_ref(&hit.doc_ref);
+            TextSearchHit {
+                entity,
+                primitive,
+                score: hit.score,
+                rank: hit.rank,
+                snippet: hit.snippet,
+                highlights: hit
+                    .highlights
+                    .into_iter()
+                    .map(|(start, end)| (start as u64, end as u64))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(Output::TextSearchResults(results))
+}
+
+/// Handle SemanticSearch command: embed the query and search the
+/// auto-embed shadow collections of one space
+///
+/// Each record is returned once, for its best-matching chunk, with that
+/// chunk's passage as the snippet. With `rerank_top_n`, that many records
+/// are rescored by the reranker and the best `k` returned with its scores.
+#[cfg(feature = "embed")]
+pub fn semantic_search(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    query: String,
+    k: Option<u64>,
+    rerank_top_n: Option<u64>,
+) -> Result<Output> {
+    use strata_engine::vector::MetadataFilter;
+    use strata_intelligence::embed::EmbedModelState;
+
+    use super::embed_hook::{SHADOW_EVENT, SHADOW_JSON, SHADOW_KV, SHADOW_STATE};
+    use crate::embed_queue::EmbedQueue;
+
+    let core_branch_id = to_core_branch_id(&branch)?;
+    let k = k.unwrap_or(DEFAULT_TEXT_SEARCH_K) as usize;
+    // Records to retrieve: the reranking candidates, or the results themselves
+    let wanted = rerank_top_n.map_or(k, |n| (n as usize).max(k));
+
+    // Let queued embeddings land so acknowledged writes are searchable.
+    p.db.extension::<EmbedQueue>()
+        .map_err(Error::from)?
+        .wait_idle();
+
+    let embedding =
+        p.db.extension::<EmbedModelState>()
+            .map_err(Error::from)?
+            .embedder(&p.db)
+            .and_then(|embedder| embedder.embed(&query))
+            .map_err(|e| Error::Internal {
+                reason: format!("Failed to embed query: {}", e),
+            })?;
+
+    let shadows = [
+        (SHADOW_KV, "kv"),
+        (SHADOW_JSON, "json"),
+        (SHADOW_STATE, "state"),
+        (SHADOW_EVENT, "event"),
+    ];
+    // A record has one vector per chunk; fetch extra matches so that k
+    // distinct records remain after keeping each record's best chunk.
+    let candidates = wanted.saturating_mul(SEMANTIC_OVERSAMPLE);
+    let mut matches = Vec::new();
+    for (collection, primitive) in shadows {
+        let filter = MetadataFilter::new().eq("source_space", space.as_str());
+        // Collections are created on first embed; a missing one has no hits.
+        let Ok(found) = p.vector.system_search(
+            core_branch_id,
+            collection,
+            &embedding,
+            candidates,
+            Some(filter),
+        ) else {
+            continue;
+        };
+        for m in found {
+            let Some(meta) = m.metadata else { continue };
+            let Some(key) = meta.get("source_key").and_then(|key| key.as_str()) else {
+                continue;
+            };
+            matches.push((primitive, key.to_string(), m.score, meta));
+        }
+    }
+
+    matches.sort_by(|a, b| b.2.total_cmp(&a.2));
+    let mut seen = std::collections::HashSet::new();
+    let mut results = Vec::new();
+    let mut passages = Vec::new();
+    for (primitive, key, score, meta) in matches {
+        if results.len() == wanted {
+            break;
+        }
+        if !seen.insert((primitive, key.clone())) {
+            continue;
+        }
+        let text = record_text(p, core_branch_id, primitive, &space, &key);
+        let snippet = text
+            .as_deref()
+            .and_then(|text| chunk_passage(text, &meta))
+            .map(str::to_string);
+        if rerank_top_n.is_some() {
+            // Rerank on the matching chunk, or the whole text for records
+            // embedded before chunking
+            passages.push(snippet.clone().or(text).unwrap_or_default());
+        }
+        let entity = if primitive == "event" {
+            format!("seq:{}", key)
+        } else {
+            key
+        };
+        results.push(SearchResultHit {
+            entity,
+            primitive: primitive.to_string(),
+            score,
+            rank: (results.len() + 1) as u32,
+            snippet,
+        });
+    }
+
+    if rerank_top_n.is_some() {
+        results = rerank(p, &query, results, &passages, k)?;
+    }
+    Ok(Output::SearchResults(results))
+}
+
+/// Shadow matches fetched per requested semantic search hit.
+#[cfg(feature = "embed")]
+const SEMANTIC_OVERSAMPLE: usize = 4;
+
+/// Rescore `hits` against `query` with the database's reranker and keep
+/// the best `k`, ranked by the reranker's score.
+///
+/// `passages[i]` is the text scored for `hits[i]`. Ties keep the embedding
+/// order.
+#[cfg(feature = "embed")]
+fn rerank(
+    p: &Arc<Primitives>,
+    query: &str,
+    hits: Vec<SearchResultHit>,
+    passages: &[String],
+    k: usize,
+) -> Result<Vec<SearchResultHit>> {
+    use strata_intelligence::embed::EmbedModelState;
+
+    if hits.is_empty() {
+        return Ok(hits);
+    }
+    let passages: Vec<&str> = passages.iter().map(String::as_str).collect();
+    let scores =
+        p.db.extension::<EmbedModelState>()
+            .map_err(Error::from)?
+            .reranker(&p.db)
+            .and_then(|reranker| reranker.score(query, &passages))
+            .map_err(|e| Error::Internal {
+                reason: format!("Failed to rerank results: {}", e),
+            })?;
+
+    let mut scored: Vec<_> = hits.into_iter().zip(scores).collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(scored
+        .into_iter()
+        .take(k)
+        .enumerate()
+        .map(|(i, (hit, score))| SearchResultHit {
+            score,
+            rank: (i + 1) as u32,
+            ..hit
+        })
+        .collect())
+}
+
+/// The passage of `text` a shadow chunk was embedded from.
+///
+/// `None` for entries without chunk offsets (embedded before chunking) or
+/// whose record no longer holds the text.
+#[cfg(feature = "embed")]
+fn chunk_passage<'a>(text: &'a str, meta: &serde_json::Value) -> Option<&'a str> {
+    let start = meta.get("chunk_start")?.as_u64()? as usize;
+    let end = meta.get("chunk_end")?.as_u64()? as usize;
+    text.get(start..end)
+}
+
+/// The embeddable text of a record, read back from its primitive.
+#[cfg(feature = "embed")]
+fn record_text(
+    p: &Arc<Primitives>,
+    branch_id: strata_core::types::BranchId,
+    primitive: &str,
+    space: &str,
+    key: &str,
+) -> Option<String> {
+    use strata_core::primitives::json::JsonPath;
+
+    let value = match primitive {
+        "kv" => p.kv.get(&branch_id, space, key).ok()??,
+        "state" => p.state.get(&branch_id, space, key).ok()??,
+        "event" => {
+            let sequence = key.parse().ok()?;
+            p.event
+                .get(&branch_id, space, sequence)
+                .ok()??
+                .value
+                .payload
+        }
+        "json" => {
+            let doc = p
+                .json
+                .get(&branch_id, space, key, &JsonPath::root())
+                .ok()??;
+            crate::bridge::json_to_value(doc).ok()?
+        }
+        _ => return None,
+    };
+    super::embed_hook::extract_text(&value)
+}
+
+/// SemanticSearch needs the embedding model, which is only built with the
+/// `embed` feature.
+#[cfg(not(feature = "embed"))]
+pub fn semantic_search(
+    _p: &Arc<Primitives>,
+    _branch: BranchId,
+    _space: String,
+    _query: String,
+    _k: Option<u64>,
+    _rerank_top_n: Option<u64>,
+) -> Result<Output> {
+    Err(Error::NotImplemented {
+        feature: "SemanticSearch".into(),
+        reason: "requires the 'embed' feature to be compiled in".into(),
+    })
+}
+
+/// Handle HybridSearch command: full-text and vector search fused into one ranking
+#[allow(clippy::too_many_arguments)]
+pub fn hybrid_search(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    collection: String,
+    query: String,
+    embedding: Vec<f32>,
+    k: Option<u64>,
+    fusion: Option<FusionMethod>,
+) -> Result<Output> {
+    let core_branch_id = to_core_branch_id(&branch)?;
+    let k = k.unwrap_or(DEFAULT_TEXT_SEARCH_K) as usize;
+
+    let fuser: Box<dyn Fuser> = match fusion.unwrap_or_default() {
+        FusionMethod::Rrf => Box::new(RRFFuser::default()),
+        FusionMethod::Weighted { vector_weight } => {
+            if !(0.0..=1.0).contains(&vector_weight) {
+                return Err(Error::InvalidInput {
+                    reason: format!(
+                        "vector_weight must be between 0 and 1, got {}",
+                        vector_weight
+                    ),
+                });
+            }
+            Box::new(WeightedFuser::new(vector_weight))
+        }
+    };
+
+    let fused = HybridSearch::new(p.db.clone())
+        .text_and_vector(
+            &core_branch_id,
+            &space,
+            &query,
+            &collection,
+            &embedding,
+            k,
+            fuser.as_ref(),
+        )
+        .map_err(Error::from)?;
+
+    let results = fused
+        .hits
+        .into_iter()
+        .map(|hit| {
+            let (entity, primitive) = format_entity_ref(&hit.doc_ref);
+            SearchResultHit {
+                entity,
+                primitive,
+                score: hit.score,
+                rank: hit.rank,
+                snippet: hit.snippet,
+            }
+        })
+        .collect();
+
+    Ok(Output::SearchResults(results))
+}
+
 /// Format an EntityRef into (entity_string, primitive_string) for display
 fn format_entity_ref(doc_ref: &strata_engine::search::EntityRef) -> (String, String) {
     match doc_ref {
@@ -63,9 +63,11 @@ pub fn space_delete(
             TypeTag::Event,
             TypeTag::State,
             TypeTag::Trace,
+            TypeTag::List,
             TypeTag::Json,
             TypeTag::Vector,
             TypeTag::VectorConfig,
+            TypeTag::Custom,
         ] {
             let prefix = Key::new(ns.clone(), type_tag, vec![]);
             let entries = txn.scan_prefix(&prefix)?;
@@ -39,10 +39,12 @@ pub fn state_getv(
     branch: BranchId,
     space: String,
     cell: String,
+    limit: Option<u64>,
 ) -> Result<Output> {
     let branch_id = bridge::to_core_branch_id(&branch)?;
     convert_result(bridge::validate_key(&cell))?;
-    let result = convert_result(p.state.getv(&branch_id, &space, &cell))?;
+    let limit = limit.map(|n| n as usize);
+    let result = convert_result(p.state.history(&branch_id, &space, &cell, limit))?;
     let mapped = result.map(|history| {
         history
             .into_versions()
@@ -91,6 +93,23 @@ pub fn state_set(
     Ok(Output::Version(bridge::extract_version(&version)))
 }
 
+/// Handle StateIncr command — atomically add to a numeric cell.
+pub fn state_incr(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    cell: String,
+    delta: Value,
+) -> Result<Output> {
+    require_branch_exists(p, &branch)?;
+    let branch_id = bridge::to_core_branch_id(&branch)?;
+    convert_result(bridge::validate_key(&cell))?;
+    let result = convert_result(p.state.incr(&branch_id, &space, &cell, delta))?;
+    Ok(Output::MaybeVersioned(Some(bridge::to_versioned_value(
+        result,
+    ))))
+}
+
 /// Handle StateGet command.
 ///
 /// Returns `MaybeVersioned` with value, version, and timestamp metadata.
@@ -108,6 +127,23 @@ pub fn state_get(
     ))
 }
 
+/// Handle StateGetVersion command — read a cell at a version counter.
+pub fn state_get_version(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    cell: String,
+    version: u64,
+) -> Result<Output> {
+    let branch_id = bridge::to_core_branch_id(&branch)?;
+    convert_result(bridge::validate_key(&cell))?;
+    let version = Version::counter(version);
+    let result = convert_result(p.state.get_version(&branch_id, &space, &cell, version))?;
+    Ok(Output::MaybeVersioned(
+        result.map(bridge::to_versioned_value),
+    ))
+}
+
 /// Handle StateGet with as_of timestamp (time-travel read).
 pub fn state_get_at(
     p: &Arc<Primitives>,
@@ -7,14 +7,15 @@ use std::sync::Arc;
 use strata_core::Value;
 
 use crate::bridge::{
-    extract_version, from_engine_metric, is_internal_collection, serde_json_to_value_public,
-    to_core_branch_id, to_engine_filter, to_engine_metric, validate_key,
-    validate_not_internal_collection, validate_vector, value_to_serde_json_public, Primitives,
+    extract_version, from_engine_dtype, from_engine_metric, is_internal_collection,
+    serde_json_to_value_public, to_core_branch_id, to_engine_dtype, to_engine_filter,
+    to_engine_index, to_engine_metric, validate_key, validate_not_internal_collection,
+    validate_vector, value_to_serde_json_public, Primitives,
 };
 use crate::convert::convert_result;
 use crate::types::{
-    BranchId, CollectionInfo, DistanceMetric, MetadataFilter, VectorData, VectorMatch,
-    VersionedVectorData,
+    BranchId, CollectionInfo, DistanceMetric, MetadataFilter, StorageDtype, VectorData,
+    VectorIndexType, VectorMatch, VersionedVectorData,
 };
 use crate::{Output, Result};
 
@@ -71,6 +72,7 @@ fn to_vector_match(m: strata_engine::VectorMatch) -> Result<VectorMatch> {
 // =============================================================================
 
 /// Handle VectorUpsert command.
+#[allow(clippy::too_many_arguments)]
 pub fn vector_upsert(
     p: &Arc<Primitives>,
     branch: BranchId,
@@ -79,6 +81,7 @@ pub fn vector_upsert(
     key: String,
     vector: Vec<f32>,
     metadata: Option<Value>,
+    namespace: Option<String>,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_key(&key))?;
@@ -90,10 +93,11 @@ pub fn vector_upsert(
         .transpose()
         .map_err(crate::Error::from)?;
     let version = convert_vector_result(
-        p.vector.insert(
+        p.vector.insert_in_namespace(
             branch_id,
             &space,
             &collection,
+            namespace.as_deref(),
             &key,
             &vector,
             json_metadata,
@@ -194,28 +198,111 @@ pub fn vector_search(
     query: Vec<f32>,
     k: u64,
     filter: Option<Vec<MetadataFilter>>,
-    _metric: Option<DistanceMetric>,
+    metric: Option<DistanceMetric>,
+    namespace: Option<String>,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
+    check_search_metric(p, branch_id, &space, &collection, metric)?;
 
-    let engine_filter = filter.as_ref().and_then(|f| to_engine_filter(f));
-    let matches = convert_vector_result(
-        p.vector.search(
+    let engine_filter = match &filter {
+        Some(f) => to_engine_filter(f)?,
+        None => None,
+    };
+    let matches = match namespace {
+        Some(namespace) => p.vector.search_in_namespace(
             branch_id,
             &space,
             &collection,
+            &namespace,
             &query,
             k as usize,
             engine_filter,
         ),
-        branch_id,
-    )?;
+        None => p.vector.search(
+            branch_id,
+            &space,
+            &collection,
+            &query,
+            k as usize,
+            engine_filter,
+        ),
+    };
+    let matches = convert_vector_result(matches, branch_id)?;
 
     let results: Result<Vec<VectorMatch>> = matches.into_iter().map(to_vector_match).collect();
     Ok(Output::VectorMatches(results?))
 }
 
+/// Reject a search `metric` that differs from the collection's.
+///
+/// Scores always come from the metric the index was built with, so a
+/// mismatched override would silently be ignored. Missing collections are
+/// left for the search itself to report.
+fn check_search_metric(
+    p: &Arc<Primitives>,
+    branch_id: strata_core::BranchId,
+    space: &str,
+    collection: &str,
+    metric: Option<DistanceMetric>,
+) -> Result<()> {
+    let Some(metric) = metric else {
+        return Ok(());
+    };
+    let collections =
+        convert_vector_result(p.vector.list_collections(branch_id, space), branch_id)?;
+    let Some(info) = collections.into_iter().find(|c| c.name == collection) else {
+        return Ok(());
+    };
+    if info.config.metric != to_engine_metric(metric) {
+        return Err(crate::Error::InvalidInput {
+            reason: format!(
+                "Collection '{}' uses the {} metric; reindex it to search with {}",
+                collection,
+                info.config.metric.name(),
+                to_engine_metric(metric).name()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Handle VectorSearchBatch command.
+pub fn vector_search_batch(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    collection: String,
+    queries: Vec<Vec<f32>>,
+    k: u64,
+    filter: Option<Vec<MetadataFilter>>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_not_internal_collection(&collection))?;
+
+    let engine_filter = match &filter {
+        Some(f) => to_engine_filter(f)?,
+        None => None,
+    };
+    let batches = convert_vector_result(
+        p.vector.search_batch(
+            branch_id,
+            &space,
+            &collection,
+            &queries,
+            k as usize,
+            engine_filter,
+        ),
+        branch_id,
+    )?;
+
+    let results: Result<Vec<Vec<VectorMatch>>> = batches
+        .into_iter()
+        .map(|matches| matches.into_iter().map(to_vector_match).collect())
+        .collect();
+    Ok(Output::VectorBatchMatches(results?))
+}
+
 /// Handle VectorCreateCollection command.
 pub fn vector_create_collection(
     p: &Arc<Primitives>,
@@ -224,6 +311,7 @@ pub fn vector_create_collection(
     collection: String,
     dimension: u64,
     metric: DistanceMetric,
+    storage_dtype: Option<StorageDtype>,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
@@ -231,7 +319,8 @@ pub fn vector_create_collection(
     let config = convert_result(strata_core::primitives::VectorConfig::new(
         dimension as usize,
         to_engine_metric(metric),
-    ))?;
+    ))?
+    .with_storage_dtype(to_engine_dtype(storage_dtype.unwrap_or_default()));
     let versioned = convert_vector_result(
         p.vector
             .create_collection(branch_id, &space, &collection, config),
@@ -240,6 +329,47 @@ pub fn vector_create_collection(
     Ok(Output::Version(extract_version(&versioned.version)))
 }
 
+/// Handle VectorReindex command.
+pub fn vector_reindex(
+    p: &Arc<Primitives>,
+    branch: BranchId,
+    space: String,
+    collection: String,
+    metric: Option<DistanceMetric>,
+    index_type: Option<VectorIndexType>,
+) -> Result<Output> {
+    let branch_id = to_core_branch_id(&branch)?;
+    convert_result(validate_not_internal_collection(&collection))?;
+
+    let versioned = convert_vector_result(
+        p.vector.reindex(
+            branch_id,
+            &space,
+            &collection,
+            metric.map(to_engine_metric),
+            index_type.map(to_engine_index),
+        ),
+        branch_id,
+    )?;
+    let info = versioned.value;
+
+    let (index_type, memory_bytes) = p
+        .vector
+        .collection_backend_stats(branch_id, &space, &info.name)
+        .map(|(it, mem)| (Some(it.to_string()), Some(mem as u64)))
+        .unwrap_or((None, None));
+
+    Ok(Output::VectorCollectionList(vec![CollectionInfo {
+        name: info.name,
+        dimension: info.config.dimension,
+        metric: from_engine_metric(info.config.metric),
+        count: info.count as u64,
+        index_type,
+        memory_bytes,
+        storage_dtype: from_engine_dtype(info.config.storage_dtype),
+    }]))
+}
+
 /// Handle VectorDeleteCollection command.
 pub fn vector_delete_collection(
     p: &Arc<Primitives>,
@@ -285,6 +415,7 @@ pub fn vector_list_collections(
                 count: info.count as u64,
                 index_type,
                 memory_bytes,
+                storage_dtype: from_engine_dtype(info.config.storage_dtype),
             }
         })
         .collect();
@@ -323,6 +454,7 @@ pub fn vector_collection_stats(
         count: info.count as u64,
         index_type,
         memory_bytes,
+        storage_dtype: from_engine_dtype(info.config.storage_dtype),
     };
     Ok(Output::VectorCollectionList(vec![stats]))
 }
@@ -334,6 +466,7 @@ pub fn vector_batch_upsert(
     space: String,
     collection: String,
     entries: Vec<crate::types::BatchVectorEntry>,
+    namespace: Option<String>,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
@@ -351,10 +484,11 @@ pub fn vector_batch_upsert(
     }
 
     let versions = convert_vector_result(
-        p.vector.batch_insert(
+        p.vector.batch_insert_in_namespace(
             branch_id,
             &space,
             &collection,
+            namespace.as_deref(),
             engine_entries,
         ),
         branch_id,
@@ -374,13 +508,17 @@ pub fn vector_search_at(
     query: Vec<f32>,
     k: u64,
     filter: Option<Vec<MetadataFilter>>,
-    _metric: Option<DistanceMetric>,
+    metric: Option<DistanceMetric>,
     as_of_ts: u64,
 ) -> Result<Output> {
     let branch_id = to_core_branch_id(&branch)?;
     convert_result(validate_not_internal_collection(&collection))?;
+    check_search_metric(p, branch_id, &space, &collection, metric)?;
 
-    let engine_filter = filter.as_ref().and_then(|f| to_engine_filter(f));
+    let engine_filter = match &filter {
+        Some(f) => to_engine_filter(f)?,
+        None => None,
+    };
     let matches = convert_vector_result(
         p.vector.search_at(
             branch_id,
@@ -398,22 +536,6 @@ pub fn vector_search_at(
     Ok(Output::VectorMatches(results?))
 }
 
-/// Handle TimeRange command — get the available time range for a branch.
-pub fn time_range(p: &Arc<Primitives>, branch: BranchId) -> Result<Output> {
-    let branch_id = to_core_branch_id(&branch)?;
-    let range = convert_result(p.db.time_range(branch_id))?;
-    match range {
-        Some((oldest, latest)) => Ok(Output::TimeRange {
-            oldest_ts: Some(oldest),
-            latest_ts: Some(latest),
-        }),
-        None => Ok(Output::TimeRange {
-            oldest_ts: None,
-            latest_ts: None,
-        }),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,7 +13,6 @@
 //!
 //! This ensures round-trip serialization preserves exact values.
 
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use serde::de;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value as JsonValue;
@@ -46,115 +45,18 @@ where
 }
 
 /// Convert a Value to a JSON value with special encoding.
+///
+/// This is `strata_core`'s `From<&Value> for serde_json::Value`.
 pub fn value_to_json(value: &Value) -> JsonValue {
-    match value {
-        Value::Null => JsonValue::Null,
-        Value::Bool(b) => JsonValue::Bool(*b),
-        Value::Int(i) => JsonValue::Number((*i).into()),
-        Value::Float(f) => float_to_json(*f),
-        Value::String(s) => JsonValue::String(s.clone()),
-        Value::Bytes(b) => {
-            let encoded = BASE64.encode(b);
-            serde_json::json!({"$bytes": encoded})
-        }
-        Value::Array(arr) => {
-            let items: Vec<JsonValue> = arr.iter().map(value_to_json).collect();
-            JsonValue::Array(items)
-        }
-        Value::Object(map) => {
-            let obj: serde_json::Map<String, JsonValue> = map
-                .iter()
-                .map(|(k, v)| (k.clone(), value_to_json(v)))
-                .collect();
-            JsonValue::Object(obj)
-        }
-    }
+    JsonValue::from(value)
 }
 
 /// Convert a JSON value to a Value, recognizing special encodings.
+///
+/// This is `strata_core`'s `TryFrom<&serde_json::Value> for Value`: a
+/// `$bytes` or `$f64` wrapper whose payload doesn't decode is an error.
 pub fn json_to_value(json: &JsonValue) -> Result<Value, String> {
-    match json {
-        JsonValue::Null => Ok(Value::Null),
-        JsonValue::Bool(b) => Ok(Value::Bool(*b)),
-        JsonValue::Number(n) => {
-            if let Some(i) = n.as_i64() {
-                Ok(Value::Int(i))
-            } else if let Some(f) = n.as_f64() {
-                Ok(Value::Float(f))
-            } else {
-                Err("Invalid number".to_string())
-            }
-        }
-        JsonValue::String(s) => Ok(Value::String(s.clone())),
-        JsonValue::Array(arr) => {
-            let items: Result<Vec<Value>, String> = arr.iter().map(json_to_value).collect();
-            Ok(Value::Array(items?))
-        }
-        JsonValue::Object(obj) => {
-            // Check for special encodings
-            if let Some(bytes_value) = obj.get("$bytes") {
-                if obj.len() == 1 {
-                    if let JsonValue::String(encoded) = bytes_value {
-                        let decoded = BASE64
-                            .decode(encoded)
-                            .map_err(|e| format!("Invalid base64: {}", e))?;
-                        return Ok(Value::Bytes(decoded));
-                    }
-                }
-            }
-            if let Some(float_value) = obj.get("$f64") {
-                if obj.len() == 1 {
-                    if let JsonValue::String(s) = float_value {
-                        let f = json_special_float_from_str(s)?;
-                        return Ok(Value::Float(f));
-                    }
-                }
-            }
-
-            // Regular object
-            let map: Result<std::collections::HashMap<String, Value>, String> = obj
-                .iter()
-                .map(|(k, v)| json_to_value(v).map(|val| (k.clone(), val)))
-                .collect();
-            Ok(Value::Object(map?))
-        }
-    }
-}
-
-/// Convert a float to JSON, handling special values.
-fn float_to_json(f: f64) -> JsonValue {
-    if f.is_nan() {
-        serde_json::json!({"$f64": "NaN"})
-    } else if f.is_infinite() {
-        if f.is_sign_positive() {
-            serde_json::json!({"$f64": "+Inf"})
-        } else {
-            serde_json::json!({"$f64": "-Inf"})
-        }
-    } else if f == 0.0 && f.is_sign_negative() {
-        serde_json::json!({"$f64": "-0.0"})
-    } else {
-        // Regular float - use standard JSON number
-        serde_json::Number::from_f64(f)
-            .map(JsonValue::Number)
-            .unwrap_or_else(|| {
-                // Fallback for edge cases (shouldn't happen with normal floats)
-                serde_json::json!({"$f64": format!("{}", f)})
-            })
-    }
-}
-
-/// Parse a special float string.
-fn json_special_float_from_str(s: &str) -> Result<f64, String> {
-    match s {
-        "NaN" => Ok(f64::NAN),
-        "+Inf" => Ok(f64::INFINITY),
-        "-Inf" => Ok(f64::NEG_INFINITY),
-        "-0.0" => Ok(-0.0_f64),
-        other => other
-            .parse::<f64>()
-            .map_err(|e| format!("Invalid float: {}", e)),
-    }
+    Value::try_from(json).map_err(|e| e.to_string())
 }
 
 /// A wrapper for Value that uses canonical JSON serialization.
@@ -308,4 +210,19 @@ mod tests {
         let restored: CanonicalValue = serde_json::from_str(&json).unwrap();
         assert_eq!(value, restored);
     }
+
+    #[test]
+    fn test_undecodable_wrappers_match_core() {
+        for json in [
+            serde_json::json!({"$bytes": "!!"}),
+            serde_json::json!({"$f64": "one"}),
+        ] {
+            assert!(json_to_value(&json).is_err());
+            assert!(Value::try_from(&json).is_err());
+            assert!(serde_json::from_value::<CanonicalValue>(json).is_err());
+        }
+        // Not wrappers: ordinary objects either way
+        let json = serde_json::json!({"$bytes": "AAEC", "len": 3});
+        assert_eq!(json_to_value(&json).ok(), Value::try_from(&json).ok());
+    }
 }
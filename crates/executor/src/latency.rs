@@ -0,0 +1,84 @@
+//! Per-command latency tracking.
+//!
+//! Every command run through an [`Executor`](crate::Executor) is timed and
+//! recorded in an HDR histogram keyed by command name. The tracker lives in a
+//! database extension, so all executors and sessions on the same database
+//! feed one set of histograms.
+//!
+//! `Command::LatencyReport` returns p50/p95/p99/max per command and
+//! `Command::LatencyReset` clears them. Neither command is recorded itself.
+//!
+//! Histograms keep two significant digits over 1ns..1h, which costs a few
+//! kilobytes per command variant that has run.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use hdrhistogram::Histogram;
+
+use crate::types::{CommandLatency, LatencyReport};
+
+/// Largest latency tracked exactly; slower runs are clamped to it.
+const MAX_TRACKED_NS: u64 = 3_600_000_000_000;
+
+/// Significant decimal digits kept by each histogram.
+const SIGNIFICANT_DIGITS: u8 = 2;
+
+/// Latency histograms for one database, keyed by command name.
+#[derive(Default)]
+pub(crate) struct LatencyTracker {
+    histograms: RwLock<HashMap<&'static str, Mutex<Histogram<u64>>>>,
+}
+
+impl LatencyTracker {
+    /// Record one run of `command`.
+    pub(crate) fn record(&self, command: &'static str, elapsed: Duration) {
+        let ns = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        {
+            let histograms = self.histograms.read().unwrap();
+            if let Some(h) = histograms.get(command) {
+                h.lock().unwrap().saturating_record(ns);
+                return;
+            }
+        }
+        let mut histograms = self.histograms.write().unwrap();
+        histograms
+            .entry(command)
+            .or_insert_with(|| Mutex::new(new_histogram()))
+            .get_mut()
+            .unwrap()
+            .saturating_record(ns);
+    }
+
+    /// Percentiles for every command recorded since the last reset.
+    pub(crate) fn report(&self) -> LatencyReport {
+        let histograms = self.histograms.read().unwrap();
+        let mut commands: Vec<CommandLatency> = histograms
+            .iter()
+            .map(|(name, h)| {
+                let h = h.lock().unwrap();
+                CommandLatency {
+                    command: name.to_string(),
+                    count: h.len(),
+                    p50_ns: h.value_at_quantile(0.50),
+                    p95_ns: h.value_at_quantile(0.95),
+                    p99_ns: h.value_at_quantile(0.99),
+                    max_ns: h.max(),
+                }
+            })
+            .collect();
+        commands.sort_by(|a, b| a.command.cmp(&b.command));
+        LatencyReport { commands }
+    }
+
+    /// Drop all recorded latencies.
+    pub(crate) fn reset(&self) {
+        self.histograms.write().unwrap().clear();
+    }
+}
+
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(1, MAX_TRACKED_NS, SIGNIFICANT_DIGITS)
+        .expect("static histogram bounds are valid")
+}
@@ -52,10 +52,15 @@ mod api;
 pub(crate) mod bridge;
 mod command;
 mod convert;
+mod cursor;
+mod embed_queue;
 mod error;
 mod executor;
 pub(crate) mod json;
+mod latency;
+mod manager;
 mod output;
+mod prepared;
 mod session;
 mod types;
 
@@ -71,25 +76,95 @@ mod tests;
 // =============================================================================
 
 // Core types
+#[cfg(feature = "search")]
+pub use api::Search;
 pub use api::{
-    BranchDiffEntry, BranchDiffResult, Branches, ConflictEntry, DiffSummary, ForkInfo, MergeInfo,
-    MergeStrategy, SpaceDiff, Strata,
+    BranchAncestor, BranchDiffEntry, BranchDiffResult, Branches, ConflictEntry, Custom,
+    DiffSummary, Documents, EntryChange, Events, ForkInfo, IndexVerification, Maintenance,
+    MergeInfo, MergeStrategy, SchemaChange, SchemaChangeKind, Sessions, SpaceDiff, States, Strata,
+    SESSION_STREAM,
 };
 pub use command::Command;
+pub use cursor::{DEFAULT_CURSOR_PAGE_SIZE, DEFAULT_CURSOR_TTL_SECS};
 pub use error::Error;
 pub use executor::Executor;
+pub use manager::{ResourceLimits, StrataManager, DEFAULT_MAX_OPEN_DATABASES};
 pub use output::Output;
+pub use prepared::{ParamRef, Params, PreparedCommand};
 pub use session::Session;
 pub use types::*;
 
 // Re-export Value from strata_core so users don't need to import it
 pub use strata_core::Value;
 
+// Conversions between Rust types and Value; the derive macros share the
+// trait names, so `use stratadb::ToValue` brings in both
+#[doc(hidden)]
+pub use strata_core::BytesField;
+pub use strata_core::{FromValue, FromValueError, ToValue};
+pub use strata_derive::{FromValue, ToValue};
+
+// Wire encoding of values (`$bytes` / `$f64` wrappers), for tools that
+// read or write records outside the command API
+pub use json::{json_to_value, value_to_json};
+
 // Re-export security types so users don't need strata-security directly
 pub use strata_security::{AccessMode, OpenOptions};
 
 // Re-export WAL counters (return type of Strata::durability_counters)
 pub use strata_engine::WalCounters;
 
+// Re-export the crash loss bound (return type of Strata::max_loss_window)
+pub use strata_engine::LossWindow;
+
+// Re-export the hot backup manifest (return type of Strata::begin_backup)
+pub use strata_engine::{BackupFile, BackupManifest};
+
+// Re-export the embedding provider (set via OpenOptions::embed_provider)
+pub use strata_engine::EmbedProvider;
+
+// Re-export the reranking provider (set via OpenOptions::rerank_provider)
+pub use strata_engine::RerankProvider;
+
+// Re-export the environment recorded in branches and bundles
+pub use strata_engine::EnvironmentInfo;
+
+// Re-export the background worker pool (set via OpenOptions::worker_pool)
+pub use strata_engine::{PoolJob, WorkerPool, WorkerPoolBuilder};
+
+// Re-export recovery progress and cancellation (set via OpenOptions)
+pub use strata_engine::{CancellationToken, RecoveryProgress};
+
+// Re-export the automatic checkpoint policy (set via OpenOptions::checkpoint_every)
+pub use strata_engine::CheckpointPolicy;
+
+// Re-export the cache database memory budget (set via OpenOptions::max_memory)
+pub use strata_core::types::TypeTag;
+pub use strata_engine::{EvictionPolicy, MemoryBudget, MemoryBudgetStats};
+
+// Re-export the primitive kinds (keys of BranchRetention overrides)
+pub use strata_core::PrimitiveType;
+
+// Re-export the transaction lock modes (set via TxnOptions::lock_mode)
+pub use strata_engine::LockMode;
+
+// Re-export the per-thread transaction pool limits (pool activity is in Stats)
+pub use strata_engine::{TransactionPool, TransactionPoolConfig};
+
+// Re-export the custom primitive extension API (entries via Strata::custom)
+pub use strata_engine::{
+    register_custom_primitive, CustomPrimitive, CustomStore, CUSTOM_PRIMITIVE_IDS,
+};
+
+/// Engine types in the [`CustomPrimitive`] hook signatures.
+///
+/// The hooks run inside the engine, so they receive its database handle,
+/// binary branch IDs and error type rather than the executor's.
+pub mod custom {
+    pub use strata_core::types::BranchId;
+    pub use strata_core::{StrataError, StrataResult};
+    pub use strata_engine::Database;
+}
+
 /// Result type for executor operations
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,673 @@
+//! Multi-database manager.
+//!
+//! A [`StrataManager`] owns a root directory and treats each subdirectory as
+//! a separate database, for hosts that embed one Strata instance per tenant.
+//!
+//! ```text
+//! <root>/
+//! ├── acme/        # manager.get("acme")
+//! ├── globex/      # manager.get("globex")
+//! └── initech/
+//! ```
+//!
+//! ## Handle Cache
+//!
+//! Databases are opened lazily on first [`get`](StrataManager::get) and kept
+//! in an LRU cache of at most `capacity` entries. Opening one more evicts the
+//! least recently used database. Evicting or closing only drops the manager's
+//! reference: handles already returned keep working, and the database shuts
+//! down once the last of them is dropped.
+//!
+//! ## Resource Limits
+//!
+//! [`ResourceLimits`] caps memory, background threads, and open files summed
+//! over the cached databases. When opening a database pushes the total over
+//! a cap, least recently used databases are evicted until it fits again.
+//!
+//! ## Compaction
+//!
+//! Databases with `compaction_interval_secs` set in `strata.toml` normally
+//! run their own compaction thread. While cached by a manager they share a
+//! single scheduler thread instead, which compacts one database at a time,
+//! most overdue first, so a large tenant cannot starve the others.
+//!
+//! With a [`WorkerPool`](strata_engine::WorkerPool) in the manager's
+//! [`OpenOptions`], the scheduler and every database's WAL flush run on the
+//! pool's threads, so the host fixes the background thread count up front.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use strata_engine::{Database, PoolJob};
+use strata_security::{AccessMode, OpenOptions};
+
+use crate::executor::database_stats;
+use crate::types::{DatabaseStats, ManagerStats, ResourceUsage};
+use crate::{Error, Result, Strata};
+
+/// Default number of databases a manager holds open at once.
+pub const DEFAULT_MAX_OPEN_DATABASES: usize = 64;
+
+/// How often the compaction scheduler checks for due databases.
+const COMPACTION_TICK: Duration = Duration::from_millis(100);
+
+/// Caps on resources summed over all databases a manager holds open.
+///
+/// Unset caps are unlimited. Use the builder pattern to configure:
+///
+/// ```text
+/// use strata_executor::ResourceLimits;
+///
+/// let limits = ResourceLimits::new()
+///     .max_memory_bytes(4 << 30)
+///     .max_background_threads(32);
+/// ```
+///
+/// A single database that exceeds a cap on its own stays open; limits only
+/// decide how many others can be held alongside it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Approximate bytes of data held in memory.
+    pub max_memory_bytes: Option<u64>,
+    /// Background threads, including the manager's compaction scheduler.
+    pub max_background_threads: Option<u64>,
+    /// File descriptors held by open databases.
+    pub max_open_files: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Create limits with no caps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap approximate in-memory data size across databases.
+    ///
+    /// Measuring memory walks each database's storage, so opening a
+    /// database gets slower as the total data size grows.
+    pub fn max_memory_bytes(mut self, bytes: u64) -> Self {
+        self.max_memory_bytes = Some(bytes);
+        self
+    }
+
+    /// Cap background threads across databases.
+    pub fn max_background_threads(mut self, threads: u64) -> Self {
+        self.max_background_threads = Some(threads);
+        self
+    }
+
+    /// Cap open file descriptors across databases.
+    pub fn max_open_files(mut self, files: u64) -> Self {
+        self.max_open_files = Some(files);
+        self
+    }
+
+    fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn exceeded_by(&self, usage: &ResourceUsage) -> bool {
+        let over = |cap: Option<u64>, used: u64| cap.is_some_and(|cap| used > cap);
+        over(self.max_memory_bytes, usage.memory_bytes)
+            || over(self.max_background_threads, usage.background_threads)
+            || over(self.max_open_files, usage.open_files)
+    }
+}
+
+impl ResourceUsage {
+    fn add(&mut self, other: &ResourceUsage) {
+        self.memory_bytes += other.memory_bytes;
+        self.background_threads += other.background_threads;
+        self.open_files += other.open_files;
+    }
+
+    fn sub(&mut self, other: &ResourceUsage) {
+        self.memory_bytes = self.memory_bytes.saturating_sub(other.memory_bytes);
+        self.background_threads = self
+            .background_threads
+            .saturating_sub(other.background_threads);
+        self.open_files = self.open_files.saturating_sub(other.open_files);
+    }
+}
+
+fn usage_of(db: &Database) -> ResourceUsage {
+    ResourceUsage {
+        memory_bytes: db.memory_usage() as u64,
+        background_threads: db.background_threads() as u64,
+        open_files: db.open_files() as u64,
+    }
+}
+
+struct CachedDatabase {
+    db: Arc<Database>,
+    last_used: u64,
+    /// Compaction taken over from the database's own thread.
+    compaction: Option<CompactionSlot>,
+}
+
+struct CompactionSlot {
+    interval: Duration,
+    due: Instant,
+}
+
+/// A database leaving the cache, with the compaction schedule to hand back.
+type Released = (Arc<Database>, Option<Duration>);
+
+#[derive(Default)]
+struct ManagerInner {
+    open: HashMap<String, CachedDatabase>,
+    /// Logical clock for LRU ordering.
+    clock: u64,
+    hits: u64,
+    opened: u64,
+    closed: u64,
+    evicted: u64,
+    compactions: u64,
+    /// Whether the compaction scheduler runs on its own thread.
+    scheduler_thread: bool,
+}
+
+impl ManagerInner {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, name: &str) -> Option<Released> {
+        self.open
+            .remove(name)
+            .map(|cached| (cached.db, cached.compaction.map(|slot| slot.interval)))
+    }
+
+    /// Remove least recently used databases, never `keep`, until at most
+    /// `capacity` remain and the measured `usage` fits within `limits`.
+    fn evict(
+        &mut self,
+        capacity: usize,
+        limits: &ResourceLimits,
+        usage: &HashMap<String, ResourceUsage>,
+        keep: &str,
+    ) -> Vec<Released> {
+        let mut total = ResourceUsage {
+            background_threads: u64::from(self.scheduler_thread),
+            ..ResourceUsage::default()
+        };
+        for name in self.open.keys() {
+            if let Some(u) = usage.get(name) {
+                total.add(u);
+            }
+        }
+
+        let mut evicted = Vec::new();
+        while self.open.len() > capacity || limits.exceeded_by(&total) {
+            let Some(name) = self
+                .open
+                .iter()
+                .filter(|(name, _)| name.as_str() != keep)
+                .min_by_key(|(_, c)| c.last_used)
+                .map(|(name, _)| name.clone())
+            else {
+                break;
+            };
+            if let Some(released) = self.remove(&name) {
+                if let Some(u) = usage.get(&name) {
+                    total.sub(u);
+                }
+                self.evicted += 1;
+                evicted.push(released);
+            }
+        }
+        evicted
+    }
+
+    /// Claim the most overdue database for compaction and push back its
+    /// next due time.
+    fn next_due(&mut self, now: Instant) -> Option<Arc<Database>> {
+        let cached = self
+            .open
+            .values_mut()
+            .filter(|c| c.compaction.as_ref().is_some_and(|slot| slot.due <= now))
+            .min_by_key(|c| c.compaction.as_ref().map(|slot| slot.due))?;
+        if let Some(slot) = cached.compaction.as_mut() {
+            slot.due = now + slot.interval;
+        }
+        Some(cached.db.clone())
+    }
+}
+
+/// Hand a released database back to its own compaction thread if someone
+/// else still holds it.
+fn release(released: Vec<Released>) {
+    for (db, interval) in released {
+        if let Some(interval) = interval {
+            if Arc::strong_count(&db) > 1 {
+                if let Err(e) = db.schedule_compaction(interval) {
+                    tracing::warn!(target: "strata::manager", error = %e, "Failed to restore compaction schedule");
+                }
+            }
+        }
+        // Dropped outside the manager lock: the last reference flushes the WAL
+        drop(db);
+    }
+}
+
+struct Scheduler {
+    shutdown: Arc<AtomicBool>,
+    runner: SchedulerRunner,
+}
+
+enum SchedulerRunner {
+    Thread(JoinHandle<()>),
+    Pooled(PoolJob),
+}
+
+/// Opens and caches databases stored under one root directory.
+///
+/// # Example
+///
+/// ```text
+/// use strata_executor::StrataManager;
+///
+/// let manager = StrataManager::open("/var/data/tenants", 100)?;
+/// let mut acme = manager.get("acme")?;
+/// acme.kv_put("plan", "enterprise")?;
+/// println!("{:?}", manager.stats()?);
+/// ```
+pub struct StrataManager {
+    root: PathBuf,
+    capacity: usize,
+    options: OpenOptions,
+    limits: ResourceLimits,
+    inner: Arc<Mutex<ManagerInner>>,
+    scheduler: Mutex<Option<Scheduler>>,
+}
+
+impl StrataManager {
+    /// Manage databases under `root`, holding at most `capacity` open.
+    ///
+    /// The root directory is created if it doesn't exist. Databases open in
+    /// read-write mode.
+    pub fn open<P: AsRef<Path>>(root: P, capacity: usize) -> Result<Self> {
+        Self::open_with(root, capacity, OpenOptions::default())
+    }
+
+    /// Manage databases under `root` and open each one with `options`.
+    ///
+    /// A read-only manager never creates databases or the root directory;
+    /// [`get`](Self::get) returns `DatabaseNotFound` for unknown names.
+    pub fn open_with<P: AsRef<Path>>(
+        root: P,
+        capacity: usize,
+        options: OpenOptions,
+    ) -> Result<Self> {
+        if capacity == 0 {
+            return Err(Error::InvalidInput {
+                reason: "capacity must be greater than 0".into(),
+            });
+        }
+        let root = root.as_ref().to_path_buf();
+        if options.access_mode == AccessMode::ReadWrite {
+            std::fs::create_dir_all(&root).map_err(|e| Error::Io {
+                reason: format!("Failed to create '{}': {}", root.display(), e),
+            })?;
+        }
+        Ok(Self {
+            root,
+            capacity,
+            options,
+            limits: ResourceLimits::default(),
+            inner: Arc::new(Mutex::new(ManagerInner::default())),
+            scheduler: Mutex::new(None),
+        })
+    }
+
+    /// Cap resources summed over the open databases.
+    ///
+    /// Takes effect the next time a database is opened.
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Root directory holding the managed databases.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resource caps in effect.
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    /// Get a handle to database `name`, opening it if needed.
+    ///
+    /// In read-write mode the database is created on first use. The handle
+    /// starts on the "default" branch like [`Strata::open`].
+    pub fn get(&self, name: &str) -> Result<Strata> {
+        let db = self.database(name)?;
+        Strata::from_database_with_mode(db, self.options.access_mode)
+    }
+
+    /// Release database `name` from the cache.
+    ///
+    /// Returns `false` if it was not open. The database shuts down once all
+    /// handles returned by [`get`](Self::get) are dropped.
+    pub fn close(&self, name: &str) -> bool {
+        let released = {
+            let mut inner = self.lock();
+            let released = inner.remove(name);
+            if released.is_some() {
+                inner.closed += 1;
+            }
+            released
+        };
+        let was_open = released.is_some();
+        release(released.into_iter().collect());
+        was_open
+    }
+
+    /// Release every open database from the cache.
+    pub fn close_all(&self) {
+        let released: Vec<_> = {
+            let mut inner = self.lock();
+            inner.closed += inner.open.len() as u64;
+            let names: Vec<_> = inner.open.keys().cloned().collect();
+            names.iter().filter_map(|name| inner.remove(name)).collect()
+        };
+        release(released);
+    }
+
+    /// Names of the databases currently held open, most recently used first.
+    pub fn open_databases(&self) -> Vec<String> {
+        let inner = self.lock();
+        let mut open: Vec<_> = inner.open.iter().collect();
+        open.sort_by_key(|(_, c)| std::cmp::Reverse(c.last_used));
+        open.into_iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Names of all databases under the root directory, sorted.
+    ///
+    /// A subdirectory counts as a database once it has a `strata.toml`.
+    pub fn list(&self) -> Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(Error::Io {
+                    reason: format!("Failed to read '{}': {}", self.root.display(), e),
+                })
+            }
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .path()
+                    .join(strata_engine::database::config::CONFIG_FILE_NAME)
+                    .is_file()
+            })
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| validate_name(name).is_ok())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Cache counters, resource usage, and statistics summed over the open
+    /// databases.
+    pub fn stats(&self) -> Result<ManagerStats> {
+        let (mut stats, open) = {
+            let inner = self.lock();
+            let stats = ManagerStats {
+                open: inner.open.len() as u64,
+                capacity: self.capacity as u64,
+                hits: inner.hits,
+                opened: inner.opened,
+                closed: inner.closed,
+                evicted: inner.evicted,
+                compactions: inner.compactions,
+                usage: ResourceUsage {
+                    background_threads: u64::from(inner.scheduler_thread),
+                    ..ResourceUsage::default()
+                },
+                totals: DatabaseStats::default(),
+            };
+            let open: Vec<_> = inner.open.values().map(|c| c.db.clone()).collect();
+            (stats, open)
+        };
+        for db in &open {
+            stats.usage.add(&usage_of(db));
+            let db_stats = database_stats(db)?;
+            let (total, add) = (&mut stats.totals.cursors, db_stats.cursors);
+            total.open += add.open;
+            total.opened += add.opened;
+            total.closed += add.closed;
+            total.expired += add.expired;
+            total.buffered_rows += add.buffered_rows;
+            stats.totals.primitives.add(&db_stats.primitives);
+            stats.totals.memory_bytes += db_stats.memory_bytes;
+            stats.totals.wal_bytes += db_stats.wal_bytes;
+            let (total, add) = (&mut stats.totals.transactions, db_stats.transactions);
+            total.active += add.active;
+            total.started += add.started;
+            total.committed += add.committed;
+            total.aborted += add.aborted;
+            total.conflicts += add.conflicts;
+            total.retries += add.retries;
+            total.retries_exhausted += add.retries_exhausted;
+            // Pool counters are process-wide, not per database
+            stats.totals.transaction_pool = db_stats.transaction_pool;
+        }
+        Ok(stats)
+    }
+
+    /// Cached database for `name`, opening it on a miss.
+    fn database(&self, name: &str) -> Result<Arc<Database>> {
+        validate_name(name)?;
+        {
+            let mut inner = self.lock();
+            let now = inner.tick();
+            if let Some(cached) = inner.open.get_mut(name) {
+                cached.last_used = now;
+                let db = cached.db.clone();
+                inner.hits += 1;
+                return Ok(db);
+            }
+        }
+
+        // Open without holding the lock so a slow recovery doesn't stall
+        // lookups of other databases. Concurrent opens of the same path
+        // receive the same instance from the engine's registry.
+        let path = self.root.join(name);
+        if self.options.access_mode == AccessMode::ReadOnly && !path.is_dir() {
+            return Err(Error::DatabaseNotFound {
+                name: name.to_string(),
+            });
+        }
+        let db = Strata::open_database(&path, &self.options)?;
+
+        // Take over the database's own compaction thread
+        let compaction = db.scheduled_compaction();
+        if compaction.is_some() {
+            db.stop_scheduled_compaction();
+        }
+
+        let slot = compaction.map(|interval| CompactionSlot {
+            interval,
+            due: Instant::now() + interval,
+        });
+        let open = {
+            let mut inner = self.lock();
+            let now = inner.tick();
+            if let Some(cached) = inner.open.get_mut(name) {
+                // Another opener got the same instance in first; make sure
+                // whichever of us stopped its compaction thread schedules it
+                cached.last_used = now;
+                if cached.compaction.is_none() {
+                    cached.compaction = slot;
+                }
+                return Ok(cached.db.clone());
+            }
+            inner.opened += 1;
+            inner.open.insert(
+                name.to_string(),
+                CachedDatabase {
+                    db: db.clone(),
+                    last_used: now,
+                    compaction: slot,
+                },
+            );
+            inner.open.len()
+        };
+
+        if compaction.is_some() {
+            self.start_scheduler()?;
+        }
+
+        if open > self.capacity || !self.limits.is_unlimited() {
+            release(self.evict(name));
+        }
+        Ok(db)
+    }
+
+    /// Evict databases until the cache fits its capacity and limits.
+    fn evict(&self, keep: &str) -> Vec<Released> {
+        // Measure outside the lock: memory accounting walks storage
+        let usage: HashMap<String, ResourceUsage> = if self.limits.is_unlimited() {
+            HashMap::new()
+        } else {
+            let open: Vec<_> = {
+                let inner = self.lock();
+                inner
+                    .open
+                    .iter()
+                    .map(|(name, c)| (name.clone(), c.db.clone()))
+                    .collect()
+            };
+            open.into_iter()
+                .map(|(name, db)| (name, usage_of(&db)))
+                .collect()
+        };
+        self.lock().evict(self.capacity, &self.limits, &usage, keep)
+    }
+
+    /// Start the shared compaction thread if it isn't running.
+    fn start_scheduler(&self) -> Result<()> {
+        let mut scheduler = self.scheduler.lock().unwrap_or_else(|e| e.into_inner());
+        if scheduler.is_some() {
+            return Ok(());
+        }
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let inner = Arc::downgrade(&self.inner);
+        let stop = Arc::clone(&shutdown);
+        let runner = if let Some(pool) = &self.options.worker_pool {
+            SchedulerRunner::Pooled(pool.schedule(
+                "strata-manager-compaction",
+                COMPACTION_TICK,
+                move || match inner.upgrade() {
+                    Some(inner) => {
+                        compact_due(&inner, &stop);
+                        true
+                    }
+                    None => false,
+                },
+            ))
+        } else {
+            let handle = std::thread::Builder::new()
+                .name("strata-manager-compaction".to_string())
+                .spawn(move || run_scheduler(inner, stop))
+                .map_err(|e| Error::Internal {
+                    reason: format!("failed to spawn compaction scheduler: {}", e),
+                })?;
+            self.lock().scheduler_thread = true;
+            SchedulerRunner::Thread(handle)
+        };
+        *scheduler = Some(Scheduler { shutdown, runner });
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ManagerInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for StrataManager {
+    fn drop(&mut self) {
+        let scheduler = self
+            .scheduler
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(scheduler) = scheduler {
+            scheduler.shutdown.store(true, Ordering::SeqCst);
+            match scheduler.runner {
+                SchedulerRunner::Thread(handle) => {
+                    let _ = handle.join();
+                }
+                SchedulerRunner::Pooled(job) => job.stop(),
+            }
+        }
+        self.close_all();
+    }
+}
+
+/// Compact due databases one at a time until the manager shuts down.
+fn run_scheduler(inner: Weak<Mutex<ManagerInner>>, shutdown: Arc<AtomicBool>) {
+    while !shutdown.load(Ordering::Relaxed) {
+        std::thread::sleep(COMPACTION_TICK);
+        let Some(inner) = inner.upgrade() else {
+            break;
+        };
+        compact_due(&inner, &shutdown);
+    }
+}
+
+/// Compact every database that is due, most overdue first.
+fn compact_due(inner: &Mutex<ManagerInner>, shutdown: &AtomicBool) {
+    loop {
+        if shutdown.load(Ordering::Relaxed) {
+            return;
+        }
+        let due = inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .next_due(Instant::now());
+        let Some(db) = due else {
+            return;
+        };
+        match db.compact() {
+            Ok(_) => {
+                inner.lock().unwrap_or_else(|e| e.into_inner()).compactions += 1;
+            }
+            Err(e) => {
+                tracing::warn!(target: "strata::manager", error = %e, "Scheduled compaction failed");
+            }
+        }
+    }
+}
+
+/// Database names map to a single directory under the root.
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 255
+        && !name.starts_with('.')
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput {
+            reason: format!(
+                "invalid database name '{}': use letters, digits, '-', '_' or '.', \
+                 not starting with '.'",
+                name
+            ),
+        })
+    }
+}
@@ -30,7 +30,7 @@ use crate::types::*;
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Output {
     // ==================== Primitive Results ====================
-    /// No return value (delete, flush, compact)
+    /// No return value (delete, flush)
     Unit,
 
     /// Optional value (for get operations that may not find a key)
@@ -59,9 +59,24 @@ pub enum Output {
     /// None if the key/cell/document doesn't exist.
     VersionHistory(Option<Vec<VersionedValue>>),
 
+    /// Key history under its history policy (KvHistory)
+    KvHistory(KvHistory),
+
     /// List of keys
     Keys(Vec<String>),
 
+    /// List of values (list ranges)
+    Values(Vec<Value>),
+
+    /// Optional values, one per requested JSON path
+    MaybeValues(Vec<Option<Value>>),
+
+    /// Unacknowledged events of a consumer group
+    PendingEvents(Vec<PendingEvent>),
+
+    /// Schema violations of a JSON document; empty if it matches
+    SchemaViolations(Vec<SchemaViolation>),
+
     // ==================== Scan Results ====================
     /// JSON list result with cursor
     JsonListResult {
@@ -71,10 +86,23 @@ pub enum Output {
         cursor: Option<String>,
     },
 
+    /// One page of a server-side result cursor
+    CursorPage {
+        /// The page, as the output variant the paged query returns.
+        page: Box<Output>,
+        /// Cursor for fetching the next page, if more rows remain.
+        cursor: Option<String>,
+        /// Rows remaining after this page.
+        remaining: u64,
+    },
+
     // ==================== Search Results ====================
     /// Vector search matches
     VectorMatches(Vec<VectorMatch>),
 
+    /// Vector search matches for each query of a batch, in query order
+    VectorBatchMatches(Vec<Vec<VectorMatch>>),
+
     // ==================== Vector-specific ====================
     /// Single vector data
     VectorData(Option<VersionedVectorData>),
@@ -126,10 +154,28 @@ pub enum Output {
         version: String,
     },
 
+    /// Compaction result
+    Compacted(CompactResult),
+
+    /// Database runtime statistics (boxed: by far the largest output)
+    Stats(Box<DatabaseStats>),
+
+    /// Entry counts and memory usage for one branch
+    BranchStats(BranchStats),
+
+    /// History retention of a branch
+    BranchRetention(BranchRetention),
+
+    /// Per-command latency percentiles
+    LatencyReport(LatencyReport),
+
     // ==================== Intelligence ====================
     /// Search results across primitives
     SearchResults(Vec<SearchResultHit>),
 
+    /// Full-text search results
+    TextSearchResults(Vec<TextSearchHit>),
+
     // ==================== Space ====================
     /// List of space names
     SpaceList(Vec<String>),
@@ -144,6 +190,12 @@ pub enum Output {
     /// Bundle validation result
     BundleValidated(BundleValidateResult),
 
+    /// Whole-database export result
+    DatabaseExported(DatabaseExportResult),
+
+    /// Whole-database import result
+    DatabaseImported(DatabaseImportResult),
+
     /// Time range for a branch (oldest and latest timestamps in microseconds since epoch)
     TimeRange {
         /// Oldest timestamp, or None if branch has no data.
@@ -0,0 +1,585 @@
+//! Prepared commands with parameter binding.
+//!
+//! A prepared command is a [`Command`] template whose string fields and
+//! [`Value`] fields may contain parameter placeholders. The template is
+//! parsed and validated once by [`Executor::prepare`](crate::Executor::prepare);
+//! each execution clones it, assigns the bound values to the placeholder
+//! fields and validates only those, so clients that issue the same command
+//! shape many times skip re-parsing and re-validating the fixed parts.
+//!
+//! ## Placeholder syntax
+//!
+//! | Placeholder | Meaning |
+//! |-------------|---------|
+//! | `$1`, `$2`, ... | Positional parameter (1-based) |
+//! | `$name` | Named parameter |
+//! | `$$text` | Literal string `$text` (escape) |
+//!
+//! A placeholder can appear as a whole string field of the command (key,
+//! cell, collection, branch, space, ...) or as a `Value::String` anywhere in
+//! a value field (value, payload, metadata, ...). String fields must be
+//! bound to `Value::String`; value fields accept any `Value`. Placeholders
+//! elsewhere, such as inside vector entries, are rejected by `prepare`, and
+//! so are `$$` escapes. Positional and named parameters cannot be mixed in
+//! one template. A bound key is checked against the KV key rules only for
+//! KV and JSON commands; other commands check their keys when they run.
+//!
+//! ## Example
+//!
+//! ```text
+//! use strata_executor::{Command, Params, Value};
+//!
+//! let stmt = executor.prepare(Command::KvPut {
+//!     branch: None,
+//!     space: None,
+//!     key: "$1".into(),
+//!     value: Value::String("$2".into()),
+//! })?;
+//!
+//! for i in 0..1000 {
+//!     executor.execute_prepared(
+//!         &stmt,
+//!         Params::Positional(vec![Value::String(format!("user:{}", i)), Value::Int(i)]),
+//!     )?;
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use strata_core::Value;
+
+use crate::bridge::validate_key;
+use crate::convert::convert_result;
+use crate::types::BranchId;
+use crate::{Command, Error, Result};
+
+/// Parameters bound to a prepared command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Params {
+    /// Values for `$1`, `$2`, ... in order.
+    Positional(Vec<Value>),
+    /// Values for `$name` placeholders, keyed by name.
+    Named(HashMap<String, Value>),
+}
+
+impl Params {
+    /// No parameters.
+    pub fn none() -> Self {
+        Params::Positional(Vec::new())
+    }
+}
+
+/// A parameter reference found in a command template.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParamRef {
+    /// Positional parameter, 1-based.
+    Positional(usize),
+    /// Named parameter.
+    Named(String),
+}
+
+impl std::fmt::Display for ParamRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamRef::Positional(i) => write!(f, "${}", i),
+            ParamRef::Named(name) => write!(f, "${}", name),
+        }
+    }
+}
+
+/// Where a placeholder sits in the serialized template.
+#[derive(Debug, Clone)]
+enum PathSeg {
+    Field(String),
+    Index(usize),
+}
+
+/// What a placeholder stands in for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotKind {
+    /// A plain string field; must be bound to `Value::String`.
+    String,
+    /// A `Value` field; accepts any value.
+    Value,
+}
+
+/// A step from a value field to the value a placeholder replaces.
+#[derive(Debug, Clone)]
+enum ValueSeg {
+    Index(usize),
+    Key(String),
+}
+
+/// What a `$`-prefixed string in the template turned out to be.
+#[derive(Debug, Clone)]
+enum Marker {
+    Param(ParamRef),
+    /// A `$$` escape, to be unescaped in the typed template
+    Escape(String),
+}
+
+/// Where a placeholder sits in the typed command.
+#[derive(Debug, Clone)]
+struct Location {
+    /// Command field holding the placeholder
+    field: String,
+    /// Element of a list field (`ListPush` values)
+    index: Option<usize>,
+    /// Path within a value field
+    path: Vec<ValueSeg>,
+    kind: SlotKind,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    at: Location,
+    param: ParamRef,
+}
+
+/// A validated command template ready for repeated execution.
+///
+/// Create with [`Executor::prepare`](crate::Executor::prepare) and run with
+/// [`Executor::execute_prepared`](crate::Executor::execute_prepared) or
+/// [`Session::execute_prepared`](crate::Session::execute_prepared).
+#[derive(Debug, Clone)]
+pub struct PreparedCommand {
+    template: Command,
+    slots: Vec<Slot>,
+    params: Vec<ParamRef>,
+    name: &'static str,
+    is_write: bool,
+}
+
+impl PreparedCommand {
+    /// Parse and validate a command template.
+    ///
+    /// Fails if the template mixes positional and named parameters, if
+    /// positional parameters are not numbered `$1..$n` without gaps, if a
+    /// placeholder sits where parameters are not supported, or if a literal
+    /// key in the template is invalid.
+    pub(crate) fn new(mut template: Command) -> Result<Self> {
+        let name = template.name();
+        let is_write = template.is_write();
+
+        // Placeholders are found in the serialized form, but bound into the
+        // typed template: a JSON round trip would lose non-finite floats.
+        let json = serde_json::to_value(&template).map_err(|e| Error::Serialization {
+            reason: e.to_string(),
+        })?;
+        let mut found = Vec::new();
+        collect_slots(&json, &mut Vec::new(), &mut found)?;
+
+        let mut slots = Vec::with_capacity(found.len());
+        for (path, marker, kind) in found {
+            let at = locate(path, kind)
+                .filter(|at| slot_exists(&mut template, at))
+                .ok_or_else(|| {
+                    let shown = match &marker {
+                        Marker::Param(param) => format!("Parameter {}", param),
+                        Marker::Escape(s) => format!("Escaped string '{}'", s),
+                    };
+                    Error::InvalidInput {
+                        reason: format!("{} is not supported there in {}", shown, name),
+                    }
+                })?;
+            match marker {
+                Marker::Param(param) => slots.push(Slot { at, param }),
+                Marker::Escape(_) => unescape(&mut template, &at),
+            }
+        }
+
+        // Literal keys are validated here, once, rather than on every execution.
+        if checks_key(&template) && !slots.iter().any(|s| s.at.field == "key") {
+            if let Some(key) = string_field(&mut template, "key") {
+                convert_result(validate_key(key))?;
+            }
+        }
+
+        let mut params: Vec<ParamRef> = Vec::new();
+        for slot in &slots {
+            if !params.contains(&slot.param) {
+                params.push(slot.param.clone());
+            }
+        }
+
+        let positional = params
+            .iter()
+            .filter(|p| matches!(p, ParamRef::Positional(_)))
+            .count();
+        if positional > 0 && positional != params.len() {
+            return Err(Error::InvalidInput {
+                reason: "Cannot mix positional and named parameters".into(),
+            });
+        }
+        if positional > 0 {
+            params.sort_by_key(|p| match p {
+                ParamRef::Positional(i) => *i,
+                ParamRef::Named(_) => 0,
+            });
+            for (expected, p) in (1..).zip(&params) {
+                if *p != ParamRef::Positional(expected) {
+                    return Err(Error::InvalidInput {
+                        reason: format!("Missing positional parameter ${}", expected),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            template,
+            slots,
+            params,
+            name,
+            is_write,
+        })
+    }
+
+    /// Name of the templated command.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether the templated command writes data.
+    pub fn is_write(&self) -> bool {
+        self.is_write
+    }
+
+    /// Parameters the template expects, in positional order for `$n`
+    /// templates and first-use order for named templates.
+    pub fn params(&self) -> &[ParamRef] {
+        &self.params
+    }
+
+    /// Substitute parameters and produce an executable command.
+    ///
+    /// Only the bound values are checked: string fields must be bound to
+    /// strings, and a bound KV or JSON key must be a valid key.
+    pub fn bind(&self, params: Params) -> Result<Command> {
+        let values: HashMap<ParamRef, Value> = match params {
+            Params::Positional(values) => values
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (ParamRef::Positional(i + 1), v))
+                .collect(),
+            Params::Named(values) => values
+                .into_iter()
+                .map(|(k, v)| (ParamRef::Named(k), v))
+                .collect(),
+        };
+
+        if values.len() != self.params.len() || self.params.iter().any(|p| !values.contains_key(p))
+        {
+            let expected = self
+                .params
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "Prepared {} expects parameters [{}], got {}",
+                    self.name,
+                    expected,
+                    values.len()
+                ),
+            });
+        }
+
+        let mut cmd = self.template.clone();
+        let checks_key = checks_key(&cmd);
+        for slot in &self.slots {
+            let value = values.get(&slot.param).expect("checked above");
+            match slot.at.kind {
+                SlotKind::String => {
+                    let Value::String(s) = value else {
+                        return Err(Error::WrongType {
+                            expected: "String".into(),
+                            actual: format!("{} for parameter {}", value.type_name(), slot.param),
+                        });
+                    };
+                    if checks_key && slot.at.field == "key" {
+                        convert_result(validate_key(s))?;
+                    }
+                    *string_field(&mut cmd, &slot.at.field).expect("located by prepare") =
+                        s.clone();
+                }
+                SlotKind::Value => {
+                    *value_slot(&mut cmd, &slot.at).expect("located by prepare") = value.clone();
+                }
+            }
+        }
+        Ok(cmd)
+    }
+}
+
+/// Whether `cmd` takes a KV or JSON key, checked with [`validate_key`].
+///
+/// Vector and branch metadata keys follow other rules, checked when the
+/// command runs.
+fn checks_key(cmd: &Command) -> bool {
+    matches!(
+        cmd,
+        Command::KvPut { .. }
+            | Command::KvGet { .. }
+            | Command::KvDelete { .. }
+            | Command::KvGetv { .. }
+            | Command::KvIncr { .. }
+            | Command::KvHistory { .. }
+            | Command::JsonSet { .. }
+            | Command::JsonGet { .. }
+            | Command::JsonGetPaths { .. }
+            | Command::JsonDelete { .. }
+            | Command::JsonGetv { .. }
+            | Command::JsonMerge { .. }
+            | Command::JsonPatch { .. }
+            | Command::JsonArrayAppend { .. }
+            | Command::JsonArrayInsert { .. }
+            | Command::JsonArrayRemove { .. }
+            | Command::JsonArrayLen { .. }
+            | Command::JsonValidate { .. }
+    )
+}
+
+/// Turn the serialized path of a placeholder into a location on the typed
+/// command. Returns `None` for positions parameters are not supported in.
+fn locate(path: Vec<PathSeg>, kind: SlotKind) -> Option<Location> {
+    // The first segment is the variant name
+    let mut segs = path.into_iter().skip(1);
+    let Some(PathSeg::Field(field)) = segs.next() else {
+        return None;
+    };
+    let mut at = Location {
+        field,
+        index: None,
+        path: Vec::new(),
+        kind,
+    };
+    match kind {
+        SlotKind::String => segs.next().is_none().then_some(at),
+        SlotKind::Value => {
+            let mut segs = segs.peekable();
+            if let Some(PathSeg::Index(i)) = segs.peek() {
+                at.index = Some(*i);
+                segs.next();
+            }
+            // A serialized value is externally tagged: `{"Array": [..]}`
+            while let Some(tag) = segs.next() {
+                let seg = match (tag, segs.next()?) {
+                    (PathSeg::Field(tag), PathSeg::Index(i)) if tag == "Array" => {
+                        ValueSeg::Index(i)
+                    }
+                    (PathSeg::Field(tag), PathSeg::Field(key)) if tag == "Object" => {
+                        ValueSeg::Key(key)
+                    }
+                    _ => return None,
+                };
+                at.path.push(seg);
+            }
+            Some(at)
+        }
+    }
+}
+
+fn slot_exists(cmd: &mut Command, at: &Location) -> bool {
+    match at.kind {
+        SlotKind::String => at.index.is_none() && string_field(cmd, &at.field).is_some(),
+        SlotKind::Value => value_slot(cmd, at).is_some(),
+    }
+}
+
+/// Drop the leading `$` of the `$$` escape at `at`.
+fn unescape(cmd: &mut Command, at: &Location) {
+    let s = match at.kind {
+        SlotKind::String => string_field(cmd, &at.field),
+        SlotKind::Value => match value_slot(cmd, at) {
+            Some(Value::String(s)) => Some(s),
+            _ => None,
+        },
+    };
+    if let Some(s) = s {
+        s.remove(0);
+    }
+}
+
+fn value_slot<'c>(cmd: &'c mut Command, at: &Location) -> Option<&'c mut Value> {
+    let root = match (at.field.as_str(), at.index, cmd) {
+        ("values", Some(i), Command::ListPush { values, .. }) => values.get_mut(i),
+        (field, None, cmd) => value_field(cmd, field),
+        _ => None,
+    }?;
+    at.path
+        .iter()
+        .try_fold(root, |node, seg| match (seg, node) {
+            (ValueSeg::Index(i), Value::Array(items)) => items.get_mut(*i),
+            (ValueSeg::Key(key), Value::Object(fields)) => fields.get_mut(key),
+            _ => None,
+        })
+}
+
+/// Accessor for one kind of field across the variants of [`Command`].
+///
+/// Each line names a field, the pattern that binds it and the variants
+/// that have it; the accessor returns `None` for any other variant.
+macro_rules! field_accessor {
+    ($(#[$attr:meta])* fn $name:ident -> $ty:ty {
+        $($field:ident: $pattern:pat => $bind:ident in $($variant:ident)|+;)*
+    }) => {
+        $(#[$attr])*
+        fn $name<'c>(cmd: &'c mut Command, field: &str) -> Option<&'c mut $ty> {
+            match (field, cmd) {
+                $((stringify!($field), $(Command::$variant { $field: $pattern, .. })|+) => {
+                    Some($bind)
+                })*
+                _ => None,
+            }
+        }
+    };
+}
+
+field_accessor! {
+    /// The string field `field` of `cmd`, if the command has one set.
+    fn string_field -> String {
+        as_branch: Some(as_branch) => as_branch in BranchImport;
+        branch: BranchId(branch) => branch in BranchGet | BranchExists | BranchStats
+            | BranchSetRetention | BranchGetRetention | BranchAddTag | BranchRemoveTag
+            | BranchSetMetadata | BranchClose | BranchFail | BranchDelete;
+        branch: Some(BranchId(branch)) => branch in KvPut | KvGet | KvDelete | KvList | KvGetv
+            | KvIncr | KvHistory | KvSetHistoryPolicy | JsonSet | JsonGet | JsonGetPaths
+            | JsonDelete | JsonGetv | JsonList | JsonMerge | JsonPatch | JsonArrayAppend
+            | JsonArrayInsert | JsonArrayRemove | JsonArrayLen | JsonSetSchema | JsonGetSchema
+            | JsonValidate | EventAppend | EventGet | EventGetByType | EventLen | EventSetRetention
+            | EventSetSchema | EventGetSchema | EventCreateGroup | EventReadGroup | EventAck
+            | EventPending | StateSet | StateGet | StateCas | StateIncr | StateGetv
+            | StateGetVersion | StateInit | StateDelete | StateList | ListPush | ListPop | ListRange
+            | ListLen | VectorUpsert | VectorGet | VectorDelete | VectorSearch | VectorSearchBatch
+            | VectorCreateCollection | VectorReindex | VectorDeleteCollection
+            | VectorListCollections | VectorCollectionStats | VectorBatchUpsert | TxnBegin
+            | RetentionApply | RetentionStats | RetentionPreview | TimeRange | Search | TextSearch
+            | SemanticSearch | HybridSearch | SpaceList | SpaceCreate | SpaceDelete | SpaceExists;
+        branch_id: Some(branch_id) => branch_id in BranchCreate;
+        branch_id: branch_id => branch_id in BranchExport;
+        cell: cell => cell in StateSet | StateGet | StateCas | StateIncr | StateGetv
+            | StateGetVersion | StateInit | StateDelete;
+        collection: collection => collection in VectorUpsert | VectorGet | VectorDelete
+            | VectorSearch | VectorSearchBatch | VectorCreateCollection | VectorReindex
+            | VectorDeleteCollection | VectorCollectionStats | VectorBatchUpsert | HybridSearch;
+        consumer: consumer => consumer in EventReadGroup;
+        cursor: Some(cursor) => cursor in KvList | JsonList;
+        cursor: cursor => cursor in CursorFetch | CursorClose;
+        error: error => error in BranchFail;
+        event_type: event_type => event_type in EventAppend | EventGetByType | EventSetRetention
+            | EventSetSchema | EventGetSchema | EventCreateGroup | EventReadGroup | EventAck
+            | EventPending;
+        group: group => group in EventCreateGroup | EventReadGroup | EventAck | EventPending;
+        key: key => key in KvPut | KvGet | KvDelete | KvGetv | KvIncr | KvHistory | JsonSet
+            | JsonGet | JsonGetPaths | JsonDelete | JsonGetv | JsonMerge | JsonPatch
+            | JsonArrayAppend | JsonArrayInsert | JsonArrayRemove | JsonArrayLen | JsonValidate
+            | VectorUpsert | VectorGet | VectorDelete | BranchSetMetadata;
+        list: list => list in ListPush | ListPop | ListRange | ListLen;
+        namespace: Some(namespace) => namespace in VectorUpsert | VectorSearch | VectorBatchUpsert;
+        path: path => path in JsonSet | JsonGet | JsonDelete | JsonArrayAppend | JsonArrayInsert
+            | JsonArrayRemove | JsonArrayLen | BranchExport | BranchImport | BranchBundleValidate
+            | DatabaseExport | DatabaseImport;
+        prefix: Some(prefix) => prefix in KvList | JsonList | StateList;
+        prefix: prefix => prefix in KvSetHistoryPolicy | JsonSetSchema | JsonGetSchema;
+        query: query => query in Search | TextSearch | SemanticSearch | HybridSearch;
+        space: Some(space) => space in KvPut | KvGet | KvDelete | KvList | KvGetv | KvIncr
+            | KvHistory | KvSetHistoryPolicy | JsonSet | JsonGet | JsonGetPaths | JsonDelete
+            | JsonGetv | JsonList | JsonMerge | JsonPatch | JsonArrayAppend | JsonArrayInsert
+            | JsonArrayRemove | JsonArrayLen | JsonSetSchema | JsonGetSchema | JsonValidate
+            | EventAppend | EventGet | EventGetByType | EventLen | EventSetRetention
+            | EventSetSchema | EventGetSchema | EventCreateGroup | EventReadGroup | EventAck
+            | EventPending | StateSet | StateGet | StateCas | StateIncr | StateGetv
+            | StateGetVersion | StateInit | StateDelete | StateList | ListPush | ListPop | ListRange
+            | ListLen | VectorUpsert | VectorGet | VectorDelete | VectorSearch | VectorSearchBatch
+            | VectorCreateCollection | VectorReindex | VectorDeleteCollection
+            | VectorListCollections | VectorCollectionStats | VectorBatchUpsert | Search
+            | TextSearch | SemanticSearch | HybridSearch;
+        space: space => space in SpaceCreate | SpaceDelete | SpaceExists;
+        tag: tag => tag in BranchAddTag | BranchRemoveTag;
+    }
+}
+
+field_accessor! {
+    /// The value field `field` of `cmd`, if the command has one set.
+    fn value_field -> Value {
+        delta: delta => delta in KvIncr | StateIncr;
+        metadata: Some(metadata) => metadata in VectorUpsert | BranchCreate;
+        ops: ops => ops in JsonPatch;
+        patch: patch => patch in JsonMerge;
+        payload: payload => payload in EventAppend;
+        schema: schema => schema in JsonSetSchema | EventSetSchema;
+        value: value => value in KvPut | JsonSet | JsonArrayAppend | JsonArrayInsert | JsonValidate
+            | StateSet | StateCas | StateInit | BranchSetMetadata;
+    }
+}
+
+/// Parse a placeholder string. Returns `Ok(None)` for literals.
+fn parse_placeholder(s: &str) -> Result<Option<Marker>> {
+    let Some(rest) = s.strip_prefix('$') else {
+        return Ok(None);
+    };
+    if rest.starts_with('$') {
+        return Ok(Some(Marker::Escape(s.to_string())));
+    }
+    if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()) {
+        return match rest.parse::<usize>() {
+            Ok(i) if i > 0 => Ok(Some(Marker::Param(ParamRef::Positional(i)))),
+            _ => Err(Error::InvalidInput {
+                reason: format!("Invalid positional parameter '{}'", s),
+            }),
+        };
+    }
+    let mut chars = rest.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(Some(Marker::Param(ParamRef::Named(rest.to_string()))))
+    } else {
+        // Not a placeholder: an ordinary string that happens to start with '$'
+        Ok(None)
+    }
+}
+
+fn collect_slots(
+    node: &serde_json::Value,
+    path: &mut Vec<PathSeg>,
+    slots: &mut Vec<(Vec<PathSeg>, Marker, SlotKind)>,
+) -> Result<()> {
+    match node {
+        serde_json::Value::String(s) => {
+            if let Some(marker) = parse_placeholder(s)? {
+                slots.push((path.clone(), marker, SlotKind::String));
+            }
+        }
+        serde_json::Value::Object(map) => {
+            // A serialized `Value::String` is `{"String": "..."}`; a placeholder
+            // there stands in for a whole Value.
+            if map.len() == 1 {
+                if let Some(serde_json::Value::String(s)) = map.get("String") {
+                    if let Some(marker) = parse_placeholder(s)? {
+                        slots.push((path.clone(), marker, SlotKind::Value));
+                    }
+                    return Ok(());
+                }
+            }
+            for (k, v) in map {
+                path.push(PathSeg::Field(k.clone()));
+                collect_slots(v, path, slots)?;
+                path.pop();
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                path.push(PathSeg::Index(i));
+                collect_slots(v, path, slots)?;
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -34,7 +34,7 @@ use crate::bridge::{
 };
 use crate::convert::convert_result;
 use crate::types::BranchId;
-use crate::{Command, Error, Executor, Output, Result};
+use crate::{Command, Error, Executor, Output, Params, PreparedCommand, Result};
 
 /// A stateful session that wraps an [`Executor`] and manages an optional
 /// open transaction with read-your-writes semantics.
@@ -48,27 +48,35 @@ pub struct Session {
     executor: Executor,
     db: Arc<Database>,
     txn_ctx: Option<TransactionContext>,
-    txn_branch_id: Option<strata_core::types::BranchId>,
+    /// Branch the open transaction began on, used by commands that name none
+    txn_branch: Option<BranchId>,
 }
 
 impl Session {
     /// Create a new session.
+    ///
+    /// Takes an engine type, so it is outside the stable API; use
+    /// [`Strata::session`](crate::Strata::session) instead.
+    #[doc(hidden)]
     pub fn new(db: Arc<Database>) -> Self {
         Self {
             executor: Executor::new(db.clone()),
             db,
             txn_ctx: None,
-            txn_branch_id: None,
+            txn_branch: None,
         }
     }
 
     /// Create a new session with an explicit access mode.
+    ///
+    /// Outside the stable API, like [`new`](Self::new).
+    #[doc(hidden)]
     pub fn new_with_mode(db: Arc<Database>, access_mode: AccessMode) -> Self {
         Self {
             executor: Executor::new_with_mode(db.clone(), access_mode),
             db,
             txn_ctx: None,
-            txn_branch_id: None,
+            txn_branch: None,
         }
     }
 
@@ -85,6 +93,13 @@ impl Session {
             });
         }
 
+        // Inside a transaction, commands that name no branch use the
+        // transaction's branch rather than the default one
+        if let (Some(txn_branch), Some((branch @ None, _))) =
+            (&self.txn_branch, txn_target(&mut cmd))
+        {
+            *branch = Some(txn_branch.clone());
+        }
         cmd.resolve_defaults();
 
         match &cmd {
@@ -100,6 +115,7 @@ impl Session {
             Command::VectorUpsert { .. }
             | Command::VectorDelete { .. }
             | Command::VectorCreateCollection { .. }
+            | Command::VectorReindex { .. }
             | Command::VectorDeleteCollection { .. }
                 if self.txn_ctx.is_some() =>
             {
@@ -109,6 +125,12 @@ impl Session {
                 })
             }
 
+            // Cursors materialize committed data, so they would not see the
+            // transaction's uncommitted writes.
+            Command::CursorOpen { .. } if self.txn_ctx.is_some() => Err(Error::InvalidInput {
+                reason: "Cursors are not supported inside a transaction".to_string(),
+            }),
+
             // Branch create/delete modify global state outside the transaction
             // scope and are not supported inside a transaction.
             Command::BranchCreate { .. } | Command::BranchDelete { .. }
@@ -127,6 +149,16 @@ impl Session {
             | Command::BranchGet { .. }
             | Command::BranchList { .. }
             | Command::BranchExists { .. }
+            | Command::BranchStats { .. }
+            | Command::BranchSetRetention { .. }
+            | Command::BranchGetRetention { .. }
+            | Command::BranchAddTag { .. }
+            | Command::BranchRemoveTag { .. }
+            | Command::BranchSetMetadata { .. }
+            | Command::BranchQuery { .. }
+            | Command::BranchCount
+            | Command::BranchClose { .. }
+            | Command::BranchFail { .. }
             | Command::BranchDelete { .. }
             // Vector commands: writes delegate to executor outside txn,
             // reads are always safe to delegate.
@@ -134,32 +166,49 @@ impl Session {
             | Command::VectorGet { .. }
             | Command::VectorDelete { .. }
             | Command::VectorSearch { .. }
+            | Command::VectorSearchBatch { .. }
             | Command::VectorCreateCollection { .. }
+            | Command::VectorReindex { .. }
             | Command::VectorDeleteCollection { .. }
             | Command::VectorListCollections { .. }
             | Command::Ping
             | Command::Info
             | Command::Flush
             | Command::Compact
+            | Command::Stats
+            | Command::LatencyReport
+            | Command::LatencyReset
             | Command::RetentionApply { .. }
             | Command::RetentionStats { .. }
             | Command::RetentionPreview { .. }
             | Command::BranchExport { .. }
             | Command::BranchImport { .. }
             | Command::BranchBundleValidate { .. }
+            | Command::DatabaseExport { .. }
+            | Command::DatabaseImport { .. }
             | Command::Search { .. }
+            | Command::TextSearch { .. }
+            | Command::SemanticSearch { .. }
+            | Command::HybridSearch { .. }
             // Space commands: manage spaces at the branch level,
             // not transactional.
             | Command::SpaceList { .. }
             | Command::SpaceCreate { .. }
             | Command::SpaceDelete { .. }
             | Command::SpaceExists { .. }
-            // Version history commands (KvGetv, StateGetv, JsonGetv) require
-            // storage-layer version chains which are not available through the
-            // transaction context. These always read from the committed store,
-            // even during an active transaction.
+            // Cursor commands: opening is rejected above inside a transaction;
+            // fetch and close only touch already materialized results.
+            | Command::CursorOpen { .. }
+            | Command::CursorFetch { .. }
+            | Command::CursorClose { .. }
+            // Version history commands (KvGetv, KvHistory, StateGetv,
+            // StateGetVersion, JsonGetv) require storage-layer version chains which are not
+            // available through the transaction context. These always read
+            // from the committed store, even during an active transaction.
             | Command::KvGetv { .. }
+            | Command::KvHistory { .. }
             | Command::StateGetv { .. }
+            | Command::StateGetVersion { .. }
             | Command::JsonGetv { .. }
             // JsonList enumerates keys via storage-layer scan. Making it
             // txn-aware would require merging the write-set with a committed
@@ -173,7 +222,46 @@ impl Session {
             // The transaction write-set does not maintain per-type indexes, so
             // this always reads from the committed store even during an active
             // transaction.
-            | Command::EventGetByType { .. } => self.executor.execute(cmd),
+            | Command::EventGetByType { .. }
+            // Retention and history policies and stream and document
+            // schemas are configuration, applied and committed on their own
+            // like the other Retention commands. JsonValidate only reads the
+            // schema.
+            | Command::EventSetRetention { .. }
+            | Command::EventSetSchema { .. }
+            | Command::EventGetSchema { .. }
+            | Command::JsonSetSchema { .. }
+            | Command::JsonGetSchema { .. }
+            | Command::JsonValidate { .. }
+            | Command::KvSetHistoryPolicy { .. }
+            // Merge, patch and array operations read, apply and write the
+            // document in a transaction of their own; JsonGetPaths and
+            // JsonArrayLen read the committed document like JsonList.
+            | Command::JsonGetPaths { .. }
+            | Command::JsonMerge { .. }
+            | Command::JsonPatch { .. }
+            | Command::JsonArrayAppend { .. }
+            | Command::JsonArrayInsert { .. }
+            | Command::JsonArrayRemove { .. }
+            | Command::JsonArrayLen { .. }
+            // Consumer group state is shared by every consumer of the group,
+            // so deliveries and acks commit immediately rather than with the
+            // session's transaction.
+            | Command::EventCreateGroup { .. }
+            | Command::EventReadGroup { .. }
+            | Command::EventAck { .. }
+            | Command::EventPending { .. }
+            // Atomic increments commit on their own, so counters shared by
+            // several sessions never lose updates.
+            | Command::KvIncr { .. }
+            | Command::StateIncr { .. }
+            // Lists are work queues shared between sessions: a pop must hand
+            // a value to exactly one worker, and a blocking pop must not
+            // hold a transaction open while it waits.
+            | Command::ListPush { .. }
+            | Command::ListPop { .. }
+            | Command::ListRange { .. }
+            | Command::ListLen { .. } => self.executor.execute(cmd),
 
             // Data commands: route through txn if active, else delegate
             _ => {
@@ -186,6 +274,13 @@ impl Session {
         }
     }
 
+    /// Bind parameters to a prepared command and execute it in this session.
+    ///
+    /// Routes through the active transaction exactly like [`Session::execute`].
+    pub fn execute_prepared(&mut self, stmt: &PreparedCommand, params: Params) -> Result<Output> {
+        self.execute(stmt.bind(params)?)
+    }
+
     /// Get a reference to the underlying executor.
     pub fn executor(&self) -> &Executor {
         &self.executor
@@ -200,22 +295,26 @@ impl Session {
             return Err(Error::TransactionAlreadyActive);
         }
 
-        let branch = match cmd {
-            Command::TxnBegin { branch, .. } => branch.clone().unwrap_or_else(BranchId::default),
+        let (branch, options) = match cmd {
+            Command::TxnBegin { branch, options } => (
+                branch.clone().unwrap_or_else(BranchId::default),
+                options.clone().unwrap_or_default(),
+            ),
             _ => unreachable!(),
         };
 
         let core_branch_id = to_core_branch_id(&branch)?;
-        let ctx = self.db.begin_transaction(core_branch_id);
+        let mut ctx = self.db.begin_transaction(core_branch_id);
+        ctx.set_lock_mode(options.lock_mode);
         self.txn_ctx = Some(ctx);
-        self.txn_branch_id = Some(core_branch_id);
+        self.txn_branch = Some(branch);
 
         Ok(Output::TxnBegun)
     }
 
     fn handle_commit(&mut self) -> Result<Output> {
         let mut ctx = self.txn_ctx.take().ok_or(Error::TransactionNotActive)?;
-        self.txn_branch_id = None;
+        self.txn_branch = None;
 
         match self.db.commit_transaction(&mut ctx) {
             Ok(version) => {
@@ -251,8 +350,8 @@ impl Session {
 
     fn handle_abort(&mut self) -> Result<Output> {
         let ctx = self.txn_ctx.take().ok_or(Error::TransactionNotActive)?;
-        self.txn_branch_id = None;
-        self.db.end_transaction(ctx);
+        self.txn_branch = None;
+        self.db.abort_transaction(ctx);
         Ok(Output::TxnAborted)
     }
 
@@ -272,38 +371,34 @@ impl Session {
     // In-transaction command execution
     // =========================================================================
 
-    fn execute_in_txn(&mut self, cmd: Command) -> Result<Output> {
-        let branch_id = self
-            .txn_branch_id
-            .expect("txn_branch_id set when txn_ctx is Some");
-
-        // Extract space from the command being executed
-        let space = match &cmd {
-            Command::KvPut { space, .. }
-            | Command::KvGet { space, .. }
-            | Command::KvDelete { space, .. }
-            | Command::KvList { space, .. }
-            | Command::KvGetv { space, .. }
-            | Command::StateSet { space, .. }
-            | Command::StateGet { space, .. }
-            | Command::StateGetv { space, .. }
-            | Command::StateDelete { space, .. }
-            | Command::StateInit { space, .. }
-            | Command::StateCas { space, .. }
-            | Command::StateList { space, .. }
-            | Command::EventAppend { space, .. }
-            | Command::EventGet { space, .. }
-            | Command::EventGetByType { space, .. }
-            | Command::EventLen { space, .. }
-            | Command::JsonSet { space, .. }
-            | Command::JsonGet { space, .. }
-            | Command::JsonGetv { space, .. }
-            | Command::JsonDelete { space, .. }
-            | Command::JsonList { space, .. } => {
-                space.clone().unwrap_or_else(|| "default".to_string())
-            }
-            _ => "default".to_string(),
+    fn execute_in_txn(&mut self, mut cmd: Command) -> Result<Output> {
+        let (branch, space) = match txn_target(&mut cmd) {
+            Some((branch, space)) => (
+                branch.clone().unwrap_or_default(),
+                space.clone().unwrap_or_else(|| "default".to_string()),
+            ),
+            None => (BranchId::default(), "default".to_string()),
         };
+
+        // A command on another branch adds that branch to the transaction;
+        // the commit applies to every branch written, or to none
+        let txn_branch = self
+            .txn_branch
+            .as_ref()
+            .expect("txn_branch set when txn_ctx is Some");
+        if &branch != txn_branch && !branch.is_default() {
+            match self.executor.execute(Command::BranchExists {
+                branch: branch.clone(),
+            })? {
+                Output::Bool(true) => {}
+                _ => {
+                    return Err(Error::BranchNotFound {
+                        branch: branch.as_str().to_string(),
+                    })
+                }
+            }
+        }
+        let branch_id = to_core_branch_id(&branch)?;
         let ns = Namespace::for_branch_space(branch_id, &space);
 
         // Temporarily take the context to create a Transaction
@@ -345,6 +440,7 @@ impl Session {
                 let keys: Vec<String> = entries
                     .into_iter()
                     .filter_map(|(k, _)| k.user_key_string())
+                    .filter(|k| strata_core::key::reserved_prefix(k).is_none())
                     .collect();
                 if let Some(lim) = limit {
                     let start_idx = if let Some(ref cur) = cursor {
@@ -487,6 +583,7 @@ impl Session {
                 Ok(Output::MaybeVersion(Some(extract_version(&version))))
             }
             Command::StateSet { cell, value, .. } => {
+                strata_core::key::ensure_not_reserved(&cell).map_err(Error::from)?;
                 // Construct key using the space-aware namespace (not StateCellExt
                 // which hardcodes the "default" space).
                 let full_key = Key::new_state(ns, &cell);
@@ -537,8 +634,37 @@ impl Session {
 
 impl Drop for Session {
     fn drop(&mut self) {
+        // Also reached while unwinding from a panic mid-transaction
         if let Some(ctx) = self.txn_ctx.take() {
-            self.db.end_transaction(ctx);
+            self.db.abort_transaction(ctx);
         }
     }
 }
+
+/// Branch and space fields of a command that runs inside a transaction.
+fn txn_target(cmd: &mut Command) -> Option<(&mut Option<BranchId>, &mut Option<String>)> {
+    match cmd {
+        Command::KvPut { branch, space, .. }
+        | Command::KvGet { branch, space, .. }
+        | Command::KvDelete { branch, space, .. }
+        | Command::KvList { branch, space, .. }
+        | Command::KvGetv { branch, space, .. }
+        | Command::StateSet { branch, space, .. }
+        | Command::StateGet { branch, space, .. }
+        | Command::StateGetv { branch, space, .. }
+        | Command::StateDelete { branch, space, .. }
+        | Command::StateInit { branch, space, .. }
+        | Command::StateCas { branch, space, .. }
+        | Command::StateList { branch, space, .. }
+        | Command::EventAppend { branch, space, .. }
+        | Command::EventGet { branch, space, .. }
+        | Command::EventGetByType { branch, space, .. }
+        | Command::EventLen { branch, space, .. }
+        | Command::JsonSet { branch, space, .. }
+        | Command::JsonGet { branch, space, .. }
+        | Command::JsonGetv { branch, space, .. }
+        | Command::JsonDelete { branch, space, .. }
+        | Command::JsonList { branch, space, .. } => Some((branch, space)),
+        _ => None,
+    }
+}
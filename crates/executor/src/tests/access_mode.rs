@@ -3,9 +3,9 @@
 use std::sync::Arc;
 
 use strata_engine::Database;
-use strata_security::AccessMode;
+use strata_security::{AccessMode, OpenOptions};
 
-use crate::types::DistanceMetric;
+use crate::types::{DistanceMetric, ImportConflict, ListEnd, RetentionPolicy};
 use crate::{Command, Error, Executor, Session, Strata, Value};
 
 // =============================================================================
@@ -78,6 +78,18 @@ fn test_read_only_blocks_all_writes() {
             space: None,
             key: "k".into(),
         },
+        Command::KvIncr {
+            branch: None,
+            space: None,
+            key: "k".into(),
+            delta: Value::Int(1),
+        },
+        Command::KvSetHistoryPolicy {
+            branch: None,
+            space: None,
+            prefix: "s:".into(),
+            policy: RetentionPolicy::MaxLen(1),
+        },
         Command::JsonSet {
             branch: None,
             space: None,
@@ -121,6 +133,26 @@ fn test_read_only_blocks_all_writes() {
             space: None,
             cell: "c".into(),
         },
+        Command::StateIncr {
+            branch: None,
+            space: None,
+            cell: "c".into(),
+            delta: Value::Int(1),
+        },
+        Command::ListPush {
+            branch: None,
+            space: None,
+            list: "l".into(),
+            end: ListEnd::Right,
+            values: vec![Value::Int(1)],
+        },
+        Command::ListPop {
+            branch: None,
+            space: None,
+            list: "l".into(),
+            end: ListEnd::Left,
+            timeout_ms: None,
+        },
         Command::VectorUpsert {
             branch: None,
             space: None,
@@ -128,6 +160,7 @@ fn test_read_only_blocks_all_writes() {
             key: "k".into(),
             vector: vec![1.0],
             metadata: None,
+            namespace: None,
         },
         Command::VectorDelete {
             branch: None,
@@ -141,6 +174,7 @@ fn test_read_only_blocks_all_writes() {
             collection: "c".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         },
         Command::VectorDeleteCollection {
             branch: None,
@@ -201,6 +235,12 @@ fn test_read_only_allows_all_reads() {
             key: "k".into(),
             as_of: None,
         },
+        Command::KvHistory {
+            branch: None,
+            space: None,
+            key: "k".into(),
+            limit: None,
+        },
         Command::JsonGet {
             branch: None,
             space: None,
@@ -251,6 +291,7 @@ fn test_read_only_allows_all_reads() {
             space: None,
             cell: "c".into(),
             as_of: None,
+            limit: None,
         },
         Command::StateList {
             branch: None,
@@ -284,6 +325,28 @@ fn test_read_only_allows_all_reads() {
             k: None,
             primitives: None,
         },
+        Command::TextSearch {
+            branch: None,
+            space: None,
+            query: "test".into(),
+            k: None,
+        },
+        Command::SemanticSearch {
+            branch: None,
+            space: None,
+            query: "test".into(),
+            k: None,
+            rerank_top_n: None,
+        },
+        Command::HybridSearch {
+            branch: None,
+            space: None,
+            collection: "c".into(),
+            query: "test".into(),
+            embedding: vec![1.0],
+            k: None,
+            fusion: None,
+        },
     ];
 
     for cmd in read_commands {
@@ -317,6 +380,106 @@ fn test_read_only_propagates_to_handle() {
     assert_eq!(ro_executor.access_mode(), AccessMode::ReadOnly);
 }
 
+#[test]
+fn test_read_only_open_while_writer_is_open() {
+    let dir = tempfile::tempdir().unwrap();
+    let writer = Strata::open(dir.path()).unwrap();
+    writer.kv_put("k", "v").unwrap();
+
+    let reader = Strata::open_with(
+        dir.path(),
+        OpenOptions::new().access_mode(AccessMode::ReadOnly),
+    )
+    .unwrap();
+    assert_eq!(reader.kv_get("k").unwrap(), Some(Value::String("v".into())));
+    assert!(matches!(
+        reader.kv_put("k", "w"),
+        Err(Error::AccessDenied { .. })
+    ));
+    writer.kv_put("k", "w").unwrap();
+}
+
+#[test]
+fn test_read_only_open_missing_path_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+    let result = Strata::open_with(
+        &missing,
+        OpenOptions::new().access_mode(AccessMode::ReadOnly),
+    );
+    assert!(result.is_err());
+    assert!(!missing.exists());
+}
+
+#[test]
+fn test_cancelled_recovery_fails_open() {
+    let dir = tempfile::tempdir().unwrap();
+    Strata::open(dir.path()).unwrap().kv_put("k", "v").unwrap();
+
+    let token = crate::CancellationToken::new();
+    token.cancel();
+    let result = Strata::open_with(dir.path(), OpenOptions::new().cancel_recovery(token));
+    assert!(matches!(result, Err(Error::Cancelled { .. })));
+
+    let reports = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = Arc::clone(&reports);
+    let db = Strata::open_with(
+        dir.path(),
+        OpenOptions::new().on_recovery_progress(move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }),
+    )
+    .unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::String("v".into())));
+    assert!(reports.load(std::sync::atomic::Ordering::Relaxed) > 0);
+}
+
+#[test]
+fn test_open_with_checkpoint_policy() {
+    use crate::CheckpointPolicy;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir().unwrap();
+    let result = Strata::open_with(
+        dir.path(),
+        OpenOptions::new().checkpoint_every(CheckpointPolicy::WalBytes(0)),
+    );
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    let db = Strata::open_with(
+        dir.path(),
+        OpenOptions::new().checkpoint_every(Duration::from_secs(60)),
+    )
+    .unwrap();
+    assert_eq!(
+        db.executor().primitives().db.scheduled_checkpoints(),
+        Some(CheckpointPolicy::Interval(Duration::from_secs(60)))
+    );
+}
+
+#[test]
+fn test_memory_budget_requires_cache_database() {
+    use crate::EvictionPolicy;
+
+    let dir = tempfile::tempdir().unwrap();
+    let result = Strata::open_with(dir.path(), OpenOptions::new().max_memory(1 << 20));
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    let db = Strata::cache_with(
+        OpenOptions::new()
+            .max_memory(64 * 1024)
+            .eviction_policy(EvictionPolicy::Lfu),
+    )
+    .unwrap();
+    for i in 0..200 {
+        db.kv_put(&format!("k{}", i), Value::Bytes(vec![0; 1024]))
+            .unwrap();
+    }
+    let stats = db.executor().primitives().db.memory_budget_stats();
+    assert_eq!(stats.max_bytes, 64 * 1024);
+    assert!(stats.evicted_keys > 0);
+}
+
 // =============================================================================
 // Session-level tests
 // =============================================================================
@@ -373,6 +536,18 @@ fn test_is_write_classification() {
             space: None,
             key: "".into(),
         },
+        Command::KvIncr {
+            branch: None,
+            space: None,
+            key: "".into(),
+            delta: Value::Null,
+        },
+        Command::KvSetHistoryPolicy {
+            branch: None,
+            space: None,
+            prefix: "".into(),
+            policy: RetentionPolicy::KeepAll,
+        },
         Command::JsonSet {
             branch: None,
             space: None,
@@ -416,6 +591,26 @@ fn test_is_write_classification() {
             space: None,
             cell: "".into(),
         },
+        Command::StateIncr {
+            branch: None,
+            space: None,
+            cell: "".into(),
+            delta: Value::Null,
+        },
+        Command::ListPush {
+            branch: None,
+            space: None,
+            list: "".into(),
+            end: ListEnd::Left,
+            values: vec![],
+        },
+        Command::ListPop {
+            branch: None,
+            space: None,
+            list: "".into(),
+            end: ListEnd::Right,
+            timeout_ms: Some(0),
+        },
         Command::VectorUpsert {
             branch: None,
             space: None,
@@ -423,6 +618,7 @@ fn test_is_write_classification() {
             key: "".into(),
             vector: vec![],
             metadata: None,
+            namespace: None,
         },
         Command::VectorDelete {
             branch: None,
@@ -436,6 +632,7 @@ fn test_is_write_classification() {
             collection: "".into(),
             dimension: 0,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         },
         Command::VectorDeleteCollection {
             branch: None,
@@ -462,7 +659,15 @@ fn test_is_write_classification() {
             branch_id: "".into(),
             path: "".into(),
         },
-        Command::BranchImport { path: "".into() },
+        Command::BranchImport {
+            path: "".into(),
+            as_branch: None,
+        },
+        Command::DatabaseExport { path: "".into() },
+        Command::DatabaseImport {
+            path: "".into(),
+            on_conflict: ImportConflict::Skip,
+        },
     ];
 
     for cmd in &writes {
@@ -495,6 +700,12 @@ fn test_is_write_classification() {
             key: "".into(),
             as_of: None,
         },
+        Command::KvHistory {
+            branch: None,
+            space: None,
+            key: "".into(),
+            limit: Some(1),
+        },
         Command::JsonGet {
             branch: None,
             space: None,
@@ -545,6 +756,13 @@ fn test_is_write_classification() {
             space: None,
             cell: "".into(),
             as_of: None,
+            limit: None,
+        },
+        Command::StateGetVersion {
+            branch: None,
+            space: None,
+            cell: "".into(),
+            version: 1,
         },
         Command::StateList {
             branch: None,
@@ -552,6 +770,18 @@ fn test_is_write_classification() {
             prefix: None,
             as_of: None,
         },
+        Command::ListRange {
+            branch: None,
+            space: None,
+            list: "".into(),
+            start: 0,
+            stop: -1,
+        },
+        Command::ListLen {
+            branch: None,
+            space: None,
+            list: "".into(),
+        },
         Command::VectorGet {
             branch: None,
             space: None,
@@ -568,6 +798,7 @@ fn test_is_write_classification() {
             filter: None,
             metric: None,
             as_of: None,
+            namespace: None,
         },
         Command::VectorListCollections {
             branch: None,
@@ -598,6 +829,28 @@ fn test_is_write_classification() {
             k: None,
             primitives: None,
         },
+        Command::TextSearch {
+            branch: None,
+            space: None,
+            query: "".into(),
+            k: None,
+        },
+        Command::SemanticSearch {
+            branch: None,
+            space: None,
+            query: "".into(),
+            k: None,
+            rerank_top_n: None,
+        },
+        Command::HybridSearch {
+            branch: None,
+            space: None,
+            collection: "c".into(),
+            query: "".into(),
+            embedding: vec![1.0],
+            k: None,
+            fusion: None,
+        },
     ];
 
     for cmd in &reads {
@@ -0,0 +1,236 @@
+//! Tests for server-side result cursors.
+
+use std::time::Duration;
+
+use crate::types::*;
+use crate::Value;
+use crate::{Command, Error, Executor, Output, Session};
+use strata_engine::Database;
+
+fn create_test_executor() -> Executor {
+    Executor::new(Database::cache().unwrap())
+}
+
+fn put_keys(executor: &Executor, n: usize) {
+    for i in 0..n {
+        executor
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: format!("k{:02}", i),
+                value: Value::Int(i as i64),
+            })
+            .unwrap();
+    }
+}
+
+fn kv_list() -> Command {
+    Command::KvList {
+        branch: None,
+        space: None,
+        prefix: None,
+        cursor: None,
+        limit: None,
+        as_of: None,
+    }
+}
+
+fn open(executor: &Executor, page_size: u64, ttl_secs: Option<u64>) -> Output {
+    executor
+        .execute(Command::CursorOpen {
+            query: Box::new(kv_list()),
+            page_size: Some(page_size),
+            ttl_secs,
+        })
+        .unwrap()
+}
+
+fn unpack(output: Output) -> (Vec<String>, Option<String>, u64) {
+    match output {
+        Output::CursorPage {
+            page,
+            cursor,
+            remaining,
+        } => match *page {
+            Output::Keys(keys) => (keys, cursor, remaining),
+            other => panic!("expected Keys page, got {:?}", other),
+        },
+        other => panic!("expected CursorPage, got {:?}", other),
+    }
+}
+
+fn cursor_stats(executor: &Executor) -> CursorStats {
+    match executor.execute(Command::Stats).unwrap() {
+        Output::Stats(stats) => stats.cursors,
+        other => panic!("expected Stats, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cursor_pages_through_result() {
+    let executor = create_test_executor();
+    put_keys(&executor, 10);
+
+    let (mut keys, mut cursor, remaining) = unpack(open(&executor, 3, None));
+    assert_eq!(keys.len(), 3);
+    assert_eq!(remaining, 7);
+    assert_eq!(cursor_stats(&executor).buffered_rows, 7);
+
+    let mut pages = 1;
+    while let Some(id) = cursor {
+        let (page, next, _) = unpack(
+            executor
+                .execute(Command::CursorFetch { cursor: id })
+                .unwrap(),
+        );
+        keys.extend(page);
+        cursor = next;
+        pages += 1;
+    }
+    assert_eq!(pages, 4);
+
+    match executor.execute(kv_list()).unwrap() {
+        Output::Keys(all) => assert_eq!(keys, all),
+        other => panic!("expected Keys, got {:?}", other),
+    }
+
+    // Fetching the last page closes the cursor
+    let stats = cursor_stats(&executor);
+    assert_eq!(
+        stats,
+        CursorStats {
+            open: 0,
+            opened: 1,
+            closed: 1,
+            expired: 0,
+            buffered_rows: 0,
+        }
+    );
+}
+
+#[test]
+fn test_cursor_single_page_keeps_no_state() {
+    let executor = create_test_executor();
+    put_keys(&executor, 2);
+
+    let (keys, cursor, remaining) = unpack(open(&executor, 10, None));
+    assert_eq!(keys.len(), 2);
+    assert_eq!(cursor, None);
+    assert_eq!(remaining, 0);
+    assert_eq!(cursor_stats(&executor), CursorStats::default());
+}
+
+#[test]
+fn test_cursor_close() {
+    let executor = create_test_executor();
+    put_keys(&executor, 5);
+
+    let (_, cursor, _) = unpack(open(&executor, 2, None));
+    let id = cursor.unwrap();
+    assert_eq!(cursor_stats(&executor).open, 1);
+
+    let close = Command::CursorClose { cursor: id.clone() };
+    assert_eq!(executor.execute(close.clone()).unwrap(), Output::Bool(true));
+    assert_eq!(executor.execute(close).unwrap(), Output::Bool(false));
+
+    let result = executor.execute(Command::CursorFetch { cursor: id.clone() });
+    assert_eq!(result, Err(Error::CursorNotFound { cursor: id }));
+
+    let stats = cursor_stats(&executor);
+    assert_eq!((stats.open, stats.closed), (0, 1));
+}
+
+#[test]
+fn test_cursor_expires_after_ttl() {
+    let executor = create_test_executor();
+    put_keys(&executor, 5);
+
+    let (_, cursor, _) = unpack(open(&executor, 2, Some(1)));
+    let id = cursor.unwrap();
+    std::thread::sleep(Duration::from_millis(1100));
+
+    let result = executor.execute(Command::CursorFetch { cursor: id.clone() });
+    assert_eq!(result, Err(Error::CursorNotFound { cursor: id }));
+
+    let stats = cursor_stats(&executor);
+    assert_eq!((stats.open, stats.expired, stats.closed), (0, 1, 0));
+}
+
+#[test]
+fn test_cursor_shared_across_executors() {
+    let db = Database::cache().unwrap();
+    let first = Executor::new(db.clone());
+    let second = Executor::new(db);
+    put_keys(&first, 4);
+
+    let (_, cursor, _) = unpack(open(&first, 2, None));
+    let (page, cursor, remaining) = unpack(
+        second
+            .execute(Command::CursorFetch {
+                cursor: cursor.unwrap(),
+            })
+            .unwrap(),
+    );
+    assert_eq!(page, vec!["k02".to_string(), "k03".to_string()]);
+    assert_eq!((cursor, remaining), (None, 0));
+}
+
+#[test]
+fn test_cursor_rejects_invalid_queries() {
+    let executor = create_test_executor();
+
+    let write = executor.execute(Command::CursorOpen {
+        query: Box::new(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "k".into(),
+            value: Value::Int(1),
+        }),
+        page_size: None,
+        ttl_secs: None,
+    });
+    assert!(matches!(write, Err(Error::InvalidInput { .. })));
+
+    let not_a_list = executor.execute(Command::CursorOpen {
+        query: Box::new(Command::KvGet {
+            branch: None,
+            space: None,
+            key: "k".into(),
+            as_of: None,
+        }),
+        page_size: None,
+        ttl_secs: None,
+    });
+    assert!(matches!(not_a_list, Err(Error::InvalidInput { .. })));
+
+    let zero_page = executor.execute(Command::CursorOpen {
+        query: Box::new(kv_list()),
+        page_size: Some(0),
+        ttl_secs: None,
+    });
+    assert!(matches!(zero_page, Err(Error::InvalidInput { .. })));
+}
+
+#[test]
+fn test_cursor_open_rejected_in_transaction() {
+    let mut session = Session::new(Database::cache().unwrap());
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    let result = session.execute(Command::CursorOpen {
+        query: Box::new(kv_list()),
+        page_size: None,
+        ttl_secs: None,
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // Stats still work inside a transaction
+    assert!(matches!(
+        session.execute(Command::Stats),
+        Ok(Output::Stats(_))
+    ));
+}
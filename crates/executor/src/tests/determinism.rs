@@ -294,6 +294,7 @@ fn test_vector_search_determinism() {
             collection: "embeddings".to_string(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -309,6 +310,7 @@ fn test_vector_search_determinism() {
                 key: format!("v{}", i),
                 vector: vec,
                 metadata: None,
+                namespace: None,
             })
             .unwrap();
     }
@@ -326,6 +328,7 @@ fn test_vector_search_determinism() {
                 filter: None,
                 metric: None,
                 as_of: None,
+                namespace: None,
             })
         })
         .collect();
@@ -442,3 +445,204 @@ fn test_json_get_determinism() {
         }
     }
 }
+
+// =============================================================================
+// Listing Order Tests
+// =============================================================================
+
+fn sorted(mut items: Vec<String>) -> Vec<String> {
+    items.sort();
+    items
+}
+
+#[test]
+fn test_kv_list_is_lexicographic() {
+    let executor = create_test_executor();
+    let keys = ["user:b", "user:10", "user:a", "user:2", "user:1"];
+    for key in keys {
+        executor
+            .execute(Command::KvPut {
+                branch: Some(BranchId::from("default")),
+                space: None,
+                key: key.to_string(),
+                value: Value::Int(1),
+            })
+            .unwrap();
+    }
+    let expected = sorted(keys.iter().map(|k| k.to_string()).collect());
+
+    let list = |cursor: Option<String>, limit: Option<u64>| match executor
+        .execute(Command::KvList {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            prefix: Some("user:".to_string()),
+            cursor,
+            limit,
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::Keys(keys) => keys,
+        other => panic!("Expected Keys output, got {:?}", other),
+    };
+
+    assert_eq!(list(None, None), expected);
+
+    // Paging with the last key as cursor visits every key once, in order
+    let mut paged = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = list(cursor, Some(2));
+        if page.is_empty() {
+            break;
+        }
+        cursor = page.last().cloned();
+        paged.extend(page);
+    }
+    assert_eq!(paged, expected);
+}
+
+#[test]
+fn test_state_list_is_lexicographic() {
+    let executor = create_test_executor();
+    let cells = ["zeta", "alpha", "mid", "Alpha"];
+    for cell in cells {
+        executor
+            .execute(Command::StateSet {
+                branch: Some(BranchId::from("default")),
+                space: None,
+                cell: cell.to_string(),
+                value: Value::Int(1),
+            })
+            .unwrap();
+    }
+
+    let listed = executor
+        .execute(Command::StateList {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            prefix: None,
+            as_of: None,
+        })
+        .unwrap();
+    assert_eq!(
+        listed,
+        Output::Keys(sorted(cells.iter().map(|c| c.to_string()).collect()))
+    );
+}
+
+#[test]
+fn test_json_list_pages_past_deleted_cursor() {
+    let executor = create_test_executor();
+    for id in ["doc4", "doc2", "doc0", "doc3", "doc1"] {
+        executor
+            .execute(Command::JsonSet {
+                branch: Some(BranchId::from("default")),
+                space: None,
+                key: id.to_string(),
+                path: "$".to_string(),
+                value: Value::Int(1),
+            })
+            .unwrap();
+    }
+
+    let list = |cursor: Option<String>| match executor
+        .execute(Command::JsonList {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            prefix: Some("doc".to_string()),
+            cursor,
+            limit: 2,
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::JsonListResult { keys, cursor } => (keys, cursor),
+        other => panic!("Expected JsonListResult output, got {:?}", other),
+    };
+
+    let (first, cursor) = list(None);
+    assert_eq!(first, vec!["doc0", "doc1"]);
+    assert_eq!(cursor.as_deref(), Some("doc1"));
+
+    // Deleting the cursor document must not restart or end the listing
+    executor
+        .execute(Command::JsonDelete {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            key: "doc1".to_string(),
+            path: "$".to_string(),
+        })
+        .unwrap();
+
+    let (second, cursor) = list(cursor);
+    assert_eq!(second, vec!["doc2", "doc3"]);
+    let (third, cursor) = list(cursor);
+    assert_eq!(third, vec!["doc4"]);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn test_space_list_is_lexicographic() {
+    let executor = create_test_executor();
+    for space in ["zeta", "alpha", "events"] {
+        executor
+            .execute(Command::SpaceCreate {
+                branch: Some(BranchId::from("default")),
+                space: space.to_string(),
+            })
+            .unwrap();
+    }
+
+    let listed = executor
+        .execute(Command::SpaceList {
+            branch: Some(BranchId::from("default")),
+        })
+        .unwrap();
+    assert_eq!(
+        listed,
+        Output::SpaceList(vec![
+            "alpha".to_string(),
+            "default".to_string(),
+            "events".to_string(),
+            "zeta".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn test_branch_list_offset_pages_in_order() {
+    let executor = create_test_executor();
+    for name in ["zeta", "alpha", "mid", "beta"] {
+        executor
+            .execute(Command::BranchCreate {
+                branch_id: Some(name.to_string()),
+                metadata: None,
+            })
+            .unwrap();
+    }
+
+    let list = |limit: Option<u64>, offset: Option<u64>| match executor
+        .execute(Command::BranchList {
+            state: None,
+            limit,
+            offset,
+        })
+        .unwrap()
+    {
+        Output::BranchInfoList(branches) => branches
+            .into_iter()
+            .map(|b| b.info.id.0)
+            .collect::<Vec<_>>(),
+        other => panic!("Expected BranchInfoList output, got {:?}", other),
+    };
+
+    let all = list(None, None);
+    assert_eq!(all, sorted(all.clone()));
+
+    let paged: Vec<String> = (0..all.len() as u64)
+        .step_by(2)
+        .flat_map(|offset| list(Some(2), Some(offset)))
+        .collect();
+    assert_eq!(paged, all);
+}
@@ -0,0 +1,112 @@
+//! Tests for the background auto-embed queue.
+
+use std::sync::{mpsc, Arc, Mutex};
+
+use crate::embed_queue::{EmbedQueue, TextJob, MAX_TEXT_BATCH};
+
+#[test]
+fn test_jobs_run_in_order_before_wait_idle_returns() {
+    let queue = Arc::new(EmbedQueue::default());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    for i in 0..100 {
+        let seen = seen.clone();
+        queue.enqueue(Box::new(move || seen.lock().unwrap().push(i)));
+    }
+    queue.wait_idle();
+
+    assert_eq!(*seen.lock().unwrap(), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_wait_idle_on_empty_queue_returns() {
+    EmbedQueue::default().wait_idle();
+}
+
+#[test]
+fn test_panicking_job_does_not_stop_the_queue() {
+    let queue = Arc::new(EmbedQueue::default());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    queue.enqueue(Box::new(|| panic!("model exploded")));
+    let s = seen.clone();
+    queue.enqueue(Box::new(move || s.lock().unwrap().push("after")));
+    queue.wait_idle();
+
+    assert_eq!(*seen.lock().unwrap(), vec!["after"]);
+
+    // The worker restarts for jobs queued after it went idle.
+    let s = seen.clone();
+    queue.enqueue(Box::new(move || s.lock().unwrap().push("restart")));
+    queue.wait_idle();
+    assert_eq!(*seen.lock().unwrap(), vec!["after", "restart"]);
+}
+
+/// A text job that records its result under `label`.
+fn text_job(
+    label: &'static str,
+    calls: &Arc<Mutex<Vec<usize>>>,
+    seen: &Arc<Mutex<Vec<String>>>,
+) -> TextJob {
+    let calls = calls.clone();
+    let seen = seen.clone();
+    TextJob {
+        text: label.to_string(),
+        embed: Box::new(move |texts| {
+            calls.lock().unwrap().push(texts.len());
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }),
+        finish: Box::new(move |embedding| {
+            seen.lock()
+                .unwrap()
+                .push(format!("{}={:?}", label, embedding.unwrap()));
+        }),
+    }
+}
+
+#[test]
+fn test_queued_texts_are_embedded_in_batches() {
+    let queue = Arc::new(EmbedQueue::default());
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    // Hold the worker so the texts pile up behind it
+    let (release, blocked) = mpsc::channel::<()>();
+    queue.enqueue(Box::new(move || blocked.recv().unwrap()));
+    for _ in 0..MAX_TEXT_BATCH + 8 {
+        queue.enqueue_text(text_job("ab", &calls, &seen));
+    }
+    let s = seen.clone();
+    queue.enqueue(Box::new(move || s.lock().unwrap().push("remove".into())));
+    queue.enqueue_text(text_job("abc", &calls, &seen));
+    release.send(()).unwrap();
+    queue.wait_idle();
+
+    // A non-text job splits batches, and everything runs in order
+    assert_eq!(*calls.lock().unwrap(), vec![MAX_TEXT_BATCH, 8, 1]);
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), MAX_TEXT_BATCH + 10);
+    assert!(seen[..MAX_TEXT_BATCH + 8].iter().all(|s| s == "ab=[2.0]"));
+    assert_eq!(seen[MAX_TEXT_BATCH + 8..], ["remove", "abc=[3.0]"]);
+}
+
+#[test]
+fn test_failed_batch_reports_error_to_every_job() {
+    let queue = Arc::new(EmbedQueue::default());
+    let errors = Arc::new(Mutex::new(Vec::new()));
+
+    let (release, blocked) = mpsc::channel::<()>();
+    queue.enqueue(Box::new(move || blocked.recv().unwrap()));
+    for _ in 0..3 {
+        let errors = errors.clone();
+        queue.enqueue_text(TextJob {
+            text: "text".into(),
+            embed: Box::new(|_| Err("backend down".into())),
+            finish: Box::new(move |embedding| errors.lock().unwrap().push(embedding.unwrap_err())),
+        });
+    }
+    release.send(()).unwrap();
+    queue.wait_idle();
+
+    assert_eq!(*errors.lock().unwrap(), vec!["backend down"; 3]);
+}
@@ -0,0 +1,127 @@
+//! Tests for per-command latency tracking.
+
+use std::sync::Arc;
+
+use crate::types::*;
+use crate::Value;
+use crate::{Command, Executor, Output, Session};
+use strata_engine::Database;
+
+fn kv_put(key: &str) -> Command {
+    Command::KvPut {
+        branch: None,
+        space: None,
+        key: key.to_string(),
+        value: Value::Int(1),
+    }
+}
+
+fn kv_get(key: &str) -> Command {
+    Command::KvGet {
+        branch: None,
+        space: None,
+        key: key.to_string(),
+        as_of: None,
+    }
+}
+
+fn report(executor: &Executor) -> LatencyReport {
+    match executor.execute(Command::LatencyReport).unwrap() {
+        Output::LatencyReport(r) => r,
+        other => panic!("expected LatencyReport, got {:?}", other),
+    }
+}
+
+fn entry<'a>(report: &'a LatencyReport, command: &str) -> &'a CommandLatency {
+    report
+        .commands
+        .iter()
+        .find(|c| c.command == command)
+        .unwrap_or_else(|| panic!("no latency entry for {}", command))
+}
+
+#[test]
+fn test_latency_report_counts_each_command() {
+    let executor = Executor::new(Database::cache().unwrap());
+    for i in 0..5 {
+        executor.execute(kv_put(&format!("k{}", i))).unwrap();
+    }
+    executor.execute(kv_get("k0")).unwrap();
+
+    let r = report(&executor);
+    let names: Vec<&str> = r.commands.iter().map(|c| c.command.as_str()).collect();
+    assert_eq!(names, vec!["KvGet", "KvPut"]);
+
+    let put = entry(&r, "KvPut");
+    assert_eq!(put.count, 5);
+    assert!(put.p50_ns > 0);
+    assert!(put.p50_ns <= put.p95_ns);
+    assert!(put.p95_ns <= put.p99_ns);
+    assert!(put.p99_ns <= put.max_ns);
+    assert_eq!(entry(&r, "KvGet").count, 1);
+}
+
+#[test]
+fn test_latency_report_does_not_record_itself() {
+    let executor = Executor::new(Database::cache().unwrap());
+    assert!(report(&executor).commands.is_empty());
+    assert!(report(&executor).commands.is_empty());
+    executor.execute(Command::LatencyReset).unwrap();
+    assert!(report(&executor).commands.is_empty());
+}
+
+#[test]
+fn test_latency_records_failed_commands() {
+    let executor = Executor::new(Database::cache().unwrap());
+    let result = executor.execute(Command::CursorFetch {
+        cursor: "missing".to_string(),
+    });
+    assert!(result.is_err());
+    assert_eq!(entry(&report(&executor), "CursorFetch").count, 1);
+}
+
+#[test]
+fn test_latency_reset_clears_report() {
+    let executor = Executor::new(Database::cache().unwrap());
+    executor.execute(kv_put("k")).unwrap();
+    assert!(matches!(
+        executor.execute(Command::LatencyReset),
+        Ok(Output::Unit)
+    ));
+    assert!(report(&executor).commands.is_empty());
+
+    executor.execute(kv_get("k")).unwrap();
+    let r = report(&executor);
+    assert_eq!(r.commands.len(), 1);
+    assert_eq!(entry(&r, "KvGet").count, 1);
+}
+
+#[test]
+fn test_latency_shared_across_executors() {
+    let db: Arc<Database> = Database::cache().unwrap();
+    let a = Executor::new(db.clone());
+    let b = Executor::new(db);
+    a.execute(kv_put("k")).unwrap();
+    b.execute(kv_put("k")).unwrap();
+    assert_eq!(entry(&report(&a), "KvPut").count, 2);
+}
+
+#[test]
+fn test_latency_report_inside_transaction() {
+    let mut session = Session::new(Database::cache().unwrap());
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    assert!(matches!(
+        session.execute(Command::LatencyReport),
+        Ok(Output::LatencyReport(_))
+    ));
+    assert!(matches!(
+        session.execute(Command::LatencyReset),
+        Ok(Output::Unit)
+    ));
+}
@@ -0,0 +1,265 @@
+//! Tests for the multi-database manager.
+
+use crate::types::*;
+use crate::Value;
+use crate::{AccessMode, Command, Error, OpenOptions, ResourceLimits, StrataManager, WorkerPool};
+
+fn manager(capacity: usize) -> (tempfile::TempDir, StrataManager) {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = StrataManager::open(dir.path().join("tenants"), capacity).unwrap();
+    (dir, manager)
+}
+
+#[test]
+fn test_manager_opens_lazily_and_isolates_databases() {
+    let (_dir, manager) = manager(4);
+    assert!(manager.list().unwrap().is_empty());
+    assert!(manager.open_databases().is_empty());
+
+    let acme = manager.get("acme").unwrap();
+    let globex = manager.get("globex").unwrap();
+    acme.kv_put("plan", "enterprise").unwrap();
+    assert_eq!(globex.kv_get("plan").unwrap(), None);
+
+    // A second handle to the same name shares the database
+    let again = manager.get("acme").unwrap();
+    assert_eq!(
+        again.kv_get("plan").unwrap(),
+        Some(Value::String("enterprise".into()))
+    );
+
+    assert_eq!(manager.list().unwrap(), vec!["acme", "globex"]);
+    assert_eq!(manager.open_databases(), vec!["acme", "globex"]);
+    assert!(manager.root().join("acme").is_dir());
+}
+
+#[test]
+fn test_manager_evicts_least_recently_used() {
+    let (_dir, manager) = manager(2);
+
+    let a = manager.get("a").unwrap();
+    a.kv_put("k", 1i64).unwrap();
+    manager.get("b").unwrap();
+    manager.get("a").unwrap(); // "b" is now least recently used
+    manager.get("c").unwrap();
+
+    assert_eq!(manager.open_databases(), vec!["c", "a"]);
+
+    let stats = manager.stats().unwrap();
+    assert_eq!((stats.open, stats.capacity), (2, 2));
+    assert_eq!((stats.opened, stats.hits, stats.evicted), (3, 1, 1));
+
+    // Evicting "a" leaves the outstanding handle usable
+    manager.get("d").unwrap();
+    assert!(!manager.open_databases().contains(&"a".to_string()));
+    assert_eq!(a.kv_get("k").unwrap(), Some(Value::Int(1)));
+}
+
+#[test]
+fn test_manager_reopens_after_close() {
+    let (_dir, manager) = manager(2);
+    {
+        let db = manager.get("acme").unwrap();
+        db.kv_put("k", "v").unwrap();
+    }
+
+    assert!(manager.close("acme"));
+    assert!(!manager.close("acme"));
+    assert!(manager.open_databases().is_empty());
+
+    let db = manager.get("acme").unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::String("v".into())));
+
+    manager.get("globex").unwrap();
+    manager.close_all();
+    let stats = manager.stats().unwrap();
+    assert_eq!((stats.open, stats.opened, stats.closed), (0, 3, 3));
+}
+
+#[test]
+fn test_manager_aggregates_database_stats() {
+    let (_dir, manager) = manager(4);
+    for name in ["a", "b"] {
+        let db = manager.get(name).unwrap();
+        for i in 0..3 {
+            db.kv_put(&format!("k{}", i), i as i64).unwrap();
+        }
+        db.executor()
+            .execute(Command::CursorOpen {
+                query: Box::new(Command::KvList {
+                    branch: None,
+                    space: None,
+                    prefix: None,
+                    cursor: None,
+                    limit: None,
+                    as_of: None,
+                }),
+                page_size: Some(1),
+                ttl_secs: None,
+            })
+            .unwrap();
+    }
+
+    let totals = manager.stats().unwrap().totals.cursors;
+    assert_eq!(
+        totals,
+        CursorStats {
+            open: 2,
+            opened: 2,
+            closed: 0,
+            expired: 0,
+            buffered_rows: 4,
+        }
+    );
+}
+
+#[test]
+fn test_manager_read_only_does_not_create() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("tenants");
+    {
+        let manager = StrataManager::open(&root, 2).unwrap();
+        manager.get("acme").unwrap().kv_put("k", 1i64).unwrap();
+    }
+
+    let read_only = StrataManager::open_with(
+        &root,
+        2,
+        OpenOptions::new().access_mode(AccessMode::ReadOnly),
+    )
+    .unwrap();
+    let db = read_only.get("acme").unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+    assert!(matches!(
+        db.kv_put("k", 2i64),
+        Err(Error::AccessDenied { .. })
+    ));
+
+    assert_eq!(
+        read_only.get("globex").err(),
+        Some(Error::DatabaseNotFound {
+            name: "globex".into()
+        })
+    );
+    assert!(!root.join("globex").exists());
+}
+
+#[test]
+fn test_manager_rejects_invalid_names_and_capacity() {
+    let (_dir, manager) = manager(2);
+    for name in ["", ".", "..", "../escape", "a/b", ".hidden", "a b"] {
+        assert!(
+            matches!(manager.get(name), Err(Error::InvalidInput { .. })),
+            "{:?}",
+            name
+        );
+    }
+    assert!(manager.get("tenant-1_v2.0").is_ok());
+
+    let dir = tempfile::tempdir().unwrap();
+    assert!(matches!(
+        StrataManager::open(dir.path(), 0),
+        Err(Error::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn test_manager_limits_evict_by_threads_and_files() {
+    // Each disk database holds a lock file and a WAL segment, and runs a
+    // WAL flush thread under standard durability
+    let (_dir, files) = manager(8);
+    let files = files.with_limits(ResourceLimits::new().max_open_files(4));
+    files.get("a").unwrap();
+    files.get("b").unwrap();
+    files.get("c").unwrap();
+    assert_eq!(files.open_databases(), vec!["c", "b"]);
+    let stats = files.stats().unwrap();
+    assert_eq!((stats.evicted, stats.usage.open_files), (1, 4));
+
+    let (_dir, threads) = manager(8);
+    let threads = threads.with_limits(ResourceLimits::new().max_background_threads(1));
+    threads.get("a").unwrap();
+    threads.get("b").unwrap();
+    assert_eq!(threads.open_databases(), vec!["b"]);
+    assert_eq!(threads.stats().unwrap().usage.background_threads, 1);
+}
+
+#[test]
+fn test_manager_limits_evict_by_memory() {
+    let (_dir, unlimited) = manager(8);
+    let manager = unlimited.with_limits(ResourceLimits::new().max_memory_bytes(64 * 1024));
+
+    manager
+        .get("big")
+        .unwrap()
+        .kv_put("blob", Value::Bytes(vec![0; 128 * 1024]))
+        .unwrap();
+    // A database over the cap on its own stays open
+    assert_eq!(manager.open_databases(), vec!["big"]);
+
+    manager.get("small").unwrap();
+    assert_eq!(manager.open_databases(), vec!["small"]);
+    let stats = manager.stats().unwrap();
+    assert_eq!(stats.evicted, 1);
+    assert!(stats.usage.memory_bytes < 64 * 1024);
+}
+
+#[test]
+fn test_manager_schedules_compaction_across_databases() {
+    let (_dir, manager) = manager(4);
+    for name in ["a", "b"] {
+        let path = manager.root().join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("strata.toml"), "compaction_interval_secs = 1\n").unwrap();
+        let db = manager.get(name).unwrap();
+        for i in 0..10 {
+            db.kv_put("k", i as i64).unwrap();
+        }
+    }
+
+    // One shared scheduler instead of a compaction thread per database
+    assert_eq!(manager.stats().unwrap().usage.background_threads, 3);
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while manager.stats().unwrap().compactions < 2 {
+        assert!(std::time::Instant::now() < deadline, "compaction never ran");
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    let db = manager.get("a").unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(9)));
+}
+
+#[test]
+fn test_manager_runs_background_jobs_on_worker_pool() {
+    let dir = tempfile::tempdir().unwrap();
+    let pool = WorkerPool::builder()
+        .threads(1)
+        .name_prefix("tenant-bg")
+        .build()
+        .unwrap();
+    let manager = StrataManager::open_with(
+        dir.path().join("tenants"),
+        4,
+        OpenOptions::new().worker_pool(pool.clone()),
+    )
+    .unwrap();
+    for name in ["a", "b"] {
+        let path = manager.root().join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(path.join("strata.toml"), "compaction_interval_secs = 1\n").unwrap();
+        manager.get(name).unwrap().kv_put("k", 1i64).unwrap();
+    }
+
+    // Two WAL flush jobs and the compaction scheduler, no threads of their own
+    assert_eq!(pool.jobs(), 3);
+    assert_eq!(manager.stats().unwrap().usage.background_threads, 0);
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while manager.stats().unwrap().compactions < 2 {
+        assert!(std::time::Instant::now() < deadline, "compaction never ran");
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    drop(manager);
+    assert_eq!(pool.jobs(), 0);
+}
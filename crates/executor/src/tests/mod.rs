@@ -1,10 +1,16 @@
 //! Test modules for the executor crate.
 
 pub mod access_mode;
+pub mod cursor;
 pub mod determinism;
+pub mod embed_queue;
 pub mod execute_many;
+pub mod latency;
+pub mod manager;
 pub mod parity;
+pub mod prepared;
 pub mod search;
 pub mod serialization;
 pub mod session;
 pub mod spaces;
+pub mod stats;
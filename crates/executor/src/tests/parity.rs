@@ -342,6 +342,7 @@ fn test_vector_create_collection_parity() {
         collection: "embeddings".to_string(),
         dimension: 4,
         metric: DistanceMetric::Cosine,
+        storage_dtype: None,
     });
 
     assert!(result.is_ok());
@@ -378,6 +379,7 @@ fn test_vector_upsert_search_parity() {
             key: "v1".to_string(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            namespace: None,
         })
         .unwrap();
 
@@ -403,6 +405,7 @@ fn test_vector_upsert_search_parity() {
         filter: None,
         metric: None,
         as_of: None,
+        namespace: None,
     });
 
     match search_result {
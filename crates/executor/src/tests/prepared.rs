@@ -0,0 +1,318 @@
+//! Tests for prepared commands and parameter binding.
+
+use std::collections::HashMap;
+
+use crate::types::*;
+use crate::Value;
+use crate::{AccessMode, Command, Error, Executor, Output, ParamRef, Params};
+
+/// Create a test executor with a cache in-memory database.
+fn create_test_executor() -> Executor {
+    use strata_engine::Database;
+
+    let db = Database::cache().unwrap();
+    Executor::new(db)
+}
+
+fn kv_get(key: &str) -> Command {
+    Command::KvGet {
+        branch: None,
+        space: None,
+        key: key.into(),
+        as_of: None,
+    }
+}
+
+#[test]
+fn test_prepared_positional_put_then_get() {
+    let executor = create_test_executor();
+    let put = executor
+        .prepare(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "$1".into(),
+            value: Value::String("$2".into()),
+        })
+        .unwrap();
+    assert_eq!(
+        put.params(),
+        &[ParamRef::Positional(1), ParamRef::Positional(2)]
+    );
+
+    for i in 0..10 {
+        let out = executor
+            .execute_prepared(
+                &put,
+                Params::Positional(vec![Value::String(format!("k{}", i)), Value::Int(i)]),
+            )
+            .unwrap();
+        assert!(matches!(out, Output::Version(_)));
+    }
+
+    let get = executor.prepare(kv_get("$1")).unwrap();
+    let prepared = executor
+        .execute_prepared(&get, Params::Positional(vec![Value::String("k7".into())]))
+        .unwrap();
+    assert_eq!(prepared, executor.execute(kv_get("k7")).unwrap());
+}
+
+#[test]
+fn test_prepared_named_params_reuse_placeholder() {
+    let executor = create_test_executor();
+    let stmt = executor
+        .prepare(Command::StateSet {
+            branch: None,
+            space: None,
+            cell: "$cell".into(),
+            value: Value::Array(vec![Value::String("$v".into()), Value::String("$v".into())]),
+        })
+        .unwrap();
+    assert_eq!(stmt.params().len(), 2);
+
+    let mut params = HashMap::new();
+    params.insert("cell".to_string(), Value::String("counter".into()));
+    params.insert("v".to_string(), Value::Int(3));
+    let cmd = stmt.bind(Params::Named(params)).unwrap();
+    assert_eq!(
+        cmd,
+        Command::StateSet {
+            branch: None,
+            space: None,
+            cell: "counter".into(),
+            value: Value::Array(vec![Value::Int(3), Value::Int(3)]),
+        }
+    );
+}
+
+#[test]
+fn test_prepared_escaped_dollar_is_literal() {
+    let executor = create_test_executor();
+    let stmt = executor.prepare(kv_get("$$price")).unwrap();
+    assert!(stmt.params().is_empty());
+    assert_eq!(stmt.bind(Params::none()).unwrap(), kv_get("$price"));
+}
+
+#[test]
+fn test_prepared_rejects_invalid_templates() {
+    let executor = create_test_executor();
+
+    // Mixed positional and named
+    let result = executor.prepare(Command::KvPut {
+        branch: None,
+        space: None,
+        key: "$1".into(),
+        value: Value::String("$value".into()),
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // Gap in positional numbering
+    let result = executor.prepare(Command::KvPut {
+        branch: None,
+        space: None,
+        key: "$2".into(),
+        value: Value::Int(1),
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // Literal keys are validated at prepare time
+    let result = executor.prepare(kv_get(""));
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+}
+
+#[test]
+fn test_prepared_rejects_bad_bindings() {
+    let executor = create_test_executor();
+    let stmt = executor.prepare(kv_get("$1")).unwrap();
+
+    // Wrong arity
+    let result = executor.execute_prepared(&stmt, Params::none());
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // Wrong kind of params
+    let mut named = HashMap::new();
+    named.insert("key".to_string(), Value::String("k".into()));
+    let result = executor.execute_prepared(&stmt, Params::Named(named));
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // String field bound to a non-string
+    let result = executor.execute_prepared(&stmt, Params::Positional(vec![Value::Int(1)]));
+    assert!(matches!(result, Err(Error::WrongType { .. })));
+}
+
+#[test]
+fn test_prepared_write_rejected_on_read_only() {
+    let db = strata_engine::Database::cache().unwrap();
+    let executor = Executor::new_with_mode(db, AccessMode::ReadOnly);
+
+    let result = executor.prepare(Command::KvDelete {
+        branch: None,
+        space: None,
+        key: "$1".into(),
+    });
+    assert!(matches!(result, Err(Error::AccessDenied { .. })));
+    assert!(executor.prepare(kv_get("$1")).is_ok());
+}
+
+#[test]
+fn test_prepared_params_serialization_round_trip() {
+    let params = Params::Positional(vec![Value::String("k".into()), Value::Int(1)]);
+    let json = serde_json::to_string(&params).unwrap();
+    let restored: Params = serde_json::from_str(&json).unwrap();
+    assert_eq!(params, restored);
+}
+
+#[test]
+fn test_prepared_with_explicit_branch() {
+    let executor = create_test_executor();
+    let stmt = executor
+        .prepare(Command::KvGet {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            key: "$1".into(),
+            as_of: None,
+        })
+        .unwrap();
+    let out = executor
+        .execute_prepared(
+            &stmt,
+            Params::Positional(vec![Value::String("missing".into())]),
+        )
+        .unwrap();
+    assert_eq!(out, executor.execute(kv_get("missing")).unwrap());
+}
+
+#[test]
+fn test_prepared_binds_nested_values_and_names() {
+    let executor = create_test_executor();
+    let stmt = executor
+        .prepare(Command::EventAppend {
+            branch: None,
+            space: Some("$space".into()),
+            event_type: "$type".into(),
+            payload: Value::Object(HashMap::from([
+                ("user".to_string(), Value::String("$user".into())),
+                ("source".to_string(), Value::String("web".into())),
+            ])),
+        })
+        .unwrap();
+
+    let params = HashMap::from([
+        ("space".to_string(), Value::String("audit".into())),
+        ("type".to_string(), Value::String("login".into())),
+        ("user".to_string(), Value::Int(7)),
+    ]);
+    let cmd = stmt.bind(Params::Named(params)).unwrap();
+    assert_eq!(
+        cmd,
+        Command::EventAppend {
+            branch: None,
+            space: Some("audit".into()),
+            event_type: "login".into(),
+            payload: Value::Object(HashMap::from([
+                ("user".to_string(), Value::Int(7)),
+                ("source".to_string(), Value::String("web".into())),
+            ])),
+        }
+    );
+}
+
+#[test]
+fn test_prepared_rejects_unsupported_positions_and_bad_keys() {
+    let executor = create_test_executor();
+
+    // Placeholders inside vector entries are not bound
+    let result = executor.prepare(Command::VectorBatchUpsert {
+        branch: None,
+        space: None,
+        collection: "docs".into(),
+        namespace: None,
+        entries: vec![BatchVectorEntry {
+            key: "$1".into(),
+            vector: vec![1.0],
+            metadata: None,
+        }],
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+
+    // Bound keys are validated like literal ones
+    let stmt = executor.prepare(kv_get("$1")).unwrap();
+    let result = stmt.bind(Params::Positional(vec![Value::String(String::new())]));
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+}
+
+#[test]
+fn test_prepared_keeps_non_finite_float_literals() {
+    let executor = create_test_executor();
+    let stmt = executor
+        .prepare(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "$1".into(),
+            value: Value::Array(vec![
+                Value::Float(f64::NAN),
+                Value::Float(f64::INFINITY),
+                Value::Float(f64::NEG_INFINITY),
+                Value::String("$$usd".into()),
+            ]),
+        })
+        .unwrap();
+
+    let Command::KvPut { key, value, .. } = stmt
+        .bind(Params::Positional(vec![Value::String("limits".into())]))
+        .unwrap()
+    else {
+        panic!("expected KvPut");
+    };
+    assert_eq!(key, "limits");
+    let Value::Array(items) = value else {
+        panic!("expected an array");
+    };
+    assert!(items[0].as_float().unwrap().is_nan());
+    assert_eq!(items[1], Value::Float(f64::INFINITY));
+    assert_eq!(items[2], Value::Float(f64::NEG_INFINITY));
+    assert_eq!(items[3], Value::String("$usd".into()));
+}
+
+#[test]
+fn test_prepared_checks_kv_key_rules_only_for_kv_and_json() {
+    let executor = create_test_executor();
+    executor
+        .execute(Command::BranchCreate {
+            branch_id: Some("run".into()),
+            metadata: None,
+        })
+        .unwrap();
+    let set_metadata = |key: &str| Command::BranchSetMetadata {
+        branch: BranchId::from("run"),
+        key: key.into(),
+        value: Value::String("$1".into()),
+    };
+
+    // Valid as a branch metadata key, though reserved as a KV key
+    let direct = executor.execute(set_metadata("__idx_owner"));
+    assert_eq!(direct.unwrap(), Output::Unit);
+
+    let literal = executor.prepare(set_metadata("__idx_owner")).unwrap();
+    let out = executor.execute_prepared(&literal, Params::Positional(vec![Value::Int(1)]));
+    assert_eq!(out.unwrap(), Output::Unit);
+
+    let stmt = executor
+        .prepare(Command::BranchSetMetadata {
+            branch: BranchId::from("run"),
+            key: "$1".into(),
+            value: Value::String("$2".into()),
+        })
+        .unwrap();
+    let out = executor.execute_prepared(
+        &stmt,
+        Params::Positional(vec![Value::String("__idx_owner".into()), Value::Int(2)]),
+    );
+    assert_eq!(out.unwrap(), Output::Unit);
+
+    // The same key is still refused for KV
+    let stmt = executor.prepare(kv_get("$1")).unwrap();
+    let result = stmt.bind(Params::Positional(vec![Value::String(
+        "__idx_owner".into(),
+    )]));
+    assert!(result.is_err());
+}
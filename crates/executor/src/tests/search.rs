@@ -6,7 +6,7 @@
 //! even when primitives return empty results.
 
 use crate::Value;
-use crate::{Command, Executor, Output};
+use crate::{Command, DistanceMetric, Error, Executor, FusionMethod, Output};
 use strata_engine::Database;
 
 fn create_executor() -> Executor {
@@ -134,3 +134,318 @@ fn test_search_command_infrastructure_works() {
         other => panic!("Expected SearchResults output type, got {:?}", other),
     }
 }
+
+#[test]
+fn test_text_search_finds_kv_values() {
+    let executor = create_executor();
+
+    for (key, value) in [("greeting", "hello world"), ("farewell", "goodbye world")] {
+        executor
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: key.to_string(),
+                value: Value::String(value.into()),
+            })
+            .unwrap();
+    }
+
+    let result = executor.execute(Command::TextSearch {
+        branch: None,
+        space: None,
+        query: "hello".to_string(),
+        k: None,
+    });
+
+    match result {
+        Ok(Output::TextSearchResults(hits)) => {
+            assert_eq!(hits.len(), 1);
+            assert_eq!(hits[0].entity, "greeting");
+            assert_eq!(hits[0].primitive, "kv");
+            assert_eq!(hits[0].snippet, "hello world");
+            assert_eq!(hits[0].highlights, vec![(0, 5)]);
+        }
+        other => panic!("Expected TextSearchResults, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_hybrid_search_rejects_out_of_range_weight() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "chunks".to_string(),
+            dimension: 2,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: None,
+        })
+        .unwrap();
+
+    let hybrid = |fusion| {
+        executor.execute(Command::HybridSearch {
+            branch: None,
+            space: None,
+            collection: "chunks".to_string(),
+            query: "hello".to_string(),
+            embedding: vec![1.0, 0.0],
+            k: None,
+            fusion: Some(fusion),
+        })
+    };
+
+    assert!(matches!(
+        hybrid(FusionMethod::Weighted { vector_weight: 1.5 }),
+        Err(Error::InvalidInput { .. })
+    ));
+    match hybrid(FusionMethod::Rrf) {
+        Ok(Output::SearchResults(hits)) => assert!(hits.is_empty()),
+        other => panic!("Expected SearchResults, got {:?}", other),
+    }
+}
+
+#[cfg(not(feature = "embed"))]
+#[test]
+fn test_semantic_search_requires_embed_feature() {
+    let executor = create_executor();
+
+    let result = executor.execute(Command::SemanticSearch {
+        branch: None,
+        space: None,
+        query: "disk failure".to_string(),
+        k: None,
+        rerank_top_n: None,
+    });
+
+    assert!(matches!(result, Err(Error::NotImplemented { .. })));
+}
+
+/// Serve JSON POST requests at `path` until the listener is dropped,
+/// answering each request body with `respond(body)`.
+#[cfg(feature = "embed")]
+fn spawn_json_server(path: &str, respond: fn(serde_json::Value) -> serde_json::Value) -> String {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}{}", listener.local_addr().unwrap(), path);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { return };
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                    break;
+                }
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            if reader.read_exact(&mut body).is_err() {
+                continue;
+            }
+            let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            let body = respond(request).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = reader.get_mut().write_all(response.as_bytes());
+        }
+    });
+    url
+}
+
+/// Serve OpenAI-style embedding requests until the listener is dropped.
+/// Texts mentioning "disk" embed to `[1, 0]`, everything else to `[0, 1]`.
+#[cfg(feature = "embed")]
+fn spawn_embedding_server() -> String {
+    spawn_json_server("/v1/embeddings", |request| {
+        // One vector per input, whether `input` is a string or a batch
+        let inputs = match &request["input"] {
+            serde_json::Value::Array(inputs) => inputs.clone(),
+            input => vec![input.clone()],
+        };
+        let data: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| {
+                let embedding = if input.as_str().unwrap().contains("disk") {
+                    [1.0, 0.0]
+                } else {
+                    [0.0, 1.0]
+                };
+                serde_json::json!({ "index": index, "embedding": embedding })
+            })
+            .collect();
+        serde_json::json!({ "data": data })
+    })
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn test_semantic_search_with_http_provider() {
+    use crate::{EmbedProvider, OpenOptions, Strata};
+
+    let dir = tempfile::tempdir().unwrap();
+    let opts = OpenOptions::new()
+        .auto_embed(true)
+        .embed_provider(EmbedProvider::Http {
+            url: spawn_embedding_server(),
+            model: "test".into(),
+            api_key: None,
+        });
+    let db = Strata::open_with(dir.path(), opts).unwrap();
+
+    db.kv_put("incident:42", "disk failure on node 7").unwrap();
+    db.kv_put("note:1", "lunch at noon").unwrap();
+
+    let hits = db.search().semantic("disk trouble", 1).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].entity, "incident:42");
+    assert_eq!(hits[0].primitive, "kv");
+    assert_eq!(hits[0].rank, 1);
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn test_semantic_search_returns_matching_chunk() {
+    use crate::{EmbedProvider, OpenOptions, Strata};
+
+    let dir = tempfile::tempdir().unwrap();
+    let opts = OpenOptions::new()
+        .auto_embed(true)
+        .embed_provider(EmbedProvider::Http {
+            url: spawn_embedding_server(),
+            model: "test".into(),
+            api_key: None,
+        });
+    let db = Strata::open_with(dir.path(), opts).unwrap();
+
+    // Long enough to be split: only the last paragraph is about disks
+    let report = format!(
+        "{}\n\ndisk failure on node 7",
+        "lunch at noon. ".repeat(100)
+    );
+    db.kv_put("report", report.as_str()).unwrap();
+
+    let hits = db.search().semantic("disk trouble", 10).unwrap();
+    assert_eq!(hits.len(), 1, "one hit per record, not per chunk");
+    assert_eq!(hits[0].entity, "report");
+    // The snippet is the matching passage, not the whole document
+    let snippet = hits[0].snippet.as_deref().unwrap();
+    assert!(snippet.ends_with("disk failure on node 7"));
+    assert!(snippet.len() <= 1024 && snippet.len() < report.len());
+
+    // Rewriting with shorter text drops the old chunks
+    db.kv_put("report", "lunch at noon").unwrap();
+    let hits = db.search().semantic("disk trouble", 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].score < 0.5, "stale disk chunk still indexed");
+    assert_eq!(hits[0].snippet.as_deref(), Some("lunch at noon"));
+}
+
+/// Serve Cohere-style rerank requests until the listener is dropped.
+/// Documents mentioning "node 7" score 0.9, everything else 0.2.
+#[cfg(feature = "embed")]
+fn spawn_rerank_server() -> String {
+    spawn_json_server("/v1/rerank", |request| {
+        let results: Vec<_> = request["documents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(index, document)| {
+                let score = if document.as_str().unwrap().contains("node 7") {
+                    0.9
+                } else {
+                    0.2
+                };
+                serde_json::json!({ "index": index, "relevance_score": score })
+            })
+            .collect();
+        serde_json::json!({ "results": results })
+    })
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn test_semantic_search_reranked() {
+    use crate::{EmbedProvider, OpenOptions, RerankProvider, Strata};
+
+    let dir = tempfile::tempdir().unwrap();
+    let opts = OpenOptions::new()
+        .auto_embed(true)
+        .embed_provider(EmbedProvider::Http {
+            url: spawn_embedding_server(),
+            model: "test".into(),
+            api_key: None,
+        })
+        .rerank_provider(RerankProvider::Http {
+            url: spawn_rerank_server(),
+            model: "test-reranker".into(),
+            api_key: None,
+        });
+    let db = Strata::open_with(dir.path(), opts).unwrap();
+
+    db.kv_put("incident:1", "disk failure on node 3").unwrap();
+    db.kv_put("incident:2", "disk failure on node 7").unwrap();
+    db.kv_put("note:1", "lunch at noon").unwrap();
+
+    // Both incidents embed identically; only the reranker tells them apart
+    let hits = db
+        .search()
+        .semantic_reranked("which disk failed on node 7", 2, 10)
+        .unwrap();
+    assert_eq!(hits.len(), 2);
+    assert_eq!(hits[0].entity, "incident:2");
+    assert_eq!(hits[0].rank, 1);
+    assert!(
+        (hits[0].score - 0.9).abs() < 1e-6,
+        "reranker score is returned"
+    );
+    assert_eq!(hits[1].rank, 2);
+    assert!((hits[1].score - 0.2).abs() < 1e-6);
+
+    // k larger than rerank_top_n still returns k candidates
+    let hits = db.search().semantic_reranked("disk", 3, 1).unwrap();
+    assert_eq!(hits.len(), 3);
+    assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[cfg(feature = "embed")]
+#[test]
+fn test_semantic_search_rerank_failure_is_an_error() {
+    use crate::{EmbedProvider, OpenOptions, RerankProvider, Strata};
+
+    let dir = tempfile::tempdir().unwrap();
+    let opts = OpenOptions::new()
+        .auto_embed(true)
+        .embed_provider(EmbedProvider::Http {
+            url: spawn_embedding_server(),
+            model: "test".into(),
+            api_key: None,
+        })
+        .rerank_provider(RerankProvider::Http {
+            url: "http://127.0.0.1:1/v1/rerank".into(),
+            model: "test-reranker".into(),
+            api_key: None,
+        });
+    let db = Strata::open_with(dir.path(), opts).unwrap();
+    db.kv_put("incident:1", "disk failure on node 3").unwrap();
+
+    assert!(matches!(
+        db.search().semantic_reranked("disk", 1, 10),
+        Err(Error::Internal { .. })
+    ));
+    // Plain semantic search does not need the reranker
+    assert_eq!(db.search().semantic("disk", 1).unwrap().len(), 1);
+}
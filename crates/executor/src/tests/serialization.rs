@@ -61,6 +61,40 @@ fn test_command_compact() {
     test_command_round_trip(Command::Compact);
 }
 
+#[test]
+fn test_command_stats() {
+    test_command_round_trip(Command::Stats);
+}
+
+#[test]
+fn test_command_latency_report() {
+    test_command_round_trip(Command::LatencyReport);
+}
+
+#[test]
+fn test_command_latency_reset() {
+    test_command_round_trip(Command::LatencyReset);
+}
+
+#[test]
+fn test_command_cursor_open() {
+    test_command_round_trip(Command::CursorOpen {
+        query: Box::new(Command::KvList {
+            branch: Some(BranchId::from("default")),
+            space: None,
+            prefix: Some("user:".into()),
+            cursor: None,
+            limit: None,
+            as_of: None,
+        }),
+        page_size: Some(50),
+        ttl_secs: None,
+    });
+    test_command_round_trip(Command::CursorFetch {
+        cursor: "abc".into(),
+    });
+}
+
 // =============================================================================
 // KV Command Tests (4 MVP)
 // =============================================================================
@@ -106,6 +140,36 @@ fn test_command_kv_list() {
     });
 }
 
+#[test]
+fn test_command_kv_incr() {
+    test_command_round_trip(Command::KvIncr {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        key: "hits".to_string(),
+        delta: Value::Int(-3),
+    });
+}
+
+#[test]
+fn test_command_kv_history() {
+    test_command_round_trip(Command::KvHistory {
+        branch: None,
+        space: None,
+        key: "session:1".to_string(),
+        limit: Some(10),
+    });
+}
+
+#[test]
+fn test_command_kv_set_history_policy() {
+    test_command_round_trip(Command::KvSetHistoryPolicy {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        prefix: "session:".to_string(),
+        policy: RetentionPolicy::MaxAgeSecs(3600),
+    });
+}
+
 // =============================================================================
 // JSON Command Tests
 // =============================================================================
@@ -180,6 +244,17 @@ fn test_command_event_len() {
     });
 }
 
+#[test]
+fn test_command_event_ack() {
+    test_command_round_trip(Command::EventAck {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        event_type: "orders".to_string(),
+        group: "billing".to_string(),
+        sequences: vec![1, 2, 3],
+    });
+}
+
 // =============================================================================
 // State Command Tests
 // =============================================================================
@@ -205,6 +280,83 @@ fn test_command_state_cas() {
     });
 }
 
+#[test]
+fn test_command_state_getv_limit() {
+    test_command_round_trip(Command::StateGetv {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        cell: "status".to_string(),
+        as_of: None,
+        limit: Some(10),
+    });
+}
+
+#[test]
+fn test_command_state_get_version() {
+    test_command_round_trip(Command::StateGetVersion {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        cell: "status".to_string(),
+        version: 3,
+    });
+}
+
+#[test]
+fn test_command_state_incr() {
+    test_command_round_trip(Command::StateIncr {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        cell: "count".to_string(),
+        delta: Value::Float(0.5),
+    });
+}
+
+// =============================================================================
+// List Command Tests
+// =============================================================================
+
+#[test]
+fn test_command_list_push() {
+    test_command_round_trip(Command::ListPush {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        list: "jobs".to_string(),
+        end: ListEnd::Left,
+        values: vec![Value::Int(1), Value::String("two".into())],
+    });
+}
+
+#[test]
+fn test_command_list_pop() {
+    test_command_round_trip(Command::ListPop {
+        branch: None,
+        space: Some("queues".to_string()),
+        list: "jobs".to_string(),
+        end: ListEnd::Right,
+        timeout_ms: Some(500),
+    });
+}
+
+#[test]
+fn test_command_list_range() {
+    test_command_round_trip(Command::ListRange {
+        branch: None,
+        space: None,
+        list: "jobs".to_string(),
+        start: 0,
+        stop: -1,
+    });
+}
+
+#[test]
+fn test_command_list_len() {
+    test_command_round_trip(Command::ListLen {
+        branch: None,
+        space: None,
+        list: "jobs".to_string(),
+    });
+}
+
 // =============================================================================
 // Vector Command Tests
 // =============================================================================
@@ -222,6 +374,7 @@ fn test_command_vector_upsert() {
                 .into_iter()
                 .collect(),
         )),
+        namespace: None,
     });
 }
 
@@ -236,9 +389,95 @@ fn test_command_vector_search() {
         filter: None,
         metric: Some(DistanceMetric::Cosine),
         as_of: None,
+        namespace: None,
     });
 }
 
+#[test]
+fn test_command_vector_namespace() {
+    test_command_round_trip(Command::VectorUpsert {
+        branch: None,
+        space: None,
+        collection: "embeddings".to_string(),
+        key: "vec1".to_string(),
+        vector: vec![0.1, 0.2],
+        metadata: None,
+        namespace: Some("tenant-a".to_string()),
+    });
+    test_command_round_trip(Command::VectorSearch {
+        branch: None,
+        space: None,
+        collection: "embeddings".to_string(),
+        query: vec![0.1, 0.2],
+        k: 3,
+        filter: None,
+        metric: None,
+        as_of: None,
+        namespace: Some("tenant-a".to_string()),
+    });
+}
+
+#[test]
+fn test_command_vector_search_batch() {
+    test_command_round_trip(Command::VectorSearchBatch {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        collection: "embeddings".to_string(),
+        queries: vec![vec![0.1, 0.2], vec![0.3, 0.4]],
+        k: 5,
+        filter: Some(vec![MetadataFilter {
+            field: "tag".to_string(),
+            op: FilterOp::Eq,
+            value: Value::String("a".to_string()),
+            filters: vec![],
+        }]),
+    });
+}
+
+#[test]
+fn test_command_vector_search_compound_filter() {
+    test_command_round_trip(Command::VectorSearch {
+        branch: None,
+        space: None,
+        collection: "embeddings".to_string(),
+        query: vec![0.1, 0.2, 0.3, 0.4],
+        k: 10,
+        filter: Some(vec![MetadataFilter {
+            field: String::new(),
+            op: FilterOp::Or,
+            value: Value::Null,
+            filters: vec![
+                MetadataFilter {
+                    field: "path".to_string(),
+                    op: FilterOp::Prefix,
+                    value: Value::String("docs/".to_string()),
+                    filters: vec![],
+                },
+                MetadataFilter {
+                    field: "year".to_string(),
+                    op: FilterOp::Range,
+                    value: Value::Array(vec![Value::Int(2000), Value::Int(2010)]),
+                    filters: vec![],
+                },
+            ],
+        }]),
+        metric: None,
+        as_of: None,
+        namespace: None,
+    });
+}
+
+#[test]
+fn test_metadata_filter_json_omits_field_and_value_for_or() {
+    let json = r#"{"op":"not","filters":[{"field":"draft","op":"eq","value":{"Bool":true}}]}"#;
+    let filter: MetadataFilter = serde_json::from_str(json).unwrap();
+    assert_eq!(filter.op, FilterOp::Not);
+    assert!(filter.field.is_empty());
+    assert_eq!(filter.value, Value::Null);
+    assert_eq!(filter.filters.len(), 1);
+    assert_eq!(filter.filters[0].value, Value::Bool(true));
+}
+
 #[test]
 fn test_command_vector_create_collection() {
     test_command_round_trip(Command::VectorCreateCollection {
@@ -247,6 +486,107 @@ fn test_command_vector_create_collection() {
         collection: "embeddings".to_string(),
         dimension: 384,
         metric: DistanceMetric::Cosine,
+        storage_dtype: None,
+    });
+}
+
+#[test]
+fn test_command_vector_create_collection_with_storage_dtype() {
+    let cmd = Command::VectorCreateCollection {
+        branch: None,
+        space: None,
+        collection: "embeddings".to_string(),
+        dimension: 384,
+        metric: DistanceMetric::DotProduct,
+        storage_dtype: Some(StorageDtype::Int8),
+    };
+    let json = serde_json::to_string(&cmd).unwrap();
+    assert!(json.contains(r#""storage_dtype":"int8""#), "{}", json);
+    test_command_round_trip(cmd);
+
+    // Omitted dtype deserializes as None
+    let cmd: Command = serde_json::from_str(
+        r#"{"VectorCreateCollection":{"collection":"c","dimension":4,"metric":"cosine"}}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        cmd,
+        Command::VectorCreateCollection {
+            storage_dtype: None,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_command_vector_reindex() {
+    let cmd = Command::VectorReindex {
+        branch: None,
+        space: None,
+        collection: "embeddings".to_string(),
+        metric: Some(DistanceMetric::Euclidean),
+        index_type: Some(VectorIndexType::Hnsw),
+    };
+    let json = serde_json::to_string(&cmd).unwrap();
+    assert!(json.contains(r#""index_type":"hnsw""#), "{}", json);
+    test_command_round_trip(cmd);
+
+    test_command_round_trip(Command::VectorReindex {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        collection: "embeddings".to_string(),
+        metric: None,
+        index_type: None,
+    });
+}
+
+#[test]
+fn test_command_text_search() {
+    test_command_round_trip(Command::TextSearch {
+        branch: Some(BranchId::from("default")),
+        space: Some("notes".to_string()),
+        query: "disk failure".to_string(),
+        k: Some(5),
+    });
+}
+
+#[test]
+fn test_command_semantic_search() {
+    test_command_round_trip(Command::SemanticSearch {
+        branch: Some(BranchId::from("default")),
+        space: Some("notes".to_string()),
+        query: "storage hardware problems".to_string(),
+        k: Some(5),
+        rerank_top_n: None,
+    });
+    test_command_round_trip(Command::SemanticSearch {
+        branch: None,
+        space: None,
+        query: "disk".to_string(),
+        k: None,
+        rerank_top_n: Some(20),
+    });
+}
+
+#[test]
+fn test_command_hybrid_search() {
+    test_command_round_trip(Command::HybridSearch {
+        branch: Some(BranchId::from("default")),
+        space: None,
+        collection: "chunks".to_string(),
+        query: "disk failure".to_string(),
+        embedding: vec![0.25, -0.5, 1.0],
+        k: Some(5),
+        fusion: Some(FusionMethod::Weighted { vector_weight: 0.7 }),
+    });
+    test_command_round_trip(Command::HybridSearch {
+        branch: None,
+        space: None,
+        collection: "chunks".to_string(),
+        query: "disk".to_string(),
+        embedding: vec![1.0],
+        k: None,
+        fusion: None,
     });
 }
 
@@ -275,6 +615,19 @@ fn test_command_branch_list() {
     });
 }
 
+#[test]
+fn test_command_branch_query() {
+    test_command_round_trip(Command::BranchQuery {
+        filter: BranchFilter::new()
+            .with_status(BranchStatus::Active)
+            .created_after(1000000)
+            .with_tag("eval")
+            .with_metadata("model", "small"),
+        limit: Some(10),
+        offset: None,
+    });
+}
+
 // =============================================================================
 // Transaction Command Tests
 // =============================================================================
@@ -283,7 +636,10 @@ fn test_command_branch_list() {
 fn test_command_txn_begin() {
     test_command_round_trip(Command::TxnBegin {
         branch: None,
-        options: Some(TxnOptions { read_only: true }),
+        options: Some(TxnOptions {
+            read_only: true,
+            lock_mode: crate::LockMode::Pessimistic,
+        }),
     });
 }
 
@@ -341,6 +697,34 @@ fn test_output_keys() {
     ]));
 }
 
+#[test]
+fn test_output_values() {
+    test_output_round_trip(Output::Values(vec![Value::Int(1), Value::Null]));
+    test_output_round_trip(Output::Values(vec![]));
+}
+
+#[test]
+fn test_output_pending_events() {
+    test_output_round_trip(Output::PendingEvents(vec![PendingEvent {
+        sequence: 7,
+        consumer: "worker-1".to_string(),
+        delivered_at: 1000,
+    }]));
+}
+
+#[test]
+fn test_output_maybe_values() {
+    test_output_round_trip(Output::MaybeValues(vec![Some(Value::Int(1)), None]));
+}
+
+#[test]
+fn test_output_schema_violations() {
+    test_output_round_trip(Output::SchemaViolations(vec![SchemaViolation {
+        path: "$.id".to_string(),
+        message: "missing required field".to_string(),
+    }]));
+}
+
 #[test]
 fn test_output_versioned_values() {
     test_output_round_trip(Output::VersionedValues(vec![
@@ -357,6 +741,24 @@ fn test_output_versioned_values() {
     ]));
 }
 
+#[test]
+fn test_output_kv_history() {
+    test_output_round_trip(Output::KvHistory(KvHistory {
+        versions: vec![VersionedValue {
+            value: Value::Int(5),
+            version: 5,
+            timestamp: 5000,
+        }],
+        policy: Some(RetentionPolicy::MaxLen(1)),
+        truncated: true,
+    }));
+    test_output_round_trip(Output::KvHistory(KvHistory {
+        versions: vec![],
+        policy: None,
+        truncated: false,
+    }));
+}
+
 #[test]
 fn test_output_vector_matches() {
     test_output_round_trip(Output::VectorMatches(vec![VectorMatch {
@@ -366,6 +768,30 @@ fn test_output_vector_matches() {
     }]));
 }
 
+#[test]
+fn test_output_vector_batch_matches() {
+    test_output_round_trip(Output::VectorBatchMatches(vec![
+        vec![VectorMatch {
+            key: "vec1".to_string(),
+            score: 0.95,
+            metadata: None,
+        }],
+        vec![],
+    ]));
+}
+
+#[test]
+fn test_output_text_search_results() {
+    test_output_round_trip(Output::TextSearchResults(vec![TextSearchHit {
+        entity: "incident:42".to_string(),
+        primitive: "kv".to_string(),
+        score: 1.5,
+        rank: 1,
+        snippet: "...the disk failed".to_string(),
+        highlights: vec![(7, 11)],
+    }]));
+}
+
 #[test]
 fn test_output_branch_info() {
     test_output_round_trip(Output::BranchWithVersion {
@@ -374,7 +800,15 @@ fn test_output_branch_info() {
             status: BranchStatus::Active,
             created_at: 1000000,
             updated_at: 1000000,
+            completed_at: Some(2000000),
+            error: Some("timeout".to_string()),
             parent_id: None,
+            fork_version: None,
+            environment: None,
+            tags: vec!["eval".to_string()],
+            metadata: [("model".to_string(), Value::String("small".to_string()))]
+                .into_iter()
+                .collect(),
         },
         version: 1,
     });
@@ -397,6 +831,20 @@ fn test_output_database_info() {
     }));
 }
 
+#[test]
+fn test_output_latency_report() {
+    test_output_round_trip(Output::LatencyReport(LatencyReport {
+        commands: vec![CommandLatency {
+            command: "KvPut".to_string(),
+            count: 42,
+            p50_ns: 1_200,
+            p95_ns: 3_400,
+            p99_ns: 9_800,
+            max_ns: 120_000,
+        }],
+    }));
+}
+
 // =============================================================================
 // Complex Value Serialization Tests
 // =============================================================================
@@ -1,7 +1,8 @@
 //! Session tests: verify transactional session lifecycle and routing.
 
+use crate::types::TxnOptions;
 use crate::Value;
-use crate::{Command, Error, Output, Session};
+use crate::{Command, Error, LockMode, Output, Session};
 use strata_engine::Database;
 
 /// Create a test session with a cache in-memory database.
@@ -52,6 +53,36 @@ fn test_begin_abort_lifecycle() {
     assert!(!session.in_transaction());
 }
 
+#[test]
+fn test_session_dropped_by_panic_aborts_transaction() {
+    let db = Database::cache().unwrap();
+
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut session = Session::new(db.clone());
+        session
+            .execute(Command::TxnBegin {
+                branch: None,
+                options: None,
+            })
+            .unwrap();
+        session
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: "k".into(),
+                value: Value::Int(1),
+            })
+            .unwrap();
+        panic!("injected panic with a transaction open");
+    }));
+    assert!(outcome.is_err());
+
+    // The open transaction was aborted, so shutdown has nothing to wait for
+    let start = std::time::Instant::now();
+    db.shutdown().unwrap();
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+}
+
 #[test]
 fn test_double_begin_returns_error() {
     let mut session = create_test_session();
@@ -418,6 +449,132 @@ fn test_event_append_in_txn() {
     session.execute(Command::TxnCommit).unwrap();
 }
 
+#[test]
+fn test_event_append_in_txn_continues_existing_log() {
+    let mut session = create_test_session();
+    let payload = |n: i64| {
+        Value::Object(std::collections::HashMap::from([(
+            "n".to_string(),
+            Value::Int(n),
+        )]))
+    };
+    let append = |session: &mut Session, n: i64| {
+        session.execute(Command::EventAppend {
+            branch: None,
+            space: None,
+            event_type: "orders".to_string(),
+            payload: payload(n),
+        })
+    };
+
+    assert_eq!(append(&mut session, 0).unwrap(), Output::Version(0));
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    assert_eq!(append(&mut session, 1).unwrap(), Output::Version(1));
+    assert_eq!(append(&mut session, 2).unwrap(), Output::Version(2));
+    session.execute(Command::TxnCommit).unwrap();
+
+    // The earlier event is kept and the stream index covers all three
+    let events = match session
+        .execute(Command::EventGetByType {
+            branch: None,
+            space: None,
+            event_type: "orders".to_string(),
+            limit: None,
+            after_sequence: None,
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::VersionedValues(events) => events,
+        other => panic!("Expected VersionedValues, got {:?}", other),
+    };
+    let numbers: Vec<_> = events
+        .iter()
+        .map(|e| match &e.value {
+            Value::Object(map) => map["n"].clone(),
+            other => panic!("Expected Object payload, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(numbers, vec![Value::Int(0), Value::Int(1), Value::Int(2)]);
+}
+
+#[test]
+fn test_event_append_in_txn_checks_stream_schema() {
+    let mut session = create_test_session();
+    session
+        .execute(Command::EventSetSchema {
+            branch: None,
+            space: None,
+            event_type: "orders".to_string(),
+            schema: Value::try_from(&serde_json::json!({"type": "object", "required": ["id"]}))
+                .unwrap(),
+        })
+        .unwrap();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    let result = session.execute(Command::EventAppend {
+        branch: None,
+        space: None,
+        event_type: "orders".to_string(),
+        payload: Value::Object(std::collections::HashMap::new()),
+    });
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+    session.execute(Command::TxnRollback).unwrap();
+}
+
+#[test]
+fn test_json_set_in_txn_checks_document_schema() {
+    let mut session = create_test_session();
+    session
+        .execute(Command::JsonSetSchema {
+            branch: None,
+            space: None,
+            prefix: "order:".to_string(),
+            schema: Value::try_from(&serde_json::json!({
+                "type": "object",
+                "properties": {"id": {"type": "integer"}}
+            }))
+            .unwrap(),
+        })
+        .unwrap();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    let set = |path: &str, value: Value| Command::JsonSet {
+        branch: None,
+        space: None,
+        key: "order:1".to_string(),
+        path: path.to_string(),
+        value,
+    };
+    session
+        .execute(set(
+            "$",
+            Value::try_from(&serde_json::json!({"id": 1})).unwrap(),
+        ))
+        .unwrap();
+    // Checked against the document as this transaction left it
+    let result = session.execute(set("$.id", Value::from("1")));
+    assert!(matches!(result, Err(Error::InvalidInput { .. })));
+    session.execute(set("$.note", Value::from("ok"))).unwrap();
+    session.execute(Command::TxnRollback).unwrap();
+}
+
 // =============================================================================
 // State Operations In Transaction
 // =============================================================================
@@ -443,3 +600,175 @@ fn test_state_init_in_txn() {
 
     session.execute(Command::TxnCommit).unwrap();
 }
+
+#[test]
+fn test_reserved_keys_rejected_in_txn() {
+    let mut session = create_test_session();
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+
+    let kv = session.execute(Command::KvPut {
+        branch: None,
+        space: None,
+        key: "_strata/config".to_string(),
+        value: Value::Int(1),
+    });
+    assert!(matches!(kv, Err(Error::InvalidKey { .. })));
+
+    let state = session.execute(Command::StateSet {
+        branch: None,
+        space: None,
+        cell: "__meta__".to_string(),
+        value: Value::Int(1),
+    });
+    assert!(matches!(state, Err(Error::InvalidKey { .. })));
+
+    session.execute(Command::TxnCommit).unwrap();
+}
+
+#[test]
+fn test_read_cache_sees_session_commits() {
+    let db = Database::cache().unwrap();
+    db.set_read_cache_capacity(16);
+    let mut session = Session::new(db.clone());
+    let get = |session: &mut Session| match session
+        .execute(Command::KvGet {
+            branch: None,
+            space: None,
+            key: "hot".to_string(),
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::MaybeVersioned(v) => v.map(|vv| vv.value),
+        other => panic!("Expected MaybeVersioned, got {:?}", other),
+    };
+    let put = |session: &mut Session, value: i64| {
+        session
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: "hot".to_string(),
+                value: Value::Int(value),
+            })
+            .unwrap();
+    };
+
+    put(&mut session, 1);
+    assert_eq!(get(&mut session), Some(Value::Int(1)));
+    assert_eq!(get(&mut session), Some(Value::Int(1)));
+    assert!(db.read_cache_stats().hits >= 1);
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    put(&mut session, 2);
+    session.execute(Command::TxnCommit).unwrap();
+
+    assert_eq!(get(&mut session), Some(Value::Int(2)));
+}
+
+#[test]
+fn test_pessimistic_txn_reads_writes_committed_after_begin() {
+    let db = Database::cache().unwrap();
+    let mut session = Session::new(db.clone());
+    let mut other = Session::new(db);
+    let put = |session: &mut Session, value: i64| {
+        session
+            .execute(Command::KvPut {
+                branch: None,
+                space: None,
+                key: "hot".to_string(),
+                value: Value::Int(value),
+            })
+            .unwrap();
+    };
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: Some(TxnOptions {
+                read_only: false,
+                lock_mode: LockMode::Pessimistic,
+            }),
+        })
+        .unwrap();
+    put(&mut other, 5);
+
+    // The first access locks the key and reads the value committed above,
+    // so the increment does not conflict with it
+    let current = session
+        .execute(Command::KvGet {
+            branch: None,
+            space: None,
+            key: "hot".to_string(),
+            as_of: None,
+        })
+        .unwrap();
+    assert!(matches!(current, Output::Maybe(Some(Value::Int(5)))));
+    put(&mut session, 6);
+    assert!(matches!(
+        session.execute(Command::TxnCommit),
+        Ok(Output::TxnCommitted { .. })
+    ));
+}
+
+#[test]
+fn test_txn_writes_to_second_branch_commit_together() {
+    let db = Database::cache().unwrap();
+    let mut session = Session::new(db.clone());
+    let mut other = Session::new(db);
+    session
+        .execute(Command::BranchCreate {
+            branch_id: Some("exp".to_string()),
+            metadata: None,
+        })
+        .unwrap();
+    let put = |session: &mut Session, branch: &str, value: i64| {
+        session.execute(Command::KvPut {
+            branch: Some(branch.into()),
+            space: None,
+            key: "k".to_string(),
+            value: Value::Int(value),
+        })
+    };
+    let get = |session: &mut Session, branch: &str| match session
+        .execute(Command::KvGet {
+            branch: Some(branch.into()),
+            space: None,
+            key: "k".to_string(),
+            as_of: None,
+        })
+        .unwrap()
+    {
+        Output::MaybeVersioned(v) => v.map(|vv| vv.value),
+        other => panic!("Expected MaybeVersioned, got {:?}", other),
+    };
+
+    session
+        .execute(Command::TxnBegin {
+            branch: None,
+            options: None,
+        })
+        .unwrap();
+    put(&mut session, "default", 1).unwrap();
+    put(&mut session, "exp", 2).unwrap();
+    assert!(matches!(
+        put(&mut session, "missing", 3),
+        Err(Error::BranchNotFound { .. })
+    ));
+
+    // Neither write is visible until the commit, then both are
+    assert_eq!(get(&mut other, "exp"), None);
+    session.execute(Command::TxnCommit).unwrap();
+    assert_eq!(get(&mut other, "default"), Some(Value::Int(1)));
+    assert_eq!(get(&mut other, "exp"), Some(Value::Int(2)));
+}
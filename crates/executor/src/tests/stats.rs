@@ -0,0 +1,61 @@
+//! Tests for database and branch statistics.
+
+use crate::{Error, Strata, Value};
+
+#[test]
+fn test_stats_counts_entries_per_primitive_and_branch() {
+    let mut db = Strata::cache().unwrap();
+    db.kv_put("a", 1i64).unwrap();
+    db.kv_put("b", 2i64).unwrap();
+    db.state_set("cell", "on").unwrap();
+    db.kv_delete("b").unwrap();
+
+    db.create_branch("big").unwrap();
+    db.set_branch("big").unwrap();
+    for i in 0..20 {
+        db.kv_put(&format!("k{}", i), Value::Bytes(vec![0; 1024]))
+            .unwrap();
+    }
+
+    let stats = db.stats().unwrap();
+    assert_eq!(stats.primitives.kv, 21);
+    assert_eq!(stats.primitives.state, 1);
+    assert!(stats.memory_bytes > 20 * 1024);
+    assert_eq!(stats.wal_bytes, 0);
+
+    // Largest branch first
+    let names: Vec<_> = stats.branches.iter().map(|b| b.branch.as_str()).collect();
+    assert_eq!(names, ["big", "default"]);
+    assert_eq!(stats.branches[1].primitives.kv, 1);
+    assert_eq!(stats.branches[1].primitives.total(), 2);
+    assert!(stats.branches[0].memory_bytes > stats.branches[1].memory_bytes);
+    assert!(stats.transactions.committed >= 24);
+
+    assert_eq!(db.info().unwrap().total_keys, 22);
+}
+
+#[test]
+fn test_branch_stats_matches_stats_entry() {
+    let db = Strata::cache().unwrap();
+    db.kv_put("a", 1i64).unwrap();
+    db.event_append(
+        "tool",
+        Value::try_from(&serde_json::json!({"x": 1})).unwrap(),
+    )
+    .unwrap();
+
+    let branch = db.branch_stats("default").unwrap();
+    assert_eq!(branch.primitives.kv, 1);
+    assert!(branch.primitives.events >= 1);
+    let stats = db.stats().unwrap();
+    assert_eq!(stats.branches, vec![branch]);
+}
+
+#[test]
+fn test_branch_stats_unknown_branch() {
+    let db = Strata::cache().unwrap();
+    assert!(matches!(
+        db.branch_stats("missing"),
+        Err(Error::BranchNotFound { .. })
+    ));
+}
@@ -4,7 +4,9 @@
 //! All types are serializable for cross-language use.
 
 use serde::{Deserialize, Serialize};
-use strata_core::Value;
+use std::collections::{BTreeMap, HashMap};
+use strata_core::{PrimitiveType, Value};
+use strata_engine::{EnvironmentInfo, LockMode};
 
 // =============================================================================
 // Branch Types
@@ -59,6 +61,10 @@ impl std::fmt::Display for BranchId {
 pub enum BranchStatus {
     /// Branch is active and accepting reads/writes.
     Active,
+    /// Branch was closed normally.
+    Completed,
+    /// Branch ended with an error.
+    Failed,
 }
 
 /// Branch information
@@ -72,8 +78,77 @@ pub struct BranchInfo {
     pub created_at: u64,
     /// Unix timestamp of the last update.
     pub updated_at: u64,
+    /// Unix timestamp the branch was closed or failed at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<u64>,
+    /// Why the branch failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
     /// Parent branch, if this branch was forked.
     pub parent_id: Option<BranchId>,
+    /// Version of the parent this branch was forked at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_version: Option<u64>,
+    /// Environment the branch was created in, if the database captures it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Box<EnvironmentInfo>>,
+    /// Tags, sorted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// User metadata.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, Value>,
+}
+
+/// Conditions a branch must meet to be returned by a branch query.
+///
+/// An empty filter matches every branch; each condition set narrows it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BranchFilter {
+    /// Status the branch must have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<BranchStatus>,
+    /// The branch must have been created after this timestamp, in the
+    /// unit of `BranchInfo::created_at`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<u64>,
+    /// Tag the branch must carry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Metadata entries the branch must have, with equal values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metadata: Vec<(String, Value)>,
+}
+
+impl BranchFilter {
+    /// Match every branch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match branches with `status`.
+    pub fn with_status(mut self, status: BranchStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only match branches created after `timestamp`.
+    pub fn created_after(mut self, timestamp: u64) -> Self {
+        self.created_after = Some(timestamp);
+        self
+    }
+
+    /// Only match branches tagged `tag`.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Only match branches whose metadata has `key` set to `value`.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
 }
 
 /// Versioned branch information
@@ -102,6 +177,158 @@ pub struct VersionedValue {
     pub timestamp: u64,
 }
 
+/// Version history of a key under its history policy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KvHistory {
+    /// Retained versions, newest first.
+    pub versions: Vec<VersionedValue>,
+    /// History policy governing the key, if one is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<RetentionPolicy>,
+    /// Whether older versions were pruned or are hidden by the policy.
+    pub truncated: bool,
+}
+
+// =============================================================================
+// JSON Types
+// =============================================================================
+
+/// A place where a JSON document doesn't match its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON path of the offending value, `$` being the document root.
+    pub path: String,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+// =============================================================================
+// Event Types
+// =============================================================================
+
+/// An event read from a stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// Position in the event log. Monotonically increasing across all
+    /// streams on a branch and space; pass it to `read_after` to resume.
+    pub sequence: u64,
+    /// Stream (event type) the event was appended to.
+    pub event_type: String,
+    /// Event payload data.
+    pub payload: Value,
+    /// Unix timestamp when the event was appended.
+    pub timestamp: u64,
+}
+
+/// Retention policy for an event stream or a KV key prefix.
+///
+/// Events the policy no longer retains are deleted, oldest first, when the
+/// stream is appended to or when retention is applied to the branch. For
+/// KV history, the limits count versions of each key instead of events, and
+/// old versions are pruned by compaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionPolicy {
+    /// Keep every event (the default).
+    #[default]
+    KeepAll,
+    /// Keep at most this many events.
+    MaxLen(u64),
+    /// Keep events appended within this many seconds.
+    MaxAgeSecs(u64),
+}
+
+/// History retention of a branch, with optional per-primitive policies.
+///
+/// Limits count versions of each key. Compaction prunes the versions a
+/// key's policy no longer retains and always keeps the latest one. A KV
+/// history policy set on a key prefix takes precedence for the keys it
+/// covers.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct BranchRetention {
+    /// Policy for primitives without an override.
+    #[serde(default)]
+    pub default: RetentionPolicy,
+    /// Per-primitive policies.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub overrides: HashMap<PrimitiveType, RetentionPolicy>,
+}
+
+impl BranchRetention {
+    /// Apply `default` to every primitive.
+    pub fn new(default: RetentionPolicy) -> Self {
+        BranchRetention {
+            default,
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Use `policy` for `primitive` instead of the default.
+    pub fn with(mut self, primitive: PrimitiveType, policy: RetentionPolicy) -> Self {
+        self.overrides.insert(primitive, policy);
+        self
+    }
+}
+
+impl From<RetentionPolicy> for BranchRetention {
+    fn from(policy: RetentionPolicy) -> Self {
+        BranchRetention::new(policy)
+    }
+}
+
+/// An event delivered to a consumer group that has not been acknowledged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingEvent {
+    /// Sequence number of the delivered event.
+    pub sequence: u64,
+    /// Consumer the event was delivered to.
+    pub consumer: String,
+    /// When the event was delivered (microseconds since epoch).
+    pub delivered_at: u64,
+}
+
+// =============================================================================
+// Session Types
+// =============================================================================
+
+/// A message of a conversation session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionMessage {
+    /// Position in the session, starting at 0.
+    pub index: u64,
+    /// Who sent it: `user`, `assistant`, `system`, `tool` or any other role.
+    pub role: String,
+    /// Message text.
+    pub content: String,
+    /// Sequence of the event holding the message.
+    pub sequence: u64,
+    /// Unix timestamp when the message was appended (microseconds).
+    pub timestamp: u64,
+}
+
+/// The rolling summary of a session's older messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    /// Summary text, as returned by the summarize hook.
+    pub text: String,
+    /// Number of messages the summary covers: indices `0..through`.
+    pub through: u64,
+}
+
+// =============================================================================
+// List Types
+// =============================================================================
+
+/// End of a list that a push or pop works on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListEnd {
+    /// The head of the list (index 0).
+    Left,
+    /// The tail of the list (index -1).
+    Right,
+}
+
 // =============================================================================
 // Vector Types
 // =============================================================================
@@ -119,15 +346,50 @@ pub enum DistanceMetric {
     DotProduct,
 }
 
+/// Element type used to store a collection's embeddings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageDtype {
+    /// 32-bit floats (default, exact).
+    #[default]
+    F32,
+    /// 16-bit floats (half the memory).
+    F16,
+    /// 8-bit codes with a per-vector scale (a quarter of the memory).
+    Int8,
+}
+
+/// Index backend used to search a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorIndexType {
+    /// Exact O(n) scan.
+    BruteForce,
+    /// Approximate HNSW graph.
+    Hnsw,
+}
+
 /// Metadata filter for vector search
+///
+/// `Or` and `Not` combine the sub-filters in `filters` and ignore `field`
+/// and `value`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MetadataFilter {
     /// Metadata field name to filter on.
+    #[serde(default)]
     pub field: String,
     /// Comparison operator.
     pub op: FilterOp,
     /// Value to compare against.
+    #[serde(default = "null_value")]
     pub value: Value,
+    /// Sub-filters for `Or` and `Not`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<MetadataFilter>,
+}
+
+fn null_value() -> Value {
+    Value::Null
 }
 
 /// Filter operation
@@ -146,10 +408,18 @@ pub enum FilterOp {
     Lt,
     /// Less than or equal.
     Lte,
-    /// Value is in a set.
+    /// Value is in a set (`value` is an array).
     In,
     /// String/array contains value.
     Contains,
+    /// String starts with value.
+    Prefix,
+    /// Number is within an inclusive range (`value` is `[min, max]`).
+    Range,
+    /// At least one of `filters` matches.
+    Or,
+    /// Not all of `filters` match.
+    Not,
 }
 
 /// Vector data (embedding + metadata)
@@ -202,6 +472,9 @@ pub struct CollectionInfo {
     /// Approximate memory usage in bytes
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_bytes: Option<u64>,
+    /// Element type of the in-memory index
+    #[serde(default)]
+    pub storage_dtype: StorageDtype,
 }
 
 /// Batch vector entry for bulk upsert
@@ -224,6 +497,10 @@ pub struct BatchVectorEntry {
 pub struct TxnOptions {
     /// If true, the transaction only permits reads.
     pub read_only: bool,
+    /// `Pessimistic` locks each key on first access, so transactions on the
+    /// same hot keys wait for each other instead of conflicting at commit.
+    #[serde(default)]
+    pub lock_mode: LockMode,
 }
 
 /// Transaction information
@@ -266,6 +543,228 @@ pub struct DatabaseInfo {
     pub total_keys: u64,
 }
 
+/// Information about a compaction run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompactResult {
+    /// Number of WAL segments removed.
+    pub segments_removed: u64,
+    /// Bytes of disk space reclaimed.
+    pub bytes_reclaimed: u64,
+    /// Old versions pruned by KV history policies.
+    pub versions_removed: u64,
+    /// Version watermark the WAL was compacted to, or None if nothing ran.
+    pub watermark: Option<u64>,
+    /// Duration of the compaction in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// Database runtime statistics
+///
+/// Collecting these walks every branch's data to count entries and estimate
+/// memory, so poll them rather than fetching them per request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseStats {
+    /// Server-side result cursor accounting.
+    pub cursors: CursorStats,
+    /// Transaction context pool activity (process-wide).
+    #[serde(default)]
+    pub transaction_pool: TransactionPoolStats,
+    /// Live entries per primitive, summed over all branches.
+    #[serde(default)]
+    pub primitives: PrimitiveCounts,
+    /// Entry counts and memory per branch, largest memory first.
+    #[serde(default)]
+    pub branches: Vec<BranchStats>,
+    /// Approximate bytes held in memory, including old versions.
+    #[serde(default)]
+    pub memory_bytes: u64,
+    /// Bytes of WAL segments on disk (0 for cache databases).
+    #[serde(default)]
+    pub wal_bytes: u64,
+    /// Version covered by the latest snapshot checkpoint, if any.
+    #[serde(default)]
+    pub snapshot_watermark: Option<u64>,
+    /// Transaction outcomes since the database was opened.
+    #[serde(default)]
+    pub transactions: TransactionStats,
+}
+
+/// Live entries per primitive
+///
+/// Counts storage entries whose latest version is not a deletion. Events,
+/// lists and vectors keep a few bookkeeping entries next to their data, so
+/// their counts can exceed the number of user-visible items.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrimitiveCounts {
+    /// KV entries.
+    pub kv: u64,
+    /// Event log entries.
+    pub events: u64,
+    /// State cells.
+    pub state: u64,
+    /// JSON documents.
+    pub json: u64,
+    /// List entries.
+    pub lists: u64,
+    /// Vector entries.
+    pub vectors: u64,
+    /// Custom primitive entries.
+    pub custom: u64,
+}
+
+impl PrimitiveCounts {
+    /// Entries across all primitives.
+    pub fn total(&self) -> u64 {
+        self.kv + self.events + self.state + self.json + self.lists + self.vectors + self.custom
+    }
+}
+
+/// Entry counts and memory for one branch
+///
+/// Each branch is a separate storage shard, so `memory_bytes` is the
+/// shard's footprint.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BranchStats {
+    /// Branch name.
+    pub branch: String,
+    /// Live entries per primitive.
+    pub primitives: PrimitiveCounts,
+    /// Approximate bytes held, including old versions and deletions not yet
+    /// garbage collected.
+    pub memory_bytes: u64,
+}
+
+/// Transaction outcomes since the database was opened
+///
+/// A rising `conflicts` count means writers are racing on the same keys and
+/// retrying; `aborted` also includes transactions rolled back on purpose.
+/// `retries_exhausted` counts writers that gave up, and is the one to alert on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionStats {
+    /// Transactions currently open.
+    pub active: u64,
+    /// Transactions started.
+    pub started: u64,
+    /// Transactions committed.
+    pub committed: u64,
+    /// Transactions aborted, for any reason.
+    pub aborted: u64,
+    /// Aborts caused by a read or compare-and-swap conflict at commit.
+    pub conflicts: u64,
+    /// Conflicted transactions that a retry loop ran again.
+    #[serde(default)]
+    pub retries: u64,
+    /// Retry loops that ran out of attempts and returned the conflict.
+    #[serde(default)]
+    pub retries_exhausted: u64,
+}
+
+/// Server-side result cursor accounting
+///
+/// `open` should return to zero when clients are idle; a steadily growing
+/// `expired` count means clients are abandoning cursors instead of closing them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CursorStats {
+    /// Cursors currently open.
+    pub open: u64,
+    /// Cursors opened since the database was opened.
+    pub opened: u64,
+    /// Cursors closed explicitly or by fetching their last page.
+    pub closed: u64,
+    /// Cursors dropped after sitting idle past their TTL.
+    pub expired: u64,
+    /// Rows held by open cursors and not yet fetched.
+    pub buffered_rows: u64,
+}
+
+/// Transaction context pool activity
+///
+/// Pools are per thread but the counters are summed over every thread in the
+/// process, so every database reports the same numbers. A high `misses` to
+/// `hits` ratio or a growing `discarded` count suggests raising the pool size;
+/// a growing `shrunk` count means occasional huge transactions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionPoolStats {
+    /// Transactions that reused a pooled context.
+    pub hits: u64,
+    /// Transactions that allocated a new context.
+    pub misses: u64,
+    /// Contexts kept for reuse when their transaction ended.
+    pub returned: u64,
+    /// Contexts dropped because the pool was full.
+    pub discarded: u64,
+    /// Contexts shrunk after growing past the shrink threshold.
+    pub shrunk: u64,
+}
+
+/// [`StrataManager`](crate::StrataManager) statistics
+///
+/// Counters cover the manager's lifetime; `totals` sums [`DatabaseStats`]
+/// over the databases that are currently open. Per-branch entries and
+/// snapshot watermarks are not summed and stay empty in `totals`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManagerStats {
+    /// Databases currently held open by the manager.
+    pub open: u64,
+    /// Maximum number of databases held open at once.
+    pub capacity: u64,
+    /// Lookups served by an already-open database.
+    pub hits: u64,
+    /// Databases opened on first use.
+    pub opened: u64,
+    /// Databases released by `close` or `close_all`.
+    pub closed: u64,
+    /// Databases released to make room for another.
+    pub evicted: u64,
+    /// Compactions run by the manager's scheduler.
+    pub compactions: u64,
+    /// Resources held by the open databases and the manager itself.
+    pub usage: ResourceUsage,
+    /// Statistics summed over the open databases.
+    pub totals: DatabaseStats,
+}
+
+/// Resources held by open databases
+///
+/// Compared against [`ResourceLimits`](crate::ResourceLimits) when a
+/// [`StrataManager`](crate::StrataManager) opens a database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Approximate bytes of data held in memory.
+    pub memory_bytes: u64,
+    /// Background threads (WAL flush, compaction).
+    pub background_threads: u64,
+    /// Open file descriptors (directory locks, active WAL segments).
+    pub open_files: u64,
+}
+
+/// Per-command latency percentiles
+///
+/// Built from every command run through an [`Executor`](crate::Executor) on
+/// the database since it was opened or the report was last reset.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyReport {
+    /// One entry per command that has run, sorted by command name.
+    pub commands: Vec<CommandLatency>,
+}
+
+/// Latency distribution for one command
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandLatency {
+    /// Command name, as returned by [`Command::name`](crate::Command::name).
+    pub command: String,
+    /// Number of times the command ran.
+    pub count: u64,
+    /// Median latency in nanoseconds.
+    pub p50_ns: u64,
+    /// 95th percentile latency in nanoseconds.
+    pub p95_ns: u64,
+    /// 99th percentile latency in nanoseconds.
+    pub p99_ns: u64,
+    /// Slowest run in nanoseconds.
+    pub max_ns: u64,
+}
+
 // =============================================================================
 // Bundle Types
 // =============================================================================
@@ -292,6 +791,9 @@ pub struct BranchImportResult {
     pub transactions_applied: u64,
     /// Total keys written during import.
     pub keys_written: u64,
+    /// Name the branch had in the bundle, when imported under a new name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
 }
 
 /// Information about bundle validation
@@ -305,6 +807,48 @@ pub struct BundleValidateResult {
     pub entry_count: u64,
     /// Whether all checksums passed validation.
     pub checksums_valid: bool,
+    /// Environment of the exporting database, if it captured one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Box<EnvironmentInfo>>,
+}
+
+/// What a database import does with a bundled branch whose name is taken.
+///
+/// Every database has a `default` branch, so importing a whole database
+/// into another one needs `skip`, `rename` or `overwrite`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflict {
+    /// Import nothing and fail if any bundled branch already exists.
+    Fail,
+    /// Keep the existing branch and leave the bundled one out.
+    Skip,
+    /// Import under `<name>-imported` (or `<name>-imported-N`).
+    Rename,
+    /// Delete the existing branch and all its data, then import.
+    Overwrite,
+}
+
+/// Information about a whole-database export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseExportResult {
+    /// File path of the created bundle.
+    pub path: String,
+    /// Names of the exported branches.
+    pub branches: Vec<String>,
+    /// Number of entries across all branches.
+    pub entry_count: u64,
+    /// Bundle file size in bytes.
+    pub bundle_size: u64,
+}
+
+/// Information about a whole-database import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseImportResult {
+    /// Imported branches, under the names they were imported as.
+    pub imported: Vec<BranchImportResult>,
+    /// Bundled branches left out because they already existed.
+    pub skipped: Vec<String>,
 }
 
 // =============================================================================
@@ -325,3 +869,34 @@ pub struct SearchResultHit {
     /// Optional text snippet
     pub snippet: Option<String>,
 }
+
+/// A single hit from a full-text search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextSearchHit {
+    /// Entity identifier string
+    pub entity: String,
+    /// Primitive type that produced this hit
+    pub primitive: String,
+    /// BM25 score (higher = more relevant)
+    pub score: f32,
+    /// Rank in result set (1-indexed)
+    pub rank: u32,
+    /// Excerpt of the matching text around the first match
+    pub snippet: String,
+    /// Byte ranges `(start, end)` of matched terms within `snippet`
+    pub highlights: Vec<(u64, u64)>,
+}
+
+/// How hybrid search combines its text and vector rankings
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FusionMethod {
+    /// Reciprocal Rank Fusion: sums `1 / (60 + rank)` across both lists (default).
+    #[default]
+    Rrf,
+    /// Weighted sum of min-max normalized scores.
+    Weighted {
+        /// Weight of the vector score in [0, 1]; text gets the remainder.
+        vector_weight: f32,
+    },
+}
@@ -0,0 +1,93 @@
+/*
+ * C interface to Strata.
+ *
+ * Link against libstrata_ffi (cdylib or staticlib) built from crates/ffi.
+ * See crates/ffi/src/lib.rs for the conventions: status codes, UTF-8
+ * strings, JSON values and library-owned buffers.
+ */
+
+#ifndef STRATA_H
+#define STRATA_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define STRATA_ABI_VERSION 1
+
+/* Status codes */
+#define STRATA_OK 0
+#define STRATA_NOT_FOUND 1
+#define STRATA_INVALID_ARGUMENT 2
+#define STRATA_CONFLICT 3
+#define STRATA_ACCESS_DENIED 4
+#define STRATA_HISTORY_UNAVAILABLE 5
+#define STRATA_IO 6
+#define STRATA_NOT_IMPLEMENTED 7
+#define STRATA_INTERNAL 8
+#define STRATA_PANIC 9
+
+/* Vector distance metrics */
+#define STRATA_METRIC_COSINE 0
+#define STRATA_METRIC_EUCLIDEAN 1
+#define STRATA_METRIC_DOT_PRODUCT 2
+
+/* An open database. */
+typedef struct StrataDb StrataDb;
+
+/* Bytes owned by the library. Release with strata_buffer_free. */
+typedef struct StrataBuffer {
+    uint8_t *data;
+    size_t len;
+} StrataBuffer;
+
+/* Lifecycle */
+uint32_t strata_abi_version(void);
+int32_t strata_open(const char *path, StrataDb **out_db);
+int32_t strata_open_cache(StrataDb **out_db);
+void strata_close(StrataDb *db);
+int32_t strata_set_branch(StrataDb *db, const char *branch);
+
+/* Errors and buffers */
+const char *strata_last_error(void);
+void strata_buffer_free(StrataBuffer buffer);
+
+/* KV */
+int32_t strata_kv_put(StrataDb *db, const char *key,
+                      const uint8_t *value, size_t value_len,
+                      uint64_t *out_version);
+int32_t strata_kv_get(StrataDb *db, const char *key, StrataBuffer *out_value);
+
+/* JSON documents; a NULL path is the document root */
+int32_t strata_json_set(StrataDb *db, const char *key, const char *path,
+                        const uint8_t *value, size_t value_len,
+                        uint64_t *out_version);
+int32_t strata_json_get(StrataDb *db, const char *key, const char *path,
+                        StrataBuffer *out_value);
+
+/* Events */
+int32_t strata_event_append(StrataDb *db, const char *event_type,
+                            const uint8_t *payload, size_t payload_len,
+                            uint64_t *out_sequence);
+
+/* Vectors */
+int32_t strata_vector_create_collection(StrataDb *db, const char *collection,
+                                        uint64_t dimension, uint32_t metric,
+                                        uint64_t *out_version);
+int32_t strata_vector_upsert(StrataDb *db, const char *collection,
+                             const char *key,
+                             const float *vector, size_t dimension,
+                             const uint8_t *metadata, size_t metadata_len,
+                             uint64_t *out_version);
+int32_t strata_vector_search(StrataDb *db, const char *collection,
+                             const float *query, size_t dimension, uint64_t k,
+                             StrataBuffer *out_matches);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* STRATA_H */
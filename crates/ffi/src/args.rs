@@ -0,0 +1,95 @@
+//! Reading arguments from C and writing results back.
+//!
+//! Every pointer is checked for NULL before it is read; a bad argument is
+//! reported as [`Error::InvalidInput`] like any other rejected input.
+
+use std::ffi::{c_char, CStr};
+use std::slice;
+
+use strata_executor::{json_to_value, Error, Result, Value};
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidInput { reason }
+}
+
+/// A NUL-terminated UTF-8 string argument.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or point to a NUL-terminated string that outlives `'a`.
+pub(crate) unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        return Err(invalid(format!("{} is NULL", name)));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| invalid(format!("{} is not valid UTF-8", name)))
+}
+
+/// An optional string argument: NULL means absent.
+///
+/// # Safety
+///
+/// As for [`str_arg`].
+pub(crate) unsafe fn opt_str_arg<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        str_arg(ptr, name).map(Some)
+    }
+}
+
+/// A `(ptr, len)` array argument. `ptr` may be NULL when `len` is 0.
+///
+/// # Safety
+///
+/// Unless NULL, `ptr` must point to `len` initialized elements that
+/// outlive `'a`.
+pub(crate) unsafe fn slice_arg<'a, T>(ptr: *const T, len: usize, name: &str) -> Result<&'a [T]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(invalid(format!("{} is NULL", name)));
+    }
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+/// A value given as `len` bytes of UTF-8 JSON text in the wire encoding.
+///
+/// # Safety
+///
+/// As for [`slice_arg`].
+pub(crate) unsafe fn value_arg(ptr: *const u8, len: usize, name: &str) -> Result<Value> {
+    let json: serde_json::Value = serde_json::from_slice(slice_arg(ptr, len, name)?)
+        .map_err(|e| invalid(format!("{} is not valid JSON: {}", name, e)))?;
+    json_to_value(&json).map_err(|e| invalid(format!("{}: {}", name, e)))
+}
+
+/// An optional value argument: NULL means absent.
+///
+/// # Safety
+///
+/// As for [`slice_arg`].
+pub(crate) unsafe fn opt_value_arg(
+    ptr: *const u8,
+    len: usize,
+    name: &str,
+) -> Result<Option<Value>> {
+    if ptr.is_null() {
+        Ok(None)
+    } else {
+        value_arg(ptr, len, name).map(Some)
+    }
+}
+
+/// Store `value` through an out-pointer, if the caller passed one.
+///
+/// # Safety
+///
+/// `out` must be NULL or valid for a write of `T`.
+pub(crate) unsafe fn write_out<T>(out: *mut T, value: T) {
+    if !out.is_null() {
+        out.write(value);
+    }
+}
@@ -0,0 +1,120 @@
+//! Status codes and the per-thread last error message.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use strata_executor::Error;
+
+/// The call succeeded
+pub const STRATA_OK: i32 = 0;
+/// The key, document, branch or collection does not exist
+pub const STRATA_NOT_FOUND: i32 = 1;
+/// A null pointer, invalid UTF-8, malformed JSON or a value the command rejects
+pub const STRATA_INVALID_ARGUMENT: i32 = 2;
+/// A version or transaction conflict, or the target already exists
+pub const STRATA_CONFLICT: i32 = 3;
+/// A write to a read-only database
+pub const STRATA_ACCESS_DENIED: i32 = 4;
+/// The requested version has been trimmed or was never retained
+pub const STRATA_HISTORY_UNAVAILABLE: i32 = 5;
+/// An I/O failure
+pub const STRATA_IO: i32 = 6;
+/// The operation is not supported by this build
+pub const STRATA_NOT_IMPLEMENTED: i32 = 7;
+/// Any other failure inside the database
+pub const STRATA_INTERNAL: i32 = 8;
+/// A panic was caught at the ABI boundary
+pub const STRATA_PANIC: i32 = 9;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Status code for each error category of [`Error`].
+pub(crate) fn status_for(error: &Error) -> i32 {
+    match error {
+        Error::KeyNotFound { .. }
+        | Error::BranchNotFound { .. }
+        | Error::CollectionNotFound { .. }
+        | Error::StreamNotFound { .. }
+        | Error::CellNotFound { .. }
+        | Error::DocumentNotFound { .. }
+        | Error::CursorNotFound { .. }
+        | Error::DatabaseNotFound { .. } => STRATA_NOT_FOUND,
+
+        Error::WrongType { .. }
+        | Error::InvalidKey { .. }
+        | Error::InvalidPath { .. }
+        | Error::InvalidInput { .. }
+        | Error::DimensionMismatch { .. }
+        | Error::ConstraintViolation { .. }
+        | Error::Overflow { .. }
+        | Error::Serialization { .. }
+        | Error::TransactionNotActive
+        | Error::TransactionAlreadyActive => STRATA_INVALID_ARGUMENT,
+
+        Error::VersionConflict { .. }
+        | Error::TransitionFailed { .. }
+        | Error::Conflict { .. }
+        | Error::TransactionConflict { .. }
+        | Error::BranchClosed { .. }
+        | Error::BranchExists { .. }
+        | Error::CollectionExists { .. }
+        | Error::AlreadyOpen { .. } => STRATA_CONFLICT,
+
+        Error::AccessDenied { .. } => STRATA_ACCESS_DENIED,
+
+        Error::HistoryTrimmed { .. } | Error::HistoryUnavailable { .. } => {
+            STRATA_HISTORY_UNAVAILABLE
+        }
+
+        Error::Io { .. } => STRATA_IO,
+
+        Error::NotImplemented { .. } => STRATA_NOT_IMPLEMENTED,
+
+        Error::Cancelled { .. } | Error::Internal { .. } => STRATA_INTERNAL,
+    }
+}
+
+fn set_last_error(message: String) {
+    // Messages never contain NUL in practice; strip any rather than lose the message
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run one ABI call: clear the last error, catch panics, and turn the
+/// result into a status code.
+pub(crate) fn guard(call: impl FnOnce() -> Result<(), Error>) -> i32 {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(())) => STRATA_OK,
+        Ok(Err(error)) => {
+            let status = status_for(&error);
+            set_last_error(error.to_string());
+            status
+        }
+        Err(panic) => {
+            let detail = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            set_last_error(format!("panic: {}", detail));
+            STRATA_PANIC
+        }
+    }
+}
+
+/// Message of the last failed call on this thread, or NULL after a success.
+///
+/// The string is owned by the library and stays valid until the next
+/// `strata_*` call on the same thread.
+#[no_mangle]
+pub extern "C" fn strata_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
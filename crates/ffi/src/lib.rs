@@ -0,0 +1,381 @@
+//! C ABI for Strata
+//!
+//! Lets Go, Node, C++ and other hosts embed a database in-process through a
+//! shared or static library. The declarations live in `include/strata.h`.
+//!
+//! # Conventions
+//!
+//! - Every fallible function returns an `int32_t` status: `STRATA_OK` (0)
+//!   or one of the `STRATA_*` error codes. `strata_last_error()` gives the
+//!   message for the last failure on the calling thread.
+//! - Names (keys, paths, branches, collections, event types) are
+//!   NUL-terminated UTF-8 strings.
+//! - Values are passed as `(const uint8_t *json, size_t len)`: UTF-8 JSON
+//!   text in the wire encoding, where bytes are `{"$bytes": "<base64>"}`
+//!   and non-finite floats `{"$f64": "NaN"}`. No NUL terminator is needed.
+//! - Results that carry data are written to a [`StrataBuffer`] owned by
+//!   the library; release it with `strata_buffer_free`.
+//! - Out-pointers for versions may be NULL when the caller doesn't need them.
+//! - A `StrataDb` may be shared between threads, except for
+//!   `strata_set_branch` and `strata_close`, which need exclusive access.
+//!
+//! # Safety
+//!
+//! Every exported function is `unsafe` for the same reasons: pointer
+//! arguments must be NULL or valid for the documented length, strings
+//! NUL-terminated, and `db` a live handle from `strata_open*`. NULL is
+//! rejected with `STRATA_INVALID_ARGUMENT` wherever a value is required.
+//!
+//! ```c
+//! StrataDb *db;
+//! if (strata_open_cache(&db) != STRATA_OK) { puts(strata_last_error()); }
+//! const char *value = "{\"name\": \"Ann\"}";
+//! strata_kv_put(db, "user:1", (const uint8_t *)value, strlen(value), NULL);
+//! StrataBuffer out;
+//! if (strata_kv_get(db, "user:1", &out) == STRATA_OK) {
+//!     fwrite(out.data, 1, out.len, stdout);
+//!     strata_buffer_free(out);
+//! }
+//! strata_close(db);
+//! ```
+
+#![allow(clippy::missing_safety_doc)]
+
+mod args;
+mod error;
+
+use std::ffi::c_char;
+use std::ptr;
+
+use serde_json::json;
+use strata_executor::{value_to_json, DistanceMetric, Error, Strata, Value};
+
+use args::{opt_str_arg, opt_value_arg, slice_arg, str_arg, value_arg, write_out};
+use error::guard;
+pub use error::{
+    strata_last_error, STRATA_ACCESS_DENIED, STRATA_CONFLICT, STRATA_HISTORY_UNAVAILABLE,
+    STRATA_INTERNAL, STRATA_INVALID_ARGUMENT, STRATA_IO, STRATA_NOT_FOUND, STRATA_NOT_IMPLEMENTED,
+    STRATA_OK, STRATA_PANIC,
+};
+
+/// Version of the C ABI. Bumped on any incompatible change to `strata.h`.
+pub const STRATA_ABI_VERSION: u32 = 1;
+
+/// Cosine similarity
+pub const STRATA_METRIC_COSINE: u32 = 0;
+/// Euclidean (L2) distance
+pub const STRATA_METRIC_EUCLIDEAN: u32 = 1;
+/// Dot product similarity
+pub const STRATA_METRIC_DOT_PRODUCT: u32 = 2;
+
+/// An open database, opaque to C.
+pub struct StrataDb(Strata);
+
+/// Bytes owned by the library, returned to the caller.
+///
+/// Release with `strata_buffer_free`. An empty buffer has a NULL `data`.
+#[repr(C)]
+pub struct StrataBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl StrataBuffer {
+    const EMPTY: StrataBuffer = StrataBuffer {
+        data: ptr::null_mut(),
+        len: 0,
+    };
+
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        if bytes.is_empty() {
+            return Self::EMPTY;
+        }
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        StrataBuffer { data, len }
+    }
+
+    fn json(json: &serde_json::Value) -> Self {
+        Self::from_vec(json.to_string().into_bytes())
+    }
+}
+
+/// Borrow a handle shared with other threads.
+unsafe fn db_arg<'a>(db: *mut StrataDb) -> Result<&'a Strata, Error> {
+    db.as_ref().map(|db| &db.0).ok_or(Error::InvalidInput {
+        reason: "db is NULL".into(),
+    })
+}
+
+/// Borrow a handle exclusively, for calls documented to need it.
+unsafe fn db_arg_mut<'a>(db: *mut StrataDb) -> Result<&'a mut Strata, Error> {
+    db.as_mut().map(|db| &mut db.0).ok_or(Error::InvalidInput {
+        reason: "db is NULL".into(),
+    })
+}
+
+/// Write a value found by a read to `out`, or report `not_found`.
+unsafe fn write_found(
+    out: *mut StrataBuffer,
+    value: Option<Value>,
+    not_found: impl FnOnce() -> Error,
+) -> Result<(), Error> {
+    let value = value.ok_or_else(not_found)?;
+    write_out(out, StrataBuffer::json(&value_to_json(&value)));
+    Ok(())
+}
+
+// =============================================================================
+// Lifecycle
+// =============================================================================
+
+/// The ABI version this library implements.
+#[no_mangle]
+pub extern "C" fn strata_abi_version() -> u32 {
+    STRATA_ABI_VERSION
+}
+
+/// Open (or create) a database at `path`.
+#[no_mangle]
+pub unsafe extern "C" fn strata_open(path: *const c_char, out_db: *mut *mut StrataDb) -> i32 {
+    write_out(out_db, ptr::null_mut());
+    guard(|| {
+        let db = Strata::open(str_arg(path, "path")?)?;
+        write_out(out_db, Box::into_raw(Box::new(StrataDb(db))));
+        Ok(())
+    })
+}
+
+/// Open an ephemeral in-memory database.
+#[no_mangle]
+pub unsafe extern "C" fn strata_open_cache(out_db: *mut *mut StrataDb) -> i32 {
+    write_out(out_db, ptr::null_mut());
+    guard(|| {
+        let db = Strata::cache()?;
+        write_out(out_db, Box::into_raw(Box::new(StrataDb(db))));
+        Ok(())
+    })
+}
+
+/// Close a database opened by `strata_open` or `strata_open_cache`.
+///
+/// NULL is ignored. `db` must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn strata_close(db: *mut StrataDb) {
+    if !db.is_null() {
+        guard(|| {
+            drop(Box::from_raw(db));
+            Ok(())
+        });
+    }
+}
+
+/// Make `branch` the branch later calls on `db` operate on.
+#[no_mangle]
+pub unsafe extern "C" fn strata_set_branch(db: *mut StrataDb, branch: *const c_char) -> i32 {
+    guard(|| db_arg_mut(db)?.set_branch(str_arg(branch, "branch")?))
+}
+
+/// Release a buffer returned by the library. Empty buffers are ignored.
+#[no_mangle]
+pub unsafe extern "C" fn strata_buffer_free(buffer: StrataBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+// =============================================================================
+// KV
+// =============================================================================
+
+/// Store a value under `key`.
+#[no_mangle]
+pub unsafe extern "C" fn strata_kv_put(
+    db: *mut StrataDb,
+    key: *const c_char,
+    value: *const u8,
+    value_len: usize,
+    out_version: *mut u64,
+) -> i32 {
+    guard(|| {
+        let version =
+            db_arg(db)?.kv_put(str_arg(key, "key")?, value_arg(value, value_len, "value")?)?;
+        write_out(out_version, version);
+        Ok(())
+    })
+}
+
+/// Read the value under `key` as JSON. `STRATA_NOT_FOUND` if absent.
+#[no_mangle]
+pub unsafe extern "C" fn strata_kv_get(
+    db: *mut StrataDb,
+    key: *const c_char,
+    out_value: *mut StrataBuffer,
+) -> i32 {
+    write_out(out_value, StrataBuffer::EMPTY);
+    guard(|| {
+        let key = str_arg(key, "key")?;
+        let value = db_arg(db)?.kv_get(key)?;
+        write_found(out_value, value, || Error::KeyNotFound { key: key.into() })
+    })
+}
+
+// =============================================================================
+// JSON
+// =============================================================================
+
+/// Set the value at `path` in document `key`. A NULL `path` is the root, `$`.
+#[no_mangle]
+pub unsafe extern "C" fn strata_json_set(
+    db: *mut StrataDb,
+    key: *const c_char,
+    path: *const c_char,
+    value: *const u8,
+    value_len: usize,
+    out_version: *mut u64,
+) -> i32 {
+    guard(|| {
+        let path = opt_str_arg(path, "path")?.unwrap_or("$");
+        let value = value_arg(value, value_len, "value")?;
+        let version = db_arg(db)?.json_set(str_arg(key, "key")?, path, value)?;
+        write_out(out_version, version);
+        Ok(())
+    })
+}
+
+/// Read the value at `path` in document `key` as JSON. A NULL `path` is the
+/// root. `STRATA_NOT_FOUND` if the document or path is absent.
+#[no_mangle]
+pub unsafe extern "C" fn strata_json_get(
+    db: *mut StrataDb,
+    key: *const c_char,
+    path: *const c_char,
+    out_value: *mut StrataBuffer,
+) -> i32 {
+    write_out(out_value, StrataBuffer::EMPTY);
+    guard(|| {
+        let key = str_arg(key, "key")?;
+        let path = opt_str_arg(path, "path")?.unwrap_or("$");
+        let value = db_arg(db)?.json_get(key, path)?;
+        write_found(out_value, value, || Error::DocumentNotFound {
+            key: key.into(),
+        })
+    })
+}
+
+// =============================================================================
+// Events
+// =============================================================================
+
+/// Append an event. The payload must be a JSON object.
+#[no_mangle]
+pub unsafe extern "C" fn strata_event_append(
+    db: *mut StrataDb,
+    event_type: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    out_sequence: *mut u64,
+) -> i32 {
+    guard(|| {
+        let payload = value_arg(payload, payload_len, "payload")?;
+        let sequence = db_arg(db)?.event_append(str_arg(event_type, "event_type")?, payload)?;
+        write_out(out_sequence, sequence);
+        Ok(())
+    })
+}
+
+// =============================================================================
+// Vectors
+// =============================================================================
+
+/// Create a vector collection. `metric` is one of the `STRATA_METRIC_*` values.
+#[no_mangle]
+pub unsafe extern "C" fn strata_vector_create_collection(
+    db: *mut StrataDb,
+    collection: *const c_char,
+    dimension: u64,
+    metric: u32,
+    out_version: *mut u64,
+) -> i32 {
+    guard(|| {
+        let metric = match metric {
+            STRATA_METRIC_COSINE => DistanceMetric::Cosine,
+            STRATA_METRIC_EUCLIDEAN => DistanceMetric::Euclidean,
+            STRATA_METRIC_DOT_PRODUCT => DistanceMetric::DotProduct,
+            other => {
+                return Err(Error::InvalidInput {
+                    reason: format!("unknown metric {}", other),
+                })
+            }
+        };
+        let version = db_arg(db)?.vector_create_collection(
+            str_arg(collection, "collection")?,
+            dimension,
+            metric,
+        )?;
+        write_out(out_version, version);
+        Ok(())
+    })
+}
+
+/// Insert or replace the vector under `key`. `metadata` may be NULL.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn strata_vector_upsert(
+    db: *mut StrataDb,
+    collection: *const c_char,
+    key: *const c_char,
+    vector: *const f32,
+    dimension: usize,
+    metadata: *const u8,
+    metadata_len: usize,
+    out_version: *mut u64,
+) -> i32 {
+    guard(|| {
+        let vector = slice_arg(vector, dimension, "vector")?.to_vec();
+        let metadata = opt_value_arg(metadata, metadata_len, "metadata")?;
+        let version = db_arg(db)?.vector_upsert(
+            str_arg(collection, "collection")?,
+            str_arg(key, "key")?,
+            vector,
+            metadata,
+        )?;
+        write_out(out_version, version);
+        Ok(())
+    })
+}
+
+/// Find the `k` vectors nearest to `query`.
+///
+/// Writes a JSON array of `{"key", "score", "metadata"}` objects, best
+/// match first, to `out_matches`.
+#[no_mangle]
+pub unsafe extern "C" fn strata_vector_search(
+    db: *mut StrataDb,
+    collection: *const c_char,
+    query: *const f32,
+    dimension: usize,
+    k: u64,
+    out_matches: *mut StrataBuffer,
+) -> i32 {
+    write_out(out_matches, StrataBuffer::EMPTY);
+    guard(|| {
+        let query = slice_arg(query, dimension, "query")?.to_vec();
+        let matches = db_arg(db)?.vector_search(str_arg(collection, "collection")?, query, k)?;
+        let matches: Vec<_> = matches
+            .iter()
+            .map(|m| {
+                json!({
+                    "key": m.key,
+                    "score": m.score,
+                    "metadata": m.metadata.as_ref().map(value_to_json),
+                })
+            })
+            .collect();
+        write_out(out_matches, StrataBuffer::json(&json!(matches)));
+        Ok(())
+    })
+}
@@ -0,0 +1,252 @@
+//! The C ABI called from Rust, the way a host would call it.
+
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use strata_ffi::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn open() -> *mut StrataDb {
+    let mut db = ptr::null_mut();
+    assert_eq!(unsafe { strata_open_cache(&mut db) }, STRATA_OK);
+    assert!(!db.is_null());
+    db
+}
+
+/// Take a returned buffer as a string and free it.
+fn take(buffer: StrataBuffer) -> String {
+    let text = unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) };
+    let text = String::from_utf8(text.to_vec()).unwrap();
+    unsafe { strata_buffer_free(buffer) };
+    text
+}
+
+fn last_error() -> String {
+    let message = strata_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+fn empty() -> StrataBuffer {
+    StrataBuffer {
+        data: ptr::null_mut(),
+        len: 0,
+    }
+}
+
+#[test]
+fn kv_and_json_round_trip() {
+    let db = open();
+    unsafe {
+        let value = br#"{"name": "Ann", "avatar": {"$bytes": "AAEC"}}"#;
+        let mut version = 0;
+        let status = strata_kv_put(
+            db,
+            c("user:1").as_ptr(),
+            value.as_ptr(),
+            value.len(),
+            &mut version,
+        );
+        assert_eq!(status, STRATA_OK);
+        assert!(version > 0);
+        assert!(strata_last_error().is_null());
+
+        let mut out = empty();
+        assert_eq!(strata_kv_get(db, c("user:1").as_ptr(), &mut out), STRATA_OK);
+        let json: serde_json::Value = serde_json::from_str(&take(out)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"name": "Ann", "avatar": {"$bytes": "AAEC"}})
+        );
+
+        let mut out = empty();
+        let status = strata_kv_get(db, c("missing").as_ptr(), &mut out);
+        assert_eq!(status, STRATA_NOT_FOUND);
+        assert!(out.data.is_null());
+        assert!(last_error().contains("missing"));
+
+        let doc = br#"{"user": {"name": "Ann"}}"#;
+        let status = strata_json_set(
+            db,
+            c("profile").as_ptr(),
+            ptr::null(),
+            doc.as_ptr(),
+            doc.len(),
+            ptr::null_mut(),
+        );
+        assert_eq!(status, STRATA_OK);
+        let mut out = empty();
+        let status = strata_json_get(
+            db,
+            c("profile").as_ptr(),
+            c("$.user.name").as_ptr(),
+            &mut out,
+        );
+        assert_eq!(status, STRATA_OK);
+        assert_eq!(take(out), r#""Ann""#);
+
+        strata_close(db);
+    }
+}
+
+#[test]
+fn events_and_vectors() {
+    let db = open();
+    unsafe {
+        let payload = br#"{"tool": "search"}"#;
+        let mut sequence = u64::MAX;
+        let status = strata_event_append(
+            db,
+            c("tool_call").as_ptr(),
+            payload.as_ptr(),
+            payload.len(),
+            &mut sequence,
+        );
+        assert_eq!(status, STRATA_OK);
+        assert_ne!(sequence, u64::MAX);
+
+        let docs = c("docs");
+        let status = strata_vector_create_collection(
+            db,
+            docs.as_ptr(),
+            2,
+            STRATA_METRIC_COSINE,
+            ptr::null_mut(),
+        );
+        assert_eq!(status, STRATA_OK);
+        for (key, vector) in [("a", [1.0f32, 0.0]), ("b", [0.0, 1.0])] {
+            let metadata = format!(r#"{{"key": "{}"}}"#, key);
+            let status = strata_vector_upsert(
+                db,
+                docs.as_ptr(),
+                c(key).as_ptr(),
+                vector.as_ptr(),
+                vector.len(),
+                metadata.as_ptr(),
+                metadata.len(),
+                ptr::null_mut(),
+            );
+            assert_eq!(status, STRATA_OK);
+        }
+
+        let query = [0.9f32, 0.1];
+        let mut out = empty();
+        let status =
+            strata_vector_search(db, docs.as_ptr(), query.as_ptr(), query.len(), 1, &mut out);
+        assert_eq!(status, STRATA_OK);
+        let matches: serde_json::Value = serde_json::from_str(&take(out)).unwrap();
+        assert_eq!(matches[0]["key"], "a");
+        assert_eq!(matches[0]["metadata"], serde_json::json!({"key": "a"}));
+        assert_eq!(matches.as_array().unwrap().len(), 1);
+
+        let status = strata_vector_upsert(
+            db,
+            docs.as_ptr(),
+            c("c").as_ptr(),
+            query.as_ptr(),
+            1,
+            ptr::null(),
+            0,
+            ptr::null_mut(),
+        );
+        assert_eq!(status, STRATA_INVALID_ARGUMENT);
+
+        strata_close(db);
+    }
+}
+
+#[test]
+fn bad_arguments_are_reported_not_crashed_on() {
+    let db = open();
+    unsafe {
+        let value = b"{not json";
+        let status = strata_kv_put(
+            db,
+            c("k").as_ptr(),
+            value.as_ptr(),
+            value.len(),
+            ptr::null_mut(),
+        );
+        assert_eq!(status, STRATA_INVALID_ARGUMENT);
+        assert!(last_error().contains("JSON"));
+
+        let status = strata_kv_put(db, ptr::null(), b"1".as_ptr(), 1, ptr::null_mut());
+        assert_eq!(status, STRATA_INVALID_ARGUMENT);
+        assert!(last_error().contains("key is NULL"));
+
+        let status = strata_kv_put(
+            ptr::null_mut(),
+            c("k").as_ptr(),
+            b"1".as_ptr(),
+            1,
+            ptr::null_mut(),
+        );
+        assert_eq!(status, STRATA_INVALID_ARGUMENT);
+
+        let status = strata_set_branch(db, c("nope").as_ptr());
+        assert_eq!(status, STRATA_NOT_FOUND);
+
+        let status = strata_vector_create_collection(db, c("v").as_ptr(), 2, 99, ptr::null_mut());
+        assert_eq!(status, STRATA_INVALID_ARGUMENT);
+
+        strata_close(db);
+        strata_close(ptr::null_mut());
+        strata_buffer_free(empty());
+    }
+}
+
+#[test]
+fn header_declares_every_export() {
+    let header = include_str!("../include/strata.h");
+    let source = include_str!("../src/lib.rs").to_string() + include_str!("../src/error.rs");
+
+    let exports: Vec<&str> = source
+        .split("extern \"C\" fn ")
+        .skip(1)
+        .map(|rest| &rest[..rest.find('(').unwrap()])
+        .collect();
+    assert!(exports.len() > 10);
+    for name in exports {
+        assert!(
+            header.contains(&format!("{}(", name)),
+            "{} missing from strata.h",
+            name
+        );
+    }
+
+    for (name, value) in [
+        ("STRATA_ABI_VERSION", strata_abi_version() as i64),
+        ("STRATA_OK", STRATA_OK as i64),
+        ("STRATA_NOT_FOUND", STRATA_NOT_FOUND as i64),
+        ("STRATA_INVALID_ARGUMENT", STRATA_INVALID_ARGUMENT as i64),
+        ("STRATA_CONFLICT", STRATA_CONFLICT as i64),
+        ("STRATA_ACCESS_DENIED", STRATA_ACCESS_DENIED as i64),
+        (
+            "STRATA_HISTORY_UNAVAILABLE",
+            STRATA_HISTORY_UNAVAILABLE as i64,
+        ),
+        ("STRATA_IO", STRATA_IO as i64),
+        ("STRATA_NOT_IMPLEMENTED", STRATA_NOT_IMPLEMENTED as i64),
+        ("STRATA_INTERNAL", STRATA_INTERNAL as i64),
+        ("STRATA_PANIC", STRATA_PANIC as i64),
+        ("STRATA_METRIC_COSINE", STRATA_METRIC_COSINE as i64),
+        ("STRATA_METRIC_EUCLIDEAN", STRATA_METRIC_EUCLIDEAN as i64),
+        (
+            "STRATA_METRIC_DOT_PRODUCT",
+            STRATA_METRIC_DOT_PRODUCT as i64,
+        ),
+    ] {
+        let define = format!("#define {} {}\n", name, value);
+        assert!(
+            header.contains(&define),
+            "strata.h should have {:?}",
+            define
+        );
+    }
+}
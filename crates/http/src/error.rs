@@ -0,0 +1,112 @@
+//! Error responses.
+//!
+//! Every failed request answers with a JSON body carrying the structured
+//! executor [`Error`] and its message:
+//!
+//! ```json
+//! {"error": {"KeyNotFound": {"key": "user:1"}}, "message": "key not found: user:1"}
+//! ```
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use strata_executor::Error;
+
+/// An executor error, or a request the HTTP layer rejected before execution.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    error: Error,
+}
+
+impl ApiError {
+    /// A malformed request body or query string
+    pub(crate) fn bad_request(reason: impl Into<String>) -> Self {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            error: Error::InvalidInput {
+                reason: reason.into(),
+            },
+        }
+    }
+
+    /// The command ran but returned an output the route doesn't expect
+    pub(crate) fn unexpected_output(command: &str) -> Self {
+        ApiError::from(Error::Internal {
+            reason: format!("unexpected output for {}", command),
+        })
+    }
+
+    /// The HTTP status this error is answered with
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The structured error in the response body
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError {
+            status: status_for(&error),
+            error,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({
+            "error": self.error,
+            "message": self.error.to_string(),
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// HTTP status for each error category of [`Error`].
+fn status_for(error: &Error) -> StatusCode {
+    match error {
+        Error::KeyNotFound { .. }
+        | Error::BranchNotFound { .. }
+        | Error::CollectionNotFound { .. }
+        | Error::StreamNotFound { .. }
+        | Error::CellNotFound { .. }
+        | Error::DocumentNotFound { .. }
+        | Error::CursorNotFound { .. }
+        | Error::DatabaseNotFound { .. } => StatusCode::NOT_FOUND,
+
+        Error::WrongType { .. }
+        | Error::InvalidKey { .. }
+        | Error::InvalidPath { .. }
+        | Error::InvalidInput { .. }
+        | Error::DimensionMismatch { .. }
+        | Error::ConstraintViolation { .. }
+        | Error::Overflow { .. }
+        | Error::Serialization { .. }
+        | Error::TransactionNotActive
+        | Error::TransactionAlreadyActive => StatusCode::BAD_REQUEST,
+
+        Error::VersionConflict { .. }
+        | Error::TransitionFailed { .. }
+        | Error::Conflict { .. }
+        | Error::TransactionConflict { .. }
+        | Error::BranchClosed { .. }
+        | Error::BranchExists { .. }
+        | Error::CollectionExists { .. }
+        | Error::AlreadyOpen { .. } => StatusCode::CONFLICT,
+
+        Error::HistoryTrimmed { .. } | Error::HistoryUnavailable { .. } => StatusCode::GONE,
+
+        Error::AccessDenied { .. } => StatusCode::FORBIDDEN,
+
+        Error::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED,
+
+        Error::Io { .. } | Error::Cancelled { .. } | Error::Internal { .. } => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
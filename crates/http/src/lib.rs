@@ -0,0 +1,73 @@
+//! HTTP/REST interface for Strata
+//!
+//! Serves the executor's commands over HTTP for clients that don't embed
+//! the Rust crate. [`router`] builds an [`axum`] router over a database
+//! handle; [`serve`] runs it on a listener.
+//!
+//! ```text
+//! let db = Strata::open("/data/agents")?;
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:7070").await?;
+//! strata_http::serve(db, listener).await?;
+//! ```
+//!
+//! # Routes
+//!
+//! | Route | Command |
+//! |-------|---------|
+//! | `GET /branches/{branch}/kv` | `KvList` (`prefix`, `cursor`, `limit`) |
+//! | `GET /branches/{branch}/kv/{key}` | `KvGet` (`as_of`) |
+//! | `PUT /branches/{branch}/kv/{key}` | `KvPut` |
+//! | `DELETE /branches/{branch}/kv/{key}` | `KvDelete` |
+//! | `GET /branches/{branch}/json/{key}` | `JsonGet` (`path`, `as_of`) |
+//! | `PUT /branches/{branch}/json/{key}` | `JsonSet` (`path`) |
+//! | `DELETE /branches/{branch}/json/{key}` | `JsonDelete` (`path`) |
+//! | `POST /branches/{branch}/events/{event_type}` | `EventAppend` |
+//! | `PUT /branches/{branch}/vectors/{collection}/{key}` | `VectorUpsert` |
+//! | `POST /branches/{branch}/vectors/{collection}/search` | `VectorSearch` |
+//! | `POST /execute` | Any `Command`, in its serde form |
+//! | `GET /openapi.json` | OpenAPI 3.0 description of the above |
+//!
+//! Every route takes a `space` query parameter. Values in bodies use the
+//! wire encoding of `value_to_json`/`json_to_value`: plain JSON, with
+//! `{"$bytes": ...}` and `{"$f64": ...}` for bytes and non-finite floats.
+//! Errors answer with a status for their category and a body holding the
+//! structured [`Error`](strata_executor::Error).
+
+mod error;
+mod openapi;
+mod routes;
+
+use std::sync::Arc;
+
+use axum::Router;
+use strata_executor::Strata;
+
+pub use error::ApiError;
+pub use openapi::spec;
+
+/// Build the router serving every route over `db`.
+///
+/// The handle's current branch and space are not used: each route names
+/// its branch, and its space defaults to `default`.
+pub fn router(db: Strata) -> Router {
+    let router = routes::ROUTES.iter().fold(Router::new(), |router, route| {
+        router.route(&axum_path(route.path), (route.handler)())
+    });
+    router.with_state(Arc::new(db))
+}
+
+/// Serve [`router`] on `listener` until the listener fails.
+pub async fn serve(db: Strata, listener: tokio::net::TcpListener) -> std::io::Result<()> {
+    axum::serve(listener, router(db)).await
+}
+
+/// `{param}` placeholders to axum's `:param` captures.
+fn axum_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(name) => format!(":{}", name.trim_end_matches('}')),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
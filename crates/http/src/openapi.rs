@@ -0,0 +1,188 @@
+//! OpenAPI 3.0 description of the routes.
+//!
+//! Generated from the route table in [`routes`](crate::routes), so the
+//! spec lists exactly the routes the router serves.
+
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::routes::ROUTES;
+
+/// The OpenAPI document served at `/openapi.json`.
+pub fn spec() -> JsonValue {
+    let mut paths = Map::new();
+    for route in ROUTES {
+        let mut parameters: Vec<JsonValue> = path_params(route.path)
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                })
+            })
+            .collect();
+        parameters.extend(route.query.iter().map(|param| {
+            json!({
+                "name": param.name,
+                "in": "query",
+                "required": false,
+                "description": param.description,
+                "schema": { "type": param.kind },
+            })
+        }));
+
+        let mut operation = json!({
+            "summary": route.summary,
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": "Success",
+                    "content": { "application/json": { "schema": schema_ref(route.response) } },
+                },
+                "default": {
+                    "description": "Error",
+                    "content": { "application/json": { "schema": schema_ref("Error") } },
+                },
+            },
+        });
+        if let Some(body) = route.body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema_ref(body) } },
+            });
+        }
+
+        let item = paths
+            .entry(route.path)
+            .or_insert_with(|| JsonValue::Object(Map::new()));
+        item[route.method] = operation;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Strata",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": { "schemas": schemas() },
+    })
+}
+
+/// Names of the `{param}` placeholders in `path`.
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+}
+
+fn schema_ref(name: &str) -> JsonValue {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+fn schemas() -> JsonValue {
+    json!({
+        "Value": {
+            "description": "Any JSON value. Bytes are written as {\"$bytes\": \"<base64>\"} \
+                            and NaN, infinities and -0.0 as {\"$f64\": \"NaN\"} and so on.",
+        },
+        "VersionedValue": {
+            "type": "object",
+            "required": ["value"],
+            "properties": {
+                "value": schema_ref("Value"),
+                "version": { "type": "integer" },
+                "timestamp": { "type": "integer" },
+            },
+        },
+        "Version": {
+            "type": "object",
+            "required": ["version"],
+            "properties": { "version": { "type": "integer" } },
+        },
+        "Keys": {
+            "type": "object",
+            "required": ["keys"],
+            "properties": { "keys": { "type": "array", "items": { "type": "string" } } },
+        },
+        "Deleted": {
+            "type": "object",
+            "required": ["deleted"],
+            "properties": { "deleted": { "type": "boolean" } },
+        },
+        "Removed": {
+            "type": "object",
+            "required": ["removed"],
+            "properties": { "removed": { "type": "integer" } },
+        },
+        "VectorUpsert": {
+            "type": "object",
+            "required": ["vector"],
+            "properties": {
+                "vector": { "type": "array", "items": { "type": "number" } },
+                "metadata": schema_ref("Value"),
+                "namespace": { "type": "string" },
+            },
+        },
+        "VectorSearch": {
+            "type": "object",
+            "required": ["query", "k"],
+            "properties": {
+                "query": { "type": "array", "items": { "type": "number" } },
+                "k": { "type": "integer" },
+                "filter": { "type": "array", "items": schema_ref("MetadataFilter") },
+                "metric": { "type": "string", "enum": ["cosine", "euclidean", "dot_product"] },
+                "namespace": { "type": "string" },
+            },
+        },
+        "MetadataFilter": {
+            "type": "object",
+            "required": ["op"],
+            "properties": {
+                "field": { "type": "string" },
+                "op": {
+                    "type": "string",
+                    "enum": [
+                        "eq", "ne", "gt", "gte", "lt", "lte", "in", "contains",
+                        "prefix", "range", "or", "not",
+                    ],
+                },
+                "value": schema_ref("Value"),
+                "filters": { "type": "array", "items": schema_ref("MetadataFilter") },
+            },
+        },
+        "VectorMatches": {
+            "type": "object",
+            "required": ["matches"],
+            "properties": {
+                "matches": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "key": { "type": "string" },
+                            "score": { "type": "number" },
+                            "metadata": schema_ref("Value"),
+                        },
+                    },
+                },
+            },
+        },
+        "Command": {
+            "type": "object",
+            "description": "A command in its serde form, e.g. {\"KvGet\": {\"key\": \"k\"}}. \
+                            Values inside use the tagged form, e.g. {\"Int\": 1}.",
+        },
+        "Output": {
+            "description": "The command's output in its serde form, e.g. {\"Version\": 1}.",
+        },
+        "OpenApi": { "type": "object" },
+        "Error": {
+            "type": "object",
+            "required": ["error", "message"],
+            "properties": {
+                "error": { "description": "The structured error, e.g. {\"KeyNotFound\": {\"key\": \"k\"}}" },
+                "message": { "type": "string" },
+            },
+        },
+    })
+}
@@ -0,0 +1,579 @@
+//! REST routes and the handlers behind them.
+//!
+//! Each route builds one [`Command`], runs it on a blocking thread and maps
+//! the [`Output`] to a small JSON body. Values in request and response
+//! bodies use the wire encoding (`{"$bytes": ...}`, `{"$f64": ...}`), so
+//! they are plain JSON for every type that has one.
+
+use std::sync::Arc;
+
+use axum::async_trait;
+use axum::body::Bytes;
+use axum::extract::{FromRequestParts, Path, Query, State};
+use axum::http::request::Parts;
+use axum::routing::{delete, get, post, put, MethodRouter};
+use axum::Json;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+use strata_executor::{
+    json_to_value, value_to_json, BranchId, Command, DistanceMetric, Error, FilterOp,
+    MetadataFilter, Output, Strata, Value, VersionedValue,
+};
+
+use crate::error::ApiError;
+use crate::openapi;
+
+/// Shared handle the handlers execute commands through.
+pub(crate) type Db = Arc<Strata>;
+
+type Reply = Result<Json<JsonValue>, ApiError>;
+
+/// A query string parameter of a route.
+pub(crate) struct Param {
+    pub name: &'static str,
+    /// OpenAPI type: `string` or `integer`
+    pub kind: &'static str,
+    pub description: &'static str,
+}
+
+/// One route: what the OpenAPI spec says about it and the handler serving it.
+pub(crate) struct Route {
+    pub method: &'static str,
+    /// Path with `{param}` placeholders, as written in the spec
+    pub path: &'static str,
+    pub summary: &'static str,
+    pub query: &'static [Param],
+    /// Schema of the request body, if the route takes one
+    pub body: Option<&'static str>,
+    /// Schema of the success response
+    pub response: &'static str,
+    pub handler: fn() -> MethodRouter<Db>,
+}
+
+const SPACE: Param = Param {
+    name: "space",
+    kind: "string",
+    description: "Space within the branch (default: `default`)",
+};
+const AS_OF: Param = Param {
+    name: "as_of",
+    kind: "integer",
+    description: "Read as of this timestamp (microseconds since the epoch)",
+};
+const PATH: Param = Param {
+    name: "path",
+    kind: "string",
+    description: "JSON path inside the document (default: `$`)",
+};
+
+/// Every route the router serves, in spec order.
+pub(crate) const ROUTES: &[Route] = &[
+    Route {
+        method: "get",
+        path: "/branches/{branch}/kv",
+        summary: "List keys",
+        query: &[
+            SPACE,
+            Param {
+                name: "prefix",
+                kind: "string",
+                description: "Only keys starting with this prefix",
+            },
+            Param {
+                name: "cursor",
+                kind: "string",
+                description: "Resume after this key",
+            },
+            Param {
+                name: "limit",
+                kind: "integer",
+                description: "Maximum number of keys",
+            },
+        ],
+        body: None,
+        response: "Keys",
+        handler: || get(kv_list),
+    },
+    Route {
+        method: "get",
+        path: "/branches/{branch}/kv/{key}",
+        summary: "Get a value",
+        query: &[SPACE, AS_OF],
+        body: None,
+        response: "VersionedValue",
+        handler: || get(kv_get),
+    },
+    Route {
+        method: "put",
+        path: "/branches/{branch}/kv/{key}",
+        summary: "Put a value",
+        query: &[SPACE],
+        body: Some("Value"),
+        response: "Version",
+        handler: || put(kv_put),
+    },
+    Route {
+        method: "delete",
+        path: "/branches/{branch}/kv/{key}",
+        summary: "Delete a key",
+        query: &[SPACE],
+        body: None,
+        response: "Deleted",
+        handler: || delete(kv_delete),
+    },
+    Route {
+        method: "get",
+        path: "/branches/{branch}/json/{key}",
+        summary: "Get a document or a path inside it",
+        query: &[SPACE, PATH, AS_OF],
+        body: None,
+        response: "VersionedValue",
+        handler: || get(json_get),
+    },
+    Route {
+        method: "put",
+        path: "/branches/{branch}/json/{key}",
+        summary: "Set a document or a path inside it",
+        query: &[SPACE, PATH],
+        body: Some("Value"),
+        response: "Version",
+        handler: || put(json_set),
+    },
+    Route {
+        method: "delete",
+        path: "/branches/{branch}/json/{key}",
+        summary: "Delete a document or a path inside it",
+        query: &[SPACE, PATH],
+        body: None,
+        response: "Removed",
+        handler: || delete(json_delete),
+    },
+    Route {
+        method: "post",
+        path: "/branches/{branch}/events/{event_type}",
+        summary: "Append an event",
+        query: &[SPACE],
+        body: Some("Value"),
+        response: "Version",
+        handler: || post(event_append),
+    },
+    Route {
+        method: "put",
+        path: "/branches/{branch}/vectors/{collection}/{key}",
+        summary: "Insert or replace a vector",
+        query: &[SPACE],
+        body: Some("VectorUpsert"),
+        response: "Version",
+        handler: || put(vector_upsert),
+    },
+    Route {
+        method: "post",
+        path: "/branches/{branch}/vectors/{collection}/search",
+        summary: "Search a collection by similarity",
+        query: &[SPACE],
+        body: Some("VectorSearch"),
+        response: "VectorMatches",
+        handler: || post(vector_search),
+    },
+    Route {
+        method: "post",
+        path: "/execute",
+        summary: "Execute any command",
+        query: &[],
+        body: Some("Command"),
+        response: "Output",
+        handler: || post(execute),
+    },
+    Route {
+        method: "get",
+        path: "/openapi.json",
+        summary: "This OpenAPI document",
+        query: &[],
+        body: None,
+        response: "OpenApi",
+        handler: || get(spec),
+    },
+];
+
+// =============================================================================
+// Extraction helpers
+// =============================================================================
+
+/// Query string extractor that rejects with an [`ApiError`] body.
+struct Args<T>(T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Args<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        Query::<T>::from_request_parts(parts, state)
+            .await
+            .map(|Query(args)| Args(args))
+            .map_err(|e| ApiError::bad_request(e.body_text()))
+    }
+}
+
+/// A request body holding a single value in the wire encoding.
+fn wire_body(body: &[u8]) -> Result<Value, ApiError> {
+    let json: JsonValue = json_body(body)?;
+    json_to_value(&json).map_err(ApiError::bad_request)
+}
+
+/// A request body deserialized into `T`.
+fn json_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|e| ApiError::bad_request(format!("invalid body: {}", e)))
+}
+
+/// Run `cmd` off the async runtime; the executor blocks on I/O and locks.
+async fn run(db: Db, cmd: Command) -> Result<Output, ApiError> {
+    tokio::task::spawn_blocking(move || db.executor().execute(cmd))
+        .await
+        .map_err(|e| {
+            ApiError::from(Error::Internal {
+                reason: format!("command task failed: {}", e),
+            })
+        })?
+        .map_err(ApiError::from)
+}
+
+fn versioned(vv: VersionedValue) -> JsonValue {
+    json!({
+        "value": value_to_json(&vv.value),
+        "version": vv.version,
+        "timestamp": vv.timestamp,
+    })
+}
+
+/// Body for a read that found `output`, or `not_found` if it found nothing.
+///
+/// Reads `as_of` a timestamp return the bare value without a version.
+fn found(output: Output, command: &str, not_found: impl FnOnce() -> Error) -> Reply {
+    match output {
+        Output::MaybeVersioned(Some(vv)) => Ok(Json(versioned(vv))),
+        Output::Maybe(Some(value)) => Ok(Json(json!({ "value": value_to_json(&value) }))),
+        Output::MaybeVersioned(None) | Output::Maybe(None) => Err(not_found().into()),
+        _ => Err(ApiError::unexpected_output(command)),
+    }
+}
+
+fn version(output: Output, command: &str) -> Reply {
+    match output {
+        Output::Version(version) => Ok(Json(json!({ "version": version }))),
+        _ => Err(ApiError::unexpected_output(command)),
+    }
+}
+
+// =============================================================================
+// KV
+// =============================================================================
+
+#[derive(Deserialize)]
+struct Scope {
+    space: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReadArgs {
+    space: Option<String>,
+    as_of: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ListArgs {
+    space: Option<String>,
+    prefix: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u64>,
+}
+
+async fn kv_list(
+    State(db): State<Db>,
+    Path(branch): Path<String>,
+    Args(args): Args<ListArgs>,
+) -> Reply {
+    let cmd = Command::KvList {
+        branch: Some(BranchId::from(branch)),
+        space: args.space,
+        prefix: args.prefix,
+        cursor: args.cursor,
+        limit: args.limit,
+        as_of: None,
+    };
+    match run(db, cmd).await? {
+        Output::Keys(keys) => Ok(Json(json!({ "keys": keys }))),
+        _ => Err(ApiError::unexpected_output("KvList")),
+    }
+}
+
+async fn kv_get(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(args): Args<ReadArgs>,
+) -> Reply {
+    let cmd = Command::KvGet {
+        branch: Some(BranchId::from(branch)),
+        space: args.space,
+        key: key.clone(),
+        as_of: args.as_of,
+    };
+    found(run(db, cmd).await?, "KvGet", || Error::KeyNotFound { key })
+}
+
+async fn kv_put(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(scope): Args<Scope>,
+    body: Bytes,
+) -> Reply {
+    let cmd = Command::KvPut {
+        branch: Some(BranchId::from(branch)),
+        space: scope.space,
+        key,
+        value: wire_body(&body)?,
+    };
+    version(run(db, cmd).await?, "KvPut")
+}
+
+async fn kv_delete(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(scope): Args<Scope>,
+) -> Reply {
+    let cmd = Command::KvDelete {
+        branch: Some(BranchId::from(branch)),
+        space: scope.space,
+        key,
+    };
+    match run(db, cmd).await? {
+        Output::Bool(deleted) => Ok(Json(json!({ "deleted": deleted }))),
+        _ => Err(ApiError::unexpected_output("KvDelete")),
+    }
+}
+
+// =============================================================================
+// JSON documents
+// =============================================================================
+
+#[derive(Deserialize)]
+struct JsonArgs {
+    space: Option<String>,
+    path: Option<String>,
+    as_of: Option<u64>,
+}
+
+fn doc_path(path: Option<String>) -> String {
+    path.unwrap_or_else(|| "$".to_string())
+}
+
+async fn json_get(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(args): Args<JsonArgs>,
+) -> Reply {
+    let cmd = Command::JsonGet {
+        branch: Some(BranchId::from(branch)),
+        space: args.space,
+        key: key.clone(),
+        path: doc_path(args.path),
+        as_of: args.as_of,
+    };
+    found(run(db, cmd).await?, "JsonGet", || Error::DocumentNotFound {
+        key,
+    })
+}
+
+async fn json_set(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(args): Args<JsonArgs>,
+    body: Bytes,
+) -> Reply {
+    let cmd = Command::JsonSet {
+        branch: Some(BranchId::from(branch)),
+        space: args.space,
+        key,
+        path: doc_path(args.path),
+        value: wire_body(&body)?,
+    };
+    version(run(db, cmd).await?, "JsonSet")
+}
+
+async fn json_delete(
+    State(db): State<Db>,
+    Path((branch, key)): Path<(String, String)>,
+    Args(args): Args<JsonArgs>,
+) -> Reply {
+    let cmd = Command::JsonDelete {
+        branch: Some(BranchId::from(branch)),
+        space: args.space,
+        key,
+        path: doc_path(args.path),
+    };
+    match run(db, cmd).await? {
+        Output::Uint(removed) => Ok(Json(json!({ "removed": removed }))),
+        _ => Err(ApiError::unexpected_output("JsonDelete")),
+    }
+}
+
+// =============================================================================
+// Events
+// =============================================================================
+
+async fn event_append(
+    State(db): State<Db>,
+    Path((branch, event_type)): Path<(String, String)>,
+    Args(scope): Args<Scope>,
+    body: Bytes,
+) -> Reply {
+    let cmd = Command::EventAppend {
+        branch: Some(BranchId::from(branch)),
+        space: scope.space,
+        event_type,
+        payload: wire_body(&body)?,
+    };
+    version(run(db, cmd).await?, "EventAppend")
+}
+
+// =============================================================================
+// Vectors
+// =============================================================================
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VectorUpsertBody {
+    vector: Vec<f32>,
+    #[serde(default)]
+    metadata: Option<JsonValue>,
+    #[serde(default)]
+    namespace: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VectorSearchBody {
+    query: Vec<f32>,
+    k: u64,
+    #[serde(default)]
+    filter: Option<Vec<FilterBody>>,
+    #[serde(default)]
+    metric: Option<DistanceMetric>,
+    #[serde(default)]
+    namespace: Option<String>,
+}
+
+/// A metadata filter whose `value` is plain wire JSON.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterBody {
+    #[serde(default)]
+    field: String,
+    op: FilterOp,
+    #[serde(default)]
+    value: JsonValue,
+    #[serde(default)]
+    filters: Vec<FilterBody>,
+}
+
+impl FilterBody {
+    fn into_filter(self) -> Result<MetadataFilter, ApiError> {
+        Ok(MetadataFilter {
+            field: self.field,
+            op: self.op,
+            value: json_to_value(&self.value).map_err(ApiError::bad_request)?,
+            filters: self
+                .filters
+                .into_iter()
+                .map(FilterBody::into_filter)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+async fn vector_upsert(
+    State(db): State<Db>,
+    Path((branch, collection, key)): Path<(String, String, String)>,
+    Args(scope): Args<Scope>,
+    body: Bytes,
+) -> Reply {
+    let body: VectorUpsertBody = json_body(&body)?;
+    let metadata = body
+        .metadata
+        .map(|m| json_to_value(&m).map_err(ApiError::bad_request))
+        .transpose()?;
+    let cmd = Command::VectorUpsert {
+        branch: Some(BranchId::from(branch)),
+        space: scope.space,
+        collection,
+        key,
+        vector: body.vector,
+        metadata,
+        namespace: body.namespace,
+    };
+    version(run(db, cmd).await?, "VectorUpsert")
+}
+
+async fn vector_search(
+    State(db): State<Db>,
+    Path((branch, collection)): Path<(String, String)>,
+    Args(scope): Args<Scope>,
+    body: Bytes,
+) -> Reply {
+    let body: VectorSearchBody = json_body(&body)?;
+    let filter = body
+        .filter
+        .map(|filters| {
+            filters
+                .into_iter()
+                .map(FilterBody::into_filter)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    let cmd = Command::VectorSearch {
+        branch: Some(BranchId::from(branch)),
+        space: scope.space,
+        collection,
+        query: body.query,
+        k: body.k,
+        filter,
+        metric: body.metric,
+        as_of: None,
+        namespace: body.namespace,
+    };
+    match run(db, cmd).await? {
+        Output::VectorMatches(matches) => {
+            let matches: Vec<JsonValue> = matches
+                .into_iter()
+                .map(|m| {
+                    json!({
+                        "key": m.key,
+                        "score": m.score,
+                        "metadata": m.metadata.as_ref().map(value_to_json),
+                    })
+                })
+                .collect();
+            Ok(Json(json!({ "matches": matches })))
+        }
+        _ => Err(ApiError::unexpected_output("VectorSearch")),
+    }
+}
+
+// =============================================================================
+// Commands and spec
+// =============================================================================
+
+/// Run a command in its serde form and answer with the output in its serde
+/// form, for everything the REST routes don't cover.
+async fn execute(State(db): State<Db>, body: Bytes) -> Result<Json<Output>, ApiError> {
+    let cmd: Command = json_body(&body)?;
+    Ok(Json(run(db, cmd).await?))
+}
+
+async fn spec() -> Json<JsonValue> {
+    Json(openapi::spec())
+}
@@ -0,0 +1,228 @@
+//! Requests through the router against an in-memory database.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use serde_json::{json, Value as JsonValue};
+use strata_executor::{DistanceMetric, Strata, Value};
+use tower::ServiceExt;
+
+fn app() -> (Strata, Router) {
+    let db = Strata::cache().unwrap();
+    let app = strata_http::router(db.new_handle().unwrap());
+    (db, app)
+}
+
+async fn call(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<JsonValue>,
+) -> (StatusCode, JsonValue) {
+    let body = match body {
+        Some(json) => Body::from(json.to_string()),
+        None => Body::empty(),
+    };
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(body)
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = if bytes.is_empty() {
+        JsonValue::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, json)
+}
+
+#[tokio::test]
+async fn kv_round_trip_uses_wire_values() {
+    let (db, app) = app();
+
+    let (status, body) = call(
+        &app,
+        "PUT",
+        "/branches/default/kv/blob",
+        Some(json!({"$bytes": "AAEC"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["version"].is_u64());
+    assert_eq!(
+        db.kv_get("blob").unwrap(),
+        Some(Value::Bytes(vec![0, 1, 2]))
+    );
+
+    let (status, body) = call(&app, "GET", "/branches/default/kv/blob", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["value"], json!({"$bytes": "AAEC"}));
+
+    call(&app, "PUT", "/branches/default/kv/blog", Some(json!(1))).await;
+    let (_, body) = call(&app, "GET", "/branches/default/kv?prefix=blo&limit=1", None).await;
+    assert_eq!(body, json!({"keys": ["blob"]}));
+
+    let (_, body) = call(&app, "DELETE", "/branches/default/kv/blob", None).await;
+    assert_eq!(body, json!({"deleted": true}));
+
+    let (status, body) = call(&app, "GET", "/branches/default/kv/blob", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"], json!({"KeyNotFound": {"key": "blob"}}));
+}
+
+#[tokio::test]
+async fn json_paths_and_spaces() {
+    let (_db, app) = app();
+
+    let doc = json!({"user": {"name": "Ann"}, "tags": ["a"]});
+    let (status, _) = call(
+        &app,
+        "PUT",
+        "/branches/default/json/profile?space=crm",
+        Some(doc),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    call(
+        &app,
+        "PUT",
+        "/branches/default/json/profile?space=crm&path=$.user.name",
+        Some(json!("Bo")),
+    )
+    .await;
+
+    let (_, body) = call(
+        &app,
+        "GET",
+        "/branches/default/json/profile?space=crm&path=$.user",
+        None,
+    )
+    .await;
+    assert_eq!(body["value"], json!({"name": "Bo"}));
+
+    let (status, _) = call(&app, "GET", "/branches/default/json/profile", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (_, body) = call(
+        &app,
+        "DELETE",
+        "/branches/default/json/profile?space=crm&path=$.tags",
+        None,
+    )
+    .await;
+    assert_eq!(body, json!({"removed": 1}));
+}
+
+#[tokio::test]
+async fn events_and_vector_search() {
+    let (db, app) = app();
+
+    let (status, _) = call(
+        &app,
+        "POST",
+        "/branches/default/events/tool_call",
+        Some(json!({"tool": "search"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(db.event_len().unwrap(), 1);
+
+    db.vector_create_collection("docs", 2, DistanceMetric::Cosine)
+        .unwrap();
+    for (key, vector, lang) in [("a", [1.0, 0.0], "en"), ("b", [0.9, 0.1], "de")] {
+        let body = json!({"vector": vector, "metadata": {"lang": lang}});
+        let (status, _) = call(
+            &app,
+            "PUT",
+            &format!("/branches/default/vectors/docs/{}", key),
+            Some(body),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let search = json!({
+        "query": [1.0, 0.0],
+        "k": 5,
+        "filter": [{"field": "lang", "op": "eq", "value": "de"}],
+    });
+    let (status, body) = call(
+        &app,
+        "POST",
+        "/branches/default/vectors/docs/search",
+        Some(search),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let matches = body["matches"].as_array().unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["key"], "b");
+    assert_eq!(matches[0]["metadata"], json!({"lang": "de"}));
+}
+
+#[tokio::test]
+async fn errors_carry_status_and_structured_body() {
+    let (_db, app) = app();
+
+    let (status, body) = call(&app, "PUT", "/branches/nope/kv/k", Some(json!(1))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"]["BranchNotFound"].is_object());
+    assert!(body["message"].as_str().unwrap().contains("nope"));
+
+    let request = Request::builder()
+        .method("PUT")
+        .uri("/branches/default/kv/k")
+        .body(Body::from("{not json"))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let (status, body) = call(&app, "GET", "/branches/default/kv/k?as_of=yesterday", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]["InvalidInput"].is_object());
+}
+
+#[tokio::test]
+async fn execute_runs_any_command() {
+    let (db, app) = app();
+    db.kv_put("k", 7i64).unwrap();
+
+    let (status, body) = call(
+        &app,
+        "POST",
+        "/execute",
+        Some(json!({"KvGet": {"key": "k"}})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["MaybeVersioned"]["value"], json!({"Int": 7}));
+}
+
+#[tokio::test]
+async fn openapi_describes_every_route() {
+    let (_db, app) = app();
+    let (status, spec) = call(&app, "GET", "/openapi.json", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(spec, strata_http::spec());
+
+    let put = &spec["paths"]["/branches/{branch}/kv/{key}"]["put"];
+    let params: Vec<&str> = put["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(params, ["branch", "key", "space"]);
+
+    // Every schema reference resolves
+    let text = spec.to_string();
+    for reference in text.split("\"#/components/schemas/").skip(1) {
+        let name = &reference[..reference.find('"').unwrap()];
+        assert!(spec["components"]["schemas"][name].is_object(), "{}", name);
+    }
+}
@@ -0,0 +1,410 @@
+//! Text chunking for document embedding
+//!
+//! An embedding model sees a bounded number of tokens, and one vector for a
+//! whole document blurs every passage in it. This module splits text into
+//! overlapping chunks so each chunk is embedded on its own and retrieval can
+//! point at the passage that matched.
+//!
+//! Three strategies are provided:
+//! - [`ChunkStrategy::FixedSize`]: windows of at most `chunk_size` bytes
+//! - [`ChunkStrategy::Sentence`]: whole sentences packed up to `chunk_size`
+//! - [`ChunkStrategy::Recursive`]: split on paragraphs, then lines, then
+//!   sentences, then words, keeping the largest units that fit
+//!
+//! Sizes and offsets are in bytes and always fall on UTF-8 character
+//! boundaries. Chunks are trimmed of surrounding whitespace, so
+//! `&text[chunk.start..chunk.end] == chunk.text`.
+
+/// Default chunk size in bytes (≈256 tokens, the embedding model's input limit)
+pub const DEFAULT_CHUNK_SIZE: usize = 1024;
+
+/// Default overlap between consecutive chunks in bytes
+pub const DEFAULT_CHUNK_OVERLAP: usize = 128;
+
+/// Separators tried by the recursive strategy, coarsest first
+const RECURSIVE_SEPARATORS: [&str; 4] = ["\n\n", "\n", ". ", " "];
+
+/// How text is split into chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkStrategy {
+    /// Fixed-size windows, ignoring text structure
+    FixedSize,
+    /// Whole sentences; a sentence longer than a chunk is split by size
+    Sentence,
+    /// Paragraphs, then lines, sentences and words, as large as fit
+    #[default]
+    Recursive,
+}
+
+/// Chunking parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkConfig {
+    /// Splitting strategy
+    pub strategy: ChunkStrategy,
+    /// Maximum chunk length in bytes
+    pub chunk_size: usize,
+    /// Bytes of context repeated from the end of the previous chunk
+    ///
+    /// Structured strategies repeat whole units (sentences, words), so the
+    /// actual overlap is at most this much.
+    pub overlap: usize,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        ChunkConfig {
+            strategy: ChunkStrategy::default(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            overlap: DEFAULT_CHUNK_OVERLAP,
+        }
+    }
+}
+
+/// A chunk of text with its byte offsets in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chunk<'a> {
+    /// The chunk text
+    pub text: &'a str,
+    /// Byte offset of the first byte in the source text
+    pub start: usize,
+    /// Byte offset one past the last byte in the source text
+    pub end: usize,
+}
+
+/// Split `text` into chunks according to `config`.
+///
+/// Text that fits in one chunk yields a single chunk. Whitespace-only text
+/// yields none.
+pub fn chunk_text<'a>(text: &'a str, config: &ChunkConfig) -> Vec<Chunk<'a>> {
+    let size = config.chunk_size.max(1);
+    let overlap = config.overlap.min(size - 1);
+    let spans = match config.strategy {
+        ChunkStrategy::FixedSize => fixed_windows(text, size, overlap),
+        ChunkStrategy::Sentence => {
+            let mut units = Vec::new();
+            for (start, end) in sentence_spans(text) {
+                fixed_units(text, start, end, size, &mut units);
+            }
+            pack(&units, size, overlap)
+        }
+        ChunkStrategy::Recursive => {
+            let mut units = Vec::new();
+            recursive_units(text, 0, text.len(), &RECURSIVE_SEPARATORS, size, &mut units);
+            pack(&units, size, overlap)
+        }
+    };
+
+    spans
+        .into_iter()
+        .filter_map(|(start, end)| trimmed(text, start, end))
+        .collect()
+}
+
+/// Overlapping windows of at most `size` bytes
+fn fixed_windows(text: &str, size: usize, overlap: usize) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let end = window_end(text, start, size);
+        spans.push((start, end));
+        if end == text.len() {
+            break;
+        }
+        // Step back for the overlap, but always move forward
+        let next = floor_char_boundary(text, end - overlap);
+        start = if next > start { next } else { end };
+    }
+    spans
+}
+
+/// Non-overlapping pieces of `start..end`, each at most `size` bytes
+fn fixed_units(text: &str, start: usize, end: usize, size: usize, out: &mut Vec<(usize, usize)>) {
+    let mut start = start;
+    while start < end {
+        let piece_end = window_end(&text[..end], start, size);
+        out.push((start, piece_end));
+        start = piece_end;
+    }
+}
+
+/// End of a window starting at `start`: at most `size` bytes, on a char
+/// boundary, and never empty
+fn window_end(text: &str, start: usize, size: usize) -> usize {
+    if text.len() - start <= size {
+        return text.len();
+    }
+    let end = floor_char_boundary(text, start + size);
+    if end > start {
+        end
+    } else {
+        // A single character wider than `size`
+        start + text[start..].chars().next().map_or(1, char::len_utf8)
+    }
+}
+
+/// Sentence spans covering the whole text
+///
+/// A sentence ends after `.`, `!` or `?` followed by whitespace, or at a
+/// line break; trailing whitespace belongs to the sentence it follows.
+fn sentence_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let ends_sentence = match c {
+            '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_some_and(|&(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if ends_sentence {
+            let mut end = i + c.len_utf8();
+            while let Some(&(j, next)) = chars.peek() {
+                if !next.is_whitespace() {
+                    break;
+                }
+                end = j + next.len_utf8();
+                chars.next();
+            }
+            spans.push((start, end));
+            start = end;
+        }
+    }
+    if start < text.len() {
+        spans.push((start, text.len()));
+    }
+    spans
+}
+
+/// Split `start..end` into units of at most `size` bytes, using the
+/// coarsest separator that makes them fit
+fn recursive_units(
+    text: &str,
+    start: usize,
+    end: usize,
+    separators: &[&str],
+    size: usize,
+    out: &mut Vec<(usize, usize)>,
+) {
+    if end - start <= size {
+        out.push((start, end));
+        return;
+    }
+    let Some((separator, finer)) = separators.split_first() else {
+        fixed_units(text, start, end, size, out);
+        return;
+    };
+
+    // Each piece keeps the separator that ends it
+    let mut piece_start = start;
+    for (offset, _) in text[start..end].match_indices(separator) {
+        let piece_end = start + offset + separator.len();
+        if piece_end > piece_start {
+            recursive_units(text, piece_start, piece_end, finer, size, out);
+            piece_start = piece_end;
+        }
+    }
+    if piece_start < end {
+        recursive_units(text, piece_start, end, finer, size, out);
+    }
+}
+
+/// Merge contiguous units into chunks of at most `size` bytes
+///
+/// Each chunk after the first starts with the trailing units of the
+/// previous chunk that fit in `overlap` bytes.
+fn pack(units: &[(usize, usize)], size: usize, overlap: usize) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut first = 0;
+    while first < units.len() {
+        let start = units[first].0;
+        let mut last = first;
+        while last + 1 < units.len() && units[last + 1].1 - start <= size {
+            last += 1;
+        }
+        let end = units[last].1;
+        spans.push((start, end));
+        if last + 1 == units.len() {
+            break;
+        }
+
+        let mut next = last + 1;
+        while next > first + 1 && end - units[next - 1].0 <= overlap {
+            next -= 1;
+        }
+        first = next;
+    }
+    spans
+}
+
+/// The chunk for `start..end` without surrounding whitespace, if any text
+/// remains
+fn trimmed(text: &str, start: usize, end: usize) -> Option<Chunk<'_>> {
+    let span = &text[start..end];
+    let trimmed = span.trim_start();
+    let start = start + (span.len() - trimmed.len());
+    let trimmed = trimmed.trim_end();
+    if trimmed.is_empty() {
+        return None;
+    }
+    Some(Chunk {
+        text: trimmed,
+        start,
+        end: start + trimmed.len(),
+    })
+}
+
+/// Largest char boundary at or before `index`
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(strategy: ChunkStrategy, chunk_size: usize, overlap: usize) -> ChunkConfig {
+        ChunkConfig {
+            strategy,
+            chunk_size,
+            overlap,
+        }
+    }
+
+    fn assert_offsets(text: &str, chunks: &[Chunk<'_>], size: usize) {
+        for chunk in chunks {
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+            assert!(chunk.text.len() <= size, "chunk too long: {:?}", chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_short_text_is_one_chunk() {
+        for strategy in [
+            ChunkStrategy::FixedSize,
+            ChunkStrategy::Sentence,
+            ChunkStrategy::Recursive,
+        ] {
+            let chunks = chunk_text("  hello world  ", &config(strategy, 100, 10));
+            assert_eq!(chunks.len(), 1);
+            assert_eq!(chunks[0].text, "hello world");
+            assert_eq!((chunks[0].start, chunks[0].end), (2, 13));
+        }
+    }
+
+    #[test]
+    fn test_whitespace_only_has_no_chunks() {
+        assert!(chunk_text("", &ChunkConfig::default()).is_empty());
+        assert!(chunk_text(" \n\t ", &ChunkConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_fixed_size_windows_overlap() {
+        let text = "abcdefghijklmnopqrstuvwxyz";
+        let chunks = chunk_text(text, &config(ChunkStrategy::FixedSize, 10, 3));
+        let texts: Vec<_> = chunks.iter().map(|c| c.text).collect();
+        assert_eq!(texts, ["abcdefghij", "hijklmnopq", "opqrstuvwx", "vwxyz"]);
+        assert_offsets(text, &chunks, 10);
+    }
+
+    #[test]
+    fn test_fixed_size_respects_char_boundaries() {
+        let text = "é".repeat(20); // 2 bytes each
+        let chunks = chunk_text(&text, &config(ChunkStrategy::FixedSize, 5, 1));
+        assert_offsets(&text, &chunks, 5);
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+
+    #[test]
+    fn test_sentence_chunks_keep_sentences_whole() {
+        let text = "One fish. Two fish! Red fish? Blue fish.";
+        let chunks = chunk_text(text, &config(ChunkStrategy::Sentence, 20, 0));
+        let texts: Vec<_> = chunks.iter().map(|c| c.text).collect();
+        assert_eq!(texts, ["One fish. Two fish!", "Red fish? Blue fish."]);
+        assert_offsets(text, &chunks, 20);
+    }
+
+    #[test]
+    fn test_sentence_overlap_repeats_whole_sentences() {
+        let text = "Alpha one. Beta two. Gamma three. Delta four.";
+        // Overlap counts the whitespace after a sentence
+        let chunks = chunk_text(text, &config(ChunkStrategy::Sentence, 24, 14));
+        let texts: Vec<_> = chunks.iter().map(|c| c.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "Alpha one. Beta two.",
+                "Beta two. Gamma three.",
+                "Gamma three. Delta four."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentence_longer_than_chunk_is_split() {
+        let text = "Short. This sentence is much longer than the chunk size allows.";
+        let chunks = chunk_text(text, &config(ChunkStrategy::Sentence, 16, 0));
+        assert_offsets(text, &chunks, 16);
+        assert_eq!(chunks[0].text, "Short.");
+        assert_eq!(chunks.last().unwrap().end, text.len());
+    }
+
+    #[test]
+    fn test_recursive_prefers_paragraphs() {
+        let text = "First paragraph here.\n\nSecond paragraph here.\n\nThird one.";
+        let chunks = chunk_text(text, &config(ChunkStrategy::Recursive, 30, 0));
+        let texts: Vec<_> = chunks.iter().map(|c| c.text).collect();
+        assert_eq!(
+            texts,
+            [
+                "First paragraph here.",
+                "Second paragraph here.",
+                "Third one."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_falls_back_to_words() {
+        let text = "alpha beta gamma delta epsilon zeta eta theta";
+        let chunks = chunk_text(text, &config(ChunkStrategy::Recursive, 12, 0));
+        assert_offsets(text, &chunks, 12);
+        // No word is cut in half
+        for chunk in &chunks {
+            for word in chunk.text.split(' ') {
+                assert!(text.split(' ').any(|w| w == word), "split word {:?}", word);
+            }
+        }
+    }
+
+    #[test]
+    fn test_recursive_splits_unbroken_text_by_size() {
+        let text = "x".repeat(25);
+        let chunks = chunk_text(&text, &config(ChunkStrategy::Recursive, 10, 0));
+        let lens: Vec<_> = chunks.iter().map(|c| c.text.len()).collect();
+        assert_eq!(lens, [10, 10, 5]);
+    }
+
+    #[test]
+    fn test_chunks_cover_the_text() {
+        let text = "Lorem ipsum dolor sit amet. ".repeat(50);
+        for strategy in [
+            ChunkStrategy::FixedSize,
+            ChunkStrategy::Sentence,
+            ChunkStrategy::Recursive,
+        ] {
+            let chunks = chunk_text(&text, &config(strategy, 100, 20));
+            assert_offsets(&text, &chunks, 100);
+            assert_eq!(chunks[0].start, 0);
+            assert_eq!(chunks.last().unwrap().end, text.trim_end().len());
+            for pair in chunks.windows(2) {
+                assert!(pair[1].start > pair[0].start);
+                let gap = pair[0].end.min(pair[1].start)..pair[1].start;
+                assert!(text[gap].trim().is_empty(), "text lost between chunks");
+            }
+        }
+    }
+}
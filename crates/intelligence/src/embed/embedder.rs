@@ -0,0 +1,232 @@
+//! Embedding providers.
+//!
+//! [`Embedder`] turns text into a vector. The built-in MiniLM-L6-v2 model
+//! ([`EmbedModel`], or `OnnxEmbedModel` with the `onnx` feature) is one
+//! implementation; [`HttpEmbedder`] calls an OpenAI-compatible `/embeddings`
+//! endpoint so auto-embedding can run without local model files. The database's
+//! [`EmbedProvider`](strata_engine::EmbedProvider) selects which is used.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use super::model::EmbedModel;
+
+/// Request timeout for remote embedding calls.
+pub(super) const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A source of text embeddings.
+pub trait Embedder: Send + Sync {
+    /// Embed `text` into a single vector.
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String>;
+
+    /// Embed several texts, returning one vector per text in order.
+    ///
+    /// Backends that can run a batch at once override this; the default
+    /// embeds each text in turn.
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        texts.iter().map(|text| self.embed(text)).collect()
+    }
+}
+
+impl Embedder for EmbedModel {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        Ok(EmbedModel::embed(self, text))
+    }
+}
+
+/// Embedder backed by an OpenAI-compatible embeddings endpoint.
+///
+/// Sends `{"model": model, "input": text}` and reads `data[].embedding`
+/// from the response, which is the shape returned by OpenAI and by most
+/// self-hosted servers (vLLM, Ollama, LiteLLM, TEI). Batches are sent as one
+/// request with an array `input`.
+pub struct HttpEmbedder {
+    agent: ureq::Agent,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl HttpEmbedder {
+    /// Create an embedder for `url`, requesting `model`.
+    ///
+    /// `api_key`, when set, is sent as a bearer token.
+    pub fn new(url: String, model: String, api_key: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(HTTP_TIMEOUT))
+            .build()
+            .into();
+        HttpEmbedder {
+            agent,
+            url,
+            model,
+            api_key,
+        }
+    }
+}
+
+impl HttpEmbedder {
+    /// POST `input` (a string or an array of strings) and return the
+    /// response's embeddings ordered by `index`.
+    fn request(&self, input: serde_json::Value) -> Result<Vec<EmbeddingData>, String> {
+        let body = serde_json::json!({ "model": self.model, "input": input });
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .header("Content-Type", "application/json");
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", &format!("Bearer {}", key));
+        }
+
+        let response = request
+            .send(body.to_string())
+            .map_err(|e| format!("Embedding request to '{}' failed: {}", self.url, e))?;
+        let text = response
+            .into_body()
+            .read_to_string()
+            .map_err(|e| format!("Failed to read embedding response: {}", e))?;
+        let mut parsed: EmbeddingResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid embedding response from '{}': {}", self.url, e))?;
+
+        parsed.data.sort_by_key(|data| data.index);
+        Ok(parsed.data)
+    }
+}
+
+impl Embedder for HttpEmbedder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        match self.request(text.into())?.into_iter().next() {
+            Some(data) if !data.embedding.is_empty() => Ok(data.embedding),
+            _ => Err(format!(
+                "Embedding response from '{}' has no vector",
+                self.url
+            )),
+        }
+    }
+
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let data = self.request(texts.into())?;
+        if data.len() != texts.len() || data.iter().any(|d| d.embedding.is_empty()) {
+            return Err(format!(
+                "Embedding response from '{}' has {} vectors for {} inputs",
+                self.url,
+                data.len(),
+                texts.len()
+            ));
+        }
+        Ok(data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve one HTTP request with `status` and `body`, returning the URL and
+    /// a handle yielding the raw request.
+    fn serve_once(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/embeddings", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            head + &String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_http_embedder_parses_openai_response() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"object":"list","data":[{"object":"embedding","index":0,"embedding":[0.5,-0.25,1.0]}],"model":"m"}"#,
+        );
+        let embedder =
+            HttpEmbedder::new(url, "text-embedding-3-small".into(), Some("sk-test".into()));
+
+        assert_eq!(embedder.embed("hello").unwrap(), vec![0.5, -0.25, 1.0]);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/embeddings"));
+        assert!(
+            request.contains("authorization: Bearer sk-test")
+                || request.contains("Authorization: Bearer sk-test")
+        );
+        assert!(request.contains(r#""model":"text-embedding-3-small""#));
+        assert!(request.contains(r#""input":"hello""#));
+    }
+
+    #[test]
+    fn test_http_embedder_batches_in_one_request() {
+        // Out of order on purpose: results are matched up by `index`
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"data":[{"index":1,"embedding":[0.0,1.0]},{"index":0,"embedding":[1.0,0.0]}]}"#,
+        );
+        let embedder = HttpEmbedder::new(url, "m".into(), None);
+
+        let embeddings = embedder.embed_batch(&["first", "second"]).unwrap();
+        assert_eq!(embeddings, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+        let request = server.join().unwrap();
+        assert!(request.contains(r#""input":["first","second"]"#));
+
+        let (url, server) = serve_once("200 OK", r#"{"data":[{"embedding":[1.0]}]}"#);
+        let embedder = HttpEmbedder::new(url, "m".into(), None);
+        assert!(embedder.embed_batch(&["a", "b"]).is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_http_embedder_reports_errors() {
+        let (url, server) = serve_once("401 Unauthorized", r#"{"error":"bad key"}"#);
+        let embedder = HttpEmbedder::new(url, "m".into(), None);
+        assert!(embedder.embed("hello").is_err());
+        server.join().unwrap();
+
+        let (url, server) = serve_once("200 OK", r#"{"data":[]}"#);
+        let embedder = HttpEmbedder::new(url, "m".into(), None);
+        assert!(embedder.embed("hello").unwrap_err().contains("no vector"));
+        server.join().unwrap();
+    }
+}
@@ -2,8 +2,11 @@
 
 use strata_core::Value;
 
-/// Maximum text length sent to the tokenizer (≈256 tokens).
-const MAX_TEXT_LEN: usize = 1024;
+/// Maximum text length extracted for embedding (64 KiB).
+///
+/// Auto-embedding splits the text into chunks that fit the model (see
+/// [`crate::chunking`]), so this only bounds the work per value.
+const MAX_TEXT_LEN: usize = 64 * 1024;
 
 /// Maximum recursion depth for nested Array/Object values.
 const MAX_DEPTH: usize = 16;
@@ -142,7 +145,7 @@ mod tests {
 
     #[test]
     fn test_truncation() {
-        let long_str = Value::String("a".repeat(2000));
+        let long_str = Value::String("a".repeat(MAX_TEXT_LEN + 1000));
         let text = extract_text(&long_str).unwrap();
         assert_eq!(text.len(), MAX_TEXT_LEN);
     }
@@ -156,7 +159,7 @@ mod tests {
     fn test_truncation_multibyte_safe() {
         // Each '😀' is 4 bytes. Fill past MAX_TEXT_LEN to ensure we don't panic
         // on a multi-byte char boundary.
-        let emoji_str = "😀".repeat(300); // 300 * 4 = 1200 bytes > 1024
+        let emoji_str = "😀".repeat(MAX_TEXT_LEN / 4 + 50);
         let text = extract_text(&Value::String(emoji_str)).unwrap();
         assert!(text.len() <= MAX_TEXT_LEN);
         // Must be valid UTF-8 (no panic, no partial chars)
@@ -1,61 +1,165 @@
-//! Auto-embedding module: MiniLM-L6-v2 text embeddings.
+//! Auto-embedding module: text embeddings from MiniLM-L6-v2 or a remote API.
 //!
-//! Provides a lazy-loading model lifecycle via [`EmbedModelState`] and
-//! text extraction from Strata [`Value`] types.
+//! Provides a lazy-loading model lifecycle via [`EmbedModelState`], the
+//! [`Embedder`] trait selected by the database's embed provider, the
+//! [`Reranker`] trait selected by its rerank provider, and text extraction
+//! from Strata [`Value`] types.
+//!
+//! The local model runs on the native tensor runtime, or on ONNX Runtime
+//! when the `onnx` feature is enabled and the model directory holds an ONNX
+//! export (see [`onnx`]).
 
 pub mod download;
+pub mod embedder;
 pub mod extract;
 pub mod model;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+pub mod rerank;
 pub mod tokenizer;
 
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+use model::{CrossEncoderModel, EmbedModel};
+use strata_engine::{EmbedProvider, RerankProvider};
 
-use model::EmbedModel;
+pub use embedder::{Embedder, HttpEmbedder};
+#[cfg(feature = "onnx")]
+pub use onnx::OnnxEmbedModel;
+pub use rerank::{HttpReranker, Reranker};
 
 /// Lazy-loading model state stored as a Database extension.
 ///
 /// On first use, loads the MiniLM-L6-v2 model from the model directory.
 /// If model files are missing, stores the error and never retries.
+/// Also caches the HTTP embedder for the current provider, and the
+/// reranker the same way.
 pub struct EmbedModelState {
     model: once_cell::sync::OnceCell<Result<Arc<EmbedModel>, String>>,
+    local: once_cell::sync::OnceCell<Result<Arc<dyn Embedder>, String>>,
+    http: Mutex<Option<(EmbedProvider, Arc<HttpEmbedder>)>>,
+    cross_encoder: once_cell::sync::OnceCell<Result<Arc<CrossEncoderModel>, String>>,
+    http_reranker: Mutex<Option<(RerankProvider, Arc<HttpReranker>)>>,
 }
 
 impl Default for EmbedModelState {
     fn default() -> Self {
         Self {
             model: once_cell::sync::OnceCell::new(),
+            local: once_cell::sync::OnceCell::new(),
+            http: Mutex::new(None),
+            cross_encoder: once_cell::sync::OnceCell::new(),
+            http_reranker: Mutex::new(None),
         }
     }
 }
 
 impl EmbedModelState {
-    /// Get or load the embedding model.
+    /// Get the embedder for the database's configured provider.
+    ///
+    /// The local model is loaded on first use (see [`local_embedder`](Self::local_embedder));
+    /// the HTTP embedder is rebuilt only when the provider changes.
+    pub fn embedder(&self, db: &strata_engine::Database) -> Result<Arc<dyn Embedder>, String> {
+        let provider = db.embed_provider();
+        let EmbedProvider::Http {
+            url,
+            model,
+            api_key,
+        } = &provider
+        else {
+            return self.local_embedder(&db.model_dir());
+        };
+
+        let mut cached = self.http.lock().unwrap();
+        if let Some((cached_provider, embedder)) = cached.as_ref() {
+            if *cached_provider == provider {
+                return Ok(embedder.clone());
+            }
+        }
+        let embedder = Arc::new(HttpEmbedder::new(
+            url.clone(),
+            model.clone(),
+            api_key.clone(),
+        ));
+        *cached = Some((provider, embedder.clone()));
+        Ok(embedder)
+    }
+
+    /// Get the reranker for the database's configured rerank provider.
+    ///
+    /// The local cross-encoder is loaded on first use from
+    /// `rerank_model_dir()`; the HTTP reranker is rebuilt only when the
+    /// provider changes.
+    pub fn reranker(&self, db: &strata_engine::Database) -> Result<Arc<dyn Reranker>, String> {
+        let provider = db.rerank_provider();
+        let RerankProvider::Http {
+            url,
+            model,
+            api_key,
+        } = &provider
+        else {
+            return Ok(self.get_or_load_cross_encoder(&db.rerank_model_dir())?);
+        };
+
+        let mut cached = self.http_reranker.lock().unwrap();
+        if let Some((cached_provider, reranker)) = cached.as_ref() {
+            if *cached_provider == provider {
+                return Ok(reranker.clone());
+            }
+        }
+        let reranker = Arc::new(HttpReranker::new(
+            url.clone(),
+            model.clone(),
+            api_key.clone(),
+        ));
+        *cached = Some((provider, reranker.clone()));
+        Ok(reranker)
+    }
+
+    /// Get or load the local cross-encoder.
+    ///
+    /// Loads from `model_dir/model.safetensors` and `model_dir/vocab.txt`,
+    /// caching the result like [`get_or_load`](Self::get_or_load).
+    pub fn get_or_load_cross_encoder(
+        &self,
+        model_dir: &Path,
+    ) -> Result<Arc<CrossEncoderModel>, String> {
+        self.cross_encoder
+            .get_or_init(|| {
+                let (safetensors_bytes, vocab_text) = read_model_files(model_dir)?;
+                let model = CrossEncoderModel::load(&safetensors_bytes, &vocab_text)?;
+                Ok(Arc::new(model))
+            })
+            .clone()
+    }
+
+    /// Get or load the local embedding model.
+    ///
+    /// With the `onnx` feature, an ONNX export (`model_dir/model.onnx`) is
+    /// run on ONNX Runtime. If there is no export, or it or the runtime
+    /// library fails to load, the native model from
+    /// [`get_or_load`](Self::get_or_load) is used instead.
+    pub fn local_embedder(&self, model_dir: &Path) -> Result<Arc<dyn Embedder>, String> {
+        self.local
+            .get_or_init(|| {
+                #[cfg(feature = "onnx")]
+                if let Some(model) = load_onnx(model_dir) {
+                    return Ok(model);
+                }
+                Ok(self.get_or_load(model_dir)?)
+            })
+            .clone()
+    }
+
+    /// Get or load the native embedding model.
     ///
     /// Loads from `model_dir/model.safetensors` and `model_dir/vocab.txt`.
     /// Caches the result (success or failure) so filesystem is probed at most once.
     pub fn get_or_load(&self, model_dir: &Path) -> Result<Arc<EmbedModel>, String> {
         self.model
             .get_or_init(|| {
-                let safetensors_path = model_dir.join("model.safetensors");
-                let vocab_path = model_dir.join("vocab.txt");
-
-                let safetensors_bytes = std::fs::read(&safetensors_path).map_err(|e| {
-                    format!(
-                        "Failed to read model file '{}': {}",
-                        safetensors_path.display(),
-                        e
-                    )
-                })?;
-
-                let vocab_text = std::fs::read_to_string(&vocab_path).map_err(|e| {
-                    format!(
-                        "Failed to read vocab file '{}': {}",
-                        vocab_path.display(),
-                        e
-                    )
-                })?;
-
+                let (safetensors_bytes, vocab_text) = read_model_files(model_dir)?;
                 let model = EmbedModel::load(&safetensors_bytes, &vocab_text)?;
                 Ok(Arc::new(model))
             })
@@ -63,13 +167,58 @@ impl EmbedModelState {
     }
 }
 
-/// Embed a query string using the cached MiniLM model from the database.
+/// Read `model.safetensors` and `vocab.txt` from `model_dir`.
+fn read_model_files(model_dir: &Path) -> Result<(Vec<u8>, String), String> {
+    let safetensors_path = model_dir.join("model.safetensors");
+    let vocab_path = model_dir.join("vocab.txt");
+
+    let safetensors_bytes = std::fs::read(&safetensors_path).map_err(|e| {
+        format!(
+            "Failed to read model file '{}': {}",
+            safetensors_path.display(),
+            e
+        )
+    })?;
+
+    let vocab_text = std::fs::read_to_string(&vocab_path).map_err(|e| {
+        format!(
+            "Failed to read vocab file '{}': {}",
+            vocab_path.display(),
+            e
+        )
+    })?;
+
+    Ok((safetensors_bytes, vocab_text))
+}
+
+/// Load the ONNX export in `model_dir`, or `None` to use the native model.
+#[cfg(feature = "onnx")]
+fn load_onnx(model_dir: &Path) -> Option<Arc<dyn Embedder>> {
+    let onnx_path = model_dir.join(onnx::ONNX_MODEL_FILE);
+    if !onnx_path.exists() {
+        return None;
+    }
+    let loaded = std::fs::read_to_string(model_dir.join("vocab.txt"))
+        .map_err(|e| format!("Failed to read vocab file: {}", e))
+        .and_then(|vocab| OnnxEmbedModel::load(&onnx_path, &vocab));
+    match loaded {
+        Ok(model) => {
+            tracing::info!(target: "strata::embed", path = %onnx_path.display(), "Using ONNX Runtime for embeddings");
+            Some(Arc::new(model))
+        }
+        Err(e) => {
+            tracing::warn!(target: "strata::embed", error = %e, "Falling back to the native embedding runtime");
+            None
+        }
+    }
+}
+
+/// Embed a query string with the database's embedding provider.
 ///
-/// Loads or retrieves the cached model via [`EmbedModelState`], then embeds the
-/// given text. Returns `None` (with a warning log) if the model cannot be loaded
-/// or embedding fails. This is a best-effort helper for hybrid search.
+/// Gets the embedder via [`EmbedModelState`], then embeds the given text.
+/// Returns `None` (with a warning log) if the model cannot be loaded or
+/// embedding fails. This is a best-effort helper for hybrid search.
 pub fn embed_query(db: &strata_engine::Database, text: &str) -> Option<Vec<f32>> {
-    let model_dir = db.model_dir();
     let state = match db.extension::<EmbedModelState>() {
         Ok(s) => s,
         Err(e) => {
@@ -77,14 +226,20 @@ pub fn embed_query(db: &strata_engine::Database, text: &str) -> Option<Vec<f32>>
             return None;
         }
     };
-    let model = match state.get_or_load(&model_dir) {
+    let embedder = match state.embedder(db) {
         Ok(m) => m,
         Err(e) => {
-            tracing::warn!(target: "strata::hybrid", error = %e, "Failed to load embed model for hybrid search");
+            tracing::warn!(target: "strata::hybrid", error = %e, "Failed to load embedder for hybrid search");
             return None;
         }
     };
-    Some(model.embed(text))
+    match embedder.embed(text) {
+        Ok(embedding) => Some(embedding),
+        Err(e) => {
+            tracing::warn!(target: "strata::hybrid", error = %e, "Failed to embed query for hybrid search");
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -121,6 +276,16 @@ mod tests {
         assert_eq!(err1, err2, "error should be cached and identical");
     }
 
+    #[test]
+    fn test_missing_cross_encoder_is_cached_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = EmbedModelState::default();
+        let err1 = state.get_or_load_cross_encoder(dir.path()).err().unwrap();
+        let err2 = state.get_or_load_cross_encoder(dir.path()).err().unwrap();
+        assert!(err1.contains(dir.path().to_str().unwrap()), "{}", err1);
+        assert_eq!(err1, err2);
+    }
+
     #[test]
     fn test_missing_vocab_file() {
         let dir = tempfile::tempdir().unwrap();
@@ -169,10 +169,21 @@ impl EmbedModel {
     /// Embed a text string into a 384-dimensional vector.
     pub fn embed(&self, text: &str) -> Vec<f32> {
         let input = self.tokenizer.tokenize(text);
+        let hidden = self.encode(&input);
+
+        // Mean pooling (exclude padding)
+        let pooled = self.mean_pool(&hidden, &input.attention_mask);
+
+        // L2 normalize
+        l2_normalize(&pooled)
+    }
+
+    /// Run the encoder, returning one hidden state per token.
+    fn encode(&self, input: &TokenizedInput) -> Tensor {
         let seq_len = input.input_ids.len();
 
         // 1. Gather embeddings
-        let hidden = self.gather_embeddings(&input, seq_len);
+        let hidden = self.gather_embeddings(input, seq_len);
 
         // 2. Layer norm
         let mut hidden = hidden.layer_norm(&self.embed_ln_weight, &self.embed_ln_bias, LAYER_NORM_EPS);
@@ -181,12 +192,7 @@ impl EmbedModel {
         for layer in &self.layers {
             hidden = self.transformer_layer(layer, &hidden, &input.attention_mask);
         }
-
-        // 4. Mean pooling (exclude padding)
-        let pooled = self.mean_pool(&hidden, &input.attention_mask);
-
-        // 5. L2 normalize
-        l2_normalize(&pooled)
+        hidden
     }
 
     fn gather_embeddings(&self, input: &TokenizedInput, seq_len: usize) -> Tensor {
@@ -326,7 +332,87 @@ impl EmbedModel {
     }
 }
 
-fn l2_normalize(v: &[f32]) -> Vec<f32> {
+/// A MiniLM-L6 cross-encoder (e.g. `ms-marco-MiniLM-L-6-v2`) that scores
+/// how well a passage answers a query.
+///
+/// Query and passage are encoded together; the `[CLS]` state goes through
+/// the BERT pooler and a single-logit classifier.
+pub struct CrossEncoderModel {
+    encoder: EmbedModel,
+    pooler_weight: Tensor,
+    pooler_bias: Vec<f32>,
+    classifier_weight: Tensor,
+    classifier_bias: f32,
+}
+
+impl CrossEncoderModel {
+    /// Load a cross-encoder from SafeTensors bytes and vocabulary text.
+    ///
+    /// Besides the encoder weights (see [`EmbedModel::load`]) the file must
+    /// hold `bert.pooler.dense.*` and a one-label `classifier.*` head.
+    pub fn load(safetensors_bytes: &[u8], vocab_text: &str) -> Result<Self, String> {
+        let encoder = EmbedModel::load(safetensors_bytes, vocab_text)?;
+        let st = SafeTensors::from_bytes(safetensors_bytes)?;
+
+        let pooler_weight = st
+            .tensor("bert.pooler.dense.weight")
+            .ok_or("Missing bert.pooler.dense.weight")?;
+        let pooler_bias = st
+            .tensor_1d("bert.pooler.dense.bias")
+            .ok_or("Missing bert.pooler.dense.bias")?;
+        let classifier_weight = st
+            .tensor("classifier.weight")
+            .ok_or("Missing classifier.weight")?;
+        let classifier_bias = st
+            .tensor_1d("classifier.bias")
+            .ok_or("Missing classifier.bias")?;
+
+        if pooler_weight.rows != HIDDEN_SIZE || pooler_weight.cols != HIDDEN_SIZE {
+            return Err(format!(
+                "pooler shape mismatch: expected {}x{}, got {}x{}",
+                HIDDEN_SIZE, HIDDEN_SIZE, pooler_weight.rows, pooler_weight.cols
+            ));
+        }
+        if classifier_weight.rows != 1 || classifier_weight.cols != HIDDEN_SIZE {
+            return Err(format!(
+                "classifier shape mismatch: expected 1x{}, got {}x{}",
+                HIDDEN_SIZE, classifier_weight.rows, classifier_weight.cols
+            ));
+        }
+
+        Ok(Self {
+            encoder,
+            pooler_weight,
+            pooler_bias,
+            classifier_weight,
+            classifier_bias: classifier_bias.first().copied().unwrap_or(0.0),
+        })
+    }
+
+    /// Relevance of `passage` to `query` as a probability in `[0, 1]`.
+    pub fn score(&self, query: &str, passage: &str) -> f32 {
+        let input = self.encoder.tokenizer.tokenize_pair(query, passage);
+        let hidden = self.encoder.encode(&input);
+
+        // Pooler: dense + tanh over the [CLS] state
+        let cls = hidden.slice_rows(0, 1);
+        let mut pooled = cls.matmul_transpose(&self.pooler_weight);
+        pooled.add_bias(&self.pooler_bias);
+        for v in pooled.data.iter_mut() {
+            *v = v.tanh();
+        }
+
+        let logit = pooled.matmul_transpose(&self.classifier_weight).data[0] + self.classifier_bias;
+        sigmoid(logit)
+    }
+}
+
+/// Map a relevance logit to a probability.
+pub(super) fn sigmoid(logit: f32) -> f32 {
+    1.0 / (1.0 + (-logit).exp())
+}
+
+pub(super) fn l2_normalize(v: &[f32]) -> Vec<f32> {
     let norm: f32 = v.iter().map(|x| x * x).sum::<f32>().sqrt();
     if norm > 0.0 {
         v.iter().map(|x| x / norm).collect()
@@ -349,6 +435,13 @@ mod tests {
         assert!((norm - 1.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn test_sigmoid() {
+        assert!((sigmoid(0.0) - 0.5).abs() < 1e-6);
+        assert!(sigmoid(10.0) > 0.99);
+        assert!(sigmoid(-10.0) < 0.01);
+    }
+
     #[test]
     fn test_l2_normalize_zero() {
         let v = vec![0.0, 0.0];
@@ -0,0 +1,216 @@
+//! ONNX Runtime backend for the embedding model.
+//!
+//! [`OnnxEmbedModel`] runs an ONNX export of MiniLM-L6-v2 (`model.onnx` in
+//! the model directory) on ONNX Runtime, which is much faster on CPU than the
+//! native tensor runtime and embeds a whole batch of texts per call.
+//!
+//! ONNX Runtime is loaded at run time, not linked: point `ORT_DYLIB_PATH` at
+//! `libonnxruntime`, or put it on the library search path. If the export or
+//! the library is missing, [`EmbedModelState`](super::EmbedModelState) falls
+//! back to the native model.
+
+use std::borrow::Cow;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::Mutex;
+
+use ort::session::{Session, SessionInputValue};
+use ort::value::Tensor;
+
+use super::embedder::Embedder;
+use super::model::l2_normalize;
+use super::tokenizer::WordPieceTokenizer;
+
+/// File name of the ONNX export inside the model directory.
+pub const ONNX_MODEL_FILE: &str = "model.onnx";
+
+/// Most texts sent to ONNX Runtime in one run; larger batches are split.
+const MAX_BATCH_SIZE: usize = 32;
+
+/// MiniLM-L6-v2 running on ONNX Runtime.
+pub struct OnnxEmbedModel {
+    tokenizer: WordPieceTokenizer,
+    session: Mutex<Session>,
+    /// Whether the export takes a `token_type_ids` input
+    uses_token_type_ids: bool,
+}
+
+impl OnnxEmbedModel {
+    /// Load an ONNX export from `model_path` with the given vocabulary.
+    ///
+    /// The export must take `input_ids` and `attention_mask` (and optionally
+    /// `token_type_ids`) and return either token embeddings
+    /// (`[batch, seq, hidden]`, mean-pooled here) or sentence embeddings
+    /// (`[batch, hidden]`).
+    pub fn load(model_path: &Path, vocab_text: &str) -> Result<Self, String> {
+        // ort panics when the shared library cannot be loaded
+        let session = catch_unwind(AssertUnwindSafe(|| {
+            Session::builder().and_then(|builder| builder.commit_from_file(model_path))
+        }))
+        .map_err(|_| "ONNX Runtime library could not be loaded (set ORT_DYLIB_PATH)".to_string())?
+        .map_err(|e| {
+            format!(
+                "Failed to load ONNX model '{}': {}",
+                model_path.display(),
+                e
+            )
+        })?;
+
+        let has_input = |name: &str| session.inputs.iter().any(|input| input.name == name);
+        for required in ["input_ids", "attention_mask"] {
+            if !has_input(required) {
+                return Err(format!(
+                    "ONNX model '{}' has no '{}' input",
+                    model_path.display(),
+                    required
+                ));
+            }
+        }
+        let uses_token_type_ids = has_input("token_type_ids");
+
+        Ok(OnnxEmbedModel {
+            tokenizer: WordPieceTokenizer::from_vocab(vocab_text),
+            session: Mutex::new(session),
+            uses_token_type_ids,
+        })
+    }
+
+    /// Embed up to [`MAX_BATCH_SIZE`] texts in one ONNX Runtime run.
+    fn run_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        let inputs: Vec<_> = texts.iter().map(|t| self.tokenizer.tokenize(t)).collect();
+        let batch = inputs.len();
+        let seq_len = inputs
+            .iter()
+            .map(|input| input.input_ids.len())
+            .max()
+            .unwrap_or(0);
+
+        // Right-pad every sequence to the longest one in the batch
+        let mut input_ids = vec![0i64; batch * seq_len];
+        let mut attention_mask = vec![0i64; batch * seq_len];
+        let mut token_type_ids = vec![0i64; batch * seq_len];
+        for (row, input) in inputs.iter().enumerate() {
+            let offset = row * seq_len;
+            for (i, &id) in input.input_ids.iter().enumerate() {
+                input_ids[offset + i] = id as i64;
+                attention_mask[offset + i] = input.attention_mask[i] as i64;
+                token_type_ids[offset + i] = input.token_type_ids[i] as i64;
+            }
+        }
+
+        let shape = [batch as i64, seq_len as i64];
+        let tensor = |data: Vec<i64>| -> Result<SessionInputValue<'static>, String> {
+            Tensor::from_array((shape, data))
+                .map(Into::into)
+                .map_err(|e| format!("Failed to build ONNX input: {}", e))
+        };
+        let mut feed: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = vec![
+            ("input_ids".into(), tensor(input_ids)?),
+            ("attention_mask".into(), tensor(attention_mask.clone())?),
+        ];
+        if self.uses_token_type_ids {
+            feed.push(("token_type_ids".into(), tensor(token_type_ids)?));
+        }
+
+        let mut session = self.session.lock().unwrap();
+        let outputs = session
+            .run(feed)
+            .map_err(|e| format!("ONNX inference failed: {}", e))?;
+        let (output_shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| format!("Unexpected ONNX model output: {}", e))?;
+
+        match **output_shape {
+            [b, s, hidden] if b as usize == batch && s as usize == seq_len => Ok(mean_pool_batch(
+                data,
+                &attention_mask,
+                batch,
+                seq_len,
+                hidden as usize,
+            )),
+            [b, hidden] if b as usize == batch && hidden > 0 => {
+                Ok(data.chunks(hidden as usize).map(l2_normalize).collect())
+            }
+            _ => Err(format!(
+                "Unexpected ONNX model output shape {:?} for a batch of {}",
+                &**output_shape, batch
+            )),
+        }
+    }
+}
+
+impl Embedder for OnnxEmbedModel {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        self.run_batch(&[text])?
+            .pop()
+            .ok_or_else(|| "ONNX model returned no embedding".to_string())
+    }
+
+    fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>, String> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for chunk in texts.chunks(MAX_BATCH_SIZE) {
+            embeddings.extend(self.run_batch(chunk)?);
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Mean-pool token embeddings over unmasked positions, then L2-normalize.
+///
+/// `hidden` is `[batch, seq_len, dim]` and `attention_mask` is
+/// `[batch, seq_len]`, both row-major.
+fn mean_pool_batch(
+    hidden: &[f32],
+    attention_mask: &[i64],
+    batch: usize,
+    seq_len: usize,
+    dim: usize,
+) -> Vec<Vec<f32>> {
+    (0..batch)
+        .map(|b| {
+            let mut sum = vec![0.0f32; dim];
+            let mut count = 0.0f32;
+            for s in 0..seq_len {
+                if attention_mask[b * seq_len + s] == 0 {
+                    continue;
+                }
+                let offset = (b * seq_len + s) * dim;
+                for (acc, x) in sum.iter_mut().zip(&hidden[offset..offset + dim]) {
+                    *acc += x;
+                }
+                count += 1.0;
+            }
+            if count > 0.0 {
+                for v in sum.iter_mut() {
+                    *v /= count;
+                }
+            }
+            l2_normalize(&sum)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_pool_batch_ignores_padding() {
+        // Two sequences of length 3, dim 2; the second has one padded position
+        let hidden = [
+            1.0, 0.0, 3.0, 0.0, 2.0, 0.0, // seq 0
+            0.0, 2.0, 0.0, 4.0, 100.0, 100.0, // seq 1 (last is padding)
+        ];
+        let mask = [1, 1, 1, 1, 1, 0];
+
+        let pooled = mean_pool_batch(&hidden, &mask, 2, 3, 2);
+        assert_eq!(pooled, vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_load_missing_model_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = OnnxEmbedModel::load(&dir.path().join(ONNX_MODEL_FILE), "[PAD]\n");
+        assert!(result.is_err());
+    }
+}
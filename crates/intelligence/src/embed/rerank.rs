@@ -0,0 +1,198 @@
+//! Rerankers: score how well candidate passages answer a query.
+//!
+//! [`Reranker`] is the reranking counterpart of [`Embedder`](super::Embedder).
+//! The local implementation is a MiniLM-L6 cross-encoder
+//! ([`CrossEncoderModel`]); [`HttpReranker`] calls a Cohere-compatible
+//! `/rerank` endpoint. The database's
+//! [`RerankProvider`](strata_engine::RerankProvider) selects which is used.
+//!
+//! Scores are calibrated relevance probabilities in `[0, 1]`, so they can be
+//! compared across queries and thresholded.
+
+use serde::Deserialize;
+
+use super::embedder::HTTP_TIMEOUT;
+use super::model::CrossEncoderModel;
+
+/// A source of query–passage relevance scores.
+pub trait Reranker: Send + Sync {
+    /// Score each passage against `query`, returning one score in `[0, 1]`
+    /// per passage in order.
+    fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, String>;
+}
+
+impl Reranker for CrossEncoderModel {
+    fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, String> {
+        Ok(passages
+            .iter()
+            .map(|passage| CrossEncoderModel::score(self, query, passage))
+            .collect())
+    }
+}
+
+/// Reranker backed by a Cohere-compatible rerank endpoint.
+///
+/// Sends `{"model", "query", "documents", "top_n"}` and reads
+/// `results[].relevance_score`, matched to passages by `index`. This is the
+/// shape served by Cohere, Jina, vLLM and Infinity, all of which return
+/// relevance scores in `[0, 1]`.
+pub struct HttpReranker {
+    agent: ureq::Agent,
+    url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+#[derive(Deserialize)]
+struct RerankResult {
+    index: usize,
+    #[serde(alias = "score")]
+    relevance_score: f32,
+}
+
+impl HttpReranker {
+    /// Create a reranker for `url`, requesting `model`.
+    ///
+    /// `api_key`, when set, is sent as a bearer token.
+    pub fn new(url: String, model: String, api_key: Option<String>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(HTTP_TIMEOUT))
+            .build()
+            .into();
+        HttpReranker {
+            agent,
+            url,
+            model,
+            api_key,
+        }
+    }
+}
+
+impl Reranker for HttpReranker {
+    fn score(&self, query: &str, passages: &[&str]) -> Result<Vec<f32>, String> {
+        if passages.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body = serde_json::json!({
+            "model": self.model,
+            "query": query,
+            "documents": passages,
+            "top_n": passages.len(),
+        });
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .header("Content-Type", "application/json");
+        if let Some(key) = &self.api_key {
+            request = request.header("Authorization", &format!("Bearer {}", key));
+        }
+
+        let response = request
+            .send(body.to_string())
+            .map_err(|e| format!("Rerank request to '{}' failed: {}", self.url, e))?;
+        let text = response
+            .into_body()
+            .read_to_string()
+            .map_err(|e| format!("Failed to read rerank response: {}", e))?;
+        let parsed: RerankResponse = serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid rerank response from '{}': {}", self.url, e))?;
+
+        let mut scores = vec![None; passages.len()];
+        for result in parsed.results {
+            if let Some(slot) = scores.get_mut(result.index) {
+                *slot = Some(result.relevance_score);
+            }
+        }
+        scores
+            .into_iter()
+            .collect::<Option<Vec<f32>>>()
+            .ok_or_else(|| {
+                format!(
+                    "Rerank response from '{}' does not score all {} passages",
+                    self.url,
+                    passages.len()
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve one HTTP request with `body`, returning the URL and a handle
+    /// yielding the raw request.
+    fn serve_once(body: &str) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/rerank", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            head + &String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_http_reranker_matches_scores_by_index() {
+        // Results come back sorted by relevance, not in passage order
+        let (url, server) = serve_once(
+            r#"{"results":[{"index":1,"relevance_score":0.93},{"index":0,"relevance_score":0.02}]}"#,
+        );
+        let reranker = HttpReranker::new(url, "rerank-v3".into(), Some("sk-test".into()));
+
+        let scores = reranker
+            .score("disk failure", &["the weather", "node 7 lost a disk"])
+            .unwrap();
+        assert_eq!(scores, vec![0.02, 0.93]);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /v1/rerank"));
+        assert!(request.contains(r#""query":"disk failure""#));
+        assert!(request.contains(r#""documents":["the weather","node 7 lost a disk"]"#));
+        assert!(request.contains(r#""model":"rerank-v3""#));
+    }
+
+    #[test]
+    fn test_http_reranker_rejects_partial_results() {
+        let (url, server) = serve_once(r#"{"results":[{"index":0,"score":0.5}]}"#);
+        let reranker = HttpReranker::new(url, "m".into(), None);
+        let err = reranker.score("q", &["a", "b"]).unwrap_err();
+        assert!(err.contains("does not score all 2 passages"), "{}", err);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_http_reranker_skips_request_without_passages() {
+        let reranker = HttpReranker::new("http://127.0.0.1:1/rerank".into(), "m".into(), None);
+        assert!(reranker.score("q", &[]).unwrap().is_empty());
+    }
+}
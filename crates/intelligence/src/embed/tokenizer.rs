@@ -65,6 +65,60 @@ impl WordPieceTokenizer {
         }
     }
 
+    /// Tokenize a text pair as `[CLS] first [SEP] second [SEP]`.
+    ///
+    /// Tokens of `second` have token type 1. When the pair is too long,
+    /// tokens are dropped from the end of the longer text first.
+    pub fn tokenize_pair(&self, first: &str, second: &str) -> TokenizedInput {
+        let mut first_ids = self.word_ids(first);
+        let mut second_ids = self.word_ids(second);
+
+        // Room for [CLS] and two [SEP]s
+        let budget = self.max_seq_len - 3;
+        while first_ids.len() + second_ids.len() > budget {
+            if second_ids.len() >= first_ids.len() {
+                second_ids.pop();
+            } else {
+                first_ids.pop();
+            }
+        }
+
+        let mut tokens = Vec::with_capacity(first_ids.len() + second_ids.len() + 3);
+        tokens.push(CLS_ID);
+        tokens.extend_from_slice(&first_ids);
+        tokens.push(SEP_ID);
+        let first_len = tokens.len();
+        tokens.extend_from_slice(&second_ids);
+        tokens.push(SEP_ID);
+
+        let len = tokens.len();
+        let mut token_type_ids = vec![0u32; len];
+        for type_id in &mut token_type_ids[first_len..] {
+            *type_id = 1;
+        }
+
+        TokenizedInput {
+            input_ids: tokens,
+            attention_mask: vec![1u32; len],
+            token_type_ids,
+        }
+    }
+
+    /// WordPiece ids of `text`, without special tokens, capped at the
+    /// maximum sequence length.
+    fn word_ids(&self, text: &str) -> Vec<u32> {
+        let lower = text.to_lowercase();
+        let mut tokens = Vec::new();
+        for word in &basic_split(&lower) {
+            self.wordpiece_tokenize(word, &mut tokens);
+            if tokens.len() >= self.max_seq_len {
+                tokens.truncate(self.max_seq_len);
+                break;
+            }
+        }
+        tokens
+    }
+
     fn wordpiece_tokenize(&self, word: &str, tokens: &mut Vec<u32>) {
         if word.is_empty() {
             return;
@@ -362,4 +416,29 @@ mod tests {
         assert_eq!(r3.input_ids[0], CLS_ID);
         assert_eq!(*r3.input_ids.last().unwrap(), SEP_ID);
     }
+
+    #[test]
+    fn test_tokenize_pair_segments() {
+        let vocab = test_vocab();
+        let tok = WordPieceTokenizer::from_vocab(&vocab);
+        let result = tok.tokenize_pair("hello", "world test");
+
+        assert_eq!(
+            result.input_ids,
+            vec![CLS_ID, 103, SEP_ID, 104, 106, SEP_ID]
+        );
+        assert_eq!(result.token_type_ids, vec![0, 0, 0, 1, 1, 1]);
+        assert!(result.attention_mask.iter().all(|&v| v == 1));
+    }
+
+    #[test]
+    fn test_tokenize_pair_truncates_longer_text() {
+        let vocab = test_vocab();
+        let tok = WordPieceTokenizer::from_vocab(&vocab);
+        let result = tok.tokenize_pair("hello world", &"test ".repeat(400));
+
+        assert_eq!(result.input_ids.len(), 256);
+        assert_eq!(&result.input_ids[..4], &[CLS_ID, 103, 104, SEP_ID]);
+        assert_eq!(*result.input_ids.last().unwrap(), SEP_ID);
+    }
 }
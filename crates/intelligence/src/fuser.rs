@@ -4,6 +4,7 @@
 //! - Fuser trait for pluggable fusion algorithms
 //! - SimpleFuser: basic concatenation + sort (M6 default)
 //! - RRFFuser: Reciprocal Rank Fusion (advanced fusion)
+//! - WeightedFuser: weighted sum of normalized scores
 //!
 //! See `docs/architecture/M6_ARCHITECTURE.md` for authoritative specification.
 
@@ -249,6 +250,112 @@ impl Fuser for RRFFuser {
     }
 }
 
+// ============================================================================
+// WeightedFuser
+// ============================================================================
+
+/// Weighted score fusion
+///
+/// Score = w * norm(vector score) + (1 - w) * norm(other score)
+///
+/// Scores from different rankers are not comparable (BM25 is unbounded,
+/// cosine similarity is not), so each list is min-max normalized into
+/// [0, 1] before weighting. A list whose hits all share one score
+/// normalizes to 1.0. Lists tagged `PrimitiveType::Vector` take the
+/// vector weight; every other list takes the remainder.
+///
+/// Ties keep the order in which documents were first seen.
+#[derive(Debug, Clone)]
+pub struct WeightedFuser {
+    /// Weight of vector lists, in [0, 1]
+    vector_weight: f32,
+}
+
+impl Default for WeightedFuser {
+    fn default() -> Self {
+        WeightedFuser { vector_weight: 0.5 }
+    }
+}
+
+impl WeightedFuser {
+    /// Create a new WeightedFuser, clamping the weight into [0, 1]
+    pub fn new(vector_weight: f32) -> Self {
+        WeightedFuser {
+            vector_weight: vector_weight.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Get the vector weight
+    pub fn vector_weight(&self) -> f32 {
+        self.vector_weight
+    }
+}
+
+impl Fuser for WeightedFuser {
+    fn fuse(&self, results: Vec<(PrimitiveType, SearchResponse)>, k: usize) -> FusedResult {
+        use std::collections::HashMap;
+
+        let mut order: Vec<EntityRef> = Vec::new();
+        let mut fused: HashMap<EntityRef, (f32, SearchHit)> = HashMap::new();
+
+        for (primitive, response) in results {
+            let weight = if primitive == PrimitiveType::Vector {
+                self.vector_weight
+            } else {
+                1.0 - self.vector_weight
+            };
+
+            let (min, max) = response
+                .hits
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), hit| {
+                    (lo.min(hit.score), hi.max(hit.score))
+                });
+            let range = max - min;
+
+            for hit in response.hits {
+                let normalized = if range > 0.0 {
+                    (hit.score - min) / range
+                } else {
+                    1.0
+                };
+                match fused.get_mut(&hit.doc_ref) {
+                    Some((score, _)) => *score += weight * normalized,
+                    None => {
+                        order.push(hit.doc_ref.clone());
+                        fused.insert(hit.doc_ref.clone(), (weight * normalized, hit));
+                    }
+                }
+            }
+        }
+
+        let mut scored: Vec<(f32, SearchHit)> = order
+            .iter()
+            .filter_map(|doc_ref| fused.remove(doc_ref))
+            .collect();
+        // Stable sort keeps first-seen order among equal scores
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let truncated = scored.len() > k;
+        let hits: Vec<SearchHit> = scored
+            .into_iter()
+            .take(k)
+            .enumerate()
+            .map(|(i, (score, mut hit))| {
+                hit.score = score;
+                hit.rank = (i + 1) as u32;
+                hit
+            })
+            .collect();
+
+        FusedResult::new(hits, truncated)
+    }
+
+    fn name(&self) -> &str {
+        "weighted"
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -367,6 +474,7 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<SimpleFuser>();
         assert_send_sync::<RRFFuser>();
+        assert_send_sync::<WeightedFuser>();
     }
 
     // ========================================
@@ -538,4 +646,48 @@ mod tests {
         let fuser = RRFFuser::default();
         assert_eq!(fuser.name(), "rrf");
     }
+
+    // ========================================
+    // WeightedFuser Tests
+    // ========================================
+
+    #[test]
+    fn test_weighted_fuser_normalizes_and_weights() {
+        let branch_id = BranchId::new();
+        let a = make_kv_doc_ref(&branch_id, "a");
+        let b = make_kv_doc_ref(&branch_id, "b");
+        let c = make_kv_doc_ref(&branch_id, "c");
+
+        // BM25-scale text scores, cosine-scale vector scores
+        let text = vec![make_hit(a.clone(), 12.0, 1), make_hit(b.clone(), 2.0, 2)];
+        let vector = vec![make_hit(b.clone(), 0.9, 1), make_hit(c.clone(), 0.1, 2)];
+        let results = || {
+            vec![
+                (PrimitiveType::Kv, make_response(text.clone())),
+                (PrimitiveType::Vector, make_response(vector.clone())),
+            ]
+        };
+
+        // Even weights: a = 0.5, b = 0 + 0.5, c = 0; a was seen first
+        let result = WeightedFuser::default().fuse(results(), 10);
+        let order: Vec<_> = result.hits.iter().map(|h| h.doc_ref.clone()).collect();
+        assert_eq!(order, vec![a.clone(), b.clone(), c.clone()]);
+        assert!((result.hits[0].score - 0.5).abs() < 0.0001);
+        assert!((result.hits[1].score - 0.5).abs() < 0.0001);
+
+        // Favouring vectors puts b first
+        let result = WeightedFuser::new(0.8).fuse(results(), 2);
+        assert_eq!(result.hits[0].doc_ref, b);
+        assert!((result.hits[0].score - 0.8).abs() < 0.0001);
+        assert_eq!(result.hits[1].doc_ref, a);
+        assert_eq!(result.hits[1].rank, 2);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn test_weighted_fuser_clamps_weight() {
+        assert_eq!(WeightedFuser::new(3.0).vector_weight(), 1.0);
+        assert_eq!(WeightedFuser::new(-1.0).vector_weight(), 0.0);
+        assert_eq!(WeightedFuser::default().name(), "weighted");
+    }
 }
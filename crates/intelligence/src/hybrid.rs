@@ -15,16 +15,21 @@
 //!
 //! HybridSearch is STATELESS. It holds only references to Database and primitives.
 
-use crate::fuser::{Fuser, RRFFuser, SimpleFuser};
+use crate::fuser::{FusedResult, Fuser, RRFFuser, SimpleFuser};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
+use strata_core::types::BranchId;
+use strata_core::EntityRef;
 use strata_core::PrimitiveType;
 use strata_core::StrataResult;
-use strata_engine::search::{SearchBudget, SearchMode, SearchRequest, SearchResponse, SearchStats};
-#[cfg(feature = "embed")]
-use strata_engine::search::SearchHit;
+use strata_engine::search::{
+    SearchBudget, SearchHit, SearchMode, SearchRequest, SearchResponse, SearchStats,
+};
 use strata_engine::Database;
-use strata_engine::{BranchIndex, EventLog, JsonStore, KVStore, StateCell, VectorStore};
+use strata_engine::{
+    BranchIndex, EventLog, JsonStore, KVStore, StateCell, TextSearch, VectorStore,
+};
 
 // Shadow vector collection names (duplicated from executor::embed_hook to avoid
 // cross-crate dependency from intelligence → executor).
@@ -37,6 +42,11 @@ const SHADOW_EVENT: &str = "_system_embed_event";
 #[cfg(feature = "embed")]
 const SHADOW_STATE: &str = "_system_embed_state";
 
+/// How many candidates each ranker contributes per requested result in
+/// `text_and_vector()`. Fetching deeper than `k` lets a document that is
+/// mediocre in one list but strong in the other still reach the top-k.
+const HYBRID_CANDIDATE_FACTOR: usize = 3;
+
 // ============================================================================
 // HybridSearch
 // ============================================================================
@@ -81,8 +91,7 @@ const SHADOW_STATE: &str = "_system_embed_state";
 /// All search state is ephemeral per-request.
 #[derive(Clone)]
 pub struct HybridSearch {
-    /// Database reference (kept for future snapshot consistency)
-    #[allow(dead_code)]
+    /// Database reference
     db: Arc<Database>,
     /// Fuser for combining results
     fuser: Arc<dyn Fuser>,
@@ -226,6 +235,10 @@ impl HybridSearch {
                             .partial_cmp(&a.score)
                             .unwrap_or(std::cmp::Ordering::Equal)
                     });
+                    // Long records have one vector per chunk: keep each
+                    // record's best chunk so RRF counts it once.
+                    let mut seen = std::collections::HashSet::new();
+                    vector_hits.retain(|hit| seen.insert(hit.doc_ref.clone()));
                     for (i, hit) in vector_hits.iter_mut().enumerate() {
                         hit.rank = (i + 1) as u32;
                     }
@@ -321,6 +334,8 @@ impl HybridSearch {
             // - For vector/hybrid search with embeddings, the orchestrator
             //   should call vector.search_response() directly with the embedding
             PrimitiveType::Vector => Searchable::search(&self.vector, req),
+            // Lists hold queued work items, not searchable documents
+            PrimitiveType::List => Ok(SearchResponse::empty()),
         }
     }
 
@@ -332,6 +347,119 @@ impl HybridSearch {
     pub fn vector(&self) -> &VectorStore {
         &self.vector
     }
+
+    // ========================================================================
+    // Text + Vector Fusion
+    // ========================================================================
+
+    /// Fuse BM25 full-text search with vector similarity in one space
+    ///
+    /// Runs `query` against the full-text index and `embedding` against
+    /// `collection`, then combines the two rankings with `fuser`. The text
+    /// list is tagged with the primitive of its best hit and the vector
+    /// list with `PrimitiveType::Vector`, so weight-aware fusers can tell
+    /// them apart.
+    ///
+    /// A vector whose key equals the key of a KV entry or the id of a JSON
+    /// document in the same space is treated as that document, which is how
+    /// RAG callers usually store a chunk and its embedding. Other vectors
+    /// are returned as vector hits.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the collection does not exist or `embedding` does not
+    /// match its dimension.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_and_vector(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        query: &str,
+        collection: &str,
+        embedding: &[f32],
+        k: usize,
+        fuser: &dyn Fuser,
+    ) -> StrataResult<FusedResult> {
+        let depth = k.saturating_mul(HYBRID_CANDIDATE_FACTOR);
+
+        let text_hits: Vec<SearchHit> = TextSearch::new(self.db.clone())
+            .text(branch_id, space, query, depth)?
+            .into_iter()
+            .map(|hit| SearchHit::new(hit.doc_ref, hit.score, hit.rank).with_snippet(hit.snippet))
+            .collect();
+
+        // Vector keys that name a document already found by text search
+        let mut by_key: HashMap<&str, &EntityRef> = HashMap::new();
+        for hit in &text_hits {
+            match &hit.doc_ref {
+                EntityRef::Kv { key, .. } => {
+                    by_key.entry(key.as_str()).or_insert(&hit.doc_ref);
+                }
+                EntityRef::Json { doc_id, .. } => {
+                    by_key.entry(doc_id.as_str()).or_insert(&hit.doc_ref);
+                }
+                _ => {}
+            }
+        }
+
+        let matches = self
+            .vector
+            .search(*branch_id, space, collection, embedding, depth, None)?;
+        let mut vector_hits = Vec::with_capacity(matches.len());
+        for (i, m) in matches.into_iter().enumerate() {
+            let doc_ref = match by_key.get(m.key.as_str()) {
+                Some(doc_ref) => (*doc_ref).clone(),
+                None => self.resolve_vector_key(branch_id, space, collection, m.key)?,
+            };
+            vector_hits.push(SearchHit::new(doc_ref, m.score, (i + 1) as u32));
+        }
+
+        let text_primitive = text_hits
+            .first()
+            .map(|hit| hit.doc_ref.primitive_type())
+            .unwrap_or(PrimitiveType::Kv);
+        let stats = || SearchStats::new(0, 0);
+        let lists = vec![
+            (
+                text_primitive,
+                SearchResponse::new(text_hits, false, stats()),
+            ),
+            (
+                PrimitiveType::Vector,
+                SearchResponse::new(vector_hits, false, stats()),
+            ),
+        ];
+
+        Ok(fuser.fuse(lists, k))
+    }
+
+    /// Map a vector key to the KV entry or JSON document it names, falling
+    /// back to the vector itself
+    fn resolve_vector_key(
+        &self,
+        branch_id: &BranchId,
+        space: &str,
+        collection: &str,
+        key: String,
+    ) -> StrataResult<EntityRef> {
+        if self.kv.get(branch_id, space, &key)?.is_some() {
+            return Ok(EntityRef::Kv {
+                branch_id: *branch_id,
+                key,
+            });
+        }
+        if self.json.exists(branch_id, space, &key)? {
+            return Ok(EntityRef::Json {
+                branch_id: *branch_id,
+                doc_id: key,
+            });
+        }
+        Ok(EntityRef::Vector {
+            branch_id: *branch_id,
+            collection: collection.to_string(),
+            key,
+        })
+    }
 }
 
 // ============================================================================
@@ -420,7 +548,7 @@ mod tests {
         // Test without filter (all primitives)
         let req_all = SearchRequest::new(branch_id, "test");
         let all_primitives = hybrid.select_primitives(&req_all);
-        assert_eq!(all_primitives.len(), 6); // Kv, Event, State, Branch, Json, Vector
+        assert_eq!(all_primitives.len(), 7); // Kv, Event, State, Branch, Json, Vector, List
     }
 
     #[test]
@@ -490,4 +618,134 @@ mod tests {
         assert!(response.hits.is_empty());
         assert!(!response.truncated);
     }
+
+    fn seed_text_and_vectors(db: &Arc<Database>, branch_id: &BranchId) {
+        use strata_engine::{DistanceMetric, VectorConfig};
+
+        let kv = KVStore::new(db.clone());
+        kv.put(
+            branch_id,
+            "default",
+            "doc:a",
+            Value::String("rust database engine".into()),
+        )
+        .unwrap();
+        kv.put(
+            branch_id,
+            "default",
+            "doc:b",
+            Value::String("cooking fresh pasta".into()),
+        )
+        .unwrap();
+
+        let vector = VectorStore::new(db.clone());
+        let config = VectorConfig::new(3, DistanceMetric::Cosine).unwrap();
+        vector
+            .create_collection(*branch_id, "default", "chunks", config)
+            .unwrap();
+        for (key, embedding) in [
+            ("doc:a", [1.0, 0.0, 0.0]),
+            ("doc:b", [0.0, 1.0, 0.0]),
+            ("orphan", [0.0, 0.0, 1.0]),
+        ] {
+            vector
+                .insert(*branch_id, "default", "chunks", key, &embedding, None)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_text_and_vector_fuses_by_key() {
+        let db = test_db();
+        let branch_id = BranchId::new();
+        seed_text_and_vectors(&db, &branch_id);
+        let hybrid = HybridSearch::new(db);
+
+        // Text ranks doc:a first; the embedding is closest to doc:b, then
+        // orphan, then doc:a
+        let fused = hybrid
+            .text_and_vector(
+                &branch_id,
+                "default",
+                "database",
+                "chunks",
+                &[0.0, 1.0, 0.1],
+                10,
+                &RRFFuser::default(),
+            )
+            .unwrap();
+
+        let refs: Vec<_> = fused.hits.iter().map(|h| h.doc_ref.clone()).collect();
+        assert_eq!(
+            refs,
+            vec![
+                EntityRef::Kv {
+                    branch_id,
+                    key: "doc:a".into()
+                },
+                EntityRef::Kv {
+                    branch_id,
+                    key: "doc:b".into()
+                },
+                EntityRef::Vector {
+                    branch_id,
+                    collection: "chunks".into(),
+                    key: "orphan".into()
+                },
+            ]
+        );
+        // doc:a appears in both lists and keeps its text snippet
+        assert!(fused.hits[0]
+            .snippet
+            .as_deref()
+            .unwrap()
+            .contains("database"));
+        assert!(fused.hits[1].snippet.is_none());
+
+        // Leaning fully on vectors follows the embedding instead
+        let fused = hybrid
+            .text_and_vector(
+                &branch_id,
+                "default",
+                "database",
+                "chunks",
+                &[0.0, 1.0, 0.1],
+                1,
+                &crate::fuser::WeightedFuser::new(1.0),
+            )
+            .unwrap();
+        assert_eq!(fused.hits.len(), 1);
+        assert!(fused.truncated);
+        assert_eq!(
+            fused.hits[0].doc_ref,
+            EntityRef::Kv {
+                branch_id,
+                key: "doc:b".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_text_and_vector_rejects_bad_embedding() {
+        let db = test_db();
+        let branch_id = BranchId::new();
+        seed_text_and_vectors(&db, &branch_id);
+        let hybrid = HybridSearch::new(db);
+
+        let fuser = RRFFuser::default();
+        assert!(hybrid
+            .text_and_vector(&branch_id, "default", "rust", "chunks", &[1.0], 5, &fuser)
+            .is_err());
+        assert!(hybrid
+            .text_and_vector(
+                &branch_id,
+                "default",
+                "rust",
+                "missing",
+                &[1.0, 0.0, 0.0],
+                5,
+                &fuser
+            )
+            .is_err());
+    }
 }
@@ -11,6 +11,7 @@
 //! - Basic tokenizer
 //! - Fuser trait for result fusion
 //! - HybridSearch for composite search orchestration
+//! - Text chunking for document embedding
 //! - DatabaseSearchExt extension trait for db.hybrid() accessor
 //!
 //! # Usage
@@ -24,6 +25,7 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod chunking;
 pub mod fuser;
 pub mod hybrid;
 pub mod index;
@@ -40,7 +42,8 @@ use std::sync::Arc;
 use strata_engine::Database;
 
 // Re-export commonly used types
-pub use fuser::{FusedResult, Fuser, RRFFuser, SimpleFuser};
+pub use chunking::{chunk_text, Chunk, ChunkConfig, ChunkStrategy};
+pub use fuser::{FusedResult, Fuser, RRFFuser, SimpleFuser, WeightedFuser};
 pub use hybrid::HybridSearch;
 pub use index::{InvertedIndex, PostingEntry, PostingList};
 pub use scorer::{BM25LiteScorer, Scorer, ScorerContext, SearchDoc};
@@ -0,0 +1,57 @@
+//! Documents and their provenance.
+
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+
+/// A passage of text with metadata, as passed between pipeline stages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    /// Key the document is stored under; `None` until it is stored
+    pub id: Option<String>,
+    /// The text embedded and handed to the model
+    pub page_content: String,
+    /// Metadata stored with the embedding and matched by filters
+    pub metadata: HashMap<String, JsonValue>,
+    /// Similarity to the query, higher is closer; 0 for documents not
+    /// returned by a search
+    pub score: f32,
+    /// Where the document was read from; `None` for documents not returned
+    /// by a search
+    pub source: Option<SourceRef>,
+}
+
+impl Document {
+    /// A document with `page_content` and no metadata.
+    pub fn new(page_content: impl Into<String>) -> Self {
+        Document {
+            page_content: page_content.into(),
+            ..Document::default()
+        }
+    }
+
+    /// Store the document under `id` instead of a generated one.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Add a metadata field.
+    pub fn with_metadata(mut self, field: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.metadata.insert(field.into(), value.into());
+        self
+    }
+}
+
+/// The record a retrieved document was read from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceRef {
+    /// Branch holding the record
+    pub branch: String,
+    /// Space within the branch
+    pub space: String,
+    /// Primitive holding the record: `kv`, `json`, `state` or `event`
+    pub primitive: String,
+    /// Key, document id, cell name or `seq:<n>` for events
+    pub key: String,
+}
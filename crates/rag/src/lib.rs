@@ -0,0 +1,93 @@
+//! Retrieval adapters for RAG pipelines
+//!
+//! Puts Strata behind the small set of traits most Rust RAG frameworks
+//! expect, so an existing pipeline can swap its vector store for Strata
+//! without rewriting the retrieval step:
+//!
+//! - [`VectorStore`]: add, search and delete [`Document`]s.
+//! - [`Retriever`]: turn a query into the documents to put in the prompt.
+//! - [`Embedder`]: the embedding model the pipeline already uses.
+//!
+//! Two implementations are provided:
+//!
+//! - [`StrataVectorStore`] embeds with your [`Embedder`], keeps each
+//!   document's text in KV and its embedding in a vector collection under
+//!   the same key, and supports [`MetadataFilter`]s.
+//! - [`SemanticRetriever`] needs no embedder: it searches the values Strata
+//!   embeds itself on write (`auto_embed`, `embed` feature).
+//!
+//! Every returned document carries a [`SourceRef`] naming the record it
+//! came from, so answers can cite their sources.
+//!
+//! ```text
+//! use strata_rag::{Document, Retriever, StrataVectorStore, VectorStore};
+//!
+//! let store = StrataVectorStore::new(db.new_handle()?, "docs", my_embedder);
+//! store.add_documents(&[
+//!     Document::new("Strata stores agent state").with_metadata("lang", "en"),
+//! ])?;
+//!
+//! for doc in store.as_retriever(4).retrieve("where is agent state kept?")? {
+//!     println!("{:.2} {} ({})", doc.score, doc.page_content, doc.source.unwrap().key);
+//! }
+//! ```
+
+mod document;
+mod semantic;
+mod store;
+
+pub use document::{Document, SourceRef};
+pub use semantic::SemanticRetriever;
+pub use store::{StoreRetriever, StrataVectorStore};
+
+pub use strata_executor::{Error, FilterOp, MetadataFilter, Result, Value};
+
+/// A source of text embeddings.
+///
+/// Same shape as the embedder behind auto-embedding: errors are reported
+/// as strings and surface as [`Error::Internal`].
+pub trait Embedder: Send + Sync {
+    /// Embed `text` into a single vector.
+    fn embed(&self, text: &str) -> std::result::Result<Vec<f32>, String>;
+
+    /// Embed several texts, returning one vector per text in order.
+    ///
+    /// Backends that can run a batch at once override this; the default
+    /// embeds each text in turn.
+    fn embed_batch(&self, texts: &[&str]) -> std::result::Result<Vec<Vec<f32>>, String> {
+        texts.iter().map(|text| self.embed(text)).collect()
+    }
+}
+
+/// A store of embedded documents.
+pub trait VectorStore {
+    /// Embed and store `documents`, returning their ids in order.
+    ///
+    /// Documents without an id get a random one. Storing a document under
+    /// an existing id replaces it.
+    fn add_documents(&self, documents: &[Document]) -> Result<Vec<String>>;
+
+    /// Return the `k` documents most similar to `query` whose metadata
+    /// matches every filter in `filter`, best first.
+    fn similarity_search(
+        &self,
+        query: &str,
+        k: usize,
+        filter: &[MetadataFilter],
+    ) -> Result<Vec<Document>>;
+
+    /// Remove the documents with these ids. Unknown ids are ignored.
+    fn delete(&self, ids: &[String]) -> Result<()>;
+}
+
+/// Retrieves the documents relevant to a query.
+pub trait Retriever {
+    /// Documents relevant to `query`, best first.
+    fn retrieve(&self, query: &str) -> Result<Vec<Document>>;
+}
+
+fn embed_error(reason: String) -> Error {
+    Error::Internal {
+        reason: format!("embedding failed: {}", reason),
+    }
+}
@@ -0,0 +1,64 @@
+//! [`Retriever`] over auto-embedding.
+
+use std::collections::HashMap;
+
+use strata_executor::{Result, Strata};
+
+use crate::{Document, Retriever, SourceRef};
+
+/// [`Retriever`] over the values Strata embeds itself on write.
+///
+/// Searches the KV values, JSON documents, state cells and events of the
+/// handle's current branch and space with
+/// [`Search::semantic`](strata_executor::Search::semantic). Each document
+/// is the best-matching passage of one record, with the record as its
+/// [`SourceRef`]. Requires `auto_embed` and the executor's `embed`
+/// feature; metadata filters are not supported.
+pub struct SemanticRetriever {
+    db: Strata,
+    k: u64,
+    rerank_top_n: Option<u64>,
+}
+
+impl SemanticRetriever {
+    /// A retriever returning the `k` closest records.
+    pub fn new(db: Strata, k: u64) -> Self {
+        SemanticRetriever {
+            db,
+            k,
+            rerank_top_n: None,
+        }
+    }
+
+    /// Rerank the `top_n` closest records with the cross-encoder and keep
+    /// the best `k`.
+    pub fn reranked(mut self, top_n: u64) -> Self {
+        self.rerank_top_n = Some(top_n);
+        self
+    }
+}
+
+impl Retriever for SemanticRetriever {
+    fn retrieve(&self, query: &str) -> Result<Vec<Document>> {
+        let search = self.db.search();
+        let hits = match self.rerank_top_n {
+            Some(top_n) => search.semantic_reranked(query, self.k, top_n)?,
+            None => search.semantic(query, self.k)?,
+        };
+        Ok(hits
+            .into_iter()
+            .map(|hit| Document {
+                id: Some(hit.entity.clone()),
+                page_content: hit.snippet.unwrap_or_default(),
+                metadata: HashMap::new(),
+                score: hit.score,
+                source: Some(SourceRef {
+                    branch: self.db.current_branch().to_string(),
+                    space: self.db.current_space().to_string(),
+                    primitive: hit.primitive,
+                    key: hit.entity,
+                }),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,198 @@
+//! [`VectorStore`] over a vector collection and KV.
+//!
+//! A document with id `doc-1` is stored twice, under the same key: its text
+//! as a KV string, and its embedding and metadata in the collection. Keeping
+//! the text in KV makes it visible to full-text and hybrid search, which
+//! rank a vector and a KV value with the same key as one document.
+
+use serde_json::Value as JsonValue;
+use strata_executor::{
+    BatchVectorEntry, BranchId, Command, DistanceMetric, Error, MetadataFilter, Output, Result,
+    Strata, Value,
+};
+
+use crate::{embed_error, Document, Embedder, Retriever, SourceRef, VectorStore};
+
+/// [`VectorStore`] over one collection in the handle's current branch and
+/// space.
+///
+/// The collection is created with cosine distance on the first
+/// [`add_documents`](VectorStore::add_documents), sized to the embedder's
+/// vectors.
+pub struct StrataVectorStore<E> {
+    db: Strata,
+    collection: String,
+    embedder: E,
+}
+
+impl<E: Embedder> StrataVectorStore<E> {
+    /// A store over `collection`, embedding with `embedder`.
+    pub fn new(db: Strata, collection: impl Into<String>, embedder: E) -> Self {
+        StrataVectorStore {
+            db,
+            collection: collection.into(),
+            embedder,
+        }
+    }
+
+    /// A [`Retriever`] returning the `k` most similar documents.
+    pub fn as_retriever(&self, k: usize) -> StoreRetriever<'_, E> {
+        StoreRetriever {
+            store: self,
+            k,
+            filter: Vec::new(),
+        }
+    }
+
+    /// The database handle the store reads and writes through.
+    pub fn db(&self) -> &Strata {
+        &self.db
+    }
+
+    fn ensure_collection(&self, dimension: usize) -> Result<()> {
+        match self.db.vector_collection_stats(&self.collection) {
+            Ok(_) => Ok(()),
+            Err(Error::CollectionNotFound { .. }) => self
+                .db
+                .vector_create_collection(
+                    &self.collection,
+                    dimension as u64,
+                    DistanceMetric::Cosine,
+                )
+                .map(drop),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn source(&self, key: String) -> SourceRef {
+        SourceRef {
+            branch: self.db.current_branch().to_string(),
+            space: self.db.current_space().to_string(),
+            primitive: "kv".to_string(),
+            key,
+        }
+    }
+}
+
+impl<E: Embedder> VectorStore for StrataVectorStore<E> {
+    fn add_documents(&self, documents: &[Document]) -> Result<Vec<String>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        let texts: Vec<&str> = documents.iter().map(|d| d.page_content.as_str()).collect();
+        let vectors = self.embedder.embed_batch(&texts).map_err(embed_error)?;
+        if vectors.len() != documents.len() {
+            return Err(embed_error(format!(
+                "{} vectors for {} documents",
+                vectors.len(),
+                documents.len()
+            )));
+        }
+        self.ensure_collection(vectors[0].len())?;
+
+        let mut ids = Vec::with_capacity(documents.len());
+        let mut entries = Vec::with_capacity(documents.len());
+        for (document, vector) in documents.iter().zip(vectors) {
+            let id = document
+                .id
+                .clone()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+            self.db.kv_put(&id, document.page_content.as_str())?;
+            let metadata = JsonValue::Object(document.metadata.clone().into_iter().collect());
+            let metadata = Value::try_from(&metadata).map_err(|e| Error::InvalidInput {
+                reason: format!("metadata of document '{}': {}", id, e),
+            })?;
+            entries.push(BatchVectorEntry {
+                key: id.clone(),
+                vector,
+                metadata: Some(metadata),
+            });
+            ids.push(id);
+        }
+        self.db.vector_batch_upsert(&self.collection, entries)?;
+        Ok(ids)
+    }
+
+    fn similarity_search(
+        &self,
+        query: &str,
+        k: usize,
+        filter: &[MetadataFilter],
+    ) -> Result<Vec<Document>> {
+        let embedding = self.embedder.embed(query).map_err(embed_error)?;
+        let matches = match self.db.executor().execute(Command::VectorSearch {
+            branch: Some(BranchId::from(self.db.current_branch())),
+            space: Some(self.db.current_space().to_string()),
+            collection: self.collection.clone(),
+            query: embedding,
+            k: k as u64,
+            filter: (!filter.is_empty()).then(|| filter.to_vec()),
+            metric: None,
+            as_of: None,
+            namespace: None,
+        }) {
+            Ok(Output::VectorMatches(matches)) => matches,
+            Ok(_) => {
+                return Err(Error::Internal {
+                    reason: "Unexpected output for VectorSearch".into(),
+                })
+            }
+            // Nothing has been added yet
+            Err(Error::CollectionNotFound { .. }) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut documents = Vec::with_capacity(matches.len());
+        for m in matches {
+            // Text removed behind the store's back: the embedding is stale
+            let Some(Value::String(page_content)) = self.db.kv_get(&m.key)? else {
+                continue;
+            };
+            let metadata = match m.metadata.map(JsonValue::from) {
+                Some(JsonValue::Object(fields)) => fields.into_iter().collect(),
+                _ => Default::default(),
+            };
+            documents.push(Document {
+                id: Some(m.key.clone()),
+                page_content,
+                metadata,
+                score: m.score,
+                source: Some(self.source(m.key)),
+            });
+        }
+        Ok(documents)
+    }
+
+    fn delete(&self, ids: &[String]) -> Result<()> {
+        for id in ids {
+            match self.db.vector_delete(&self.collection, id) {
+                Ok(_) | Err(Error::CollectionNotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+            self.db.kv_delete(id)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`Retriever`] over a [`StrataVectorStore`], from
+/// [`as_retriever`](StrataVectorStore::as_retriever).
+pub struct StoreRetriever<'a, E> {
+    store: &'a StrataVectorStore<E>,
+    k: usize,
+    filter: Vec<MetadataFilter>,
+}
+
+impl<E> StoreRetriever<'_, E> {
+    /// Only retrieve documents whose metadata matches `filter`.
+    pub fn with_filter(mut self, filter: MetadataFilter) -> Self {
+        self.filter.push(filter);
+        self
+    }
+}
+
+impl<E: Embedder> Retriever for StoreRetriever<'_, E> {
+    fn retrieve(&self, query: &str) -> Result<Vec<Document>> {
+        self.store.similarity_search(query, self.k, &self.filter)
+    }
+}
@@ -0,0 +1,127 @@
+//! The vector store and its retriever, with a keyword-counting embedder.
+
+use strata_executor::Strata;
+use strata_rag::{
+    Document, Embedder, Error, FilterOp, MetadataFilter, Retriever, StrataVectorStore, Value,
+    VectorStore,
+};
+
+const VOCABULARY: [&str; 4] = ["agent", "memory", "vector", "weather"];
+
+/// Counts vocabulary words; the last dimension keeps vectors non-zero.
+struct Keywords;
+
+impl Embedder for Keywords {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, String> {
+        let mut vector: Vec<f32> = VOCABULARY
+            .iter()
+            .map(|word| text.matches(word).count() as f32)
+            .collect();
+        vector.push(0.1);
+        Ok(vector)
+    }
+}
+
+struct Broken;
+
+impl Embedder for Broken {
+    fn embed(&self, _text: &str) -> Result<Vec<f32>, String> {
+        Err("model not loaded".into())
+    }
+}
+
+fn store() -> (Strata, StrataVectorStore<Keywords>) {
+    let db = Strata::cache().unwrap();
+    let store = StrataVectorStore::new(db.new_handle().unwrap(), "docs", Keywords);
+    (db, store)
+}
+
+fn add_corpus(store: &StrataVectorStore<Keywords>) -> Vec<String> {
+    store
+        .add_documents(&[
+            Document::new("agent memory lives in the event log")
+                .with_id("a")
+                .with_metadata("lang", "en"),
+            Document::new("vector search over agent memory")
+                .with_id("b")
+                .with_metadata("lang", "de"),
+            Document::new("weather report").with_metadata("lang", "en"),
+        ])
+        .unwrap()
+}
+
+#[test]
+fn search_returns_text_metadata_and_source() {
+    let (db, store) = store();
+    let ids = add_corpus(&store);
+    assert_eq!(&ids[..2], ["a", "b"]);
+    assert!(!ids[2].is_empty());
+
+    // Text is plain KV, visible to the rest of the database
+    assert_eq!(
+        db.kv_get("a").unwrap(),
+        Some(Value::String("agent memory lives in the event log".into()))
+    );
+
+    let docs = store.similarity_search("agent memory", 2, &[]).unwrap();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].id.as_deref(), Some("a"));
+    assert!(docs[0].score >= docs[1].score);
+    assert_eq!(docs[0].page_content, "agent memory lives in the event log");
+    assert_eq!(docs[0].metadata["lang"], "en");
+
+    let source = docs[0].source.as_ref().unwrap();
+    assert_eq!(source.branch, "default");
+    assert_eq!(source.space, "default");
+    assert_eq!(source.primitive, "kv");
+    assert_eq!(source.key, "a");
+}
+
+#[test]
+fn retriever_applies_metadata_filters() {
+    let (_db, store) = store();
+    add_corpus(&store);
+
+    let german = MetadataFilter {
+        field: "lang".into(),
+        op: FilterOp::Eq,
+        value: Value::from("de"),
+        filters: Vec::new(),
+    };
+    let docs = store
+        .as_retriever(5)
+        .with_filter(german)
+        .retrieve("agent memory")
+        .unwrap();
+    let ids: Vec<_> = docs.iter().map(|d| d.id.as_deref().unwrap()).collect();
+    assert_eq!(ids, ["b"]);
+}
+
+#[test]
+fn replace_and_delete_by_id() {
+    let (db, store) = store();
+    assert!(store.similarity_search("agent", 3, &[]).unwrap().is_empty());
+    add_corpus(&store);
+
+    store
+        .add_documents(&[Document::new("weather on the vector coast").with_id("a")])
+        .unwrap();
+    let docs = store.similarity_search("weather vector", 1, &[]).unwrap();
+    assert_eq!(docs[0].id.as_deref(), Some("a"));
+    assert!(docs[0].metadata.is_empty());
+
+    store
+        .delete(&["a".to_string(), "missing".to_string()])
+        .unwrap();
+    assert_eq!(db.kv_get("a").unwrap(), None);
+    let docs = store.similarity_search("weather vector", 5, &[]).unwrap();
+    assert!(docs.iter().all(|d| d.id.as_deref() != Some("a")));
+}
+
+#[test]
+fn embedder_errors_surface() {
+    let db = Strata::cache().unwrap();
+    let store = StrataVectorStore::new(db, "docs", Broken);
+    let err = store.add_documents(&[Document::new("text")]).unwrap_err();
+    assert!(matches!(err, Error::Internal { ref reason } if reason.contains("model not loaded")));
+}
@@ -6,6 +6,11 @@
 #![warn(missing_docs)]
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use strata_engine::{
+    CancellationToken, CheckpointPolicy, EmbedProvider, EvictionPolicy, MemoryBudget,
+    RecoveryObserver, RecoveryProgress, RerankProvider, WorkerPool,
+};
 
 /// Controls whether the database allows writes or is read-only.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -33,6 +38,30 @@ pub struct OpenOptions {
     /// Enable automatic text embedding for semantic search.
     /// `None` means "use the config file default".
     pub auto_embed: Option<bool>,
+    /// Source of embeddings for auto-embedding and semantic search.
+    /// `None` means "use the config file default".
+    pub embed_provider: Option<EmbedProvider>,
+    /// Source of relevance scores for reranked semantic search.
+    /// `None` means "use the config file default".
+    pub rerank_provider: Option<RerankProvider>,
+    /// Shared pool for background jobs (WAL flush, compaction).
+    /// `None` gives each database its own background threads.
+    pub worker_pool: Option<WorkerPool>,
+    /// Break a write lock left by a crashed writer before opening.
+    /// Ignored for read-only opens.
+    pub force_unlock: bool,
+    /// Run transaction begin and commit inside `tracing` spans.
+    pub trace_commits: bool,
+    /// Number of hot KV and state reads to cache. 0 disables the cache.
+    pub read_cache: usize,
+    /// Progress callback and cancellation token for WAL replay at open.
+    pub recovery: RecoveryObserver,
+    /// When to checkpoint and truncate the WAL in the background.
+    /// `None` leaves checkpoints to the application.
+    pub checkpoint: Option<CheckpointPolicy>,
+    /// Memory limit and eviction policy of a cache database.
+    /// A limit of 0 means unlimited.
+    pub memory_budget: MemoryBudget,
 }
 
 impl OpenOptions {
@@ -52,6 +81,122 @@ impl OpenOptions {
         self.auto_embed = Some(enabled);
         self
     }
+
+    /// Choose where embeddings come from.
+    ///
+    /// [`EmbedProvider::Http`] calls an OpenAI-compatible endpoint, so
+    /// auto-embedding works without local model files.
+    pub fn embed_provider(mut self, provider: EmbedProvider) -> Self {
+        self.embed_provider = Some(provider);
+        self
+    }
+
+    /// Choose where reranking scores come from.
+    ///
+    /// [`RerankProvider::Http`] calls a Cohere-compatible rerank endpoint
+    /// instead of the built-in cross-encoder.
+    pub fn rerank_provider(mut self, provider: RerankProvider) -> Self {
+        self.rerank_provider = Some(provider);
+        self
+    }
+
+    /// Run background jobs on a shared worker pool.
+    ///
+    /// Embedders opening many databases use one pool to bound the total
+    /// number of background threads, and to name or pin them.
+    pub fn worker_pool(mut self, pool: WorkerPool) -> Self {
+        self.worker_pool = Some(pool);
+        self
+    }
+
+    /// Break a stale write lock before opening.
+    ///
+    /// Operator recovery for a lock still held after its writer crashed.
+    /// The open still fails if the lock's owner is running on this host.
+    pub fn force_unlock(mut self, force: bool) -> Self {
+        self.force_unlock = force;
+        self
+    }
+
+    /// Emit `tracing` spans for each transaction's begin and commit.
+    ///
+    /// Commits get child spans for validation, the WAL append and the
+    /// storage apply, so a subscriber shows where commit time goes. Off by
+    /// default to keep the commit path free of span overhead.
+    pub fn trace_commits(mut self, enabled: bool) -> Self {
+        self.trace_commits = enabled;
+        self
+    }
+
+    /// Cache up to `capacity` recently read KV entries and state cells.
+    ///
+    /// Repeated reads of a hot key are answered without a snapshot or a
+    /// decode. Writes evict the keys they touch, so reads still return the
+    /// latest committed value. Off (0) by default.
+    pub fn read_cache(mut self, capacity: usize) -> Self {
+        self.read_cache = capacity;
+        self
+    }
+
+    /// Report WAL replay progress while the database opens.
+    ///
+    /// `callback` runs on the opening thread, about every 100ms and after
+    /// each WAL segment, with the bytes and records replayed so far and an
+    /// ETA. Large databases can use it to drive a progress bar.
+    pub fn on_recovery_progress(
+        mut self,
+        callback: impl Fn(RecoveryProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.recovery.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Abort WAL replay when `token` is cancelled.
+    ///
+    /// The open then fails with a cancelled error, releasing the write lock
+    /// and leaving the data directory untouched.
+    pub fn cancel_recovery(mut self, token: CancellationToken) -> Self {
+        self.recovery.cancel = Some(token);
+        self
+    }
+
+    /// Checkpoint and truncate the WAL automatically.
+    ///
+    /// Accepts a [`CheckpointPolicy`] (`Transactions(n)`, `WalBytes(n)`) or
+    /// a `Duration`. Each time the policy is met, a background job writes a
+    /// snapshot and compacts the WAL, bounding how long recovery takes.
+    /// Compaction keeps every version storage holds, so history is only
+    /// pruned as far as retention policies say.
+    /// Ignored for read-only and cache databases.
+    pub fn checkpoint_every(mut self, policy: impl Into<CheckpointPolicy>) -> Self {
+        self.checkpoint = Some(policy.into());
+        self
+    }
+
+    /// Cap the memory a cache database holds, in approximate bytes.
+    ///
+    /// Past the limit the [`eviction_policy`](Self::eviction_policy)
+    /// applies: least recently used keys are evicted by default. Branch
+    /// metadata, events and internal records are never evicted. Only cache
+    /// databases accept a limit;
+    /// opening a database on disk with one fails.
+    pub fn max_memory(mut self, bytes: usize) -> Self {
+        self.memory_budget.max_bytes = bytes;
+        self
+    }
+
+    /// Choose what happens once [`max_memory`](Self::max_memory) is exceeded.
+    pub fn eviction_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.memory_budget.policy = policy;
+        self
+    }
+
+    /// Set the whole memory budget, including which key types are exempt
+    /// from eviction.
+    pub fn memory_budget(mut self, budget: MemoryBudget) -> Self {
+        self.memory_budget = budget;
+        self
+    }
 }
 
 impl Default for OpenOptions {
@@ -59,6 +204,15 @@ impl Default for OpenOptions {
         Self {
             access_mode: AccessMode::ReadWrite,
             auto_embed: None,
+            embed_provider: None,
+            rerank_provider: None,
+            worker_pool: None,
+            force_unlock: false,
+            trace_commits: false,
+            read_cache: 0,
+            recovery: RecoveryObserver::default(),
+            checkpoint: None,
+            memory_budget: MemoryBudget::default(),
         }
     }
 }
@@ -0,0 +1,114 @@
+//! Read Path Benchmarks
+//!
+//! Measures point reads of the latest value, which is what the
+//! `epoch-reads` feature changes:
+//! - `read_path/get`: `Storage::get` with no concurrent writes
+//! - `read_path/get_with_writer`: `Storage::get` while another thread keeps
+//!   committing batches to the same branch
+//! - `read_path/get_versioned_with_writer`: snapshot read at the current
+//!   version under the same write load
+//!
+//! The feature stays off by default; it should only be turned on where these
+//! numbers improve. Compare the two builds with criterion baselines:
+//!
+//! ```text
+//! cargo bench -p strata-storage --bench read_path_benchmarks -- --save-baseline dashmap
+//! cargo bench -p strata-storage --bench read_path_benchmarks --features epoch-reads -- --baseline dashmap
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use strata_core::traits::Storage;
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::value::Value;
+use strata_storage::ShardedStore;
+
+/// Keys pre-populated in the read branch.
+const KEYS: usize = 10_000;
+
+/// Keys written per batch by the background writer.
+const WRITER_BATCH: usize = 16;
+
+// ============================================================================
+// Setup
+// ============================================================================
+
+fn test_key(branch_id: BranchId, i: u64) -> Key {
+    let ns = Namespace::new(
+        "tenant".to_string(),
+        "app".to_string(),
+        "agent".to_string(),
+        branch_id,
+        "default".to_string(),
+    );
+    Key::new_kv(ns, format!("key{:08}", i))
+}
+
+fn populated_store() -> (Arc<ShardedStore>, Vec<Key>) {
+    let store = Arc::new(ShardedStore::new());
+    let branch_id = BranchId::new();
+    let keys: Vec<Key> = (0..KEYS as u64).map(|i| test_key(branch_id, i)).collect();
+    let writes: Vec<(Key, Value)> = keys
+        .iter()
+        .map(|key| (key.clone(), Value::Int(0)))
+        .collect();
+    store.apply_batch(&writes, &[], 1).unwrap();
+    (store, keys)
+}
+
+/// Background thread committing batches over `keys` until `stop` is set.
+fn spawn_writer(store: Arc<ShardedStore>, keys: Vec<Key>, stop: Arc<AtomicBool>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut i = 0;
+        while !stop.load(Ordering::Relaxed) {
+            let writes: Vec<(Key, Value)> = (0..WRITER_BATCH)
+                .map(|j| (keys[(i + j) % keys.len()].clone(), Value::Int(i as i64)))
+                .collect();
+            let version = store.next_version();
+            store.apply_batch(&writes, &[], version).unwrap();
+            i += WRITER_BATCH;
+        }
+    })
+}
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+fn bench_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_path");
+    group.throughput(Throughput::Elements(1));
+
+    let (store, keys) = populated_store();
+    let counter = AtomicU64::new(0);
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            let i = counter.fetch_add(1, Ordering::Relaxed) as usize % keys.len();
+            store.get(&keys[i]).unwrap()
+        })
+    });
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let writer = spawn_writer(Arc::clone(&store), keys.clone(), Arc::clone(&stop));
+    group.bench_function("get_with_writer", |b| {
+        b.iter(|| {
+            let i = counter.fetch_add(1, Ordering::Relaxed) as usize % keys.len();
+            store.get(&keys[i]).unwrap()
+        })
+    });
+    group.bench_function("get_versioned_with_writer", |b| {
+        b.iter(|| {
+            let i = counter.fetch_add(1, Ordering::Relaxed) as usize % keys.len();
+            store.get_versioned(&keys[i], store.version()).unwrap()
+        })
+    });
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_get);
+criterion_main!(benches);
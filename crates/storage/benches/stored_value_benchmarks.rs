@@ -0,0 +1,181 @@
+//! Stored Value Benchmarks
+//!
+//! Measures the storage-layer cost of small values, which dominate agent
+//! workloads (flags, counters, short strings):
+//! - `stored_value/get`: latest-version read of a pre-populated key
+//! - `stored_value/apply_batch`: committing a single-write batch
+//!
+//! Each group runs an integer, strings that fit inline in `StoredValue`
+//! (16 and 48 bytes) and a string that does not (256 bytes).
+//!
+//! Run with `cargo bench -p strata-storage --bench stored_value_benchmarks`.
+//! After criterion finishes, heap allocations per operation for each case
+//! are printed, counted by a wrapping global allocator over a fixed run
+//! outside criterion's timing.
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+use strata_core::traits::Storage;
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::value::Value;
+use strata_storage::ShardedStore;
+
+/// Keys pre-populated for the get group.
+const GET_KEYS: usize = 10_000;
+
+/// Operations whose allocations are counted and averaged.
+const ALLOC_MEASURED_OPS: usize = 1_000;
+
+// ============================================================================
+// Allocation counting
+// ============================================================================
+
+/// Counts allocations (including reallocations) made by any thread.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+// ============================================================================
+// Setup
+// ============================================================================
+
+fn test_key(branch_id: BranchId, i: u64) -> Key {
+    let ns = Namespace::new(
+        "tenant".to_string(),
+        "app".to_string(),
+        "agent".to_string(),
+        branch_id,
+        "default".to_string(),
+    );
+    Key::new_kv(ns, format!("key{:08}", i))
+}
+
+/// Value cases: name and value.
+fn cases() -> Vec<(&'static str, Value)> {
+    vec![
+        ("int", Value::Int(42)),
+        ("str_16b", Value::String("x".repeat(16))),
+        ("str_48b", Value::String("x".repeat(48))),
+        ("str_256b", Value::String("x".repeat(256))),
+    ]
+}
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+fn bench_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stored_value/get");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, value) in cases() {
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let keys: Vec<Key> = (0..GET_KEYS as u64)
+            .map(|i| test_key(branch_id, i))
+            .collect();
+        for (i, key) in keys.iter().enumerate() {
+            store
+                .apply_batch(&[(key.clone(), value.clone())], &[], i as u64 + 1)
+                .unwrap();
+        }
+
+        let counter = AtomicU64::new(0);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &keys, |b, keys| {
+            b.iter(|| {
+                let i = counter.fetch_add(1, Ordering::Relaxed) as usize % keys.len();
+                store.get(&keys[i]).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_apply_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stored_value/apply_batch");
+    group.throughput(Throughput::Elements(1));
+
+    for (name, value) in cases() {
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        // Cycle over a fixed key set so map growth doesn't dominate, and
+        // build the writes up front so only the store's own copy is timed.
+        let writes: Vec<[(Key, Value); 1]> = (0..GET_KEYS as u64)
+            .map(|i| [(test_key(branch_id, i), value.clone())])
+            .collect();
+
+        let counter = AtomicU64::new(0);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &writes, |b, writes| {
+            b.iter(|| {
+                let i = counter.fetch_add(1, Ordering::Relaxed);
+                let batch = &writes[i as usize % writes.len()];
+                store.apply_batch(batch, &[], i + 1).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_get, bench_apply_batch);
+
+/// Print average allocations per get and per single-write batch for each case.
+fn report_allocations() {
+    println!("\nallocations per op (get / apply_batch):");
+    for (name, value) in cases() {
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let writes: Vec<[(Key, Value); 1]> = (0..ALLOC_MEASURED_OPS as u64)
+            .map(|i| [(test_key(branch_id, i), value.clone())])
+            .collect();
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for (i, batch) in writes.iter().enumerate() {
+            store.apply_batch(batch, &[], i as u64 + 1).unwrap();
+        }
+        let writes_allocs = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        for [(key, _)] in &writes {
+            std::hint::black_box(store.get(key).unwrap());
+        }
+        let get_allocs = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+        println!(
+            "  {:<10} {:>6.2} / {:>6.2}",
+            name,
+            get_allocs as f64 / ALLOC_MEASURED_OPS as f64,
+            writes_allocs as f64 / ALLOC_MEASURED_OPS as f64,
+        );
+    }
+}
+
+fn main() {
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    report_allocations();
+}
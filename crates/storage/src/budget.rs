@@ -0,0 +1,241 @@
+//! Memory budget for in-memory stores
+//!
+//! A [`MemoryBudget`] caps the approximate bytes a
+//! [`ShardedStore`](crate::ShardedStore) holds. Writes add their size to a
+//! running estimate; once it passes the limit the store measures itself
+//! exactly and then, depending on the [`EvictionPolicy`], drops whole keys
+//! until usage is back under 90% of the limit, or refuses further writes.
+//!
+//! Eviction removes every version of a key as if it had never been
+//! written, so a budget only makes sense for data that is not also in a
+//! WAL. Keys whose type is exempt are never evicted but still count toward
+//! usage, and neither are keys under a reserved prefix (see
+//! [`RESERVED_PREFIXES`]): primitives keep their own metadata there, and
+//! losing it would corrupt the data it describes.
+//!
+//! Recency and frequency are tracked for point reads and writes. Scans do
+//! not count as a use, so a full scan does not make every key look hot.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use strata_core::key::RESERVED_PREFIXES;
+use strata_core::types::{Key, TypeTag};
+
+/// Percentage of the limit that eviction frees memory down to
+///
+/// Evicting below the limit means the next exact measurement is a batch of
+/// writes away rather than on every write.
+pub(crate) const LOW_WATER_PERCENT: usize = 90;
+
+/// What a store does when its memory budget is exceeded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Evict the least recently used keys
+    #[default]
+    Lru,
+    /// Evict the least frequently used keys, oldest first among equals
+    Lfu,
+    /// Evict nothing and fail writes until memory is freed
+    RejectWrites,
+}
+
+/// Memory limit of a store and how it is enforced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// Approximate bytes the store may hold; 0 means unlimited
+    pub max_bytes: usize,
+    /// What happens once `max_bytes` is exceeded
+    pub policy: EvictionPolicy,
+    /// Key types that are never evicted
+    pub exempt: Vec<TypeTag>,
+}
+
+impl MemoryBudget {
+    /// Key types exempt by default: branch and space metadata, vectors,
+    /// whose search index lives outside the store, and events, since
+    /// dropping one breaks the hash chain of its log
+    pub const DEFAULT_EXEMPT: [TypeTag; 5] = [
+        TypeTag::Branch,
+        TypeTag::Space,
+        TypeTag::Vector,
+        TypeTag::VectorConfig,
+        TypeTag::Event,
+    ];
+
+    /// An LRU budget of `max_bytes` with the default exemptions
+    pub fn new(max_bytes: usize) -> Self {
+        MemoryBudget {
+            max_bytes,
+            policy: EvictionPolicy::default(),
+            exempt: Self::DEFAULT_EXEMPT.to_vec(),
+        }
+    }
+
+    /// Set the eviction policy
+    pub fn policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Never evict keys of type `tag`
+    pub fn exempt(mut self, tag: TypeTag) -> Self {
+        if !self.exempt.contains(&tag) {
+            self.exempt.push(tag);
+        }
+        self
+    }
+
+    /// Whether the budget limits anything
+    pub fn is_enabled(&self) -> bool {
+        self.max_bytes > 0
+    }
+
+    /// Whether `key` is never evicted: its type is exempt or it is
+    /// internal metadata under a reserved prefix
+    pub(crate) fn is_exempt(&self, key: &Key) -> bool {
+        self.exempt.contains(&key.type_tag)
+            || RESERVED_PREFIXES
+                .iter()
+                .any(|prefix| key.user_key.starts_with(prefix.as_bytes()))
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Usage and enforcement counters of a store's memory budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryBudgetStats {
+    /// Configured limit (0 when unlimited)
+    pub max_bytes: usize,
+    /// Approximate bytes held, as of the last write or measurement
+    pub used_bytes: usize,
+    /// Keys evicted since the budget was set
+    pub evicted_keys: u64,
+    /// Writes refused under [`EvictionPolicy::RejectWrites`]
+    pub rejected_writes: u64,
+}
+
+/// Last use and number of uses of a key, for choosing eviction victims
+#[derive(Debug, Default)]
+pub(crate) struct AccessStats {
+    last: AtomicU64,
+    uses: AtomicU64,
+}
+
+impl Clone for AccessStats {
+    fn clone(&self) -> Self {
+        AccessStats {
+            last: AtomicU64::new(self.last()),
+            uses: AtomicU64::new(self.uses.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl AccessStats {
+    pub(crate) fn touch(&self, tick: u64) {
+        self.last.store(tick, Ordering::Relaxed);
+        self.uses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn last(&self) -> u64 {
+        self.last.load(Ordering::Relaxed)
+    }
+
+    /// Sort key for `policy`; the lowest is evicted first
+    pub(crate) fn rank(&self, policy: EvictionPolicy) -> (u64, u64) {
+        match policy {
+            EvictionPolicy::Lfu => (self.uses.load(Ordering::Relaxed), self.last()),
+            EvictionPolicy::Lru | EvictionPolicy::RejectWrites => (self.last(), 0),
+        }
+    }
+}
+
+/// Budget configuration and counters owned by a store
+#[derive(Default)]
+pub(crate) struct BudgetState {
+    config: Mutex<MemoryBudget>,
+    /// Copy of `config.max_bytes` for lock-free checks
+    max_bytes: AtomicUsize,
+    /// Whether reads and writes record access (LRU and LFU budgets)
+    tracks_access: AtomicBool,
+    /// Bytes held at the last measurement plus bytes written since
+    estimate: AtomicUsize,
+    /// Logical clock for access recency
+    clock: AtomicU64,
+    evicted: AtomicU64,
+    rejected: AtomicU64,
+    /// Held while measuring and evicting, so one writer does it at a time
+    pub(crate) enforcing: Mutex<()>,
+}
+
+impl BudgetState {
+    pub(crate) fn set(&self, budget: MemoryBudget, used: usize) {
+        let mut config = self.config.lock().unwrap_or_else(|e| e.into_inner());
+        self.estimate.store(used, Ordering::Relaxed);
+        self.tracks_access.store(
+            budget.is_enabled() && budget.policy != EvictionPolicy::RejectWrites,
+            Ordering::Relaxed,
+        );
+        self.max_bytes.store(budget.max_bytes, Ordering::Relaxed);
+        *config = budget;
+    }
+
+    pub(crate) fn config(&self) -> MemoryBudget {
+        self.config
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.max_bytes.load(Ordering::Relaxed) > 0
+    }
+
+    pub(crate) fn tracks_access(&self) -> bool {
+        self.tracks_access.load(Ordering::Relaxed)
+    }
+
+    /// The next access tick, when access is tracked
+    #[inline]
+    pub(crate) fn tick(&self) -> Option<u64> {
+        self.tracks_access()
+            .then(|| self.clock.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Add written bytes to the estimate; true once it exceeds the limit
+    pub(crate) fn record_write(&self, bytes: usize) -> bool {
+        let max = self.max_bytes.load(Ordering::Relaxed);
+        let estimate = self.estimate.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        max > 0 && estimate > max
+    }
+
+    pub(crate) fn is_over_estimate(&self) -> bool {
+        let max = self.max_bytes.load(Ordering::Relaxed);
+        max > 0 && self.estimate.load(Ordering::Relaxed) > max
+    }
+
+    pub(crate) fn set_estimate(&self, used: usize) {
+        self.estimate.store(used, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_evicted(&self, keys: u64) {
+        self.evicted.fetch_add(keys, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self) -> MemoryBudgetStats {
+        MemoryBudgetStats {
+            max_bytes: self.max_bytes.load(Ordering::Relaxed),
+            used_bytes: self.estimate.load(Ordering::Relaxed),
+            evicted_keys: self.evicted.load(Ordering::Relaxed),
+            rejected_writes: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Epoch-reclaimed index of latest values (`epoch-reads` feature)
+//!
+//! [`HotReads`] keeps, per branch, a copy of the newest [`StoredValue`] of
+//! every key in a hash table that readers walk without taking any lock: a
+//! read pins the current epoch, follows atomic pointers and clones the value
+//! out. Nodes, values and tables that writers replace are freed by
+//! crossbeam-epoch once no pinned reader can still see them.
+//!
+//! # Writers
+//!
+//! Each branch's table has exactly one writer at a time. `ShardedStore`
+//! guarantees this by only updating a branch's table while holding that
+//! branch's DashMap shard lock, which every write path already takes.
+//! Branches are added and removed copy-on-write under a separate mutex;
+//! that happens once per branch, not per write.
+//!
+//! # Costs
+//!
+//! - One extra copy of each key's latest value
+//! - One allocation per write (the replacement value)
+//! - When a table doubles, every key is cloned into the new table
+//!
+//! A read that misses the index is not authoritative: callers fall back to
+//! the shard.
+
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+use rustc_hash::{FxHashMap, FxHasher};
+use strata_core::types::{BranchId, Key};
+
+use crate::stored_value::StoredValue;
+
+/// Buckets in a new branch table (power of two)
+const INITIAL_BUCKETS: usize = 64;
+
+struct Node {
+    key: Key,
+    /// Owned by the newest table containing this node's key; older tables
+    /// share the pointer but never free it
+    value: Atomic<StoredValue>,
+    next: Atomic<Node>,
+}
+
+struct Table {
+    buckets: Box<[Atomic<Node>]>,
+    /// Bits to shift the hash right by to get a bucket index
+    shift: u32,
+}
+
+impl Table {
+    fn new(buckets: usize) -> Self {
+        debug_assert!(buckets.is_power_of_two());
+        Table {
+            buckets: (0..buckets).map(|_| Atomic::null()).collect(),
+            shift: u64::BITS - buckets.trailing_zeros(),
+        }
+    }
+
+    fn bucket(&self, key: &Key) -> &Atomic<Node> {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        // FxHash mixes upward, so take the high bits
+        let index = hasher.finish().checked_shr(self.shift).unwrap_or(0) as usize;
+        &self.buckets[index]
+    }
+
+    /// Free the table and its nodes, but not the values they point to.
+    ///
+    /// # Safety
+    ///
+    /// No reader may still reach the table.
+    unsafe fn destroy_nodes(table: Shared<'_, Table>) {
+        let guard = epoch::unprotected();
+        let table = table.into_owned();
+        for bucket in table.buckets.iter() {
+            let mut node = bucket.load(Ordering::Relaxed, guard);
+            while !node.is_null() {
+                let next = node.deref().next.load(Ordering::Relaxed, guard);
+                drop(node.into_owned());
+                node = next;
+            }
+        }
+    }
+}
+
+/// Lock-free latest-value table for one branch
+struct HotIndex {
+    table: Atomic<Table>,
+    len: AtomicUsize,
+}
+
+impl HotIndex {
+    fn new() -> Self {
+        HotIndex {
+            table: Atomic::new(Table::new(INITIAL_BUCKETS)),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    fn get<'g>(&self, key: &Key, guard: &'g Guard) -> Option<&'g StoredValue> {
+        // SAFETY: the table is never null, and anything reachable from it is
+        // only freed after every guard pinned before its removal is dropped.
+        unsafe {
+            let table = self.table.load(Ordering::Acquire, guard).deref();
+            let mut node = table.bucket(key).load(Ordering::Acquire, guard);
+            while let Some(n) = node.as_ref() {
+                if n.key == *key {
+                    return Some(n.value.load(Ordering::Acquire, guard).deref());
+                }
+                node = n.next.load(Ordering::Acquire, guard);
+            }
+        }
+        None
+    }
+
+    /// Set the latest value of `key`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be this index's only writer.
+    unsafe fn set(&self, key: &Key, value: StoredValue, guard: &Guard) {
+        let table = self.table.load(Ordering::Acquire, guard);
+        let bucket = table.deref().bucket(key);
+
+        let mut node = bucket.load(Ordering::Acquire, guard);
+        while let Some(n) = node.as_ref() {
+            if n.key == *key {
+                let old = n.value.swap(Owned::new(value), Ordering::AcqRel, guard);
+                guard.defer_destroy(old);
+                return;
+            }
+            node = n.next.load(Ordering::Acquire, guard);
+        }
+
+        let head = bucket.load(Ordering::Acquire, guard);
+        bucket.store(
+            Owned::new(Node {
+                key: key.clone(),
+                value: Atomic::new(value),
+                next: Atomic::from(head),
+            }),
+            Ordering::Release,
+        );
+        let len = self.len.fetch_add(1, Ordering::Relaxed) + 1;
+        if len > table.deref().buckets.len() {
+            self.grow(table, guard);
+        }
+    }
+
+    /// Double the bucket count, copying nodes into a fresh table.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be this index's only writer.
+    unsafe fn grow(&self, old: Shared<'_, Table>, guard: &Guard) {
+        let old_table = old.deref();
+        let table = Table::new(old_table.buckets.len() * 2);
+        for bucket in old_table.buckets.iter() {
+            let mut node = bucket.load(Ordering::Acquire, guard);
+            while let Some(n) = node.as_ref() {
+                let slot = table.bucket(&n.key);
+                let head = slot.load(Ordering::Relaxed, guard);
+                slot.store(
+                    Owned::new(Node {
+                        key: n.key.clone(),
+                        value: Atomic::from(n.value.load(Ordering::Acquire, guard)),
+                        next: Atomic::from(head),
+                    }),
+                    Ordering::Relaxed,
+                );
+                node = n.next.load(Ordering::Acquire, guard);
+            }
+        }
+        self.table.store(Owned::new(table), Ordering::Release);
+        // Readers may still be walking the old table; its values now belong
+        // to the new one.
+        guard.defer_unchecked(move || Table::destroy_nodes(old));
+    }
+}
+
+impl Drop for HotIndex {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` means no reader or writer remains.
+        unsafe {
+            let guard = epoch::unprotected();
+            let table = self.table.load(Ordering::Relaxed, guard);
+            for bucket in table.deref().buckets.iter() {
+                let mut node = bucket.load(Ordering::Relaxed, guard);
+                while let Some(n) = node.as_ref() {
+                    drop(n.value.load(Ordering::Relaxed, guard).into_owned());
+                    node = n.next.load(Ordering::Relaxed, guard);
+                }
+            }
+            Table::destroy_nodes(table);
+        }
+    }
+}
+
+/// Latest-value indexes for every branch of a `ShardedStore`
+pub(crate) struct HotReads {
+    /// Replaced wholesale when a branch is added or removed
+    branches: Atomic<FxHashMap<BranchId, Arc<HotIndex>>>,
+    /// Serializes branch additions and removals
+    lock: Mutex<()>,
+}
+
+impl HotReads {
+    pub(crate) fn new() -> Self {
+        HotReads {
+            branches: Atomic::new(FxHashMap::default()),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Apply `f` to the indexed latest value of `key`.
+    ///
+    /// Returns `None` when the key is not indexed.
+    pub(crate) fn get<R>(&self, key: &Key, f: impl FnOnce(&StoredValue) -> R) -> Option<R> {
+        let guard = epoch::pin();
+        let index = self.branch(&key.namespace.branch_id, &guard)?;
+        index.get(key, &guard).map(f)
+    }
+
+    /// Start updating the index of `branch_id`, creating it if needed.
+    ///
+    /// # Safety
+    ///
+    /// The caller must hold the branch's shard lock for as long as the
+    /// returned writer is alive.
+    pub(crate) unsafe fn writer(&self, branch_id: BranchId) -> HotWriter {
+        let guard = epoch::pin();
+        let index = match self.branch(&branch_id, &guard) {
+            Some(index) => Arc::clone(index),
+            None => self.add_branch(branch_id, &guard),
+        };
+        HotWriter { index, guard }
+    }
+
+    /// Drop the index of `branch_id`.
+    ///
+    /// The caller must hold the branch's shard lock, so that no writer for
+    /// it is alive.
+    pub(crate) fn remove_branch(&self, branch_id: &BranchId) {
+        let guard = epoch::pin();
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.branches.load(Ordering::Acquire, &guard);
+        // SAFETY: the map is never null and is freed only after this guard.
+        let map = unsafe { current.deref() };
+        if !map.contains_key(branch_id) {
+            return;
+        }
+        let mut map = map.clone();
+        map.remove(branch_id);
+        self.branches.store(Owned::new(map), Ordering::Release);
+        // SAFETY: the old map is unreachable to readers pinned from now on.
+        unsafe { guard.defer_destroy(current) };
+    }
+
+    fn branch<'g>(&self, branch_id: &BranchId, guard: &'g Guard) -> Option<&'g Arc<HotIndex>> {
+        // SAFETY: the map is never null and is freed only after this guard.
+        let map = unsafe { self.branches.load(Ordering::Acquire, guard).deref() };
+        map.get(branch_id)
+    }
+
+    fn add_branch(&self, branch_id: BranchId, guard: &Guard) -> Arc<HotIndex> {
+        let _lock = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.branches.load(Ordering::Acquire, guard);
+        // SAFETY: the map is never null and is freed only after this guard.
+        let mut map = unsafe { current.deref() }.clone();
+        let index = Arc::clone(
+            map.entry(branch_id)
+                .or_insert_with(|| Arc::new(HotIndex::new())),
+        );
+        self.branches.store(Owned::new(map), Ordering::Release);
+        // SAFETY: the old map is unreachable to readers pinned from now on.
+        unsafe { guard.defer_destroy(current) };
+        index
+    }
+}
+
+impl Drop for HotReads {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` means no reader or writer remains.
+        unsafe {
+            let guard = epoch::unprotected();
+            drop(self.branches.load(Ordering::Relaxed, guard).into_owned());
+        }
+    }
+}
+
+/// Single writer for one branch's index, created by [`HotReads::writer`]
+pub(crate) struct HotWriter {
+    index: Arc<HotIndex>,
+    guard: Guard,
+}
+
+impl HotWriter {
+    /// Record `value` as the latest version of `key`.
+    pub(crate) fn set(&self, key: &Key, value: &StoredValue) {
+        // SAFETY: `HotReads::writer` requires the shard lock, which makes
+        // this the branch's only writer.
+        unsafe { self.index.set(key, value.clone(), &self.guard) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use strata_core::types::Namespace;
+    use strata_core::value::Value;
+    use strata_core::Version;
+
+    fn key(branch_id: BranchId, name: &str) -> Key {
+        let ns = Namespace::new(
+            "tenant".to_string(),
+            "app".to_string(),
+            "agent".to_string(),
+            branch_id,
+            "default".to_string(),
+        );
+        Key::new_kv(ns, name)
+    }
+
+    fn stored(value: i64, version: u64) -> StoredValue {
+        StoredValue::new(Value::Int(value), Version::txn(version), None)
+    }
+
+    fn latest(hot: &HotReads, key: &Key) -> Option<(Value, u64)> {
+        hot.get(key, |sv| (sv.value().into_owned(), sv.version().as_u64()))
+    }
+
+    #[test]
+    fn test_set_replaces_latest() {
+        let hot = HotReads::new();
+        let branch_id = BranchId::new();
+        let k = key(branch_id, "k");
+        assert_eq!(latest(&hot, &k), None);
+
+        let writer = unsafe { hot.writer(branch_id) };
+        writer.set(&k, &stored(1, 1));
+        writer.set(&k, &stored(2, 2));
+        drop(writer);
+
+        assert_eq!(latest(&hot, &k), Some((Value::Int(2), 2)));
+        assert_eq!(latest(&hot, &key(branch_id, "other")), None);
+        assert_eq!(latest(&hot, &key(BranchId::new(), "k")), None);
+    }
+
+    #[test]
+    fn test_grow_keeps_every_key() {
+        let hot = HotReads::new();
+        let branch_id = BranchId::new();
+        let writer = unsafe { hot.writer(branch_id) };
+        for i in 0..(INITIAL_BUCKETS * 8) as i64 {
+            writer.set(&key(branch_id, &format!("k{}", i)), &stored(i, 1));
+        }
+        // Overwrite after growth so replaced values come from the new table
+        writer.set(&key(branch_id, "k0"), &stored(-1, 2));
+        drop(writer);
+
+        for i in 1..(INITIAL_BUCKETS * 8) as i64 {
+            let k = key(branch_id, &format!("k{}", i));
+            assert_eq!(latest(&hot, &k), Some((Value::Int(i), 1)));
+        }
+        assert_eq!(
+            latest(&hot, &key(branch_id, "k0")),
+            Some((Value::Int(-1), 2))
+        );
+    }
+
+    #[test]
+    fn test_remove_branch() {
+        let hot = HotReads::new();
+        let (a, b) = (BranchId::new(), BranchId::new());
+        for branch_id in [a, b] {
+            unsafe { hot.writer(branch_id) }.set(&key(branch_id, "k"), &stored(1, 1));
+        }
+
+        hot.remove_branch(&a);
+        assert_eq!(latest(&hot, &key(a, "k")), None);
+        assert_eq!(latest(&hot, &key(b, "k")), Some((Value::Int(1), 1)));
+        // Removing an unknown branch is a no-op
+        hot.remove_branch(&a);
+    }
+
+    #[test]
+    fn test_readers_never_see_torn_values() {
+        let hot = Arc::new(HotReads::new());
+        let branch_id = BranchId::new();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let hot = Arc::clone(&hot);
+                let done = Arc::clone(&done);
+                std::thread::spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        for i in 0..256 {
+                            let k = key(branch_id, &format!("k{}", i));
+                            // Each write stores its version as the value
+                            if let Some((value, version)) = latest(&hot, &k) {
+                                assert_eq!(value, Value::Int(version as i64));
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let writer = unsafe { hot.writer(branch_id) };
+        for version in 1..=50u64 {
+            for i in 0..256 {
+                let k = key(branch_id, &format!("k{}", i));
+                writer.set(&k, &stored(version as i64, version));
+            }
+        }
+        drop(writer);
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+
+        let k = key(branch_id, "k255");
+        assert_eq!(latest(&hot, &k), Some((Value::Int(50), 50)));
+    }
+}
@@ -6,24 +6,35 @@
 //! - Lock-free reads via DashMap
 //! - Per-BranchId sharding (no cross-branch contention)
 //! - FxHashMap for O(1) lookups
+//! - MaterializedSnapshot: frozen, lock-free copy for long-running scans
+//! - Copy-on-write forks: a forked branch reads through to its parent until
+//!   it writes a key
+//! - `epoch-reads` feature: epoch-reclaimed latest-value index, so point
+//!   reads never touch a DashMap lock
 //!
 //! Persistence and durability are handled by the `strata-durability` crate.
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod budget;
+#[cfg(feature = "epoch-reads")]
+mod hot_index;
 pub mod index;
+pub mod materialized;
 pub mod primitive_ext;
 pub mod registry;
 pub mod sharded;
 pub mod stored_value;
 pub mod ttl;
 
+pub use budget::{EvictionPolicy, MemoryBudget, MemoryBudgetStats};
 pub use index::{BranchIndex, TypeIndex};
+pub use materialized::MaterializedSnapshot;
 pub use primitive_ext::{
     is_future_wal_type, is_vector_wal_type, primitive_for_wal_type, primitive_type_ids, wal_ranges,
     PrimitiveExtError, PrimitiveStorageExt,
 };
 pub use registry::PrimitiveRegistry;
-pub use sharded::{Shard, ShardedSnapshot, ShardedStore};
+pub use sharded::{ForkPoint, Shard, ShardUsage, ShardedSnapshot, ShardedStore};
 pub use ttl::TTLIndex;
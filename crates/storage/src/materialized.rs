@@ -0,0 +1,228 @@
+//! Materialized snapshots for long-running reads
+//!
+//! A [`ShardedSnapshot`](crate::ShardedSnapshot) is O(1) to create but every
+//! read goes back to the live store: it takes the branch shard's lock and
+//! walks version chains, and it depends on the versions it needs not being
+//! garbage collected. That is the right trade-off for transactions, which
+//! are short. Analytical scans are not.
+//!
+//! A [`MaterializedSnapshot`] copies every live entry at the captured
+//! version out of the store once. Each shard is locked only while it is
+//! copied. After that the view never touches the store again: reads take
+//! no locks, and writers, compaction and version GC proceed unaffected.
+//! The copied data sits behind an `Arc`, so cloning the view (e.g. to hand
+//! it to scan worker threads) shares it instead of copying it again.
+//!
+//! # Cost
+//!
+//! Creation is O(n) in the number of live entries and the view holds a
+//! full copy of them. Build one per analytical job, not per request.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+use strata_core::traits::SnapshotView;
+use strata_core::types::{BranchId, Key, TypeTag};
+use strata_core::{StrataResult, VersionedValue};
+
+/// Entries of one branch, sorted by key.
+type BranchEntries = BTreeMap<Key, VersionedValue>;
+
+/// Frozen, fully materialized copy of the store at one version
+///
+/// Contains only live entries: tombstones and values already expired at
+/// materialization time are dropped.
+#[derive(Clone)]
+pub struct MaterializedSnapshot {
+    /// Version the entries were materialized at
+    version: u64,
+    /// Entries by branch
+    branches: Arc<FxHashMap<BranchId, BranchEntries>>,
+}
+
+impl MaterializedSnapshot {
+    /// Build a view from entries already filtered to `version`.
+    pub(crate) fn new(version: u64, branches: FxHashMap<BranchId, BranchEntries>) -> Self {
+        Self {
+            version,
+            branches: Arc::new(branches),
+        }
+    }
+
+    /// Version the view was materialized at
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Branches that had live entries at the view's version
+    pub fn branch_ids(&self) -> Vec<BranchId> {
+        self.branches.keys().copied().collect()
+    }
+
+    /// Iterate all entries of a branch in key order
+    pub fn iter_branch(
+        &self,
+        branch_id: &BranchId,
+    ) -> impl Iterator<Item = (&Key, &VersionedValue)> + '_ {
+        self.branches
+            .get(branch_id)
+            .into_iter()
+            .flat_map(|entries| entries.iter())
+    }
+
+    /// Iterate entries matching a prefix in key order
+    pub fn iter_prefix<'a>(
+        &'a self,
+        prefix: &'a Key,
+    ) -> impl Iterator<Item = (&'a Key, &'a VersionedValue)> + 'a {
+        self.branches
+            .get(&prefix.namespace.branch_id)
+            .into_iter()
+            .flat_map(move |entries| {
+                entries
+                    .range::<Key, _>(prefix..)
+                    .take_while(move |(k, _)| k.starts_with(prefix))
+            })
+    }
+
+    /// Iterate entries of a specific type in a branch, in key order
+    pub fn iter_by_type(
+        &self,
+        branch_id: &BranchId,
+        type_tag: TypeTag,
+    ) -> impl Iterator<Item = (&Key, &VersionedValue)> + '_ {
+        self.iter_branch(branch_id)
+            .filter(move |(k, _)| k.type_tag == type_tag)
+    }
+
+    /// Number of entries in a branch
+    pub fn branch_entry_count(&self, branch_id: &BranchId) -> usize {
+        self.branches.get(branch_id).map_or(0, |e| e.len())
+    }
+
+    /// Number of entries across all branches
+    pub fn total_entries(&self) -> usize {
+        self.branches.values().map(|e| e.len()).sum()
+    }
+}
+
+impl std::fmt::Debug for MaterializedSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaterializedSnapshot")
+            .field("version", &self.version)
+            .field("branches", &self.branches.len())
+            .field("total_entries", &self.total_entries())
+            .finish()
+    }
+}
+
+impl SnapshotView for MaterializedSnapshot {
+    fn get(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
+        Ok(self
+            .branches
+            .get(&key.namespace.branch_id)
+            .and_then(|entries| entries.get(key))
+            .cloned())
+    }
+
+    fn scan_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        Ok(self
+            .iter_prefix(prefix)
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect())
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sharded::ShardedStore;
+    use crate::stored_value::StoredValue;
+    use strata_core::types::Namespace;
+    use strata_core::value::Value;
+    use strata_core::Version;
+
+    fn put(store: &ShardedStore, key: &Key, value: i64) -> u64 {
+        let version = store.next_version();
+        store.put(
+            key.clone(),
+            StoredValue::new(Value::Int(value), Version::txn(version), None),
+        );
+        version
+    }
+
+    #[test]
+    fn test_materialize_captures_version() {
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        let a = Key::new_kv(ns.clone(), "a");
+        let b = Key::new_kv(ns.clone(), "b");
+        put(&store, &a, 1);
+        put(&store, &b, 2);
+
+        let view = store.snapshot().materialize();
+
+        // Later writes and deletes are invisible to the view
+        put(&store, &a, 10);
+        store.delete(&b);
+        put(&store, &Key::new_kv(ns.clone(), "c"), 3);
+
+        assert_eq!(view.get(&a).unwrap().unwrap().value, Value::Int(1));
+        assert_eq!(view.get(&b).unwrap().unwrap().value, Value::Int(2));
+        assert_eq!(view.branch_entry_count(&branch_id), 2);
+        assert_eq!(view.total_entries(), 2);
+    }
+
+    #[test]
+    fn test_materialize_survives_gc() {
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let key = Key::new_kv(Namespace::for_branch(branch_id), "k");
+        put(&store, &key, 1);
+
+        let view = store.snapshot().materialize();
+        let v2 = put(&store, &key, 2);
+        store.gc_branch(branch_id, v2);
+
+        assert_eq!(view.get(&key).unwrap().unwrap().value, Value::Int(1));
+    }
+
+    #[test]
+    fn test_materialize_prefix_and_type() {
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let ns = Namespace::for_branch(branch_id);
+        put(&store, &Key::new_kv(ns.clone(), "user:2"), 2);
+        put(&store, &Key::new_kv(ns.clone(), "user:1"), 1);
+        put(&store, &Key::new_kv(ns.clone(), "order:1"), 3);
+        put(&store, &Key::new_state(ns.clone(), "user:3"), 4);
+
+        let view = store.snapshot().materialize();
+        let users = view.scan_prefix(&Key::new_kv(ns.clone(), "user:")).unwrap();
+        let names: Vec<_> = users.iter().map(|(k, _)| k.user_key.clone()).collect();
+        assert_eq!(names, vec![b"user:1".to_vec(), b"user:2".to_vec()]);
+
+        assert_eq!(view.iter_by_type(&branch_id, TypeTag::KV).count(), 3);
+        assert_eq!(view.iter_by_type(&branch_id, TypeTag::State).count(), 1);
+        assert!(view.iter_branch(&BranchId::new()).next().is_none());
+    }
+
+    #[test]
+    fn test_materialized_clone_shares_data() {
+        let store = Arc::new(ShardedStore::new());
+        let key = Key::new_kv(Namespace::for_branch(BranchId::new()), "k");
+        put(&store, &key, 1);
+
+        let view = store.snapshot().materialize();
+        let clone = view.clone();
+        assert!(Arc::ptr_eq(&view.branches, &clone.branches));
+        assert_eq!(clone.version(), view.version());
+    }
+}
@@ -8,10 +8,15 @@
 //! - DashMap: 16-way sharded by default, lock-free reads
 //! - FxHashMap: O(1) lookups, fast non-crypto hash
 //! - Per-BranchId: Natural agent partitioning, no cross-branch contention
+//! - Copy-on-write forks: a branch forked from another keeps no copy of its
+//!   parent's data; reads of keys it has not written fall through to the
+//!   parent's shard as of the fork version (see `ShardedStore::fork_branch`)
 //!
 //! # Performance Targets
 //!
-//! - get(): Lock-free via DashMap
+//! - get(): Lock-free via DashMap; with the `epoch-reads` feature, latest
+//!   values are also served from an epoch-reclaimed index that takes no lock
+//!   at all (see `hot_index`)
 //! - put(): Only locks target shard
 //! - Snapshot acquisition: < 500ns
 //! - Different branches: Never contend
@@ -32,13 +37,18 @@
 
 use dashmap::DashMap;
 use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use strata_core::types::{BranchId, Key};
+use std::sync::{Arc, Condvar, Mutex};
+use strata_core::types::{BranchId, Key, TypeTag};
 use strata_core::{Timestamp, Version, VersionedValue};
 
+use crate::budget::{AccessStats, BudgetState, MemoryBudget, MemoryBudgetStats, LOW_WATER_PERCENT};
+#[cfg(feature = "epoch-reads")]
+use crate::hot_index::HotReads;
+use crate::materialized::MaterializedSnapshot;
 use crate::stored_value::StoredValue;
 
 /// Per-branch shard containing branch's data
@@ -58,6 +68,10 @@ pub struct VersionChain {
     /// VecDeque provides O(1) push_front for new versions
     /// Uses StoredValue to include TTL information
     versions: VecDeque<StoredValue>,
+    /// Whether older versions were ever pruned from this chain
+    trimmed: bool,
+    /// Uses of the key, recorded while a memory budget evicts by use
+    access: AccessStats,
 }
 
 impl VersionChain {
@@ -65,7 +79,11 @@ impl VersionChain {
     pub fn new(value: StoredValue) -> Self {
         let mut versions = VecDeque::with_capacity(4);
         versions.push_front(value);
-        Self { versions }
+        Self {
+            versions,
+            trimmed: false,
+            access: AccessStats::default(),
+        }
     }
 
     /// Add a new version (must be newer than existing versions)
@@ -103,6 +121,18 @@ impl VersionChain {
             .find(|sv| u64::from(sv.timestamp()) <= max_timestamp)
     }
 
+    /// Get the newest version at or before both `max_version` and
+    /// `max_timestamp`
+    fn get_at_version_and_timestamp(
+        &self,
+        max_version: u64,
+        max_timestamp: u64,
+    ) -> Option<&StoredValue> {
+        self.versions.iter().find(|sv| {
+            sv.version().as_u64() <= max_version && u64::from(sv.timestamp()) <= max_timestamp
+        })
+    }
+
     /// Get the latest version
     #[inline]
     pub fn latest(&self) -> Option<&StoredValue> {
@@ -131,9 +161,44 @@ impl VersionChain {
                 break;
             }
         }
+        self.trimmed |= pruned > 0;
         pruned
     }
 
+    /// Remove the oldest versions that `keep` rejects
+    ///
+    /// `keep` receives each version with its position in the chain
+    /// (0 = latest). Trimming stops at the first version kept, and the
+    /// latest version is always kept. Returns the number of pruned versions.
+    pub fn trim(&mut self, mut keep: impl FnMut(&StoredValue, usize) -> bool) -> usize {
+        let mut pruned = 0;
+        while self.versions.len() > 1 {
+            let position = self.versions.len() - 1;
+            match self.versions.back() {
+                Some(oldest) if !keep(oldest, position) => {
+                    self.versions.pop_back();
+                    pruned += 1;
+                }
+                _ => break,
+            }
+        }
+        self.trimmed |= pruned > 0;
+        pruned
+    }
+
+    /// Record a use of the key at `tick`, if a memory budget tracks uses
+    #[inline]
+    pub(crate) fn touch(&self, tick: Option<u64>) {
+        if let Some(tick) = tick {
+            self.access.touch(tick);
+        }
+    }
+
+    /// Whether older versions were pruned by `gc()` or `trim()`
+    pub fn is_trimmed(&self) -> bool {
+        self.trimmed
+    }
+
     /// Number of versions stored
     pub fn version_count(&self) -> usize {
         self.versions.len()
@@ -176,6 +241,51 @@ impl VersionChain {
     pub fn is_empty(&self) -> bool {
         self.versions.is_empty()
     }
+
+    /// Approximate bytes held by all versions in the chain
+    pub fn memory_usage(&self) -> usize {
+        self.versions.iter().map(stored_value_bytes).sum()
+    }
+}
+
+/// Approximate bytes held by one version
+fn stored_value_bytes(sv: &StoredValue) -> usize {
+    let heap = if sv.is_inline() {
+        0
+    } else {
+        value_heap_bytes(&sv.value())
+    };
+    std::mem::size_of::<StoredValue>() + heap
+}
+
+/// Approximate heap bytes owned by a value, excluding the enum itself
+fn value_heap_bytes(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Int(_) | Value::Float(_) => 0,
+        Value::String(s) => s.len(),
+        Value::Bytes(b) => b.len(),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| std::mem::size_of::<Value>() + value_heap_bytes(v))
+            .sum(),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| {
+                std::mem::size_of::<String>()
+                    + k.len()
+                    + std::mem::size_of::<Value>()
+                    + value_heap_bytes(v)
+            })
+            .sum(),
+    }
+}
+
+/// Approximate bytes held by a key, counting both the map and the ordered index
+fn key_heap_bytes(key: &Key) -> usize {
+    let ns = &key.namespace;
+    let owned =
+        key.user_key.len() + ns.tenant.len() + ns.app.len() + ns.agent.len() + ns.space.len();
+    2 * (std::mem::size_of::<Key>() + owned)
 }
 
 /// Each BranchId gets its own shard with an FxHashMap for O(1) lookups.
@@ -225,6 +335,14 @@ impl Shard {
     pub fn is_empty(&self) -> bool {
         self.data.is_empty()
     }
+
+    /// Approximate bytes held by this shard's keys and version chains
+    pub fn memory_usage(&self) -> usize {
+        self.data
+            .iter()
+            .map(|(key, chain)| key_heap_bytes(key) + chain.memory_usage())
+            .sum()
+    }
 }
 
 impl Default for Shard {
@@ -233,6 +351,24 @@ impl Default for Shard {
     }
 }
 
+/// Live entry counts and memory held by one branch's shard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShardUsage {
+    /// Live entries (latest version not a tombstone) per type tag
+    pub entries: BTreeMap<TypeTag, usize>,
+    /// Approximate bytes held, including old versions and tombstones
+    pub memory_bytes: usize,
+}
+
+/// Where a copy-on-write fork reads the data it has not written itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkPoint {
+    /// Branch the fork was taken from
+    pub parent: BranchId,
+    /// Store version the fork sees its parent at
+    pub version: u64,
+}
+
 /// Sharded storage - DashMap by BranchId, HashMap within
 ///
 /// # Design
@@ -248,6 +384,15 @@ impl Default for Shard {
 /// - put(): Only locks the target branch's shard
 /// - Different branches never contend
 ///
+/// # Forks
+///
+/// A branch registered with `fork_branch()` has a shard of its own writes
+/// only. Point reads and scans resolve each key in the fork's shard first,
+/// then in its parent's (and the parent's parent's) as of the fork version,
+/// so writes and deletes in the fork shadow what it inherits. Maintenance
+/// that prunes versions (`gc_branch()`, `trim_*()`) keeps the versions a
+/// fork still reads.
+///
 /// # Example
 ///
 /// ```text
@@ -260,31 +405,71 @@ impl Default for Shard {
 pub struct ShardedStore {
     /// Per-branch shards using DashMap
     shards: DashMap<BranchId, Shard>,
+    /// Fork point of every copy-on-write fork; always locked before `shards`
+    forks: DashMap<BranchId, ForkPoint>,
     /// Global version for snapshots
     version: AtomicU64,
+    /// Commit versions reserved but not yet published (see `reserve_version`)
+    pending: Mutex<BTreeSet<u64>>,
+    /// Signalled whenever a pending version is published
+    published: Condvar,
+    /// Lowest pending version, or `u64::MAX` when none; read lock-free by snapshots
+    pending_floor: AtomicU64,
+    /// Latest value of every key, updated under the branch's shard lock
+    #[cfg(feature = "epoch-reads")]
+    hot: HotReads,
+    /// Memory limit and eviction state (unlimited by default)
+    budget: BudgetState,
 }
 
 impl ShardedStore {
     /// Create new sharded store
     pub fn new() -> Self {
-        Self {
-            shards: DashMap::new(),
-            version: AtomicU64::new(0),
-        }
+        Self::with_capacity(0)
     }
 
     /// Create with expected number of branches
     pub fn with_capacity(num_branches: usize) -> Self {
         Self {
             shards: DashMap::with_capacity(num_branches),
+            forks: DashMap::new(),
             version: AtomicU64::new(0),
+            pending: Mutex::new(BTreeSet::new()),
+            published: Condvar::new(),
+            pending_floor: AtomicU64::new(u64::MAX),
+            #[cfg(feature = "epoch-reads")]
+            hot: HotReads::new(),
+            budget: BudgetState::default(),
         }
     }
 
     /// Get current version
+    ///
+    /// This is the highest version visible to snapshots: it never passes a
+    /// version reserved by an in-flight commit that has not been published.
     #[inline]
     pub fn version(&self) -> u64 {
-        self.version.load(Ordering::Acquire)
+        // Load the applied version before the floor: a commit below the
+        // loaded version is either still pending (and caps the result) or
+        // already applied.
+        let applied = self.version.load(Ordering::SeqCst);
+        let floor = self.pending_floor.load(Ordering::SeqCst);
+        applied.min(floor.saturating_sub(1))
+    }
+
+    /// Block until every reserved commit version has been published
+    ///
+    /// Callers that must see all WAL-appended commits in storage (such as
+    /// WAL compaction) hold the WAL lock, so no new version can be reserved,
+    /// and then wait here for in-flight applies to finish.
+    pub fn wait_for_pending_versions(&self) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        while !pending.is_empty() {
+            pending = self
+                .published
+                .wait(pending)
+                .unwrap_or_else(|e| e.into_inner());
+        }
     }
 
     /// Increment version and return new value
@@ -314,7 +499,7 @@ impl ShardedStore {
 
     /// Check if a branch exists
     pub fn has_branch(&self, branch_id: &BranchId) -> bool {
-        self.shards.contains_key(branch_id)
+        self.shards.contains_key(branch_id) || self.forks.contains_key(branch_id)
     }
 
     /// Get total number of entries across all shards
@@ -322,6 +507,351 @@ impl ShardedStore {
         self.shards.iter().map(|entry| entry.value().len()).sum()
     }
 
+    /// Approximate bytes held by all shards, including old versions and
+    /// tombstones not yet garbage collected
+    ///
+    /// Walks every entry, so the cost is proportional to the data size.
+    pub fn memory_usage(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|entry| entry.value().memory_usage())
+            .sum()
+    }
+
+    /// Live entries per type and approximate bytes held by a branch's shard
+    ///
+    /// Walks the whole shard in one pass. A branch without data reports
+    /// no entries and zero bytes.
+    pub fn shard_usage(&self, branch_id: &BranchId) -> ShardUsage {
+        let Some(shard) = self.shards.get(branch_id) else {
+            return ShardUsage::default();
+        };
+        let mut usage = ShardUsage::default();
+        for (key, chain) in &shard.data {
+            usage.memory_bytes += key_heap_bytes(key) + chain.memory_usage();
+            if chain.latest().is_some_and(|sv| !sv.is_tombstone()) {
+                *usage.entries.entry(key.type_tag).or_default() += 1;
+            }
+        }
+        usage
+    }
+
+    // ========================================================================
+    // Copy-on-Write Forks
+    // ========================================================================
+
+    /// Make `child` a copy-on-write fork of `parent` as of `version`
+    ///
+    /// Nothing is copied: reads of keys `child` has not written fall through
+    /// to `parent` as it was at `version`, and writes go to `child`'s own
+    /// shard, shadowing the entries it inherits. Branch index entries are
+    /// never inherited. Versions `child` already holds at or below `version`
+    /// predate the fork (a deleted branch of the same ID) and are dropped,
+    /// so registering a fork again after recovery keeps only its own writes.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidInput` if `child` is already a fork, or is `parent` or one of
+    /// its ancestors.
+    pub fn fork_branch(&self, parent: BranchId, child: BranchId, version: u64) -> StrataResult<()> {
+        if self
+            .lineage(parent)
+            .iter()
+            .any(|(ancestor, _)| *ancestor == child)
+        {
+            return Err(strata_core::StrataError::invalid_input(format!(
+                "cannot fork branch {} into itself or one of its ancestors",
+                parent
+            )));
+        }
+        let dashmap::mapref::entry::Entry::Vacant(slot) = self.forks.entry(child) else {
+            return Err(strata_core::StrataError::invalid_input(format!(
+                "branch {} is already a fork",
+                child
+            )));
+        };
+        if let Some(mut shard) = self.shards.get_mut(&child) {
+            let shard = &mut *shard;
+            shard.data.retain(|_, chain| {
+                chain.versions.retain(|sv| sv.version().as_u64() > version);
+                !chain.versions.is_empty()
+            });
+            let data = &shard.data;
+            shard.ordered_keys.retain(|key| data.contains_key(key));
+            // Rebuild the read index without the dropped keys
+            #[cfg(feature = "epoch-reads")]
+            {
+                self.hot.remove_branch(&child);
+                // SAFETY: `hot` is dropped before `shard` releases the entry lock
+                let hot = unsafe { self.hot.writer(child) };
+                for (key, chain) in &shard.data {
+                    if let Some(latest) = chain.latest() {
+                        hot.set(key, latest);
+                    }
+                }
+            }
+        }
+        slot.insert(ForkPoint { parent, version });
+        Ok(())
+    }
+
+    /// Where `branch_id` was forked from, or `None` if it is not a fork
+    pub fn fork_point(&self, branch_id: &BranchId) -> Option<ForkPoint> {
+        self.forks.get(branch_id).map(|fork| *fork)
+    }
+
+    /// Branches forked directly from `branch_id`
+    pub fn forks_of(&self, branch_id: &BranchId) -> Vec<BranchId> {
+        self.forks
+            .iter()
+            .filter(|fork| fork.parent == *branch_id)
+            .map(|fork| *fork.key())
+            .collect()
+    }
+
+    /// Copy every entry a fork still inherits into its own shard, keeping
+    /// versions, and stop it reading through its parent
+    ///
+    /// Returns false if `branch_id` is not a fork.
+    pub fn detach_fork(&self, branch_id: &BranchId) -> bool {
+        for (key, value) in self.inherited_entries(branch_id) {
+            let mut shard = self.shards.entry(*branch_id).or_default();
+            // A write since the scan already shadows the inherited entry
+            if shard.data.contains_key(&key) {
+                continue;
+            }
+            #[cfg(feature = "epoch-reads")]
+            {
+                // SAFETY: `hot` is dropped before `shard` releases the entry lock
+                let hot = unsafe { self.hot.writer(*branch_id) };
+                hot.set(&key, &value);
+            }
+            shard.ordered_keys.insert(key.clone());
+            shard.data.insert(key, VersionChain::new(value));
+        }
+        self.forks.remove(branch_id).is_some()
+    }
+
+    /// Keys a fork reads from its ancestors because it has not written
+    /// them, excluding deleted ones; empty for a branch that is not a fork
+    pub fn inherited_keys(&self, branch_id: &BranchId) -> Vec<Key> {
+        self.inherited_entries(branch_id)
+            .into_iter()
+            .filter(|(_, sv)| !sv.is_tombstone())
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// What a WAL rewrite must keep of a branch, as `(key, version, value)`
+    ///
+    /// Every version each key in the branch's own shard still holds, oldest
+    /// first, so history and the versions forks read survive recovery;
+    /// pruning is left to `gc_branch()` and `trim_branch()`. A delete
+    /// (`None`) is only kept where it hides something: in a fork, or above
+    /// an older version that is kept. Inherited entries are left to the
+    /// parent, so a fork stays copy-on-write across recovery.
+    pub fn durable_entries(&self, branch_id: &BranchId) -> Vec<(Key, u64, Option<Value>)> {
+        let is_fork = self.forks.contains_key(branch_id);
+        let Some(shard) = self.shards.get(branch_id) else {
+            return Vec::new();
+        };
+        let mut entries = Vec::new();
+        for (key, chain) in &shard.data {
+            let mut kept_older = false;
+            for sv in chain.versions.iter().rev() {
+                let version = sv.version().as_u64();
+                if !sv.is_tombstone() {
+                    entries.push((key.clone(), version, Some(sv.value().into_owned())));
+                } else if is_fork || kept_older {
+                    entries.push((key.clone(), version, None));
+                } else {
+                    continue;
+                }
+                kept_older = true;
+            }
+        }
+        entries
+    }
+
+    /// Whether `branch_id` is a copy-on-write fork
+    #[inline]
+    fn is_fork(&self, branch_id: &BranchId) -> bool {
+        self.forks.contains_key(branch_id)
+    }
+
+    /// `branch_id` and its ancestors, each with the highest version that
+    /// reads through the fork chain may see in it
+    fn lineage(&self, branch_id: BranchId) -> Vec<(BranchId, u64)> {
+        let mut lineage = vec![(branch_id, u64::MAX)];
+        let mut cap = u64::MAX;
+        let mut branch_id = branch_id;
+        while let Some(fork) = self.fork_point(&branch_id) {
+            cap = cap.min(fork.version);
+            branch_id = fork.parent;
+            lineage.push((branch_id, cap));
+        }
+        lineage
+    }
+
+    /// Fork versions of the branches forked from `branch_id`
+    fn fork_pins(&self, branch_id: &BranchId) -> Vec<u64> {
+        self.forks
+            .iter()
+            .filter(|fork| fork.parent == *branch_id)
+            .map(|fork| fork.version)
+            .collect()
+    }
+
+    /// Resolve `key` through its branch's fork lineage
+    ///
+    /// `f` gets the version chain of `key` in each branch holding one,
+    /// the branch itself first, with the highest version readable there;
+    /// the first `Some` it returns is the result.
+    fn resolve<R>(
+        &self,
+        key: &Key,
+        mut f: impl FnMut(&VersionChain, u64) -> Option<R>,
+    ) -> Option<R> {
+        let mut branch_id = key.namespace.branch_id;
+        let mut cap = u64::MAX;
+        let mut inherited: Option<Key> = None;
+        loop {
+            let lookup = inherited.as_ref().unwrap_or(key);
+            if let Some(shard) = self.shards.get(&branch_id) {
+                if let Some(found) = shard.data.get(lookup).and_then(|chain| f(chain, cap)) {
+                    return Some(found);
+                }
+            }
+            if key.type_tag == TypeTag::Branch {
+                return None;
+            }
+            let fork = self.fork_point(&branch_id)?;
+            cap = cap.min(fork.version);
+            branch_id = fork.parent;
+            inherited = Some(rebranch(key, branch_id));
+        }
+    }
+
+    /// Entries of a fork under `prefix` (or in the whole branch), filtered
+    /// by `type_tag`, sorted by key
+    ///
+    /// `pick` gets each key's version chain and the highest version
+    /// readable from it, and returns `None` to let an ancestor answer,
+    /// `Some(None)` for a key that is absent (deleted or expired), or the
+    /// value. Keys are returned under `branch_id`.
+    fn scan_merged(
+        &self,
+        branch_id: BranchId,
+        prefix: Option<&Key>,
+        type_tag: Option<TypeTag>,
+        pick: impl Fn(&VersionChain, u64) -> Option<Option<VersionedValue>>,
+    ) -> Vec<(Key, VersionedValue)> {
+        let mut merged: BTreeMap<Key, Option<VersionedValue>> = BTreeMap::new();
+        for (level, (ancestor, cap)) in self.lineage(branch_id).into_iter().enumerate() {
+            let Some(shard) = self.shards.get(&ancestor) else {
+                continue;
+            };
+            let level_prefix = prefix.map(|p| rebranch(p, ancestor));
+            let keys: Box<dyn Iterator<Item = &Key>> = match &level_prefix {
+                Some(p) => Box::new(shard.keys_with_prefix(p)),
+                None => Box::new(shard.ordered_keys.iter()),
+            };
+            for key in keys {
+                if (level > 0 && key.type_tag == TypeTag::Branch)
+                    || type_tag.is_some_and(|tag| tag != key.type_tag)
+                {
+                    continue;
+                }
+                let own = if level == 0 {
+                    key.clone()
+                } else {
+                    rebranch(key, branch_id)
+                };
+                if let std::collections::btree_map::Entry::Vacant(slot) = merged.entry(own) {
+                    if let Some(found) = shard.data.get(key).and_then(|chain| pick(chain, cap)) {
+                        slot.insert(found);
+                    }
+                }
+            }
+        }
+        merged
+            .into_iter()
+            .filter_map(|(key, found)| found.map(|vv| (key, vv)))
+            .collect()
+    }
+
+    /// Version history of a fork's key: its own versions, then those of
+    /// its ancestors up to each fork version, newest first
+    fn fork_history(
+        &self,
+        key: &Key,
+        limit: Option<usize>,
+        before_version: Option<u64>,
+    ) -> Vec<VersionedValue> {
+        let mut history = Vec::new();
+        let mut before = before_version.unwrap_or(u64::MAX);
+        for (level, (ancestor, cap)) in self
+            .lineage(key.namespace.branch_id)
+            .into_iter()
+            .enumerate()
+        {
+            if level > 0 && key.type_tag == TypeTag::Branch {
+                break;
+            }
+            let Some(shard) = self.shards.get(&ancestor) else {
+                continue;
+            };
+            let Some(chain) = shard.data.get(&rebranch(key, ancestor)) else {
+                continue;
+            };
+            let below = before.min(cap.saturating_add(1));
+            history.extend(
+                chain
+                    .history(None, Some(below))
+                    .into_iter()
+                    .filter(|sv| !sv.is_expired())
+                    .map(|sv| sv.to_versioned()),
+            );
+            if let Some(oldest) = chain.versions.back() {
+                before = before.min(oldest.version().as_u64());
+            }
+        }
+        if let Some(limit) = limit {
+            history.truncate(limit);
+        }
+        history
+    }
+
+    /// Latest entries a fork reads from its ancestors, tombstones included,
+    /// keyed under the fork
+    fn inherited_entries(&self, branch_id: &BranchId) -> Vec<(Key, StoredValue)> {
+        let lineage = self.lineage(*branch_id);
+        let mut seen: BTreeSet<Key> = match self.shards.get(branch_id) {
+            Some(shard) => shard.ordered_keys.clone(),
+            None => BTreeSet::new(),
+        };
+        let mut entries = Vec::new();
+        for (ancestor, cap) in lineage.into_iter().skip(1) {
+            let Some(shard) = self.shards.get(&ancestor) else {
+                continue;
+            };
+            for (key, chain) in &shard.data {
+                if key.type_tag == TypeTag::Branch {
+                    continue;
+                }
+                let own = rebranch(key, *branch_id);
+                if seen.contains(&own) {
+                    continue;
+                }
+                if let Some(sv) = chain.get_at_version(cap) {
+                    seen.insert(own.clone());
+                    entries.push((own, sv.clone()));
+                }
+            }
+        }
+        entries
+    }
+
     // ========================================================================
     // Get/Put/Delete Operations
     // ========================================================================
@@ -349,14 +879,31 @@ impl ShardedStore {
     pub fn put(&self, key: Key, value: StoredValue) {
         let branch_id = key.namespace.branch_id;
         let mut shard = self.shards.entry(branch_id).or_default();
-
+        // SAFETY: `hot` is dropped before `shard` releases the entry lock
+        #[cfg(feature = "epoch-reads")]
+        let hot = unsafe { self.hot.writer(branch_id) };
+        #[cfg(feature = "epoch-reads")]
+        hot.set(&key, &value);
+
+        let written = self.written_bytes(&shard, &key, &value);
+        let tick = self.budget.tick();
         if let Some(chain) = shard.data.get_mut(&key) {
             // Add new version to existing chain
             chain.push(value);
+            chain.touch(tick);
         } else {
             // Create new chain — also add to BTreeSet index
             shard.ordered_keys.insert(key.clone());
-            shard.data.insert(key, VersionChain::new(value));
+            let chain = VersionChain::new(value);
+            chain.touch(tick);
+            shard.data.insert(key, chain);
+        }
+
+        #[cfg(feature = "epoch-reads")]
+        drop(hot);
+        drop(shard);
+        if written > 0 && self.budget.record_write(written) {
+            self.enforce_memory_budget();
         }
     }
 
@@ -391,21 +938,13 @@ impl ShardedStore {
     ) -> StrataResult<Option<VersionedValue>> {
         use strata_core::Version;
 
-        let branch_id = key.namespace.branch_id;
-
-        // Get the previous value before adding tombstone
-        let previous = self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.latest().and_then(|sv| {
-                    // Don't return tombstones as "previous value"
-                    if sv.is_tombstone() {
-                        None
-                    } else {
-                        Some(sv.versioned().clone())
-                    }
-                })
+        // Get the previous value before adding tombstone; a fork's may be
+        // inherited. Don't return tombstones as "previous value"
+        let previous = self
+            .resolve(key, |chain, cap| {
+                chain.get_at_version(cap).map(undeleted_versioned)
             })
-        });
+            .flatten();
 
         // Add tombstone to version chain
         let tombstone = StoredValue::tombstone(Version::txn(version));
@@ -420,16 +959,10 @@ impl ShardedStore {
     /// Returns false for deleted keys (tombstones).
     #[inline]
     pub fn contains(&self, key: &Key) -> bool {
-        let branch_id = key.namespace.branch_id;
-        self.shards
-            .get(&branch_id)
-            .map(|shard| {
-                shard
-                    .data
-                    .get(key)
-                    .is_some_and(|chain| chain.latest().is_some_and(|sv| !sv.is_tombstone()))
-            })
-            .unwrap_or(false)
+        self.resolve(key, |chain, cap| {
+            chain.get_at_version(cap).map(|sv| !sv.is_tombstone())
+        })
+        .unwrap_or(false)
     }
 
     /// Apply a batch of writes and deletes atomically
@@ -469,8 +1002,7 @@ impl ShardedStore {
             FxHashMap::default();
 
         for (key, value) in writes {
-            let stored =
-                StoredValue::with_timestamp(value.clone(), Version::txn(version), timestamp, None);
+            let stored = StoredValue::from_borrowed(value, Version::txn(version), timestamp, None);
             branch_ops
                 .entry(key.namespace.branch_id)
                 .or_insert_with(|| (Vec::new(), Vec::new()))
@@ -487,20 +1019,34 @@ impl ShardedStore {
         }
 
         // Apply atomically per branch (hold shard lock for entire branch batch)
+        let mut written = 0;
         for (branch_id, (branch_writes, branch_deletes)) in branch_ops {
             let mut shard = self.shards.entry(branch_id).or_default();
+            // SAFETY: `hot` is dropped before `shard` releases the entry lock
+            #[cfg(feature = "epoch-reads")]
+            let hot = unsafe { self.hot.writer(branch_id) };
+            let tick = self.budget.tick();
 
             for (key, stored) in branch_writes {
+                #[cfg(feature = "epoch-reads")]
+                hot.set(&key, &stored);
+                written += self.written_bytes(&shard, &key, &stored);
                 if let Some(chain) = shard.data.get_mut(&key) {
                     chain.push(stored);
+                    chain.touch(tick);
                 } else {
                     shard.ordered_keys.insert(key.clone());
-                    shard.data.insert(key, VersionChain::new(stored));
+                    let chain = VersionChain::new(stored);
+                    chain.touch(tick);
+                    shard.data.insert(key, chain);
                 }
             }
 
             for key in branch_deletes {
                 let tombstone = StoredValue::tombstone(Version::txn(version));
+                #[cfg(feature = "epoch-reads")]
+                hot.set(&key, &tombstone);
+                written += self.written_bytes(&shard, &key, &tombstone);
                 if let Some(chain) = shard.data.get_mut(&key) {
                     chain.push(tombstone);
                 } else {
@@ -514,6 +1060,9 @@ impl ShardedStore {
         // This ensures subsequent snapshots can see the committed data
         self.version.fetch_max(version, Ordering::AcqRel);
 
+        if written > 0 && self.budget.record_write(written) {
+            self.enforce_memory_budget();
+        }
         Ok(())
     }
 
@@ -528,18 +1077,13 @@ impl ShardedStore {
     /// Get value at or before the given timestamp.
     /// Returns None if key doesn't exist, has no version at that time, is expired, or is a tombstone.
     pub fn get_at_timestamp(&self, key: &Key, max_timestamp: u64) -> strata_core::StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_timestamp(max_timestamp).and_then(|sv| {
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
+        Ok(self
+            .resolve(key, |chain, cap| {
+                chain
+                    .get_at_version_and_timestamp(cap, max_timestamp)
+                    .map(live_versioned)
             })
-        }))
+            .flatten())
     }
 
     /// Scan keys matching a prefix, returning values at or before the given timestamp.
@@ -549,13 +1093,21 @@ impl ShardedStore {
         max_timestamp: u64,
     ) -> strata_core::StrataResult<Vec<(Key, VersionedValue)>> {
         let branch_id = prefix.namespace.branch_id;
+        if self.is_fork(&branch_id) {
+            let entries = self.scan_merged(branch_id, Some(prefix), None, |chain, cap| {
+                chain
+                    .get_at_version_and_timestamp(cap, max_timestamp)
+                    .map(live_versioned)
+            });
+            return Ok(entries);
+        }
         Ok(self.shards.get(&branch_id).map(|shard| {
             shard.keys_with_prefix(prefix)
                 .filter_map(|k| {
                     shard.data.get(k).and_then(|chain| {
                         chain.get_at_timestamp(max_timestamp).and_then(|sv| {
                             if !sv.is_expired() && !sv.is_tombstone() {
-                                Some((k.clone(), sv.versioned().clone()))
+                                Some((k.clone(), sv.to_versioned()))
                             } else {
                                 None
                             }
@@ -577,6 +1129,19 @@ impl ShardedStore {
     /// predates timestamp tracking, so it is not meaningful for time-range
     /// queries.
     pub fn time_range(&self, branch_id: BranchId) -> strata_core::StrataResult<Option<(u64, u64)>> {
+        if self.is_fork(&branch_id) {
+            let timestamps: Vec<u64> = self
+                .list_branch(&branch_id)
+                .iter()
+                .map(|(_, vv)| vv.timestamp.as_micros())
+                .filter(|ts| *ts > 0)
+                .collect();
+            return Ok(timestamps
+                .iter()
+                .min()
+                .zip(timestamps.iter().max())
+                .map(|(min, max)| (*min, *max)));
+        }
         Ok(self.shards.get(&branch_id).and_then(|shard| {
             let mut min_ts = u64::MAX;
             let mut max_ts = 0u64;
@@ -602,15 +1167,107 @@ impl ShardedStore {
     /// Calls `VersionChain::gc(min_version)` on each entry in the branch's shard.
     /// Returns the total number of pruned versions.
     pub fn gc_branch(&self, branch_id: BranchId, min_version: u64) -> usize {
+        let pins = self.fork_pins(&branch_id);
         let mut pruned = 0;
         if let Some(mut shard) = self.shards.get_mut(&branch_id) {
             for chain in shard.data.values_mut() {
-                pruned += chain.gc(min_version);
+                if pins.is_empty() {
+                    pruned += chain.gc(min_version);
+                } else {
+                    // Keep the versions forks of this branch read
+                    let pinned = pinned_versions(chain, &pins);
+                    pruned += chain.trim(|sv, _| {
+                        let version = sv.version().as_u64();
+                        version >= min_version || pinned.contains(&version)
+                    });
+                }
+            }
+        }
+        pruned
+    }
+
+    /// Trim the version chains of every key under `prefix`.
+    ///
+    /// Calls `VersionChain::trim()` on each matching entry, passing the key
+    /// to `keep` along with each version. Returns the total number of
+    /// pruned versions.
+    pub fn trim_prefix(
+        &self,
+        prefix: &Key,
+        mut keep: impl FnMut(&Key, &StoredValue, usize) -> bool,
+    ) -> usize {
+        let pins = self.fork_pins(&prefix.namespace.branch_id);
+        let mut pruned = 0;
+        if let Some(mut shard) = self.shards.get_mut(&prefix.namespace.branch_id) {
+            let shard = &mut *shard;
+            for key in shard
+                .ordered_keys
+                .range::<Key, _>(prefix..)
+                .take_while(|k| k.starts_with(prefix))
+            {
+                if let Some(chain) = shard.data.get_mut(key) {
+                    let pinned = pinned_versions(chain, &pins);
+                    pruned += chain.trim(|sv, position| {
+                        pinned.contains(&sv.version().as_u64()) || keep(key, sv, position)
+                    });
+                }
             }
         }
         pruned
     }
 
+    /// Trim the version chains of every key in a branch.
+    ///
+    /// Like `trim_prefix()`, across all of the branch's keys.
+    pub fn trim_branch(
+        &self,
+        branch_id: BranchId,
+        mut keep: impl FnMut(&Key, &StoredValue, usize) -> bool,
+    ) -> usize {
+        let pins = self.fork_pins(&branch_id);
+        let mut pruned = 0;
+        if let Some(mut shard) = self.shards.get_mut(&branch_id) {
+            for (key, chain) in shard.data.iter_mut() {
+                let pinned = pinned_versions(chain, &pins);
+                pruned += chain.trim(|sv, position| {
+                    pinned.contains(&sv.version().as_u64()) || keep(key, sv, position)
+                });
+            }
+        }
+        pruned
+    }
+
+    /// Whether older versions of `key` were pruned from storage
+    pub fn is_history_trimmed(&self, key: &Key) -> bool {
+        self.resolve(key, |chain, _| Some(chain.is_trimmed()))
+            .unwrap_or(false)
+    }
+
+    /// Keys in the branch's own shard whose older versions were pruned
+    ///
+    /// A WAL rewrite records these so recovery can restore the flag.
+    pub fn trimmed_keys(&self, branch_id: &BranchId) -> Vec<Key> {
+        let Some(shard) = self.shards.get(branch_id) else {
+            return Vec::new();
+        };
+        shard
+            .data
+            .iter()
+            .filter(|(_, chain)| chain.is_trimmed())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Record that older versions of `key` were pruned before it was
+    /// restored; a key with no versions is left alone
+    pub fn mark_history_trimmed(&self, key: &Key) {
+        if let Some(mut shard) = self.shards.get_mut(&key.namespace.branch_id) {
+            if let Some(chain) = shard.data.get_mut(key) {
+                chain.trimmed = true;
+            }
+        }
+    }
+
     // ========================================================================
     // List Operations
     // ========================================================================
@@ -628,6 +1285,11 @@ impl ShardedStore {
     ///
     /// Vector of (Key, VersionedValue) pairs, sorted by key
     pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
+        if self.is_fork(branch_id) {
+            return self.scan_merged(*branch_id, None, None, |chain, cap| {
+                chain.get_at_version(cap).map(undeleted_versioned)
+            });
+        }
         self.shards
             .get(branch_id)
             .map(|shard| {
@@ -638,7 +1300,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -667,6 +1329,11 @@ impl ShardedStore {
     /// Vector of (Key, VersionedValue) pairs matching prefix, sorted by key
     pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
         let branch_id = prefix.namespace.branch_id;
+        if self.is_fork(&branch_id) {
+            return self.scan_merged(branch_id, Some(prefix), None, |chain, cap| {
+                chain.get_at_version(cap).map(undeleted_versioned)
+            });
+        }
 
         self.shards
             .get(&branch_id)
@@ -677,7 +1344,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -706,6 +1373,11 @@ impl ShardedStore {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> Vec<(Key, VersionedValue)> {
+        if self.is_fork(branch_id) {
+            return self.scan_merged(*branch_id, None, Some(type_tag), |chain, cap| {
+                chain.get_at_version(cap).map(undeleted_versioned)
+            });
+        }
         self.shards
             .get(branch_id)
             .map(|shard| {
@@ -717,7 +1389,7 @@ impl ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.latest().and_then(|sv| {
                                 if !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -735,6 +1407,9 @@ impl ShardedStore {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> usize {
+        if self.is_fork(branch_id) {
+            return self.list_by_type(branch_id, type_tag).len();
+        }
         self.shards
             .get(branch_id)
             .map(|shard| {
@@ -756,17 +1431,186 @@ impl ShardedStore {
 
     /// Iterate over all branches
     ///
-    /// Returns an iterator over all BranchIds that have data
+    /// Returns all BranchIds that have data, including forks that have
+    /// written nothing of their own yet
     pub fn branch_ids(&self) -> Vec<BranchId> {
-        self.shards.iter().map(|entry| *entry.key()).collect()
+        let mut ids: Vec<BranchId> = self.shards.iter().map(|entry| *entry.key()).collect();
+        for fork in self.forks.iter() {
+            if !self.shards.contains_key(fork.key()) {
+                ids.push(*fork.key());
+            }
+        }
+        ids
     }
 
     /// Clear all data for a branch
     ///
-    /// Removes the entire shard for the given branch.
+    /// Removes the entire shard for the given branch, and its fork point
+    /// if it is a fork. Forks of the branch are detached first, keeping a
+    /// copy of what they read from it.
     /// Returns true if the branch existed and was removed.
     pub fn clear_branch(&self, branch_id: &BranchId) -> bool {
-        self.shards.remove(branch_id).is_some()
+        for fork in self.forks_of(branch_id) {
+            self.detach_fork(&fork);
+        }
+        let was_fork = self.forks.remove(branch_id).is_some();
+        let had_shard = match self.shards.entry(*branch_id) {
+            dashmap::mapref::entry::Entry::Occupied(entry) => {
+                // Drop the read index under the entry lock, so no writer
+                // can recreate it before the shard is gone
+                #[cfg(feature = "epoch-reads")]
+                self.hot.remove_branch(branch_id);
+                entry.remove();
+                true
+            }
+            dashmap::mapref::entry::Entry::Vacant(_) => false,
+        };
+        had_shard || was_fork
+    }
+
+    // ========================================================================
+    // Memory Budget
+    // ========================================================================
+
+    /// Limit the memory this store holds
+    ///
+    /// Replaces the previous budget; a budget of 0 bytes removes the limit.
+    /// If usage is already over the new limit, LRU and LFU budgets evict
+    /// right away. Keys evicted by a budget are gone from every version, so
+    /// only set one on a store whose contents are not also in a WAL.
+    pub fn set_memory_budget(&self, budget: MemoryBudget) {
+        let used = if budget.is_enabled() {
+            self.memory_usage()
+        } else {
+            0
+        };
+        self.budget.set(budget, used);
+        self.enforce_memory_budget();
+    }
+
+    /// The configured memory budget (0 bytes when unlimited)
+    pub fn memory_budget(&self) -> MemoryBudget {
+        self.budget.config()
+    }
+
+    /// Usage and eviction counters of the memory budget
+    pub fn memory_budget_stats(&self) -> MemoryBudgetStats {
+        self.budget.stats()
+    }
+
+    /// Check that a write fits a [`RejectWrites`](crate::EvictionPolicy::RejectWrites) budget
+    ///
+    /// Callers check before committing data, since a write already applied
+    /// cannot be refused. Returns `CapacityExceeded` while the store holds
+    /// more than the limit; other policies make room instead and always
+    /// pass.
+    pub fn check_memory_budget(&self) -> StrataResult<()> {
+        if self.budget.tracks_access() || !self.budget.is_over_estimate() {
+            return Ok(());
+        }
+        // Deletes and trimming since the last measurement may have made room
+        let _enforcing = self
+            .budget
+            .enforcing
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let used = self.memory_usage();
+        self.budget.set_estimate(used);
+        let max_bytes = self.budget.config().max_bytes;
+        if max_bytes == 0 || used <= max_bytes {
+            return Ok(());
+        }
+        self.budget.record_rejected();
+        Err(strata_core::StrataError::capacity_exceeded(
+            "memory budget",
+            max_bytes,
+            used,
+        ))
+    }
+
+    /// Bytes a write of `value` to `key` adds, or 0 without a budget
+    #[inline]
+    fn written_bytes(&self, shard: &Shard, key: &Key, value: &StoredValue) -> usize {
+        if !self.budget.is_enabled() {
+            return 0;
+        }
+        let key_bytes = if shard.data.contains_key(key) {
+            0
+        } else {
+            key_heap_bytes(key)
+        };
+        stored_value_bytes(value) + key_bytes
+    }
+
+    /// Measure usage and, for LRU and LFU budgets, evict down to the low
+    /// water mark once over the limit
+    ///
+    /// Called by writers whose estimate passed the limit. A writer that
+    /// finds another already enforcing returns at once.
+    fn enforce_memory_budget(&self) {
+        if !self.budget.tracks_access() || !self.budget.is_over_estimate() {
+            return;
+        }
+        let Ok(_enforcing) = self.budget.enforcing.try_lock() else {
+            return;
+        };
+        let budget = self.budget.config();
+        let mut used = self.memory_usage();
+        if used <= budget.max_bytes {
+            self.budget.set_estimate(used);
+            return;
+        }
+
+        // Rank every evictable key, then remove the lowest ranked ones
+        let mut candidates: Vec<((u64, u64), u64, Key, usize)> = Vec::new();
+        for shard in self.shards.iter() {
+            for (key, chain) in &shard.data {
+                if budget.is_exempt(key) {
+                    continue;
+                }
+                let bytes = key_heap_bytes(key) + chain.memory_usage();
+                let access = &chain.access;
+                candidates.push((
+                    access.rank(budget.policy),
+                    access.last(),
+                    key.clone(),
+                    bytes,
+                ));
+            }
+        }
+        candidates.sort_unstable_by_key(|(rank, ..)| *rank);
+
+        let target = budget.max_bytes / 100 * LOW_WATER_PERCENT;
+        let mut evicted = 0;
+        for (_, last, key, bytes) in candidates {
+            if used <= target {
+                break;
+            }
+            let branch_id = key.namespace.branch_id;
+            let Some(mut shard) = self.shards.get_mut(&branch_id) else {
+                continue;
+            };
+            // Skip keys used since they were ranked
+            let Some(chain) = shard.data.get(&key) else {
+                continue;
+            };
+            if chain.access.last() != last {
+                continue;
+            }
+            #[cfg(feature = "epoch-reads")]
+            if let Some(latest) = chain.latest() {
+                // SAFETY: `hot` is dropped before `shard` releases the entry lock
+                let hot = unsafe { self.hot.writer(branch_id) };
+                hot.set(&key, &StoredValue::tombstone(latest.version()));
+            }
+            shard.data.remove(&key);
+            shard.ordered_keys.remove(&key);
+            used = used.saturating_sub(bytes);
+            evicted += 1;
+        }
+
+        self.budget.set_estimate(used);
+        self.budget.record_evicted(evicted);
     }
 
     // ========================================================================
@@ -805,7 +1649,7 @@ impl ShardedStore {
     #[inline]
     pub fn snapshot(self: &Arc<Self>) -> ShardedSnapshot {
         ShardedSnapshot {
-            version: self.version.load(Ordering::Acquire),
+            version: self.version(),
             store: Arc::clone(self),
         }
     }
@@ -914,6 +1758,9 @@ impl ShardedSnapshot {
     /// filtering out expired values and tombstones.
     /// Results are sorted by key (BTreeSet iteration order).
     pub fn list_branch(&self, branch_id: &BranchId) -> Vec<(Key, VersionedValue)> {
+        if self.store.is_fork(branch_id) {
+            return self.fork_entries(*branch_id, None, None);
+        }
         self.store
             .shards
             .get(branch_id)
@@ -925,7 +1772,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -944,6 +1791,9 @@ impl ShardedSnapshot {
     /// Uses BTreeSet range scan for O(log n + k) performance.
     pub fn list_by_prefix(&self, prefix: &Key) -> Vec<(Key, VersionedValue)> {
         let branch_id = prefix.namespace.branch_id;
+        if self.store.is_fork(&branch_id) {
+            return self.fork_entries(branch_id, Some(prefix), None);
+        }
         self.store
             .shards
             .get(&branch_id)
@@ -954,7 +1804,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -976,6 +1826,9 @@ impl ShardedSnapshot {
         branch_id: &BranchId,
         type_tag: strata_core::types::TypeTag,
     ) -> Vec<(Key, VersionedValue)> {
+        if self.store.is_fork(branch_id) {
+            return self.fork_entries(*branch_id, None, Some(type_tag));
+        }
         self.store
             .shards
             .get(branch_id)
@@ -988,7 +1841,7 @@ impl ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -1005,6 +1858,9 @@ impl ShardedSnapshot {
     /// Counts only entries that existed at the snapshot version
     /// (excludes tombstones and expired values).
     pub fn branch_entry_count(&self, branch_id: &BranchId) -> usize {
+        if self.store.is_fork(branch_id) {
+            return self.list_branch(branch_id).len();
+        }
         self.store
             .shards
             .get(branch_id)
@@ -1049,6 +1905,43 @@ impl ShardedSnapshot {
     pub fn shard_count(&self) -> usize {
         self.store.shard_count()
     }
+
+    /// Live entries of a fork at the snapshot version, inherited ones
+    /// included
+    fn fork_entries(
+        &self,
+        branch_id: BranchId,
+        prefix: Option<&Key>,
+        type_tag: Option<TypeTag>,
+    ) -> Vec<(Key, VersionedValue)> {
+        self.store
+            .scan_merged(branch_id, prefix, type_tag, |chain, cap| {
+                chain
+                    .get_at_version(self.version.min(cap))
+                    .map(live_versioned)
+            })
+    }
+
+    /// Copy every live entry at the snapshot version out of the store
+    ///
+    /// The returned view no longer references the store, so long scans
+    /// over it hold no shard locks and are unaffected by later writes or
+    /// version GC. Each shard is locked only while it is copied.
+    ///
+    /// # Performance
+    ///
+    /// O(n) in the number of keys; the view holds a full copy of the data.
+    pub fn materialize(&self) -> MaterializedSnapshot {
+        let mut branches = FxHashMap::default();
+        for branch_id in self.store.branch_ids() {
+            let entries: BTreeMap<Key, VersionedValue> =
+                self.list_branch(&branch_id).into_iter().collect();
+            if !entries.is_empty() {
+                branches.insert(branch_id, entries);
+            }
+        }
+        MaterializedSnapshot::new(self.version, branches)
+    }
 }
 
 impl std::fmt::Debug for ShardedSnapshot {
@@ -1070,43 +1963,83 @@ use strata_core::traits::Storage;
 use strata_core::value::Value;
 use strata_core::StrataResult;
 
+/// Contract view of a stored value, or `None` if it is expired or a tombstone
+fn live_versioned(sv: &StoredValue) -> Option<VersionedValue> {
+    if !sv.is_expired() && !sv.is_tombstone() {
+        Some(sv.to_versioned())
+    } else {
+        None
+    }
+}
+
+/// Contract view of a stored value, or `None` if it is a tombstone
+fn undeleted_versioned(sv: &StoredValue) -> Option<VersionedValue> {
+    if !sv.is_tombstone() {
+        Some(sv.to_versioned())
+    } else {
+        None
+    }
+}
+
+/// `key` as stored in the shard of `branch_id`
+fn rebranch(key: &Key, branch_id: BranchId) -> Key {
+    let mut key = key.clone();
+    key.namespace.branch_id = branch_id;
+    key
+}
+
+/// Versions of `chain` read by forks taken at the `pins` versions
+fn pinned_versions(chain: &VersionChain, pins: &[u64]) -> Vec<u64> {
+    pins.iter()
+        .filter_map(|&pin| chain.get_at_version(pin))
+        .map(|sv| sv.version().as_u64())
+        .collect()
+}
+
 impl Storage for ShardedStore {
     /// Get current value for key (latest version)
     ///
     /// Returns None if key doesn't exist, is expired, or is a tombstone.
     fn get(&self, key: &Key) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.latest().and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
+        // Uses are recorded on the chain, so a budget tracking them skips the index
+        #[cfg(feature = "epoch-reads")]
+        if !self.budget.tracks_access() {
+            if let Some(found) = self.hot.get(key, live_versioned) {
+                return Ok(found);
+            }
+        }
+        // Filter out expired values and tombstones; a fork falls through
+        // to its parent for keys it has not written
+        Ok(self
+            .resolve(key, |chain, cap| {
+                chain.touch(self.budget.tick());
+                chain.get_at_version(cap).map(live_versioned)
             })
-        }))
+            .flatten())
     }
 
     /// Get value at or before specified version (for snapshot isolation)
     ///
     /// Returns the value if version <= max_version, not expired, and not a tombstone.
     fn get_versioned(&self, key: &Key, max_version: u64) -> StrataResult<Option<VersionedValue>> {
-        let branch_id = key.namespace.branch_id;
-        Ok(self.shards.get(&branch_id).and_then(|shard| {
-            shard.data.get(key).and_then(|chain| {
-                chain.get_at_version(max_version).and_then(|sv| {
-                    // Filter out expired values and tombstones
-                    if !sv.is_expired() && !sv.is_tombstone() {
-                        Some(sv.versioned().clone())
-                    } else {
-                        None
-                    }
-                })
+        // The index only holds the latest version; older snapshots use the chain
+        #[cfg(feature = "epoch-reads")]
+        if !self.budget.tracks_access() {
+            if let Some(Some(found)) = self.hot.get(key, |sv| {
+                (sv.version().as_u64() <= max_version).then(|| live_versioned(sv))
+            }) {
+                return Ok(found);
+            }
+        }
+        // Filter out expired values and tombstones
+        Ok(self
+            .resolve(key, |chain, cap| {
+                chain.touch(self.budget.tick());
+                chain
+                    .get_at_version(max_version.min(cap))
+                    .map(live_versioned)
             })
-        }))
+            .flatten())
     }
 
     /// Get version history for a key
@@ -1119,6 +2052,9 @@ impl Storage for ShardedStore {
         before_version: Option<u64>,
     ) -> StrataResult<Vec<VersionedValue>> {
         let branch_id = key.namespace.branch_id;
+        if self.is_fork(&branch_id) {
+            return Ok(self.fork_history(key, limit, before_version));
+        }
 
         // Get the shard and extract history within the same scope to avoid lifetime issues
         let result = match self.shards.get(&branch_id) {
@@ -1127,7 +2063,7 @@ impl Storage for ShardedStore {
                     .history(limit, before_version)
                     .into_iter()
                     .filter(|sv| !sv.is_expired())
-                    .map(|sv| sv.versioned().clone())
+                    .map(|sv| sv.to_versioned())
                     .collect(),
                 None => Vec::new(),
             },
@@ -1167,6 +2103,14 @@ impl Storage for ShardedStore {
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
         let branch_id = prefix.namespace.branch_id;
+        if self.is_fork(&branch_id) {
+            let entries = self.scan_merged(branch_id, Some(prefix), None, |chain, cap| {
+                chain
+                    .get_at_version(max_version.min(cap))
+                    .map(live_versioned)
+            });
+            return Ok(entries);
+        }
         Ok(self
             .shards
             .get(&branch_id)
@@ -1177,7 +2121,7 @@ impl Storage for ShardedStore {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(max_version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -1197,6 +2141,13 @@ impl Storage for ShardedStore {
         branch_id: BranchId,
         max_version: u64,
     ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        if self.is_fork(&branch_id) {
+            return Ok(self.scan_merged(branch_id, None, None, |chain, cap| {
+                chain
+                    .get_at_version(max_version.min(cap))
+                    .map(live_versioned)
+            }));
+        }
         Ok(self
             .shards
             .get(&branch_id)
@@ -1208,7 +2159,7 @@ impl Storage for ShardedStore {
                         chain.get_at_version(max_version).and_then(|sv| {
                             // Filter out expired values and tombstones
                             if !sv.is_expired() && !sv.is_tombstone() {
-                                Some((k.clone(), sv.versioned().clone()))
+                                Some((k.clone(), sv.to_versioned()))
                             } else {
                                 None
                             }
@@ -1224,7 +2175,7 @@ impl Storage for ShardedStore {
 
     /// Get current global version
     fn current_version(&self) -> u64 {
-        self.version.load(Ordering::Acquire)
+        self.version()
     }
 
     /// Put a value with a specific version
@@ -1259,6 +2210,30 @@ impl Storage for ShardedStore {
 
         result
     }
+
+    fn reserve_version(&self, version: u64) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.insert(version);
+        if let Some(&lowest) = pending.first() {
+            self.pending_floor.store(lowest, Ordering::SeqCst);
+        }
+    }
+
+    fn publish_version(&self, version: u64) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.remove(&version);
+        let floor = pending.first().copied().unwrap_or(u64::MAX);
+        self.pending_floor.store(floor, Ordering::SeqCst);
+        self.published.notify_all();
+
+        // Visible once no lower version is still being applied
+        while pending.first().is_some_and(|&lowest| lowest < version) {
+            pending = self
+                .published
+                .wait(pending)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
 }
 
 // ============================================================================
@@ -1283,6 +2258,9 @@ impl SnapshotView for ShardedSnapshot {
     /// Returns all matching keys at or before snapshot version.
     fn scan_prefix(&self, prefix: &Key) -> StrataResult<Vec<(Key, VersionedValue)>> {
         let branch_id = prefix.namespace.branch_id;
+        if self.store.is_fork(&branch_id) {
+            return Ok(self.fork_entries(branch_id, Some(prefix), None));
+        }
         Ok(self
             .store
             .shards
@@ -1294,7 +2272,7 @@ impl SnapshotView for ShardedSnapshot {
                         shard.data.get(k).and_then(|chain| {
                             chain.get_at_version(self.version).and_then(|sv| {
                                 if !sv.is_expired() && !sv.is_tombstone() {
-                                    Some((k.clone(), sv.versioned().clone()))
+                                    Some((k.clone(), sv.to_versioned()))
                                 } else {
                                     None
                                 }
@@ -1306,15 +2284,60 @@ impl SnapshotView for ShardedSnapshot {
             .unwrap_or_default())
     }
 
+    /// Scan at most `limit` keys with prefix, starting at `start`
+    ///
+    /// Seeks to `start` in the BTreeSet, so keys before it cost nothing.
+    fn scan_prefix_from(
+        &self,
+        prefix: &Key,
+        start: &Key,
+        limit: usize,
+    ) -> StrataResult<Vec<(Key, VersionedValue)>> {
+        let branch_id = prefix.namespace.branch_id;
+        if self.store.is_fork(&branch_id) {
+            return Ok(self
+                .fork_entries(branch_id, Some(prefix), None)
+                .into_iter()
+                .filter(|(key, _)| key >= start)
+                .take(limit)
+                .collect());
+        }
+        let from = start.max(prefix);
+        Ok(self
+            .store
+            .shards
+            .get(&branch_id)
+            .map(|shard| {
+                shard
+                    .ordered_keys
+                    .range::<Key, _>(from..)
+                    .take_while(|k| k.starts_with(prefix))
+                    .filter_map(|k| {
+                        let sv = shard.data.get(k)?.get_at_version(self.version)?;
+                        (!sv.is_expired() && !sv.is_tombstone())
+                            .then(|| (k.clone(), sv.to_versioned()))
+                    })
+                    .take(limit)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     /// Get snapshot version
     fn version(&self) -> u64 {
         self.version
     }
+
+    /// Snapshot the same store at its current version
+    fn latest(&self) -> Option<Box<dyn SnapshotView>> {
+        Some(Box::new(self.store.snapshot()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::budget::EvictionPolicy;
     use std::sync::Arc;
 
     #[test]
@@ -1557,6 +2580,149 @@ mod tests {
         assert_eq!(store.total_entries(), 5);
     }
 
+    #[test]
+    fn test_memory_usage_tracks_values_and_versions() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        assert_eq!(store.memory_usage(), 0);
+
+        let key = create_test_key(branch_id, "blob");
+        store.put(
+            key.clone(),
+            create_stored_value(Value::Bytes(vec![0; 4096]), 1),
+        );
+        let one_version = store.memory_usage();
+        assert!(one_version > 4096);
+
+        store.put(key, create_stored_value(Value::Bytes(vec![0; 4096]), 2));
+        assert!(store.memory_usage() >= one_version + 4096);
+    }
+
+    #[test]
+    fn test_shard_usage_counts_live_entries_per_type() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        assert_eq!(store.shard_usage(&branch_id), ShardUsage::default());
+
+        store.put(
+            create_test_key(branch_id, "a"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        store.put(
+            create_test_key(branch_id, "b"),
+            create_stored_value(Value::Int(2), 2),
+        );
+        store.delete(&create_test_key(branch_id, "b"));
+
+        let usage = store.shard_usage(&branch_id);
+        assert_eq!(usage.entries.get(&TypeTag::KV), Some(&1));
+        assert_eq!(usage.memory_bytes, store.memory_usage());
+        assert_eq!(store.shard_usage(&BranchId::new()).memory_bytes, 0);
+    }
+
+    /// Store with a budget of `keys` 1KB values, and a key naming function
+    fn budgeted_store(
+        keys: usize,
+        policy: EvictionPolicy,
+    ) -> (ShardedStore, impl Fn(usize) -> Key) {
+        let branch_id = BranchId::new();
+        let key = move |i: usize| create_test_key(branch_id, &format!("key{}", i));
+        let probe = ShardedStore::new();
+        probe.put(key(0), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        let store = ShardedStore::new();
+        store.set_memory_budget(MemoryBudget::new(keys * probe.memory_usage()).policy(policy));
+        (store, key)
+    }
+
+    #[test]
+    fn test_lru_budget_evicts_least_recently_used() {
+        let (store, key) = budgeted_store(8, EvictionPolicy::Lru);
+        let branch = Key::new_branch(key(0).namespace.clone(), BranchId::new());
+        store.put(
+            branch.clone(),
+            create_stored_value(Value::Bytes(vec![0; 1024]), 1),
+        );
+        for i in 0..6 {
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        Storage::get(&store, &key(0)).unwrap().unwrap();
+
+        for i in 6..9 {
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        let stats = store.memory_budget_stats();
+        assert!(stats.evicted_keys > 0);
+        assert!(store.memory_usage() <= stats.max_bytes);
+        assert_eq!(stats.used_bytes, store.memory_usage());
+
+        // The read kept key0; the oldest untouched key went first
+        assert!(Storage::get(&store, &key(0)).unwrap().is_some());
+        assert!(Storage::get(&store, &key(1)).unwrap().is_none());
+        assert!(Storage::get(&store, &key(8)).unwrap().is_some());
+        // Branch metadata is exempt by default
+        assert!(store.contains(&branch));
+    }
+
+    #[test]
+    fn test_budget_never_evicts_reserved_keys() {
+        let (store, key) = budgeted_store(4, EvictionPolicy::Lru);
+        let policy = create_test_key(key(0).namespace.branch_id, "__history__orders");
+        store.put(
+            policy.clone(),
+            create_stored_value(Value::Bytes(vec![0; 1024]), 1),
+        );
+        for i in 0..8 {
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        assert!(store.memory_budget_stats().evicted_keys > 0);
+        assert!(store.contains(&policy));
+    }
+
+    #[test]
+    fn test_lfu_budget_keeps_frequently_used() {
+        let (store, key) = budgeted_store(4, EvictionPolicy::Lfu);
+        for i in 0..3 {
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        for _ in 0..3 {
+            Storage::get(&store, &key(0)).unwrap();
+        }
+        Storage::get(&store, &key(1)).unwrap();
+
+        for i in 3..6 {
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        assert!(store.contains(&key(0)));
+        assert!(!store.contains(&key(2)));
+    }
+
+    #[test]
+    fn test_reject_writes_budget() {
+        let (store, key) = budgeted_store(3, EvictionPolicy::RejectWrites);
+        for i in 0..3 {
+            store.check_memory_budget().unwrap();
+            store.put(key(i), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        }
+        store.put(key(3), create_stored_value(Value::Bytes(vec![0; 1024]), 1));
+        assert!(matches!(
+            store.check_memory_budget(),
+            Err(strata_core::StrataError::CapacityExceeded { .. })
+        ));
+        let stats = store.memory_budget_stats();
+        assert_eq!((stats.evicted_keys, stats.rejected_writes), (0, 1));
+
+        // Freeing memory lets writes through again
+        store.clear_branch(&key(0).namespace.branch_id);
+        store.check_memory_budget().unwrap();
+
+        store.set_memory_budget(MemoryBudget::default());
+        assert_eq!(store.memory_budget_stats().max_bytes, 0);
+    }
+
     #[test]
     fn test_concurrent_writes_different_branches() {
         use std::thread;
@@ -1835,6 +3001,261 @@ mod tests {
         assert!(!store.clear_branch(&branch_id));
     }
 
+    fn get_int(store: &ShardedStore, key: &Key) -> Option<i64> {
+        match Storage::get(store, key).unwrap().map(|vv| vv.value) {
+            Some(strata_core::value::Value::Int(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_fork_reads_parent_until_written() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        store.put(
+            create_test_key(parent, "a"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        store.put(
+            create_test_key(parent, "b"),
+            create_stored_value(Value::Int(2), 2),
+        );
+        store.fork_branch(parent, child, 2).unwrap();
+        assert_eq!(store.branch_entry_count(&child), 0);
+
+        // Later parent writes stay out of the fork
+        store.put(
+            create_test_key(parent, "a"),
+            create_stored_value(Value::Int(10), 3),
+        );
+        store.put(
+            create_test_key(parent, "c"),
+            create_stored_value(Value::Int(3), 4),
+        );
+        assert_eq!(get_int(&store, &create_test_key(child, "a")), Some(1));
+        assert_eq!(get_int(&store, &create_test_key(child, "c")), None);
+
+        // Fork writes and deletes shadow the parent without touching it
+        store.put(
+            create_test_key(child, "b"),
+            create_stored_value(Value::Int(20), 5),
+        );
+        store
+            .delete_with_version(&create_test_key(child, "a"), 6)
+            .unwrap();
+        assert_eq!(get_int(&store, &create_test_key(child, "b")), Some(20));
+        assert_eq!(get_int(&store, &create_test_key(parent, "b")), Some(2));
+        assert_eq!(get_int(&store, &create_test_key(child, "a")), None);
+        assert_eq!(get_int(&store, &create_test_key(parent, "a")), Some(10));
+
+        let listed = store.list_branch(&child);
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, create_test_key(child, "b"));
+        let history =
+            Storage::get_history(&store, &create_test_key(child, "b"), None, None).unwrap();
+        let versions: Vec<u64> = history.iter().map(|vv| vv.version.as_u64()).collect();
+        assert_eq!(versions, vec![5, 2]);
+        assert_eq!(
+            store.inherited_keys(&child),
+            Vec::<Key>::new(),
+            "every inherited key is written or deleted"
+        );
+        assert!(store.fork_branch(child, parent, 6).is_err());
+    }
+
+    #[test]
+    fn test_fork_keeps_parent_versions_it_reads() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        let key = create_test_key(parent, "k");
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        store.put(key.clone(), create_stored_value(Value::Int(2), 2));
+        store.fork_branch(parent, child, 2).unwrap();
+        store.put(key.clone(), create_stored_value(Value::Int(3), 3));
+
+        // Only the version older than the fork point is pruned
+        assert_eq!(store.gc_branch(parent, 10), 1);
+        assert_eq!(get_int(&store, &create_test_key(child, "k")), Some(2));
+
+        // A WAL rewrite keeps the pinned version of the parent
+        let mut durable: Vec<u64> = store
+            .durable_entries(&parent)
+            .into_iter()
+            .map(|(_, version, _)| version)
+            .collect();
+        durable.sort_unstable();
+        assert_eq!(durable, vec![2, 3]);
+
+        // Clearing the parent leaves the fork its own copy
+        assert!(store.clear_branch(&parent));
+        assert_eq!(store.fork_point(&child), None);
+        assert_eq!(get_int(&store, &create_test_key(child, "k")), Some(2));
+    }
+
+    #[test]
+    fn test_parent_delete_after_fork_survives_rewrite() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        let key = create_test_key(parent, "gone");
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        store.fork_branch(parent, child, 1).unwrap();
+        store.delete_with_version(&key, 2).unwrap();
+
+        // The pinned version is kept, so the delete above it must be too
+        let durable = store.durable_entries(&parent);
+        let versions: Vec<(u64, bool)> = durable
+            .iter()
+            .map(|(_, version, value)| (*version, value.is_some()))
+            .collect();
+        assert_eq!(versions, vec![(1, true), (2, false)]);
+
+        // Replaying the rewrite, as recovery does, keeps the key deleted
+        let restored = ShardedStore::new();
+        for (key, version, value) in durable {
+            match value {
+                Some(value) => restored.put(key, create_stored_value(value, version)),
+                None => {
+                    restored.delete_with_version(&key, version).unwrap();
+                }
+            }
+        }
+        restored.fork_branch(parent, child, 1).unwrap();
+        assert_eq!(get_int(&restored, &key), None);
+        assert_eq!(get_int(&restored, &create_test_key(child, "gone")), Some(1));
+    }
+
+    #[test]
+    fn test_refork_drops_versions_before_fork() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let parent = BranchId::new();
+        let child = BranchId::new();
+        store.put(
+            create_test_key(parent, "k"),
+            create_stored_value(Value::Int(1), 1),
+        );
+        // Left over from an earlier branch with the child's ID
+        store.put(
+            create_test_key(child, "k"),
+            create_stored_value(Value::Int(7), 2),
+        );
+        store
+            .delete_with_version(&create_test_key(child, "k"), 3)
+            .unwrap();
+        store.fork_branch(parent, child, 4).unwrap();
+        store
+            .delete_with_version(&create_test_key(child, "gone"), 5)
+            .unwrap();
+
+        assert_eq!(get_int(&store, &create_test_key(child, "k")), Some(1));
+        let durable = store.durable_entries(&child);
+        assert_eq!(durable.len(), 1);
+        assert_eq!(durable[0].1, 5);
+        assert!(durable[0].2.is_none());
+    }
+
+    #[test]
+    fn test_reads_after_clear_branch() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "k");
+
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        assert!(store.clear_branch(&branch_id));
+        assert!(Storage::get(&store, &key).unwrap().is_none());
+        assert!(store.get_versioned(&key, u64::MAX).unwrap().is_none());
+
+        // The branch can be written again from scratch
+        store.put(key.clone(), create_stored_value(Value::Int(2), 2));
+        assert_eq!(
+            Storage::get(&store, &key).unwrap().unwrap().value,
+            Value::Int(2)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_reads_skip_newer_latest() {
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let branch_id = BranchId::new();
+        let key = create_test_key(branch_id, "k");
+
+        store.put(key.clone(), create_stored_value(Value::Int(1), 1));
+        store
+            .apply_batch(&[(key.clone(), Value::Int(2))], &[], 2)
+            .unwrap();
+        store
+            .apply_batch(&[], std::slice::from_ref(&key), 3)
+            .unwrap();
+
+        assert!(Storage::get(&store, &key).unwrap().is_none());
+        assert!(store.get_versioned(&key, 3).unwrap().is_none());
+        let at_2 = store.get_versioned(&key, 2).unwrap().unwrap();
+        assert_eq!((at_2.value, at_2.version), (Value::Int(2), Version::txn(2)));
+        let at_1 = store.get_versioned(&key, 1).unwrap().unwrap();
+        assert_eq!(at_1.value, Value::Int(1));
+        assert!(store.get_versioned(&key, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_concurrent_reads_see_whole_values() {
+        use std::sync::atomic::AtomicBool;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let branch_id = BranchId::new();
+        let keys: Vec<Key> = (0..64)
+            .map(|i| create_test_key(branch_id, &format!("k{}", i)))
+            .collect();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let store = Arc::clone(&store);
+                let keys = keys.clone();
+                let done = Arc::clone(&done);
+                std::thread::spawn(move || {
+                    while !done.load(Ordering::Relaxed) {
+                        for key in &keys {
+                            // Every write stores its version as the value
+                            if let Some(vv) = Storage::get(&*store, key).unwrap() {
+                                assert_eq!(vv.value, Value::Int(vv.version.as_u64() as i64));
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for version in 1..=40u64 {
+            let writes: Vec<_> = keys
+                .iter()
+                .map(|key| (key.clone(), Value::Int(version as i64)))
+                .collect();
+            store.apply_batch(&writes, &[], version).unwrap();
+            if version % 10 == 0 {
+                store.clear_branch(&branch_id);
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+
     #[test]
     fn test_list_sorted_order() {
         use strata_core::types::Namespace;
@@ -2261,6 +3682,65 @@ mod tests {
         assert!(Storage::current_version(&store) >= 42);
     }
 
+    #[test]
+    fn test_reserved_version_hides_later_commits() {
+        use strata_core::traits::Storage;
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let key_a = Key::new_kv(Namespace::for_branch(BranchId::new()), "a");
+        let key_b = Key::new_kv(Namespace::for_branch(BranchId::new()), "b");
+
+        // Version 2 is applied before version 1, which is still in flight
+        store.reserve_version(1);
+        store.reserve_version(2);
+        Storage::put_with_version(&*store, key_b.clone(), Value::Int(2), 2, None).unwrap();
+        assert_eq!(store.snapshot().version(), 0);
+        assert_eq!(Storage::current_version(&*store), 0);
+
+        Storage::put_with_version(&*store, key_a.clone(), Value::Int(1), 1, None).unwrap();
+        store.publish_version(1);
+        store.publish_version(2);
+        let snapshot = store.snapshot();
+        assert_eq!(snapshot.version(), 2);
+        assert!(snapshot.get(&key_a).unwrap().is_some());
+        assert!(snapshot.get(&key_b).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_publish_waits_for_lower_versions() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+        use strata_core::traits::Storage;
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let key = Key::new_kv(Namespace::for_branch(BranchId::new()), "k");
+        store.reserve_version(1);
+        store.reserve_version(2);
+        Storage::put_with_version(&*store, key, Value::Int(2), 2, None).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::spawn({
+            let store = Arc::clone(&store);
+            move || {
+                store.publish_version(2);
+                tx.send(store.version()).unwrap();
+            }
+        });
+
+        // Version 2 cannot become visible while 1 is pending
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+        store.publish_version(1);
+        assert_eq!(rx.recv_timeout(Duration::from_secs(5)).unwrap(), 2);
+        handle.join().unwrap();
+
+        // Nothing pending: compaction-style waits return at once
+        store.wait_for_pending_versions();
+    }
+
     #[test]
     fn test_snapshot_view_trait() {
         use strata_core::traits::{SnapshotView, Storage};
@@ -2346,6 +3826,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_snapshot_view_scan_prefix_from() {
+        use strata_core::traits::{SnapshotView, Storage};
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = Arc::new(ShardedStore::new());
+        let ns = Namespace::for_branch(BranchId::new());
+        for name in ["user:a", "user:b", "user:c", "user:d", "zone:a"] {
+            Storage::put(&*store, Key::new_kv(ns.clone(), name), Value::Int(1), None).unwrap();
+        }
+        Storage::delete(&*store, &Key::new_kv(ns.clone(), "user:c")).unwrap();
+
+        let snapshot = store.snapshot();
+        let prefix = Key::new_kv(ns.clone(), "user:");
+        let names = |start: &str, limit| -> Vec<Vec<u8>> {
+            SnapshotView::scan_prefix_from(
+                &snapshot,
+                &prefix,
+                &Key::new_kv(ns.clone(), start),
+                limit,
+            )
+            .unwrap()
+            .into_iter()
+            .map(|(k, _)| k.user_key)
+            .collect()
+        };
+
+        // Deleted keys are skipped and the scan stops at the prefix's end
+        assert_eq!(
+            names("user:b", 10),
+            vec![b"user:b".to_vec(), b"user:d".to_vec()]
+        );
+        assert_eq!(names("user:a", 1), vec![b"user:a".to_vec()]);
+        assert_eq!(names("", 10).len(), 3);
+    }
+
     // ========================================================================
     // VersionChain::history() Tests
     // ========================================================================
@@ -2442,6 +3959,51 @@ mod tests {
         assert_eq!(chain.version_count(), 1);
     }
 
+    #[test]
+    fn test_version_chain_trim_keeps_latest() {
+        use strata_core::value::Value;
+
+        let mut chain = VersionChain::new(create_stored_value(Value::Int(1), 1));
+        for v in 2..=5 {
+            chain.push(create_stored_value(Value::Int(v as i64), v));
+        }
+        assert!(!chain.is_trimmed());
+
+        // Keep the newest two
+        assert_eq!(chain.trim(|_, position| position < 2), 3);
+        assert_eq!(chain.version_count(), 2);
+        assert_eq!(chain.history(None, None)[1].version().as_u64(), 4);
+        assert!(chain.is_trimmed());
+
+        // Rejecting everything still leaves the latest version
+        assert_eq!(chain.trim(|_, _| false), 1);
+        assert_eq!(chain.latest().unwrap().version().as_u64(), 5);
+    }
+
+    #[test]
+    fn test_trim_prefix_only_touches_matching_keys() {
+        use strata_core::types::Namespace;
+        use strata_core::value::Value;
+
+        let store = ShardedStore::new();
+        let ns = Namespace::for_branch(BranchId::new());
+        let session = Key::new_kv(ns.clone(), "session:1");
+        let other = Key::new_kv(ns.clone(), "user:1");
+        for v in 1..=3 {
+            let value = create_stored_value(Value::Int(v as i64), v);
+            store.put(session.clone(), value.clone());
+            store.put(other.clone(), value);
+        }
+
+        let prefix = Key::new_kv(ns, "session:");
+        let pruned = store.trim_prefix(&prefix, |_, _, position| position == 0);
+        assert_eq!(pruned, 2);
+        assert!(store.is_history_trimmed(&session));
+        assert!(!store.is_history_trimmed(&other));
+        assert_eq!(store.get_history(&session, None, None).unwrap().len(), 1);
+        assert_eq!(store.get_history(&other, None, None).unwrap().len(), 3);
+    }
+
     // ========================================================================
     // VersionChain::get_at_version() Tests (MVCC)
     // ========================================================================
@@ -2483,19 +4045,19 @@ mod tests {
         let result = chain.get_at_version(3);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 3);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(300));
+        assert_eq!(result.unwrap().value().into_owned(), Value::Int(300));
 
         // Query at version 2 should return version 2
         let result = chain.get_at_version(2);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 2);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(200));
+        assert_eq!(result.unwrap().value().into_owned(), Value::Int(200));
 
         // Query at version 1 should return version 1
         let result = chain.get_at_version(1);
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 1);
-        assert_eq!(result.unwrap().versioned().value, Value::Int(100));
+        assert_eq!(result.unwrap().value().into_owned(), Value::Int(100));
 
         // Query at version 0 should return None
         let result = chain.get_at_version(0);
@@ -2550,7 +4112,7 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap().version().as_u64(), 1);
         assert_eq!(
-            result.unwrap().versioned().value,
+            result.unwrap().value().into_owned(),
             Value::String("v1".into())
         );
 
@@ -2,22 +2,131 @@
 //!
 //! The contract type `Versioned<T>` doesn't include TTL because TTL is
 //! a storage concern, not a contract concern. This module provides
-//! `StoredValue` which combines a `VersionedValue` with optional TTL
-//! for the storage layer.
+//! `StoredValue` which combines a value, version and timestamp with
+//! optional TTL for the storage layer.
+//!
+//! Short strings and byte values (up to [`INLINE_CAPACITY`] bytes) are
+//! stored inline rather than in their own heap allocation. Most agent
+//! values are flags, counters and short strings, so this keeps the bulk of
+//! a store's values next to their version metadata.
 
+use std::borrow::Cow;
+use std::fmt;
 use std::time::Duration;
 
 use strata_core::{Timestamp, Value, Version, VersionedValue};
 
+/// Longest string or byte value stored inline, in bytes
+///
+/// 54 bytes plus a length byte is the most that fits in the space `Value`
+/// already occupies, so inlining does not grow `StoredValue`.
+pub const INLINE_CAPACITY: usize = 54;
+
+/// Fixed-capacity byte buffer for inline values
+#[derive(Clone, Copy)]
+struct InlineBytes {
+    len: u8,
+    buf: [u8; INLINE_CAPACITY],
+}
+
+impl InlineBytes {
+    /// Copy `bytes` inline, or `None` if they exceed `INLINE_CAPACITY`
+    fn new(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > INLINE_CAPACITY {
+            return None;
+        }
+        let mut buf = [0u8; INLINE_CAPACITY];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Some(InlineBytes {
+            len: bytes.len() as u8,
+            buf,
+        })
+    }
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        &self.buf[..self.len as usize]
+    }
+}
+
+impl fmt::Debug for InlineBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl PartialEq for InlineBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// Value storage: inline for short strings and bytes, `Value` otherwise
+#[derive(Debug, Clone, PartialEq)]
+enum Payload {
+    /// Any value not stored inline
+    Value(Value),
+    /// `Value::String` of at most `INLINE_CAPACITY` bytes (valid UTF-8)
+    InlineString(InlineBytes),
+    /// `Value::Bytes` of at most `INLINE_CAPACITY` bytes
+    InlineBytes(InlineBytes),
+}
+
+impl Payload {
+    /// Take ownership of `value`, moving short strings and bytes inline
+    fn from_value(value: Value) -> Self {
+        Self::from_ref(&value).unwrap_or(Payload::Value(value))
+    }
+
+    /// Inline payload for a short string or byte value, or `None`
+    fn from_ref(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => InlineBytes::new(s.as_bytes()).map(Payload::InlineString),
+            Value::Bytes(b) => InlineBytes::new(b).map(Payload::InlineBytes),
+            _ => None,
+        }
+    }
+
+    /// Copy `value`, without a heap allocation when it fits inline
+    fn from_cloned(value: &Value) -> Self {
+        Self::from_ref(value).unwrap_or_else(|| Payload::Value(value.clone()))
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Payload::Value(v) => v.clone(),
+            Payload::InlineString(b) => Value::String(inline_str(b).to_owned()),
+            Payload::InlineBytes(b) => Value::Bytes(b.as_slice().to_vec()),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Payload::Value(v) => v,
+            inline => inline.to_value(),
+        }
+    }
+}
+
+/// View an inline string payload as `&str`
+fn inline_str(bytes: &InlineBytes) -> &str {
+    // Only ever built from a `String`, so always valid UTF-8.
+    std::str::from_utf8(bytes.as_slice()).expect("inline string is valid UTF-8")
+}
+
 /// A stored value with optional TTL
 ///
-/// Wraps `VersionedValue` with TTL metadata for the storage layer.
-/// This separation keeps TTL as a storage concern, not part of the
-/// contract types.
+/// Holds the value, version and timestamp of a `VersionedValue` plus TTL
+/// metadata for the storage layer. This separation keeps TTL as a storage
+/// concern, not part of the contract types.
 #[derive(Debug, Clone, PartialEq)]
 pub struct StoredValue {
-    /// The versioned value (value + version + timestamp)
-    inner: VersionedValue,
+    /// The value, inline when short
+    payload: Payload,
+    /// Version of this entry
+    version: Version,
+    /// Creation timestamp
+    timestamp: Timestamp,
     /// Optional time-to-live
     ttl: Option<Duration>,
     /// Whether this entry is a tombstone (explicit deletion marker)
@@ -27,11 +136,7 @@ pub struct StoredValue {
 impl StoredValue {
     /// Create a new stored value with TTL
     pub fn new(value: Value, version: Version, ttl: Option<Duration>) -> Self {
-        StoredValue {
-            inner: VersionedValue::new(value, version),
-            ttl,
-            is_tombstone: false,
-        }
+        Self::with_timestamp(value, version, Timestamp::now(), ttl)
     }
 
     /// Create a stored value with explicit timestamp
@@ -42,28 +147,41 @@ impl StoredValue {
         ttl: Option<Duration>,
     ) -> Self {
         StoredValue {
-            inner: VersionedValue::with_timestamp(value, version, timestamp),
+            payload: Payload::from_value(value),
+            version,
+            timestamp,
             ttl,
             is_tombstone: false,
         }
     }
 
-    /// Create from a VersionedValue without TTL
-    pub fn from_versioned(vv: VersionedValue) -> Self {
+    /// Create a stored value by copying a borrowed value
+    ///
+    /// Short strings and bytes are copied inline, so unlike cloning the
+    /// value first this does not allocate for them.
+    pub fn from_borrowed(
+        value: &Value,
+        version: Version,
+        timestamp: Timestamp,
+        ttl: Option<Duration>,
+    ) -> Self {
         StoredValue {
-            inner: vv,
-            ttl: None,
+            payload: Payload::from_cloned(value),
+            version,
+            timestamp,
+            ttl,
             is_tombstone: false,
         }
     }
 
+    /// Create from a VersionedValue without TTL
+    pub fn from_versioned(vv: VersionedValue) -> Self {
+        Self::from_versioned_with_ttl(vv, None)
+    }
+
     /// Create from a VersionedValue with TTL
     pub fn from_versioned_with_ttl(vv: VersionedValue, ttl: Option<Duration>) -> Self {
-        StoredValue {
-            inner: vv,
-            ttl,
-            is_tombstone: false,
-        }
+        Self::with_timestamp(vv.value, vv.version, vv.timestamp, ttl)
     }
 
     /// Create a tombstone entry (explicit deletion marker)
@@ -72,7 +190,9 @@ impl StoredValue {
     /// conflating `Value::Null` with deletion.
     pub fn tombstone(version: Version) -> Self {
         StoredValue {
-            inner: VersionedValue::new(Value::Null, version),
+            payload: Payload::Value(Value::Null),
+            version,
+            timestamp: Timestamp::now(),
             ttl: None,
             is_tombstone: true,
         }
@@ -84,34 +204,46 @@ impl StoredValue {
         self.is_tombstone
     }
 
-    /// Get the inner VersionedValue
+    /// Check whether the value is stored inline (no heap allocation)
     #[inline]
-    pub fn versioned(&self) -> &VersionedValue {
-        &self.inner
+    pub fn is_inline(&self) -> bool {
+        !matches!(self.payload, Payload::Value(_))
+    }
+
+    /// Copy out a `VersionedValue`
+    #[inline]
+    pub fn to_versioned(&self) -> VersionedValue {
+        VersionedValue::with_timestamp(self.payload.to_value(), self.version, self.timestamp)
     }
 
     /// Consume and return the inner VersionedValue
     #[inline]
     pub fn into_versioned(self) -> VersionedValue {
-        self.inner
+        VersionedValue::with_timestamp(self.payload.into_value(), self.version, self.timestamp)
     }
 
     /// Get the value
+    ///
+    /// Borrowed unless the value is stored inline, in which case it is
+    /// materialized into an owned `Value`.
     #[inline]
-    pub fn value(&self) -> &Value {
-        &self.inner.value
+    pub fn value(&self) -> Cow<'_, Value> {
+        match &self.payload {
+            Payload::Value(v) => Cow::Borrowed(v),
+            inline => Cow::Owned(inline.to_value()),
+        }
     }
 
     /// Get the version
     #[inline]
     pub fn version(&self) -> Version {
-        self.inner.version
+        self.version
     }
 
     /// Get the timestamp
     #[inline]
     pub fn timestamp(&self) -> Timestamp {
-        self.inner.timestamp
+        self.timestamp
     }
 
     /// Get the TTL
@@ -124,7 +256,7 @@ impl StoredValue {
     pub fn is_expired(&self) -> bool {
         if let Some(ttl) = self.ttl {
             let now = Timestamp::now();
-            if let Some(age) = now.duration_since(self.inner.timestamp) {
+            if let Some(age) = now.duration_since(self.timestamp) {
                 return age >= ttl;
             }
         }
@@ -135,13 +267,13 @@ impl StoredValue {
     ///
     /// Returns `Some(timestamp)` when the value will expire, or `None` if no TTL.
     pub fn expiry_timestamp(&self) -> Option<Timestamp> {
-        self.ttl.map(|ttl| self.inner.timestamp.saturating_add(ttl))
+        self.ttl.map(|ttl| self.timestamp.saturating_add(ttl))
     }
 }
 
 impl From<StoredValue> for VersionedValue {
     fn from(sv: StoredValue) -> Self {
-        sv.inner
+        sv.into_versioned()
     }
 }
 
@@ -225,4 +357,53 @@ mod tests {
         assert_eq!(sv.version(), Version::Sequence(10));
         assert!(sv.ttl().is_none());
     }
+
+    #[test]
+    fn test_short_values_are_inline() {
+        let sv = StoredValue::new(Value::String("enabled".into()), Version::txn(1), None);
+        assert!(sv.is_inline());
+        assert_eq!(*sv.value(), Value::String("enabled".into()));
+
+        let sv = StoredValue::new(Value::Bytes(vec![1, 2, 3]), Version::txn(1), None);
+        assert!(sv.is_inline());
+        assert_eq!(sv.into_versioned().value, Value::Bytes(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_inline_capacity_boundary() {
+        let fits = "é".repeat(INLINE_CAPACITY / 2);
+        let sv = StoredValue::new(Value::String(fits.clone()), Version::txn(1), None);
+        assert!(sv.is_inline());
+        assert_eq!(sv.to_versioned().value, Value::String(fits));
+
+        let too_long = "x".repeat(INLINE_CAPACITY + 1);
+        let sv = StoredValue::new(Value::String(too_long.clone()), Version::txn(1), None);
+        assert!(!sv.is_inline());
+        assert_eq!(sv.to_versioned().value, Value::String(too_long));
+
+        let empty = StoredValue::new(Value::String(String::new()), Version::txn(1), None);
+        assert!(empty.is_inline());
+        assert_eq!(*empty.value(), Value::String(String::new()));
+    }
+
+    #[test]
+    fn test_from_borrowed_matches_owned() {
+        let ts = Timestamp::from_micros(5);
+        for value in [
+            Value::Int(7),
+            Value::String("short".into()),
+            Value::String("y".repeat(200)),
+            Value::Bytes(vec![0; 10]),
+        ] {
+            let borrowed = StoredValue::from_borrowed(&value, Version::txn(3), ts, None);
+            let owned = StoredValue::with_timestamp(value.clone(), Version::txn(3), ts, None);
+            assert_eq!(borrowed, owned);
+            assert_eq!(borrowed.to_versioned().value, value);
+        }
+    }
+
+    #[test]
+    fn test_inline_payload_does_not_grow_value() {
+        assert_eq!(std::mem::size_of::<Payload>(), std::mem::size_of::<Value>());
+    }
 }
@@ -0,0 +1,40 @@
+//! Decode arbitrary bytes as a JSON document plus a path into it
+//!
+//! The first line is parsed as a `JsonPath`, the rest as a `JsonValue`.
+//! Path parsing and the get/set/delete path operations must never panic,
+//! whatever the shape of the document.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strata_core::primitives::json::{delete_at_path, get_at_path, set_at_path};
+use strata_core::{JsonPath, JsonValue};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (path, document) = text.split_once('\n').unwrap_or((text, "null"));
+
+    let Ok(path) = path.parse::<JsonPath>() else {
+        return;
+    };
+    // A parsed path must print back to a string that parses to itself
+    let reparsed: JsonPath = path
+        .to_path_string()
+        .parse()
+        .expect("printed path must parse");
+    assert_eq!(path, reparsed, "path changed across a round trip");
+    let _ = path.validate();
+
+    let Ok(mut value) = document.parse::<JsonValue>() else {
+        return;
+    };
+    let _ = value.validate();
+
+    let _ = get_at_path(&value, &path);
+    if set_at_path(&mut value, &path, JsonValue::from(1)).is_ok() {
+        assert_eq!(get_at_path(&value, &path), Some(&JsonValue::from(1)));
+    }
+    let _ = delete_at_path(&mut value, &path);
+});
@@ -0,0 +1,21 @@
+//! Decode arbitrary bytes as a wire `Command`
+//!
+//! Commands arrive as JSON from the CLI and client bindings. Decoding must
+//! reject malformed input with an error, and anything that decodes must
+//! survive a serialize/decode round trip unchanged.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use strata_executor::Command;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(command) = serde_json::from_slice::<Command>(data) else {
+        return;
+    };
+    let encoded = serde_json::to_vec(&command).expect("decoded command must serialize");
+    let decoded: Command =
+        serde_json::from_slice(&encoded).expect("serialized command must decode");
+    let reencoded = serde_json::to_vec(&decoded).expect("decoded command must serialize");
+    assert_eq!(encoded, reencoded, "command changed across a round trip");
+});
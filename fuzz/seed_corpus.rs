@@ -0,0 +1,184 @@
+//! Write seed inputs for the fuzz targets
+//!
+//! Every seed comes from the real encoders, so fuzzing starts from inputs
+//! that reach deep into each parser rather than failing the first check.
+//!
+//! ```text
+//! cargo run --manifest-path fuzz/Cargo.toml --bin seed_corpus
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use strata_concurrency::TransactionPayload;
+use strata_core::types::{BranchId, Key, Namespace};
+use strata_core::{EntityRef, Value};
+use strata_durability::codec::IdentityCodec;
+use strata_durability::disk_snapshot::{SnapshotSection, SnapshotWriter};
+use strata_durability::format::{
+    primitive_tags, BranchSnapshotEntry, EventSnapshotEntry, JsonSnapshotEntry, KvSnapshotEntry,
+    SegmentHeader, SnapshotSerializer, StateSnapshotEntry, VectorCollectionSnapshotEntry,
+    VectorSnapshotEntry, WalRecord, Writeset,
+};
+
+const COMMANDS: &[&str] = &[
+    r#"{"KvPut":{"key":"foo","value":{"Int":42}}}"#,
+    r#"{"KvGet":{"branch":"default","key":"foo","as_of":1700000000000000}}"#,
+    r#"{"KvList":{"prefix":"user:","limit":10}}"#,
+    r#"{"JsonSet":{"key":"doc","path":"$.items[0].name","value":{"String":"x"}}}"#,
+    r#"{"JsonGet":{"key":"doc","path":"$"}}"#,
+    r#"{"EventAppend":{"event_type":"click","payload":{"Object":{"x":{"Float":1.5}}}}}"#,
+    r#"{"StateCas":{"cell":"c","expected_counter":3,"value":{"Bytes":[1,2,3]}}}"#,
+    r#"{"VectorSearch":{"collection":"docs","query":[0.1,0.2],"k":5,"filter":[{"op":"or","filters":[{"field":"tag","op":"eq","value":{"String":"a"}},{"field":"n","op":"range","value":{"Array":[{"Int":1},{"Int":9}]}}]}]}}"#,
+    r#"{"VectorSearchBatch":{"collection":"docs","queries":[[0.1,0.2],[0.3,0.4]],"k":3}}"#,
+    r#"{"VectorSearch":{"collection":"docs","query":[0.1,0.2],"k":5,"namespace":"tenant-a"}}"#,
+    r#"{"VectorReindex":{"collection":"docs","metric":"euclidean","index_type":"hnsw"}}"#,
+    r#"{"SemanticSearch":{"query":"disk failure","k":5}}"#,
+    r#"{"SemanticSearch":{"query":"disk failure","k":5,"rerank_top_n":20}}"#,
+    r#"{"BranchCreate":{"branch_id":"feature","metadata":null}}"#,
+    r#""Ping""#,
+    r#""LatencyReport""#,
+];
+
+const JSON_DOCS: &[&str] = &[
+    "$\n{\"a\":1}",
+    "$.user.name\n{\"user\":{\"name\":\"Alice\",\"tags\":[\"x\",\"y\"]}}",
+    "$.items[1]\n{\"items\":[1,2,3]}",
+    "$[0][0]\n[[true,null],{\"k\":-1.5e10}]",
+    "$.a.b.c\n{}",
+];
+
+fn main() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("corpus");
+
+    for (i, command) in COMMANDS.iter().enumerate() {
+        // Seeds must stay in step with the wire format
+        let command: strata_executor::Command = serde_json::from_str(command)
+            .unwrap_or_else(|e| panic!("seed command {} no longer decodes: {}", i, e));
+        let bytes = serde_json::to_vec(&command).unwrap();
+        write_seed(&root, "wire_request", &format!("command-{}", i), &bytes);
+    }
+
+    for (i, doc) in JSON_DOCS.iter().enumerate() {
+        write_seed(&root, "wire_json", &format!("doc-{}", i), doc.as_bytes());
+    }
+
+    for (name, bytes) in wal_seeds() {
+        write_seed(&root, "wal_record", name, &bytes);
+    }
+
+    write_seed(&root, "snapshot", "snapshot-all-sections", &snapshot_seed());
+}
+
+fn write_seed(root: &Path, target: &str, name: &str, bytes: &[u8]) {
+    let dir = root.join(target);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(name), bytes).unwrap();
+}
+
+fn wal_seeds() -> Vec<(&'static str, Vec<u8>)> {
+    let branch_id = BranchId::new();
+    let ns = Namespace::for_branch(branch_id);
+    let payload = TransactionPayload {
+        version: 7,
+        puts: vec![
+            (Key::new_kv(ns.clone(), "k"), Value::Int(1)),
+            (Key::new_kv(ns.clone(), "s"), Value::String("v".into())),
+        ],
+        deletes: vec![Key::new_kv(ns, "gone")],
+    };
+    let payload_bytes = payload.to_bytes();
+    let record = WalRecord::new(
+        3,
+        *branch_id.as_bytes(),
+        1_700_000_000,
+        payload_bytes.clone(),
+    );
+
+    let mut writeset = Writeset::new();
+    writeset.put(EntityRef::kv(branch_id, "k"), b"value".to_vec(), 7);
+    writeset.delete(EntityRef::json(branch_id, "doc"));
+    writeset.append(EntityRef::event(branch_id, 4), b"event".to_vec(), 8);
+    writeset.put(EntityRef::vector(branch_id, "col", "v1"), vec![0; 8], 9);
+    writeset.put(EntityRef::state(branch_id, "cell"), vec![1], 10);
+    writeset.put(EntityRef::branch(branch_id), vec![2], 11);
+
+    vec![
+        ("record-payload", record.to_bytes()),
+        ("transaction-payload", payload_bytes),
+        ("writeset", writeset.to_bytes()),
+        (
+            "segment-header",
+            SegmentHeader::new(1, *branch_id.as_bytes()).to_bytes(),
+        ),
+    ]
+}
+
+fn snapshot_seed() -> Vec<u8> {
+    let serializer = SnapshotSerializer::new(Box::new(IdentityCodec));
+    let sections = vec![
+        SnapshotSection::new(
+            primitive_tags::KV,
+            serializer.serialize_kv(&[KvSnapshotEntry {
+                key: "k".into(),
+                value: b"v".to_vec(),
+                version: 1,
+                timestamp: 2,
+            }]),
+        ),
+        SnapshotSection::new(
+            primitive_tags::EVENT,
+            serializer.serialize_events(&[EventSnapshotEntry {
+                sequence: 1,
+                payload: b"e".to_vec(),
+                timestamp: 2,
+            }]),
+        ),
+        SnapshotSection::new(
+            primitive_tags::STATE,
+            serializer.serialize_states(&[StateSnapshotEntry {
+                name: "cell".into(),
+                value: b"s".to_vec(),
+                counter: 3,
+                timestamp: 4,
+            }]),
+        ),
+        SnapshotSection::new(
+            primitive_tags::BRANCH,
+            serializer.serialize_branches(&[BranchSnapshotEntry {
+                branch_id: [1; 16],
+                name: "main".into(),
+                created_at: 5,
+                metadata: b"{}".to_vec(),
+            }]),
+        ),
+        SnapshotSection::new(
+            primitive_tags::JSON,
+            serializer.serialize_json(&[JsonSnapshotEntry {
+                doc_id: "doc".into(),
+                content: br#"{"a":1}"#.to_vec(),
+                version: 6,
+                timestamp: 7,
+            }]),
+        ),
+        SnapshotSection::new(
+            primitive_tags::VECTOR,
+            serializer.serialize_vectors(&[VectorCollectionSnapshotEntry {
+                name: "col".into(),
+                config: b"{}".to_vec(),
+                vectors: vec![VectorSnapshotEntry {
+                    key: "v1".into(),
+                    vector_id: 1,
+                    embedding: vec![0.5, -0.25],
+                    metadata: Vec::new(),
+                }],
+            }]),
+        ),
+    ];
+
+    let dir = tempfile::tempdir().unwrap();
+    let writer =
+        SnapshotWriter::new(dir.path().to_path_buf(), Box::new(IdentityCodec), [9; 16]).unwrap();
+    let info = writer.create_snapshot(1, 42, sections).unwrap();
+    fs::read(info.path).unwrap()
+}
@@ -20,7 +20,8 @@
 //!     assert_eq!(db.kv_get("user:name")?, Some(Value::String("Alice".into())));
 //!
 //!     // Append-only event log
-//!     db.event_append("tool_call", serde_json::json!({"tool": "search"}).into())?;
+//!     let call = Value::try_from(&serde_json::json!({"tool": "search"}))?;
+//!     db.event_append("tool_call", call)?;
 //!
 //!     // Branch isolation (like git branches)
 //!     db.create_branch("experiment")?;
@@ -54,6 +55,14 @@
 //!
 //! Internal crates (storage, concurrency, durability, engine) are not exposed.
 //! Only the public API surface in this crate is stable.
+//!
+//! # Stability
+//!
+//! Every item reachable from `stratadb::` follows semver: removing or renaming
+//! one, or changing its signature, only happens in a major release. Items marked
+//! `#[doc(hidden)]` and the internal crates themselves carry no guarantee and
+//! may change in any release. `tests/public_api.rs` pins the stable surface, so
+//! an accidental break fails CI.
 
 // Re-export the public API from strata-executor
 pub use strata_executor::*;
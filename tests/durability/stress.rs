@@ -8,6 +8,9 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 /// 10K key recovery
+///
+/// Checks correctness only; recovery timings are tracked by the engine's
+/// `recovery_benchmarks` suite.
 #[test]
 #[ignore]
 fn stress_large_wal_recovery() {
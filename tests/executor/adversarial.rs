@@ -1095,6 +1095,7 @@ fn error_recovery() {
         filter: None,
         metric: None,
         as_of: None,
+        namespace: None,
     });
     assert!(result.is_err());
 
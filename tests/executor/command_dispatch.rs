@@ -5,7 +5,10 @@
 
 use crate::common::*;
 use strata_core::Value;
-use strata_executor::{BranchId, Command, DistanceMetric, Output};
+use strata_executor::{
+    BatchVectorEntry, BranchId, Command, DistanceMetric, Error, FilterOp, MetadataFilter, Output,
+    StorageDtype, VectorIndexType,
+};
 
 // ============================================================================
 // Database Commands
@@ -56,6 +59,29 @@ fn compact_succeeds_on_ephemeral() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn stats_report_transaction_pool_activity() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::KvPut {
+            branch: None,
+            space: None,
+            key: "k".into(),
+            value: Value::Int(1),
+        })
+        .unwrap();
+
+    match executor.execute(Command::Stats).unwrap() {
+        Output::Stats(stats) => {
+            let pool = stats.transaction_pool;
+            assert!(pool.hits + pool.misses >= 1);
+            assert!(pool.returned + pool.discarded >= 1);
+        }
+        _ => panic!("Expected Stats output"),
+    }
+}
+
 // ============================================================================
 // KV Commands
 // ============================================================================
@@ -267,6 +293,7 @@ fn vector_create_collection_and_upsert() {
             collection: "embeddings".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -281,6 +308,7 @@ fn vector_create_collection_and_upsert() {
             key: "v1".into(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            namespace: None,
         })
         .unwrap();
 
@@ -298,6 +326,7 @@ fn vector_search_returns_matches() {
             collection: "search_test".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -309,6 +338,7 @@ fn vector_search_returns_matches() {
             key: "v1".into(),
             vector: vec![1.0, 0.0, 0.0, 0.0],
             metadata: None,
+            namespace: None,
         })
         .unwrap();
 
@@ -320,6 +350,7 @@ fn vector_search_returns_matches() {
             key: "v2".into(),
             vector: vec![0.0, 1.0, 0.0, 0.0],
             metadata: None,
+            namespace: None,
         })
         .unwrap();
 
@@ -333,6 +364,7 @@ fn vector_search_returns_matches() {
             filter: None,
             metric: None,
             as_of: None,
+            namespace: None,
         })
         .unwrap();
 
@@ -345,6 +377,135 @@ fn vector_search_returns_matches() {
     }
 }
 
+fn filter(field: &str, op: FilterOp, value: Value) -> MetadataFilter {
+    MetadataFilter {
+        field: field.into(),
+        op,
+        value,
+        filters: vec![],
+    }
+}
+
+#[test]
+fn vector_search_with_prefix_range_or_not_filters() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "filtered".into(),
+            dimension: 4,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: None,
+        })
+        .unwrap();
+
+    let docs = [
+        ("a", "docs/a", 2001, "en"),
+        ("b", "docs/b", 2015, "fr"),
+        ("c", "src/c", 2005, "en"),
+        ("d", "docs/d", 2008, "de"),
+    ];
+    for (key, path, year, lang) in docs {
+        let metadata = std::collections::HashMap::from([
+            ("path".to_string(), Value::String(path.into())),
+            ("year".to_string(), Value::Int(year)),
+            ("lang".to_string(), Value::String(lang.into())),
+        ]);
+        executor
+            .execute(Command::VectorUpsert {
+                branch: None,
+                space: None,
+                collection: "filtered".into(),
+                key: key.into(),
+                vector: vec![1.0, 0.0, 0.0, 0.0],
+                metadata: Some(Value::Object(metadata)),
+                namespace: None,
+            })
+            .unwrap();
+    }
+
+    let search = |filters: Vec<MetadataFilter>| -> Vec<String> {
+        let output = executor
+            .execute(Command::VectorSearch {
+                branch: None,
+                space: None,
+                collection: "filtered".into(),
+                query: vec![1.0, 0.0, 0.0, 0.0],
+                k: 10,
+                filter: Some(filters),
+                metric: None,
+                as_of: None,
+                namespace: None,
+            })
+            .unwrap();
+        match output {
+            Output::VectorMatches(matches) => {
+                let mut keys: Vec<String> = matches.into_iter().map(|m| m.key).collect();
+                keys.sort();
+                keys
+            }
+            _ => panic!("Expected VectorMatches output"),
+        }
+    };
+
+    assert_eq!(
+        search(vec![filter(
+            "path",
+            FilterOp::Prefix,
+            Value::String("docs/".into())
+        )]),
+        vec!["a", "b", "d"]
+    );
+    assert_eq!(
+        search(vec![filter(
+            "year",
+            FilterOp::Range,
+            Value::Array(vec![Value::Int(2000), Value::Int(2008)])
+        )]),
+        vec!["a", "c", "d"]
+    );
+    assert_eq!(
+        search(vec![filter(
+            "lang",
+            FilterOp::In,
+            Value::Array(vec![Value::String("fr".into()), Value::String("de".into())])
+        )]),
+        vec!["b", "d"]
+    );
+
+    // docs/* and (lang = en or year > 2010)
+    let either = MetadataFilter {
+        field: String::new(),
+        op: FilterOp::Or,
+        value: Value::Null,
+        filters: vec![
+            filter("lang", FilterOp::Eq, Value::String("en".into())),
+            filter("year", FilterOp::Gt, Value::Int(2010)),
+        ],
+    };
+    assert_eq!(
+        search(vec![
+            filter("path", FilterOp::Prefix, Value::String("docs/".into())),
+            either,
+        ]),
+        vec!["a", "b"]
+    );
+
+    // not (lang = en and year < 2003)
+    let not = MetadataFilter {
+        field: String::new(),
+        op: FilterOp::Not,
+        value: Value::Null,
+        filters: vec![
+            filter("lang", FilterOp::Eq, Value::String("en".into())),
+            filter("year", FilterOp::Lt, Value::Int(2003)),
+        ],
+    };
+    assert_eq!(search(vec![not]), vec!["b", "c", "d"]);
+}
+
 #[test]
 fn vector_list_collections() {
     let executor = create_executor();
@@ -356,6 +517,7 @@ fn vector_list_collections() {
             collection: "coll_a".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -366,6 +528,7 @@ fn vector_list_collections() {
             collection: "coll_b".into(),
             dimension: 8,
             metric: DistanceMetric::Euclidean,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -384,6 +547,197 @@ fn vector_list_collections() {
     }
 }
 
+#[test]
+fn vector_create_collection_with_storage_dtype() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "quantized".into(),
+            dimension: 4,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: Some(StorageDtype::Int8),
+        })
+        .unwrap();
+
+    for (key, vector) in [("a", [1.0, 0.0, 0.0, 0.0]), ("b", [0.0, 1.0, 0.0, 0.0])] {
+        executor
+            .execute(Command::VectorUpsert {
+                branch: None,
+                space: None,
+                collection: "quantized".into(),
+                key: key.into(),
+                vector: vector.to_vec(),
+                metadata: None,
+                namespace: None,
+            })
+            .unwrap();
+    }
+
+    let output = executor
+        .execute(Command::VectorCollectionStats {
+            branch: None,
+            space: None,
+            collection: "quantized".into(),
+        })
+        .unwrap();
+    match output {
+        Output::VectorCollectionList(infos) => {
+            assert_eq!(infos[0].storage_dtype, StorageDtype::Int8);
+            assert_eq!(infos[0].count, 2);
+        }
+        _ => panic!("Expected VectorCollectionList output"),
+    }
+
+    let output = executor
+        .execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "quantized".into(),
+            query: vec![0.9, 0.1, 0.0, 0.0],
+            k: 2,
+            filter: None,
+            metric: None,
+            as_of: None,
+            namespace: None,
+        })
+        .unwrap();
+    match output {
+        Output::VectorMatches(matches) => {
+            assert_eq!(matches[0].key, "a");
+            assert_eq!(matches[1].key, "b");
+        }
+        _ => panic!("Expected VectorMatches output"),
+    }
+}
+
+#[test]
+fn vector_search_namespace_rejects_as_of() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "docs".into(),
+            dimension: 2,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: None,
+        })
+        .unwrap();
+    executor
+        .execute(Command::VectorBatchUpsert {
+            branch: None,
+            space: None,
+            collection: "docs".into(),
+            entries: vec![BatchVectorEntry {
+                key: "a".into(),
+                vector: vec![1.0, 0.0],
+                metadata: None,
+            }],
+            namespace: Some("tenant".into()),
+        })
+        .unwrap();
+
+    let search = |namespace: &str, as_of| Command::VectorSearch {
+        branch: None,
+        space: None,
+        collection: "docs".into(),
+        query: vec![1.0, 0.0],
+        k: 5,
+        filter: None,
+        metric: None,
+        as_of,
+        namespace: Some(namespace.into()),
+    };
+    match executor.execute(search("tenant", None)).unwrap() {
+        Output::VectorMatches(matches) => assert_eq!(matches.len(), 1),
+        _ => panic!("Expected VectorMatches output"),
+    }
+    match executor.execute(search("other", None)).unwrap() {
+        Output::VectorMatches(matches) => assert!(matches.is_empty()),
+        _ => panic!("Expected VectorMatches output"),
+    }
+
+    let err = executor.execute(search("tenant", Some(1))).unwrap_err();
+    assert!(matches!(err, Error::InvalidInput { .. }), "{:?}", err);
+}
+
+#[test]
+fn vector_reindex_changes_metric_and_index() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "docs".into(),
+            dimension: 2,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: None,
+        })
+        .unwrap();
+    for (key, vector) in [("a", [1.0, 0.0]), ("b", [10.0, 1.0])] {
+        executor
+            .execute(Command::VectorUpsert {
+                branch: None,
+                space: None,
+                collection: "docs".into(),
+                key: key.into(),
+                vector: vector.to_vec(),
+                metadata: None,
+                namespace: None,
+            })
+            .unwrap();
+    }
+
+    let search = |metric| Command::VectorSearch {
+        branch: None,
+        space: None,
+        collection: "docs".into(),
+        query: vec![10.0, 0.0],
+        k: 1,
+        filter: None,
+        metric,
+        as_of: None,
+        namespace: None,
+    };
+
+    // A metric override the index wasn't built with is rejected, not ignored
+    let err = executor
+        .execute(search(Some(DistanceMetric::Euclidean)))
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidInput { .. }), "{:?}", err);
+
+    let output = executor
+        .execute(Command::VectorReindex {
+            branch: None,
+            space: None,
+            collection: "docs".into(),
+            metric: Some(DistanceMetric::Euclidean),
+            index_type: Some(VectorIndexType::Hnsw),
+        })
+        .unwrap();
+    match output {
+        Output::VectorCollectionList(infos) => {
+            assert_eq!(infos[0].metric, DistanceMetric::Euclidean);
+            assert_eq!(infos[0].index_type.as_deref(), Some("hnsw"));
+            assert_eq!(infos[0].count, 2);
+        }
+        _ => panic!("Expected VectorCollectionList output"),
+    }
+
+    match executor
+        .execute(search(Some(DistanceMetric::Euclidean)))
+        .unwrap()
+    {
+        Output::VectorMatches(matches) => assert_eq!(matches[0].key, "b"),
+        _ => panic!("Expected VectorMatches output"),
+    }
+}
+
 // ============================================================================
 // Branch Commands
 // ============================================================================
@@ -4,7 +4,7 @@
 
 use crate::common::*;
 use strata_core::Value;
-use strata_executor::{BranchId, Command, DistanceMetric, Error, Output};
+use strata_executor::{BranchId, Command, DistanceMetric, Error, FilterOp, MetadataFilter, Output};
 
 // ============================================================================
 // Vector Errors
@@ -21,6 +21,7 @@ fn vector_upsert_to_nonexistent_collection_behavior() {
         key: "v1".into(),
         vector: vec![1.0, 0.0, 0.0, 0.0],
         metadata: None,
+        namespace: None,
     });
 
     // Vector auto-create was removed (#923) - upsert to nonexistent collection
@@ -51,6 +52,7 @@ fn vector_search_in_nonexistent_collection_fails() {
         filter: None,
         metric: None,
         as_of: None,
+        namespace: None,
     });
 
     match result {
@@ -65,6 +67,55 @@ fn vector_search_in_nonexistent_collection_fails() {
     }
 }
 
+#[test]
+fn vector_search_with_malformed_filter_fails() {
+    let executor = create_executor();
+
+    executor
+        .execute(Command::VectorCreateCollection {
+            branch: None,
+            space: None,
+            collection: "filtered".into(),
+            dimension: 4,
+            metric: DistanceMetric::Cosine,
+            storage_dtype: None,
+        })
+        .unwrap();
+
+    let malformed = [
+        MetadataFilter {
+            field: "year".into(),
+            op: FilterOp::Range,
+            value: Value::Int(2000),
+            filters: vec![],
+        },
+        MetadataFilter {
+            field: String::new(),
+            op: FilterOp::Or,
+            value: Value::Null,
+            filters: vec![],
+        },
+    ];
+    for filter in malformed {
+        let result = executor.execute(Command::VectorSearch {
+            branch: None,
+            space: None,
+            collection: "filtered".into(),
+            query: vec![1.0, 0.0, 0.0, 0.0],
+            k: 10,
+            filter: Some(vec![filter]),
+            metric: None,
+            as_of: None,
+            namespace: None,
+        });
+        assert!(
+            matches!(result, Err(Error::InvalidInput { .. })),
+            "Expected InvalidInput, got {:?}",
+            result
+        );
+    }
+}
+
 #[test]
 fn vector_wrong_dimension_fails() {
     let executor = create_executor();
@@ -76,6 +127,7 @@ fn vector_wrong_dimension_fails() {
             collection: "dim4".into(),
             dimension: 4,
             metric: DistanceMetric::Cosine,
+            storage_dtype: None,
         })
         .unwrap();
 
@@ -87,6 +139,7 @@ fn vector_wrong_dimension_fails() {
         key: "v1".into(),
         vector: vec![1.0, 0.0], // Only 2 dimensions
         metadata: None,
+        namespace: None,
     });
 
     match result {
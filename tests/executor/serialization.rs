@@ -85,6 +85,7 @@ fn vector_search_roundtrip() {
         filter: None,
         metric: Some(DistanceMetric::Cosine),
         as_of: None,
+        namespace: None,
     };
 
     let json = serde_json::to_string(&cmd).unwrap();
@@ -455,6 +456,7 @@ fn distance_metric_roundtrip() {
             collection: "test".into(),
             dimension: 4,
             metric,
+            storage_dtype: None,
         };
 
         let json = serde_json::to_string(&cmd).unwrap();
@@ -471,6 +471,7 @@ fn vector_write_blocked_inside_transaction() {
         collection: "txn_coll".into(),
         dimension: 4,
         metric: DistanceMetric::Cosine,
+        storage_dtype: None,
     });
 
     assert!(
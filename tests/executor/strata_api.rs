@@ -6,7 +6,7 @@
 
 use crate::common::*;
 use strata_core::Value;
-use strata_executor::DistanceMetric;
+use strata_executor::{DistanceMetric, Error, OpenOptions, Strata, VectorIndexType};
 
 // ============================================================================
 // Database Operations
@@ -42,7 +42,104 @@ fn compact_succeeds_on_ephemeral() {
     let db = create_strata();
 
     // compact() on an ephemeral database is a no-op
-    assert!(db.compact().is_ok());
+    let result = db.compact().unwrap();
+    assert_eq!(result.segments_removed, 0);
+    assert_eq!(result.watermark, None);
+}
+
+#[test]
+fn latency_report_and_reset() {
+    let db = create_strata();
+
+    db.ping().unwrap();
+    db.ping().unwrap();
+    let report = db.latency_report().unwrap();
+    let ping = report
+        .commands
+        .iter()
+        .find(|c| c.command == "Ping")
+        .unwrap();
+    assert_eq!(ping.count, 2);
+    assert!(ping.p99_ns <= ping.max_ns);
+
+    db.reset_latency().unwrap();
+    assert!(db.latency_report().unwrap().commands.is_empty());
+}
+
+#[test]
+fn force_unlock_refuses_while_writer_is_running() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    let db = Strata::open(&path).unwrap();
+    db.kv_put("k", 1i64).unwrap();
+
+    // The lock belongs to this (running) process
+    match Strata::open_with(&path, OpenOptions::new().force_unlock(true)) {
+        Err(Error::AlreadyOpen {
+            path: locked,
+            holder,
+        }) => {
+            assert!(locked.ends_with("db"));
+            assert!(holder.contains(&format!("pid {}", std::process::id())));
+        }
+        other => panic!("expected AlreadyOpen, got {:?}", other.err()),
+    }
+    drop(db);
+
+    // Nothing to break once the writer closed
+    let db = Strata::open_with(&path, OpenOptions::new().force_unlock(true)).unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+}
+
+#[test]
+fn hot_backup_copy_opens_at_backup_version() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Strata::open(dir.path().join("db")).unwrap();
+    db.kv_put("k", 1i64).unwrap();
+
+    let backup = db.begin_backup().unwrap();
+    db.kv_put("k", 2i64).unwrap();
+    db.compact().unwrap();
+    backup.copy_to(&dir.path().join("copy")).unwrap();
+    db.end_backup(backup.id).unwrap();
+
+    let copy = Strata::open(dir.path().join("copy")).unwrap();
+    assert_eq!(copy.kv_get("k").unwrap(), Some(Value::Int(1)));
+    assert!(create_strata().begin_backup().is_err());
+}
+
+#[test]
+fn capture_environment_records_branch_and_bundle_environment() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db");
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(path.join("strata.toml"), "capture_environment = true\n").unwrap();
+    let db = Strata::open(&path).unwrap();
+    db.create_branch("exp").unwrap();
+
+    let info = db.branch_get("exp").unwrap().unwrap().info;
+    let env = info.environment.expect("branch environment");
+    assert_eq!(env.models["embed"], "minilm-l6-v2");
+
+    let bundle = dir.path().join("exp.branchbundle.tar.zst");
+    let bundle = bundle.to_str().unwrap();
+    db.branch_export("exp", bundle).unwrap();
+    let validated = db.branch_validate_bundle(bundle).unwrap();
+    assert_eq!(
+        validated.environment.unwrap().strata_version,
+        env.strata_version
+    );
+
+    // Off by default
+    let db = create_strata();
+    db.create_branch("plain").unwrap();
+    assert!(db
+        .branch_get("plain")
+        .unwrap()
+        .unwrap()
+        .info
+        .environment
+        .is_none());
 }
 
 // ============================================================================
@@ -167,6 +264,86 @@ fn vector_search() {
     assert_eq!(matches[0].key, "v1");
 }
 
+#[test]
+fn vector_search_in_namespace() {
+    let db = create_strata();
+
+    db.vector_create_collection("ns", 4u64, DistanceMetric::Cosine)
+        .unwrap();
+    db.vector_upsert_in_namespace("ns", "a", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
+        .unwrap();
+    db.vector_upsert_in_namespace("ns", "b", "v2", vec![0.9, 0.1, 0.0, 0.0], None)
+        .unwrap();
+    db.vector_upsert("ns", "v3", vec![1.0, 0.0, 0.0, 0.0], None)
+        .unwrap();
+
+    let matches = db
+        .vector_search_in_namespace("ns", "b", vec![1.0, 0.0, 0.0, 0.0], 10u64)
+        .unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].key, "v2");
+
+    // Unrestricted search still sees every vector
+    let matches = db
+        .vector_search("ns", vec![1.0, 0.0, 0.0, 0.0], 10u64)
+        .unwrap();
+    assert_eq!(matches.len(), 3);
+}
+
+#[test]
+fn vector_search_batch() {
+    let db = create_strata();
+
+    db.vector_create_collection("search", 4u64, DistanceMetric::Cosine)
+        .unwrap();
+    db.vector_upsert("search", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
+        .unwrap();
+    db.vector_upsert("search", "v2", vec![0.0, 1.0, 0.0, 0.0], None)
+        .unwrap();
+
+    let queries = vec![vec![1.0, 0.0, 0.0, 0.0], vec![0.0, 1.0, 0.0, 0.0]];
+    let batches = db
+        .vector_search_batch("search", queries, 1u64, None)
+        .unwrap();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0][0].key, "v1");
+    assert_eq!(batches[1][0].key, "v2");
+
+    // Each query's matches are what vector_search returns for it
+    let single = db
+        .vector_search("search", vec![0.0, 1.0, 0.0, 0.0], 1u64)
+        .unwrap();
+    assert_eq!(batches[1], single);
+}
+
+#[test]
+fn vector_reindex() {
+    let db = create_strata();
+
+    db.vector_create_collection("reindex", 4u64, DistanceMetric::Cosine)
+        .unwrap();
+    db.vector_upsert("reindex", "v1", vec![1.0, 0.0, 0.0, 0.0], None)
+        .unwrap();
+
+    let info = db
+        .vector_reindex("reindex", Some(DistanceMetric::DotProduct), None)
+        .unwrap();
+    assert_eq!(info.metric, DistanceMetric::DotProduct);
+    assert_eq!(info.index_type.as_deref(), Some("brute_force"));
+
+    let info = db
+        .vector_reindex("reindex", None, Some(VectorIndexType::Hnsw))
+        .unwrap();
+    assert_eq!(info.metric, DistanceMetric::DotProduct);
+    assert_eq!(info.index_type.as_deref(), Some("hnsw"));
+    assert_eq!(info.count, 1);
+
+    let matches = db
+        .vector_search("reindex", vec![1.0, 0.0, 0.0, 0.0], 1u64)
+        .unwrap();
+    assert_eq!(matches[0].key, "v1");
+}
+
 #[test]
 fn vector_list_collections() {
     let db = create_strata();
@@ -233,6 +410,38 @@ fn branch_list() {
     );
 }
 
+#[test]
+fn branch_import_as_loads_bundle_next_to_existing_data() {
+    let mut db = create_strata();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("run.branchbundle.tar.zst");
+    let path = path.to_str().unwrap();
+
+    db.create_branch("run").unwrap();
+    db.set_branch("run").unwrap();
+    db.kv_put("status", "failed").unwrap();
+    db.branch_export("run", path).unwrap();
+    db.kv_put("status", "retried").unwrap();
+
+    let result = db.branch_import_as(path, "run-inspect").unwrap();
+    assert_eq!(result.branch_id, "run-inspect");
+    assert_eq!(result.renamed_from.as_deref(), Some("run"));
+
+    assert_eq!(
+        db.kv_get("status").unwrap(),
+        Some(Value::String("retried".into()))
+    );
+    db.set_branch("run-inspect").unwrap();
+    assert_eq!(
+        db.kv_get("status").unwrap(),
+        Some(Value::String("failed".into()))
+    );
+
+    // Taken and invalid names are rejected
+    assert!(db.branch_import_as(path, "run").is_err());
+    assert!(db.branch_import_as(path, "").is_err());
+}
+
 // ============================================================================
 // JSON Operations
 // ============================================================================
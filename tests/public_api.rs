@@ -0,0 +1,136 @@
+//! Public API stability tests
+//!
+//! Pins the `stratadb` facade: every name users build against and the
+//! signatures of the main `Strata` methods. Removing or renaming an item,
+//! or changing a pinned signature, fails to compile here, so breaking
+//! changes show up in review and come with a version bump.
+//!
+//! Only `stratadb` is imported: the internal crates are not part of the
+//! stable surface.
+
+#![allow(unused_imports, clippy::type_complexity)]
+
+use std::path::PathBuf;
+
+#[cfg(feature = "search")]
+use stratadb::Search;
+use stratadb::{
+    json_to_value, register_custom_primitive, value_to_json, AccessMode, BackupFile,
+    BackupManifest, BatchVectorEntry, BranchAncestor, BranchDiffEntry, BranchDiffResult,
+    BranchExportResult, BranchFilter, BranchId, BranchImportResult, BranchInfo, BranchRetention,
+    BranchStats, BranchStatus, Branches, BundleValidateResult, CancellationToken, CheckpointPolicy,
+    CollectionInfo, Command, CommandLatency, CompactResult, ConflictEntry, CursorStats, Custom,
+    CustomPrimitive, CustomStore, DatabaseExportResult, DatabaseImportResult, DatabaseInfo,
+    DatabaseStats, DiffSummary, DistanceMetric, Documents, EmbedProvider, EntryChange,
+    EnvironmentInfo, Error, Event, Events, EvictionPolicy, Executor, FilterOp, ForkInfo, FromValue,
+    FromValueError, FusionMethod, ImportConflict, IndexVerification, KvHistory, LatencyReport,
+    ListEnd, LockMode, LossWindow, Maintenance, ManagerStats, MemoryBudget, MemoryBudgetStats,
+    MergeInfo, MergeStrategy, MetadataFilter, OpenOptions, Output, ParamRef, Params, PendingEvent,
+    PoolJob, PreparedCommand, PrimitiveCounts, PrimitiveType, RecoveryProgress, RerankProvider,
+    ResourceLimits, ResourceUsage, Result, RetentionPolicy, SchemaChange, SchemaChangeKind,
+    SchemaViolation, SearchResultHit, Session, SpaceDiff, States, StorageDtype, Strata,
+    StrataManager, TextSearchHit, ToValue, TransactionInfo, TransactionPool, TransactionPoolConfig,
+    TransactionPoolStats, TransactionStats, TxnOptions, TxnStatus, TypeTag, Value, VectorData,
+    VectorIndexType, VectorMatch, VersionedBranchInfo, VersionedValue, VersionedVectorData,
+    WalCounters, WorkerPool, WorkerPoolBuilder, CUSTOM_PRIMITIVE_IDS, DEFAULT_CURSOR_PAGE_SIZE,
+    DEFAULT_CURSOR_TTL_SECS, DEFAULT_MAX_OPEN_DATABASES,
+};
+
+#[test]
+fn strata_signatures() {
+    let _: fn(PathBuf) -> Result<Strata> = Strata::open::<PathBuf>;
+    let _: fn(PathBuf, OpenOptions) -> Result<Strata> = Strata::open_with::<PathBuf>;
+    let _: fn() -> Result<Strata> = Strata::cache;
+    let _: fn(OpenOptions) -> Result<Strata> = Strata::cache_with;
+    let _: fn(&Strata) -> Result<Strata> = Strata::new_handle;
+    let _: fn(&Strata) -> Session = Strata::session;
+    let _: fn(&Strata) -> AccessMode = Strata::access_mode;
+
+    let _: fn(&Strata, &str) -> Result<Option<Value>> = Strata::kv_get;
+    let _: fn(&Strata, &str) -> Result<bool> = Strata::kv_delete;
+    let _: fn(&Strata, Option<&str>) -> Result<Vec<String>> = Strata::kv_list;
+
+    let _: fn(&Strata, &str) -> Result<Option<Value>> = Strata::state_get;
+
+    let _: fn(&Strata, &str, Value) -> Result<u64> = Strata::event_append;
+    let _: fn(&Strata, u64) -> Result<Option<VersionedValue>> = Strata::event_get;
+
+    let _: fn(&Strata, &str, &str) -> Result<Option<Value>> = Strata::json_get;
+
+    #[cfg(feature = "vectors")]
+    {
+        let _: fn(&Strata, &str, &str, Vec<f32>, Option<Value>) -> Result<u64> =
+            Strata::vector_upsert;
+        let _: fn(&Strata, &str, Vec<f32>, u64) -> Result<Vec<VectorMatch>> = Strata::vector_search;
+    }
+
+    let _: fn(&Strata) -> &str = Strata::current_branch;
+    let _: fn(&mut Strata, &str) -> Result<()> = Strata::set_branch;
+    let _: fn(&Strata, &str) -> Result<()> = Strata::create_branch;
+    let _: fn(&Strata, &str) -> Result<ForkInfo> = Strata::fork_branch;
+    let _: fn(&Strata) -> Result<Vec<String>> = Strata::list_branches;
+    let _: fn(&Strata) -> &str = Strata::current_space;
+    let _: fn(&mut Strata, &str) -> Result<()> = Strata::set_space;
+
+    let _: fn(&Strata) -> Result<String> = Strata::ping;
+    let _: fn(&Strata) -> Result<DatabaseInfo> = Strata::info;
+    let _: fn(&Strata) -> LossWindow = Strata::max_loss_window;
+    let _: fn(&Strata) -> Result<BackupManifest> = Strata::begin_backup;
+    let _: fn(&Strata) -> Result<DatabaseStats> = Strata::stats;
+    let _: fn(&Strata, &str) -> Result<BranchStats> = Strata::branch_stats;
+}
+
+/// Writers taking `impl Into<Value>` cannot be coerced to fn pointers, so
+/// their argument and return types are pinned through calls instead.
+#[test]
+fn generic_writer_signatures() {
+    let db = Strata::cache().unwrap();
+    let _: Result<u64> = db.kv_put("k", Value::Int(1));
+    let _: Result<u64> = db.state_set("cell", "v");
+    let _: Result<Option<u64>> = db.state_cas("cell", None::<u64>, 1i64);
+    let _: Result<u64> = db.json_set("doc", "$", Value::Null);
+}
+
+#[test]
+fn command_interface_signatures() {
+    let _: fn(&Executor, Command) -> Result<Output> = Executor::execute;
+    let _: fn(&mut Session, Command) -> Result<Output> = Session::execute;
+    let _: fn(&Session) -> bool = Session::in_transaction;
+}
+
+#[test]
+fn facade_round_trip() {
+    let mut db = Strata::cache().unwrap();
+    db.kv_put("k", 1i64).unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), Some(Value::Int(1)));
+
+    db.create_branch("b").unwrap();
+    db.set_branch("b").unwrap();
+    assert_eq!(db.kv_get("k").unwrap(), None);
+
+    let output = db
+        .executor()
+        .execute(Command::Ping)
+        .expect("ping through the command interface");
+    assert!(matches!(output, Output::Pong { .. }));
+}
+
+/// The derives expand to `::stratadb` paths by default.
+#[test]
+fn derived_conversions_round_trip() {
+    #[derive(Debug, PartialEq, ToValue, FromValue)]
+    struct Chat {
+        user: String,
+        turns: Vec<u32>,
+    }
+
+    let db = Strata::cache().unwrap();
+    let chat = Chat {
+        user: "ada".into(),
+        turns: vec![1, 2],
+    };
+    db.kv_put("chat", chat.to_value()).unwrap();
+    let stored = db.kv_get("chat").unwrap().unwrap();
+    let decoded: std::result::Result<Chat, FromValueError> = Chat::from_value(stored);
+    assert_eq!(decoded.unwrap(), chat);
+}